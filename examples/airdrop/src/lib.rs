@@ -1,4 +1,11 @@
 //! Airdrop Distributor — upload addresses + amounts, recipients claim allocations.
+//!
+//! Large airdrops can skip the upload and commit to a Merkle root instead.
+//! Each leaf is `hash_list_leaf(address || amount.to_le_bytes())` (see
+//! [`merkle_leaf`]); recipients claim by submitting their amount and proof.
+//!
+//! An optional claim deadline closes claiming; afterwards the creator can
+//! reclaim everything unclaimed, including allocated-but-unclaimed tokens.
 
 #![no_std]
 
@@ -25,6 +32,14 @@ pub struct AirdropConfig {
     pub recipient_count: u64,
     pub finalized: bool,
     pub created_at: u64,
+    /// Set in Merkle mode; replaces the uploaded recipient list.
+    pub merkle_root: Option<[u8; 32]>,
+    /// Sum of uploaded allocations (list mode only).
+    pub allocated_amount: u128,
+    pub reclaimed_amount: u128,
+    /// After this timestamp claims are rejected and unclaimed allocations
+    /// become reclaimable.
+    pub claim_deadline: Option<u64>,
 }
 
 #[derive(Debug, BorshSerialize, BorshDeserialize, Clone)]
@@ -68,6 +83,10 @@ impl Airdrop {
             recipient_count: 0,
             finalized: false,
             created_at: ctx.timestamp(),
+            merkle_root: None,
+            allocated_amount: 0,
+            reclaimed_amount: 0,
+            claim_deadline: None,
         })?;
         INITIALIZED.save(&true)?;
 
@@ -86,6 +105,7 @@ impl Airdrop {
             ctx.sender() == config.creator,
             "only creator can add recipients"
         );
+        ensure!(config.merkle_root.is_none(), "airdrop uses a merkle root");
         ensure!(!recipients.is_empty(), "recipients list is empty");
         ensure!(recipients.len() <= 100, "max 100 recipients per batch");
 
@@ -98,7 +118,12 @@ impl Airdrop {
                 config.recipient_count = safe_add_u64(config.recipient_count, 1)?;
             }
             ALLOCATIONS.save(&alloc.address, &safe_add(existing, alloc.amount)?)?;
+            config.allocated_amount = safe_add(config.allocated_amount, alloc.amount)?;
         }
+        ensure!(
+            config.allocated_amount <= config.total_amount,
+            "allocations exceed total_amount"
+        );
 
         CONFIG.save(&config)?;
 
@@ -122,6 +147,7 @@ impl Airdrop {
     pub fn claim(&mut self, ctx: &Context) -> ContractResult {
         let mut config = CONFIG.load()?;
         ensure!(config.finalized, "airdrop not finalized yet");
+        ensure!(claim_open(&config, ctx), "claim deadline has passed");
 
         let already_claimed = CLAIMED.load(&ctx.sender()).unwrap_or(false);
         ensure!(!already_claimed, "already claimed");
@@ -138,21 +164,116 @@ impl Airdrop {
             .add_attribute("amount", format!("{}", allocation)))
     }
 
+    /// Reclaim tokens nobody can claim anymore: unallocated tokens while the
+    /// claim window is open, and everything unclaimed once the deadline passes.
     #[execute]
     pub fn reclaim_remaining(&mut self, ctx: &Context) -> ContractResult {
-        let config = CONFIG.load()?;
+        let mut config = CONFIG.load()?;
         ensure!(config.finalized, "airdrop not finalized yet");
         ensure!(ctx.sender() == config.creator, "only creator can reclaim");
 
-        let remaining = safe_sub(config.total_amount, config.claimed_amount)?;
+        let reserved = if claim_open(&config, ctx) {
+            // Merkle allocations are unknown on-chain, so the whole total stays reserved
+            match config.merkle_root {
+                Some(_) => config.total_amount,
+                None => config.allocated_amount,
+            }
+        } else {
+            config.claimed_amount
+        };
+        let remaining = safe_sub(
+            safe_sub(config.total_amount, reserved)?,
+            config.reclaimed_amount,
+        )?;
         ensure!(remaining > 0, "nothing to reclaim");
 
+        config.reclaimed_amount = safe_add(config.reclaimed_amount, remaining)?;
+        CONFIG.save(&config)?;
         ctx.transfer_from_contract(&config.creator, &config.token_id, remaining);
 
         Ok(Response::with_action("reclaim_remaining")
             .add_attribute("amount", format!("{}", remaining)))
     }
 
+    /// Switch to Merkle mode by committing to the root of all allocations.
+    /// Only allowed before finalization and before any recipients are added.
+    #[execute]
+    pub fn set_merkle_root(&mut self, ctx: &Context, root: [u8; 32]) -> ContractResult {
+        let mut config = CONFIG.load()?;
+        ensure!(!config.finalized, "airdrop is finalized");
+        ensure!(
+            ctx.sender() == config.creator,
+            "only creator can set merkle root"
+        );
+        ensure!(
+            config.recipient_count == 0,
+            "airdrop already has recipients"
+        );
+
+        config.merkle_root = Some(root);
+        CONFIG.save(&config)?;
+
+        Ok(Response::with_action("set_merkle_root"))
+    }
+
+    /// Claim an allocation in Merkle mode by proving `(sender, amount)` is a
+    /// leaf of the committed root.
+    #[execute]
+    pub fn claim_merkle(
+        &mut self,
+        ctx: &Context,
+        amount: u128,
+        proof: Vec<[u8; 32]>,
+    ) -> ContractResult {
+        let mut config = CONFIG.load()?;
+        ensure!(config.finalized, "airdrop not finalized yet");
+        ensure!(claim_open(&config, ctx), "claim deadline has passed");
+        let root = config.merkle_root.ok_or("airdrop does not use a merkle root")?;
+
+        let already_claimed = CLAIMED.load(&ctx.sender()).unwrap_or(false);
+        ensure!(!already_claimed, "already claimed");
+        ensure!(amount > 0, "allocation must be positive");
+        ensure!(
+            verify_merkle_proof(&root, &merkle_leaf(&ctx.sender(), amount), &proof),
+            "invalid merkle proof"
+        );
+
+        let claimed_amount = safe_add(config.claimed_amount, amount)?;
+        ensure!(
+            claimed_amount <= config.total_amount,
+            "claim exceeds airdrop total"
+        );
+
+        ctx.transfer_from_contract(&ctx.sender(), &config.token_id, amount);
+        CLAIMED.save(&ctx.sender(), &true)?;
+        config.claimed_amount = claimed_amount;
+        CONFIG.save(&config)?;
+
+        Ok(Response::with_action("claim")
+            .add_attribute("amount", format!("{}", amount)))
+    }
+
+    /// Set or change the claim deadline. Only allowed before finalization.
+    #[execute]
+    pub fn set_claim_deadline(&mut self, ctx: &Context, deadline: u64) -> ContractResult {
+        let mut config = CONFIG.load()?;
+        ensure!(!config.finalized, "airdrop is finalized");
+        ensure!(
+            ctx.sender() == config.creator,
+            "only creator can set claim deadline"
+        );
+        ensure!(
+            deadline > ctx.timestamp(),
+            "claim deadline must be in the future"
+        );
+
+        config.claim_deadline = Some(deadline);
+        CONFIG.save(&config)?;
+
+        Ok(Response::with_action("set_claim_deadline")
+            .add_attribute("deadline", format!("{}", deadline)))
+    }
+
     #[query]
     pub fn get_config(&self, _ctx: &Context) -> ContractResult {
         let config = CONFIG.load()?;
@@ -172,6 +293,22 @@ impl Airdrop {
     }
 }
 
+// ── Helpers ────────────────────────────────────────────────────────────
+
+fn claim_open(config: &AirdropConfig, ctx: &Context) -> bool {
+    config
+        .claim_deadline
+        .is_none_or(|deadline| ctx.timestamp() < deadline)
+}
+
+/// Merkle leaf for an allocation: `hash_list_leaf(address || amount_le)`.
+pub fn merkle_leaf(addr: &Address, amount: u128) -> [u8; 32] {
+    let mut data = [0u8; 36];
+    data[..20].copy_from_slice(addr);
+    data[20..].copy_from_slice(&amount.to_le_bytes());
+    hash_list_leaf(&data)
+}
+
 // ── Tests ──────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -312,4 +449,182 @@ mod tests {
         env.set_sender(ALICE);
         ad.reclaim_remaining(&env.ctx()).unwrap();
     }
+
+    /// Root and proofs for BOB: 5_000, CHARLIE: 3_000, DAVE: 2_000.
+    fn merkle_tree() -> ([u8; 32], Vec<Vec<[u8; 32]>>) {
+        use norn_sdk::merkle::hash_sorted_pair;
+        let bob = merkle_leaf(&BOB, 5_000);
+        let charlie = merkle_leaf(&CHARLIE, 3_000);
+        let dave = merkle_leaf(&DAVE, 2_000);
+        let left = hash_sorted_pair(&bob, &charlie);
+        let root = hash_sorted_pair(&left, &dave);
+        let proofs = alloc::vec![
+            alloc::vec![charlie, dave],
+            alloc::vec![bob, dave],
+            alloc::vec![left],
+        ];
+        (root, proofs)
+    }
+
+    #[test]
+    fn test_merkle_claim() {
+        let (env, mut ad) = setup();
+        let (root, proofs) = merkle_tree();
+        ad.set_merkle_root(&env.ctx(), root).unwrap();
+        ad.finalize(&env.ctx()).unwrap();
+
+        env.set_sender(BOB);
+        let resp = ad.claim_merkle(&env.ctx(), 5_000, proofs[0].clone()).unwrap();
+        assert_attribute(&resp, "amount", "5000");
+        let err = ad
+            .claim_merkle(&env.ctx(), 5_000, proofs[0].clone())
+            .unwrap_err();
+        assert_err_contains(&err, "already claimed");
+
+        env.set_sender(DAVE);
+        ad.claim_merkle(&env.ctx(), 2_000, proofs[2].clone()).unwrap();
+
+        let resp = ad.get_config(&env.ctx()).unwrap();
+        let config: AirdropConfig = from_response(&resp).unwrap();
+        assert_eq!(config.claimed_amount, 7_000);
+    }
+
+    #[test]
+    fn test_merkle_claim_rejects_bad_proof() {
+        let (env, mut ad) = setup();
+        let (root, proofs) = merkle_tree();
+        ad.set_merkle_root(&env.ctx(), root).unwrap();
+        ad.finalize(&env.ctx()).unwrap();
+
+        // Wrong amount
+        env.set_sender(CHARLIE);
+        let err = ad
+            .claim_merkle(&env.ctx(), 30_000, proofs[1].clone())
+            .unwrap_err();
+        assert_err_contains(&err, "invalid merkle proof");
+
+        // Someone else's proof
+        env.set_sender(ALICE);
+        let err = ad
+            .claim_merkle(&env.ctx(), 3_000, proofs[1].clone())
+            .unwrap_err();
+        assert_err_contains(&err, "invalid merkle proof");
+    }
+
+    #[test]
+    fn test_merkle_mode_excludes_recipient_list() {
+        let (env, mut ad) = setup();
+        let (root, _) = merkle_tree();
+        ad.set_merkle_root(&env.ctx(), root).unwrap();
+        let err = ad
+            .add_recipients(
+                &env.ctx(),
+                alloc::vec![Allocation { address: BOB, amount: 1 }],
+            )
+            .unwrap_err();
+        assert_err_contains(&err, "airdrop uses a merkle root");
+
+        let (env, mut ad) = setup();
+        ad.add_recipients(
+            &env.ctx(),
+            alloc::vec![Allocation { address: BOB, amount: 1 }],
+        )
+        .unwrap();
+        let err = ad.set_merkle_root(&env.ctx(), root).unwrap_err();
+        assert_err_contains(&err, "airdrop already has recipients");
+        ad.finalize(&env.ctx()).unwrap();
+        env.set_sender(BOB);
+        let err = ad.claim_merkle(&env.ctx(), 1, Vec::new()).unwrap_err();
+        assert_err_contains(&err, "airdrop does not use a merkle root");
+    }
+
+    #[test]
+    fn test_reclaim_keeps_unclaimed_allocations_before_deadline() {
+        let (env, mut ad) = setup();
+        ad.add_recipients(
+            &env.ctx(),
+            alloc::vec![
+                Allocation { address: BOB, amount: 5_000 },
+                Allocation { address: CHARLIE, amount: 3_000 },
+            ],
+        )
+        .unwrap();
+        ad.finalize(&env.ctx()).unwrap();
+
+        let resp = ad.reclaim_remaining(&env.ctx()).unwrap();
+        assert_attribute(&resp, "amount", "92000");
+        let err = ad.reclaim_remaining(&env.ctx()).unwrap_err();
+        assert_err_contains(&err, "nothing to reclaim");
+
+        // Allocations are still claimable
+        env.set_sender(BOB);
+        ad.claim(&env.ctx()).unwrap();
+    }
+
+    #[test]
+    fn test_claim_deadline_and_clawback() {
+        let (env, mut ad) = setup();
+        ad.add_recipients(
+            &env.ctx(),
+            alloc::vec![
+                Allocation { address: BOB, amount: 5_000 },
+                Allocation { address: CHARLIE, amount: 3_000 },
+            ],
+        )
+        .unwrap();
+        ad.set_claim_deadline(&env.ctx(), 2_000).unwrap();
+        ad.finalize(&env.ctx()).unwrap();
+        ad.reclaim_remaining(&env.ctx()).unwrap();
+
+        env.set_sender(BOB);
+        ad.claim(&env.ctx()).unwrap();
+
+        env.set_timestamp(2_000);
+        env.set_sender(CHARLIE);
+        let err = ad.claim(&env.ctx()).unwrap_err();
+        assert_err_contains(&err, "claim deadline has passed");
+
+        // CHARLIE's unclaimed 3_000 is now reclaimable
+        env.set_sender(ALICE);
+        let resp = ad.reclaim_remaining(&env.ctx()).unwrap();
+        assert_attribute(&resp, "amount", "3000");
+    }
+
+    #[test]
+    fn test_merkle_reclaim_waits_for_deadline() {
+        let (env, mut ad) = setup();
+        let (root, proofs) = merkle_tree();
+        ad.set_merkle_root(&env.ctx(), root).unwrap();
+        ad.set_claim_deadline(&env.ctx(), 2_000).unwrap();
+        ad.finalize(&env.ctx()).unwrap();
+
+        let err = ad.reclaim_remaining(&env.ctx()).unwrap_err();
+        assert_err_contains(&err, "nothing to reclaim");
+
+        env.set_sender(BOB);
+        ad.claim_merkle(&env.ctx(), 5_000, proofs[0].clone()).unwrap();
+
+        env.set_timestamp(2_000);
+        env.set_sender(DAVE);
+        let err = ad
+            .claim_merkle(&env.ctx(), 2_000, proofs[2].clone())
+            .unwrap_err();
+        assert_err_contains(&err, "claim deadline has passed");
+
+        env.set_sender(ALICE);
+        let resp = ad.reclaim_remaining(&env.ctx()).unwrap();
+        assert_attribute(&resp, "amount", "95000");
+    }
+
+    #[test]
+    fn test_allocations_cannot_exceed_total() {
+        let (env, mut ad) = setup();
+        let err = ad
+            .add_recipients(
+                &env.ctx(),
+                alloc::vec![Allocation { address: BOB, amount: 100_001 }],
+            )
+            .unwrap_err();
+        assert_err_contains(&err, "allocations exceed total_amount");
+    }
 }
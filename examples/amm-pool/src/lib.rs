@@ -3,6 +3,9 @@
 //! Every pool pairs a token with NORN. To swap Token A for Token B the path
 //! is A -> NORN -> B (two hops). Liquidity providers earn swap fees (default
 //! 0.3%) proportional to their share of the pool.
+//!
+//! Each pool keeps a cumulative price accumulator (Uniswap v2 style) so other
+//! contracts can derive a time-weighted average price from two observations.
 
 #![no_std]
 
@@ -21,6 +24,9 @@ const LP_TOTAL: Map<u64, u128> = Map::new("lp_tot");
 const FEE_BPS: Item<u16> = Item::new("fee_bps");
 const OWNER: Item<Address> = Item::new("owner");
 
+/// Fixed-point scale for prices (NORN per token).
+pub const PRICE_SCALE: u128 = 1_000_000_000_000;
+
 // ── Types ────────────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
@@ -30,6 +36,17 @@ pub struct Pool {
     pub reserve_norn: u128,
     pub reserve_token: u128,
     pub created_at: u64,
+    /// Sum of `spot_price * seconds`, wrapping on overflow.
+    pub price_cumulative: u128,
+    pub last_update: u64,
+}
+
+/// A snapshot of a pool's price accumulator. TWAP between two observations
+/// is `(b.price_cumulative - a.price_cumulative) / (b.timestamp - a.timestamp)`.
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct PriceObservation {
+    pub price_cumulative: u128,
+    pub timestamp: u64,
 }
 
 // ── Math helpers ─────────────────────────────────────────────────────────
@@ -65,6 +82,26 @@ fn compute_output(
         .ok_or(ContractError::Overflow)
 }
 
+/// Spot price of the token in NORN, scaled by `PRICE_SCALE`.
+fn spot_price(pool: &Pool) -> Result<u128, ContractError> {
+    safe_mul(pool.reserve_norn, PRICE_SCALE)?
+        .checked_div(pool.reserve_token)
+        .ok_or(ContractError::Overflow)
+}
+
+/// Fold the time since the last update into the price accumulator.
+/// Must run before the reserves change.
+fn accrue_price(pool: &mut Pool, now: u64) -> Result<(), ContractError> {
+    if now > pool.last_update && pool.reserve_token > 0 {
+        let elapsed = (now - pool.last_update) as u128;
+        pool.price_cumulative = pool
+            .price_cumulative
+            .wrapping_add(spot_price(pool)?.wrapping_mul(elapsed));
+    }
+    pool.last_update = now;
+    Ok(())
+}
+
 // ── Contract ─────────────────────────────────────────────────────────────
 
 #[norn_contract]
@@ -102,8 +139,8 @@ impl AmmPool {
         let norn_token = [0u8; 32]; // NORN is the zero token
 
         // Transfer tokens to pool contract
-        ctx.transfer(&ctx.sender(), &contract, &norn_token, norn_amount);
-        ctx.transfer(&ctx.sender(), &contract, &token, token_amount);
+        ctx.transfer_checked(&ctx.sender(), &contract, &norn_token, norn_amount)?;
+        ctx.transfer_checked(&ctx.sender(), &contract, &token, token_amount)?;
 
         let id = POOL_COUNT.load_or(0u64);
         POOLS.save(
@@ -114,6 +151,8 @@ impl AmmPool {
                 reserve_norn: norn_amount,
                 reserve_token: token_amount,
                 created_at: ctx.timestamp(),
+                price_cumulative: 0,
+                last_update: ctx.timestamp(),
             },
         )?;
         TOKEN_TO_POOL.save(&token, &id)?;
@@ -146,12 +185,13 @@ impl AmmPool {
         let mut pool = POOLS.load(&pool_id)?;
         let total_lp = LP_TOTAL.load_or(&pool_id, 0u128);
         ensure!(total_lp > 0, "pool has no liquidity");
+        accrue_price(&mut pool, ctx.timestamp())?;
 
         let contract = ctx.contract_address();
         let norn_token = [0u8; 32];
 
-        ctx.transfer(&ctx.sender(), &contract, &norn_token, norn_amount);
-        ctx.transfer(&ctx.sender(), &contract, &pool.token, token_amount);
+        ctx.transfer_checked(&ctx.sender(), &contract, &norn_token, norn_amount)?;
+        ctx.transfer_checked(&ctx.sender(), &contract, &pool.token, token_amount)?;
 
         // LP = min(norn * total_lp / reserve_norn, token * total_lp / reserve_token)
         let lp_norn = safe_mul(norn_amount, total_lp)?
@@ -194,6 +234,7 @@ impl AmmPool {
         let total_lp = LP_TOTAL.load_or(&pool_id, 0u128);
         let user_lp = LP_BALANCES.load_or(&(pool_id, ctx.sender()), 0u128);
         ensure!(user_lp >= lp_amount, "insufficient LP balance");
+        accrue_price(&mut pool, ctx.timestamp())?;
 
         // Calculate share of reserves
         let norn_out = safe_mul(lp_amount, pool.reserve_norn)?
@@ -235,6 +276,7 @@ impl AmmPool {
 
         let mut pool = POOLS.load(&pool_id)?;
         let fee_bps = FEE_BPS.load_or(30u16);
+        accrue_price(&mut pool, ctx.timestamp())?;
 
         let token_out =
             compute_output(pool.reserve_norn, pool.reserve_token, norn_amount, fee_bps)?;
@@ -243,7 +285,7 @@ impl AmmPool {
 
         let contract = ctx.contract_address();
         let norn_token = [0u8; 32];
-        ctx.transfer(&ctx.sender(), &contract, &norn_token, norn_amount);
+        ctx.transfer_checked(&ctx.sender(), &contract, &norn_token, norn_amount)?;
         ctx.transfer_from_contract(&ctx.sender(), &pool.token, token_out);
 
         pool.reserve_norn = safe_add(pool.reserve_norn, norn_amount)?;
@@ -270,6 +312,7 @@ impl AmmPool {
 
         let mut pool = POOLS.load(&pool_id)?;
         let fee_bps = FEE_BPS.load_or(30u16);
+        accrue_price(&mut pool, ctx.timestamp())?;
 
         let norn_out =
             compute_output(pool.reserve_token, pool.reserve_norn, token_amount, fee_bps)?;
@@ -278,7 +321,7 @@ impl AmmPool {
 
         let contract = ctx.contract_address();
         let norn_token = [0u8; 32];
-        ctx.transfer(&ctx.sender(), &contract, &pool.token, token_amount);
+        ctx.transfer_checked(&ctx.sender(), &contract, &pool.token, token_amount)?;
         ctx.transfer_from_contract(&ctx.sender(), &norn_token, norn_out);
 
         pool.reserve_token = safe_add(pool.reserve_token, token_amount)?;
@@ -303,6 +346,24 @@ impl AmmPool {
         Ok(Response::with_action("set_fee_bps").add_attribute("fee_bps", format!("{}", fee_bps)))
     }
 
+    /// Bring a pool's price accumulator up to date and return it as a
+    /// `PriceObservation`. Exposed as an execute so other contracts can
+    /// reach it through a cross-contract call.
+    #[execute]
+    pub fn observe(&mut self, ctx: &Context, pool_id: u64) -> ContractResult {
+        let mut pool = POOLS.load(&pool_id)?;
+        accrue_price(&mut pool, ctx.timestamp())?;
+        POOLS.save(&pool_id, &pool)?;
+
+        let obs = PriceObservation {
+            price_cumulative: pool.price_cumulative,
+            timestamp: pool.last_update,
+        };
+        Ok(Response::with_action("observe")
+            .add_attribute("pool_id", format!("{}", pool_id))
+            .set_data(&obs))
+    }
+
     // ── Query ────────────────────────────────────────────────────────
 
     #[query]
@@ -355,6 +416,13 @@ impl AmmPool {
         let owner = OWNER.load()?;
         ok((fee_bps, owner))
     }
+
+    /// Spot price of the token in NORN, scaled by `PRICE_SCALE`.
+    #[query]
+    pub fn get_spot_price(&self, _ctx: &Context, pool_id: u64) -> ContractResult {
+        let pool = POOLS.load(&pool_id)?;
+        ok(spot_price(&pool)?)
+    }
 }
 
 // ── Tests ────────────────────────────────────────────────────────────────
@@ -635,6 +703,29 @@ mod tests {
         assert_err_contains(&err, "insufficient LP balance");
     }
 
+    #[test]
+    fn test_price_accumulator_twap() {
+        let (env, mut amm) = setup();
+        amm.create_pool(&env.ctx(), TOKEN_A, 10_000, 20_000)
+            .unwrap();
+        let start: PriceObservation = from_response(&amm.observe(&env.ctx(), 0).unwrap()).unwrap();
+        assert_eq!(start.price_cumulative, 0);
+
+        // 100s at 0.5 NORN/token, then the price moves up after a swap
+        env.set_timestamp(1100);
+        amm.swap_norn_for_token(&env.ctx(), 0, 10_000, 0).unwrap();
+        let pool: Pool = from_response(&amm.get_pool(&env.ctx(), 0).unwrap()).unwrap();
+        let new_spot: u128 = from_response(&amm.get_spot_price(&env.ctx(), 0).unwrap()).unwrap();
+        assert!(new_spot > PRICE_SCALE / 2);
+        assert_eq!(pool.price_cumulative, PRICE_SCALE / 2 * 100);
+
+        env.set_timestamp(1200);
+        let end: PriceObservation = from_response(&amm.observe(&env.ctx(), 0).unwrap()).unwrap();
+        let twap = (end.price_cumulative - start.price_cumulative)
+            / (end.timestamp - start.timestamp) as u128;
+        assert_eq!(twap, (PRICE_SCALE / 2 + new_spot) / 2);
+    }
+
     #[test]
     fn test_isqrt() {
         assert_eq!(isqrt(0), 0);
@@ -0,0 +1,564 @@
+//! Auction House — English (ascending) and Dutch (declining) auctions for a token lot.
+//! Sellers escrow the lot, bids are escrowed, outbid bidders withdraw their refunds.
+//! English auctions extend when a bid lands in the final window (anti-sniping).
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::format;
+use norn_sdk::prelude::*;
+
+// ── Storage ────────────────────────────────────────────────────────────
+
+const AUCTION_COUNT: Item<u64> = Item::new("auction_count");
+const AUCTIONS: Map<u64, Auction> = Map::new("auctions");
+const PENDING_RETURNS: Map<(Address, TokenId), u128> = Map::new("pending_returns");
+
+// ── Types ──────────────────────────────────────────────────────────────
+
+#[derive(Debug, BorshSerialize, BorshDeserialize, Clone, PartialEq)]
+pub enum AuctionKind {
+    /// Ascending bids; late bids push `end_time` out by `extension_window`.
+    English {
+        reserve_price: u128,
+        min_increment: u128,
+        extension_window: u64,
+    },
+    /// Price declines linearly from `start_price` to `end_price`; first buyer wins.
+    Dutch { start_price: u128, end_price: u128 },
+}
+
+#[derive(Debug, BorshSerialize, BorshDeserialize, Clone, PartialEq)]
+pub enum AuctionStatus {
+    Open,
+    Settled,
+    Cancelled,
+}
+
+#[derive(Debug, BorshSerialize, BorshDeserialize, Clone)]
+pub struct Auction {
+    pub id: u64,
+    pub seller: Address,
+    pub lot_token: TokenId,
+    pub lot_amount: u128,
+    pub pay_token: TokenId,
+    pub kind: AuctionKind,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub highest_bidder: Option<Address>,
+    pub highest_bid: u128,
+    pub status: AuctionStatus,
+    pub created_at: u64,
+}
+
+// ── Contract ───────────────────────────────────────────────────────────
+
+#[norn_contract]
+pub struct AuctionHouse;
+
+#[norn_contract]
+impl AuctionHouse {
+    #[init]
+    pub fn new(_ctx: &Context) -> Self {
+        AUCTION_COUNT.init(&0u64);
+        AuctionHouse
+    }
+
+    #[execute]
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_english(
+        &mut self,
+        ctx: &Context,
+        lot_token: TokenId,
+        lot_amount: u128,
+        pay_token: TokenId,
+        reserve_price: u128,
+        min_increment: u128,
+        start_time: u64,
+        end_time: u64,
+        extension_window: u64,
+    ) -> ContractResult {
+        ensure!(reserve_price > 0, "reserve_price must be positive");
+        ensure!(min_increment > 0, "min_increment must be positive");
+        let kind = AuctionKind::English {
+            reserve_price,
+            min_increment,
+            extension_window,
+        };
+        create_auction(ctx, lot_token, lot_amount, pay_token, kind, start_time, end_time)
+    }
+
+    #[execute]
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_dutch(
+        &mut self,
+        ctx: &Context,
+        lot_token: TokenId,
+        lot_amount: u128,
+        pay_token: TokenId,
+        start_price: u128,
+        end_price: u128,
+        start_time: u64,
+        end_time: u64,
+    ) -> ContractResult {
+        ensure!(end_price > 0, "end_price must be positive");
+        ensure!(start_price > end_price, "start_price must exceed end_price");
+        let kind = AuctionKind::Dutch {
+            start_price,
+            end_price,
+        };
+        create_auction(ctx, lot_token, lot_amount, pay_token, kind, start_time, end_time)
+    }
+
+    /// Place a bid on an English auction. The bid is escrowed; the previous
+    /// highest bid becomes withdrawable by its bidder.
+    #[execute]
+    pub fn bid(&mut self, ctx: &Context, auction_id: u64, amount: u128) -> ContractResult {
+        let mut auction = AUCTIONS.load(&auction_id)?;
+        ensure!(auction.status == AuctionStatus::Open, "auction is not open");
+        let (reserve_price, min_increment, extension_window) = match auction.kind {
+            AuctionKind::English {
+                reserve_price,
+                min_increment,
+                extension_window,
+            } => (reserve_price, min_increment, extension_window),
+            AuctionKind::Dutch { .. } => return Err("use buy for dutch auctions".into()),
+        };
+        let now = ctx.timestamp();
+        ensure!(now >= auction.start_time, "auction has not started");
+        ensure!(now < auction.end_time, "auction has ended");
+        ensure!(ctx.sender() != auction.seller, "seller cannot bid");
+
+        if auction.highest_bidder.is_some() {
+            ensure!(
+                amount >= safe_add(auction.highest_bid, min_increment)?,
+                "bid below minimum increment"
+            );
+        } else {
+            ensure!(amount >= reserve_price, "bid below reserve price");
+        }
+
+        let contract = ctx.contract_address();
+        ctx.transfer(&ctx.sender(), &contract, &auction.pay_token, amount);
+
+        if let Some(prev) = auction.highest_bidder {
+            credit_return(&prev, &auction.pay_token, auction.highest_bid)?;
+        }
+        auction.highest_bidder = Some(ctx.sender());
+        auction.highest_bid = amount;
+
+        // Anti-sniping: a bid inside the final window extends the auction
+        let window_start = auction.end_time.saturating_sub(extension_window);
+        if now >= window_start {
+            auction.end_time = safe_add_u64(now, extension_window)?;
+        }
+        AUCTIONS.save(&auction_id, &auction)?;
+
+        Ok(Response::with_action("bid")
+            .add_attribute("auction_id", format!("{}", auction_id))
+            .add_attribute("amount", format!("{}", amount))
+            .add_attribute("end_time", format!("{}", auction.end_time)))
+    }
+
+    /// Buy a Dutch auction lot at the current price. Settles immediately.
+    #[execute]
+    pub fn buy(&mut self, ctx: &Context, auction_id: u64) -> ContractResult {
+        let mut auction = AUCTIONS.load(&auction_id)?;
+        ensure!(auction.status == AuctionStatus::Open, "auction is not open");
+        ensure!(
+            matches!(auction.kind, AuctionKind::Dutch { .. }),
+            "use bid for english auctions"
+        );
+        let now = ctx.timestamp();
+        ensure!(now >= auction.start_time, "auction has not started");
+        ensure!(now < auction.end_time, "auction has ended");
+        ensure!(ctx.sender() != auction.seller, "seller cannot buy");
+
+        let price = current_price(&auction, now)?;
+        ctx.transfer(&ctx.sender(), &auction.seller, &auction.pay_token, price);
+        ctx.transfer_from_contract(&ctx.sender(), &auction.lot_token, auction.lot_amount);
+
+        auction.highest_bidder = Some(ctx.sender());
+        auction.highest_bid = price;
+        auction.status = AuctionStatus::Settled;
+        AUCTIONS.save(&auction_id, &auction)?;
+
+        Ok(Response::with_action("buy")
+            .add_attribute("auction_id", format!("{}", auction_id))
+            .add_attribute("price", format!("{}", price)))
+    }
+
+    /// Settle an auction after its end time. Callable by anyone.
+    /// The winner receives the lot and the seller the winning bid; an
+    /// auction without a winner returns the lot to the seller.
+    #[execute]
+    pub fn settle(&mut self, ctx: &Context, auction_id: u64) -> ContractResult {
+        let mut auction = AUCTIONS.load(&auction_id)?;
+        ensure!(auction.status == AuctionStatus::Open, "auction is not open");
+        ensure!(
+            ctx.timestamp() >= auction.end_time,
+            "auction has not ended yet"
+        );
+
+        let resp = match auction.highest_bidder {
+            Some(winner) => {
+                ctx.transfer_from_contract(&winner, &auction.lot_token, auction.lot_amount);
+                ctx.transfer_from_contract(
+                    &auction.seller,
+                    &auction.pay_token,
+                    auction.highest_bid,
+                );
+                Response::with_action("settle")
+                    .add_address("winner", &winner)
+                    .add_attribute("price", format!("{}", auction.highest_bid))
+            }
+            None => {
+                ctx.transfer_from_contract(
+                    &auction.seller,
+                    &auction.lot_token,
+                    auction.lot_amount,
+                );
+                Response::with_action("settle").add_attribute("winner", "none")
+            }
+        };
+
+        auction.status = AuctionStatus::Settled;
+        AUCTIONS.save(&auction_id, &auction)?;
+
+        Ok(resp.add_attribute("auction_id", format!("{}", auction_id)))
+    }
+
+    #[execute]
+    pub fn cancel(&mut self, ctx: &Context, auction_id: u64) -> ContractResult {
+        let mut auction = AUCTIONS.load(&auction_id)?;
+        ensure!(ctx.sender() == auction.seller, "only seller can cancel");
+        ensure!(auction.status == AuctionStatus::Open, "auction is not open");
+        ensure!(auction.highest_bidder.is_none(), "auction has bids");
+
+        ctx.transfer_from_contract(&auction.seller, &auction.lot_token, auction.lot_amount);
+        auction.status = AuctionStatus::Cancelled;
+        AUCTIONS.save(&auction_id, &auction)?;
+
+        Ok(Response::with_action("cancel")
+            .add_attribute("auction_id", format!("{}", auction_id)))
+    }
+
+    /// Withdraw escrowed bids that were outbid.
+    #[execute]
+    pub fn withdraw(&mut self, ctx: &Context, token_id: TokenId) -> ContractResult {
+        let key = (ctx.sender(), token_id);
+        let amount = PENDING_RETURNS.load(&key).unwrap_or(0u128);
+        ensure!(amount > 0, "nothing to withdraw");
+
+        PENDING_RETURNS.save(&key, &0u128)?;
+        ctx.transfer_from_contract(&ctx.sender(), &token_id, amount);
+
+        Ok(Response::with_action("withdraw")
+            .add_attribute("amount", format!("{}", amount)))
+    }
+
+    #[query]
+    pub fn get_auction(&self, _ctx: &Context, auction_id: u64) -> ContractResult {
+        let auction = AUCTIONS.load(&auction_id)?;
+        ok(auction)
+    }
+
+    #[query]
+    pub fn get_auction_count(&self, _ctx: &Context) -> ContractResult {
+        let count = AUCTION_COUNT.load_or(0u64);
+        ok(count)
+    }
+
+    #[query]
+    pub fn get_current_price(&self, ctx: &Context, auction_id: u64) -> ContractResult {
+        let auction = AUCTIONS.load(&auction_id)?;
+        let price = current_price(&auction, ctx.timestamp())?;
+        ok(price)
+    }
+
+    #[query]
+    pub fn get_pending_return(
+        &self,
+        _ctx: &Context,
+        addr: Address,
+        token_id: TokenId,
+    ) -> ContractResult {
+        let amount = PENDING_RETURNS.load(&(addr, token_id)).unwrap_or(0u128);
+        ok(amount)
+    }
+}
+
+// ── Helpers ────────────────────────────────────────────────────────────
+
+#[allow(clippy::too_many_arguments)]
+fn create_auction(
+    ctx: &Context,
+    lot_token: TokenId,
+    lot_amount: u128,
+    pay_token: TokenId,
+    kind: AuctionKind,
+    start_time: u64,
+    end_time: u64,
+) -> ContractResult {
+    ensure!(lot_amount > 0, "lot_amount must be positive");
+    ensure!(end_time > start_time, "end_time must be after start_time");
+    ensure!(end_time > ctx.timestamp(), "end_time must be in the future");
+
+    // Escrow the lot
+    let contract = ctx.contract_address();
+    ctx.transfer(&ctx.sender(), &contract, &lot_token, lot_amount);
+
+    let id = AUCTION_COUNT.load_or(0u64);
+    AUCTIONS.save(
+        &id,
+        &Auction {
+            id,
+            seller: ctx.sender(),
+            lot_token,
+            lot_amount,
+            pay_token,
+            kind,
+            start_time,
+            end_time,
+            highest_bidder: None,
+            highest_bid: 0,
+            status: AuctionStatus::Open,
+            created_at: ctx.timestamp(),
+        },
+    )?;
+    AUCTION_COUNT.save(&safe_add_u64(id, 1)?)?;
+
+    Ok(Response::with_action("create_auction")
+        .add_attribute("auction_id", format!("{}", id))
+        .set_data(&id))
+}
+
+/// Price of an auction at `now`: the next acceptable bid for English
+/// auctions, the linearly declining price for Dutch auctions.
+fn current_price(auction: &Auction, now: u64) -> Result<u128, ContractError> {
+    match auction.kind {
+        AuctionKind::English {
+            reserve_price,
+            min_increment,
+            ..
+        } => match auction.highest_bidder {
+            Some(_) => safe_add(auction.highest_bid, min_increment),
+            None => Ok(reserve_price),
+        },
+        AuctionKind::Dutch {
+            start_price,
+            end_price,
+        } => {
+            if now <= auction.start_time {
+                return Ok(start_price);
+            }
+            if now >= auction.end_time {
+                return Ok(end_price);
+            }
+            let elapsed = (now - auction.start_time) as u128;
+            let duration = (auction.end_time - auction.start_time) as u128;
+            let drop = safe_mul(start_price - end_price, elapsed)? / duration;
+            safe_sub(start_price, drop)
+        }
+    }
+}
+
+fn credit_return(addr: &Address, token_id: &TokenId, amount: u128) -> Result<(), ContractError> {
+    let key = (*addr, *token_id);
+    let existing = PENDING_RETURNS.load(&key).unwrap_or(0u128);
+    PENDING_RETURNS.save(&key, &safe_add(existing, amount)?)
+}
+
+// ── Tests ──────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use norn_sdk::testing::*;
+
+    const LOT: TokenId = [42u8; 32];
+    const NORN: TokenId = [0u8; 32];
+    const CONTRACT_ADDR: Address = [99u8; 20];
+
+    fn setup() -> (TestEnv, AuctionHouse) {
+        let env = TestEnv::new()
+            .with_sender(ALICE)
+            .with_timestamp(1000)
+            .with_contract_address(CONTRACT_ADDR);
+        let ah = AuctionHouse::new(&env.ctx());
+        (env, ah)
+    }
+
+    /// English auction 0: reserve 100, increment 10, ends at 2000, 60s window.
+    fn setup_english() -> (TestEnv, AuctionHouse) {
+        let (env, mut ah) = setup();
+        ah.create_english(&env.ctx(), LOT, 1, NORN, 100, 10, 1000, 2000, 60)
+            .unwrap();
+        (env, ah)
+    }
+
+    /// Dutch auction 0: 1_000 down to 100 between 1000 and 2000.
+    fn setup_dutch() -> (TestEnv, AuctionHouse) {
+        let (env, mut ah) = setup();
+        ah.create_dutch(&env.ctx(), LOT, 1, NORN, 1_000, 100, 1000, 2000)
+            .unwrap();
+        (env, ah)
+    }
+
+    #[test]
+    fn test_create_escrows_lot() {
+        let (env, ah) = setup_english();
+        let transfers = env.transfers();
+        assert_eq!(transfers[0].0, ALICE.to_vec());
+        assert_eq!(transfers[0].1, CONTRACT_ADDR.to_vec());
+        assert_eq!(transfers[0].2, LOT.to_vec());
+
+        let resp = ah.get_auction(&env.ctx(), 0).unwrap();
+        let auction: Auction = from_response(&resp).unwrap();
+        assert_eq!(auction.seller, ALICE);
+        assert_eq!(auction.status, AuctionStatus::Open);
+    }
+
+    #[test]
+    fn test_english_bidding_and_outbid_refund() {
+        let (env, mut ah) = setup_english();
+        env.set_timestamp(1500);
+
+        env.set_sender(BOB);
+        let err = ah.bid(&env.ctx(), 0, 99).unwrap_err();
+        assert_err_contains(&err, "bid below reserve price");
+        ah.bid(&env.ctx(), 0, 100).unwrap();
+
+        env.set_sender(CHARLIE);
+        let err = ah.bid(&env.ctx(), 0, 105).unwrap_err();
+        assert_err_contains(&err, "bid below minimum increment");
+        ah.bid(&env.ctx(), 0, 110).unwrap();
+
+        let resp = ah.get_pending_return(&env.ctx(), BOB, NORN).unwrap();
+        let pending: u128 = from_response(&resp).unwrap();
+        assert_eq!(pending, 100);
+
+        env.set_sender(BOB);
+        let resp = ah.withdraw(&env.ctx(), NORN).unwrap();
+        assert_attribute(&resp, "amount", "100");
+        let err = ah.withdraw(&env.ctx(), NORN).unwrap_err();
+        assert_err_contains(&err, "nothing to withdraw");
+    }
+
+    #[test]
+    fn test_english_anti_sniping_extension() {
+        let (env, mut ah) = setup_english();
+        env.set_sender(BOB);
+
+        env.set_timestamp(1900);
+        let resp = ah.bid(&env.ctx(), 0, 100).unwrap();
+        assert_attribute(&resp, "end_time", "2000");
+
+        env.set_timestamp(1990);
+        env.set_sender(CHARLIE);
+        let resp = ah.bid(&env.ctx(), 0, 110).unwrap();
+        assert_attribute(&resp, "end_time", "2050");
+
+        // Original end time no longer closes the auction
+        env.set_timestamp(2010);
+        let err = ah.settle(&env.ctx(), 0).unwrap_err();
+        assert_err_contains(&err, "auction has not ended yet");
+    }
+
+    #[test]
+    fn test_english_settle_pays_seller() {
+        let (env, mut ah) = setup_english();
+        env.set_timestamp(1500);
+        env.set_sender(BOB);
+        ah.bid(&env.ctx(), 0, 250).unwrap();
+
+        env.set_timestamp(2000);
+        env.set_sender(DAVE);
+        env.clear_transfers();
+        let resp = ah.settle(&env.ctx(), 0).unwrap();
+        assert_attribute(&resp, "price", "250");
+
+        let transfers = env.transfers();
+        assert_eq!(transfers[0].1, BOB.to_vec());
+        assert_eq!(transfers[0].2, LOT.to_vec());
+        assert_eq!(transfers[1].1, ALICE.to_vec());
+        assert_eq!(transfers[1].3, 250);
+    }
+
+    #[test]
+    fn test_settle_without_bids_returns_lot() {
+        let (env, mut ah) = setup_english();
+        env.set_timestamp(2000);
+        env.clear_transfers();
+        let resp = ah.settle(&env.ctx(), 0).unwrap();
+        assert_attribute(&resp, "winner", "none");
+        assert_eq!(env.transfers()[0].1, ALICE.to_vec());
+
+        let err = ah.settle(&env.ctx(), 0).unwrap_err();
+        assert_err_contains(&err, "auction is not open");
+    }
+
+    #[test]
+    fn test_dutch_price_declines() {
+        let (env, ah) = setup_dutch();
+        let price = |t: u64| -> u128 {
+            env.set_timestamp(t);
+            from_response(&ah.get_current_price(&env.ctx(), 0).unwrap()).unwrap()
+        };
+        assert_eq!(price(1000), 1_000);
+        assert_eq!(price(1500), 550);
+        assert_eq!(price(2500), 100);
+    }
+
+    #[test]
+    fn test_dutch_buy_settles_immediately() {
+        let (env, mut ah) = setup_dutch();
+        env.set_timestamp(1500);
+        env.set_sender(BOB);
+        env.clear_transfers();
+        let resp = ah.buy(&env.ctx(), 0).unwrap();
+        assert_attribute(&resp, "price", "550");
+
+        let transfers = env.transfers();
+        assert_eq!(transfers[0].0, BOB.to_vec());
+        assert_eq!(transfers[0].1, ALICE.to_vec());
+        assert_eq!(transfers[0].3, 550);
+        assert_eq!(transfers[1].1, BOB.to_vec());
+        assert_eq!(transfers[1].2, LOT.to_vec());
+
+        env.set_sender(CHARLIE);
+        let err = ah.buy(&env.ctx(), 0).unwrap_err();
+        assert_err_contains(&err, "auction is not open");
+    }
+
+    #[test]
+    fn test_bid_and_buy_reject_wrong_kind() {
+        let (env, mut ah) = setup_dutch();
+        env.set_timestamp(1500);
+        env.set_sender(BOB);
+        let err = ah.bid(&env.ctx(), 0, 1_000).unwrap_err();
+        assert_err_contains(&err, "use buy for dutch auctions");
+
+        env.set_sender(ALICE);
+        ah.create_english(&env.ctx(), LOT, 1, NORN, 100, 10, 1000, 2000, 60)
+            .unwrap();
+        env.set_sender(BOB);
+        let err = ah.buy(&env.ctx(), 1).unwrap_err();
+        assert_err_contains(&err, "use bid for english auctions");
+    }
+
+    #[test]
+    fn test_cancel_only_without_bids() {
+        let (env, mut ah) = setup_english();
+        env.set_timestamp(1500);
+        env.set_sender(BOB);
+        let err = ah.cancel(&env.ctx(), 0).unwrap_err();
+        assert_err_contains(&err, "only seller can cancel");
+
+        ah.bid(&env.ctx(), 0, 100).unwrap();
+        env.set_sender(ALICE);
+        let err = ah.cancel(&env.ctx(), 0).unwrap_err();
+        assert_err_contains(&err, "auction has bids");
+    }
+}
@@ -0,0 +1,877 @@
+//! Bridge — lock-and-mint token transfers between two looms.
+//!
+//! Implements the inter-loom transfer standard in `norn_types::loom_transfer`
+//! (ICS-20 style). Deploy the contract twice and open a channel on each
+//! instance pointing at the other; the two instances form a bridge.
+//!
+//! Sending a native token escrows it at the contract address and stores a
+//! commitment to the packet. A relayer submits the packet to the other
+//! instance, which fetches the sender's commitment with a cross-contract call
+//! and, if it matches, credits a voucher denominated
+//! `transfer/<channel>/<denom>`. Sending the voucher back over the same
+//! channel burns it and the original instance releases the escrow.
+//!
+//! Vouchers live in this contract's own ledger and can be moved with
+//! `transfer_voucher`. A voucher can only go back over the channel it arrived
+//! on; forwarding it to a third loom is out of scope for the example.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use norn_sdk::host::MAX_TRANSFER_AMOUNT;
+use norn_sdk::merkle::hash_list_leaf;
+use norn_sdk::prelude::*;
+
+// ── Storage ──────────────────────────────────────────────────────────────
+
+const SELF_LOOM: Item<LoomId> = Item::new("self_loom");
+const CHANNELS: Map<String, Channel> = Map::new("channels");
+/// Commitments of sent packets still awaiting acknowledgement or timeout.
+const COMMITMENTS: Map<(String, u64), [u8; 32]> = Map::new("commitments");
+/// Sequences received per channel, to reject replays.
+const RECEIPTS: Map<(String, u64), bool> = Map::new("receipts");
+/// Native tokens escrowed per channel.
+const ESCROW: Map<(String, TokenId), u128> = Map::new("escrow");
+/// Voucher balances keyed by denom hash.
+const VOUCHERS: Map<([u8; 32], Address), u128> = Map::new("vouchers");
+const VOUCHER_SUPPLY: Map<[u8; 32], u128> = Map::new("voucher_supply");
+
+/// Borsh discriminant of `Bridge::packet_commitment` in the execute enum.
+const EXEC_PACKET_COMMITMENT: u8 = 6;
+/// Borsh discriminant of `Bridge::has_receipt` in the execute enum.
+const EXEC_HAS_RECEIPT: u8 = 7;
+
+const TRANSFER_PORT: &str = "transfer";
+const CHANNEL_PREFIX: &str = "channel-";
+
+// ── Types ────────────────────────────────────────────────────────────────
+
+/// One side of a channel.
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct Channel {
+    pub counterparty_loom: LoomId,
+    pub counterparty_channel: String,
+    /// Sequence the next sent packet gets.
+    pub next_sequence: u64,
+}
+
+/// Mirrors `norn_types::loom_transfer::TokenPacketData`.
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct TokenPacketData {
+    pub denom: String,
+    pub amount: u128,
+    pub sender: Address,
+    pub receiver: Address,
+    pub memo: String,
+}
+
+/// Mirrors `norn_types::loom_transfer::LoomTransferPacket`.
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct LoomTransferPacket {
+    pub sequence: u64,
+    pub source_loom: LoomId,
+    pub source_channel: String,
+    pub dest_loom: LoomId,
+    pub dest_channel: String,
+    pub data: TokenPacketData,
+    pub timeout_timestamp: u64,
+}
+
+// ── Contract ─────────────────────────────────────────────────────────────
+
+#[norn_contract]
+pub struct Bridge;
+
+#[norn_contract]
+impl Bridge {
+    /// `loom_id` must be this loom's own ID; it is stamped on sent packets.
+    #[init]
+    pub fn new(ctx: &Context, loom_id: LoomId) -> Self {
+        assert!(
+            loom_address(&loom_id) == ctx.contract_address(),
+            "loom_id does not match this loom"
+        );
+        Ownable::init(&ctx.sender()).unwrap();
+        SELF_LOOM.init(&loom_id);
+        Bridge
+    }
+
+    // ── Execute ──────────────────────────────────────────────────────
+
+    /// Bind `channel` to `counterparty_channel` on `counterparty_loom`
+    /// (owner-only). Channels cannot be changed once opened.
+    #[execute]
+    pub fn open_channel(
+        &mut self,
+        ctx: &Context,
+        channel: String,
+        counterparty_loom: LoomId,
+        counterparty_channel: String,
+    ) -> ContractResult {
+        Ownable::require_owner(ctx)?;
+        ensure!(is_channel_id(&channel), "invalid channel id");
+        ensure!(
+            is_channel_id(&counterparty_channel),
+            "invalid counterparty channel id"
+        );
+        ensure!(!CHANNELS.has(&channel), "channel already open");
+        CHANNELS.save(
+            &channel,
+            &Channel {
+                counterparty_loom,
+                counterparty_channel: counterparty_channel.clone(),
+                next_sequence: 1,
+            },
+        )?;
+        Ok(Response::with_action("open_channel")
+            .add_attribute("channel", channel)
+            .add_attribute("counterparty_channel", counterparty_channel))
+    }
+
+    /// Send `amount` of `denom` over `channel` to `receiver` on the other
+    /// side. Returns the packet for the relayer.
+    #[execute]
+    #[allow(clippy::too_many_arguments)]
+    pub fn send_transfer(
+        &mut self,
+        ctx: &Context,
+        channel: String,
+        denom: String,
+        amount: u128,
+        receiver: Address,
+        timeout_timestamp: u64,
+        memo: String,
+    ) -> ContractResult {
+        ensure!(amount > 0, "amount must be positive");
+        ensure!(
+            timeout_timestamp == 0 || timeout_timestamp > ctx.timestamp(),
+            "timeout must be in the future"
+        );
+        let mut ch = load_channel(&channel)?;
+        let sender = ctx.sender();
+
+        if strip_channel_prefix(&channel, &denom).is_some() {
+            // A voucher going home: burn it here, the other side unescrows.
+            burn_voucher(&denom, &sender, amount)?;
+        } else {
+            let token = parse_native_denom(&denom)
+                .ok_or("only native tokens or vouchers from this channel can be sent")?;
+            ensure!(
+                amount <= MAX_TRANSFER_AMOUNT,
+                "amount exceeds transfer limit"
+            );
+            ensure!(
+                ctx.balance_of(&sender, &token) >= amount,
+                ContractError::InsufficientFunds
+            );
+            ctx.transfer_checked(&sender, &ctx.contract_address(), &token, amount)?;
+            let key = (channel.clone(), token);
+            ESCROW.save(&key, &safe_add(ESCROW.load_or(&key, 0), amount)?)?;
+        }
+
+        let packet = LoomTransferPacket {
+            sequence: ch.next_sequence,
+            source_loom: SELF_LOOM.load()?,
+            source_channel: channel.clone(),
+            dest_loom: ch.counterparty_loom,
+            dest_channel: ch.counterparty_channel.clone(),
+            data: TokenPacketData {
+                denom,
+                amount,
+                sender,
+                receiver,
+                memo,
+            },
+            timeout_timestamp,
+        };
+        COMMITMENTS.save(
+            &(channel.clone(), packet.sequence),
+            &packet_commitment(&packet),
+        )?;
+        ch.next_sequence += 1;
+        CHANNELS.save(&channel, &ch)?;
+
+        Ok(Response::with_action("send_packet")
+            .add_attribute("channel", channel)
+            .add_u128("sequence", packet.sequence as u128)
+            .add_u128("amount", amount)
+            .set_data(&packet))
+    }
+
+    /// Deliver a packet sent by the counterparty. Anyone may relay; the
+    /// packet is checked against the commitment the sending loom stored.
+    #[execute]
+    pub fn recv_packet(&mut self, ctx: &Context, packet: LoomTransferPacket) -> ContractResult {
+        let ch = load_channel(&packet.dest_channel)?;
+        ensure!(
+            packet.source_loom == ch.counterparty_loom
+                && packet.source_channel == ch.counterparty_channel,
+            "packet is not from this channel's counterparty"
+        );
+        ensure!(
+            packet.dest_loom == SELF_LOOM.load()?,
+            "packet is addressed to another loom"
+        );
+        ensure!(
+            packet.timeout_timestamp == 0 || ctx.timestamp() < packet.timeout_timestamp,
+            "packet timed out"
+        );
+        let receipt = (packet.dest_channel.clone(), packet.sequence);
+        ensure!(!RECEIPTS.has(&receipt), "packet already received");
+        let committed = query_commitment(ctx, &ch, packet.sequence)?;
+        ensure!(
+            committed == Some(packet_commitment(&packet)),
+            "packet does not match the sender's commitment"
+        );
+
+        let data = &packet.data;
+        let denom = match strip_channel_prefix(&packet.source_channel, &data.denom) {
+            // Our token coming back: release it from escrow.
+            Some(base) => {
+                let token = parse_native_denom(base).ok_or("unsupported denom")?;
+                let key = (packet.dest_channel.clone(), token);
+                ESCROW.save(&key, &safe_sub(ESCROW.load_or(&key, 0), data.amount)?)?;
+                ctx.transfer_checked(&ctx.contract_address(), &data.receiver, &token, data.amount)?;
+                String::from(base)
+            }
+            // A foreign token: credit a voucher that records the hop.
+            None => {
+                let voucher = prefix_denom(&packet.dest_channel, &data.denom);
+                mint_voucher(&voucher, &data.receiver, data.amount)?;
+                voucher
+            }
+        };
+        RECEIPTS.save(&receipt, &true)?;
+
+        Ok(Response::with_action("recv_packet")
+            .add_attribute("channel", packet.dest_channel)
+            .add_u128("sequence", packet.sequence as u128)
+            .add_attribute("denom", denom)
+            .add_u128("amount", data.amount))
+    }
+
+    /// Clear the commitment of a packet the counterparty has received.
+    #[execute]
+    pub fn acknowledge_packet(
+        &mut self,
+        ctx: &Context,
+        channel: String,
+        sequence: u64,
+    ) -> ContractResult {
+        let ch = load_channel(&channel)?;
+        let key = (channel.clone(), sequence);
+        ensure!(COMMITMENTS.has(&key), "no pending packet");
+        ensure!(
+            query_receipt(ctx, &ch, sequence)?,
+            "packet not received by counterparty"
+        );
+        COMMITMENTS.remove(&key);
+        Ok(Response::with_action("acknowledge_packet")
+            .add_attribute("channel", channel)
+            .add_u128("sequence", sequence as u128))
+    }
+
+    /// Refund a packet that timed out before the counterparty received it.
+    #[execute]
+    pub fn timeout_packet(&mut self, ctx: &Context, packet: LoomTransferPacket) -> ContractResult {
+        let ch = load_channel(&packet.source_channel)?;
+        let key = (packet.source_channel.clone(), packet.sequence);
+        ensure!(
+            COMMITMENTS.load(&key).ok() == Some(packet_commitment(&packet)),
+            "no matching pending packet"
+        );
+        ensure!(
+            packet.timeout_timestamp != 0 && ctx.timestamp() >= packet.timeout_timestamp,
+            "packet has not timed out"
+        );
+        ensure!(
+            !query_receipt(ctx, &ch, packet.sequence)?,
+            "packet was received by counterparty"
+        );
+        COMMITMENTS.remove(&key);
+
+        let data = &packet.data;
+        if strip_channel_prefix(&packet.source_channel, &data.denom).is_some() {
+            mint_voucher(&data.denom, &data.sender, data.amount)?;
+        } else {
+            let token = parse_native_denom(&data.denom).ok_or("unsupported denom")?;
+            let escrow = (packet.source_channel.clone(), token);
+            ESCROW.save(&escrow, &safe_sub(ESCROW.load_or(&escrow, 0), data.amount)?)?;
+            ctx.transfer_checked(&ctx.contract_address(), &data.sender, &token, data.amount)?;
+        }
+
+        Ok(Response::with_action("timeout_packet")
+            .add_attribute("channel", packet.source_channel)
+            .add_u128("sequence", packet.sequence as u128)
+            .add_u128("refunded", data.amount))
+    }
+
+    /// Move vouchers between accounts on this loom.
+    #[execute]
+    pub fn transfer_voucher(
+        &mut self,
+        ctx: &Context,
+        denom: String,
+        to: Address,
+        amount: u128,
+    ) -> ContractResult {
+        ensure!(amount > 0, "amount must be positive");
+        let sender = ctx.sender();
+        ensure_ne!(sender, to, "cannot transfer to self");
+        let id = denom_hash(&denom);
+        let from_bal = VOUCHERS.load_or(&(id, sender), 0);
+        ensure!(amount <= from_bal, ContractError::InsufficientFunds);
+        let to_bal = VOUCHERS.load_or(&(id, to), 0);
+        VOUCHERS.save(&(id, sender), &(from_bal - amount))?;
+        VOUCHERS.save(&(id, to), &safe_add(to_bal, amount)?)?;
+        Ok(Response::with_action("transfer_voucher")
+            .add_attribute("denom", denom)
+            .add_u128("amount", amount))
+    }
+
+    /// Commitment of a pending sent packet. Exposed as an execute so the
+    /// counterparty can reach it through a cross-contract call.
+    #[execute]
+    pub fn packet_commitment(
+        &mut self,
+        _ctx: &Context,
+        channel: String,
+        sequence: u64,
+    ) -> ContractResult {
+        ok(COMMITMENTS.load(&(channel, sequence)).ok())
+    }
+
+    /// Whether a packet was received. Exposed as an execute so the
+    /// counterparty can reach it through a cross-contract call.
+    #[execute]
+    pub fn has_receipt(
+        &mut self,
+        _ctx: &Context,
+        channel: String,
+        sequence: u64,
+    ) -> ContractResult {
+        ok(RECEIPTS.has(&(channel, sequence)))
+    }
+
+    // ── Query ────────────────────────────────────────────────────────
+
+    #[query]
+    pub fn get_channel(&self, _ctx: &Context, channel: String) -> ContractResult {
+        ok(load_channel(&channel)?)
+    }
+
+    #[query]
+    pub fn voucher_balance(&self, _ctx: &Context, denom: String, addr: Address) -> ContractResult {
+        ok(VOUCHERS.load_or(&(denom_hash(&denom), addr), 0))
+    }
+
+    #[query]
+    pub fn voucher_supply(&self, _ctx: &Context, denom: String) -> ContractResult {
+        ok(VOUCHER_SUPPLY.load_or(&denom_hash(&denom), 0))
+    }
+
+    /// Native `token_id` escrowed for transfers over `channel`.
+    #[query]
+    pub fn escrowed(&self, _ctx: &Context, channel: String, token_id: TokenId) -> ContractResult {
+        ok(ESCROW.load_or(&(channel, token_id), 0))
+    }
+}
+
+// ── Helpers ──────────────────────────────────────────────────────────────
+
+fn load_channel(channel: &String) -> Result<Channel, ContractError> {
+    CHANNELS
+        .load(channel)
+        .map_err(|_| ContractError::Custom(format!("unknown channel {channel}")))
+}
+
+fn query_commitment(
+    ctx: &Context,
+    ch: &Channel,
+    sequence: u64,
+) -> Result<Option<[u8; 32]>, ContractError> {
+    let output = cross_call(ctx, ch, EXEC_PACKET_COMMITMENT, sequence)?;
+    Option::<[u8; 32]>::try_from_slice(&output)
+        .map_err(|_| ContractError::Custom("invalid counterparty response".into()))
+}
+
+fn query_receipt(ctx: &Context, ch: &Channel, sequence: u64) -> Result<bool, ContractError> {
+    let output = cross_call(ctx, ch, EXEC_HAS_RECEIPT, sequence)?;
+    bool::try_from_slice(&output)
+        .map_err(|_| ContractError::Custom("invalid counterparty response".into()))
+}
+
+/// Call `(channel, sequence)` method `disc` on the counterparty loom.
+fn cross_call(
+    ctx: &Context,
+    ch: &Channel,
+    disc: u8,
+    sequence: u64,
+) -> Result<Vec<u8>, ContractError> {
+    let mut input = Vec::from([disc]);
+    input.extend_from_slice(&borsh::to_vec(&(&ch.counterparty_channel, sequence)).unwrap());
+    Ok(ctx
+        .call_contract_raw(&ch.counterparty_loom, &input)
+        .ok_or("counterparty call failed")?)
+}
+
+fn mint_voucher(denom: &str, to: &Address, amount: u128) -> Result<(), ContractError> {
+    let id = denom_hash(denom);
+    VOUCHERS.save(
+        &(id, *to),
+        &safe_add(VOUCHERS.load_or(&(id, *to), 0), amount)?,
+    )?;
+    VOUCHER_SUPPLY.save(&id, &safe_add(VOUCHER_SUPPLY.load_or(&id, 0), amount)?)
+}
+
+fn burn_voucher(denom: &str, from: &Address, amount: u128) -> Result<(), ContractError> {
+    let id = denom_hash(denom);
+    let bal = VOUCHERS.load_or(&(id, *from), 0);
+    ensure!(amount <= bal, ContractError::InsufficientFunds);
+    VOUCHERS.save(&(id, *from), &(bal - amount))?;
+    VOUCHER_SUPPLY.save(&id, &safe_sub(VOUCHER_SUPPLY.load_or(&id, 0), amount)?)
+}
+
+// Mirrors of the `norn_types::loom_transfer` helpers, which looms cannot
+// link against directly.
+
+fn packet_commitment(packet: &LoomTransferPacket) -> [u8; 32] {
+    hash_list_leaf(&borsh::to_vec(packet).unwrap())
+}
+
+fn denom_hash(denom: &str) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(b"norn_denom");
+    hasher.update(denom.as_bytes());
+    *hasher.finalize().as_bytes()
+}
+
+/// `norn_types::primitives::derive_contract_address`.
+fn loom_address(loom_id: &LoomId) -> Address {
+    let mut addr = [0u8; 20];
+    addr.copy_from_slice(&blake3::hash(loom_id).as_bytes()[..20]);
+    addr
+}
+
+fn is_channel_id(s: &str) -> bool {
+    s.strip_prefix(CHANNEL_PREFIX).is_some_and(|n| {
+        !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()) && (n == "0" || !n.starts_with('0'))
+    })
+}
+
+fn parse_native_denom(denom: &str) -> Option<TokenId> {
+    if denom.len() != 64 {
+        return None;
+    }
+    let mut id = [0u8; 32];
+    for (i, byte) in id.iter_mut().enumerate() {
+        let hi = hex_nibble(denom.as_bytes()[2 * i])?;
+        let lo = hex_nibble(denom.as_bytes()[2 * i + 1])?;
+        *byte = (hi << 4) | lo;
+    }
+    Some(id)
+}
+
+fn hex_nibble(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        _ => None,
+    }
+}
+
+fn prefix_denom(channel: &str, denom: &str) -> String {
+    format!("{TRANSFER_PORT}/{channel}/{denom}")
+}
+
+fn strip_channel_prefix<'a>(channel: &str, denom: &'a str) -> Option<&'a str> {
+    denom
+        .strip_prefix(TRANSFER_PORT)?
+        .strip_prefix('/')?
+        .strip_prefix(channel)?
+        .strip_prefix('/')
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use core::cell::RefCell;
+    use norn_sdk::host;
+    use norn_sdk::testing::*;
+    use std::rc::Rc;
+
+    const SELF_ID: LoomId = [1u8; 32];
+    const PEER: LoomId = [2u8; 32];
+    const TOKEN: TokenId = [7u8; 32];
+
+    /// State the mocked counterparty reports through cross-calls.
+    #[derive(Default)]
+    struct Peer {
+        commitments: Vec<(u64, [u8; 32])>,
+        receipts: Vec<u64>,
+    }
+
+    fn set_peer() -> Rc<RefCell<Peer>> {
+        let peer = Rc::new(RefCell::new(Peer::default()));
+        let handle = peer.clone();
+        host::mock_set_cross_call_handler(move |target, input| {
+            assert_eq!(target, &PEER);
+            let (channel, seq) = <(String, u64)>::try_from_slice(&input[1..]).unwrap();
+            assert_eq!(channel, "channel-5");
+            let peer = handle.borrow();
+            let out = match input[0] {
+                EXEC_PACKET_COMMITMENT => borsh::to_vec(
+                    &peer
+                        .commitments
+                        .iter()
+                        .find(|(s, _)| *s == seq)
+                        .map(|(_, c)| *c),
+                ),
+                EXEC_HAS_RECEIPT => borsh::to_vec(&peer.receipts.contains(&seq)),
+                _ => panic!("unexpected cross-call"),
+            };
+            Some(out.unwrap())
+        });
+        peer
+    }
+
+    /// Bridge with `channel-0` bound to the peer's `channel-5`. ALICE owns
+    /// 1_000 of TOKEN.
+    fn setup() -> (TestEnv, Bridge, Rc<RefCell<Peer>>) {
+        let env = TestEnv::new()
+            .with_sender(ALICE)
+            .with_timestamp(1_000)
+            .with_contract_address(loom_address(&SELF_ID))
+            .with_balance(ALICE, TOKEN, 1_000);
+        let peer = set_peer();
+        let mut bridge = Bridge::new(&env.ctx(), SELF_ID);
+        bridge
+            .open_channel(
+                &env.ctx(),
+                String::from("channel-0"),
+                PEER,
+                String::from("channel-5"),
+            )
+            .unwrap();
+        (env, bridge, peer)
+    }
+
+    fn token_denom() -> String {
+        TOKEN.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    fn incoming(seq: u64, denom: String, amount: u128) -> LoomTransferPacket {
+        LoomTransferPacket {
+            sequence: seq,
+            source_loom: PEER,
+            source_channel: String::from("channel-5"),
+            dest_loom: SELF_ID,
+            dest_channel: String::from("channel-0"),
+            data: TokenPacketData {
+                denom,
+                amount,
+                sender: CHARLIE,
+                receiver: BOB,
+                memo: String::new(),
+            },
+            timeout_timestamp: 2_000,
+        }
+    }
+
+    fn send(env: &TestEnv, bridge: &mut Bridge, denom: String, amount: u128) -> LoomTransferPacket {
+        let resp = bridge
+            .send_transfer(
+                &env.ctx(),
+                String::from("channel-0"),
+                denom,
+                amount,
+                DAVE,
+                2_000,
+                String::new(),
+            )
+            .unwrap();
+        from_response(&resp).unwrap()
+    }
+
+    #[test]
+    fn test_discriminants_match_execute_enum() {
+        let input = borsh::to_vec(&__BridgeExecute::PacketCommitment {
+            channel: String::new(),
+            sequence: 0,
+        })
+        .unwrap();
+        assert_eq!(input[0], EXEC_PACKET_COMMITMENT);
+        let input = borsh::to_vec(&__BridgeExecute::HasReceipt {
+            channel: String::new(),
+            sequence: 0,
+        })
+        .unwrap();
+        assert_eq!(input[0], EXEC_HAS_RECEIPT);
+    }
+
+    #[test]
+    fn test_commitment_matches_standard() {
+        // Same packet and vector as the norn-types loom_transfer tests.
+        let packet = LoomTransferPacket {
+            sequence: 1,
+            source_loom: [1u8; 32],
+            source_channel: String::from("channel-0"),
+            dest_loom: [2u8; 32],
+            dest_channel: String::from("channel-3"),
+            data: TokenPacketData {
+                denom: "0".repeat(64),
+                amount: 500,
+                sender: [3u8; 20],
+                receiver: [4u8; 20],
+                memo: String::new(),
+            },
+            timeout_timestamp: 1_000,
+        };
+        let hex: String = packet_commitment(&packet)
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect();
+        assert_eq!(
+            hex,
+            "cc52d73ae533d00cea5b0fee3cc0c74e171c01116365a1586b6b2a17209f8228"
+        );
+    }
+
+    #[test]
+    fn test_open_channel_owner_only() {
+        let (env, mut bridge, _peer) = setup();
+        env.set_sender(BOB);
+        let err = bridge
+            .open_channel(
+                &env.ctx(),
+                String::from("channel-1"),
+                PEER,
+                String::from("channel-6"),
+            )
+            .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized);
+
+        env.set_sender(ALICE);
+        let err = bridge
+            .open_channel(
+                &env.ctx(),
+                String::from("channel-0"),
+                PEER,
+                String::from("channel-6"),
+            )
+            .unwrap_err();
+        assert_eq!(err.message(), "channel already open");
+    }
+
+    #[test]
+    fn test_send_escrows_and_commits() {
+        let (env, mut bridge, _peer) = setup();
+        let packet = send(&env, &mut bridge, token_denom(), 400);
+        assert_eq!(packet.sequence, 1);
+        assert_eq!(packet.source_loom, SELF_ID);
+        assert_eq!(packet.dest_loom, PEER);
+        assert_eq!(packet.dest_channel, "channel-5");
+        assert_eq!(packet.data.sender, ALICE);
+
+        assert_eq!(env.ctx().balance_of(&ALICE, &TOKEN), 600);
+        assert_eq!(ESCROW.load_or(&(String::from("channel-0"), TOKEN), 0), 400);
+        let resp = bridge
+            .packet_commitment(&env.ctx(), String::from("channel-0"), 1)
+            .unwrap();
+        assert_data(&resp, &Some(packet_commitment(&packet)));
+
+        let next = send(&env, &mut bridge, token_denom(), 100);
+        assert_eq!(next.sequence, 2);
+    }
+
+    #[test]
+    fn test_send_rejects_foreign_vouchers_and_overdraw() {
+        let (env, mut bridge, _peer) = setup();
+        let err = bridge
+            .send_transfer(
+                &env.ctx(),
+                String::from("channel-0"),
+                prefix_denom("channel-9", &token_denom()),
+                1,
+                DAVE,
+                0,
+                String::new(),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.message(),
+            "only native tokens or vouchers from this channel can be sent"
+        );
+        let err = bridge
+            .send_transfer(
+                &env.ctx(),
+                String::from("channel-0"),
+                token_denom(),
+                1_001,
+                DAVE,
+                0,
+                String::new(),
+            )
+            .unwrap_err();
+        assert_eq!(err, ContractError::InsufficientFunds);
+    }
+
+    #[test]
+    fn test_recv_mints_voucher() {
+        let (env, mut bridge, peer) = setup();
+        let packet = incoming(1, token_denom(), 250);
+        peer.borrow_mut()
+            .commitments
+            .push((1, packet_commitment(&packet)));
+
+        let resp = bridge.recv_packet(&env.ctx(), packet.clone()).unwrap();
+        let voucher = prefix_denom("channel-0", &token_denom());
+        assert_attribute(&resp, "denom", &voucher);
+        assert_eq!(VOUCHERS.load_or(&(denom_hash(&voucher), BOB), 0), 250);
+        assert_eq!(VOUCHER_SUPPLY.load_or(&denom_hash(&voucher), 0), 250);
+        let resp = bridge
+            .has_receipt(&env.ctx(), String::from("channel-0"), 1)
+            .unwrap();
+        assert_data(&resp, &true);
+
+        let err = bridge.recv_packet(&env.ctx(), packet).unwrap_err();
+        assert_eq!(err.message(), "packet already received");
+    }
+
+    #[test]
+    fn test_recv_rejects_unproven_packets() {
+        let (env, mut bridge, peer) = setup();
+        let packet = incoming(1, token_denom(), 250);
+
+        // Nothing committed on the counterparty.
+        let err = bridge.recv_packet(&env.ctx(), packet.clone()).unwrap_err();
+        assert_eq!(
+            err.message(),
+            "packet does not match the sender's commitment"
+        );
+
+        // Relayer inflates the amount of a real packet.
+        peer.borrow_mut()
+            .commitments
+            .push((1, packet_commitment(&packet)));
+        let mut forged = packet.clone();
+        forged.data.amount = 10_000;
+        let err = bridge.recv_packet(&env.ctx(), forged).unwrap_err();
+        assert_eq!(
+            err.message(),
+            "packet does not match the sender's commitment"
+        );
+
+        let mut wrong_source = packet.clone();
+        wrong_source.source_loom = [9u8; 32];
+        let err = bridge.recv_packet(&env.ctx(), wrong_source).unwrap_err();
+        assert_eq!(
+            err.message(),
+            "packet is not from this channel's counterparty"
+        );
+
+        env.set_timestamp(2_000);
+        let err = bridge.recv_packet(&env.ctx(), packet).unwrap_err();
+        assert_eq!(err.message(), "packet timed out");
+    }
+
+    #[test]
+    fn test_returning_voucher_releases_escrow() {
+        let (env, mut bridge, peer) = setup();
+        send(&env, &mut bridge, token_denom(), 400);
+
+        // The peer minted `transfer/channel-5/<token>` and sends 150 back.
+        let packet = incoming(1, prefix_denom("channel-5", &token_denom()), 150);
+        peer.borrow_mut()
+            .commitments
+            .push((1, packet_commitment(&packet)));
+        let resp = bridge.recv_packet(&env.ctx(), packet).unwrap();
+        assert_attribute(&resp, "denom", &token_denom());
+
+        assert_eq!(env.ctx().balance_of(&BOB, &TOKEN), 150);
+        assert_eq!(ESCROW.load_or(&(String::from("channel-0"), TOKEN), 0), 250);
+    }
+
+    #[test]
+    fn test_returning_more_than_escrowed_fails() {
+        let (env, mut bridge, peer) = setup();
+        send(&env, &mut bridge, token_denom(), 100);
+        let packet = incoming(1, prefix_denom("channel-5", &token_denom()), 101);
+        peer.borrow_mut()
+            .commitments
+            .push((1, packet_commitment(&packet)));
+        assert!(bridge.recv_packet(&env.ctx(), packet).is_err());
+    }
+
+    #[test]
+    fn test_send_voucher_home_burns_it() {
+        let (env, mut bridge, peer) = setup();
+        let packet = incoming(1, token_denom(), 250);
+        peer.borrow_mut()
+            .commitments
+            .push((1, packet_commitment(&packet)));
+        bridge.recv_packet(&env.ctx(), packet).unwrap();
+
+        let voucher = prefix_denom("channel-0", &token_denom());
+        env.set_sender(BOB);
+        bridge
+            .transfer_voucher(&env.ctx(), voucher.clone(), CHARLIE, 50)
+            .unwrap();
+        let out = send(&env, &mut bridge, voucher.clone(), 200);
+        assert_eq!(out.data.denom, voucher);
+        assert_eq!(VOUCHERS.load_or(&(denom_hash(&voucher), BOB), 0), 0);
+        assert_eq!(VOUCHER_SUPPLY.load_or(&denom_hash(&voucher), 0), 50);
+        assert!(env.transfers().is_empty());
+    }
+
+    #[test]
+    fn test_acknowledge_clears_commitment() {
+        let (env, mut bridge, peer) = setup();
+        let packet = send(&env, &mut bridge, token_denom(), 400);
+
+        let err = bridge
+            .acknowledge_packet(&env.ctx(), String::from("channel-0"), 1)
+            .unwrap_err();
+        assert_eq!(err.message(), "packet not received by counterparty");
+
+        peer.borrow_mut().receipts.push(1);
+        bridge
+            .acknowledge_packet(&env.ctx(), String::from("channel-0"), 1)
+            .unwrap();
+        assert!(!COMMITMENTS.has(&(String::from("channel-0"), 1)));
+
+        env.set_timestamp(2_000);
+        let err = bridge.timeout_packet(&env.ctx(), packet).unwrap_err();
+        assert_eq!(err.message(), "no matching pending packet");
+    }
+
+    #[test]
+    fn test_timeout_refunds_sender() {
+        let (env, mut bridge, peer) = setup();
+        let packet = send(&env, &mut bridge, token_denom(), 400);
+
+        let err = bridge
+            .timeout_packet(&env.ctx(), packet.clone())
+            .unwrap_err();
+        assert_eq!(err.message(), "packet has not timed out");
+
+        env.set_timestamp(2_000);
+        peer.borrow_mut().receipts.push(1);
+        let err = bridge
+            .timeout_packet(&env.ctx(), packet.clone())
+            .unwrap_err();
+        assert_eq!(err.message(), "packet was received by counterparty");
+
+        peer.borrow_mut().receipts.clear();
+        let resp = bridge.timeout_packet(&env.ctx(), packet).unwrap();
+        assert_attribute(&resp, "refunded", "400");
+        assert_eq!(env.ctx().balance_of(&ALICE, &TOKEN), 1_000);
+        assert_eq!(ESCROW.load_or(&(String::from("channel-0"), TOKEN), 0), 0);
+    }
+}
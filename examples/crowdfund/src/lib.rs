@@ -1,11 +1,14 @@
 //! Crowdfund — all-or-nothing fundraising with a goal and deadline.
 //! If goal met, creator gets funds. If not, contributors get refunds.
+//! Optional reward tiers pay contributors from reward tokens deposited by the creator.
+//! In milestone mode, raised funds unlock in tranches approved by a contribution-weighted
+//! vote; a rejected milestone lets contributors refund the undisbursed remainder.
 
 #![no_std]
 
 extern crate alloc;
 
-use alloc::format;
+use alloc::{format, vec::Vec};
 use norn_sdk::prelude::*;
 
 // ── Storage ────────────────────────────────────────────────────────────
@@ -15,6 +18,17 @@ const CONFIG: Item<CrowdfundConfig> = Item::new("config");
 const TOTAL_RAISED: Item<u128> = Item::new("total_raised");
 const CONTRIBUTIONS: Map<Address, u128> = Map::new("contributions");
 const CONTRIBUTOR_COUNT: Item<u64> = Item::new("contributor_count");
+const REWARDS: Item<RewardConfig> = Item::new("rewards");
+const REWARD_CLAIMED: Map<Address, bool> = Map::new("reward_claimed");
+
+const MILESTONES: Item<MilestoneState> = Item::new("milestones");
+const MILESTONE_VOTES: Map<(u64, Address), bool> = Map::new("milestone_votes");
+
+const MAX_REWARD_TIERS: usize = 10;
+const MAX_MILESTONES: usize = 10;
+const BPS_DENOMINATOR: u128 = 10_000;
+const MIN_TURNOUT_BPS: u128 = 2_000;
+const MAX_STRETCH_GOALS: usize = 10;
 
 // ── Types ──────────────────────────────────────────────────────────────
 
@@ -37,6 +51,53 @@ pub struct CrowdfundConfig {
     pub created_at: u64,
 }
 
+/// Contributors at or above `threshold` receive `reward_amount` reward tokens.
+#[derive(Debug, BorshSerialize, BorshDeserialize, Clone, PartialEq)]
+pub struct RewardTier {
+    pub threshold: u128,
+    pub reward_amount: u128,
+}
+
+#[derive(Debug, BorshSerialize, BorshDeserialize, Clone, PartialEq)]
+pub struct StretchGoal {
+    pub amount: u128,
+    pub description: String,
+}
+
+#[derive(Debug, BorshSerialize, BorshDeserialize, Clone, PartialEq)]
+pub struct Milestone {
+    pub description: String,
+    pub release_bps: u64, // share of total raised, in basis points
+}
+
+#[derive(Debug, BorshSerialize, BorshDeserialize, Clone, PartialEq)]
+pub struct MilestoneVote {
+    pub milestone: u64,
+    pub ends_at: u64,
+    pub yes_weight: u128,
+    pub no_weight: u128,
+}
+
+#[derive(Debug, BorshSerialize, BorshDeserialize, Clone)]
+pub struct MilestoneState {
+    pub milestones: Vec<Milestone>,
+    pub vote_duration: u64,
+    pub next: u64,     // index of the next milestone to be released
+    pub released: u128, // funds already paid to the creator
+    pub vote: Option<MilestoneVote>,
+    pub rejected: bool,
+}
+
+#[derive(Debug, BorshSerialize, BorshDeserialize, Clone)]
+pub struct RewardConfig {
+    pub reward_token: TokenId,
+    pub tiers: Vec<RewardTier>,        // sorted by ascending threshold
+    pub stretch_goals: Vec<StretchGoal>, // sorted by ascending amount
+    pub deposited: u128,
+    pub distributed: u128,
+    pub owed: u128, // rewards earned by contributors but not yet claimed
+}
+
 // ── Contract ───────────────────────────────────────────────────────────
 
 #[norn_contract]
@@ -98,7 +159,16 @@ impl Crowdfund {
             let count = CONTRIBUTOR_COUNT.load_or(0u64);
             CONTRIBUTOR_COUNT.save(&safe_add_u64(count, 1)?)?;
         }
-        CONTRIBUTIONS.save(&ctx.sender(), &safe_add(existing, amount)?)?;
+        let contribution = safe_add(existing, amount)?;
+        CONTRIBUTIONS.save(&ctx.sender(), &contribution)?;
+        if let Ok(mut rewards) = REWARDS.load() {
+            let earned = safe_sub(
+                reward_for(&rewards, contribution),
+                reward_for(&rewards, existing),
+            )?;
+            rewards.owed = safe_add(rewards.owed, earned)?;
+            REWARDS.save(&rewards)?;
+        }
         let total = TOTAL_RAISED.load_or(0u128);
         TOTAL_RAISED.save(&safe_add(total, amount)?)?;
 
@@ -118,8 +188,10 @@ impl Crowdfund {
         let total = TOTAL_RAISED.load_or(0u128);
 
         if total >= config.goal {
-            // Success — send funds to creator
-            ctx.transfer_from_contract(&config.creator, &config.token_id, total);
+            // Success — send funds to creator, unless they unlock per milestone
+            if !MILESTONES.exists() {
+                ctx.transfer_from_contract(&config.creator, &config.token_id, total);
+            }
             config.status = CampaignStatus::Succeeded;
         } else {
             config.status = CampaignStatus::Failed;
@@ -135,19 +207,335 @@ impl Crowdfund {
     #[execute]
     pub fn refund(&mut self, ctx: &Context) -> ContractResult {
         let config = CONFIG.load()?;
+        let milestones_rejected = MILESTONES.load().map(|m| m.rejected).unwrap_or(false);
         ensure!(
-            config.status == CampaignStatus::Failed,
+            config.status == CampaignStatus::Failed || milestones_rejected,
             "refunds only available for failed campaigns"
         );
 
         let contribution = CONTRIBUTIONS.load(&ctx.sender()).unwrap_or(0u128);
         ensure!(contribution > 0, "no contribution to refund");
 
-        ctx.transfer_from_contract(&ctx.sender(), &config.token_id, contribution);
+        // After a rejected milestone, only the undisbursed share is returned
+        let amount = if config.status == CampaignStatus::Failed {
+            contribution
+        } else {
+            // A refunded contributor can no longer claim their reward
+            if let Ok(mut rewards) = REWARDS.load() {
+                if !REWARD_CLAIMED.load(&ctx.sender()).unwrap_or(false) {
+                    rewards.owed = safe_sub(rewards.owed, reward_for(&rewards, contribution))?;
+                    REWARDS.save(&rewards)?;
+                }
+            }
+            let state = MILESTONES.load()?;
+            let total = TOTAL_RAISED.load_or(0u128);
+            let remaining = safe_sub(total, state.released)?;
+            safe_mul(contribution, remaining)? / total
+        };
+
+        ctx.transfer_from_contract(&ctx.sender(), &config.token_id, amount);
         CONTRIBUTIONS.save(&ctx.sender(), &0u128)?;
 
         Ok(Response::with_action("refund")
-            .add_attribute("amount", format!("{}", contribution)))
+            .add_attribute("amount", format!("{}", amount)))
+    }
+
+    #[execute]
+    pub fn configure_rewards(
+        &mut self,
+        ctx: &Context,
+        reward_token: TokenId,
+        tiers: Vec<RewardTier>,
+        stretch_goals: Vec<StretchGoal>,
+    ) -> ContractResult {
+        let config = CONFIG.load()?;
+        ensure!(
+            ctx.sender() == config.creator,
+            "only creator can configure rewards"
+        );
+        ensure!(config.status == CampaignStatus::Active, "campaign is not active");
+        ensure!(
+            TOTAL_RAISED.load_or(0u128) == 0,
+            "rewards are locked once contributions start"
+        );
+        ensure!(!REWARDS.exists(), "rewards already configured");
+        ensure!(!tiers.is_empty(), "at least one reward tier required");
+        ensure!(tiers.len() <= MAX_REWARD_TIERS, "too many reward tiers (max 10)");
+        ensure!(
+            stretch_goals.len() <= MAX_STRETCH_GOALS,
+            "too many stretch goals (max 10)"
+        );
+
+        let mut prev_threshold = 0u128;
+        for tier in &tiers {
+            ensure!(
+                tier.threshold > prev_threshold,
+                "tier thresholds must be positive and ascending"
+            );
+            ensure!(tier.reward_amount > 0, "reward_amount must be positive");
+            prev_threshold = tier.threshold;
+        }
+
+        let mut prev_amount = config.goal;
+        for goal in &stretch_goals {
+            ensure!(
+                goal.amount > prev_amount,
+                "stretch goals must exceed the goal and be ascending"
+            );
+            ensure!(
+                goal.description.len() <= 128,
+                "stretch goal description too long (max 128)"
+            );
+            prev_amount = goal.amount;
+        }
+
+        let tier_count = tiers.len();
+        REWARDS.save(&RewardConfig {
+            reward_token,
+            tiers,
+            stretch_goals,
+            deposited: 0,
+            distributed: 0,
+            owed: 0,
+        })?;
+
+        Ok(Response::with_action("configure_rewards")
+            .add_attribute("tiers", format!("{}", tier_count)))
+    }
+
+    #[execute]
+    pub fn deposit_rewards(&mut self, ctx: &Context, amount: u128) -> ContractResult {
+        let config = CONFIG.load()?;
+        ensure!(
+            ctx.sender() == config.creator,
+            "only creator can deposit rewards"
+        );
+        ensure!(config.status != CampaignStatus::Failed, "campaign has failed");
+        ensure!(amount > 0, "amount must be positive");
+        let mut rewards = REWARDS.load()?;
+
+        let contract = ctx.contract_address();
+        ctx.transfer(&ctx.sender(), &contract, &rewards.reward_token, amount);
+        rewards.deposited = safe_add(rewards.deposited, amount)?;
+        REWARDS.save(&rewards)?;
+
+        Ok(Response::with_action("deposit_rewards")
+            .add_attribute("amount", format!("{}", amount))
+            .add_attribute("deposited", format!("{}", rewards.deposited)))
+    }
+
+    #[execute]
+    pub fn claim_reward(&mut self, ctx: &Context) -> ContractResult {
+        let config = CONFIG.load()?;
+        ensure!(
+            config.status == CampaignStatus::Succeeded,
+            "rewards only available for successful campaigns"
+        );
+        let mut rewards = REWARDS.load()?;
+
+        let already_claimed = REWARD_CLAIMED.load(&ctx.sender()).unwrap_or(false);
+        ensure!(!already_claimed, "reward already claimed");
+
+        let contribution = CONTRIBUTIONS.load(&ctx.sender()).unwrap_or(0u128);
+        ensure!(contribution > 0, "no contribution found");
+
+        let reward = reward_for(&rewards, contribution);
+        ensure!(reward > 0, "contribution below lowest reward tier");
+
+        let distributed = safe_add(rewards.distributed, reward)?;
+        ensure!(
+            distributed <= rewards.deposited,
+            "insufficient reward tokens deposited"
+        );
+
+        ctx.transfer_from_contract(&ctx.sender(), &rewards.reward_token, reward);
+        REWARD_CLAIMED.save(&ctx.sender(), &true)?;
+        rewards.distributed = distributed;
+        rewards.owed = safe_sub(rewards.owed, reward)?;
+        REWARDS.save(&rewards)?;
+
+        Ok(Response::with_action("claim_reward")
+            .add_attribute("reward", format!("{}", reward)))
+    }
+
+    /// Return reward tokens no contributor is owed to the creator once the
+    /// campaign has ended. A failed campaign owes no rewards at all.
+    #[execute]
+    pub fn reclaim_rewards(&mut self, ctx: &Context) -> ContractResult {
+        let config = CONFIG.load()?;
+        ensure!(
+            ctx.sender() == config.creator,
+            "only creator can reclaim rewards"
+        );
+        ensure!(
+            ctx.timestamp() >= config.deadline,
+            "campaign has not ended yet"
+        );
+        let mut rewards = REWARDS.load()?;
+        let owed = if config.status == CampaignStatus::Failed {
+            0
+        } else {
+            rewards.owed
+        };
+        let remaining = rewards
+            .deposited
+            .saturating_sub(safe_add(rewards.distributed, owed)?);
+        ensure!(remaining > 0, "nothing to reclaim");
+
+        ctx.transfer_from_contract(&config.creator, &rewards.reward_token, remaining);
+        rewards.distributed = safe_add(rewards.distributed, remaining)?;
+        REWARDS.save(&rewards)?;
+
+        Ok(Response::with_action("reclaim_rewards")
+            .add_attribute("amount", format!("{}", remaining)))
+    }
+
+    #[execute]
+    pub fn configure_milestones(
+        &mut self,
+        ctx: &Context,
+        milestones: Vec<Milestone>,
+        vote_duration: u64,
+    ) -> ContractResult {
+        let config = CONFIG.load()?;
+        ensure!(
+            ctx.sender() == config.creator,
+            "only creator can configure milestones"
+        );
+        ensure!(config.status == CampaignStatus::Active, "campaign is not active");
+        ensure!(
+            TOTAL_RAISED.load_or(0u128) == 0,
+            "milestones are locked once contributions start"
+        );
+        ensure!(!MILESTONES.exists(), "milestones already configured");
+        ensure!(!milestones.is_empty(), "at least one milestone required");
+        ensure!(milestones.len() <= MAX_MILESTONES, "too many milestones (max 10)");
+        ensure!(vote_duration > 0, "vote_duration must be positive");
+
+        let mut total_bps = 0u64;
+        for m in &milestones {
+            ensure!(m.release_bps > 0, "milestone release must be positive");
+            ensure!(
+                m.description.len() <= 128,
+                "milestone description too long (max 128)"
+            );
+            total_bps = safe_add_u64(total_bps, m.release_bps)?;
+        }
+        ensure!(
+            total_bps as u128 == BPS_DENOMINATOR,
+            "milestone releases must sum to 10000 bps"
+        );
+
+        let count = milestones.len();
+        MILESTONES.save(&MilestoneState {
+            milestones,
+            vote_duration,
+            next: 0,
+            released: 0,
+            vote: None,
+            rejected: false,
+        })?;
+
+        Ok(Response::with_action("configure_milestones")
+            .add_attribute("milestones", format!("{}", count)))
+    }
+
+    #[execute]
+    pub fn request_milestone(&mut self, ctx: &Context) -> ContractResult {
+        let config = CONFIG.load()?;
+        ensure!(
+            ctx.sender() == config.creator,
+            "only creator can request a milestone"
+        );
+        ensure!(
+            config.status == CampaignStatus::Succeeded,
+            "campaign has not succeeded"
+        );
+        let mut state = MILESTONES.load()?;
+        ensure!(!state.rejected, "milestones were rejected");
+        ensure!(state.vote.is_none(), "milestone vote already open");
+        ensure!(
+            (state.next as usize) < state.milestones.len(),
+            "all milestones released"
+        );
+
+        let ends_at = safe_add_u64(ctx.timestamp(), state.vote_duration)?;
+        state.vote = Some(MilestoneVote {
+            milestone: state.next,
+            ends_at,
+            yes_weight: 0,
+            no_weight: 0,
+        });
+        let milestone = state.next;
+        MILESTONES.save(&state)?;
+
+        Ok(Response::with_action("request_milestone")
+            .add_attribute("milestone", format!("{}", milestone))
+            .add_attribute("ends_at", format!("{}", ends_at)))
+    }
+
+    #[execute]
+    pub fn vote_milestone(&mut self, ctx: &Context, approve: bool) -> ContractResult {
+        let mut state = MILESTONES.load()?;
+        let mut vote = state.vote.clone().ok_or("no milestone vote open")?;
+        ensure!(ctx.timestamp() < vote.ends_at, "voting period has ended");
+
+        let weight = CONTRIBUTIONS.load(&ctx.sender()).unwrap_or(0u128);
+        ensure!(weight > 0, "only contributors can vote");
+
+        let key = (vote.milestone, ctx.sender());
+        ensure!(!MILESTONE_VOTES.has(&key), "already voted");
+        MILESTONE_VOTES.save(&key, &approve)?;
+
+        if approve {
+            vote.yes_weight = safe_add(vote.yes_weight, weight)?;
+        } else {
+            vote.no_weight = safe_add(vote.no_weight, weight)?;
+        }
+        state.vote = Some(vote);
+        MILESTONES.save(&state)?;
+
+        Ok(Response::with_action("vote_milestone")
+            .add_attribute("approve", if approve { "true" } else { "false" })
+            .add_attribute("weight", format!("{}", weight)))
+    }
+
+    /// Close the open milestone vote. Approval (yes weight > no weight, with at
+    /// least 20% of raised funds voting) releases the tranche; otherwise the
+    /// milestone is rejected and refunds of the remainder open.
+    #[execute]
+    pub fn resolve_milestone(&mut self, ctx: &Context) -> ContractResult {
+        let config = CONFIG.load()?;
+        let mut state = MILESTONES.load()?;
+        let vote = state.vote.take().ok_or("no milestone vote open")?;
+        ensure!(ctx.timestamp() >= vote.ends_at, "voting period has not ended");
+
+        let total = TOTAL_RAISED.load_or(0u128);
+        let turnout = safe_add(vote.yes_weight, vote.no_weight)?;
+        let quorum = safe_mul(total, MIN_TURNOUT_BPS)? / BPS_DENOMINATOR;
+        let approved = vote.yes_weight > vote.no_weight && turnout >= quorum;
+        let mut released = 0u128;
+        if approved {
+            let is_last = (vote.milestone as usize) + 1 == state.milestones.len();
+            // The last tranche takes any rounding remainder
+            released = if is_last {
+                safe_sub(total, state.released)?
+            } else {
+                let bps = state.milestones[vote.milestone as usize].release_bps as u128;
+                safe_mul(total, bps)? / BPS_DENOMINATOR
+            };
+            ctx.transfer_from_contract(&config.creator, &config.token_id, released);
+            state.released = safe_add(state.released, released)?;
+            state.next = safe_add_u64(state.next, 1)?;
+        } else {
+            state.rejected = true;
+        }
+        MILESTONES.save(&state)?;
+
+        Ok(Response::with_action("resolve_milestone")
+            .add_attribute("milestone", format!("{}", vote.milestone))
+            .add_attribute("approved", if approved { "true" } else { "false" })
+            .add_attribute("released", format!("{}", released)))
     }
 
     #[query]
@@ -173,6 +561,55 @@ impl Crowdfund {
         let count = CONTRIBUTOR_COUNT.load_or(0u64);
         ok(count)
     }
+
+    #[query]
+    pub fn get_milestones(&self, _ctx: &Context) -> ContractResult {
+        let state = MILESTONES.load()?;
+        ok(state)
+    }
+
+    #[query]
+    pub fn get_rewards(&self, _ctx: &Context) -> ContractResult {
+        let rewards = REWARDS.load()?;
+        ok(rewards)
+    }
+
+    #[query]
+    pub fn get_reward(&self, _ctx: &Context, addr: Address) -> ContractResult {
+        let contribution = CONTRIBUTIONS.load(&addr).unwrap_or(0u128);
+        let reward = match REWARDS.load() {
+            Ok(rewards) => reward_for(&rewards, contribution),
+            Err(_) => 0,
+        };
+        ok(reward)
+    }
+
+    #[query]
+    pub fn get_stretch_goals_reached(&self, _ctx: &Context) -> ContractResult {
+        let total = TOTAL_RAISED.load_or(0u128);
+        let reached = match REWARDS.load() {
+            Ok(rewards) => rewards
+                .stretch_goals
+                .iter()
+                .filter(|g| total >= g.amount)
+                .count() as u64,
+            Err(_) => 0,
+        };
+        ok(reached)
+    }
+}
+
+// ── Helpers ────────────────────────────────────────────────────────────
+
+/// Reward of the highest tier whose threshold `contribution` meets.
+fn reward_for(rewards: &RewardConfig, contribution: u128) -> u128 {
+    rewards
+        .tiers
+        .iter()
+        .rev()
+        .find(|t| contribution >= t.threshold)
+        .map(|t| t.reward_amount)
+        .unwrap_or(0)
 }
 
 // ── Tests ──────────────────────────────────────────────────────────────
@@ -183,6 +620,7 @@ mod tests {
     use norn_sdk::testing::*;
 
     const TOKEN: TokenId = [42u8; 32];
+    const REWARD_TOKEN: TokenId = [7u8; 32];
     const CONTRACT_ADDR: Address = [99u8; 20];
 
     fn setup() -> (TestEnv, Crowdfund) {
@@ -325,4 +763,347 @@ mod tests {
         let count: u64 = from_response(&resp).unwrap();
         assert_eq!(count, 2);
     }
+
+    fn setup_rewards(env: &TestEnv, cf: &mut Crowdfund) {
+        cf.configure_rewards(
+            &env.ctx(),
+            REWARD_TOKEN,
+            alloc::vec![
+                RewardTier { threshold: 1_000, reward_amount: 10 },
+                RewardTier { threshold: 5_000, reward_amount: 100 },
+            ],
+            alloc::vec![StretchGoal {
+                amount: 15_000,
+                description: "Add a bike lane".into(),
+            }],
+        )
+        .unwrap();
+        cf.deposit_rewards(&env.ctx(), 110).unwrap();
+    }
+
+    #[test]
+    fn test_claim_reward_by_tier() {
+        let (env, mut cf) = setup();
+        setup_rewards(&env, &mut cf);
+
+        env.set_timestamp(1500);
+        env.set_sender(BOB);
+        cf.contribute(&env.ctx(), 6_000).unwrap();
+        env.set_sender(CHARLIE);
+        cf.contribute(&env.ctx(), 4_000).unwrap();
+
+        env.set_sender(ALICE);
+        env.set_timestamp(2500);
+        cf.finalize(&env.ctx()).unwrap();
+
+        env.set_sender(BOB);
+        let resp = cf.claim_reward(&env.ctx()).unwrap();
+        assert_attribute(&resp, "reward", "100");
+        let transfers = env.transfers();
+        assert_eq!(transfers.last().unwrap().1, BOB.to_vec());
+        assert_eq!(transfers.last().unwrap().2, REWARD_TOKEN.to_vec());
+
+        env.set_sender(CHARLIE);
+        let resp = cf.claim_reward(&env.ctx()).unwrap();
+        assert_attribute(&resp, "reward", "10");
+
+        let err = cf.claim_reward(&env.ctx()).unwrap_err();
+        assert_err_contains(&err, "reward already claimed");
+    }
+
+    #[test]
+    fn test_claim_reward_below_lowest_tier() {
+        let (env, mut cf) = setup();
+        setup_rewards(&env, &mut cf);
+
+        env.set_timestamp(1500);
+        env.set_sender(BOB);
+        cf.contribute(&env.ctx(), 9_500).unwrap();
+        env.set_sender(CHARLIE);
+        cf.contribute(&env.ctx(), 500).unwrap();
+
+        env.set_sender(ALICE);
+        env.set_timestamp(2500);
+        cf.finalize(&env.ctx()).unwrap();
+
+        env.set_sender(CHARLIE);
+        let err = cf.claim_reward(&env.ctx()).unwrap_err();
+        assert_err_contains(&err, "contribution below lowest reward tier");
+    }
+
+    #[test]
+    fn test_claim_reward_requires_success() {
+        let (env, mut cf) = setup();
+        setup_rewards(&env, &mut cf);
+
+        env.set_timestamp(1500);
+        env.set_sender(BOB);
+        cf.contribute(&env.ctx(), 5_000).unwrap();
+
+        env.set_timestamp(2500);
+        cf.finalize(&env.ctx()).unwrap();
+
+        let err = cf.claim_reward(&env.ctx()).unwrap_err();
+        assert_err_contains(&err, "rewards only available for successful campaigns");
+
+        // Creator takes the reward tokens back
+        env.set_sender(ALICE);
+        let resp = cf.reclaim_rewards(&env.ctx()).unwrap();
+        assert_attribute(&resp, "amount", "110");
+    }
+
+    #[test]
+    fn test_claim_reward_insufficient_deposit() {
+        let (env, mut cf) = setup();
+        setup_rewards(&env, &mut cf);
+
+        env.set_timestamp(1500);
+        for who in [BOB, CHARLIE] {
+            env.set_sender(who);
+            cf.contribute(&env.ctx(), 5_000).unwrap();
+        }
+
+        env.set_timestamp(2500);
+        cf.finalize(&env.ctx()).unwrap();
+
+        env.set_sender(BOB);
+        cf.claim_reward(&env.ctx()).unwrap();
+        env.set_sender(CHARLIE);
+        let err = cf.claim_reward(&env.ctx()).unwrap_err();
+        assert_err_contains(&err, "insufficient reward tokens deposited");
+    }
+
+    #[test]
+    fn test_reclaim_unowed_rewards_after_success() {
+        let (env, mut cf) = setup();
+        setup_rewards(&env, &mut cf);
+        cf.deposit_rewards(&env.ctx(), 50).unwrap();
+
+        env.set_timestamp(1500);
+        env.set_sender(BOB);
+        cf.contribute(&env.ctx(), 6_000).unwrap();
+        env.set_sender(CHARLIE);
+        cf.contribute(&env.ctx(), 4_000).unwrap();
+
+        env.set_sender(ALICE);
+        let err = cf.reclaim_rewards(&env.ctx()).unwrap_err();
+        assert_err_contains(&err, "campaign has not ended yet");
+
+        // 160 deposited, BOB is owed 100 and CHARLIE 10
+        env.set_timestamp(2500);
+        cf.finalize(&env.ctx()).unwrap();
+        let resp = cf.reclaim_rewards(&env.ctx()).unwrap();
+        assert_attribute(&resp, "amount", "50");
+        let err = cf.reclaim_rewards(&env.ctx()).unwrap_err();
+        assert_err_contains(&err, "nothing to reclaim");
+
+        env.set_sender(BOB);
+        let resp = cf.claim_reward(&env.ctx()).unwrap();
+        assert_attribute(&resp, "reward", "100");
+        env.set_sender(CHARLIE);
+        let resp = cf.claim_reward(&env.ctx()).unwrap();
+        assert_attribute(&resp, "reward", "10");
+    }
+
+    #[test]
+    fn test_reward_tiers_must_ascend() {
+        let (env, mut cf) = setup();
+        let err = cf
+            .configure_rewards(
+                &env.ctx(),
+                REWARD_TOKEN,
+                alloc::vec![
+                    RewardTier { threshold: 5_000, reward_amount: 100 },
+                    RewardTier { threshold: 1_000, reward_amount: 10 },
+                ],
+                alloc::vec![],
+            )
+            .unwrap_err();
+        assert_err_contains(&err, "tier thresholds must be positive and ascending");
+    }
+
+    #[test]
+    fn test_rewards_locked_after_contributions() {
+        let (env, mut cf) = setup();
+        env.set_timestamp(1500);
+        env.set_sender(BOB);
+        cf.contribute(&env.ctx(), 1_000).unwrap();
+
+        env.set_sender(ALICE);
+        let err = cf
+            .configure_rewards(
+                &env.ctx(),
+                REWARD_TOKEN,
+                alloc::vec![RewardTier { threshold: 1, reward_amount: 1 }],
+                alloc::vec![],
+            )
+            .unwrap_err();
+        assert_err_contains(&err, "rewards are locked once contributions start");
+    }
+
+    #[test]
+    fn test_stretch_goals_reached() {
+        let (env, mut cf) = setup();
+        setup_rewards(&env, &mut cf);
+
+        env.set_timestamp(1500);
+        env.set_sender(BOB);
+        cf.contribute(&env.ctx(), 12_000).unwrap();
+        let reached: u64 =
+            from_response(&cf.get_stretch_goals_reached(&env.ctx()).unwrap()).unwrap();
+        assert_eq!(reached, 0);
+
+        cf.contribute(&env.ctx(), 3_000).unwrap();
+        let reached: u64 =
+            from_response(&cf.get_stretch_goals_reached(&env.ctx()).unwrap()).unwrap();
+        assert_eq!(reached, 1);
+    }
+
+    fn setup_milestones(env: &TestEnv, cf: &mut Crowdfund) {
+        cf.configure_milestones(
+            &env.ctx(),
+            alloc::vec![
+                Milestone { description: "Design".into(), release_bps: 3_000 },
+                Milestone { description: "Build".into(), release_bps: 7_000 },
+            ],
+            100,
+        )
+        .unwrap();
+
+        env.set_timestamp(1500);
+        env.set_sender(BOB);
+        cf.contribute(&env.ctx(), 6_000).unwrap();
+        env.set_sender(CHARLIE);
+        cf.contribute(&env.ctx(), 4_000).unwrap();
+
+        env.set_sender(ALICE);
+        env.set_timestamp(2500);
+        env.clear_transfers();
+        cf.finalize(&env.ctx()).unwrap();
+    }
+
+    #[test]
+    fn test_milestone_mode_holds_funds() {
+        let (env, mut cf) = setup();
+        setup_milestones(&env, &mut cf);
+        assert!(env.transfers().is_empty());
+    }
+
+    #[test]
+    fn test_milestones_released_on_approval() {
+        let (env, mut cf) = setup();
+        setup_milestones(&env, &mut cf);
+
+        cf.request_milestone(&env.ctx()).unwrap();
+        env.set_sender(BOB);
+        cf.vote_milestone(&env.ctx(), true).unwrap();
+        env.set_sender(CHARLIE);
+        cf.vote_milestone(&env.ctx(), false).unwrap();
+
+        env.set_timestamp(2600);
+        let resp = cf.resolve_milestone(&env.ctx()).unwrap();
+        assert_attribute(&resp, "approved", "true");
+        assert_attribute(&resp, "released", "3000");
+
+        env.set_sender(ALICE);
+        cf.request_milestone(&env.ctx()).unwrap();
+        env.set_sender(CHARLIE);
+        cf.vote_milestone(&env.ctx(), true).unwrap();
+        env.set_timestamp(2700);
+        let resp = cf.resolve_milestone(&env.ctx()).unwrap();
+        assert_attribute(&resp, "released", "7000");
+
+        env.set_sender(ALICE);
+        let err = cf.request_milestone(&env.ctx()).unwrap_err();
+        assert_err_contains(&err, "all milestones released");
+    }
+
+    #[test]
+    fn test_rejected_milestone_refunds_remainder() {
+        let (env, mut cf) = setup();
+        setup_milestones(&env, &mut cf);
+
+        cf.request_milestone(&env.ctx()).unwrap();
+        env.set_sender(BOB);
+        cf.vote_milestone(&env.ctx(), true).unwrap();
+        env.set_timestamp(2600);
+        cf.resolve_milestone(&env.ctx()).unwrap();
+
+        env.set_sender(ALICE);
+        cf.request_milestone(&env.ctx()).unwrap();
+        env.set_sender(BOB);
+        cf.vote_milestone(&env.ctx(), false).unwrap();
+        env.set_timestamp(2700);
+        let resp = cf.resolve_milestone(&env.ctx()).unwrap();
+        assert_attribute(&resp, "approved", "false");
+
+        // 7_000 undisbursed, BOB holds 60% of contributions
+        let resp = cf.refund(&env.ctx()).unwrap();
+        assert_attribute(&resp, "amount", "4200");
+        env.set_sender(CHARLIE);
+        let resp = cf.refund(&env.ctx()).unwrap();
+        assert_attribute(&resp, "amount", "2800");
+
+        env.set_sender(ALICE);
+        let err = cf.request_milestone(&env.ctx()).unwrap_err();
+        assert_err_contains(&err, "milestones were rejected");
+    }
+
+    #[test]
+    fn test_milestone_without_votes_is_rejected() {
+        let (env, mut cf) = setup();
+        setup_milestones(&env, &mut cf);
+
+        cf.request_milestone(&env.ctx()).unwrap();
+        env.set_timestamp(2600);
+        env.clear_transfers();
+        let resp = cf.resolve_milestone(&env.ctx()).unwrap();
+        assert_attribute(&resp, "approved", "false");
+        assert_attribute(&resp, "released", "0");
+        assert!(env.transfers().is_empty());
+
+        let err = cf.request_milestone(&env.ctx()).unwrap_err();
+        assert_err_contains(&err, "milestones were rejected");
+    }
+
+    #[test]
+    fn test_milestone_vote_rules() {
+        let (env, mut cf) = setup();
+        setup_milestones(&env, &mut cf);
+
+        env.set_sender(BOB);
+        let err = cf.vote_milestone(&env.ctx(), true).unwrap_err();
+        assert_err_contains(&err, "no milestone vote open");
+
+        env.set_sender(ALICE);
+        cf.request_milestone(&env.ctx()).unwrap();
+        let err = cf.vote_milestone(&env.ctx(), true).unwrap_err();
+        assert_err_contains(&err, "only contributors can vote");
+
+        env.set_sender(BOB);
+        cf.vote_milestone(&env.ctx(), true).unwrap();
+        let err = cf.vote_milestone(&env.ctx(), true).unwrap_err();
+        assert_err_contains(&err, "already voted");
+
+        let err = cf.resolve_milestone(&env.ctx()).unwrap_err();
+        assert_err_contains(&err, "voting period has not ended");
+
+        env.set_timestamp(2600);
+        env.set_sender(CHARLIE);
+        let err = cf.vote_milestone(&env.ctx(), false).unwrap_err();
+        assert_err_contains(&err, "voting period has ended");
+    }
+
+    #[test]
+    fn test_milestones_must_sum_to_full_release() {
+        let (env, mut cf) = setup();
+        let err = cf
+            .configure_milestones(
+                &env.ctx(),
+                alloc::vec![Milestone { description: "Half".into(), release_bps: 5_000 }],
+                100,
+            )
+            .unwrap_err();
+        assert_err_contains(&err, "milestone releases must sum to 10000 bps");
+    }
 }
@@ -1,5 +1,8 @@
 //! P2P Escrow contract — demonstrates contract-derived addresses for
 //! token custody, state machines, and role-based access control.
+//!
+//! Each deal is custodied under its own sub-address derived from the deal ID,
+//! so funds for one deal can be audited independently of the others.
 
 #![no_std]
 
@@ -97,9 +100,9 @@ impl Escrow {
         );
         ensure!(deal.buyer == ctx.sender(), "only buyer can fund");
 
-        // Transfer tokens from buyer to contract address.
-        let contract = ctx.contract_address();
-        ctx.transfer(&ctx.sender(), &contract, &deal.token_id, deal.amount);
+        // Transfer tokens from buyer to the deal's custody address.
+        let custody = ctx.derive_address(&deal_salt(deal_id));
+        ctx.transfer(&ctx.sender(), &custody, &deal.token_id, deal.amount);
 
         deal.status = DealStatus::Funded;
         deal.funded_at = ctx.timestamp();
@@ -139,7 +142,12 @@ impl Escrow {
         ensure!(deal.buyer == ctx.sender(), "only buyer can confirm");
 
         // Release funds to seller.
-        ctx.transfer_from_contract(&deal.seller, &deal.token_id, deal.amount);
+        ctx.transfer_from_sub_address(
+            &deal_salt(deal_id),
+            &deal.seller,
+            &deal.token_id,
+            deal.amount,
+        );
 
         deal.status = DealStatus::Completed;
         DEALS.save(&deal_id, &deal)?;
@@ -193,7 +201,12 @@ impl Escrow {
         );
 
         // Refund tokens to buyer.
-        ctx.transfer_from_contract(&deal.buyer, &deal.token_id, deal.amount);
+        ctx.transfer_from_sub_address(
+            &deal_salt(deal_id),
+            &deal.buyer,
+            &deal.token_id,
+            deal.amount,
+        );
 
         let mut deal = deal;
         deal.status = DealStatus::Refunded;
@@ -216,6 +229,17 @@ impl Escrow {
         let count = DEAL_COUNT.load_or(0u64);
         ok(count)
     }
+
+    /// Address holding the funds of a deal.
+    #[query]
+    pub fn get_deal_address(&self, ctx: &Context, deal_id: u64) -> ContractResult {
+        DEALS.load(&deal_id)?;
+        ok(ctx.derive_address(&deal_salt(deal_id)))
+    }
+}
+
+fn deal_salt(deal_id: u64) -> [u8; 8] {
+    deal_id.to_le_bytes()
 }
 
 // ── Tests ───────────────────────────────────────────────────────────────
@@ -251,6 +275,10 @@ mod tests {
         from_response::<u64>(&resp).unwrap()
     }
 
+    fn deal_address(env: &TestEnv, escrow: &Escrow, deal_id: u64) -> Address {
+        from_response(&escrow.get_deal_address(&env.ctx(), deal_id).unwrap()).unwrap()
+    }
+
     #[test]
     fn test_create_deal() {
         let (env, mut escrow) = setup();
@@ -281,7 +309,7 @@ mod tests {
         let transfers = env.transfers();
         assert_eq!(transfers.len(), 1);
         assert_eq!(transfers[0].0, ALICE.to_vec());
-        assert_eq!(transfers[0].1, CONTRACT_ADDR.to_vec());
+        assert_eq!(transfers[0].1, deal_address(&env, &escrow, 0).to_vec());
         assert_eq!(transfers[0].3, 500);
     }
 
@@ -308,7 +336,7 @@ mod tests {
         // Verify transfers: fund(buyer->contract) + release(contract->seller)
         let transfers = env.transfers();
         assert_eq!(transfers.len(), 2);
-        assert_eq!(transfers[1].0, CONTRACT_ADDR.to_vec());
+        assert_eq!(transfers[1].0, deal_address(&env, &escrow, 0).to_vec());
         assert_eq!(transfers[1].1, BOB.to_vec());
         assert_eq!(transfers[1].3, 500);
     }
@@ -365,7 +393,7 @@ mod tests {
         // Verify refund transfer: contract -> buyer
         let transfers = env.transfers();
         assert_eq!(transfers.len(), 2);
-        assert_eq!(transfers[1].0, CONTRACT_ADDR.to_vec());
+        assert_eq!(transfers[1].0, deal_address(&env, &escrow, 0).to_vec());
         assert_eq!(transfers[1].1, ALICE.to_vec());
     }
 
@@ -403,6 +431,25 @@ mod tests {
         assert_err_contains(&err, "only seller can mark delivered");
     }
 
+    #[test]
+    fn test_each_deal_has_own_custody_address() {
+        let (env, mut escrow) = setup();
+        create_deal(&env, &mut escrow);
+        create_deal(&env, &mut escrow);
+        escrow.fund_deal(&env.ctx(), 0).unwrap();
+        escrow.fund_deal(&env.ctx(), 1).unwrap();
+
+        let first = deal_address(&env, &escrow, 0);
+        let second = deal_address(&env, &escrow, 1);
+        assert_ne!(first, second);
+        assert_ne!(first, CONTRACT_ADDR);
+        assert_eq!(env.ctx().balance_of(&first, &TOKEN), 500);
+        assert_eq!(env.ctx().balance_of(&second, &TOKEN), 500);
+
+        let err = escrow.get_deal_address(&env.ctx(), 7).unwrap_err();
+        assert!(matches!(err, ContractError::NotFound(_)));
+    }
+
     #[test]
     fn test_query_deal_count() {
         let (env, mut escrow) = setup();
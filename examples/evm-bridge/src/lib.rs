@@ -0,0 +1,532 @@
+//! EVM bridge — wrapped EVM tokens minted by an attestation committee.
+//!
+//! The loom side of the `norn-node` bridge watcher. A lock contract on an EVM
+//! chain holds deposited tokens and emits `Locked` events. Each member of an
+//! M-of-N committee runs a watcher that reports every confirmed lock here with
+//! `attest_mint`; once `threshold` members report the same instruction, the
+//! recipient is credited with the wrapped token.
+//!
+//! The reverse flow starts with `burn`, which destroys wrapped tokens and
+//! emits `BridgeBurn`. Watchers sign a release for the burn with their EVM keys
+//! and post the signatures with `attest_release`. Anyone can read `threshold`
+//! signatures from `get_burn` and submit them to the lock contract, which
+//! checks them with `ecrecover` and pays out.
+//!
+//! The committee is fixed at deployment; rotating it means deploying a new
+//! bridge loom.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use norn_sdk::prelude::*;
+
+/// A 20-byte address on the EVM chain.
+pub type EvmAddress = [u8; 20];
+
+/// Length of an EVM `r || s || v` signature.
+const EVM_SIGNATURE_LEN: usize = 65;
+
+// ── Storage ──────────────────────────────────────────────────────────────
+
+const INFO: Item<BridgeInfo> = Item::new("info");
+/// Mint attestations keyed by the lock contract's nonce.
+const MINTS: Map<u64, MintRecord> = Map::new("mints");
+const BURNS: Map<u64, BurnRecord> = Map::new("burns");
+const NEXT_BURN: Item<u64> = Item::new("next_burn");
+const BALANCES: Map<(EvmAddress, Address), u128> = Map::new("balances");
+const SUPPLY: Map<EvmAddress, u128> = Map::new("supply");
+
+// ── Types ────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct BridgeInfo {
+    pub chain_id: u64,
+    pub lock_contract: EvmAddress,
+    pub committee: Vec<Address>,
+    pub threshold: u16,
+}
+
+/// A confirmed lock on the EVM chain, as reported by a watcher.
+///
+/// Mirrors `norn_node::bridge::instruction::MintInstruction`.
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct MintInstruction {
+    pub chain_id: u64,
+    pub lock_contract: EvmAddress,
+    pub lock_nonce: u64,
+    pub tx_hash: [u8; 32],
+    pub evm_token: EvmAddress,
+    pub amount: u128,
+    pub recipient: Address,
+}
+
+/// A version of a lock's instruction and the members who attested it.
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct MintCandidate {
+    pub instruction: MintInstruction,
+    pub attesters: Vec<Address>,
+}
+
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct MintRecord {
+    /// Distinct instructions reported for the lock. Honest watchers agree, so
+    /// there is normally one.
+    pub candidates: Vec<MintCandidate>,
+    pub minted: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct ReleaseSignature {
+    pub attester: Address,
+    pub signature: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct BurnRecord {
+    pub burner: Address,
+    pub evm_token: EvmAddress,
+    pub amount: u128,
+    pub evm_recipient: EvmAddress,
+    pub signatures: Vec<ReleaseSignature>,
+}
+
+// ── Contract ─────────────────────────────────────────────────────────────
+
+#[norn_contract]
+pub struct EvmBridge;
+
+#[norn_contract]
+impl EvmBridge {
+    #[init]
+    pub fn new(
+        _ctx: &Context,
+        chain_id: u64,
+        lock_contract: EvmAddress,
+        committee: Vec<Address>,
+        threshold: u16,
+    ) -> Self {
+        assert!(threshold > 0, "threshold must be positive");
+        assert!(
+            threshold as usize <= committee.len(),
+            "threshold exceeds committee size"
+        );
+        let mut unique = committee.clone();
+        unique.sort();
+        unique.dedup();
+        assert!(
+            unique.len() == committee.len(),
+            "duplicate committee member"
+        );
+        INFO.init(&BridgeInfo {
+            chain_id,
+            lock_contract,
+            committee,
+            threshold,
+        });
+        NEXT_BURN.init(&1);
+        EvmBridge
+    }
+
+    // ── Execute ──────────────────────────────────────────────────────
+
+    /// Report a confirmed lock (committee only). Mints once `threshold`
+    /// members have attested the same instruction.
+    #[execute]
+    pub fn attest_mint(&mut self, ctx: &Context, instruction: MintInstruction) -> ContractResult {
+        let info = require_member(ctx)?;
+        ensure!(
+            instruction.chain_id == info.chain_id
+                && instruction.lock_contract == info.lock_contract,
+            "instruction is for another lock contract"
+        );
+        ensure!(instruction.amount > 0, "amount must be positive");
+
+        let sender = ctx.sender();
+        let nonce = instruction.lock_nonce;
+        let mut record = MINTS.load(&nonce).unwrap_or(MintRecord {
+            candidates: Vec::new(),
+            minted: false,
+        });
+        ensure!(!record.minted, "lock already minted");
+        ensure!(
+            !record
+                .candidates
+                .iter()
+                .any(|c| c.attesters.contains(&sender)),
+            "lock already attested"
+        );
+
+        let idx = match record
+            .candidates
+            .iter()
+            .position(|c| c.instruction == instruction)
+        {
+            Some(idx) => idx,
+            None => {
+                record.candidates.push(MintCandidate {
+                    instruction: instruction.clone(),
+                    attesters: Vec::new(),
+                });
+                record.candidates.len() - 1
+            }
+        };
+        record.candidates[idx].attesters.push(sender);
+        let count = record.candidates[idx].attesters.len();
+
+        let mut resp = Response::with_action("attest_mint")
+            .add_u128("lock_nonce", nonce as u128)
+            .add_event(
+                Event::new("MintAttested")
+                    .add_u128("lock_nonce", nonce as u128)
+                    .add_address("attester", &sender)
+                    .add_u128("count", count as u128),
+            );
+        if count >= info.threshold as usize {
+            record.minted = true;
+            let token = instruction.evm_token;
+            let key = (token, instruction.recipient);
+            BALANCES.save(
+                &key,
+                &safe_add(BALANCES.load_or(&key, 0), instruction.amount)?,
+            )?;
+            SUPPLY.save(
+                &token,
+                &safe_add(SUPPLY.load_or(&token, 0), instruction.amount)?,
+            )?;
+            resp = resp.add_event(
+                Event::new("BridgeMint")
+                    .add_u128("lock_nonce", nonce as u128)
+                    .add_address("evm_token", &token)
+                    .add_address("recipient", &instruction.recipient)
+                    .add_u128("amount", instruction.amount),
+            );
+        }
+        MINTS.save(&nonce, &record)?;
+        Ok(resp)
+    }
+
+    /// Burn wrapped tokens to release them to `evm_recipient` on the EVM
+    /// chain. Returns the burn nonce.
+    #[execute]
+    pub fn burn(
+        &mut self,
+        ctx: &Context,
+        evm_token: EvmAddress,
+        amount: u128,
+        evm_recipient: EvmAddress,
+    ) -> ContractResult {
+        ensure!(amount > 0, "amount must be positive");
+        ensure!(evm_recipient != ZERO_ADDRESS, "invalid EVM recipient");
+        let burner = ctx.sender();
+        let key = (evm_token, burner);
+        let balance = BALANCES.load_or(&key, 0);
+        ensure!(amount <= balance, ContractError::InsufficientFunds);
+        BALANCES.save(&key, &(balance - amount))?;
+        SUPPLY.save(
+            &evm_token,
+            &safe_sub(SUPPLY.load_or(&evm_token, 0), amount)?,
+        )?;
+
+        let nonce = NEXT_BURN.load()?;
+        NEXT_BURN.save(&(nonce + 1))?;
+        BURNS.save(
+            &nonce,
+            &BurnRecord {
+                burner,
+                evm_token,
+                amount,
+                evm_recipient,
+                signatures: Vec::new(),
+            },
+        )?;
+
+        Ok(Response::with_action("burn")
+            .add_event(
+                Event::new("BridgeBurn")
+                    .add_u128("nonce", nonce as u128)
+                    .add_address("burner", &burner)
+                    .add_address("evm_token", &evm_token)
+                    .add_u128("amount", amount)
+                    .add_address("evm_recipient", &evm_recipient),
+            )
+            .set_data(&nonce))
+    }
+
+    /// Post this member's EVM signature releasing burn `nonce` (committee
+    /// only). Signatures are checked by the lock contract, not here.
+    #[execute]
+    pub fn attest_release(
+        &mut self,
+        ctx: &Context,
+        nonce: u64,
+        signature: Vec<u8>,
+    ) -> ContractResult {
+        let info = require_member(ctx)?;
+        ensure!(
+            signature.len() == EVM_SIGNATURE_LEN,
+            "signature must be 65 bytes"
+        );
+        let mut record = BURNS
+            .load(&nonce)
+            .map_err(|_| ContractError::Custom(format!("unknown burn {nonce}")))?;
+        let sender = ctx.sender();
+        ensure!(
+            !record.signatures.iter().any(|s| s.attester == sender),
+            "release already attested"
+        );
+        record.signatures.push(ReleaseSignature {
+            attester: sender,
+            signature,
+        });
+        let count = record.signatures.len();
+        BURNS.save(&nonce, &record)?;
+
+        Ok(Response::with_action("attest_release").add_event(
+            Event::new("ReleaseAttested")
+                .add_u128("nonce", nonce as u128)
+                .add_address("attester", &sender)
+                .add_u128("count", count as u128)
+                .add_attribute(
+                    "ready",
+                    if count >= info.threshold as usize {
+                        "true"
+                    } else {
+                        "false"
+                    },
+                ),
+        ))
+    }
+
+    #[execute]
+    pub fn transfer(
+        &mut self,
+        ctx: &Context,
+        evm_token: EvmAddress,
+        to: Address,
+        amount: u128,
+    ) -> ContractResult {
+        ensure!(amount > 0, "amount must be positive");
+        let sender = ctx.sender();
+        ensure_ne!(sender, to, "cannot transfer to self");
+        let from_bal = BALANCES.load_or(&(evm_token, sender), 0);
+        ensure!(amount <= from_bal, ContractError::InsufficientFunds);
+        let to_bal = BALANCES.load_or(&(evm_token, to), 0);
+        BALANCES.save(&(evm_token, sender), &(from_bal - amount))?;
+        BALANCES.save(&(evm_token, to), &safe_add(to_bal, amount)?)?;
+        Ok(Response::with_action("transfer")
+            .add_address("evm_token", &evm_token)
+            .add_address("to", &to)
+            .add_u128("amount", amount))
+    }
+
+    // ── Query ────────────────────────────────────────────────────────
+
+    #[query]
+    pub fn balance(&self, _ctx: &Context, evm_token: EvmAddress, addr: Address) -> ContractResult {
+        ok(BALANCES.load_or(&(evm_token, addr), 0))
+    }
+
+    #[query]
+    pub fn total_supply(&self, _ctx: &Context, evm_token: EvmAddress) -> ContractResult {
+        ok(SUPPLY.load_or(&evm_token, 0))
+    }
+
+    #[query]
+    pub fn get_mint(&self, _ctx: &Context, lock_nonce: u64) -> ContractResult {
+        ok(MINTS.load(&lock_nonce).ok())
+    }
+
+    #[query]
+    pub fn get_burn(&self, _ctx: &Context, nonce: u64) -> ContractResult {
+        ok(BURNS.load(&nonce).ok())
+    }
+
+    #[query]
+    pub fn info(&self, _ctx: &Context) -> ContractResult {
+        ok(INFO.load()?)
+    }
+}
+
+fn require_member(ctx: &Context) -> Result<BridgeInfo, ContractError> {
+    let info = INFO.load()?;
+    ensure!(
+        info.committee.contains(&ctx.sender()),
+        ContractError::Unauthorized
+    );
+    Ok(info)
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use norn_sdk::testing::*;
+
+    const CHAIN_ID: u64 = 11_155_111;
+    const LOCK: EvmAddress = [0xaa; 20];
+    const USDC: EvmAddress = [0xcc; 20];
+    const USER: Address = [0x55; 20];
+
+    fn setup() -> (TestEnv, EvmBridge) {
+        let env = TestEnv::new().with_sender(ALICE);
+        let bridge = EvmBridge::new(
+            &env.ctx(),
+            CHAIN_ID,
+            LOCK,
+            alloc::vec![ALICE, BOB, CHARLIE],
+            2,
+        );
+        (env, bridge)
+    }
+
+    fn lock(nonce: u64, amount: u128) -> MintInstruction {
+        MintInstruction {
+            chain_id: CHAIN_ID,
+            lock_contract: LOCK,
+            lock_nonce: nonce,
+            tx_hash: [nonce as u8; 32],
+            evm_token: USDC,
+            amount,
+            recipient: USER,
+        }
+    }
+
+    fn attest(
+        env: &TestEnv,
+        bridge: &mut EvmBridge,
+        who: Address,
+        ins: MintInstruction,
+    ) -> ContractResult {
+        env.set_sender(who);
+        bridge.attest_mint(&env.ctx(), ins)
+    }
+
+    #[test]
+    fn test_watcher_discriminants() {
+        // `norn_node::bridge::instruction` encodes these by index.
+        let input = borsh::to_vec(&__EvmBridgeExecute::AttestMint {
+            instruction: lock(1, 1),
+        })
+        .unwrap();
+        assert_eq!(input[0], 0);
+        let input = borsh::to_vec(&__EvmBridgeExecute::AttestRelease {
+            nonce: 1,
+            signature: Vec::new(),
+        })
+        .unwrap();
+        assert_eq!(input[0], 2);
+    }
+
+    #[test]
+    fn test_mint_requires_threshold() {
+        let (env, mut bridge) = setup();
+        let resp = attest(&env, &mut bridge, ALICE, lock(1, 500)).unwrap();
+        assert_event(&resp, "MintAttested");
+        assert_eq!(BALANCES.load_or(&(USDC, USER), 0), 0);
+
+        let resp = attest(&env, &mut bridge, BOB, lock(1, 500)).unwrap();
+        assert_event(&resp, "BridgeMint");
+        assert_eq!(BALANCES.load_or(&(USDC, USER), 0), 500);
+        assert_eq!(SUPPLY.load_or(&USDC, 0), 500);
+
+        let err = attest(&env, &mut bridge, CHARLIE, lock(1, 500)).unwrap_err();
+        assert_eq!(err.message(), "lock already minted");
+    }
+
+    #[test]
+    fn test_mint_rejects_outsiders_and_repeats() {
+        let (env, mut bridge) = setup();
+        let err = attest(&env, &mut bridge, DAVE, lock(1, 500)).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized);
+
+        attest(&env, &mut bridge, ALICE, lock(1, 500)).unwrap();
+        let err = attest(&env, &mut bridge, ALICE, lock(1, 500)).unwrap_err();
+        assert_eq!(err.message(), "lock already attested");
+
+        let mut foreign = lock(2, 500);
+        foreign.lock_contract = [0xbb; 20];
+        let err = attest(&env, &mut bridge, ALICE, foreign).unwrap_err();
+        assert_eq!(err.message(), "instruction is for another lock contract");
+    }
+
+    #[test]
+    fn test_conflicting_instructions_count_separately() {
+        let (env, mut bridge) = setup();
+        // A faulty member reports an inflated amount first.
+        attest(&env, &mut bridge, CHARLIE, lock(1, 9_999)).unwrap();
+        attest(&env, &mut bridge, ALICE, lock(1, 500)).unwrap();
+        assert_eq!(BALANCES.load_or(&(USDC, USER), 0), 0);
+
+        attest(&env, &mut bridge, BOB, lock(1, 500)).unwrap();
+        assert_eq!(BALANCES.load_or(&(USDC, USER), 0), 500);
+        let record = MINTS.load(&1).unwrap();
+        assert_eq!(record.candidates.len(), 2);
+        assert!(record.minted);
+    }
+
+    #[test]
+    fn test_burn_and_release_signatures() {
+        let (env, mut bridge) = setup();
+        attest(&env, &mut bridge, ALICE, lock(1, 500)).unwrap();
+        attest(&env, &mut bridge, BOB, lock(1, 500)).unwrap();
+
+        env.set_sender(USER);
+        let resp = bridge.burn(&env.ctx(), USDC, 200, [0xee; 20]).unwrap();
+        assert_event(&resp, "BridgeBurn");
+        assert_data(&resp, &1u64);
+        assert_eq!(BALANCES.load_or(&(USDC, USER), 0), 300);
+        assert_eq!(SUPPLY.load_or(&USDC, 0), 300);
+
+        let err = bridge
+            .attest_release(&env.ctx(), 1, alloc::vec![0u8; 65])
+            .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized);
+
+        env.set_sender(ALICE);
+        let err = bridge
+            .attest_release(&env.ctx(), 1, alloc::vec![0u8; 64])
+            .unwrap_err();
+        assert_eq!(err.message(), "signature must be 65 bytes");
+        let resp = bridge
+            .attest_release(&env.ctx(), 1, alloc::vec![1u8; 65])
+            .unwrap();
+        assert_event_attribute(&resp, "ReleaseAttested", "ready", "false");
+        let err = bridge
+            .attest_release(&env.ctx(), 1, alloc::vec![1u8; 65])
+            .unwrap_err();
+        assert_eq!(err.message(), "release already attested");
+
+        env.set_sender(BOB);
+        let resp = bridge
+            .attest_release(&env.ctx(), 1, alloc::vec![2u8; 65])
+            .unwrap();
+        assert_event_attribute(&resp, "ReleaseAttested", "ready", "true");
+        let record: Option<BurnRecord> =
+            from_response(&bridge.get_burn(&env.ctx(), 1).unwrap()).unwrap();
+        assert_eq!(record.unwrap().signatures.len(), 2);
+    }
+
+    #[test]
+    fn test_burn_more_than_balance_fails() {
+        let (env, mut bridge) = setup();
+        env.set_sender(USER);
+        let err = bridge.burn(&env.ctx(), USDC, 1, [0xee; 20]).unwrap_err();
+        assert_eq!(err, ContractError::InsufficientFunds);
+        assert_eq!(NEXT_BURN.load().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_transfer_wrapped() {
+        let (env, mut bridge) = setup();
+        attest(&env, &mut bridge, ALICE, lock(1, 500)).unwrap();
+        attest(&env, &mut bridge, BOB, lock(1, 500)).unwrap();
+
+        env.set_sender(USER);
+        bridge.transfer(&env.ctx(), USDC, DAVE, 120).unwrap();
+        let bal: u128 = from_response(&bridge.balance(&env.ctx(), USDC, DAVE).unwrap()).unwrap();
+        assert_eq!(bal, 120);
+    }
+}
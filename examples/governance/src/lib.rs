@@ -1,5 +1,15 @@
 //! DAO Governance — token-weighted voting on proposals.
 //! Proposal → voting period → execute or reject based on quorum.
+//!
+//! Sealed proposals use commit-reveal ballots so no tally is visible while
+//! voting is open: voters commit `ballot_commitment(...)` with a deposit
+//! during the voting period, reveal their choice and salt during the reveal
+//! period to get the deposit back, and only revealed ballots are counted.
+//! Deposits of ballots never revealed are forfeited to the DAO.
+//!
+//! Once the creator sets the network's chain ID, votes can also be signed
+//! off-chain as typed data ([`VOTE_TYPE`] in the `norn-governance` domain)
+//! and submitted by anyone with `cast_signed_vote`.
 
 #![no_std]
 
@@ -15,6 +25,15 @@ const CONFIG: Item<GovConfig> = Item::new("config");
 const PROPOSAL_COUNT: Item<u64> = Item::new("prop_count");
 const PROPOSALS: Map<u64, GovProposal> = Map::new("proposals");
 const VOTES: Map<(u64, [u8; 20]), u8> = Map::new("votes"); // 0=not voted, 1=for, 2=against
+const COMMITMENTS: Map<(u64, [u8; 20]), [u8; 32]> = Map::new("commitments");
+const CHAIN_ID: Item<String> = Item::new("chain_id");
+
+/// Typed-data domain name of signed votes.
+pub const VOTE_DOMAIN_NAME: &str = "norn-governance";
+/// Typed-data domain version of signed votes.
+pub const VOTE_DOMAIN_VERSION: &str = "1";
+/// Typed-data type of a signed vote.
+pub const VOTE_TYPE: &str = "Vote(u64 proposal,bool support)";
 
 // ── Types ──────────────────────────────────────────────────────────────
 
@@ -33,6 +52,8 @@ pub struct GovConfig {
     pub voting_period: u64, // seconds
     pub quorum: u64,        // minimum total votes needed
     pub created_at: u64,
+    pub reveal_period: u64, // seconds after voting ends to reveal sealed ballots
+    pub ballot_deposit: u128, // native deposit per sealed ballot
 }
 
 #[derive(Debug, BorshSerialize, BorshDeserialize, Clone)]
@@ -46,6 +67,11 @@ pub struct GovProposal {
     pub start_time: u64,
     pub end_time: u64,
     pub status: ProposalStatus,
+    pub sealed: bool,
+    pub reveal_end: u64,
+    pub ballot_deposit: u128,
+    pub commit_count: u64,
+    pub reveal_count: u64,
 }
 
 // ── Contract ───────────────────────────────────────────────────────────
@@ -81,6 +107,8 @@ impl Governance {
             voting_period,
             quorum,
             created_at: ctx.timestamp(),
+            reveal_period: voting_period,
+            ballot_deposit: 0,
         })?;
         INITIALIZED.save(&true)?;
 
@@ -88,35 +116,8 @@ impl Governance {
     }
 
     #[execute]
-    pub fn propose(
-        &mut self,
-        ctx: &Context,
-        title: String,
-        description: String,
-    ) -> ContractResult {
-        let config = CONFIG.load()?;
-        ensure!(title.len() <= 128, "title too long (max 128)");
-        ensure!(description.len() <= 512, "description too long (max 512)");
-
-        let id = PROPOSAL_COUNT.load_or(0u64);
-        let now = ctx.timestamp();
-
-        PROPOSALS.save(
-            &id,
-            &GovProposal {
-                id,
-                proposer: ctx.sender(),
-                title,
-                description,
-                for_votes: 0,
-                against_votes: 0,
-                start_time: now,
-                end_time: now + config.voting_period,
-                status: ProposalStatus::Active,
-            },
-        )?;
-        PROPOSAL_COUNT.save(&safe_add_u64(id, 1)?)?;
-
+    pub fn propose(&mut self, ctx: &Context, title: String, description: String) -> ContractResult {
+        let id = create_proposal(ctx, title, description, false)?;
         Ok(Response::with_action("propose")
             .add_attribute("proposal_id", format!("{}", id))
             .set_data(&id))
@@ -139,6 +140,8 @@ impl Governance {
             "voting period has ended"
         );
 
+        ensure!(!proposal.sealed, "proposal uses sealed ballots");
+
         let key = (proposal_id, ctx.sender());
         let existing = VOTES.load(&key).unwrap_or(0);
         ensure!(existing == 0, "already voted");
@@ -157,6 +160,78 @@ impl Governance {
             .add_attribute("support", format!("{}", support)))
     }
 
+    /// Set the chain ID signed votes are bound to.
+    #[execute]
+    pub fn set_chain_id(&mut self, ctx: &Context, chain_id: String) -> ContractResult {
+        let config = CONFIG.load()?;
+        ensure!(
+            ctx.sender() == config.creator,
+            "only the creator can set the chain id"
+        );
+        ensure!(
+            !chain_id.is_empty() && chain_id.len() <= 64,
+            "chain id must be 1-64 bytes"
+        );
+        CHAIN_ID.save(&chain_id)?;
+
+        Ok(Response::with_action("set_chain_id").add_attribute("chain_id", chain_id))
+    }
+
+    /// Count a vote signed off-chain by `voter_pubkey`. Anyone may submit it.
+    #[execute]
+    pub fn cast_signed_vote(
+        &mut self,
+        ctx: &Context,
+        proposal_id: u64,
+        support: bool,
+        voter_pubkey: [u8; 32],
+        signature: [u8; 64],
+    ) -> ContractResult {
+        let chain_id = CHAIN_ID
+            .load()
+            .map_err(|_| ContractError::custom("signed votes are not enabled"))?;
+        let mut proposal = PROPOSALS.load(&proposal_id)?;
+        ensure!(
+            proposal.status == ProposalStatus::Active,
+            "proposal is not active"
+        );
+        ensure!(
+            ctx.timestamp() < proposal.end_time,
+            "voting period has ended"
+        );
+        ensure!(!proposal.sealed, "proposal uses sealed ballots");
+
+        let domain = TypedDomain::new(
+            VOTE_DOMAIN_NAME,
+            VOTE_DOMAIN_VERSION,
+            &chain_id,
+            ctx.contract_address(),
+        );
+        let vote = TypedStruct::new(VOTE_TYPE).u64(proposal_id).bool(support);
+        ensure!(
+            ctx.verify_typed(&domain, &vote, &voter_pubkey, &signature),
+            "invalid vote signature"
+        );
+
+        let voter = pubkey_to_addr(&voter_pubkey);
+        let key = (proposal_id, voter);
+        ensure!(VOTES.load(&key).unwrap_or(0) == 0, "already voted");
+
+        if support {
+            proposal.for_votes = safe_add_u64(proposal.for_votes, 1)?;
+        } else {
+            proposal.against_votes = safe_add_u64(proposal.against_votes, 1)?;
+        }
+
+        VOTES.save(&key, &if support { 1u8 } else { 2u8 })?;
+        PROPOSALS.save(&proposal_id, &proposal)?;
+
+        Ok(Response::with_action("cast_signed_vote")
+            .add_attribute("proposal_id", format!("{}", proposal_id))
+            .add_attribute("voter", addr_to_hex(&voter))
+            .add_attribute("support", format!("{}", support)))
+    }
+
     #[execute]
     pub fn finalize(&mut self, ctx: &Context, proposal_id: u64) -> ContractResult {
         let config = CONFIG.load()?;
@@ -169,6 +244,10 @@ impl Governance {
             ctx.timestamp() >= proposal.end_time,
             "voting period has not ended"
         );
+        ensure!(
+            ctx.timestamp() >= proposal.reveal_end,
+            "reveal period has not ended"
+        );
 
         let total_votes = safe_add_u64(proposal.for_votes, proposal.against_votes)?;
 
@@ -182,9 +261,134 @@ impl Governance {
 
         PROPOSALS.save(&proposal_id, &proposal)?;
 
+        // Deposits of unrevealed ballots stay with the DAO.
+        let unrevealed = proposal.commit_count - proposal.reveal_count;
+        let slashed = safe_mul(unrevealed as u128, proposal.ballot_deposit)?;
+
         Ok(Response::with_action("finalize")
             .add_attribute("proposal_id", format!("{}", proposal_id))
-            .add_attribute("status", format!("{:?}", proposal.status)))
+            .add_attribute("status", format!("{:?}", proposal.status))
+            .add_attribute("slashed", format!("{}", slashed)))
+    }
+
+    #[execute]
+    pub fn configure_ballots(
+        &mut self,
+        ctx: &Context,
+        reveal_period: u64,
+        ballot_deposit: u128,
+    ) -> ContractResult {
+        let mut config = CONFIG.load()?;
+        ensure!(
+            ctx.sender() == config.creator,
+            "only the creator can configure ballots"
+        );
+        ensure!(reveal_period > 0, "reveal_period must be positive");
+
+        config.reveal_period = reveal_period;
+        config.ballot_deposit = ballot_deposit;
+        CONFIG.save(&config)?;
+
+        Ok(Response::with_action("configure_ballots")
+            .add_attribute("reveal_period", format!("{}", reveal_period))
+            .add_attribute("ballot_deposit", format!("{}", ballot_deposit)))
+    }
+
+    /// Create a proposal voted on with commit-reveal ballots.
+    #[execute]
+    pub fn propose_sealed(
+        &mut self,
+        ctx: &Context,
+        title: String,
+        description: String,
+    ) -> ContractResult {
+        let id = create_proposal(ctx, title, description, true)?;
+        Ok(Response::with_action("propose_sealed")
+            .add_attribute("proposal_id", format!("{}", id))
+            .set_data(&id))
+    }
+
+    /// Commit a sealed ballot, locking the proposal's ballot deposit.
+    #[execute]
+    pub fn commit_vote(
+        &mut self,
+        ctx: &Context,
+        proposal_id: u64,
+        commitment: [u8; 32],
+    ) -> ContractResult {
+        let mut proposal = PROPOSALS.load(&proposal_id)?;
+        ensure!(proposal.sealed, "proposal does not use sealed ballots");
+        ensure!(
+            proposal.status == ProposalStatus::Active,
+            "proposal is not active"
+        );
+        ensure!(
+            ctx.timestamp() < proposal.end_time,
+            "voting period has ended"
+        );
+
+        let key = (proposal_id, ctx.sender());
+        ensure!(!COMMITMENTS.has(&key), "already committed");
+
+        if proposal.ballot_deposit > 0 {
+            let contract = ctx.contract_address();
+            ctx.transfer(
+                &ctx.sender(),
+                &contract,
+                &NATIVE_TOKEN_ID,
+                proposal.ballot_deposit,
+            );
+        }
+        COMMITMENTS.save(&key, &commitment)?;
+        proposal.commit_count = safe_add_u64(proposal.commit_count, 1)?;
+        PROPOSALS.save(&proposal_id, &proposal)?;
+
+        Ok(Response::with_action("commit_vote")
+            .add_attribute("proposal_id", format!("{}", proposal_id)))
+    }
+
+    /// Reveal a committed ballot, counting it and refunding the deposit.
+    #[execute]
+    pub fn reveal_vote(
+        &mut self,
+        ctx: &Context,
+        proposal_id: u64,
+        support: bool,
+        salt: [u8; 32],
+    ) -> ContractResult {
+        let mut proposal = PROPOSALS.load(&proposal_id)?;
+        ensure!(proposal.sealed, "proposal does not use sealed ballots");
+        let now = ctx.timestamp();
+        ensure!(now >= proposal.end_time, "voting period has not ended");
+        ensure!(now < proposal.reveal_end, "reveal period has ended");
+
+        let voter = ctx.sender();
+        let key = (proposal_id, voter);
+        let commitment = COMMITMENTS
+            .load(&key)
+            .map_err(|_| ContractError::custom("no committed ballot"))?;
+        ensure!(VOTES.load(&key).unwrap_or(0) == 0, "already revealed");
+        ensure!(
+            commitment == ballot_commitment(proposal_id, &voter, support, &salt),
+            "ballot does not match commitment"
+        );
+
+        if support {
+            proposal.for_votes = safe_add_u64(proposal.for_votes, 1)?;
+        } else {
+            proposal.against_votes = safe_add_u64(proposal.against_votes, 1)?;
+        }
+        proposal.reveal_count = safe_add_u64(proposal.reveal_count, 1)?;
+
+        VOTES.save(&key, &if support { 1u8 } else { 2u8 })?;
+        PROPOSALS.save(&proposal_id, &proposal)?;
+        if proposal.ballot_deposit > 0 {
+            ctx.transfer_from_contract(&voter, &NATIVE_TOKEN_ID, proposal.ballot_deposit);
+        }
+
+        Ok(Response::with_action("reveal_vote")
+            .add_attribute("proposal_id", format!("{}", proposal_id))
+            .add_attribute("support", format!("{}", support)))
     }
 
     #[query]
@@ -193,6 +397,11 @@ impl Governance {
         ok(config)
     }
 
+    #[query]
+    pub fn get_chain_id(&self, _ctx: &Context) -> ContractResult {
+        ok(CHAIN_ID.load().ok())
+    }
+
     #[query]
     pub fn get_proposal(&self, _ctx: &Context, proposal_id: u64) -> ContractResult {
         let proposal = PROPOSALS.load(&proposal_id)?;
@@ -210,6 +419,77 @@ impl Governance {
         let vote = VOTES.load(&(proposal_id, voter)).unwrap_or(0);
         ok(vote)
     }
+
+    #[query]
+    pub fn get_commitment(
+        &self,
+        _ctx: &Context,
+        proposal_id: u64,
+        voter: Address,
+    ) -> ContractResult {
+        ok(COMMITMENTS.load(&(proposal_id, voter)).ok())
+    }
+}
+
+fn create_proposal(
+    ctx: &Context,
+    title: String,
+    description: String,
+    sealed: bool,
+) -> Result<u64, ContractError> {
+    let config = CONFIG.load()?;
+    ensure!(title.len() <= 128, "title too long (max 128)");
+    ensure!(description.len() <= 512, "description too long (max 512)");
+
+    let id = PROPOSAL_COUNT.load_or(0u64);
+    let now = ctx.timestamp();
+    let end_time = now + config.voting_period;
+    let (reveal_end, ballot_deposit) = if sealed {
+        (end_time + config.reveal_period, config.ballot_deposit)
+    } else {
+        (end_time, 0)
+    };
+
+    PROPOSALS.save(
+        &id,
+        &GovProposal {
+            id,
+            proposer: ctx.sender(),
+            title,
+            description,
+            for_votes: 0,
+            against_votes: 0,
+            start_time: now,
+            end_time,
+            status: ProposalStatus::Active,
+            sealed,
+            reveal_end,
+            ballot_deposit,
+            commit_count: 0,
+            reveal_count: 0,
+        },
+    )?;
+    PROPOSAL_COUNT.save(&safe_add_u64(id, 1)?)?;
+    Ok(id)
+}
+
+/// Commitment to a sealed ballot: H(proposal_id || voter || support || salt).
+///
+/// Binding the proposal and voter stops a ballot being copied to another
+/// proposal or replayed by another voter.
+pub fn ballot_commitment(
+    proposal_id: u64,
+    voter: &Address,
+    support: bool,
+    salt: &[u8; 32],
+) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(b"norn_gov_ballot");
+    hasher.update(&proposal_id.to_le_bytes());
+    hasher.update(voter);
+    hasher.update(&[support as u8]);
+    hasher.update(salt);
+    *hasher.finalize().as_bytes()
 }
 
 // ── Tests ──────────────────────────────────────────────────────────────
@@ -370,4 +650,173 @@ mod tests {
         let err = gov.vote(&env.ctx(), 0, true).unwrap_err();
         assert_err_contains(&err, "voting period has ended");
     }
+
+    fn setup_sealed() -> (TestEnv, Governance) {
+        let (env, mut gov) = setup();
+        gov.configure_ballots(&env.ctx(), 600, 50).unwrap();
+        gov.propose_sealed(&env.ctx(), "Rotate keys".into(), "Sealed vote".into())
+            .unwrap();
+        (env, gov)
+    }
+
+    fn commit(env: &TestEnv, gov: &mut Governance, voter: Address, support: bool) {
+        env.set_sender(voter);
+        let commitment = ballot_commitment(0, &voter, support, &[voter[0]; 32]);
+        gov.commit_vote(&env.ctx(), 0, commitment).unwrap();
+    }
+
+    fn reveal(
+        env: &TestEnv,
+        gov: &mut Governance,
+        voter: Address,
+        support: bool,
+    ) -> ContractResult {
+        env.set_sender(voter);
+        gov.reveal_vote(&env.ctx(), 0, support, [voter[0]; 32])
+    }
+
+    #[test]
+    fn test_sealed_commit_hides_tally_and_locks_deposit() {
+        let (env, mut gov) = setup_sealed();
+        commit(&env, &mut gov, ALICE, true);
+
+        let p: GovProposal = from_response(&gov.get_proposal(&env.ctx(), 0).unwrap()).unwrap();
+        assert!(p.sealed);
+        assert_eq!((p.for_votes, p.against_votes, p.commit_count), (0, 0, 1));
+        assert_eq!(p.reveal_end, 1000 + 3600 + 600);
+
+        let transfers = env.transfers();
+        assert_eq!(transfers[0].0, ALICE.to_vec());
+        assert_eq!(transfers[0].1, CONTRACT_ADDR.to_vec());
+        assert_eq!(transfers[0].3, 50);
+
+        let err = gov.commit_vote(&env.ctx(), 0, [0u8; 32]).unwrap_err();
+        assert_err_contains(&err, "already committed");
+        let err = gov.vote(&env.ctx(), 0, true).unwrap_err();
+        assert_err_contains(&err, "sealed ballots");
+    }
+
+    #[test]
+    fn test_sealed_reveal_window() {
+        let (env, mut gov) = setup_sealed();
+        commit(&env, &mut gov, ALICE, true);
+
+        let err = reveal(&env, &mut gov, ALICE, true).unwrap_err();
+        assert_err_contains(&err, "voting period has not ended");
+
+        env.set_timestamp(1000 + 3600);
+        let err = gov.commit_vote(&env.ctx(), 0, [0u8; 32]).unwrap_err();
+        assert_err_contains(&err, "voting period has ended");
+        let err = reveal(&env, &mut gov, ALICE, false).unwrap_err();
+        assert_err_contains(&err, "does not match commitment");
+
+        env.clear_transfers();
+        reveal(&env, &mut gov, ALICE, true).unwrap();
+        let transfers = env.transfers();
+        assert_eq!(transfers[0].1, ALICE.to_vec());
+        assert_eq!(transfers[0].3, 50);
+        let err = reveal(&env, &mut gov, ALICE, true).unwrap_err();
+        assert_err_contains(&err, "already revealed");
+
+        env.set_timestamp(1000 + 3600 + 600);
+        let err = reveal(&env, &mut gov, BOB, true).unwrap_err();
+        assert_err_contains(&err, "reveal period has ended");
+    }
+
+    #[test]
+    fn test_sealed_tally_counts_only_revealed_and_slashes_rest() {
+        let (env, mut gov) = setup_sealed();
+        commit(&env, &mut gov, ALICE, true);
+        commit(&env, &mut gov, BOB, true);
+        commit(&env, &mut gov, CHARLIE, false);
+
+        env.set_timestamp(1000 + 3600);
+        reveal(&env, &mut gov, ALICE, true).unwrap();
+        reveal(&env, &mut gov, CHARLIE, false).unwrap();
+
+        let err = gov.finalize(&env.ctx(), 0).unwrap_err();
+        assert_err_contains(&err, "reveal period has not ended");
+
+        env.set_timestamp(1000 + 3600 + 600);
+        let resp = gov.finalize(&env.ctx(), 0).unwrap();
+        assert_attribute(&resp, "slashed", "50");
+
+        let p: GovProposal = from_response(&gov.get_proposal(&env.ctx(), 0).unwrap()).unwrap();
+        assert_eq!((p.for_votes, p.against_votes), (1, 1));
+        assert_eq!(p.status, ProposalStatus::Rejected);
+    }
+
+    #[test]
+    fn test_only_creator_configures_ballots() {
+        let (env, mut gov) = setup();
+        env.set_sender(BOB);
+        let err = gov.configure_ballots(&env.ctx(), 600, 50).unwrap_err();
+        assert_err_contains(&err, "only the creator");
+    }
+
+    fn signed_vote(proposal_id: u64, support: bool, chain_id: &str) -> ([u8; 32], [u8; 64]) {
+        let keypair = norn_crypto::keys::Keypair::from_seed(&[8u8; 32]);
+        let domain = TypedDomain::new(
+            VOTE_DOMAIN_NAME,
+            VOTE_DOMAIN_VERSION,
+            chain_id,
+            CONTRACT_ADDR,
+        );
+        let vote = TypedStruct::new(VOTE_TYPE).u64(proposal_id).bool(support);
+        let signature = keypair.sign(&domain.signing_hash(&vote));
+        (keypair.public_key(), signature)
+    }
+
+    #[test]
+    fn test_cast_signed_vote() {
+        let (env, mut gov) = setup();
+        let id = create_proposal(&env, &mut gov);
+        gov.set_chain_id(&env.ctx(), "norn-dev".into()).unwrap();
+
+        let (pubkey, signature) = signed_vote(id, true, "norn-dev");
+        env.set_sender(CHARLIE);
+        gov.cast_signed_vote(&env.ctx(), id, true, pubkey, signature)
+            .unwrap();
+
+        let proposal = PROPOSALS.load(&id).unwrap();
+        assert_eq!(proposal.for_votes, 1);
+        let voter = pubkey_to_addr(&pubkey);
+        assert_eq!(VOTES.load(&(id, voter)).unwrap(), 1);
+
+        let err = gov
+            .cast_signed_vote(&env.ctx(), id, true, pubkey, signature)
+            .unwrap_err();
+        assert_eq!(err.message(), "already voted");
+    }
+
+    #[test]
+    fn test_signed_vote_rejects_bad_signatures() {
+        let (env, mut gov) = setup();
+        let id = create_proposal(&env, &mut gov);
+        let (pubkey, signature) = signed_vote(id, true, "norn-dev");
+        let err = gov
+            .cast_signed_vote(&env.ctx(), id, true, pubkey, signature)
+            .unwrap_err();
+        assert_eq!(err.message(), "signed votes are not enabled");
+
+        gov.set_chain_id(&env.ctx(), "norn-mainnet".into()).unwrap();
+        let err = gov
+            .cast_signed_vote(&env.ctx(), id, true, pubkey, signature)
+            .unwrap_err();
+        assert_eq!(err.message(), "invalid vote signature");
+
+        gov.set_chain_id(&env.ctx(), "norn-dev".into()).unwrap();
+        let err = gov
+            .cast_signed_vote(&env.ctx(), id, false, pubkey, signature)
+            .unwrap_err();
+        assert_eq!(err.message(), "invalid vote signature");
+    }
+
+    #[test]
+    fn test_only_creator_sets_chain_id() {
+        let (env, mut gov) = setup();
+        env.set_sender(BOB);
+        let err = gov.set_chain_id(&env.ctx(), "norn-dev".into()).unwrap_err();
+        assert_eq!(err.message(), "only the creator can set the chain id");
+    }
 }
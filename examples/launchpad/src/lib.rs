@@ -1,11 +1,15 @@
 //! Token Launchpad — fixed-price token sale with hard cap.
 //! Creator deposits tokens, buyers contribute NORN, unsold tokens returned after deadline.
+//! An optional allowlist phase restricts early contributions to tiered addresses.
+//! If the soft cap is not reached by the deadline, the sale fails and contributors are refunded.
+//! In overflow mode contributions are uncapped; tokens and the hard cap are shared pro-rata
+//! and excess NORN is refunded at claim time.
 
 #![no_std]
 
 extern crate alloc;
 
-use alloc::format;
+use alloc::{format, vec::Vec};
 use norn_sdk::prelude::*;
 
 const NATIVE_TOKEN: TokenId = [0u8; 32];
@@ -17,20 +21,36 @@ const CONFIG: Item<LaunchConfig> = Item::new("config");
 const TOTAL_RAISED: Item<u128> = Item::new("total_raised");
 const CONTRIBUTIONS: Map<Address, u128> = Map::new("contributions");
 const CLAIMED: Map<Address, bool> = Map::new("claimed");
+const TIERS: Map<u64, u128> = Map::new("tiers");
+const ALLOWLIST: Map<Address, u64> = Map::new("allowlist");
+
+const MAX_ALLOWLIST_BATCH: usize = 100;
 
 // ── Types ──────────────────────────────────────────────────────────────
 
+#[derive(Debug, BorshSerialize, BorshDeserialize, Clone, PartialEq)]
+pub enum SaleMode {
+    /// First-come-first-served until the hard cap is reached.
+    FixedPrice,
+    /// Uncapped contributions, allocated pro-rata with excess NORN refunded.
+    Overflow,
+}
+
 #[derive(Debug, BorshSerialize, BorshDeserialize, Clone)]
 pub struct LaunchConfig {
     pub creator: Address,
     pub token_id: TokenId,
     pub price: u128,         // NORN per token (scaled 1e12)
     pub hard_cap: u128,      // max NORN to raise
+    pub soft_cap: u128,      // min NORN for the sale to succeed
     pub max_per_wallet: u128,
     pub start_time: u64,
     pub end_time: u64,
     pub total_tokens: u128,  // tokens deposited by creator
     pub finalized: bool,
+    pub public_start_time: u64, // before this, only allowlisted addresses may contribute
+    pub failed: bool,        // soft cap missed; contributors refund, creator reclaims tokens
+    pub sale_mode: SaleMode,
 }
 
 // ── Contract ───────────────────────────────────────────────────────────
@@ -55,14 +75,17 @@ impl Launchpad {
         token_id: TokenId,
         price: u128,
         hard_cap: u128,
+        soft_cap: u128,
         max_per_wallet: u128,
         start_time: u64,
         end_time: u64,
         total_tokens: u128,
+        sale_mode: SaleMode,
     ) -> ContractResult {
         ensure!(!INITIALIZED.load_or(false), "already initialized");
         ensure!(price > 0, "price must be positive");
         ensure!(hard_cap > 0, "hard_cap must be positive");
+        ensure!(soft_cap <= hard_cap, "soft_cap must not exceed hard_cap");
         ensure!(total_tokens > 0, "total_tokens must be positive");
         ensure!(end_time > start_time, "end_time must be after start_time");
         ensure!(max_per_wallet > 0, "max_per_wallet must be positive");
@@ -76,11 +99,15 @@ impl Launchpad {
             token_id,
             price,
             hard_cap,
+            soft_cap,
             max_per_wallet,
             start_time,
             end_time,
             total_tokens,
             finalized: false,
+            public_start_time: start_time,
+            failed: false,
+            sale_mode,
         })?;
         INITIALIZED.save(&true)?;
 
@@ -96,15 +123,25 @@ impl Launchpad {
         ensure!(amount > 0, "amount must be positive");
 
         let total = TOTAL_RAISED.load_or(0u128);
-        ensure!(
-            safe_add(total, amount)? <= config.hard_cap,
-            "would exceed hard cap"
-        );
+        if config.sale_mode == SaleMode::FixedPrice {
+            ensure!(
+                safe_add(total, amount)? <= config.hard_cap,
+                "would exceed hard cap"
+            );
+        }
 
         let existing = CONTRIBUTIONS.load(&ctx.sender()).unwrap_or(0u128);
         let new_total = safe_add(existing, amount)?;
         ensure!(new_total <= config.max_per_wallet, "exceeds max per wallet");
 
+        // Allowlist phase: only listed addresses, capped by their tier
+        if ctx.timestamp() < config.public_start_time {
+            ensure!(ALLOWLIST.has(&ctx.sender()), "not on allowlist");
+            let tier = ALLOWLIST.load(&ctx.sender())?;
+            let tier_cap = TIERS.load(&tier).unwrap_or(0u128);
+            ensure!(new_total <= tier_cap, "exceeds tier allocation");
+        }
+
         // Transfer NORN from buyer to contract
         let contract = ctx.contract_address();
         ctx.transfer(&ctx.sender(), &contract, &NATIVE_TOKEN, amount);
@@ -121,6 +158,7 @@ impl Launchpad {
     pub fn claim_tokens(&mut self, ctx: &Context) -> ContractResult {
         let config = CONFIG.load()?;
         ensure!(config.finalized, "sale not finalized yet");
+        ensure!(!config.failed, "sale failed, use refund");
 
         let already_claimed = CLAIMED.load(&ctx.sender()).unwrap_or(false);
         ensure!(!already_claimed, "already claimed");
@@ -129,14 +167,21 @@ impl Launchpad {
         ensure!(contribution > 0, "no contribution found");
 
         // tokens = contribution / price
-        let tokens = safe_mul(contribution, config.total_tokens)?
-            / TOTAL_RAISED.load_or(1u128);
+        let total_raised = TOTAL_RAISED.load_or(1u128);
+        let tokens = safe_mul(contribution, config.total_tokens)? / total_raised;
 
         ctx.transfer_from_contract(&ctx.sender(), &config.token_id, tokens);
+
+        // Overflow: only a pro-rata share of the hard cap is accepted, the rest is returned
+        let excess = overflow_excess(&config, contribution, total_raised)?;
+        if excess > 0 {
+            ctx.transfer_from_contract(&ctx.sender(), &NATIVE_TOKEN, excess);
+        }
         CLAIMED.save(&ctx.sender(), &true)?;
 
         Ok(Response::with_action("claim_tokens")
-            .add_attribute("tokens", format!("{}", tokens)))
+            .add_attribute("tokens", format!("{}", tokens))
+            .add_attribute("refund", format!("{}", excess)))
     }
 
     #[execute]
@@ -154,9 +199,25 @@ impl Launchpad {
 
         let total_raised = TOTAL_RAISED.load_or(0u128);
 
-        // Send raised NORN to creator
-        if total_raised > 0 {
-            ctx.transfer_from_contract(&config.creator, &NATIVE_TOKEN, total_raised);
+        // Soft cap missed: keep NORN for refunds, return every token to the creator
+        if total_raised < config.soft_cap {
+            ctx.transfer_from_contract(&config.creator, &config.token_id, config.total_tokens);
+            config.finalized = true;
+            config.failed = true;
+            CONFIG.save(&config)?;
+
+            return Ok(Response::with_action("finalize")
+                .add_attribute("total_raised", format!("{}", total_raised))
+                .add_attribute("status", "failed"));
+        }
+
+        // Send raised NORN to creator (overflow sales keep the excess for refunds)
+        let accepted = match config.sale_mode {
+            SaleMode::FixedPrice => total_raised,
+            SaleMode::Overflow => core::cmp::min(total_raised, config.hard_cap),
+        };
+        if accepted > 0 {
+            ctx.transfer_from_contract(&config.creator, &NATIVE_TOKEN, accepted);
         }
 
         // Return unsold tokens to creator
@@ -175,7 +236,8 @@ impl Launchpad {
         CONFIG.save(&config)?;
 
         Ok(Response::with_action("finalize")
-            .add_attribute("total_raised", format!("{}", total_raised)))
+            .add_attribute("total_raised", format!("{}", total_raised))
+            .add_attribute("accepted", format!("{}", accepted)))
     }
 
     #[execute]
@@ -186,8 +248,13 @@ impl Launchpad {
             "sale has not ended yet"
         );
 
+        // Refunds are open once the deadline passes below the soft cap, even if
+        // the creator never finalizes.
         let total_raised = TOTAL_RAISED.load_or(0u128);
-        ensure!(total_raised == 0, "sale had contributions, use claim_tokens after finalize");
+        ensure!(
+            total_raised < config.soft_cap,
+            "soft cap reached, use claim_tokens after finalize"
+        );
 
         let contribution = CONTRIBUTIONS.load(&ctx.sender()).unwrap_or(0u128);
         ensure!(contribution > 0, "no contribution to refund");
@@ -199,6 +266,77 @@ impl Launchpad {
             .add_attribute("amount", format!("{}", contribution)))
     }
 
+    #[execute]
+    pub fn set_public_start_time(
+        &mut self,
+        ctx: &Context,
+        public_start_time: u64,
+    ) -> ContractResult {
+        let mut config = CONFIG.load()?;
+        ensure!(
+            ctx.sender() == config.creator,
+            "only creator can configure allowlist"
+        );
+        ensure!(ctx.timestamp() < config.start_time, "sale has already started");
+        ensure!(
+            public_start_time >= config.start_time && public_start_time <= config.end_time,
+            "public_start_time must be within the sale window"
+        );
+
+        config.public_start_time = public_start_time;
+        CONFIG.save(&config)?;
+
+        Ok(Response::with_action("set_public_start_time")
+            .add_attribute("public_start_time", format!("{}", public_start_time)))
+    }
+
+    #[execute]
+    pub fn set_tier(&mut self, ctx: &Context, tier: u64, max_allocation: u128) -> ContractResult {
+        let config = CONFIG.load()?;
+        ensure!(
+            ctx.sender() == config.creator,
+            "only creator can configure allowlist"
+        );
+        ensure!(ctx.timestamp() < config.start_time, "sale has already started");
+        ensure!(max_allocation > 0, "max_allocation must be positive");
+
+        TIERS.save(&tier, &max_allocation)?;
+
+        Ok(Response::with_action("set_tier")
+            .add_attribute("tier", format!("{}", tier))
+            .add_attribute("max_allocation", format!("{}", max_allocation)))
+    }
+
+    #[execute]
+    pub fn add_to_allowlist(
+        &mut self,
+        ctx: &Context,
+        tier: u64,
+        addresses: Vec<Address>,
+    ) -> ContractResult {
+        let config = CONFIG.load()?;
+        ensure!(
+            ctx.sender() == config.creator,
+            "only creator can configure allowlist"
+        );
+        ensure!(ctx.timestamp() < config.start_time, "sale has already started");
+        ensure!(TIERS.has(&tier), "unknown tier");
+        ensure!(!addresses.is_empty(), "address list is empty");
+        ensure!(
+            addresses.len() <= MAX_ALLOWLIST_BATCH,
+            "max 100 addresses per batch"
+        );
+
+        for addr in &addresses {
+            ensure!(*addr != ZERO_ADDRESS, "cannot allowlist zero address");
+            ALLOWLIST.save(addr, &tier)?;
+        }
+
+        Ok(Response::with_action("add_to_allowlist")
+            .add_attribute("tier", format!("{}", tier))
+            .add_attribute("count", format!("{}", addresses.len())))
+    }
+
     #[query]
     pub fn get_config(&self, _ctx: &Context) -> ContractResult {
         let config = CONFIG.load()?;
@@ -216,6 +354,34 @@ impl Launchpad {
         let total = TOTAL_RAISED.load_or(0u128);
         ok(total)
     }
+
+    #[query]
+    pub fn get_tier(&self, _ctx: &Context, tier: u64) -> ContractResult {
+        let max_allocation = TIERS.load(&tier).unwrap_or(0u128);
+        ok(max_allocation)
+    }
+
+    #[query]
+    pub fn get_allowlist_tier(&self, _ctx: &Context, addr: Address) -> ContractResult {
+        let tier: Option<u64> = ALLOWLIST.load(&addr).ok();
+        ok(tier)
+    }
+}
+
+// ── Helpers ────────────────────────────────────────────────────────────
+
+/// NORN to return to a contributor in an oversubscribed overflow sale.
+fn overflow_excess(
+    config: &LaunchConfig,
+    contribution: u128,
+    total_raised: u128,
+) -> Result<u128, ContractError> {
+    if config.sale_mode != SaleMode::Overflow || total_raised <= config.hard_cap {
+        return Ok(0);
+    }
+    // Round the accepted share up so refunds round down and never exceed the excess held
+    let accepted = safe_add(safe_mul(contribution, config.hard_cap)?, total_raised - 1)? / total_raised;
+    safe_sub(contribution, accepted)
 }
 
 // ── Tests ──────────────────────────────────────────────────────────────
@@ -239,10 +405,12 @@ mod tests {
             TOKEN,
             100,         // price
             10_000,      // hard_cap
+            1_000,       // soft_cap
             5_000,       // max_per_wallet
             1000,        // start_time
             2000,        // end_time
             100_000,     // total_tokens
+            SaleMode::FixedPrice,
         )
         .unwrap();
         (env, lp)
@@ -256,6 +424,7 @@ mod tests {
         assert_eq!(config.creator, ALICE);
         assert_eq!(config.price, 100);
         assert_eq!(config.hard_cap, 10_000);
+        assert_eq!(config.soft_cap, 1_000);
         assert!(!config.finalized);
     }
 
@@ -263,7 +432,18 @@ mod tests {
     fn test_cannot_initialize_twice() {
         let (env, mut lp) = setup();
         let err = lp
-            .initialize(&env.ctx(), TOKEN, 100, 10_000, 5_000, 1000, 2000, 100_000)
+            .initialize(
+                &env.ctx(),
+                TOKEN,
+                100,
+                10_000,
+                1_000,
+                5_000,
+                1000,
+                2000,
+                100_000,
+                SaleMode::FixedPrice,
+            )
             .unwrap_err();
         assert_err_contains(&err, "already initialized");
     }
@@ -350,6 +530,296 @@ mod tests {
         assert_err_contains(&err, "sale has not ended yet");
     }
 
+    #[test]
+    fn test_soft_cap_cannot_exceed_hard_cap() {
+        let env = TestEnv::new()
+            .with_sender(ALICE)
+            .with_timestamp(1000)
+            .with_contract_address(CONTRACT_ADDR);
+        let mut lp = Launchpad::new(&env.ctx());
+        let err = lp
+            .initialize(
+                &env.ctx(),
+                TOKEN,
+                100,
+                10_000,
+                10_001,
+                5_000,
+                1000,
+                2000,
+                100_000,
+                SaleMode::FixedPrice,
+            )
+            .unwrap_err();
+        assert_err_contains(&err, "soft_cap must not exceed hard_cap");
+    }
+
+    #[test]
+    fn test_soft_cap_missed_marks_sale_failed() {
+        let (env, mut lp) = setup();
+        env.set_sender(BOB);
+        env.set_timestamp(1500);
+        lp.contribute(&env.ctx(), 500).unwrap();
+
+        env.set_sender(ALICE);
+        env.set_timestamp(2500);
+        env.clear_transfers();
+        let resp = lp.finalize(&env.ctx()).unwrap();
+        assert_attribute(&resp, "status", "failed");
+
+        // All tokens go back to the creator, no NORN is paid out
+        let transfers = env.transfers();
+        assert_eq!(transfers.len(), 1);
+        assert_eq!(transfers[0].1, ALICE.to_vec());
+        assert_eq!(transfers[0].2, TOKEN.to_vec());
+        assert_eq!(transfers[0].3, 100_000);
+
+        let config: LaunchConfig = from_response(&lp.get_config(&env.ctx()).unwrap()).unwrap();
+        assert!(config.failed);
+
+        env.set_sender(BOB);
+        let err = lp.claim_tokens(&env.ctx()).unwrap_err();
+        assert_err_contains(&err, "sale failed, use refund");
+    }
+
+    #[test]
+    fn test_refund_after_failed_sale() {
+        let (env, mut lp) = setup();
+        env.set_sender(BOB);
+        env.set_timestamp(1500);
+        lp.contribute(&env.ctx(), 500).unwrap();
+
+        // Refund is available after the deadline even before finalization
+        env.set_timestamp(2500);
+        env.clear_transfers();
+        let resp = lp.refund(&env.ctx()).unwrap();
+        assert_attribute(&resp, "amount", "500");
+        let transfers = env.transfers();
+        assert_eq!(transfers[0].1, BOB.to_vec());
+        assert_eq!(transfers[0].3, 500);
+
+        let err = lp.refund(&env.ctx()).unwrap_err();
+        assert_err_contains(&err, "no contribution to refund");
+    }
+
+    #[test]
+    fn test_cannot_refund_when_soft_cap_reached() {
+        let (env, mut lp) = setup();
+        env.set_sender(BOB);
+        env.set_timestamp(1500);
+        lp.contribute(&env.ctx(), 1_000).unwrap();
+
+        env.set_timestamp(2500);
+        let err = lp.refund(&env.ctx()).unwrap_err();
+        assert_err_contains(&err, "soft cap reached");
+    }
+
+    fn setup_overflow() -> (TestEnv, Launchpad) {
+        let env = TestEnv::new()
+            .with_sender(ALICE)
+            .with_timestamp(1000)
+            .with_contract_address(CONTRACT_ADDR);
+        let mut lp = Launchpad::new(&env.ctx());
+        lp.initialize(
+            &env.ctx(),
+            TOKEN,
+            100,
+            10_000,
+            1_000,
+            50_000,
+            1000,
+            2000,
+            100_000,
+            SaleMode::Overflow,
+        )
+        .unwrap();
+        (env, lp)
+    }
+
+    #[test]
+    fn test_overflow_accepts_beyond_hard_cap() {
+        let (env, mut lp) = setup_overflow();
+        env.set_timestamp(1500);
+        env.set_sender(BOB);
+        lp.contribute(&env.ctx(), 15_000).unwrap();
+        env.set_sender(CHARLIE);
+        lp.contribute(&env.ctx(), 5_000).unwrap();
+
+        let total: u128 = from_response(&lp.get_total_raised(&env.ctx()).unwrap()).unwrap();
+        assert_eq!(total, 20_000);
+    }
+
+    #[test]
+    fn test_overflow_pro_rata_claim_and_refund() {
+        let (env, mut lp) = setup_overflow();
+        env.set_timestamp(1500);
+        env.set_sender(BOB);
+        lp.contribute(&env.ctx(), 15_000).unwrap();
+        env.set_sender(CHARLIE);
+        lp.contribute(&env.ctx(), 5_000).unwrap();
+
+        // Creator only receives the hard cap
+        env.set_sender(ALICE);
+        env.set_timestamp(2500);
+        let resp = lp.finalize(&env.ctx()).unwrap();
+        assert_attribute(&resp, "accepted", "10000");
+
+        // BOB: 75% of tokens, 7_500 of 15_000 NORN accepted
+        env.set_sender(BOB);
+        let resp = lp.claim_tokens(&env.ctx()).unwrap();
+        assert_attribute(&resp, "tokens", "75000");
+        assert_attribute(&resp, "refund", "7500");
+
+        // CHARLIE: 25% of tokens, 2_500 of 5_000 NORN accepted
+        env.set_sender(CHARLIE);
+        let resp = lp.claim_tokens(&env.ctx()).unwrap();
+        assert_attribute(&resp, "tokens", "25000");
+        assert_attribute(&resp, "refund", "2500");
+    }
+
+    #[test]
+    fn test_overflow_uneven_shares_never_overpay() {
+        let (env, mut lp) = setup_overflow();
+        env.set_timestamp(1500);
+        for (buyer, amount) in [(BOB, 10_001u128), (CHARLIE, 10_001), (DAVE, 10_001)] {
+            env.set_sender(buyer);
+            lp.contribute(&env.ctx(), amount).unwrap();
+        }
+
+        env.set_sender(ALICE);
+        env.set_timestamp(2500);
+        lp.finalize(&env.ctx()).unwrap();
+        for buyer in [BOB, CHARLIE, DAVE] {
+            env.set_sender(buyer);
+            let resp = lp.claim_tokens(&env.ctx()).unwrap();
+            assert_attribute(&resp, "refund", "6667");
+        }
+
+        // NORN in minus NORN out must never go negative
+        let native = NATIVE_TOKEN.to_vec();
+        let contract = CONTRACT_ADDR.to_vec();
+        let (mut held, mut paid) = (0u128, 0u128);
+        for (from, to, token, amount) in env.transfers() {
+            if token != native {
+                continue;
+            }
+            if to == contract {
+                held += amount;
+            }
+            if from == contract {
+                paid += amount;
+            }
+        }
+        assert!(paid <= held);
+        assert_eq!(held - paid, 2);
+    }
+
+    #[test]
+    fn test_overflow_undersubscribed_has_no_refund() {
+        let (env, mut lp) = setup_overflow();
+        env.set_timestamp(1500);
+        env.set_sender(BOB);
+        lp.contribute(&env.ctx(), 4_000).unwrap();
+
+        env.set_sender(ALICE);
+        env.set_timestamp(2500);
+        let resp = lp.finalize(&env.ctx()).unwrap();
+        assert_attribute(&resp, "accepted", "4000");
+
+        env.set_sender(BOB);
+        let resp = lp.claim_tokens(&env.ctx()).unwrap();
+        assert_attribute(&resp, "tokens", "100000");
+        assert_attribute(&resp, "refund", "0");
+    }
+
+    fn setup_allowlist(env: &TestEnv, lp: &mut Launchpad) {
+        env.set_timestamp(500);
+        lp.set_public_start_time(&env.ctx(), 1500).unwrap();
+        lp.set_tier(&env.ctx(), 1, 3_000).unwrap();
+        lp.set_tier(&env.ctx(), 2, 1_000).unwrap();
+        lp.add_to_allowlist(&env.ctx(), 1, alloc::vec![BOB]).unwrap();
+        lp.add_to_allowlist(&env.ctx(), 2, alloc::vec![CHARLIE]).unwrap();
+    }
+
+    #[test]
+    fn test_allowlist_phase_restricts_contributors() {
+        let (env, mut lp) = setup();
+        setup_allowlist(&env, &mut lp);
+
+        env.set_timestamp(1200);
+        env.set_sender(DAVE);
+        let err = lp.contribute(&env.ctx(), 100).unwrap_err();
+        assert_err_contains(&err, "not on allowlist");
+
+        env.set_sender(BOB);
+        lp.contribute(&env.ctx(), 100).unwrap();
+
+        // Public phase is open to everyone
+        env.set_timestamp(1500);
+        env.set_sender(DAVE);
+        lp.contribute(&env.ctx(), 100).unwrap();
+    }
+
+    #[test]
+    fn test_allowlist_tier_cap_enforced() {
+        let (env, mut lp) = setup();
+        setup_allowlist(&env, &mut lp);
+
+        env.set_timestamp(1200);
+        env.set_sender(CHARLIE);
+        lp.contribute(&env.ctx(), 1_000).unwrap();
+        let err = lp.contribute(&env.ctx(), 1).unwrap_err();
+        assert_err_contains(&err, "exceeds tier allocation");
+
+        env.set_sender(BOB);
+        lp.contribute(&env.ctx(), 3_000).unwrap();
+
+        // Tier cap no longer applies once the public phase begins
+        env.set_timestamp(1500);
+        env.set_sender(CHARLIE);
+        lp.contribute(&env.ctx(), 500).unwrap();
+    }
+
+    #[test]
+    fn test_allowlist_requires_known_tier() {
+        let (env, mut lp) = setup();
+        env.set_timestamp(500);
+        lp.set_public_start_time(&env.ctx(), 1500).unwrap();
+        let err = lp
+            .add_to_allowlist(&env.ctx(), 7, alloc::vec![BOB])
+            .unwrap_err();
+        assert_err_contains(&err, "unknown tier");
+    }
+
+    #[test]
+    fn test_only_creator_can_configure_allowlist() {
+        let (env, mut lp) = setup();
+        env.set_timestamp(500);
+        env.set_sender(BOB);
+        let err = lp.set_tier(&env.ctx(), 1, 1_000).unwrap_err();
+        assert_err_contains(&err, "only creator can configure allowlist");
+    }
+
+    #[test]
+    fn test_cannot_configure_allowlist_after_start() {
+        let (env, mut lp) = setup();
+        env.set_timestamp(1000);
+        let err = lp.set_public_start_time(&env.ctx(), 1500).unwrap_err();
+        assert_err_contains(&err, "sale has already started");
+    }
+
+    #[test]
+    fn test_cannot_extend_allowlist_during_allowlist_phase() {
+        let (env, mut lp) = setup();
+        setup_allowlist(&env, &mut lp);
+
+        env.set_timestamp(1200);
+        let err = lp
+            .add_to_allowlist(&env.ctx(), 1, alloc::vec![DAVE])
+            .unwrap_err();
+        assert_err_contains(&err, "sale has already started");
+    }
+
     #[test]
     fn test_only_creator_can_finalize() {
         let (env, mut lp) = setup();
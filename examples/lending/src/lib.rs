@@ -0,0 +1,746 @@
+//! Lending — single-asset money market with NORN collateral.
+//!
+//! Suppliers deposit the market asset and receive shares that appreciate as
+//! borrowers pay interest. Borrowers lock NORN as collateral and borrow up to
+//! the collateral factor. Interest accrues per second at a fixed annual rate.
+//! Positions whose health factor drops below 1 can be liquidated.
+//!
+//! Prices come from the AMM pool's time-weighted average price: the contract
+//! keeps a checkpoint of the pool's price accumulator and derives the TWAP
+//! once at least `twap_window` seconds have passed since the checkpoint.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use norn_sdk::prelude::*;
+
+// ── Storage ──────────────────────────────────────────────────────────────
+
+const INITIALIZED: Item<bool> = Item::new("initialized");
+const CONFIG: Item<LendingConfig> = Item::new("config");
+const MARKET: Item<Market> = Item::new("market");
+const ORACLE: Item<OracleState> = Item::new("oracle");
+const SUPPLY_SHARES: Map<Address, u128> = Map::new("supply_shares");
+const COLLATERAL: Map<Address, u128> = Map::new("collateral");
+const BORROWS: Map<Address, BorrowPosition> = Map::new("borrows");
+
+const NORN: TokenId = [0u8; 32];
+const BPS: u128 = 10_000;
+const SECONDS_PER_YEAR: u128 = 31_536_000;
+/// Fixed-point scale for the borrow index.
+const INDEX_SCALE: u128 = 1_000_000_000_000_000_000;
+/// Fixed-point scale for prices (NORN per asset), matching the AMM pool.
+pub const PRICE_SCALE: u128 = 1_000_000_000_000;
+/// Borsh discriminant of `AmmPool::observe` in the AMM's execute enum.
+const AMM_OBSERVE: u8 = 6;
+
+// ── Types ────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+pub struct LendingConfig {
+    pub owner: Address,
+    pub asset: TokenId,
+    /// AMM pool loom used as the price feed.
+    pub oracle: LoomId,
+    pub pool_id: u64,
+    /// Maximum borrow value as a share of collateral value.
+    pub collateral_factor_bps: u16,
+    /// Collateral share at which a position becomes liquidatable.
+    pub liquidation_threshold_bps: u16,
+    /// Extra collateral paid to liquidators.
+    pub liquidation_bonus_bps: u16,
+    /// Annual borrow rate.
+    pub borrow_rate_bps: u16,
+    pub twap_window: u64,
+}
+
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+pub struct Market {
+    pub total_supply_shares: u128,
+    pub total_borrows: u128,
+    /// Asset held by the contract and available to borrow or withdraw.
+    pub cash: u128,
+    pub borrow_index: u128,
+    pub last_accrual: u64,
+}
+
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+pub struct BorrowPosition {
+    pub principal: u128,
+    /// Borrow index at the time `principal` was last updated.
+    pub index: u128,
+}
+
+/// Mirror of the AMM pool's `PriceObservation`.
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct PriceObservation {
+    pub price_cumulative: u128,
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Clone, Default, BorshSerialize, BorshDeserialize)]
+pub struct OracleState {
+    pub checkpoint: Option<PriceObservation>,
+    /// Last computed TWAP (NORN per asset, scaled by `PRICE_SCALE`).
+    pub price: u128,
+    pub updated_at: u64,
+}
+
+// ── Contract ─────────────────────────────────────────────────────────────
+
+#[norn_contract]
+pub struct Lending;
+
+#[norn_contract]
+impl Lending {
+    #[init]
+    pub fn new(_ctx: &Context) -> Self {
+        INITIALIZED.init(&false);
+        Lending
+    }
+
+    // ── Execute ──────────────────────────────────────────────────────
+
+    #[execute]
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize(
+        &mut self,
+        ctx: &Context,
+        asset: TokenId,
+        oracle: LoomId,
+        pool_id: u64,
+        collateral_factor_bps: u16,
+        liquidation_threshold_bps: u16,
+        liquidation_bonus_bps: u16,
+        borrow_rate_bps: u16,
+        twap_window: u64,
+    ) -> ContractResult {
+        ensure!(!INITIALIZED.load_or(false), "already initialized");
+        ensure!(asset != NORN, "asset must not be NORN");
+        ensure!(
+            collateral_factor_bps > 0,
+            "collateral_factor_bps must be positive"
+        );
+        ensure!(
+            collateral_factor_bps <= liquidation_threshold_bps,
+            "collateral factor must not exceed liquidation threshold"
+        );
+        ensure!(
+            liquidation_threshold_bps as u128 <= BPS,
+            "liquidation threshold cannot exceed 100%"
+        );
+        ensure!(twap_window > 0, "twap_window must be positive");
+
+        CONFIG.save(&LendingConfig {
+            owner: ctx.sender(),
+            asset,
+            oracle,
+            pool_id,
+            collateral_factor_bps,
+            liquidation_threshold_bps,
+            liquidation_bonus_bps,
+            borrow_rate_bps,
+            twap_window,
+        })?;
+        MARKET.save(&Market {
+            total_supply_shares: 0,
+            total_borrows: 0,
+            cash: 0,
+            borrow_index: INDEX_SCALE,
+            last_accrual: ctx.timestamp(),
+        })?;
+        ORACLE.save(&OracleState::default())?;
+        INITIALIZED.save(&true)?;
+
+        Ok(Response::with_action("initialize"))
+    }
+
+    /// Deposit the market asset in exchange for supply shares.
+    #[execute]
+    pub fn supply(&mut self, ctx: &Context, amount: u128) -> ContractResult {
+        ensure!(amount > 0, "amount must be positive");
+        let mut market = load_market(ctx)?;
+        let config = CONFIG.load()?;
+
+        let total_assets = safe_add(market.cash, market.total_borrows)?;
+        let shares = if market.total_supply_shares == 0 || total_assets == 0 {
+            amount
+        } else {
+            safe_mul(amount, market.total_supply_shares)? / total_assets
+        };
+        ensure!(shares > 0, "amount too small");
+
+        let contract = ctx.contract_address();
+        ctx.transfer(&ctx.sender(), &contract, &config.asset, amount);
+
+        let prev = SUPPLY_SHARES.load_or(&ctx.sender(), 0u128);
+        SUPPLY_SHARES.save(&ctx.sender(), &safe_add(prev, shares)?)?;
+        market.total_supply_shares = safe_add(market.total_supply_shares, shares)?;
+        market.cash = safe_add(market.cash, amount)?;
+        MARKET.save(&market)?;
+
+        Ok(Response::with_action("supply")
+            .add_u128("amount", amount)
+            .add_u128("shares", shares))
+    }
+
+    /// Redeem supply shares for the underlying asset plus earned interest.
+    #[execute]
+    pub fn withdraw(&mut self, ctx: &Context, shares: u128) -> ContractResult {
+        ensure!(shares > 0, "shares must be positive");
+        let mut market = load_market(ctx)?;
+        let config = CONFIG.load()?;
+
+        let owned = SUPPLY_SHARES.load_or(&ctx.sender(), 0u128);
+        ensure!(owned >= shares, "insufficient shares");
+
+        let total_assets = safe_add(market.cash, market.total_borrows)?;
+        let amount = safe_mul(shares, total_assets)? / market.total_supply_shares;
+        ensure!(amount <= market.cash, "insufficient liquidity");
+
+        SUPPLY_SHARES.save(&ctx.sender(), &safe_sub(owned, shares)?)?;
+        market.total_supply_shares = safe_sub(market.total_supply_shares, shares)?;
+        market.cash = safe_sub(market.cash, amount)?;
+        MARKET.save(&market)?;
+
+        ctx.transfer_from_contract(&ctx.sender(), &config.asset, amount);
+
+        Ok(Response::with_action("withdraw")
+            .add_u128("shares", shares)
+            .add_u128("amount", amount))
+    }
+
+    #[execute]
+    pub fn deposit_collateral(&mut self, ctx: &Context, amount: u128) -> ContractResult {
+        ensure!(INITIALIZED.load_or(false), "not initialized");
+        ensure!(amount > 0, "amount must be positive");
+
+        let contract = ctx.contract_address();
+        ctx.transfer(&ctx.sender(), &contract, &NORN, amount);
+
+        let prev = COLLATERAL.load_or(&ctx.sender(), 0u128);
+        COLLATERAL.save(&ctx.sender(), &safe_add(prev, amount)?)?;
+
+        Ok(Response::with_action("deposit_collateral").add_u128("amount", amount))
+    }
+
+    /// Withdraw collateral, as long as the remaining position stays within
+    /// the collateral factor.
+    #[execute]
+    pub fn withdraw_collateral(&mut self, ctx: &Context, amount: u128) -> ContractResult {
+        ensure!(amount > 0, "amount must be positive");
+        let market = load_market(ctx)?;
+        let config = CONFIG.load()?;
+
+        let collateral = COLLATERAL.load_or(&ctx.sender(), 0u128);
+        ensure!(collateral >= amount, "insufficient collateral");
+        let remaining = safe_sub(collateral, amount)?;
+
+        let debt = debt_of(&ctx.sender(), &market)?;
+        if debt > 0 {
+            let price = refresh_price(ctx, &config)?;
+            let max_debt = scaled_bps(remaining, config.collateral_factor_bps)?;
+            ensure!(
+                debt_value(debt, price)? <= max_debt,
+                "withdrawal would exceed borrow limit"
+            );
+        }
+        MARKET.save(&market)?;
+        COLLATERAL.save(&ctx.sender(), &remaining)?;
+
+        ctx.transfer_from_contract(&ctx.sender(), &NORN, amount);
+
+        Ok(Response::with_action("withdraw_collateral").add_u128("amount", amount))
+    }
+
+    /// Borrow the market asset against deposited collateral.
+    #[execute]
+    pub fn borrow(&mut self, ctx: &Context, amount: u128) -> ContractResult {
+        ensure!(amount > 0, "amount must be positive");
+        let mut market = load_market(ctx)?;
+        let config = CONFIG.load()?;
+        ensure!(amount <= market.cash, "insufficient liquidity");
+
+        let price = refresh_price(ctx, &config)?;
+        let debt = safe_add(debt_of(&ctx.sender(), &market)?, amount)?;
+        let collateral = COLLATERAL.load_or(&ctx.sender(), 0u128);
+        let max_debt = scaled_bps(collateral, config.collateral_factor_bps)?;
+        ensure!(debt_value(debt, price)? <= max_debt, "exceeds borrow limit");
+
+        BORROWS.save(
+            &ctx.sender(),
+            &BorrowPosition {
+                principal: debt,
+                index: market.borrow_index,
+            },
+        )?;
+        market.total_borrows = safe_add(market.total_borrows, amount)?;
+        market.cash = safe_sub(market.cash, amount)?;
+        MARKET.save(&market)?;
+
+        ctx.transfer_from_contract(&ctx.sender(), &config.asset, amount);
+
+        Ok(Response::with_action("borrow")
+            .add_u128("amount", amount)
+            .add_u128("debt", debt))
+    }
+
+    /// Repay outstanding debt. Repayments above the debt are capped.
+    #[execute]
+    pub fn repay(&mut self, ctx: &Context, amount: u128) -> ContractResult {
+        ensure!(amount > 0, "amount must be positive");
+        let mut market = load_market(ctx)?;
+        let config = CONFIG.load()?;
+
+        let debt = debt_of(&ctx.sender(), &market)?;
+        ensure!(debt > 0, "no outstanding debt");
+        let paid = amount.min(debt);
+
+        let contract = ctx.contract_address();
+        ctx.transfer(&ctx.sender(), &contract, &config.asset, paid);
+        reduce_debt(&ctx.sender(), &mut market, debt, paid)?;
+        MARKET.save(&market)?;
+
+        Ok(Response::with_action("repay")
+            .add_u128("amount", paid)
+            .add_u128("remaining_debt", safe_sub(debt, paid)?))
+    }
+
+    /// Repay part of an unhealthy position's debt in exchange for its
+    /// collateral at the TWAP price plus the liquidation bonus.
+    #[execute]
+    pub fn liquidate(
+        &mut self,
+        ctx: &Context,
+        borrower: Address,
+        repay_amount: u128,
+    ) -> ContractResult {
+        ensure!(repay_amount > 0, "repay_amount must be positive");
+        ensure!(ctx.sender() != borrower, "cannot liquidate own position");
+        let mut market = load_market(ctx)?;
+        let config = CONFIG.load()?;
+        let price = refresh_price(ctx, &config)?;
+
+        let debt = debt_of(&borrower, &market)?;
+        ensure!(debt > 0, "no outstanding debt");
+        let collateral = COLLATERAL.load_or(&borrower, 0u128);
+        let threshold = scaled_bps(collateral, config.liquidation_threshold_bps)?;
+        ensure!(debt_value(debt, price)? > threshold, "position is healthy");
+
+        let paid = repay_amount.min(debt);
+        let bonus = BPS + config.liquidation_bonus_bps as u128;
+        let seized = (safe_mul(debt_value(paid, price)?, bonus)? / BPS).min(collateral);
+
+        let contract = ctx.contract_address();
+        ctx.transfer(&ctx.sender(), &contract, &config.asset, paid);
+        ctx.transfer_from_contract(&ctx.sender(), &NORN, seized);
+
+        reduce_debt(&borrower, &mut market, debt, paid)?;
+        MARKET.save(&market)?;
+        COLLATERAL.save(&borrower, &safe_sub(collateral, seized)?)?;
+
+        Ok(Response::with_action("liquidate")
+            .add_address("borrower", &borrower)
+            .add_u128("repaid", paid)
+            .add_u128("seized", seized))
+    }
+
+    /// Roll the oracle checkpoint forward. Callable by anyone.
+    #[execute]
+    pub fn update_price(&mut self, ctx: &Context) -> ContractResult {
+        let config = CONFIG.load()?;
+        let oracle = roll_oracle(ctx, &config)?;
+        Ok(Response::with_action("update_price")
+            .add_u128("price", oracle.price)
+            .set_data(&oracle.price))
+    }
+
+    // ── Query ────────────────────────────────────────────────────────
+
+    #[query]
+    pub fn get_config(&self, _ctx: &Context) -> ContractResult {
+        let config = CONFIG.load()?;
+        ok(config)
+    }
+
+    #[query]
+    pub fn get_market(&self, ctx: &Context) -> ContractResult {
+        let market = load_market(ctx)?;
+        ok(market)
+    }
+
+    #[query]
+    pub fn get_price(&self, _ctx: &Context) -> ContractResult {
+        let oracle = ORACLE.load_or(OracleState::default());
+        ok(oracle)
+    }
+
+    /// Returns `(shares, underlying_amount)`.
+    #[query]
+    pub fn get_supply_balance(&self, ctx: &Context, addr: Address) -> ContractResult {
+        let market = load_market(ctx)?;
+        let shares = SUPPLY_SHARES.load_or(&addr, 0u128);
+        let total_assets = safe_add(market.cash, market.total_borrows)?;
+        let amount = safe_mul(shares, total_assets)?
+            .checked_div(market.total_supply_shares)
+            .unwrap_or(0);
+        ok((shares, amount))
+    }
+
+    #[query]
+    pub fn get_collateral(&self, _ctx: &Context, addr: Address) -> ContractResult {
+        let collateral = COLLATERAL.load_or(&addr, 0u128);
+        ok(collateral)
+    }
+
+    #[query]
+    pub fn get_debt(&self, ctx: &Context, addr: Address) -> ContractResult {
+        let market = load_market(ctx)?;
+        ok(debt_of(&addr, &market)?)
+    }
+
+    /// Health factor scaled by `BPS` (10_000 = 1.0) at the last TWAP price.
+    /// Positions without debt report `u128::MAX`.
+    #[query]
+    pub fn get_health_factor(&self, ctx: &Context, addr: Address) -> ContractResult {
+        let market = load_market(ctx)?;
+        let config = CONFIG.load()?;
+        let oracle = ORACLE.load_or(OracleState::default());
+
+        let debt = debt_value(debt_of(&addr, &market)?, oracle.price)?;
+        if debt == 0 {
+            return ok(u128::MAX);
+        }
+        let collateral = COLLATERAL.load_or(&addr, 0u128);
+        let threshold = scaled_bps(collateral, config.liquidation_threshold_bps)?;
+        ok(safe_mul(threshold, BPS)? / debt)
+    }
+}
+
+// ── Helpers ──────────────────────────────────────────────────────────────
+
+/// Load the market with interest accrued up to the current timestamp.
+fn load_market(ctx: &Context) -> Result<Market, ContractError> {
+    ensure!(INITIALIZED.load_or(false), "not initialized");
+    let config = CONFIG.load()?;
+    let mut market = MARKET.load()?;
+    accrue_interest(&mut market, &config, ctx.timestamp())?;
+    Ok(market)
+}
+
+fn accrue_interest(
+    market: &mut Market,
+    config: &LendingConfig,
+    now: u64,
+) -> Result<(), ContractError> {
+    if now <= market.last_accrual {
+        return Ok(());
+    }
+    let elapsed = (now - market.last_accrual) as u128;
+    market.last_accrual = now;
+    if market.total_borrows == 0 {
+        return Ok(());
+    }
+
+    // Interest factor for the elapsed period, scaled by INDEX_SCALE
+    let factor = safe_mul(
+        safe_mul(config.borrow_rate_bps as u128, elapsed)?,
+        INDEX_SCALE,
+    )? / (BPS * SECONDS_PER_YEAR);
+    let interest = safe_mul(market.total_borrows, factor)? / INDEX_SCALE;
+    market.total_borrows = safe_add(market.total_borrows, interest)?;
+    market.borrow_index = safe_add(
+        market.borrow_index,
+        safe_mul(market.borrow_index, factor)? / INDEX_SCALE,
+    )?;
+    Ok(())
+}
+
+fn debt_of(addr: &Address, market: &Market) -> Result<u128, ContractError> {
+    match BORROWS.load(addr) {
+        Ok(pos) if pos.principal > 0 => {
+            Ok(safe_mul(pos.principal, market.borrow_index)? / pos.index)
+        }
+        _ => Ok(0),
+    }
+}
+
+fn reduce_debt(
+    addr: &Address,
+    market: &mut Market,
+    debt: u128,
+    paid: u128,
+) -> Result<(), ContractError> {
+    BORROWS.save(
+        addr,
+        &BorrowPosition {
+            principal: safe_sub(debt, paid)?,
+            index: market.borrow_index,
+        },
+    )?;
+    // Per-position rounding can leave total_borrows slightly below the sum
+    market.total_borrows = market.total_borrows.saturating_sub(paid);
+    market.cash = safe_add(market.cash, paid)?;
+    Ok(())
+}
+
+/// Value of `amount` of the market asset in NORN.
+fn debt_value(amount: u128, price: u128) -> Result<u128, ContractError> {
+    Ok(safe_mul(amount, price)? / PRICE_SCALE)
+}
+
+fn scaled_bps(amount: u128, bps: u16) -> Result<u128, ContractError> {
+    Ok(safe_mul(amount, bps as u128)? / BPS)
+}
+
+/// Fetch the AMM pool's current price accumulator via a cross-contract call.
+fn observe_pool(ctx: &Context, config: &LendingConfig) -> Result<PriceObservation, ContractError> {
+    let mut input = Vec::from([AMM_OBSERVE]);
+    input.extend_from_slice(&config.pool_id.to_le_bytes());
+    let output = ctx
+        .call_contract_raw(&config.oracle, &input)
+        .ok_or("oracle call failed")?;
+    PriceObservation::try_from_slice(&output)
+        .map_err(|_| ContractError::Custom("invalid oracle response".into()))
+}
+
+/// Take a fresh observation and recompute the TWAP if the checkpoint is at
+/// least `twap_window` seconds old.
+fn roll_oracle(ctx: &Context, config: &LendingConfig) -> Result<OracleState, ContractError> {
+    let mut oracle = ORACLE.load_or(OracleState::default());
+    let obs = observe_pool(ctx, config)?;
+    match &oracle.checkpoint {
+        None => oracle.checkpoint = Some(obs),
+        Some(cp) if obs.timestamp >= cp.timestamp + config.twap_window => {
+            let elapsed = (obs.timestamp - cp.timestamp) as u128;
+            oracle.price = obs.price_cumulative.wrapping_sub(cp.price_cumulative) / elapsed;
+            oracle.updated_at = obs.timestamp;
+            oracle.checkpoint = Some(obs);
+        }
+        Some(_) => {}
+    }
+    ORACLE.save(&oracle)?;
+    Ok(oracle)
+}
+
+fn refresh_price(ctx: &Context, config: &LendingConfig) -> Result<u128, ContractError> {
+    let oracle = roll_oracle(ctx, config)?;
+    ensure!(oracle.price > 0, "oracle price not available");
+    Ok(oracle.price)
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use core::cell::RefCell;
+    use norn_sdk::host;
+    use norn_sdk::testing::*;
+    use std::rc::Rc;
+
+    const ASSET: TokenId = [7u8; 32];
+    const AMM: LoomId = [5u8; 32];
+    const CONTRACT_ADDR: Address = [99u8; 20];
+
+    /// Stand-in for the AMM pool: accumulates `price * seconds` like the
+    /// real pool and answers `observe` cross-calls.
+    struct MockAmm {
+        price: u128,
+        cumulative: u128,
+        last: u64,
+    }
+
+    fn set_mock_amm(start: u64, price: u128) -> Rc<RefCell<MockAmm>> {
+        let amm = Rc::new(RefCell::new(MockAmm {
+            price,
+            cumulative: 0,
+            last: start,
+        }));
+        let handle = amm.clone();
+        host::mock_set_cross_call_handler(move |target, input| {
+            assert_eq!(target, &AMM);
+            assert_eq!(input[0], AMM_OBSERVE);
+            let mut amm = handle.borrow_mut();
+            let now = host::timestamp();
+            amm.cumulative += amm.price * (now - amm.last) as u128;
+            amm.last = now;
+            let obs = PriceObservation {
+                price_cumulative: amm.cumulative,
+                timestamp: now,
+            };
+            Some(borsh::to_vec(&obs).unwrap())
+        });
+        amm
+    }
+
+    fn set_price(amm: &Rc<RefCell<MockAmm>>, price: u128) {
+        let mut amm = amm.borrow_mut();
+        let now = host::timestamp();
+        amm.cumulative += amm.price * (now - amm.last) as u128;
+        amm.last = now;
+        amm.price = price;
+    }
+
+    /// Market: 75% collateral factor, 80% liquidation threshold, 5% bonus,
+    /// 10% APR, 60s TWAP window. BOB supplies 10_000, price is 2 NORN/asset.
+    fn setup() -> (TestEnv, Lending, Rc<RefCell<MockAmm>>) {
+        let env = TestEnv::new()
+            .with_sender(ALICE)
+            .with_timestamp(1000)
+            .with_contract_address(CONTRACT_ADDR);
+        let amm = set_mock_amm(0, 2 * PRICE_SCALE);
+        let mut lending = Lending::new(&env.ctx());
+        lending
+            .initialize(&env.ctx(), ASSET, AMM, 0, 7_500, 8_000, 500, 1_000, 60)
+            .unwrap();
+        lending.update_price(&env.ctx()).unwrap();
+
+        env.set_sender(BOB);
+        lending.supply(&env.ctx(), 10_000).unwrap();
+
+        env.set_timestamp(1060);
+        lending.update_price(&env.ctx()).unwrap();
+        (env, lending, amm)
+    }
+
+    #[test]
+    fn test_twap_warmup() {
+        let env = TestEnv::new()
+            .with_sender(ALICE)
+            .with_timestamp(1000)
+            .with_contract_address(CONTRACT_ADDR);
+        set_mock_amm(0, 2 * PRICE_SCALE);
+        let mut lending = Lending::new(&env.ctx());
+        lending
+            .initialize(&env.ctx(), ASSET, AMM, 0, 7_500, 8_000, 500, 1_000, 60)
+            .unwrap();
+        lending.supply(&env.ctx(), 1_000).unwrap();
+        lending.deposit_collateral(&env.ctx(), 1_000).unwrap();
+
+        // First observation only sets the checkpoint
+        let err = lending.borrow(&env.ctx(), 10).unwrap_err();
+        assert_err_contains(&err, "oracle price not available");
+
+        env.set_timestamp(1060);
+        lending.borrow(&env.ctx(), 10).unwrap();
+        let oracle: OracleState = from_response(&lending.get_price(&env.ctx()).unwrap()).unwrap();
+        assert_eq!(oracle.price, 2 * PRICE_SCALE);
+    }
+
+    #[test]
+    fn test_supply_and_withdraw() {
+        let (env, mut lending, _amm) = setup();
+        let resp = lending.get_supply_balance(&env.ctx(), BOB).unwrap();
+        let (shares, amount): (u128, u128) = from_response(&resp).unwrap();
+        assert_eq!((shares, amount), (10_000, 10_000));
+
+        let resp = lending.withdraw(&env.ctx(), 4_000).unwrap();
+        assert_attribute(&resp, "amount", "4000");
+
+        let err = lending.withdraw(&env.ctx(), 7_000).unwrap_err();
+        assert_err_contains(&err, "insufficient shares");
+    }
+
+    #[test]
+    fn test_borrow_within_collateral_factor() {
+        let (env, mut lending, _amm) = setup();
+        env.set_sender(CHARLIE);
+        lending.deposit_collateral(&env.ctx(), 2_000).unwrap();
+
+        // 2_000 NORN * 75% = 1_500 NORN = 750 asset at 2 NORN/asset
+        let err = lending.borrow(&env.ctx(), 751).unwrap_err();
+        assert_err_contains(&err, "exceeds borrow limit");
+
+        env.clear_transfers();
+        lending.borrow(&env.ctx(), 750).unwrap();
+        let transfers = env.transfers();
+        assert_eq!(transfers[0].1, CHARLIE.to_vec());
+        assert_eq!(transfers[0].2, ASSET.to_vec());
+        assert_eq!(transfers[0].3, 750);
+
+        let err = lending.withdraw_collateral(&env.ctx(), 1).unwrap_err();
+        assert_err_contains(&err, "withdrawal would exceed borrow limit");
+    }
+
+    #[test]
+    fn test_interest_accrues_per_second() {
+        let (env, mut lending, _amm) = setup();
+        env.set_sender(CHARLIE);
+        lending.deposit_collateral(&env.ctx(), 100_000).unwrap();
+        lending.borrow(&env.ctx(), 5_000).unwrap();
+
+        // One year at 10% APR
+        env.set_timestamp(1060 + SECONDS_PER_YEAR as u64);
+        let debt: u128 = from_response(&lending.get_debt(&env.ctx(), CHARLIE).unwrap()).unwrap();
+        assert_eq!(debt, 5_500);
+
+        // Suppliers earn the interest
+        let resp = lending.get_supply_balance(&env.ctx(), BOB).unwrap();
+        let (_, amount): (u128, u128) = from_response(&resp).unwrap();
+        assert_eq!(amount, 10_500);
+
+        let resp = lending.repay(&env.ctx(), 10_000).unwrap();
+        assert_attribute(&resp, "amount", "5500");
+        assert_attribute(&resp, "remaining_debt", "0");
+
+        env.set_sender(BOB);
+        let resp = lending.withdraw(&env.ctx(), 10_000).unwrap();
+        assert_attribute(&resp, "amount", "10500");
+    }
+
+    #[test]
+    fn test_liquidation_on_price_drop() {
+        let (env, mut lending, amm) = setup();
+        env.set_sender(CHARLIE);
+        lending.deposit_collateral(&env.ctx(), 2_000).unwrap();
+        lending.borrow(&env.ctx(), 750).unwrap();
+
+        env.set_sender(DAVE);
+        let err = lending.liquidate(&env.ctx(), CHARLIE, 100).unwrap_err();
+        assert_err_contains(&err, "position is healthy");
+
+        // Asset price rises to 3 NORN: debt 2_250 NORN > 80% of 2_000
+        set_price(&amm, 3 * PRICE_SCALE);
+        env.set_timestamp(1120);
+        lending.update_price(&env.ctx()).unwrap();
+        let hf: u128 =
+            from_response(&lending.get_health_factor(&env.ctx(), CHARLIE).unwrap()).unwrap();
+        assert!(hf < BPS);
+
+        env.clear_transfers();
+        let resp = lending.liquidate(&env.ctx(), CHARLIE, 300).unwrap();
+        // 300 asset * 3 NORN * 1.05 = 945 NORN
+        assert_attribute(&resp, "seized", "945");
+        let transfers = env.transfers();
+        assert_eq!(transfers[0].0, DAVE.to_vec());
+        assert_eq!(transfers[0].3, 300);
+        assert_eq!(transfers[1].1, DAVE.to_vec());
+        assert_eq!(transfers[1].2, NORN.to_vec());
+
+        let debt: u128 = from_response(&lending.get_debt(&env.ctx(), CHARLIE).unwrap()).unwrap();
+        assert_eq!(debt, 450);
+        let collateral: u128 =
+            from_response(&lending.get_collateral(&env.ctx(), CHARLIE).unwrap()).unwrap();
+        assert_eq!(collateral, 1_055);
+    }
+
+    #[test]
+    fn test_initialize_validation() {
+        let env = TestEnv::new().with_sender(ALICE);
+        let mut lending = Lending::new(&env.ctx());
+        let err = lending
+            .initialize(&env.ctx(), ASSET, AMM, 0, 9_000, 8_000, 500, 1_000, 60)
+            .unwrap_err();
+        assert_err_contains(
+            &err,
+            "collateral factor must not exceed liquidation threshold",
+        );
+        let err = lending.supply(&env.ctx(), 100).unwrap_err();
+        assert_err_contains(&err, "not initialized");
+    }
+}
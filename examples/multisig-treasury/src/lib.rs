@@ -1,6 +1,10 @@
 //! Multisig Treasury contract — shared funds requiring N-of-M approvals
 //! for outgoing transfers. Supports proposals, approvals, rejections,
 //! deposits, and automatic execution when threshold is met.
+//!
+//! Once an owner sets the network's chain ID, owners can also approve by
+//! signing [`APPROVAL_TYPE`] off-chain in the `norn-multisig` domain; anyone
+//! can then submit the signatures with `approve_signed`.
 
 #![no_std]
 
@@ -15,6 +19,14 @@ const CONFIG: Item<TreasuryConfig> = Item::new("config");
 const PROPOSAL_COUNT: Item<u64> = Item::new("prop_count");
 const PROPOSALS: Map<u64, Proposal> = Map::new("proposals");
 const APPROVALS: Map<(u64, [u8; 20]), bool> = Map::new("approvals");
+const CHAIN_ID: Item<String> = Item::new("chain_id");
+
+/// Typed-data domain name of signed approvals.
+pub const APPROVAL_DOMAIN_NAME: &str = "norn-multisig";
+/// Typed-data domain version of signed approvals.
+pub const APPROVAL_DOMAIN_VERSION: &str = "1";
+/// Typed-data type of a signed approval.
+pub const APPROVAL_TYPE: &str = "Approval(u64 proposal,address to,bytes32 token_id,u128 amount)";
 
 // ── Types ───────────────────────────────────────────────────────────────
 
@@ -65,6 +77,38 @@ fn has_duplicates(owners: &[Address]) -> bool {
     false
 }
 
+/// Count `owner`'s approval of `proposal`, executing it once the threshold
+/// is met.
+fn record_approval(
+    ctx: &Context,
+    config: &TreasuryConfig,
+    mut proposal: Proposal,
+    owner: &Address,
+) -> Result<Proposal, ContractError> {
+    ensure!(
+        proposal.status == ProposalStatus::Proposed,
+        "proposal is not in Proposed status"
+    );
+    ensure!(ctx.timestamp() < proposal.deadline, "proposal has expired");
+
+    let key = (proposal.id, *owner);
+    let already = APPROVALS.load(&key).unwrap_or(false);
+    ensure!(!already, "already approved");
+
+    APPROVALS.save(&key, &true)?;
+    proposal.approval_count = safe_add_u64(proposal.approval_count, 1)?;
+
+    // Auto-execute if threshold met
+    if proposal.approval_count >= config.required_approvals {
+        let contract = ctx.contract_address();
+        ctx.transfer(&contract, &proposal.to, &proposal.token_id, proposal.amount);
+        proposal.status = ProposalStatus::Executed;
+    }
+
+    PROPOSALS.save(&proposal.id, &proposal)?;
+    Ok(proposal)
+}
+
 // ── Contract ────────────────────────────────────────────────────────────
 
 #[norn_contract]
@@ -151,37 +195,72 @@ impl MultisigTreasury {
         let config = CONFIG.load()?;
         ensure!(is_owner(&config, &ctx.sender()), "only owners can approve");
 
-        let mut proposal = PROPOSALS.load(&proposal_id)?;
-        ensure!(
-            proposal.status == ProposalStatus::Proposed,
-            "proposal is not in Proposed status"
-        );
-        ensure!(
-            ctx.timestamp() < proposal.deadline,
-            "proposal has expired"
-        );
-
-        let key = (proposal_id, ctx.sender());
-        let already = APPROVALS.load(&key).unwrap_or(false);
-        ensure!(!already, "already approved");
+        let proposal = PROPOSALS.load(&proposal_id)?;
+        let proposal = record_approval(ctx, &config, proposal, &ctx.sender())?;
 
-        APPROVALS.save(&key, &true)?;
-        proposal.approval_count = safe_add_u64(proposal.approval_count, 1)?;
+        Ok(Response::with_action("approve")
+            .add_attribute("proposal_id", format!("{}", proposal_id))
+            .add_attribute("approval_count", format!("{}", proposal.approval_count)))
+    }
 
-        // Auto-execute if threshold met
-        if proposal.approval_count >= config.required_approvals {
-            let contract = ctx.contract_address();
-            ctx.transfer(&contract, &proposal.to, &proposal.token_id, proposal.amount);
-            proposal.status = ProposalStatus::Executed;
-        }
+    /// Record an approval signed off-chain by an owner. Anyone may submit it.
+    #[execute]
+    pub fn approve_signed(
+        &mut self,
+        ctx: &Context,
+        proposal_id: u64,
+        owner_pubkey: [u8; 32],
+        signature: [u8; 64],
+    ) -> ContractResult {
+        let chain_id = CHAIN_ID
+            .load()
+            .map_err(|_| ContractError::custom("signed approvals are not enabled"))?;
+        let config = CONFIG.load()?;
+        let owner = pubkey_to_addr(&owner_pubkey);
+        ensure!(is_owner(&config, &owner), "only owners can approve");
 
-        PROPOSALS.save(&proposal_id, &proposal)?;
+        let proposal = PROPOSALS.load(&proposal_id)?;
+        let domain = TypedDomain::new(
+            APPROVAL_DOMAIN_NAME,
+            APPROVAL_DOMAIN_VERSION,
+            &chain_id,
+            ctx.contract_address(),
+        );
+        let approval = TypedStruct::new(APPROVAL_TYPE)
+            .u64(proposal_id)
+            .address(&proposal.to)
+            .bytes32(&proposal.token_id)
+            .u128(proposal.amount);
+        ensure!(
+            ctx.verify_typed(&domain, &approval, &owner_pubkey, &signature),
+            "invalid approval signature"
+        );
+        let proposal = record_approval(ctx, &config, proposal, &owner)?;
 
-        Ok(Response::with_action("approve")
+        Ok(Response::with_action("approve_signed")
             .add_attribute("proposal_id", format!("{}", proposal_id))
+            .add_attribute("owner", addr_to_hex(&owner))
             .add_attribute("approval_count", format!("{}", proposal.approval_count)))
     }
 
+    /// Set the chain ID signed approvals are bound to. Can only be set once.
+    #[execute]
+    pub fn set_chain_id(&mut self, ctx: &Context, chain_id: String) -> ContractResult {
+        let config = CONFIG.load()?;
+        ensure!(
+            is_owner(&config, &ctx.sender()),
+            "only owners can set the chain id"
+        );
+        ensure!(!CHAIN_ID.exists(), "chain id already set");
+        ensure!(
+            !chain_id.is_empty() && chain_id.len() <= 64,
+            "chain id must be 1-64 bytes"
+        );
+        CHAIN_ID.save(&chain_id)?;
+
+        Ok(Response::with_action("set_chain_id").add_attribute("chain_id", chain_id))
+    }
+
     #[execute]
     pub fn reject(&mut self, ctx: &Context, proposal_id: u64) -> ContractResult {
         let config = CONFIG.load()?;
@@ -263,6 +342,11 @@ impl MultisigTreasury {
         ok(config)
     }
 
+    #[query]
+    pub fn get_chain_id(&self, _ctx: &Context) -> ContractResult {
+        ok(CHAIN_ID.load().ok())
+    }
+
     #[query]
     pub fn get_proposal(&self, _ctx: &Context, proposal_id: u64) -> ContractResult {
         let proposal = PROPOSALS.load(&proposal_id)?;
@@ -516,4 +600,117 @@ mod tests {
         let count: u64 = from_response(&resp).unwrap();
         assert_eq!(count, 2);
     }
+
+    fn signer() -> norn_crypto::keys::Keypair {
+        norn_crypto::keys::Keypair::from_seed(&[9u8; 32])
+    }
+
+    /// A 2-of-2 treasury owned by ALICE and `signer()`.
+    fn setup_signed() -> (TestEnv, MultisigTreasury, u64) {
+        let env = TestEnv::new()
+            .with_sender(ALICE)
+            .with_timestamp(1000)
+            .with_contract_address(CONTRACT_ADDR);
+        let signer_addr = pubkey_to_addr(&signer().public_key());
+        let mut treasury = MultisigTreasury::new(&env.ctx());
+        treasury
+            .initialize(
+                &env.ctx(),
+                vec![ALICE, signer_addr],
+                2,
+                String::from("Team Treasury"),
+            )
+            .unwrap();
+        let id = create_proposal(&env, &mut treasury);
+        (env, treasury, id)
+    }
+
+    fn sign_approval(proposal_id: u64, amount: u128, chain_id: &str) -> [u8; 64] {
+        let domain = TypedDomain::new(
+            APPROVAL_DOMAIN_NAME,
+            APPROVAL_DOMAIN_VERSION,
+            chain_id,
+            CONTRACT_ADDR,
+        );
+        let approval = TypedStruct::new(APPROVAL_TYPE)
+            .u64(proposal_id)
+            .address(&CHARLIE)
+            .bytes32(&TOKEN)
+            .u128(amount);
+        signer().sign(&domain.signing_hash(&approval))
+    }
+
+    #[test]
+    fn test_approve_signed_executes() {
+        let (env, mut treasury, id) = setup_signed();
+        treasury
+            .set_chain_id(&env.ctx(), "norn-dev".into())
+            .unwrap();
+        treasury.approve(&env.ctx(), id).unwrap();
+
+        env.set_sender(CHARLIE);
+        let signature = sign_approval(id, 1000, "norn-dev");
+        treasury
+            .approve_signed(&env.ctx(), id, signer().public_key(), signature)
+            .unwrap();
+
+        let resp = treasury.get_proposal(&env.ctx(), id).unwrap();
+        let proposal: Proposal = from_response(&resp).unwrap();
+        assert_eq!(proposal.approval_count, 2);
+        assert_eq!(proposal.status, ProposalStatus::Executed);
+    }
+
+    #[test]
+    fn test_approve_signed_rejects_bad_signatures() {
+        let (env, mut treasury, id) = setup_signed();
+        let pubkey = signer().public_key();
+        let signature = sign_approval(id, 1000, "norn-dev");
+        let err = treasury
+            .approve_signed(&env.ctx(), id, pubkey, signature)
+            .unwrap_err();
+        assert_eq!(err.message(), "signed approvals are not enabled");
+
+        treasury
+            .set_chain_id(&env.ctx(), "norn-dev".into())
+            .unwrap();
+        let err = treasury
+            .approve_signed(&env.ctx(), id, pubkey, sign_approval(id, 999, "norn-dev"))
+            .unwrap_err();
+        assert_eq!(err.message(), "invalid approval signature");
+        let err = treasury
+            .approve_signed(
+                &env.ctx(),
+                id,
+                pubkey,
+                sign_approval(id, 1000, "norn-mainnet"),
+            )
+            .unwrap_err();
+        assert_eq!(err.message(), "invalid approval signature");
+
+        treasury
+            .approve_signed(&env.ctx(), id, pubkey, signature)
+            .unwrap();
+        let err = treasury
+            .approve_signed(&env.ctx(), id, pubkey, signature)
+            .unwrap_err();
+        assert_eq!(err.message(), "already approved");
+    }
+
+    #[test]
+    fn test_approve_signed_requires_owner_key() {
+        let (env, mut treasury, id) = setup_signed();
+        treasury
+            .set_chain_id(&env.ctx(), "norn-dev".into())
+            .unwrap();
+        let stranger = norn_crypto::keys::Keypair::from_seed(&[10u8; 32]);
+        let err = treasury
+            .approve_signed(&env.ctx(), id, stranger.public_key(), [0u8; 64])
+            .unwrap_err();
+        assert_eq!(err.message(), "only owners can approve");
+
+        let err = treasury
+            .set_chain_id(&env.ctx(), "norn-mainnet".into())
+            .unwrap_err();
+        assert_eq!(err.message(), "chain id already set");
+    }
 }
@@ -0,0 +1,535 @@
+//! Name Service — loom-based name registry with expiry and subdomains.
+//!
+//! Mirrors the node's native name registry, but entirely in contract storage:
+//! names are leased for whole years, renewable during a grace period after
+//! expiry, and carry resolver records (an address plus text records).
+//! Registration fees go to a configurable fee sink (the zero address burns).
+//! Owners of a top-level name can delegate subdomains, which share the
+//! parent's expiry.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use norn_sdk::prelude::*;
+
+// ── Storage ──────────────────────────────────────────────────────────────
+
+const INITIALIZED: Item<bool> = Item::new("initialized");
+const CONFIG: Item<NameServiceConfig> = Item::new("config");
+const NAMES: Map<String, NameRecord> = Map::new("names");
+
+const NORN: TokenId = [0u8; 32];
+const SECONDS_PER_YEAR: u64 = 31_536_000;
+const MAX_YEARS: u64 = 10;
+const MAX_TEXT_RECORDS: usize = 16;
+const MAX_TEXT_KEY_LEN: usize = 32;
+const MAX_TEXT_VALUE_LEN: usize = 256;
+
+// ── Types ────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+pub struct NameServiceConfig {
+    pub owner: Address,
+    /// Registration and renewal fee per year, in NORN.
+    pub annual_fee: u128,
+    /// Seconds after expiry during which the name can still be renewed
+    /// but not registered by someone else.
+    pub grace_period: u64,
+    /// Recipient of fees; `ZERO_ADDRESS` burns them.
+    pub fee_sink: Address,
+}
+
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+pub struct NameRecord {
+    pub name: String,
+    pub owner: Address,
+    /// Address the name resolves to.
+    pub resolved: Address,
+    /// Expiry for top-level names; subdomains follow their parent.
+    pub expires_at: u64,
+    pub registered_at: u64,
+    /// Parent name for subdomains.
+    pub parent: Option<String>,
+    pub text: Vec<(String, String)>,
+}
+
+// ── Contract ─────────────────────────────────────────────────────────────
+
+#[norn_contract]
+pub struct NameService;
+
+#[norn_contract]
+impl NameService {
+    #[init]
+    pub fn new(_ctx: &Context) -> Self {
+        INITIALIZED.init(&false);
+        NameService
+    }
+
+    // ── Execute ──────────────────────────────────────────────────────
+
+    #[execute]
+    pub fn initialize(
+        &mut self,
+        ctx: &Context,
+        annual_fee: u128,
+        grace_period: u64,
+        fee_sink: Address,
+    ) -> ContractResult {
+        ensure!(!INITIALIZED.load_or(false), "already initialized");
+        CONFIG.save(&NameServiceConfig {
+            owner: ctx.sender(),
+            annual_fee,
+            grace_period,
+            fee_sink,
+        })?;
+        INITIALIZED.save(&true)?;
+
+        Ok(Response::with_action("initialize")
+            .add_u128("annual_fee", annual_fee)
+            .add_address("fee_sink", &fee_sink))
+    }
+
+    /// Register a top-level name for `years` years. Names past their grace
+    /// period can be registered again by anyone.
+    #[execute]
+    pub fn register(&mut self, ctx: &Context, name: String, years: u64) -> ContractResult {
+        let config = load_config()?;
+        validate_label(&name)?;
+        ensure!(years > 0 && years <= MAX_YEARS, "years must be 1-10");
+
+        let now = ctx.timestamp();
+        if let Ok(existing) = NAMES.load(&name) {
+            ensure!(
+                now >= safe_add_u64(existing.expires_at, config.grace_period)?,
+                "name is taken"
+            );
+        }
+
+        let fee = charge_fee(ctx, &config, years)?;
+        let expires_at = safe_add_u64(now, years * SECONDS_PER_YEAR)?;
+        NAMES.save(
+            &name,
+            &NameRecord {
+                name: name.clone(),
+                owner: ctx.sender(),
+                resolved: ctx.sender(),
+                expires_at,
+                registered_at: now,
+                parent: None,
+                text: Vec::new(),
+            },
+        )?;
+
+        Ok(Response::with_action("register")
+            .add_attribute("name", name)
+            .add_address("owner", &ctx.sender())
+            .add_u128("fee", fee)
+            .add_attribute("expires_at", format!("{}", expires_at)))
+    }
+
+    /// Extend a top-level name by `years` years. Anyone can pay for a
+    /// renewal, but only until the grace period runs out.
+    #[execute]
+    pub fn renew(&mut self, ctx: &Context, name: String, years: u64) -> ContractResult {
+        let config = load_config()?;
+        ensure!(years > 0 && years <= MAX_YEARS, "years must be 1-10");
+        let mut record = NAMES.load(&name)?;
+        ensure!(
+            record.parent.is_none(),
+            "subdomains expire with their parent"
+        );
+        ensure!(
+            ctx.timestamp() < safe_add_u64(record.expires_at, config.grace_period)?,
+            "grace period has ended"
+        );
+
+        let fee = charge_fee(ctx, &config, years)?;
+        record.expires_at = safe_add_u64(record.expires_at, years * SECONDS_PER_YEAR)?;
+        NAMES.save(&name, &record)?;
+
+        Ok(Response::with_action("renew")
+            .add_attribute("name", name)
+            .add_u128("fee", fee)
+            .add_attribute("expires_at", format!("{}", record.expires_at)))
+    }
+
+    /// Create or reassign `label.parent`. Only the parent's owner can
+    /// delegate, and delegation can be revoked by reassigning.
+    #[execute]
+    pub fn set_subdomain(
+        &mut self,
+        ctx: &Context,
+        parent: String,
+        label: String,
+        owner: Address,
+    ) -> ContractResult {
+        validate_label(&label)?;
+        let parent_record = load_active(ctx, &parent)?;
+        ensure!(
+            parent_record.parent.is_none(),
+            "only one level of subdomains"
+        );
+        ensure!(
+            parent_record.owner == ctx.sender(),
+            "only parent owner can delegate"
+        );
+
+        let full = format!("{}.{}", label, parent);
+        NAMES.save(
+            &full,
+            &NameRecord {
+                name: full.clone(),
+                owner,
+                resolved: owner,
+                expires_at: 0,
+                registered_at: ctx.timestamp(),
+                parent: Some(parent),
+                text: Vec::new(),
+            },
+        )?;
+
+        Ok(Response::with_action("set_subdomain")
+            .add_attribute("name", full)
+            .add_address("owner", &owner))
+    }
+
+    #[execute]
+    pub fn set_address(&mut self, ctx: &Context, name: String, addr: Address) -> ContractResult {
+        let mut record = load_active(ctx, &name)?;
+        ensure!(
+            record.owner == ctx.sender(),
+            "only owner can update records"
+        );
+        record.resolved = addr;
+        NAMES.save(&name, &record)?;
+
+        Ok(Response::with_action("set_address")
+            .add_attribute("name", name)
+            .add_address("resolved", &addr))
+    }
+
+    /// Set a text record. An empty value removes the key.
+    #[execute]
+    pub fn set_text(
+        &mut self,
+        ctx: &Context,
+        name: String,
+        key: String,
+        value: String,
+    ) -> ContractResult {
+        let mut record = load_active(ctx, &name)?;
+        ensure!(
+            record.owner == ctx.sender(),
+            "only owner can update records"
+        );
+        ensure!(
+            !key.is_empty() && key.len() <= MAX_TEXT_KEY_LEN,
+            "key must be 1-32 bytes"
+        );
+        ensure!(value.len() <= MAX_TEXT_VALUE_LEN, "value too long");
+
+        record.text.retain(|(k, _)| *k != key);
+        if !value.is_empty() {
+            ensure!(
+                record.text.len() < MAX_TEXT_RECORDS,
+                "too many text records"
+            );
+            record.text.push((key.clone(), value));
+        }
+        NAMES.save(&name, &record)?;
+
+        Ok(Response::with_action("set_text")
+            .add_attribute("name", name)
+            .add_attribute("key", key))
+    }
+
+    #[execute]
+    pub fn transfer(&mut self, ctx: &Context, name: String, to: Address) -> ContractResult {
+        let mut record = load_active(ctx, &name)?;
+        ensure!(record.owner == ctx.sender(), "only owner can transfer");
+        record.owner = to;
+        NAMES.save(&name, &record)?;
+
+        Ok(Response::with_action("transfer")
+            .add_attribute("name", name)
+            .add_address("to", &to))
+    }
+
+    // ── Query ────────────────────────────────────────────────────────
+
+    #[query]
+    pub fn get_config(&self, _ctx: &Context) -> ContractResult {
+        let config = load_config()?;
+        ok(config)
+    }
+
+    #[query]
+    pub fn get_name(&self, _ctx: &Context, name: String) -> ContractResult {
+        let record = NAMES.load(&name)?;
+        ok(record)
+    }
+
+    /// Resolve a name to its address. Expired names do not resolve.
+    #[query]
+    pub fn resolve(&self, ctx: &Context, name: String) -> ContractResult {
+        let record = load_active(ctx, &name)?;
+        ok(record.resolved)
+    }
+
+    #[query]
+    pub fn get_text(&self, ctx: &Context, name: String, key: String) -> ContractResult {
+        let record = load_active(ctx, &name)?;
+        let value = record
+            .text
+            .into_iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| v);
+        ok(value)
+    }
+
+    /// Whether a top-level name can be registered right now.
+    #[query]
+    pub fn is_available(&self, ctx: &Context, name: String) -> ContractResult {
+        let config = load_config()?;
+        let available = match NAMES.load(&name) {
+            Ok(record) => ctx.timestamp() >= safe_add_u64(record.expires_at, config.grace_period)?,
+            Err(_) => validate_label(&name).is_ok(),
+        };
+        ok(available)
+    }
+}
+
+// ── Helpers ──────────────────────────────────────────────────────────────
+
+fn load_config() -> Result<NameServiceConfig, ContractError> {
+    ensure!(INITIALIZED.load_or(false), "not initialized");
+    CONFIG.load()
+}
+
+/// Same rules as the native registry: 3-32 chars of lowercase
+/// alphanumerics and hyphens, no leading or trailing hyphen.
+fn validate_label(label: &str) -> Result<(), ContractError> {
+    ensure!(
+        label.len() >= 3 && label.len() <= 32,
+        "name must be 3-32 characters"
+    );
+    ensure!(
+        !label.starts_with('-') && !label.ends_with('-'),
+        "name must not start or end with a hyphen"
+    );
+    ensure!(
+        label
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-'),
+        "name must be lowercase alphanumeric or hyphens"
+    );
+    Ok(())
+}
+
+/// Load a name and ensure it (or its parent, for subdomains) has not expired.
+fn load_active(ctx: &Context, name: &String) -> Result<NameRecord, ContractError> {
+    let record = NAMES.load(name)?;
+    let expires_at = match &record.parent {
+        Some(parent) => {
+            let parent_record = NAMES.load(parent)?;
+            // A re-registered parent invalidates subdomains delegated before it
+            ensure!(
+                record.registered_at >= parent_record.registered_at,
+                "subdomain delegation is stale"
+            );
+            parent_record.expires_at
+        }
+        None => record.expires_at,
+    };
+    ensure!(ctx.timestamp() < expires_at, "name has expired");
+    Ok(record)
+}
+
+fn charge_fee(
+    ctx: &Context,
+    config: &NameServiceConfig,
+    years: u64,
+) -> Result<u128, ContractError> {
+    let fee = safe_mul(config.annual_fee, years as u128)?;
+    if fee > 0 {
+        ctx.transfer(&ctx.sender(), &config.fee_sink, &NORN, fee);
+    }
+    Ok(fee)
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use norn_sdk::testing::*;
+
+    const CONTRACT_ADDR: Address = [99u8; 20];
+    const SINK: Address = [77u8; 20];
+    const FEE: u128 = 1_000;
+    const GRACE: u64 = 30 * 86_400;
+
+    fn setup() -> (TestEnv, NameService) {
+        let env = TestEnv::new()
+            .with_sender(ALICE)
+            .with_timestamp(1000)
+            .with_contract_address(CONTRACT_ADDR);
+        let mut ns = NameService::new(&env.ctx());
+        ns.initialize(&env.ctx(), FEE, GRACE, SINK).unwrap();
+        (env, ns)
+    }
+
+    fn name(s: &str) -> String {
+        String::from(s)
+    }
+
+    #[test]
+    fn test_register_charges_fee_to_sink() {
+        let (env, mut ns) = setup();
+        env.clear_transfers();
+        let resp = ns.register(&env.ctx(), name("alice"), 2).unwrap();
+        assert_attribute(&resp, "fee", "2000");
+
+        let transfers = env.transfers();
+        assert_eq!(transfers[0].0, ALICE.to_vec());
+        assert_eq!(transfers[0].1, SINK.to_vec());
+        assert_eq!(transfers[0].3, 2_000);
+
+        let resolved: Address =
+            from_response(&ns.resolve(&env.ctx(), name("alice")).unwrap()).unwrap();
+        assert_eq!(resolved, ALICE);
+
+        env.set_sender(BOB);
+        let err = ns.register(&env.ctx(), name("alice"), 1).unwrap_err();
+        assert_err_contains(&err, "name is taken");
+    }
+
+    #[test]
+    fn test_invalid_names_rejected() {
+        let (env, mut ns) = setup();
+        let err = ns.register(&env.ctx(), name("ab"), 1).unwrap_err();
+        assert_err_contains(&err, "3-32 characters");
+        let err = ns.register(&env.ctx(), name("-abc"), 1).unwrap_err();
+        assert_err_contains(&err, "hyphen");
+        let err = ns.register(&env.ctx(), name("Alice"), 1).unwrap_err();
+        assert_err_contains(&err, "lowercase alphanumeric");
+        let err = ns.register(&env.ctx(), name("a.bc"), 1).unwrap_err();
+        assert_err_contains(&err, "lowercase alphanumeric");
+        let err = ns.register(&env.ctx(), name("alice"), 11).unwrap_err();
+        assert_err_contains(&err, "years must be 1-10");
+    }
+
+    #[test]
+    fn test_expiry_grace_and_reregistration() {
+        let (env, mut ns) = setup();
+        ns.register(&env.ctx(), name("alice"), 1).unwrap();
+        let expiry = 1000 + SECONDS_PER_YEAR;
+
+        env.set_timestamp(expiry);
+        let err = ns.resolve(&env.ctx(), name("alice")).unwrap_err();
+        assert_err_contains(&err, "name has expired");
+
+        // Within grace: nobody else can take it, renewal still works
+        env.set_sender(BOB);
+        let err = ns.register(&env.ctx(), name("alice"), 1).unwrap_err();
+        assert_err_contains(&err, "name is taken");
+        env.set_sender(ALICE);
+        let resp = ns.renew(&env.ctx(), name("alice"), 1).unwrap();
+        assert_attribute(
+            &resp,
+            "expires_at",
+            &format!("{}", expiry + SECONDS_PER_YEAR),
+        );
+        ns.resolve(&env.ctx(), name("alice")).unwrap();
+
+        // Past expiry + grace: renewal fails, anyone can register
+        env.set_timestamp(expiry + SECONDS_PER_YEAR + GRACE);
+        let err = ns.renew(&env.ctx(), name("alice"), 1).unwrap_err();
+        assert_err_contains(&err, "grace period has ended");
+        env.set_sender(BOB);
+        let available: bool =
+            from_response(&ns.is_available(&env.ctx(), name("alice")).unwrap()).unwrap();
+        assert!(available);
+        ns.register(&env.ctx(), name("alice"), 1).unwrap();
+        let record: NameRecord =
+            from_response(&ns.get_name(&env.ctx(), name("alice")).unwrap()).unwrap();
+        assert_eq!(record.owner, BOB);
+    }
+
+    #[test]
+    fn test_subdomain_delegation() {
+        let (env, mut ns) = setup();
+        ns.register(&env.ctx(), name("acme"), 1).unwrap();
+
+        env.set_sender(BOB);
+        let err = ns
+            .set_subdomain(&env.ctx(), name("acme"), name("pay"), BOB)
+            .unwrap_err();
+        assert_err_contains(&err, "only parent owner can delegate");
+
+        env.set_sender(ALICE);
+        ns.set_subdomain(&env.ctx(), name("acme"), name("pay"), BOB)
+            .unwrap();
+
+        // The delegate manages the subdomain's records
+        env.set_sender(BOB);
+        ns.set_address(&env.ctx(), name("pay.acme"), CHARLIE)
+            .unwrap();
+        let resolved: Address =
+            from_response(&ns.resolve(&env.ctx(), name("pay.acme")).unwrap()).unwrap();
+        assert_eq!(resolved, CHARLIE);
+
+        // Subdomains expire with their parent
+        env.set_timestamp(1000 + SECONDS_PER_YEAR);
+        let err = ns.resolve(&env.ctx(), name("pay.acme")).unwrap_err();
+        assert_err_contains(&err, "name has expired");
+    }
+
+    #[test]
+    fn test_stale_subdomain_after_parent_reregistration() {
+        let (env, mut ns) = setup();
+        ns.register(&env.ctx(), name("acme"), 1).unwrap();
+        ns.set_subdomain(&env.ctx(), name("acme"), name("pay"), BOB)
+            .unwrap();
+
+        env.set_timestamp(1000 + SECONDS_PER_YEAR + GRACE);
+        env.set_sender(CHARLIE);
+        ns.register(&env.ctx(), name("acme"), 1).unwrap();
+        let err = ns.resolve(&env.ctx(), name("pay.acme")).unwrap_err();
+        assert_err_contains(&err, "subdomain delegation is stale");
+    }
+
+    #[test]
+    fn test_text_records_and_transfer() {
+        let (env, mut ns) = setup();
+        ns.register(&env.ctx(), name("alice"), 1).unwrap();
+        ns.set_text(
+            &env.ctx(),
+            name("alice"),
+            name("url"),
+            name("https://a.example"),
+        )
+        .unwrap();
+
+        let value: Option<String> =
+            from_response(&ns.get_text(&env.ctx(), name("alice"), name("url")).unwrap()).unwrap();
+        assert_eq!(value.as_deref(), Some("https://a.example"));
+
+        ns.set_text(&env.ctx(), name("alice"), name("url"), String::new())
+            .unwrap();
+        let value: Option<String> =
+            from_response(&ns.get_text(&env.ctx(), name("alice"), name("url")).unwrap()).unwrap();
+        assert_eq!(value, None);
+
+        ns.transfer(&env.ctx(), name("alice"), BOB).unwrap();
+        let err = ns
+            .set_address(&env.ctx(), name("alice"), ALICE)
+            .unwrap_err();
+        assert_err_contains(&err, "only owner can update records");
+    }
+}
@@ -0,0 +1,525 @@
+//! Oracle — medianized price feeds pushed by whitelisted reporters.
+//!
+//! The owner lists the feeds (e.g. `"NORN/USD"`) and the reporters allowed
+//! to update them. A reporter pushes `(feed, price, timestamp)` updates with
+//! `submit` or `submit_batch`; the execution signature authenticates it, so
+//! reports can be relayed by any node. `norn-node` can run as a reporter
+//! (see its `[oracle]` config), fetching prices from HTTP sources.
+//!
+//! The loom keeps each reporter's latest report per feed. `get_price`
+//! returns the median of the reports no older than the feed's `max_age`,
+//! and fails unless at least `min_reporters` of them are fresh. It is an
+//! execute method so other looms can reach it through a cross-contract call
+//! with input `[2] || borsh(feed)`; the output is a borsh `PriceData`.
+//! `price` is the same lookup as a query.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use norn_sdk::prelude::*;
+
+/// Longest feed ID, in bytes.
+const MAX_FEED_LEN: usize = 32;
+/// Most reporters on the whitelist.
+const MAX_REPORTERS: usize = 32;
+/// How far a report's timestamp may run ahead of the block time.
+pub const MAX_CLOCK_DRIFT: u64 = 60;
+
+// ── Storage ──────────────────────────────────────────────────────────────
+
+const FEEDS: Map<String, FeedConfig> = Map::new("feeds");
+const REPORTERS: Item<Vec<Address>> = Item::new("reporters");
+/// Latest report per (feed, reporter).
+const REPORTS: Map<(String, Address), Report> = Map::new("reports");
+
+// ── Types ────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct FeedConfig {
+    /// Decimal places of reported prices.
+    pub decimals: u8,
+    /// Seconds a report counts towards the median.
+    pub max_age: u64,
+    /// Fresh reports needed for a price.
+    pub min_reporters: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct Report {
+    pub price: u128,
+    pub timestamp: u64,
+}
+
+/// One update in a `submit_batch`.
+///
+/// Mirrors `norn_node::oracle::PriceUpdate`.
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct PriceUpdate {
+    pub feed: String,
+    pub price: u128,
+    pub timestamp: u64,
+}
+
+/// A medianized price.
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct PriceData {
+    pub price: u128,
+    pub decimals: u8,
+    /// Timestamp of the oldest report in the median.
+    pub timestamp: u64,
+    /// Number of fresh reports in the median.
+    pub reporters: u16,
+}
+
+// ── Contract ─────────────────────────────────────────────────────────────
+
+#[norn_contract]
+pub struct Oracle;
+
+#[norn_contract]
+impl Oracle {
+    #[init]
+    pub fn new(ctx: &Context) -> Self {
+        Ownable::init(&ctx.sender()).unwrap();
+        REPORTERS.init(&Vec::new());
+        Oracle
+    }
+
+    // ── Execute ──────────────────────────────────────────────────────
+
+    /// Report the price of `feed` at `timestamp` (reporters only).
+    #[execute]
+    pub fn submit(
+        &mut self,
+        ctx: &Context,
+        feed: String,
+        price: u128,
+        timestamp: u64,
+    ) -> ContractResult {
+        require_reporter(ctx)?;
+        let update = PriceUpdate {
+            feed: feed.clone(),
+            price,
+            timestamp,
+        };
+        check(ctx, &update)?;
+        Ok(Response::with_action("submit")
+            .add_attribute("feed", feed)
+            .add_event(record(ctx, update)?))
+    }
+
+    /// Report several feeds at once (reporters only). Fails as a whole if
+    /// any update is rejected.
+    #[execute]
+    pub fn submit_batch(&mut self, ctx: &Context, updates: Vec<PriceUpdate>) -> ContractResult {
+        require_reporter(ctx)?;
+        ensure!(!updates.is_empty(), "empty batch");
+        for (i, update) in updates.iter().enumerate() {
+            ensure!(
+                !updates[..i].iter().any(|u| u.feed == update.feed),
+                "duplicate feed in batch"
+            );
+            check(ctx, update)?;
+        }
+        let count = updates.len();
+        let mut resp = Response::with_action("submit_batch").add_u128("count", count as u128);
+        for update in updates {
+            resp = resp.add_event(record(ctx, update)?);
+        }
+        Ok(resp)
+    }
+
+    /// Median price of `feed`, for cross-contract callers.
+    #[execute]
+    pub fn get_price(&mut self, ctx: &Context, feed: String) -> ContractResult {
+        ok(median_price(ctx, &feed)?)
+    }
+
+    /// Create or reconfigure a feed (owner only).
+    #[execute]
+    pub fn set_feed(
+        &mut self,
+        ctx: &Context,
+        feed: String,
+        decimals: u8,
+        max_age: u64,
+        min_reporters: u16,
+    ) -> ContractResult {
+        Ownable::require_owner(ctx)?;
+        ensure!(
+            !feed.is_empty() && feed.len() <= MAX_FEED_LEN,
+            "invalid feed id"
+        );
+        ensure!(decimals <= 36, "too many decimals");
+        ensure!(max_age > 0, "max_age must be positive");
+        ensure!(min_reporters > 0, "min_reporters must be positive");
+        FEEDS.save(
+            &feed,
+            &FeedConfig {
+                decimals,
+                max_age,
+                min_reporters,
+            },
+        )?;
+        Ok(Response::with_action("set_feed")
+            .add_attribute("feed", feed)
+            .add_u128("max_age", max_age as u128)
+            .add_u128("min_reporters", min_reporters as u128))
+    }
+
+    /// Whitelist a reporter (owner only).
+    #[execute]
+    pub fn add_reporter(&mut self, ctx: &Context, reporter: Address) -> ContractResult {
+        Ownable::require_owner(ctx)?;
+        let mut reporters = REPORTERS.load()?;
+        ensure!(!reporters.contains(&reporter), "already a reporter");
+        ensure!(reporters.len() < MAX_REPORTERS, "too many reporters");
+        reporters.push(reporter);
+        REPORTERS.save(&reporters)?;
+        Ok(Response::with_action("add_reporter").add_address("reporter", &reporter))
+    }
+
+    /// Remove a reporter (owner only). Its reports stop counting at once.
+    #[execute]
+    pub fn remove_reporter(&mut self, ctx: &Context, reporter: Address) -> ContractResult {
+        Ownable::require_owner(ctx)?;
+        let mut reporters = REPORTERS.load()?;
+        let idx = reporters
+            .iter()
+            .position(|r| *r == reporter)
+            .ok_or("not a reporter")?;
+        reporters.remove(idx);
+        REPORTERS.save(&reporters)?;
+        Ok(Response::with_action("remove_reporter").add_address("reporter", &reporter))
+    }
+
+    // ── Query ────────────────────────────────────────────────────────
+
+    #[query]
+    pub fn price(&self, ctx: &Context, feed: String) -> ContractResult {
+        ok(median_price(ctx, &feed)?)
+    }
+
+    #[query]
+    pub fn feed(&self, _ctx: &Context, feed: String) -> ContractResult {
+        ok(FEEDS.load(&feed).ok())
+    }
+
+    #[query]
+    pub fn report(&self, _ctx: &Context, feed: String, reporter: Address) -> ContractResult {
+        ok(REPORTS.load(&(feed, reporter)).ok())
+    }
+
+    #[query]
+    pub fn reporters(&self, _ctx: &Context) -> ContractResult {
+        ok(REPORTERS.load()?)
+    }
+}
+
+fn require_reporter(ctx: &Context) -> Result<(), ContractError> {
+    ensure!(
+        REPORTERS.load()?.contains(&ctx.sender()),
+        ContractError::Unauthorized
+    );
+    Ok(())
+}
+
+fn load_feed(feed: &str) -> Result<FeedConfig, ContractError> {
+    FEEDS
+        .load(&String::from(feed))
+        .map_err(|_| ContractError::Custom(format!("unknown feed {feed}")))
+}
+
+/// Validate one of the sender's reports against its feed and the
+/// sender's previous report.
+fn check(ctx: &Context, update: &PriceUpdate) -> Result<(), ContractError> {
+    let config = load_feed(&update.feed)?;
+    ensure!(update.price > 0, "price must be positive");
+    let now = ctx.timestamp();
+    ensure!(
+        update.timestamp <= now.saturating_add(MAX_CLOCK_DRIFT),
+        "timestamp is in the future"
+    );
+    ensure!(
+        now.saturating_sub(update.timestamp) <= config.max_age,
+        "report is stale"
+    );
+    if let Ok(last) = REPORTS.load(&(update.feed.clone(), ctx.sender())) {
+        ensure!(
+            update.timestamp > last.timestamp,
+            "report is not newer than the last one"
+        );
+    }
+    Ok(())
+}
+
+/// Store a checked report and return its `PriceReported` event.
+fn record(ctx: &Context, update: PriceUpdate) -> Result<Event, ContractError> {
+    let sender = ctx.sender();
+    let key = (update.feed.clone(), sender);
+    REPORTS.save(
+        &key,
+        &Report {
+            price: update.price,
+            timestamp: update.timestamp,
+        },
+    )?;
+    Ok(Event::new("PriceReported")
+        .add_attribute("feed", update.feed)
+        .add_address("reporter", &sender)
+        .add_u128("price", update.price)
+        .add_u128("timestamp", update.timestamp as u128))
+}
+
+/// Median of the fresh reports of whitelisted reporters.
+fn median_price(ctx: &Context, feed: &str) -> Result<PriceData, ContractError> {
+    let config = load_feed(feed)?;
+    let now = ctx.timestamp();
+    let fresh: Vec<Report> = REPORTERS
+        .load()?
+        .into_iter()
+        .filter_map(|reporter| REPORTS.load(&(String::from(feed), reporter)).ok())
+        .filter(|r| now.saturating_sub(r.timestamp) <= config.max_age)
+        .collect();
+    ensure!(
+        fresh.len() >= config.min_reporters as usize,
+        "not enough fresh reports"
+    );
+
+    let mut prices: Vec<u128> = fresh.iter().map(|r| r.price).collect();
+    prices.sort_unstable();
+    let mid = prices.len() / 2;
+    let price = if prices.len() % 2 == 1 {
+        prices[mid]
+    } else {
+        let (a, b) = (prices[mid - 1], prices[mid]);
+        a / 2 + b / 2 + (a % 2 + b % 2) / 2
+    };
+    Ok(PriceData {
+        price,
+        decimals: config.decimals,
+        timestamp: fresh.iter().map(|r| r.timestamp).min().unwrap_or(now),
+        reporters: fresh.len() as u16,
+    })
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use norn_sdk::testing::*;
+
+    const NOW: u64 = 1_700_000_000;
+
+    fn setup() -> (TestEnv, Oracle) {
+        let env = TestEnv::new().with_sender(ALICE).with_timestamp(NOW);
+        let mut oracle = Oracle::new(&env.ctx());
+        oracle
+            .set_feed(&env.ctx(), String::from("NORN/USD"), 8, 300, 2)
+            .unwrap();
+        for reporter in [BOB, CHARLIE, DAVE] {
+            oracle.add_reporter(&env.ctx(), reporter).unwrap();
+        }
+        (env, oracle)
+    }
+
+    fn submit(
+        env: &TestEnv,
+        oracle: &mut Oracle,
+        who: Address,
+        price: u128,
+        timestamp: u64,
+    ) -> ContractResult {
+        env.set_sender(who);
+        oracle.submit(&env.ctx(), String::from("NORN/USD"), price, timestamp)
+    }
+
+    fn price(env: &TestEnv, oracle: &mut Oracle) -> Result<PriceData, ContractError> {
+        from_response(&oracle.get_price(&env.ctx(), String::from("NORN/USD"))?)
+    }
+
+    #[test]
+    fn test_cross_call_discriminants() {
+        // Consumers call `get_price` as `[2] || borsh(feed)`; `norn-node`
+        // submits batches as `[1] || borsh(updates)`.
+        let input = borsh::to_vec(&__OracleExecute::GetPrice {
+            feed: String::from("NORN/USD"),
+        })
+        .unwrap();
+        assert_eq!(input[0], 2);
+        let input = borsh::to_vec(&__OracleExecute::SubmitBatch {
+            updates: Vec::new(),
+        })
+        .unwrap();
+        assert_eq!(input[0], 1);
+    }
+
+    #[test]
+    fn test_median_of_fresh_reports() {
+        let (env, mut oracle) = setup();
+        let resp = submit(&env, &mut oracle, BOB, 100, NOW - 10).unwrap();
+        assert_event_attribute(&resp, "PriceReported", "price", "100");
+        let err = price(&env, &mut oracle).unwrap_err();
+        assert_eq!(err.message(), "not enough fresh reports");
+
+        submit(&env, &mut oracle, CHARLIE, 300, NOW - 5).unwrap();
+        let data = price(&env, &mut oracle).unwrap();
+        assert_eq!(data.price, 200);
+        assert_eq!(data.decimals, 8);
+        assert_eq!(data.timestamp, NOW - 10);
+        assert_eq!(data.reporters, 2);
+
+        // An outlier cannot move an odd-sized median.
+        submit(&env, &mut oracle, DAVE, 1_000_000, NOW).unwrap();
+        assert_eq!(price(&env, &mut oracle).unwrap().price, 300);
+
+        let resp = oracle.price(&env.ctx(), String::from("NORN/USD")).unwrap();
+        assert_eq!(from_response::<PriceData>(&resp).unwrap().price, 300);
+    }
+
+    #[test]
+    fn test_stale_reports_drop_out() {
+        let (env, mut oracle) = setup();
+        submit(&env, &mut oracle, BOB, 100, NOW).unwrap();
+        submit(&env, &mut oracle, CHARLIE, 110, NOW).unwrap();
+        submit(&env, &mut oracle, DAVE, 120, NOW + 200).unwrap_err();
+
+        env.set_timestamp(NOW + 200);
+        submit(&env, &mut oracle, DAVE, 120, NOW + 200).unwrap();
+        assert_eq!(price(&env, &mut oracle).unwrap().price, 110);
+
+        env.set_timestamp(NOW + 301);
+        let data = price(&env, &mut oracle);
+        assert_eq!(data.unwrap_err().message(), "not enough fresh reports");
+    }
+
+    #[test]
+    fn test_rejects_bad_timestamps() {
+        let (env, mut oracle) = setup();
+        let err = submit(&env, &mut oracle, BOB, 100, NOW + MAX_CLOCK_DRIFT + 1).unwrap_err();
+        assert_eq!(err.message(), "timestamp is in the future");
+        let err = submit(&env, &mut oracle, BOB, 100, NOW - 301).unwrap_err();
+        assert_eq!(err.message(), "report is stale");
+
+        submit(&env, &mut oracle, BOB, 100, NOW).unwrap();
+        let err = submit(&env, &mut oracle, BOB, 101, NOW).unwrap_err();
+        assert_eq!(err.message(), "report is not newer than the last one");
+        submit(&env, &mut oracle, BOB, 101, NOW + 1).unwrap();
+    }
+
+    #[test]
+    fn test_only_reporters_submit() {
+        let (env, mut oracle) = setup();
+        let err = submit(&env, &mut oracle, ALICE, 100, NOW).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized);
+
+        env.set_sender(BOB);
+        let err = oracle
+            .submit(&env.ctx(), String::from("BTC/USD"), 100, NOW)
+            .unwrap_err();
+        assert_eq!(err.message(), "unknown feed BTC/USD");
+        let err = submit(&env, &mut oracle, BOB, 0, NOW).unwrap_err();
+        assert_eq!(err.message(), "price must be positive");
+    }
+
+    #[test]
+    fn test_removed_reporter_stops_counting() {
+        let (env, mut oracle) = setup();
+        submit(&env, &mut oracle, BOB, 100, NOW).unwrap();
+        submit(&env, &mut oracle, CHARLIE, 200, NOW).unwrap();
+        assert_eq!(price(&env, &mut oracle).unwrap().price, 150);
+
+        env.set_sender(ALICE);
+        oracle.remove_reporter(&env.ctx(), CHARLIE).unwrap();
+        assert!(price(&env, &mut oracle).is_err());
+        let err = submit(&env, &mut oracle, CHARLIE, 200, NOW + 1).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized);
+
+        env.set_sender(BOB);
+        let err = oracle.add_reporter(&env.ctx(), CHARLIE).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized);
+        env.set_sender(ALICE);
+        let err = oracle.remove_reporter(&env.ctx(), CHARLIE).unwrap_err();
+        assert_eq!(err.message(), "not a reporter");
+    }
+
+    #[test]
+    fn test_submit_batch_is_atomic() {
+        let (env, mut oracle) = setup();
+        env.set_sender(ALICE);
+        oracle
+            .set_feed(&env.ctx(), String::from("BTC/USD"), 2, 60, 1)
+            .unwrap();
+
+        let update = |feed: &str, price: u128| PriceUpdate {
+            feed: String::from(feed),
+            price,
+            timestamp: NOW,
+        };
+        env.set_sender(BOB);
+        let err = oracle
+            .submit_batch(
+                &env.ctx(),
+                alloc::vec![update("NORN/USD", 100), update("ETH/USD", 5)],
+            )
+            .unwrap_err();
+        assert_eq!(err.message(), "unknown feed ETH/USD");
+        let err = oracle
+            .submit_batch(
+                &env.ctx(),
+                alloc::vec![update("NORN/USD", 100), update("NORN/USD", 5)],
+            )
+            .unwrap_err();
+        assert_eq!(err.message(), "duplicate feed in batch");
+        assert!(REPORTS.load(&(String::from("NORN/USD"), BOB)).is_err());
+
+        let resp = oracle
+            .submit_batch(
+                &env.ctx(),
+                alloc::vec![update("NORN/USD", 100), update("BTC/USD", 6_500_000)],
+            )
+            .unwrap();
+        assert_attribute(&resp, "count", "2");
+        let data: PriceData = from_response(
+            &oracle
+                .get_price(&env.ctx(), String::from("BTC/USD"))
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(data.price, 6_500_000);
+        assert_eq!(data.decimals, 2);
+    }
+
+    #[test]
+    fn test_feed_admin() {
+        let (env, mut oracle) = setup();
+        env.set_sender(BOB);
+        let err = oracle
+            .set_feed(&env.ctx(), String::from("BTC/USD"), 8, 60, 1)
+            .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized);
+
+        env.set_sender(ALICE);
+        let err = oracle
+            .set_feed(&env.ctx(), String::from("BTC/USD"), 8, 60, 0)
+            .unwrap_err();
+        assert_eq!(err.message(), "min_reporters must be positive");
+        let err = oracle.add_reporter(&env.ctx(), BOB).unwrap_err();
+        assert_eq!(err.message(), "already a reporter");
+
+        // Raising the quorum applies to existing reports.
+        submit(&env, &mut oracle, BOB, 100, NOW).unwrap();
+        submit(&env, &mut oracle, CHARLIE, 200, NOW).unwrap();
+        env.set_sender(ALICE);
+        oracle
+            .set_feed(&env.ctx(), String::from("NORN/USD"), 8, 300, 3)
+            .unwrap();
+        assert!(price(&env, &mut oracle).is_err());
+        let feed: Option<FeedConfig> =
+            from_response(&oracle.feed(&env.ctx(), String::from("NORN/USD")).unwrap()).unwrap();
+        assert_eq!(feed.unwrap().min_reporters, 3);
+    }
+}
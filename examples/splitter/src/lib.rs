@@ -1,5 +1,9 @@
 //! Payment Splitter — route incoming payments to multiple recipients by percentage.
 //! Set once, anyone can send to it.
+//!
+//! In pull mode, splits only credit per-recipient balances and each recipient
+//! calls `withdraw` for themselves, so a split costs one transfer regardless
+//! of how many recipients there are.
 
 #![no_std]
 
@@ -12,6 +16,7 @@ use norn_sdk::prelude::*;
 
 const INITIALIZED: Item<bool> = Item::new("initialized");
 const CONFIG: Item<SplitterConfig> = Item::new("config");
+const CLAIMABLE: Map<(Address, TokenId), u128> = Map::new("claimable");
 
 // ── Types ──────────────────────────────────────────────────────────────
 
@@ -21,12 +26,21 @@ pub struct Recipient {
     pub share_bps: u64, // basis points (100 = 1%, 10000 = 100%)
 }
 
+#[derive(Debug, BorshSerialize, BorshDeserialize, Clone, PartialEq)]
+pub enum DistributionMode {
+    /// Transfer each share to its recipient during `split`.
+    Push,
+    /// Credit shares to claimable balances; recipients `withdraw`.
+    Pull,
+}
+
 #[derive(Debug, BorshSerialize, BorshDeserialize, Clone)]
 pub struct SplitterConfig {
     pub name: String,
     pub creator: Address,
     pub recipients: Vec<Recipient>,
     pub created_at: u64,
+    pub mode: DistributionMode,
 }
 
 // ── Contract ───────────────────────────────────────────────────────────
@@ -67,6 +81,7 @@ impl Splitter {
             creator: ctx.sender(),
             recipients,
             created_at: ctx.timestamp(),
+            mode: DistributionMode::Push,
         })?;
         INITIALIZED.save(&true)?;
 
@@ -97,7 +112,16 @@ impl Splitter {
                 safe_mul(amount, r.share_bps as u128)? / 10_000
             };
             if share > 0 {
-                ctx.transfer_from_contract(&r.address, &token_id, share);
+                match config.mode {
+                    DistributionMode::Push => {
+                        ctx.transfer_from_contract(&r.address, &token_id, share);
+                    }
+                    DistributionMode::Pull => {
+                        let key = (r.address, token_id);
+                        let balance = CLAIMABLE.load(&key).unwrap_or(0u128);
+                        CLAIMABLE.save(&key, &safe_add(balance, share)?)?;
+                    }
+                }
                 distributed = safe_add(distributed, share)?;
             }
         }
@@ -107,11 +131,48 @@ impl Splitter {
             .add_attribute("recipients", format!("{}", config.recipients.len())))
     }
 
+    /// Switch between push and pull distribution. Creator only. Balances
+    /// credited in pull mode stay withdrawable after switching back.
+    #[execute]
+    pub fn set_mode(&mut self, ctx: &Context, mode: DistributionMode) -> ContractResult {
+        let mut config = CONFIG.load()?;
+        ensure!(ctx.sender() == config.creator, "only creator can set mode");
+        config.mode = mode;
+        CONFIG.save(&config)?;
+
+        Ok(Response::with_action("set_mode"))
+    }
+
+    /// Withdraw the sender's claimable balance of `token_id`.
+    #[execute]
+    pub fn withdraw(&mut self, ctx: &Context, token_id: TokenId) -> ContractResult {
+        let key = (ctx.sender(), token_id);
+        let amount = CLAIMABLE.load(&key).unwrap_or(0u128);
+        ensure!(amount > 0, "nothing to withdraw");
+
+        CLAIMABLE.save(&key, &0u128)?;
+        ctx.transfer_from_contract(&ctx.sender(), &token_id, amount);
+
+        Ok(Response::with_action("withdraw")
+            .add_attribute("amount", format!("{}", amount)))
+    }
+
     #[query]
     pub fn get_config(&self, _ctx: &Context) -> ContractResult {
         let config = CONFIG.load()?;
         ok(config)
     }
+
+    #[query]
+    pub fn get_claimable(
+        &self,
+        _ctx: &Context,
+        addr: Address,
+        token_id: TokenId,
+    ) -> ContractResult {
+        let amount = CLAIMABLE.load(&(addr, token_id)).unwrap_or(0u128);
+        ok(amount)
+    }
 }
 
 // ── Tests ──────────────────────────────────────────────────────────────
@@ -241,4 +302,47 @@ mod tests {
             .unwrap_err();
         assert_err_contains(&err, "need at least 2 recipients");
     }
+
+    #[test]
+    fn test_pull_mode_credits_balances() {
+        let (env, mut s) = setup();
+        s.set_mode(&env.ctx(), DistributionMode::Pull).unwrap();
+
+        env.set_sender(BOB);
+        env.clear_transfers();
+        s.split(&env.ctx(), TOKEN, 10_000).unwrap();
+        s.split(&env.ctx(), TOKEN, 10_001).unwrap();
+
+        // Only the deposits move tokens
+        assert_eq!(env.transfers().len(), 2);
+
+        let resp = s.get_claimable(&env.ctx(), CHARLIE, TOKEN).unwrap();
+        let amount: u128 = from_response(&resp).unwrap();
+        assert_eq!(amount, 1000 + 1001);
+
+        env.set_sender(ALICE);
+        env.clear_transfers();
+        let resp = s.withdraw(&env.ctx(), TOKEN).unwrap();
+        assert_attribute(&resp, "amount", "12000");
+        let transfers = env.transfers();
+        assert_eq!(transfers[0].0, CONTRACT_ADDR.to_vec());
+        assert_eq!(transfers[0].1, ALICE.to_vec());
+
+        let err = s.withdraw(&env.ctx(), TOKEN).unwrap_err();
+        assert_err_contains(&err, "nothing to withdraw");
+    }
+
+    #[test]
+    fn test_balances_survive_mode_switch() {
+        let (env, mut s) = setup();
+        s.set_mode(&env.ctx(), DistributionMode::Pull).unwrap();
+        s.split(&env.ctx(), TOKEN, 10_000).unwrap();
+        s.set_mode(&env.ctx(), DistributionMode::Push).unwrap();
+
+        env.set_sender(BOB);
+        let err = s.set_mode(&env.ctx(), DistributionMode::Pull).unwrap_err();
+        assert_err_contains(&err, "only creator can set mode");
+        let resp = s.withdraw(&env.ctx(), TOKEN).unwrap();
+        assert_attribute(&resp, "amount", "3000");
+    }
 }
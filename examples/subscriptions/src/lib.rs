@@ -0,0 +1,511 @@
+//! Recurring Payments — merchants publish plans, subscribers prepay a budget held by the contract.
+//! Anyone can trigger a due payment; missed payments lapse after a grace period.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::format;
+use norn_sdk::prelude::*;
+
+// ── Storage ────────────────────────────────────────────────────────────
+
+const PLAN_COUNT: Item<u64> = Item::new("plan_count");
+const PLANS: Map<u64, Plan> = Map::new("plans");
+const SUBSCRIPTIONS: Map<(Address, u64), Subscription> = Map::new("subscriptions");
+const ALLOWANCES: Map<(Address, u64), u128> = Map::new("allowances");
+
+// ── Types ──────────────────────────────────────────────────────────────
+
+#[derive(Debug, BorshSerialize, BorshDeserialize, Clone)]
+pub struct Plan {
+    pub id: u64,
+    pub merchant: Address,
+    pub token_id: TokenId,
+    pub amount: u128,
+    pub interval: u64,     // seconds between payments
+    pub grace_period: u64, // seconds a payment may be late before the subscription lapses
+    pub active: bool,
+    pub created_at: u64,
+}
+
+#[derive(Debug, BorshSerialize, BorshDeserialize, Clone, PartialEq)]
+pub enum SubscriptionStatus {
+    Active,
+    Cancelled,
+    Lapsed,
+}
+
+#[derive(Debug, BorshSerialize, BorshDeserialize, Clone)]
+pub struct Subscription {
+    pub plan_id: u64,
+    pub subscriber: Address,
+    pub next_due: u64,
+    pub payments: u64,
+    pub total_paid: u128,
+    pub status: SubscriptionStatus,
+    pub started_at: u64,
+}
+
+// ── Contract ───────────────────────────────────────────────────────────
+
+#[norn_contract]
+pub struct Subscriptions;
+
+#[norn_contract]
+impl Subscriptions {
+    #[init]
+    pub fn new(_ctx: &Context) -> Self {
+        PLAN_COUNT.init(&0u64);
+        Subscriptions
+    }
+
+    #[execute]
+    pub fn create_plan(
+        &mut self,
+        ctx: &Context,
+        token_id: TokenId,
+        amount: u128,
+        interval: u64,
+        grace_period: u64,
+    ) -> ContractResult {
+        ensure!(amount > 0, "amount must be positive");
+        ensure!(interval > 0, "interval must be positive");
+        ensure!(
+            grace_period < interval,
+            "grace_period must be shorter than interval"
+        );
+
+        let id = PLAN_COUNT.load_or(0u64);
+        PLANS.save(
+            &id,
+            &Plan {
+                id,
+                merchant: ctx.sender(),
+                token_id,
+                amount,
+                interval,
+                grace_period,
+                active: true,
+                created_at: ctx.timestamp(),
+            },
+        )?;
+        PLAN_COUNT.save(&safe_add_u64(id, 1)?)?;
+
+        Ok(Response::with_action("create_plan")
+            .add_attribute("plan_id", format!("{}", id))
+            .set_data(&id))
+    }
+
+    #[execute]
+    pub fn deactivate_plan(&mut self, ctx: &Context, plan_id: u64) -> ContractResult {
+        let mut plan = PLANS.load(&plan_id)?;
+        ensure!(ctx.sender() == plan.merchant, "only merchant can deactivate");
+        ensure!(plan.active, "plan is not active");
+
+        plan.active = false;
+        PLANS.save(&plan_id, &plan)?;
+
+        Ok(Response::with_action("deactivate_plan")
+            .add_attribute("plan_id", format!("{}", plan_id)))
+    }
+
+    /// Set the budget the contract holds for the sender's payments to a plan.
+    /// Raising it moves the difference into the contract; lowering it returns
+    /// the difference. Setting zero withdraws the budget; pending payments then fail.
+    #[execute]
+    pub fn approve(
+        &mut self,
+        ctx: &Context,
+        plan_id: u64,
+        allowance: u128,
+    ) -> ContractResult {
+        let plan = PLANS.load(&plan_id)?;
+        let key = (ctx.sender(), plan_id);
+        let held = ALLOWANCES.load(&key).unwrap_or(0u128);
+        if allowance > held {
+            let contract = ctx.contract_address();
+            ctx.transfer(&ctx.sender(), &contract, &plan.token_id, allowance - held);
+        } else if allowance < held {
+            ctx.transfer_from_contract(&ctx.sender(), &plan.token_id, held - allowance);
+        }
+        ALLOWANCES.save(&key, &allowance)?;
+
+        Ok(Response::with_action("approve")
+            .add_attribute("plan_id", format!("{}", plan_id))
+            .add_attribute("allowance", format!("{}", allowance)))
+    }
+
+    /// Activate a subscription. The first payment is taken from the budget immediately.
+    #[execute]
+    pub fn subscribe(&mut self, ctx: &Context, plan_id: u64) -> ContractResult {
+        let plan = PLANS.load(&plan_id)?;
+        ensure!(plan.active, "plan is not active");
+
+        let key = (ctx.sender(), plan_id);
+        if let Ok(existing) = SUBSCRIPTIONS.load(&key) {
+            ensure!(
+                existing.status != SubscriptionStatus::Active,
+                "already subscribed"
+            );
+        }
+
+        let now = ctx.timestamp();
+        let mut sub = Subscription {
+            plan_id,
+            subscriber: ctx.sender(),
+            next_due: now,
+            payments: 0,
+            total_paid: 0,
+            status: SubscriptionStatus::Active,
+            started_at: now,
+        };
+        collect_payment(ctx, &plan, &mut sub)?;
+        SUBSCRIPTIONS.save(&key, &sub)?;
+
+        Ok(Response::with_action("subscribe")
+            .add_attribute("plan_id", format!("{}", plan_id))
+            .add_attribute("next_due", format!("{}", sub.next_due)))
+    }
+
+    /// Pay a due payment out of the subscriber's budget. Callable by anyone
+    /// (merchant, keeper, or subscriber).
+    /// If the payment is past its grace period, the subscription lapses instead.
+    #[execute]
+    pub fn process_due(
+        &mut self,
+        ctx: &Context,
+        subscriber: Address,
+        plan_id: u64,
+    ) -> ContractResult {
+        let plan = PLANS.load(&plan_id)?;
+        let key = (subscriber, plan_id);
+        let mut sub = SUBSCRIPTIONS.load(&key)?;
+        ensure!(
+            sub.status == SubscriptionStatus::Active,
+            "subscription is not active"
+        );
+        ensure!(ctx.timestamp() >= sub.next_due, "payment not due yet");
+
+        let deadline = safe_add_u64(sub.next_due, plan.grace_period)?;
+        if !plan.active || ctx.timestamp() > deadline {
+            sub.status = SubscriptionStatus::Lapsed;
+            SUBSCRIPTIONS.save(&key, &sub)?;
+            return Ok(Response::with_action("lapse")
+                .add_attribute("plan_id", format!("{}", plan_id))
+                .add_address("subscriber", &subscriber));
+        }
+
+        collect_payment(ctx, &plan, &mut sub)?;
+        SUBSCRIPTIONS.save(&key, &sub)?;
+
+        Ok(Response::with_action("process_due")
+            .add_attribute("plan_id", format!("{}", plan_id))
+            .add_attribute("amount", format!("{}", plan.amount))
+            .add_attribute("next_due", format!("{}", sub.next_due)))
+    }
+
+    /// Cancel a subscription and return the unused budget to the subscriber.
+    /// Either the subscriber or the plan's merchant may cancel.
+    #[execute]
+    pub fn cancel(
+        &mut self,
+        ctx: &Context,
+        subscriber: Address,
+        plan_id: u64,
+    ) -> ContractResult {
+        let plan = PLANS.load(&plan_id)?;
+        ensure!(
+            ctx.sender() == subscriber || ctx.sender() == plan.merchant,
+            "only subscriber or merchant can cancel"
+        );
+        let key = (subscriber, plan_id);
+        let mut sub = SUBSCRIPTIONS.load(&key)?;
+        ensure!(
+            sub.status == SubscriptionStatus::Active,
+            "subscription is not active"
+        );
+
+        sub.status = SubscriptionStatus::Cancelled;
+        SUBSCRIPTIONS.save(&key, &sub)?;
+
+        let refund = ALLOWANCES.load(&key).unwrap_or(0u128);
+        if refund > 0 {
+            ctx.transfer_from_contract(&subscriber, &plan.token_id, refund);
+            ALLOWANCES.save(&key, &0u128)?;
+        }
+
+        Ok(Response::with_action("cancel")
+            .add_attribute("plan_id", format!("{}", plan_id))
+            .add_attribute("refund", format!("{}", refund)))
+    }
+
+    #[query]
+    pub fn get_plan(&self, _ctx: &Context, plan_id: u64) -> ContractResult {
+        let plan = PLANS.load(&plan_id)?;
+        ok(plan)
+    }
+
+    #[query]
+    pub fn get_plan_count(&self, _ctx: &Context) -> ContractResult {
+        let count = PLAN_COUNT.load_or(0u64);
+        ok(count)
+    }
+
+    #[query]
+    pub fn get_subscription(
+        &self,
+        _ctx: &Context,
+        subscriber: Address,
+        plan_id: u64,
+    ) -> ContractResult {
+        let sub = SUBSCRIPTIONS.load(&(subscriber, plan_id))?;
+        ok(sub)
+    }
+
+    #[query]
+    pub fn get_allowance(
+        &self,
+        _ctx: &Context,
+        subscriber: Address,
+        plan_id: u64,
+    ) -> ContractResult {
+        let allowance = ALLOWANCES.load(&(subscriber, plan_id)).unwrap_or(0u128);
+        ok(allowance)
+    }
+
+    #[query]
+    pub fn is_due(&self, ctx: &Context, subscriber: Address, plan_id: u64) -> ContractResult {
+        let due = match SUBSCRIPTIONS.load(&(subscriber, plan_id)) {
+            Ok(sub) => {
+                sub.status == SubscriptionStatus::Active && ctx.timestamp() >= sub.next_due
+            }
+            Err(_) => false,
+        };
+        ok(due)
+    }
+}
+
+// ── Helpers ────────────────────────────────────────────────────────────
+
+/// Pay one payment from the subscriber's budget held by the contract to the
+/// merchant and advance the due date by one interval.
+fn collect_payment(
+    ctx: &Context,
+    plan: &Plan,
+    sub: &mut Subscription,
+) -> Result<(), ContractError> {
+    let key = (sub.subscriber, plan.id);
+    let allowance = ALLOWANCES.load(&key).unwrap_or(0u128);
+    ensure!(allowance >= plan.amount, "insufficient allowance");
+
+    ctx.transfer_from_contract(&plan.merchant, &plan.token_id, plan.amount);
+    ALLOWANCES.save(&key, &safe_sub(allowance, plan.amount)?)?;
+
+    sub.next_due = safe_add_u64(sub.next_due, plan.interval)?;
+    sub.payments = safe_add_u64(sub.payments, 1)?;
+    sub.total_paid = safe_add(sub.total_paid, plan.amount)?;
+    Ok(())
+}
+
+// ── Tests ──────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use norn_sdk::testing::*;
+
+    const TOKEN: TokenId = [42u8; 32];
+    const CONTRACT_ADDR: Address = [99u8; 20];
+
+    /// ALICE is the merchant with plan 0: 100 tokens every 1000s, 100s grace.
+    fn setup() -> (TestEnv, Subscriptions) {
+        let env = TestEnv::new()
+            .with_sender(ALICE)
+            .with_timestamp(1000)
+            .with_contract_address(CONTRACT_ADDR);
+        let mut subs = Subscriptions::new(&env.ctx());
+        subs.create_plan(&env.ctx(), TOKEN, 100, 1000, 100).unwrap();
+        (env, subs)
+    }
+
+    fn subscribe_bob(env: &TestEnv, subs: &mut Subscriptions, allowance: u128) {
+        env.set_sender(BOB);
+        subs.approve(&env.ctx(), 0, allowance).unwrap();
+        subs.subscribe(&env.ctx(), 0).unwrap();
+    }
+
+    #[test]
+    fn test_create_plan() {
+        let (env, subs) = setup();
+        let resp = subs.get_plan(&env.ctx(), 0).unwrap();
+        let plan: Plan = from_response(&resp).unwrap();
+        assert_eq!(plan.merchant, ALICE);
+        assert_eq!(plan.amount, 100);
+        assert!(plan.active);
+    }
+
+    #[test]
+    fn test_subscribe_pays_first_payment() {
+        let (env, mut subs) = setup();
+        subscribe_bob(&env, &mut subs, 300);
+
+        // The budget moves into the contract, which pays the merchant
+        let transfers = env.transfers();
+        assert_eq!(transfers.len(), 2);
+        assert_eq!(transfers[0].0, BOB.to_vec());
+        assert_eq!(transfers[0].1, CONTRACT_ADDR.to_vec());
+        assert_eq!(transfers[0].3, 300);
+        assert_eq!(transfers[1].0, CONTRACT_ADDR.to_vec());
+        assert_eq!(transfers[1].1, ALICE.to_vec());
+        assert_eq!(transfers[1].3, 100);
+
+        let resp = subs.get_subscription(&env.ctx(), BOB, 0).unwrap();
+        let sub: Subscription = from_response(&resp).unwrap();
+        assert_eq!(sub.next_due, 2000);
+        assert_eq!(sub.payments, 1);
+
+        let resp = subs.get_allowance(&env.ctx(), BOB, 0).unwrap();
+        let allowance: u128 = from_response(&resp).unwrap();
+        assert_eq!(allowance, 200);
+    }
+
+    #[test]
+    fn test_subscribe_requires_allowance() {
+        let (env, mut subs) = setup();
+        env.set_sender(BOB);
+        let err = subs.subscribe(&env.ctx(), 0).unwrap_err();
+        assert_err_contains(&err, "insufficient allowance");
+    }
+
+    #[test]
+    fn test_process_due_by_anyone() {
+        let (env, mut subs) = setup();
+        subscribe_bob(&env, &mut subs, 300);
+
+        env.set_sender(CHARLIE);
+        env.set_timestamp(1500);
+        let err = subs.process_due(&env.ctx(), BOB, 0).unwrap_err();
+        assert_err_contains(&err, "payment not due yet");
+
+        env.set_timestamp(2050);
+        env.clear_transfers();
+        let resp = subs.process_due(&env.ctx(), BOB, 0).unwrap();
+        assert_attribute(&resp, "next_due", "3000");
+
+        // The runtime only lets a call move funds from its sender or the
+        // contract, so a keeper's call must not draw on the subscriber.
+        let transfers = env.transfers();
+        assert_eq!(transfers.len(), 1);
+        assert_eq!(transfers[0].0, CONTRACT_ADDR.to_vec());
+        assert_eq!(transfers[0].1, ALICE.to_vec());
+    }
+
+    #[test]
+    fn test_approve_adjusts_budget_held() {
+        let (env, mut subs) = setup();
+        env.set_sender(BOB);
+        subs.approve(&env.ctx(), 0, 300).unwrap();
+        env.clear_transfers();
+
+        subs.approve(&env.ctx(), 0, 500).unwrap();
+        subs.approve(&env.ctx(), 0, 0).unwrap();
+        let transfers = env.transfers();
+        assert_eq!(transfers.len(), 2);
+        assert_eq!(transfers[0].0, BOB.to_vec());
+        assert_eq!(transfers[0].1, CONTRACT_ADDR.to_vec());
+        assert_eq!(transfers[0].3, 200);
+        assert_eq!(transfers[1].0, CONTRACT_ADDR.to_vec());
+        assert_eq!(transfers[1].1, BOB.to_vec());
+        assert_eq!(transfers[1].3, 500);
+    }
+
+    #[test]
+    fn test_payment_lapses_after_grace_period() {
+        let (env, mut subs) = setup();
+        subscribe_bob(&env, &mut subs, 300);
+
+        env.set_timestamp(2101);
+        let resp = subs.process_due(&env.ctx(), BOB, 0).unwrap();
+        assert_attribute(&resp, "action", "lapse");
+
+        let resp = subs.get_subscription(&env.ctx(), BOB, 0).unwrap();
+        let sub: Subscription = from_response(&resp).unwrap();
+        assert_eq!(sub.status, SubscriptionStatus::Lapsed);
+        assert_eq!(env.transfers().len(), 2);
+    }
+
+    #[test]
+    fn test_process_due_fails_when_allowance_exhausted() {
+        let (env, mut subs) = setup();
+        subscribe_bob(&env, &mut subs, 100);
+
+        env.set_timestamp(2000);
+        let err = subs.process_due(&env.ctx(), BOB, 0).unwrap_err();
+        assert_err_contains(&err, "insufficient allowance");
+
+        // Topping up within the grace period recovers the payment
+        env.set_sender(BOB);
+        subs.approve(&env.ctx(), 0, 100).unwrap();
+        subs.process_due(&env.ctx(), BOB, 0).unwrap();
+    }
+
+    #[test]
+    fn test_cancel() {
+        let (env, mut subs) = setup();
+        subscribe_bob(&env, &mut subs, 300);
+
+        env.set_sender(CHARLIE);
+        let err = subs.cancel(&env.ctx(), BOB, 0).unwrap_err();
+        assert_err_contains(&err, "only subscriber or merchant can cancel");
+
+        // The merchant may cancel too; the unused budget goes back to BOB
+        env.set_sender(ALICE);
+        env.clear_transfers();
+        let resp = subs.cancel(&env.ctx(), BOB, 0).unwrap();
+        assert_attribute(&resp, "refund", "200");
+        let transfers = env.transfers();
+        assert_eq!(transfers[0].0, CONTRACT_ADDR.to_vec());
+        assert_eq!(transfers[0].1, BOB.to_vec());
+        assert_eq!(transfers[0].3, 200);
+
+        env.set_timestamp(2000);
+        let err = subs.process_due(&env.ctx(), BOB, 0).unwrap_err();
+        assert_err_contains(&err, "subscription is not active");
+
+        // Resubscribing starts a fresh billing cycle
+        env.set_sender(BOB);
+        let err = subs.subscribe(&env.ctx(), 0).unwrap_err();
+        assert_err_contains(&err, "insufficient allowance");
+        subs.approve(&env.ctx(), 0, 100).unwrap();
+        subs.subscribe(&env.ctx(), 0).unwrap();
+        let resp = subs.get_subscription(&env.ctx(), BOB, 0).unwrap();
+        let sub: Subscription = from_response(&resp).unwrap();
+        assert_eq!(sub.next_due, 3000);
+    }
+
+    #[test]
+    fn test_cannot_subscribe_twice() {
+        let (env, mut subs) = setup();
+        subscribe_bob(&env, &mut subs, 300);
+        let err = subs.subscribe(&env.ctx(), 0).unwrap_err();
+        assert_err_contains(&err, "already subscribed");
+    }
+
+    #[test]
+    fn test_deactivated_plan_lapses_subscriptions() {
+        let (env, mut subs) = setup();
+        subscribe_bob(&env, &mut subs, 300);
+
+        env.set_sender(ALICE);
+        subs.deactivate_plan(&env.ctx(), 0).unwrap();
+
+        env.set_timestamp(2000);
+        let resp = subs.process_due(&env.ctx(), BOB, 0).unwrap();
+        assert_attribute(&resp, "action", "lapse");
+
+        env.set_sender(CHARLIE);
+        let err = subs.subscribe(&env.ctx(), 0).unwrap_err();
+        assert_err_contains(&err, "plan is not active");
+    }
+}
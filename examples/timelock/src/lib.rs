@@ -1,5 +1,9 @@
 //! Time-locked Vault — deposit tokens with an unlock date.
 //! Self-custody with a forced hold period.
+//!
+//! Locks can name a beneficiary who may also claim after unlock (for
+//! inheritance-style locks), and can opt into early withdrawal, which
+//! releases funds before unlock minus a penalty sent to a chosen address.
 
 #![no_std]
 
@@ -13,6 +17,8 @@ use norn_sdk::prelude::*;
 const LOCK_COUNT: Item<u64> = Item::new("lock_count");
 const LOCKS: Map<u64, LockInfo> = Map::new("locks");
 
+const MAX_PENALTY_BPS: u16 = 10_000;
+
 // ── Types ──────────────────────────────────────────────────────────────
 
 #[derive(Debug, BorshSerialize, BorshDeserialize, Clone)]
@@ -24,6 +30,17 @@ pub struct LockInfo {
     pub unlock_time: u64,
     pub withdrawn: bool,
     pub created_at: u64,
+    /// May withdraw after unlock in addition to the owner.
+    pub beneficiary: Option<Address>,
+    pub early_withdrawal: Option<EarlyWithdrawal>,
+}
+
+/// Terms for withdrawing before `unlock_time`.
+#[derive(Debug, BorshSerialize, BorshDeserialize, Clone, PartialEq)]
+pub struct EarlyWithdrawal {
+    pub penalty_bps: u16,
+    /// Receives the penalty; `ZERO_ADDRESS` burns it.
+    pub penalty_recipient: Address,
 }
 
 // ── Contract ───────────────────────────────────────────────────────────
@@ -47,46 +64,28 @@ impl Timelock {
         amount: u128,
         unlock_time: u64,
     ) -> ContractResult {
-        ensure!(amount > 0, "amount must be positive");
-        ensure!(
-            unlock_time > ctx.timestamp(),
-            "unlock_time must be in the future"
-        );
-
-        let contract = ctx.contract_address();
-        ctx.transfer(&ctx.sender(), &contract, &token_id, amount);
-
-        let id = LOCK_COUNT.load_or(0u64);
-        LOCKS.save(
-            &id,
-            &LockInfo {
-                id,
-                owner: ctx.sender(),
-                token_id,
-                amount,
-                unlock_time,
-                withdrawn: false,
-                created_at: ctx.timestamp(),
-            },
-        )?;
-        LOCK_COUNT.save(&safe_add_u64(id, 1)?)?;
-
-        Ok(Response::with_action("lock")
-            .add_attribute("lock_id", format!("{}", id))
-            .set_data(&id))
+        create_lock(ctx, token_id, amount, unlock_time, None, None)
     }
 
+    /// Withdraw after unlock. The owner or, if set, the beneficiary can
+    /// withdraw; tokens go to the caller.
     #[execute]
     pub fn withdraw(&mut self, ctx: &Context, lock_id: u64) -> ContractResult {
         let mut lock = LOCKS.load(&lock_id)?;
-        ensure!(ctx.sender() == lock.owner, "only owner can withdraw");
+        match lock.beneficiary {
+            Some(beneficiary) => ensure!(
+                ctx.sender() == lock.owner || ctx.sender() == beneficiary,
+                "only owner or beneficiary can withdraw"
+            ),
+            None => ensure!(ctx.sender() == lock.owner, "only owner can withdraw"),
+        }
         ensure!(!lock.withdrawn, "already withdrawn");
         ensure!(
             ctx.timestamp() >= lock.unlock_time,
             "tokens are still locked"
         );
 
-        ctx.transfer_from_contract(&lock.owner, &lock.token_id, lock.amount);
+        ctx.transfer_from_contract(&ctx.sender(), &lock.token_id, lock.amount);
         lock.withdrawn = true;
         LOCKS.save(&lock_id, &lock)?;
 
@@ -95,6 +94,91 @@ impl Timelock {
             .add_attribute("amount", format!("{}", lock.amount)))
     }
 
+    /// Lock tokens with an optional beneficiary and early-withdrawal terms.
+    #[execute]
+    pub fn lock_with_options(
+        &mut self,
+        ctx: &Context,
+        token_id: TokenId,
+        amount: u128,
+        unlock_time: u64,
+        beneficiary: Option<Address>,
+        early_withdrawal: Option<EarlyWithdrawal>,
+    ) -> ContractResult {
+        if let Some(b) = beneficiary {
+            ensure!(b != ZERO_ADDRESS, "beneficiary cannot be zero");
+        }
+        if let Some(terms) = &early_withdrawal {
+            ensure!(
+                terms.penalty_bps <= MAX_PENALTY_BPS,
+                "penalty cannot exceed 100%"
+            );
+        }
+        create_lock(
+            ctx,
+            token_id,
+            amount,
+            unlock_time,
+            beneficiary,
+            early_withdrawal,
+        )
+    }
+
+    /// Change or clear the beneficiary. Owner only, before withdrawal.
+    #[execute]
+    pub fn set_beneficiary(
+        &mut self,
+        ctx: &Context,
+        lock_id: u64,
+        beneficiary: Option<Address>,
+    ) -> ContractResult {
+        let mut lock = LOCKS.load(&lock_id)?;
+        ensure!(ctx.sender() == lock.owner, "only owner can set beneficiary");
+        ensure!(!lock.withdrawn, "already withdrawn");
+        if let Some(b) = beneficiary {
+            ensure!(b != ZERO_ADDRESS, "beneficiary cannot be zero");
+        }
+
+        lock.beneficiary = beneficiary;
+        LOCKS.save(&lock_id, &lock)?;
+
+        Ok(Response::with_action("set_beneficiary")
+            .add_attribute("lock_id", format!("{}", lock_id)))
+    }
+
+    /// Withdraw before unlock, paying the lock's early-withdrawal penalty.
+    /// Owner only.
+    #[execute]
+    pub fn withdraw_early(&mut self, ctx: &Context, lock_id: u64) -> ContractResult {
+        let mut lock = LOCKS.load(&lock_id)?;
+        ensure!(ctx.sender() == lock.owner, "only owner can withdraw");
+        ensure!(!lock.withdrawn, "already withdrawn");
+        ensure!(
+            ctx.timestamp() < lock.unlock_time,
+            "lock has expired, use withdraw"
+        );
+        let terms = lock
+            .early_withdrawal
+            .clone()
+            .ok_or("early withdrawal not enabled")?;
+
+        let penalty = safe_mul(lock.amount, terms.penalty_bps as u128)? / 10_000;
+        let payout = safe_sub(lock.amount, penalty)?;
+        if penalty > 0 {
+            ctx.transfer_from_contract(&terms.penalty_recipient, &lock.token_id, penalty);
+        }
+        if payout > 0 {
+            ctx.transfer_from_contract(&lock.owner, &lock.token_id, payout);
+        }
+        lock.withdrawn = true;
+        LOCKS.save(&lock_id, &lock)?;
+
+        Ok(Response::with_action("withdraw_early")
+            .add_attribute("lock_id", format!("{}", lock_id))
+            .add_attribute("amount", format!("{}", payout))
+            .add_attribute("penalty", format!("{}", penalty)))
+    }
+
     #[query]
     pub fn get_lock(&self, _ctx: &Context, lock_id: u64) -> ContractResult {
         let lock = LOCKS.load(&lock_id)?;
@@ -108,6 +192,47 @@ impl Timelock {
     }
 }
 
+// ── Helpers ────────────────────────────────────────────────────────────
+
+fn create_lock(
+    ctx: &Context,
+    token_id: TokenId,
+    amount: u128,
+    unlock_time: u64,
+    beneficiary: Option<Address>,
+    early_withdrawal: Option<EarlyWithdrawal>,
+) -> ContractResult {
+    ensure!(amount > 0, "amount must be positive");
+    ensure!(
+        unlock_time > ctx.timestamp(),
+        "unlock_time must be in the future"
+    );
+
+    let contract = ctx.contract_address();
+    ctx.transfer(&ctx.sender(), &contract, &token_id, amount);
+
+    let id = LOCK_COUNT.load_or(0u64);
+    LOCKS.save(
+        &id,
+        &LockInfo {
+            id,
+            owner: ctx.sender(),
+            token_id,
+            amount,
+            unlock_time,
+            withdrawn: false,
+            created_at: ctx.timestamp(),
+            beneficiary,
+            early_withdrawal,
+        },
+    )?;
+    LOCK_COUNT.save(&safe_add_u64(id, 1)?)?;
+
+    Ok(Response::with_action("lock")
+        .add_attribute("lock_id", format!("{}", id))
+        .set_data(&id))
+}
+
 // ── Tests ──────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -222,4 +347,86 @@ mod tests {
         assert_eq!(lock.token_id, token_b);
         assert_eq!(lock.amount, 2_000);
     }
+
+    #[test]
+    fn test_beneficiary_claims_after_unlock() {
+        let (env, mut tl) = setup();
+        tl.lock_with_options(&env.ctx(), TOKEN, 5_000, 2000, Some(BOB), None)
+            .unwrap();
+
+        env.set_sender(BOB);
+        env.set_timestamp(1500);
+        let err = tl.withdraw(&env.ctx(), 0).unwrap_err();
+        assert_err_contains(&err, "tokens are still locked");
+
+        env.set_timestamp(2000);
+        env.clear_transfers();
+        tl.withdraw(&env.ctx(), 0).unwrap();
+        let transfers = env.transfers();
+        assert_eq!(transfers[0].1, BOB.to_vec());
+        assert_eq!(transfers[0].3, 5_000);
+
+        env.set_sender(CHARLIE);
+        let err = tl.withdraw(&env.ctx(), 0).unwrap_err();
+        assert_err_contains(&err, "only owner or beneficiary can withdraw");
+    }
+
+    #[test]
+    fn test_set_beneficiary_owner_only() {
+        let (env, mut tl) = setup();
+        tl.lock(&env.ctx(), TOKEN, 5_000, 2000).unwrap();
+
+        env.set_sender(BOB);
+        let err = tl.set_beneficiary(&env.ctx(), 0, Some(BOB)).unwrap_err();
+        assert_err_contains(&err, "only owner can set beneficiary");
+
+        env.set_sender(ALICE);
+        tl.set_beneficiary(&env.ctx(), 0, Some(CHARLIE)).unwrap();
+        let lock: LockInfo = from_response(&tl.get_lock(&env.ctx(), 0).unwrap()).unwrap();
+        assert_eq!(lock.beneficiary, Some(CHARLIE));
+    }
+
+    #[test]
+    fn test_early_withdrawal_with_penalty() {
+        let (env, mut tl) = setup();
+        let terms = EarlyWithdrawal {
+            penalty_bps: 1_000,
+            penalty_recipient: DAVE,
+        };
+        tl.lock_with_options(&env.ctx(), TOKEN, 5_000, 2000, None, Some(terms))
+            .unwrap();
+
+        env.set_timestamp(1500);
+        env.clear_transfers();
+        let resp = tl.withdraw_early(&env.ctx(), 0).unwrap();
+        assert_attribute(&resp, "penalty", "500");
+        assert_attribute(&resp, "amount", "4500");
+
+        let transfers = env.transfers();
+        assert_eq!(transfers[0].1, DAVE.to_vec());
+        assert_eq!(transfers[0].3, 500);
+        assert_eq!(transfers[1].1, ALICE.to_vec());
+        assert_eq!(transfers[1].3, 4_500);
+
+        env.set_timestamp(2000);
+        let err = tl.withdraw(&env.ctx(), 0).unwrap_err();
+        assert_err_contains(&err, "already withdrawn");
+    }
+
+    #[test]
+    fn test_early_withdrawal_requires_opt_in() {
+        let (env, mut tl) = setup();
+        tl.lock(&env.ctx(), TOKEN, 5_000, 2000).unwrap();
+        let err = tl.withdraw_early(&env.ctx(), 0).unwrap_err();
+        assert_err_contains(&err, "early withdrawal not enabled");
+
+        let terms = EarlyWithdrawal {
+            penalty_bps: 10_001,
+            penalty_recipient: ZERO_ADDRESS,
+        };
+        let err = tl
+            .lock_with_options(&env.ctx(), TOKEN, 5_000, 2000, None, Some(terms))
+            .unwrap_err();
+        assert_err_contains(&err, "penalty cannot exceed 100%");
+    }
 }
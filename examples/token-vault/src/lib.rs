@@ -1,5 +1,9 @@
-//! Token vault contract — demonstrates `#[norn_contract]` with `Item` storage,
-//! `Response` builder, guard macros, and native testing.
+//! Token vault contract — demonstrates `#[norn_contract]` with `Item` and
+//! `Map` storage, `Response` builder, guard macros, and native testing.
+//!
+//! Any address can deposit any token. Deposits are pulled into the contract's
+//! own address and tracked per depositor and per token; withdrawals pay out
+//! from the contract.
 
 #![no_std]
 
@@ -11,8 +15,8 @@ use norn_sdk::prelude::*;
 
 const OWNER: Item<Address> = Item::new("owner");
 const NAME: Item<String> = Item::new("name");
-const BALANCE: Item<u128> = Item::new("balance");
-const TOKEN_ID: Item<TokenId> = Item::new("token_id");
+const BALANCES: Map<(Address, TokenId), u128> = Map::new("balances");
+const TOTALS: Map<TokenId, u128> = Map::new("totals");
 
 // ── Contract ───────────────────────────────────────────────────────────────
 
@@ -20,8 +24,6 @@ const TOKEN_ID: Item<TokenId> = Item::new("token_id");
 pub struct VaultInfo {
     pub owner: Address,
     pub name: String,
-    pub balance: u128,
-    pub token_id: TokenId,
 }
 
 #[norn_contract]
@@ -33,32 +35,45 @@ impl TokenVault {
     pub fn new(ctx: &Context) -> Self {
         OWNER.init(&ctx.sender());
         NAME.init(&String::from("vault"));
-        BALANCE.init(&0u128);
-        TOKEN_ID.init(&[0u8; 32]);
         TokenVault
     }
 
+    /// Pull `amount` of `token_id` from the sender into the vault.
     #[execute]
-    pub fn deposit(&mut self, _ctx: &Context, amount: u128) -> ContractResult {
+    pub fn deposit(&mut self, ctx: &Context, token_id: TokenId, amount: u128) -> ContractResult {
         ensure!(amount > 0, "deposit amount must be positive");
-        let bal = BALANCE.load_or(0u128);
-        let new_bal = safe_add(bal, amount)?;
-        BALANCE.save(&new_bal)?;
+        let contract = ctx.contract_address();
+        ctx.transfer(&ctx.sender(), &contract, &token_id, amount);
+
+        let key = (ctx.sender(), token_id);
+        let new_bal = safe_add(BALANCES.load_or(&key, 0u128), amount)?;
+        BALANCES.save(&key, &new_bal)?;
+        TOTALS.save(&token_id, &safe_add(TOTALS.load_or(&token_id, 0u128), amount)?)?;
+
         Ok(Response::with_action("deposit")
             .add_u128("amount", amount)
             .set_data(&new_bal))
     }
 
+    /// Withdraw from the sender's own balance to `to`.
     #[execute]
-    pub fn withdraw(&mut self, ctx: &Context, to: Address, amount: u128) -> ContractResult {
-        let owner = OWNER.load()?;
-        ctx.require_sender(&owner)?;
-        let bal = BALANCE.load_or(0u128);
+    pub fn withdraw(
+        &mut self,
+        ctx: &Context,
+        token_id: TokenId,
+        to: Address,
+        amount: u128,
+    ) -> ContractResult {
+        ensure!(amount > 0, "withdraw amount must be positive");
+        let key = (ctx.sender(), token_id);
+        let bal = BALANCES.load_or(&key, 0u128);
         ensure!(amount <= bal, ContractError::InsufficientFunds);
+
         let new_bal = bal - amount;
-        BALANCE.save(&new_bal)?;
-        let token = TOKEN_ID.load_or([0u8; 32]);
-        ctx.transfer(&owner, &to, &token, amount);
+        BALANCES.save(&key, &new_bal)?;
+        TOTALS.save(&token_id, &safe_sub(TOTALS.load_or(&token_id, 0u128), amount)?)?;
+        ctx.transfer_from_contract(&to, &token_id, amount);
+
         Ok(Response::with_action("withdraw")
             .add_u128("amount", amount)
             .set_data(&new_bal))
@@ -78,10 +93,19 @@ impl TokenVault {
         ok(VaultInfo {
             owner: OWNER.load_or(ZERO_ADDRESS),
             name: NAME.load_or(String::from("")),
-            balance: BALANCE.load_or(0u128),
-            token_id: TOKEN_ID.load_or([0u8; 32]),
         })
     }
+
+    #[query]
+    pub fn get_balance(&self, _ctx: &Context, addr: Address, token_id: TokenId) -> ContractResult {
+        ok(BALANCES.load_or(&(addr, token_id), 0u128))
+    }
+
+    /// Total custody of `token_id` across all depositors.
+    #[query]
+    pub fn get_total(&self, _ctx: &Context, token_id: TokenId) -> ContractResult {
+        ok(TOTALS.load_or(&token_id, 0u128))
+    }
 }
 
 // ── Tests ──────────────────────────────────────────────────────────────────
@@ -91,77 +115,115 @@ mod tests {
     use super::*;
     use norn_sdk::testing::*;
 
+    const NORN: TokenId = [0u8; 32];
+    const TOKEN: TokenId = [42u8; 32];
+    const CONTRACT_ADDR: Address = [99u8; 20];
+
+    fn setup() -> (TestEnv, TokenVault) {
+        let env = TestEnv::new()
+            .with_sender(ALICE)
+            .with_contract_address(CONTRACT_ADDR);
+        let vault = TokenVault::new(&env.ctx());
+        (env, vault)
+    }
+
     #[test]
     fn test_init_sets_owner() {
         let env = TestEnv::new().with_sender(ALICE);
         TokenVault::new(&env.ctx());
         assert_eq!(OWNER.load().unwrap(), ALICE);
-        assert_eq!(BALANCE.load().unwrap(), 0);
     }
 
     #[test]
-    fn test_deposit() {
-        let env = TestEnv::new().with_sender(ALICE);
-        let mut vault = TokenVault::new(&env.ctx());
-        let resp = vault.deposit(&env.ctx(), 500).unwrap();
+    fn test_deposit_pulls_tokens() {
+        let (env, mut vault) = setup();
+        let resp = vault.deposit(&env.ctx(), NORN, 500).unwrap();
         assert_attribute(&resp, "action", "deposit");
         assert_attribute(&resp, "amount", "500");
         assert_data::<u128>(&resp, &500);
+
+        let transfers = env.transfers();
+        assert_eq!(transfers.len(), 1);
+        assert_eq!(transfers[0].0, ALICE.to_vec());
+        assert_eq!(transfers[0].1, CONTRACT_ADDR.to_vec());
+        assert_eq!(transfers[0].3, 500);
     }
 
     #[test]
     fn test_deposit_zero_fails() {
-        let env = TestEnv::new().with_sender(ALICE);
-        let mut vault = TokenVault::new(&env.ctx());
-        let err = vault.deposit(&env.ctx(), 0).unwrap_err();
+        let (env, mut vault) = setup();
+        let err = vault.deposit(&env.ctx(), NORN, 0).unwrap_err();
         assert_eq!(err.message(), "deposit amount must be positive");
     }
 
     #[test]
-    fn test_withdraw_owner_only() {
-        let env = TestEnv::new().with_sender(ALICE);
-        let mut vault = TokenVault::new(&env.ctx());
-        vault.deposit(&env.ctx(), 100).unwrap();
-
-        // Bob tries to withdraw
+    fn test_per_depositor_per_token_accounting() {
+        let (env, mut vault) = setup();
+        vault.deposit(&env.ctx(), NORN, 100).unwrap();
+        vault.deposit(&env.ctx(), TOKEN, 30).unwrap();
         env.set_sender(BOB);
-        let err = vault.withdraw(&env.ctx(), BOB, 50).unwrap_err();
-        assert_eq!(err, ContractError::Unauthorized);
+        vault.deposit(&env.ctx(), NORN, 50).unwrap();
+
+        let bal: u128 =
+            from_response(&vault.get_balance(&env.ctx(), ALICE, TOKEN).unwrap()).unwrap();
+        assert_eq!(bal, 30);
+        let bal: u128 =
+            from_response(&vault.get_balance(&env.ctx(), BOB, NORN).unwrap()).unwrap();
+        assert_eq!(bal, 50);
+        let total: u128 = from_response(&vault.get_total(&env.ctx(), NORN).unwrap()).unwrap();
+        assert_eq!(total, 150);
+    }
 
-        // Alice withdraws
-        env.set_sender(ALICE);
-        let resp = vault.withdraw(&env.ctx(), BOB, 50).unwrap();
-        assert_data::<u128>(&resp, &50);
+    #[test]
+    fn test_withdraw_pays_from_contract() {
+        let (env, mut vault) = setup();
+        vault.deposit(&env.ctx(), NORN, 100).unwrap();
+        env.clear_transfers();
+
+        let resp = vault.withdraw(&env.ctx(), NORN, BOB, 40).unwrap();
+        assert_data::<u128>(&resp, &60);
+
+        let transfers = env.transfers();
+        assert_eq!(transfers[0].0, CONTRACT_ADDR.to_vec());
+        assert_eq!(transfers[0].1, BOB.to_vec());
+        assert_eq!(transfers[0].3, 40);
     }
 
     #[test]
-    fn test_withdraw_insufficient() {
-        let env = TestEnv::new().with_sender(ALICE);
-        let mut vault = TokenVault::new(&env.ctx());
-        vault.deposit(&env.ctx(), 10).unwrap();
-        let err = vault.withdraw(&env.ctx(), BOB, 100).unwrap_err();
+    fn test_withdraw_only_own_balance() {
+        let (env, mut vault) = setup();
+        vault.deposit(&env.ctx(), NORN, 100).unwrap();
+
+        // Bob has no balance of his own
+        env.set_sender(BOB);
+        let err = vault.withdraw(&env.ctx(), NORN, BOB, 50).unwrap_err();
+        assert_eq!(err, ContractError::InsufficientFunds);
+
+        // Alice's balance is per token
+        env.set_sender(ALICE);
+        let err = vault.withdraw(&env.ctx(), TOKEN, ALICE, 1).unwrap_err();
         assert_eq!(err, ContractError::InsufficientFunds);
     }
 
     #[test]
     fn test_set_name() {
-        let env = TestEnv::new().with_sender(ALICE);
-        let mut vault = TokenVault::new(&env.ctx());
+        let (env, mut vault) = setup();
         let resp = vault.set_name(&env.ctx(), String::from("my-vault")).unwrap();
         assert_attribute(&resp, "action", "set_name");
         assert_attribute(&resp, "name", "my-vault");
         assert_eq!(NAME.load().unwrap(), "my-vault");
+
+        env.set_sender(BOB);
+        let err = vault.set_name(&env.ctx(), String::from("bobs")).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized);
     }
 
     #[test]
     fn test_query_info() {
-        let env = TestEnv::new().with_sender(ALICE);
-        let mut vault = TokenVault::new(&env.ctx());
-        vault.deposit(&env.ctx(), 42).unwrap();
+        let (env, vault) = setup();
         let resp = vault.get_info(&env.ctx()).unwrap();
         let info: VaultInfo = from_response(&resp).unwrap();
         assert_eq!(info.owner, ALICE);
-        assert_eq!(info.balance, 42);
         assert_eq!(info.name, "vault");
     }
 }
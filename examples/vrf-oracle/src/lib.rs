@@ -0,0 +1,327 @@
+//! VRF Oracle — verifiable randomness from registered VRF operators.
+//!
+//! The owner registers operators by their Ed25519 VRF public key. Anyone
+//! can `request_randomness` from an operator, committing to a seed; the
+//! request's VRF input (`alpha`) binds this loom, the request id, the
+//! requester, the seed and the block height. The operator answers with
+//! `fulfill`, posting an ECVRF proof (RFC 9381) of `alpha` that the loom
+//! verifies on-chain through the `norn_vrf_verify` host function. Since a
+//! proof can only verify for one output, the operator cannot bias the
+//! result, and nobody else can predict it before it is posted. Anyone may
+//! relay the proof: it authenticates itself.
+//!
+//! `get_randomness` returns the 64-byte output of a fulfilled request. It is
+//! an execute method so other looms can reach it through a cross-contract
+//! call with input `[2] || borsh(request_id)`.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use norn_sdk::prelude::*;
+
+/// Most registered operators.
+const MAX_OPERATORS: usize = 32;
+
+/// An Ed25519 VRF public key.
+pub type VrfPublicKey = [u8; 32];
+/// An ECVRF-EDWARDS25519-SHA512-TAI proof.
+pub type VrfProof = [u8; 80];
+/// A VRF output.
+pub type Randomness = [u8; 64];
+
+// ── Storage ──────────────────────────────────────────────────────────────
+
+const OPERATORS: Item<Vec<VrfPublicKey>> = Item::new("operators");
+const NEXT_REQUEST_ID: Item<u64> = Item::new("next_request_id");
+const REQUESTS: Map<u64, RandomnessRequest> = Map::new("requests");
+
+// ── Types ────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct RandomnessRequest {
+    pub requester: Address,
+    /// Public key of the operator that must answer.
+    pub operator: VrfPublicKey,
+    pub seed: [u8; 32],
+    pub block_height: u64,
+    /// The VRF output, once fulfilled.
+    pub randomness: Option<Randomness>,
+}
+
+// ── Contract ─────────────────────────────────────────────────────────────
+
+#[norn_contract]
+pub struct VrfOracle;
+
+#[norn_contract]
+impl VrfOracle {
+    #[init]
+    pub fn new(ctx: &Context) -> Self {
+        Ownable::init(&ctx.sender()).unwrap();
+        OPERATORS.init(&Vec::new());
+        NEXT_REQUEST_ID.init(&0);
+        VrfOracle
+    }
+
+    // ── Execute ──────────────────────────────────────────────────────
+
+    /// Request randomness from `operator`, returning the request id.
+    #[execute]
+    pub fn request_randomness(
+        &mut self,
+        ctx: &Context,
+        operator: VrfPublicKey,
+        seed: [u8; 32],
+    ) -> ContractResult {
+        ensure!(
+            OPERATORS.load()?.contains(&operator),
+            "not a registered operator"
+        );
+        let id = NEXT_REQUEST_ID.load()?;
+        let request = RandomnessRequest {
+            requester: ctx.sender(),
+            operator,
+            seed,
+            block_height: ctx.block_height(),
+            randomness: None,
+        };
+        REQUESTS.save(&id, &request)?;
+        NEXT_REQUEST_ID.save(&safe_add_u64(id, 1)?)?;
+        Ok(Response::with_action("request_randomness")
+            .add_event(
+                Event::new("RandomnessRequested")
+                    .add_u128("request_id", id as u128)
+                    .add_address("requester", &request.requester)
+                    .add_attribute("operator", to_hex(&operator))
+                    .add_attribute("alpha", to_hex(&alpha(ctx, id, &request))),
+            )
+            .set_data(&id))
+    }
+
+    /// Answer a request with the operator's VRF proof of its `alpha`.
+    #[execute]
+    pub fn fulfill(&mut self, ctx: &Context, request_id: u64, proof: VrfProof) -> ContractResult {
+        let mut request = load_request(request_id)?;
+        ensure!(request.randomness.is_none(), "request already fulfilled");
+        let randomness = ctx
+            .vrf_verify(&request.operator, &alpha(ctx, request_id, &request), &proof)
+            .ok_or("invalid VRF proof")?;
+        request.randomness = Some(randomness);
+        REQUESTS.save(&request_id, &request)?;
+        Ok(Response::with_action("fulfill").add_event(
+            Event::new("RandomnessFulfilled")
+                .add_u128("request_id", request_id as u128)
+                .add_attribute("randomness", to_hex(&randomness)),
+        ))
+    }
+
+    /// Output of a fulfilled request, for cross-contract callers.
+    #[execute]
+    pub fn get_randomness(&mut self, _ctx: &Context, request_id: u64) -> ContractResult {
+        ok(load_request(request_id)?
+            .randomness
+            .ok_or("request not fulfilled")?)
+    }
+
+    /// Register a VRF operator (owner only).
+    #[execute]
+    pub fn add_operator(&mut self, ctx: &Context, operator: VrfPublicKey) -> ContractResult {
+        Ownable::require_owner(ctx)?;
+        let mut operators = OPERATORS.load()?;
+        ensure!(!operators.contains(&operator), "already an operator");
+        ensure!(operators.len() < MAX_OPERATORS, "too many operators");
+        operators.push(operator);
+        OPERATORS.save(&operators)?;
+        Ok(Response::with_action("add_operator").add_attribute("operator", to_hex(&operator)))
+    }
+
+    /// Deregister a VRF operator (owner only). Its pending requests can
+    /// still be fulfilled; it takes no new ones.
+    #[execute]
+    pub fn remove_operator(&mut self, ctx: &Context, operator: VrfPublicKey) -> ContractResult {
+        Ownable::require_owner(ctx)?;
+        let mut operators = OPERATORS.load()?;
+        let idx = operators
+            .iter()
+            .position(|o| *o == operator)
+            .ok_or("not an operator")?;
+        operators.remove(idx);
+        OPERATORS.save(&operators)?;
+        Ok(Response::with_action("remove_operator").add_attribute("operator", to_hex(&operator)))
+    }
+
+    // ── Query ────────────────────────────────────────────────────────
+
+    #[query]
+    pub fn get_request(&self, _ctx: &Context, request_id: u64) -> ContractResult {
+        ok(REQUESTS.load(&request_id).ok())
+    }
+
+    /// The VRF input the operator must prove for `request_id`.
+    #[query]
+    pub fn get_alpha(&self, ctx: &Context, request_id: u64) -> ContractResult {
+        let request = load_request(request_id)?;
+        ok(alpha(ctx, request_id, &request))
+    }
+
+    #[query]
+    pub fn operators(&self, _ctx: &Context) -> ContractResult {
+        ok(OPERATORS.load()?)
+    }
+}
+
+fn load_request(request_id: u64) -> Result<RandomnessRequest, ContractError> {
+    REQUESTS
+        .load(&request_id)
+        .map_err(|_| ContractError::Custom(format!("unknown request {request_id}")))
+}
+
+/// VRF input of a request: `loom address || request_id || requester ||
+/// seed || block_height`, integers little-endian.
+pub fn alpha(ctx: &Context, request_id: u64, request: &RandomnessRequest) -> Vec<u8> {
+    let mut alpha = Vec::with_capacity(88);
+    alpha.extend_from_slice(&ctx.contract_address());
+    alpha.extend_from_slice(&request_id.to_le_bytes());
+    alpha.extend_from_slice(&request.requester);
+    alpha.extend_from_slice(&request.seed);
+    alpha.extend_from_slice(&request.block_height.to_le_bytes());
+    alpha
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use norn_crypto::keys::Keypair;
+    use norn_sdk::testing::*;
+
+    fn operator() -> Keypair {
+        Keypair::from_seed(&[7u8; 32])
+    }
+
+    fn setup() -> (TestEnv, VrfOracle) {
+        let env = TestEnv::new().with_sender(ALICE).with_block_height(10);
+        let mut oracle = VrfOracle::new(&env.ctx());
+        oracle
+            .add_operator(&env.ctx(), operator().public_key())
+            .unwrap();
+        (env, oracle)
+    }
+
+    fn request(env: &TestEnv, oracle: &mut VrfOracle, seed: u8) -> u64 {
+        env.set_sender(BOB);
+        let resp = oracle
+            .request_randomness(&env.ctx(), operator().public_key(), [seed; 32])
+            .unwrap();
+        from_response(&resp).unwrap()
+    }
+
+    fn prove(env: &TestEnv, oracle: &VrfOracle, keypair: &Keypair, id: u64) -> VrfProof {
+        let alpha: Vec<u8> = from_response(&oracle.get_alpha(&env.ctx(), id).unwrap()).unwrap();
+        norn_crypto::vrf::prove(keypair, &alpha)
+    }
+
+    #[test]
+    fn test_cross_call_discriminant() {
+        let input = borsh::to_vec(&__VrfOracleExecute::GetRandomness { request_id: 3 }).unwrap();
+        assert_eq!(input[0], 2);
+    }
+
+    #[test]
+    fn test_request_and_fulfill() {
+        let (env, mut oracle) = setup();
+        let id = request(&env, &mut oracle, 1);
+        assert_eq!(id, 0);
+        let err = oracle.get_randomness(&env.ctx(), id).unwrap_err();
+        assert_eq!(err.message(), "request not fulfilled");
+
+        // Anyone can relay the operator's proof.
+        let proof = prove(&env, &oracle, &operator(), id);
+        env.set_sender(CHARLIE);
+        let resp = oracle.fulfill(&env.ctx(), id, proof).unwrap();
+        let expected = norn_crypto::vrf::proof_to_hash(&proof).unwrap();
+        assert_event_attribute(
+            &resp,
+            "RandomnessFulfilled",
+            "randomness",
+            &to_hex(&expected),
+        );
+
+        let resp = oracle.get_randomness(&env.ctx(), id).unwrap();
+        assert_eq!(from_response::<Randomness>(&resp).unwrap(), expected);
+        let err = oracle.fulfill(&env.ctx(), id, proof).unwrap_err();
+        assert_eq!(err.message(), "request already fulfilled");
+    }
+
+    #[test]
+    fn test_rejects_invalid_proofs() {
+        let (env, mut oracle) = setup();
+        let first = request(&env, &mut oracle, 1);
+        let second = request(&env, &mut oracle, 1);
+
+        // Another key, or a proof for another request, does not verify.
+        let impostor = prove(&env, &oracle, &Keypair::from_seed(&[8u8; 32]), first);
+        let err = oracle.fulfill(&env.ctx(), first, impostor).unwrap_err();
+        assert_eq!(err.message(), "invalid VRF proof");
+        let other = prove(&env, &oracle, &operator(), second);
+        assert!(oracle.fulfill(&env.ctx(), first, other).is_err());
+        let mut tampered = prove(&env, &oracle, &operator(), first);
+        tampered[40] ^= 1;
+        assert!(oracle.fulfill(&env.ctx(), first, tampered).is_err());
+
+        let err = oracle.fulfill(&env.ctx(), 9, other).unwrap_err();
+        assert_eq!(err.message(), "unknown request 9");
+        oracle.fulfill(&env.ctx(), second, other).unwrap();
+    }
+
+    #[test]
+    fn test_alpha_binds_request() {
+        let (env, mut oracle) = setup();
+        let a = request(&env, &mut oracle, 1);
+        let b = request(&env, &mut oracle, 1);
+        env.set_block_height(11);
+        let c = request(&env, &mut oracle, 2);
+        let alphas: Vec<Vec<u8>> = [a, b, c]
+            .iter()
+            .map(|id| from_response(&oracle.get_alpha(&env.ctx(), *id).unwrap()).unwrap())
+            .collect();
+        assert_ne!(alphas[0], alphas[1]);
+        assert_ne!(alphas[1], alphas[2]);
+        assert_eq!(alphas[0].len(), 88);
+    }
+
+    #[test]
+    fn test_operator_management() {
+        let (env, mut oracle) = setup();
+        let key = operator().public_key();
+        env.set_sender(BOB);
+        let err = oracle.add_operator(&env.ctx(), [1u8; 32]).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized);
+        let err = oracle
+            .request_randomness(&env.ctx(), [1u8; 32], [0u8; 32])
+            .unwrap_err();
+        assert_eq!(err.message(), "not a registered operator");
+
+        let id = request(&env, &mut oracle, 1);
+        env.set_sender(ALICE);
+        let err = oracle.add_operator(&env.ctx(), key).unwrap_err();
+        assert_eq!(err.message(), "already an operator");
+        oracle.remove_operator(&env.ctx(), key).unwrap();
+        let ops: Vec<VrfPublicKey> = from_response(&oracle.operators(&env.ctx()).unwrap()).unwrap();
+        assert!(ops.is_empty());
+
+        // No new requests, but pending ones can still be answered.
+        assert!(oracle
+            .request_randomness(&env.ctx(), key, [0u8; 32])
+            .is_err());
+        let proof = prove(&env, &oracle, &operator(), id);
+        oracle.fulfill(&env.ctx(), id, proof).unwrap();
+    }
+}
@@ -0,0 +1,240 @@
+//! Wrapped NORN (wNORN) — the native token as a Norn20.
+//!
+//! Depositing NORN pulls it into the contract's own address and mints the
+//! same amount of wNORN; withdrawing burns wNORN and pays the NORN back out.
+//! Every wNORN is backed 1:1 by NORN held at the contract address, so looms
+//! that speak Norn20 can handle the native asset through the same interface
+//! as any other token. There is no owner and no way to mint without a deposit.
+
+#![no_std]
+
+extern crate alloc;
+
+use norn_sdk::host::MAX_TRANSFER_AMOUNT;
+use norn_sdk::prelude::*;
+
+// ── Contract ─────────────────────────────────────────────────────────────────
+
+/// Unit struct — balances and allowances live in the Norn20 storage.
+#[norn_contract]
+pub struct WrappedNorn;
+
+#[norn_contract]
+impl WrappedNorn {
+    #[init]
+    pub fn new(_ctx: &Context) -> Self {
+        Norn20::init("Wrapped NORN", "wNORN", 12).unwrap();
+        WrappedNorn
+    }
+
+    /// Wrap `amount` of the sender's NORN into wNORN.
+    #[execute]
+    pub fn deposit(&mut self, ctx: &Context, amount: u128) -> ContractResult {
+        ensure!(amount > 0, "deposit amount must be positive");
+        ensure!(
+            amount <= MAX_TRANSFER_AMOUNT,
+            "deposit amount exceeds transfer limit"
+        );
+        let sender = ctx.sender();
+        ensure!(
+            ctx.balance_of(&sender, &NATIVE_TOKEN_ID) >= amount,
+            ContractError::InsufficientFunds
+        );
+        ctx.transfer_checked(&sender, &ctx.contract_address(), &NATIVE_TOKEN_ID, amount)?;
+
+        Ok(Response::with_action("deposit")
+            .add_u128("amount", amount)
+            .merge(Norn20::mint(&sender, amount)?))
+    }
+
+    /// Unwrap `amount` of the sender's wNORN back into NORN.
+    #[execute]
+    pub fn withdraw(&mut self, ctx: &Context, amount: u128) -> ContractResult {
+        ensure!(
+            amount <= MAX_TRANSFER_AMOUNT,
+            "withdraw amount exceeds transfer limit"
+        );
+        let sender = ctx.sender();
+        let burned = Norn20::burn(&sender, amount)?;
+        ctx.transfer_checked(&ctx.contract_address(), &sender, &NATIVE_TOKEN_ID, amount)?;
+
+        Ok(Response::with_action("withdraw")
+            .add_u128("amount", amount)
+            .merge(burned))
+    }
+
+    #[execute]
+    pub fn transfer(&mut self, ctx: &Context, to: Address, amount: u128) -> ContractResult {
+        Norn20::transfer(ctx, &to, amount)
+    }
+
+    #[execute]
+    pub fn approve(&mut self, ctx: &Context, spender: Address, amount: u128) -> ContractResult {
+        Norn20::approve(ctx, &spender, amount)
+    }
+
+    #[execute]
+    pub fn transfer_from(
+        &mut self,
+        ctx: &Context,
+        from: Address,
+        to: Address,
+        amount: u128,
+    ) -> ContractResult {
+        Norn20::transfer_from(ctx, &from, &to, amount)
+    }
+
+    #[query]
+    pub fn balance(&self, _ctx: &Context, addr: Address) -> ContractResult {
+        ok(Norn20::balance_of(&addr))
+    }
+
+    #[query]
+    pub fn allowance(&self, _ctx: &Context, owner: Address, spender: Address) -> ContractResult {
+        ok(Norn20::allowance(&owner, &spender))
+    }
+
+    #[query]
+    pub fn total_supply(&self, _ctx: &Context) -> ContractResult {
+        ok(Norn20::total_supply())
+    }
+
+    #[query]
+    pub fn info(&self, _ctx: &Context) -> ContractResult {
+        ok(Norn20::info()?)
+    }
+
+    /// NORN held by the contract. Never less than the wNORN total supply.
+    #[query]
+    pub fn reserves(&self, ctx: &Context) -> ContractResult {
+        ok(ctx.balance_of(&ctx.contract_address(), &NATIVE_TOKEN_ID))
+    }
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use norn_sdk::testing::*;
+
+    const CONTRACT_ADDR: Address = [99u8; 20];
+
+    fn setup() -> (TestEnv, WrappedNorn) {
+        let env = TestEnv::new()
+            .with_sender(ALICE)
+            .with_contract_address(CONTRACT_ADDR)
+            .with_balance(ALICE, NATIVE_TOKEN_ID, 1_000)
+            .with_balance(BOB, NATIVE_TOKEN_ID, 500);
+        let wnorn = WrappedNorn::new(&env.ctx());
+        (env, wnorn)
+    }
+
+    fn reserves(env: &TestEnv, wnorn: &WrappedNorn) -> u128 {
+        from_response(&wnorn.reserves(&env.ctx()).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_init() {
+        let (env, wnorn) = setup();
+        let info: Norn20Info = from_response(&wnorn.info(&env.ctx()).unwrap()).unwrap();
+        assert_eq!(info.name, "Wrapped NORN");
+        assert_eq!(info.symbol, "wNORN");
+        assert_eq!(info.decimals, 12);
+        assert_eq!(info.total_supply, 0);
+    }
+
+    #[test]
+    fn test_deposit_mints_one_to_one() {
+        let (env, mut wnorn) = setup();
+        let resp = wnorn.deposit(&env.ctx(), 400).unwrap();
+        assert_attribute(&resp, "action", "deposit");
+        assert_event(&resp, "Mint");
+
+        assert_eq!(Norn20::balance_of(&ALICE), 400);
+        assert_eq!(Norn20::total_supply(), 400);
+        assert_eq!(env.ctx().balance_of(&ALICE, &NATIVE_TOKEN_ID), 600);
+        assert_eq!(reserves(&env, &wnorn), 400);
+
+        let transfers = env.transfers();
+        assert_eq!(transfers.len(), 1);
+        assert_eq!(transfers[0].0, ALICE.to_vec());
+        assert_eq!(transfers[0].1, CONTRACT_ADDR.to_vec());
+        assert_eq!(transfers[0].2, NATIVE_TOKEN_ID.to_vec());
+    }
+
+    #[test]
+    fn test_deposit_more_than_balance_fails() {
+        let (env, mut wnorn) = setup();
+        let err = wnorn.deposit(&env.ctx(), 1_001).unwrap_err();
+        assert_eq!(err, ContractError::InsufficientFunds);
+        assert!(env.transfers().is_empty());
+        assert_eq!(Norn20::total_supply(), 0);
+    }
+
+    #[test]
+    fn test_deposit_above_transfer_limit_fails() {
+        let (env, mut wnorn) = setup();
+        env.set_balance(ALICE, NATIVE_TOKEN_ID, u128::MAX);
+        let err = wnorn
+            .deposit(&env.ctx(), MAX_TRANSFER_AMOUNT + 1)
+            .unwrap_err();
+        assert_eq!(err.message(), "deposit amount exceeds transfer limit");
+    }
+
+    #[test]
+    fn test_withdraw_burns_and_pays_out() {
+        let (env, mut wnorn) = setup();
+        wnorn.deposit(&env.ctx(), 400).unwrap();
+        env.clear_transfers();
+
+        let resp = wnorn.withdraw(&env.ctx(), 150).unwrap();
+        assert_attribute(&resp, "action", "withdraw");
+        assert_event(&resp, "Burn");
+        assert_eq!(Norn20::balance_of(&ALICE), 250);
+        assert_eq!(env.ctx().balance_of(&ALICE, &NATIVE_TOKEN_ID), 750);
+        assert_eq!(reserves(&env, &wnorn), 250);
+
+        let transfers = env.transfers();
+        assert_eq!(transfers.len(), 1);
+        assert_eq!(transfers[0].0, CONTRACT_ADDR.to_vec());
+        assert_eq!(transfers[0].1, ALICE.to_vec());
+        assert_eq!(transfers[0].3, 150);
+    }
+
+    #[test]
+    fn test_withdraw_more_than_wrapped_fails() {
+        let (env, mut wnorn) = setup();
+        wnorn.deposit(&env.ctx(), 100).unwrap();
+        let err = wnorn.withdraw(&env.ctx(), 101).unwrap_err();
+        assert_eq!(err, ContractError::InsufficientFunds);
+        assert_eq!(reserves(&env, &wnorn), 100);
+    }
+
+    #[test]
+    fn test_transferred_wnorn_can_be_unwrapped() {
+        let (env, mut wnorn) = setup();
+        wnorn.deposit(&env.ctx(), 300).unwrap();
+        wnorn.transfer(&env.ctx(), BOB, 120).unwrap();
+
+        env.set_sender(BOB);
+        wnorn.withdraw(&env.ctx(), 120).unwrap();
+        assert_eq!(env.ctx().balance_of(&BOB, &NATIVE_TOKEN_ID), 620);
+        assert_eq!(Norn20::total_supply(), 180);
+        assert_eq!(reserves(&env, &wnorn), 180);
+    }
+
+    #[test]
+    fn test_approve_and_transfer_from() {
+        let (env, mut wnorn) = setup();
+        wnorn.deposit(&env.ctx(), 300).unwrap();
+        wnorn.approve(&env.ctx(), BOB, 200).unwrap();
+
+        env.set_sender(BOB);
+        wnorn
+            .transfer_from(&env.ctx(), ALICE, CHARLIE, 200)
+            .unwrap();
+        assert_eq!(Norn20::balance_of(&CHARLIE), 200);
+        assert_eq!(Norn20::allowance(&ALICE, &BOB), 0);
+    }
+}
@@ -1,7 +1,25 @@
+//! Address derivation and encoding.
+//!
+//! Addresses are 20 bytes. Besides `0x`-prefixed hex, they have a bech32m
+//! (BIP-350) form whose human-readable part names the network (`norn1...`
+//! on mainnet, `tnorn1...` on testnet, `dnorn1...` on dev), so a typo is
+//! caught by the checksum and an address from another network is rejected.
+
+use norn_types::error::NornError;
+use norn_types::network::NetworkId;
 use norn_types::primitives::{Address, PublicKey};
 
 use crate::hash::blake3_hash;
 
+/// bech32 data alphabet.
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Checksum constant distinguishing bech32m from bech32.
+const BECH32M_CONST: u32 = 0x2bc8_30a3;
+
+/// Maximum length of a bech32 string.
+const MAX_BECH32_LEN: usize = 90;
+
 /// Derive an address from a public key.
 /// Address = BLAKE3(pubkey)[0..20]
 pub fn pubkey_to_address(pubkey: &PublicKey) -> Address {
@@ -11,6 +29,172 @@ pub fn pubkey_to_address(pubkey: &PublicKey) -> Address {
     address
 }
 
+/// Encode an address as bech32m with the network's human-readable part.
+pub fn address_to_bech32(address: &Address, network: NetworkId) -> String {
+    bech32m_encode(network.address_hrp(), &convert_bits(address, 8, 5, true))
+}
+
+/// Decode a bech32m address, returning the network it belongs to.
+pub fn bech32_to_address(s: &str) -> Result<(NetworkId, Address), NornError> {
+    let (hrp, data) = bech32m_decode(s)?;
+    let network = NetworkId::from_address_hrp(&hrp)
+        .ok_or_else(|| invalid(format!("unknown address prefix '{hrp}'")))?;
+    let bytes = convert_bits(&data, 5, 8, false);
+    if data.len() != 32 || bytes.len() != 20 {
+        return Err(invalid("address must be 20 bytes"));
+    }
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&bytes);
+    Ok((network, address))
+}
+
+/// Parse an address given as bech32m or as hex (with or without `0x`).
+///
+/// A bech32m address for a network other than `network` is rejected; hex
+/// carries no network and is accepted as is.
+pub fn parse_address(s: &str, network: NetworkId) -> Result<Address, NornError> {
+    if is_bech32_address(s) {
+        let (found, address) = bech32_to_address(s)?;
+        if found != network {
+            return Err(invalid(format!(
+                "address is for {}, expected {}",
+                found.as_str(),
+                network.as_str()
+            )));
+        }
+        return Ok(address);
+    }
+    let hex_str = s.strip_prefix("0x").unwrap_or(s);
+    let bytes = hex::decode(hex_str).map_err(|e| invalid(format!("invalid hex: {e}")))?;
+    if bytes.len() != 20 {
+        return Err(invalid(format!(
+            "address must be 20 bytes, got {}",
+            bytes.len()
+        )));
+    }
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&bytes);
+    Ok(address)
+}
+
+/// Whether `s` looks like a bech32m address of some Norn network.
+pub fn is_bech32_address(s: &str) -> bool {
+    let lower = s.to_ascii_lowercase();
+    lower
+        .rsplit_once('1')
+        .is_some_and(|(hrp, _)| NetworkId::from_address_hrp(hrp).is_some())
+}
+
+fn invalid(reason: impl Into<String>) -> NornError {
+    NornError::InvalidAddress {
+        reason: reason.into(),
+    }
+}
+
+fn polymod(values: &[u8]) -> u32 {
+    const GEN: [u32; 5] = [
+        0x3b6a_57b2,
+        0x2650_8e6d,
+        0x1ea1_19fa,
+        0x3d42_33dd,
+        0x2a14_62b3,
+    ];
+    let mut chk: u32 = 1;
+    for &v in values {
+        let top = chk >> 25;
+        chk = ((chk & 0x01ff_ffff) << 5) ^ v as u32;
+        for (i, g) in GEN.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= g;
+            }
+        }
+    }
+    chk
+}
+
+fn hrp_expand(hrp: &str) -> Vec<u8> {
+    let bytes = hrp.as_bytes();
+    let mut out: Vec<u8> = bytes.iter().map(|b| b >> 5).collect();
+    out.push(0);
+    out.extend(bytes.iter().map(|b| b & 31));
+    out
+}
+
+/// Regroup `data` from `from`-bit to `to`-bit values. Without padding,
+/// leftover bits must be zero and fewer than `from`; otherwise the result
+/// is empty.
+fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Vec<u8> {
+    let mut acc: u32 = 0;
+    let mut bits: u32 = 0;
+    let max = (1u32 << to) - 1;
+    let mut out = Vec::new();
+    for &value in data {
+        acc = (acc << from) | value as u32;
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            out.push(((acc >> bits) & max) as u8);
+        }
+    }
+    if pad {
+        if bits > 0 {
+            out.push(((acc << (to - bits)) & max) as u8);
+        }
+    } else if bits >= from || (acc << (to - bits)) & max != 0 {
+        return Vec::new();
+    }
+    out
+}
+
+fn bech32m_encode(hrp: &str, data: &[u8]) -> String {
+    let mut values = hrp_expand(hrp);
+    values.extend_from_slice(data);
+    values.extend_from_slice(&[0; 6]);
+    let pm = polymod(&values) ^ BECH32M_CONST;
+
+    let mut out = String::with_capacity(hrp.len() + 1 + data.len() + 6);
+    out.push_str(hrp);
+    out.push('1');
+    for &d in data {
+        out.push(CHARSET[d as usize] as char);
+    }
+    for i in 0..6 {
+        out.push(CHARSET[((pm >> (5 * (5 - i))) & 31) as usize] as char);
+    }
+    out
+}
+
+fn bech32m_decode(s: &str) -> Result<(String, Vec<u8>), NornError> {
+    if s.len() > MAX_BECH32_LEN {
+        return Err(invalid("address too long"));
+    }
+    if s.bytes().any(|b| !(33..=126).contains(&b)) {
+        return Err(invalid("invalid character"));
+    }
+    let lower = s.to_ascii_lowercase();
+    if lower != s && s.to_ascii_uppercase() != s {
+        return Err(invalid("mixed-case address"));
+    }
+    let (hrp, data_part) = lower
+        .rsplit_once('1')
+        .ok_or_else(|| invalid("missing separator"))?;
+    if hrp.is_empty() || data_part.len() < 6 {
+        return Err(invalid("address too short"));
+    }
+    let data = data_part
+        .bytes()
+        .map(|c| CHARSET.iter().position(|&x| x == c).map(|p| p as u8))
+        .collect::<Option<Vec<u8>>>()
+        .ok_or_else(|| invalid("invalid character"))?;
+
+    let mut values = hrp_expand(hrp);
+    values.extend_from_slice(&data);
+    if polymod(&values) != BECH32M_CONST {
+        return Err(invalid("checksum mismatch"));
+    }
+    Ok((hrp.to_string(), data[..data.len() - 6].to_vec()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -38,4 +222,62 @@ mod tests {
         let addr = pubkey_to_address(&pubkey);
         assert_eq!(addr.len(), 20);
     }
+
+    #[test]
+    fn test_bech32m_vectors() {
+        // Valid bech32m strings from BIP-350.
+        for s in [
+            "A1LQFN3A",
+            "a1lqfn3a",
+            "abcdef1l7aum6echk45nj3s0wdvt2fg8x9yrzpqzd3ryx",
+            "split1checkupstagehandshakeupstreamerranterredcaperredlc445v",
+            "?1v759aa",
+        ] {
+            let (hrp, data) = bech32m_decode(s).unwrap();
+            assert_eq!(bech32m_encode(&hrp, &data), s.to_ascii_lowercase());
+        }
+        // A valid bech32 (not bech32m) string must not verify.
+        assert!(bech32m_decode("a12uel5l").is_err());
+        assert!(bech32m_decode("a1lqfN3a").is_err());
+    }
+
+    #[test]
+    fn test_bech32_address_roundtrip() {
+        let addr = pubkey_to_address(&[7u8; 32]);
+        for network in [NetworkId::Dev, NetworkId::Testnet, NetworkId::Mainnet] {
+            let encoded = address_to_bech32(&addr, network);
+            assert!(encoded.starts_with(&format!("{}1", network.address_hrp())));
+            assert_eq!(bech32_to_address(&encoded).unwrap(), (network, addr));
+            assert_eq!(
+                bech32_to_address(&encoded.to_ascii_uppercase()).unwrap(),
+                (network, addr)
+            );
+        }
+    }
+
+    #[test]
+    fn test_bech32_address_detects_typo() {
+        let encoded = address_to_bech32(&[9u8; 20], NetworkId::Mainnet);
+        let mut chars: Vec<char> = encoded.chars().collect();
+        let last = chars.len() - 1;
+        chars[last] = if chars[last] == 'q' { 'p' } else { 'q' };
+        let typo: String = chars.into_iter().collect();
+        assert!(bech32_to_address(&typo).is_err());
+    }
+
+    #[test]
+    fn test_parse_address_checks_network() {
+        let addr = [0xabu8; 20];
+        let mainnet = address_to_bech32(&addr, NetworkId::Mainnet);
+        assert_eq!(parse_address(&mainnet, NetworkId::Mainnet).unwrap(), addr);
+        assert!(parse_address(&mainnet, NetworkId::Testnet).is_err());
+
+        let hex_addr = hex::encode(addr);
+        assert_eq!(parse_address(&hex_addr, NetworkId::Dev).unwrap(), addr);
+        assert_eq!(
+            parse_address(&format!("0x{hex_addr}"), NetworkId::Dev).unwrap(),
+            addr
+        );
+        assert!(parse_address("0xdeadbeef", NetworkId::Dev).is_err());
+    }
 }
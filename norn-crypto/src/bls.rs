@@ -0,0 +1,445 @@
+//! BLS12-381 signatures with aggregation (minimal-pubkey-size variant of the
+//! IETF BLS signature scheme, proof-of-possession ciphersuite).
+//!
+//! Public keys live in G1 (48 bytes) and signatures in G2 (96 bytes).
+//! Signatures over the same message aggregate into a single signature that
+//! verifies against the sum of the signers' public keys. Aggregating keys is
+//! only safe once each key has proven possession of its secret, which guards
+//! against rogue-key attacks; see [`verify_possession`].
+
+use bls12_381::hash_to_curve::{ExpandMsgXmd, HashToCurve};
+use bls12_381::{
+    multi_miller_loop, G1Affine, G1Projective, G2Affine, G2Prepared, G2Projective, Gt, Scalar,
+};
+use norn_types::consensus::{
+    signer_bitmap, vote_signing_data, ConsensusPhase, QuorumCertificate, Vote,
+};
+use norn_types::error::NornError;
+use norn_types::primitives::{BlsPublicKey, BlsSignature, Hash};
+use norn_types::weave::ValidatorSet;
+use zeroize::Zeroize;
+
+use crate::keys::Keypair;
+
+/// Domain separation tag for message signatures.
+const SIG_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// Domain separation tag for proofs of possession.
+const POP_DST: &[u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// BLAKE3 key-derivation context for deriving a BLS secret from a seed.
+const KEYGEN_CONTEXT: &str = "norn-protocol 2026 bls12-381 secret key";
+
+/// A BLS12-381 secret key and its public key.
+pub struct BlsKeypair {
+    secret: Scalar,
+    public: BlsPublicKey,
+}
+
+impl BlsKeypair {
+    /// Derive a keypair from a 32-byte seed.
+    ///
+    /// Validators derive their BLS key from the same seed as their Ed25519
+    /// key, so a single secret backs both identities.
+    pub fn from_seed(seed: &[u8; 32]) -> Self {
+        let mut wide = [0u8; 64];
+        blake3::Hasher::new_derive_key(KEYGEN_CONTEXT)
+            .update(seed)
+            .finalize_xof()
+            .fill(&mut wide);
+        let mut secret = Scalar::from_bytes_wide(&wide);
+        wide.zeroize();
+        if secret == Scalar::zero() {
+            // Unreachable in practice (probability 2^-255).
+            secret = Scalar::one();
+        }
+        let public = G1Affine::from(G1Projective::generator() * secret).to_compressed();
+        Self { secret, public }
+    }
+
+    /// Derive the BLS keypair that goes with an Ed25519 keypair.
+    pub fn from_keypair(keypair: &Keypair) -> Self {
+        Self::from_seed(&keypair.seed())
+    }
+
+    /// Get the compressed public key.
+    pub fn public_key(&self) -> BlsPublicKey {
+        self.public
+    }
+
+    /// Sign a message, returning the compressed signature.
+    pub fn sign(&self, message: &[u8]) -> BlsSignature {
+        G2Affine::from(hash_to_g2(message, SIG_DST) * self.secret).to_compressed()
+    }
+
+    /// Prove possession of the secret key by signing the public key.
+    pub fn prove_possession(&self) -> BlsSignature {
+        G2Affine::from(hash_to_g2(&self.public, POP_DST) * self.secret).to_compressed()
+    }
+}
+
+impl Drop for BlsKeypair {
+    fn drop(&mut self) {
+        self.secret.zeroize();
+    }
+}
+
+/// Verify a BLS signature.
+pub fn verify(
+    message: &[u8],
+    signature: &BlsSignature,
+    pubkey: &BlsPublicKey,
+) -> Result<(), NornError> {
+    let pk = decode_public_key(pubkey)?;
+    verify_point(message, SIG_DST, signature, &pk)
+}
+
+/// Verify a proof of possession for `pubkey`.
+pub fn verify_possession(pubkey: &BlsPublicKey, proof: &BlsSignature) -> Result<(), NornError> {
+    let pk = decode_public_key(pubkey)?;
+    verify_point(pubkey, POP_DST, proof, &pk)
+}
+
+/// Aggregate signatures into one.
+///
+/// Returns `InvalidSignature` with the index of the first signature that does
+/// not decode, or index 0 if `signatures` is empty.
+pub fn aggregate_signatures(signatures: &[BlsSignature]) -> Result<BlsSignature, NornError> {
+    if signatures.is_empty() {
+        return Err(NornError::InvalidSignature { signer_index: 0 });
+    }
+    let mut acc = G2Projective::identity();
+    for (i, sig) in signatures.iter().enumerate() {
+        let point = Option::<G2Affine>::from(G2Affine::from_compressed(sig))
+            .ok_or(NornError::InvalidSignature { signer_index: i })?;
+        acc += point;
+    }
+    Ok(G2Affine::from(acc).to_compressed())
+}
+
+/// Aggregate public keys into one.
+///
+/// Every key must have passed [`verify_possession`] beforehand.
+pub fn aggregate_public_keys(pubkeys: &[BlsPublicKey]) -> Result<BlsPublicKey, NornError> {
+    if pubkeys.is_empty() {
+        return Err(NornError::InvalidKeyMaterial);
+    }
+    let mut acc = G1Projective::identity();
+    for pk in pubkeys {
+        acc += decode_public_key(pk)?;
+    }
+    Ok(G1Affine::from(acc).to_compressed())
+}
+
+/// Verify an aggregate signature by `pubkeys` over a single message.
+///
+/// Every key must have passed [`verify_possession`] beforehand.
+pub fn verify_aggregate(
+    message: &[u8],
+    signature: &BlsSignature,
+    pubkeys: &[BlsPublicKey],
+) -> Result<(), NornError> {
+    let aggregate = aggregate_public_keys(pubkeys)?;
+    verify(message, signature, &aggregate)
+}
+
+/// Aggregate `phase` votes for one view and block into a quorum certificate.
+/// Every voter must be a member of `validator_set`; repeated votes count
+/// once. Does not check the signatures or the quorum size.
+pub fn aggregate_votes(
+    phase: ConsensusPhase,
+    votes: &[Vote],
+    validator_set: &ValidatorSet,
+) -> Result<QuorumCertificate, NornError> {
+    let first = votes.first().ok_or(NornError::InsufficientQuorum {
+        have: 0,
+        need: validator_set.quorum_size(),
+    })?;
+    let mut indices = Vec::with_capacity(votes.len());
+    let mut signatures = Vec::with_capacity(votes.len());
+    for vote in votes {
+        if vote.phase != phase {
+            return Err(NornError::InvalidQuorumCertificate {
+                reason: format!("expected {:?} votes, got a {:?} vote", phase, vote.phase),
+            });
+        }
+        if vote.view != first.view || vote.block_hash != first.block_hash {
+            return Err(NornError::InvalidQuorumCertificate {
+                reason: "votes are for different views or blocks".to_string(),
+            });
+        }
+        let index = validator_set.index_of(&vote.voter).ok_or_else(|| {
+            NornError::InvalidQuorumCertificate {
+                reason: "voter is not a validator".to_string(),
+            }
+        })?;
+        if !indices.contains(&index) {
+            indices.push(index);
+            signatures.push(vote.bls_signature);
+        }
+    }
+    Ok(QuorumCertificate {
+        view: first.view,
+        block_hash: first.block_hash,
+        phase,
+        signers: signer_bitmap(indices, validator_set.len()),
+        signature: aggregate_signatures(&signatures)?,
+    })
+}
+
+/// Verify that `qc` commits the block with `block_hash`: it is a commit
+/// certificate for that block, valid under `validator_set`. The phase is
+/// part of the signed data, so a certificate from an earlier phase cannot
+/// pass by relabelling.
+pub fn verify_commit_certificate(
+    qc: &QuorumCertificate,
+    block_hash: &Hash,
+    validator_set: &ValidatorSet,
+) -> Result<(), NornError> {
+    if qc.phase != ConsensusPhase::Commit {
+        return Err(NornError::InvalidQuorumCertificate {
+            reason: format!("expected a commit certificate, got {:?}", qc.phase),
+        });
+    }
+    if qc.block_hash != *block_hash {
+        return Err(NornError::InvalidQuorumCertificate {
+            reason: "certificate is for a different block".to_string(),
+        });
+    }
+    verify_quorum_certificate(qc, validator_set)
+}
+
+/// Verify a quorum certificate against `validator_set`: its signer bitmap
+/// names a quorum of the set, and their aggregate signature covers the
+/// certificate's phase, view and block.
+pub fn verify_quorum_certificate(
+    qc: &QuorumCertificate,
+    validator_set: &ValidatorSet,
+) -> Result<(), NornError> {
+    if qc.signers.len() != validator_set.len().div_ceil(8) {
+        return Err(NornError::InvalidQuorumCertificate {
+            reason: "signer bitmap does not match the validator set".to_string(),
+        });
+    }
+    let signers = qc.signer_indices();
+    if signers.last().is_some_and(|i| *i >= validator_set.len()) {
+        return Err(NornError::InvalidQuorumCertificate {
+            reason: "signer bitmap names a validator outside the set".to_string(),
+        });
+    }
+    let need = validator_set.quorum_size();
+    if signers.len() < need {
+        return Err(NornError::InsufficientQuorum {
+            have: signers.len(),
+            need,
+        });
+    }
+    let pubkeys: Vec<BlsPublicKey> = signers
+        .iter()
+        .map(|i| validator_set.validators[*i].bls_pubkey)
+        .collect();
+    verify_aggregate(
+        &vote_signing_data(qc.phase, qc.view, &qc.block_hash),
+        &qc.signature,
+        &pubkeys,
+    )
+    .map_err(|_| NornError::InvalidQuorumCertificate {
+        reason: "invalid aggregate signature".to_string(),
+    })
+}
+
+fn decode_public_key(pubkey: &BlsPublicKey) -> Result<G1Affine, NornError> {
+    // `from_compressed` rejects points outside the prime-order subgroup.
+    Option::<G1Affine>::from(G1Affine::from_compressed(pubkey))
+        .filter(|pk| !bool::from(pk.is_identity()))
+        .ok_or(NornError::InvalidKeyMaterial)
+}
+
+fn hash_to_g2(message: &[u8], dst: &[u8]) -> G2Projective {
+    <G2Projective as HashToCurve<ExpandMsgXmd<sha2_09::Sha256>>>::hash_to_curve(message, dst)
+}
+
+/// Check `e(pk, H(m)) == e(g1, sig)`.
+fn verify_point(
+    message: &[u8],
+    dst: &[u8],
+    signature: &BlsSignature,
+    pk: &G1Affine,
+) -> Result<(), NornError> {
+    let sig = Option::<G2Affine>::from(G2Affine::from_compressed(signature))
+        .ok_or(NornError::InvalidSignature { signer_index: 0 })?;
+    let h = G2Affine::from(hash_to_g2(message, dst));
+    let neg_g1 = -G1Affine::generator();
+    let result = multi_miller_loop(&[
+        (pk, &G2Prepared::from(h)),
+        (&neg_g1, &G2Prepared::from(sig)),
+    ])
+    .final_exponentiation();
+    if result == Gt::identity() {
+        Ok(())
+    } else {
+        Err(NornError::InvalidSignature { signer_index: 0 })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_verify() {
+        let kp = BlsKeypair::from_seed(&[1u8; 32]);
+        let sig = kp.sign(b"block");
+        assert!(verify(b"block", &sig, &kp.public_key()).is_ok());
+        assert!(verify(b"other", &sig, &kp.public_key()).is_err());
+        let other = BlsKeypair::from_seed(&[2u8; 32]);
+        assert!(verify(b"block", &sig, &other.public_key()).is_err());
+        let mut bad = sig;
+        bad[10] ^= 1;
+        assert!(verify(b"block", &bad, &kp.public_key()).is_err());
+    }
+
+    #[test]
+    fn test_deterministic_from_seed() {
+        let a = BlsKeypair::from_seed(&[9u8; 32]);
+        let b = BlsKeypair::from_seed(&[9u8; 32]);
+        assert_eq!(a.public_key(), b.public_key());
+        assert_eq!(a.sign(b"m"), b.sign(b"m"));
+        assert_ne!(
+            a.public_key(),
+            BlsKeypair::from_seed(&[8u8; 32]).public_key()
+        );
+    }
+
+    #[test]
+    fn test_aggregate_verify() {
+        let keys: Vec<BlsKeypair> = (1..=4u8).map(|i| BlsKeypair::from_seed(&[i; 32])).collect();
+        let sigs: Vec<BlsSignature> = keys.iter().map(|k| k.sign(b"qc")).collect();
+        let pks: Vec<BlsPublicKey> = keys.iter().map(|k| k.public_key()).collect();
+        let agg = aggregate_signatures(&sigs).unwrap();
+        assert!(verify_aggregate(b"qc", &agg, &pks).is_ok());
+        assert!(verify_aggregate(b"qc", &agg, &pks[..3]).is_err());
+        assert!(verify_aggregate(b"other", &agg, &pks).is_err());
+        let partial = aggregate_signatures(&sigs[..3]).unwrap();
+        assert!(verify_aggregate(b"qc", &partial, &pks[..3]).is_ok());
+        assert!(aggregate_signatures(&[]).is_err());
+        assert!(aggregate_public_keys(&[]).is_err());
+    }
+
+    #[test]
+    fn test_proof_of_possession() {
+        let kp = BlsKeypair::from_seed(&[3u8; 32]);
+        let pop = kp.prove_possession();
+        assert!(verify_possession(&kp.public_key(), &pop).is_ok());
+        // A message signature over the key bytes is not a proof of possession.
+        assert!(verify_possession(&kp.public_key(), &kp.sign(&kp.public_key())).is_err());
+        let other = BlsKeypair::from_seed(&[4u8; 32]);
+        assert!(verify_possession(&other.public_key(), &pop).is_err());
+    }
+
+    #[test]
+    fn test_verify_quorum_certificate() {
+        use norn_types::weave::Validator;
+
+        let keys: Vec<BlsKeypair> = (1..=4u8).map(|i| BlsKeypair::from_seed(&[i; 32])).collect();
+        let vs = ValidatorSet {
+            validators: keys
+                .iter()
+                .enumerate()
+                .map(|(i, k)| Validator {
+                    pubkey: [i as u8; 32],
+                    bls_pubkey: k.public_key(),
+                    address: [0u8; 20],
+                    stake: 1000,
+                    active: true,
+                })
+                .collect(),
+            total_stake: 4000,
+            epoch: 0,
+        };
+        let message = vote_signing_data(ConsensusPhase::Commit, 5, &[7u8; 32]);
+        let qc = |signers: &[usize]| QuorumCertificate {
+            view: 5,
+            block_hash: [7u8; 32],
+            phase: ConsensusPhase::Commit,
+            signers: signer_bitmap(signers.iter().copied(), 4),
+            signature: aggregate_signatures(
+                &signers
+                    .iter()
+                    .map(|i| keys[*i].sign(&message))
+                    .collect::<Vec<_>>(),
+            )
+            .unwrap(),
+        };
+
+        assert!(verify_quorum_certificate(&qc(&[0, 2, 3]), &vs).is_ok());
+        assert!(verify_commit_certificate(&qc(&[0, 2, 3]), &[7u8; 32], &vs).is_ok());
+        assert!(verify_commit_certificate(&qc(&[0, 2, 3]), &[8u8; 32], &vs).is_err());
+        assert_eq!(
+            verify_quorum_certificate(&qc(&[0, 2]), &vs),
+            Err(NornError::InsufficientQuorum { have: 2, need: 3 })
+        );
+        // Claiming a signer that did not sign.
+        let mut forged = qc(&[0, 2]);
+        forged.signers = signer_bitmap([0, 1, 2], 4);
+        assert!(verify_quorum_certificate(&forged, &vs).is_err());
+        let mut moved = qc(&[0, 1, 2]);
+        moved.view = 6;
+        assert!(verify_quorum_certificate(&moved, &vs).is_err());
+        let mut outside = qc(&[0, 1, 2]);
+        outside.signers[0] |= 0x80;
+        assert!(verify_quorum_certificate(&outside, &vs).is_err());
+        let mut long = qc(&[0, 1, 2]);
+        long.signers.push(0);
+        assert!(verify_quorum_certificate(&long, &vs).is_err());
+
+        // Aggregating votes gives the same certificate.
+        let votes: Vec<Vote> = [3usize, 0, 2, 0]
+            .iter()
+            .map(|i| Vote {
+                view: 5,
+                phase: ConsensusPhase::Commit,
+                block_hash: [7u8; 32],
+                voter: [*i as u8; 32],
+                signature: [0u8; 64],
+                bls_signature: keys[*i].sign(&message),
+            })
+            .collect();
+        let aggregated = aggregate_votes(ConsensusPhase::Commit, &votes, &vs).unwrap();
+        assert_eq!(aggregated, qc(&[0, 2, 3]));
+        assert!(aggregate_votes(ConsensusPhase::PreCommit, &votes, &vs).is_err());
+
+        // A prepare certificate relabelled as a commit certificate.
+        let prepare_message = vote_signing_data(ConsensusPhase::Prepare, 5, &[7u8; 32]);
+        let mut relabelled = qc(&[0, 2, 3]);
+        relabelled.signature = aggregate_signatures(
+            &[0usize, 2, 3]
+                .iter()
+                .map(|i| keys[*i].sign(&prepare_message))
+                .collect::<Vec<_>>(),
+        )
+        .unwrap();
+        relabelled.phase = ConsensusPhase::Prepare;
+        assert!(verify_quorum_certificate(&relabelled, &vs).is_ok());
+        relabelled.phase = ConsensusPhase::Commit;
+        assert!(verify_commit_certificate(&relabelled, &[7u8; 32], &vs).is_err());
+    }
+
+    #[test]
+    fn test_rejects_invalid_encodings() {
+        let kp = BlsKeypair::from_seed(&[5u8; 32]);
+        let sig = kp.sign(b"m");
+        assert_eq!(
+            verify(b"m", &sig, &[0u8; 48]),
+            Err(NornError::InvalidKeyMaterial)
+        );
+        // The compressed identity is a valid encoding but not a usable key.
+        let mut identity = [0u8; 48];
+        identity[0] = 0xc0;
+        assert!(verify(b"m", &sig, &identity).is_err());
+        assert_eq!(
+            aggregate_signatures(&[sig, [0u8; 96]]),
+            Err(NornError::InvalidSignature { signer_index: 1 })
+        );
+    }
+}
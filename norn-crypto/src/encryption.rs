@@ -126,6 +126,39 @@ pub fn decrypt(
         })
 }
 
+/// Encrypt `plaintext` under a 32-byte symmetric key with a random nonce,
+/// returning the nonce and the ciphertext with its authentication tag.
+///
+/// Looms decrypt these with the `norn_decrypt` host function once the key
+/// is revealed.
+pub fn seal(key: &[u8; 32], plaintext: &[u8]) -> Result<([u8; 24], Vec<u8>), NornError> {
+    let mut nonce = [0u8; 24];
+    rand::rngs::OsRng.fill_bytes(&mut nonce);
+    let cipher =
+        XChaCha20Poly1305::new_from_slice(key).map_err(|e| NornError::EncryptionFailed {
+            reason: e.to_string(),
+        })?;
+    let ciphertext = cipher
+        .encrypt(XNonce::from_slice(&nonce), plaintext)
+        .map_err(|e| NornError::EncryptionFailed {
+            reason: e.to_string(),
+        })?;
+    Ok((nonce, ciphertext))
+}
+
+/// Decrypt a ciphertext produced by [`seal`].
+pub fn open(key: &[u8; 32], nonce: &[u8; 24], ciphertext: &[u8]) -> Result<Vec<u8>, NornError> {
+    let cipher =
+        XChaCha20Poly1305::new_from_slice(key).map_err(|e| NornError::DecryptionFailed {
+            reason: e.to_string(),
+        })?;
+    cipher
+        .decrypt(XNonce::from_slice(nonce), ciphertext)
+        .map_err(|e| NornError::DecryptionFailed {
+            reason: e.to_string(),
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -216,4 +249,14 @@ mod tests {
 
         assert_eq!(decrypted, plaintext);
     }
+
+    #[test]
+    fn test_seal_open_roundtrip() {
+        let key = [7u8; 32];
+        let (nonce, ciphertext) = seal(&key, b"bid: 500").unwrap();
+        assert_eq!(ciphertext.len(), 8 + 16);
+        assert_eq!(open(&key, &nonce, &ciphertext).unwrap(), b"bid: 500");
+        assert!(open(&[8u8; 32], &nonce, &ciphertext).is_err());
+        assert!(open(&key, &[0u8; 24], &ciphertext).is_err());
+    }
 }
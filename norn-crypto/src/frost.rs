@@ -0,0 +1,1039 @@
+//! FROST threshold Ed25519 signatures (FROST(Ed25519, SHA-512), RFC 9591).
+//!
+//! A committee of `max_signers` participants shares one Ed25519 key, and any
+//! `min_signers` of them can sign with it. The result is an ordinary 64-byte
+//! Ed25519 signature under the group public key, so it is accepted anywhere
+//! [`crate::keys::verify`] is, such as a watchtower federation's spindle key
+//! or a multi-operator loom's operator key. No participant ever holds the
+//! group secret.
+//!
+//! Keys are shared either by the distributed key generation in
+//! [`dkg_round1`], [`dkg_round2`] and [`dkg_finalize`], or by splitting an
+//! existing [`Keypair`] with [`split_keypair`]. Signing takes two rounds:
+//! each signer publishes [`SigningCommitments`] from [`commit`], then signs
+//! the message and the full commitment list with [`sign`], and a coordinator
+//! combines the shares with [`aggregate`].
+
+use std::collections::BTreeMap;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
+use curve25519_dalek::scalar::{clamp_integer, Scalar};
+use curve25519_dalek::traits::{Identity, VartimeMultiscalarMul};
+use norn_types::error::NornError;
+use norn_types::primitives::{PublicKey, Signature};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha512};
+use zeroize::Zeroize;
+
+use crate::keys::{verify, Keypair};
+
+/// RFC 9591 context string of FROST(Ed25519, SHA-512).
+const CONTEXT: &[u8] = b"FROST-ED25519-SHA512-v1";
+
+/// A participant's identifier: its 1-based position in the committee.
+pub type Identifier = u16;
+
+/// A participant's share of the group key.
+pub struct KeyPackage {
+    identifier: Identifier,
+    signing_share: Scalar,
+    verifying_share: PublicKey,
+    group_public_key: PublicKey,
+    min_signers: u16,
+}
+
+impl KeyPackage {
+    /// This participant's identifier.
+    pub fn identifier(&self) -> Identifier {
+        self.identifier
+    }
+
+    /// The public key of this participant's share.
+    pub fn verifying_share(&self) -> PublicKey {
+        self.verifying_share
+    }
+
+    /// The group's Ed25519 public key.
+    pub fn group_public_key(&self) -> PublicKey {
+        self.group_public_key
+    }
+
+    /// The number of signers needed to sign.
+    pub fn min_signers(&self) -> u16 {
+        self.min_signers
+    }
+}
+
+impl Drop for KeyPackage {
+    fn drop(&mut self) {
+        self.signing_share.zeroize();
+    }
+}
+
+/// The public side of a shared key: what a coordinator needs to check
+/// signature shares and aggregate them.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct PublicKeyPackage {
+    /// The group's Ed25519 public key.
+    pub group_public_key: PublicKey,
+    /// Each participant's verifying share, by identifier.
+    pub verifying_shares: BTreeMap<Identifier, PublicKey>,
+    /// The number of signers needed to sign.
+    pub min_signers: u16,
+}
+
+// ── Distributed key generation ───────────────────────────────────────────
+
+/// A participant's secret state between DKG rounds 1 and 2.
+pub struct DkgRound1Secret {
+    identifier: Identifier,
+    coefficients: Vec<Scalar>,
+    commitments: Vec<PublicKey>,
+    min_signers: u16,
+    max_signers: u16,
+}
+
+impl Drop for DkgRound1Secret {
+    fn drop(&mut self) {
+        self.coefficients.zeroize();
+    }
+}
+
+/// A participant's DKG round 1 broadcast: commitments to its polynomial and
+/// a proof of knowledge of its constant term.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct DkgRound1Package {
+    /// The sender's identifier.
+    pub identifier: Identifier,
+    /// Commitments to the sender's polynomial coefficients.
+    pub commitments: Vec<PublicKey>,
+    /// Proof of knowledge commitment `R`.
+    pub proof_r: [u8; 32],
+    /// Proof of knowledge response `mu`.
+    pub proof_mu: [u8; 32],
+}
+
+/// A participant's secret state between DKG round 2 and finalization.
+pub struct DkgRound2Secret {
+    identifier: Identifier,
+    own_share: Scalar,
+    commitments: BTreeMap<Identifier, Vec<EdwardsPoint>>,
+    min_signers: u16,
+}
+
+impl Drop for DkgRound2Secret {
+    fn drop(&mut self) {
+        self.own_share.zeroize();
+    }
+}
+
+/// A secret share of one participant's polynomial for another. It must
+/// reach its recipient privately, e.g. encrypted with
+/// [`crate::encryption`].
+#[derive(Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct DkgRound2Package {
+    /// The sender's identifier.
+    pub sender: Identifier,
+    /// The recipient's identifier.
+    pub recipient: Identifier,
+    /// The sender's polynomial evaluated at the recipient's identifier.
+    pub share: [u8; 32],
+}
+
+impl Drop for DkgRound2Package {
+    fn drop(&mut self) {
+        self.share.zeroize();
+    }
+}
+
+/// Start the DKG as participant `identifier` of `max_signers`, for a key
+/// that any `min_signers` can sign with. Broadcast the package and keep the
+/// secret for [`dkg_round2`].
+pub fn dkg_round1(
+    identifier: Identifier,
+    min_signers: u16,
+    max_signers: u16,
+) -> Result<(DkgRound1Secret, DkgRound1Package), NornError> {
+    check_parameters(min_signers, max_signers)?;
+    if identifier == 0 || identifier > max_signers {
+        return Err(frost_error(format!(
+            "identifier {} is outside 1..={}",
+            identifier, max_signers
+        )));
+    }
+
+    let coefficients: Vec<Scalar> = (0..min_signers).map(|_| random_scalar()).collect();
+    let commitments: Vec<PublicKey> = coefficients
+        .iter()
+        .map(|a| EdwardsPoint::mul_base(a).compress().to_bytes())
+        .collect();
+
+    let k = random_scalar();
+    let r = EdwardsPoint::mul_base(&k).compress().to_bytes();
+    let c = dkg_challenge(identifier, &commitments[0], &r);
+    let mu = k + coefficients[0] * c;
+
+    let package = DkgRound1Package {
+        identifier,
+        commitments: commitments.clone(),
+        proof_r: r,
+        proof_mu: mu.to_bytes(),
+    };
+    let secret = DkgRound1Secret {
+        identifier,
+        coefficients,
+        commitments,
+        min_signers,
+        max_signers,
+    };
+    Ok((secret, package))
+}
+
+/// Check the other participants' round 1 packages and compute the share of
+/// this participant's polynomial for each of them. Send each package to its
+/// recipient privately and keep the secret for [`dkg_finalize`].
+pub fn dkg_round2(
+    secret: DkgRound1Secret,
+    round1_packages: &[DkgRound1Package],
+) -> Result<(DkgRound2Secret, Vec<DkgRound2Package>), NornError> {
+    if round1_packages.len() != usize::from(secret.max_signers) - 1 {
+        return Err(frost_error(format!(
+            "expected {} round 1 packages, got {}",
+            secret.max_signers - 1,
+            round1_packages.len()
+        )));
+    }
+
+    let mut commitments = BTreeMap::new();
+    commitments.insert(
+        secret.identifier,
+        secret
+            .commitments
+            .iter()
+            .map(decode_point)
+            .collect::<Result<Vec<_>, _>>()?,
+    );
+    for package in round1_packages {
+        if package.identifier == 0 || package.identifier > secret.max_signers {
+            return Err(frost_error(format!(
+                "identifier {} is outside 1..={}",
+                package.identifier, secret.max_signers
+            )));
+        }
+        if package.commitments.len() != usize::from(secret.min_signers) {
+            return Err(frost_error(format!(
+                "participant {} committed to {} coefficients, expected {}",
+                package.identifier,
+                package.commitments.len(),
+                secret.min_signers
+            )));
+        }
+        let points = package
+            .commitments
+            .iter()
+            .map(decode_point)
+            .collect::<Result<Vec<_>, _>>()?;
+        verify_proof_of_knowledge(package, &points[0])?;
+        if commitments.insert(package.identifier, points).is_some() {
+            return Err(frost_error(format!(
+                "duplicate round 1 package from participant {}",
+                package.identifier
+            )));
+        }
+    }
+
+    let packages = commitments
+        .keys()
+        .filter(|id| **id != secret.identifier)
+        .map(|id| DkgRound2Package {
+            sender: secret.identifier,
+            recipient: *id,
+            share: evaluate(&secret.coefficients, *id).to_bytes(),
+        })
+        .collect();
+    let round2 = DkgRound2Secret {
+        identifier: secret.identifier,
+        own_share: evaluate(&secret.coefficients, secret.identifier),
+        commitments,
+        min_signers: secret.min_signers,
+    };
+    Ok((round2, packages))
+}
+
+/// Check the shares sent to this participant against the senders'
+/// commitments and derive this participant's key package and the group's
+/// public key package.
+pub fn dkg_finalize(
+    secret: DkgRound2Secret,
+    round2_packages: &[DkgRound2Package],
+) -> Result<(KeyPackage, PublicKeyPackage), NornError> {
+    if round2_packages.len() != secret.commitments.len() - 1 {
+        return Err(frost_error(format!(
+            "expected {} round 2 packages, got {}",
+            secret.commitments.len() - 1,
+            round2_packages.len()
+        )));
+    }
+
+    let mut signing_share = secret.own_share;
+    let mut senders = Vec::with_capacity(round2_packages.len());
+    for package in round2_packages {
+        if package.recipient != secret.identifier {
+            return Err(frost_error(format!(
+                "share from participant {} is addressed to {}",
+                package.sender, package.recipient
+            )));
+        }
+        if package.sender == secret.identifier || senders.contains(&package.sender) {
+            return Err(frost_error(format!(
+                "unexpected share from participant {}",
+                package.sender
+            )));
+        }
+        let commitments = secret
+            .commitments
+            .get(&package.sender)
+            .ok_or_else(|| frost_error(format!("unknown participant {}", package.sender)))?;
+        let share = decode_scalar(&package.share)?;
+        if EdwardsPoint::mul_base(&share) != evaluate_commitments(commitments, secret.identifier) {
+            return Err(frost_error(format!(
+                "share from participant {} does not match its commitments",
+                package.sender
+            )));
+        }
+        senders.push(package.sender);
+        signing_share += share;
+    }
+
+    let group_public_key = secret
+        .commitments
+        .values()
+        .map(|c| c[0])
+        .fold(EdwardsPoint::identity(), |acc, c| acc + c);
+    let verifying_shares: BTreeMap<Identifier, PublicKey> = secret
+        .commitments
+        .keys()
+        .map(|id| {
+            let share = secret
+                .commitments
+                .values()
+                .map(|c| evaluate_commitments(c, *id))
+                .fold(EdwardsPoint::identity(), |acc, p| acc + p);
+            (*id, share.compress().to_bytes())
+        })
+        .collect();
+
+    let key_package = KeyPackage {
+        identifier: secret.identifier,
+        verifying_share: EdwardsPoint::mul_base(&signing_share).compress().to_bytes(),
+        signing_share,
+        group_public_key: group_public_key.compress().to_bytes(),
+        min_signers: secret.min_signers,
+    };
+    let public_key_package = PublicKeyPackage {
+        group_public_key: key_package.group_public_key,
+        verifying_shares,
+        min_signers: secret.min_signers,
+    };
+    Ok((key_package, public_key_package))
+}
+
+/// Split an existing keypair among `max_signers` participants, any
+/// `min_signers` of whom can sign with it. The group public key is the
+/// keypair's public key, so an existing identity can be handed to a
+/// committee. Whoever runs this sees the whole key; prefer the DKG for new
+/// keys.
+pub fn split_keypair(
+    keypair: &Keypair,
+    min_signers: u16,
+    max_signers: u16,
+) -> Result<(Vec<KeyPackage>, PublicKeyPackage), NornError> {
+    check_parameters(min_signers, max_signers)?;
+
+    let mut expanded: [u8; 64] = Sha512::digest(keypair.seed()).into();
+    let mut scalar_bytes = [0u8; 32];
+    scalar_bytes.copy_from_slice(&expanded[..32]);
+    let mut coefficients = vec![Scalar::from_bytes_mod_order(clamp_integer(scalar_bytes))];
+    expanded.zeroize();
+    scalar_bytes.zeroize();
+    coefficients.extend((1..min_signers).map(|_| random_scalar()));
+
+    let group_public_key = keypair.public_key();
+    let key_packages: Vec<KeyPackage> = (1..=max_signers)
+        .map(|identifier| {
+            let signing_share = evaluate(&coefficients, identifier);
+            KeyPackage {
+                identifier,
+                verifying_share: EdwardsPoint::mul_base(&signing_share).compress().to_bytes(),
+                signing_share,
+                group_public_key,
+                min_signers,
+            }
+        })
+        .collect();
+    coefficients.zeroize();
+
+    let public_key_package = PublicKeyPackage {
+        group_public_key,
+        verifying_shares: key_packages
+            .iter()
+            .map(|k| (k.identifier, k.verifying_share))
+            .collect(),
+        min_signers,
+    };
+    Ok((key_packages, public_key_package))
+}
+
+// ── Signing ──────────────────────────────────────────────────────────────
+
+/// A signer's secret nonces for one signing session. Using them for two
+/// messages reveals the signing share, so [`sign`] consumes them.
+pub struct SigningNonces {
+    hiding: Scalar,
+    binding: Scalar,
+    commitments: SigningCommitments,
+}
+
+impl Drop for SigningNonces {
+    fn drop(&mut self) {
+        self.hiding.zeroize();
+        self.binding.zeroize();
+    }
+}
+
+/// A signer's public commitments to its nonces, sent to the coordinator.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct SigningCommitments {
+    /// The signer's identifier.
+    pub identifier: Identifier,
+    /// Commitment to the hiding nonce.
+    pub hiding: [u8; 32],
+    /// Commitment to the binding nonce.
+    pub binding: [u8; 32],
+}
+
+/// A signer's share of a signature.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct SignatureShare {
+    /// The signer's identifier.
+    pub identifier: Identifier,
+    /// The share `z_i`.
+    pub share: [u8; 32],
+}
+
+/// Round one of signing: draw fresh nonces. Send the commitments to the
+/// coordinator and keep the nonces for [`sign`].
+pub fn commit(key_package: &KeyPackage) -> (SigningNonces, SigningCommitments) {
+    let hiding = generate_nonce(&key_package.signing_share);
+    let binding = generate_nonce(&key_package.signing_share);
+    let commitments = SigningCommitments {
+        identifier: key_package.identifier,
+        hiding: EdwardsPoint::mul_base(&hiding).compress().to_bytes(),
+        binding: EdwardsPoint::mul_base(&binding).compress().to_bytes(),
+    };
+    (
+        SigningNonces {
+            hiding,
+            binding,
+            commitments,
+        },
+        commitments,
+    )
+}
+
+/// Round two of signing: sign `message` given the commitments of every
+/// signer taking part, this one included.
+pub fn sign(
+    key_package: &KeyPackage,
+    nonces: SigningNonces,
+    message: &[u8],
+    commitments: &[SigningCommitments],
+) -> Result<SignatureShare, NornError> {
+    let commitments = sorted_commitments(commitments, key_package.min_signers)?;
+    let own = commitments
+        .iter()
+        .find(|c| c.identifier == key_package.identifier)
+        .ok_or_else(|| frost_error("the commitment list does not include this signer"))?;
+    if *own != nonces.commitments {
+        return Err(frost_error(
+            "the commitment list does not match this signer's nonces",
+        ));
+    }
+
+    let session = Session::new(&key_package.group_public_key, message, &commitments)?;
+    let rho = session.binding_factor(key_package.identifier);
+    let lambda = session.lagrange(key_package.identifier);
+    let z = nonces.hiding
+        + nonces.binding * rho
+        + lambda * key_package.signing_share * session.challenge;
+    Ok(SignatureShare {
+        identifier: key_package.identifier,
+        share: z.to_bytes(),
+    })
+}
+
+/// Check each signature share and combine them into an Ed25519 signature
+/// over `message` by the group key.
+///
+/// Returns `InvalidSignature` with the position in `shares` of the first
+/// share that does not verify, so a misbehaving signer can be identified.
+pub fn aggregate(
+    message: &[u8],
+    commitments: &[SigningCommitments],
+    shares: &[SignatureShare],
+    public_key_package: &PublicKeyPackage,
+) -> Result<Signature, NornError> {
+    let commitments = sorted_commitments(commitments, public_key_package.min_signers)?;
+    if shares.len() != commitments.len() {
+        return Err(frost_error(format!(
+            "{} signers committed but {} shares were given",
+            commitments.len(),
+            shares.len()
+        )));
+    }
+    let session = Session::new(&public_key_package.group_public_key, message, &commitments)?;
+
+    let mut z = Scalar::ZERO;
+    for (index, share) in shares.iter().enumerate() {
+        let invalid = NornError::InvalidSignature {
+            signer_index: index,
+        };
+        let Some(signer) = commitments
+            .iter()
+            .find(|c| c.identifier == share.identifier)
+        else {
+            return Err(invalid);
+        };
+        let verifying_share = public_key_package
+            .verifying_shares
+            .get(&share.identifier)
+            .ok_or(NornError::InvalidSignature {
+                signer_index: index,
+            })
+            .and_then(decode_point)?;
+        let z_i = decode_scalar(&share.share).map_err(|_| NornError::InvalidSignature {
+            signer_index: index,
+        })?;
+
+        // z_i*B == D_i + rho_i*E_i + c*lambda_i*Y_i
+        let rho = session.binding_factor(share.identifier);
+        let lambda = session.lagrange(share.identifier);
+        let expected = decode_point(&signer.hiding)?
+            + decode_point(&signer.binding)? * rho
+            + verifying_share * (session.challenge * lambda);
+        if EdwardsPoint::mul_base(&z_i) != expected {
+            return Err(invalid);
+        }
+        z += z_i;
+    }
+
+    let mut signature = [0u8; 64];
+    signature[..32].copy_from_slice(session.group_commitment.compress().as_bytes());
+    signature[32..].copy_from_slice(z.as_bytes());
+    verify(message, &signature, &public_key_package.group_public_key)?;
+    Ok(signature)
+}
+
+/// The per-message values every signer and the coordinator derive alike.
+struct Session {
+    identifiers: Vec<Identifier>,
+    binding_factors: Vec<Scalar>,
+    group_commitment: EdwardsPoint,
+    challenge: Scalar,
+}
+
+impl Session {
+    fn new(
+        group_public_key: &PublicKey,
+        message: &[u8],
+        commitments: &[SigningCommitments],
+    ) -> Result<Self, NornError> {
+        let mut encoded = Vec::with_capacity(commitments.len() * 96);
+        for c in commitments {
+            encoded.extend_from_slice(identifier_scalar(c.identifier).as_bytes());
+            encoded.extend_from_slice(&c.hiding);
+            encoded.extend_from_slice(&c.binding);
+        }
+        let mut prefix = Vec::with_capacity(160);
+        prefix.extend_from_slice(group_public_key);
+        prefix.extend_from_slice(&h(b"msg", &[message]));
+        prefix.extend_from_slice(&h(b"com", &[&encoded]));
+        let binding_factors: Vec<Scalar> = commitments
+            .iter()
+            .map(|c| {
+                Scalar::from_bytes_mod_order_wide(&h(
+                    b"rho",
+                    &[&prefix, identifier_scalar(c.identifier).as_bytes()],
+                ))
+            })
+            .collect();
+
+        let hiding: Vec<EdwardsPoint> = commitments
+            .iter()
+            .map(|c| decode_point(&c.hiding))
+            .collect::<Result<_, _>>()?;
+        let binding: Vec<EdwardsPoint> = commitments
+            .iter()
+            .map(|c| decode_point(&c.binding))
+            .collect::<Result<_, _>>()?;
+        let group_commitment = hiding.iter().fold(EdwardsPoint::identity(), |a, p| a + p)
+            + EdwardsPoint::vartime_multiscalar_mul(&binding_factors, &binding);
+
+        // The Ed25519 challenge, so the aggregate verifies as a plain signature.
+        let challenge = Scalar::from_bytes_mod_order_wide(
+            &Sha512::new()
+                .chain_update(group_commitment.compress().as_bytes())
+                .chain_update(group_public_key)
+                .chain_update(message)
+                .finalize()
+                .into(),
+        );
+
+        Ok(Self {
+            identifiers: commitments.iter().map(|c| c.identifier).collect(),
+            binding_factors,
+            group_commitment,
+            challenge,
+        })
+    }
+
+    fn binding_factor(&self, identifier: Identifier) -> Scalar {
+        self.identifiers
+            .iter()
+            .position(|id| *id == identifier)
+            .map(|i| self.binding_factors[i])
+            .unwrap_or(Scalar::ZERO)
+    }
+
+    /// The Lagrange coefficient of `identifier` over the signing set.
+    fn lagrange(&self, identifier: Identifier) -> Scalar {
+        let x = identifier_scalar(identifier);
+        let (num, den) = self
+            .identifiers
+            .iter()
+            .filter(|id| **id != identifier)
+            .map(|id| identifier_scalar(*id))
+            .fold((Scalar::ONE, Scalar::ONE), |(num, den), x_j| {
+                (num * x_j, den * (x_j - x))
+            });
+        num * den.invert()
+    }
+}
+
+/// Sort a commitment list by identifier, rejecting duplicates, unknown
+/// identifiers, and lists too short to sign.
+fn sorted_commitments(
+    commitments: &[SigningCommitments],
+    min_signers: u16,
+) -> Result<Vec<SigningCommitments>, NornError> {
+    let mut sorted = commitments.to_vec();
+    sorted.sort_by_key(|c| c.identifier);
+    if sorted.len() < usize::from(min_signers) {
+        return Err(frost_error(format!(
+            "{} signers committed, need {}",
+            sorted.len(),
+            min_signers
+        )));
+    }
+    if sorted.first().is_some_and(|c| c.identifier == 0)
+        || sorted
+            .windows(2)
+            .any(|w| w[0].identifier == w[1].identifier)
+    {
+        return Err(frost_error("invalid or duplicate signer identifiers"));
+    }
+    Ok(sorted)
+}
+
+fn check_parameters(min_signers: u16, max_signers: u16) -> Result<(), NornError> {
+    if min_signers < 2 {
+        return Err(frost_error("min_signers must be at least 2"));
+    }
+    if max_signers < min_signers {
+        return Err(frost_error("max_signers must be >= min_signers"));
+    }
+    Ok(())
+}
+
+fn verify_proof_of_knowledge(
+    package: &DkgRound1Package,
+    constant: &EdwardsPoint,
+) -> Result<(), NornError> {
+    let invalid = || {
+        frost_error(format!(
+            "invalid proof of knowledge from participant {}",
+            package.identifier
+        ))
+    };
+    let r = decode_point(&package.proof_r).map_err(|_| invalid())?;
+    let mu = decode_scalar(&package.proof_mu).map_err(|_| invalid())?;
+    let c = dkg_challenge(
+        package.identifier,
+        &package.commitments[0],
+        &package.proof_r,
+    );
+    // mu*B == R + c*C_0
+    if EdwardsPoint::mul_base(&mu) != r + constant * c {
+        return Err(invalid());
+    }
+    Ok(())
+}
+
+fn dkg_challenge(identifier: Identifier, constant: &PublicKey, r: &[u8; 32]) -> Scalar {
+    Scalar::from_bytes_mod_order_wide(&h(
+        b"dkg",
+        &[identifier_scalar(identifier).as_bytes(), constant, r],
+    ))
+}
+
+/// Evaluate the polynomial with `coefficients` at `identifier`.
+fn evaluate(coefficients: &[Scalar], identifier: Identifier) -> Scalar {
+    let x = identifier_scalar(identifier);
+    coefficients
+        .iter()
+        .rev()
+        .fold(Scalar::ZERO, |acc, a| acc * x + a)
+}
+
+/// Evaluate committed coefficients at `identifier`: the public key of the
+/// share for `identifier`.
+fn evaluate_commitments(commitments: &[EdwardsPoint], identifier: Identifier) -> EdwardsPoint {
+    let x = identifier_scalar(identifier);
+    commitments
+        .iter()
+        .rev()
+        .fold(EdwardsPoint::identity(), |acc, c| acc * x + c)
+}
+
+/// RFC 9591 `nonce_generate`: fresh randomness hashed with the secret, so a
+/// weak RNG alone does not leak the share.
+fn generate_nonce(secret: &Scalar) -> Scalar {
+    let mut random = [0u8; 32];
+    rand::rngs::OsRng.fill_bytes(&mut random);
+    let nonce = derive_nonce(&random, secret);
+    random.zeroize();
+    nonce
+}
+
+fn derive_nonce(random: &[u8; 32], secret: &Scalar) -> Scalar {
+    Scalar::from_bytes_mod_order_wide(&h(b"nonce", &[random, secret.as_bytes()]))
+}
+
+fn random_scalar() -> Scalar {
+    let mut wide = [0u8; 64];
+    rand::rngs::OsRng.fill_bytes(&mut wide);
+    let scalar = Scalar::from_bytes_mod_order_wide(&wide);
+    wide.zeroize();
+    scalar
+}
+
+fn identifier_scalar(identifier: Identifier) -> Scalar {
+    Scalar::from(u64::from(identifier))
+}
+
+/// SHA-512 of the context string, a domain tag, and `parts`.
+fn h(tag: &[u8], parts: &[&[u8]]) -> [u8; 64] {
+    let mut hasher = Sha512::new();
+    hasher.update(CONTEXT);
+    hasher.update(tag);
+    for part in parts {
+        hasher.update(part);
+    }
+    hasher.finalize().into()
+}
+
+/// Decode a canonical, non-identity point of the prime-order subgroup.
+fn decode_point(bytes: &[u8; 32]) -> Result<EdwardsPoint, NornError> {
+    CompressedEdwardsY(*bytes)
+        .decompress()
+        .filter(|p| p.compress().as_bytes() == bytes)
+        .filter(|p| p.is_torsion_free() && *p != EdwardsPoint::identity())
+        .ok_or(NornError::InvalidKeyMaterial)
+}
+
+fn decode_scalar(bytes: &[u8; 32]) -> Result<Scalar, NornError> {
+    Option::from(Scalar::from_canonical_bytes(*bytes)).ok_or(NornError::InvalidKeyMaterial)
+}
+
+fn frost_error(reason: impl Into<String>) -> NornError {
+    NornError::ThresholdSigningError {
+        reason: reason.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Run the DKG among `max_signers` participants.
+    fn run_dkg(min_signers: u16, max_signers: u16) -> (Vec<KeyPackage>, Vec<PublicKeyPackage>) {
+        let round1: Vec<_> = (1..=max_signers)
+            .map(|id| dkg_round1(id, min_signers, max_signers).unwrap())
+            .collect();
+        let packages: Vec<DkgRound1Package> = round1.iter().map(|(_, p)| p.clone()).collect();
+
+        let mut secrets = Vec::new();
+        let mut shares = Vec::new();
+        for (secret, own) in round1 {
+            let others: Vec<_> = packages.iter().filter(|p| *p != &own).cloned().collect();
+            let (secret, sent) = dkg_round2(secret, &others).unwrap();
+            secrets.push(secret);
+            shares.extend(sent);
+        }
+
+        secrets
+            .into_iter()
+            .map(|secret| {
+                let received: Vec<_> = shares
+                    .iter()
+                    .filter(|s| s.recipient == secret.identifier)
+                    .cloned()
+                    .collect();
+                dkg_finalize(secret, &received).unwrap()
+            })
+            .unzip()
+    }
+
+    fn threshold_sign(
+        signers: &[&KeyPackage],
+        public: &PublicKeyPackage,
+        message: &[u8],
+    ) -> Result<Signature, NornError> {
+        let (nonces, commitments): (Vec<_>, Vec<_>) = signers.iter().map(|k| commit(k)).unzip();
+        let shares: Vec<SignatureShare> = signers
+            .iter()
+            .zip(nonces)
+            .map(|(k, n)| sign(k, n, message, &commitments).unwrap())
+            .collect();
+        aggregate(message, &commitments, &shares, public)
+    }
+
+    fn hex32(s: &str) -> [u8; 32] {
+        hex::decode(s).unwrap().try_into().unwrap()
+    }
+
+    fn scalar(s: &str) -> Scalar {
+        decode_scalar(&hex32(s)).unwrap()
+    }
+
+    fn point(s: &str) -> EdwardsPoint {
+        decode_point(&hex32(s)).unwrap()
+    }
+
+    /// RFC 9591 Appendix E.1: FROST(Ed25519, SHA-512), 2-of-3, signers 1 and 3.
+    #[test]
+    fn test_rfc9591_vectors() {
+        let group_secret =
+            scalar("7b1c33d3f5291d85de664833beb1ad469f7fb6025a0ec78b3a790c6e13a98304");
+        let coefficient =
+            scalar("178199860edd8c62f5212ee91eff1295d0d670ab4ed4506866bae57e7030b204");
+        let group_public_key =
+            hex32("15d21ccd7ee42959562fc8aa63224c8851fb3ec85a3faf66040d380fb9738673");
+        let message = hex::decode("74657374").unwrap();
+        assert_eq!(
+            EdwardsPoint::mul_base(&group_secret).compress().to_bytes(),
+            group_public_key
+        );
+
+        let shares = [
+            "929dcc590407aae7d388761cddb0c0db6f5627aea8e217f4a033f2ec83d93509",
+            "a91e66e012e4364ac9aaa405fcafd370402d9859f7b6685c07eed76bf409e80d",
+            "d3cb090a075eb154e82fdb4b3cb507f110040905468bb9c46da8bdea643a9a02",
+        ];
+        let key_packages: Vec<KeyPackage> = (1..=3u16)
+            .map(|identifier| {
+                let signing_share = evaluate(&[group_secret, coefficient], identifier);
+                assert_eq!(signing_share, scalar(shares[usize::from(identifier) - 1]));
+                KeyPackage {
+                    identifier,
+                    verifying_share: EdwardsPoint::mul_base(&signing_share).compress().to_bytes(),
+                    signing_share,
+                    group_public_key,
+                    min_signers: 2,
+                }
+            })
+            .collect();
+        let public_key_package = PublicKeyPackage {
+            group_public_key,
+            verifying_shares: key_packages
+                .iter()
+                .map(|k| (k.identifier, k.verifying_share))
+                .collect(),
+            min_signers: 2,
+        };
+
+        // (signer, hiding randomness, binding randomness, hiding nonce, binding nonce,
+        //  hiding commitment, binding commitment)
+        let round_one = [
+            (
+                &key_packages[0],
+                "0fd2e39e111cdc266f6c0f4d0fd45c947761f1f5d3cb583dfcb9bbaf8d4c9fec",
+                "69cd85f631d5f7f2721ed5e40519b1366f340a87c2f6856363dbdcda348a7501",
+                "812d6104142944d5a55924de6d49940956206909f2acaeedecda2b726e630407",
+                "b1110165fc2334149750b28dd813a39244f315cff14d4e89e6142f262ed83301",
+                "b5aa8ab305882a6fc69cbee9327e5a45e54c08af61ae77cb8207be3d2ce13de3",
+                "67e98ab55aa310c3120418e5050c9cf76cf387cb20ac9e4b6fdb6f82a469f932",
+            ),
+            (
+                &key_packages[2],
+                "86d64a260059e495d0fb4fcc17ea3da7452391baa494d4b00321098ed2a0062f",
+                "13e6b25afb2eba51716a9a7d44130c0dbae0004a9ef8d7b5550c8a0e07c61775",
+                "c256de65476204095ebdc01bd11dc10e57b36bc96284595b8215222374f99c0e",
+                "243d71944d929063bc51205714ae3c2218bd3451d0214dfb5aeec2a90c35180d",
+                "cfbdb165bd8aad6eb79deb8d287bcc0ab6658ae57fdcc98ed12c0669e90aec91",
+                "7487bc41a6e712eea2f2af24681b58b1cf1da278ea11fe4e8b78398965f13552",
+            ),
+        ];
+        let mut nonces = Vec::new();
+        let mut commitments = Vec::new();
+        for (key, hiding_random, binding_random, hiding, binding, hiding_c, binding_c) in round_one
+        {
+            let hiding_nonce = derive_nonce(&hex32(hiding_random), &key.signing_share);
+            let binding_nonce = derive_nonce(&hex32(binding_random), &key.signing_share);
+            assert_eq!(hiding_nonce, scalar(hiding));
+            assert_eq!(binding_nonce, scalar(binding));
+            let c = SigningCommitments {
+                identifier: key.identifier,
+                hiding: EdwardsPoint::mul_base(&hiding_nonce).compress().to_bytes(),
+                binding: EdwardsPoint::mul_base(&binding_nonce).compress().to_bytes(),
+            };
+            assert_eq!(c.hiding, hex32(hiding_c));
+            assert_eq!(c.binding, hex32(binding_c));
+            nonces.push(SigningNonces {
+                hiding: hiding_nonce,
+                binding: binding_nonce,
+                commitments: c,
+            });
+            commitments.push(c);
+        }
+
+        let session = Session::new(&group_public_key, &message, &commitments).unwrap();
+        assert_eq!(
+            session.binding_factor(1),
+            scalar("f2cb9d7dd9beff688da6fcc83fa89046b3479417f47f55600b106760eb3b5603")
+        );
+        assert_eq!(
+            session.binding_factor(3),
+            scalar("b087686bf35a13f3dc78e780a34b0fe8a77fef1b9938c563f5573d71d8d7890f")
+        );
+
+        let sig_shares: Vec<SignatureShare> = [&key_packages[0], &key_packages[2]]
+            .into_iter()
+            .zip(nonces)
+            .map(|(key, n)| sign(key, n, &message, &commitments).unwrap())
+            .collect();
+        assert_eq!(
+            sig_shares[0].share,
+            hex32("001719ab5a53ee1a12095cd088fd149702c0720ce5fd2f29dbecf24b7281b603")
+        );
+        assert_eq!(
+            sig_shares[1].share,
+            hex32("bd86125de990acc5e1f13781d8e32c03a9bbd4c53539bbc106058bfd14326007")
+        );
+
+        let signature =
+            aggregate(&message, &commitments, &sig_shares, &public_key_package).unwrap();
+        let expected = hex::decode(
+            "36282629c383bb820a88b71cae937d41f2f2adfcc3d02e55507e2fb9e2dd3cbe\
+             bd9d2b0844e49ae0f3fa935161e1419aab7b47d21a37ebeae1f17d4987b3160b",
+        )
+        .unwrap();
+        // The group commitment is the signature's R.
+        assert_eq!(
+            session.group_commitment,
+            point("36282629c383bb820a88b71cae937d41f2f2adfcc3d02e55507e2fb9e2dd3cbe")
+        );
+        assert_eq!(signature.to_vec(), expected);
+    }
+
+    #[test]
+    fn test_dkg_and_threshold_sign() {
+        let (keys, publics) = run_dkg(3, 5);
+        let public = &publics[0];
+        assert!(publics.iter().all(|p| p == public));
+        for key in &keys {
+            assert_eq!(key.group_public_key(), public.group_public_key);
+            assert_eq!(
+                public.verifying_shares[&key.identifier()],
+                key.verifying_share()
+            );
+        }
+
+        // Any three of the five produce a plain Ed25519 signature.
+        for subset in [[0, 1, 2], [1, 3, 4], [4, 0, 2]] {
+            let signers: Vec<&KeyPackage> = subset.iter().map(|i| &keys[*i]).collect();
+            let sig = threshold_sign(&signers, public, b"anchor").unwrap();
+            assert!(verify(b"anchor", &sig, &public.group_public_key).is_ok());
+            assert!(verify(b"other", &sig, &public.group_public_key).is_err());
+        }
+
+        // Two are not enough.
+        let (nonces, commitments): (Vec<_>, Vec<_>) = keys[..2].iter().map(commit).unzip();
+        let nonce = nonces.into_iter().next().unwrap();
+        assert!(sign(&keys[0], nonce, b"anchor", &commitments).is_err());
+    }
+
+    #[test]
+    fn test_split_keypair_keeps_public_key() {
+        let kp = Keypair::from_seed(&[4u8; 32]);
+        let (keys, public) = split_keypair(&kp, 2, 3).unwrap();
+        assert_eq!(public.group_public_key, kp.public_key());
+        let sig = threshold_sign(&[&keys[2], &keys[0]], &public, b"handover").unwrap();
+        assert!(verify(b"handover", &sig, &kp.public_key()).is_ok());
+    }
+
+    #[test]
+    fn test_aggregate_identifies_bad_share() {
+        let (keys, publics) = run_dkg(2, 3);
+        let signers = [&keys[0], &keys[2]];
+        let (nonces, commitments): (Vec<_>, Vec<_>) = signers.iter().map(|k| commit(k)).unzip();
+        let mut shares: Vec<SignatureShare> = signers
+            .iter()
+            .zip(nonces)
+            .map(|(k, n)| sign(k, n, b"m", &commitments).unwrap())
+            .collect();
+        shares[1].share = Scalar::ONE.to_bytes();
+        assert_eq!(
+            aggregate(b"m", &commitments, &shares, &publics[0]),
+            Err(NornError::InvalidSignature { signer_index: 1 })
+        );
+    }
+
+    #[test]
+    fn test_sign_rejects_mismatched_commitments() {
+        let (keys, _) = run_dkg(2, 3);
+        let (nonces, _) = commit(&keys[0]);
+        let (_, fresh) = commit(&keys[0]);
+        let (_, other) = commit(&keys[1]);
+        assert!(sign(&keys[0], nonces, b"m", &[fresh, other]).is_err());
+        let (nonces, own) = commit(&keys[0]);
+        assert!(sign(&keys[0], nonces, b"m", &[own, own]).is_err());
+    }
+
+    #[test]
+    fn test_dkg_rejects_bad_packages() {
+        let (secret, _) = dkg_round1(1, 2, 3).unwrap();
+        let (_, p2) = dkg_round1(2, 2, 3).unwrap();
+        let (_, mut p3) = dkg_round1(3, 2, 3).unwrap();
+        p3.proof_mu = Scalar::ONE.to_bytes();
+        assert!(dkg_round2(secret, &[p2.clone(), p3]).is_err());
+
+        // A share that does not match the sender's commitments.
+        let (s1, p1) = dkg_round1(1, 2, 2).unwrap();
+        let (s2, p2) = dkg_round1(2, 2, 2).unwrap();
+        let (s1, _) = dkg_round2(s1, &[p2]).unwrap();
+        let (_, mut to_1) = dkg_round2(s2, &[p1]).unwrap();
+        to_1[0].share = Scalar::ONE.to_bytes();
+        assert!(dkg_finalize(s1, &to_1).is_err());
+
+        assert!(dkg_round1(0, 2, 3).is_err());
+        assert!(dkg_round1(1, 1, 3).is_err());
+        assert!(dkg_round1(1, 3, 2).is_err());
+    }
+}
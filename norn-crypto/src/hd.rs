@@ -3,16 +3,79 @@ use norn_types::error::NornError;
 
 use crate::keys::Keypair;
 
+/// Highest index a hardened path component can have.
+const MAX_HARDENED_INDEX: u32 = 0x7fff_ffff;
+
+/// Path of the accounts of a wallet, to which the account index is
+/// appended: m/44'/{NORN_COIN_TYPE}'/0'/0'.
+pub fn default_account_path() -> Vec<u32> {
+    vec![44, NORN_COIN_TYPE, 0, 0]
+}
+
+/// Parse a derivation path such as `m/44'/1337'/0'/0'`.
+///
+/// SLIP-0010 only defines hardened derivation for Ed25519, so every
+/// component must be hardened (`'` or `h` suffix).
+pub fn parse_derivation_path(path: &str) -> Result<Vec<u32>, NornError> {
+    let invalid = |reason: String| NornError::DerivationFailed { reason };
+    let mut parts = path.trim().split('/');
+    if parts.next() != Some("m") {
+        return Err(invalid(format!("path must start with 'm/': {path}")));
+    }
+    parts
+        .map(|part| {
+            let digits = part
+                .strip_suffix('\'')
+                .or_else(|| part.strip_suffix('h'))
+                .ok_or_else(|| invalid(format!("component '{part}' is not hardened")))?;
+            digits
+                .parse::<u32>()
+                .ok()
+                .filter(|i| *i <= MAX_HARDENED_INDEX)
+                .ok_or_else(|| invalid(format!("invalid path component '{part}'")))
+        })
+        .collect()
+}
+
+/// Format a derivation path as `m/44'/1337'/...`.
+pub fn format_derivation_path(path: &[u32]) -> String {
+    let mut s = String::from("m");
+    for index in path {
+        s.push_str(&format!("/{index}'"));
+    }
+    s
+}
+
+/// Derive an Ed25519 keypair at an arbitrary hardened path from a BIP-39 seed.
+pub fn derive_keypair_at_path(seed: &[u8; 64], path: &[u32]) -> Result<Keypair, NornError> {
+    if let Some(i) = path.iter().find(|i| **i > MAX_HARDENED_INDEX) {
+        return Err(NornError::DerivationFailed {
+            reason: format!("path component {i} out of range"),
+        });
+    }
+    let derived = slip10_ed25519::derive_ed25519_private_key(seed, path);
+    Ok(Keypair::from_seed(&derived))
+}
+
+/// Derive the keypair of account `index` under an account path such as
+/// [`default_account_path`].
+pub fn derive_account_keypair(
+    seed: &[u8; 64],
+    account_path: &[u32],
+    index: u32,
+) -> Result<Keypair, NornError> {
+    let mut path = account_path.to_vec();
+    path.push(index);
+    derive_keypair_at_path(seed, &path)
+}
+
 /// Derive an Ed25519 keypair using SLIP-0010 from a BIP-39 seed.
 ///
 /// Path: m/44'/{NORN_COIN_TYPE}'/0'/0'/{index}'
 ///
 /// All path components are hardened (required for Ed25519 by SLIP-0010).
 pub fn derive_keypair(seed: &[u8; 64], index: u32) -> Result<Keypair, NornError> {
-    let path = [44, NORN_COIN_TYPE, 0, 0, index];
-
-    let derived = slip10_ed25519::derive_ed25519_private_key(seed, &path);
-    Ok(Keypair::from_seed(&derived))
+    derive_account_keypair(seed, &default_account_path(), index)
 }
 
 /// Derive a keypair at the default index (0).
@@ -62,4 +125,39 @@ mod tests {
         let kp_zero = derive_keypair(&seed, 0).unwrap();
         assert_eq!(kp_default.public_key(), kp_zero.public_key());
     }
+
+    #[test]
+    fn test_parse_derivation_path() {
+        let path = parse_derivation_path(&format!("m/44'/{NORN_COIN_TYPE}'/0h/0'")).unwrap();
+        assert_eq!(path, default_account_path());
+        assert_eq!(
+            parse_derivation_path(&format_derivation_path(&path)).unwrap(),
+            path
+        );
+        assert_eq!(parse_derivation_path("m").unwrap(), Vec::<u32>::new());
+
+        assert!(parse_derivation_path("44'/0'").is_err());
+        assert!(parse_derivation_path("m/44'/0").is_err());
+        assert!(parse_derivation_path("m/2147483648'").is_err());
+        assert!(parse_derivation_path("m/x'").is_err());
+    }
+
+    #[test]
+    fn test_account_path_matches_default_derivation() {
+        let seed = [5u8; 64];
+        for index in [0, 1, 7] {
+            assert_eq!(
+                derive_account_keypair(&seed, &default_account_path(), index)
+                    .unwrap()
+                    .public_key(),
+                derive_keypair(&seed, index).unwrap().public_key()
+            );
+        }
+        let custom = derive_account_keypair(&seed, &[44, NORN_COIN_TYPE, 1, 0], 0).unwrap();
+        assert_ne!(
+            custom.public_key(),
+            derive_keypair(&seed, 0).unwrap().public_key()
+        );
+        assert!(derive_keypair_at_path(&seed, &[0x8000_0000]).is_err());
+    }
 }
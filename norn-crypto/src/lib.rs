@@ -1,14 +1,22 @@
 //! Cryptographic primitives for the Norn Protocol.
 //!
-//! Provides Ed25519 signatures, BLAKE3 hashing, Merkle trees, BIP-39 mnemonic
-//! generation, SLIP-0010 HD key derivation, XChaCha20-Poly1305 authenticated
-//! encryption, and Shamir's Secret Sharing.
+//! Provides Ed25519 signatures, FROST threshold Ed25519 signatures,
+//! aggregatable BLS12-381 signatures, BLAKE3 hashing, Merkle trees, BIP-39
+//! mnemonic generation, SLIP-0010 HD key derivation, XChaCha20-Poly1305
+//! authenticated encryption, Shamir's Secret Sharing, simplified payment
+//! verification, typed structured-data signatures, and an Ed25519
+//! verifiable random function.
 
 pub mod address;
+pub mod bls;
 pub mod encryption;
+pub mod frost;
 pub mod hash;
 pub mod hd;
 pub mod keys;
 pub mod merkle;
 pub mod seed;
 pub mod shamir;
+pub mod spv;
+pub mod typed_data;
+pub mod vrf;
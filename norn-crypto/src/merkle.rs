@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::convert::Infallible;
 use std::hash::{BuildHasher, Hasher};
 
 use borsh::{BorshDeserialize, BorshSerialize};
@@ -25,6 +26,23 @@ pub struct MerkleProof {
     pub siblings: Vec<Hash>,
 }
 
+/// A compact proof for a set of keys in a sparse Merkle tree.
+///
+/// Path segments shared between keys are proved once, and empty siblings,
+/// which are almost all of them in a sparse tree, cost one bit instead of a
+/// hash.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct MerkleMultiProof {
+    /// The proved keys in ascending order, with their values (empty vec for
+    /// non-inclusion).
+    pub leaves: Vec<(Hash, Vec<u8>)>,
+    /// One bit per sibling the verifier consumes, MSB-first: set if the
+    /// sibling is the next entry of `siblings`, clear if it is empty.
+    pub sibling_bitmap: Vec<u8>,
+    /// The non-empty sibling hashes, in the order the verifier consumes them.
+    pub siblings: Vec<Hash>,
+}
+
 // ─── FxHash-style hasher for pre-hashed keys ────────────────────────────────
 
 /// A fast non-cryptographic hasher using multiply-rotate-xor mixing.
@@ -216,6 +234,66 @@ impl SparseMerkleTree {
         }
     }
 
+    /// Generate one compact proof for a set of keys. Duplicate keys are
+    /// proved once.
+    pub fn prove_many(&self, keys: &[Hash]) -> MerkleMultiProof {
+        let mut keys = keys.to_vec();
+        keys.sort_unstable();
+        keys.dedup();
+
+        let mut sibling_bitmap = Vec::new();
+        let mut siblings = Vec::new();
+        let mut bits = 0usize;
+        let mut record = |key: &Hash, depth: usize| -> Result<Hash, Infallible> {
+            let mut sibling_prefix = truncate_key(key, depth + 1);
+            flip_bit(&mut sibling_prefix, depth);
+            let hash = self.nodes[depth + 1]
+                .get(&sibling_prefix)
+                .copied()
+                .unwrap_or(EMPTY_HASH);
+            if bits.is_multiple_of(8) {
+                sibling_bitmap.push(0);
+            }
+            if hash != EMPTY_HASH {
+                sibling_bitmap[bits / 8] |= 0x80 >> (bits % 8);
+                siblings.push(hash);
+            }
+            bits += 1;
+            Ok(hash)
+        };
+
+        let mut walk = MultiProofWalk::default();
+        let mut leaves = Vec::with_capacity(keys.len());
+        for key in keys {
+            let value = self.data.get(&key).cloned().unwrap_or_default();
+            let Ok(()) = walk.push(key, leaf_hash(&key, &value), &mut record);
+            leaves.push((key, value));
+        }
+        if !leaves.is_empty() {
+            let Ok(_) = walk.finish(&mut record);
+        }
+
+        MerkleMultiProof {
+            leaves,
+            sibling_bitmap,
+            siblings,
+        }
+    }
+
+    /// Verify a multiproof against a given root. A proof for no keys is
+    /// rejected.
+    pub fn verify_multiproof(root: &Hash, proof: &MerkleMultiProof) -> Result<(), NornError> {
+        let mut verifier = MultiProofVerifier::new(
+            *root,
+            proof.sibling_bitmap.clone(),
+            proof.siblings.iter().copied(),
+        );
+        for (key, value) in &proof.leaves {
+            verifier.push(*key, value)?;
+        }
+        verifier.finish()
+    }
+
     /// Incrementally update all cached node hashes along the path from a
     /// leaf to the root.
     ///
@@ -276,6 +354,214 @@ impl Default for SparseMerkleTree {
     }
 }
 
+// ─── Multiproofs ────────────────────────────────────────────────────────────
+
+/// Verifies a [`MerkleMultiProof`] incrementally, so a large proof can be
+/// checked while it is still being read. Push the leaves in ascending key
+/// order, then call [`finish`](Self::finish); siblings are pulled from the
+/// iterator only as they are needed.
+pub struct MultiProofVerifier<I> {
+    root: Hash,
+    sibling_bitmap: Vec<u8>,
+    bits_read: usize,
+    siblings: I,
+    walk: MultiProofWalk,
+    last_key: Option<Hash>,
+}
+
+impl<I: Iterator<Item = Hash>> MultiProofVerifier<I> {
+    /// Start verifying a proof against `root`.
+    pub fn new(root: Hash, sibling_bitmap: Vec<u8>, siblings: I) -> Self {
+        Self {
+            root,
+            sibling_bitmap,
+            bits_read: 0,
+            siblings,
+            walk: MultiProofWalk::default(),
+            last_key: None,
+        }
+    }
+
+    /// Feed the next proved leaf. Keys must be strictly ascending.
+    pub fn push(&mut self, key: Hash, value: &[u8]) -> Result<(), NornError> {
+        if self.last_key.is_some_and(|last| key <= last) {
+            return Err(NornError::MerkleProofInvalid);
+        }
+        self.last_key = Some(key);
+        let Self {
+            sibling_bitmap,
+            bits_read,
+            siblings,
+            walk,
+            ..
+        } = self;
+        walk.push(key, leaf_hash(&key, value), &mut |_, _| {
+            next_sibling(sibling_bitmap, bits_read, siblings)
+        })
+    }
+
+    /// Check that the leaves pushed, and exactly the siblings in the proof,
+    /// hash to the root.
+    pub fn finish(self) -> Result<(), NornError> {
+        let Self {
+            root,
+            sibling_bitmap,
+            mut bits_read,
+            mut siblings,
+            walk,
+            last_key,
+        } = self;
+        if last_key.is_none() {
+            return Err(NornError::MerkleProofInvalid);
+        }
+        let computed =
+            walk.finish(&mut |_, _| next_sibling(&sibling_bitmap, &mut bits_read, &mut siblings))?;
+        if computed != root
+            || bits_read.div_ceil(8) != sibling_bitmap.len()
+            || siblings.next().is_some()
+        {
+            return Err(NornError::MerkleProofInvalid);
+        }
+        Ok(())
+    }
+}
+
+/// Read the next sibling of a multiproof: empty, or the next listed hash.
+fn next_sibling<I: Iterator<Item = Hash>>(
+    sibling_bitmap: &[u8],
+    bits_read: &mut usize,
+    siblings: &mut I,
+) -> Result<Hash, NornError> {
+    let byte = sibling_bitmap
+        .get(*bits_read / 8)
+        .ok_or(NornError::MerkleProofInvalid)?;
+    let present = byte & (0x80 >> (*bits_read % 8)) != 0;
+    *bits_read += 1;
+    if present {
+        siblings.next().ok_or(NornError::MerkleProofInvalid)
+    } else {
+        Ok(EMPTY_HASH)
+    }
+}
+
+/// The bottom-up walk shared by multiproof generation and verification.
+///
+/// Leaves arrive in ascending key order. Each is hashed up its path until it
+/// meets either the subtree deferred on top of the stack, which it absorbs,
+/// or the path of the next key, where it is deferred in turn. Every other
+/// sibling on the way is asked of the caller, so the prover and the verifier
+/// visit siblings in the same order.
+#[derive(Default)]
+struct MultiProofWalk {
+    /// Subtrees waiting for their right-hand neighbour, as (depth of the
+    /// node where they join it, subtree hash). Depths grow towards the top.
+    deferred: Vec<(usize, Hash)>,
+    /// The latest leaf (key, leaf hash), held until the next key shows where
+    /// it joins the rest.
+    last: Option<(Hash, Hash)>,
+}
+
+impl MultiProofWalk {
+    fn push<E, F>(&mut self, key: Hash, leaf_hash: Hash, sibling: &mut F) -> Result<(), E>
+    where
+        F: FnMut(&Hash, usize) -> Result<Hash, E>,
+    {
+        if let Some((last_key, last_hash)) = self.last.take() {
+            let join = common_prefix_len(&last_key, &key);
+            self.climb(&last_key, last_hash, Some(join), sibling)?;
+        }
+        self.last = Some((key, leaf_hash));
+        Ok(())
+    }
+
+    /// Climb the last leaf to the root and return it.
+    fn finish<E, F>(mut self, sibling: &mut F) -> Result<Hash, E>
+    where
+        F: FnMut(&Hash, usize) -> Result<Hash, E>,
+    {
+        match self.last.take() {
+            Some((key, hash)) => Ok(self.climb(&key, hash, None, sibling)?.unwrap_or(EMPTY_HASH)),
+            None => Ok(EMPTY_HASH),
+        }
+    }
+
+    /// Hash a leaf up its path, absorbing deferred subtrees on the left,
+    /// until it reaches `join` (deferring itself) or the root (returning it).
+    fn climb<E, F>(
+        &mut self,
+        key: &Hash,
+        mut hash: Hash,
+        join: Option<usize>,
+        sibling: &mut F,
+    ) -> Result<Option<Hash>, E>
+    where
+        F: FnMut(&Hash, usize) -> Result<Hash, E>,
+    {
+        let mut depth = TREE_DEPTH;
+        loop {
+            let left = self.deferred.last().map(|&(d, _)| d);
+            // Of the two joins, the deeper one comes first.
+            let joins_right = match (left, join) {
+                (Some(l), Some(r)) => r > l,
+                (None, Some(_)) => true,
+                _ => false,
+            };
+            let target = if joins_right { join } else { left };
+            let stop = target.map_or(0, |t| t + 1);
+            while depth > stop {
+                depth -= 1;
+                let sib = sibling(key, depth)?;
+                hash = if get_bit(key, depth) == 0 {
+                    hash_children(&hash, &sib)
+                } else {
+                    hash_children(&sib, &hash)
+                };
+            }
+            if joins_right {
+                self.deferred.push((depth - 1, hash));
+                return Ok(None);
+            }
+            match self.deferred.pop() {
+                Some((d, left_hash)) => {
+                    hash = hash_children(&left_hash, &hash);
+                    depth = d;
+                }
+                None => return Ok(Some(hash)),
+            }
+        }
+    }
+}
+
+/// The hash a leaf contributes: empty for a missing key.
+fn leaf_hash(key: &Hash, value: &[u8]) -> Hash {
+    if value.is_empty() {
+        EMPTY_HASH
+    } else {
+        hash_leaf(key, &blake3_hash(value))
+    }
+}
+
+/// Hash two children into their parent, keeping empty subtrees empty.
+fn hash_children(left: &Hash, right: &Hash) -> Hash {
+    if *left == EMPTY_HASH && *right == EMPTY_HASH {
+        EMPTY_HASH
+    } else {
+        hash_internal(left, right)
+    }
+}
+
+/// The number of leading bits two distinct keys share: the depth of the node
+/// where their paths split.
+fn common_prefix_len(a: &Hash, b: &Hash) -> usize {
+    for (i, (x, y)) in a.iter().zip(b).enumerate() {
+        let diff = x ^ y;
+        if diff != 0 {
+            return i * 8 + diff.leading_zeros() as usize;
+        }
+    }
+    TREE_DEPTH
+}
+
 // ─── Key helpers ────────────────────────────────────────────────────────────
 
 /// Truncate a key to its first `depth` bits, zeroing all bits from `depth` onwards.
@@ -346,6 +632,79 @@ pub fn get_bit(key: &Hash, depth: usize) -> u8 {
     }
 }
 
+// ─── Binary Merkle tree over ordered lists ─────────────────────────────────
+
+/// Hash a list leaf: H(0x00 || data).
+pub fn hash_list_leaf(data: &[u8]) -> Hash {
+    let mut buf = Vec::with_capacity(1 + data.len());
+    buf.push(0x00);
+    buf.extend_from_slice(data);
+    blake3_hash(&buf)
+}
+
+/// Hash two sibling nodes in sorted order, so proofs need no left/right flags.
+pub fn hash_sorted_pair(a: &Hash, b: &Hash) -> Hash {
+    if a <= b {
+        hash_internal(a, b)
+    } else {
+        hash_internal(b, a)
+    }
+}
+
+/// Compute the root of a binary Merkle tree over already-hashed leaves.
+/// An odd node at any level is promoted unchanged. Empty input yields `EMPTY_HASH`.
+pub fn build_merkle_root(leaves: &[Hash]) -> Hash {
+    if leaves.is_empty() {
+        return EMPTY_HASH;
+    }
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [a, b] => hash_sorted_pair(a, b),
+                [a] => *a,
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+    level[0]
+}
+
+/// Build the sibling path for `leaves[index]`, or `None` if out of range.
+pub fn build_merkle_proof(leaves: &[Hash], index: usize) -> Option<Vec<Hash>> {
+    if index >= leaves.len() {
+        return None;
+    }
+    let mut proof = Vec::new();
+    let mut level = leaves.to_vec();
+    let mut idx = index;
+    while level.len() > 1 {
+        let sibling = idx ^ 1;
+        if sibling < level.len() {
+            proof.push(level[sibling]);
+        }
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [a, b] => hash_sorted_pair(a, b),
+                [a] => *a,
+                _ => unreachable!(),
+            })
+            .collect();
+        idx /= 2;
+    }
+    Some(proof)
+}
+
+/// Verify that `leaf` is included under `root` given its sibling path.
+pub fn verify_merkle_proof(root: &Hash, leaf: &Hash, proof: &[Hash]) -> bool {
+    let computed = proof
+        .iter()
+        .fold(*leaf, |acc, sibling| hash_sorted_pair(&acc, sibling));
+    &computed == root
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -596,4 +955,159 @@ mod tests {
         assert_eq!(truncate_key(&key, 4), expected);
         assert_eq!(truncate_key(&key, 256), key);
     }
+
+    #[test]
+    fn test_multiproof_inclusion_and_non_inclusion() {
+        let mut tree = SparseMerkleTree::new();
+        for i in 0..50u8 {
+            tree.insert(blake3_hash(&[i]), vec![i; 8]);
+        }
+        let root = tree.root();
+
+        // Present keys, a missing key, and a duplicate, in no particular order.
+        let keys: Vec<Hash> = [7u8, 3, 200, 42, 3]
+            .iter()
+            .map(|i| blake3_hash(&[*i]))
+            .collect();
+        let proof = tree.prove_many(&keys);
+        assert_eq!(proof.leaves.len(), 4);
+        assert!(proof.leaves.windows(2).all(|w| w[0].0 < w[1].0));
+        let missing = blake3_hash(&[200]);
+        for (key, value) in &proof.leaves {
+            assert_eq!(value.is_empty(), *key == missing);
+        }
+        assert!(SparseMerkleTree::verify_multiproof(&root, &proof).is_ok());
+        assert!(SparseMerkleTree::verify_multiproof(&blake3_hash(b"wrong"), &proof).is_err());
+    }
+
+    #[test]
+    fn test_multiproof_is_smaller_than_single_proofs() {
+        let mut tree = SparseMerkleTree::new();
+        for i in 0..=255u8 {
+            tree.insert(blake3_hash(&[i]), vec![i]);
+        }
+        let keys: Vec<Hash> = (0..32u8).map(|i| blake3_hash(&[i])).collect();
+        let proof = tree.prove_many(&keys);
+        let multi = borsh::to_vec(&proof).unwrap().len();
+        let single: usize = keys
+            .iter()
+            .map(|k| borsh::to_vec(&tree.prove(k)).unwrap().len())
+            .sum();
+        assert!(multi * 10 < single, "multi={multi} single={single}");
+        assert!(SparseMerkleTree::verify_multiproof(&tree.root(), &proof).is_ok());
+    }
+
+    #[test]
+    fn test_multiproof_rejects_tampering() {
+        let mut tree = SparseMerkleTree::new();
+        for i in 0..20u8 {
+            tree.insert(blake3_hash(&[i]), vec![i; 4]);
+        }
+        let root = tree.root();
+        let keys: Vec<Hash> = (0..5u8).map(|i| blake3_hash(&[i])).collect();
+        let proof = tree.prove_many(&keys);
+
+        let mut wrong_value = proof.clone();
+        wrong_value.leaves[0].1 = vec![99];
+        assert!(SparseMerkleTree::verify_multiproof(&root, &wrong_value).is_err());
+
+        let mut reordered = proof.clone();
+        reordered.leaves.swap(0, 1);
+        assert!(SparseMerkleTree::verify_multiproof(&root, &reordered).is_err());
+
+        let mut dropped_leaf = proof.clone();
+        dropped_leaf.leaves.pop();
+        assert!(SparseMerkleTree::verify_multiproof(&root, &dropped_leaf).is_err());
+
+        let mut extra_sibling = proof.clone();
+        extra_sibling.siblings.push([1u8; 32]);
+        assert!(SparseMerkleTree::verify_multiproof(&root, &extra_sibling).is_err());
+
+        let mut flipped_bit = proof.clone();
+        flipped_bit.sibling_bitmap[0] ^= 0x80;
+        assert!(SparseMerkleTree::verify_multiproof(&root, &flipped_bit).is_err());
+
+        let empty = tree.prove_many(&[]);
+        assert!(SparseMerkleTree::verify_multiproof(&root, &empty).is_err());
+    }
+
+    #[test]
+    fn test_multiproof_streaming_verification() {
+        let mut tree = SparseMerkleTree::new();
+        for i in 0..10u8 {
+            tree.insert(blake3_hash(&[i]), vec![i; 3]);
+        }
+        let keys: Vec<Hash> = (0..10u8).map(|i| blake3_hash(&[i])).collect();
+        let proof = tree.prove_many(&keys);
+
+        let mut verifier = MultiProofVerifier::new(
+            tree.root(),
+            proof.sibling_bitmap.clone(),
+            proof.siblings.clone().into_iter(),
+        );
+        for (key, value) in &proof.leaves {
+            verifier.push(*key, value).unwrap();
+        }
+        assert!(verifier.finish().is_ok());
+
+        // Out-of-order leaves are rejected as soon as they are pushed.
+        let mut verifier = MultiProofVerifier::new(
+            tree.root(),
+            proof.sibling_bitmap.clone(),
+            proof.siblings.clone().into_iter(),
+        );
+        verifier
+            .push(proof.leaves[1].0, &proof.leaves[1].1)
+            .unwrap();
+        assert!(verifier
+            .push(proof.leaves[0].0, &proof.leaves[0].1)
+            .is_err());
+    }
+
+    #[test]
+    fn test_multiproof_single_key_and_empty_tree() {
+        let empty = SparseMerkleTree::new();
+        let key = blake3_hash(b"key");
+        let proof = empty.prove_many(&[key]);
+        assert!(proof.siblings.is_empty());
+        assert!(SparseMerkleTree::verify_multiproof(&EMPTY_HASH, &proof).is_ok());
+
+        let mut tree = SparseMerkleTree::new();
+        tree.insert(key, b"value".to_vec());
+        tree.insert(blake3_hash(b"other"), b"value".to_vec());
+        let proof = tree.prove_many(&[key]);
+        assert_eq!(proof.siblings.len(), 1);
+        assert!(SparseMerkleTree::verify_multiproof(&tree.root(), &proof).is_ok());
+    }
+
+    #[test]
+    fn test_binary_merkle_proofs_verify() {
+        for n in 1..=9 {
+            let leaves: Vec<Hash> = (0..n as u8).map(|i| hash_list_leaf(&[i])).collect();
+            let root = build_merkle_root(&leaves);
+            for (i, leaf) in leaves.iter().enumerate() {
+                let proof = build_merkle_proof(&leaves, i).unwrap();
+                assert!(verify_merkle_proof(&root, leaf, &proof), "n={n} i={i}");
+            }
+            assert!(build_merkle_proof(&leaves, n).is_none());
+        }
+    }
+
+    #[test]
+    fn test_binary_merkle_rejects_bad_proof() {
+        let leaves: Vec<Hash> = (0..5u8).map(|i| hash_list_leaf(&[i])).collect();
+        let root = build_merkle_root(&leaves);
+        let proof = build_merkle_proof(&leaves, 2).unwrap();
+        assert!(!verify_merkle_proof(&root, &hash_list_leaf(&[99]), &proof));
+        assert!(!verify_merkle_proof(&root, &leaves[3], &proof));
+        assert!(!verify_merkle_proof(&root, &leaves[2], &proof[1..]));
+    }
+
+    #[test]
+    fn test_binary_merkle_root_edge_cases() {
+        assert_eq!(build_merkle_root(&[]), EMPTY_HASH);
+        let leaf = hash_list_leaf(b"only");
+        assert_eq!(build_merkle_root(&[leaf]), leaf);
+        assert!(verify_merkle_proof(&leaf, &leaf, &[]));
+    }
 }
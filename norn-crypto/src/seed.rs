@@ -16,6 +16,11 @@ pub fn parse_mnemonic(phrase: &str) -> Result<Mnemonic, NornError> {
 
 /// Derive a 64-byte seed from a mnemonic with an optional passphrase.
 /// Uses BIP-39 PBKDF2 derivation.
+///
+/// The passphrase (the "25th word") is NFKD-normalized, so visually
+/// identical spellings derive the same seed. Every passphrase yields a
+/// valid, different wallet: a mistyped one opens an empty wallet rather
+/// than failing.
 pub fn mnemonic_to_seed(mnemonic: &Mnemonic, passphrase: &str) -> [u8; 64] {
     mnemonic.to_seed(passphrase)
 }
@@ -77,4 +82,37 @@ mod tests {
 
         assert_eq!(kp1.public_key(), kp2.public_key());
     }
+
+    #[test]
+    fn test_bip39_passphrase_vectors() {
+        // Reference vectors from the BIP-39 test suite (passphrase "TREZOR").
+        let vectors = [
+            (
+                "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+                "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+            ),
+            (
+                "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art",
+                "bda85446c68413707090a52022edd26a1c9462295029f2e60cd7c4f2bbd3097170af7a4d73245cafa9c3cca8d561a7c3de6f5d4a10be8ed2a5e608d68f92fcc8",
+            ),
+        ];
+        for (phrase, seed) in vectors {
+            let mnemonic = parse_mnemonic(phrase).unwrap();
+            assert_eq!(hex::encode(mnemonic_to_seed(&mnemonic, "TREZOR")), seed);
+        }
+    }
+
+    #[test]
+    fn test_passphrase_is_nfkd_normalized() {
+        let mnemonic = generate_mnemonic();
+        // Precomposed and decomposed "é".
+        assert_eq!(
+            mnemonic_to_seed(&mnemonic, "caf\u{e9}"),
+            mnemonic_to_seed(&mnemonic, "cafe\u{301}")
+        );
+        assert_ne!(
+            mnemonic_to_seed(&mnemonic, "cafe"),
+            mnemonic_to_seed(&mnemonic, "Cafe")
+        );
+    }
 }
@@ -0,0 +1,340 @@
+//! Simplified payment verification for weave transfers.
+//!
+//! A [`TransferProof`] carries a transfer, its Merkle proof under the
+//! `transfers_root` of the block that included it, and the headers from that
+//! block up to the tip. Checking it needs only the validator set, so a
+//! deposit can be credited without trusting the node that served the proof.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use norn_types::error::NornError;
+use norn_types::primitives::Hash;
+use norn_types::weave::{BlockHeader, BlockTransfer, ValidatorSet, WeaveBlock};
+use serde::{Deserialize, Serialize};
+
+use crate::bls::verify_commit_certificate;
+use crate::hash::blake3_hash;
+use crate::keys::verify;
+use crate::merkle::{MerkleProof, SparseMerkleTree};
+
+/// Proof that a transfer was included in the weave.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct TransferProof {
+    /// The transfer as it appears in the block.
+    pub transfer: BlockTransfer,
+    /// Proof of the transfer under the including block's `transfers_root`.
+    pub inclusion: MerkleProof,
+    /// The including block's header, followed by the consecutive headers
+    /// built on top of it.
+    pub headers: Vec<BlockHeader>,
+}
+
+impl TransferProof {
+    /// Build a proof for the transfer with `knot_id` in `block`, followed by
+    /// `later` headers. Returns `None` if the block has no such transfer.
+    pub fn build(block: &WeaveBlock, knot_id: &Hash, later: Vec<BlockHeader>) -> Option<Self> {
+        let transfer = block.transfers.iter().find(|t| t.knot_id == *knot_id)?;
+
+        // Same tree as the block's transfers_root: leaves keyed by the hash
+        // of their borsh encoding.
+        let mut tree = SparseMerkleTree::new();
+        for t in &block.transfers {
+            if let Ok(bytes) = borsh::to_vec(t) {
+                tree.insert(blake3_hash(&bytes), bytes);
+            }
+        }
+        let key = blake3_hash(&borsh::to_vec(transfer).ok()?);
+
+        let mut headers = Vec::with_capacity(1 + later.len());
+        headers.push(block.header());
+        headers.extend(later);
+        Some(Self {
+            transfer: transfer.clone(),
+            inclusion: tree.prove(&key),
+            headers,
+        })
+    }
+}
+
+/// A transfer accepted by [`verify_transfer_proof`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifiedTransfer {
+    /// Height of the block that included the transfer.
+    pub height: u64,
+    /// Hash of the block that included the transfer.
+    pub block_hash: Hash,
+    /// Number of certified blocks from the including block to the last
+    /// header in the proof, counting the including block itself.
+    pub confirmations: u64,
+}
+
+/// Verify a transfer proof against a trusted validator set: the transfer is
+/// included in the first header, and every header is correctly hashed,
+/// extends the one before it, and is certified by a quorum of `validator_set`.
+pub fn verify_transfer_proof(
+    proof: &TransferProof,
+    validator_set: &ValidatorSet,
+) -> Result<VerifiedTransfer, NornError> {
+    let block = proof
+        .headers
+        .first()
+        .ok_or_else(|| NornError::InvalidWeaveBlock {
+            reason: "transfer proof has no headers".to_string(),
+        })?;
+    verify_transfer_inclusion(&proof.transfer, &proof.inclusion, &block.transfers_root)?;
+    verify_header_chain(&proof.headers, validator_set)?;
+    Ok(VerifiedTransfer {
+        height: block.height,
+        block_hash: block.hash,
+        confirmations: proof.headers.len() as u64,
+    })
+}
+
+/// Check that `transfer` is one of the transfers under `transfers_root`.
+pub fn verify_transfer_inclusion(
+    transfer: &BlockTransfer,
+    proof: &MerkleProof,
+    transfers_root: &Hash,
+) -> Result<(), NornError> {
+    let bytes = borsh::to_vec(transfer).map_err(|e| NornError::SerializationError {
+        reason: e.to_string(),
+    })?;
+    if proof.key != blake3_hash(&bytes) || proof.value != bytes {
+        return Err(NornError::MerkleProofInvalid);
+    }
+    SparseMerkleTree::verify_proof(transfers_root, proof)
+}
+
+/// Check a run of consecutive headers, each signed by its proposer and
+/// certified by a quorum of `validator_set`.
+pub fn verify_header_chain(
+    headers: &[BlockHeader],
+    validator_set: &ValidatorSet,
+) -> Result<(), NornError> {
+    for (i, header) in headers.iter().enumerate() {
+        let invalid = |reason: &str| NornError::InvalidWeaveBlock {
+            reason: format!("header {}: {}", header.height, reason),
+        };
+        if header.compute_hash() != header.hash {
+            return Err(invalid("hash does not match header contents"));
+        }
+        if i > 0 {
+            let prev = &headers[i - 1];
+            if header.height != prev.height + 1 || header.prev_hash != prev.hash {
+                return Err(invalid("does not extend the previous header"));
+            }
+        }
+        if !validator_set.contains(&header.proposer) {
+            return Err(invalid("proposer is not a validator"));
+        }
+
+        if verify(&header.hash, &header.proposer_signature, &header.proposer).is_err() {
+            return Err(invalid("invalid proposer signature"));
+        }
+        let qc = header
+            .certificate
+            .as_ref()
+            .ok_or_else(|| invalid("no commit certificate"))?;
+        verify_commit_certificate(qc, &header.hash, validator_set)
+            .map_err(|e| invalid(&e.to_string()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bls::{aggregate_votes, BlsKeypair};
+    use crate::keys::Keypair;
+    use norn_types::consensus::{vote_signing_data, ConsensusPhase, QuorumCertificate, Vote};
+    use norn_types::primitives::NATIVE_TOKEN_ID;
+    use norn_types::weave::Validator;
+
+    fn validator_set(keypairs: &[Keypair]) -> ValidatorSet {
+        let validators: Vec<Validator> = keypairs
+            .iter()
+            .map(|kp| Validator {
+                pubkey: kp.public_key(),
+                bls_pubkey: BlsKeypair::from_keypair(kp).public_key(),
+                address: [0u8; 20],
+                stake: 1000,
+                active: true,
+            })
+            .collect();
+        ValidatorSet {
+            total_stake: 1000 * validators.len() as u128,
+            validators,
+            epoch: 0,
+        }
+    }
+
+    fn transfer(knot: u8, amount: u128) -> BlockTransfer {
+        BlockTransfer {
+            from: [1u8; 20],
+            to: [2u8; 20],
+            token_id: NATIVE_TOKEN_ID,
+            amount,
+            memo: None,
+            knot_id: [knot; 32],
+            timestamp: 1000,
+        }
+    }
+
+    /// A `phase` certificate for `block_hash` signed by `signers`.
+    fn certify(
+        phase: ConsensusPhase,
+        block_hash: Hash,
+        signers: &[Keypair],
+        vs: &ValidatorSet,
+    ) -> QuorumCertificate {
+        let signing_data = vote_signing_data(phase, 1, &block_hash);
+        let votes: Vec<Vote> = signers
+            .iter()
+            .map(|kp| Vote {
+                view: 1,
+                phase,
+                block_hash,
+                voter: kp.public_key(),
+                signature: kp.sign(&signing_data),
+                bls_signature: BlsKeypair::from_keypair(kp).sign(&signing_data),
+            })
+            .collect();
+        aggregate_votes(phase, &votes, vs).unwrap()
+    }
+
+    /// A block at `height` after `prev_hash` with `transfers`, proposed by
+    /// the first of `signers` and certified by all of them.
+    fn block(
+        height: u64,
+        prev_hash: Hash,
+        transfers: Vec<BlockTransfer>,
+        signers: &[Keypair],
+        vs: &ValidatorSet,
+    ) -> WeaveBlock {
+        let mut tree = SparseMerkleTree::new();
+        for t in &transfers {
+            let bytes = borsh::to_vec(t).unwrap();
+            tree.insert(blake3_hash(&bytes), bytes);
+        }
+        let mut block = WeaveBlock {
+            height,
+            hash: [0u8; 32],
+            prev_hash,
+            commitments_root: [0u8; 32],
+            registrations_root: [0u8; 32],
+            anchors_root: [0u8; 32],
+            commitments: vec![],
+            registrations: vec![],
+            anchors: vec![],
+            name_registrations: vec![],
+            name_registrations_root: [0u8; 32],
+            name_transfers: vec![],
+            name_transfers_root: [0u8; 32],
+            name_record_updates: vec![],
+            name_record_updates_root: [0u8; 32],
+            fraud_proofs: vec![],
+            fraud_proofs_root: [0u8; 32],
+            transfers_root: tree.root(),
+            transfers,
+            token_definitions: vec![],
+            token_definitions_root: [0u8; 32],
+            token_mints: vec![],
+            token_mints_root: [0u8; 32],
+            token_burns: vec![],
+            token_metadata_updates: vec![],
+            token_burns_root: [0u8; 32],
+            token_metadata_updates_root: [0u8; 32],
+            loom_deploys: vec![],
+            loom_deploys_root: [0u8; 32],
+            operator_handovers: vec![],
+            operator_handovers_root: [0u8; 32],
+            key_updates: vec![],
+            key_updates_root: [0u8; 32],
+            stake_operations: vec![],
+            stake_operations_root: [0u8; 32],
+            slashing_evidence: vec![],
+            slashing_evidence_root: [0u8; 32],
+            spindle_operations: vec![],
+            spindle_operations_root: [0u8; 32],
+            base_fee: 0,
+            state_root: [0u8; 32],
+            validator_set_hash: [0u8; 32],
+            timestamp: 1000 + height,
+            proposer: signers[0].public_key(),
+            proposer_signature: [0u8; 64],
+            certificate: None,
+        };
+        block.hash = block.header().compute_hash();
+        block.proposer_signature = signers[0].sign(&block.hash);
+        block.certificate = Some(certify(ConsensusPhase::Commit, block.hash, signers, vs));
+        block
+    }
+
+    #[test]
+    fn test_transfer_proof_roundtrip() {
+        let keypairs: Vec<Keypair> = (0..4).map(|_| Keypair::generate()).collect();
+        let vs = validator_set(&keypairs);
+        let included = block(
+            5,
+            [0u8; 32],
+            vec![transfer(1, 10), transfer(2, 20)],
+            &keypairs[..3],
+            &vs,
+        );
+        let next = block(6, included.hash, vec![], &keypairs[1..], &vs);
+
+        let proof = TransferProof::build(&included, &[2u8; 32], vec![next.header()]).unwrap();
+        let bytes = borsh::to_vec(&proof).unwrap();
+        let decoded: TransferProof = borsh::from_slice(&bytes).unwrap();
+        let verified = verify_transfer_proof(&decoded, &vs).unwrap();
+        assert_eq!(verified.height, 5);
+        assert_eq!(verified.block_hash, included.hash);
+        assert_eq!(verified.confirmations, 2);
+
+        assert!(TransferProof::build(&included, &[9u8; 32], vec![]).is_none());
+    }
+
+    #[test]
+    fn test_transfer_proof_rejects_tampering() {
+        let keypairs: Vec<Keypair> = (0..4).map(|_| Keypair::generate()).collect();
+        let vs = validator_set(&keypairs);
+        let included = block(5, [0u8; 32], vec![transfer(1, 10)], &keypairs[..3], &vs);
+        let proof = TransferProof::build(&included, &[1u8; 32], vec![]).unwrap();
+
+        let mut inflated = proof.clone();
+        inflated.transfer.amount = 1_000;
+        assert_eq!(
+            verify_transfer_proof(&inflated, &vs),
+            Err(NornError::MerkleProofInvalid)
+        );
+
+        // Under-signed blocks and unrelated validator sets are rejected.
+        let weak = block(5, [0u8; 32], vec![transfer(1, 10)], &keypairs[..2], &vs);
+        let weak_proof = TransferProof::build(&weak, &[1u8; 32], vec![]).unwrap();
+        assert!(verify_transfer_proof(&weak_proof, &vs).is_err());
+        let others: Vec<Keypair> = (0..4).map(|_| Keypair::generate()).collect();
+        assert!(verify_transfer_proof(&proof, &validator_set(&others)).is_err());
+
+        // A later header that does not extend the including block.
+        let fork = block(6, [7u8; 32], vec![], &keypairs[..3], &vs);
+        let mut forked = proof.clone();
+        forked.headers.push(fork.header());
+        assert!(verify_transfer_proof(&forked, &vs).is_err());
+
+        // A header anchored on a prepare certificate relabelled as a commit
+        // certificate.
+        let mut prepare = certify(ConsensusPhase::Prepare, included.hash, &keypairs[..3], &vs);
+        prepare.phase = ConsensusPhase::Commit;
+        let mut relabelled = proof.clone();
+        relabelled.headers[0].certificate = Some(prepare);
+        assert!(verify_transfer_proof(&relabelled, &vs).is_err());
+
+        // A header whose certificate was stripped.
+        let mut uncertified = proof.clone();
+        uncertified.headers[0].certificate = None;
+        assert!(verify_transfer_proof(&uncertified, &vs).is_err());
+
+        let mut empty = proof;
+        empty.headers.clear();
+        assert!(verify_transfer_proof(&empty, &vs).is_err());
+    }
+}
@@ -0,0 +1,74 @@
+//! Signatures over typed structured data.
+//!
+//! The digest is defined by [`norn_types::typed_data`]; this module signs
+//! and verifies it with Ed25519.
+
+use norn_types::error::NornError;
+use norn_types::primitives::{PublicKey, Signature};
+use norn_types::typed_data::TypedData;
+
+use crate::keys::{verify, Keypair};
+
+/// Sign typed data.
+pub fn sign_typed_data(keypair: &Keypair, data: &TypedData) -> Result<Signature, NornError> {
+    Ok(keypair.sign(&data.signing_hash()?))
+}
+
+/// Verify a signature over typed data.
+pub fn verify_typed_data(
+    data: &TypedData,
+    signature: &Signature,
+    pubkey: &PublicKey,
+) -> Result<(), NornError> {
+    verify(&data.signing_hash()?, signature, pubkey)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use norn_types::typed_data::{StructType, TypedDomain, TypedValue};
+
+    fn permit() -> TypedData {
+        TypedData {
+            domain: TypedDomain {
+                name: "norn20".to_string(),
+                version: "1".to_string(),
+                chain_id: "norn-dev".to_string(),
+                loom: [3u8; 20],
+            },
+            message_type: StructType::parse(
+                "Permit(address owner,address spender,u128 amount,u64 nonce,u64 deadline)",
+            )
+            .unwrap(),
+            values: vec![
+                TypedValue::Address([1u8; 20]),
+                TypedValue::Address([2u8; 20]),
+                TypedValue::U128(500),
+                TypedValue::U64(0),
+                TypedValue::U64(1_000),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_sign_verify_typed_data() {
+        let keypair = Keypair::from_seed(&[4u8; 32]);
+        let data = permit();
+        let sig = sign_typed_data(&keypair, &data).unwrap();
+        assert!(verify_typed_data(&data, &sig, &keypair.public_key()).is_ok());
+
+        // The same signature is not valid on another network.
+        let mut replay = data.clone();
+        replay.domain.chain_id = "norn-mainnet".to_string();
+        assert!(verify_typed_data(&replay, &sig, &keypair.public_key()).is_err());
+
+        // Nor for a plain message with the same bytes.
+        let digest = data.signing_hash().unwrap();
+        assert!(verify(
+            &crate::hash::blake3_hash(&digest),
+            &sig,
+            &keypair.public_key()
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,233 @@
+//! Ed25519 verifiable random function (ECVRF-EDWARDS25519-SHA512-TAI,
+//! RFC 9381).
+//!
+//! A VRF output is a pseudorandom hash of an input that only the holder of
+//! the secret key can compute, but that anyone can check against the public
+//! key using the accompanying proof. VRF keys are ordinary Ed25519 keys, so
+//! any [`Keypair`] can prove.
+
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
+use curve25519_dalek::scalar::{clamp_integer, Scalar};
+use norn_types::error::NornError;
+use norn_types::primitives::PublicKey;
+use sha2::{Digest, Sha512};
+
+use crate::keys::Keypair;
+
+/// Size of a VRF proof: `Gamma` (32) || `c` (16) || `s` (32).
+pub const PROOF_SIZE: usize = 80;
+
+/// Size of a VRF output.
+pub const OUTPUT_SIZE: usize = 64;
+
+/// A VRF proof.
+pub type VrfProof = [u8; PROOF_SIZE];
+
+/// A VRF output (RFC 9381 `beta`).
+pub type VrfOutput = [u8; OUTPUT_SIZE];
+
+/// RFC 9381 suite string of ECVRF-EDWARDS25519-SHA512-TAI.
+const SUITE: u8 = 0x03;
+
+/// Length of the challenge `c` in bytes.
+const CHALLENGE_LEN: usize = 16;
+
+/// Prove `alpha` with `keypair`, returning the proof.
+pub fn prove(keypair: &Keypair, alpha: &[u8]) -> VrfProof {
+    let expanded: [u8; 64] = Sha512::digest(keypair.seed()).into();
+    let mut scalar_bytes = [0u8; 32];
+    scalar_bytes.copy_from_slice(&expanded[..32]);
+    let x = Scalar::from_bytes_mod_order(clamp_integer(scalar_bytes));
+    let public_key = keypair.public_key();
+
+    let h = encode_to_curve(&public_key, alpha)
+        .expect("try-and-increment finds a point with overwhelming probability");
+    let h_bytes = h.compress().to_bytes();
+    let gamma = x * h;
+
+    let nonce: [u8; 64] = Sha512::new()
+        .chain_update(&expanded[32..])
+        .chain_update(h_bytes)
+        .finalize()
+        .into();
+    let k = Scalar::from_bytes_mod_order_wide(&nonce);
+    let c = challenge(
+        &public_key,
+        &h_bytes,
+        &gamma,
+        &EdwardsPoint::mul_base(&k),
+        &(k * h),
+    );
+    let s = k + c * x;
+
+    let mut proof = [0u8; PROOF_SIZE];
+    proof[..32].copy_from_slice(gamma.compress().as_bytes());
+    proof[32..48].copy_from_slice(&c.as_bytes()[..CHALLENGE_LEN]);
+    proof[48..].copy_from_slice(s.as_bytes());
+    proof
+}
+
+/// Verify `proof` of `alpha` under `public_key`, returning the VRF output.
+pub fn verify(
+    public_key: &PublicKey,
+    alpha: &[u8],
+    proof: &VrfProof,
+) -> Result<VrfOutput, NornError> {
+    let y = CompressedEdwardsY(*public_key)
+        .decompress()
+        .filter(|y| !y.is_small_order())
+        .ok_or(NornError::InvalidKeyMaterial)?;
+    let (gamma, c, s) = decode_proof(proof)?;
+    let h = encode_to_curve(public_key, alpha).ok_or(NornError::InvalidVrfProof)?;
+
+    // U = s*B - c*Y, V = s*H - c*Gamma
+    let u = EdwardsPoint::vartime_double_scalar_mul_basepoint(&-c, &y, &s);
+    let v = s * h - c * gamma;
+    if challenge(public_key, &h.compress().to_bytes(), &gamma, &u, &v) != c {
+        return Err(NornError::InvalidVrfProof);
+    }
+    Ok(gamma_to_hash(&gamma))
+}
+
+/// The VRF output of `proof`, without verifying it.
+///
+/// Only use this on proofs already verified, or produced by [`prove`].
+pub fn proof_to_hash(proof: &VrfProof) -> Result<VrfOutput, NornError> {
+    let (gamma, _, _) = decode_proof(proof)?;
+    Ok(gamma_to_hash(&gamma))
+}
+
+fn decode_proof(proof: &VrfProof) -> Result<(EdwardsPoint, Scalar, Scalar), NornError> {
+    let mut gamma = [0u8; 32];
+    gamma.copy_from_slice(&proof[..32]);
+    let gamma = CompressedEdwardsY(gamma)
+        .decompress()
+        .ok_or(NornError::InvalidVrfProof)?;
+    let mut c = [0u8; 32];
+    c[..CHALLENGE_LEN].copy_from_slice(&proof[32..48]);
+    let mut s = [0u8; 32];
+    s.copy_from_slice(&proof[48..]);
+    let s = Option::from(Scalar::from_canonical_bytes(s)).ok_or(NornError::InvalidVrfProof)?;
+    Ok((gamma, Scalar::from_bytes_mod_order(c), s))
+}
+
+/// Hash `alpha` to a point of the prime-order subgroup by try-and-increment.
+fn encode_to_curve(public_key: &PublicKey, alpha: &[u8]) -> Option<EdwardsPoint> {
+    (0..=u8::MAX).find_map(|ctr| {
+        let hash = Sha512::new()
+            .chain_update([SUITE, 0x01])
+            .chain_update(public_key)
+            .chain_update(alpha)
+            .chain_update([ctr, 0x00])
+            .finalize();
+        let mut candidate = [0u8; 32];
+        candidate.copy_from_slice(&hash[..32]);
+        CompressedEdwardsY(candidate)
+            .decompress()
+            .map(|p| p.mul_by_cofactor())
+    })
+}
+
+fn challenge(
+    public_key: &PublicKey,
+    h: &[u8; 32],
+    gamma: &EdwardsPoint,
+    u: &EdwardsPoint,
+    v: &EdwardsPoint,
+) -> Scalar {
+    let hash = Sha512::new()
+        .chain_update([SUITE, 0x02])
+        .chain_update(public_key)
+        .chain_update(h)
+        .chain_update(gamma.compress().as_bytes())
+        .chain_update(u.compress().as_bytes())
+        .chain_update(v.compress().as_bytes())
+        .chain_update([0x00])
+        .finalize();
+    let mut c = [0u8; 32];
+    c[..CHALLENGE_LEN].copy_from_slice(&hash[..CHALLENGE_LEN]);
+    Scalar::from_bytes_mod_order(c)
+}
+
+fn gamma_to_hash(gamma: &EdwardsPoint) -> VrfOutput {
+    Sha512::new()
+        .chain_update([SUITE, 0x03])
+        .chain_update(gamma.mul_by_cofactor().compress().as_bytes())
+        .chain_update([0x00])
+        .finalize()
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keypair(seed_hex: &str) -> Keypair {
+        Keypair::from_seed(&hex::decode(seed_hex).unwrap().try_into().unwrap())
+    }
+
+    #[test]
+    fn test_rfc9381_vectors() {
+        // RFC 9381, Appendix B.3, examples 16 and 17.
+        let vectors = [
+            (
+                "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+                "",
+                "8657106690b5526245a92b003bb079ccd1a92130477671f6fc01ad16f26f723f\
+                 26f8a57ccaed74ee1b190bed1f479d9727d2d0f9b005a6e456a35d4fb0daab12\
+                 68a1b0db10836d9826a528ca76567805",
+                "90cf1df3b703cce59e2a35b925d411164068269d7b2d29f3301c03dd757876ff\
+                 66b71dda49d2de59d03450451af026798e8f81cd2e333de5cdf4f3e140fdd8ae",
+            ),
+            (
+                "4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb",
+                "72",
+                "f3141cd382dc42909d19ec5110469e4feae18300e94f304590abdced48aed593\
+                 3bf0864a62558b3ed7f2fea45c92a465301b3bbf5e3e54ddf2d935be3b67926d\
+                 a3ef39226bbc355bdc9850112c8f4b02",
+                "eb4440665d3891d668e7e0fcaf587f1b4bd7fbfe99d0eb2211ccec90496310eb\
+                 5e33821bc613efb94db5e5b54c70a848a0bef4553a41befc57663b56373a5031",
+            ),
+        ];
+        for (seed, alpha, proof, output) in vectors {
+            let kp = keypair(seed);
+            let alpha = hex::decode(alpha).unwrap();
+            let pi = prove(&kp, &alpha);
+            assert_eq!(hex::encode(pi), proof);
+            assert_eq!(hex::encode(proof_to_hash(&pi).unwrap()), output);
+            assert_eq!(
+                hex::encode(verify(&kp.public_key(), &alpha, &pi).unwrap()),
+                output
+            );
+        }
+    }
+
+    #[test]
+    fn test_verify_rejects_tampering() {
+        let kp = Keypair::from_seed(&[7u8; 32]);
+        let other = Keypair::from_seed(&[8u8; 32]);
+        let proof = prove(&kp, b"request-1");
+        assert!(verify(&kp.public_key(), b"request-1", &proof).is_ok());
+        assert!(verify(&kp.public_key(), b"request-2", &proof).is_err());
+        assert!(verify(&other.public_key(), b"request-1", &proof).is_err());
+        for i in [0, 40, 70] {
+            let mut bad = proof;
+            bad[i] ^= 1;
+            assert!(verify(&kp.public_key(), b"request-1", &bad).is_err());
+        }
+        // Non-canonical `s`.
+        let mut bad = proof;
+        bad[48..].copy_from_slice(&[0xff; 32]);
+        assert!(verify(&kp.public_key(), b"request-1", &bad).is_err());
+    }
+
+    #[test]
+    fn test_output_is_deterministic() {
+        let kp = Keypair::from_seed(&[7u8; 32]);
+        assert_eq!(prove(&kp, b"a"), prove(&kp, b"a"));
+        assert_ne!(
+            proof_to_hash(&prove(&kp, b"a")).unwrap(),
+            proof_to_hash(&prove(&kp, b"b")).unwrap()
+        );
+    }
+}
@@ -0,0 +1,572 @@
+use std::collections::BTreeMap;
+
+use borsh::BorshSerialize;
+use norn_crypto::merkle::{MerkleMultiProof, MerkleProof};
+use norn_crypto::spv::{self, TransferProof};
+use norn_types::consensus::QuorumCertificate;
+use norn_types::primitives::{Address, Amount, Hash, TokenId};
+use norn_types::weave::{BlockHeader, ValidatorSet, ValidatorSignature};
+
+use crate::error::LightClientError;
+use crate::verify;
+
+/// Default number of recent headers kept for proof verification.
+pub const DEFAULT_HEADER_RETENTION: usize = 1_024;
+
+/// Follows the weave header by header from a trusted checkpoint.
+pub struct LightClient {
+    validator_set: ValidatorSet,
+    /// Verified headers by height, the most recent `retention` of them.
+    headers: BTreeMap<u64, BlockHeader>,
+    retention: usize,
+}
+
+impl LightClient {
+    /// Start from a trusted checkpoint header and the validator set that
+    /// certifies the blocks after it. The checkpoint itself is not verified;
+    /// it must come from a source the caller trusts.
+    pub fn new(checkpoint: BlockHeader, validator_set: ValidatorSet) -> Self {
+        let mut headers = BTreeMap::new();
+        headers.insert(checkpoint.height, checkpoint);
+        Self {
+            validator_set,
+            headers,
+            retention: DEFAULT_HEADER_RETENTION,
+        }
+    }
+
+    /// Keep at most `retention` recent headers (at least one).
+    pub fn with_retention(mut self, retention: usize) -> Self {
+        self.retention = retention.max(1);
+        self.prune();
+        self
+    }
+
+    /// The most recent verified header.
+    pub fn latest(&self) -> &BlockHeader {
+        self.headers
+            .values()
+            .next_back()
+            .expect("a light client always holds its latest header")
+    }
+
+    /// The verified header at `height`, if still retained.
+    pub fn header(&self, height: u64) -> Option<&BlockHeader> {
+        self.headers.get(&height)
+    }
+
+    /// The validator set headers are currently checked against.
+    pub fn validator_set(&self) -> &ValidatorSet {
+        &self.validator_set
+    }
+
+    /// Verify and append the next header. It must extend the latest header
+    /// and be certified either by the commit certificate `qc`, or, without
+    /// one, by the certificate it carries.
+    pub fn apply_header(
+        &mut self,
+        header: BlockHeader,
+        qc: Option<&QuorumCertificate>,
+    ) -> Result<(), LightClientError> {
+        let latest = self.latest();
+        if header.height != latest.height + 1 {
+            return Err(LightClientError::InvalidHeader {
+                height: header.height,
+                reason: format!("expected height {}", latest.height + 1),
+            });
+        }
+        if header.prev_hash != latest.hash {
+            return Err(LightClientError::InvalidHeader {
+                height: header.height,
+                reason: "does not extend the latest header".to_string(),
+            });
+        }
+        verify::verify_header_hash(&header)?;
+        if !self.validator_set.contains(&header.proposer) {
+            return Err(LightClientError::InvalidHeader {
+                height: header.height,
+                reason: "proposer is not a validator".to_string(),
+            });
+        }
+        match qc {
+            Some(qc) => {
+                if qc.block_hash != header.hash {
+                    return Err(LightClientError::InvalidQuorumCertificate {
+                        reason: "certificate is for a different block".to_string(),
+                    });
+                }
+                verify::verify_quorum_certificate(qc, &self.validator_set)?;
+            }
+            None => verify::verify_header_signatures(&header, &self.validator_set)?,
+        }
+
+        self.headers.insert(header.height, header);
+        self.prune();
+        Ok(())
+    }
+
+    /// Apply a run of consecutive headers, each certified by its own
+    /// certificate. Returns the new latest height.
+    pub fn sync(
+        &mut self,
+        headers: impl IntoIterator<Item = BlockHeader>,
+    ) -> Result<u64, LightClientError> {
+        for header in headers {
+            self.apply_header(header, None)?;
+        }
+        Ok(self.latest().height)
+    }
+
+    /// Hand over to the next validator set. A quorum of the current set must
+    /// have signed its [`validator_set_digest`](verify::validator_set_digest),
+    /// and its epoch must be later than the current one.
+    pub fn rotate_validator_set(
+        &mut self,
+        next: ValidatorSet,
+        endorsements: &[ValidatorSignature],
+    ) -> Result<(), LightClientError> {
+        if next.epoch <= self.validator_set.epoch {
+            return Err(LightClientError::InvalidValidatorSet {
+                reason: format!(
+                    "epoch {} does not follow epoch {}",
+                    next.epoch, self.validator_set.epoch
+                ),
+            });
+        }
+        if next.is_empty() {
+            return Err(LightClientError::InvalidValidatorSet {
+                reason: "validator set is empty".to_string(),
+            });
+        }
+        let digest = verify::validator_set_digest(&next);
+        verify::verify_quorum(&digest, endorsements, &self.validator_set)?;
+        self.validator_set = next;
+        Ok(())
+    }
+
+    /// Move to the validator set the latest header commits to in its
+    /// `validator_set_hash`, e.g. after syncing an epoch boundary block.
+    /// Headers signed by a quorum of the current set vouch for the next one,
+    /// so no separate endorsements are needed.
+    pub fn apply_committed_validator_set(
+        &mut self,
+        next: ValidatorSet,
+    ) -> Result<(), LightClientError> {
+        if next.hash() != self.latest().validator_set_hash {
+            return Err(LightClientError::InvalidValidatorSet {
+                reason: "latest header commits to a different validator set".to_string(),
+            });
+        }
+        if next.epoch <= self.validator_set.epoch {
+            return Err(LightClientError::InvalidValidatorSet {
+                reason: format!(
+                    "epoch {} does not follow epoch {}",
+                    next.epoch, self.validator_set.epoch
+                ),
+            });
+        }
+        if next.is_empty() {
+            return Err(LightClientError::InvalidValidatorSet {
+                reason: "validator set is empty".to_string(),
+            });
+        }
+        self.validator_set = next;
+        Ok(())
+    }
+
+    /// Verify a balance against the state root at `height`.
+    pub fn verify_balance(
+        &self,
+        height: u64,
+        address: &Address,
+        token_id: &TokenId,
+        balance: Amount,
+        proof: &MerkleProof,
+    ) -> Result<(), LightClientError> {
+        let header = self.require_header(height)?;
+        verify::verify_balance(&header.state_root, address, token_id, balance, proof)
+    }
+
+    /// Verify several balances against the state root at `height` with one
+    /// multiproof.
+    pub fn verify_balances(
+        &self,
+        height: u64,
+        balances: &[(Address, TokenId, Amount)],
+        proof: &MerkleMultiProof,
+    ) -> Result<(), LightClientError> {
+        let header = self.require_header(height)?;
+        verify::verify_balances(&header.state_root, balances, proof)
+    }
+
+    /// Verify that `item` is included in the block at `height` under the
+    /// content root `root` picks from its header, e.g.
+    /// `|h| h.commitments_root`.
+    pub fn verify_block_item<T: BorshSerialize>(
+        &self,
+        height: u64,
+        root: impl FnOnce(&BlockHeader) -> Hash,
+        item: &T,
+        proof: &MerkleProof,
+    ) -> Result<(), LightClientError> {
+        let header = self.require_header(height)?;
+        verify::verify_block_item(&root(header), item, proof)
+    }
+
+    /// Verify that a transfer is included in a block this client has synced.
+    /// Only the proof's first header is used, and it must be the retained
+    /// header at its height; the rest of the chain comes from the client's
+    /// own sync. Returns the including block's height.
+    pub fn verify_transfer(&self, proof: &TransferProof) -> Result<u64, LightClientError> {
+        let block = proof
+            .headers
+            .first()
+            .ok_or_else(|| LightClientError::InvalidProof {
+                reason: "transfer proof has no headers".to_string(),
+            })?;
+        let header = self.require_header(block.height)?;
+        if header.hash != block.hash {
+            return Err(LightClientError::InvalidProof {
+                reason: "transfer proof is for a different block".to_string(),
+            });
+        }
+        spv::verify_transfer_inclusion(&proof.transfer, &proof.inclusion, &header.transfers_root)
+            .map_err(|e| LightClientError::InvalidProof {
+            reason: e.to_string(),
+        })?;
+        Ok(header.height)
+    }
+
+    fn require_header(&self, height: u64) -> Result<&BlockHeader, LightClientError> {
+        self.header(height)
+            .ok_or(LightClientError::UnknownHeader { height })
+    }
+
+    fn prune(&mut self) {
+        while self.headers.len() > self.retention {
+            self.headers.pop_first();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use norn_crypto::bls::{self, BlsKeypair};
+    use norn_crypto::hash::blake3_hash;
+    use norn_crypto::keys::Keypair;
+    use norn_crypto::merkle::SparseMerkleTree;
+    use norn_types::consensus::{vote_signing_data, ConsensusPhase, Vote};
+    use norn_types::primitives::NATIVE_TOKEN_ID;
+    use norn_types::weave::{BlockTransfer, CommitmentUpdate, Validator};
+
+    fn validator_set(keypairs: &[Keypair], epoch: u64) -> ValidatorSet {
+        let validators: Vec<Validator> = keypairs
+            .iter()
+            .map(|kp| Validator {
+                pubkey: kp.public_key(),
+                bls_pubkey: BlsKeypair::from_keypair(kp).public_key(),
+                address: [0u8; 20],
+                stake: 1000,
+                active: true,
+            })
+            .collect();
+        ValidatorSet {
+            total_stake: 1000 * validators.len() as u128,
+            validators,
+            epoch,
+        }
+    }
+
+    fn checkpoint() -> BlockHeader {
+        BlockHeader {
+            height: 0,
+            hash: [0xAA; 32],
+            prev_hash: [0u8; 32],
+            commitments_root: [0u8; 32],
+            registrations_root: [0u8; 32],
+            anchors_root: [0u8; 32],
+            name_registrations_root: [0u8; 32],
+            name_transfers_root: [0u8; 32],
+            name_record_updates_root: [0u8; 32],
+            fraud_proofs_root: [0u8; 32],
+            transfers_root: [0u8; 32],
+            token_definitions_root: [0u8; 32],
+            token_mints_root: [0u8; 32],
+            token_burns_root: [0u8; 32],
+            token_metadata_updates_root: [0u8; 32],
+            loom_deploys_root: [0u8; 32],
+            operator_handovers_root: [0u8; 32],
+            key_updates_root: [0u8; 32],
+            stake_operations_root: [0u8; 32],
+            slashing_evidence_root: [0u8; 32],
+            spindle_operations_root: [0u8; 32],
+            base_fee: 0,
+            state_root: [0u8; 32],
+            validator_set_hash: [0u8; 32],
+            timestamp: 0,
+            proposer: [0u8; 32],
+            body_hashes: Vec::new(),
+            proposer_signature: [0u8; 64],
+            certificate: None,
+        }
+    }
+
+    /// The header after `prev`, proposed by the first of `signers` and
+    /// certified by all of them.
+    fn next_header(prev: &BlockHeader, signers: &[Keypair]) -> BlockHeader {
+        let mut header = BlockHeader {
+            height: prev.height + 1,
+            prev_hash: prev.hash,
+            timestamp: prev.timestamp + 3,
+            proposer: signers[0].public_key(),
+            ..prev.clone()
+        };
+        sign(&mut header, signers);
+        header
+    }
+
+    /// Rehash `header`, re-sign it as its proposer, and replace its
+    /// certificate with commit votes from `signers`, which must be a prefix
+    /// of the validator set checking it.
+    fn sign(header: &mut BlockHeader, signers: &[Keypair]) {
+        header.hash = header.compute_hash();
+        header.proposer_signature = signers[0].sign(&header.hash);
+        header.certificate = Some(commit_qc(signers, 0, header.hash));
+    }
+
+    /// A commit certificate for `block_hash` from `signers`, indexed as a
+    /// prefix of the validator set.
+    fn commit_qc(signers: &[Keypair], view: u64, block_hash: Hash) -> QuorumCertificate {
+        let signing_data = vote_signing_data(ConsensusPhase::Commit, view, &block_hash);
+        let votes: Vec<Vote> = signers
+            .iter()
+            .map(|kp| Vote {
+                view,
+                phase: ConsensusPhase::Commit,
+                block_hash,
+                voter: kp.public_key(),
+                signature: kp.sign(&signing_data),
+                bls_signature: BlsKeypair::from_keypair(kp).sign(&signing_data),
+            })
+            .collect();
+        bls::aggregate_votes(ConsensusPhase::Commit, &votes, &validator_set(signers, 0)).unwrap()
+    }
+
+    #[test]
+    fn test_sync_follows_signed_headers() {
+        let keypairs: Vec<Keypair> = (0..4).map(|_| Keypair::generate()).collect();
+        let mut client = LightClient::new(checkpoint(), validator_set(&keypairs, 0));
+
+        let mut headers = Vec::new();
+        let mut prev = checkpoint();
+        for _ in 0..5 {
+            prev = next_header(&prev, &keypairs[..3]);
+            headers.push(prev.clone());
+        }
+        assert_eq!(client.sync(headers).unwrap(), 5);
+        assert_eq!(client.latest().hash, prev.hash);
+
+        // Too few signatures.
+        let weak = next_header(&prev, &keypairs[..2]);
+        assert!(matches!(
+            client.apply_header(weak, None),
+            Err(LightClientError::InsufficientQuorum { have: 2, need: 3 })
+        ));
+
+        // Contents that do not match the signed hash.
+        let mut forged = next_header(&prev, &keypairs[..3]);
+        forged.state_root = [9u8; 32];
+        assert!(client.apply_header(forged, None).is_err());
+
+        // A header that skips a height.
+        let skip = next_header(&next_header(&prev, &keypairs), &keypairs);
+        assert!(client.apply_header(skip, None).is_err());
+    }
+
+    #[test]
+    fn test_apply_header_with_commit_certificate() {
+        let keypairs: Vec<Keypair> = (0..4).map(|_| Keypair::generate()).collect();
+        let mut client = LightClient::new(checkpoint(), validator_set(&keypairs, 0));
+        // The header arrives without a certificate of its own.
+        let mut header = next_header(&checkpoint(), &keypairs[..3]);
+        header.certificate = None;
+        assert!(client.apply_header(header.clone(), None).is_err());
+
+        let qc = commit_qc(&keypairs[..3], 7, header.hash);
+        let mut other = qc.clone();
+        other.block_hash = [1u8; 32];
+        assert!(client.apply_header(header.clone(), Some(&other)).is_err());
+        client.apply_header(header, Some(&qc)).unwrap();
+        assert_eq!(client.latest().height, 1);
+    }
+
+    #[test]
+    fn test_rotate_validator_set() {
+        let current: Vec<Keypair> = (0..4).map(|_| Keypair::generate()).collect();
+        let next: Vec<Keypair> = (0..4).map(|_| Keypair::generate()).collect();
+        let mut client = LightClient::new(checkpoint(), validator_set(&current, 0));
+
+        let next_set = validator_set(&next, 1);
+        let digest = verify::validator_set_digest(&next_set);
+        let endorse = |signers: &[Keypair]| -> Vec<ValidatorSignature> {
+            signers
+                .iter()
+                .map(|kp| ValidatorSignature {
+                    validator: kp.public_key(),
+                    signature: kp.sign(&digest),
+                })
+                .collect()
+        };
+        assert!(client
+            .rotate_validator_set(next_set.clone(), &endorse(&current[..2]))
+            .is_err());
+        assert!(client
+            .rotate_validator_set(next_set.clone(), &endorse(&next))
+            .is_err());
+        client
+            .rotate_validator_set(next_set.clone(), &endorse(&current[..3]))
+            .unwrap();
+
+        // Headers signed by the old set are no longer accepted.
+        let old = next_header(&checkpoint(), &current);
+        assert!(client.apply_header(old, None).is_err());
+        client
+            .apply_header(next_header(&checkpoint(), &next[..3]), None)
+            .unwrap();
+
+        // Replaying an endorsement for an earlier epoch is rejected.
+        let stale = validator_set(&current, 1);
+        let digest = verify::validator_set_digest(&stale);
+        let endorsements: Vec<ValidatorSignature> = next
+            .iter()
+            .map(|kp| ValidatorSignature {
+                validator: kp.public_key(),
+                signature: kp.sign(&digest),
+            })
+            .collect();
+        assert!(client.rotate_validator_set(stale, &endorsements).is_err());
+    }
+
+    #[test]
+    fn test_apply_committed_validator_set() {
+        let current: Vec<Keypair> = (0..4).map(|_| Keypair::generate()).collect();
+        let next: Vec<Keypair> = (0..4).map(|_| Keypair::generate()).collect();
+        let mut client = LightClient::new(checkpoint(), validator_set(&current, 0));
+        let next_set = validator_set(&next, 1);
+
+        // A boundary header committing to the next set.
+        let mut boundary = next_header(&checkpoint(), &current[..3]);
+        boundary.validator_set_hash = next_set.hash();
+        sign(&mut boundary, &current[..3]);
+        client.apply_header(boundary.clone(), None).unwrap();
+
+        assert!(client
+            .apply_committed_validator_set(validator_set(&current, 1))
+            .is_err());
+        client.apply_committed_validator_set(next_set).unwrap();
+        client
+            .apply_header(next_header(&boundary, &next[..3]), None)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_proofs_against_synced_headers() {
+        let keypairs: Vec<Keypair> = (0..1).map(|_| Keypair::generate()).collect();
+        let mut client =
+            LightClient::new(checkpoint(), validator_set(&keypairs, 0)).with_retention(2);
+        let address = [1u8; 20];
+
+        let mut state = SparseMerkleTree::new();
+        state.insert(
+            verify::balance_key(&address, &NATIVE_TOKEN_ID),
+            42u128.to_le_bytes().to_vec(),
+        );
+        let commitment = CommitmentUpdate {
+            thread_id: address,
+            owner: [2u8; 32],
+            version: 1,
+            state_hash: [3u8; 32],
+            prev_commitment_hash: [0u8; 32],
+            knot_count: 1,
+            timestamp: 10,
+            priority_fee: 0,
+            signature: [0u8; 64],
+        };
+        let bytes = borsh::to_vec(&commitment).unwrap();
+        let mut commitments = SparseMerkleTree::new();
+        commitments.insert(blake3_hash(&bytes), bytes);
+
+        let mut header = next_header(&checkpoint(), &keypairs);
+        header.state_root = state.root();
+        header.commitments_root = commitments.root();
+        sign(&mut header, &keypairs);
+        client.apply_header(header, None).unwrap();
+
+        let balance_proof = state.prove(&verify::balance_key(&address, &NATIVE_TOKEN_ID));
+        client
+            .verify_balance(1, &address, &NATIVE_TOKEN_ID, 42, &balance_proof)
+            .unwrap();
+        assert!(client
+            .verify_balance(1, &address, &NATIVE_TOKEN_ID, 43, &balance_proof)
+            .is_err());
+
+        let item_proof = commitments.prove(&blake3_hash(&borsh::to_vec(&commitment).unwrap()));
+        client
+            .verify_block_item(1, |h| h.commitments_root, &commitment, &item_proof)
+            .unwrap();
+        assert!(client
+            .verify_block_item(1, |h| h.transfers_root, &commitment, &item_proof)
+            .is_err());
+
+        // Older headers fall out of retention.
+        for _ in 0..2 {
+            let next = next_header(client.latest(), &keypairs);
+            client.apply_header(next, None).unwrap();
+        }
+        assert!(matches!(
+            client.verify_balance(1, &address, &NATIVE_TOKEN_ID, 42, &balance_proof),
+            Err(LightClientError::UnknownHeader { height: 1 })
+        ));
+    }
+
+    #[test]
+    fn test_verify_transfer_against_synced_header() {
+        let keypairs: Vec<Keypair> = (0..1).map(|_| Keypair::generate()).collect();
+        let mut client = LightClient::new(checkpoint(), validator_set(&keypairs, 0));
+        let transfer = BlockTransfer {
+            from: [1u8; 20],
+            to: [2u8; 20],
+            token_id: NATIVE_TOKEN_ID,
+            amount: 250,
+            memo: None,
+            knot_id: [4u8; 32],
+            timestamp: 10,
+        };
+        let bytes = borsh::to_vec(&transfer).unwrap();
+        let mut transfers = SparseMerkleTree::new();
+        transfers.insert(blake3_hash(&bytes), bytes.clone());
+
+        let mut header = next_header(&checkpoint(), &keypairs);
+        header.transfers_root = transfers.root();
+        sign(&mut header, &keypairs);
+        client.apply_header(header.clone(), None).unwrap();
+
+        let proof = TransferProof {
+            transfer: transfer.clone(),
+            inclusion: transfers.prove(&blake3_hash(&bytes)),
+            headers: vec![header.clone()],
+        };
+        assert_eq!(client.verify_transfer(&proof).unwrap(), 1);
+
+        let mut inflated = proof.clone();
+        inflated.transfer.amount = 2_500;
+        assert!(client.verify_transfer(&inflated).is_err());
+
+        // A header the client never synced.
+        let mut unsynced = proof;
+        unsynced.headers[0].state_root = [5u8; 32];
+        sign(&mut unsynced.headers[0], &keypairs);
+        assert!(client.verify_transfer(&unsynced).is_err());
+    }
+}
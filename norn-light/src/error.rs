@@ -0,0 +1,23 @@
+use thiserror::Error;
+
+/// Errors from header sync and proof verification.
+#[derive(Debug, Error)]
+pub enum LightClientError {
+    #[error("invalid header at height {height}: {reason}")]
+    InvalidHeader { height: u64, reason: String },
+
+    #[error("signed by {have} validators, need {need}")]
+    InsufficientQuorum { have: usize, need: usize },
+
+    #[error("invalid quorum certificate: {reason}")]
+    InvalidQuorumCertificate { reason: String },
+
+    #[error("invalid validator set: {reason}")]
+    InvalidValidatorSet { reason: String },
+
+    #[error("no header at height {height}")]
+    UnknownHeader { height: u64 },
+
+    #[error("invalid proof: {reason}")]
+    InvalidProof { reason: String },
+}
@@ -0,0 +1,12 @@
+//! Light client for the Norn Protocol.
+//!
+//! Follows the weave from a trusted checkpoint by checking block headers
+//! against the current validator set: each header's hash, its link to the
+//! previous header, and either a quorum of validator signatures or a HotStuff
+//! commit certificate. Balances and block contents are then checked with
+//! Merkle proofs against the synced headers, so wallets and bridges need not
+//! trust the node serving them.
+
+pub mod client;
+pub mod error;
+pub mod verify;
@@ -0,0 +1,328 @@
+use std::collections::HashSet;
+
+use borsh::BorshSerialize;
+use norn_crypto::bls;
+use norn_crypto::hash::blake3_hash;
+use norn_crypto::keys::verify;
+use norn_crypto::merkle::{MerkleMultiProof, MerkleProof, SparseMerkleTree};
+use norn_types::consensus::{ConsensusPhase, QuorumCertificate};
+use norn_types::error::NornError;
+use norn_types::primitives::{Address, Amount, Hash, TokenId};
+use norn_types::weave::{BlockHeader, ValidatorSet, ValidatorSignature};
+
+use crate::error::LightClientError;
+
+/// Check that a header's hash matches its contents.
+pub fn verify_header_hash(header: &BlockHeader) -> Result<(), LightClientError> {
+    if header.compute_hash() != header.hash {
+        return Err(LightClientError::InvalidHeader {
+            height: header.height,
+            reason: "hash does not match header contents".to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Check that the header carries its proposer's signature and a commit
+/// certificate from a quorum of `validator_set`.
+pub fn verify_header_signatures(
+    header: &BlockHeader,
+    validator_set: &ValidatorSet,
+) -> Result<(), LightClientError> {
+    if verify(&header.hash, &header.proposer_signature, &header.proposer).is_err() {
+        return Err(LightClientError::InvalidHeader {
+            height: header.height,
+            reason: "invalid proposer signature".to_string(),
+        });
+    }
+    let qc =
+        header
+            .certificate
+            .as_ref()
+            .ok_or_else(|| LightClientError::InvalidQuorumCertificate {
+                reason: "header carries no certificate".to_string(),
+            })?;
+    bls::verify_commit_certificate(qc, &header.hash, validator_set).map_err(quorum_error)
+}
+
+/// Check that distinct members of `validator_set` forming a quorum signed
+/// `message`. Signatures from outside the set, invalid signatures, and
+/// repeats are ignored.
+pub fn verify_quorum(
+    message: &[u8],
+    signatures: &[ValidatorSignature],
+    validator_set: &ValidatorSet,
+) -> Result<(), LightClientError> {
+    let signers: HashSet<_> = signatures
+        .iter()
+        .filter(|sig| {
+            validator_set.contains(&sig.validator)
+                && verify(message, &sig.signature, &sig.validator).is_ok()
+        })
+        .map(|sig| sig.validator)
+        .collect();
+    let need = validator_set.quorum_size();
+    if signers.len() < need {
+        return Err(LightClientError::InsufficientQuorum {
+            have: signers.len(),
+            need,
+        });
+    }
+    Ok(())
+}
+
+/// Check that a quorum certificate carries a valid aggregate commit
+/// signature from a quorum of `validator_set`.
+pub fn verify_quorum_certificate(
+    qc: &QuorumCertificate,
+    validator_set: &ValidatorSet,
+) -> Result<(), LightClientError> {
+    if qc.phase != ConsensusPhase::Commit {
+        return Err(LightClientError::InvalidQuorumCertificate {
+            reason: format!("expected a commit certificate, got {:?}", qc.phase),
+        });
+    }
+    bls::verify_quorum_certificate(qc, validator_set).map_err(quorum_error)
+}
+
+/// The digest the current validators sign to hand over to `validator_set`,
+/// the same [`ValidatorSet::hash`] block headers commit to.
+pub fn validator_set_digest(validator_set: &ValidatorSet) -> Hash {
+    validator_set.hash()
+}
+
+/// The state tree key of a balance: BLAKE3(address ++ token_id).
+pub fn balance_key(address: &Address, token_id: &TokenId) -> Hash {
+    let mut data = Vec::with_capacity(20 + 32);
+    data.extend_from_slice(address);
+    data.extend_from_slice(token_id);
+    blake3_hash(&data)
+}
+
+/// The state tree value of a balance: the amount in little-endian, or empty
+/// for a zero balance that was never set.
+fn balance_matches(value: &[u8], balance: Amount) -> bool {
+    if value.is_empty() {
+        return balance == 0;
+    }
+    value == balance.to_le_bytes()
+}
+
+/// Check a balance against a state root.
+pub fn verify_balance(
+    state_root: &Hash,
+    address: &Address,
+    token_id: &TokenId,
+    balance: Amount,
+    proof: &MerkleProof,
+) -> Result<(), LightClientError> {
+    if proof.key != balance_key(address, token_id) {
+        return Err(invalid_proof("proof is for a different balance"));
+    }
+    if !balance_matches(&proof.value, balance) {
+        return Err(invalid_proof("proved balance differs"));
+    }
+    SparseMerkleTree::verify_proof(state_root, proof).map_err(|e| invalid_proof(e.to_string()))
+}
+
+/// Check several balances against a state root with one multiproof. Every
+/// balance must be covered by the proof.
+pub fn verify_balances(
+    state_root: &Hash,
+    balances: &[(Address, TokenId, Amount)],
+    proof: &MerkleMultiProof,
+) -> Result<(), LightClientError> {
+    for (address, token_id, balance) in balances {
+        let key = balance_key(address, token_id);
+        let (_, value) = proof
+            .leaves
+            .iter()
+            .find(|(k, _)| *k == key)
+            .ok_or_else(|| invalid_proof("proof does not cover a requested balance"))?;
+        if !balance_matches(value, *balance) {
+            return Err(invalid_proof("proved balance differs"));
+        }
+    }
+    SparseMerkleTree::verify_multiproof(state_root, proof).map_err(|e| invalid_proof(e.to_string()))
+}
+
+/// Check that `item` is one of the entries under a block content root, such
+/// as a header's `commitments_root` or `transfers_root`.
+pub fn verify_block_item<T: BorshSerialize>(
+    root: &Hash,
+    item: &T,
+    proof: &MerkleProof,
+) -> Result<(), LightClientError> {
+    let bytes = borsh::to_vec(item).map_err(|e| invalid_proof(e.to_string()))?;
+    if proof.key != blake3_hash(&bytes) || proof.value != bytes {
+        return Err(invalid_proof("proof is for a different item"));
+    }
+    SparseMerkleTree::verify_proof(root, proof).map_err(|e| invalid_proof(e.to_string()))
+}
+
+fn quorum_error(e: NornError) -> LightClientError {
+    match e {
+        NornError::InsufficientQuorum { have, need } => {
+            LightClientError::InsufficientQuorum { have, need }
+        }
+        NornError::InvalidQuorumCertificate { reason } => {
+            LightClientError::InvalidQuorumCertificate { reason }
+        }
+        other => LightClientError::InvalidQuorumCertificate {
+            reason: other.to_string(),
+        },
+    }
+}
+
+fn invalid_proof(reason: impl Into<String>) -> LightClientError {
+    LightClientError::InvalidProof {
+        reason: reason.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use norn_crypto::bls::BlsKeypair;
+    use norn_crypto::keys::Keypair;
+    use norn_types::consensus::{vote_signing_data, Vote};
+    use norn_types::primitives::NATIVE_TOKEN_ID;
+    use norn_types::weave::Validator;
+
+    fn validator_set(keypairs: &[Keypair]) -> ValidatorSet {
+        let validators: Vec<Validator> = keypairs
+            .iter()
+            .map(|kp| Validator {
+                pubkey: kp.public_key(),
+                bls_pubkey: BlsKeypair::from_keypair(kp).public_key(),
+                address: [0u8; 20],
+                stake: 1000,
+                active: true,
+            })
+            .collect();
+        ValidatorSet {
+            total_stake: 1000 * validators.len() as u128,
+            validators,
+            epoch: 0,
+        }
+    }
+
+    fn sign_votes(
+        keypairs: &[Keypair],
+        phase: ConsensusPhase,
+        view: u64,
+        block_hash: Hash,
+    ) -> Vec<Vote> {
+        let signing_data = vote_signing_data(phase, view, &block_hash);
+        keypairs
+            .iter()
+            .map(|kp| Vote {
+                view,
+                phase,
+                block_hash,
+                voter: kp.public_key(),
+                signature: kp.sign(&signing_data),
+                bls_signature: BlsKeypair::from_keypair(kp).sign(&signing_data),
+            })
+            .collect()
+    }
+
+    fn commit_qc(
+        keypairs: &[Keypair],
+        vs: &ValidatorSet,
+        view: u64,
+        block_hash: Hash,
+    ) -> QuorumCertificate {
+        let votes = sign_votes(keypairs, ConsensusPhase::Commit, view, block_hash);
+        bls::aggregate_votes(ConsensusPhase::Commit, &votes, vs).unwrap()
+    }
+
+    #[test]
+    fn test_quorum_certificate() {
+        let keypairs: Vec<Keypair> = (0..4).map(|_| Keypair::generate()).collect();
+        let vs = validator_set(&keypairs);
+
+        verify_quorum_certificate(&commit_qc(&keypairs[..3], &vs, 5, [1u8; 32]), &vs).unwrap();
+        assert!(matches!(
+            verify_quorum_certificate(&commit_qc(&keypairs[..2], &vs, 5, [1u8; 32]), &vs),
+            Err(LightClientError::InsufficientQuorum { have: 2, need: 3 })
+        ));
+
+        // Repeated votes do not count twice.
+        let mut votes = sign_votes(&keypairs[..2], ConsensusPhase::Commit, 5, [1u8; 32]);
+        votes.push(votes[0].clone());
+        let repeated = bls::aggregate_votes(ConsensusPhase::Commit, &votes, &vs).unwrap();
+        assert!(verify_quorum_certificate(&repeated, &vs).is_err());
+
+        let mut prepare = commit_qc(&keypairs[..3], &vs, 5, [1u8; 32]);
+        prepare.phase = ConsensusPhase::Prepare;
+        assert!(verify_quorum_certificate(&prepare, &vs).is_err());
+
+        // A genuine prepare certificate relabelled as a commit certificate
+        // passes the label check but not the signature check.
+        let prepare_votes = sign_votes(&keypairs[..3], ConsensusPhase::Prepare, 5, [1u8; 32]);
+        let mut relabelled =
+            bls::aggregate_votes(ConsensusPhase::Prepare, &prepare_votes, &vs).unwrap();
+        assert!(bls::verify_quorum_certificate(&relabelled, &vs).is_ok());
+        relabelled.phase = ConsensusPhase::Commit;
+        assert!(matches!(
+            verify_quorum_certificate(&relabelled, &vs),
+            Err(LightClientError::InvalidQuorumCertificate { reason })
+                if reason.contains("aggregate signature")
+        ));
+
+        let mut moved = commit_qc(&keypairs[..3], &vs, 5, [1u8; 32]);
+        moved.block_hash = [2u8; 32];
+        assert!(verify_quorum_certificate(&moved, &vs).is_err());
+
+        // Claiming a signer whose signature is not in the aggregate.
+        let mut padded = commit_qc(&keypairs[..3], &vs, 5, [1u8; 32]);
+        padded.signers[0] |= 1 << 3;
+        assert!(verify_quorum_certificate(&padded, &vs).is_err());
+    }
+
+    #[test]
+    fn test_balance_proofs() {
+        let (alice, bob) = ([1u8; 20], [2u8; 20]);
+        let mut tree = SparseMerkleTree::new();
+        tree.insert(
+            balance_key(&alice, &NATIVE_TOKEN_ID),
+            500u128.to_le_bytes().to_vec(),
+        );
+        let root = tree.root();
+
+        let proof = tree.prove(&balance_key(&alice, &NATIVE_TOKEN_ID));
+        verify_balance(&root, &alice, &NATIVE_TOKEN_ID, 500, &proof).unwrap();
+        assert!(verify_balance(&root, &alice, &NATIVE_TOKEN_ID, 501, &proof).is_err());
+        assert!(verify_balance(&root, &bob, &NATIVE_TOKEN_ID, 500, &proof).is_err());
+
+        // An unset balance proves as zero.
+        let absent = tree.prove(&balance_key(&bob, &NATIVE_TOKEN_ID));
+        verify_balance(&root, &bob, &NATIVE_TOKEN_ID, 0, &absent).unwrap();
+
+        let multi = tree.prove_many(&[
+            balance_key(&alice, &NATIVE_TOKEN_ID),
+            balance_key(&bob, &NATIVE_TOKEN_ID),
+        ]);
+        let balances = [(alice, NATIVE_TOKEN_ID, 500), (bob, NATIVE_TOKEN_ID, 0)];
+        verify_balances(&root, &balances, &multi).unwrap();
+        assert!(verify_balances(&root, &[(alice, [9u8; 32], 0)], &multi).is_err());
+        assert!(verify_balances(&[0u8; 32], &balances, &multi).is_err());
+    }
+
+    #[test]
+    fn test_block_item_proof() {
+        let items = [b"first".to_vec(), b"second".to_vec()];
+        let mut tree = SparseMerkleTree::new();
+        for item in &items {
+            let bytes = borsh::to_vec(item).unwrap();
+            tree.insert(blake3_hash(&bytes), bytes);
+        }
+        let root = tree.root();
+
+        let key = blake3_hash(&borsh::to_vec(&items[1]).unwrap());
+        let proof = tree.prove(&key);
+        verify_block_item(&root, &items[1], &proof).unwrap();
+        assert!(verify_block_item(&root, &items[0], &proof).is_err());
+    }
+}
@@ -1,13 +1,18 @@
 use std::collections::HashMap;
 
+use borsh::{BorshDeserialize, BorshSerialize};
+use norn_crypto::hash::{blake3_hash, blake3_hash_multi};
+use norn_crypto::merkle::{
+    build_merkle_proof, build_merkle_root, hash_list_leaf, verify_merkle_proof,
+};
 use norn_types::loom::{LoomBytecode, LoomStateTransition};
-use norn_types::primitives::Address;
+use norn_types::primitives::{Address, Amount, Hash, LoomId};
 
 use crate::error::LoomError;
 use crate::gas::DEFAULT_GAS_LIMIT;
-use crate::host::LoomHostState;
+use crate::host::{LoomHostState, StepRecorder};
 use crate::runtime::LoomRuntime;
-use crate::state::LoomState;
+use crate::state::{LoomState, LoomStateSnapshot};
 
 /// The result of challenging a loom state transition.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -46,38 +51,652 @@ pub fn challenge_transition(
         });
     }
 
+    let post_hash = reexecute(
+        transition.loom_id,
+        &bytecode.bytecode,
+        initial_state,
+        &transition.inputs,
+        sender,
+        block_height,
+        timestamp,
+    )?;
+
+    // Compare against the claimed new state hash.
+    if post_hash != transition.new_state_hash {
+        Ok(DisputeResult::Invalid {
+            reason: format!(
+                "post-execution state hash mismatch: computed {:?} but transition claims {:?}",
+                post_hash, transition.new_state_hash
+            ),
+        })
+    } else {
+        Ok(DisputeResult::Valid)
+    }
+}
+
+/// Execute `input` against `initial_state` and return the resulting state hash.
+fn reexecute(
+    loom_id: LoomId,
+    bytecode: &[u8],
+    initial_state: &HashMap<Vec<u8>, Vec<u8>>,
+    input: &[u8],
+    sender: Address,
+    block_height: u64,
+    timestamp: u64,
+) -> Result<Hash, LoomError> {
     // Set up the host state with the initial data.
     let mut host_state = LoomHostState::new(sender, block_height, timestamp, DEFAULT_GAS_LIMIT);
     host_state.state = initial_state.clone();
 
     // Instantiate and execute.
     let runtime = LoomRuntime::new()?;
-    let mut instance = runtime.instantiate(&bytecode.bytecode, host_state)?;
-    let _outputs = instance.call_execute(&transition.inputs)?;
+    let mut instance = runtime.instantiate(bytecode, host_state)?;
+    let _outputs = instance.call_execute(input)?;
 
     // Extract the post-execution state and compute its hash.
     let host_state = instance.into_host_state();
-    let mut post_state = LoomState::new(transition.loom_id);
+    let mut post_state = LoomState::new(loom_id);
     post_state.data = host_state.state;
-    let post_hash = post_state.compute_hash();
+    Ok(post_state.compute_hash())
+}
 
-    // Compare against the claimed new state hash.
-    if post_hash != transition.new_state_hash {
-        Ok(DisputeResult::Invalid {
+/// Canonical evidence for a disputed loom execution.
+///
+/// Carries everything a validator needs to re-execute the transition on its
+/// own: the full prior state (whose hash is the prior state root), the code
+/// that ran, the input and block context, and the output root the defendant
+/// claimed. The borsh encoding is the canonical form; its hash is the
+/// dispute ID.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct EvidenceBundle {
+    /// The loom whose transition is disputed.
+    pub loom_id: LoomId,
+    /// Code ID (blake3 of the bytecode) the loom ran.
+    pub code_id: Hash,
+    /// State before the transition. Its `state_hash` is the prior state root.
+    pub prior_state: LoomStateSnapshot,
+    /// Input passed to `execute`.
+    pub input: Vec<u8>,
+    /// Sender of the disputed execution.
+    pub sender: Address,
+    /// Block height the execution ran at.
+    pub block_height: u64,
+    /// Timestamp the execution ran at.
+    pub timestamp: u64,
+    /// State root the defendant claimed the execution produced.
+    pub claimed_output_root: Hash,
+}
+
+impl EvidenceBundle {
+    /// The dispute ID: blake3 of the canonical encoding.
+    pub fn id(&self) -> Hash {
+        blake3_hash(&borsh::to_vec(self).unwrap_or_default())
+    }
+
+    /// The state root the disputed transition started from.
+    pub fn prior_state_root(&self) -> Hash {
+        self.prior_state.state_hash
+    }
+
+    /// Check the bundle is internally consistent and matches `bytecode`.
+    pub fn validate(&self, bytecode: &[u8]) -> Result<(), LoomError> {
+        if self.prior_state.loom_id != self.loom_id {
+            return Err(LoomError::InvalidEvidence {
+                reason: "prior state belongs to a different loom".to_string(),
+            });
+        }
+        if blake3_hash(bytecode) != self.code_id {
+            return Err(LoomError::InvalidEvidence {
+                reason: "bytecode does not match the bundle's code ID".to_string(),
+            });
+        }
+        self.prior_state
+            .verify()
+            .map_err(|e| LoomError::InvalidEvidence {
+                reason: e.to_string(),
+            })
+    }
+}
+
+/// Re-execute an evidence bundle and compare against the claimed output root.
+///
+/// Returns the verdict along with the output root the re-execution produced.
+/// An execution that fails (traps or runs out of gas) leaves the state
+/// unchanged, so its output root is the prior state root.
+pub fn verify_evidence(
+    bundle: &EvidenceBundle,
+    bytecode: &[u8],
+) -> Result<(DisputeResult, Hash), LoomError> {
+    bundle.validate(bytecode)?;
+
+    let initial_state: HashMap<Vec<u8>, Vec<u8>> =
+        bundle.prior_state.entries.iter().cloned().collect();
+    let computed = reexecute(
+        bundle.loom_id,
+        bytecode,
+        &initial_state,
+        &bundle.input,
+        bundle.sender,
+        bundle.block_height,
+        bundle.timestamp,
+    )
+    .unwrap_or_else(|_| bundle.prior_state_root());
+
+    let result = if computed == bundle.claimed_output_root {
+        DisputeResult::Valid
+    } else {
+        DisputeResult::Invalid {
             reason: format!(
-                "post-execution state hash mismatch: computed {:?} but transition claims {:?}",
-                post_hash, transition.new_state_hash
+                "claimed output root {} but re-execution produced {}",
+                hex::encode(bundle.claimed_output_root),
+                hex::encode(computed)
             ),
+        }
+    };
+    Ok((result, computed))
+}
+
+/// Where a dispute stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisputeStatus {
+    /// Opened but not yet re-executed.
+    Pending,
+    /// Re-execution disagreed with the claim: the defendant loses its bond.
+    Upheld,
+    /// Re-execution matched the claim: the challenger loses its bond.
+    Rejected,
+}
+
+impl DisputeStatus {
+    /// Stable name used in RPC responses.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DisputeStatus::Pending => "pending",
+            DisputeStatus::Upheld => "upheld",
+            DisputeStatus::Rejected => "rejected",
+        }
+    }
+}
+
+/// A dispute between a challenger and the loom operator over one execution.
+#[derive(Debug, Clone)]
+pub struct Dispute {
+    /// Dispute ID (the evidence bundle's hash).
+    pub id: Hash,
+    /// The evidence under dispute.
+    pub evidence: EvidenceBundle,
+    /// Address that opened the dispute.
+    pub challenger: Address,
+    /// Address defending the claim (the loom operator).
+    pub defendant: Address,
+    /// Bond each party has at stake.
+    pub bond: Amount,
+    /// Current status.
+    pub status: DisputeStatus,
+    /// Output root produced by re-execution, once resolved.
+    pub computed_output_root: Option<Hash>,
+    /// Why the dispute was resolved the way it was.
+    pub reason: Option<String>,
+    /// When the dispute was opened.
+    pub opened_at: u64,
+    /// When the dispute was resolved.
+    pub resolved_at: Option<u64>,
+}
+
+impl Dispute {
+    fn settle(&mut self, result: DisputeResult, now: u64) {
+        let (status, reason) = match result {
+            DisputeResult::Invalid { reason } => (DisputeStatus::Upheld, reason),
+            DisputeResult::Valid => (
+                DisputeStatus::Rejected,
+                "re-execution matches the defendant's claim".to_string(),
+            ),
+        };
+        self.status = status;
+        self.reason = Some(reason);
+        self.resolved_at = Some(now);
+    }
+
+    /// The bond payment owed once resolved: `(from, to, amount)`.
+    ///
+    /// The losing party's bond goes to the winner. Pending disputes owe nothing.
+    pub fn settlement(&self) -> Option<(Address, Address, Amount)> {
+        match self.status {
+            DisputeStatus::Pending => None,
+            DisputeStatus::Upheld => Some((self.defendant, self.challenger, self.bond)),
+            DisputeStatus::Rejected => Some((self.challenger, self.defendant, self.bond)),
+        }
+    }
+}
+
+/// Tracks disputes from submission to resolution.
+#[derive(Debug, Default)]
+pub struct DisputeRegistry {
+    disputes: HashMap<Hash, Dispute>,
+    /// Dispute IDs in the order they were opened.
+    order: Vec<Hash>,
+}
+
+impl DisputeRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open a dispute over `evidence`. Returns the dispute ID.
+    pub fn open(
+        &mut self,
+        evidence: EvidenceBundle,
+        challenger: Address,
+        defendant: Address,
+        bond: Amount,
+        now: u64,
+    ) -> Result<Hash, LoomError> {
+        let id = evidence.id();
+        if self.disputes.contains_key(&id) {
+            return Err(LoomError::InvalidEvidence {
+                reason: format!("dispute {} already exists", hex::encode(id)),
+            });
+        }
+        self.disputes.insert(
+            id,
+            Dispute {
+                id,
+                evidence,
+                challenger,
+                defendant,
+                bond,
+                status: DisputeStatus::Pending,
+                computed_output_root: None,
+                reason: None,
+                opened_at: now,
+                resolved_at: None,
+            },
+        );
+        self.order.push(id);
+        Ok(id)
+    }
+
+    /// Resolve a pending dispute by re-executing its evidence against `bytecode`.
+    ///
+    /// Resolving an already-resolved dispute returns it unchanged.
+    pub fn resolve(&mut self, id: &Hash, bytecode: &[u8], now: u64) -> Result<&Dispute, LoomError> {
+        let dispute = self
+            .disputes
+            .get_mut(id)
+            .ok_or(LoomError::DisputeNotFound { dispute_id: *id })?;
+        if dispute.status == DisputeStatus::Pending {
+            let (result, computed) = verify_evidence(&dispute.evidence, bytecode)?;
+            dispute.settle(result, now);
+            dispute.computed_output_root = Some(computed);
+        }
+        Ok(dispute)
+    }
+
+    /// Resolve a pending dispute with a finished bisection game, re-executing
+    /// only up to the step the game narrowed down to.
+    pub fn resolve_bisection(
+        &mut self,
+        id: &Hash,
+        game: &BisectionGame,
+        bytecode: &[u8],
+        now: u64,
+    ) -> Result<&Dispute, LoomError> {
+        let dispute = self
+            .disputes
+            .get_mut(id)
+            .ok_or(LoomError::DisputeNotFound { dispute_id: *id })?;
+        if game.evidence.id() != *id {
+            return Err(LoomError::InvalidEvidence {
+                reason: "bisection game is for a different dispute".to_string(),
+            });
+        }
+        if dispute.status == DisputeStatus::Pending {
+            let result = game.resolve(bytecode)?;
+            dispute.settle(result, now);
+        }
+        Ok(dispute)
+    }
+
+    /// Get a dispute by ID.
+    pub fn get(&self, id: &Hash) -> Option<&Dispute> {
+        self.disputes.get(id)
+    }
+
+    /// List disputes in the order they were opened, optionally for one loom.
+    pub fn list(&self, loom_id: Option<&LoomId>) -> Vec<&Dispute> {
+        self.order
+            .iter()
+            .filter_map(|id| self.disputes.get(id))
+            .filter(|d| loom_id.is_none_or(|l| d.evidence.loom_id == *l))
+            .collect()
+    }
+}
+
+// ── Interactive bisection ───────────────────────────────────────────────
+//
+// Re-executing a whole disputed execution is too costly for gas-heavy looms,
+// so the parties can instead bisect. Each side commits to the chain of step
+// roots its execution produced (one step per state write, plus a final step
+// binding the output root) and the game repeatedly halves the range between
+// the last step both agree on and the first they dispute. Once the range is
+// a single step, the verifier re-executes only up to that step and checks
+// which side's root is correct.
+
+/// A party's commitment to its step trace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct TraceCommitment {
+    /// Number of steps, including the final output step.
+    pub steps: u64,
+    /// Merkle root over the step roots.
+    pub trace_root: Hash,
+}
+
+/// A step root revealed with its inclusion proof.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct StepOpening {
+    /// Step index (1-based; step 0 is the prior state root).
+    pub step: u64,
+    /// Root after the step.
+    pub root: Hash,
+    /// Merkle proof of the step against the trace root.
+    pub proof: Vec<Hash>,
+}
+
+impl StepOpening {
+    fn verify(&self, commitment: &TraceCommitment) -> bool {
+        self.step >= 1
+            && self.step <= commitment.steps
+            && verify_merkle_proof(
+                &commitment.trace_root,
+                &step_leaf(self.step, &self.root),
+                &self.proof,
+            )
+    }
+}
+
+/// Opening of a trace's final output step.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct OutputOpening {
+    /// The last write step, or `None` if the trace has no writes.
+    pub last_write: Option<StepOpening>,
+    /// The final output step.
+    pub output: StepOpening,
+    /// The output root the final step binds.
+    pub output_root: Hash,
+}
+
+/// The step roots of an execution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StepTrace {
+    /// Root the trace starts from (the prior state root).
+    pub prior_root: Hash,
+    /// Root after each step. When complete, the last entry is the output step.
+    pub roots: Vec<Hash>,
+    /// Output root, or `None` if recording stopped at a step limit.
+    pub output_root: Option<Hash>,
+}
+
+impl StepTrace {
+    /// Commit to the trace.
+    pub fn commitment(&self) -> TraceCommitment {
+        TraceCommitment {
+            steps: self.roots.len() as u64,
+            trace_root: build_merkle_root(&self.leaves()),
+        }
+    }
+
+    /// Root after `step` (step 0 is the prior state root).
+    pub fn root_at(&self, step: u64) -> Option<Hash> {
+        match step {
+            0 => Some(self.prior_root),
+            n => self.roots.get(n as usize - 1).copied(),
+        }
+    }
+
+    /// Open the final output step, or `None` if the trace is incomplete.
+    pub fn open_output(&self) -> Option<OutputOpening> {
+        let steps = self.roots.len() as u64;
+        Some(OutputOpening {
+            last_write: (steps > 1).then(|| self.open(steps - 1)).flatten(),
+            output: self.open(steps)?,
+            output_root: self.output_root?,
         })
-    } else {
-        Ok(DisputeResult::Valid)
+    }
+
+    /// Reveal the root at `step` with its proof.
+    pub fn open(&self, step: u64) -> Option<StepOpening> {
+        let root = self.root_at(step).filter(|_| step >= 1)?;
+        let proof = build_merkle_proof(&self.leaves(), step as usize - 1)?;
+        Some(StepOpening { step, root, proof })
+    }
+
+    fn leaves(&self) -> Vec<Hash> {
+        self.roots
+            .iter()
+            .enumerate()
+            .map(|(i, root)| step_leaf(i as u64 + 1, root))
+            .collect()
+    }
+}
+
+fn step_leaf(step: u64, root: &Hash) -> Hash {
+    let mut data = step.to_le_bytes().to_vec();
+    data.extend_from_slice(root);
+    hash_list_leaf(&data)
+}
+
+/// The final step of a trace, binding the output root to the write chain.
+pub fn output_step_root(prev: &Hash, output_root: &Hash) -> Hash {
+    blake3_hash_multi(&[b"norn_step_end", prev, output_root])
+}
+
+/// Re-execute the evidence and record its step trace.
+///
+/// With a `limit`, execution halts once that many write steps are recorded,
+/// so checking an early step costs only the execution up to it.
+pub fn record_step_trace(
+    evidence: &EvidenceBundle,
+    bytecode: &[u8],
+    limit: Option<u64>,
+) -> Result<StepTrace, LoomError> {
+    evidence.validate(bytecode)?;
+    let prior_root = evidence.prior_state_root();
+
+    let mut host_state = LoomHostState::new(
+        evidence.sender,
+        evidence.block_height,
+        evidence.timestamp,
+        DEFAULT_GAS_LIMIT,
+    );
+    host_state.state = evidence.prior_state.entries.iter().cloned().collect();
+    host_state.steps = Some(StepRecorder::new(prior_root, limit));
+
+    let runtime = LoomRuntime::new()?;
+    let mut instance = runtime.instantiate(bytecode, host_state)?;
+    let result = instance.call_execute(&evidence.input);
+    let host_state = instance.into_host_state();
+    let recorder = host_state
+        .steps
+        .unwrap_or_else(|| StepRecorder::new(prior_root, limit));
+
+    let output_root = match result {
+        Err(LoomError::StepLimitReached { .. }) => {
+            return Ok(StepTrace {
+                prior_root,
+                roots: recorder.roots,
+                output_root: None,
+            });
+        }
+        Ok(_) => {
+            let mut post_state = LoomState::new(evidence.loom_id);
+            post_state.data = host_state.state;
+            post_state.compute_hash()
+        }
+        // A failed execution leaves the state unchanged.
+        Err(_) => prior_root,
+    };
+    let mut roots = recorder.roots;
+    roots.push(output_step_root(&recorder.last_root, &output_root));
+    Ok(StepTrace {
+        prior_root,
+        roots,
+        output_root: Some(output_root),
+    })
+}
+
+/// An interactive bisection over two disagreeing step traces.
+#[derive(Debug, Clone)]
+pub struct BisectionGame {
+    /// The disputed execution. Its claimed output root is the defendant's.
+    pub evidence: EvidenceBundle,
+    /// The defendant's trace commitment.
+    pub defendant: TraceCommitment,
+    /// The challenger's trace commitment.
+    pub challenger: TraceCommitment,
+    /// Last step both sides agree on.
+    agreed: u64,
+    /// First step the sides are known to disagree on.
+    disputed: u64,
+    /// Each side's root at `disputed`: (defendant, challenger).
+    disputed_roots: (Hash, Hash),
+}
+
+impl BisectionGame {
+    /// Start a game.
+    ///
+    /// The defendant opens its final output step to show its trace ends in
+    /// the claimed output root. Each side then reveals its root at the
+    /// shorter trace's final step, where the traces must differ.
+    pub fn open(
+        evidence: EvidenceBundle,
+        defendant: TraceCommitment,
+        challenger: TraceCommitment,
+        defendant_output: OutputOpening,
+        defendant_opening: StepOpening,
+        challenger_opening: StepOpening,
+    ) -> Result<Self, LoomError> {
+        if defendant.steps == 0 || challenger.steps == 0 {
+            return Err(LoomError::InvalidEvidence {
+                reason: "trace commitments must have at least one step".to_string(),
+            });
+        }
+
+        // The defendant's final step must bind the claimed output root.
+        if defendant_output.output_root != evidence.claimed_output_root {
+            return Err(LoomError::InvalidEvidence {
+                reason: "defendant trace does not end in the claimed output root".to_string(),
+            });
+        }
+        let last_write_root = match (&defendant_output.last_write, defendant.steps) {
+            (None, 1) => Some(evidence.prior_state_root()),
+            (Some(opening), steps) if opening.step == steps - 1 && opening.verify(&defendant) => {
+                Some(opening.root)
+            }
+            _ => None,
+        };
+        let output = &defendant_output.output;
+        let binds_output = last_write_root.is_some_and(|prev| {
+            output.step == defendant.steps
+                && output.verify(&defendant)
+                && output.root == output_step_root(&prev, &defendant_output.output_root)
+        });
+        if !binds_output {
+            return Err(LoomError::InvalidEvidence {
+                reason: "invalid opening of the defendant's output step".to_string(),
+            });
+        }
+
+        let disputed = defendant.steps.min(challenger.steps);
+        for (opening, commitment, party) in [
+            (&defendant_opening, &defendant, "defendant"),
+            (&challenger_opening, &challenger, "challenger"),
+        ] {
+            if opening.step != disputed || !opening.verify(commitment) {
+                return Err(LoomError::InvalidEvidence {
+                    reason: format!("invalid {party} opening of step {disputed}"),
+                });
+            }
+        }
+        if defendant_opening.root == challenger_opening.root {
+            return Err(LoomError::InvalidEvidence {
+                reason: format!("traces agree at step {disputed}; nothing to bisect"),
+            });
+        }
+
+        Ok(Self {
+            evidence,
+            defendant,
+            challenger,
+            agreed: 0,
+            disputed,
+            disputed_roots: (defendant_opening.root, challenger_opening.root),
+        })
+    }
+
+    /// The step both sides must reveal next, or `None` once the disputed
+    /// range is a single step.
+    pub fn next_step(&self) -> Option<u64> {
+        (self.disputed - self.agreed > 1).then(|| self.agreed + (self.disputed - self.agreed) / 2)
+    }
+
+    /// The single disputed step, once bisection has finished.
+    pub fn disputed_step(&self) -> Option<u64> {
+        self.next_step().is_none().then_some(self.disputed)
+    }
+
+    /// Apply one round: both sides reveal their root at `next_step()`.
+    pub fn bisect(
+        &mut self,
+        defendant: StepOpening,
+        challenger: StepOpening,
+    ) -> Result<(), LoomError> {
+        let step = self.next_step().ok_or_else(|| LoomError::InvalidEvidence {
+            reason: "bisection already finished".to_string(),
+        })?;
+        if defendant.step != step || !defendant.verify(&self.defendant) {
+            return Err(LoomError::InvalidEvidence {
+                reason: format!("invalid defendant opening of step {step}"),
+            });
+        }
+        if challenger.step != step || !challenger.verify(&self.challenger) {
+            return Err(LoomError::InvalidEvidence {
+                reason: format!("invalid challenger opening of step {step}"),
+            });
+        }
+        if defendant.root == challenger.root {
+            self.agreed = step;
+        } else {
+            self.disputed = step;
+            self.disputed_roots = (defendant.root, challenger.root);
+        }
+        Ok(())
+    }
+
+    /// Settle a finished game by re-executing up to the disputed step.
+    ///
+    /// The defendant wins only if its root at the disputed step is correct.
+    pub fn resolve(&self, bytecode: &[u8]) -> Result<DisputeResult, LoomError> {
+        let step = self
+            .disputed_step()
+            .ok_or_else(|| LoomError::InvalidEvidence {
+                reason: "bisection has not finished".to_string(),
+            })?;
+        let trace = record_step_trace(&self.evidence, bytecode, Some(step))?;
+        let (defendant_root, _) = self.disputed_roots;
+        if trace.root_at(step) == Some(defendant_root) {
+            Ok(DisputeResult::Valid)
+        } else {
+            Ok(DisputeResult::Invalid {
+                reason: format!("defendant's root at step {step} does not match re-execution"),
+            })
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use norn_crypto::hash::blake3_hash;
 
     fn simple_wasm() -> Vec<u8> {
         let wat = r#"
@@ -222,4 +841,231 @@ mod tests {
                 .unwrap();
         assert!(matches!(result, DisputeResult::Invalid { .. }));
     }
+
+    fn counter_wasm() -> Vec<u8> {
+        // Writes key "k" = "v" on every execution.
+        let wat = r#"
+            (module
+                (import "norn" "norn_state_set" (func $set (param i32 i32 i32 i32)))
+                (memory (export "memory") 1)
+                (data (i32.const 0) "kv")
+                (func (export "execute") (param i32 i32) (result i32)
+                    (call $set (i32.const 0) (i32.const 1) (i32.const 1) (i32.const 1))
+                    i32.const 0
+                )
+            )
+        "#;
+        wat::parse_str(wat).expect("failed to compile WAT")
+    }
+
+    fn make_evidence(wasm: &[u8], claimed_output_root: Hash) -> EvidenceBundle {
+        let loom_id = [1u8; 32];
+        EvidenceBundle {
+            loom_id,
+            code_id: blake3_hash(wasm),
+            prior_state: LoomState::new(loom_id).export(),
+            input: vec![],
+            sender: [3u8; 20],
+            block_height: 100,
+            timestamp: 1000,
+            claimed_output_root,
+        }
+    }
+
+    fn honest_root() -> Hash {
+        let mut state = LoomState::new([1u8; 32]);
+        state.set(b"k".to_vec(), b"v".to_vec());
+        state.compute_hash()
+    }
+
+    #[test]
+    fn test_verify_evidence() {
+        let wasm = counter_wasm();
+
+        let (result, computed) =
+            verify_evidence(&make_evidence(&wasm, honest_root()), &wasm).unwrap();
+        assert_eq!(result, DisputeResult::Valid);
+        assert_eq!(computed, honest_root());
+
+        let (result, computed) = verify_evidence(&make_evidence(&wasm, [0xEE; 32]), &wasm).unwrap();
+        assert!(matches!(result, DisputeResult::Invalid { .. }));
+        assert_eq!(computed, honest_root());
+
+        // Evidence naming different code is rejected outright.
+        assert!(matches!(
+            verify_evidence(&make_evidence(&wasm, honest_root()), &simple_wasm()),
+            Err(LoomError::InvalidEvidence { .. })
+        ));
+    }
+
+    #[test]
+    fn test_dispute_registry_settlement() {
+        let wasm = counter_wasm();
+        let challenger = [5u8; 20];
+        let operator = [6u8; 20];
+        let mut registry = DisputeRegistry::new();
+
+        let fraud = registry
+            .open(
+                make_evidence(&wasm, [0xEE; 32]),
+                challenger,
+                operator,
+                10,
+                1,
+            )
+            .unwrap();
+        assert_eq!(registry.get(&fraud).unwrap().status, DisputeStatus::Pending);
+        assert!(registry.get(&fraud).unwrap().settlement().is_none());
+        let dispute = registry.resolve(&fraud, &wasm, 2).unwrap();
+        assert_eq!(dispute.status, DisputeStatus::Upheld);
+        assert_eq!(dispute.settlement(), Some((operator, challenger, 10)));
+
+        let honest = registry
+            .open(
+                make_evidence(&wasm, honest_root()),
+                challenger,
+                operator,
+                10,
+                3,
+            )
+            .unwrap();
+        let dispute = registry.resolve(&honest, &wasm, 4).unwrap();
+        assert_eq!(dispute.status, DisputeStatus::Rejected);
+        assert_eq!(dispute.settlement(), Some((challenger, operator, 10)));
+
+        // The same evidence cannot be disputed twice.
+        assert!(registry
+            .open(
+                make_evidence(&wasm, honest_root()),
+                challenger,
+                operator,
+                10,
+                5
+            )
+            .is_err());
+        assert_eq!(registry.list(Some(&[1u8; 32])).len(), 2);
+        assert!(registry.list(Some(&[2u8; 32])).is_empty());
+    }
+
+    fn looping_wasm() -> Vec<u8> {
+        // Writes keys 0..8, each set to its own index.
+        let wat = r#"
+            (module
+                (import "norn" "norn_state_set" (func $set (param i32 i32 i32 i32)))
+                (memory (export "memory") 1)
+                (func (export "execute") (param i32 i32) (result i32)
+                    (local $i i32)
+                    (block $done
+                        (loop $next
+                            (br_if $done (i32.ge_u (local.get $i) (i32.const 8)))
+                            (i32.store8 (i32.const 0) (local.get $i))
+                            (call $set (i32.const 0) (i32.const 1) (i32.const 0) (i32.const 1))
+                            (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                            (br $next)))
+                    i32.const 0
+                )
+            )
+        "#;
+        wat::parse_str(wat).expect("failed to compile WAT")
+    }
+
+    /// A trace that agrees with `honest` before `from` and diverges after,
+    /// ending in `output_root`.
+    fn forged_trace(honest: &StepTrace, from: u64, output_root: Hash) -> StepTrace {
+        let writes = honest.roots.len() - 1;
+        let mut roots = honest.roots[..from as usize - 1].to_vec();
+        let mut last = honest.root_at(from - 1).unwrap();
+        for _ in from as usize..=writes {
+            last = StepRecorder::step_root(&last, b"x", b"forged");
+            roots.push(last);
+        }
+        roots.push(output_step_root(&last, &output_root));
+        StepTrace {
+            prior_root: honest.prior_root,
+            roots,
+            output_root: Some(output_root),
+        }
+    }
+
+    fn play(
+        evidence: EvidenceBundle,
+        defendant: &StepTrace,
+        challenger: &StepTrace,
+    ) -> Result<BisectionGame, LoomError> {
+        let (d, c) = (defendant.commitment(), challenger.commitment());
+        let start = d.steps.min(c.steps);
+        let mut game = BisectionGame::open(
+            evidence,
+            d,
+            c,
+            defendant.open_output().unwrap(),
+            defendant.open(start).unwrap(),
+            challenger.open(start).unwrap(),
+        )?;
+        while let Some(step) = game.next_step() {
+            game.bisect(
+                defendant.open(step).unwrap(),
+                challenger.open(step).unwrap(),
+            )?;
+        }
+        Ok(game)
+    }
+
+    #[test]
+    fn test_step_trace_limit() {
+        let wasm = looping_wasm();
+        let evidence = make_evidence(&wasm, [0; 32]);
+        let full = record_step_trace(&evidence, &wasm, None).unwrap();
+        assert_eq!(full.roots.len(), 9);
+        let output_root = full.output_root.unwrap();
+        assert_eq!(
+            verify_evidence(&make_evidence(&wasm, output_root), &wasm)
+                .unwrap()
+                .0,
+            DisputeResult::Valid
+        );
+
+        let partial = record_step_trace(&evidence, &wasm, Some(3)).unwrap();
+        assert_eq!(partial.roots, full.roots[..3].to_vec());
+        assert!(partial.output_root.is_none());
+    }
+
+    #[test]
+    fn test_bisection_catches_forged_trace() {
+        let wasm = looping_wasm();
+        let honest = record_step_trace(&make_evidence(&wasm, [0; 32]), &wasm, None).unwrap();
+        let forged = forged_trace(&honest, 5, [0xEE; 32]);
+        let evidence = make_evidence(&wasm, [0xEE; 32]);
+
+        let game = play(evidence.clone(), &forged, &honest).unwrap();
+        assert_eq!(game.disputed_step(), Some(5));
+        assert!(matches!(
+            game.resolve(&wasm).unwrap(),
+            DisputeResult::Invalid { .. }
+        ));
+
+        let mut registry = DisputeRegistry::new();
+        let id = registry
+            .open(evidence, [5u8; 20], [6u8; 20], 10, 1)
+            .unwrap();
+        let dispute = registry.resolve_bisection(&id, &game, &wasm, 2).unwrap();
+        assert_eq!(dispute.status, DisputeStatus::Upheld);
+    }
+
+    #[test]
+    fn test_bisection_defends_honest_trace() {
+        let wasm = looping_wasm();
+        let honest = record_step_trace(&make_evidence(&wasm, [0; 32]), &wasm, None).unwrap();
+        let output_root = honest.output_root.unwrap();
+        let forged = forged_trace(&honest, 2, [0xEE; 32]);
+
+        let game = play(make_evidence(&wasm, output_root), &honest, &forged).unwrap();
+        assert_eq!(game.disputed_step(), Some(2));
+        assert_eq!(game.resolve(&wasm).unwrap(), DisputeResult::Valid);
+
+        // The defendant's trace must end in the claimed output root.
+        assert!(play(make_evidence(&wasm, [0xAB; 32]), &honest, &forged).is_err());
+        // Identical traces leave nothing to bisect.
+        assert!(play(make_evidence(&wasm, output_root), &honest, &honest).is_err());
+    }
 }
@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+use crate::runtime::ModuleDiagnostic;
+
 /// Errors that can occur in the loom subsystem.
 #[derive(Debug, Error)]
 pub enum LoomError {
@@ -18,6 +20,12 @@ pub enum LoomError {
     #[error("Loom not found: {loom_id:?}")]
     LoomNotFound { loom_id: [u8; 32] },
 
+    #[error("Loom already exists: {loom_id:?}")]
+    LoomAlreadyExists { loom_id: [u8; 32] },
+
+    #[error("Code not found: {code_id:?}")]
+    CodeNotFound { code_id: [u8; 32] },
+
     #[error("Not a participant: {address:?}")]
     NotParticipant { address: [u8; 20] },
 
@@ -39,6 +47,39 @@ pub enum LoomError {
     #[error("Call depth exceeded: {depth} > {max}")]
     CallDepthExceeded { depth: u8, max: u8 },
 
+    #[error("Storage limit exceeded: {resource} {used} > {limit}")]
+    StorageLimitExceeded {
+        resource: &'static str,
+        used: u64,
+        limit: u64,
+    },
+
+    #[error("Invalid module: {}", join_diagnostics(.diagnostics))]
+    InvalidModule { diagnostics: Vec<ModuleDiagnostic> },
+
+    #[error("Step limit reached after {steps} steps")]
+    StepLimitReached { steps: u64 },
+
+    #[error("Invalid evidence: {reason}")]
+    InvalidEvidence { reason: String },
+
+    #[error("Dispute not found: {dispute_id:?}")]
+    DisputeNotFound { dispute_id: [u8; 32] },
+
+    #[error("Permission denied: {reason}")]
+    PermissionDenied { reason: String },
+
+    #[error("Invalid operator set: {reason}")]
+    InvalidOperatorSet { reason: String },
+
     #[error("Storage error: {0}")]
     StorageError(#[from] norn_storage::error::StorageError),
 }
+
+fn join_diagnostics(diagnostics: &[ModuleDiagnostic]) -> String {
+    diagnostics
+        .iter()
+        .map(|d| d.to_string())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
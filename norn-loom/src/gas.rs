@@ -17,6 +17,22 @@ pub const GAS_BYTE_READ: u64 = 1;
 /// Cost per byte written to state.
 pub const GAS_BYTE_WRITE: u64 = 2;
 
+/// Cost for a single on-ledger balance lookup.
+pub const GAS_BALANCE_QUERY: u64 = 100;
+
+/// Cost for deriving a contract sub-address.
+pub const GAS_DERIVE_ADDRESS: u64 = 100;
+
+/// Cost for verifying a VRF proof.
+pub const GAS_VRF_VERIFY: u64 = 25_000;
+
+/// Cost for decrypting a sealed box (base overhead; each ciphertext byte
+/// is also charged as a byte read).
+pub const GAS_DECRYPT: u64 = 1_000;
+
+/// Cost for verifying an Ed25519 signature.
+pub const GAS_SIGNATURE_VERIFY: u64 = 5_000;
+
 /// Cost for a single token transfer operation.
 pub const GAS_TRANSFER: u64 = 500;
 
@@ -29,12 +45,105 @@ pub const GAS_EMIT_EVENT: u64 = 75;
 /// Cost for a cross-contract call (base overhead).
 pub const GAS_CROSS_CALL: u64 = 2_500;
 
+/// Cost for instantiating a loom from stored code (base overhead).
+pub const GAS_INSTANTIATE: u64 = 10_000;
+
 /// Maximum nested cross-contract call depth.
 pub const MAX_CALL_DEPTH: u8 = 8;
 
 /// Default gas limit when none is specified.
 pub const DEFAULT_GAS_LIMIT: u64 = 10_000_000;
 
+// ─── Gas Schedules ──────────────────────────────────────────────────────────
+
+/// Gas costs in effect from a given block height.
+///
+/// Schedules are append-only: changing a cost means adding a new version with
+/// a later activation height, so executions at earlier heights keep the costs
+/// they were originally charged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GasSchedule {
+    /// Schedule version (starts at 1, increments by one).
+    pub version: u32,
+    /// First block height the schedule applies to.
+    pub activation_height: u64,
+    /// Cost per Wasm instruction executed.
+    pub instruction: u64,
+    /// Cost for a single state read operation.
+    pub state_read: u64,
+    /// Cost for a single state write operation.
+    pub state_write: u64,
+    /// Cost per byte read from state.
+    pub byte_read: u64,
+    /// Cost per byte written to state.
+    pub byte_write: u64,
+    /// Cost for a single on-ledger balance lookup.
+    pub balance_query: u64,
+    /// Cost for deriving a contract sub-address.
+    pub derive_address: u64,
+    /// Cost for verifying a VRF proof.
+    pub vrf_verify: u64,
+    /// Cost for decrypting a sealed box (base overhead).
+    pub decrypt: u64,
+    /// Cost for verifying an Ed25519 signature.
+    pub signature_verify: u64,
+    /// Cost for a single token transfer operation.
+    pub transfer: u64,
+    /// Cost for a single log emission.
+    pub log: u64,
+    /// Cost for emitting a structured event.
+    pub emit_event: u64,
+    /// Cost for a cross-contract call (base overhead).
+    pub cross_call: u64,
+    /// Cost for instantiating a loom from stored code (base overhead).
+    pub instantiate: u64,
+}
+
+/// The original gas schedule, active from genesis.
+pub const GAS_SCHEDULE_V1: GasSchedule = GasSchedule {
+    version: 1,
+    activation_height: 0,
+    instruction: GAS_PER_INSTRUCTION,
+    state_read: GAS_STATE_READ,
+    state_write: GAS_STATE_WRITE,
+    byte_read: GAS_BYTE_READ,
+    byte_write: GAS_BYTE_WRITE,
+    balance_query: GAS_BALANCE_QUERY,
+    derive_address: GAS_DERIVE_ADDRESS,
+    vrf_verify: GAS_VRF_VERIFY,
+    decrypt: GAS_DECRYPT,
+    signature_verify: GAS_SIGNATURE_VERIFY,
+    transfer: GAS_TRANSFER,
+    log: GAS_LOG,
+    emit_event: GAS_EMIT_EVENT,
+    cross_call: GAS_CROSS_CALL,
+    instantiate: GAS_INSTANTIATE,
+};
+
+/// All gas schedules, ordered by activation height.
+pub const GAS_SCHEDULES: &[GasSchedule] = &[GAS_SCHEDULE_V1];
+
+/// Return the gas schedule in effect at `block_height`.
+pub fn schedule_for_height(block_height: u64) -> &'static GasSchedule {
+    GAS_SCHEDULES
+        .iter()
+        .rev()
+        .find(|s| s.activation_height <= block_height)
+        .unwrap_or(&GAS_SCHEDULES[0])
+}
+
+impl GasSchedule {
+    /// Convert gas to wasmtime fuel (one unit of fuel per instruction).
+    pub fn gas_to_fuel(&self, gas: u64) -> u64 {
+        gas / self.instruction.max(1)
+    }
+
+    /// Convert consumed wasmtime fuel back to gas.
+    pub fn fuel_to_gas(&self, fuel: u64) -> u64 {
+        fuel.saturating_mul(self.instruction.max(1))
+    }
+}
+
 // ─── Gas Meter ──────────────────────────────────────────────────────────────
 
 /// Tracks gas consumption during loom execution.
@@ -113,6 +222,46 @@ mod tests {
         assert!(meter.charge(1).is_err());
     }
 
+    #[test]
+    fn test_schedule_v1_costs() {
+        // Pinned: changing any of these is consensus-breaking. Add a new
+        // schedule version instead.
+        let s = GAS_SCHEDULE_V1;
+        assert_eq!((s.version, s.activation_height, s.instruction), (1, 0, 1));
+        assert_eq!((s.state_read, s.state_write), (100, 200));
+        assert_eq!((s.byte_read, s.byte_write), (1, 2));
+        assert_eq!(
+            (s.balance_query, s.derive_address, s.transfer),
+            (100, 100, 500)
+        );
+        assert_eq!(
+            (s.vrf_verify, s.decrypt, s.signature_verify),
+            (25_000, 1_000, 5_000)
+        );
+        assert_eq!((s.log, s.emit_event), (50, 75));
+        assert_eq!((s.cross_call, s.instantiate), (2_500, 10_000));
+    }
+
+    #[test]
+    fn test_schedules_are_well_formed() {
+        assert_eq!(GAS_SCHEDULES[0].activation_height, 0);
+        for (i, s) in GAS_SCHEDULES.iter().enumerate() {
+            assert_eq!(s.version as usize, i + 1);
+            assert!(s.instruction > 0);
+        }
+        for pair in GAS_SCHEDULES.windows(2) {
+            assert!(pair[0].activation_height < pair[1].activation_height);
+        }
+    }
+
+    #[test]
+    fn test_schedule_for_height() {
+        for s in GAS_SCHEDULES {
+            assert_eq!(schedule_for_height(s.activation_height), s);
+        }
+        assert_eq!(schedule_for_height(u64::MAX), GAS_SCHEDULES.last().unwrap());
+    }
+
     #[test]
     fn test_gas_meter_zero_charge() {
         let mut meter = GasMeter::new(100);
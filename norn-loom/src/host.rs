@@ -1,7 +1,9 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
 
-use norn_types::primitives::{Address, Amount, LoomId, TokenId};
+use norn_crypto::hash::blake3_hash_multi;
+use norn_crypto::vrf::{VrfOutput, VrfProof};
+use norn_types::primitives::{Address, Amount, Hash, LoomId, PublicKey, Signature, TokenId};
 use wasmtime::StoreLimits;
 
 use crate::call_stack::CallStack;
@@ -12,6 +14,17 @@ use crate::gas::*;
 pub type SharedLoomStates = Arc<Mutex<HashMap<LoomId, HashMap<Vec<u8>, Vec<u8>>>>>;
 /// Shared cross-call bytecode map: LoomId -> wasm bytecode.
 pub type SharedLoomBytecodes = Arc<Mutex<HashMap<LoomId, Vec<u8>>>>;
+/// Shared read-only code registry: code ID (blake3 of the wasm) -> bytecode.
+pub type SharedCodes = Arc<HashMap<Hash, Vec<u8>>>;
+/// Shared read-only view of on-ledger balances.
+pub type SharedBalanceView = Arc<dyn BalanceView>;
+
+/// Read-only access to on-ledger token balances, backing the
+/// `norn_balance_of` host function.
+pub trait BalanceView: Send + Sync {
+    /// Return the settled balance of `address` for `token_id`.
+    fn balance(&self, address: &Address, token_id: &TokenId) -> Amount;
+}
 
 /// Maximum WASM memory: 16 MB.
 pub const MAX_WASM_MEMORY_BYTES: usize = 16 * 1024 * 1024;
@@ -22,6 +35,146 @@ pub const MAX_PENDING_TRANSFERS: usize = 256;
 pub const MAX_LOGS: usize = 1_000;
 /// Maximum events per execution (including cross-call merges).
 pub const MAX_EVENTS: usize = 1_000;
+/// Maximum looms instantiated per execution.
+pub const MAX_PENDING_INSTANTIATIONS: usize = 16;
+/// Maximum salt length for derived addresses and instance IDs.
+pub const MAX_SALT_SIZE: usize = 256;
+/// Maximum VRF input a contract may verify a proof of.
+pub const MAX_VRF_INPUT_SIZE: usize = 1_024;
+/// Maximum ciphertext a contract may decrypt in one call.
+pub const MAX_DECRYPT_SIZE: usize = 16 * 1024;
+/// Maximum message a contract may verify a signature over.
+pub const MAX_SIGNED_MESSAGE_SIZE: usize = 1_024;
+/// Maximum state reads per execution (including cross-calls).
+pub const MAX_STORAGE_READS: u64 = 50_000;
+/// Maximum key and value bytes read from state per execution.
+pub const MAX_STORAGE_BYTES_READ: u64 = 16 * 1024 * 1024;
+/// Maximum state writes per execution (including cross-calls).
+pub const MAX_STORAGE_WRITES: u64 = 10_000;
+/// Maximum key and value bytes written to state per execution.
+pub const MAX_STORAGE_BYTES_WRITTEN: u64 = 4 * 1024 * 1024;
+
+/// Storage operations performed during an execution.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StorageUsage {
+    /// Number of state reads.
+    pub reads: u64,
+    /// Key and value bytes read.
+    pub bytes_read: u64,
+    /// Number of state writes.
+    pub writes: u64,
+    /// Key and value bytes written.
+    pub bytes_written: u64,
+}
+
+impl StorageUsage {
+    /// Fail with `StorageLimitExceeded` if any counter is over its limit.
+    fn check(&self) -> Result<(), LoomError> {
+        let limits = [
+            ("reads", self.reads, MAX_STORAGE_READS),
+            ("bytes_read", self.bytes_read, MAX_STORAGE_BYTES_READ),
+            ("writes", self.writes, MAX_STORAGE_WRITES),
+            (
+                "bytes_written",
+                self.bytes_written,
+                MAX_STORAGE_BYTES_WRITTEN,
+            ),
+        ];
+        for (resource, used, limit) in limits {
+            if used > limit {
+                return Err(LoomError::StorageLimitExceeded {
+                    resource,
+                    used,
+                    limit,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Maximum host calls recorded in an execution trace.
+pub const MAX_TRACE_CALLS: usize = 10_000;
+/// Maximum debug log messages recorded in an execution trace.
+pub const MAX_DEBUG_LOGS: usize = 1_000;
+/// Maximum length of a single debug log message, in bytes.
+pub const MAX_DEBUG_LOG_SIZE: usize = 4_096;
+
+/// A host function call recorded while tracing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostCallTrace {
+    /// Cross-call depth of the loom that made the call (0 for the entry loom).
+    pub depth: u32,
+    /// Host function name, e.g. `norn_state_get`.
+    pub host_fn: &'static str,
+    /// Gas charged by the call, including any nested execution.
+    pub gas: u64,
+    /// Human-readable arguments and result.
+    pub detail: String,
+}
+
+/// Trace buffer filled during a traced execution.
+///
+/// Tracing never changes gas charges or results, so a traced run matches
+/// the consensus execution it debugs.
+#[derive(Debug, Clone, Default)]
+pub struct ExecutionTracer {
+    /// Cross-call depth of the loom this tracer belongs to.
+    pub depth: u32,
+    /// Host calls in the order they completed. Nested cross-call and
+    /// instantiation calls appear before the call that made them.
+    pub calls: Vec<HostCallTrace>,
+    /// Messages passed to `norn_debug_log`.
+    pub debug_logs: Vec<String>,
+    /// Whether entries were dropped after a limit was reached.
+    pub truncated: bool,
+}
+
+impl ExecutionTracer {
+    /// Tracer for a loom called from this one.
+    pub fn nested(&self) -> Self {
+        Self {
+            depth: self.depth + 1,
+            ..Self::default()
+        }
+    }
+
+    /// Append a nested tracer's entries to this one.
+    pub fn merge(&mut self, nested: ExecutionTracer) {
+        for call in nested.calls {
+            self.push_call(call);
+        }
+        for message in nested.debug_logs {
+            self.push_debug_log(message);
+        }
+        self.truncated |= nested.truncated;
+    }
+
+    fn push_call(&mut self, call: HostCallTrace) {
+        if self.calls.len() < MAX_TRACE_CALLS {
+            self.calls.push(call);
+        } else {
+            self.truncated = true;
+        }
+    }
+
+    fn push_debug_log(&mut self, message: String) {
+        if self.debug_logs.len() < MAX_DEBUG_LOGS {
+            self.debug_logs.push(message);
+        } else {
+            self.truncated = true;
+        }
+    }
+}
+
+/// Render bytes for a trace: as text when printable ASCII, else as hex.
+pub fn trace_bytes(bytes: &[u8]) -> String {
+    if !bytes.is_empty() && bytes.iter().all(|b| b.is_ascii_graphic() || *b == b' ') {
+        String::from_utf8_lossy(bytes).into_owned()
+    } else {
+        format!("0x{}", hex::encode(bytes))
+    }
+}
 
 /// A pending token transfer produced during loom execution.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -36,6 +189,19 @@ pub struct PendingTransfer {
     pub amount: Amount,
 }
 
+/// A loom instantiated from stored code during execution (registered on success).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingInstantiation {
+    /// ID of the new loom.
+    pub loom_id: LoomId,
+    /// Code the new loom runs.
+    pub code_id: Hash,
+    /// Contract address that created the loom.
+    pub creator: Address,
+    /// State produced by the instance's `init`.
+    pub state: HashMap<Vec<u8>, Vec<u8>>,
+}
+
 /// A structured event emitted by a loom contract via the `norn_emit_event` host function.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct HostEvent {
@@ -45,6 +211,46 @@ pub struct HostEvent {
     pub attributes: Vec<(String, String)>,
 }
 
+/// Records an execution as a chain of state-write steps, for bisection
+/// disputes. Step `k` commits to every write up to and including the `k`th.
+#[derive(Debug, Clone)]
+pub struct StepRecorder {
+    /// Root after the latest step (the prior state root before any step).
+    pub last_root: Hash,
+    /// Root after each step, in order.
+    pub roots: Vec<Hash>,
+    /// Halt execution once this many steps are recorded.
+    pub limit: Option<u64>,
+}
+
+impl StepRecorder {
+    /// Start recording from `prior_root`.
+    pub fn new(prior_root: Hash, limit: Option<u64>) -> Self {
+        Self {
+            last_root: prior_root,
+            roots: Vec::new(),
+            limit,
+        }
+    }
+
+    /// Chain a state write onto the previous step root.
+    pub fn step_root(prev: &Hash, key: &[u8], value: &[u8]) -> Hash {
+        let pair = borsh::to_vec(&(key, value)).unwrap_or_default();
+        blake3_hash_multi(&[b"norn_step", prev, &pair])
+    }
+
+    /// Record a write, failing with `StepLimitReached` once the limit is hit.
+    fn record(&mut self, key: &[u8], value: &[u8]) -> Result<(), LoomError> {
+        self.last_root = Self::step_root(&self.last_root, key, value);
+        self.roots.push(self.last_root);
+        let steps = self.roots.len() as u64;
+        if self.limit.is_some_and(|limit| steps >= limit) {
+            return Err(LoomError::StepLimitReached { steps });
+        }
+        Ok(())
+    }
+}
+
 /// Host-side state accessible to Wasm loom contracts via host functions.
 ///
 /// This struct is owned by the wasmtime `Store` and provides the backing
@@ -52,6 +258,8 @@ pub struct HostEvent {
 pub struct LoomHostState {
     /// Gas meter for tracking execution cost.
     pub gas_meter: GasMeter,
+    /// Gas costs in effect at `block_height`.
+    pub gas_schedule: GasSchedule,
     /// Key-value state of the loom contract.
     pub state: HashMap<Vec<u8>, Vec<u8>>,
     /// Transfers emitted during execution (applied on success).
@@ -78,6 +286,20 @@ pub struct LoomHostState {
     pub loom_bytecodes: Option<SharedLoomBytecodes>,
     /// The loom ID of the currently executing contract (for cross-call context).
     pub current_loom_id: Option<LoomId>,
+    /// Ledger balances visible to the contract (zero for every account when unset).
+    pub balance_view: Option<SharedBalanceView>,
+    /// Sub-addresses derived during this execution; the contract may transfer from them.
+    pub sub_addresses: HashSet<Address>,
+    /// Stored code available to `norn_instantiate` (instantiation disabled when unset).
+    pub codes: Option<SharedCodes>,
+    /// Looms instantiated during execution (registered on success).
+    pub instantiations: Vec<PendingInstantiation>,
+    /// Storage operations so far, carried through cross-calls.
+    pub storage_usage: StorageUsage,
+    /// Trace buffer; `None` unless the execution is being traced.
+    pub tracer: Option<ExecutionTracer>,
+    /// Step recorder; `None` unless the execution is being bisected.
+    pub steps: Option<StepRecorder>,
 }
 
 impl LoomHostState {
@@ -86,6 +308,7 @@ impl LoomHostState {
         use wasmtime::StoreLimitsBuilder;
         Self {
             gas_meter: GasMeter::new(gas_limit),
+            gas_schedule: *schedule_for_height(block_height),
             state: HashMap::new(),
             pending_transfers: Vec::new(),
             logs: Vec::new(),
@@ -100,24 +323,72 @@ impl LoomHostState {
             loom_states: None,
             loom_bytecodes: None,
             current_loom_id: None,
+            balance_view: None,
+            sub_addresses: HashSet::new(),
+            codes: None,
+            instantiations: Vec::new(),
+            storage_usage: StorageUsage::default(),
+            tracer: None,
+            steps: None,
+        }
+    }
+
+    /// Record a host call if tracing is enabled. `gas_before` is the gas
+    /// meter reading when the call started.
+    pub fn trace_call(
+        &mut self,
+        host_fn: &'static str,
+        gas_before: u64,
+        detail: impl FnOnce() -> String,
+    ) {
+        let gas = self.gas_meter.used().saturating_sub(gas_before);
+        if let Some(tracer) = self.tracer.as_mut() {
+            let depth = tracer.depth;
+            tracer.push_call(HostCallTrace {
+                depth,
+                host_fn,
+                gas,
+                detail: detail(),
+            });
+        }
+    }
+
+    /// Record a debug message if tracing is enabled; otherwise do nothing.
+    /// Never charges gas, so debug logging cannot change execution results.
+    pub fn debug_log(&mut self, message: &[u8]) {
+        if let Some(tracer) = self.tracer.as_mut() {
+            let message = &message[..message.len().min(MAX_DEBUG_LOG_SIZE)];
+            tracer.push_debug_log(String::from_utf8_lossy(message).into_owned());
         }
     }
 
     /// Read a value from the loom state.
-    /// Charges GAS_STATE_READ plus GAS_BYTE_READ per byte of the value.
+    /// Charges the schedule's `state_read` plus `byte_read` per byte of the value.
+    /// Counts toward the per-execution storage limits.
     pub fn state_get(&mut self, key: &[u8]) -> Result<Option<Vec<u8>>, LoomError> {
-        self.gas_meter.charge(GAS_STATE_READ)?;
+        let gas_before = self.gas_meter.used();
+        self.gas_meter.charge(self.gas_schedule.state_read)?;
         let value = self.state.get(key).cloned();
-        if let Some(ref v) = value {
-            self.gas_meter
-                .charge(GAS_BYTE_READ.saturating_mul(v.len() as u64))?;
-        }
+        let value_len = value.as_ref().map_or(0, |v| v.len() as u64);
+        self.storage_usage.reads += 1;
+        self.storage_usage.bytes_read = self
+            .storage_usage
+            .bytes_read
+            .saturating_add(key.len() as u64 + value_len);
+        self.storage_usage.check()?;
+        self.gas_meter
+            .charge(self.gas_schedule.byte_read.saturating_mul(value_len))?;
+        self.trace_call("norn_state_get", gas_before, || match &value {
+            Some(v) => format!("{} -> {}", trace_bytes(key), trace_bytes(v)),
+            None => format!("{} -> (none)", trace_bytes(key)),
+        });
         Ok(value)
     }
 
     /// Write a value to the loom state.
-    /// Charges GAS_STATE_WRITE plus GAS_BYTE_WRITE per byte of the value.
-    /// Bounded to prevent unbounded state growth.
+    /// Charges the schedule's `state_write` plus `byte_write` per byte of the value.
+    /// Bounded to prevent unbounded state growth, and counts toward the
+    /// per-execution storage limits.
     pub fn state_set(&mut self, key: &[u8], value: &[u8]) -> Result<(), LoomError> {
         const MAX_KEY_SIZE: usize = 1024;
         const MAX_VALUE_SIZE: usize = 65_536;
@@ -138,15 +409,34 @@ impl LoomHostState {
                 reason: "state entry limit reached".to_string(),
             });
         }
-        self.gas_meter.charge(GAS_STATE_WRITE)?;
-        self.gas_meter
-            .charge(GAS_BYTE_WRITE.saturating_mul(value.len() as u64))?;
+        self.storage_usage.writes += 1;
+        self.storage_usage.bytes_written = self
+            .storage_usage
+            .bytes_written
+            .saturating_add((key.len() + value.len()) as u64);
+        self.storage_usage.check()?;
+        let gas_before = self.gas_meter.used();
+        self.gas_meter.charge(self.gas_schedule.state_write)?;
+        self.gas_meter.charge(
+            self.gas_schedule
+                .byte_write
+                .saturating_mul(value.len() as u64),
+        )?;
         self.state.insert(key.to_vec(), value.to_vec());
+        self.trace_call("norn_state_set", gas_before, || {
+            format!("{} = {}", trace_bytes(key), trace_bytes(value))
+        });
+        if let Some(steps) = self.steps.as_mut() {
+            steps.record(key, value)?;
+        }
         Ok(())
     }
 
     /// Queue a token transfer.
-    /// Charges GAS_TRANSFER. Bounded to prevent memory exhaustion.
+    /// Charges the schedule's `transfer` cost. Bounded to prevent memory exhaustion.
+    /// When a balance view is attached, a transfer that would overdraw `from`
+    /// (counting transfers already queued) is rejected, so a contract never
+    /// acts on funds that would fail to settle.
     pub fn transfer(
         &mut self,
         from: Address,
@@ -154,47 +444,221 @@ impl LoomHostState {
         token_id: TokenId,
         amount: Amount,
     ) -> Result<(), LoomError> {
-        self.gas_meter.charge(GAS_TRANSFER)?;
+        let gas_before = self.gas_meter.used();
+        self.gas_meter.charge(self.gas_schedule.transfer)?;
         if self.pending_transfers.len() >= MAX_PENDING_TRANSFERS {
             return Err(LoomError::RuntimeError {
                 reason: "too many pending transfers".to_string(),
             });
         }
+        if self.balance_view.is_some() && self.effective_balance(&from, &token_id) < amount {
+            return Err(LoomError::RuntimeError {
+                reason: "insufficient balance for transfer".to_string(),
+            });
+        }
         self.pending_transfers.push(PendingTransfer {
             from,
             to,
             token_id,
             amount,
         });
+        self.trace_call("norn_transfer", gas_before, || {
+            format!(
+                "{amount} of 0x{} from 0x{} to 0x{}",
+                hex::encode(token_id),
+                hex::encode(from),
+                hex::encode(to)
+            )
+        });
         Ok(())
     }
 
+    /// Derive a sub-address of the current loom from `salt`.
+    /// Charges the schedule's `derive_address` cost. The address is remembered
+    /// so the contract can transfer funds out of it for the rest of the
+    /// execution.
+    pub fn derive_address(&mut self, salt: &[u8]) -> Result<Address, LoomError> {
+        if salt.len() > MAX_SALT_SIZE {
+            return Err(LoomError::RuntimeError {
+                reason: "address salt too large".to_string(),
+            });
+        }
+        let loom_id = self.current_loom_id.ok_or(LoomError::RuntimeError {
+            reason: "no loom_id set in host state".to_string(),
+        })?;
+        let gas_before = self.gas_meter.used();
+        self.gas_meter.charge(self.gas_schedule.derive_address)?;
+        let addr = norn_types::primitives::derive_sub_address(&loom_id, salt);
+        self.sub_addresses.insert(addr);
+        self.trace_call("norn_derive_address", gas_before, || {
+            format!("{} -> 0x{}", trace_bytes(salt), hex::encode(addr))
+        });
+        Ok(addr)
+    }
+
+    /// Verify a VRF `proof` of `alpha` under `public_key`, returning the VRF
+    /// output, or `None` if the proof does not verify.
+    /// Charges the schedule's `vrf_verify` cost.
+    pub fn vrf_verify(
+        &mut self,
+        public_key: &PublicKey,
+        alpha: &[u8],
+        proof: &VrfProof,
+    ) -> Result<Option<VrfOutput>, LoomError> {
+        if alpha.len() > MAX_VRF_INPUT_SIZE {
+            return Err(LoomError::RuntimeError {
+                reason: "VRF input too large".to_string(),
+            });
+        }
+        let gas_before = self.gas_meter.used();
+        self.gas_meter.charge(self.gas_schedule.vrf_verify)?;
+        let output = norn_crypto::vrf::verify(public_key, alpha, proof).ok();
+        self.trace_call("norn_vrf_verify", gas_before, || {
+            format!(
+                "0x{} {} -> {}",
+                hex::encode(public_key),
+                trace_bytes(alpha),
+                if output.is_some() { "valid" } else { "invalid" }
+            )
+        });
+        Ok(output)
+    }
+
+    /// Open an XChaCha20-Poly1305 sealed box, returning the plaintext, or
+    /// `None` if the key is wrong or the ciphertext was tampered with.
+    /// Charges the schedule's `decrypt` cost plus `byte_read` per ciphertext byte.
+    pub fn decrypt(
+        &mut self,
+        key: &[u8; 32],
+        nonce: &[u8; 24],
+        ciphertext: &[u8],
+    ) -> Result<Option<Vec<u8>>, LoomError> {
+        if ciphertext.len() > MAX_DECRYPT_SIZE {
+            return Err(LoomError::RuntimeError {
+                reason: "ciphertext too large".to_string(),
+            });
+        }
+        let gas_before = self.gas_meter.used();
+        let byte_cost = self
+            .gas_schedule
+            .byte_read
+            .saturating_mul(ciphertext.len() as u64);
+        self.gas_meter
+            .charge(self.gas_schedule.decrypt.saturating_add(byte_cost))?;
+        let plaintext = norn_crypto::encryption::open(key, nonce, ciphertext).ok();
+        self.trace_call("norn_decrypt", gas_before, || {
+            format!(
+                "{} bytes -> {}",
+                ciphertext.len(),
+                if plaintext.is_some() { "ok" } else { "failed" }
+            )
+        });
+        Ok(plaintext)
+    }
+
+    /// Verify an Ed25519 `signature` of `message` under `public_key`.
+    /// Charges the schedule's `signature_verify` cost.
+    pub fn verify_signature(
+        &mut self,
+        public_key: &PublicKey,
+        message: &[u8],
+        signature: &Signature,
+    ) -> Result<bool, LoomError> {
+        if message.len() > MAX_SIGNED_MESSAGE_SIZE {
+            return Err(LoomError::RuntimeError {
+                reason: "signed message too large".to_string(),
+            });
+        }
+        let gas_before = self.gas_meter.used();
+        self.gas_meter.charge(self.gas_schedule.signature_verify)?;
+        let valid = norn_crypto::keys::verify(message, signature, public_key).is_ok();
+        self.trace_call("norn_verify_signature", gas_before, || {
+            format!(
+                "0x{} {} -> {}",
+                hex::encode(public_key),
+                trace_bytes(message),
+                if valid { "valid" } else { "invalid" }
+            )
+        });
+        Ok(valid)
+    }
+
+    /// Look up the balance of `address` for `token_id`.
+    /// Charges the schedule's `balance_query` cost. The settled ledger balance
+    /// is adjusted by the transfers already queued in this execution, so a
+    /// contract sees the effect of its own transfers before they are applied.
+    pub fn balance_of(
+        &mut self,
+        address: &Address,
+        token_id: &TokenId,
+    ) -> Result<Amount, LoomError> {
+        let gas_before = self.gas_meter.used();
+        self.gas_meter.charge(self.gas_schedule.balance_query)?;
+        let balance = self.effective_balance(address, token_id);
+        self.trace_call("norn_balance_of", gas_before, || {
+            format!(
+                "0x{} of 0x{} -> {balance}",
+                hex::encode(address),
+                hex::encode(token_id)
+            )
+        });
+        Ok(balance)
+    }
+
+    /// Settled balance adjusted by the transfers queued in this execution.
+    fn effective_balance(&self, address: &Address, token_id: &TokenId) -> Amount {
+        let settled = self
+            .balance_view
+            .as_ref()
+            .map(|view| view.balance(address, token_id))
+            .unwrap_or(0);
+        let mut credited: Amount = 0;
+        let mut debited: Amount = 0;
+        for t in self
+            .pending_transfers
+            .iter()
+            .filter(|t| t.token_id == *token_id)
+        {
+            if t.to == *address {
+                credited = credited.saturating_add(t.amount);
+            }
+            if t.from == *address {
+                debited = debited.saturating_add(t.amount);
+            }
+        }
+        settled.saturating_add(credited).saturating_sub(debited)
+    }
+
     /// Emit a log message.
-    /// Charges GAS_LOG. Bounded to prevent memory exhaustion.
+    /// Charges the schedule's `log` cost. Bounded to prevent memory exhaustion.
     pub fn log(&mut self, message: &str) -> Result<(), LoomError> {
-        self.gas_meter.charge(GAS_LOG)?;
+        let gas_before = self.gas_meter.used();
+        self.gas_meter.charge(self.gas_schedule.log)?;
         if self.logs.len() >= MAX_LOGS {
             return Err(LoomError::RuntimeError {
                 reason: "too many log messages".to_string(),
             });
         }
         self.logs.push(message.to_string());
+        self.trace_call("norn_log", gas_before, || message.to_string());
         Ok(())
     }
 
     /// Emit a structured event.
-    /// Charges GAS_EMIT_EVENT. Bounded to prevent memory exhaustion.
+    /// Charges the schedule's `emit_event` cost. Bounded to prevent memory exhaustion.
     pub fn emit_event(
         &mut self,
         ty: String,
         attributes: Vec<(String, String)>,
     ) -> Result<(), LoomError> {
-        self.gas_meter.charge(GAS_EMIT_EVENT)?;
+        let gas_before = self.gas_meter.used();
+        self.gas_meter.charge(self.gas_schedule.emit_event)?;
         if self.events.len() >= MAX_EVENTS {
             return Err(LoomError::RuntimeError {
                 reason: "too many events".to_string(),
             });
         }
+        self.trace_call("norn_emit_event", gas_before, || ty.clone());
         self.events.push(HostEvent { ty, attributes });
         Ok(())
     }
@@ -239,6 +703,37 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_storage_usage_limits() {
+        let mut host = LoomHostState::new([1u8; 20], 100, 1_000_000, u64::MAX);
+
+        host.state_set(b"key", b"val").unwrap();
+        host.state_get(b"key").unwrap();
+        host.state_get(b"missing").unwrap();
+        assert_eq!(
+            host.storage_usage,
+            StorageUsage {
+                reads: 2,
+                bytes_read: 13,
+                writes: 1,
+                bytes_written: 6,
+            }
+        );
+
+        // Rewriting the same key still counts against the write limit.
+        for _ in 1..MAX_STORAGE_WRITES {
+            host.state_set(b"key", b"val").unwrap();
+        }
+        let err = host.state_set(b"key", b"val").unwrap_err();
+        assert!(matches!(
+            err,
+            LoomError::StorageLimitExceeded {
+                resource: "writes",
+                ..
+            }
+        ));
+    }
+
     #[test]
     fn test_transfer() {
         let mut host = test_host_state();
@@ -253,6 +748,144 @@ mod tests {
         assert_eq!(host.gas_meter.used(), GAS_TRANSFER);
     }
 
+    struct FixedBalances(HashMap<(Address, TokenId), Amount>);
+
+    impl BalanceView for FixedBalances {
+        fn balance(&self, address: &Address, token_id: &TokenId) -> Amount {
+            self.0.get(&(*address, *token_id)).copied().unwrap_or(0)
+        }
+    }
+
+    #[test]
+    fn test_balance_of() {
+        let mut host = test_host_state();
+        let alice = [1u8; 20];
+        let bob = [2u8; 20];
+
+        // Without a view every balance reads as zero.
+        assert_eq!(host.balance_of(&alice, &NATIVE_TOKEN_ID).unwrap(), 0);
+        assert_eq!(host.gas_meter.used(), GAS_BALANCE_QUERY);
+
+        let mut balances = HashMap::new();
+        balances.insert((alice, NATIVE_TOKEN_ID), 1_000);
+        host.balance_view = Some(Arc::new(FixedBalances(balances)));
+        assert_eq!(host.balance_of(&alice, &NATIVE_TOKEN_ID).unwrap(), 1_000);
+        assert_eq!(host.balance_of(&alice, &[9u8; 32]).unwrap(), 0);
+
+        // Queued transfers are reflected before settlement.
+        host.transfer(alice, bob, NATIVE_TOKEN_ID, 400).unwrap();
+        assert_eq!(host.balance_of(&alice, &NATIVE_TOKEN_ID).unwrap(), 600);
+        assert_eq!(host.balance_of(&bob, &NATIVE_TOKEN_ID).unwrap(), 400);
+    }
+
+    #[test]
+    fn test_transfer_rejects_overdraw_with_balance_view() {
+        let mut host = test_host_state();
+        let alice = [1u8; 20];
+        let bob = [2u8; 20];
+
+        // Without a view transfers are queued unchecked.
+        host.transfer(alice, bob, NATIVE_TOKEN_ID, 5_000).unwrap();
+        host.pending_transfers.clear();
+
+        let mut balances = HashMap::new();
+        balances.insert((alice, NATIVE_TOKEN_ID), 1_000);
+        host.balance_view = Some(Arc::new(FixedBalances(balances)));
+
+        host.transfer(alice, bob, NATIVE_TOKEN_ID, 600).unwrap();
+        // Only 400 is left once the queued transfer is counted.
+        assert!(host.transfer(alice, bob, NATIVE_TOKEN_ID, 500).is_err());
+        host.transfer(alice, bob, NATIVE_TOKEN_ID, 400).unwrap();
+        // Funds received earlier in the execution can be passed on.
+        host.transfer(bob, alice, NATIVE_TOKEN_ID, 1_000).unwrap();
+        assert_eq!(host.pending_transfers.len(), 3);
+    }
+
+    #[test]
+    fn test_derive_address() {
+        let mut host = test_host_state();
+        assert!(host.derive_address(b"deal-1").is_err());
+
+        host.current_loom_id = Some([5u8; 32]);
+        let a = host.derive_address(b"deal-1").unwrap();
+        let b = host.derive_address(b"deal-2").unwrap();
+        assert_ne!(a, b);
+        assert_eq!(a, host.derive_address(b"deal-1").unwrap());
+        assert_ne!(
+            a,
+            norn_types::primitives::derive_contract_address(&[5u8; 32])
+        );
+        assert!(host.sub_addresses.contains(&a));
+        assert_eq!(host.gas_meter.used(), 3 * GAS_DERIVE_ADDRESS);
+
+        assert!(host.derive_address(&[0u8; 257]).is_err());
+    }
+
+    #[test]
+    fn test_vrf_verify() {
+        let mut host = test_host_state();
+        let keypair = norn_crypto::keys::Keypair::from_seed(&[3u8; 32]);
+        let proof = norn_crypto::vrf::prove(&keypair, b"round-1");
+
+        let output = host
+            .vrf_verify(&keypair.public_key(), b"round-1", &proof)
+            .unwrap();
+        assert_eq!(output, norn_crypto::vrf::proof_to_hash(&proof).ok());
+        assert_eq!(
+            host.vrf_verify(&keypair.public_key(), b"round-2", &proof)
+                .unwrap(),
+            None
+        );
+        assert_eq!(host.gas_meter.used(), 2 * GAS_VRF_VERIFY);
+
+        let too_long = [0u8; MAX_VRF_INPUT_SIZE + 1];
+        assert!(host
+            .vrf_verify(&keypair.public_key(), &too_long, &proof)
+            .is_err());
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        let mut host = test_host_state();
+        let keypair = norn_crypto::keys::Keypair::from_seed(&[3u8; 32]);
+        let signature = keypair.sign(b"approve:7");
+
+        assert!(host
+            .verify_signature(&keypair.public_key(), b"approve:7", &signature)
+            .unwrap());
+        assert!(!host
+            .verify_signature(&keypair.public_key(), b"approve:8", &signature)
+            .unwrap());
+        // A malformed key is an invalid signature, not a trap.
+        assert!(!host
+            .verify_signature(&[0xff; 32], b"approve:7", &signature)
+            .unwrap());
+        assert_eq!(host.gas_meter.used(), 3 * GAS_SIGNATURE_VERIFY);
+
+        let too_long = [0u8; MAX_SIGNED_MESSAGE_SIZE + 1];
+        assert!(host
+            .verify_signature(&keypair.public_key(), &too_long, &signature)
+            .is_err());
+    }
+
+    #[test]
+    fn test_decrypt() {
+        let mut host = test_host_state();
+        let key = [9u8; 32];
+        let (nonce, ciphertext) = norn_crypto::encryption::seal(&key, b"bid:500").unwrap();
+
+        assert_eq!(
+            host.decrypt(&key, &nonce, &ciphertext).unwrap(),
+            Some(b"bid:500".to_vec())
+        );
+        assert_eq!(host.decrypt(&[8u8; 32], &nonce, &ciphertext).unwrap(), None);
+        let per_call = GAS_DECRYPT + GAS_BYTE_READ * ciphertext.len() as u64;
+        assert_eq!(host.gas_meter.used(), 2 * per_call);
+
+        let too_long = vec![0u8; MAX_DECRYPT_SIZE + 1];
+        assert!(host.decrypt(&key, &nonce, &too_long).is_err());
+    }
+
     #[test]
     fn test_log() {
         let mut host = test_host_state();
@@ -2,15 +2,23 @@ use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
 use norn_crypto::hash::blake3_hash;
-use norn_types::loom::{Loom, LoomBytecode, LoomConfig, LoomStateTransition, Participant};
+use norn_types::loom::{
+    Loom, LoomBytecode, LoomConfig, LoomStateTransition, OperatorHandover, OperatorSet,
+    Participant, ParticipantRole,
+};
 use norn_types::primitives::*;
+use norn_types::weave::LoomAnchor;
 
 use crate::call_stack::CallStack;
+use crate::dispute::{Dispute, DisputeRegistry, EvidenceBundle};
 use crate::error::LoomError;
 use crate::gas::DEFAULT_GAS_LIMIT;
-use crate::host::{LoomHostState, PendingTransfer};
-use crate::runtime::LoomRuntime;
-use crate::state::LoomState;
+use crate::host::{
+    ExecutionTracer, HostCallTrace, LoomHostState, PendingInstantiation, PendingTransfer,
+    SharedBalanceView, SharedCodes,
+};
+use crate::runtime::{validate_module, LoomRuntime};
+use crate::state::{LoomState, LoomStateSnapshot};
 
 /// Result of a state-changing loom execution, wrapping the consensus-level
 /// `LoomStateTransition` with runtime-level data (gas, logs, events, transfers).
@@ -26,6 +34,8 @@ pub struct ExecutionOutcome {
     pub pending_transfers: Vec<PendingTransfer>,
     /// Structured events emitted during execution.
     pub events: Vec<LoomEvent>,
+    /// Looms created from stored code during execution, in creation order.
+    pub instantiated: Vec<LoomId>,
 }
 
 /// Result of a read-only loom query.
@@ -41,6 +51,45 @@ pub struct QueryOutcome {
     pub events: Vec<LoomEvent>,
 }
 
+/// Gas charged to one host function during a traced execution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostGasUsage {
+    /// Host function name.
+    pub host_fn: &'static str,
+    /// Number of calls.
+    pub calls: u64,
+    /// Total gas charged by those calls.
+    pub gas: u64,
+}
+
+/// Result of a traced loom execution. Nothing is committed.
+#[derive(Debug)]
+pub struct ExecutionTrace {
+    /// Whether execution succeeded.
+    pub success: bool,
+    /// Error message if execution failed.
+    pub error: Option<String>,
+    /// Output bytes (empty on failure).
+    pub output: Vec<u8>,
+    /// Total gas consumed.
+    pub gas_used: u64,
+    /// Gas spent on Wasm instructions rather than host calls.
+    pub instruction_gas: u64,
+    /// Gas per host function for calls made by the traced loom itself
+    /// (nested calls are counted in `norn_call_contract` / `norn_instantiate`).
+    pub host_gas: Vec<HostGasUsage>,
+    /// Every recorded host call, including nested ones.
+    pub calls: Vec<HostCallTrace>,
+    /// Messages passed to `norn_debug_log`.
+    pub debug_logs: Vec<String>,
+    /// Log messages emitted during execution.
+    pub logs: Vec<String>,
+    /// Structured events emitted during execution.
+    pub events: Vec<LoomEvent>,
+    /// Whether trace entries were dropped after a limit was reached.
+    pub truncated: bool,
+}
+
 /// A structured event emitted by a loom contract.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct LoomEvent {
@@ -55,10 +104,15 @@ pub struct LoomEvent {
 pub struct LoomManager {
     /// Registered looms keyed by their LoomId.
     looms: HashMap<LoomId, Loom>,
-    /// Deployed bytecodes keyed by LoomId.
-    bytecodes: HashMap<LoomId, LoomBytecode>,
+    /// Code ID each loom runs, keyed by LoomId.
+    code_ids: HashMap<LoomId, Hash>,
     /// Per-loom key-value state.
     states: HashMap<LoomId, LoomState>,
+    /// Bytecode keyed by code ID (blake3 hash of the bytecode). Looms running
+    /// the same bytecode share one entry.
+    codes: SharedCodes,
+    /// Disputes over loom executions.
+    disputes: DisputeRegistry,
 }
 
 impl LoomManager {
@@ -66,8 +120,10 @@ impl LoomManager {
     pub fn new() -> Self {
         Self {
             looms: HashMap::new(),
-            bytecodes: HashMap::new(),
+            code_ids: HashMap::new(),
             states: HashMap::new(),
+            codes: Arc::new(HashMap::new()),
+            disputes: DisputeRegistry::new(),
         }
     }
 
@@ -83,6 +139,12 @@ impl LoomManager {
     ) -> Result<LoomId, LoomError> {
         let loom_id = config.loom_id;
 
+        OperatorSet::from_config(&operator, &config)
+            .validate()
+            .map_err(|e| LoomError::InvalidOperatorSet {
+                reason: e.to_string(),
+            })?;
+
         // Validate bytecode is not empty.
         if bytecode.is_empty() {
             return Err(LoomError::InvalidBytecode {
@@ -90,13 +152,7 @@ impl LoomManager {
             });
         }
 
-        let wasm_hash = blake3_hash(&bytecode);
-
-        let loom_bytecode = LoomBytecode {
-            loom_id,
-            wasm_hash,
-            bytecode,
-        };
+        let code_id = self.store_code(bytecode)?;
 
         let initial_state = LoomState::new(loom_id);
         let state_hash = initial_state.compute_hash();
@@ -112,13 +168,16 @@ impl LoomManager {
         };
 
         self.looms.insert(loom_id, loom);
-        self.bytecodes.insert(loom_id, loom_bytecode);
+        self.code_ids.insert(loom_id, code_id);
         self.states.insert(loom_id, initial_state);
 
         Ok(loom_id)
     }
 
     /// Add a participant to a loom.
+    ///
+    /// Operator keys join with the operator role; everyone else joins as a
+    /// submitter.
     pub fn join(
         &mut self,
         loom_id: &LoomId,
@@ -152,16 +211,96 @@ impl LoomManager {
             return Ok(());
         }
 
+        let role = if loom.operator_set().contains(&pubkey) {
+            ParticipantRole::Operator
+        } else {
+            ParticipantRole::Submitter
+        };
         loom.participants.push(Participant {
             pubkey,
             address,
             joined_at: timestamp,
             active: true,
+            role,
         });
 
         Ok(())
     }
 
+    /// Change a participant's role. Only loom operators may change roles,
+    /// and the operator role is reserved for operator keys.
+    pub fn set_participant_role(
+        &mut self,
+        loom_id: &LoomId,
+        caller: &PublicKey,
+        address: &Address,
+        role: ParticipantRole,
+    ) -> Result<(), LoomError> {
+        let loom = self
+            .looms
+            .get_mut(loom_id)
+            .ok_or(LoomError::LoomNotFound { loom_id: *loom_id })?;
+        let operators = loom.operator_set();
+        if !operators.contains(caller) {
+            return Err(LoomError::PermissionDenied {
+                reason: "only loom operators may change participant roles".to_string(),
+            });
+        }
+
+        let participant = loom
+            .participants
+            .iter_mut()
+            .find(|p| p.address == *address && p.active)
+            .ok_or(LoomError::NotParticipant { address: *address })?;
+        if (role == ParticipantRole::Operator) != operators.contains(&participant.pubkey) {
+            return Err(LoomError::PermissionDenied {
+                reason: "the operator role is held exactly by operator keys".to_string(),
+            });
+        }
+        participant.role = role;
+        Ok(())
+    }
+
+    /// Replace one of a loom's operator keys.
+    ///
+    /// The handover's signatures must already have been checked by the
+    /// weave; this applies the rotation and moves the operator role from the
+    /// old key's participant entry to the new key's.
+    pub fn rotate_operator(&mut self, handover: &OperatorHandover) -> Result<(), LoomError> {
+        let loom = self
+            .looms
+            .get_mut(&handover.loom_id)
+            .ok_or(LoomError::LoomNotFound {
+                loom_id: handover.loom_id,
+            })?;
+        let mut operators = loom.operator_set();
+        operators
+            .rotate(&handover.old_operator, handover.new_operator)
+            .map_err(|e| LoomError::InvalidOperatorSet {
+                reason: e.to_string(),
+            })?;
+
+        if loom.operator == handover.old_operator {
+            loom.operator = handover.new_operator;
+        } else if let Some(op) = loom
+            .config
+            .co_operators
+            .iter_mut()
+            .find(|op| **op == handover.old_operator)
+        {
+            *op = handover.new_operator;
+        }
+        for p in &mut loom.participants {
+            if p.pubkey == handover.old_operator {
+                p.role = ParticipantRole::Submitter;
+            } else if p.pubkey == handover.new_operator {
+                p.role = ParticipantRole::Operator;
+            }
+        }
+        loom.last_updated = handover.timestamp;
+        Ok(())
+    }
+
     /// Remove (deactivate) a participant from a loom.
     pub fn leave(&mut self, loom_id: &LoomId, address: &Address) -> Result<(), LoomError> {
         let loom = self
@@ -191,6 +330,23 @@ impl LoomManager {
         sender: Address,
         block_height: u64,
         timestamp: u64,
+    ) -> Result<ExecutionOutcome, LoomError> {
+        self.execute_with_balances(loom_id, input, sender, block_height, timestamp, None)
+    }
+
+    /// Execute a transaction with a view of on-ledger balances.
+    ///
+    /// Same as [`execute`](Self::execute), but the contract can read account
+    /// balances from `balances` through the `norn_balance_of` host function.
+    #[tracing::instrument(name = "loom", skip_all, fields(loom_id = %hex::encode(loom_id)))]
+    pub fn execute_with_balances(
+        &mut self,
+        loom_id: &LoomId,
+        input: &[u8],
+        sender: Address,
+        block_height: u64,
+        timestamp: u64,
+        balances: Option<SharedBalanceView>,
     ) -> Result<ExecutionOutcome, LoomError> {
         // Validate loom exists.
         let loom = self
@@ -198,13 +354,16 @@ impl LoomManager {
             .get(loom_id)
             .ok_or(LoomError::LoomNotFound { loom_id: *loom_id })?;
 
-        // Validate sender is a participant.
-        let is_participant = loom
+        // Validate sender is a participant allowed to execute.
+        let participant = loom
             .participants
             .iter()
-            .any(|p| p.address == sender && p.active);
-        if !is_participant {
-            return Err(LoomError::NotParticipant { address: sender });
+            .find(|p| p.address == sender && p.active)
+            .ok_or(LoomError::NotParticipant { address: sender })?;
+        if !participant.role.can_execute() {
+            return Err(LoomError::PermissionDenied {
+                reason: "observers cannot execute".to_string(),
+            });
         }
 
         // Get current state.
@@ -218,16 +377,17 @@ impl LoomManager {
         let mut host_state = LoomHostState::new(sender, block_height, timestamp, DEFAULT_GAS_LIMIT);
         host_state.state = state.data.clone();
         host_state.current_loom_id = Some(*loom_id);
+        host_state.balance_view = balances;
+        host_state.codes = Some(self.codes.clone());
 
         // Get bytecode.
-        let bytecode_entry = self
-            .bytecodes
-            .get(loom_id)
+        let bytecode = self
+            .get_bytecode_bytes(loom_id)
             .ok_or(LoomError::LoomNotFound { loom_id: *loom_id })?;
 
         // Instantiate and execute.
         let runtime = LoomRuntime::new()?;
-        let mut instance = runtime.instantiate(&bytecode_entry.bytecode, host_state)?;
+        let mut instance = runtime.instantiate(bytecode, host_state)?;
         let outputs = instance.call_execute(input)?;
 
         // Capture gas BEFORE consuming the instance.
@@ -246,6 +406,9 @@ impl LoomManager {
             })
             .collect();
 
+        // Reject instance IDs that are already taken before committing anything.
+        self.check_instances(&host_state.instantiations)?;
+
         // Update the loom's stored state.
         let loom_state = self
             .states
@@ -263,6 +426,8 @@ impl LoomManager {
         loom.version += 1;
         loom.last_updated = timestamp;
 
+        let instantiated = self.register_instances(loom_id, host_state.instantiations, timestamp);
+
         Ok(ExecutionOutcome {
             transition: LoomStateTransition {
                 loom_id: *loom_id,
@@ -275,6 +440,88 @@ impl LoomManager {
             logs,
             pending_transfers,
             events,
+            instantiated,
+        })
+    }
+
+    /// Execute a transaction with tracing enabled, without committing it.
+    ///
+    /// Runs the same execution as [`execute_with_balances`](Self::execute_with_balances)
+    /// but records every host call and `norn_debug_log` message, and returns
+    /// a trace even when the execution fails. Participation is not checked,
+    /// so a sender can be traced before joining.
+    pub fn trace_with_balances(
+        &self,
+        loom_id: &LoomId,
+        input: &[u8],
+        sender: Address,
+        block_height: u64,
+        timestamp: u64,
+        balances: Option<SharedBalanceView>,
+    ) -> Result<ExecutionTrace, LoomError> {
+        let state = self
+            .states
+            .get(loom_id)
+            .ok_or(LoomError::LoomNotFound { loom_id: *loom_id })?;
+
+        let mut host_state = LoomHostState::new(sender, block_height, timestamp, DEFAULT_GAS_LIMIT);
+        host_state.state = state.data.clone();
+        host_state.current_loom_id = Some(*loom_id);
+        host_state.balance_view = balances;
+        host_state.codes = Some(self.codes.clone());
+        host_state.tracer = Some(ExecutionTracer::default());
+
+        let bytecode = self
+            .get_bytecode_bytes(loom_id)
+            .ok_or(LoomError::LoomNotFound { loom_id: *loom_id })?;
+
+        let runtime = LoomRuntime::new()?;
+        let mut instance = runtime.instantiate(bytecode, host_state)?;
+        let result = instance.call_execute(input);
+        let gas_used = instance.gas_used();
+        let host_state = instance.into_host_state();
+        let tracer = host_state.tracer.unwrap_or_default();
+
+        let mut host_gas: Vec<HostGasUsage> = Vec::new();
+        for call in tracer.calls.iter().filter(|c| c.depth == 0) {
+            match host_gas.iter_mut().find(|u| u.host_fn == call.host_fn) {
+                Some(usage) => {
+                    usage.calls += 1;
+                    usage.gas += call.gas;
+                }
+                None => host_gas.push(HostGasUsage {
+                    host_fn: call.host_fn,
+                    calls: 1,
+                    gas: call.gas,
+                }),
+            }
+        }
+        host_gas.sort_by_key(|u| u.host_fn);
+        let host_total: u64 = host_gas.iter().map(|u| u.gas).sum();
+
+        let (success, error, output) = match result {
+            Ok(output) => (true, None, output),
+            Err(e) => (false, Some(e.to_string()), Vec::new()),
+        };
+        Ok(ExecutionTrace {
+            success,
+            error,
+            output,
+            gas_used,
+            instruction_gas: gas_used.saturating_sub(host_total),
+            host_gas,
+            calls: tracer.calls,
+            debug_logs: tracer.debug_logs,
+            logs: host_state.logs,
+            events: host_state
+                .events
+                .into_iter()
+                .map(|e| LoomEvent {
+                    ty: e.ty,
+                    attributes: e.attributes,
+                })
+                .collect(),
+            truncated: tracer.truncated,
         })
     }
 
@@ -283,6 +530,7 @@ impl LoomManager {
     /// Sets up shared state, bytecode, and call-stack references so that the
     /// executing contract can call other contracts via the `norn_call_contract`
     /// host function.
+    #[tracing::instrument(name = "loom", skip_all, fields(loom_id = %hex::encode(loom_id)))]
     pub fn execute_with_cross_call(
         &mut self,
         loom_id: &LoomId,
@@ -296,12 +544,15 @@ impl LoomManager {
             .looms
             .get(loom_id)
             .ok_or(LoomError::LoomNotFound { loom_id: *loom_id })?;
-        let is_participant = loom
+        let participant = loom
             .participants
             .iter()
-            .any(|p| p.address == sender && p.active);
-        if !is_participant {
-            return Err(LoomError::NotParticipant { address: sender });
+            .find(|p| p.address == sender && p.active)
+            .ok_or(LoomError::NotParticipant { address: sender })?;
+        if !participant.role.can_execute() {
+            return Err(LoomError::PermissionDenied {
+                reason: "observers cannot execute".to_string(),
+            });
         }
 
         let state = self
@@ -323,9 +574,9 @@ impl LoomManager {
 
         // Collect all bytecodes into the shared map.
         let shared_bytecodes: HashMap<LoomId, Vec<u8>> = self
-            .bytecodes
+            .code_ids
             .iter()
-            .map(|(id, b)| (*id, b.bytecode.clone()))
+            .filter_map(|(id, code_id)| Some((*id, self.codes.get(code_id)?.clone())))
             .collect();
         let loom_bytecodes = Arc::new(Mutex::new(shared_bytecodes));
 
@@ -336,16 +587,16 @@ impl LoomManager {
         host_state.loom_states = Some(loom_states.clone());
         host_state.loom_bytecodes = Some(loom_bytecodes.clone());
         host_state.current_loom_id = Some(*loom_id);
+        host_state.codes = Some(self.codes.clone());
 
         // Get bytecode.
-        let bytecode_entry = self
-            .bytecodes
-            .get(loom_id)
+        let bytecode = self
+            .get_bytecode_bytes(loom_id)
             .ok_or(LoomError::LoomNotFound { loom_id: *loom_id })?;
 
         // Instantiate and execute.
         let runtime = LoomRuntime::new()?;
-        let mut instance = runtime.instantiate(&bytecode_entry.bytecode, host_state)?;
+        let mut instance = runtime.instantiate(bytecode, host_state)?;
         let outputs = instance.call_execute(input)?;
         let gas_used = instance.gas_used();
         let host_state = instance.into_host_state();
@@ -360,6 +611,9 @@ impl LoomManager {
             })
             .collect();
 
+        // Reject instance IDs that are already taken before committing anything.
+        self.check_instances(&host_state.instantiations)?;
+
         // Commit the primary contract's state.
         let loom_state = self
             .states
@@ -393,6 +647,8 @@ impl LoomManager {
         loom.version += 1;
         loom.last_updated = timestamp;
 
+        let instantiated = self.register_instances(loom_id, host_state.instantiations, timestamp);
+
         Ok(ExecutionOutcome {
             transition: LoomStateTransition {
                 loom_id: *loom_id,
@@ -405,6 +661,7 @@ impl LoomManager {
             logs,
             pending_transfers,
             events,
+            instantiated,
         })
     }
 
@@ -422,9 +679,48 @@ impl LoomManager {
         self.looms.get(loom_id)
     }
 
-    /// Get a reference to a loom's bytecode.
-    pub fn get_bytecode(&self, loom_id: &LoomId) -> Option<&LoomBytecode> {
-        self.bytecodes.get(loom_id)
+    /// Get a loom's operators and state-signing threshold.
+    pub fn operator_set(&self, loom_id: &LoomId) -> Option<OperatorSet> {
+        self.looms.get(loom_id).map(Loom::operator_set)
+    }
+
+    /// Build an unsigned anchor of a loom's current state.
+    ///
+    /// Operators sign [`norn_types::loom::loom_state_signing_data`] for the
+    /// returned anchor; the weave accepts it once the loom's threshold of
+    /// operator signatures is attached.
+    pub fn prepare_anchor(
+        &self,
+        loom_id: &LoomId,
+        block_height: u64,
+        timestamp: Timestamp,
+    ) -> Result<LoomAnchor, LoomError> {
+        let loom = self
+            .looms
+            .get(loom_id)
+            .ok_or(LoomError::LoomNotFound { loom_id: *loom_id })?;
+        Ok(LoomAnchor {
+            loom_id: *loom_id,
+            state_hash: loom.state_hash,
+            block_height,
+            timestamp,
+            signatures: Vec::new(),
+        })
+    }
+
+    /// Get a loom's bytecode record.
+    pub fn get_bytecode(&self, loom_id: &LoomId) -> Option<LoomBytecode> {
+        let code_id = self.code_ids.get(loom_id)?;
+        Some(LoomBytecode {
+            loom_id: *loom_id,
+            wasm_hash: *code_id,
+            bytecode: self.codes.get(code_id)?.clone(),
+        })
+    }
+
+    /// Get the code ID a loom runs.
+    pub fn get_code_id(&self, loom_id: &LoomId) -> Option<Hash> {
+        self.code_ids.get(loom_id).copied()
     }
 
     /// Get a reference to a loom's state.
@@ -432,19 +728,124 @@ impl LoomManager {
         self.states.get(loom_id)
     }
 
+    /// Export a loom's state as a verifiable snapshot (for backups and migration).
+    pub fn export_state(&self, loom_id: &LoomId) -> Result<LoomStateSnapshot, LoomError> {
+        if !self.looms.contains_key(loom_id) {
+            return Err(LoomError::LoomNotFound { loom_id: *loom_id });
+        }
+        Ok(self
+            .states
+            .get(loom_id)
+            .map(LoomState::export)
+            .unwrap_or_else(|| LoomState::new(*loom_id).export()))
+    }
+
+    /// Replace a loom's state with a verified snapshot.
+    ///
+    /// The snapshot must have been exported from the same loom. The import
+    /// counts as a state transition: the loom's state hash is updated and its
+    /// version bumped. Returns the new state hash.
+    pub fn import_state(
+        &mut self,
+        loom_id: &LoomId,
+        snapshot: LoomStateSnapshot,
+    ) -> Result<Hash, LoomError> {
+        if snapshot.loom_id != *loom_id {
+            return Err(LoomError::StateError {
+                reason: format!(
+                    "snapshot belongs to loom {}, not {}",
+                    hex::encode(snapshot.loom_id),
+                    hex::encode(loom_id)
+                ),
+            });
+        }
+        let loom = self
+            .looms
+            .get_mut(loom_id)
+            .ok_or(LoomError::LoomNotFound { loom_id: *loom_id })?;
+        let state = LoomState::import(snapshot)?;
+        let state_hash = state.compute_hash();
+        loom.state_hash = state_hash;
+        loom.version += 1;
+        self.states.insert(*loom_id, state);
+        Ok(state_hash)
+    }
+
+    /// Open a dispute over a loom execution. The loom operator defends it.
+    ///
+    /// The evidence must reference a deployed loom and stored code, and its
+    /// prior state must verify. Returns the dispute ID.
+    pub fn open_dispute(
+        &mut self,
+        evidence: EvidenceBundle,
+        challenger: Address,
+        bond: Amount,
+        now: u64,
+    ) -> Result<Hash, LoomError> {
+        let loom = self
+            .looms
+            .get(&evidence.loom_id)
+            .ok_or(LoomError::LoomNotFound {
+                loom_id: evidence.loom_id,
+            })?;
+        let defendant = norn_crypto::address::pubkey_to_address(&loom.operator);
+        let bytecode = self
+            .codes
+            .get(&evidence.code_id)
+            .ok_or(LoomError::CodeNotFound {
+                code_id: evidence.code_id,
+            })?;
+        evidence.validate(bytecode)?;
+        self.disputes
+            .open(evidence, challenger, defendant, bond, now)
+    }
+
+    /// Resolve a dispute by re-executing its evidence.
+    pub fn resolve_dispute(&mut self, dispute_id: &Hash, now: u64) -> Result<&Dispute, LoomError> {
+        let code_id = self
+            .disputes
+            .get(dispute_id)
+            .ok_or(LoomError::DisputeNotFound {
+                dispute_id: *dispute_id,
+            })?
+            .evidence
+            .code_id;
+        let bytecode = self
+            .codes
+            .get(&code_id)
+            .ok_or(LoomError::CodeNotFound { code_id })?;
+        self.disputes.resolve(dispute_id, bytecode, now)
+    }
+
+    /// Get a dispute by ID.
+    pub fn get_dispute(&self, dispute_id: &Hash) -> Option<&Dispute> {
+        self.disputes.get(dispute_id)
+    }
+
+    /// List disputes in the order they were opened, optionally for one loom.
+    pub fn list_disputes(&self, loom_id: Option<&LoomId>) -> Vec<&Dispute> {
+        self.disputes.list(loom_id)
+    }
+
     /// Restore a previously persisted loom (used during state rebuild).
+    ///
+    /// The loom's code must already be stored with [`store_code`](Self::store_code).
     pub fn restore_loom(
         &mut self,
         loom_id: LoomId,
         loom: Loom,
-        bytecode: LoomBytecode,
+        code_id: Hash,
         state_data: HashMap<Vec<u8>, Vec<u8>>,
-    ) {
+    ) -> Result<(), LoomError> {
+        if !self.codes.contains_key(&code_id) {
+            return Err(LoomError::CodeNotFound { code_id });
+        }
         let mut state = LoomState::new(loom_id);
         state.data = state_data;
         self.looms.insert(loom_id, loom);
-        self.bytecodes.insert(loom_id, bytecode);
+        self.code_ids.insert(loom_id, code_id);
         self.states.insert(loom_id, state);
+        Ok(())
     }
 
     /// List all deployed looms.
@@ -461,32 +862,70 @@ impl LoomManager {
         block_height: u64,
         timestamp: u64,
     ) -> Result<QueryOutcome, LoomError> {
-        // Validate loom exists.
-        let _loom = self
-            .looms
-            .get(loom_id)
-            .ok_or(LoomError::LoomNotFound { loom_id: *loom_id })?;
+        self.query_with_balances(loom_id, input, sender, block_height, timestamp, None)
+    }
 
+    /// Query a loom contract with a view of on-ledger balances.
+    pub fn query_with_balances(
+        &self,
+        loom_id: &LoomId,
+        input: &[u8],
+        sender: Address,
+        block_height: u64,
+        timestamp: u64,
+        balances: Option<SharedBalanceView>,
+    ) -> Result<QueryOutcome, LoomError> {
         // Get current state.
         let state = self
             .states
             .get(loom_id)
             .ok_or(LoomError::LoomNotFound { loom_id: *loom_id })?;
 
-        // Set up host state with the loom's current data.
+        self.query_with_state(
+            loom_id,
+            state.data.clone(),
+            input,
+            sender,
+            block_height,
+            timestamp,
+            balances,
+        )
+    }
+
+    /// Query a loom contract against the given state data instead of its
+    /// current state, e.g. a historical snapshot. Cross-loom calls made by
+    /// the contract still see the other looms' current state.
+    #[allow(clippy::too_many_arguments)]
+    pub fn query_with_state(
+        &self,
+        loom_id: &LoomId,
+        state_data: HashMap<Vec<u8>, Vec<u8>>,
+        input: &[u8],
+        sender: Address,
+        block_height: u64,
+        timestamp: u64,
+        balances: Option<SharedBalanceView>,
+    ) -> Result<QueryOutcome, LoomError> {
+        // Validate loom exists.
+        let _loom = self
+            .looms
+            .get(loom_id)
+            .ok_or(LoomError::LoomNotFound { loom_id: *loom_id })?;
+
+        // Set up host state with the given data.
         let mut host_state = LoomHostState::new(sender, block_height, timestamp, DEFAULT_GAS_LIMIT);
-        host_state.state = state.data.clone();
+        host_state.state = state_data;
         host_state.current_loom_id = Some(*loom_id);
+        host_state.balance_view = balances;
 
         // Get bytecode.
-        let bytecode_entry = self
-            .bytecodes
-            .get(loom_id)
+        let bytecode = self
+            .get_bytecode_bytes(loom_id)
             .ok_or(LoomError::LoomNotFound { loom_id: *loom_id })?;
 
         // Instantiate and query (read-only — state is discarded).
         let runtime = LoomRuntime::new()?;
-        let mut instance = runtime.instantiate(&bytecode_entry.bytecode, host_state)?;
+        let mut instance = runtime.instantiate(bytecode, host_state)?;
         let outputs = instance.call_query(input)?;
 
         // Capture gas and logs before discarding state.
@@ -518,6 +957,9 @@ impl LoomManager {
     /// If `init_msg` is provided, it is passed to the init function (new SDK
     /// v0.13+ contracts). If `None`, an empty byte slice is used (compatible
     /// with both old `()->()` and new `(i32,i32)->i32` init signatures).
+    ///
+    /// The bytecode is checked with `validate_module` first, so modules the
+    /// runtime cannot execute deterministically are rejected here.
     pub fn upload_bytecode(
         &mut self,
         loom_id: &LoomId,
@@ -535,13 +977,7 @@ impl LoomManager {
                 reason: "bytecode cannot be empty".to_string(),
             });
         }
-
-        let wasm_hash = blake3_hash(&bytecode);
-        let loom_bytecode = LoomBytecode {
-            loom_id: *loom_id,
-            wasm_hash,
-            bytecode,
-        };
+        validate_module(&bytecode)?;
 
         // Initialize state if not present.
         if !self.states.contains_key(loom_id) {
@@ -556,7 +992,7 @@ impl LoomManager {
 
         // Instantiate and call init().
         let runtime = LoomRuntime::new()?;
-        let mut instance = runtime.instantiate(&loom_bytecode.bytecode, host_state)?;
+        let mut instance = runtime.instantiate(&bytecode, host_state)?;
         let init_input = init_msg.as_deref().unwrap_or(&[]);
         instance.call_init(init_input)?;
 
@@ -570,15 +1006,16 @@ impl LoomManager {
         let loom = self.looms.get_mut(loom_id).unwrap();
         loom.state_hash = new_hash;
 
-        // Store bytecode.
-        self.bytecodes.insert(*loom_id, loom_bytecode);
+        // Store bytecode, shared with any loom already running it.
+        let code_id = self.store_code(bytecode)?;
+        self.code_ids.insert(*loom_id, code_id);
 
         Ok(())
     }
 
     /// Check if a loom has bytecode uploaded.
     pub fn has_bytecode(&self, loom_id: &LoomId) -> bool {
-        self.bytecodes.contains_key(loom_id)
+        self.code_ids.contains_key(loom_id)
     }
 
     /// Get the number of active participants for a loom.
@@ -596,7 +1033,107 @@ impl LoomManager {
 
     /// Get raw bytecode bytes for persistence.
     pub fn get_bytecode_bytes(&self, loom_id: &LoomId) -> Option<&[u8]> {
-        self.bytecodes.get(loom_id).map(|b| b.bytecode.as_slice())
+        let code_id = self.code_ids.get(loom_id)?;
+        self.codes.get(code_id).map(|b| b.as_slice())
+    }
+
+    /// Store reusable bytecode and return its code ID.
+    ///
+    /// Stored code can be instantiated by contracts via `norn_instantiate`.
+    /// Storing the same bytecode twice returns the same code ID.
+    pub fn store_code(&mut self, bytecode: Vec<u8>) -> Result<Hash, LoomError> {
+        if bytecode.is_empty() {
+            return Err(LoomError::InvalidBytecode {
+                reason: "bytecode cannot be empty".to_string(),
+            });
+        }
+        let code_id = blake3_hash(&bytecode);
+        if !self.codes.contains_key(&code_id) {
+            Arc::make_mut(&mut self.codes).insert(code_id, bytecode);
+        }
+        Ok(code_id)
+    }
+
+    /// Get stored bytecode by code ID.
+    pub fn get_code(&self, code_id: &Hash) -> Option<&[u8]> {
+        self.codes.get(code_id).map(|b| b.as_slice())
+    }
+
+    /// List stored code IDs with their bytecode, sorted by code ID.
+    pub fn list_codes(&self) -> Vec<(&Hash, &[u8])> {
+        let mut codes: Vec<_> = self
+            .codes
+            .iter()
+            .map(|(id, b)| (id, b.as_slice()))
+            .collect();
+        codes.sort_by_key(|(id, _)| **id);
+        codes
+    }
+
+    /// Number of looms running the given code.
+    pub fn code_instance_count(&self, code_id: &Hash) -> usize {
+        self.code_ids.values().filter(|id| *id == code_id).count()
+    }
+
+    /// Fail if any pending instantiation would overwrite an existing loom.
+    fn check_instances(&self, instances: &[PendingInstantiation]) -> Result<(), LoomError> {
+        match instances
+            .iter()
+            .find(|i| self.looms.contains_key(&i.loom_id))
+        {
+            Some(i) => Err(LoomError::LoomAlreadyExists { loom_id: i.loom_id }),
+            None => Ok(()),
+        }
+    }
+
+    /// Register looms created during execution of `parent`.
+    ///
+    /// Instances inherit the parent's operator and participant limits and
+    /// start active with the state produced by their `init`.
+    fn register_instances(
+        &mut self,
+        parent: &LoomId,
+        instances: Vec<PendingInstantiation>,
+        timestamp: Timestamp,
+    ) -> Vec<LoomId> {
+        let Some(parent_loom) = self.looms.get(parent).cloned() else {
+            return Vec::new();
+        };
+        let mut ids = Vec::with_capacity(instances.len());
+        for inst in instances {
+            if !self.codes.contains_key(&inst.code_id) {
+                continue;
+            }
+            let mut state = LoomState::new(inst.loom_id);
+            state.data = inst.state;
+            let state_hash = state.compute_hash();
+            let loom = Loom {
+                config: LoomConfig {
+                    loom_id: inst.loom_id,
+                    name: format!(
+                        "instance-{:02x}{:02x}{:02x}{:02x}",
+                        inst.loom_id[0], inst.loom_id[1], inst.loom_id[2], inst.loom_id[3]
+                    ),
+                    max_participants: parent_loom.config.max_participants,
+                    min_participants: parent_loom.config.min_participants,
+                    accepted_tokens: parent_loom.config.accepted_tokens.clone(),
+                    config_data: Vec::new(),
+                    co_operators: parent_loom.config.co_operators.clone(),
+                    operator_threshold: parent_loom.config.operator_threshold,
+                },
+                operator: parent_loom.operator,
+                participants: Vec::new(),
+                state_hash,
+                version: 0,
+                active: true,
+                last_updated: timestamp,
+            };
+            self.looms.insert(inst.loom_id, loom);
+            self.code_ids.insert(inst.loom_id, inst.code_id);
+            self.states.insert(inst.loom_id, state);
+            ids.push(inst.loom_id);
+        }
+        ids
     }
 
     /// Register a loom metadata entry (from on-chain registration) without bytecode.
@@ -626,6 +1163,8 @@ mod tests {
             min_participants: 1,
             accepted_tokens: vec![NATIVE_TOKEN_ID],
             config_data: vec![],
+            co_operators: vec![],
+            operator_threshold: 1,
         }
     }
 
@@ -666,6 +1205,37 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_deploy_multi_operator() {
+        let mut manager = LoomManager::new();
+        let loom_id = [1u8; 32];
+        let mut config = test_config(loom_id);
+        config.co_operators = vec![[3u8; 32], [4u8; 32]];
+        config.operator_threshold = 2;
+        manager
+            .deploy(config.clone(), [2u8; 32], simple_wasm(), 1000)
+            .unwrap();
+
+        let ops = manager.operator_set(&loom_id).unwrap();
+        assert_eq!(ops.operators, vec![[2u8; 32], [3u8; 32], [4u8; 32]]);
+        assert_eq!(ops.threshold, 2);
+
+        let anchor = manager.prepare_anchor(&loom_id, 7, 2000).unwrap();
+        assert_eq!(
+            anchor.state_hash,
+            manager.get_loom(&loom_id).unwrap().state_hash
+        );
+        assert!(anchor.signatures.is_empty());
+
+        // A threshold above the operator count is rejected.
+        config.loom_id = [5u8; 32];
+        config.operator_threshold = 4;
+        assert!(matches!(
+            manager.deploy(config, [2u8; 32], simple_wasm(), 1000),
+            Err(LoomError::InvalidOperatorSet { .. })
+        ));
+    }
+
     #[test]
     fn test_join_and_leave() {
         let mut manager = LoomManager::new();
@@ -740,6 +1310,78 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_participant_roles() {
+        let mut manager = LoomManager::new();
+        let loom_id = [1u8; 32];
+        let operator = [2u8; 32];
+        manager
+            .deploy(test_config(loom_id), operator, simple_wasm(), 1000)
+            .unwrap();
+
+        let op_addr = [2u8; 20];
+        let sender = [3u8; 20];
+        manager.join(&loom_id, operator, op_addr, 1001).unwrap();
+        manager.join(&loom_id, [3u8; 32], sender, 1001).unwrap();
+        let roles: Vec<_> = manager
+            .get_loom(&loom_id)
+            .unwrap()
+            .participants
+            .iter()
+            .map(|p| p.role)
+            .collect();
+        assert_eq!(
+            roles,
+            vec![ParticipantRole::Operator, ParticipantRole::Submitter]
+        );
+
+        // Only operators change roles, and observers cannot execute.
+        assert!(manager
+            .set_participant_role(&loom_id, &[3u8; 32], &sender, ParticipantRole::Observer)
+            .is_err());
+        manager
+            .set_participant_role(&loom_id, &operator, &sender, ParticipantRole::Observer)
+            .unwrap();
+        assert!(matches!(
+            manager.execute(&loom_id, &[], sender, 100, 1002),
+            Err(LoomError::PermissionDenied { .. })
+        ));
+        assert!(manager
+            .set_participant_role(&loom_id, &operator, &sender, ParticipantRole::Operator)
+            .is_err());
+    }
+
+    #[test]
+    fn test_rotate_operator() {
+        let mut manager = LoomManager::new();
+        let loom_id = [1u8; 32];
+        let old = [2u8; 32];
+        let new = [4u8; 32];
+        manager
+            .deploy(test_config(loom_id), old, simple_wasm(), 1000)
+            .unwrap();
+        manager.join(&loom_id, old, [2u8; 20], 1001).unwrap();
+        manager.join(&loom_id, new, [4u8; 20], 1001).unwrap();
+
+        let handover = OperatorHandover {
+            loom_id,
+            old_operator: old,
+            new_operator: new,
+            timestamp: 2000,
+            old_signature: [0u8; 64],
+            new_signature: [0u8; 64],
+        };
+        manager.rotate_operator(&handover).unwrap();
+
+        let loom = manager.get_loom(&loom_id).unwrap();
+        assert_eq!(loom.operator, new);
+        assert_eq!(loom.participants[0].role, ParticipantRole::Submitter);
+        assert_eq!(loom.participants[1].role, ParticipantRole::Operator);
+
+        // The retired key can no longer hand over.
+        assert!(manager.rotate_operator(&handover).is_err());
+    }
+
     #[test]
     fn test_anchor() {
         let mut manager = LoomManager::new();
@@ -755,6 +1397,78 @@ mod tests {
         assert_eq!(hash.len(), 32);
     }
 
+    #[test]
+    fn test_export_import_state() {
+        let loom_id = [1u8; 32];
+        let mut source = LoomManager::new();
+        source
+            .deploy(test_config(loom_id), [2u8; 32], simple_wasm(), 1000)
+            .unwrap();
+        source
+            .states
+            .get_mut(&loom_id)
+            .unwrap()
+            .set(b"k".to_vec(), b"v".to_vec());
+        let snapshot = source.export_state(&loom_id).unwrap();
+
+        let mut target = LoomManager::new();
+        target
+            .deploy(test_config(loom_id), [2u8; 32], simple_wasm(), 1000)
+            .unwrap();
+        let state_hash = target.import_state(&loom_id, snapshot.clone()).unwrap();
+        assert_eq!(state_hash, snapshot.state_hash);
+        assert_eq!(
+            target.get_state(&loom_id).unwrap().get(b"k"),
+            Some(b"v".as_ref())
+        );
+        let (hash, version) = target.anchor(&loom_id).unwrap();
+        assert_eq!(hash, state_hash);
+        assert_eq!(version, 1);
+
+        // Snapshots cannot be imported into a different loom.
+        let other = [9u8; 32];
+        target
+            .deploy(test_config(other), [2u8; 32], simple_wasm(), 1000)
+            .unwrap();
+        assert!(target.import_state(&other, snapshot).is_err());
+    }
+
+    #[test]
+    fn test_open_and_resolve_dispute() {
+        let mut manager = LoomManager::new();
+        let loom_id = [1u8; 32];
+        let operator = [2u8; 32];
+        let wasm = simple_wasm();
+        manager
+            .deploy(test_config(loom_id), operator, wasm.clone(), 1000)
+            .unwrap();
+
+        // simple_wasm never writes state, so any other output root is fraud.
+        let prior_state = manager.export_state(&loom_id).unwrap();
+        let evidence = EvidenceBundle {
+            loom_id,
+            code_id: blake3_hash(&wasm),
+            prior_state,
+            input: vec![],
+            sender: [3u8; 20],
+            block_height: 1,
+            timestamp: 1001,
+            claimed_output_root: [0xEE; 32],
+        };
+        let challenger = [4u8; 20];
+        let id = manager
+            .open_dispute(evidence, challenger, 10, 1002)
+            .unwrap();
+        let dispute = manager.resolve_dispute(&id, 1003).unwrap();
+        assert_eq!(dispute.status, crate::dispute::DisputeStatus::Upheld);
+        assert_eq!(
+            dispute.defendant,
+            norn_crypto::address::pubkey_to_address(&operator)
+        );
+        assert_eq!(manager.list_disputes(Some(&loom_id)).len(), 1);
+        assert!(manager.resolve_dispute(&[0u8; 32], 1004).is_err());
+    }
+
     #[test]
     fn test_full_lifecycle() {
         let mut manager = LoomManager::new();
@@ -873,4 +1587,171 @@ mod tests {
         assert_eq!(outcome.transition.outputs, 42i32.to_le_bytes().to_vec());
         assert!(outcome.gas_used > 0);
     }
+
+    #[test]
+    fn test_upload_bytecode_rejects_invalid_module() {
+        let mut manager = LoomManager::new();
+        let loom_id = [1u8; 32];
+        manager
+            .deploy(test_config(loom_id), [2u8; 32], simple_wasm(), 1000)
+            .unwrap();
+
+        // simple_wasm() has no memory or init export.
+        let result = manager.upload_bytecode(&loom_id, simple_wasm(), None);
+        assert!(matches!(result, Err(LoomError::InvalidModule { .. })));
+    }
+
+    #[test]
+    fn test_store_code() {
+        let mut manager = LoomManager::new();
+        let code_id = manager.store_code(simple_wasm()).unwrap();
+        assert_eq!(code_id, blake3_hash(&simple_wasm()));
+        assert_eq!(manager.store_code(simple_wasm()).unwrap(), code_id);
+        assert_eq!(manager.get_code(&code_id), Some(simple_wasm().as_slice()));
+        assert!(manager.store_code(vec![]).is_err());
+    }
+
+    #[test]
+    fn test_looms_share_identical_bytecode() {
+        let mut manager = LoomManager::new();
+        manager
+            .deploy(test_config([1u8; 32]), [2u8; 32], simple_wasm(), 1000)
+            .unwrap();
+        manager
+            .deploy(test_config([2u8; 32]), [2u8; 32], simple_wasm(), 1000)
+            .unwrap();
+
+        let code_id = blake3_hash(&simple_wasm());
+        assert_eq!(manager.list_codes().len(), 1);
+        assert_eq!(manager.code_instance_count(&code_id), 2);
+        assert_eq!(manager.get_code_id(&[1u8; 32]), Some(code_id));
+        assert_eq!(
+            manager.get_bytecode_bytes(&[2u8; 32]),
+            Some(simple_wasm().as_slice())
+        );
+    }
+
+    #[test]
+    fn test_trace_execution() {
+        let mut manager = LoomManager::new();
+        let wat = r#"
+            (module
+                (import "norn" "norn_debug_log" (func $dbg (param i32 i32)))
+                (import "norn" "norn_state_set" (func $set (param i32 i32 i32 i32)))
+                (import "norn" "norn_log" (func $log (param i32 i32)))
+                (memory (export "memory") 1)
+                (data (i32.const 0) "keyvalhere")
+                (func (export "execute") (param i32 i32) (result i32)
+                    (call $dbg (i32.const 6) (i32.const 4))
+                    (call $set (i32.const 0) (i32.const 3) (i32.const 3) (i32.const 3))
+                    (call $log (i32.const 6) (i32.const 4))
+                    unreachable
+                )
+            )
+        "#;
+        let loom_id = [1u8; 32];
+        manager
+            .deploy(
+                test_config(loom_id),
+                [2u8; 32],
+                wat::parse_str(wat).unwrap(),
+                1000,
+            )
+            .unwrap();
+        let sender = [3u8; 20];
+
+        let trace = manager
+            .trace_with_balances(&loom_id, &[], sender, 100, 1002, None)
+            .unwrap();
+        assert!(!trace.success);
+        assert!(trace.error.is_some());
+        assert_eq!(trace.debug_logs, vec!["here".to_string()]);
+        assert_eq!(trace.logs, vec!["here".to_string()]);
+        let names: Vec<_> = trace.calls.iter().map(|c| c.host_fn).collect();
+        assert_eq!(names, vec!["norn_state_set", "norn_log"]);
+        assert_eq!(trace.calls[0].detail, "key = val");
+        assert_eq!(trace.calls[0].gas, 206);
+        let host_total: u64 = trace.host_gas.iter().map(|u| u.gas).sum();
+        assert_eq!(host_total, 256);
+        assert_eq!(trace.instruction_gas, trace.gas_used - 256);
+
+        // Tracing commits nothing.
+        assert!(manager.get_state(&loom_id).unwrap().data.is_empty());
+        assert_eq!(manager.get_loom(&loom_id).unwrap().version, 0);
+    }
+
+    #[test]
+    fn test_execute_instantiates_from_code() {
+        let mut manager = LoomManager::new();
+        let child = wat::parse_str(
+            r#"
+            (module
+                (func (export "init"))
+                (func (export "execute") (param i32 i32) (result i32)
+                    i32.const 7
+                )
+            )
+        "#,
+        )
+        .unwrap();
+        let code_id = manager.store_code(child).unwrap();
+
+        // Factory: instantiates the stored code with salt "a" and returns the
+        // host result code.
+        let factory_wat = format!(
+            r#"
+            (module
+                (import "norn" "norn_instantiate"
+                    (func $inst (param i32 i32 i32 i32 i32 i32) (result i32)))
+                (memory (export "memory") 1)
+                (data (i32.const 0) "{code_id_escaped}")
+                (data (i32.const 32) "a")
+                (func (export "execute") (param i32 i32) (result i32)
+                    (call $inst (i32.const 0) (i32.const 0) (i32.const 0)
+                                (i32.const 32) (i32.const 1) (i32.const 100))
+                )
+            )
+        "#,
+            code_id_escaped = code_id
+                .iter()
+                .map(|b| format!("\\{b:02x}"))
+                .collect::<String>()
+        );
+        let factory_id = [1u8; 32];
+        manager
+            .deploy(
+                test_config(factory_id),
+                [2u8; 32],
+                wat::parse_str(&factory_wat).unwrap(),
+                1000,
+            )
+            .unwrap();
+        let sender = [3u8; 20];
+        manager.join(&factory_id, [3u8; 32], sender, 1001).unwrap();
+
+        let outcome = manager
+            .execute(&factory_id, &[], sender, 100, 1002)
+            .unwrap();
+        assert_eq!(outcome.transition.outputs, 0i32.to_le_bytes().to_vec());
+        let creator = derive_contract_address(&factory_id);
+        let instance_id = norn_types::loom::derive_instance_id(&creator, &code_id, b"a");
+        assert_eq!(outcome.instantiated, vec![instance_id]);
+
+        let instance = manager.get_loom(&instance_id).unwrap();
+        assert!(instance.active);
+        assert_eq!(instance.operator, [2u8; 32]);
+        assert_eq!(manager.get_code_id(&instance_id), Some(code_id));
+
+        // The new loom is executable like any deployed loom.
+        manager.join(&instance_id, [3u8; 32], sender, 1003).unwrap();
+        let outcome = manager
+            .execute(&instance_id, &[], sender, 100, 1004)
+            .unwrap();
+        assert_eq!(outcome.transition.outputs, 7i32.to_le_bytes().to_vec());
+
+        // Reusing the salt collides with the existing instance.
+        let result = manager.execute(&factory_id, &[], sender, 100, 1005);
+        assert!(matches!(result, Err(LoomError::LoomAlreadyExists { .. })));
+        assert_eq!(manager.get_loom(&factory_id).unwrap().version, 1);
+    }
 }
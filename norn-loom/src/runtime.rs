@@ -1,9 +1,49 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+use norn_crypto::hash::blake3_hash;
+use norn_types::primitives::Hash;
+use wasmparser::{ExternalKind, Parser, Payload, TypeRef, Validator, WasmFeatures};
 use wasmtime::{Config, Engine, Instance, Linker, Memory, Module, Store};
 
 use crate::call_stack::CallFrame;
 use crate::error::LoomError;
-use crate::gas::GAS_CROSS_CALL;
-use crate::host::LoomHostState;
+use crate::host::{
+    ExecutionTracer, LoomHostState, PendingInstantiation, MAX_EVENTS, MAX_LOGS,
+    MAX_PENDING_INSTANTIATIONS, MAX_PENDING_TRANSFERS, MAX_SALT_SIZE, MAX_WASM_MEMORY_BYTES,
+};
+
+/// Host functions available to looms in the `norn` import namespace.
+pub const HOST_FUNCTIONS: &[&str] = &[
+    "norn_log",
+    "norn_debug_log",
+    "norn_state_get",
+    "norn_state_set",
+    "norn_transfer",
+    "norn_contract_address",
+    "norn_derive_address",
+    "norn_vrf_verify",
+    "norn_decrypt",
+    "norn_verify_signature",
+    "norn_balance_of",
+    "norn_emit_event",
+    "norn_sender",
+    "norn_block_height",
+    "norn_timestamp",
+    "norn_call_contract",
+    "norn_instantiate",
+];
+
+/// Exports every uploaded module must provide.
+pub const REQUIRED_EXPORTS: &[(&str, ExternalKind)] = &[
+    ("memory", ExternalKind::Memory),
+    ("init", ExternalKind::Func),
+    ("execute", ExternalKind::Func),
+];
+
+/// Maximum linear memory an uploaded module may declare, in 64 KiB pages.
+pub const MAX_MEMORY_PAGES: u64 = (MAX_WASM_MEMORY_BYTES / 65_536) as u64;
 
 /// Validate WASM pointer parameters and compute the memory range.
 /// Returns (start, end) as usize, or an error if the values are invalid.
@@ -21,10 +61,314 @@ fn validate_wasm_ptr(ptr: i32, len: i32) -> Result<(usize, usize), wasmtime::Err
     Ok((start, end))
 }
 
+/// Convert a host state error into a trap. Storage and step limit errors keep
+/// their type so the call site can surface them unchanged.
+fn state_error(op: &str, e: LoomError) -> wasmtime::Error {
+    match e {
+        LoomError::StorageLimitExceeded { .. } | LoomError::StepLimitReached { .. } => {
+            wasmtime::Error::new(e)
+        }
+        e => wasmtime::Error::msg(format!("host {op} error: {e}")),
+    }
+}
+
+/// Deduct the gas charged to the host's `GasMeter` since `gas_before` from
+/// wasmtime fuel, so host calls draw on the same budget as compute.
+fn consume_host_gas(
+    caller: &mut wasmtime::Caller<'_, LoomHostState>,
+    gas_before: u64,
+) -> Result<(), wasmtime::Error> {
+    let host = caller.data();
+    let charged = host.gas_meter.used().saturating_sub(gas_before);
+    let fuel = host.gas_schedule.gas_to_fuel(charged);
+    let current_fuel = caller.get_fuel().unwrap_or(0);
+    caller
+        .set_fuel(current_fuel.saturating_sub(fuel))
+        .map_err(|e| wasmtime::Error::msg(format!("fuel error on host call: {e}")))
+}
+
+/// Map a failed guest call to a `LoomError`, preserving typed storage and
+/// step limit errors raised by host functions.
+fn call_error(context: &str, e: wasmtime::Error) -> LoomError {
+    match e.downcast::<LoomError>() {
+        Ok(err @ (LoomError::StorageLimitExceeded { .. } | LoomError::StepLimitReached { .. })) => {
+            err
+        }
+        Ok(err) => LoomError::RuntimeError {
+            reason: format!("{context}: {err}"),
+        },
+        Err(e) => LoomError::RuntimeError {
+            reason: format!("{context}: {e}"),
+        },
+    }
+}
+
+/// Category of a problem found by [`validate_module`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticKind {
+    /// The bytecode is not a valid Wasm module.
+    Malformed,
+    /// The module uses floating-point, SIMD, threads or other features
+    /// whose results can differ between hosts.
+    NonDeterministic,
+    /// The module imports something other than a `norn` host function.
+    DisallowedImport,
+    /// The module declares more linear memory than a loom may use.
+    ExcessiveMemory,
+    /// The module lacks an export the runtime calls.
+    MissingExport,
+}
+
+impl DiagnosticKind {
+    /// Stable snake_case name, used in RPC responses.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DiagnosticKind::Malformed => "malformed",
+            DiagnosticKind::NonDeterministic => "non_deterministic",
+            DiagnosticKind::DisallowedImport => "disallowed_import",
+            DiagnosticKind::ExcessiveMemory => "excessive_memory",
+            DiagnosticKind::MissingExport => "missing_export",
+        }
+    }
+}
+
+/// A single problem found by [`validate_module`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleDiagnostic {
+    pub kind: DiagnosticKind,
+    pub message: String,
+}
+
+impl std::fmt::Display for ModuleDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.kind.as_str(), self.message)
+    }
+}
+
+/// Wasm features allowed in loom bytecode: Wasm 2.0 without floats or SIMD.
+fn deterministic_features() -> WasmFeatures {
+    WasmFeatures::WASM2 - WasmFeatures::FLOATS - WasmFeatures::SIMD
+}
+
+/// Check uploaded bytecode before it is stored.
+///
+/// Rejects modules that are malformed, use non-deterministic features,
+/// import anything other than `norn` host functions, declare more memory
+/// than [`MAX_MEMORY_PAGES`], or lack a [`REQUIRED_EXPORTS`] entry. All
+/// import, memory and export problems are reported together.
+pub fn validate_module(bytecode: &[u8]) -> Result<(), LoomError> {
+    let invalid = |diagnostics| LoomError::InvalidModule { diagnostics };
+
+    if let Err(e) = Validator::new_with_features(deterministic_features()).validate_all(bytecode) {
+        let kind = if Validator::new().validate_all(bytecode).is_ok() {
+            DiagnosticKind::NonDeterministic
+        } else {
+            DiagnosticKind::Malformed
+        };
+        return Err(invalid(vec![ModuleDiagnostic {
+            kind,
+            message: e.to_string(),
+        }]));
+    }
+
+    let malformed = |e: wasmparser::BinaryReaderError| {
+        invalid(vec![ModuleDiagnostic {
+            kind: DiagnosticKind::Malformed,
+            message: e.to_string(),
+        }])
+    };
+    let mut diagnostics = Vec::new();
+    let mut exports = Vec::new();
+    for payload in Parser::new(0).parse_all(bytecode) {
+        match payload.map_err(malformed)? {
+            Payload::ImportSection(reader) => {
+                for import in reader {
+                    let import = import.map_err(malformed)?;
+                    let allowed = import.module == "norn"
+                        && matches!(import.ty, TypeRef::Func(_))
+                        && HOST_FUNCTIONS.contains(&import.name);
+                    if !allowed {
+                        diagnostics.push(ModuleDiagnostic {
+                            kind: DiagnosticKind::DisallowedImport,
+                            message: format!("import {}::{}", import.module, import.name),
+                        });
+                    }
+                }
+            }
+            Payload::MemorySection(reader) => {
+                for memory in reader {
+                    let memory = memory.map_err(malformed)?;
+                    let declared = memory.maximum.unwrap_or(0).max(memory.initial);
+                    if declared > MAX_MEMORY_PAGES {
+                        diagnostics.push(ModuleDiagnostic {
+                            kind: DiagnosticKind::ExcessiveMemory,
+                            message: format!(
+                                "memory declares {declared} pages, limit is {MAX_MEMORY_PAGES}"
+                            ),
+                        });
+                    }
+                }
+            }
+            Payload::ExportSection(reader) => {
+                for export in reader {
+                    let export = export.map_err(malformed)?;
+                    exports.push((export.name.to_string(), export.kind));
+                }
+            }
+            _ => {}
+        }
+    }
+    for (name, kind) in REQUIRED_EXPORTS {
+        if !exports.iter().any(|(n, k)| n == name && k == kind) {
+            diagnostics.push(ModuleDiagnostic {
+                kind: DiagnosticKind::MissingExport,
+                message: format!("missing {kind:?} export `{name}`"),
+            });
+        }
+    }
+
+    if diagnostics.is_empty() {
+        Ok(())
+    } else {
+        Err(invalid(diagnostics))
+    }
+}
+
+/// Compiled modules kept in memory by the process-wide module cache.
+pub const MODULE_CACHE_CAPACITY: usize = 256;
+
+/// Cache of compiled modules keyed by the blake3 hash of their bytecode.
+///
+/// Holds up to `capacity` modules in memory, evicting the least recently
+/// used. With a directory set, compiled artifacts are also written to disk
+/// so a restarted node can load them without recompiling.
+pub struct ModuleCache {
+    capacity: usize,
+    dir: Option<PathBuf>,
+    entries: HashMap<Hash, (Module, u64)>,
+    tick: u64,
+}
+
+impl ModuleCache {
+    /// Create an empty cache.
+    pub fn new(capacity: usize, dir: Option<PathBuf>) -> Self {
+        Self {
+            capacity,
+            dir,
+            entries: HashMap::new(),
+            tick: 0,
+        }
+    }
+
+    /// Number of modules held in memory.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no modules are held in memory.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn artifact_path(&self, code_hash: &Hash) -> Option<PathBuf> {
+        self.dir
+            .as_ref()
+            .map(|dir| dir.join(format!("{}.cwasm", hex::encode(code_hash))))
+    }
+
+    /// Look up a module in memory, then on disk.
+    ///
+    /// Disk artifacts that fail to load (corrupt, or written by a different
+    /// wasmtime version or engine config) are removed.
+    pub fn get(&mut self, engine: &Engine, code_hash: &Hash) -> Option<Module> {
+        self.tick += 1;
+        if let Some((module, used)) = self.entries.get_mut(code_hash) {
+            *used = self.tick;
+            return Some(module.clone());
+        }
+        let path = self.artifact_path(code_hash)?;
+        if !path.exists() {
+            return None;
+        }
+        // SAFETY: artifacts are only written by `insert` from modules this
+        // process compiled, and wasmtime rejects artifacts whose version or
+        // engine configuration does not match.
+        match unsafe { Module::deserialize_file(engine, &path) } {
+            Ok(module) => {
+                self.insert_in_memory(*code_hash, module.clone());
+                Some(module)
+            }
+            Err(e) => {
+                tracing::warn!("discarding cached module {}: {e}", path.display());
+                let _ = std::fs::remove_file(&path);
+                None
+            }
+        }
+    }
+
+    /// Add a freshly compiled module, persisting it when a directory is set.
+    pub fn insert(&mut self, code_hash: Hash, module: Module) {
+        if let Some(path) = self.artifact_path(&code_hash) {
+            if let Err(e) = write_artifact(&path, &module) {
+                tracing::warn!("failed to cache module {}: {e}", path.display());
+            }
+        }
+        self.insert_in_memory(code_hash, module);
+    }
+
+    fn insert_in_memory(&mut self, code_hash: Hash, module: Module) {
+        self.tick += 1;
+        self.entries.insert(code_hash, (module, self.tick));
+        while self.entries.len() > self.capacity {
+            let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, used))| *used)
+                .map(|(hash, _)| *hash)
+            else {
+                break;
+            };
+            self.entries.remove(&oldest);
+        }
+    }
+}
+
+/// Write a serialized module via a temporary file so readers never see a
+/// partial artifact.
+fn write_artifact(path: &std::path::Path, module: &Module) -> Result<(), wasmtime::Error> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, module.serialize()?)?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// The process-wide engine. Compiled modules are tied to the engine that
+/// built them, so every runtime shares one to make cached modules reusable.
+static ENGINE: OnceLock<Result<Engine, String>> = OnceLock::new();
+
+/// The process-wide module cache used by `LoomRuntime::instantiate`.
+static MODULE_CACHE: OnceLock<Mutex<ModuleCache>> = OnceLock::new();
+
+fn module_cache() -> &'static Mutex<ModuleCache> {
+    MODULE_CACHE.get_or_init(|| Mutex::new(ModuleCache::new(MODULE_CACHE_CAPACITY, None)))
+}
+
+/// Set the directory for on-disk compiled module artifacts, or `None` to
+/// keep the cache in memory only. Modules already cached in memory are kept.
+pub fn set_module_cache_dir(dir: Option<PathBuf>) {
+    if let Ok(mut cache) = module_cache().lock() {
+        cache.dir = dir;
+    }
+}
+
 /// The Wasm runtime engine for loom contracts.
 ///
 /// Wraps a wasmtime `Engine` configured with fuel metering for deterministic
-/// gas accounting.
+/// gas accounting. All runtimes share one engine and a module cache, so a
+/// loom's bytecode is compiled once per process (or loaded from disk).
 pub struct LoomRuntime {
     engine: Engine,
 }
@@ -41,17 +385,48 @@ pub struct LoomInstance {
 impl LoomRuntime {
     /// Create a new runtime with fuel metering enabled.
     pub fn new() -> Result<Self, LoomError> {
-        let mut config = Config::new();
-        config.consume_fuel(true);
-        let engine = Engine::new(&config).map_err(|e| LoomError::RuntimeError {
-            reason: format!("failed to create wasmtime engine: {e}"),
-        })?;
+        let engine = ENGINE
+            .get_or_init(|| {
+                let mut config = Config::new();
+                config.consume_fuel(true);
+                Engine::new(&config).map_err(|e| e.to_string())
+            })
+            .clone()
+            .map_err(|e| LoomError::RuntimeError {
+                reason: format!("failed to create wasmtime engine: {e}"),
+            })?;
         Ok(Self { engine })
     }
 
+    /// Compile bytecode, reusing a cached module when one exists.
+    fn compile(&self, bytecode: &[u8]) -> Result<Module, LoomError> {
+        let code_hash = blake3_hash(bytecode);
+        let lock_err = |e: std::sync::PoisonError<_>| LoomError::RuntimeError {
+            reason: format!("module cache lock error: {e}"),
+        };
+        if let Some(module) = module_cache()
+            .lock()
+            .map_err(lock_err)?
+            .get(&self.engine, &code_hash)
+        {
+            return Ok(module);
+        }
+        // Compile without holding the lock so other looms can run meanwhile.
+        let module =
+            Module::new(&self.engine, bytecode).map_err(|e| LoomError::InvalidBytecode {
+                reason: format!("failed to compile wasm module: {e}"),
+            })?;
+        module_cache()
+            .lock()
+            .map_err(lock_err)?
+            .insert(code_hash, module.clone());
+        Ok(module)
+    }
+
     /// Compile and instantiate a Wasm module with the given host state.
     ///
-    /// Host functions are registered in the `norn` namespace and delegate to
+    /// Compiled modules come from the module cache when available. Host
+    /// functions are registered in the `norn` namespace and delegate to
     /// methods on `LoomHostState`. The store is seeded with fuel equal to the
     /// gas limit configured in the host state.
     pub fn instantiate(
@@ -59,19 +434,16 @@ impl LoomRuntime {
         bytecode: &[u8],
         host_state: LoomHostState,
     ) -> Result<LoomInstance, LoomError> {
-        let module =
-            Module::new(&self.engine, bytecode).map_err(|e| LoomError::InvalidBytecode {
-                reason: format!("failed to compile wasm module: {e}"),
-            })?;
+        let module = self.compile(bytecode)?;
 
-        let gas_limit = host_state.gas_meter.limit;
+        let fuel = host_state
+            .gas_schedule
+            .gas_to_fuel(host_state.gas_meter.limit);
         let mut store = Store::new(&self.engine, host_state);
         store.limiter(|state| &mut state.store_limits);
-        store
-            .set_fuel(gas_limit)
-            .map_err(|e| LoomError::RuntimeError {
-                reason: format!("failed to set fuel: {e}"),
-            })?;
+        store.set_fuel(fuel).map_err(|e| LoomError::RuntimeError {
+            reason: format!("failed to set fuel: {e}"),
+        })?;
 
         let mut linker: Linker<LoomHostState> = Linker::new(&self.engine);
 
@@ -95,17 +467,49 @@ impl LoomRuntime {
                         data[start..end].to_vec()
                     };
                     let msg = std::str::from_utf8(&msg_bytes).unwrap_or("<invalid utf8>");
+                    let gas_before = caller.data().gas_meter.used();
                     caller
                         .data_mut()
                         .log(msg)
                         .map_err(|e| wasmtime::Error::msg(format!("host log error: {e}")))?;
-                    Ok(())
+                    consume_host_gas(&mut caller, gas_before)
                 },
             )
             .map_err(|e| LoomError::RuntimeError {
                 reason: format!("failed to register norn_log: {e}"),
             })?;
 
+        // ── Host function: norn_debug_log ────────────────────────────────
+        // Free and a no-op unless the execution is traced, so contracts can
+        // leave debug output in without affecting consensus results.
+        linker
+            .func_wrap(
+                "norn",
+                "norn_debug_log",
+                |mut caller: wasmtime::Caller<'_, LoomHostState>, msg_ptr: i32, msg_len: i32| {
+                    if caller.data().tracer.is_none() {
+                        return Ok(());
+                    }
+                    let memory = caller
+                        .get_export("memory")
+                        .and_then(|e| e.into_memory())
+                        .ok_or(wasmtime::Error::msg("missing memory export"))?;
+                    let (start, end) = validate_wasm_ptr(msg_ptr, msg_len)?;
+                    let msg_bytes = {
+                        let data = memory.data(&caller);
+                        if end > data.len() {
+                            return Err(wasmtime::Error::msg("out of bounds memory access"));
+                        }
+                        data[start..end].to_vec()
+                    };
+                    caller.data_mut().debug_log(&msg_bytes);
+                    Ok(())
+                },
+            )
+            .map_err(|e| LoomError::RuntimeError {
+                reason: format!("failed to register norn_debug_log: {e}"),
+            })?;
+
         // ── Host function: norn_state_get ────────────────────────────────
         // Signature: (key_ptr, key_len, out_ptr, out_max_len) -> i32
         // If out_ptr == 0: query mode — returns value length (or -1 if not found)
@@ -131,10 +535,12 @@ impl LoomRuntime {
                         return Err(wasmtime::Error::msg("out of bounds memory access"));
                     }
                     let key = data[start..end].to_vec();
+                    let gas_before = caller.data().gas_meter.used();
                     let value = caller
                         .data_mut()
                         .state_get(&key)
-                        .map_err(|e| wasmtime::Error::msg(format!("host state_get error: {e}")))?;
+                        .map_err(|e| state_error("state_get", e))?;
+                    consume_host_gas(&mut caller, gas_before)?;
                     match value {
                         Some(v) => {
                             let val_len = v.len() as i32;
@@ -196,11 +602,12 @@ impl LoomRuntime {
                     }
                     let key = data[key_start..key_end].to_vec();
                     let val = data[val_start..val_end].to_vec();
+                    let gas_before = caller.data().gas_meter.used();
                     caller
                         .data_mut()
                         .state_set(&key, &val)
-                        .map_err(|e| wasmtime::Error::msg(format!("host state_set error: {e}")))?;
-                    Ok(())
+                        .map_err(|e| state_error("state_set", e))?;
+                    consume_host_gas(&mut caller, gas_before)
                 },
             )
             .map_err(|e| LoomError::RuntimeError {
@@ -252,17 +659,21 @@ impl LoomRuntime {
                         .data()
                         .current_loom_id
                         .map(|id| norn_types::primitives::derive_contract_address(&id));
-                    if from != sender && Some(from) != contract_addr {
+                    if from != sender
+                        && Some(from) != contract_addr
+                        && !caller.data().sub_addresses.contains(&from)
+                    {
                         return Err(wasmtime::Error::msg(
                             "norn_transfer: from address must match the caller or contract address",
                         ));
                     }
 
+                    let gas_before = caller.data().gas_meter.used();
                     caller
                         .data_mut()
                         .transfer(from, to, token_id, amount as u128)
                         .map_err(|e| wasmtime::Error::msg(format!("host transfer error: {e}")))?;
-                    Ok(())
+                    consume_host_gas(&mut caller, gas_before)
                 },
             )
             .map_err(|e| LoomError::RuntimeError {
@@ -302,6 +713,251 @@ impl LoomRuntime {
                 reason: format!("failed to register norn_contract_address: {e}"),
             })?;
 
+        // ── Host function: norn_derive_address ──────────────────────────────
+        // Signature: (salt_ptr: i32, salt_len: i32, out_ptr: i32) -> ()
+        // Writes the 20-byte sub-address derived from the loom ID and salt.
+        linker
+            .func_wrap(
+                "norn",
+                "norn_derive_address",
+                |mut caller: wasmtime::Caller<'_, LoomHostState>,
+                 salt_ptr: i32,
+                 salt_len: i32,
+                 out_ptr: i32|
+                 -> Result<(), wasmtime::Error> {
+                    let memory = caller
+                        .get_export("memory")
+                        .and_then(|e| e.into_memory())
+                        .ok_or(wasmtime::Error::msg("missing memory export"))?;
+                    let (salt_start, salt_end) = validate_wasm_ptr(salt_ptr, salt_len)?;
+                    let (out_start, out_end) = validate_wasm_ptr(out_ptr, 20)?;
+                    let data = memory.data(&caller);
+                    if salt_end > data.len() || out_end > data.len() {
+                        return Err(wasmtime::Error::msg("out of bounds memory access"));
+                    }
+                    let salt = data[salt_start..salt_end].to_vec();
+                    let gas_before = caller.data().gas_meter.used();
+                    let addr = caller.data_mut().derive_address(&salt).map_err(|e| {
+                        wasmtime::Error::msg(format!("host derive_address error: {e}"))
+                    })?;
+                    consume_host_gas(&mut caller, gas_before)?;
+                    memory.data_mut(&mut caller)[out_start..out_end].copy_from_slice(&addr);
+                    Ok(())
+                },
+            )
+            .map_err(|e| LoomError::RuntimeError {
+                reason: format!("failed to register norn_derive_address: {e}"),
+            })?;
+
+        // ── Host function: norn_vrf_verify ──────────────────────────────────
+        // Signature: (pk_ptr: i32, alpha_ptr: i32, alpha_len: i32, proof_ptr: i32,
+        //             out_ptr: i32) -> i32
+        // Verifies an 80-byte VRF proof of alpha under the 32-byte public key.
+        // Returns 1 and writes the 64-byte VRF output if it verifies, else 0.
+        linker
+            .func_wrap(
+                "norn",
+                "norn_vrf_verify",
+                |mut caller: wasmtime::Caller<'_, LoomHostState>,
+                 pk_ptr: i32,
+                 alpha_ptr: i32,
+                 alpha_len: i32,
+                 proof_ptr: i32,
+                 out_ptr: i32|
+                 -> Result<i32, wasmtime::Error> {
+                    let memory = caller
+                        .get_export("memory")
+                        .and_then(|e| e.into_memory())
+                        .ok_or(wasmtime::Error::msg("missing memory export"))?;
+                    let (pk_start, pk_end) = validate_wasm_ptr(pk_ptr, 32)?;
+                    let (alpha_start, alpha_end) = validate_wasm_ptr(alpha_ptr, alpha_len)?;
+                    let (proof_start, proof_end) =
+                        validate_wasm_ptr(proof_ptr, norn_crypto::vrf::PROOF_SIZE as i32)?;
+                    let (out_start, out_end) =
+                        validate_wasm_ptr(out_ptr, norn_crypto::vrf::OUTPUT_SIZE as i32)?;
+                    let data = memory.data(&caller);
+                    if pk_end > data.len()
+                        || alpha_end > data.len()
+                        || proof_end > data.len()
+                        || out_end > data.len()
+                    {
+                        return Err(wasmtime::Error::msg("out of bounds memory access"));
+                    }
+                    let mut public_key = [0u8; 32];
+                    public_key.copy_from_slice(&data[pk_start..pk_end]);
+                    let alpha = data[alpha_start..alpha_end].to_vec();
+                    let mut proof = [0u8; norn_crypto::vrf::PROOF_SIZE];
+                    proof.copy_from_slice(&data[proof_start..proof_end]);
+                    let gas_before = caller.data().gas_meter.used();
+                    let output = caller
+                        .data_mut()
+                        .vrf_verify(&public_key, &alpha, &proof)
+                        .map_err(|e| wasmtime::Error::msg(format!("host vrf_verify error: {e}")))?;
+                    consume_host_gas(&mut caller, gas_before)?;
+                    match output {
+                        Some(output) => {
+                            memory.data_mut(&mut caller)[out_start..out_end]
+                                .copy_from_slice(&output);
+                            Ok(1)
+                        }
+                        None => Ok(0),
+                    }
+                },
+            )
+            .map_err(|e| LoomError::RuntimeError {
+                reason: format!("failed to register norn_vrf_verify: {e}"),
+            })?;
+
+        // ── Host function: norn_verify_signature ────────────────────────────
+        // Signature: (pk_ptr: i32, msg_ptr: i32, msg_len: i32, sig_ptr: i32) -> i32
+        // Verifies a 64-byte Ed25519 signature of the message under the
+        // 32-byte public key. Returns 1 if it verifies, else 0.
+        linker
+            .func_wrap(
+                "norn",
+                "norn_verify_signature",
+                |mut caller: wasmtime::Caller<'_, LoomHostState>,
+                 pk_ptr: i32,
+                 msg_ptr: i32,
+                 msg_len: i32,
+                 sig_ptr: i32|
+                 -> Result<i32, wasmtime::Error> {
+                    let memory = caller
+                        .get_export("memory")
+                        .and_then(|e| e.into_memory())
+                        .ok_or(wasmtime::Error::msg("missing memory export"))?;
+                    let (pk_start, pk_end) = validate_wasm_ptr(pk_ptr, 32)?;
+                    let (msg_start, msg_end) = validate_wasm_ptr(msg_ptr, msg_len)?;
+                    let (sig_start, sig_end) = validate_wasm_ptr(sig_ptr, 64)?;
+                    let data = memory.data(&caller);
+                    if pk_end > data.len() || msg_end > data.len() || sig_end > data.len() {
+                        return Err(wasmtime::Error::msg("out of bounds memory access"));
+                    }
+                    let mut public_key = [0u8; 32];
+                    public_key.copy_from_slice(&data[pk_start..pk_end]);
+                    let message = data[msg_start..msg_end].to_vec();
+                    let mut signature = [0u8; 64];
+                    signature.copy_from_slice(&data[sig_start..sig_end]);
+                    let gas_before = caller.data().gas_meter.used();
+                    let valid = caller
+                        .data_mut()
+                        .verify_signature(&public_key, &message, &signature)
+                        .map_err(|e| {
+                            wasmtime::Error::msg(format!("host verify_signature error: {e}"))
+                        })?;
+                    consume_host_gas(&mut caller, gas_before)?;
+                    Ok(valid as i32)
+                },
+            )
+            .map_err(|e| LoomError::RuntimeError {
+                reason: format!("failed to register norn_verify_signature: {e}"),
+            })?;
+
+        // ── Host function: norn_decrypt ─────────────────────────────────────
+        // Signature: (key_ptr: i32, nonce_ptr: i32, ct_ptr: i32, ct_len: i32,
+        //             out_ptr: i32) -> i32
+        // Opens an XChaCha20-Poly1305 sealed box with the 32-byte key and
+        // 24-byte nonce. Returns 1 and writes the `ct_len - 16` plaintext bytes
+        // if authentication succeeds, else 0.
+        linker
+            .func_wrap(
+                "norn",
+                "norn_decrypt",
+                |mut caller: wasmtime::Caller<'_, LoomHostState>,
+                 key_ptr: i32,
+                 nonce_ptr: i32,
+                 ct_ptr: i32,
+                 ct_len: i32,
+                 out_ptr: i32|
+                 -> Result<i32, wasmtime::Error> {
+                    if ct_len < 16 {
+                        return Ok(0);
+                    }
+                    let memory = caller
+                        .get_export("memory")
+                        .and_then(|e| e.into_memory())
+                        .ok_or(wasmtime::Error::msg("missing memory export"))?;
+                    let (key_start, key_end) = validate_wasm_ptr(key_ptr, 32)?;
+                    let (nonce_start, nonce_end) = validate_wasm_ptr(nonce_ptr, 24)?;
+                    let (ct_start, ct_end) = validate_wasm_ptr(ct_ptr, ct_len)?;
+                    let (out_start, out_end) = validate_wasm_ptr(out_ptr, ct_len - 16)?;
+                    let data = memory.data(&caller);
+                    if key_end > data.len()
+                        || nonce_end > data.len()
+                        || ct_end > data.len()
+                        || out_end > data.len()
+                    {
+                        return Err(wasmtime::Error::msg("out of bounds memory access"));
+                    }
+                    let mut key = [0u8; 32];
+                    key.copy_from_slice(&data[key_start..key_end]);
+                    let mut nonce = [0u8; 24];
+                    nonce.copy_from_slice(&data[nonce_start..nonce_end]);
+                    let ciphertext = data[ct_start..ct_end].to_vec();
+                    let gas_before = caller.data().gas_meter.used();
+                    let plaintext = caller
+                        .data_mut()
+                        .decrypt(&key, &nonce, &ciphertext)
+                        .map_err(|e| wasmtime::Error::msg(format!("host decrypt error: {e}")))?;
+                    consume_host_gas(&mut caller, gas_before)?;
+                    match plaintext {
+                        Some(plaintext) => {
+                            memory.data_mut(&mut caller)[out_start..out_end]
+                                .copy_from_slice(&plaintext);
+                            Ok(1)
+                        }
+                        None => Ok(0),
+                    }
+                },
+            )
+            .map_err(|e| LoomError::RuntimeError {
+                reason: format!("failed to register norn_decrypt: {e}"),
+            })?;
+
+        // ── Host function: norn_balance_of ──────────────────────────────────
+        // Signature: (addr_ptr: i32, token_ptr: i32, out_ptr: i32) -> ()
+        // Writes the balance as a 16-byte little-endian u128 to the output pointer.
+        linker
+            .func_wrap(
+                "norn",
+                "norn_balance_of",
+                |mut caller: wasmtime::Caller<'_, LoomHostState>,
+                 addr_ptr: i32,
+                 token_ptr: i32,
+                 out_ptr: i32|
+                 -> Result<(), wasmtime::Error> {
+                    let memory = caller
+                        .get_export("memory")
+                        .and_then(|e| e.into_memory())
+                        .ok_or(wasmtime::Error::msg("missing memory export"))?;
+                    let (addr_start, addr_end) = validate_wasm_ptr(addr_ptr, 20)?;
+                    let (token_start, token_end) = validate_wasm_ptr(token_ptr, 32)?;
+                    let (out_start, out_end) = validate_wasm_ptr(out_ptr, 16)?;
+                    let data = memory.data(&caller);
+                    if addr_end > data.len() || token_end > data.len() || out_end > data.len() {
+                        return Err(wasmtime::Error::msg("out of bounds memory access"));
+                    }
+
+                    let mut address = [0u8; 20];
+                    address.copy_from_slice(&data[addr_start..addr_end]);
+                    let mut token_id = [0u8; 32];
+                    token_id.copy_from_slice(&data[token_start..token_end]);
+
+                    let gas_before = caller.data().gas_meter.used();
+                    let balance = caller
+                        .data_mut()
+                        .balance_of(&address, &token_id)
+                        .map_err(|e| wasmtime::Error::msg(format!("host balance_of error: {e}")))?;
+                    consume_host_gas(&mut caller, gas_before)?;
+                    memory.data_mut(&mut caller)[out_start..out_end]
+                        .copy_from_slice(&balance.to_le_bytes());
+                    Ok(())
+                },
+            )
+            .map_err(|e| LoomError::RuntimeError {
+                reason: format!("failed to register norn_balance_of: {e}"),
+            })?;
+
         // ── Host function: norn_emit_event ────────────────────────────────
         // Signature: (type_ptr, type_len, data_ptr, data_len) -> ()
         // type is a UTF-8 string, data is borsh-encoded Vec<(String, String)>
@@ -337,11 +993,12 @@ impl LoomRuntime {
                         .to_string();
                     let attributes: Vec<(String, String)> =
                         borsh::from_slice(&data_bytes).unwrap_or_default();
+                    let gas_before = caller.data().gas_meter.used();
                     caller
                         .data_mut()
                         .emit_event(ty, attributes)
                         .map_err(|e| wasmtime::Error::msg(format!("host emit_event error: {e}")))?;
-                    Ok(())
+                    consume_host_gas(&mut caller, gas_before)
                 },
             )
             .map_err(|e| LoomError::RuntimeError {
@@ -443,14 +1100,17 @@ impl LoomRuntime {
                     let input = data[in_start..in_end].to_vec();
 
                     // Charge cross-call gas (both GasMeter and wasmtime fuel).
+                    let gas_before = caller.data().gas_meter.used();
+                    let schedule = caller.data().gas_schedule;
                     caller
                         .data_mut()
                         .gas_meter
-                        .charge(GAS_CROSS_CALL)
+                        .charge(schedule.cross_call)
                         .map_err(|e| wasmtime::Error::msg(format!("gas exhausted: {e}")))?;
                     {
                         let current_fuel = caller.get_fuel().unwrap_or(0);
-                        let new_fuel = current_fuel.saturating_sub(GAS_CROSS_CALL);
+                        let new_fuel =
+                            current_fuel.saturating_sub(schedule.gas_to_fuel(schedule.cross_call));
                         caller.set_fuel(new_fuel).map_err(|e| {
                             wasmtime::Error::msg(format!("fuel error on cross-call overhead: {e}"))
                         })?;
@@ -533,6 +1193,9 @@ impl LoomRuntime {
                     sub_host.loom_states = Some(loom_states.clone());
                     sub_host.loom_bytecodes = Some(loom_bytecodes.clone());
                     sub_host.current_loom_id = Some(target_id);
+                    sub_host.balance_view = caller.data().balance_view.clone();
+                    sub_host.storage_usage = caller.data().storage_usage;
+                    sub_host.tracer = caller.data().tracer.as_ref().map(ExecutionTracer::nested);
 
                     // Create a fresh runtime and execute the target contract.
                     let sub_runtime = LoomRuntime::new().map_err(|e| {
@@ -571,14 +1234,21 @@ impl LoomRuntime {
                             .map_err(|e| wasmtime::Error::msg(format!("gas exhausted: {e}")))?;
                         {
                             let current_fuel = caller.get_fuel().unwrap_or(0);
-                            let new_fuel = current_fuel.saturating_sub(sub_gas_used);
+                            let new_fuel =
+                                current_fuel.saturating_sub(schedule.gas_to_fuel(sub_gas_used));
                             caller.set_fuel(new_fuel).map_err(|e| {
                                 wasmtime::Error::msg(format!("fuel error on cross-call: {e}"))
                             })?;
                         }
 
+                        caller.data_mut().storage_usage = sub_host_state.storage_usage;
+                        if let (Some(tracer), Some(sub_tracer)) =
+                            (caller.data_mut().tracer.as_mut(), sub_host_state.tracer)
+                        {
+                            tracer.merge(sub_tracer);
+                        }
+
                         // Merge transfers, logs, events from subcall (bounded).
-                        use crate::host::{MAX_EVENTS, MAX_LOGS, MAX_PENDING_TRANSFERS};
                         for t in sub_host_state.pending_transfers {
                             if caller.data().pending_transfers.len() >= MAX_PENDING_TRANSFERS {
                                 return Err(wasmtime::Error::msg(
@@ -621,6 +1291,15 @@ impl LoomRuntime {
                         }
                     }
 
+                    caller
+                        .data_mut()
+                        .trace_call("norn_call_contract", gas_before, || match &sub_result {
+                            Ok(output) => {
+                                format!("0x{} -> {} bytes", hex::encode(target_id), output.len())
+                            }
+                            Err(e) => format!("0x{} -> failed: {e}", hex::encode(target_id)),
+                        });
+
                     match sub_result {
                         Ok(output) => {
                             if output_ptr == 0 {
@@ -658,13 +1337,227 @@ impl LoomRuntime {
                 reason: format!("failed to register norn_call_contract: {e}"),
             })?;
 
-        let instance =
-            linker
-                .instantiate(&mut store, &module)
-                .map_err(|e| LoomError::RuntimeError {
-                    reason: format!("failed to instantiate module: {e}"),
-                })?;
-
+        // ── Host function: norn_instantiate ───────────────────────────────
+        // Signature: (code_id_ptr, init_ptr, init_len, salt_ptr, salt_len, out_ptr) -> i32
+        // Runs `init` of a new loom from stored code and writes its 32-byte ID
+        // to out_ptr. Returns 0 on success, -1 if the code is unknown, the ID is
+        // already taken in this execution, or init fails.
+        linker
+            .func_wrap(
+                "norn",
+                "norn_instantiate",
+                |mut caller: wasmtime::Caller<'_, LoomHostState>,
+                 code_id_ptr: i32,
+                 init_ptr: i32,
+                 init_len: i32,
+                 salt_ptr: i32,
+                 salt_len: i32,
+                 out_ptr: i32|
+                 -> Result<i32, wasmtime::Error> {
+                    let memory = caller
+                        .get_export("memory")
+                        .and_then(|e| e.into_memory())
+                        .ok_or(wasmtime::Error::msg("missing memory export"))?;
+                    let (code_start, code_end) = validate_wasm_ptr(code_id_ptr, 32)?;
+                    let (init_start, init_end) = validate_wasm_ptr(init_ptr, init_len)?;
+                    let (salt_start, salt_end) = validate_wasm_ptr(salt_ptr, salt_len)?;
+                    let (out_start, out_end) = validate_wasm_ptr(out_ptr, 32)?;
+                    let (code_id, init_msg, salt) = {
+                        let data = memory.data(&caller);
+                        if code_end > data.len()
+                            || init_end > data.len()
+                            || salt_end > data.len()
+                            || out_end > data.len()
+                        {
+                            return Err(wasmtime::Error::msg("out of bounds memory access"));
+                        }
+                        let mut code_id = [0u8; 32];
+                        code_id.copy_from_slice(&data[code_start..code_end]);
+                        (
+                            code_id,
+                            data[init_start..init_end].to_vec(),
+                            data[salt_start..salt_end].to_vec(),
+                        )
+                    };
+                    if salt.len() > MAX_SALT_SIZE {
+                        return Err(wasmtime::Error::msg("norn_instantiate: salt too large"));
+                    }
+
+                    // Charge instantiation gas (both GasMeter and wasmtime fuel).
+                    let gas_before = caller.data().gas_meter.used();
+                    let schedule = caller.data().gas_schedule;
+                    caller
+                        .data_mut()
+                        .gas_meter
+                        .charge(schedule.instantiate)
+                        .map_err(|e| wasmtime::Error::msg(format!("gas exhausted: {e}")))?;
+                    {
+                        let current_fuel = caller.get_fuel().unwrap_or(0);
+                        caller
+                            .set_fuel(
+                                current_fuel
+                                    .saturating_sub(schedule.gas_to_fuel(schedule.instantiate)),
+                            )
+                            .map_err(|e| {
+                                wasmtime::Error::msg(format!("fuel error on instantiate: {e}"))
+                            })?;
+                    }
+
+                    let codes = caller.data().codes.clone().ok_or(wasmtime::Error::msg(
+                        "norn_instantiate: instantiation not available (no code registry)",
+                    ))?;
+                    let creator = caller
+                        .data()
+                        .current_loom_id
+                        .map(|id| norn_types::primitives::derive_contract_address(&id))
+                        .ok_or(wasmtime::Error::msg(
+                            "norn_instantiate: no loom_id set in host state",
+                        ))?;
+                    let Some(bytecode) = codes.get(&code_id) else {
+                        return Ok(-1);
+                    };
+                    let loom_id = norn_types::loom::derive_instance_id(&creator, &code_id, &salt);
+                    if caller
+                        .data()
+                        .instantiations
+                        .iter()
+                        .any(|i| i.loom_id == loom_id)
+                    {
+                        return Ok(-1);
+                    }
+                    if let Some(states) = caller.data().loom_states.as_ref() {
+                        let taken = states
+                            .lock()
+                            .map_err(|e| wasmtime::Error::msg(format!("lock error: {e}")))?
+                            .contains_key(&loom_id);
+                        if taken {
+                            return Ok(-1);
+                        }
+                    }
+                    if caller.data().instantiations.len() >= MAX_PENDING_INSTANTIATIONS {
+                        return Err(wasmtime::Error::msg(
+                            "norn_instantiate: too many instantiations",
+                        ));
+                    }
+
+                    // Run the new loom's init with the creating contract as sender.
+                    let mut sub_host = LoomHostState::new(
+                        creator,
+                        caller.data().block_height,
+                        caller.data().timestamp,
+                        caller.data().gas_meter.remaining(),
+                    );
+                    sub_host.current_loom_id = Some(loom_id);
+                    sub_host.balance_view = caller.data().balance_view.clone();
+                    sub_host.codes = Some(codes.clone());
+                    sub_host.storage_usage = caller.data().storage_usage;
+                    sub_host.tracer = caller.data().tracer.as_ref().map(ExecutionTracer::nested);
+                    let sub_result = (|| -> Result<(u64, LoomHostState), LoomError> {
+                        let mut sub_instance =
+                            LoomRuntime::new()?.instantiate(bytecode, sub_host)?;
+                        sub_instance.call_init(&init_msg)?;
+                        let gas_used = sub_instance.gas_used();
+                        Ok((gas_used, sub_instance.into_host_state()))
+                    })();
+                    let (sub_gas_used, sub_host_state) = match sub_result {
+                        Ok(result) => result,
+                        Err(e) => {
+                            caller
+                                .data_mut()
+                                .trace_call("norn_instantiate", gas_before, || {
+                                    format!("0x{} -> failed: {e}", hex::encode(code_id))
+                                });
+                            return Ok(-1);
+                        }
+                    };
+
+                    // Charge the init's gas to the caller (both GasMeter and wasmtime fuel).
+                    caller
+                        .data_mut()
+                        .gas_meter
+                        .charge(sub_gas_used)
+                        .map_err(|e| wasmtime::Error::msg(format!("gas exhausted: {e}")))?;
+                    {
+                        let current_fuel = caller.get_fuel().unwrap_or(0);
+                        caller
+                            .set_fuel(
+                                current_fuel.saturating_sub(schedule.gas_to_fuel(sub_gas_used)),
+                            )
+                            .map_err(|e| {
+                                wasmtime::Error::msg(format!("fuel error on instantiate: {e}"))
+                            })?;
+                    }
+
+                    // Make the instance callable for the rest of this execution.
+                    if let Some(states) = caller.data().loom_states.clone() {
+                        states
+                            .lock()
+                            .map_err(|e| wasmtime::Error::msg(format!("lock error: {e}")))?
+                            .insert(loom_id, sub_host_state.state.clone());
+                    }
+                    if let Some(bytecodes) = caller.data().loom_bytecodes.clone() {
+                        bytecodes
+                            .lock()
+                            .map_err(|e| wasmtime::Error::msg(format!("lock error: {e}")))?
+                            .insert(loom_id, bytecode.clone());
+                    }
+
+                    let host = caller.data_mut();
+                    if host.pending_transfers.len() + sub_host_state.pending_transfers.len()
+                        > MAX_PENDING_TRANSFERS
+                        || host.instantiations.len() + sub_host_state.instantiations.len()
+                            >= MAX_PENDING_INSTANTIATIONS
+                    {
+                        return Err(wasmtime::Error::msg(
+                            "norn_instantiate: too many pending effects",
+                        ));
+                    }
+                    host.storage_usage = sub_host_state.storage_usage;
+                    host.pending_transfers
+                        .extend(sub_host_state.pending_transfers);
+                    host.instantiations.extend(sub_host_state.instantiations);
+                    for l in sub_host_state.logs {
+                        if host.logs.len() >= MAX_LOGS {
+                            break;
+                        }
+                        host.logs.push(l);
+                    }
+                    for ev in sub_host_state.events {
+                        if host.events.len() >= MAX_EVENTS {
+                            break;
+                        }
+                        host.events.push(ev);
+                    }
+                    if let (Some(tracer), Some(sub_tracer)) =
+                        (host.tracer.as_mut(), sub_host_state.tracer)
+                    {
+                        tracer.merge(sub_tracer);
+                    }
+                    host.instantiations.push(PendingInstantiation {
+                        loom_id,
+                        code_id,
+                        creator,
+                        state: sub_host_state.state,
+                    });
+                    host.trace_call("norn_instantiate", gas_before, || {
+                        format!("0x{} -> 0x{}", hex::encode(code_id), hex::encode(loom_id))
+                    });
+
+                    memory.data_mut(&mut caller)[out_start..out_end].copy_from_slice(&loom_id);
+                    Ok(0)
+                },
+            )
+            .map_err(|e| LoomError::RuntimeError {
+                reason: format!("failed to register norn_instantiate: {e}"),
+            })?;
+
+        let instance =
+            linker
+                .instantiate(&mut store, &module)
+                .map_err(|e| LoomError::RuntimeError {
+                    reason: format!("failed to instantiate module: {e}"),
+                })?;
+
         Ok(LoomInstance { store, instance })
     }
 }
@@ -754,11 +1647,9 @@ impl LoomInstance {
             .get_typed_func::<(i32, i32), i32>(&mut self.store, "init")
         {
             let (ptr, len) = self.write_input(input);
-            let result =
-                init.call(&mut self.store, (ptr, len))
-                    .map_err(|e| LoomError::RuntimeError {
-                        reason: format!("init execution failed: {e}"),
-                    })?;
+            let result = init
+                .call(&mut self.store, (ptr, len))
+                .map_err(|e| call_error("init execution failed", e))?;
             if result != 0 {
                 return Err(LoomError::RuntimeError {
                     reason: "init returned error".to_string(),
@@ -773,9 +1664,7 @@ impl LoomInstance {
             .get_typed_func::<(), ()>(&mut self.store, "init")
         {
             init.call(&mut self.store, ())
-                .map_err(|e| LoomError::RuntimeError {
-                    reason: format!("init execution failed: {e}"),
-                })?;
+                .map_err(|e| call_error("init execution failed", e))?;
             return Ok(());
         }
 
@@ -797,12 +1686,9 @@ impl LoomInstance {
         {
             let (ptr, len) = self.write_input(input);
 
-            let result =
-                execute
-                    .call(&mut self.store, (ptr, len))
-                    .map_err(|e| LoomError::RuntimeError {
-                        reason: format!("execute failed: {e}"),
-                    })?;
+            let result = execute
+                .call(&mut self.store, (ptr, len))
+                .map_err(|e| call_error("execute failed", e))?;
 
             // Try SDK output buffer first; fall back to i32-as-bytes.
             let output = self.read_output_buffer();
@@ -817,12 +1703,9 @@ impl LoomInstance {
             .instance
             .get_typed_func::<(), i32>(&mut self.store, "execute")
         {
-            let result =
-                execute
-                    .call(&mut self.store, ())
-                    .map_err(|e| LoomError::RuntimeError {
-                        reason: format!("execute failed: {e}"),
-                    })?;
+            let result = execute
+                .call(&mut self.store, ())
+                .map_err(|e| call_error("execute failed", e))?;
 
             let output = self.read_output_buffer();
             if !output.is_empty() {
@@ -844,12 +1727,9 @@ impl LoomInstance {
         {
             let (ptr, len) = self.write_input(input);
 
-            let result =
-                query
-                    .call(&mut self.store, (ptr, len))
-                    .map_err(|e| LoomError::RuntimeError {
-                        reason: format!("query failed: {e}"),
-                    })?;
+            let result = query
+                .call(&mut self.store, (ptr, len))
+                .map_err(|e| call_error("query failed", e))?;
 
             // Try SDK output buffer first; fall back to i32-as-bytes.
             let output = self.read_output_buffer();
@@ -867,8 +1747,10 @@ impl LoomInstance {
     /// Return the amount of gas (fuel) consumed so far.
     pub fn gas_used(&self) -> u64 {
         let remaining = self.store.get_fuel().unwrap_or(0);
-        let limit = self.store.data().gas_meter.limit;
-        limit.saturating_sub(remaining)
+        let host = self.store.data();
+        let fuel_limit = host.gas_schedule.gas_to_fuel(host.gas_meter.limit);
+        host.gas_schedule
+            .fuel_to_gas(fuel_limit.saturating_sub(remaining))
     }
 
     /// Consume this instance and return the host state (with all accumulated
@@ -886,7 +1768,7 @@ impl LoomInstance {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::gas::DEFAULT_GAS_LIMIT;
+    use crate::gas::{DEFAULT_GAS_LIMIT, GAS_DECRYPT, GAS_SIGNATURE_VERIFY, GAS_VRF_VERIFY};
 
     /// Minimal WAT module that exports an `execute` function returning 42.
     const SIMPLE_WAT: &str = r#"
@@ -935,6 +1817,204 @@ mod tests {
         assert!(instance.gas_used() > 0);
     }
 
+    #[test]
+    fn test_host_gas_conformance_v1() {
+        // Host-function charges under schedule v1 are pinned; a cost change
+        // must ship as a new schedule version.
+        let runtime = LoomRuntime::new().unwrap();
+        let wat = r#"
+            (module
+                (import "norn" "norn_state_set" (func $set (param i32 i32 i32 i32)))
+                (import "norn" "norn_state_get" (func $get (param i32 i32 i32 i32) (result i32)))
+                (import "norn" "norn_log" (func $log (param i32 i32)))
+                (memory (export "memory") 1)
+                (data (i32.const 0) "keyval")
+                (func (export "execute") (param i32 i32) (result i32)
+                    (call $set (i32.const 0) (i32.const 3) (i32.const 3) (i32.const 3))
+                    (call $get (i32.const 0) (i32.const 3) (i32.const 100) (i32.const 64))
+                    (drop)
+                    (call $log (i32.const 0) (i32.const 3))
+                    i32.const 0
+                )
+            )
+        "#;
+        let bytecode = compile_wat(wat);
+        let host_state = LoomHostState::new([1u8; 20], 0, 1_000_000, DEFAULT_GAS_LIMIT);
+        assert_eq!(host_state.gas_schedule, crate::gas::GAS_SCHEDULE_V1);
+        let mut instance = runtime.instantiate(&bytecode, host_state).unwrap();
+        instance.call_execute(&[]).unwrap();
+
+        // write 200 + 3*2, read 100 + 3*1, log 50
+        let host = instance.into_host_state();
+        assert_eq!(host.gas_meter.used(), 359);
+    }
+
+    #[test]
+    fn test_storage_gas_consumes_fuel() {
+        let runtime = LoomRuntime::new().unwrap();
+        let wat = r#"
+            (module
+                (import "norn" "norn_state_set" (func $set (param i32 i32 i32 i32)))
+                (memory (export "memory") 1)
+                (data (i32.const 0) "keyval")
+                (func (export "execute") (param i32 i32) (result i32)
+                    (call $set (i32.const 0) (i32.const 3) (i32.const 3) (i32.const 3))
+                    i32.const 0
+                )
+            )
+        "#;
+        let bytecode = compile_wat(wat);
+        let host_state = LoomHostState::new([1u8; 20], 0, 1_000_000, DEFAULT_GAS_LIMIT);
+        let mut instance = runtime.instantiate(&bytecode, host_state).unwrap();
+        instance.call_execute(&[]).unwrap();
+
+        // The write's 206 gas is reflected in fuel, not only the GasMeter.
+        assert!(instance.gas_used() >= 206);
+    }
+
+    #[test]
+    fn test_storage_write_limit() {
+        let runtime = LoomRuntime::new().unwrap();
+        let wat = r#"
+            (module
+                (import "norn" "norn_state_set" (func $set (param i32 i32 i32 i32)))
+                (memory (export "memory") 1)
+                (data (i32.const 0) "keyval")
+                (func (export "execute") (param i32 i32) (result i32)
+                    (loop $again
+                        (call $set (i32.const 0) (i32.const 3) (i32.const 3) (i32.const 3))
+                        (br $again)
+                    )
+                    i32.const 0
+                )
+            )
+        "#;
+        let bytecode = compile_wat(wat);
+        let host_state = LoomHostState::new([1u8; 20], 0, 1_000_000, 100_000_000);
+        let mut instance = runtime.instantiate(&bytecode, host_state).unwrap();
+        let err = instance.call_execute(&[]).unwrap_err();
+        assert!(matches!(
+            err,
+            LoomError::StorageLimitExceeded {
+                resource: "writes",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_validate_module_accepts_contract() {
+        let wat = r#"
+            (module
+                (import "norn" "norn_log" (func $log (param i32 i32)))
+                (memory (export "memory") 1)
+                (func (export "init") (param i32 i32) (result i32) i32.const 0)
+                (func (export "execute") (param i32 i32) (result i32) i32.const 0)
+            )
+        "#;
+        assert!(validate_module(&compile_wat(wat)).is_ok());
+    }
+
+    #[test]
+    fn test_validate_module_rejects_floats() {
+        let wat = r#"
+            (module
+                (memory (export "memory") 1)
+                (func (export "init") (param i32 i32) (result i32) i32.const 0)
+                (func (export "execute") (param i32 i32) (result i32)
+                    f32.const 1.5
+                    i32.trunc_f32_s
+                )
+            )
+        "#;
+        let Err(LoomError::InvalidModule { diagnostics }) = validate_module(&compile_wat(wat))
+        else {
+            panic!("expected InvalidModule");
+        };
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].kind, DiagnosticKind::NonDeterministic);
+    }
+
+    #[test]
+    fn test_validate_module_rejects_malformed() {
+        let Err(LoomError::InvalidModule { diagnostics }) = validate_module(&[0xFF, 0xFF, 0xFF])
+        else {
+            panic!("expected InvalidModule");
+        };
+        assert_eq!(diagnostics[0].kind, DiagnosticKind::Malformed);
+    }
+
+    #[test]
+    fn test_validate_module_reports_all_problems() {
+        let wat = r#"
+            (module
+                (import "wasi_snapshot_preview1" "random_get" (func (param i32 i32) (result i32)))
+                (import "norn" "norn_unknown" (func))
+                (memory (export "memory") 1024)
+                (func (export "execute") (param i32 i32) (result i32) i32.const 0)
+            )
+        "#;
+        let Err(LoomError::InvalidModule { diagnostics }) = validate_module(&compile_wat(wat))
+        else {
+            panic!("expected InvalidModule");
+        };
+        let kinds: Vec<_> = diagnostics.iter().map(|d| d.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                DiagnosticKind::DisallowedImport,
+                DiagnosticKind::DisallowedImport,
+                DiagnosticKind::ExcessiveMemory,
+                DiagnosticKind::MissingExport,
+            ]
+        );
+        assert!(diagnostics[3].message.contains("`init`"));
+    }
+
+    #[test]
+    fn test_module_cache_evicts_least_recently_used() {
+        let engine = LoomRuntime::new().unwrap().engine;
+        let module = Module::new(&engine, compile_wat(SIMPLE_WAT)).unwrap();
+        let mut cache = ModuleCache::new(2, None);
+
+        cache.insert([1u8; 32], module.clone());
+        cache.insert([2u8; 32], module.clone());
+        assert!(cache.get(&engine, &[1u8; 32]).is_some());
+        cache.insert([3u8; 32], module);
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&engine, &[1u8; 32]).is_some());
+        assert!(cache.get(&engine, &[2u8; 32]).is_none());
+        assert!(cache.get(&engine, &[3u8; 32]).is_some());
+    }
+
+    #[test]
+    fn test_module_cache_persists_to_disk() {
+        let dir = std::env::temp_dir().join(format!("norn-module-cache-{}", std::process::id()));
+        let engine = LoomRuntime::new().unwrap().engine;
+        let bytecode = compile_wat(SIMPLE_WAT);
+        let code_hash = blake3_hash(&bytecode);
+
+        let mut cache = ModuleCache::new(2, Some(dir.clone()));
+        cache.insert(code_hash, Module::new(&engine, &bytecode).unwrap());
+
+        // A fresh cache (as after a restart) loads the artifact from disk.
+        let mut restarted = ModuleCache::new(2, Some(dir.clone()));
+        let module = restarted.get(&engine, &code_hash).unwrap();
+        assert_eq!(restarted.len(), 1);
+
+        let host_state = LoomHostState::new([1u8; 20], 100, 1_000_000, DEFAULT_GAS_LIMIT);
+        let mut store = Store::new(&engine, host_state);
+        store.set_fuel(1_000_000).unwrap();
+        let instance = Instance::new(&mut store, &module, &[]).unwrap();
+        let execute = instance
+            .get_typed_func::<(i32, i32), i32>(&mut store, "execute")
+            .unwrap();
+        assert_eq!(execute.call(&mut store, (0, 0)).unwrap(), 42);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn test_init_then_execute() {
         let runtime = LoomRuntime::new().unwrap();
@@ -1053,6 +2133,48 @@ mod tests {
         assert_eq!(result, (-1i32).to_le_bytes().to_vec());
     }
 
+    #[test]
+    fn test_balance_of_writes_u128_to_memory() {
+        use crate::host::BalanceView;
+        use std::sync::Arc;
+
+        struct Ledger;
+        impl BalanceView for Ledger {
+            fn balance(&self, address: &[u8; 20], _token_id: &[u8; 32]) -> u128 {
+                if *address == [7u8; 20] {
+                    u64::MAX as u128 + 5
+                } else {
+                    0
+                }
+            }
+        }
+
+        let runtime = LoomRuntime::new().unwrap();
+        let wat = r#"
+            (module
+                (import "norn" "norn_balance_of" (func $bal (param i32 i32 i32)))
+                (memory (export "memory") 1)
+                ;; At offset 0: 20-byte address, offset 20: 32-byte token id
+                (data (i32.const 0) "\07\07\07\07\07\07\07\07\07\07\07\07\07\07\07\07\07\07\07\07")
+                (func (export "execute") (param i32 i32) (result i32)
+                    (call $bal (i32.const 0) (i32.const 20) (i32.const 100))
+                    i32.const 0
+                )
+            )
+        "#;
+        let bytecode = compile_wat(wat);
+        let mut host_state = LoomHostState::new([1u8; 20], 100, 1_000_000, DEFAULT_GAS_LIMIT);
+        host_state.balance_view = Some(Arc::new(Ledger));
+        let mut instance = runtime.instantiate(&bytecode, host_state).unwrap();
+        instance.call_execute(&[]).unwrap();
+
+        let memory = instance.memory().unwrap();
+        let data = memory.data(&instance.store);
+        let mut out = [0u8; 16];
+        out.copy_from_slice(&data[100..116]);
+        assert_eq!(u128::from_le_bytes(out), u64::MAX as u128 + 5);
+    }
+
     #[test]
     fn test_transfer_with_negative_amount_fails() {
         let runtime = LoomRuntime::new().unwrap();
@@ -1101,6 +2223,243 @@ mod tests {
         assert!(instance.call_execute(&[]).is_err());
     }
 
+    #[test]
+    fn test_vrf_verify_host_function() {
+        let keypair = norn_crypto::keys::Keypair::from_seed(&[3u8; 32]);
+        let proof = norn_crypto::vrf::prove(&keypair, b"seed");
+        let escape = |bytes: &[u8]| {
+            bytes
+                .iter()
+                .map(|b| format!("\\{b:02x}"))
+                .collect::<String>()
+        };
+        let wat = format!(
+            r#"
+            (module
+                (import "norn" "norn_vrf_verify" (func $vrf (param i32 i32 i32 i32 i32) (result i32)))
+                (memory (export "memory") 1)
+                (data (i32.const 0) "{}")
+                (data (i32.const 32) "{}")
+                (data (i32.const 112) "seed")
+                (func (export "execute") (param i32 i32) (result i32)
+                    (call $vrf (i32.const 0) (i32.const 112) (local.get 1) (i32.const 32) (i32.const 256))
+                )
+            )
+        "#,
+            escape(&keypair.public_key()),
+            escape(&proof)
+        );
+        let runtime = LoomRuntime::new().unwrap();
+        let bytecode = compile_wat(&wat);
+
+        // The input length selects how much of "seed" is the VRF input.
+        let host_state = LoomHostState::new([1u8; 20], 100, 1_000_000, DEFAULT_GAS_LIMIT);
+        let mut instance = runtime.instantiate(&bytecode, host_state).unwrap();
+        assert_eq!(instance.call_execute(b"four").unwrap(), 1i32.to_le_bytes());
+        let memory = instance.memory().unwrap();
+        assert_eq!(
+            memory.data(&instance.store)[256..320],
+            norn_crypto::vrf::proof_to_hash(&proof).unwrap()
+        );
+        assert!(instance.gas_used() >= GAS_VRF_VERIFY);
+
+        let host_state = LoomHostState::new([1u8; 20], 100, 1_000_000, DEFAULT_GAS_LIMIT);
+        let mut instance = runtime.instantiate(&bytecode, host_state).unwrap();
+        assert_eq!(instance.call_execute(b"abc").unwrap(), 0i32.to_le_bytes());
+    }
+
+    #[test]
+    fn test_verify_signature_host_function() {
+        let keypair = norn_crypto::keys::Keypair::from_seed(&[3u8; 32]);
+        let signature = keypair.sign(b"approve");
+        let escape = |bytes: &[u8]| {
+            bytes
+                .iter()
+                .map(|b| format!("\\{b:02x}"))
+                .collect::<String>()
+        };
+        let wat = format!(
+            r#"
+            (module
+                (import "norn" "norn_verify_signature" (func $verify (param i32 i32 i32 i32) (result i32)))
+                (memory (export "memory") 1)
+                (data (i32.const 0) "{}")
+                (data (i32.const 32) "{}")
+                (data (i32.const 96) "approved")
+                (func (export "execute") (param i32 i32) (result i32)
+                    (call $verify (i32.const 0) (i32.const 96) (local.get 1) (i32.const 32))
+                )
+            )
+        "#,
+            escape(&keypair.public_key()),
+            escape(&signature)
+        );
+        let runtime = LoomRuntime::new().unwrap();
+        let bytecode = compile_wat(&wat);
+
+        // The input length selects how much of "approved" is the message.
+        let host_state = LoomHostState::new([1u8; 20], 100, 1_000_000, DEFAULT_GAS_LIMIT);
+        let mut instance = runtime.instantiate(&bytecode, host_state).unwrap();
+        assert_eq!(
+            instance.call_execute(b"seven!!").unwrap(),
+            1i32.to_le_bytes()
+        );
+        assert!(instance.gas_used() >= GAS_SIGNATURE_VERIFY);
+
+        let host_state = LoomHostState::new([1u8; 20], 100, 1_000_000, DEFAULT_GAS_LIMIT);
+        let mut instance = runtime.instantiate(&bytecode, host_state).unwrap();
+        assert_eq!(
+            instance.call_execute(b"eight!!!").unwrap(),
+            0i32.to_le_bytes()
+        );
+    }
+
+    #[test]
+    fn test_decrypt_host_function() {
+        let key = [9u8; 32];
+        let (nonce, ciphertext) = norn_crypto::encryption::seal(&key, b"bid:500").unwrap();
+        let escape = |bytes: &[u8]| {
+            bytes
+                .iter()
+                .map(|b| format!("\\{b:02x}"))
+                .collect::<String>()
+        };
+        let wat = format!(
+            r#"
+            (module
+                (import "norn" "norn_decrypt" (func $decrypt (param i32 i32 i32 i32 i32) (result i32)))
+                (memory (export "memory") 1)
+                (data (i32.const 0) "{}")
+                (data (i32.const 32) "{}")
+                (data (i32.const 64) "{}")
+                (func (export "execute") (param i32 i32) (result i32)
+                    (call $decrypt (i32.const 0) (i32.const 32) (i32.const 64) (local.get 1) (i32.const 256))
+                )
+            )
+        "#,
+            escape(&key),
+            escape(&nonce),
+            escape(&ciphertext)
+        );
+        let runtime = LoomRuntime::new().unwrap();
+        let bytecode = compile_wat(&wat);
+
+        // The input length selects how much of the ciphertext is opened.
+        let host_state = LoomHostState::new([1u8; 20], 100, 1_000_000, DEFAULT_GAS_LIMIT);
+        let mut instance = runtime.instantiate(&bytecode, host_state).unwrap();
+        let input = vec![0u8; ciphertext.len()];
+        assert_eq!(instance.call_execute(&input).unwrap(), 1i32.to_le_bytes());
+        let memory = instance.memory().unwrap();
+        assert_eq!(&memory.data(&instance.store)[256..263], b"bid:500");
+        assert!(instance.gas_used() >= GAS_DECRYPT);
+
+        // A truncated ciphertext fails authentication.
+        let host_state = LoomHostState::new([1u8; 20], 100, 1_000_000, DEFAULT_GAS_LIMIT);
+        let mut instance = runtime.instantiate(&bytecode, host_state).unwrap();
+        let input = vec![0u8; ciphertext.len() - 1];
+        assert_eq!(instance.call_execute(&input).unwrap(), 0i32.to_le_bytes());
+    }
+
+    #[test]
+    fn test_transfer_from_derived_sub_address() {
+        let runtime = LoomRuntime::new().unwrap();
+        let wat = r#"
+            (module
+                (import "norn" "norn_derive_address" (func $derive (param i32 i32 i32)))
+                (import "norn" "norn_transfer" (func $transfer (param i32 i32 i32 i64)))
+                (memory (export "memory") 1)
+                (data (i32.const 0) "deal")
+                (data (i32.const 20) "\02\02\02\02\02\02\02\02\02\02\02\02\02\02\02\02\02\02\02\02")
+                (func (export "execute") (param i32 i32) (result i32)
+                    (call $derive (i32.const 0) (i32.const 4) (i32.const 100))
+                    (call $transfer (i32.const 100) (i32.const 20) (i32.const 40) (i64.const 25))
+                    i32.const 0
+                )
+            )
+        "#;
+        let bytecode = compile_wat(wat);
+        let loom_id = [5u8; 32];
+        let mut host_state = LoomHostState::new([9u8; 20], 100, 1_000_000, DEFAULT_GAS_LIMIT);
+        host_state.current_loom_id = Some(loom_id);
+        let mut instance = runtime.instantiate(&bytecode, host_state).unwrap();
+        instance.call_execute(&[]).unwrap();
+
+        let host = instance.into_host_state();
+        assert_eq!(host.pending_transfers.len(), 1);
+        assert_eq!(
+            host.pending_transfers[0].from,
+            norn_types::primitives::derive_sub_address(&loom_id, b"deal")
+        );
+        assert_eq!(host.pending_transfers[0].amount, 25);
+    }
+
+    #[test]
+    fn test_instantiate_from_stored_code() {
+        use std::collections::HashMap;
+        use std::sync::Arc;
+
+        let runtime = LoomRuntime::new().unwrap();
+        let child = compile_wat(
+            r#"
+            (module
+                (import "norn" "norn_state_set" (func $set (param i32 i32 i32 i32)))
+                (memory (export "memory") 1)
+                (data (i32.const 0) "keyval")
+                (func (export "init")
+                    (call $set (i32.const 0) (i32.const 3) (i32.const 3) (i32.const 3))
+                )
+            )
+        "#,
+        );
+        let code_id = norn_crypto::hash::blake3_hash(&child);
+        let mut parent_wat = String::from(
+            r#"
+            (module
+                (import "norn" "norn_instantiate" (func $inst (param i32 i32 i32 i32 i32 i32) (result i32)))
+                (memory (export "memory") 1)
+                (data (i32.const 0) ""#,
+        );
+        for b in code_id {
+            parent_wat.push_str(&format!("\\{b:02x}"));
+        }
+        parent_wat.push_str(
+            r#"")
+                (data (i32.const 32) "pool")
+                (func (export "execute") (param i32 i32) (result i32)
+                    (call $inst (i32.const 0) (i32.const 0) (i32.const 0)
+                                (i32.const 32) (i32.const 4) (i32.const 100))
+                    (drop)
+                    (call $inst (i32.const 0) (i32.const 0) (i32.const 0)
+                                (i32.const 32) (i32.const 4) (i32.const 200))
+                )
+            )
+        "#,
+        );
+        let parent = compile_wat(&parent_wat);
+
+        let loom_id = [5u8; 32];
+        let mut codes = HashMap::new();
+        codes.insert(code_id, child);
+        let mut host_state = LoomHostState::new([9u8; 20], 100, 1_000_000, DEFAULT_GAS_LIMIT);
+        host_state.current_loom_id = Some(loom_id);
+        host_state.codes = Some(Arc::new(codes));
+        let mut instance = runtime.instantiate(&parent, host_state).unwrap();
+        // The second call reuses the salt, so it is rejected.
+        let result = instance.call_execute(&[]).unwrap();
+        assert_eq!(result, (-1i32).to_le_bytes().to_vec());
+
+        let host = instance.into_host_state();
+        assert_eq!(host.instantiations.len(), 1);
+        let creator = norn_types::primitives::derive_contract_address(&loom_id);
+        let inst = &host.instantiations[0];
+        assert_eq!(
+            inst.loom_id,
+            norn_types::loom::derive_instance_id(&creator, &code_id, b"pool")
+        );
+        assert_eq!(inst.creator, creator);
+        assert_eq!(inst.state.get(b"key".as_slice()), Some(&b"val".to_vec()));
+    }
+
     #[test]
     fn test_memory_limit_enforced() {
         let runtime = LoomRuntime::new().unwrap();
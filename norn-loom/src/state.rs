@@ -1,8 +1,12 @@
 use std::collections::HashMap;
 
+use borsh::{BorshDeserialize, BorshSerialize};
 use norn_crypto::hash::blake3_hash;
+use norn_crypto::merkle::{build_merkle_root, hash_list_leaf};
 use norn_types::primitives::{Hash, LoomId};
 
+use crate::error::LoomError;
+
 /// In-memory key-value state for a single loom contract.
 #[derive(Debug, Clone)]
 pub struct LoomState {
@@ -57,6 +61,104 @@ impl LoomState {
 
         blake3_hash(&buf)
     }
+
+    /// Compute a Merkle root over the state entries.
+    ///
+    /// Each leaf is the borsh-encoded (key, value) pair, in key order, so
+    /// individual entries of an exported snapshot can be proven against it.
+    pub fn merkle_root(&self) -> Hash {
+        merkle_root_of(&self.sorted_entries())
+    }
+
+    /// Export the state as a self-verifying snapshot.
+    pub fn export(&self) -> LoomStateSnapshot {
+        let entries = self.sorted_entries();
+        LoomStateSnapshot {
+            loom_id: self.loom_id,
+            state_hash: self.compute_hash(),
+            merkle_root: merkle_root_of(&entries),
+            entries,
+        }
+    }
+
+    /// Rebuild a state from a snapshot after verifying it.
+    pub fn import(snapshot: LoomStateSnapshot) -> Result<Self, LoomError> {
+        snapshot.verify()?;
+        Ok(Self {
+            loom_id: snapshot.loom_id,
+            data: snapshot.entries.into_iter().collect(),
+        })
+    }
+
+    fn sorted_entries(&self) -> Vec<(Vec<u8>, Vec<u8>)> {
+        let mut entries: Vec<(Vec<u8>, Vec<u8>)> = self
+            .data
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        entries
+    }
+}
+
+/// A portable, auditable copy of a loom's state.
+///
+/// Entries are sorted by key. `state_hash` matches [`LoomState::compute_hash`]
+/// (what the loom anchors on chain) and `merkle_root` commits to each entry.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct LoomStateSnapshot {
+    /// The loom the state was exported from.
+    pub loom_id: LoomId,
+    /// Hash of the full state, as anchored by the loom.
+    pub state_hash: Hash,
+    /// Merkle root over the borsh-encoded entries.
+    pub merkle_root: Hash,
+    /// Key-value entries, sorted by key.
+    pub entries: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+impl LoomStateSnapshot {
+    /// Check that the entries are sorted, unique, and match both roots.
+    pub fn verify(&self) -> Result<(), LoomError> {
+        if self.entries.windows(2).any(|w| w[0].0 >= w[1].0) {
+            return Err(LoomError::StateError {
+                reason: "snapshot entries are not sorted by unique key".to_string(),
+            });
+        }
+        let merkle_root = merkle_root_of(&self.entries);
+        if merkle_root != self.merkle_root {
+            return Err(LoomError::StateError {
+                reason: format!(
+                    "snapshot merkle root mismatch: expected {}, computed {}",
+                    hex::encode(self.merkle_root),
+                    hex::encode(merkle_root)
+                ),
+            });
+        }
+        let state = LoomState {
+            loom_id: self.loom_id,
+            data: self.entries.iter().cloned().collect(),
+        };
+        let state_hash = state.compute_hash();
+        if state_hash != self.state_hash {
+            return Err(LoomError::StateError {
+                reason: format!(
+                    "snapshot state hash mismatch: expected {}, computed {}",
+                    hex::encode(self.state_hash),
+                    hex::encode(state_hash)
+                ),
+            });
+        }
+        Ok(())
+    }
+}
+
+fn merkle_root_of(entries: &[(Vec<u8>, Vec<u8>)]) -> Hash {
+    let leaves: Vec<Hash> = entries
+        .iter()
+        .map(|pair| hash_list_leaf(&borsh::to_vec(pair).unwrap_or_default()))
+        .collect();
+    build_merkle_root(&leaves)
 }
 
 #[cfg(test)]
@@ -130,4 +232,41 @@ mod tests {
 
         assert_ne!(state_a.compute_hash(), state_b.compute_hash());
     }
+
+    #[test]
+    fn test_snapshot_roundtrip() {
+        let mut state = LoomState::new([3u8; 32]);
+        state.set(b"b".to_vec(), b"2".to_vec());
+        state.set(b"a".to_vec(), b"1".to_vec());
+
+        let snapshot = state.export();
+        assert_eq!(snapshot.entries[0].0, b"a".to_vec());
+        assert_eq!(snapshot.state_hash, state.compute_hash());
+        assert_eq!(snapshot.merkle_root, state.merkle_root());
+
+        let bytes = borsh::to_vec(&snapshot).unwrap();
+        let decoded: LoomStateSnapshot = borsh::from_slice(&bytes).unwrap();
+        let restored = LoomState::import(decoded).unwrap();
+        assert_eq!(restored.data, state.data);
+        assert_eq!(restored.loom_id, state.loom_id);
+    }
+
+    #[test]
+    fn test_snapshot_rejects_tampering() {
+        let mut state = LoomState::new([3u8; 32]);
+        state.set(b"a".to_vec(), b"1".to_vec());
+        state.set(b"b".to_vec(), b"2".to_vec());
+
+        let mut tampered = state.export();
+        tampered.entries[1].1 = b"9".to_vec();
+        assert!(LoomState::import(tampered).is_err());
+
+        let mut unsorted = state.export();
+        unsorted.entries.swap(0, 1);
+        assert!(unsorted.verify().is_err());
+
+        let mut wrong_hash = state.export();
+        wrong_hash.state_hash = [0u8; 32];
+        assert!(wrong_hash.verify().is_err());
+    }
 }
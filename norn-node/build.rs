@@ -0,0 +1,9 @@
+//! Generates the gRPC service from `proto/`. The protobuf files are parsed
+//! by `protox`, so building needs no `protoc`.
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("cargo:rerun-if-changed=proto");
+    let descriptors = protox::compile(["norn/v1/node.proto"], ["proto"])?;
+    tonic_build::configure().compile_fds(descriptors)?;
+    Ok(())
+}
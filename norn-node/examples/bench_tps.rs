@@ -2,13 +2,17 @@
 //!
 //! Measures Norn protocol throughput across the full transaction pipeline:
 //! keypair generation, transfer construction, knot signing, thread state
-//! management, commitment creation, mempool ingestion, and block production.
+//! management, commitment creation, sequential and parallel commitment
+//! verification and application, mempool ingestion, and block production.
 
 use std::time::Instant;
 
 use norn_crypto::address::pubkey_to_address;
+use norn_crypto::bls::BlsKeypair;
 use norn_crypto::hash::blake3_hash;
 use norn_crypto::keys::{batch_verify, verify, Keypair};
+use norn_crypto::merkle::SparseMerkleTree;
+use norn_loom::host::BalanceView;
 use norn_thread::knot::{add_signature, sign_knot, KnotBuilder};
 use norn_thread::state::{apply_transfer, compute_state_hash};
 use norn_thread::thread::Thread;
@@ -18,10 +22,23 @@ use norn_types::primitives::*;
 use norn_types::weave::{
     CommitmentUpdate, FeeState, Registration, Validator, ValidatorSet, WeaveState,
 };
+use norn_weave::commitment::{
+    apply_commitment, apply_commitments, validate_commitment, validate_commitments,
+};
 use norn_weave::engine::WeaveEngine;
 
 // ── Helpers (same as demo.rs) ───────────────────────────────────────────
 
+/// This example keeps balances in its threads rather than a ledger, so
+/// every committer is treated as able to pay its fee.
+struct Unmetered;
+
+impl BalanceView for Unmetered {
+    fn balance(&self, _address: &Address, _token_id: &TokenId) -> Amount {
+        Amount::MAX
+    }
+}
+
 fn registration_signing_data(reg: &Registration) -> Vec<u8> {
     let mut data = Vec::new();
     data.extend_from_slice(&reg.thread_id);
@@ -40,6 +57,7 @@ fn commitment_signing_data(c: &CommitmentUpdate) -> Vec<u8> {
     data.extend_from_slice(&c.prev_commitment_hash);
     data.extend_from_slice(&c.knot_count.to_le_bytes());
     data.extend_from_slice(&c.timestamp.to_le_bytes());
+    data.extend_from_slice(&c.priority_fee.to_le_bytes());
     data
 }
 
@@ -67,6 +85,7 @@ fn make_signed_commitment(thread: &Thread, timestamp: Timestamp) -> CommitmentUp
         prev_commitment_hash: [0u8; 32],
         knot_count: thread.uncommitted_count() as u64,
         timestamp,
+        priority_fee: 0,
         signature: [0u8; 64],
     };
     let sig_data = commitment_signing_data(&c);
@@ -104,12 +123,27 @@ fn fmt_duration(secs: f64) -> String {
 
 // ── Engine factory ──────────────────────────────────────────────────────
 
+fn empty_weave_state() -> WeaveState {
+    WeaveState {
+        height: 0,
+        latest_hash: [0u8; 32],
+        threads_root: [0u8; 32],
+        thread_count: 0,
+        fee_state: FeeState {
+            base_fee: 100,
+            fee_multiplier: 1000,
+            epoch_fees: 0,
+        },
+    }
+}
+
 fn make_engine() -> WeaveEngine {
     let validator_kp = Keypair::generate();
     let pubkey = validator_kp.public_key();
     let vs = ValidatorSet {
         validators: vec![Validator {
             pubkey,
+            bls_pubkey: BlsKeypair::from_keypair(&validator_kp).public_key(),
             address: pubkey_to_address(&pubkey),
             stake: 1_000_000_000_000,
             active: true,
@@ -117,18 +151,7 @@ fn make_engine() -> WeaveEngine {
         total_stake: 1_000_000_000_000,
         epoch: 0,
     };
-    let initial_state = WeaveState {
-        height: 0,
-        latest_hash: [0u8; 32],
-        threads_root: [0u8; 32],
-        thread_count: 0,
-        fee_state: FeeState {
-            base_fee: 100,
-            fee_multiplier: 1000,
-            epoch_fees: 0,
-        },
-    };
-    WeaveEngine::new(validator_kp, vs, initial_state)
+    WeaveEngine::new(validator_kp, vs, empty_weave_state())
 }
 
 // ── Micro-benchmarks ────────────────────────────────────────────────────
@@ -242,7 +265,7 @@ fn bench_mempool_ingestion(n: usize) -> f64 {
             .add_registration(make_signed_registration(t.keypair(), ts_base))
             .unwrap();
     }
-    engine.produce_block(ts_base + 1, [0u8; 32]);
+    engine.produce_block(ts_base + 1, [0u8; 32], &Unmetered);
 
     // Pre-build commitments, then measure just the add_commitment calls
     let commitments: Vec<CommitmentUpdate> = threads
@@ -257,6 +280,58 @@ fn bench_mempool_ingestion(n: usize) -> f64 {
     n as f64 / start.elapsed().as_secs_f64()
 }
 
+/// Signed commitments from `n` distinct threads.
+fn make_commitments(n: usize, timestamp: Timestamp) -> Vec<CommitmentUpdate> {
+    (0..n)
+        .map(|i| {
+            let seed = blake3_hash(&(i as u64 + 0x4000000).to_le_bytes());
+            let thread = Thread::new(Keypair::from_seed(&seed), timestamp);
+            make_signed_commitment(&thread, timestamp)
+        })
+        .collect()
+}
+
+fn bench_commitment_verify_sequential(n: usize) -> f64 {
+    let commitments = make_commitments(n, 300_000);
+    let start = Instant::now();
+    for c in &commitments {
+        let _ = std::hint::black_box(validate_commitment(c, None, None, 300_000));
+    }
+    n as f64 / start.elapsed().as_secs_f64()
+}
+
+fn bench_commitment_verify_parallel(n: usize) -> f64 {
+    let commitments = make_commitments(n, 300_000);
+    let start = Instant::now();
+    let _ = std::hint::black_box(validate_commitments(
+        &commitments,
+        |_| None,
+        |_| None,
+        300_000,
+    ));
+    n as f64 / start.elapsed().as_secs_f64()
+}
+
+fn bench_commitment_apply_sequential(n: usize) -> f64 {
+    let commitments = make_commitments(n, 300_000);
+    let mut state = empty_weave_state();
+    let mut tree = SparseMerkleTree::new();
+    let start = Instant::now();
+    for c in &commitments {
+        apply_commitment(&mut state, &mut tree, c).unwrap();
+    }
+    n as f64 / start.elapsed().as_secs_f64()
+}
+
+fn bench_commitment_apply_batch(n: usize) -> f64 {
+    let commitments = make_commitments(n, 300_000);
+    let mut state = empty_weave_state();
+    let mut tree = SparseMerkleTree::new();
+    let start = Instant::now();
+    apply_commitments(&mut state, &mut tree, &commitments).unwrap();
+    n as f64 / start.elapsed().as_secs_f64()
+}
+
 // ── End-to-end TPS benchmark ────────────────────────────────────────────
 
 struct E2eResult {
@@ -297,7 +372,7 @@ fn bench_e2e(n: usize) -> E2eResult {
     }
     let reg_start = Instant::now();
     engine
-        .produce_block(ts_base + 1, [0u8; 32])
+        .produce_block(ts_base + 1, [0u8; 32], &Unmetered)
         .expect("registration block");
     let registration_block_secs = reg_start.elapsed().as_secs_f64();
 
@@ -389,7 +464,7 @@ fn bench_e2e(n: usize) -> E2eResult {
     // 5. Produce block (the core measurement)
     let block_start = Instant::now();
     let block = engine
-        .produce_block(ts_commit + 1, [0u8; 32])
+        .produce_block(ts_commit + 1, [0u8; 32], &Unmetered)
         .expect("commitment block");
     let block_production_secs = block_start.elapsed().as_secs_f64();
 
@@ -490,6 +565,30 @@ fn main() {
         fmt_ops(ops)
     );
 
+    let ops = bench_commitment_verify_sequential(10_000);
+    println!(
+        "  Commitment verify     10,000 ops    {} ops/sec",
+        fmt_ops(ops)
+    );
+
+    let ops = bench_commitment_verify_parallel(10_000);
+    println!(
+        "  Commitment verify ∥   10,000 ops    {} ops/sec",
+        fmt_ops(ops)
+    );
+
+    let ops = bench_commitment_apply_sequential(10_000);
+    println!(
+        "  Commitment apply      10,000 ops    {} ops/sec",
+        fmt_ops(ops)
+    );
+
+    let ops = bench_commitment_apply_batch(10_000);
+    println!(
+        "  Commitment apply ∥    10,000 ops    {} ops/sec",
+        fmt_ops(ops)
+    );
+
     println!();
 
     // ── End-to-end TPS ──────────────────────────────────────────────
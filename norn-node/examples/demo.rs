@@ -7,7 +7,9 @@
 //! 4. Produce blocks and verify balances
 
 use norn_crypto::address::pubkey_to_address;
+use norn_crypto::bls::BlsKeypair;
 use norn_crypto::keys::Keypair;
+use norn_loom::host::BalanceView;
 use norn_thread::knot::{add_signature, sign_knot, KnotBuilder};
 use norn_thread::state::{apply_transfer, compute_state_hash};
 use norn_thread::thread::Thread;
@@ -19,6 +21,16 @@ use norn_types::weave::{
 };
 use norn_weave::engine::WeaveEngine;
 
+/// This example keeps balances in its threads rather than a ledger, so
+/// every committer is treated as able to pay its fee.
+struct Unmetered;
+
+impl BalanceView for Unmetered {
+    fn balance(&self, _address: &Address, _token_id: &TokenId) -> Amount {
+        Amount::MAX
+    }
+}
+
 fn registration_signing_data(reg: &Registration) -> Vec<u8> {
     let mut data = Vec::new();
     data.extend_from_slice(&reg.thread_id);
@@ -37,6 +49,7 @@ fn commitment_signing_data(c: &CommitmentUpdate) -> Vec<u8> {
     data.extend_from_slice(&c.prev_commitment_hash);
     data.extend_from_slice(&c.knot_count.to_le_bytes());
     data.extend_from_slice(&c.timestamp.to_le_bytes());
+    data.extend_from_slice(&c.priority_fee.to_le_bytes());
     data
 }
 
@@ -64,6 +77,7 @@ fn make_signed_commitment(thread: &Thread, timestamp: Timestamp) -> CommitmentUp
         prev_commitment_hash: [0u8; 32],
         knot_count: thread.uncommitted_count() as u64,
         timestamp,
+        priority_fee: 0,
         signature: [0u8; 64],
     };
     let sig_data = commitment_signing_data(&c);
@@ -81,6 +95,7 @@ fn main() {
     let vs = ValidatorSet {
         validators: vec![Validator {
             pubkey,
+            bls_pubkey: BlsKeypair::from_keypair(&validator_kp).public_key(),
             address: pubkey_to_address(&pubkey),
             stake: 1_000_000_000_000,
             active: true,
@@ -143,7 +158,7 @@ fn main() {
     // ── 5. Produce Block #1 (registrations) ──
     println!("[5] Producing Block #1...");
     let block1 = engine
-        .produce_block(1001, [0u8; 32])
+        .produce_block(1001, [0u8; 32], &Unmetered)
         .expect("block should be produced");
     println!("    Block #{} produced!", block1.height);
     println!("    Hash:          {}", hex::encode(block1.hash));
@@ -246,7 +261,7 @@ fn main() {
     // ── 9. Produce Block #2 (commitments) ──
     println!("[9] Producing Block #2...");
     let block2 = engine
-        .produce_block(1004, [0u8; 32])
+        .produce_block(1004, [0u8; 32], &Unmetered)
         .expect("block should be produced");
     println!("    Block #{} produced!", block2.height);
     println!("    Hash:          {}", hex::encode(block2.hash));
@@ -0,0 +1,353 @@
+//! EVM side of the bridge: JSON-RPC log polling, `Locked` event decoding,
+//! and release signing.
+
+use jsonrpsee::core::client::ClientT;
+use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
+use jsonrpsee::rpc_params;
+use k256::ecdsa::SigningKey;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+
+use norn_types::primitives::Address;
+
+use crate::error::NodeError;
+
+/// A 20-byte address on the EVM chain.
+pub type EvmAddress = [u8; 20];
+
+/// Signature of the event the lock contract emits for each deposit. The
+/// recipient is the Norn address credited on the bridging loom.
+pub const LOCKED_EVENT_SIGNATURE: &str = "Locked(address,address,uint256,bytes20,uint256)";
+
+/// Timeout for a single EVM JSON-RPC request.
+const EVM_RPC_TIMEOUT_SECS: u64 = 30;
+
+/// Keccak-256 as used by the EVM.
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    Keccak256::digest(data).into()
+}
+
+/// `topic0` of `Locked` logs.
+pub fn locked_topic() -> [u8; 32] {
+    keccak256(LOCKED_EVENT_SIGNATURE.as_bytes())
+}
+
+/// A log entry as returned by `eth_getLogs`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvmLog {
+    pub address: String,
+    pub topics: Vec<String>,
+    pub data: String,
+    pub block_number: String,
+    pub transaction_hash: String,
+    pub log_index: String,
+    /// Set when the log was dropped by a reorg.
+    #[serde(default)]
+    pub removed: bool,
+}
+
+/// A decoded `Locked` event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockEvent {
+    pub lock_contract: EvmAddress,
+    pub token: EvmAddress,
+    pub sender: EvmAddress,
+    pub amount: u128,
+    pub recipient: Address,
+    /// Per-contract deposit counter; identifies the lock on the loom.
+    pub nonce: u64,
+    pub block_number: u64,
+    pub tx_hash: [u8; 32],
+}
+
+impl LockEvent {
+    /// Decode a `Locked` log:
+    /// `topics = [sig, token, sender]`, `data = amount || recipient || nonce`.
+    pub fn parse(log: &EvmLog) -> Result<Self, String> {
+        if log.topics.len() != 3 {
+            return Err(format!("expected 3 topics, got {}", log.topics.len()));
+        }
+        if decode_word(&log.topics[0])? != locked_topic() {
+            return Err("not a Locked event".to_string());
+        }
+        let data = decode_hex(&log.data)?;
+        if data.len() != 96 {
+            return Err(format!("expected 96 bytes of data, got {}", data.len()));
+        }
+        let recipient_word = &data[32..64];
+        if recipient_word[20..].iter().any(|&b| b != 0) {
+            return Err("recipient is not a left-aligned bytes20".to_string());
+        }
+        let mut recipient = [0u8; 20];
+        recipient.copy_from_slice(&recipient_word[..20]);
+        Ok(LockEvent {
+            lock_contract: decode_address(&log.address)?,
+            token: word_to_address(&decode_word(&log.topics[1])?)?,
+            sender: word_to_address(&decode_word(&log.topics[2])?)?,
+            amount: word_to_u128(&data[..32])?,
+            recipient,
+            nonce: word_to_u128(&data[64..])?
+                .try_into()
+                .map_err(|_| "nonce exceeds u64".to_string())?,
+            block_number: parse_quantity(&log.block_number)?,
+            tx_hash: decode_word(&log.transaction_hash)?,
+        })
+    }
+}
+
+/// Minimal EVM JSON-RPC client.
+pub struct EvmClient {
+    client: HttpClient,
+}
+
+impl EvmClient {
+    pub fn new(url: &str) -> Result<Self, NodeError> {
+        let client = HttpClientBuilder::default()
+            .request_timeout(std::time::Duration::from_secs(EVM_RPC_TIMEOUT_SECS))
+            .build(url)
+            .map_err(|e| NodeError::ConfigError {
+                reason: format!("invalid EVM RPC url '{}': {}", url, e),
+            })?;
+        Ok(Self { client })
+    }
+
+    /// Latest block number.
+    pub async fn block_number(&self) -> Result<u64, NodeError> {
+        let head: String = self
+            .client
+            .request("eth_blockNumber", rpc_params![])
+            .await
+            .map_err(bridge_error)?;
+        parse_quantity(&head).map_err(|reason| NodeError::BridgeError { reason })
+    }
+
+    /// Logs emitted by `address` with `topic0` in blocks `from..=to`.
+    pub async fn get_logs(
+        &self,
+        address: &EvmAddress,
+        topic0: &[u8; 32],
+        from: u64,
+        to: u64,
+    ) -> Result<Vec<EvmLog>, NodeError> {
+        let filter = serde_json::json!({
+            "address": format!("0x{}", hex::encode(address)),
+            "topics": [format!("0x{}", hex::encode(topic0))],
+            "fromBlock": format!("{:#x}", from),
+            "toBlock": format!("{:#x}", to),
+        });
+        self.client
+            .request("eth_getLogs", rpc_params![filter])
+            .await
+            .map_err(bridge_error)
+    }
+}
+
+fn bridge_error(e: jsonrpsee::core::ClientError) -> NodeError {
+    NodeError::BridgeError {
+        reason: format!("EVM RPC: {}", e),
+    }
+}
+
+/// Signs release authorizations with a secp256k1 key, in the form the lock
+/// contract checks with `ecrecover`.
+pub struct EvmSigner {
+    key: SigningKey,
+}
+
+impl EvmSigner {
+    /// Load a signer from a hex-encoded 32-byte private key.
+    pub fn from_hex(key_hex: &str) -> Result<Self, NodeError> {
+        let bytes = decode_hex(key_hex).map_err(|reason| NodeError::ConfigError {
+            reason: format!("invalid EVM signer key: {}", reason),
+        })?;
+        let key = SigningKey::from_slice(&bytes).map_err(|_| NodeError::ConfigError {
+            reason: "invalid EVM signer key: not a valid secp256k1 scalar".to_string(),
+        })?;
+        Ok(Self { key })
+    }
+
+    /// EVM address of the signing key.
+    pub fn address(&self) -> EvmAddress {
+        let point = self.key.verifying_key().to_encoded_point(false);
+        let hash = keccak256(&point.as_bytes()[1..]);
+        let mut addr = [0u8; 20];
+        addr.copy_from_slice(&hash[12..]);
+        addr
+    }
+
+    /// Sign `digest` as an EIP-191 personal message. Returns `r || s || v`
+    /// with `v` in {27, 28}.
+    pub fn sign(&self, digest: &[u8; 32]) -> Result<[u8; 65], NodeError> {
+        let (signature, recovery_id) = self
+            .key
+            .sign_prehash_recoverable(&eth_signed_message_hash(digest))
+            .map_err(|e| NodeError::BridgeError {
+                reason: format!("signing failed: {}", e),
+            })?;
+        let mut out = [0u8; 65];
+        out[..64].copy_from_slice(&signature.to_bytes());
+        out[64] = 27 + recovery_id.to_byte();
+        Ok(out)
+    }
+}
+
+/// `keccak256("\x19Ethereum Signed Message:\n32" || digest)`.
+pub fn eth_signed_message_hash(digest: &[u8; 32]) -> [u8; 32] {
+    let mut msg = Vec::with_capacity(60);
+    msg.extend_from_slice(b"\x19Ethereum Signed Message:\n32");
+    msg.extend_from_slice(digest);
+    keccak256(&msg)
+}
+
+/// Parse a `0x`-prefixed hex quantity.
+pub fn parse_quantity(s: &str) -> Result<u64, String> {
+    let digits = s
+        .strip_prefix("0x")
+        .ok_or_else(|| format!("quantity '{}' lacks 0x prefix", s))?;
+    u64::from_str_radix(digits, 16).map_err(|e| format!("invalid quantity '{}': {}", s, e))
+}
+
+/// Decode hex with an optional `0x` prefix.
+pub fn decode_hex(s: &str) -> Result<Vec<u8>, String> {
+    hex::decode(s.strip_prefix("0x").unwrap_or(s)).map_err(|e| format!("invalid hex: {}", e))
+}
+
+/// Decode a 20-byte address from hex.
+pub fn decode_address(s: &str) -> Result<EvmAddress, String> {
+    decode_hex(s)?
+        .try_into()
+        .map_err(|_| format!("'{}' is not a 20-byte address", s))
+}
+
+fn decode_word(s: &str) -> Result<[u8; 32], String> {
+    decode_hex(s)?
+        .try_into()
+        .map_err(|_| format!("'{}' is not a 32-byte word", s))
+}
+
+fn word_to_address(word: &[u8; 32]) -> Result<EvmAddress, String> {
+    if word[..12].iter().any(|&b| b != 0) {
+        return Err("address word has nonzero high bytes".to_string());
+    }
+    let mut addr = [0u8; 20];
+    addr.copy_from_slice(&word[12..]);
+    Ok(addr)
+}
+
+fn word_to_u128(word: &[u8]) -> Result<u128, String> {
+    if word[..16].iter().any(|&b| b != 0) {
+        return Err("uint256 value exceeds u128".to_string());
+    }
+    let mut buf = [0u8; 16];
+    buf.copy_from_slice(&word[16..32]);
+    Ok(u128::from_be_bytes(buf))
+}
+
+/// ABI-encode a `uint256`.
+pub fn u256_word(value: u128) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[16..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Build the log a lock contract emits for a deposit.
+    pub(crate) fn lock_log(
+        lock_contract: &EvmAddress,
+        token: &EvmAddress,
+        amount: u128,
+        recipient: &Address,
+        nonce: u64,
+        block: u64,
+    ) -> EvmLog {
+        let mut token_word = [0u8; 32];
+        token_word[12..].copy_from_slice(token);
+        let mut data = u256_word(amount).to_vec();
+        let mut recipient_word = [0u8; 32];
+        recipient_word[..20].copy_from_slice(recipient);
+        data.extend_from_slice(&recipient_word);
+        data.extend_from_slice(&u256_word(nonce as u128));
+        EvmLog {
+            address: format!("0x{}", hex::encode(lock_contract)),
+            topics: vec![
+                format!("0x{}", hex::encode(locked_topic())),
+                format!("0x{}", hex::encode(token_word)),
+                format!("0x{}", hex::encode([0u8; 32])),
+            ],
+            data: format!("0x{}", hex::encode(data)),
+            block_number: format!("{:#x}", block),
+            transaction_hash: format!("0x{}", hex::encode([nonce as u8; 32])),
+            log_index: "0x0".to_string(),
+            removed: false,
+        }
+    }
+
+    #[test]
+    fn test_keccak256_vectors() {
+        assert_eq!(
+            hex::encode(keccak256(b"")),
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+        // ERC-20 `Transfer` topic.
+        assert_eq!(
+            hex::encode(keccak256(b"Transfer(address,address,uint256)")),
+            "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+        );
+    }
+
+    #[test]
+    fn test_parse_lock_log() {
+        let log = lock_log(&[0xaa; 20], &[0xcc; 20], 1_500, &[0x55; 20], 7, 0x1234);
+        let event = LockEvent::parse(&log).unwrap();
+        assert_eq!(event.lock_contract, [0xaa; 20]);
+        assert_eq!(event.token, [0xcc; 20]);
+        assert_eq!(event.amount, 1_500);
+        assert_eq!(event.recipient, [0x55; 20]);
+        assert_eq!(event.nonce, 7);
+        assert_eq!(event.block_number, 0x1234);
+        assert_eq!(event.tx_hash, [7u8; 32]);
+
+        let mut other = log.clone();
+        other.topics[0] = format!("0x{}", hex::encode([1u8; 32]));
+        assert!(LockEvent::parse(&other).is_err());
+
+        // Amounts above u128 cannot be represented on Norn.
+        let mut huge = log;
+        huge.data.replace_range(2..4, "01");
+        assert!(LockEvent::parse(&huge).is_err());
+    }
+
+    #[test]
+    fn test_signer_recovers_to_address() {
+        // Private key 1 has a well-known address.
+        let signer = EvmSigner::from_hex(&format!("{:064x}", 1)).unwrap();
+        assert_eq!(
+            hex::encode(signer.address()),
+            "7e5f4552091a69125d5dfcb7b8c2659029395bdf"
+        );
+
+        let digest = keccak256(b"release");
+        let sig = signer.sign(&digest).unwrap();
+        assert!(sig[64] == 27 || sig[64] == 28);
+        let recovered = k256::ecdsa::VerifyingKey::recover_from_prehash(
+            &eth_signed_message_hash(&digest),
+            &k256::ecdsa::Signature::from_slice(&sig[..64]).unwrap(),
+            k256::ecdsa::RecoveryId::from_byte(sig[64] - 27).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(&recovered, signer.key.verifying_key());
+
+        assert!(EvmSigner::from_hex(&"00".repeat(32)).is_err());
+    }
+
+    #[test]
+    fn test_parse_quantity() {
+        assert_eq!(parse_quantity("0x0").unwrap(), 0);
+        assert_eq!(parse_quantity("0x1b4").unwrap(), 436);
+        assert!(parse_quantity("436").is_err());
+    }
+}
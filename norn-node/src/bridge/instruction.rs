@@ -0,0 +1,226 @@
+//! Messages the watcher exchanges with the bridging loom.
+//!
+//! The loom's execute methods are called with `[index] || borsh(args)`,
+//! where `index` is the method's position among the contract's
+//! `#[execute]` methods (see `examples/evm-bridge`).
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use norn_types::primitives::Address;
+
+use super::evm::{keccak256, u256_word, EvmAddress, LockEvent};
+use crate::rpc::types::IndexedEventInfo;
+
+/// Execute index of `attest_mint(instruction)`.
+pub const ATTEST_MINT: u8 = 0;
+/// Execute index of `attest_release(nonce, signature)`.
+pub const ATTEST_RELEASE: u8 = 2;
+
+/// Event the loom emits when wrapped tokens are burned for release.
+pub const BURN_EVENT: &str = "BridgeBurn";
+
+/// Domain tag of release digests.
+pub const RELEASE_DOMAIN: &[u8] = b"NORN_RELEASE";
+
+/// Loom errors meaning this member's attestation is already recorded.
+pub const ALREADY_DONE: &[&str] = &[
+    "lock already attested",
+    "lock already minted",
+    "release already attested",
+];
+
+/// A confirmed lock to be minted on the bridging loom.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct MintInstruction {
+    pub chain_id: u64,
+    pub lock_contract: EvmAddress,
+    /// The lock contract's deposit nonce.
+    pub lock_nonce: u64,
+    pub tx_hash: [u8; 32],
+    pub evm_token: EvmAddress,
+    pub amount: u128,
+    pub recipient: Address,
+}
+
+impl MintInstruction {
+    pub fn from_lock(chain_id: u64, lock: &LockEvent) -> Self {
+        MintInstruction {
+            chain_id,
+            lock_contract: lock.lock_contract,
+            lock_nonce: lock.nonce,
+            tx_hash: lock.tx_hash,
+            evm_token: lock.token,
+            amount: lock.amount,
+            recipient: lock.recipient,
+        }
+    }
+
+    /// Loom input for `attest_mint`.
+    pub fn attest_input(&self) -> Vec<u8> {
+        let mut input = vec![ATTEST_MINT];
+        input.extend(borsh::to_vec(self).expect("instruction serialization cannot fail"));
+        input
+    }
+}
+
+/// A burn on the bridging loom, awaiting release on the EVM chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BurnEvent {
+    pub nonce: u64,
+    pub evm_token: EvmAddress,
+    pub amount: u128,
+    pub evm_recipient: EvmAddress,
+}
+
+impl BurnEvent {
+    /// Decode a `BridgeBurn` event from the node's event index.
+    pub fn from_event(event: &IndexedEventInfo) -> Result<Self, String> {
+        if event.ty != BURN_EVENT {
+            return Err(format!("expected {} event, got {}", BURN_EVENT, event.ty));
+        }
+        let attr = |key: &str| {
+            event
+                .attributes
+                .iter()
+                .find(|a| a.key == key)
+                .map(|a| a.value.as_str())
+                .ok_or_else(|| format!("missing attribute '{}'", key))
+        };
+        let number = |key: &str| -> Result<u128, String> {
+            attr(key)?
+                .parse()
+                .map_err(|e| format!("invalid '{}': {}", key, e))
+        };
+        Ok(BurnEvent {
+            nonce: number("nonce")?
+                .try_into()
+                .map_err(|_| "nonce exceeds u64".to_string())?,
+            evm_token: super::evm::decode_address(attr("evm_token")?)?,
+            amount: number("amount")?,
+            evm_recipient: super::evm::decode_address(attr("evm_recipient")?)?,
+        })
+    }
+
+    /// Digest the lock contract recovers signers from:
+    /// `keccak256(abi.encodePacked("NORN_RELEASE", chainId, lockContract,
+    /// token, recipient, amount, nonce))` with `chainId`, `amount` and
+    /// `nonce` as `uint256`.
+    pub fn release_digest(&self, chain_id: u64, lock_contract: &EvmAddress) -> [u8; 32] {
+        let mut msg = Vec::with_capacity(RELEASE_DOMAIN.len() + 3 * 32 + 3 * 20);
+        msg.extend_from_slice(RELEASE_DOMAIN);
+        msg.extend_from_slice(&u256_word(chain_id as u128));
+        msg.extend_from_slice(lock_contract);
+        msg.extend_from_slice(&self.evm_token);
+        msg.extend_from_slice(&self.evm_recipient);
+        msg.extend_from_slice(&u256_word(self.amount));
+        msg.extend_from_slice(&u256_word(self.nonce as u128));
+        keccak256(&msg)
+    }
+
+    /// Loom input for `attest_release`.
+    pub fn attest_input(&self, signature: &[u8; 65]) -> Vec<u8> {
+        let mut input = vec![ATTEST_RELEASE];
+        input.extend(
+            borsh::to_vec(&(self.nonce, signature.to_vec()))
+                .expect("release serialization cannot fail"),
+        );
+        input
+    }
+}
+
+/// Whether a failed execution only means the attestation is already in.
+pub fn is_already_done(reason: &str) -> bool {
+    ALREADY_DONE.iter().any(|msg| reason.contains(msg))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::rpc::types::AttributeInfo;
+
+    pub(crate) fn burn_event(nonce: u64, amount: u128) -> IndexedEventInfo {
+        let attr = |key: &str, value: String| AttributeInfo {
+            key: key.to_string(),
+            value,
+        };
+        IndexedEventInfo {
+            seq: nonce,
+            loom_id: hex::encode([9u8; 32]),
+            sender: hex::encode([0x55u8; 20]),
+            block_height: 1,
+            timestamp: 0,
+            ty: BURN_EVENT.to_string(),
+            attributes: vec![
+                attr("nonce", nonce.to_string()),
+                attr("burner", format!("0x{}", hex::encode([0x55u8; 20]))),
+                attr("evm_token", format!("0x{}", hex::encode([0xccu8; 20]))),
+                attr("amount", amount.to_string()),
+                attr("evm_recipient", format!("0x{}", hex::encode([0xeeu8; 20]))),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_mint_input_layout() {
+        let ins = MintInstruction {
+            chain_id: 1,
+            lock_contract: [0xaa; 20],
+            lock_nonce: 3,
+            tx_hash: [4u8; 32],
+            evm_token: [0xcc; 20],
+            amount: 500,
+            recipient: [0x55; 20],
+        };
+        let input = ins.attest_input();
+        assert_eq!(input[0], ATTEST_MINT);
+        // u64 + 20 + u64 + 32 + 20 + u128 + 20
+        assert_eq!(input.len(), 1 + 8 + 20 + 8 + 32 + 20 + 16 + 20);
+        assert_eq!(MintInstruction::try_from_slice(&input[1..]).unwrap(), ins);
+    }
+
+    #[test]
+    fn test_burn_event_roundtrip() {
+        let burn = BurnEvent::from_event(&burn_event(4, 200)).unwrap();
+        assert_eq!(
+            burn,
+            BurnEvent {
+                nonce: 4,
+                evm_token: [0xcc; 20],
+                amount: 200,
+                evm_recipient: [0xee; 20],
+            }
+        );
+
+        let input = burn.attest_input(&[7u8; 65]);
+        assert_eq!(input[0], ATTEST_RELEASE);
+        let (nonce, sig) = <(u64, Vec<u8>)>::try_from_slice(&input[1..]).unwrap();
+        assert_eq!(nonce, 4);
+        assert_eq!(sig, vec![7u8; 65]);
+
+        let mut event = burn_event(4, 200);
+        event.attributes.retain(|a| a.key != "evm_recipient");
+        assert!(BurnEvent::from_event(&event).is_err());
+    }
+
+    #[test]
+    fn test_release_digest_binds_every_field() {
+        let burn = BurnEvent::from_event(&burn_event(1, 200)).unwrap();
+        let digest = burn.release_digest(1, &[0xaa; 20]);
+        assert_ne!(digest, burn.release_digest(2, &[0xaa; 20]));
+        assert_ne!(digest, burn.release_digest(1, &[0xab; 20]));
+        let mut other = burn.clone();
+        other.nonce = 2;
+        assert_ne!(digest, other.release_digest(1, &[0xaa; 20]));
+        other = burn.clone();
+        other.amount = 201;
+        assert_ne!(digest, other.release_digest(1, &[0xaa; 20]));
+    }
+
+    #[test]
+    fn test_already_done_reasons() {
+        assert!(is_already_done(
+            "execution failed: contract error: lock already minted"
+        ));
+        assert!(!is_already_done("execution failed: unauthorized"));
+    }
+}
@@ -0,0 +1,443 @@
+//! External chain bridge watcher.
+//!
+//! Moves tokens between an EVM chain and Norn through a bridging loom (see
+//! `examples/evm-bridge`) guarded by an M-of-N attestation committee. Each
+//! committee member runs a watcher:
+//!
+//! - Lock → mint: confirmed `Locked` logs from the EVM lock contract become
+//!   [`MintInstruction`]s, submitted to the loom with `attest_mint`. The loom
+//!   mints once `threshold` members have attested the same instruction.
+//! - Burn → release: `BridgeBurn` events from the loom are signed as EVM
+//!   release authorizations and posted back with `attest_release`. Anyone
+//!   can submit `threshold` signatures to the lock contract to release.
+//!
+//! Progress is kept in the node's store, so a restarted watcher resumes
+//! where it stopped. Attestations the loom already holds are skipped.
+
+pub mod evm;
+pub mod instruction;
+
+use std::sync::Arc;
+
+use norn_crypto::keys::Keypair;
+use norn_storage::traits::KvStore;
+
+use crate::config::BridgeConfig;
+use crate::error::NodeError;
+use crate::loom_client::{parse_hex32, LoomClient};
+use evm::{EvmAddress, EvmClient, EvmSigner, LockEvent};
+pub use instruction::{BurnEvent, MintInstruction};
+
+/// Most burn events fetched per `norn_getEvents` call.
+const BURN_PAGE_SIZE: u64 = 100;
+
+/// Outcome of submitting an attestation to the loom.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Submission {
+    Accepted,
+    /// This member had already attested.
+    AlreadyDone,
+}
+
+/// Relays locks and burns between the EVM chain and the bridging loom.
+pub struct BridgeWatcher {
+    chain_id: u64,
+    lock_contract: EvmAddress,
+    confirmations: u64,
+    start_block: u64,
+    max_block_range: u64,
+    evm: EvmClient,
+    loom: LoomClient,
+    signer: EvmSigner,
+    store: Arc<dyn KvStore>,
+}
+
+impl BridgeWatcher {
+    /// Create a watcher submitting through the Norn RPC server at
+    /// `norn_rpc_url`, authenticating with `api_key` if the server needs one.
+    pub fn new(
+        config: &BridgeConfig,
+        norn_rpc_url: &str,
+        api_key: Option<&str>,
+        store: Arc<dyn KvStore>,
+    ) -> Result<Self, NodeError> {
+        let config_error = |reason: String| NodeError::ConfigError { reason };
+        let lock_contract = evm::decode_address(&config.lock_contract)
+            .map_err(|e| config_error(format!("invalid bridge lock_contract: {}", e)))?;
+        let loom_id = parse_hex32(&config.loom_id)
+            .map_err(|e| config_error(format!("invalid bridge loom_id: {}", e)))?;
+        let seed = parse_hex32(&config.attester_seed)
+            .map_err(|e| config_error(format!("invalid bridge attester_seed: {}", e)))?;
+        if config.max_block_range == 0 {
+            return Err(config_error(
+                "bridge max_block_range must be positive".to_string(),
+            ));
+        }
+
+        let loom = LoomClient::new(norn_rpc_url, api_key, loom_id, Keypair::from_seed(&seed))?;
+
+        Ok(Self {
+            chain_id: config.evm_chain_id,
+            lock_contract,
+            confirmations: config.confirmations,
+            start_block: config.start_block,
+            max_block_range: config.max_block_range,
+            evm: EvmClient::new(&config.evm_rpc_url)?,
+            loom,
+            signer: EvmSigner::from_hex(&config.evm_signer_key)?,
+            store,
+        })
+    }
+
+    /// EVM address the lock contract must list as a release signer.
+    pub fn signer_address(&self) -> EvmAddress {
+        self.signer.address()
+    }
+
+    /// Relay new locks, then new burns. Returns how many of each were
+    /// processed.
+    pub async fn poll(&self) -> Result<(u64, u64), NodeError> {
+        let locks = self.relay_locks().await?;
+        let burns = self.relay_burns().await?;
+        Ok((locks, burns))
+    }
+
+    /// Attest every confirmed lock since the last stored EVM block.
+    async fn relay_locks(&self) -> Result<u64, NodeError> {
+        let head = self.evm.block_number().await?;
+        let Some(safe) = head.checked_sub(self.confirmations) else {
+            return Ok(0);
+        };
+        let mut from = self.load_cursor(b"evm_block")?.unwrap_or(self.start_block);
+        let topic = evm::locked_topic();
+        let mut relayed = 0;
+        while from <= safe {
+            let to = safe.min(from.saturating_add(self.max_block_range - 1));
+            for log in self
+                .evm
+                .get_logs(&self.lock_contract, &topic, from, to)
+                .await?
+            {
+                if log.removed {
+                    continue;
+                }
+                let lock = match LockEvent::parse(&log) {
+                    Ok(lock) if lock.lock_contract == self.lock_contract => lock,
+                    Ok(_) => continue,
+                    Err(e) => {
+                        tracing::warn!(tx = %log.transaction_hash, "skipping malformed lock log: {}", e);
+                        continue;
+                    }
+                };
+                if lock.amount == 0 {
+                    continue;
+                }
+                let instruction = MintInstruction::from_lock(self.chain_id, &lock);
+                let outcome = self.execute(instruction.attest_input()).await?;
+                tracing::info!(
+                    nonce = lock.nonce,
+                    amount = lock.amount,
+                    ?outcome,
+                    "attested EVM lock"
+                );
+                relayed += 1;
+            }
+            from = to + 1;
+            self.save_cursor(b"evm_block", from)?;
+        }
+        Ok(relayed)
+    }
+
+    /// Sign a release for every burn since the last stored event offset.
+    async fn relay_burns(&self) -> Result<u64, NodeError> {
+        let mut offset = self.load_cursor(b"burn_offset")?.unwrap_or(0);
+        let mut relayed = 0;
+        loop {
+            let events = self
+                .loom
+                .events(instruction::BURN_EVENT, BURN_PAGE_SIZE, offset)
+                .await?;
+            let fetched = events.len() as u64;
+            for event in &events {
+                match BurnEvent::from_event(event) {
+                    Ok(burn) => {
+                        let digest = burn.release_digest(self.chain_id, &self.lock_contract);
+                        let signature = self.signer.sign(&digest)?;
+                        let outcome = self.execute(burn.attest_input(&signature)).await?;
+                        tracing::info!(
+                            nonce = burn.nonce,
+                            amount = burn.amount,
+                            ?outcome,
+                            "attested loom burn"
+                        );
+                        relayed += 1;
+                    }
+                    Err(e) => {
+                        tracing::warn!(seq = event.seq, "skipping malformed burn event: {}", e)
+                    }
+                }
+                offset += 1;
+                self.save_cursor(b"burn_offset", offset)?;
+            }
+            if fetched < BURN_PAGE_SIZE {
+                return Ok(relayed);
+            }
+        }
+    }
+
+    /// Execute the bridging loom as this member.
+    async fn execute(&self, input: Vec<u8>) -> Result<Submission, NodeError> {
+        let result = self.loom.execute(&input).await?;
+        if result.success {
+            return Ok(Submission::Accepted);
+        }
+        let reason = result.reason.unwrap_or_default();
+        if instruction::is_already_done(&reason) {
+            Ok(Submission::AlreadyDone)
+        } else {
+            Err(NodeError::BridgeError {
+                reason: format!("bridging loom rejected attestation: {}", reason),
+            })
+        }
+    }
+
+    fn cursor_key(&self, name: &[u8]) -> Vec<u8> {
+        let mut key = b"bridge:".to_vec();
+        key.extend_from_slice(self.loom.loom_id());
+        key.push(b':');
+        key.extend_from_slice(name);
+        key
+    }
+
+    fn load_cursor(&self, name: &[u8]) -> Result<Option<u64>, NodeError> {
+        Ok(self
+            .store
+            .get(&self.cursor_key(name))?
+            .and_then(|v| v.try_into().ok())
+            .map(u64::from_le_bytes))
+    }
+
+    fn save_cursor(&self, name: &[u8], value: u64) -> Result<(), NodeError> {
+        Ok(self
+            .store
+            .put(&self.cursor_key(name), &value.to_le_bytes())?)
+    }
+
+    /// Poll every `interval` until the task is dropped. Failed polls are
+    /// retried from the stored progress on the next tick.
+    pub async fn run(self: Arc<Self>, interval: std::time::Duration) {
+        tracing::info!(
+            attester = %hex::encode(self.loom.address()),
+            signer = %format!("0x{}", hex::encode(self.signer_address())),
+            "bridge watcher started"
+        );
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.poll().await {
+                tracing::warn!("bridge poll failed: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::types::{EventFilterParams, ExecutionResult, IndexedEventInfo};
+    use borsh::BorshDeserialize;
+    use jsonrpsee::server::{RpcModule, Server, ServerHandle};
+    use norn_storage::memory::MemoryStore;
+    use std::sync::Mutex;
+
+    const LOCK: EvmAddress = [0xaa; 20];
+    const LOOM: norn_types::primitives::LoomId = [9u8; 32];
+
+    /// A fake EVM chain and Norn node behind one RPC server.
+    #[derive(Default)]
+    struct Mock {
+        head: u64,
+        logs: Vec<evm::EvmLog>,
+        burns: Vec<IndexedEventInfo>,
+        /// Inputs submitted to `norn_executeLoom`.
+        executed: Vec<Vec<u8>>,
+        /// Failure reason returned for every execution, if set.
+        reject: Option<String>,
+    }
+
+    async fn serve(mock: Arc<Mutex<Mock>>) -> (String, ServerHandle) {
+        let server = Server::builder().build("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", server.local_addr().unwrap());
+        let mut module = RpcModule::new(mock);
+        module
+            .register_method("eth_blockNumber", |_, mock, _| {
+                format!("{:#x}", mock.lock().unwrap().head)
+            })
+            .unwrap();
+        module
+            .register_method("eth_getLogs", |params, mock, _| {
+                let (filter,): (serde_json::Value,) = params.parse().unwrap();
+                let range = |k: &str| evm::parse_quantity(filter[k].as_str().unwrap()).unwrap();
+                let (from, to) = (range("fromBlock"), range("toBlock"));
+                mock.lock()
+                    .unwrap()
+                    .logs
+                    .iter()
+                    .filter(|log| {
+                        let block = evm::parse_quantity(&log.block_number).unwrap();
+                        from <= block && block <= to
+                    })
+                    .cloned()
+                    .collect::<Vec<_>>()
+            })
+            .unwrap();
+        module
+            .register_method("norn_getEvents", |params, mock, _| {
+                let (_, limit, offset): (EventFilterParams, u64, u64) = params.parse().unwrap();
+                mock.lock()
+                    .unwrap()
+                    .burns
+                    .iter()
+                    .skip(offset as usize)
+                    .take(limit as usize)
+                    .cloned()
+                    .collect::<Vec<_>>()
+            })
+            .unwrap();
+        module
+            .register_method("norn_executeLoom", |params, mock, _| {
+                let (loom, input, _, _, _): (String, String, String, String, String) =
+                    params.parse().unwrap();
+                assert_eq!(loom, hex::encode(LOOM));
+                let mut mock = mock.lock().unwrap();
+                mock.executed.push(hex::decode(input).unwrap());
+                serde_json::to_value(ExecutionResult {
+                    success: mock.reject.is_none(),
+                    output_hex: None,
+                    gas_used: 0,
+                    logs: Vec::new(),
+                    events: Vec::new(),
+                    reason: mock.reject.clone(),
+                    instantiated: Vec::new(),
+                })
+                .unwrap()
+            })
+            .unwrap();
+        (url, server.start(module))
+    }
+
+    fn new_watcher(url: &str, store: Arc<dyn KvStore>) -> BridgeWatcher {
+        let config = BridgeConfig {
+            evm_rpc_url: url.to_string(),
+            evm_chain_id: 5,
+            lock_contract: format!("0x{}", hex::encode(LOCK)),
+            loom_id: hex::encode(LOOM),
+            attester_seed: hex::encode([1u8; 32]),
+            evm_signer_key: format!("{:064x}", 1),
+            confirmations: 2,
+            start_block: 10,
+            poll_interval_secs: 1,
+            max_block_range: 3,
+        };
+        BridgeWatcher::new(&config, url, None, store).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_relays_confirmed_locks_once() {
+        let mock = Arc::new(Mutex::new(Mock {
+            head: 20,
+            logs: vec![
+                evm::tests::lock_log(&LOCK, &[0xcc; 20], 100, &[0x55; 20], 1, 11),
+                evm::tests::lock_log(&LOCK, &[0xcc; 20], 200, &[0x55; 20], 2, 15),
+                // Not yet buried under `confirmations` blocks.
+                evm::tests::lock_log(&LOCK, &[0xcc; 20], 300, &[0x55; 20], 3, 19),
+            ],
+            ..Default::default()
+        }));
+        let (url, _handle) = serve(mock.clone()).await;
+        let store: Arc<dyn KvStore> = Arc::new(MemoryStore::new());
+        let watcher = new_watcher(&url, store.clone());
+
+        assert_eq!(watcher.poll().await.unwrap(), (2, 0));
+        {
+            let mock = mock.lock().unwrap();
+            assert_eq!(mock.executed.len(), 2);
+            let first = MintInstruction::try_from_slice(&mock.executed[0][1..]).unwrap();
+            assert_eq!(first.chain_id, 5);
+            assert_eq!(first.lock_nonce, 1);
+            assert_eq!(first.amount, 100);
+            assert_eq!(first.recipient, [0x55; 20]);
+        }
+
+        // Progress survives a restart; the third lock is relayed once confirmed.
+        mock.lock().unwrap().head = 21;
+        let restarted = new_watcher(&url, store);
+        assert_eq!(restarted.poll().await.unwrap(), (1, 0));
+        assert_eq!(mock.lock().unwrap().executed.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_signs_releases_for_burns() {
+        let mock = Arc::new(Mutex::new(Mock {
+            head: 0,
+            burns: vec![
+                instruction::tests::burn_event(1, 200),
+                instruction::tests::burn_event(2, 50),
+            ],
+            ..Default::default()
+        }));
+        let (url, _handle) = serve(mock.clone()).await;
+        let watcher = new_watcher(&url, Arc::new(MemoryStore::new()));
+
+        assert_eq!(watcher.poll().await.unwrap(), (0, 2));
+        let input = mock.lock().unwrap().executed[0].clone();
+        assert_eq!(input[0], instruction::ATTEST_RELEASE);
+        let (nonce, sig) = <(u64, Vec<u8>)>::try_from_slice(&input[1..]).unwrap();
+        assert_eq!(nonce, 1);
+
+        let burn = BurnEvent::from_event(&instruction::tests::burn_event(1, 200)).unwrap();
+        let digest = evm::eth_signed_message_hash(&burn.release_digest(5, &LOCK));
+        let recovered = k256::ecdsa::VerifyingKey::recover_from_prehash(
+            &digest,
+            &k256::ecdsa::Signature::from_slice(&sig[..64]).unwrap(),
+            k256::ecdsa::RecoveryId::from_byte(sig[64] - 27).unwrap(),
+        )
+        .unwrap();
+        let point = recovered.to_encoded_point(false);
+        assert_eq!(
+            evm::keccak256(&point.as_bytes()[1..])[12..],
+            watcher.signer_address()
+        );
+
+        // Nothing new on the next poll.
+        assert_eq!(watcher.poll().await.unwrap(), (0, 0));
+    }
+
+    #[tokio::test]
+    async fn test_rejected_attestation_keeps_progress() {
+        let mock = Arc::new(Mutex::new(Mock {
+            head: 20,
+            logs: vec![evm::tests::lock_log(
+                &LOCK,
+                &[0xcc; 20],
+                100,
+                &[0x55; 20],
+                1,
+                11,
+            )],
+            reject: Some("execution failed: unauthorized".to_string()),
+            ..Default::default()
+        }));
+        let (url, _handle) = serve(mock.clone()).await;
+        let watcher = new_watcher(&url, Arc::new(MemoryStore::new()));
+
+        assert!(watcher.poll().await.is_err());
+        assert!(watcher.poll().await.is_err());
+        assert_eq!(mock.lock().unwrap().executed.len(), 2);
+
+        // Already recorded on the loom: counts as done.
+        mock.lock().unwrap().reject = Some("contract error: lock already minted".to_string());
+        assert_eq!(watcher.poll().await.unwrap(), (1, 0));
+        assert_eq!(watcher.poll().await.unwrap(), (0, 0));
+    }
+}
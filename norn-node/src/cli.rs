@@ -1,4 +1,5 @@
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::Shell;
 
 use crate::error::NodeError;
 use crate::wallet::cli::WalletCommand;
@@ -27,7 +28,7 @@ pub enum Command {
         /// Override RPC listen address (e.g., "0.0.0.0:9741" for LAN access)
         #[arg(long)]
         rpc_addr: Option<String>,
-        /// Storage backend: "sqlite" (default for --dev), "memory", "rocksdb"
+        /// Storage backend: "sqlite" (default for --dev), "memory", "rocksdb", "sled"
         #[arg(long)]
         storage: Option<String>,
         /// Network: "dev" (default for --dev), "testnet", "mainnet"
@@ -39,7 +40,19 @@ pub enum Command {
         /// Override data directory path
         #[arg(long)]
         data_dir: Option<String>,
-        /// Disable default bootstrap nodes (for isolated local testing)
+        /// History pruning: "archive", "minimal", or "keep-recent:<blocks>"
+        #[arg(long)]
+        pruning: Option<String>,
+        /// On first start, sync from a verified state snapshot instead of replaying from genesis
+        #[arg(long)]
+        fast_sync: bool,
+        /// Storage compression: "none", "lz4", "zstd", or "zstd:<level>"
+        #[arg(long)]
+        compression: Option<String>,
+        /// Rewrite existing data under the configured compression before starting
+        #[arg(long)]
+        recompress: bool,
+        /// Disable boot nodes and DNS seeds (for isolated local testing)
         #[arg(long)]
         no_bootstrap: bool,
         /// Boot node multiaddr to connect to (can be specified multiple times)
@@ -51,6 +64,27 @@ pub enum Command {
         /// Enable multi-validator consensus (overrides solo_mode from --dev)
         #[arg(long)]
         consensus: bool,
+        /// Apply a `[profiles.<name>]` section of the config file; other flags still override it
+        #[arg(long)]
+        profile: Option<String>,
+    },
+    /// Re-execute stored blocks from genesis and verify every state root
+    Replay {
+        /// Path to config file
+        #[arg(short, long, default_value = "norn.toml")]
+        config: String,
+        /// Replay a dev-mode node's data (same defaults as `run --dev`)
+        #[arg(long)]
+        dev: bool,
+        /// Override data directory path
+        #[arg(long)]
+        data_dir: Option<String>,
+        /// Storage backend: "sqlite" (default for --dev), "rocksdb", "sled"
+        #[arg(long)]
+        storage: Option<String>,
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
     },
     /// Initialize a new node configuration
     Init {
@@ -75,12 +109,24 @@ pub enum Command {
     },
     /// Wallet management and operations
     Wallet {
+        /// Network profile to use for this command (see `norn wallet config profiles`)
+        #[arg(long, global = true)]
+        profile: Option<String>,
         #[command(subcommand)]
         command: WalletCommand,
     },
+    /// Print a shell completion script (e.g. `norn completions zsh > _norn`)
+    Completions {
+        /// Shell to generate completions for
+        shell: Shell,
+    },
 }
 
 pub async fn run(cli: Cli) -> Result<(), NodeError> {
+    // `norn run` configures logging once its config is loaded.
+    if !matches!(cli.command, Command::Run { .. }) {
+        crate::logging::init(&crate::config::LoggingConfig::default())?;
+    }
     match cli.command {
         Command::Run {
             config,
@@ -89,33 +135,29 @@ pub async fn run(cli: Cli) -> Result<(), NodeError> {
             storage,
             network,
             data_dir,
+            pruning,
+            fast_sync,
+            compression,
+            recompress,
             reset_state,
             no_bootstrap,
             boot_nodes,
             keypair_seed,
             consensus,
+            profile,
         } => {
             crate::banner::print_banner();
 
             let mut config = if dev {
-                let mut cfg = crate::config::NodeConfig::default();
-                cfg.validator.enabled = true;
-                cfg.validator.solo_mode = true;
-                // Default to seed node keypair; operators pass --keypair-seed to override.
-                cfg.validator.keypair_seed =
-                    Some(crate::genesis::DEVNET_SEED_KEYPAIR_SEED.to_string());
-                cfg.rpc.enabled = true;
-                cfg.rpc.listen_addr = "127.0.0.1:9741".to_string();
-                cfg.storage.db_type = "sqlite".to_string();
-                cfg.network.listen_addr = "0.0.0.0:9740".to_string();
-                cfg.network_id = "dev".to_string();
-                let (devnet_config, _) = crate::genesis::devnet_genesis();
-                cfg.genesis_config = Some(devnet_config);
-                cfg
+                dev_config()
             } else {
                 crate::config::NodeConfig::load(&config)?
             };
 
+            if let Some(name) = profile {
+                config.apply_profile(&name)?;
+            }
+
             // Override solo_mode when --consensus is passed.
             if consensus {
                 config.validator.solo_mode = false;
@@ -131,11 +173,24 @@ pub async fn run(cli: Cli) -> Result<(), NodeError> {
             if let Some(dir) = data_dir {
                 config.storage.data_dir = dir;
             }
+            if let Some(mode) = pruning {
+                config.storage.pruning = mode;
+            }
+            if fast_sync {
+                config.network.fast_sync = true;
+            }
+            if let Some(compression) = compression {
+                config.storage.compression = compression;
+            }
+            if recompress {
+                config.storage.recompress = true;
+            }
             if let Some(ref net) = network {
                 config.network_id = net.clone();
             }
             if no_bootstrap {
                 config.network.boot_nodes.clear();
+                config.network.dns_seeds = Some(Vec::new());
             }
             if !boot_nodes.is_empty() {
                 config.network.boot_nodes.extend(boot_nodes);
@@ -143,6 +198,7 @@ pub async fn run(cli: Cli) -> Result<(), NodeError> {
             if let Some(seed) = keypair_seed {
                 config.validator.keypair_seed = Some(seed);
             }
+            crate::logging::init(&config.logging)?;
 
             // Wipe data directory if requested.
             if reset_state {
@@ -194,11 +250,16 @@ pub async fn run(cli: Cli) -> Result<(), NodeError> {
                     cyan.apply_to(&config.rpc.listen_addr),
                 );
                 println!("  {}  {}", dim.apply_to("Mode    "), cyan.apply_to(mode),);
-                if !config.network.boot_nodes.is_empty() {
+                let dns_seeds = config.network.resolved_dns_seeds(&config.network_id);
+                if !config.network.boot_nodes.is_empty() || !dns_seeds.is_empty() {
                     println!(
                         "  {} {}",
                         dim.apply_to("Peers   "),
-                        cyan.apply_to(format!("{} boot node(s)", config.network.boot_nodes.len())),
+                        cyan.apply_to(format!(
+                            "{} boot node(s), {} DNS seed(s)",
+                            config.network.boot_nodes.len(),
+                            dns_seeds.len()
+                        )),
                     );
                 }
                 println!();
@@ -207,6 +268,43 @@ pub async fn run(cli: Cli) -> Result<(), NodeError> {
             let mut node = crate::node::Node::new(config).await?;
             node.run().await
         }
+        Command::Replay {
+            config,
+            dev,
+            data_dir,
+            storage,
+            json,
+        } => {
+            let mut config = if dev {
+                dev_config()
+            } else {
+                crate::config::NodeConfig::load(&config)?
+            };
+            if let Some(db) = storage {
+                config.storage.db_type = db;
+            }
+            if let Some(dir) = data_dir {
+                config.storage.data_dir = dir;
+            }
+
+            let report = crate::replay::replay(&config)?;
+            if json {
+                let out =
+                    serde_json::to_string_pretty(&report).map_err(|e| NodeError::ConfigError {
+                        reason: format!("failed to serialize replay report: {}", e),
+                    })?;
+                println!("{}", out);
+            } else {
+                print_replay_report(&report);
+            }
+            match report.divergence {
+                Some(d) => Err(NodeError::WeaveError(format!(
+                    "state diverged at height {}",
+                    d.height
+                ))),
+                None => Ok(()),
+            }
+        }
         Command::Init { dir } => {
             crate::config::NodeConfig::init(&dir)?;
             tracing::info!("Node configuration initialized in {}", dir);
@@ -232,12 +330,56 @@ pub async fn run(cli: Cli) -> Result<(), NodeError> {
             println!("Address: {}", hex::encode(address));
             Ok(())
         }
-        Command::Wallet { command } => {
-            crate::wallet::run(command)
-                .await
-                .map_err(|e| NodeError::ConfigError {
-                    reason: e.to_string(),
-                })
+        Command::Wallet { profile, command } => crate::wallet::run(command, profile.as_deref())
+            .await
+            .map_err(|e| NodeError::ConfigError {
+                reason: e.to_string(),
+            }),
+        Command::Completions { shell } => {
+            clap_complete::generate(shell, &mut Cli::command(), "norn", &mut std::io::stdout());
+            Ok(())
         }
     }
 }
+
+/// Configuration used by `--dev`: a solo validator on the devnet genesis,
+/// with SQLite storage.
+fn dev_config() -> crate::config::NodeConfig {
+    let mut cfg = crate::config::NodeConfig::default();
+    cfg.validator.enabled = true;
+    cfg.validator.solo_mode = true;
+    // Default to seed node keypair; operators pass --keypair-seed to override.
+    cfg.validator.keypair_seed = Some(crate::genesis::DEVNET_SEED_KEYPAIR_SEED.to_string());
+    cfg.rpc.enabled = true;
+    cfg.rpc.listen_addr = "127.0.0.1:9741".to_string();
+    cfg.storage.db_type = "sqlite".to_string();
+    cfg.network.listen_addr = "0.0.0.0:9740".to_string();
+    cfg.network_id = "dev".to_string();
+    let (devnet_config, _) = crate::genesis::devnet_genesis();
+    cfg.genesis_config = Some(devnet_config);
+    cfg
+}
+
+fn print_replay_report(report: &crate::replay::ReplayReport) {
+    println!(
+        "Replayed {} block(s) from height {}: {} state root(s) verified, {} without a state root",
+        report.blocks_replayed, report.start_height, report.roots_verified, report.roots_unchecked,
+    );
+    let Some(ref d) = report.divergence else {
+        println!("No divergence found.");
+        return;
+    };
+    println!();
+    println!("Divergence at height {} (block {})", d.height, d.block_hash);
+    println!("  expected state root: {}", d.expected_state_root);
+    println!("  replayed state root: {}", d.replayed_state_root);
+    if d.balances.is_empty() {
+        println!("  no stored balance history to compare against");
+    }
+    for b in &d.balances {
+        println!(
+            "  0x{} token {}: replayed {}, stored {}",
+            b.address, b.token_id, b.replayed, b.stored
+        );
+    }
+}
@@ -1,6 +1,10 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::Path;
 
+use norn_storage::compression::CompressionConfig;
+use norn_storage::pruning::PruningMode;
+
 use crate::error::NodeError;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +23,36 @@ pub struct NodeConfig {
     /// Inline genesis config (programmatic only, not serialized to TOML).
     #[serde(skip)]
     pub genesis_config: Option<norn_types::genesis::GenesisConfig>,
+    /// Named overrides selected with `norn run --profile <name>`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, NodeProfile>,
+    /// External chain bridge watcher. Disabled when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bridge: Option<BridgeConfig>,
+    /// Oracle price reporter. Disabled when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oracle: Option<OracleConfig>,
+    /// Thresholds of the liveness and readiness checks.
+    #[serde(default)]
+    pub health: HealthConfig,
+}
+
+/// Settings that differ between the networks one config file serves.
+/// Unset fields keep the values from the rest of the file.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NodeProfile {
+    /// Network identity: "dev", "testnet", or "mainnet".
+    #[serde(default)]
+    pub network_id: Option<String>,
+    /// RPC listen address.
+    #[serde(default)]
+    pub rpc_listen_addr: Option<String>,
+    /// Data directory.
+    #[serde(default)]
+    pub data_dir: Option<String>,
+    /// Validator keypair file.
+    #[serde(default)]
+    pub keypair_path: Option<String>,
 }
 
 fn default_network_id() -> String {
@@ -29,14 +63,152 @@ fn default_network_id() -> String {
 pub struct NetworkConfig {
     pub listen_addr: String,
     pub boot_nodes: Vec<String>,
+    /// DNS seeds to bootstrap from, as `host` or `host:port`. If unset, the
+    /// seeds of the configured network are used.
+    #[serde(default)]
+    pub dns_seeds: Option<Vec<String>>,
     pub max_connections: usize,
+    /// On first start, download a verified state snapshot from peers
+    /// instead of replaying every block from genesis.
+    #[serde(default)]
+    pub fast_sync: bool,
+    /// Probe whether the node is publicly reachable (AutoNAT), and answer
+    /// probes from peers.
+    #[serde(default = "default_true")]
+    pub autonat: bool,
+    /// Relay connections for peers behind NAT. Only useful on publicly
+    /// reachable nodes.
+    #[serde(default)]
+    pub relay_server: bool,
+    /// Relays to listen through when the node is behind NAT, as multiaddrs
+    /// ending in `/p2p/<peer id>`. Defaults to boot nodes with a peer ID.
+    #[serde(default)]
+    pub relays: Vec<String>,
+    /// Upgrade relayed connections to direct ones by hole punching.
+    #[serde(default = "default_true")]
+    pub hole_punching: bool,
+    /// Bytes per second accepted from each peer before its messages are
+    /// dropped. 0 disables the limit.
+    #[serde(default = "default_peer_inbound_bytes_per_sec")]
+    pub peer_inbound_bytes_per_sec: u64,
+    /// Messages per second accepted from each peer. 0 disables the limit.
+    #[serde(default = "default_peer_inbound_messages_per_sec")]
+    pub peer_inbound_messages_per_sec: u64,
+    /// Bytes per second of direct messages sent to each peer; the rest are
+    /// queued. 0 disables the limit.
+    #[serde(default = "default_peer_outbound_bytes_per_sec")]
+    pub peer_outbound_bytes_per_sec: u64,
+}
+
+impl NetworkConfig {
+    /// DNS seeds to bootstrap from: the configured ones, or else those of
+    /// `network_id`.
+    pub fn resolved_dns_seeds(&self, network_id: &str) -> Vec<String> {
+        match &self.dns_seeds {
+            Some(seeds) => seeds.clone(),
+            None => norn_types::network::NetworkId::parse(network_id)
+                .map(|id| id.dns_seeds().iter().map(|s| s.to_string()).collect())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_peer_inbound_bytes_per_sec() -> u64 {
+    norn_types::constants::DEFAULT_PEER_INBOUND_BYTES_PER_SEC
+}
+
+fn default_peer_inbound_messages_per_sec() -> u64 {
+    norn_types::constants::DEFAULT_PEER_INBOUND_MESSAGES_PER_SEC
+}
+
+fn default_peer_outbound_bytes_per_sec() -> u64 {
+    norn_types::constants::DEFAULT_PEER_OUTBOUND_BYTES_PER_SEC
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StorageConfig {
     pub data_dir: String,
-    /// Storage backend: "memory", "sqlite", or "rocksdb"
+    /// Storage backend: "memory", "sqlite", "rocksdb", or "sled" (with the
+    /// `sled` feature)
     pub db_type: String,
+    /// Cache compiled loom modules under `data_dir/module-cache` so restarts
+    /// skip recompilation. Ignored for the "memory" backend.
+    #[serde(default = "default_module_cache")]
+    pub module_cache: bool,
+    /// History retention: "archive" (keep everything), "keep-recent:<blocks>"
+    /// (keep the most recent blocks and their state history), or "minimal"
+    /// (keep only the last finalized block and state).
+    #[serde(default = "default_pruning")]
+    pub pruning: String,
+    /// Seconds between background pruning passes.
+    #[serde(default = "default_pruning_interval_secs")]
+    pub pruning_interval_secs: u64,
+    /// Blocks between state snapshots served to fast-syncing peers.
+    /// Snapshots are only taken by block-producing validators; 0 disables them.
+    #[serde(default = "default_snapshot_interval")]
+    pub snapshot_interval: u64,
+    /// Value compression for the "sqlite" and "rocksdb" backends: "none",
+    /// "lz4", "zstd", or "zstd:<level>".
+    #[serde(default = "default_compression")]
+    pub compression: String,
+    /// Per-column-family compression overrides for the "rocksdb" backend.
+    #[serde(default = "default_column_family_compression")]
+    pub column_family_compression: BTreeMap<String, String>,
+    /// Rewrite existing data under the configured compression at startup.
+    #[serde(default)]
+    pub recompress: bool,
+}
+
+fn default_module_cache() -> bool {
+    true
+}
+
+fn default_pruning() -> String {
+    "archive".to_string()
+}
+
+fn default_pruning_interval_secs() -> u64 {
+    300
+}
+
+fn default_snapshot_interval() -> u64 {
+    1000
+}
+
+fn default_compression() -> String {
+    "lz4".to_string()
+}
+
+fn default_column_family_compression() -> BTreeMap<String, String> {
+    BTreeMap::from([("blocks".to_string(), "zstd".to_string())])
+}
+
+impl StorageConfig {
+    /// Parse the configured pruning mode.
+    pub fn pruning_mode(&self) -> Result<PruningMode, NodeError> {
+        self.pruning
+            .parse()
+            .map_err(|reason| NodeError::ConfigError { reason })
+    }
+
+    /// Parse the configured compression and its per-column-family overrides.
+    pub fn compression(&self) -> Result<CompressionConfig, NodeError> {
+        let parse = |s: &str| {
+            s.parse()
+                .map_err(|reason| NodeError::ConfigError { reason })
+        };
+        let mut config = CompressionConfig::uniform(parse(&self.compression)?);
+        for (cf, compression) in &self.column_family_compression {
+            config
+                .column_families
+                .insert(cf.clone(), parse(compression)?);
+        }
+        Ok(config)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -61,13 +233,278 @@ pub struct RpcConfig {
     pub max_connections: usize,
     /// Optional API key for RPC authentication.
     /// If set, mutation methods require `Authorization: Bearer <key>` header.
+    /// This key may call every method.
     #[serde(default)]
     pub api_key: Option<String>,
+    /// Further API keys, each limited to its own methods (see `rpc::auth`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub api_keys: Vec<ApiKeyConfig>,
+    /// Methods callable without an API key once auth is enabled. Defaults to
+    /// the read-only methods.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_methods: Option<Vec<String>>,
+    /// Calls a minute accepted from each client IP without an API key.
+    /// 0 disables the limit.
+    #[serde(default = "default_rpc_rate_limit_per_ip_per_minute")]
+    pub rate_limit_per_ip_per_minute: u32,
+    /// Tighter per-IP limits, in calls a minute, for expensive methods.
+    #[serde(default = "default_rpc_method_rate_limits")]
+    pub method_rate_limits: BTreeMap<String, u32>,
+    /// Largest request body accepted, in bytes.
+    #[serde(default = "default_rpc_max_body_bytes")]
+    pub max_request_body_bytes: u32,
+    /// Largest response sent, in bytes; larger ones become errors.
+    #[serde(default = "default_rpc_max_body_bytes")]
+    pub max_response_body_bytes: u32,
+    /// Seconds a call may run before it is abandoned. 0 disables the limit.
+    #[serde(default = "default_rpc_request_timeout_secs")]
+    pub request_timeout_secs: u64,
+    /// Calls slower than this many milliseconds are logged. 0 disables the
+    /// log.
+    #[serde(default = "default_rpc_slow_query_ms")]
+    pub slow_query_ms: u64,
+    /// Most calls in one JSON-RPC batch. 0 disables batches.
+    #[serde(default = "default_rpc_max_batch_size")]
+    pub max_batch_size: u32,
+    /// Also serve the REST gateway under `/v1/` (see `rpc::rest`).
+    #[serde(default)]
+    pub rest_gateway: bool,
+    /// Also serve GraphQL queries at `/graphql` and `norn_graphql` (see
+    /// `rpc::graphql`).
+    #[serde(default)]
+    pub graphql: bool,
+    /// Also serve the gRPC interface on this address (see `rpc::grpc`).
+    #[serde(default)]
+    pub grpc_listen_addr: Option<String>,
+    /// PEM certificate chain; with `tls_key_path`, the RPC and gRPC servers
+    /// accept only TLS connections (see `rpc::transport`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls_cert_path: Option<String>,
+    /// PEM private key of `tls_cert_path`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls_key_path: Option<String>,
+    /// Origins browsers may call the RPC server from, or `"*"` for any.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cors_allowed_origins: Vec<String>,
+    /// Reverse proxies, as IP addresses or CIDR ranges, whose
+    /// `X-Forwarded-For` header names the client for the per-IP limits.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trusted_proxies: Vec<String>,
+}
+
+fn default_rpc_rate_limit_per_ip_per_minute() -> u32 {
+    600
+}
+
+fn default_rpc_method_rate_limits() -> BTreeMap<String, u32> {
+    [
+        ("norn_getTransactionHistory", 60),
+        ("norn_getEvents", 60),
+        ("norn_getBlockTransactions", 60),
+        ("norn_traceLoomExecution", 30),
+        ("norn_exportLoomState", 10),
+        ("norn_graphql", 60),
+    ]
+    .into_iter()
+    .map(|(method, limit)| (method.to_string(), limit))
+    .collect()
+}
+
+fn default_rpc_max_body_bytes() -> u32 {
+    10 * 1024 * 1024
+}
+
+fn default_rpc_request_timeout_secs() -> u64 {
+    30
+}
+
+fn default_rpc_slow_query_ms() -> u64 {
+    1_000
+}
+
+fn default_rpc_max_batch_size() -> u32 {
+    100
+}
+
+/// An RPC API key and what it may call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiKeyConfig {
+    /// Name shown in logs and errors.
+    pub name: String,
+    /// The key, sent as `Authorization: Bearer <key>`. At least 16 characters.
+    pub key: String,
+    /// Methods the key may call besides the public ones: method names, `*`
+    /// for all, or a prefix such as `norn_submit*`.
+    pub methods: Vec<String>,
+    /// Most calls a minute; unlimited when unset.
+    #[serde(default)]
+    pub rate_limit_per_minute: Option<u32>,
 }
 
+/// Settings for the bridge watcher (see `crate::bridge`). The node must run
+/// its RPC server: the watcher submits attestations through it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BridgeConfig {
+    /// JSON-RPC endpoint of the EVM chain.
+    pub evm_rpc_url: String,
+    /// EVM chain ID, bound into mint instructions and release signatures.
+    pub evm_chain_id: u64,
+    /// Lock contract on the EVM chain (hex address).
+    pub lock_contract: String,
+    /// Bridging loom ID (hex).
+    pub loom_id: String,
+    /// Hex-encoded 32-byte seed of this committee member's Norn key.
+    pub attester_seed: String,
+    /// Hex-encoded 32-byte secp256k1 key that signs EVM releases.
+    pub evm_signer_key: String,
+    /// Blocks a lock must be buried under before it is attested.
+    #[serde(default = "default_bridge_confirmations")]
+    pub confirmations: u64,
+    /// First EVM block to scan when no progress is stored.
+    #[serde(default)]
+    pub start_block: u64,
+    /// Seconds between polls.
+    #[serde(default = "default_bridge_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+    /// Most EVM blocks requested per `eth_getLogs` call.
+    #[serde(default = "default_bridge_max_block_range")]
+    pub max_block_range: u64,
+}
+
+fn default_bridge_confirmations() -> u64 {
+    12
+}
+
+fn default_bridge_poll_interval_secs() -> u64 {
+    15
+}
+
+fn default_bridge_max_block_range() -> u64 {
+    1000
+}
+
+/// Settings for the oracle reporter (see `crate::oracle`). The node must run
+/// its RPC server: reports are submitted through it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OracleConfig {
+    /// Oracle loom ID (hex).
+    pub loom_id: String,
+    /// Hex-encoded 32-byte seed of the reporter's Norn key. The loom owner
+    /// must whitelist its address.
+    pub reporter_seed: String,
+    /// Seconds between reports.
+    #[serde(default = "default_oracle_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+    /// Feeds to report and where their prices come from.
+    pub feeds: Vec<OracleFeedConfig>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OracleFeedConfig {
+    /// Feed ID on the loom, e.g. "NORN/USD".
+    pub feed: String,
+    /// HTTP(S) endpoint returning the price as JSON.
+    pub url: String,
+    /// JSON pointer (RFC 6901) to the price in the response.
+    pub pointer: String,
+    /// Decimal places the loom's feed is configured with.
+    pub decimals: u8,
+}
+
+fn default_oracle_poll_interval_secs() -> u64 {
+    60
+}
+
+/// Thresholds of the liveness and readiness checks (see `crate::health`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthConfig {
+    /// Blocks the node may trail the highest height seen from peers and
+    /// still be ready.
+    #[serde(default = "default_health_max_blocks_behind")]
+    pub max_blocks_behind: u64,
+    /// Seconds the mempool may hold work without a new block before
+    /// consensus counts as stalled. Also how long the main loop may go
+    /// without a tick before the node is no longer live.
+    #[serde(default = "default_health_stall_timeout_secs")]
+    pub stall_timeout_secs: u64,
+    /// Mempool fill, in percent of its capacity, at which the node stops
+    /// being ready.
+    #[serde(default = "default_health_mempool_saturation_percent")]
+    pub mempool_saturation_percent: u8,
+    /// Fewest connected peers for the node to be ready. 0 disables the check.
+    #[serde(default)]
+    pub min_peers: usize,
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            max_blocks_behind: default_health_max_blocks_behind(),
+            stall_timeout_secs: default_health_stall_timeout_secs(),
+            mempool_saturation_percent: default_health_mempool_saturation_percent(),
+            min_peers: 0,
+        }
+    }
+}
+
+fn default_health_max_blocks_behind() -> u64 {
+    10
+}
+
+fn default_health_stall_timeout_secs() -> u64 {
+    60
+}
+
+fn default_health_mempool_saturation_percent() -> u8 {
+    90
+}
+
+/// Settings for the node's logs (see `crate::logging`).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LoggingConfig {
+    /// Default level, or a filter in `RUST_LOG` syntax. `RUST_LOG`, when
+    /// set, takes its place.
     pub level: String,
+    /// Levels of single modules, e.g. `norn_relay = "debug"`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub modules: BTreeMap<String, String>,
+    /// "text", or "json" for one JSON object per line with span fields.
+    #[serde(default = "default_log_format")]
+    pub format: String,
+    /// Directory to write log files to, instead of stdout.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dir: Option<String>,
+    /// When to start a new log file: "minutely", "hourly", "daily" or "never".
+    #[serde(default = "default_log_rotation")]
+    pub rotation: String,
+    /// Log files to keep; older ones are deleted. 0 keeps them all.
+    #[serde(default = "default_log_max_files")]
+    pub max_files: usize,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            level: "info".to_string(),
+            modules: BTreeMap::new(),
+            format: default_log_format(),
+            dir: None,
+            rotation: default_log_rotation(),
+            max_files: default_log_max_files(),
+        }
+    }
+}
+
+fn default_log_format() -> String {
+    "text".to_string()
+}
+
+fn default_log_rotation() -> String {
+    "daily".to_string()
+}
+
+fn default_log_max_files() -> usize {
+    7
 }
 
 impl Default for NodeConfig {
@@ -76,17 +513,30 @@ impl Default for NodeConfig {
             network_id: default_network_id(),
             network: NetworkConfig {
                 listen_addr: "0.0.0.0:9740".to_string(),
-                boot_nodes: norn_types::constants::DEFAULT_BOOT_NODES
-                    .iter()
-                    .map(|s| s.to_string())
-                    .collect(),
+                boot_nodes: Vec::new(),
+                dns_seeds: None,
                 max_connections: 50,
+                fast_sync: false,
+                autonat: true,
+                relay_server: false,
+                relays: Vec::new(),
+                hole_punching: true,
+                peer_inbound_bytes_per_sec: default_peer_inbound_bytes_per_sec(),
+                peer_inbound_messages_per_sec: default_peer_inbound_messages_per_sec(),
+                peer_outbound_bytes_per_sec: default_peer_outbound_bytes_per_sec(),
             },
             storage: StorageConfig {
                 data_dir: dirs::home_dir()
                     .map(|h| h.join(".norn").join("data").to_string_lossy().into_owned())
                     .unwrap_or_else(|| "./norn-data".to_string()),
                 db_type: "memory".to_string(),
+                module_cache: true,
+                pruning: default_pruning(),
+                pruning_interval_secs: default_pruning_interval_secs(),
+                snapshot_interval: default_snapshot_interval(),
+                compression: default_compression(),
+                column_family_compression: default_column_family_compression(),
+                recompress: false,
             },
             validator: ValidatorConfig {
                 enabled: false,
@@ -99,12 +549,30 @@ impl Default for NodeConfig {
                 listen_addr: "127.0.0.1:9741".to_string(),
                 max_connections: 100,
                 api_key: None,
+                api_keys: Vec::new(),
+                public_methods: None,
+                rate_limit_per_ip_per_minute: default_rpc_rate_limit_per_ip_per_minute(),
+                method_rate_limits: default_rpc_method_rate_limits(),
+                max_request_body_bytes: default_rpc_max_body_bytes(),
+                max_response_body_bytes: default_rpc_max_body_bytes(),
+                request_timeout_secs: default_rpc_request_timeout_secs(),
+                slow_query_ms: default_rpc_slow_query_ms(),
+                max_batch_size: default_rpc_max_batch_size(),
+                rest_gateway: false,
+                graphql: false,
+                grpc_listen_addr: None,
+                tls_cert_path: None,
+                tls_key_path: None,
+                cors_allowed_origins: Vec::new(),
+                trusted_proxies: Vec::new(),
             },
-            logging: LoggingConfig {
-                level: "info".to_string(),
-            },
+            logging: LoggingConfig::default(),
             genesis_path: None,
             genesis_config: None,
+            profiles: BTreeMap::new(),
+            bridge: None,
+            oracle: None,
+            health: HealthConfig::default(),
         }
     }
 }
@@ -121,6 +589,34 @@ impl NodeConfig {
         Ok(config)
     }
 
+    /// Apply the named profile's overrides.
+    pub fn apply_profile(&mut self, name: &str) -> Result<(), NodeError> {
+        let profile = self
+            .profiles
+            .get(name)
+            .cloned()
+            .ok_or_else(|| NodeError::ConfigError {
+                reason: format!(
+                    "unknown profile '{}', config defines: {}",
+                    name,
+                    self.profiles.keys().cloned().collect::<Vec<_>>().join(", ")
+                ),
+            })?;
+        if let Some(network_id) = profile.network_id {
+            self.network_id = network_id;
+        }
+        if let Some(addr) = profile.rpc_listen_addr {
+            self.rpc.listen_addr = addr;
+        }
+        if let Some(dir) = profile.data_dir {
+            self.storage.data_dir = dir;
+        }
+        if let Some(path) = profile.keypair_path {
+            self.validator.keypair_path = Some(path);
+        }
+        Ok(())
+    }
+
     /// Initialize a default configuration file in the given directory.
     pub fn init(dir: &str) -> Result<(), NodeError> {
         let dir_path = Path::new(dir);
@@ -178,10 +674,123 @@ mod tests {
     }
 
     #[test]
-    fn test_default_config_has_boot_nodes() {
+    fn test_default_config_uses_network_dns_seeds() {
         let config = NodeConfig::default();
-        assert!(!config.network.boot_nodes.is_empty());
-        assert!(config.network.boot_nodes[0].contains("seed.norn.network"));
+        assert!(config.network.boot_nodes.is_empty());
+        assert_eq!(
+            config.network.resolved_dns_seeds(&config.network_id),
+            vec!["seed.norn.network".to_string()]
+        );
+
+        let mut config = config;
+        config.network.dns_seeds = Some(vec!["seed.example.org:9740".to_string()]);
+        assert_eq!(
+            config.network.resolved_dns_seeds("mainnet"),
+            vec!["seed.example.org:9740".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_pruning_mode_defaults_to_archive() {
+        let mut config = NodeConfig::default();
+        assert_eq!(config.storage.pruning_mode().unwrap(), PruningMode::Archive);
+
+        // Configs written before pruning existed still load.
+        let toml_str = toml::to_string_pretty(&config)
+            .unwrap()
+            .replace("pruning = \"archive\"\n", "");
+        let deserialized: NodeConfig = toml::from_str(&toml_str).unwrap();
+        assert_eq!(deserialized.storage.pruning, "archive");
+
+        config.storage.pruning = "keep-recent:100".to_string();
+        assert_eq!(
+            config.storage.pruning_mode().unwrap(),
+            PruningMode::KeepRecent(100)
+        );
+        config.storage.pruning = "sometimes".to_string();
+        assert!(config.storage.pruning_mode().is_err());
+    }
+
+    #[test]
+    fn test_compression_config() {
+        let mut config = NodeConfig::default();
+        assert_eq!(
+            config.storage.compression().unwrap(),
+            CompressionConfig::default()
+        );
+
+        config
+            .storage
+            .column_family_compression
+            .insert("merkle".to_string(), "none".to_string());
+        config.storage.compression = "zstd:9".to_string();
+        let compression = config.storage.compression().unwrap();
+        assert_eq!(compression.for_cf("default").to_string(), "zstd:9");
+        assert_eq!(compression.for_cf("merkle").to_string(), "none");
+
+        config.storage.compression = "gzip".to_string();
+        assert!(config.storage.compression().is_err());
+    }
+
+    #[test]
+    fn test_profiles() {
+        let mut config = NodeConfig::default();
+        config.profiles.insert(
+            "testnet".to_string(),
+            NodeProfile {
+                network_id: Some("testnet".to_string()),
+                data_dir: Some("/var/lib/norn-testnet".to_string()),
+                ..Default::default()
+            },
+        );
+        let toml_str = toml::to_string_pretty(&config).unwrap();
+        let mut config: NodeConfig = toml::from_str(&toml_str).unwrap();
+
+        assert!(config.apply_profile("mainnet").is_err());
+        config.apply_profile("testnet").unwrap();
+        assert_eq!(config.network_id, "testnet");
+        assert_eq!(config.storage.data_dir, "/var/lib/norn-testnet");
+        assert_eq!(config.rpc.listen_addr, "127.0.0.1:9741");
+    }
+
+    #[test]
+    fn test_bridge_config_defaults() {
+        assert!(NodeConfig::default().bridge.is_none());
+        let toml_str = format!(
+            "{}\n[bridge]\nevm_rpc_url = \"http://localhost:8545\"\nevm_chain_id = 1\n\
+             lock_contract = \"0x{}\"\nloom_id = \"{}\"\nattester_seed = \"{}\"\n\
+             evm_signer_key = \"{}\"\n",
+            toml::to_string_pretty(&NodeConfig::default()).unwrap(),
+            "aa".repeat(20),
+            "09".repeat(32),
+            "01".repeat(32),
+            "02".repeat(32),
+        );
+        let config: NodeConfig = toml::from_str(&toml_str).unwrap();
+        let bridge = config.bridge.unwrap();
+        assert_eq!(bridge.confirmations, 12);
+        assert_eq!(bridge.start_block, 0);
+        assert_eq!(bridge.poll_interval_secs, 15);
+        assert_eq!(bridge.max_block_range, 1000);
+    }
+
+    #[test]
+    fn test_oracle_config_defaults() {
+        assert!(NodeConfig::default().oracle.is_none());
+        let toml_str = format!(
+            "{}\n[oracle]\nloom_id = \"{}\"\nreporter_seed = \"{}\"\n\n\
+             [[oracle.feeds]]\nfeed = \"NORN/USD\"\nurl = \"https://example.com/price\"\n\
+             pointer = \"/norn/usd\"\ndecimals = 8\n",
+            toml::to_string_pretty(&NodeConfig::default()).unwrap(),
+            "09".repeat(32),
+            "01".repeat(32),
+        );
+        let config: NodeConfig = toml::from_str(&toml_str).unwrap();
+        let oracle = config.oracle.unwrap();
+        assert_eq!(oracle.poll_interval_secs, 60);
+        assert_eq!(oracle.feeds.len(), 1);
+        assert_eq!(oracle.feeds[0].feed, "NORN/USD");
+        assert_eq!(oracle.feeds[0].decimals, 8);
     }
 
     #[test]
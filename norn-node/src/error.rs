@@ -22,6 +22,15 @@ pub enum NodeError {
     #[error("rpc error: {reason}")]
     RpcError { reason: String },
 
+    #[error("snapshot error: {reason}")]
+    SnapshotError { reason: String },
+
+    #[error("bridge error: {reason}")]
+    BridgeError { reason: String },
+
+    #[error("oracle error: {reason}")]
+    OracleError { reason: String },
+
     #[error("io error: {0}")]
     IoError(#[from] std::io::Error),
 }
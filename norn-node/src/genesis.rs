@@ -1,3 +1,4 @@
+use norn_crypto::bls::{self, BlsKeypair};
 use norn_crypto::hash::blake3_hash;
 use norn_types::genesis::{
     GenesisAllocation, GenesisConfig, GenesisNameRegistration, GenesisParameters, GenesisValidator,
@@ -9,6 +10,16 @@ use crate::error::NodeError;
 
 /// Create a genesis block and initial weave state from a genesis config.
 pub fn create_genesis_block(config: &GenesisConfig) -> Result<(WeaveBlock, WeaveState), NodeError> {
+    // Genesis validators skip the staking path, so check their BLS keys here.
+    for v in &config.validators {
+        bls::verify_possession(&v.bls_pubkey, &v.bls_pop).map_err(|_| NodeError::GenesisError {
+            reason: format!(
+                "invalid BLS proof of possession for validator {}",
+                hex::encode(v.pubkey)
+            ),
+        })?;
+    }
+
     // Build a genesis block at height 0 with empty content.
     let mut block = WeaveBlock {
         height: 0,
@@ -35,15 +46,28 @@ pub fn create_genesis_block(config: &GenesisConfig) -> Result<(WeaveBlock, Weave
         token_mints: Vec::new(),
         token_mints_root: [0u8; 32],
         token_burns: Vec::new(),
+        token_metadata_updates: Vec::new(),
         token_burns_root: [0u8; 32],
+        token_metadata_updates_root: [0u8; 32],
         loom_deploys: Vec::new(),
         loom_deploys_root: [0u8; 32],
+        operator_handovers: vec![],
+        operator_handovers_root: [0u8; 32],
+        key_updates: vec![],
+        key_updates_root: [0u8; 32],
         stake_operations: Vec::new(),
         stake_operations_root: [0u8; 32],
+        slashing_evidence: vec![],
+        slashing_evidence_root: [0u8; 32],
+        spindle_operations: vec![],
+        spindle_operations_root: [0u8; 32],
+        base_fee: 0,
         state_root: [0u8; 32],
+        validator_set_hash: [0u8; 32],
         timestamp: config.timestamp,
         proposer: [0u8; 32],
-        validator_signatures: Vec::new(),
+        proposer_signature: [0u8; 64],
+        certificate: None,
     };
 
     // Compute the genesis block hash from its fields.
@@ -143,6 +167,22 @@ const DEVNET_VALIDATOR2_ADDRESS: Address = [
     0x8f, 0x75, 0x2a, 0x84,
 ];
 
+/// A devnet genesis validator whose BLS key is derived from its keypair seed.
+fn devnet_validator(
+    seed: [u8; 32],
+    pubkey: norn_types::primitives::PublicKey,
+    address: Address,
+) -> GenesisValidator {
+    let bls_keypair = BlsKeypair::from_seed(&seed);
+    GenesisValidator {
+        pubkey,
+        bls_pubkey: bls_keypair.public_key(),
+        bls_pop: bls_keypair.prove_possession(),
+        address,
+        stake: 1_000_000_000_000,
+    }
+}
+
 /// Create a devnet genesis config with the augmnt founder pre-funded
 /// and three deterministic validators (seed + validator 1 + validator 2).
 ///
@@ -157,21 +197,17 @@ pub fn devnet_genesis() -> (GenesisConfig, Address) {
         chain_id: "norn-dev".to_string(),
         timestamp: now,
         validators: vec![
-            GenesisValidator {
-                pubkey: DEVNET_SEED_PUBKEY,
-                address: DEVNET_SEED_ADDRESS,
-                stake: 1_000_000_000_000,
-            },
-            GenesisValidator {
-                pubkey: DEVNET_VALIDATOR_PUBKEY,
-                address: DEVNET_VALIDATOR_ADDRESS,
-                stake: 1_000_000_000_000,
-            },
-            GenesisValidator {
-                pubkey: DEVNET_VALIDATOR2_PUBKEY,
-                address: DEVNET_VALIDATOR2_ADDRESS,
-                stake: 1_000_000_000_000,
-            },
+            devnet_validator([0x01; 32], DEVNET_SEED_PUBKEY, DEVNET_SEED_ADDRESS),
+            devnet_validator(
+                [0x02; 32],
+                DEVNET_VALIDATOR_PUBKEY,
+                DEVNET_VALIDATOR_ADDRESS,
+            ),
+            devnet_validator(
+                [0x03; 32],
+                DEVNET_VALIDATOR2_PUBKEY,
+                DEVNET_VALIDATOR2_ADDRESS,
+            ),
         ],
         allocations: vec![GenesisAllocation {
             address: DEVNET_FOUNDER,
@@ -186,6 +222,7 @@ pub fn devnet_genesis() -> (GenesisConfig, Address) {
             min_validator_stake: 1_000_000_000_000,
             initial_base_fee: 100,
             bonding_period: 100,
+            max_validators: 100,
         },
         name_registrations: vec![GenesisNameRegistration {
             name: "augmnt".to_string(),
@@ -265,6 +302,8 @@ mod tests {
             timestamp: 1700000000,
             validators: vec![GenesisValidator {
                 pubkey: [1u8; 32],
+                bls_pubkey: BlsKeypair::from_seed(&[1u8; 32]).public_key(),
+                bls_pop: BlsKeypair::from_seed(&[1u8; 32]).prove_possession(),
                 address: [1u8; 20],
                 stake: 1_000_000_000_000,
             }],
@@ -277,6 +316,7 @@ mod tests {
                 min_validator_stake: 1_000_000_000_000,
                 initial_base_fee: 100,
                 bonding_period: 100,
+                max_validators: 100,
             },
             name_registrations: Vec::new(),
         }
@@ -302,6 +342,13 @@ mod tests {
         assert_eq!(state.fee_state.fee_multiplier, 1000);
     }
 
+    #[test]
+    fn test_genesis_rejects_invalid_bls_possession() {
+        let mut config = make_genesis_config();
+        config.validators[0].bls_pop = BlsKeypair::from_seed(&[2u8; 32]).prove_possession();
+        assert!(create_genesis_block(&config).is_err());
+    }
+
     #[test]
     fn test_genesis_hash_is_deterministic() {
         let config = make_genesis_config();
@@ -0,0 +1,293 @@
+//! Liveness and readiness of the node, behind `norn_health`, `/healthz` and
+//! `/readyz`.
+//!
+//! The main loop reports its height, mempool size and peer count on every
+//! tick, and sync reports the heights peers have shown. The node is live
+//! while its main loop keeps ticking (or it is syncing), and ready when no
+//! [`HealthIssue`] applies.
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use norn_types::constants::MEMPOOL_MAX_SIZE;
+
+use crate::config::HealthConfig;
+
+/// A reason the node is not ready.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthIssue {
+    /// Sync with peers is running.
+    Syncing,
+    /// More than `max_blocks_behind` blocks below the highest height peers
+    /// have shown.
+    Behind,
+    /// The mempool is filled past `mempool_saturation_percent`.
+    MempoolSaturated,
+    /// The mempool has held work for `stall_timeout_secs` without a block.
+    ConsensusStalled,
+    /// Fewer than `min_peers` peers are connected.
+    LowPeers,
+}
+
+impl HealthIssue {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HealthIssue::Syncing => "syncing",
+            HealthIssue::Behind => "behind",
+            HealthIssue::MempoolSaturated => "mempool_saturated",
+            HealthIssue::ConsensusStalled => "consensus_stalled",
+            HealthIssue::LowPeers => "low_peers",
+        }
+    }
+}
+
+/// The node's health at one moment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HealthReport {
+    /// `ok`, `syncing`, `degraded` (live but not ready) or `unhealthy` (not
+    /// live).
+    pub status: String,
+    /// Whether the main loop is running.
+    pub live: bool,
+    /// Whether the node should receive traffic.
+    pub ready: bool,
+    /// Why the node is not ready.
+    pub issues: Vec<HealthIssue>,
+    /// Height as of the last tick.
+    pub height: u64,
+    /// Highest height peers have shown, or the node's own if higher.
+    pub network_height: u64,
+    /// `network_height - height`.
+    pub blocks_behind: u64,
+    /// Items in the mempool as of the last tick.
+    pub mempool_size: u64,
+    /// Capacity of the mempool.
+    pub mempool_capacity: u64,
+    /// Connected peers as of the last tick, or `None` without networking.
+    pub peer_count: Option<u64>,
+    /// Seconds the mempool has held work without a new block.
+    pub stalled_secs: u64,
+}
+
+/// The main loop's view as of its last tick.
+struct Tick {
+    at: Instant,
+    height: u64,
+    mempool_size: usize,
+    peer_count: Option<usize>,
+    /// When the height last rose.
+    progress_at: Instant,
+    /// Since when the mempool has held work.
+    pending_since: Option<Instant>,
+}
+
+/// Tracks what the health checks need. Shared by the node and RPC server.
+pub struct NodeHealth {
+    config: HealthConfig,
+    syncing: AtomicBool,
+    network_height: AtomicU64,
+    tick: Mutex<Tick>,
+}
+
+impl NodeHealth {
+    pub fn new(config: HealthConfig) -> Self {
+        let now = Instant::now();
+        Self {
+            config,
+            syncing: AtomicBool::new(false),
+            network_height: AtomicU64::new(0),
+            tick: Mutex::new(Tick {
+                at: now,
+                height: 0,
+                mempool_size: 0,
+                peer_count: None,
+                progress_at: now,
+                pending_since: None,
+            }),
+        }
+    }
+
+    /// Record the main loop's view. Called on every tick.
+    pub fn observe(&self, height: u64, mempool_size: usize, peer_count: Option<usize>) {
+        self.observe_at(Instant::now(), height, mempool_size, peer_count);
+    }
+
+    fn observe_at(
+        &self,
+        now: Instant,
+        height: u64,
+        mempool_size: usize,
+        peer_count: Option<usize>,
+    ) {
+        let mut tick = self.tick.lock().unwrap_or_else(|e| e.into_inner());
+        if height > tick.height {
+            tick.progress_at = now;
+        }
+        tick.pending_since = match (mempool_size, tick.pending_since) {
+            (0, _) => None,
+            (_, Some(since)) => Some(since),
+            (_, None) => Some(now),
+        };
+        tick.at = now;
+        tick.height = height;
+        tick.mempool_size = mempool_size;
+        tick.peer_count = peer_count;
+    }
+
+    /// Record a height a peer has shown to exist.
+    pub fn observe_network_height(&self, height: u64) {
+        self.network_height.fetch_max(height, Ordering::Relaxed);
+    }
+
+    /// Mark the node as syncing until the returned guard is dropped.
+    pub fn start_sync(self: &Arc<Self>) -> SyncGuard {
+        self.syncing.store(true, Ordering::Relaxed);
+        SyncGuard(self.clone())
+    }
+
+    /// Check the node's health now.
+    pub fn report(&self) -> HealthReport {
+        self.report_at(Instant::now())
+    }
+
+    fn report_at(&self, now: Instant) -> HealthReport {
+        let tick = self.tick.lock().unwrap_or_else(|e| e.into_inner());
+        let config = &self.config;
+        let syncing = self.syncing.load(Ordering::Relaxed);
+        let network_height = self.network_height.load(Ordering::Relaxed).max(tick.height);
+        let blocks_behind = network_height - tick.height;
+        let stall_timeout = Duration::from_secs(config.stall_timeout_secs);
+        // Stalled time counts from when work arrived or the last block,
+        // whichever is later.
+        let stalled = tick
+            .pending_since
+            .map(|since| now.saturating_duration_since(since.max(tick.progress_at)))
+            .unwrap_or_default();
+
+        let mut issues = Vec::new();
+        if syncing {
+            issues.push(HealthIssue::Syncing);
+        }
+        if blocks_behind > config.max_blocks_behind {
+            issues.push(HealthIssue::Behind);
+        }
+        if config.mempool_saturation_percent > 0
+            && tick.mempool_size * 100
+                >= MEMPOOL_MAX_SIZE * config.mempool_saturation_percent as usize
+        {
+            issues.push(HealthIssue::MempoolSaturated);
+        }
+        if config.stall_timeout_secs > 0 && stalled > stall_timeout {
+            issues.push(HealthIssue::ConsensusStalled);
+        }
+        if tick
+            .peer_count
+            .is_some_and(|peers| peers < config.min_peers)
+        {
+            issues.push(HealthIssue::LowPeers);
+        }
+
+        let live = syncing
+            || config.stall_timeout_secs == 0
+            || now.saturating_duration_since(tick.at) <= stall_timeout;
+        let ready = live && issues.is_empty();
+        let status = if !live {
+            "unhealthy"
+        } else if ready {
+            "ok"
+        } else if syncing {
+            "syncing"
+        } else {
+            "degraded"
+        };
+        HealthReport {
+            status: status.to_string(),
+            live,
+            ready,
+            issues,
+            height: tick.height,
+            network_height,
+            blocks_behind,
+            mempool_size: tick.mempool_size as u64,
+            mempool_capacity: MEMPOOL_MAX_SIZE as u64,
+            peer_count: tick.peer_count.map(|peers| peers as u64),
+            stalled_secs: stalled.as_secs(),
+        }
+    }
+}
+
+/// Clears the syncing state when dropped.
+pub struct SyncGuard(Arc<NodeHealth>);
+
+impl Drop for SyncGuard {
+    fn drop(&mut self) {
+        self.0.syncing.store(false, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ready_when_in_step() {
+        let health = NodeHealth::new(HealthConfig::default());
+        health.observe(5, 3, Some(4));
+        health.observe_network_height(12);
+        let report = health.report();
+        assert_eq!(report.status, "ok");
+        assert!(report.live && report.ready);
+        assert_eq!(report.blocks_behind, 7);
+        assert_eq!(report.peer_count, Some(4));
+    }
+
+    #[test]
+    fn test_issues() {
+        let config = HealthConfig {
+            min_peers: 2,
+            ..HealthConfig::default()
+        };
+        let health = Arc::new(NodeHealth::new(config));
+        let start = Instant::now();
+
+        {
+            let _sync = health.start_sync();
+            health.observe_network_height(100);
+            let report = health.report_at(start);
+            assert_eq!(report.status, "syncing");
+            assert_eq!(
+                report.issues,
+                vec![HealthIssue::Syncing, HealthIssue::Behind]
+            );
+        }
+
+        health.observe_at(start, 100, MEMPOOL_MAX_SIZE, Some(1));
+        let report = health.report_at(start);
+        assert_eq!(report.status, "degraded");
+        assert!(report.live && !report.ready);
+        assert_eq!(
+            report.issues,
+            vec![HealthIssue::MempoolSaturated, HealthIssue::LowPeers]
+        );
+
+        // Work pending with no new block past the timeout.
+        let later = start + Duration::from_secs(61);
+        health.observe_at(later, 100, 1, Some(2));
+        let report = health.report_at(later);
+        assert_eq!(report.issues, vec![HealthIssue::ConsensusStalled]);
+        assert_eq!(report.stalled_secs, 61);
+
+        // A new block resets the stall.
+        health.observe_at(later, 101, 1, Some(2));
+        assert!(health.report_at(later).ready);
+
+        // The main loop stopped ticking.
+        let report = health.report_at(later + Duration::from_secs(61));
+        assert_eq!(report.status, "unhealthy");
+        assert!(!report.live && !report.ready);
+    }
+}
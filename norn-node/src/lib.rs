@@ -5,13 +5,21 @@
 //! configuration, and genesis state handling.
 
 pub mod banner;
+pub mod bridge;
 pub mod cli;
 pub mod config;
 pub mod error;
 pub mod genesis;
+pub mod health;
+pub mod logging;
+pub mod loom_client;
 pub mod metrics;
 pub mod node;
+pub mod oracle;
+pub mod pruner;
+pub mod replay;
 pub mod rpc;
+pub mod snapshot;
 pub mod state_manager;
 pub mod state_store;
 pub mod wallet;
@@ -30,6 +38,8 @@ pub fn loom_from_registration(
             min_participants: 1,
             accepted_tokens: vec![norn_types::primitives::NATIVE_TOKEN_ID],
             config_data: vec![],
+            co_operators: ld.config.co_operators.clone(),
+            operator_threshold: ld.config.operator_threshold,
         },
         operator: ld.operator,
         participants: Vec::new(),
@@ -0,0 +1,201 @@
+//! Node logging.
+//!
+//! Logs are written as text or as JSON, one object per line, to stdout or to
+//! rotated files under `logging.dir`. In JSON, each line carries the fields of
+//! the spans it was logged in, such as the `height` of the block being
+//! applied, the `peer` a message came from and the `loom_id` being executed.
+//!
+//! The level filter can be changed while the node runs, through
+//! `norn_getLogLevels` and `norn_setLogLevels`.
+
+use std::collections::BTreeMap;
+use std::sync::{Mutex, OnceLock};
+
+use serde::{Deserialize, Serialize};
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use tracing_subscriber::layer::{Layered, SubscriberExt};
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, reload, EnvFilter, Layer, Registry};
+
+use crate::config::LoggingConfig;
+use crate::error::NodeError;
+
+type FilteredRegistry = Layered<reload::Layer<EnvFilter, Registry>, Registry>;
+
+/// The level filter in effect and the handle to replace it.
+struct LogHandle {
+    filter: reload::Handle<EnvFilter, Registry>,
+    levels: Mutex<LogLevels>,
+}
+
+static HANDLE: OnceLock<LogHandle> = OnceLock::new();
+
+/// Log levels: a default and per-module overrides.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogLevels {
+    /// Default level, or a filter in `RUST_LOG` syntax.
+    pub level: String,
+    /// Levels of single modules, e.g. `norn_relay` → `debug`.
+    #[serde(default)]
+    pub modules: BTreeMap<String, String>,
+}
+
+impl LogLevels {
+    /// The levels as `EnvFilter` directives.
+    pub fn directives(&self) -> String {
+        std::iter::once(self.level.clone())
+            .chain(
+                self.modules
+                    .iter()
+                    .map(|(module, level)| format!("{}={}", module, level)),
+            )
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    fn filter(&self) -> Result<EnvFilter, String> {
+        if let Some(module) = self
+            .modules
+            .keys()
+            .find(|m| m.is_empty() || m.contains(['=', ',', ' ']))
+        {
+            return Err(format!("invalid module '{}'", module));
+        }
+        if let Some((module, level)) = self.modules.iter().find(|(_, level)| {
+            level
+                .parse::<tracing::level_filters::LevelFilter>()
+                .is_err()
+        }) {
+            return Err(format!("invalid level '{}' for {}", level, module));
+        }
+        EnvFilter::try_new(self.directives()).map_err(|e| format!("invalid log level: {}", e))
+    }
+}
+
+/// Install the global logger. Runs once; later calls fail.
+///
+/// `RUST_LOG`, when set, takes the place of `logging.level`.
+pub fn init(config: &LoggingConfig) -> Result<(), NodeError> {
+    let invalid = |reason: String| NodeError::ConfigError { reason };
+    let levels = LogLevels {
+        level: std::env::var(EnvFilter::DEFAULT_ENV).unwrap_or_else(|_| config.level.clone()),
+        modules: config.modules.clone(),
+    };
+    let (filter, handle) = reload::Layer::new(
+        levels
+            .filter()
+            .map_err(|e| invalid(format!("logging: {}", e)))?,
+    );
+
+    let (writer, ansi) = match &config.dir {
+        Some(dir) => (BoxMakeWriter::new(file_appender(config, dir)?), false),
+        None => (BoxMakeWriter::new(std::io::stdout), true),
+    };
+    let output: Box<dyn Layer<FilteredRegistry> + Send + Sync> = match config.format.as_str() {
+        "text" => fmt::layer().with_ansi(ansi).with_writer(writer).boxed(),
+        "json" => fmt::layer()
+            .json()
+            .with_current_span(true)
+            .with_span_list(true)
+            .with_writer(writer)
+            .boxed(),
+        other => {
+            return Err(invalid(format!(
+                "logging.format '{}': expected 'text' or 'json'",
+                other
+            )))
+        }
+    };
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(output)
+        .try_init()
+        .map_err(|e| invalid(format!("logging: {}", e)))?;
+    let _ = HANDLE.set(LogHandle {
+        filter: handle,
+        levels: Mutex::new(levels),
+    });
+    Ok(())
+}
+
+fn file_appender(config: &LoggingConfig, dir: &str) -> Result<RollingFileAppender, NodeError> {
+    let invalid = |reason: String| NodeError::ConfigError { reason };
+    let rotation = match config.rotation.as_str() {
+        "minutely" => Rotation::MINUTELY,
+        "hourly" => Rotation::HOURLY,
+        "daily" => Rotation::DAILY,
+        "never" => Rotation::NEVER,
+        other => {
+            return Err(invalid(format!(
+                "logging.rotation '{}': expected 'minutely', 'hourly', 'daily' or 'never'",
+                other
+            )))
+        }
+    };
+    let mut builder = RollingFileAppender::builder()
+        .rotation(rotation)
+        .filename_prefix("norn")
+        .filename_suffix("log");
+    if config.max_files > 0 {
+        builder = builder.max_log_files(config.max_files);
+    }
+    builder
+        .build(dir)
+        .map_err(|e| invalid(format!("logging.dir '{}': {}", dir, e)))
+}
+
+/// The log levels in effect, or `None` before [`init`].
+pub fn levels() -> Option<LogLevels> {
+    let handle = HANDLE.get()?;
+    let levels = handle.levels.lock().unwrap_or_else(|e| e.into_inner());
+    Some(levels.clone())
+}
+
+/// Replace the log levels while the node runs.
+pub fn set_levels(levels: LogLevels) -> Result<LogLevels, String> {
+    let filter = levels.filter()?;
+    let handle = HANDLE
+        .get()
+        .ok_or_else(|| "logging is not initialized".to_string())?;
+    let mut current = handle.levels.lock().unwrap_or_else(|e| e.into_inner());
+    handle.filter.reload(filter).map_err(|e| e.to_string())?;
+    tracing::info!(levels = %levels.directives(), "log levels changed");
+    *current = levels.clone();
+    Ok(levels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levels_directives_and_validation() {
+        let mut levels = LogLevels {
+            level: "info".to_string(),
+            modules: [
+                ("norn_relay".to_string(), "debug".to_string()),
+                ("norn_weave::consensus".to_string(), "trace".to_string()),
+            ]
+            .into_iter()
+            .collect(),
+        };
+        assert_eq!(
+            levels.directives(),
+            "info,norn_relay=debug,norn_weave::consensus=trace"
+        );
+        assert!(levels.filter().is_ok());
+
+        levels
+            .modules
+            .insert("norn_loom".to_string(), "loud".to_string());
+        assert!(levels.filter().unwrap_err().contains("norn_loom"));
+        levels.modules.clear();
+        levels.modules.insert("a=b".to_string(), "info".to_string());
+        assert!(levels.filter().is_err());
+        levels.modules.clear();
+        levels.level = "info,norn_node=warn".to_string();
+        assert!(levels.filter().is_ok());
+    }
+}
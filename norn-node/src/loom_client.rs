@@ -0,0 +1,146 @@
+//! Signed loom executions through a node's JSON-RPC server, for services
+//! that act as a loom participant (the bridge watcher and oracle reporter).
+
+use jsonrpsee::core::client::ClientT;
+use jsonrpsee::http_client::{HeaderMap, HeaderValue, HttpClient, HttpClientBuilder};
+use jsonrpsee::rpc_params;
+
+use norn_crypto::keys::Keypair;
+use norn_types::primitives::{Address, LoomId};
+
+use crate::error::NodeError;
+use crate::rpc::types::{EventFilterParams, ExecutionResult, IndexedEventInfo};
+
+/// Executes one loom as one participant.
+pub struct LoomClient {
+    client: HttpClient,
+    loom_id: LoomId,
+    keypair: Keypair,
+    address: Address,
+}
+
+impl LoomClient {
+    /// Connect to the RPC server at `rpc_url`, authenticating with `api_key`
+    /// if the server needs one.
+    pub fn new(
+        rpc_url: &str,
+        api_key: Option<&str>,
+        loom_id: LoomId,
+        keypair: Keypair,
+    ) -> Result<Self, NodeError> {
+        let mut headers = HeaderMap::new();
+        if let Some(key) = api_key {
+            let value = HeaderValue::from_str(&format!("Bearer {}", key))
+                .map_err(|e| config_error(format!("invalid RPC api_key: {}", e)))?;
+            headers.insert("Authorization", value);
+        }
+        let client = HttpClientBuilder::default()
+            .set_headers(headers)
+            .build(rpc_url)
+            .map_err(|e| config_error(format!("invalid RPC url '{}': {}", rpc_url, e)))?;
+
+        let address = norn_crypto::address::pubkey_to_address(&keypair.public_key());
+        Ok(Self {
+            client,
+            loom_id,
+            keypair,
+            address,
+        })
+    }
+
+    pub fn loom_id(&self) -> &LoomId {
+        &self.loom_id
+    }
+
+    /// Address executions are sent from.
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    /// Execute the loom with `input`, signed as this participant. A contract
+    /// error comes back as an unsuccessful result, not an `Err`.
+    pub async fn execute(&self, input: &[u8]) -> Result<ExecutionResult, NodeError> {
+        let signing_msg = norn_crypto::hash::blake3_hash_multi(&[
+            b"norn_execute_loom",
+            &self.loom_id,
+            input,
+            &self.address,
+        ]);
+        let signature = self.keypair.sign(&signing_msg);
+        self.client
+            .request(
+                "norn_executeLoom",
+                rpc_params![
+                    hex::encode(self.loom_id),
+                    hex::encode(input),
+                    hex::encode(self.address),
+                    hex::encode(signature),
+                    hex::encode(self.keypair.public_key())
+                ],
+            )
+            .await
+            .map_err(rpc_error)
+    }
+
+    /// Events of type `ty` emitted by the loom, in emission order.
+    pub async fn events(
+        &self,
+        ty: &str,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<IndexedEventInfo>, NodeError> {
+        let filter = EventFilterParams {
+            loom_id: Some(hex::encode(self.loom_id)),
+            ty: Some(ty.to_string()),
+            ..Default::default()
+        };
+        self.client
+            .request("norn_getEvents", rpc_params![filter, limit, offset])
+            .await
+            .map_err(rpc_error)
+    }
+}
+
+/// Parse 32 bytes of hex, with or without a `0x` prefix.
+pub fn parse_hex32(s: &str) -> Result<[u8; 32], String> {
+    hex::decode(s.strip_prefix("0x").unwrap_or(s))
+        .map_err(|e| format!("invalid hex: {}", e))?
+        .try_into()
+        .map_err(|_| "expected 32 bytes".to_string())
+}
+
+fn config_error(reason: String) -> NodeError {
+    NodeError::ConfigError { reason }
+}
+
+fn rpc_error(e: jsonrpsee::core::ClientError) -> NodeError {
+    NodeError::RpcError {
+        reason: e.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hex32() {
+        assert_eq!(parse_hex32(&hex::encode([7u8; 32])).unwrap(), [7u8; 32]);
+        assert_eq!(
+            parse_hex32(&format!("0x{}", hex::encode([7u8; 32]))).unwrap(),
+            [7u8; 32]
+        );
+        assert!(parse_hex32("abcd").is_err());
+        assert!(parse_hex32("zz").is_err());
+    }
+
+    #[test]
+    fn test_address_matches_keypair() {
+        let keypair = Keypair::from_seed(&[1u8; 32]);
+        let expected = norn_crypto::address::pubkey_to_address(&keypair.public_key());
+        let client =
+            LoomClient::new("http://127.0.0.1:9741", Some("key"), [9u8; 32], keypair).unwrap();
+        assert_eq!(client.loom_id(), &[9u8; 32]);
+        assert_eq!(client.address(), expected);
+    }
+}
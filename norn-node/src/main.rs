@@ -1,14 +1,21 @@
 use clap::Parser;
-use tracing_subscriber::EnvFilter;
 
 mod banner;
+mod bridge;
 mod cli;
 mod config;
 mod error;
 mod genesis;
+mod health;
+mod logging;
+mod loom_client;
 mod metrics;
 mod node;
+mod oracle;
+mod pruner;
+mod replay;
 mod rpc;
+mod snapshot;
 mod state_manager;
 mod state_store;
 mod wallet;
@@ -27,6 +34,8 @@ fn loom_from_registration(
             min_participants: 1,
             accepted_tokens: vec![norn_types::primitives::NATIVE_TOKEN_ID],
             config_data: vec![],
+            co_operators: ld.config.co_operators.clone(),
+            operator_threshold: ld.config.operator_threshold,
         },
         operator: ld.operator,
         participants: Vec::new(),
@@ -38,18 +47,14 @@ fn loom_from_registration(
 }
 
 fn main() {
-    // Initialize tracing with configurable level via RUST_LOG env var.
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
-        )
-        .init();
-
+    // Logging is set up by `cli::run`, from the config for `norn run`.
     let cli = cli::Cli::parse();
 
     let rt = tokio::runtime::Runtime::new().expect("Failed to create Tokio runtime");
     rt.block_on(async {
         if let Err(e) = cli::run(cli).await {
+            // The error may have come before logging was set up.
+            let _ = logging::init(&config::LoggingConfig::default());
             tracing::error!("Fatal error: {}", e);
             std::process::exit(1);
         }
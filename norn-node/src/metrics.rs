@@ -1,16 +1,52 @@
+use prometheus_client::encoding::{EncodeLabelSet, EncodeLabelValue};
 use prometheus_client::metrics::counter::Counter;
+use prometheus_client::metrics::family::Family;
 use prometheus_client::metrics::gauge::Gauge;
 use prometheus_client::registry::Registry;
 
+/// Why the RPC server rejected a request.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, EncodeLabelValue)]
+pub enum RpcRejectReason {
+    /// The client IP was over its rate limit.
+    IpRateLimited,
+    /// The client IP was over the method's rate limit.
+    MethodRateLimited,
+    /// The API key may not call the method.
+    Forbidden,
+    /// The API key was over its rate limit.
+    KeyRateLimited,
+    /// The request body was too large.
+    TooLarge,
+    /// The call did not finish in time.
+    Timeout,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct RpcRejectLabels {
+    pub reason: RpcRejectReason,
+}
+
 /// Node-wide Prometheus metrics.
 pub struct NodeMetrics {
     pub weave_height: Gauge,
-    #[allow(dead_code)] // Registered in Prometheus; updated when relay exposes peer count API
     pub peer_count: Gauge,
+    pub peers_banned: Gauge,
+    pub peer_penalties: Counter,
+    pub relay_bytes_received: Counter,
+    pub relay_bytes_sent: Counter,
+    pub relay_messages_throttled: Counter,
+    pub relay_bytes_received_per_sec: Gauge,
+    pub relay_bytes_sent_per_sec: Gauge,
     pub mempool_size: Gauge,
     pub blocks_produced: Counter,
     pub fraud_proofs_submitted: Counter,
     pub knots_validated: Counter,
+    pub storage_disk_bytes: Gauge,
+    pub pruned_height: Gauge,
+    pub validators_slashed: Counter,
+    pub validators_jailed: Gauge,
+    pub rpc_requests_rejected: Family<RpcRejectLabels, Counter>,
+    pub rpc_slow_requests: Counter,
     pub registry: Registry,
 }
 
@@ -21,10 +57,23 @@ impl NodeMetrics {
 
         let weave_height = Gauge::default();
         let peer_count = Gauge::default();
+        let peers_banned = Gauge::default();
+        let peer_penalties = Counter::default();
+        let relay_bytes_received = Counter::default();
+        let relay_bytes_sent = Counter::default();
+        let relay_messages_throttled = Counter::default();
+        let relay_bytes_received_per_sec = Gauge::default();
+        let relay_bytes_sent_per_sec = Gauge::default();
         let mempool_size = Gauge::default();
         let blocks_produced = Counter::default();
         let fraud_proofs_submitted = Counter::default();
         let knots_validated = Counter::default();
+        let storage_disk_bytes = Gauge::default();
+        let pruned_height = Gauge::default();
+        let validators_slashed = Counter::default();
+        let validators_jailed = Gauge::default();
+        let rpc_requests_rejected = Family::<RpcRejectLabels, Counter>::default();
+        let rpc_slow_requests = Counter::default();
 
         registry.register(
             "norn_weave_height",
@@ -36,6 +85,41 @@ impl NodeMetrics {
             "Number of connected peers",
             peer_count.clone(),
         );
+        registry.register(
+            "norn_peers_banned",
+            "Number of peers currently banned for misbehavior",
+            peers_banned.clone(),
+        );
+        registry.register(
+            "norn_peer_penalties",
+            "Total misbehavior penalties applied to peers",
+            peer_penalties.clone(),
+        );
+        registry.register(
+            "norn_relay_bytes_received",
+            "Total bytes received from peers",
+            relay_bytes_received.clone(),
+        );
+        registry.register(
+            "norn_relay_bytes_sent",
+            "Total bytes sent to peers",
+            relay_bytes_sent.clone(),
+        );
+        registry.register(
+            "norn_relay_messages_throttled",
+            "Total peer messages dropped for exceeding rate limits",
+            relay_messages_throttled.clone(),
+        );
+        registry.register(
+            "norn_relay_bytes_received_per_sec",
+            "Bytes per second currently received from peers",
+            relay_bytes_received_per_sec.clone(),
+        );
+        registry.register(
+            "norn_relay_bytes_sent_per_sec",
+            "Bytes per second currently sent to peers",
+            relay_bytes_sent_per_sec.clone(),
+        );
         registry.register(
             "norn_mempool_size",
             "Number of items in the mempool",
@@ -56,18 +140,68 @@ impl NodeMetrics {
             "Total knots validated",
             knots_validated.clone(),
         );
+        registry.register(
+            "norn_storage_disk_bytes",
+            "Disk space used by the node's data directory in bytes",
+            storage_disk_bytes.clone(),
+        );
+        registry.register(
+            "norn_pruned_height",
+            "Lowest block height whose history is retained",
+            pruned_height.clone(),
+        );
+        registry.register(
+            "norn_validators_slashed",
+            "Total validator slashings applied",
+            validators_slashed.clone(),
+        );
+        registry.register(
+            "norn_validators_jailed",
+            "Number of currently jailed validators",
+            validators_jailed.clone(),
+        );
+        registry.register(
+            "norn_rpc_requests_rejected",
+            "Total RPC requests rejected by rate, size or time limits",
+            rpc_requests_rejected.clone(),
+        );
+        registry.register(
+            "norn_rpc_slow_requests",
+            "Total RPC calls slower than the slow-query threshold",
+            rpc_slow_requests.clone(),
+        );
 
         Self {
             weave_height,
             peer_count,
+            peers_banned,
+            peer_penalties,
+            relay_bytes_received,
+            relay_bytes_sent,
+            relay_messages_throttled,
+            relay_bytes_received_per_sec,
+            relay_bytes_sent_per_sec,
             mempool_size,
             blocks_produced,
             fraud_proofs_submitted,
             knots_validated,
+            storage_disk_bytes,
+            pruned_height,
+            validators_slashed,
+            validators_jailed,
+            rpc_requests_rejected,
+            rpc_slow_requests,
             registry,
         }
     }
 
+    /// Count a request rejected by the RPC server.
+    pub fn rpc_rejected(&self, reason: RpcRejectReason) {
+        self.rpc_requests_rejected
+            .get_or_create(&RpcRejectLabels { reason })
+            .inc();
+    }
+
     /// Encode all metrics in Prometheus text exposition format.
     pub fn encode(&self) -> String {
         let mut buf = String::new();
@@ -98,9 +232,26 @@ mod tests {
         let encoded = metrics.encode();
         assert!(encoded.contains("norn_weave_height"));
         assert!(encoded.contains("norn_peer_count"));
+        assert!(encoded.contains("norn_peers_banned"));
+        assert!(encoded.contains("norn_peer_penalties"));
+        assert!(encoded.contains("norn_relay_bytes_received"));
+        assert!(encoded.contains("norn_relay_bytes_sent"));
+        assert!(encoded.contains("norn_relay_messages_throttled"));
         assert!(encoded.contains("norn_blocks_produced"));
     }
 
+    #[test]
+    fn test_rpc_rejections_labelled_by_reason() {
+        let metrics = NodeMetrics::new();
+        metrics.rpc_rejected(RpcRejectReason::IpRateLimited);
+        metrics.rpc_rejected(RpcRejectReason::IpRateLimited);
+        metrics.rpc_rejected(RpcRejectReason::Timeout);
+
+        let encoded = metrics.encode();
+        assert!(encoded.contains(r#"norn_rpc_requests_rejected_total{reason="IpRateLimited"} 2"#));
+        assert!(encoded.contains(r#"norn_rpc_requests_rejected_total{reason="Timeout"} 1"#));
+    }
+
     #[test]
     fn test_metrics_encode_format() {
         let metrics = NodeMetrics::new();
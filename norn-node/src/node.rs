@@ -1,28 +1,45 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
 use norn_crypto::address::pubkey_to_address;
+use norn_crypto::bls::BlsKeypair;
 use norn_crypto::keys::Keypair;
 use norn_loom::lifecycle::LoomManager;
-use norn_relay::config::RelayConfig;
+use norn_relay::bandwidth::RateLimits;
+use norn_relay::config::{NatConfig, RelayConfig};
+use norn_relay::handshake::Capabilities;
+use norn_relay::peer_manager::Misbehavior;
 use norn_relay::relay::{RelayHandle, RelayNode};
 use norn_relay::PeerId;
 use norn_spindle::service::SpindleService;
 use norn_storage::memory::MemoryStore;
 use norn_storage::traits::KvStore;
 use norn_storage::weave_store::WeaveStore;
-use norn_types::constants::BLOCK_TIME_TARGET;
-use norn_types::network::{NetworkId, NornMessage};
-use norn_types::primitives::Address;
+use norn_thread::proposal::KnotCollector;
+use norn_types::constants::{BLOCK_TIME_TARGET, DEFAULT_SPINDLE_REDUNDANCY};
+use norn_types::loom::{OperatorHandover, OperatorSet};
+use norn_types::network::{NetworkId, NornMessage, ThreadRange};
+use norn_types::primitives::{Address, Amount, LoomId, NATIVE_TOKEN_ID};
+use norn_types::snapshot::SnapshotManifest;
 use norn_types::weave::{BlockTransfer, FeeState, Validator, ValidatorSet, WeaveBlock, WeaveState};
 use norn_weave::engine::WeaveEngine;
 
+use crate::bridge::BridgeWatcher;
 use crate::config::NodeConfig;
 use crate::error::NodeError;
+use crate::health::NodeHealth;
 use crate::metrics::NodeMetrics;
+use crate::oracle::OracleReporter;
+use crate::pruner::Pruner;
+use crate::rpc::auth::RpcAuth;
+use crate::rpc::limits::RpcLimits;
+use crate::rpc::transport::RpcTransport;
+use crate::snapshot::{Snapshot, SnapshotEntries};
 use crate::state_manager::StateManager;
 
-/// Seconds without a committed block before triggering consensus timeout.
+/// Seconds without a committed block before triggering consensus timeout,
+/// before backoff.
 const CONSENSUS_TIMEOUT_SECS: u64 = 9; // 3x block time target (3s)
 
 /// The main node that ties together all subsystems.
@@ -37,6 +54,16 @@ pub struct Node {
     broadcasters: Option<crate::rpc::server::RpcBroadcasters>,
     loom_manager: Arc<RwLock<LoomManager>>,
     weave_store: WeaveStore<Arc<dyn KvStore>>,
+    store: Arc<dyn KvStore>,
+    pruner: Arc<Pruner>,
+    /// External chain bridge watcher, if configured.
+    bridge: Option<Arc<BridgeWatcher>>,
+    /// Oracle price reporter, if configured.
+    oracle: Option<Arc<OracleReporter>>,
+    /// Signs the manifests of snapshots this node takes.
+    snapshot_keypair: Keypair,
+    /// The latest state snapshot, served to fast-syncing peers.
+    latest_snapshot: Option<Snapshot>,
     relay: Option<RelayNode>,
     relay_rx: Option<tokio::sync::broadcast::Receiver<(NornMessage, Option<PeerId>)>>,
     relay_handle: Option<RelayHandle>,
@@ -44,35 +71,61 @@ pub struct Node {
     last_block_production_us: Arc<std::sync::Mutex<Option<u64>>>,
     /// Tracks when the last block was committed (for consensus timeout detection).
     last_committed_time: Arc<std::sync::Mutex<std::time::Instant>>,
+    /// Multi-party knots waiting for signatures, shared with the RPC server.
+    knot_collector: Arc<std::sync::Mutex<KnotCollector>>,
+    /// Liveness and readiness, shared with the RPC server.
+    health: Arc<NodeHealth>,
 }
 
 /// Create a storage backend from the node configuration.
-fn create_store(config: &NodeConfig) -> Result<Arc<dyn KvStore>, NodeError> {
+pub(crate) fn create_store(config: &NodeConfig) -> Result<Arc<dyn KvStore>, NodeError> {
     match config.storage.db_type.as_str() {
         "memory" => Ok(Arc::new(MemoryStore::new())),
         "sqlite" => {
             let data_dir = std::path::Path::new(&config.storage.data_dir);
             std::fs::create_dir_all(data_dir)?;
             let db_path = data_dir.join("norn.db");
-            let store =
-                norn_storage::sqlite::SqliteStore::new(db_path.to_str().unwrap_or("norn.db"))
-                    .map_err(NodeError::StorageError)?;
+            let store = norn_storage::sqlite::SqliteStore::with_compression(
+                db_path.to_str().unwrap_or("norn.db"),
+                config.storage.compression()?.compression,
+            )
+            .map_err(NodeError::StorageError)?;
+            if config.storage.recompress {
+                let rewritten = store.recompress().map_err(NodeError::StorageError)?;
+                tracing::info!(rewritten, "recompressed stored values");
+            }
             Ok(Arc::new(store))
         }
+        #[cfg(any())] //NOROCKS
         "rocksdb" => {
             let data_dir = std::path::Path::new(&config.storage.data_dir);
             std::fs::create_dir_all(data_dir)?;
             let db_path = data_dir.join("norn.rocksdb");
-            let store = norn_storage::rocksdb::RocksDbStore::new(
+            let store = norn_storage::rocksdb::RocksDbStore::with_compression(
                 db_path.to_str().unwrap_or("norn.rocksdb"),
                 None,
+                &config.storage.compression()?,
             )
             .map_err(NodeError::StorageError)?;
+            if config.storage.recompress {
+                store.recompress().map_err(NodeError::StorageError)?;
+                tracing::info!("recompressed column families");
+            }
+            Ok(Arc::new(store))
+        }
+        #[cfg(feature = "sled")]
+        "sled" => {
+            let data_dir = std::path::Path::new(&config.storage.data_dir);
+            std::fs::create_dir_all(data_dir)?;
+            let db_path = data_dir.join("norn.sled");
+            let store =
+                norn_storage::sled::SledStore::new(db_path.to_str().unwrap_or("norn.sled"))
+                    .map_err(NodeError::StorageError)?;
             Ok(Arc::new(store))
         }
         other => Err(NodeError::ConfigError {
             reason: format!(
-                "unknown storage backend '{}', expected 'memory', 'sqlite', or 'rocksdb'",
+                "unknown storage backend '{}', expected 'memory', 'sqlite', 'rocksdb', or 'sled' (built with the sled feature)",
                 other
             ),
         }),
@@ -83,21 +136,7 @@ impl Node {
     /// Create a new node from the given configuration.
     pub async fn new(config: NodeConfig) -> Result<Self, NodeError> {
         // Create or load the validator keypair.
-        let keypair = if let Some(ref seed_hex) = config.validator.keypair_seed {
-            let seed_bytes = hex::decode(seed_hex).map_err(|e| NodeError::ConfigError {
-                reason: format!("invalid keypair seed hex: {}", e),
-            })?;
-            if seed_bytes.len() != 32 {
-                return Err(NodeError::ConfigError {
-                    reason: format!("keypair seed must be 32 bytes, got {}", seed_bytes.len()),
-                });
-            }
-            let mut seed = [0u8; 32];
-            seed.copy_from_slice(&seed_bytes);
-            Keypair::from_seed(&seed)
-        } else {
-            Keypair::generate()
-        };
+        let keypair = validator_keypair(&config)?.unwrap_or_else(Keypair::generate);
 
         // Display the validator address for operators.
         if config.validator.enabled {
@@ -143,6 +182,7 @@ impl Node {
                 .iter()
                 .map(|gv| Validator {
                     pubkey: gv.pubkey,
+                    bls_pubkey: gv.bls_pubkey,
                     address: gv.address,
                     stake: gv.stake,
                     active: true,
@@ -153,6 +193,7 @@ impl Node {
                 let pubkey = keypair.public_key();
                 validators.push(Validator {
                     pubkey,
+                    bls_pubkey: BlsKeypair::from_keypair(&keypair).public_key(),
                     address: pubkey_to_address(&pubkey),
                     stake: 1_000_000_000_000,
                     active: true,
@@ -172,6 +213,7 @@ impl Node {
                 ValidatorSet {
                     validators: vec![Validator {
                         pubkey,
+                        bls_pubkey: BlsKeypair::from_keypair(&keypair).public_key(),
                         address: pubkey_to_address(&pubkey),
                         stake: 1_000_000_000_000,
                         active: true,
@@ -182,22 +224,16 @@ impl Node {
             } else {
                 ValidatorSet::new(0)
             };
-            let initial_state = WeaveState {
-                height: 0,
-                latest_hash: [0u8; 32],
-                threads_root: [0u8; 32],
-                thread_count: 0,
-                fee_state: FeeState {
-                    base_fee: 100,
-                    fee_multiplier: 1000,
-                    epoch_fees: 0,
-                },
-            };
-            (validator_set, initial_state)
+            (validator_set, default_weave_state())
         };
 
         // Initialize persistent storage.
+        let pruning_mode = config.storage.pruning_mode()?;
         let store = create_store(&config)?;
+        if config.storage.module_cache && config.storage.db_type != "memory" {
+            let cache_dir = std::path::Path::new(&config.storage.data_dir).join("module-cache");
+            norn_loom::runtime::set_module_cache_dir(Some(cache_dir));
+        }
         let weave_store = WeaveStore::new(store.clone());
 
         // Check schema version before reading any persisted data.
@@ -220,6 +256,7 @@ impl Node {
 
         // Create a spindle keypair from the same seed (before moving keypair into WeaveEngine).
         let spindle_keypair = Keypair::from_seed(&keypair.seed());
+        let snapshot_keypair = Keypair::from_seed(&keypair.seed());
 
         let weave_engine = Arc::new(RwLock::new(WeaveEngine::new(
             keypair,
@@ -229,22 +266,18 @@ impl Node {
 
         // Seed staking state from genesis validators.
         {
-            let bonding_period = genesis_config_opt
-                .as_ref()
-                .map(|gc| gc.parameters.bonding_period)
-                .unwrap_or(100);
-            let min_stake = genesis_config_opt
-                .as_ref()
-                .map(|gc| gc.parameters.min_validator_stake)
-                .unwrap_or(1000);
             let mut engine = weave_engine.write().await;
-            engine.seed_staking(&validator_set.validators, min_stake, bonding_period);
-            tracing::info!(
-                validators = validator_set.validators.len(),
-                min_stake = min_stake,
-                bonding_period = bonding_period,
-                "seeded staking from genesis"
+            seed_genesis_staking(
+                &mut engine,
+                &validator_set.validators,
+                genesis_config_opt.as_ref(),
             );
+            // A consensus validator must not forget its votes across restarts.
+            if config.validator.enabled && !config.validator.solo_mode {
+                engine
+                    .attach_consensus_wal(store.clone())
+                    .map_err(|e| NodeError::WeaveError(e.to_string()))?;
+            }
         }
 
         let metrics = Arc::new(NodeMetrics::new());
@@ -252,6 +285,16 @@ impl Node {
         // Initialize spindle watchtower service.
         let spindle = SpindleService::new(spindle_keypair);
 
+        let data_dir = (config.storage.db_type != "memory")
+            .then(|| std::path::PathBuf::from(&config.storage.data_dir));
+        let pruner = Arc::new(Pruner::new(
+            pruning_mode,
+            store.clone(),
+            data_dir,
+            metrics.clone(),
+        ));
+        tracing::info!(mode = %pruning_mode, "storage pruning configured");
+
         // Rebuild StateManager from persistent storage.
         let ss = crate::state_store::StateStore::new(store.clone());
         let mut sm = match ss.rebuild() {
@@ -267,169 +310,70 @@ impl Node {
         }
         sm.set_store(ss);
 
-        // Seed WeaveEngine with persisted names, name owners, and threads from StateManager.
+        // Seed WeaveEngine and LoomManager from the persisted state.
+        let persisted_operators = load_loom_operators(&sm);
         {
-            let names: Vec<String> = sm.registered_names().map(|s| s.to_string()).collect();
-            let name_owners: Vec<(String, Address)> = sm
-                .name_owners()
-                .map(|(n, addr)| (n.to_string(), addr))
-                .collect();
-            let threads: Vec<[u8; 20]> = sm.registered_thread_ids().copied().collect();
-            if !names.is_empty() || !threads.is_empty() {
-                tracing::info!(
-                    names = names.len(),
-                    threads = threads.len(),
-                    "seeding WeaveEngine with persisted state"
-                );
-                let mut engine = weave_engine.write().await;
-                engine.seed_known_state(names, name_owners, threads);
-            }
+            let mut engine = weave_engine.write().await;
+            seed_weave_engine(&mut engine, &sm, &persisted_operators);
         }
+        let loom_mgr = restore_loom_manager(&sm, &persisted_operators);
+        let loom_manager = Arc::new(RwLock::new(loom_mgr));
+        let state_manager = Arc::new(RwLock::new(sm));
 
-        // Seed WeaveEngine with persisted tokens from StateManager.
+        // Initialize the relay if networking is configured (before RPC, so handle is available).
+        let dns_seeds = config.network.resolved_dns_seeds(&config.network_id);
+        let (relay, relay_rx, relay_handle) = if !config.network.boot_nodes.is_empty()
+            || !dns_seeds.is_empty()
+            || config.network.listen_addr != "0.0.0.0:0"
         {
-            let tokens: Vec<_> = sm
-                .registered_tokens()
-                .map(|(id, rec)| {
-                    (
-                        *id,
-                        norn_weave::token::TokenMeta {
-                            name: rec.name.clone(),
-                            symbol: rec.symbol.clone(),
-                            decimals: rec.decimals,
-                            max_supply: rec.max_supply,
-                            current_supply: rec.current_supply,
-                            creator: rec.creator,
-                            created_at: rec.created_at,
-                        },
-                    )
-                })
+            let listen_addr = config
+                .network
+                .listen_addr
+                .parse()
+                .unwrap_or_else(|_| "0.0.0.0:9740".parse().unwrap());
+            let boot_nodes = config
+                .network
+                .boot_nodes
+                .iter()
+                .filter_map(|s| s.parse().ok())
                 .collect();
-            if !tokens.is_empty() {
-                tracing::info!(
-                    tokens = tokens.len(),
-                    "seeding WeaveEngine with persisted tokens"
-                );
-                let mut engine = weave_engine.write().await;
-                engine.seed_known_tokens(tokens);
-            }
-        }
-
-        // Seed WeaveEngine with persisted looms from StateManager.
-        {
-            let loom_ids: Vec<_> = sm.registered_looms().copied().collect();
-            if !loom_ids.is_empty() {
-                tracing::info!(
-                    looms = loom_ids.len(),
-                    "seeding WeaveEngine with persisted looms"
-                );
-                let mut engine = weave_engine.write().await;
-                engine.seed_known_looms(loom_ids);
-            }
-        }
-
-        // Initialize LoomManager and restore persisted bytecodes/states.
-        let mut loom_mgr = LoomManager::new();
-        {
-            // Register loom metadata from StateManager so LoomManager knows about them.
-            let sm_ref = &sm;
-            for (loom_id, record) in sm_ref.list_looms() {
-                let loom = norn_types::loom::Loom {
-                    config: norn_types::loom::LoomConfig {
-                        loom_id: *loom_id,
-                        name: record.name.clone(),
-                        max_participants: 1000,
-                        min_participants: 1,
-                        accepted_tokens: vec![norn_types::primitives::NATIVE_TOKEN_ID],
-                        config_data: vec![],
-                    },
-                    operator: record.operator,
-                    participants: Vec::new(),
-                    state_hash: [0u8; 32],
-                    version: 0,
-                    active: record.active,
-                    last_updated: record.deployed_at,
-                };
-                loom_mgr.register_loom(*loom_id, loom);
-            }
-
-            // Restore bytecodes and states from persistent storage.
-            let (bytecodes, loom_states) = if let Some(store) = sm.store() {
-                (
-                    store.load_all_loom_bytecodes().unwrap_or_default(),
-                    store.load_all_loom_states().unwrap_or_default(),
-                )
-            } else {
-                (vec![], vec![])
+            let relay_config = RelayConfig {
+                listen_addr,
+                boot_nodes,
+                dns_seeds,
+                network_id: config.network_id.clone(),
+                genesis_hash,
+                capabilities: relay_capabilities(&config),
+                max_connections: config.network.max_connections,
+                keypair_seed: None,
+                data_dir: (config.storage.db_type != "memory")
+                    .then(|| std::path::PathBuf::from(&config.storage.data_dir)),
+                nat: NatConfig {
+                    autonat: config.network.autonat,
+                    relay_server: config.network.relay_server,
+                    relays: config.network.relays.clone(),
+                    hole_punching: config.network.hole_punching,
+                },
+                rate_limits: RateLimits {
+                    inbound_bytes_per_sec: config.network.peer_inbound_bytes_per_sec,
+                    inbound_messages_per_sec: config.network.peer_inbound_messages_per_sec,
+                    outbound_bytes_per_sec: config.network.peer_outbound_bytes_per_sec,
+                },
             };
-            let state_map: std::collections::HashMap<[u8; 32], Vec<u8>> =
-                loom_states.into_iter().collect();
-
-            for (loom_id, bytecode_bytes) in &bytecodes {
-                let wasm_hash = norn_crypto::hash::blake3_hash(bytecode_bytes);
-                let loom_bytecode = norn_types::loom::LoomBytecode {
-                    loom_id: *loom_id,
-                    wasm_hash,
-                    bytecode: bytecode_bytes.clone(),
-                };
-                // Restore state data if available.
-                let state_data: std::collections::HashMap<Vec<u8>, Vec<u8>> =
-                    if let Some(state_bytes) = state_map.get(loom_id) {
-                        borsh::from_slice(state_bytes).unwrap_or_default()
-                    } else {
-                        std::collections::HashMap::new()
-                    };
-                // If loom was registered, use restore_loom to overwrite with full data.
-                if let Some(loom) = loom_mgr.get_loom(loom_id).cloned() {
-                    loom_mgr.restore_loom(*loom_id, loom, loom_bytecode, state_data);
+            match RelayNode::new(relay_config).await {
+                Ok(relay_node) => {
+                    let rx = relay_node.subscribe();
+                    let handle = relay_node.handle();
+                    (Some(relay_node), Some(rx), Some(handle))
                 }
-            }
-
-            if !bytecodes.is_empty() {
-                tracing::info!(
-                    looms_with_bytecode = bytecodes.len(),
-                    "restored loom bytecodes from disk"
-                );
-            }
-        }
-
-        let loom_manager = Arc::new(RwLock::new(loom_mgr));
-        let state_manager = Arc::new(RwLock::new(sm));
-
-        // Initialize the relay if networking is configured (before RPC, so handle is available).
-        let (relay, relay_rx, relay_handle) =
-            if !config.network.boot_nodes.is_empty() || config.network.listen_addr != "0.0.0.0:0" {
-                let listen_addr = config
-                    .network
-                    .listen_addr
-                    .parse()
-                    .unwrap_or_else(|_| "0.0.0.0:9740".parse().unwrap());
-                let boot_nodes = config
-                    .network
-                    .boot_nodes
-                    .iter()
-                    .filter_map(|s| s.parse().ok())
-                    .collect();
-                let relay_config = RelayConfig {
-                    listen_addr,
-                    boot_nodes,
-                    max_connections: config.network.max_connections,
-                    keypair_seed: None,
-                };
-                match RelayNode::new(relay_config).await {
-                    Ok(relay_node) => {
-                        let rx = relay_node.subscribe();
-                        let handle = relay_node.handle();
-                        (Some(relay_node), Some(rx), Some(handle))
-                    }
-                    Err(e) => {
-                        tracing::warn!("Failed to initialize relay: {}", e);
-                        (None, None, None)
-                    }
+                Err(e) => {
+                    tracing::warn!("Failed to initialize relay: {}", e);
+                    (None, None, None)
                 }
-            } else {
-                (None, None, None)
-            };
+            }
+        } else {
+            (None, None, None)
+        };
 
         // Parse network ID.
         let network_id = NetworkId::parse(&config.network_id).unwrap_or(NetworkId::Dev);
@@ -444,28 +388,7 @@ impl Node {
                     .iter()
                     .any(|alloc| sm.is_registered(&alloc.address));
                 if !already_applied {
-                    // Register zero address so synthetic transfers from it succeed.
-                    sm.auto_register_if_needed([0u8; 20]);
-                    for alloc in &gc.allocations {
-                        sm.auto_register_if_needed(alloc.address);
-                        if let Err(e) = sm.credit(alloc.address, alloc.token_id, alloc.amount) {
-                            tracing::warn!(
-                                "Failed to process genesis allocation for {}: {}",
-                                hex::encode(alloc.address),
-                                e
-                            );
-                        } else {
-                            // Log genesis allocation as a synthetic transfer.
-                            sm.log_synthetic_transfer(
-                                [0u8; 20],
-                                alloc.address,
-                                alloc.token_id,
-                                alloc.amount,
-                                Some("Genesis allocation"),
-                                gc.timestamp,
-                            );
-                        }
-                    }
+                    credit_genesis_allocations(&mut sm, gc);
                     if !gc.allocations.is_empty() {
                         tracing::info!(
                             count = gc.allocations.len(),
@@ -480,23 +403,7 @@ impl Node {
         if let Some(ref gc) = genesis_config_opt {
             if !gc.name_registrations.is_empty() {
                 let mut sm = state_manager.write().await;
-                let mut registered = 0u32;
-                for gnr in &gc.name_registrations {
-                    if sm.resolve_name(&gnr.name).is_none() {
-                        sm.auto_register_if_needed(gnr.owner);
-                        if let Err(e) = sm.apply_peer_name_registration(
-                            &gnr.name,
-                            gnr.owner,
-                            [0u8; 32],
-                            gc.timestamp,
-                            0,
-                        ) {
-                            tracing::warn!("failed to register genesis name '{}': {}", gnr.name, e);
-                        } else {
-                            registered += 1;
-                        }
-                    }
-                }
+                let registered = register_genesis_names(&mut sm, gc);
                 if registered > 0 {
                     // Also seed WeaveEngine so it knows about genesis names and owners.
                     let names: Vec<String> = gc
@@ -529,6 +436,8 @@ impl Node {
 
         // Shared state for block production timing (node tick loop → RPC health).
         let last_block_production_us = Arc::new(std::sync::Mutex::new(None));
+        let knot_collector = Arc::new(std::sync::Mutex::new(KnotCollector::new()));
+        let health = Arc::new(NodeHealth::new(config.health.clone()));
 
         // Start the RPC server if enabled.
         let (rpc_handle, broadcasters) = if config.rpc.enabled {
@@ -541,8 +450,15 @@ impl Node {
                 relay_handle.clone(),
                 network_id,
                 config.validator.enabled,
-                config.rpc.api_key.clone(),
+                RpcAuth::from_config(&config.rpc)?,
+                RpcLimits::from_config(&config.rpc)?,
+                RpcTransport::from_config(&config.rpc)?,
+                config.rpc.rest_gateway,
+                config.rpc.graphql,
+                config.rpc.grpc_listen_addr.as_deref(),
                 last_block_production_us.clone(),
+                knot_collector.clone(),
+                health.clone(),
             )
             .await?;
             (Some(handle), Some(bc))
@@ -550,6 +466,57 @@ impl Node {
             (None, None)
         };
 
+        // The bridge watcher submits attestations through the local RPC server.
+        let bridge = match config.bridge {
+            Some(ref bridge_config) => {
+                if !config.rpc.enabled {
+                    return Err(NodeError::ConfigError {
+                        reason: "the bridge watcher requires rpc.enabled".to_string(),
+                    });
+                }
+                if config.rpc.tls_cert_path.is_some() {
+                    return Err(NodeError::ConfigError {
+                        reason: "the bridge watcher calls the local RPC server over plain HTTP, \
+                                 so rpc TLS must be off"
+                            .to_string(),
+                    });
+                }
+                let watcher = BridgeWatcher::new(
+                    bridge_config,
+                    &format!("http://{}", config.rpc.listen_addr),
+                    config.rpc.api_key.as_deref(),
+                    store.clone(),
+                )?;
+                Some(Arc::new(watcher))
+            }
+            None => None,
+        };
+
+        // So does the oracle reporter with its price reports.
+        let oracle = match config.oracle {
+            Some(ref oracle_config) => {
+                if !config.rpc.enabled {
+                    return Err(NodeError::ConfigError {
+                        reason: "the oracle reporter requires rpc.enabled".to_string(),
+                    });
+                }
+                if config.rpc.tls_cert_path.is_some() {
+                    return Err(NodeError::ConfigError {
+                        reason: "the oracle reporter calls the local RPC server over plain HTTP, \
+                                 so rpc TLS must be off"
+                            .to_string(),
+                    });
+                }
+                let reporter = OracleReporter::new(
+                    oracle_config,
+                    &format!("http://{}", config.rpc.listen_addr),
+                    config.rpc.api_key.as_deref(),
+                )?;
+                Some(Arc::new(reporter))
+            }
+            None => None,
+        };
+
         tracing::info!(
             listen = %config.network.listen_addr,
             rpc_enabled = config.rpc.enabled,
@@ -568,12 +535,20 @@ impl Node {
             rpc_handle,
             broadcasters,
             weave_store,
+            store,
+            pruner,
+            bridge,
+            oracle,
+            snapshot_keypair,
+            latest_snapshot: None,
             relay,
             relay_rx,
             relay_handle,
             spindle,
             last_block_production_us,
             last_committed_time: Arc::new(std::sync::Mutex::new(std::time::Instant::now())),
+            knot_collector,
+            health,
         })
     }
 
@@ -599,11 +574,23 @@ impl Node {
             peer_count = peers.len(),
             "Requesting state sync from peers..."
         );
+        let _syncing = self.health.start_sync();
 
         let our_genesis_hash = self.genesis_hash;
         let mut current_height: u64 = 0;
         let mut total_synced: u64 = 0;
 
+        // A fresh node can skip replaying history by installing a snapshot,
+        // then block-sync from the snapshot's anchor block onwards.
+        if self.config.network.fast_sync {
+            let local_height = self.state_manager.read().await.latest_block_height();
+            if local_height == 0 {
+                if let Some(height) = self.fast_sync(&handle, &peers).await {
+                    current_height = height;
+                }
+            }
+        }
+
         loop {
             let request = NornMessage::StateRequest {
                 current_height,
@@ -649,6 +636,7 @@ impl Node {
                                     );
                                     continue;
                                 }
+                                self.health.observe_network_height(tip_height);
                                 return Some((blocks, tip_height));
                             }
                             Ok(_) => continue,
@@ -707,6 +695,16 @@ impl Node {
                         if block.height > max_height {
                             max_height = block.height;
                         }
+                        // The engine checks fees against the state before the block.
+                        {
+                            let mut engine = self.weave_engine.write().await;
+                            let sm = self.state_manager.read().await;
+                            engine.set_timestamp(current_timestamp());
+                            let _ = engine.on_network_message(
+                                NornMessage::Block(Box::new(block.clone())),
+                                &*sm,
+                            );
+                        }
                         {
                             let mut sm = self.state_manager.write().await;
                             for reg in &block.registrations {
@@ -797,8 +795,20 @@ impl Node {
                                         loom_id,
                                         crate::loom_from_registration(ld, loom_id),
                                     );
+                                    sm.persist_loom_operators(
+                                        &loom_id,
+                                        &OperatorSet::from_config(&ld.operator, &ld.config),
+                                    );
                                 }
                             }
+                            if !block.operator_handovers.is_empty() {
+                                let mut loom_mgr = self.loom_manager.write().await;
+                                apply_operator_handovers(
+                                    &mut sm,
+                                    &mut loom_mgr,
+                                    &block.operator_handovers,
+                                );
+                            }
                             for bt in &block.transfers {
                                 if !sm.has_transfer(&bt.knot_id) {
                                     sm.auto_register_if_needed(bt.from);
@@ -825,11 +835,12 @@ impl Node {
                                     commit.knot_count,
                                 );
                             }
-                            sm.archive_block(block.clone(), None);
+                            sm.apply_stale_commit_proofs(&block);
+                            sm.apply_spindle_market(&block);
+                            sm.apply_key_updates(&block);
+                            sm.apply_token_metadata_updates(&block);
+                            sm.archive_block(block, None);
                         }
-                        let mut engine = self.weave_engine.write().await;
-                        engine.set_timestamp(current_timestamp());
-                        let _ = engine.on_network_message(NornMessage::Block(Box::new(block)));
                     }
 
                     total_synced += batch_size;
@@ -857,11 +868,221 @@ impl Node {
         }
     }
 
+    /// Fast sync: fetch the newest state snapshot offered by peers, verify it
+    /// against its anchor block, and install it. Returns the height to resume
+    /// block sync from, or `None` to fall back to replaying from genesis.
+    async fn fast_sync(&mut self, handle: &RelayHandle, peers: &[PeerId]) -> Option<u64> {
+        let request = NornMessage::SnapshotRequest {
+            genesis_hash: self.genesis_hash,
+            nonce: rand::random::<u64>(),
+        };
+        for peer_id in peers {
+            if let Err(e) = handle.send_to_peer(*peer_id, request.clone()).await {
+                tracing::warn!(%peer_id, "Failed to send snapshot request: {:?}", e);
+            }
+        }
+
+        // Collect offers for a few seconds. Validators snapshotting the same
+        // state produce the same manifest, so their signatures are merged.
+        let mut offers: Vec<(SnapshotManifest, WeaveBlock, Vec<PeerId>)> = Vec::new();
+        {
+            let rx = self.relay_rx.as_mut()?;
+            let deadline = tokio::time::Instant::now() + std::time::Duration::from_secs(5);
+            loop {
+                let (manifest, block, genesis_hash, source) =
+                    match tokio::time::timeout_at(deadline, rx.recv()).await {
+                        Ok(Ok((
+                            NornMessage::SnapshotOffer {
+                                manifest,
+                                block,
+                                genesis_hash,
+                            },
+                            source,
+                        ))) => (manifest, block, genesis_hash, source),
+                        Ok(Ok(_)) => continue,
+                        Ok(Err(_)) | Err(_) => break,
+                    };
+                if self.genesis_hash != [0u8; 32]
+                    && genesis_hash != [0u8; 32]
+                    && genesis_hash != self.genesis_hash
+                {
+                    tracing::warn!("rejecting snapshot offer: genesis hash mismatch");
+                    continue;
+                }
+                match offers.iter_mut().position(|(existing, _, _)| {
+                    crate::snapshot::merge_signatures(existing, &manifest)
+                }) {
+                    Some(index) => offers[index].2.extend(source),
+                    None => offers.push((*manifest, *block, source.into_iter().collect())),
+                }
+            }
+        }
+        if offers.is_empty() {
+            tracing::info!("Fast sync: no snapshot offered by peers, replaying from genesis");
+            return None;
+        }
+        offers.sort_by_key(|(manifest, _, _)| std::cmp::Reverse(manifest.height));
+
+        let validator_set = self.weave_engine.read().await.staking().active_validators();
+        for (manifest, block, sources) in offers {
+            if let Err(e) = crate::snapshot::verify_manifest(&manifest, &block, &validator_set) {
+                tracing::warn!(height = manifest.height, "rejecting snapshot: {}", e);
+                continue;
+            }
+            let sources = if sources.is_empty() { peers } else { &sources };
+            let Some(chunks) = self.fetch_snapshot_chunks(handle, &manifest, sources).await else {
+                continue;
+            };
+            let installed = crate::snapshot::assemble(&manifest, &chunks).and_then(|entries| {
+                crate::snapshot::verify_state(&entries, &manifest)?;
+                Ok(entries)
+            });
+            let installed = match installed {
+                Ok(entries) => self.install_snapshot(&entries, &manifest).await,
+                Err(e) => Err(e),
+            };
+            match installed {
+                Ok(()) => {
+                    tracing::info!(
+                        height = manifest.height,
+                        chunks = manifest.chunk_hashes.len(),
+                        state_root = %hex::encode(manifest.state_root),
+                        "installed state snapshot"
+                    );
+                    return Some(manifest.height - 1);
+                }
+                Err(e) => {
+                    tracing::warn!(height = manifest.height, "snapshot sync failed: {}", e);
+                }
+            }
+        }
+        None
+    }
+
+    /// Download every chunk of a snapshot, asking the given peers in turn.
+    async fn fetch_snapshot_chunks(
+        &mut self,
+        handle: &RelayHandle,
+        manifest: &SnapshotManifest,
+        sources: &[PeerId],
+    ) -> Option<Vec<Vec<u8>>> {
+        let mut chunks = Vec::with_capacity(manifest.chunk_hashes.len());
+        for (index, chunk_hash) in manifest.chunk_hashes.iter().enumerate() {
+            let mut chunk = None;
+            // Start at a different peer for each chunk to spread the load.
+            for peer_id in sources.iter().cycle().skip(index).take(sources.len()) {
+                let request = NornMessage::SnapshotChunkRequest {
+                    chunk_hash: *chunk_hash,
+                };
+                if let Err(e) = handle.send_to_peer(*peer_id, request).await {
+                    tracing::warn!(%peer_id, "Failed to send snapshot chunk request: {:?}", e);
+                    continue;
+                }
+                let rx = self.relay_rx.as_mut()?;
+                let received = tokio::time::timeout(std::time::Duration::from_secs(10), async {
+                    loop {
+                        match rx.recv().await {
+                            Ok((
+                                NornMessage::SnapshotChunk {
+                                    chunk_hash: h,
+                                    data,
+                                },
+                                _,
+                            )) if h == *chunk_hash
+                                && norn_crypto::hash::blake3_hash(&data) == *chunk_hash =>
+                            {
+                                return Some(data);
+                            }
+                            Ok(_) => continue,
+                            Err(_) => return None,
+                        }
+                    }
+                })
+                .await;
+                if let Ok(Some(data)) = received {
+                    chunk = Some(data);
+                    break;
+                }
+            }
+            let Some(chunk) = chunk else {
+                tracing::warn!(
+                    height = manifest.height,
+                    chunk = index,
+                    "no peer returned snapshot chunk"
+                );
+                return None;
+            };
+            chunks.push(chunk);
+            tracing::debug!(
+                fetched = index + 1,
+                total = manifest.chunk_hashes.len(),
+                "fetched snapshot chunk"
+            );
+        }
+        Some(chunks)
+    }
+
+    /// Replace local state with verified snapshot entries and reseed the
+    /// WeaveEngine and LoomManager from it.
+    async fn install_snapshot(
+        &mut self,
+        entries: &SnapshotEntries,
+        manifest: &SnapshotManifest,
+    ) -> Result<(), NodeError> {
+        let ss = crate::state_store::StateStore::new(self.store.clone());
+        ss.import_snapshot(entries, manifest.height)?;
+        let mut sm = ss.rebuild()?;
+        sm.set_store(ss);
+        // The anchor block is applied next, through block sync.
+        if let Some(store) = sm.store() {
+            store.set_version_height(manifest.height);
+        }
+
+        let persisted_operators = load_loom_operators(&sm);
+        {
+            let mut engine = self.weave_engine.write().await;
+            seed_weave_engine(&mut engine, &sm, &persisted_operators);
+        }
+        let loom_mgr = restore_loom_manager(&sm, &persisted_operators);
+        let mut state_manager = self.state_manager.write().await;
+        let mut loom_manager = self.loom_manager.write().await;
+        *state_manager = sm;
+        *loom_manager = loom_mgr;
+        Ok(())
+    }
+
     /// Run the main node event loop.
     pub async fn run(&mut self) -> Result<(), NodeError> {
         let block_interval = tokio::time::interval(BLOCK_TIME_TARGET);
         tokio::pin!(block_interval);
 
+        // Prune history and track disk usage in the background.
+        let pruning_task = tokio::spawn(self.pruner.clone().run(std::time::Duration::from_secs(
+            self.config.storage.pruning_interval_secs.max(1),
+        )));
+
+        // Relay bridge locks and burns in the background.
+        let bridge_task = self.bridge.clone().map(|watcher| {
+            let interval = self
+                .config
+                .bridge
+                .as_ref()
+                .map_or(15, |b| b.poll_interval_secs)
+                .max(1);
+            tokio::spawn(watcher.run(std::time::Duration::from_secs(interval)))
+        });
+
+        // Push oracle price reports in the background.
+        let oracle_task = self.oracle.clone().map(|reporter| {
+            let interval = self
+                .config
+                .oracle
+                .as_ref()
+                .map_or(60, |o| o.poll_interval_secs)
+                .max(1);
+            tokio::spawn(reporter.run(std::time::Duration::from_secs(interval)))
+        });
+
         // Spawn relay run loop in background if available.
         let relay_handle = self.relay.take().map(|mut relay| {
             tokio::spawn(async move {
@@ -901,10 +1122,42 @@ impl Node {
             if let Some(ref mut rx) = self.relay_rx {
                 while let Ok((msg, source_peer)) = rx.try_recv() {
                     match msg {
-                        NornMessage::KnotProposal(ref knot) => {
-                            // Feed to spindle watchtower for fraud detection.
+                        NornMessage::KnotProposal(ref knot)
+                        | NornMessage::KnotResponse(ref knot) => {
                             let timestamp = current_timestamp();
-                            let fraud_msgs = self.spindle.on_message(&msg, timestamp);
+                            // A multi-party knot still missing signatures waits in
+                            // the collector; it is applied once the last one arrives.
+                            let completed = if norn_thread::proposal::is_partially_signed(knot) {
+                                let collected = self
+                                    .knot_collector
+                                    .lock()
+                                    .unwrap_or_else(|e| e.into_inner())
+                                    .collect(knot, timestamp);
+                                match collected.and_then(|pending| {
+                                    if pending.is_complete() {
+                                        pending.into_knot().map(Some)
+                                    } else {
+                                        Ok(None)
+                                    }
+                                }) {
+                                    Ok(Some(k)) => Some(NornMessage::KnotProposal(Box::new(k))),
+                                    Ok(None) => continue,
+                                    Err(e) => {
+                                        tracing::debug!("P2P knot proposal rejected: {}", e);
+                                        continue;
+                                    }
+                                }
+                            } else {
+                                None
+                            };
+                            let msg = completed.as_ref().unwrap_or(&msg);
+                            let knot = match msg {
+                                NornMessage::KnotProposal(k) | NornMessage::KnotResponse(k) => k,
+                                _ => continue,
+                            };
+
+                            // Feed to spindle watchtower for fraud detection.
+                            let fraud_msgs = self.spindle.on_message(msg, timestamp);
                             for fraud_msg in fraud_msgs {
                                 if let Some(ref handle) = self.relay_handle {
                                     let h = handle.clone();
@@ -913,65 +1166,130 @@ impl Node {
                                     });
                                 }
                             }
+                            if norn_thread::rotation::is_key_update(knot) {
+                                if let Err(e) =
+                                    norn_thread::validation::validate_knot_window(knot, timestamp)
+                                {
+                                    tracing::debug!("P2P key update rejected: {}", e);
+                                    continue;
+                                }
+                                let mut sm = self.state_manager.write().await;
+                                let applied =
+                                    norn_thread::validation::validate_knot_sequence(knot, |tid| {
+                                        sm.next_knot_sequence(tid)
+                                    })
+                                    .and_then(|()| sm.apply_key_update(knot));
+                                if let Err(e) = applied {
+                                    tracing::debug!("P2P key update rejected: {}", e);
+                                    continue;
+                                }
+                                sm.advance_knot_sequence(knot);
+                                drop(sm);
+                                let mut engine = self.weave_engine.write().await;
+                                if let Err(e) = engine.add_key_update(knot.as_ref().clone()) {
+                                    tracing::debug!("P2P key update not queued: {}", e);
+                                }
+                                continue;
+                            }
                             // Validate and apply incoming knot from the network.
-                            if let norn_types::knot::KnotPayload::Transfer(ref transfer) =
+                            let legs = norn_thread::knot::transfer_legs(knot);
+                            if legs.is_empty() || knot.before_states.is_empty() {
+                                continue;
+                            }
+                            if let norn_types::knot::KnotPayload::MultiTransfer(ref multi) =
                                 knot.payload
                             {
-                                if !knot.signatures.is_empty() && !knot.before_states.is_empty() {
-                                    let sender_pubkey = knot.before_states[0].pubkey;
-                                    if norn_crypto::keys::verify(
-                                        &knot.id,
-                                        &knot.signatures[0],
-                                        &sender_pubkey,
+                                if let Err(e) =
+                                    norn_thread::validation::validate_multi_transfer_payload(multi)
+                                {
+                                    tracing::warn!("P2P knot: invalid multi-transfer: {}", e);
+                                    continue;
+                                }
+                            }
+                            if norn_thread::validation::validate_rule_1_signatures(knot).is_err() {
+                                continue;
+                            }
+                            // Replay protection: drop expired knots and knots
+                            // behind a participant's knot sequence.
+                            if let Err(e) =
+                                norn_thread::validation::validate_knot_window(knot, timestamp)
+                            {
+                                tracing::debug!("P2P knot rejected: {}", e);
+                                continue;
+                            }
+                            // Every transfer must be sent by a participant thread.
+                            if let Err(e) = norn_thread::validation::validate_transfer_senders(knot)
+                            {
+                                tracing::warn!("P2P knot: {}", e);
+                                continue;
+                            }
+                            let mut sm = self.state_manager.write().await;
+                            if let Err(e) =
+                                norn_thread::validation::validate_knot_sequence(knot, |tid| {
+                                    sm.next_knot_sequence(tid)
+                                })
+                            {
+                                tracing::debug!("P2P knot rejected: {}", e);
+                                continue;
+                            }
+                            // Each participant must sign with its thread's
+                            // current owner key.
+                            if knot
+                                .before_states
+                                .iter()
+                                .any(|p| !sm.is_thread_owner(&p.thread_id, &p.pubkey))
+                            {
+                                tracing::warn!("P2P knot: key does not control its thread");
+                                continue;
+                            }
+                            if let Err(e) = sm.check_cosignatures(knot) {
+                                tracing::warn!("P2P knot rejected: {}", e);
+                                continue;
+                            }
+                            for participant in &knot.before_states {
+                                sm.auto_register_with_pubkey(
+                                    participant.thread_id,
+                                    participant.pubkey,
+                                );
+                            }
+                            let mut applied = Vec::new();
+                            for (leg_id, transfer) in &legs {
+                                // Dedup: skip if already applied (via RPC or prior gossip).
+                                if sm.has_transfer(leg_id) {
+                                    continue;
+                                }
+                                sm.auto_register_if_needed(transfer.to);
+                                if sm
+                                    .apply_peer_transfer(
+                                        transfer.from,
+                                        transfer.to,
+                                        transfer.token_id,
+                                        transfer.amount,
+                                        *leg_id,
+                                        transfer.memo.clone(),
+                                        knot.timestamp,
                                     )
                                     .is_ok()
-                                    {
-                                        // Verify pubkey derives the claimed sender address.
-                                        if norn_crypto::address::pubkey_to_address(&sender_pubkey)
-                                            != transfer.from
-                                        {
-                                            tracing::warn!(
-                                                "P2P knot: pubkey does not derive from address {}",
-                                                hex::encode(transfer.from),
-                                            );
-                                            continue;
-                                        }
-                                        let mut sm = self.state_manager.write().await;
-                                        // Dedup: skip if already applied (via RPC or prior gossip).
-                                        if sm.has_transfer(&knot.id) {
-                                            drop(sm);
-                                            continue;
-                                        }
-                                        sm.auto_register_with_pubkey(transfer.from, sender_pubkey);
-                                        sm.auto_register_if_needed(transfer.to);
-                                        let applied = sm
-                                            .apply_peer_transfer(
-                                                transfer.from,
-                                                transfer.to,
-                                                transfer.token_id,
-                                                transfer.amount,
-                                                knot.id,
-                                                transfer.memo.clone(),
-                                                knot.timestamp,
-                                            )
-                                            .is_ok();
-                                        drop(sm);
-
-                                        // Queue for block inclusion so peers can sync.
-                                        if applied {
-                                            let bt = BlockTransfer {
-                                                from: transfer.from,
-                                                to: transfer.to,
-                                                token_id: transfer.token_id,
-                                                amount: transfer.amount,
-                                                memo: transfer.memo.clone(),
-                                                knot_id: knot.id,
-                                                timestamp: knot.timestamp,
-                                            };
-                                            let mut engine = self.weave_engine.write().await;
-                                            let _ = engine.add_transfer(bt);
-                                        }
-                                    }
+                                {
+                                    applied.push(BlockTransfer {
+                                        from: transfer.from,
+                                        to: transfer.to,
+                                        token_id: transfer.token_id,
+                                        amount: transfer.amount,
+                                        memo: transfer.memo.clone(),
+                                        knot_id: *leg_id,
+                                        timestamp: knot.timestamp,
+                                    });
+                                }
+                            }
+                            sm.advance_knot_sequence(knot);
+                            drop(sm);
+
+                            // Queue for block inclusion so peers can sync.
+                            if !applied.is_empty() {
+                                let mut engine = self.weave_engine.write().await;
+                                for bt in applied {
+                                    let _ = engine.add_transfer(bt);
                                 }
                             }
                         }
@@ -988,6 +1306,17 @@ impl Node {
                                 );
                                 continue;
                             }
+                            // Watch for validators signing conflicting blocks and
+                            // for stale commitments to watched threads.
+                            let evidence_msgs = self.spindle.on_block(&block, current_timestamp());
+                            for evidence_msg in evidence_msgs {
+                                if let Some(ref handle) = self.relay_handle {
+                                    let h = handle.clone();
+                                    tokio::spawn(async move {
+                                        let _ = h.broadcast(evidence_msg).await;
+                                    });
+                                }
+                            }
                             // Verify block integrity before applying to state.
                             {
                                 let engine = self.weave_engine.read().await;
@@ -1003,6 +1332,35 @@ impl Node {
                                     continue;
                                 }
                             }
+                            // Reject blocks that include commitments their threads
+                            // cannot pay for.
+                            {
+                                let sm = self.state_manager.read().await;
+                                let unfunded = norn_weave::fees::unfunded_commitments(
+                                    &block.commitments,
+                                    block.base_fee,
+                                    &*sm,
+                                );
+                                if !unfunded.is_empty() {
+                                    tracing::warn!(
+                                        height = block.height,
+                                        unfunded = unfunded.len(),
+                                        "rejecting peer block: commitments cannot pay their fees"
+                                    );
+                                    continue;
+                                }
+                            }
+                            // Forward to WeaveEngine while the state still holds the
+                            // balances the block's fees are checked against.
+                            let committed = {
+                                let mut engine = self.weave_engine.write().await;
+                                let sm = self.state_manager.read().await;
+                                engine.set_timestamp(current_timestamp());
+                                let _responses = engine
+                                    .on_network_message(NornMessage::Block(block.clone()), &*sm);
+                                engine.weave_state().height == block.height
+                            };
+                            self.health.observe_network_height(block.height);
                             // Apply block contents to StateManager.
                             {
                                 let mut sm = self.state_manager.write().await;
@@ -1095,8 +1453,20 @@ impl Node {
                                             loom_id,
                                             crate::loom_from_registration(ld, loom_id),
                                         );
+                                        sm.persist_loom_operators(
+                                            &loom_id,
+                                            &OperatorSet::from_config(&ld.operator, &ld.config),
+                                        );
                                     }
                                 }
+                                if !block.operator_handovers.is_empty() {
+                                    let mut loom_mgr = self.loom_manager.write().await;
+                                    apply_operator_handovers(
+                                        &mut sm,
+                                        &mut loom_mgr,
+                                        &block.operator_handovers,
+                                    );
+                                }
                                 for bt in &block.transfers {
                                     if !sm.has_transfer(&bt.knot_id) {
                                         sm.auto_register_if_needed(bt.from);
@@ -1123,13 +1493,20 @@ impl Node {
                                         commit.knot_count,
                                     );
                                 }
+                                sm.apply_commitment_fees(&block);
+                                sm.apply_stale_commit_proofs(&block);
+                                sm.apply_spindle_market(&block);
+                                sm.apply_key_updates(&block);
+                                sm.apply_token_metadata_updates(&block);
                                 sm.archive_block(*block.clone(), None);
                             }
-                            // Forward to WeaveEngine.
-                            let mut engine = self.weave_engine.write().await;
-                            engine.set_timestamp(current_timestamp());
-                            let _responses =
-                                engine.on_network_message(NornMessage::Block(block.clone()));
+                            // A block committed by the rest of the validator set is
+                            // consensus progress too.
+                            if committed {
+                                if let Ok(mut t) = self.last_committed_time.lock() {
+                                    *t = std::time::Instant::now();
+                                }
+                            }
 
                             // Fix: notify WebSocket subscribers for peer blocks too.
                             if let Some(ref bc) = self.broadcasters {
@@ -1196,6 +1573,49 @@ impl Node {
                                 }
                             }
                         }
+                        NornMessage::SnapshotRequest { genesis_hash, .. } => {
+                            if self.genesis_hash != [0u8; 32]
+                                && genesis_hash != [0u8; 32]
+                                && genesis_hash != self.genesis_hash
+                            {
+                                tracing::warn!("rejecting snapshot request: genesis hash mismatch");
+                                continue;
+                            }
+                            // Offer our latest snapshot; stay silent if we have none.
+                            if let (Some(snapshot), Some(handle), Some(peer_id)) =
+                                (&self.latest_snapshot, &self.relay_handle, source_peer)
+                            {
+                                let offer = NornMessage::SnapshotOffer {
+                                    manifest: Box::new(snapshot.manifest.clone()),
+                                    block: Box::new(snapshot.block.clone()),
+                                    genesis_hash: self.genesis_hash,
+                                };
+                                let h = handle.clone();
+                                tokio::spawn(async move {
+                                    let _ = h.send_to_peer(peer_id, offer).await;
+                                });
+                            }
+                        }
+                        NornMessage::SnapshotChunkRequest { chunk_hash } => {
+                            let chunk = self
+                                .latest_snapshot
+                                .as_ref()
+                                .and_then(|snapshot| snapshot.chunk(&chunk_hash));
+                            if let (Some(data), Some(handle), Some(peer_id)) =
+                                (chunk, &self.relay_handle, source_peer)
+                            {
+                                let resp = NornMessage::SnapshotChunk {
+                                    chunk_hash,
+                                    data: data.to_vec(),
+                                };
+                                let h = handle.clone();
+                                tokio::spawn(async move {
+                                    let _ = h.send_to_peer(peer_id, resp).await;
+                                });
+                            }
+                        }
+                        // Offers and chunks are only consumed during fast sync.
+                        NornMessage::SnapshotOffer { .. } | NornMessage::SnapshotChunk { .. } => {}
                         NornMessage::StateResponse {
                             blocks,
                             genesis_hash,
@@ -1211,6 +1631,16 @@ impl Node {
                                     theirs = %hex::encode(genesis_hash),
                                     "rejecting state response: genesis hash mismatch"
                                 );
+                                if let (Some(handle), Some(peer_id)) =
+                                    (&self.relay_handle, source_peer)
+                                {
+                                    let h = handle.clone();
+                                    tokio::spawn(async move {
+                                        let _ = h
+                                            .report_peer(peer_id, Misbehavior::ProtocolViolation)
+                                            .await;
+                                    });
+                                }
                                 continue;
                             }
                             // Apply synced blocks.
@@ -1230,6 +1660,16 @@ impl Node {
                                         continue;
                                     }
                                 }
+                                // The engine checks fees against the state before the block.
+                                {
+                                    let mut engine = self.weave_engine.write().await;
+                                    let sm = self.state_manager.read().await;
+                                    engine.set_timestamp(current_timestamp());
+                                    let _ = engine.on_network_message(
+                                        NornMessage::Block(Box::new(block.clone())),
+                                        &*sm,
+                                    );
+                                }
                                 {
                                     let mut sm = self.state_manager.write().await;
                                     for reg in &block.registrations {
@@ -1326,8 +1766,20 @@ impl Node {
                                                 loom_id,
                                                 crate::loom_from_registration(ld, loom_id),
                                             );
+                                            sm.persist_loom_operators(
+                                                &loom_id,
+                                                &OperatorSet::from_config(&ld.operator, &ld.config),
+                                            );
                                         }
                                     }
+                                    if !block.operator_handovers.is_empty() {
+                                        let mut loom_mgr = self.loom_manager.write().await;
+                                        apply_operator_handovers(
+                                            &mut sm,
+                                            &mut loom_mgr,
+                                            &block.operator_handovers,
+                                        );
+                                    }
                                     for bt in &block.transfers {
                                         if !sm.has_transfer(&bt.knot_id) {
                                             sm.auto_register_if_needed(bt.from);
@@ -1357,12 +1809,12 @@ impl Node {
                                             commit.knot_count,
                                         );
                                     }
-                                    sm.archive_block(block.clone(), None);
+                                    sm.apply_stale_commit_proofs(&block);
+                                    sm.apply_spindle_market(&block);
+                                    sm.apply_key_updates(&block);
+                                    sm.apply_token_metadata_updates(&block);
+                                    sm.archive_block(block, None);
                                 }
-                                let mut engine = self.weave_engine.write().await;
-                                engine.set_timestamp(current_timestamp());
-                                let _ =
-                                    engine.on_network_message(NornMessage::Block(Box::new(block)));
                             }
                         }
                         NornMessage::UpgradeNotice(notice) => {
@@ -1450,13 +1902,27 @@ impl Node {
                         }
                         NornMessage::NameTransfer(_) | NornMessage::NameRecordUpdate(_) => {
                             let mut engine = self.weave_engine.write().await;
-                            engine.on_network_message(msg);
+                            let sm = self.state_manager.read().await;
+                            engine.on_network_message(msg, &*sm);
                         }
                         other => {
+                            // Watch consensus votes for validator equivocation.
+                            let evidence_msgs =
+                                self.spindle.on_message(&other, current_timestamp());
+                            for evidence_msg in evidence_msgs {
+                                if let Some(ref handle) = self.relay_handle {
+                                    let h = handle.clone();
+                                    tokio::spawn(async move {
+                                        let _ = h.broadcast(evidence_msg).await;
+                                    });
+                                }
+                            }
                             // Forward all other messages to WeaveEngine.
                             let mut engine = self.weave_engine.write().await;
+                            let sm = self.state_manager.read().await;
                             engine.set_timestamp(current_timestamp());
-                            let responses = engine.on_network_message(other);
+                            let responses = engine.on_network_message(other, &*sm);
+                            drop(sm);
                             drop(engine);
                             // Route consensus responses through P2P relay.
                             for msg in responses {
@@ -1472,6 +1938,51 @@ impl Node {
                 }
             }
 
+            // Queue slashing evidence found by the spindle for the next block.
+            let spindle_evidence = self.spindle.drain_slashing_evidence();
+            if !spindle_evidence.is_empty() {
+                let mut engine = self.weave_engine.write().await;
+                for evidence in spindle_evidence {
+                    if let Err(e) = engine.add_slashing_evidence(evidence) {
+                        tracing::debug!("spindle slashing evidence not queued: {}", e);
+                    }
+                }
+            }
+
+            // Queue fraud proofs found by the spindle for the next block.
+            let spindle_fraud_proofs = self.spindle.drain_fraud_proofs();
+            if !spindle_fraud_proofs.is_empty() {
+                let mut engine = self.weave_engine.write().await;
+                for fp in spindle_fraud_proofs {
+                    if let Err(e) = engine.add_fraud_proof(fp) {
+                        tracing::debug!("spindle fraud proof not queued: {}", e);
+                    }
+                }
+            }
+
+            // Advertise the thread ranges this node's spindle covers, so that
+            // spindles split the thread space with k-of-n redundancy.
+            if let Some(ref handle) = self.relay_handle {
+                let now = current_timestamp();
+                let registry = handle.spindle_registry();
+                let ranges = match registry.lock() {
+                    Ok(mut reg) => {
+                        reg.prune_stale_coverage(now);
+                        reg.assign_coverage(self.spindle.address(), DEFAULT_SPINDLE_REDUNDANCY)
+                    }
+                    Err(_) => vec![ThreadRange::full()],
+                };
+                if let Some(coverage) = self.spindle.coverage_advertisement(ranges, now) {
+                    if let Ok(mut reg) = registry.lock() {
+                        let _ = reg.advertise_coverage(coverage.clone());
+                    }
+                    let h = handle.clone();
+                    tokio::spawn(async move {
+                        let _ = h.broadcast(NornMessage::SpindleCoverage(coverage)).await;
+                    });
+                }
+            }
+
             tokio::select! {
                 _ = block_interval.tick() => {
                     if self.config.validator.enabled && !sync_pending {
@@ -1482,13 +1993,19 @@ impl Node {
 
                         if self.config.validator.solo_mode {
                             // Solo mode: produce blocks directly, bypassing consensus.
-                            // Compute state root from StateManager.
-                            let state_root = {
-                                let sm = self.state_manager.read().await;
-                                sm.state_root()
-                            };
+                            // Compute state root from StateManager, check committers'
+                            // balances against it, and capture the state it commits
+                            // to if this block anchors a snapshot.
+                            let next_height = engine.weave_state().height + 1;
+                            let interval = self.config.storage.snapshot_interval;
+                            let sm = self.state_manager.read().await;
+                            let snapshot_entries = (interval > 0 && next_height % interval == 0)
+                                .then(|| sm.store().map(|store| store.snapshot_entries()))
+                                .flatten();
                             let production_start = std::time::Instant::now();
-                            if let Some(block) = engine.produce_block(timestamp, state_root) {
+                            let produced = engine.produce_block(timestamp, sm.state_root(), &*sm);
+                            drop(sm);
+                            if let Some(block) = produced {
                                 let production_us = production_start.elapsed().as_micros() as u64;
                                 if let Ok(mut guard) = self.last_block_production_us.lock() {
                                     *guard = Some(production_us);
@@ -1507,115 +2024,33 @@ impl Node {
                                 // Persist block and state to storage.
                                 self.persist_block(&block, engine.weave_state());
 
+                                // Keep serving the previous snapshot if this one fails.
+                                match snapshot_entries {
+                                    Some(Ok(entries)) => {
+                                        if let Some(snapshot) = take_snapshot(&self.snapshot_keypair, &entries, &block) {
+                                            self.latest_snapshot = Some(snapshot);
+                                        }
+                                    }
+                                    Some(Err(e)) => tracing::warn!("failed to read snapshot state: {}", e),
+                                    None => {}
+                                }
+
                                 // Update StateManager with block contents.
                                 {
                                     let mut sm = self.state_manager.write().await;
+                                    // Watch new threads in spindle for fraud detection.
                                     for reg in &block.registrations {
-                                        sm.register_thread(reg.thread_id, reg.owner);
-                                        // Watch new threads in spindle for fraud detection.
                                         self.spindle.watch_thread(reg.thread_id);
                                     }
-                                    // Apply name registrations (solo — deduct fee locally).
-                                    // May fail with "already registered" in multi-validator
-                                    // setups where a peer block already applied the name.
-                                    for name_reg in &block.name_registrations {
-                                        if let Err(e) = sm.register_name(
-                                            &name_reg.name,
-                                            name_reg.owner,
-                                            name_reg.timestamp,
-                                        ) {
-                                            tracing::debug!("solo name registration skipped: {}", e);
-                                        }
-                                    }
-                                    for nt in &block.name_transfers {
-                                        sm.auto_register_if_needed(nt.to);
-                                        if let Err(e) = sm.transfer_name(&nt.name, nt.from, nt.to, nt.timestamp) {
-                                            tracing::warn!("failed to apply name transfer '{}': {}", nt.name, e);
-                                        }
-                                    }
-                                    for nru in &block.name_record_updates {
-                                        if let Err(e) = sm.set_name_record(&nru.name, &nru.key, &nru.value, nru.owner, nru.timestamp) {
-                                            tracing::warn!("failed to apply name record update '{}': {}", nru.name, e);
-                                        }
-                                    }
-                                    // Apply token operations (solo — deduct creation fee locally).
-                                    for td in &block.token_definitions {
-                                        if let Err(e) = sm.create_token(
-                                            &td.name, &td.symbol, td.decimals, td.max_supply,
-                                            td.initial_supply, td.creator, td.timestamp,
-                                        ) {
-                                            tracing::debug!("solo token creation skipped: {}", e);
-                                        }
-                                    }
-                                    for tm in &block.token_mints {
-                                        if let Err(e) = sm.mint_token(tm.token_id, tm.to, tm.amount) {
-                                            tracing::debug!("solo token mint skipped: {}", e);
-                                        }
-                                    }
-                                    for tb in &block.token_burns {
-                                        if let Err(e) = sm.burn_token(tb.token_id, tb.burner, tb.amount) {
-                                            tracing::debug!("solo token burn skipped: {}", e);
-                                        }
-                                    }
-                                    // Apply loom deploys (solo — deduct deploy fee locally).
-                                    if !block.loom_deploys.is_empty() {
-                                        let mut loom_mgr = self.loom_manager.write().await;
-                                        for ld in &block.loom_deploys {
-                                            let loom_id = norn_types::loom::compute_loom_id(ld);
-                                            let operator_addr = pubkey_to_address(&ld.operator);
-                                            if let Err(e) = sm.deploy_loom(
-                                                loom_id,
-                                                &ld.config.name,
-                                                ld.operator,
-                                                operator_addr,
-                                                ld.timestamp,
-                                            ) {
-                                                tracing::debug!("solo loom deploy skipped: {}", e);
-                                            }
-                                            loom_mgr.register_loom(loom_id, crate::loom_from_registration(ld, loom_id));
-                                        }
-                                    }
-                                    // Note: transfers are NOT re-applied here — they were
-                                    // already applied by the KnotProposal handler above.
-                                    // Deduct commitment fees from committers.
-                                    let fee_per = norn_weave::fees::compute_fee(
-                                        &engine.weave_state().fee_state,
-                                        1,
-                                    );
-                                    for commit in &block.commitments {
-                                        sm.record_commitment(
-                                            commit.thread_id,
-                                            commit.version,
-                                            commit.state_hash,
-                                            commit.prev_commitment_hash,
-                                            commit.knot_count,
-                                        );
-                                        sm.debit_fee(commit.thread_id, fee_per);
-                                    }
+                                    let mut loom_mgr = self.loom_manager.write().await;
+                                    apply_block_effects(&mut sm, &mut loom_mgr, &block);
                                     sm.archive_block(block.clone(), Some(production_us));
                                 }
 
                                 // Distribute epoch rewards to validators.
                                 if let Some(rewards) = engine.take_pending_rewards() {
                                     let mut sm = self.state_manager.write().await;
-                                    let now = block.timestamp;
-                                    for (addr, amount) in &rewards {
-                                        sm.auto_register_if_needed(*addr);
-                                        if let Err(e) = sm.credit(*addr, norn_types::primitives::NATIVE_TOKEN_ID, *amount) {
-                                            tracing::warn!(
-                                                "failed to credit epoch reward to {}: {}",
-                                                hex::encode(addr), e
-                                            );
-                                        }
-                                        sm.log_synthetic_transfer(
-                                            [0u8; 20],
-                                            *addr,
-                                            norn_types::primitives::NATIVE_TOKEN_ID,
-                                            *amount,
-                                            Some("Validator epoch reward"),
-                                            now,
-                                        );
-                                    }
+                                    credit_epoch_rewards(&mut sm, &rewards, block.timestamp);
                                     tracing::info!(
                                         validators = rewards.len(),
                                         "epoch rewards distributed (solo mode)"
@@ -1640,7 +2075,10 @@ impl Node {
                             drop(engine); // Release lock before metrics.
                         } else {
                             let tick_start = std::time::Instant::now();
-                            let messages = engine.on_tick(timestamp);
+                            let messages = {
+                                let sm = self.state_manager.read().await;
+                                engine.on_tick(timestamp, &*sm)
+                            };
                             drop(engine); // Release lock before processing committed blocks.
 
                             // Track whether any block was committed in this tick.
@@ -1671,7 +2109,6 @@ impl Node {
 
                                     // Apply block contents to StateManager (same as solo mode).
                                     {
-                                        let engine = self.weave_engine.read().await;
                                         let mut sm = self.state_manager.write().await;
                                         for reg in &block.registrations {
                                             sm.register_thread(reg.thread_id, reg.owner);
@@ -1730,12 +2167,13 @@ impl Node {
                                                     tracing::debug!("consensus loom deploy skipped: {}", e);
                                                 }
                                                 loom_mgr.register_loom(loom_id, crate::loom_from_registration(ld, loom_id));
+                                                sm.persist_loom_operators(&loom_id, &OperatorSet::from_config(&ld.operator, &ld.config));
                                             }
                                         }
-                                        let fee_per = norn_weave::fees::compute_fee(
-                                            &engine.weave_state().fee_state,
-                                            1,
-                                        );
+                                        if !block.operator_handovers.is_empty() {
+                                            let mut loom_mgr = self.loom_manager.write().await;
+                                            apply_operator_handovers(&mut sm, &mut loom_mgr, &block.operator_handovers);
+                                        }
                                         for commit in &block.commitments {
                                             sm.record_commitment(
                                                 commit.thread_id,
@@ -1744,8 +2182,12 @@ impl Node {
                                                 commit.prev_commitment_hash,
                                                 commit.knot_count,
                                             );
-                                            sm.debit_fee(commit.thread_id, fee_per);
                                         }
+                                        sm.apply_commitment_fees(block);
+                                        sm.apply_stale_commit_proofs(block);
+                                        sm.apply_spindle_market(block);
+                                        sm.apply_key_updates(block);
+                                        sm.apply_token_metadata_updates(block);
                                         sm.archive_block(block.clone(), Some(production_us));
                                     }
 
@@ -1754,24 +2196,7 @@ impl Node {
                                         let mut engine = self.weave_engine.write().await;
                                         if let Some(rewards) = engine.take_pending_rewards() {
                                             let mut sm = self.state_manager.write().await;
-                                            let now = block.timestamp;
-                                            for (addr, amount) in &rewards {
-                                                sm.auto_register_if_needed(*addr);
-                                                if let Err(e) = sm.credit(*addr, norn_types::primitives::NATIVE_TOKEN_ID, *amount) {
-                                                    tracing::warn!(
-                                                        "failed to credit epoch reward to {}: {}",
-                                                        hex::encode(addr), e
-                                                    );
-                                                }
-                                                sm.log_synthetic_transfer(
-                                                    [0u8; 20],
-                                                    *addr,
-                                                    norn_types::primitives::NATIVE_TOKEN_ID,
-                                                    *amount,
-                                                    Some("Validator epoch reward"),
-                                                    now,
-                                                );
-                                            }
+                                            credit_epoch_rewards(&mut sm, &rewards, block.timestamp);
                                             tracing::info!(
                                                 validators = rewards.len(),
                                                 "epoch rewards distributed (consensus)"
@@ -1802,8 +2227,11 @@ impl Node {
                             }
 
                             // Check for consensus timeout (no block committed recently).
+                            // The timeout backs off while views keep failing.
+                            let view_timeout = self.weave_engine.read().await
+                                .consensus_view_timeout(std::time::Duration::from_secs(CONSENSUS_TIMEOUT_SECS));
                             let timed_out = self.last_committed_time.lock()
-                                .map(|t| t.elapsed() > std::time::Duration::from_secs(CONSENSUS_TIMEOUT_SECS))
+                                .map(|t| t.elapsed() > view_timeout)
                                 .unwrap_or(false);
 
                             if timed_out {
@@ -1834,8 +2262,57 @@ impl Node {
                             self.metrics
                                 .mempool_size
                                 .set(engine.mempool().total_size() as i64);
+                            let staking = engine.staking();
+                            let slashed = staking.slash_history().len() as u64;
+                            let counted = self.metrics.validators_slashed.get();
+                            if slashed > counted {
+                                self.metrics.validators_slashed.inc_by(slashed - counted);
+                            }
+                            self.metrics
+                                .validators_jailed
+                                .set(staking.jailed_count() as i64);
+                        }
+                        if let Some(ref handle) = self.relay_handle {
+                            let stats = handle.peer_stats();
+                            self.metrics.peer_count.set(stats.peers.len() as i64);
+                            self.metrics.peers_banned.set(stats.banned as i64);
+                            let counted = self.metrics.peer_penalties.get();
+                            if stats.penalties > counted {
+                                self.metrics.peer_penalties.inc_by(stats.penalties - counted);
+                            }
+                            let bandwidth = handle.bandwidth_stats();
+                            let counted = self.metrics.relay_bytes_received.get();
+                            if bandwidth.bytes_in > counted {
+                                self.metrics.relay_bytes_received.inc_by(bandwidth.bytes_in - counted);
+                            }
+                            let counted = self.metrics.relay_bytes_sent.get();
+                            if bandwidth.bytes_out > counted {
+                                self.metrics.relay_bytes_sent.inc_by(bandwidth.bytes_out - counted);
+                            }
+                            let counted = self.metrics.relay_messages_throttled.get();
+                            if bandwidth.throttled > counted {
+                                self.metrics.relay_messages_throttled.inc_by(bandwidth.throttled - counted);
+                            }
+                            self.metrics
+                                .relay_bytes_received_per_sec
+                                .set(bandwidth.bytes_in_per_sec as i64);
+                            self.metrics
+                                .relay_bytes_sent_per_sec
+                                .set(bandwidth.bytes_out_per_sec as i64);
                         }
                     }
+
+                    // Every node reports to the health checks, validator or not.
+                    {
+                        let engine = self.weave_engine.read().await;
+                        self.health.observe(
+                            engine.weave_state().height,
+                            engine.mempool().total_size(),
+                            self.relay_handle
+                                .as_ref()
+                                .map(|handle| handle.connected_peers().len()),
+                        );
+                    }
                 }
                 _ = async { if let Some(ref mut s) = sync_retry { s.await } else { std::future::pending().await } } => {
                     sync_retry = None;
@@ -1854,6 +2331,13 @@ impl Node {
                     if let Some(handle) = relay_handle {
                         handle.abort();
                     }
+                    pruning_task.abort();
+                    if let Some(task) = bridge_task {
+                        task.abort();
+                    }
+                    if let Some(task) = oracle_task {
+                        task.abort();
+                    }
                     self.shutdown().await?;
                     return Ok(());
                 }
@@ -1878,15 +2362,480 @@ impl Node {
 
     /// Persist a block and the current weave state to storage.
     fn persist_block(&self, block: &WeaveBlock, state: &WeaveState) {
-        if let Err(e) = self.weave_store.save_block(block) {
+        if let Err(e) = self.weave_store.commit_block(block, state) {
             tracing::warn!("Failed to persist block {}: {}", block.height, e);
         }
-        if let Err(e) = self.weave_store.save_weave_state(state) {
-            tracing::warn!("Failed to persist weave state: {}", e);
+    }
+}
+
+/// Load the validator keypair from the configured seed, if any.
+pub(crate) fn validator_keypair(config: &NodeConfig) -> Result<Option<Keypair>, NodeError> {
+    let Some(ref seed_hex) = config.validator.keypair_seed else {
+        return Ok(None);
+    };
+    let seed_bytes = hex::decode(seed_hex).map_err(|e| NodeError::ConfigError {
+        reason: format!("invalid keypair seed hex: {}", e),
+    })?;
+    if seed_bytes.len() != 32 {
+        return Err(NodeError::ConfigError {
+            reason: format!("keypair seed must be 32 bytes, got {}", seed_bytes.len()),
+        });
+    }
+    let mut seed = [0u8; 32];
+    seed.copy_from_slice(&seed_bytes);
+    Ok(Some(Keypair::from_seed(&seed)))
+}
+
+/// Services this node announces to peers in the relay handshake.
+fn relay_capabilities(config: &NodeConfig) -> Capabilities {
+    let mut capabilities = Capabilities::default();
+    if config.storage.snapshot_interval > 0 {
+        capabilities |= Capabilities::STATE_SNAPSHOTS;
+    }
+    if matches!(
+        config.storage.pruning_mode(),
+        Ok(norn_storage::pruning::PruningMode::Archive)
+    ) {
+        capabilities |= Capabilities::ARCHIVE;
+    }
+    capabilities
+}
+
+/// Weave state for a chain started without a genesis config.
+pub(crate) fn default_weave_state() -> WeaveState {
+    WeaveState {
+        height: 0,
+        latest_hash: [0u8; 32],
+        threads_root: [0u8; 32],
+        thread_count: 0,
+        fee_state: FeeState {
+            base_fee: 100,
+            fee_multiplier: 1000,
+            epoch_fees: 0,
+        },
+    }
+}
+
+/// Seed the engine's staking state with the genesis validators and staking
+/// parameters (or the defaults when there is no genesis config).
+pub(crate) fn seed_genesis_staking(
+    engine: &mut WeaveEngine,
+    validators: &[Validator],
+    genesis: Option<&norn_types::genesis::GenesisConfig>,
+) {
+    let bonding_period = genesis
+        .map(|gc| gc.parameters.bonding_period)
+        .unwrap_or(100);
+    let min_stake = genesis
+        .map(|gc| gc.parameters.min_validator_stake)
+        .unwrap_or(1000);
+    let max_validators = genesis
+        .map(|gc| gc.parameters.max_validators as usize)
+        .unwrap_or(norn_types::constants::MAX_VALIDATORS);
+    engine.seed_staking(validators, min_stake, bonding_period, max_validators);
+    tracing::info!(
+        validators = validators.len(),
+        min_stake = min_stake,
+        bonding_period = bonding_period,
+        max_validators = max_validators,
+        "seeded staking from genesis"
+    );
+}
+
+/// Credit the genesis allocations, logging each as a synthetic transfer
+/// from the zero address.
+pub(crate) fn credit_genesis_allocations(
+    sm: &mut StateManager,
+    gc: &norn_types::genesis::GenesisConfig,
+) {
+    // Register zero address so synthetic transfers from it succeed.
+    sm.auto_register_if_needed([0u8; 20]);
+    for alloc in &gc.allocations {
+        sm.auto_register_if_needed(alloc.address);
+        if let Err(e) = sm.credit(alloc.address, alloc.token_id, alloc.amount) {
+            tracing::warn!(
+                "Failed to process genesis allocation for {}: {}",
+                hex::encode(alloc.address),
+                e
+            );
+        } else {
+            // Log genesis allocation as a synthetic transfer.
+            sm.log_synthetic_transfer(
+                [0u8; 20],
+                alloc.address,
+                alloc.token_id,
+                alloc.amount,
+                Some("Genesis allocation"),
+                gc.timestamp,
+            );
         }
     }
 }
 
+/// Register the genesis names that are not registered yet. Returns how many
+/// were registered.
+pub(crate) fn register_genesis_names(
+    sm: &mut StateManager,
+    gc: &norn_types::genesis::GenesisConfig,
+) -> u32 {
+    let mut registered = 0u32;
+    for gnr in &gc.name_registrations {
+        if sm.resolve_name(&gnr.name).is_none() {
+            sm.auto_register_if_needed(gnr.owner);
+            if let Err(e) =
+                sm.apply_peer_name_registration(&gnr.name, gnr.owner, [0u8; 32], gc.timestamp, 0)
+            {
+                tracing::warn!("failed to register genesis name '{}': {}", gnr.name, e);
+            } else {
+                registered += 1;
+            }
+        }
+    }
+    registered
+}
+
+/// Credit validator epoch rewards, logging each as a synthetic transfer from
+/// the zero address.
+pub(crate) fn credit_epoch_rewards(sm: &mut StateManager, rewards: &[(Address, Amount)], now: u64) {
+    for (addr, amount) in rewards {
+        sm.auto_register_if_needed(*addr);
+        if let Err(e) = sm.credit(*addr, NATIVE_TOKEN_ID, *amount) {
+            tracing::warn!(
+                "failed to credit epoch reward to {}: {}",
+                hex::encode(addr),
+                e
+            );
+        }
+        sm.log_synthetic_transfer(
+            [0u8; 20],
+            *addr,
+            NATIVE_TOKEN_ID,
+            *amount,
+            Some("Validator epoch reward"),
+            now,
+        );
+    }
+}
+
+/// Apply everything in a block this node produced except its transfers
+/// (already applied when their knots were accepted): registrations, names,
+/// tokens, looms, commitments, fees, fraud proofs and spindle operations.
+///
+/// Shared by solo block production and `norn-node replay`, so replayed
+/// history goes through exactly the same state transitions.
+pub(crate) fn apply_block_effects(
+    sm: &mut StateManager,
+    loom_mgr: &mut LoomManager,
+    block: &WeaveBlock,
+) {
+    for reg in &block.registrations {
+        sm.register_thread(reg.thread_id, reg.owner);
+    }
+    // Name registrations may fail with "already registered" in
+    // multi-validator setups where a peer block already applied the name.
+    for name_reg in &block.name_registrations {
+        if let Err(e) = sm.register_name(&name_reg.name, name_reg.owner, name_reg.timestamp) {
+            tracing::debug!("solo name registration skipped: {}", e);
+        }
+    }
+    for nt in &block.name_transfers {
+        sm.auto_register_if_needed(nt.to);
+        if let Err(e) = sm.transfer_name(&nt.name, nt.from, nt.to, nt.timestamp) {
+            tracing::warn!("failed to apply name transfer '{}': {}", nt.name, e);
+        }
+    }
+    for nru in &block.name_record_updates {
+        if let Err(e) =
+            sm.set_name_record(&nru.name, &nru.key, &nru.value, nru.owner, nru.timestamp)
+        {
+            tracing::warn!("failed to apply name record update '{}': {}", nru.name, e);
+        }
+    }
+    for td in &block.token_definitions {
+        if let Err(e) = sm.create_token(
+            &td.name,
+            &td.symbol,
+            td.decimals,
+            td.max_supply,
+            td.initial_supply,
+            td.creator,
+            td.timestamp,
+        ) {
+            tracing::debug!("solo token creation skipped: {}", e);
+        }
+    }
+    for tm in &block.token_mints {
+        if let Err(e) = sm.mint_token(tm.token_id, tm.to, tm.amount) {
+            tracing::debug!("solo token mint skipped: {}", e);
+        }
+    }
+    for tb in &block.token_burns {
+        if let Err(e) = sm.burn_token(tb.token_id, tb.burner, tb.amount) {
+            tracing::debug!("solo token burn skipped: {}", e);
+        }
+    }
+    for ld in &block.loom_deploys {
+        let loom_id = norn_types::loom::compute_loom_id(ld);
+        let operator_addr = pubkey_to_address(&ld.operator);
+        if let Err(e) = sm.deploy_loom(
+            loom_id,
+            &ld.config.name,
+            ld.operator,
+            operator_addr,
+            ld.timestamp,
+        ) {
+            tracing::debug!("solo loom deploy skipped: {}", e);
+        }
+        loom_mgr.register_loom(loom_id, crate::loom_from_registration(ld, loom_id));
+        sm.persist_loom_operators(
+            &loom_id,
+            &OperatorSet::from_config(&ld.operator, &ld.config),
+        );
+    }
+    apply_operator_handovers(sm, loom_mgr, &block.operator_handovers);
+    for commit in &block.commitments {
+        sm.record_commitment(
+            commit.thread_id,
+            commit.version,
+            commit.state_hash,
+            commit.prev_commitment_hash,
+            commit.knot_count,
+        );
+    }
+    sm.apply_commitment_fees(block);
+    sm.apply_stale_commit_proofs(block);
+    sm.apply_spindle_market(block);
+    sm.apply_key_updates(block);
+    sm.apply_token_metadata_updates(block);
+}
+
+/// Apply a block's loom operator handovers and persist the rotated keys.
+fn apply_operator_handovers(
+    sm: &mut StateManager,
+    loom_mgr: &mut LoomManager,
+    handovers: &[OperatorHandover],
+) {
+    for h in handovers {
+        if let Err(e) = loom_mgr.rotate_operator(h) {
+            tracing::debug!("operator handover skipped: {}", e);
+            continue;
+        }
+        sm.rotate_loom_operator(&h.loom_id, &h.old_operator, h.new_operator);
+        if let Some(ops) = loom_mgr.operator_set(&h.loom_id) {
+            sm.persist_loom_operators(&h.loom_id, &ops);
+        }
+    }
+}
+
+/// Take a snapshot of `entries` anchored to a block this node produced.
+fn take_snapshot(
+    keypair: &Keypair,
+    entries: &SnapshotEntries,
+    block: &WeaveBlock,
+) -> Option<Snapshot> {
+    match Snapshot::new(entries, block.clone(), keypair) {
+        Ok(snapshot) => {
+            tracing::info!(
+                height = block.height,
+                chunks = snapshot.manifest.chunk_hashes.len(),
+                "took state snapshot"
+            );
+            Some(snapshot)
+        }
+        Err(e) => {
+            tracing::warn!(height = block.height, "failed to take snapshot: {}", e);
+            None
+        }
+    }
+}
+
+/// Operator sets of multi-operator looms; other looms have only their
+/// deploying operator.
+fn load_loom_operators(sm: &StateManager) -> HashMap<LoomId, OperatorSet> {
+    sm.store()
+        .and_then(|store| store.load_all_loom_operators().ok())
+        .unwrap_or_default()
+        .into_iter()
+        .collect()
+}
+
+/// Seed the WeaveEngine with the names, threads, tokens, and looms known to
+/// the StateManager.
+fn seed_weave_engine(
+    engine: &mut WeaveEngine,
+    sm: &StateManager,
+    persisted_operators: &HashMap<LoomId, OperatorSet>,
+) {
+    let names: Vec<String> = sm.registered_names().map(|s| s.to_string()).collect();
+    let name_owners: Vec<(String, Address)> = sm
+        .name_owners()
+        .map(|(n, addr)| (n.to_string(), addr))
+        .collect();
+    let threads: Vec<[u8; 20]> = sm.registered_thread_ids().copied().collect();
+    if !names.is_empty() || !threads.is_empty() {
+        tracing::info!(
+            names = names.len(),
+            threads = threads.len(),
+            "seeding WeaveEngine with persisted state"
+        );
+        engine.seed_known_state(names, name_owners, threads);
+    }
+
+    let tokens: Vec<_> = sm
+        .registered_tokens()
+        .map(|(id, rec)| {
+            (
+                *id,
+                norn_weave::token::TokenMeta {
+                    name: rec.name.clone(),
+                    symbol: rec.symbol.clone(),
+                    decimals: rec.decimals,
+                    max_supply: rec.max_supply,
+                    current_supply: rec.current_supply,
+                    creator: rec.creator,
+                    created_at: rec.created_at,
+                    metadata_updated_at: rec.metadata_updated_at,
+                },
+            )
+        })
+        .collect();
+    if !tokens.is_empty() {
+        tracing::info!(
+            tokens = tokens.len(),
+            "seeding WeaveEngine with persisted tokens"
+        );
+        engine.seed_known_tokens(tokens);
+    }
+
+    let loom_ids: Vec<_> = sm.registered_looms().copied().collect();
+    if !loom_ids.is_empty() {
+        tracing::info!(
+            looms = loom_ids.len(),
+            "seeding WeaveEngine with persisted looms"
+        );
+        let operators: Vec<_> = sm
+            .list_looms()
+            .into_iter()
+            .map(|(loom_id, record)| {
+                let ops = persisted_operators
+                    .get(loom_id)
+                    .cloned()
+                    .unwrap_or_else(|| OperatorSet::single(record.operator));
+                (*loom_id, ops)
+            })
+            .collect();
+        engine.seed_known_looms(loom_ids);
+        engine.seed_loom_operators(operators);
+    }
+
+    let thread_keys: Vec<_> = sm.updated_thread_keys().collect();
+    if !thread_keys.is_empty() {
+        tracing::info!(
+            threads = thread_keys.len(),
+            "seeding WeaveEngine with rotated thread keys"
+        );
+        engine.seed_thread_keys(thread_keys);
+    }
+}
+
+/// Build a LoomManager for the looms registered in the StateManager and
+/// restore their stored code and state.
+fn restore_loom_manager(
+    sm: &StateManager,
+    persisted_operators: &HashMap<LoomId, OperatorSet>,
+) -> LoomManager {
+    let mut loom_mgr = LoomManager::new();
+
+    // Register loom metadata from StateManager so LoomManager knows about them.
+    for (loom_id, record) in sm.list_looms() {
+        let (co_operators, operator_threshold) = match persisted_operators.get(loom_id) {
+            Some(ops) if ops.operators.first() == Some(&record.operator) => {
+                (ops.operators[1..].to_vec(), ops.threshold)
+            }
+            _ => (vec![], 1),
+        };
+        let loom = norn_types::loom::Loom {
+            config: norn_types::loom::LoomConfig {
+                loom_id: *loom_id,
+                name: record.name.clone(),
+                max_participants: 1000,
+                min_participants: 1,
+                accepted_tokens: vec![norn_types::primitives::NATIVE_TOKEN_ID],
+                config_data: vec![],
+                co_operators,
+                operator_threshold,
+            },
+            operator: record.operator,
+            participants: Vec::new(),
+            state_hash: [0u8; 32],
+            version: 0,
+            active: record.active,
+            last_updated: record.deployed_at,
+        };
+        loom_mgr.register_loom(*loom_id, loom);
+    }
+
+    // Restore stored code, then point each loom at its code ID.
+    let (codes, loom_code_ids, legacy_bytecodes, loom_states) = if let Some(store) = sm.store() {
+        (
+            store.load_all_codes().unwrap_or_default(),
+            store.load_all_loom_code_ids().unwrap_or_default(),
+            store.load_all_loom_bytecodes().unwrap_or_default(),
+            store.load_all_loom_states().unwrap_or_default(),
+        )
+    } else {
+        (vec![], vec![], vec![], vec![])
+    };
+    for (_, code) in &codes {
+        let _ = loom_mgr.store_code(code.clone());
+    }
+
+    // Migrate per-loom bytecode copies to shared, content-addressed code.
+    let mut loom_code_ids = loom_code_ids;
+    for (loom_id, bytecode_bytes) in legacy_bytecodes {
+        let Ok(code_id) = loom_mgr.store_code(bytecode_bytes.clone()) else {
+            continue;
+        };
+        if let Some(store) = sm.store() {
+            let migrated = store
+                .save_code(&code_id, &bytecode_bytes)
+                .and_then(|_| store.save_loom_code_id(&loom_id, &code_id))
+                .and_then(|_| store.delete_loom_bytecode(&loom_id));
+            if let Err(e) = migrated {
+                tracing::warn!("failed to migrate loom bytecode: {}", e);
+            }
+        }
+        loom_code_ids.push((loom_id, code_id));
+    }
+
+    let state_map: HashMap<[u8; 32], Vec<u8>> = loom_states.into_iter().collect();
+
+    for (loom_id, code_id) in &loom_code_ids {
+        // Restore state data if available.
+        let state_data: HashMap<Vec<u8>, Vec<u8>> =
+            if let Some(state_bytes) = state_map.get(loom_id) {
+                borsh::from_slice(state_bytes).unwrap_or_default()
+            } else {
+                HashMap::new()
+            };
+        // If loom was registered, use restore_loom to overwrite with full data.
+        if let Some(loom) = loom_mgr.get_loom(loom_id).cloned() {
+            if let Err(e) = loom_mgr.restore_loom(*loom_id, loom, *code_id, state_data) {
+                tracing::warn!("failed to restore loom {}: {}", hex::encode(loom_id), e);
+            }
+        }
+    }
+
+    if !loom_code_ids.is_empty() {
+        tracing::info!(
+            looms_with_bytecode = loom_code_ids.len(),
+            codes = loom_mgr.list_codes().len(),
+            "restored loom bytecodes from disk"
+        );
+    }
+    loom_mgr
+}
+
 /// Convert a WeaveBlock into a BlockInfo for WebSocket subscribers.
 fn block_info_from_weave(
     block: &WeaveBlock,
@@ -1934,8 +2883,9 @@ mod tests {
         // Disable RPC to avoid port conflicts in tests.
         config.rpc.enabled = false;
         config.validator.enabled = false;
-        // Clear boot nodes to prevent network connections in tests.
+        // Clear boot nodes and DNS seeds to prevent network connections in tests.
         config.network.boot_nodes.clear();
+        config.network.dns_seeds = Some(Vec::new());
         config
     }
 
@@ -0,0 +1,312 @@
+//! Oracle price reporter.
+//!
+//! Runs this node as one of the whitelisted reporters of an oracle loom (see
+//! `examples/oracle`). On every tick it fetches each configured feed's price
+//! from its HTTP(S) source, scales it to the feed's decimals, and submits
+//! all of them in one signed `submit_batch` execution. The loom medianizes
+//! the reports of all its reporters, so one faulty source cannot move the
+//! price on its own.
+//!
+//! A feed whose source fails is left out of that tick's batch; the loom
+//! keeps serving the median until its reports go stale.
+
+use std::sync::Arc;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use norn_crypto::keys::Keypair;
+
+use crate::config::{OracleConfig, OracleFeedConfig};
+use crate::error::NodeError;
+use crate::loom_client::{parse_hex32, LoomClient};
+
+/// Execute index of `submit_batch(updates)`.
+pub const SUBMIT_BATCH: u8 = 1;
+
+/// Most decimals a feed may use: `10^36` still leaves room in a `u128`.
+const MAX_DECIMALS: u8 = 36;
+
+/// One price report.
+///
+/// Mirrors the oracle loom's `PriceUpdate`.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct PriceUpdate {
+    pub feed: String,
+    pub price: u128,
+    pub timestamp: u64,
+}
+
+/// Loom input for `submit_batch`.
+pub fn batch_input(updates: &[PriceUpdate]) -> Vec<u8> {
+    let mut input = vec![SUBMIT_BATCH];
+    input.extend(borsh::to_vec(updates).expect("update serialization cannot fail"));
+    input
+}
+
+/// Scale `price` to an integer with `decimals` decimal places. `None` if it
+/// is not positive or does not fit.
+pub fn to_fixed(price: f64, decimals: u8) -> Option<u128> {
+    let scaled = (price * 10f64.powi(decimals as i32)).round();
+    (scaled.is_finite() && scaled >= 1.0 && scaled < u128::MAX as f64).then_some(scaled as u128)
+}
+
+/// Pushes prices from HTTP sources to an oracle loom.
+pub struct OracleReporter {
+    feeds: Vec<OracleFeedConfig>,
+    loom: LoomClient,
+}
+
+impl OracleReporter {
+    /// Create a reporter submitting through the Norn RPC server at
+    /// `norn_rpc_url`, authenticating with `api_key` if the server needs one.
+    pub fn new(
+        config: &OracleConfig,
+        norn_rpc_url: &str,
+        api_key: Option<&str>,
+    ) -> Result<Self, NodeError> {
+        let config_error = |reason: String| NodeError::ConfigError { reason };
+        let loom_id = parse_hex32(&config.loom_id)
+            .map_err(|e| config_error(format!("invalid oracle loom_id: {}", e)))?;
+        let seed = parse_hex32(&config.reporter_seed)
+            .map_err(|e| config_error(format!("invalid oracle reporter_seed: {}", e)))?;
+        if config.feeds.is_empty() {
+            return Err(config_error("oracle has no feeds".to_string()));
+        }
+        for (i, feed) in config.feeds.iter().enumerate() {
+            if config.feeds[..i].iter().any(|f| f.feed == feed.feed) {
+                return Err(config_error(format!(
+                    "duplicate oracle feed '{}'",
+                    feed.feed
+                )));
+            }
+            if feed.decimals > MAX_DECIMALS {
+                return Err(config_error(format!(
+                    "oracle feed '{}' has more than {} decimals",
+                    feed.feed, MAX_DECIMALS
+                )));
+            }
+        }
+
+        Ok(Self {
+            feeds: config.feeds.clone(),
+            loom: LoomClient::new(norn_rpc_url, api_key, loom_id, Keypair::from_seed(&seed))?,
+        })
+    }
+
+    /// Fetch every feed's price and submit them. Returns how many feeds
+    /// were reported.
+    pub async fn report(&self) -> Result<usize, NodeError> {
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let mut updates = Vec::with_capacity(self.feeds.len());
+        for feed in &self.feeds {
+            match crate::wallet::price::fetch(&feed.url, &feed.pointer).await {
+                Ok(price) => match to_fixed(price, feed.decimals) {
+                    Some(price) => updates.push(PriceUpdate {
+                        feed: feed.feed.clone(),
+                        price,
+                        timestamp,
+                    }),
+                    None => {
+                        tracing::warn!(feed = %feed.feed, price, "skipping unusable price")
+                    }
+                },
+                Err(e) => tracing::warn!(feed = %feed.feed, "price fetch failed: {}", e),
+            }
+        }
+        self.submit(&updates).await
+    }
+
+    /// Submit `updates` in one execution. Returns how many were submitted.
+    pub async fn submit(&self, updates: &[PriceUpdate]) -> Result<usize, NodeError> {
+        if updates.is_empty() {
+            return Ok(0);
+        }
+        let result = self.loom.execute(&batch_input(updates)).await?;
+        if !result.success {
+            return Err(NodeError::OracleError {
+                reason: format!(
+                    "oracle loom rejected report: {}",
+                    result.reason.unwrap_or_default()
+                ),
+            });
+        }
+        Ok(updates.len())
+    }
+
+    /// Report every `interval` until the task is dropped.
+    pub async fn run(self: Arc<Self>, interval: std::time::Duration) {
+        tracing::info!(
+            reporter = %hex::encode(self.loom.address()),
+            feeds = self.feeds.len(),
+            "oracle reporter started"
+        );
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            match self.report().await {
+                Ok(count) => tracing::debug!(count, "reported oracle prices"),
+                Err(e) => tracing::warn!("oracle report failed: {}", e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::types::ExecutionResult;
+    use jsonrpsee::server::{RpcModule, Server, ServerHandle};
+    use std::sync::Mutex;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// A fake Norn node recording executed inputs.
+    #[derive(Default)]
+    struct Mock {
+        executed: Vec<Vec<u8>>,
+        reject: Option<String>,
+    }
+
+    async fn serve(mock: Arc<Mutex<Mock>>) -> (String, ServerHandle) {
+        let server = Server::builder().build("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", server.local_addr().unwrap());
+        let mut module = RpcModule::new(mock);
+        module
+            .register_method("norn_executeLoom", |params, mock, _| {
+                let (_, input, _, _, _): (String, String, String, String, String) =
+                    params.parse().unwrap();
+                let mut mock = mock.lock().unwrap();
+                mock.executed.push(hex::decode(input).unwrap());
+                serde_json::to_value(ExecutionResult {
+                    success: mock.reject.is_none(),
+                    output_hex: None,
+                    gas_used: 0,
+                    logs: Vec::new(),
+                    events: Vec::new(),
+                    reason: mock.reject.clone(),
+                    instantiated: Vec::new(),
+                })
+                .unwrap()
+            })
+            .unwrap();
+        (url, server.start(module))
+    }
+
+    /// An HTTP endpoint answering every request with `body`.
+    async fn price_source(body: &'static str) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/price", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = [0u8; 1024];
+                let _ = socket.read(&mut buf).await;
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\n\
+                     content-length: {}\r\nconnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        url
+    }
+
+    fn feed(name: &str, url: &str, pointer: &str) -> OracleFeedConfig {
+        OracleFeedConfig {
+            feed: name.to_string(),
+            url: url.to_string(),
+            pointer: pointer.to_string(),
+            decimals: 8,
+        }
+    }
+
+    fn config(feeds: Vec<OracleFeedConfig>) -> OracleConfig {
+        OracleConfig {
+            loom_id: hex::encode([9u8; 32]),
+            reporter_seed: hex::encode([1u8; 32]),
+            poll_interval_secs: 1,
+            feeds,
+        }
+    }
+
+    fn decode(input: &[u8]) -> Vec<PriceUpdate> {
+        assert_eq!(input[0], SUBMIT_BATCH);
+        Vec::<PriceUpdate>::try_from_slice(&input[1..]).unwrap()
+    }
+
+    #[test]
+    fn test_to_fixed() {
+        assert_eq!(to_fixed(1.25, 8), Some(125_000_000));
+        assert_eq!(to_fixed(0.123456789, 8), Some(12_345_679));
+        assert_eq!(to_fixed(65_000.5, 0), Some(65_001));
+        assert_eq!(to_fixed(0.0, 8), None);
+        assert_eq!(to_fixed(0.000000001, 8), None);
+        assert_eq!(to_fixed(f64::NAN, 8), None);
+        assert_eq!(to_fixed(1e300, 36), None);
+    }
+
+    #[test]
+    fn test_new_validates_feeds() {
+        let url = "http://127.0.0.1:9741";
+        assert!(OracleReporter::new(&config(Vec::new()), url, None).is_err());
+        let dup = vec![feed("A", url, "/a"), feed("A", url, "/b")];
+        let err = OracleReporter::new(&config(dup), url, None).err().unwrap();
+        assert!(err.to_string().contains("duplicate oracle feed 'A'"));
+        let mut wide = feed("A", url, "/a");
+        wide.decimals = 40;
+        assert!(OracleReporter::new(&config(vec![wide]), url, None).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_reports_fetched_prices() {
+        let mock = Arc::new(Mutex::new(Mock::default()));
+        let (url, _handle) = serve(mock.clone()).await;
+        let source = price_source(r#"{"norn":{"usd":"0.5"},"btc":65000.25,"bad":-1}"#).await;
+        let mut btc = feed("BTC/USD", &source, "/btc");
+        btc.decimals = 2;
+        let reporter = OracleReporter::new(
+            &config(vec![
+                feed("NORN/USD", &source, "/norn/usd"),
+                btc,
+                feed("BAD/USD", &source, "/bad"),
+            ]),
+            &url,
+            None,
+        )
+        .unwrap();
+
+        // The broken feed is skipped; the rest go out in one batch.
+        assert_eq!(reporter.report().await.unwrap(), 2);
+        let updates = decode(&mock.lock().unwrap().executed[0]);
+        assert_eq!(updates.len(), 2);
+        assert_eq!(updates[0].feed, "NORN/USD");
+        assert_eq!(updates[0].price, 50_000_000);
+        assert_eq!(updates[1].feed, "BTC/USD");
+        assert_eq!(updates[1].price, 6_500_025);
+        assert!(updates[0].timestamp > 0);
+    }
+
+    #[tokio::test]
+    async fn test_rejected_report_is_an_error() {
+        let mock = Arc::new(Mutex::new(Mock {
+            reject: Some("execution failed: unauthorized".to_string()),
+            ..Default::default()
+        }));
+        let (url, _handle) = serve(mock.clone()).await;
+        let reporter =
+            OracleReporter::new(&config(vec![feed("A", &url, "/a")]), &url, None).unwrap();
+
+        let update = PriceUpdate {
+            feed: "A".to_string(),
+            price: 1,
+            timestamp: 1,
+        };
+        let err = reporter.submit(&[update]).await.unwrap_err();
+        assert!(err.to_string().contains("unauthorized"));
+        // Nothing to report means no execution.
+        assert_eq!(reporter.submit(&[]).await.unwrap(), 0);
+        assert_eq!(mock.lock().unwrap().executed.len(), 1);
+    }
+}
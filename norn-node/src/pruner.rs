@@ -0,0 +1,175 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use norn_storage::error::StorageError;
+use norn_storage::pruning::{check_prune_height, PruningMode};
+use norn_storage::traits::KvStore;
+use norn_storage::weave_store::WeaveStore;
+
+use crate::metrics::NodeMetrics;
+use crate::state_store::StateStore;
+
+/// Removes block and state history that falls outside the configured
+/// retention window, and reports disk usage.
+pub struct Pruner {
+    mode: PruningMode,
+    store: Arc<dyn KvStore>,
+    /// Data directory to measure, or `None` for the in-memory backend.
+    data_dir: Option<PathBuf>,
+    metrics: Arc<NodeMetrics>,
+}
+
+impl Pruner {
+    pub fn new(
+        mode: PruningMode,
+        store: Arc<dyn KvStore>,
+        data_dir: Option<PathBuf>,
+        metrics: Arc<NodeMetrics>,
+    ) -> Self {
+        let pruned_height = StateStore::new(store.clone())
+            .pruned_height()
+            .unwrap_or_default();
+        metrics.pruned_height.set(pruned_height as i64);
+        Self {
+            mode,
+            store,
+            data_dir,
+            metrics,
+        }
+    }
+
+    /// Run one pruning pass relative to the last finalized snapshot (the
+    /// persisted weave state). Returns the number of entries removed.
+    pub fn prune(&self) -> Result<u64, StorageError> {
+        let weave_store = WeaveStore::new(self.store.clone());
+        let state_store = StateStore::new(self.store.clone());
+        let Some(finalized_height) = weave_store.latest_height()? else {
+            return Ok(0);
+        };
+        let Some(height) = self.mode.prune_height(finalized_height) else {
+            return Ok(0);
+        };
+        if height <= state_store.pruned_height()? {
+            return Ok(0);
+        }
+        check_prune_height(height, finalized_height)?;
+
+        let removed = weave_store.prune_blocks_below(height)? + state_store.prune_below(height)?;
+        self.metrics.pruned_height.set(height as i64);
+        tracing::info!(
+            mode = %self.mode,
+            height,
+            finalized_height,
+            removed,
+            "pruned history"
+        );
+        Ok(removed)
+    }
+
+    /// Refresh the disk usage metric.
+    pub fn record_disk_usage(&self) {
+        if let Some(ref dir) = self.data_dir {
+            self.metrics.storage_disk_bytes.set(dir_size(dir) as i64);
+        }
+    }
+
+    /// Prune and measure disk usage every `interval` until the task is dropped.
+    pub async fn run(self: Arc<Self>, interval: std::time::Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            let pruner = self.clone();
+            let result = tokio::task::spawn_blocking(move || {
+                let result = pruner.prune();
+                pruner.record_disk_usage();
+                result
+            })
+            .await;
+            match result {
+                Ok(Ok(_)) => {}
+                Ok(Err(e)) => tracing::warn!("pruning failed: {}", e),
+                Err(e) => tracing::warn!("pruning task panicked: {}", e),
+            }
+        }
+    }
+}
+
+/// Total size of the files under `path`.
+fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.metadata() {
+            Ok(meta) if meta.is_dir() => dir_size(&entry.path()),
+            Ok(meta) => meta.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use norn_storage::memory::MemoryStore;
+    use norn_types::weave::{FeeState, WeaveState};
+
+    fn finalize(store: &Arc<dyn KvStore>, height: u64) {
+        WeaveStore::new(store.clone())
+            .save_weave_state(&WeaveState {
+                height,
+                latest_hash: [0u8; 32],
+                threads_root: [0u8; 32],
+                thread_count: 0,
+                fee_state: FeeState {
+                    base_fee: 100,
+                    fee_multiplier: 1000,
+                    epoch_fees: 0,
+                },
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn test_prune_respects_mode_and_finalized_height() {
+        let store: Arc<dyn KvStore> = Arc::new(MemoryStore::new());
+        let state_store = StateStore::new(store.clone());
+        for height in 1..=10 {
+            state_store.save_block_timing(height, 1).unwrap();
+        }
+        let metrics = Arc::new(NodeMetrics::new());
+
+        let archive = Pruner::new(PruningMode::Archive, store.clone(), None, metrics.clone());
+        finalize(&store, 10);
+        assert_eq!(archive.prune().unwrap(), 0);
+
+        let pruner = Pruner::new(PruningMode::KeepRecent(4), store.clone(), None, metrics);
+        assert_eq!(pruner.prune().unwrap(), 6);
+        assert_eq!(state_store.pruned_height().unwrap(), 7);
+        assert_eq!(state_store.load_all_block_timings().unwrap().len(), 4);
+        // Nothing new to prune until the finalized height moves.
+        assert_eq!(pruner.prune().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_prune_without_finalized_snapshot_is_noop() {
+        let store: Arc<dyn KvStore> = Arc::new(MemoryStore::new());
+        let pruner = Pruner::new(
+            PruningMode::Minimal,
+            store,
+            None,
+            Arc::new(NodeMetrics::new()),
+        );
+        assert_eq!(pruner.prune().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_dir_size_counts_nested_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(tmp.path().join("a"), [0u8; 10]).unwrap();
+        std::fs::create_dir(tmp.path().join("sub")).unwrap();
+        std::fs::write(tmp.path().join("sub").join("b"), [0u8; 5]).unwrap();
+        assert_eq!(dir_size(tmp.path()), 15);
+    }
+}
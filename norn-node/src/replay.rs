@@ -0,0 +1,424 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+use norn_crypto::address::pubkey_to_address;
+use norn_crypto::keys::Keypair;
+use norn_loom::lifecycle::LoomManager;
+use norn_storage::memory::MemoryStore;
+use norn_storage::traits::KvStore;
+use norn_types::genesis::GenesisConfig;
+use norn_types::primitives::{Address, Amount, PublicKey, TokenId};
+use norn_types::weave::{Validator, ValidatorSet, WeaveBlock};
+use norn_weave::engine::WeaveEngine;
+use serde::Serialize;
+
+use crate::config::NodeConfig;
+use crate::error::NodeError;
+use crate::node::{
+    apply_block_effects, credit_epoch_rewards, credit_genesis_allocations, default_weave_state,
+    register_genesis_names, seed_genesis_staking,
+};
+use crate::state_manager::StateManager;
+use crate::state_store::StateStore;
+
+/// Outcome of replaying a node's stored blocks.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayReport {
+    /// Height the replay started from: 0 for genesis, or the pruned height
+    /// when older history is no longer stored.
+    pub start_height: u64,
+    /// Number of blocks re-executed.
+    pub blocks_replayed: u64,
+    /// Number of blocks whose state root was checked and matched.
+    pub roots_verified: u64,
+    /// Number of blocks that carry no state root (consensus blocks).
+    pub roots_unchecked: u64,
+    /// The first block whose state root did not match, if any.
+    pub divergence: Option<Divergence>,
+}
+
+/// The first block at which replayed state differs from stored state.
+#[derive(Debug, Clone, Serialize)]
+pub struct Divergence {
+    pub height: u64,
+    pub block_hash: String,
+    /// State root recorded in the stored block.
+    pub expected_state_root: String,
+    /// State root computed by the replay at the same point.
+    pub replayed_state_root: String,
+    /// Balances that differ after the block, replayed against the stored
+    /// history. Empty if the history for the block has been pruned.
+    pub balances: Vec<BalanceDiff>,
+}
+
+/// A balance that differs between the replay and the stored history.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BalanceDiff {
+    pub address: String,
+    pub token_id: String,
+    pub replayed: Amount,
+    pub stored: Amount,
+}
+
+/// Replay the blocks stored under `config`'s data directory.
+pub fn replay(config: &NodeConfig) -> Result<ReplayReport, NodeError> {
+    let genesis = if let Some(ref gc) = config.genesis_config {
+        Some(gc.clone())
+    } else if let Some(ref genesis_path) = config.genesis_path {
+        let (gc, _, _) = crate::genesis::load_genesis(genesis_path)?;
+        Some(gc)
+    } else {
+        None
+    };
+    let solo_validator = crate::node::validator_keypair(config)?.map(|kp| kp.public_key());
+    let store = crate::node::create_store(config)?;
+    StateStore::new(store.clone()).check_schema_version()?;
+    replay_store(store, genesis.as_ref(), solo_validator)
+}
+
+/// Re-execute every stored block against a fresh state store, checking each
+/// block's state root, and stop at the first divergence.
+///
+/// Replay starts from genesis. If the history below some height has been
+/// pruned, it instead starts from the state recorded at the pruned height;
+/// names, tokens and looms are then taken from the latest stored state, so
+/// a divergence in those before the start height goes unnoticed.
+///
+/// `solo_validator` is the validator used when the genesis config has none,
+/// as a solo node adds its own key. It defaults to the proposer of the first
+/// replayed block.
+pub fn replay_store(
+    source: Arc<dyn KvStore>,
+    genesis: Option<&GenesisConfig>,
+    solo_validator: Option<PublicKey>,
+) -> Result<ReplayReport, NodeError> {
+    let source = StateStore::new(source);
+    let start_height = source.pruned_height()?;
+    let start_block = source.load_block(start_height)?;
+    let first_block = source.load_block(start_height + 1)?;
+
+    let mut sm = if start_height == 0 {
+        genesis_state(genesis)
+    } else {
+        snapshot_state(&source, start_height)?
+    };
+    sm.set_store(StateStore::new(Arc::new(MemoryStore::new())));
+    if let Some(block) = start_block.clone() {
+        sm.archive_block(block, None);
+    }
+    let mut loom_mgr = LoomManager::new();
+
+    let mut validators: Vec<Validator> = genesis
+        .map(|gc| {
+            gc.validators
+                .iter()
+                .map(|gv| Validator {
+                    pubkey: gv.pubkey,
+                    bls_pubkey: gv.bls_pubkey,
+                    address: gv.address,
+                    stake: gv.stake,
+                    active: true,
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    if validators.is_empty() {
+        if let Some(pubkey) = solo_validator.or(first_block.as_ref().map(|b| b.proposer)) {
+            // Replay does not check certificates, so the solo validator's
+            // BLS key is not needed.
+            validators.push(Validator {
+                pubkey,
+                bls_pubkey: [0u8; 48],
+                address: pubkey_to_address(&pubkey),
+                stake: 1_000_000_000_000,
+                active: true,
+            });
+        }
+    }
+    let total_stake = validators.iter().map(|v| v.stake).sum();
+    let validator_set = ValidatorSet {
+        validators,
+        total_stake,
+        epoch: 0,
+    };
+
+    let mut initial_state = match genesis {
+        Some(gc) => crate::genesis::create_genesis_block(gc)?.1,
+        None => default_weave_state(),
+    };
+    if start_height > 0 {
+        initial_state.height = start_height;
+        initial_state.latest_hash = start_block.as_ref().map_or([0u8; 32], |b| b.hash);
+    }
+    let mut engine = WeaveEngine::new(Keypair::generate(), validator_set.clone(), initial_state);
+    seed_genesis_staking(&mut engine, &validator_set.validators, genesis);
+
+    let mut report = ReplayReport {
+        start_height,
+        blocks_replayed: 0,
+        roots_verified: 0,
+        roots_unchecked: 0,
+        divergence: None,
+    };
+    let mut height = start_height + 1;
+    while let Some(block) = source.load_block(height)? {
+        // A solo block's state root covers balances after its transfers,
+        // before the rest of the block is applied.
+        apply_transfers(&mut sm, &block);
+        let replayed_root = sm.state_root();
+        let diverged = block.state_root != [0u8; 32] && block.state_root != replayed_root;
+
+        engine
+            .replay_block(&block)
+            .map_err(|e| NodeError::WeaveError(e.to_string()))?;
+        apply_block_effects(&mut sm, &mut loom_mgr, &block);
+        sm.archive_block(block.clone(), None);
+        report.blocks_replayed += 1;
+
+        if diverged {
+            report.divergence = Some(Divergence {
+                height,
+                block_hash: hex::encode(block.hash),
+                expected_state_root: hex::encode(block.state_root),
+                replayed_state_root: hex::encode(replayed_root),
+                balances: diff_balances(&sm, &source, height)?,
+            });
+            break;
+        }
+        if block.state_root == [0u8; 32] {
+            report.roots_unchecked += 1;
+        } else {
+            report.roots_verified += 1;
+        }
+
+        if let Some(rewards) = engine.take_pending_rewards() {
+            credit_epoch_rewards(&mut sm, &rewards, block.timestamp);
+        }
+        height += 1;
+    }
+    Ok(report)
+}
+
+/// State at genesis: the allocations and names in the genesis config.
+fn genesis_state(genesis: Option<&GenesisConfig>) -> StateManager {
+    let mut sm = StateManager::new();
+    if let Some(gc) = genesis {
+        credit_genesis_allocations(&mut sm, gc);
+        register_genesis_names(&mut sm, gc);
+    }
+    sm
+}
+
+/// State at the pruned height: thread states and metadata from the history,
+/// everything else from the latest stored state.
+fn snapshot_state(source: &StateStore, height: u64) -> Result<StateManager, NodeError> {
+    let mut thread_states = std::collections::HashMap::new();
+    let mut thread_metas = std::collections::HashMap::new();
+    for (address, _) in source.load_all_thread_states()? {
+        if let Some(state) = source.load_thread_state_at(&address, height)? {
+            thread_states.insert(address, state);
+        }
+        if let Some(meta) = source.load_thread_meta_at(&address, height)? {
+            thread_metas.insert(address, meta);
+        }
+    }
+    let mut sm = StateManager::from_parts(
+        thread_states,
+        thread_metas,
+        Vec::new(),
+        Vec::new(),
+        source.load_all_names()?.into_iter().collect(),
+        source.load_all_address_names()?.into_iter().collect(),
+    );
+    for (token_id, record) in source.load_all_tokens()? {
+        sm.seed_token(token_id, record);
+    }
+    for (loom_id, record) in source.load_all_looms()? {
+        sm.seed_loom(loom_id, record);
+    }
+    Ok(sm)
+}
+
+/// Apply a block's transfers as a node does when it accepts their knots.
+fn apply_transfers(sm: &mut StateManager, block: &WeaveBlock) {
+    for bt in &block.transfers {
+        if sm.has_transfer(&bt.knot_id) {
+            continue;
+        }
+        sm.auto_register_if_needed(bt.from);
+        sm.auto_register_if_needed(bt.to);
+        if let Err(e) = sm.apply_peer_transfer(
+            bt.from,
+            bt.to,
+            bt.token_id,
+            bt.amount,
+            bt.knot_id,
+            bt.memo.clone(),
+            bt.timestamp,
+        ) {
+            tracing::debug!("replayed transfer failed: {}", e);
+        }
+    }
+}
+
+/// Compare every balance after the block at `height` with the stored history.
+fn diff_balances(
+    sm: &StateManager,
+    source: &StateStore,
+    height: u64,
+) -> Result<Vec<BalanceDiff>, NodeError> {
+    if source.pruned_height()? > height {
+        return Ok(Vec::new());
+    }
+    let mut addresses: BTreeSet<Address> = sm.registered_thread_ids().copied().collect();
+    addresses.extend(source.load_all_thread_states()?.into_iter().map(|(a, _)| a));
+
+    let mut diffs = Vec::new();
+    for address in addresses {
+        let replayed: BTreeMap<TokenId, Amount> = sm
+            .get_thread_state(&address)
+            .map(|s| s.balances.clone())
+            .unwrap_or_default();
+        let stored: BTreeMap<TokenId, Amount> = source
+            .load_thread_state_at(&address, height)?
+            .map(|s| s.balances)
+            .unwrap_or_default();
+        let tokens: BTreeSet<&TokenId> = replayed.keys().chain(stored.keys()).collect();
+        for token_id in tokens {
+            let replayed = replayed.get(token_id).copied().unwrap_or(0);
+            let stored = stored.get(token_id).copied().unwrap_or(0);
+            if replayed != stored {
+                diffs.push(BalanceDiff {
+                    address: hex::encode(address),
+                    token_id: hex::encode(token_id),
+                    replayed,
+                    stored,
+                });
+            }
+        }
+    }
+    Ok(diffs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genesis::{devnet_genesis, DEVNET_SEED_KEYPAIR_SEED};
+    use norn_types::primitives::NATIVE_TOKEN_ID;
+    use norn_types::weave::BlockTransfer;
+
+    /// Run a solo node for three blocks, each with one transfer from the
+    /// founder, persisting everything to the returned store.
+    fn solo_chain(gc: &GenesisConfig, founder: Address) -> Arc<dyn KvStore> {
+        let store: Arc<dyn KvStore> = Arc::new(MemoryStore::new());
+        let mut sm = StateManager::new();
+        sm.set_store(StateStore::new(store.clone()));
+        credit_genesis_allocations(&mut sm, gc);
+        register_genesis_names(&mut sm, gc);
+        let (genesis_block, genesis_state) = crate::genesis::create_genesis_block(gc).unwrap();
+        sm.archive_block(genesis_block, None);
+
+        let seed: [u8; 32] = hex::decode(DEVNET_SEED_KEYPAIR_SEED)
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let validators: Vec<Validator> = gc
+            .validators
+            .iter()
+            .map(|gv| Validator {
+                pubkey: gv.pubkey,
+                bls_pubkey: gv.bls_pubkey,
+                address: gv.address,
+                stake: gv.stake,
+                active: true,
+            })
+            .collect();
+        let validator_set = ValidatorSet {
+            total_stake: validators.iter().map(|v| v.stake).sum(),
+            validators: validators.clone(),
+            epoch: 0,
+        };
+        let mut engine = WeaveEngine::new(Keypair::from_seed(&seed), validator_set, genesis_state);
+        seed_genesis_staking(&mut engine, &validators, Some(gc));
+        let mut loom_mgr = LoomManager::new();
+
+        for i in 1..=3u8 {
+            let timestamp = gc.timestamp + i as u64;
+            let transfer = BlockTransfer {
+                from: founder,
+                to: [i; 20],
+                token_id: NATIVE_TOKEN_ID,
+                amount: 1_000 * i as u128,
+                memo: None,
+                knot_id: [i; 32],
+                timestamp,
+            };
+            sm.auto_register_if_needed(transfer.to);
+            sm.apply_transfer(
+                transfer.from,
+                transfer.to,
+                transfer.token_id,
+                transfer.amount,
+                transfer.knot_id,
+                None,
+                timestamp,
+            )
+            .unwrap();
+            engine.add_transfer(transfer).unwrap();
+
+            engine.set_timestamp(timestamp);
+            let block = engine
+                .produce_block(timestamp, sm.state_root(), &sm)
+                .unwrap();
+            apply_block_effects(&mut sm, &mut loom_mgr, &block);
+            sm.archive_block(block, None);
+        }
+        store
+    }
+
+    #[test]
+    fn test_replay_verifies_state_roots() {
+        let (gc, founder) = devnet_genesis();
+        let store = solo_chain(&gc, founder);
+
+        let report = replay_store(store, Some(&gc), None).unwrap();
+        assert_eq!(report.start_height, 0);
+        assert_eq!(report.blocks_replayed, 3);
+        assert_eq!(report.roots_verified, 3);
+        assert!(report.divergence.is_none());
+    }
+
+    #[test]
+    fn test_replay_reports_first_divergence() {
+        let (gc, founder) = devnet_genesis();
+        let store = solo_chain(&gc, founder);
+
+        // Rewrite block 2 so it moves a different amount than the node applied.
+        let source = StateStore::new(store.clone());
+        let mut block = source.load_block(2).unwrap().unwrap();
+        block.transfers[0].amount = 5_000;
+        source.save_block(&block).unwrap();
+
+        let report = replay_store(store, Some(&gc), None).unwrap();
+        assert_eq!(report.blocks_replayed, 2);
+        assert_eq!(report.roots_verified, 1);
+        let divergence = report.divergence.unwrap();
+        assert_eq!(divergence.height, 2);
+        assert_ne!(
+            divergence.expected_state_root,
+            divergence.replayed_state_root
+        );
+
+        let recipient = divergence
+            .balances
+            .iter()
+            .find(|b| b.address == hex::encode([2u8; 20]))
+            .unwrap();
+        assert_eq!(recipient.replayed, 5_000);
+        assert_eq!(recipient.stored, 2_000);
+        assert!(divergence
+            .balances
+            .iter()
+            .any(|b| b.address == hex::encode(founder)));
+    }
+}
@@ -1,59 +1,405 @@
-/// API key configuration for RPC authentication.
-///
-/// Used by RPC handlers to check bearer tokens on mutation methods.
-/// Currently checked inline in mutation handlers when `api_key` is configured.
+//! RPC authentication and method-level access control.
+//!
+//! Auth is enabled by configuring `rpc.api_key` or any `rpc.api_keys`. Each
+//! call is then checked against the caller's grant:
+//!
+//! - without a key (`Authorization` header absent), only the public methods
+//!   may be called: the read-only methods below unless `rpc.public_methods`
+//!   overrides them;
+//! - `rpc.api_key` may call every method, without a rate limit;
+//! - each `rpc.api_keys` entry may call the public methods plus its own
+//!   `methods` allowlist, at most `rate_limit_per_minute` times a minute.
+//!
+//! A request with an unknown key is rejected outright. Keys are checked in
+//! the HTTP middleware and methods per call, so WebSocket calls and batch
+//! entries are covered as well.
+
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use crate::config::RpcConfig;
+use crate::error::NodeError;
+
+/// Read-only RPC methods, public by default.
+pub const READ_ONLY_METHODS: &[&str] = &[
+    "norn_getBalance",
+    "norn_getBalanceAt",
+    "norn_getBlock",
+    "norn_getLatestBlock",
+    "norn_getWeaveState",
+    "norn_getThread",
+    "norn_getThreadState",
+    "norn_getThreadStateAt",
+    "norn_health",
+    "norn_getValidatorSet",
+    "norn_getFeeEstimate",
+    "norn_getMempool",
+    "norn_estimateFee",
+    "norn_getFeeHistory",
+    "norn_getCommitmentProof",
+    "norn_getTransactionHistory",
+    "norn_getRecentTransfers",
+    "norn_resolveName",
+    "norn_listNames",
+    "norn_reverseName",
+    "norn_getNameRecords",
+    "norn_getMetrics",
+    "norn_getPeers",
+    "norn_getTokenInfo",
+    "norn_getTokenBySymbol",
+    "norn_listTokens",
+    "norn_getLoomInfo",
+    "norn_listLooms",
+    "norn_listCodes",
+    "norn_getCode",
+    "norn_getGasSchedule",
+    "norn_queryLoom",
+    "norn_queryLoomAt",
+    "norn_getDispute",
+    "norn_listDisputes",
+    "norn_getStakingInfo",
+    "norn_getValidatorRewards",
+    "norn_getDelegations",
+    "norn_getSlashingHistory",
+    "norn_getSpindles",
+    "norn_getSpindleSubscriptions",
+    "norn_getSpindleCoverage",
+    "norn_getStateRoot",
+    "norn_getStateProof",
+    "norn_getBlockTransactions",
+    "norn_getBlockHeaders",
+    "norn_getTransferProof",
+    "norn_getTransaction",
+    "norn_getEvents",
+    "norn_getKnotProposal",
+    "norn_convertAddress",
+    "norn_graphql",
+    // WebSocket subscriptions are read-only.
+    "norn_subscribeNewBlocks",
+    "norn_unsubscribeNewBlocks",
+    "norn_subscribeTransfers",
+    "norn_unsubscribeTransfers",
+    "norn_subscribeTokenEvents",
+    "norn_unsubscribeTokenEvents",
+    "norn_subscribeLoomEvents",
+    "norn_unsubscribeLoomEvents",
+    "norn_subscribeEvents",
+    "norn_unsubscribeEvents",
+    "norn_subscribePendingTransactions",
+    "norn_unsubscribePendingTransactions",
+    // Chat relay (read-only).
+    "norn_getChatHistory",
+    "norn_subscribeChatEvents",
+    "norn_unsubscribeChatEvents",
+];
+
+/// Method allowlist. An entry is a method name, `*` for every method, or a
+/// prefix ending in `*` such as `norn_get*`.
 #[derive(Debug, Clone)]
-pub struct RpcAuthConfig {
-    /// If set, mutation methods require this API key via `Authorization: Bearer <key>`.
-    pub api_key: Option<String>,
+pub struct MethodAllowlist(Vec<String>);
+
+impl MethodAllowlist {
+    pub fn new(patterns: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self(patterns.into_iter().map(Into::into).collect())
+    }
+
+    /// Whether `method` matches an entry.
+    pub fn allows(&self, method: &str) -> bool {
+        self.0.iter().any(|p| match p.strip_suffix('*') {
+            Some(prefix) => method.starts_with(prefix),
+            None => p == method,
+        })
+    }
+}
+
+/// Token bucket allowing `per_minute` calls a minute, in bursts of up to
+/// `per_minute`.
+#[derive(Debug)]
+pub struct RateLimiter {
+    per_minute: u32,
+    bucket: Mutex<(f64, Instant)>,
+}
+
+impl RateLimiter {
+    pub fn new(per_minute: u32) -> Self {
+        Self {
+            per_minute,
+            bucket: Mutex::new((per_minute as f64, Instant::now())),
+        }
+    }
+
+    /// Take one call from the bucket, or return false if it is empty.
+    pub fn try_acquire(&self, now: Instant) -> bool {
+        let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+        let (tokens, last) = &mut *bucket;
+        let elapsed = now.saturating_duration_since(*last).as_secs_f64();
+        *tokens = (*tokens + elapsed * self.per_minute as f64 / 60.0).min(self.per_minute as f64);
+        *last = now;
+        if *tokens >= 1.0 {
+            *tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// What an API key may do.
+#[derive(Debug)]
+pub struct ApiKeyGrant {
+    /// Name of the key, for logs.
+    pub name: String,
+    methods: MethodAllowlist,
+    limiter: Option<RateLimiter>,
+}
+
+/// Who is making an RPC call, attached to each request by the HTTP
+/// middleware.
+#[derive(Debug, Clone)]
+pub enum RpcCaller {
+    /// No API key.
+    Public,
+    /// A configured API key.
+    Key(Arc<ApiKeyGrant>),
+}
+
+/// Why a call was refused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthError {
+    /// The caller may not call the method.
+    Forbidden { method: String },
+    /// The caller's key is over its rate limit.
+    RateLimited { key: String },
+}
+
+impl AuthError {
+    /// JSON-RPC error code.
+    pub fn code(&self) -> i32 {
+        match self {
+            AuthError::Forbidden { .. } => -32001,
+            AuthError::RateLimited { .. } => -32005,
+        }
+    }
+}
+
+impl std::fmt::Display for AuthError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuthError::Forbidden { method } => {
+                write!(
+                    f,
+                    "unauthorized: {} requires an API key that allows it",
+                    method
+                )
+            }
+            AuthError::RateLimited { key } => {
+                write!(f, "rate limited: API key '{}' is over its limit", key)
+            }
+        }
+    }
+}
+
+/// API keys and method access rules of the RPC server.
+#[derive(Debug)]
+pub struct RpcAuth {
+    keys: Vec<(String, Arc<ApiKeyGrant>)>,
+    public: MethodAllowlist,
 }
 
-#[allow(dead_code)] // Utility methods used in tests; auth enforced via tower middleware in server.rs
-impl RpcAuthConfig {
-    /// Create a new auth config with no authentication required.
-    pub fn open() -> Self {
-        Self { api_key: None }
+impl RpcAuth {
+    /// Build the access rules from config, or `None` if no key is configured
+    /// and every method is open.
+    pub fn from_config(config: &RpcConfig) -> Result<Option<Self>, NodeError> {
+        if config.api_key.is_none() && config.api_keys.is_empty() {
+            return Ok(None);
+        }
+
+        let mut keys = Vec::new();
+        if let Some(key) = &config.api_key {
+            keys.push((
+                key.clone(),
+                Arc::new(ApiKeyGrant {
+                    name: "api_key".to_string(),
+                    methods: MethodAllowlist::new(["*"]),
+                    limiter: None,
+                }),
+            ));
+        }
+        for entry in &config.api_keys {
+            let invalid = |reason: &str| NodeError::ConfigError {
+                reason: format!("rpc.api_keys '{}': {}", entry.name, reason),
+            };
+            if entry.key.len() < 16 {
+                return Err(invalid("key must be at least 16 characters"));
+            }
+            if keys.iter().any(|(k, _)| k == &entry.key) {
+                return Err(invalid("key is used more than once"));
+            }
+            if entry.rate_limit_per_minute == Some(0) {
+                return Err(invalid("rate_limit_per_minute must be positive"));
+            }
+            keys.push((
+                entry.key.clone(),
+                Arc::new(ApiKeyGrant {
+                    name: entry.name.clone(),
+                    methods: MethodAllowlist::new(entry.methods.iter().cloned()),
+                    limiter: entry.rate_limit_per_minute.map(RateLimiter::new),
+                }),
+            ));
+        }
+
+        let public = match &config.public_methods {
+            Some(methods) => MethodAllowlist::new(methods.iter().cloned()),
+            None => MethodAllowlist::new(READ_ONLY_METHODS.iter().copied()),
+        };
+        Ok(Some(Self { keys, public }))
     }
 
-    /// Create a new auth config requiring the given API key.
-    pub fn with_key(key: String) -> Self {
-        Self { api_key: Some(key) }
+    /// Identify the caller from its bearer token. Returns `None` for a token
+    /// that matches no key.
+    pub fn authenticate(&self, bearer_token: Option<&str>) -> Option<RpcCaller> {
+        let Some(token) = bearer_token else {
+            return Some(RpcCaller::Public);
+        };
+        // Compare against every key so the time taken does not reveal which
+        // one matched.
+        let mut found = None;
+        for (key, grant) in &self.keys {
+            if constant_time_eq(token, key) {
+                found = Some(RpcCaller::Key(grant.clone()));
+            }
+        }
+        found
     }
 
-    /// Check if a provided bearer token matches the configured API key.
-    /// Returns true if no API key is configured (open access) or if the token matches.
-    pub fn check(&self, bearer_token: Option<&str>) -> bool {
-        match &self.api_key {
-            None => true,
-            Some(key) => bearer_token == Some(key.as_str()),
+    /// Check that `caller` may call `method` now, counting the call against
+    /// its rate limit.
+    pub fn authorize(&self, caller: &RpcCaller, method: &str) -> Result<(), AuthError> {
+        let forbidden = || AuthError::Forbidden {
+            method: method.to_string(),
+        };
+        match caller {
+            RpcCaller::Public => self
+                .public
+                .allows(method)
+                .then_some(())
+                .ok_or_else(forbidden),
+            RpcCaller::Key(grant) => {
+                if !grant.methods.allows(method) && !self.public.allows(method) {
+                    return Err(forbidden());
+                }
+                match &grant.limiter {
+                    Some(limiter) if !limiter.try_acquire(Instant::now()) => {
+                        Err(AuthError::RateLimited {
+                            key: grant.name.clone(),
+                        })
+                    }
+                    _ => Ok(()),
+                }
+            }
         }
     }
 }
 
+/// Constant-time string comparison to prevent timing side-channel attacks.
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut result = 0u8;
+    for (x, y) in a.bytes().zip(b.bytes()) {
+        result |= x ^ y;
+    }
+    result == 0
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::{ApiKeyConfig, NodeConfig};
+    use std::time::Duration;
+
+    fn config() -> RpcConfig {
+        let mut rpc = NodeConfig::default().rpc;
+        rpc.api_keys = vec![ApiKeyConfig {
+            name: "faucet-bot".to_string(),
+            key: "faucet-bot-secret-key".to_string(),
+            methods: vec!["norn_faucet".to_string(), "norn_submit*".to_string()],
+            rate_limit_per_minute: Some(2),
+        }];
+        rpc
+    }
+
+    #[test]
+    fn test_open_without_keys() {
+        let rpc = NodeConfig::default().rpc;
+        assert!(RpcAuth::from_config(&rpc).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_public_and_key_methods() {
+        let auth = RpcAuth::from_config(&config()).unwrap().unwrap();
+        let public = auth.authenticate(None).unwrap();
+        assert!(auth.authorize(&public, "norn_getBalance").is_ok());
+        assert_eq!(
+            auth.authorize(&public, "norn_faucet"),
+            Err(AuthError::Forbidden {
+                method: "norn_faucet".to_string()
+            })
+        );
+
+        let bot = auth.authenticate(Some("faucet-bot-secret-key")).unwrap();
+        assert!(auth.authorize(&bot, "norn_getBalance").is_ok());
+        assert!(auth.authorize(&bot, "norn_submitKnot").is_ok());
+        assert!(auth.authorize(&bot, "norn_deployLoom").is_err());
+
+        assert!(auth.authenticate(Some("wrong-key")).is_none());
+    }
 
     #[test]
-    fn test_open_allows_all() {
-        let auth = RpcAuthConfig::open();
-        assert!(auth.check(None));
-        assert!(auth.check(Some("anything")));
+    fn test_admin_key_and_public_override() {
+        let mut rpc = config();
+        rpc.api_key = Some("admin".to_string());
+        rpc.public_methods = Some(vec!["norn_health".to_string()]);
+        let auth = RpcAuth::from_config(&rpc).unwrap().unwrap();
+
+        let admin = auth.authenticate(Some("admin")).unwrap();
+        assert!(auth.authorize(&admin, "norn_deployLoom").is_ok());
+        let public = auth.authenticate(None).unwrap();
+        assert!(auth.authorize(&public, "norn_health").is_ok());
+        assert!(auth.authorize(&public, "norn_getBalance").is_err());
     }
 
     #[test]
-    fn test_with_key_requires_match() {
-        let auth = RpcAuthConfig::with_key("my-secret-key".to_string());
-        assert!(auth.check(Some("my-secret-key")));
-        assert!(!auth.check(Some("wrong-key")));
-        assert!(!auth.check(None));
+    fn test_rate_limit() {
+        let auth = RpcAuth::from_config(&config()).unwrap().unwrap();
+        let bot = auth.authenticate(Some("faucet-bot-secret-key")).unwrap();
+        assert!(auth.authorize(&bot, "norn_faucet").is_ok());
+        assert!(auth.authorize(&bot, "norn_faucet").is_ok());
+        assert_eq!(
+            auth.authorize(&bot, "norn_faucet"),
+            Err(AuthError::RateLimited {
+                key: "faucet-bot".to_string()
+            })
+        );
+
+        let limiter = RateLimiter::new(60);
+        let start = Instant::now();
+        for _ in 0..60 {
+            assert!(limiter.try_acquire(start));
+        }
+        assert!(!limiter.try_acquire(start));
+        assert!(limiter.try_acquire(start + Duration::from_secs(1)));
     }
 
     #[test]
-    fn test_empty_key_still_requires_match() {
-        let auth = RpcAuthConfig::with_key(String::new());
-        assert!(auth.check(Some("")));
-        assert!(!auth.check(Some("notempty")));
-        assert!(!auth.check(None));
+    fn test_rejects_bad_key_config() {
+        let mut rpc = config();
+        rpc.api_keys[0].key = "short".to_string();
+        assert!(RpcAuth::from_config(&rpc).is_err());
+
+        let mut rpc = config();
+        rpc.api_keys.push(rpc.api_keys[0].clone());
+        assert!(RpcAuth::from_config(&rpc).is_err());
     }
 }
@@ -0,0 +1,702 @@
+//! GraphQL queries over indexed chain data.
+//!
+//! With `rpc.graphql` enabled, the node serves a read-only GraphQL schema of
+//! blocks, transfers, tokens, looms and loom events from the state manager.
+//! Queries are made with the `norn_graphql` JSON-RPC method, whose parameter
+//! is a standard GraphQL request (`{"query", "variables", "operationName"}`)
+//! and whose result is the GraphQL response, or with `POST /graphql`. Both go
+//! through the RPC middleware, so auth and rate limits apply to a query as
+//! to any other call.
+//!
+//! Lists are connections paginated with `first` and `after`. Cursors are
+//! opaque: a block height, a knot, token or loom ID, or an event sequence
+//! number. Blocks and transfers are listed newest first, tokens and looms by
+//! ID, and events in emission order.
+
+use std::sync::Arc;
+
+use async_graphql::connection::{Connection, Edge};
+use async_graphql::{
+    Context, EmptyMutation, EmptySubscription, InputObject, Object, OutputType, Schema,
+    SimpleObject,
+};
+use jsonrpsee::types::ErrorObjectOwned;
+use jsonrpsee::RpcModule;
+use tokio::sync::RwLock;
+
+use norn_types::network::NetworkId;
+use norn_types::primitives::NATIVE_TOKEN_ID;
+use norn_types::weave::WeaveBlock;
+
+use super::handlers::{format_amount_for_token, parse_address, parse_loom_hex, parse_token_hex};
+use crate::state_manager::{EventFilter, EventRecord, StateManager, TransferRecord};
+use crate::wallet::format::format_address;
+
+/// HTTP path of the endpoint.
+pub const PATH: &str = "/graphql";
+
+/// Items per page when `first` is not given.
+const DEFAULT_PAGE_SIZE: usize = 20;
+
+/// Most items per page.
+const MAX_PAGE_SIZE: usize = 100;
+
+/// Most blocks examined for one page of `blocks`.
+const MAX_BLOCK_SCAN: u64 = 1_000;
+
+/// The node's GraphQL schema.
+pub type NornSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Build the schema over the node's state.
+pub fn schema(state_manager: Arc<RwLock<StateManager>>, network_id: NetworkId) -> NornSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(state_manager)
+        .data(network_id)
+        .limit_depth(8)
+        .limit_complexity(1_000)
+        .finish()
+}
+
+/// RPC module serving `norn_graphql`.
+pub fn rpc_module(
+    state_manager: Arc<RwLock<StateManager>>,
+    network_id: NetworkId,
+) -> RpcModule<NornSchema> {
+    let mut module = RpcModule::new(schema(state_manager, network_id));
+    module
+        .register_async_method("norn_graphql", |params, schema, _| async move {
+            let request: async_graphql::Request = params.one()?;
+            let response = schema.execute(request).await;
+            serde_json::to_value(response).map_err(|e| {
+                ErrorObjectOwned::owned(-32603, format!("GraphQL response: {}", e), None::<()>)
+            })
+        })
+        .expect("norn_graphql is registered once");
+    module
+}
+
+/// The GraphQL response to an HTTP query, from the JSON-RPC response to its
+/// `norn_graphql` call.
+pub fn http_response(body: &[u8]) -> http::Response<String> {
+    let mut response: serde_json::Map<String, serde_json::Value> =
+        match serde_json::from_slice(body) {
+            Ok(serde_json::Value::Object(response)) => response,
+            _ => return error_response(-32603, "invalid JSON-RPC response"),
+        };
+    if let Some(error) = response.remove("error") {
+        let code = error.get("code").and_then(|c| c.as_i64()).unwrap_or(-32603) as i32;
+        let message = error
+            .get("message")
+            .and_then(|m| m.as_str())
+            .unwrap_or("internal error");
+        return error_response(code, message);
+    }
+    let result = response.remove("result").unwrap_or_default();
+    json_response(http::StatusCode::OK, &result)
+}
+
+/// A GraphQL response reporting the JSON-RPC error `code`, with the HTTP
+/// status the REST gateway gives it.
+pub fn error_response(code: i32, message: &str) -> http::Response<String> {
+    let body = serde_json::json!({
+        "errors": [{ "message": message, "extensions": { "code": code } }],
+    });
+    json_response(super::rest::error_status(code), &body)
+}
+
+fn json_response(status: http::StatusCode, body: &serde_json::Value) -> http::Response<String> {
+    http::Response::builder()
+        .status(status)
+        .header(http::header::CONTENT_TYPE, "application/json")
+        .body(body.to_string())
+        .expect("valid response")
+}
+
+/// A block in the weave.
+#[derive(SimpleObject)]
+pub struct Block {
+    pub height: u64,
+    pub hash: String,
+    pub prev_hash: String,
+    pub timestamp: u64,
+    /// Proposer public key (hex).
+    pub proposer: String,
+    pub state_root: String,
+    pub commitment_count: usize,
+    pub transfer_count: usize,
+    pub token_mint_count: usize,
+    pub token_burn_count: usize,
+    pub loom_deploy_count: usize,
+    /// Time taken to produce the block, in microseconds, where known.
+    pub production_us: Option<u64>,
+}
+
+/// A token transfer.
+#[derive(SimpleObject)]
+pub struct Transfer {
+    pub knot_id: String,
+    pub from: String,
+    pub to: String,
+    pub token_id: String,
+    pub symbol: String,
+    /// Raw amount.
+    pub amount: String,
+    /// Amount in whole tokens.
+    pub human_readable: String,
+    pub memo: Option<String>,
+    pub timestamp: u64,
+    /// Height of the block that included it, once included.
+    pub block_height: Option<u64>,
+}
+
+/// A registered token.
+#[derive(SimpleObject)]
+pub struct Token {
+    pub id: String,
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub max_supply: String,
+    pub current_supply: String,
+    pub creator: String,
+    pub created_at: u64,
+    pub description: Option<String>,
+    pub icon_uri: Option<String>,
+}
+
+/// A deployed loom.
+#[derive(SimpleObject)]
+pub struct Loom {
+    pub id: String,
+    pub name: String,
+    /// Operator public key (hex).
+    pub operator: String,
+    pub active: bool,
+    pub deployed_at: u64,
+}
+
+/// An event emitted by a loom.
+#[derive(SimpleObject)]
+pub struct Event {
+    /// Position in the event log.
+    pub seq: u64,
+    pub loom_id: String,
+    /// Address whose execution emitted the event.
+    pub sender: String,
+    #[graphql(name = "type")]
+    pub ty: String,
+    pub attributes: Vec<Attribute>,
+    pub block_height: u64,
+    pub timestamp: u64,
+}
+
+/// A key-value attribute of an event.
+#[derive(SimpleObject)]
+pub struct Attribute {
+    pub key: String,
+    pub value: String,
+}
+
+/// Criteria for `blocks`. Unset fields match every block.
+#[derive(InputObject, Default)]
+pub struct BlockFilter {
+    /// Proposer public key (hex).
+    pub proposer: Option<String>,
+    pub from_height: Option<u64>,
+    pub to_height: Option<u64>,
+    /// Only blocks with at least one transfer.
+    pub has_transfers: Option<bool>,
+}
+
+/// Criteria for `transfers`. Unset fields match every transfer.
+#[derive(InputObject, Default)]
+pub struct TransferFilter {
+    /// Sender or recipient.
+    pub address: Option<String>,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    /// Token ID (hex).
+    pub token_id: Option<String>,
+    pub from_height: Option<u64>,
+    pub to_height: Option<u64>,
+}
+
+/// Criteria for `tokens`. Unset fields match every token.
+#[derive(InputObject, Default)]
+pub struct TokenFilter {
+    pub symbol: Option<String>,
+    pub creator: Option<String>,
+}
+
+/// Criteria for `looms`. Unset fields match every loom.
+#[derive(InputObject, Default)]
+pub struct LoomFilter {
+    pub name: Option<String>,
+    /// Operator public key (hex).
+    pub operator: Option<String>,
+    pub active: Option<bool>,
+}
+
+/// Criteria for `events`. Unset fields match every event.
+#[derive(InputObject, Default)]
+pub struct EventsFilter {
+    /// Loom ID (hex).
+    pub loom_id: Option<String>,
+    #[graphql(name = "type")]
+    pub ty: Option<String>,
+    /// Attribute key the event must carry.
+    pub attribute_key: Option<String>,
+    /// Required value of `attributeKey`.
+    pub attribute_value: Option<String>,
+    pub from_height: Option<u64>,
+    pub to_height: Option<u64>,
+}
+
+/// Root of all queries.
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// The block at a height.
+    async fn block(&self, ctx: &Context<'_>, height: u64) -> Option<Block> {
+        let sm = state(ctx).read().await;
+        sm.get_block_by_height(height)
+            .map(|block| block_node(&block, &sm))
+    }
+
+    /// Blocks from newest to oldest. At most 1,000 blocks are examined per
+    /// page; continue after the last one, or narrow `toHeight`.
+    async fn blocks(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<i32>,
+        after: Option<String>,
+        filter: Option<BlockFilter>,
+    ) -> async_graphql::Result<Connection<String, Block>> {
+        let first = page_size(first)?;
+        let filter = filter.unwrap_or_default();
+        let sm = state(ctx).read().await;
+
+        let mut high = filter.to_height.unwrap_or(u64::MAX);
+        high = high.min(sm.latest_block_height());
+        if let Some(after) = &after {
+            let after: u64 = after.parse().map_err(|_| "invalid cursor")?;
+            match after.checked_sub(1) {
+                Some(below) => high = high.min(below),
+                None => return Ok(Connection::new(true, false)),
+            }
+        }
+        let low = filter
+            .from_height
+            .unwrap_or(0)
+            .max(high.saturating_sub(MAX_BLOCK_SCAN - 1));
+        if low > high {
+            return Ok(Connection::new(after.is_some(), false));
+        }
+
+        let blocks = (low..=high)
+            .rev()
+            .filter_map(|height| sm.get_block_by_height(height))
+            .filter(|block| {
+                filter
+                    .proposer
+                    .as_ref()
+                    .is_none_or(|p| hex::encode(block.proposer) == p.to_lowercase())
+                    && filter
+                        .has_transfers
+                        .is_none_or(|has| has != block.transfers.is_empty())
+            })
+            .map(|block| (block.height.to_string(), block_node(&block, &sm)));
+        let mut connection = page(blocks, first, after.is_some());
+        // More blocks remain below the scanned range.
+        if low > filter.from_height.unwrap_or(0) {
+            connection.has_next_page = true;
+        }
+        Ok(connection)
+    }
+
+    /// Transfers from newest to oldest.
+    async fn transfers(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<i32>,
+        after: Option<String>,
+        filter: Option<TransferFilter>,
+    ) -> async_graphql::Result<Connection<String, Transfer>> {
+        let first = page_size(first)?;
+        let filter = filter.unwrap_or_default();
+        let network = *network(ctx);
+        let address = |a: &Option<String>| {
+            a.as_deref()
+                .map(|a| parse_address(a, network))
+                .transpose()
+                .map_err(rpc_error)
+        };
+        let (any, from, to) = (
+            address(&filter.address)?,
+            address(&filter.from)?,
+            address(&filter.to)?,
+        );
+        let token = filter
+            .token_id
+            .as_deref()
+            .map(parse_token_hex)
+            .transpose()
+            .map_err(rpc_error)?;
+        let before = after
+            .as_deref()
+            .map(parse_token_hex)
+            .transpose()
+            .map_err(|_| "invalid cursor")?;
+
+        let sm = state(ctx).read().await;
+        let transfers = sm
+            .transfers_before(before.as_ref())
+            .ok_or("cursor is no longer in the transfer log")?
+            .filter(|r| {
+                any.is_none_or(|a| r.from == a || r.to == a)
+                    && from.is_none_or(|a| r.from == a)
+                    && to.is_none_or(|a| r.to == a)
+                    && token.is_none_or(|t| r.token_id == t)
+                    && filter
+                        .from_height
+                        .is_none_or(|h| r.block_height.is_some_and(|b| b >= h))
+                    && filter
+                        .to_height
+                        .is_none_or(|h| r.block_height.is_some_and(|b| b <= h))
+            })
+            .map(|r| (hex::encode(r.knot_id), transfer_node(r, &sm)));
+        Ok(page(transfers, first, after.is_some()))
+    }
+
+    /// Registered tokens, by ID.
+    async fn tokens(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<i32>,
+        after: Option<String>,
+        filter: Option<TokenFilter>,
+    ) -> async_graphql::Result<Connection<String, Token>> {
+        let first = page_size(first)?;
+        let filter = filter.unwrap_or_default();
+        let creator = filter
+            .creator
+            .as_deref()
+            .map(|a| parse_address(a, *network(ctx)))
+            .transpose()
+            .map_err(rpc_error)?;
+        let after_id = after
+            .as_deref()
+            .map(parse_token_hex)
+            .transpose()
+            .map_err(|_| "invalid cursor")?;
+
+        let sm = state(ctx).read().await;
+        let mut tokens = sm.list_tokens();
+        tokens.sort_by_key(|(id, _)| **id);
+        let tokens = tokens
+            .into_iter()
+            .filter(|(id, record)| {
+                after_id.is_none_or(|after| **id > after)
+                    && filter
+                        .symbol
+                        .as_ref()
+                        .is_none_or(|s| record.symbol.eq_ignore_ascii_case(s))
+                    && creator.is_none_or(|c| record.creator == c)
+            })
+            .map(|(id, record)| {
+                let node = Token {
+                    id: hex::encode(id),
+                    name: record.name.clone(),
+                    symbol: record.symbol.clone(),
+                    decimals: record.decimals,
+                    max_supply: record.max_supply.to_string(),
+                    current_supply: record.current_supply.to_string(),
+                    creator: format_address(&record.creator),
+                    created_at: record.created_at,
+                    description: record.metadata.description.clone(),
+                    icon_uri: record.metadata.icon_uri.clone(),
+                };
+                (node.id.clone(), node)
+            });
+        Ok(page(tokens, first, after.is_some()))
+    }
+
+    /// Deployed looms, by ID.
+    async fn looms(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<i32>,
+        after: Option<String>,
+        filter: Option<LoomFilter>,
+    ) -> async_graphql::Result<Connection<String, Loom>> {
+        let first = page_size(first)?;
+        let filter = filter.unwrap_or_default();
+        let after_id = after
+            .as_deref()
+            .map(parse_loom_hex)
+            .transpose()
+            .map_err(|_| "invalid cursor")?;
+
+        let sm = state(ctx).read().await;
+        let mut looms = sm.list_looms();
+        looms.sort_by_key(|(id, _)| **id);
+        let looms = looms
+            .into_iter()
+            .filter(|(id, record)| {
+                after_id.is_none_or(|after| **id > after)
+                    && filter.name.as_ref().is_none_or(|n| record.name == *n)
+                    && filter
+                        .operator
+                        .as_ref()
+                        .is_none_or(|o| hex::encode(record.operator) == o.to_lowercase())
+                    && filter.active.is_none_or(|a| record.active == a)
+            })
+            .map(|(id, record)| {
+                let node = Loom {
+                    id: hex::encode(id),
+                    name: record.name.clone(),
+                    operator: hex::encode(record.operator),
+                    active: record.active,
+                    deployed_at: record.deployed_at,
+                };
+                (node.id.clone(), node)
+            });
+        Ok(page(looms, first, after.is_some()))
+    }
+
+    /// Loom events in emission order.
+    async fn events(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<i32>,
+        after: Option<String>,
+        filter: Option<EventsFilter>,
+    ) -> async_graphql::Result<Connection<String, Event>> {
+        let first = page_size(first)?;
+        let filter = filter.unwrap_or_default();
+        if filter.attribute_value.is_some() && filter.attribute_key.is_none() {
+            return Err("attributeValue requires attributeKey".into());
+        }
+        let filter = EventFilter {
+            loom_id: filter
+                .loom_id
+                .as_deref()
+                .map(parse_loom_hex)
+                .transpose()
+                .map_err(rpc_error)?,
+            ty: filter.ty,
+            attribute: filter.attribute_key.map(|k| (k, filter.attribute_value)),
+            from_height: filter.from_height,
+            to_height: filter.to_height,
+            after_seq: after
+                .as_deref()
+                .map(str::parse)
+                .transpose()
+                .map_err(|_| "invalid cursor")?,
+        };
+
+        let sm = state(ctx).read().await;
+        let events = sm
+            .get_events(&filter, first + 1, 0)
+            .into_iter()
+            .map(|event| (event.seq.to_string(), event_node(event)));
+        Ok(page(events, first, after.is_some()))
+    }
+}
+
+fn state<'a>(ctx: &Context<'a>) -> &'a Arc<RwLock<StateManager>> {
+    ctx.data_unchecked::<Arc<RwLock<StateManager>>>()
+}
+
+fn network<'a>(ctx: &Context<'a>) -> &'a NetworkId {
+    ctx.data_unchecked::<NetworkId>()
+}
+
+fn rpc_error(e: ErrorObjectOwned) -> async_graphql::Error {
+    async_graphql::Error::new(e.message())
+}
+
+fn page_size(first: Option<i32>) -> async_graphql::Result<usize> {
+    match first {
+        None => Ok(DEFAULT_PAGE_SIZE),
+        Some(n) if n >= 0 => Ok((n as usize).min(MAX_PAGE_SIZE)),
+        Some(_) => Err("first must not be negative".into()),
+    }
+}
+
+/// Collect up to `first` of `items` into a connection.
+fn page<T: OutputType>(
+    items: impl Iterator<Item = (String, T)>,
+    first: usize,
+    has_previous_page: bool,
+) -> Connection<String, T> {
+    let mut items: Vec<_> = items.take(first + 1).collect();
+    let has_next_page = items.len() > first;
+    items.truncate(first);
+    let mut connection = Connection::new(has_previous_page, has_next_page);
+    connection.edges.extend(
+        items
+            .into_iter()
+            .map(|(cursor, node)| Edge::new(cursor, node)),
+    );
+    connection
+}
+
+fn block_node(block: &WeaveBlock, sm: &StateManager) -> Block {
+    Block {
+        height: block.height,
+        hash: hex::encode(block.hash),
+        prev_hash: hex::encode(block.prev_hash),
+        timestamp: block.timestamp,
+        proposer: hex::encode(block.proposer),
+        state_root: hex::encode(block.state_root),
+        commitment_count: block.commitments.len(),
+        transfer_count: block.transfers.len(),
+        token_mint_count: block.token_mints.len(),
+        token_burn_count: block.token_burns.len(),
+        loom_deploy_count: block.loom_deploys.len(),
+        production_us: sm.get_block_production_us(block.height),
+    }
+}
+
+fn transfer_node(record: &TransferRecord, sm: &StateManager) -> Transfer {
+    Transfer {
+        knot_id: hex::encode(record.knot_id),
+        from: format_address(&record.from),
+        to: format_address(&record.to),
+        token_id: hex::encode(record.token_id),
+        symbol: if record.token_id == NATIVE_TOKEN_ID {
+            "NORN".to_string()
+        } else {
+            sm.get_token(&record.token_id)
+                .map(|t| t.symbol.clone())
+                .unwrap_or_else(|| hex::encode(&record.token_id[..4]))
+        },
+        amount: record.amount.to_string(),
+        human_readable: format_amount_for_token(record.amount, &record.token_id, sm),
+        memo: record
+            .memo
+            .as_ref()
+            .and_then(|m| String::from_utf8(m.clone()).ok()),
+        timestamp: record.timestamp,
+        block_height: record.block_height,
+    }
+}
+
+fn event_node(event: &EventRecord) -> Event {
+    Event {
+        seq: event.seq,
+        loom_id: hex::encode(event.loom_id),
+        sender: format_address(&event.sender),
+        ty: event.ty.clone(),
+        attributes: event
+            .attributes
+            .iter()
+            .map(|(key, value)| Attribute {
+                key: key.clone(),
+                value: value.clone(),
+            })
+            .collect(),
+        block_height: event.block_height,
+        timestamp: event.timestamp,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn test_schema(sm: StateManager) -> NornSchema {
+        schema(Arc::new(RwLock::new(sm)), NetworkId::Dev)
+    }
+
+    async fn query(schema: &NornSchema, query: &str) -> serde_json::Value {
+        let response = schema.execute(query).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        serde_json::to_value(response.data).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_transfers_paginate_newest_first() {
+        let mut sm = StateManager::new();
+        for i in 0..3u8 {
+            sm.log_synthetic_transfer([1; 20], [2 + i; 20], NATIVE_TOKEN_ID, 10, None, i as u64);
+        }
+        let schema = test_schema(sm);
+        let first = query(
+            &schema,
+            "{ transfers(first: 2) { nodes { timestamp } pageInfo { hasNextPage endCursor } } }",
+        )
+        .await;
+        assert_eq!(
+            first["transfers"]["nodes"],
+            json!([{ "timestamp": 2 }, { "timestamp": 1 }])
+        );
+        assert_eq!(first["transfers"]["pageInfo"]["hasNextPage"], true);
+
+        let cursor = first["transfers"]["pageInfo"]["endCursor"]
+            .as_str()
+            .unwrap();
+        let rest = query(
+            &schema,
+            &format!(
+                r#"{{ transfers(first: 2, after: "{}") {{ nodes {{ timestamp }} pageInfo {{ hasNextPage }} }} }}"#,
+                cursor
+            ),
+        )
+        .await;
+        assert_eq!(rest["transfers"]["nodes"], json!([{ "timestamp": 0 }]));
+        assert_eq!(rest["transfers"]["pageInfo"]["hasNextPage"], false);
+
+        let to = format_address(&[3; 20]);
+        let filtered = query(
+            &schema,
+            &format!(
+                r#"{{ transfers(filter: {{ to: "{}" }}) {{ nodes {{ to }} }} }}"#,
+                to
+            ),
+        )
+        .await;
+        assert_eq!(filtered["transfers"]["nodes"], json!([{ "to": to }]));
+    }
+
+    #[tokio::test]
+    async fn test_events_filter_and_cursor() {
+        let mut sm = StateManager::new();
+        sm.seed_events(
+            (0..4u64)
+                .map(|seq| EventRecord {
+                    seq,
+                    loom_id: [1; 32],
+                    sender: [9; 20],
+                    ty: if seq % 2 == 0 { "Transfer" } else { "Approval" }.to_string(),
+                    attributes: vec![("amount".to_string(), seq.to_string())],
+                    block_height: seq,
+                    timestamp: seq,
+                })
+                .collect(),
+        );
+        let schema = test_schema(sm);
+        let data = query(
+            &schema,
+            r#"{ events(first: 1, after: "0", filter: { type: "Transfer" }) {
+                edges { cursor node { seq type attributes { key value } } }
+                pageInfo { hasNextPage hasPreviousPage }
+            } }"#,
+        )
+        .await;
+        assert_eq!(
+            data["events"]["edges"],
+            json!([{
+                "cursor": "2",
+                "node": { "seq": 2, "type": "Transfer", "attributes": [{ "key": "amount", "value": "2" }] },
+            }])
+        );
+        assert_eq!(
+            data["events"]["pageInfo"],
+            json!({ "hasNextPage": false, "hasPreviousPage": true })
+        );
+    }
+}
@@ -0,0 +1,625 @@
+//! gRPC interface of the node.
+//!
+//! With `rpc.grpc_listen_addr` set, the node also serves the `norn.v1.Node`
+//! service of `proto/norn/v1/node.proto` on that address: the core queries,
+//! knot and commitment submission, and streams of new blocks and loom
+//! events, as typed protobuf messages. Each call is handled by the JSON-RPC
+//! method it mirrors and is subject to the same API keys, method allowlists,
+//! rate limits, size limits and timeout, under that method's name.
+//!
+//! API keys are sent as `authorization: Bearer <key>` metadata. JSON-RPC
+//! errors become gRPC statuses carrying the original code in the
+//! `norn-error-code` metadata.
+
+// `tonic::Status` is the error type of every gRPC call.
+#![allow(clippy::result_large_err)]
+
+use std::future::Future;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Instant;
+
+use jsonrpsee::types::ErrorObjectOwned;
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+use tokio_stream::wrappers::{BroadcastStream, TcpListenerStream};
+use tokio_stream::{Stream, StreamExt};
+use tonic::transport::ServerTlsConfig;
+use tonic::{Code, Request, Response, Status};
+
+use super::auth::{AuthError, RpcAuth, RpcCaller};
+use super::handlers::{
+    event_filter, indexed_event_info, NornRpcImpl, NornRpcServer, KNOT_EXPIRED_CODE,
+    KNOT_OUT_OF_ORDER_CODE, KNOT_REPLAYED_CODE, KNOT_TIMESTAMP_CODE,
+};
+use super::limits::{RpcLimits, FORWARDED_FOR};
+use super::types::{
+    BlockInfo, EventFilterParams, IndexedEventInfo, LoomInfo, SubmitResult, TokenInfo,
+    TransactionHistoryEntry,
+};
+use crate::error::NodeError;
+use crate::metrics::{NodeMetrics, RpcRejectReason};
+
+/// Generated protobuf messages, client and server.
+pub mod proto {
+    tonic::include_proto!("norn.v1");
+}
+
+use proto::node_server::{Node, NodeServer};
+
+type GrpcStream<T> = Pin<Box<dyn Stream<Item = Result<T, Status>> + Send>>;
+
+/// The `norn.v1.Node` service.
+pub struct NodeGrpc {
+    rpc: NornRpcImpl,
+    auth: Option<Arc<RpcAuth>>,
+    limits: Arc<RpcLimits>,
+    metrics: Arc<NodeMetrics>,
+}
+
+impl NodeGrpc {
+    /// Serve the calls with `rpc`, under the JSON-RPC server's auth and
+    /// limits.
+    pub fn new(
+        rpc: NornRpcImpl,
+        auth: Option<Arc<RpcAuth>>,
+        limits: Arc<RpcLimits>,
+        metrics: Arc<NodeMetrics>,
+    ) -> Self {
+        Self {
+            rpc,
+            auth,
+            limits,
+            metrics,
+        }
+    }
+
+    /// Check a call against the access rules and rate limits of the
+    /// JSON-RPC `method` it mirrors.
+    fn admit<T>(&self, request: &Request<T>, method: &str) -> Result<(), Status> {
+        let caller = match &self.auth {
+            Some(auth) => {
+                let bearer = request
+                    .metadata()
+                    .get("authorization")
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.strip_prefix("Bearer "));
+                let caller = auth
+                    .authenticate(bearer)
+                    .ok_or_else(|| Status::unauthenticated("unauthorized: invalid API key"))?;
+                if let Err(e) = auth.authorize(&caller, method) {
+                    tracing::debug!(method, error = %e, "gRPC call refused");
+                    let (reason, code) = match e {
+                        AuthError::Forbidden { .. } => {
+                            (RpcRejectReason::Forbidden, Code::PermissionDenied)
+                        }
+                        AuthError::RateLimited { .. } => {
+                            (RpcRejectReason::KeyRateLimited, Code::ResourceExhausted)
+                        }
+                    };
+                    self.metrics.rpc_rejected(reason);
+                    return Err(Status::new(code, e.to_string()));
+                }
+                caller
+            }
+            None => RpcCaller::Public,
+        };
+
+        // API keys carry their own limits.
+        if let (RpcCaller::Public, Some(addr)) = (&caller, request.remote_addr()) {
+            let forwarded_for = request
+                .metadata()
+                .get_all(FORWARDED_FOR)
+                .iter()
+                .filter_map(|v| v.to_str().ok());
+            let ip = self.limits.client_ip(addr.ip(), forwarded_for);
+            if let Err(e) = self.limits.check(ip, method, Instant::now()) {
+                tracing::debug!(method, ip = %ip, error = %e, "gRPC call refused");
+                self.metrics.rpc_rejected(e.reason());
+                return Err(Status::resource_exhausted(e.to_string()));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Serve `service` on `addr`, over TLS if `tls` is set, until `shutdown`
+/// completes.
+pub async fn serve(
+    addr: &str,
+    service: NodeGrpc,
+    tls: Option<ServerTlsConfig>,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) -> Result<SocketAddr, NodeError> {
+    let listener = tokio::net::TcpListener::bind(addr)
+        .await
+        .map_err(|e| NodeError::RpcError {
+            reason: format!("failed to bind gRPC server to {}: {}", addr, e),
+        })?;
+    let local_addr = listener.local_addr().map_err(|e| NodeError::RpcError {
+        reason: format!("failed to read gRPC server address: {}", e),
+    })?;
+
+    let limits = service.limits.clone();
+    let mut builder = tonic::transport::Server::builder();
+    if let Some(tls) = tls {
+        builder = builder.tls_config(tls).map_err(|e| NodeError::RpcError {
+            reason: format!("invalid gRPC TLS config: {}", e),
+        })?;
+    }
+    if let Some(timeout) = limits.request_timeout {
+        builder = builder.timeout(timeout);
+    }
+    let router = builder.add_service(
+        NodeServer::new(service)
+            .max_decoding_message_size(limits.max_request_body_bytes as usize)
+            .max_encoding_message_size(limits.max_response_body_bytes as usize),
+    );
+    tokio::spawn(async move {
+        if let Err(e) = router
+            .serve_with_incoming_shutdown(TcpListenerStream::new(listener), shutdown)
+            .await
+        {
+            tracing::warn!(error = %e, "gRPC server failed");
+        }
+    });
+    tracing::info!(addr = %local_addr, "gRPC server started");
+    Ok(local_addr)
+}
+
+#[tonic::async_trait]
+impl Node for NodeGrpc {
+    async fn get_health(
+        &self,
+        request: Request<proto::GetHealthRequest>,
+    ) -> Result<Response<proto::Health>, Status> {
+        self.admit(&request, "norn_health")?;
+        let info = self.rpc.health().await.map_err(status)?;
+        Ok(Response::new(proto::Health {
+            height: info.height,
+            is_validator: info.is_validator,
+            thread_count: info.thread_count,
+            status: info.status,
+            network: info.network,
+            chain_id: info.chain_id,
+            version: info.version,
+            block_time_target: info.block_time_target,
+            last_block_production_us: info.last_block_production_us,
+            live: info.live,
+            ready: info.ready,
+            issues: info
+                .issues
+                .iter()
+                .map(|issue| issue.as_str().to_string())
+                .collect(),
+            blocks_behind: info.blocks_behind,
+            mempool_size: info.mempool_size,
+            peer_count: info.peer_count,
+        }))
+    }
+
+    async fn get_block(
+        &self,
+        request: Request<proto::GetBlockRequest>,
+    ) -> Result<Response<proto::Block>, Status> {
+        self.admit(&request, "norn_getBlock")?;
+        let height = request.into_inner().height;
+        let block = self.rpc.get_block(height).await.map_err(status)?;
+        found(block.map(block_message), "block")
+    }
+
+    async fn get_latest_block(
+        &self,
+        request: Request<proto::GetLatestBlockRequest>,
+    ) -> Result<Response<proto::Block>, Status> {
+        self.admit(&request, "norn_getLatestBlock")?;
+        let block = self.rpc.get_latest_block().await.map_err(status)?;
+        found(block.map(block_message), "block")
+    }
+
+    async fn get_balance(
+        &self,
+        request: Request<proto::GetBalanceRequest>,
+    ) -> Result<Response<proto::Balance>, Status> {
+        self.admit(&request, "norn_getBalance")?;
+        let request = request.into_inner();
+        let token_id = if request.token_id.is_empty() {
+            norn_types::primitives::NATIVE_TOKEN_ID.to_vec()
+        } else {
+            request.token_id
+        };
+        let amount = self
+            .rpc
+            .get_balance(hex::encode(request.address), hex::encode(token_id))
+            .await
+            .map_err(status)?;
+        Ok(Response::new(proto::Balance { amount }))
+    }
+
+    async fn get_transaction(
+        &self,
+        request: Request<proto::GetTransactionRequest>,
+    ) -> Result<Response<proto::Transfer>, Status> {
+        self.admit(&request, "norn_getTransaction")?;
+        let knot_id = hex::encode(request.into_inner().knot_id);
+        let transfer = self.rpc.get_transaction(knot_id).await.map_err(status)?;
+        found(transfer.map(transfer_message), "transaction")
+    }
+
+    async fn get_transaction_history(
+        &self,
+        request: Request<proto::GetTransactionHistoryRequest>,
+    ) -> Result<Response<proto::TransferList>, Status> {
+        self.admit(&request, "norn_getTransactionHistory")?;
+        let request = request.into_inner();
+        let transfers = self
+            .rpc
+            .get_transaction_history(hex::encode(request.address), request.limit, request.offset)
+            .await
+            .map_err(status)?;
+        Ok(Response::new(proto::TransferList {
+            transfers: transfers.into_iter().map(transfer_message).collect(),
+        }))
+    }
+
+    async fn get_token(
+        &self,
+        request: Request<proto::GetTokenRequest>,
+    ) -> Result<Response<proto::Token>, Status> {
+        self.admit(&request, "norn_getTokenInfo")?;
+        let token_id = hex::encode(request.into_inner().token_id);
+        let token = self.rpc.get_token_info(token_id).await.map_err(status)?;
+        found(token.map(token_message), "token")
+    }
+
+    async fn get_loom(
+        &self,
+        request: Request<proto::GetLoomRequest>,
+    ) -> Result<Response<proto::Loom>, Status> {
+        self.admit(&request, "norn_getLoomInfo")?;
+        let loom_id = hex::encode(request.into_inner().loom_id);
+        let loom = self.rpc.get_loom_info(loom_id).await.map_err(status)?;
+        found(loom.map(loom_message), "loom")
+    }
+
+    async fn get_events(
+        &self,
+        request: Request<proto::GetEventsRequest>,
+    ) -> Result<Response<proto::EventList>, Status> {
+        self.admit(&request, "norn_getEvents")?;
+        let request = request.into_inner();
+        let events = self
+            .rpc
+            .get_events(
+                filter_params(request.filter.unwrap_or_default()),
+                request.limit,
+                request.offset,
+            )
+            .await
+            .map_err(status)?;
+        Ok(Response::new(proto::EventList {
+            events: events.into_iter().map(event_message).collect(),
+        }))
+    }
+
+    async fn submit_knot(
+        &self,
+        request: Request<proto::SubmitKnotRequest>,
+    ) -> Result<Response<proto::SubmitResponse>, Status> {
+        self.admit(&request, "norn_submitKnot")?;
+        let knot = hex::encode(request.into_inner().knot);
+        let result = self.rpc.submit_knot(knot).await.map_err(status)?;
+        Ok(Response::new(submit_message(result)))
+    }
+
+    async fn submit_commitment(
+        &self,
+        request: Request<proto::SubmitCommitmentRequest>,
+    ) -> Result<Response<proto::SubmitResponse>, Status> {
+        self.admit(&request, "norn_submitCommitment")?;
+        let commitment = hex::encode(request.into_inner().commitment);
+        let result = self
+            .rpc
+            .submit_commitment(commitment)
+            .await
+            .map_err(status)?;
+        Ok(Response::new(submit_message(result)))
+    }
+
+    type SubscribeBlocksStream = GrpcStream<proto::Block>;
+
+    async fn subscribe_blocks(
+        &self,
+        request: Request<proto::SubscribeBlocksRequest>,
+    ) -> Result<Response<Self::SubscribeBlocksStream>, Status> {
+        self.admit(&request, "norn_subscribeNewBlocks")?;
+        let rx = self.rpc.broadcasters.block_tx.subscribe();
+        let stream = BroadcastStream::new(rx).map(|block| match block {
+            Ok(block) => Ok(block_message(block)),
+            Err(e) => Err(lagged(e)),
+        });
+        Ok(Response::new(Box::pin(stream)))
+    }
+
+    type SubscribeEventsStream = GrpcStream<proto::Event>;
+
+    async fn subscribe_events(
+        &self,
+        request: Request<proto::SubscribeEventsRequest>,
+    ) -> Result<Response<Self::SubscribeEventsStream>, Status> {
+        self.admit(&request, "norn_subscribeEvents")?;
+        let filter = filter_params(request.into_inner().filter.unwrap_or_default());
+        let filter = event_filter(filter).map_err(status)?;
+        let rx = self.rpc.broadcasters.event_tx.subscribe();
+        let stream = BroadcastStream::new(rx).filter_map(move |event| match event {
+            Ok(event) => filter
+                .matches(&event)
+                .then(|| Ok(event_message(indexed_event_info(&event)))),
+            Err(e) => Some(Err(lagged(e))),
+        });
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
+
+/// The gRPC status for a JSON-RPC error.
+fn status(e: ErrorObjectOwned) -> Status {
+    let code = match e.code() {
+        -32600 | -32602 | -32700 => Code::InvalidArgument,
+        -32001 => Code::PermissionDenied,
+        -32005 => Code::ResourceExhausted,
+        -32008 => Code::DeadlineExceeded,
+        KNOT_EXPIRED_CODE | KNOT_TIMESTAMP_CODE | KNOT_OUT_OF_ORDER_CODE | KNOT_REPLAYED_CODE => {
+            Code::FailedPrecondition
+        }
+        _ => Code::Internal,
+    };
+    let mut status = Status::new(code, e.message());
+    status
+        .metadata_mut()
+        .insert("norn-error-code", e.code().into());
+    status
+}
+
+fn found<T>(item: Option<T>, what: &str) -> Result<Response<T>, Status> {
+    item.map(Response::new)
+        .ok_or_else(|| Status::not_found(format!("{} not found", what)))
+}
+
+/// A subscriber too slow for the broadcast channel loses its stream.
+fn lagged(e: BroadcastStreamRecvError) -> Status {
+    let BroadcastStreamRecvError::Lagged(missed) = e;
+    Status::data_loss(format!("subscriber fell behind by {} messages", missed))
+}
+
+/// Bytes of a hex string produced by the JSON-RPC handlers, with or without
+/// a `0x` prefix.
+fn bytes(hex_str: &str) -> Vec<u8> {
+    hex::decode(hex_str.strip_prefix("0x").unwrap_or(hex_str)).unwrap_or_default()
+}
+
+fn filter_params(filter: proto::EventFilter) -> EventFilterParams {
+    EventFilterParams {
+        loom_id: filter.loom_id.map(hex::encode),
+        ty: filter.r#type,
+        attribute_key: filter.attribute_key,
+        attribute_value: filter.attribute_value,
+        from_height: filter.from_height,
+        to_height: filter.to_height,
+    }
+}
+
+fn block_message(info: BlockInfo) -> proto::Block {
+    proto::Block {
+        height: info.height,
+        hash: bytes(&info.hash),
+        prev_hash: bytes(&info.prev_hash),
+        timestamp: info.timestamp,
+        proposer: bytes(&info.proposer),
+        state_root: bytes(&info.state_root),
+        commitment_count: info.commitment_count as u64,
+        registration_count: info.registration_count as u64,
+        anchor_count: info.anchor_count as u64,
+        fraud_proof_count: info.fraud_proof_count as u64,
+        name_registration_count: info.name_registration_count as u64,
+        name_transfer_count: info.name_transfer_count as u64,
+        name_record_update_count: info.name_record_update_count as u64,
+        transfer_count: info.transfer_count as u64,
+        token_definition_count: info.token_definition_count as u64,
+        token_mint_count: info.token_mint_count as u64,
+        token_burn_count: info.token_burn_count as u64,
+        loom_deploy_count: info.loom_deploy_count as u64,
+        stake_operation_count: info.stake_operation_count as u64,
+        production_us: info.production_us,
+    }
+}
+
+fn transfer_message(entry: TransactionHistoryEntry) -> proto::Transfer {
+    proto::Transfer {
+        knot_id: bytes(&entry.knot_id),
+        from: bytes(&entry.from),
+        to: bytes(&entry.to),
+        token_id: bytes(&entry.token_id),
+        symbol: entry.symbol,
+        amount: entry.amount,
+        human_readable: entry.human_readable,
+        memo: entry.memo,
+        timestamp: entry.timestamp,
+        block_height: entry.block_height,
+        direction: entry.direction,
+    }
+}
+
+fn token_message(info: TokenInfo) -> proto::Token {
+    proto::Token {
+        token_id: bytes(&info.token_id),
+        name: info.name,
+        symbol: info.symbol,
+        decimals: info.decimals.into(),
+        max_supply: info.max_supply,
+        current_supply: info.current_supply,
+        creator: bytes(&info.creator),
+        created_at: info.created_at,
+        description: info.description,
+        icon_uri: info.icon_uri,
+        project_url: info.project_url,
+        tags: info.tags,
+    }
+}
+
+fn loom_message(info: LoomInfo) -> proto::Loom {
+    proto::Loom {
+        loom_id: bytes(&info.loom_id),
+        name: info.name,
+        operator: bytes(&info.operator),
+        active: info.active,
+        deployed_at: info.deployed_at,
+        has_bytecode: info.has_bytecode,
+        code_hash: info.code_hash.as_deref().map(bytes),
+        participant_count: info.participant_count as u64,
+    }
+}
+
+fn event_message(info: IndexedEventInfo) -> proto::Event {
+    proto::Event {
+        seq: info.seq,
+        loom_id: bytes(&info.loom_id),
+        sender: bytes(&info.sender),
+        block_height: info.block_height,
+        timestamp: info.timestamp,
+        r#type: info.ty,
+        attributes: info
+            .attributes
+            .into_iter()
+            .map(|a| proto::Attribute {
+                key: a.key,
+                value: a.value,
+            })
+            .collect(),
+    }
+}
+
+fn submit_message(result: SubmitResult) -> proto::SubmitResponse {
+    proto::SubmitResponse {
+        success: result.success,
+        reason: result.reason,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::NodeConfig;
+    use crate::health::NodeHealth;
+    use crate::rpc::server::RpcBroadcasters;
+    use crate::state_manager::{EventRecord, StateManager};
+    use norn_crypto::keys::Keypair;
+    use norn_thread::proposal::KnotCollector;
+    use norn_types::network::NetworkId;
+    use norn_types::weave::ValidatorSet;
+    use proto::node_client::NodeClient;
+    use tokio::sync::RwLock;
+
+    #[tokio::test]
+    async fn test_grpc_queries_limits_and_streams() {
+        let mut rpc = NodeConfig::default().rpc;
+        rpc.method_rate_limits = [("norn_getBlock".to_string(), 1)].into_iter().collect();
+        let broadcasters = RpcBroadcasters::new();
+        let validators = ValidatorSet {
+            validators: vec![],
+            total_stake: 0,
+            epoch: 0,
+        };
+        let service = NodeGrpc::new(
+            NornRpcImpl {
+                weave_engine: Arc::new(RwLock::new(norn_weave::engine::WeaveEngine::new(
+                    Keypair::generate(),
+                    validators,
+                    crate::node::default_weave_state(),
+                ))),
+                state_manager: Arc::new(RwLock::new(StateManager::new())),
+                loom_manager: Arc::new(RwLock::new(norn_loom::lifecycle::LoomManager::new())),
+                metrics: Arc::new(NodeMetrics::new()),
+                broadcasters: broadcasters.clone(),
+                relay_handle: None,
+                network_id: NetworkId::Dev,
+                is_validator: false,
+                faucet_tracker: std::sync::Mutex::new(Default::default()),
+                last_block_production_us: Arc::new(std::sync::Mutex::new(None)),
+                chat_store: Default::default(),
+                knot_collector: Arc::new(std::sync::Mutex::new(KnotCollector::new())),
+                health: Arc::new(NodeHealth::new(Default::default())),
+            },
+            None,
+            Arc::new(RpcLimits::from_config(&rpc).unwrap()),
+            Arc::new(NodeMetrics::new()),
+        );
+        let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+        let addr = serve("127.0.0.1:0", service, None, async {
+            let _ = stopped.await;
+        })
+        .await
+        .unwrap();
+        let mut client = NodeClient::connect(format!("http://{}", addr))
+            .await
+            .unwrap();
+
+        let balance = client
+            .get_balance(proto::GetBalanceRequest {
+                address: vec![7; 20],
+                token_id: vec![],
+            })
+            .await
+            .unwrap();
+        assert_eq!(balance.into_inner().amount, "0");
+        let error = client
+            .get_balance(proto::GetBalanceRequest {
+                address: vec![7; 3],
+                token_id: vec![],
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), Code::InvalidArgument);
+        assert_eq!(error.metadata().get("norn-error-code").unwrap(), "-32602");
+
+        let error = client
+            .get_block(proto::GetBlockRequest { height: 5 })
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), Code::NotFound);
+        let error = client
+            .get_block(proto::GetBlockRequest { height: 5 })
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), Code::ResourceExhausted);
+
+        let mut events = client
+            .subscribe_events(proto::SubscribeEventsRequest {
+                filter: Some(proto::EventFilter {
+                    r#type: Some("Transfer".to_string()),
+                    ..Default::default()
+                }),
+            })
+            .await
+            .unwrap()
+            .into_inner();
+        for (seq, ty) in [(0, "Approval"), (1, "Transfer")] {
+            broadcasters
+                .event_tx
+                .send(EventRecord {
+                    seq,
+                    loom_id: [1; 32],
+                    sender: [2; 20],
+                    ty: ty.to_string(),
+                    attributes: vec![],
+                    block_height: 1,
+                    timestamp: 1,
+                })
+                .unwrap();
+        }
+        let event = events.message().await.unwrap().unwrap();
+        assert_eq!(event.seq, 1);
+        assert_eq!(event.sender, vec![2; 20]);
+
+        stop.send(()).unwrap();
+    }
+}
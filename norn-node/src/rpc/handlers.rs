@@ -7,33 +7,52 @@ use jsonrpsee::proc_macros::rpc;
 use jsonrpsee::types::ErrorObjectOwned;
 use jsonrpsee::PendingSubscriptionSink;
 
+use norn_thread::proposal::{KnotCollector, PendingKnot};
 use norn_types::network::NornMessage;
 use norn_weave::engine::WeaveEngine;
 
 use norn_loom::lifecycle::LoomManager;
 
 use super::types::{
-    AttributeInfo, BlockInfo, BlockLoomDeployInfo, BlockNameRecordUpdateInfo,
-    BlockNameRegistrationInfo, BlockNameTransferInfo, BlockTokenBurnInfo, BlockTokenDefinitionInfo,
-    BlockTokenMintInfo, BlockTransactionsInfo, BlockTransferInfo, ChatEvent, CommitmentProofInfo,
-    EventInfo, ExecutionResult, FeeEstimateInfo, HealthInfo, LoomExecutionEvent, LoomInfo,
-    NameInfo, NameResolution, PendingTransactionEvent, QueryResult, StakingInfo, StateProofInfo,
-    SubmitResult, ThreadInfo, ThreadStateInfo, TokenEvent, TokenInfo, TransactionHistoryEntry,
-    TransferEvent, ValidatorInfo, ValidatorRewardInfo, ValidatorRewardsInfo, ValidatorSetInfo,
-    ValidatorStakeInfo, WeaveStateInfo,
+    AddressInfo, AttributeInfo, BlockFeeInfo, BlockHeaderInfo, BlockInfo, BlockLoomDeployInfo,
+    BlockNameRecordUpdateInfo, BlockNameRegistrationInfo, BlockNameTransferInfo,
+    BlockTokenBurnInfo, BlockTokenDefinitionInfo, BlockTokenMintInfo, BlockTransactionsInfo,
+    BlockTransferInfo, ChatEvent, CodeInfo, CommitmentProofInfo, DelegationInfo, DisputeInfo,
+    EstimatedFeeInfo, EventFilterParams, EventInfo, ExecutionResult, ExecutionTraceInfo,
+    FeeEstimateInfo, FeeHistoryInfo, GasScheduleInfo, HealthInfo, HostCallInfo, HostGasInfo,
+    IndexedEventInfo, KnotProposalInfo, LoomExecutionEvent, LoomInfo, LoomStateExportInfo,
+    MempoolCommitmentInfo, MempoolInfo, ModuleDiagnosticInfo, NameInfo, NameResolution, OneOrMany,
+    PeerInfo, PendingTransactionEvent, QueryResult, SlashRecordInfo, SpindleCoverageInfo,
+    SpindleInfo, SpindleSubscriptionInfo, StakingInfo, StateMultiProofInfo, StateProofBalance,
+    StateProofInfo, StateProofResult, StoreCodeResult, SubmitResult, ThreadInfo, ThreadStateInfo,
+    TokenEvent, TokenInfo, TransactionHistoryEntry, TransferEvent, TransferProofInfo,
+    UploadBytecodeResult, ValidatorInfo, ValidatorRewardInfo, ValidatorRewardsInfo,
+    ValidatorSetInfo, ValidatorStakeInfo, WeaveStateInfo,
 };
+use crate::health::NodeHealth;
+use crate::logging::LogLevels;
 use crate::metrics::NodeMetrics;
 use crate::rpc::chat_store::{ChatEventStore, ChatHistoryFilter};
 use crate::rpc::server::RpcBroadcasters;
-use crate::state_manager::StateManager;
-use norn_types::constants::{MAX_SUPPLY, NORN_DECIMALS, TRANSFER_FEE};
+use crate::state_manager::{
+    EventFilter, EventRecord, HistoricalBalances, LedgerBalances, StateManager, TokenRecord,
+};
+use norn_types::constants::{
+    DEFAULT_FEE_CONFIDENCE, FEE_ESTIMATE_WINDOW, FEE_HISTORY_BLOCKS, MAX_SUPPLY, NORN_DECIMALS,
+    TRANSFER_FEE,
+};
+use norn_types::error::NornError;
 use norn_types::primitives::NATIVE_TOKEN_ID;
 
 use crate::wallet::format::{format_address, format_amount_with_symbol, format_token_amount};
 
 /// Format an amount using the correct decimals for the given token.
 /// Returns just the numeric string (no symbol) for programmatic use by frontends.
-fn format_amount_for_token(amount: u128, token_id: &[u8; 32], sm: &StateManager) -> String {
+pub(super) fn format_amount_for_token(
+    amount: u128,
+    token_id: &[u8; 32],
+    sm: &StateManager,
+) -> String {
     if *token_id == NATIVE_TOKEN_ID {
         format_token_amount(amount, NORN_DECIMALS as u8)
     } else if let Some(record) = sm.get_token(token_id) {
@@ -90,6 +109,23 @@ pub trait NornRpc {
         thread_id: String,
     ) -> Result<Option<ThreadStateInfo>, ErrorObjectOwned>;
 
+    /// Get balance for an address and token as of a past block height.
+    #[method(name = "norn_getBalanceAt")]
+    async fn get_balance_at(
+        &self,
+        address: String,
+        token_id: String,
+        height: u64,
+    ) -> Result<String, ErrorObjectOwned>;
+
+    /// Get thread state info as of a past block height.
+    #[method(name = "norn_getThreadStateAt")]
+    async fn get_thread_state_at(
+        &self,
+        thread_id: String,
+        height: u64,
+    ) -> Result<Option<ThreadStateInfo>, ErrorObjectOwned>;
+
     /// Request testnet faucet tokens (testnet-only, returns error in production builds).
     #[method(name = "norn_faucet")]
     async fn faucet(&self, address: String) -> Result<SubmitResult, ErrorObjectOwned>;
@@ -98,6 +134,19 @@ pub trait NornRpc {
     #[method(name = "norn_submitKnot")]
     async fn submit_knot(&self, knot: String) -> Result<SubmitResult, ErrorObjectOwned>;
 
+    /// Propose or sign a multi-party knot (hex-encoded borsh bytes, unsigned
+    /// slots all zeros). Signatures are merged with the copy already held;
+    /// once every participant has signed, the knot is submitted.
+    #[method(name = "norn_proposeKnot")]
+    async fn propose_knot(&self, knot: String) -> Result<KnotProposalInfo, ErrorObjectOwned>;
+
+    /// Get a multi-party knot still waiting for signatures.
+    #[method(name = "norn_getKnotProposal")]
+    async fn get_knot_proposal(
+        &self,
+        knot_id: String,
+    ) -> Result<Option<KnotProposalInfo>, ErrorObjectOwned>;
+
     /// Health check endpoint.
     #[method(name = "norn_health")]
     async fn health(&self) -> Result<HealthInfo, ErrorObjectOwned>;
@@ -110,6 +159,23 @@ pub trait NornRpc {
     #[method(name = "norn_getFeeEstimate")]
     async fn get_fee_estimate(&self) -> Result<FeeEstimateInfo, ErrorObjectOwned>;
 
+    /// Estimate the per-commitment fee that will be enough with
+    /// `confidence` percent (default 50) certainty, from recent blocks.
+    #[method(name = "norn_estimateFee")]
+    async fn estimate_fee(
+        &self,
+        confidence: Option<u8>,
+    ) -> Result<EstimatedFeeInfo, ErrorObjectOwned>;
+
+    /// Get the fees charged in the last `n_blocks` blocks (1 to 1,024).
+    #[method(name = "norn_getFeeHistory")]
+    async fn get_fee_history(&self, n_blocks: u64) -> Result<FeeHistoryInfo, ErrorObjectOwned>;
+
+    /// Inspect the mempool: item counts and up to `limit` (default 100)
+    /// pending commitments, highest priority first.
+    #[method(name = "norn_getMempool")]
+    async fn get_mempool(&self, limit: Option<usize>) -> Result<MempoolInfo, ErrorObjectOwned>;
+
     /// Get a Merkle commitment proof for a thread.
     #[method(name = "norn_getCommitmentProof")]
     async fn get_commitment_proof(
@@ -133,6 +199,11 @@ pub trait NornRpc {
     #[subscription(name = "norn_subscribeLoomEvents" => "norn_loomEvents", unsubscribe = "norn_unsubscribeLoomEvents", item = LoomExecutionEvent)]
     async fn subscribe_loom_events(&self, loom_id_hex: Option<String>) -> SubscriptionResult;
 
+    /// Subscribe to indexed loom events matching a filter on loom ID, event
+    /// type, and attribute value.
+    #[subscription(name = "norn_subscribeEvents" => "norn_events", unsubscribe = "norn_unsubscribeEvents", item = IndexedEventInfo)]
+    async fn subscribe_events(&self, filter: EventFilterParams) -> SubscriptionResult;
+
     /// Subscribe to pending transactions entering the mempool.
     #[subscription(name = "norn_subscribePendingTransactions" => "norn_pendingTransactions", unsubscribe = "norn_unsubscribePendingTransactions", item = PendingTransactionEvent)]
     async fn subscribe_pending_transactions(&self) -> SubscriptionResult;
@@ -228,6 +299,10 @@ pub trait NornRpc {
     #[method(name = "norn_getMetrics")]
     async fn get_metrics(&self) -> Result<String, ErrorObjectOwned>;
 
+    /// Get connected peers with their reputation scores.
+    #[method(name = "norn_getPeers")]
+    async fn get_peers(&self) -> Result<Vec<PeerInfo>, ErrorObjectOwned>;
+
     /// Submit a fraud proof (hex-encoded borsh bytes).
     #[method(name = "norn_submitFraudProof")]
     async fn submit_fraud_proof(
@@ -247,6 +322,13 @@ pub trait NornRpc {
     #[method(name = "norn_burnToken")]
     async fn burn_token(&self, token_burn_hex: String) -> Result<SubmitResult, ErrorObjectOwned>;
 
+    /// Update a token's metadata (hex-encoded borsh TokenMetadataUpdate).
+    #[method(name = "norn_updateTokenMetadata")]
+    async fn update_token_metadata(
+        &self,
+        update_hex: String,
+    ) -> Result<SubmitResult, ErrorObjectOwned>;
+
     /// Get token info by token ID (hex).
     #[method(name = "norn_getTokenInfo")]
     async fn get_token_info(
@@ -286,7 +368,8 @@ pub trait NornRpc {
 
     /// Upload bytecode to a deployed loom and initialize it.
     /// Optionally pass init_msg_hex for typed constructor parameters.
-    /// Requires operator signature for authorization.
+    /// Requires operator signature for authorization. Modules that fail
+    /// validation are rejected with diagnostics.
     #[method(name = "norn_uploadLoomBytecode")]
     async fn upload_loom_bytecode(
         &self,
@@ -295,7 +378,32 @@ pub trait NornRpc {
         init_msg_hex: Option<String>,
         operator_signature_hex: String,
         operator_pubkey_hex: String,
-    ) -> Result<SubmitResult, ErrorObjectOwned>;
+    ) -> Result<UploadBytecodeResult, ErrorObjectOwned>;
+
+    /// Store reusable bytecode that looms can instantiate by code ID.
+    /// Unlike `norn_uploadLoomBytecode`, this does not deploy a loom.
+    #[method(name = "norn_storeCode")]
+    async fn store_code(
+        &self,
+        bytecode_hex: String,
+        pubkey_hex: String,
+        signature_hex: String,
+    ) -> Result<StoreCodeResult, ErrorObjectOwned>;
+
+    /// List stored code with pagination.
+    #[method(name = "norn_listCodes")]
+    async fn list_codes(&self, limit: u64, offset: u64) -> Result<Vec<CodeInfo>, ErrorObjectOwned>;
+
+    /// Get stored code by code ID, including the bytecode.
+    #[method(name = "norn_getCode")]
+    async fn get_code(&self, code_id_hex: String) -> Result<Option<CodeInfo>, ErrorObjectOwned>;
+
+    /// Get the loom gas schedule active at a block height (default: current height).
+    #[method(name = "norn_getGasSchedule")]
+    async fn get_gas_schedule(
+        &self,
+        height: Option<u64>,
+    ) -> Result<GasScheduleInfo, ErrorObjectOwned>;
 
     /// Execute a loom contract (state-mutating). Requires sender signature.
     #[method(name = "norn_executeLoom")]
@@ -316,6 +424,81 @@ pub trait NornRpc {
         input_hex: String,
     ) -> Result<QueryResult, ErrorObjectOwned>;
 
+    /// Query a loom contract (read-only) against its state as of a past
+    /// block height.
+    #[method(name = "norn_queryLoomAt")]
+    async fn query_loom_at(
+        &self,
+        loom_id_hex: String,
+        input_hex: String,
+        height: u64,
+    ) -> Result<QueryResult, ErrorObjectOwned>;
+
+    /// Trace a loom execution without committing it: host calls, gas
+    /// breakdown, debug logs, and events. Returns a trace on failure too.
+    #[method(name = "norn_traceLoomExecution")]
+    async fn trace_loom_execution(
+        &self,
+        loom_id_hex: String,
+        input_hex: String,
+        sender_hex: String,
+    ) -> Result<ExecutionTraceInfo, ErrorObjectOwned>;
+
+    /// Export a loom's state as a snapshot with its state hash and Merkle root.
+    #[method(name = "norn_exportLoomState")]
+    async fn export_loom_state(
+        &self,
+        loom_id_hex: String,
+    ) -> Result<LoomStateExportInfo, ErrorObjectOwned>;
+
+    /// Replace a loom's state with a verified snapshot (for migration and
+    /// restoring backups). Requires operator signature for authorization.
+    #[method(name = "norn_importLoomState")]
+    async fn import_loom_state(
+        &self,
+        loom_id_hex: String,
+        snapshot_hex: String,
+        operator_signature_hex: String,
+        operator_pubkey_hex: String,
+    ) -> Result<SubmitResult, ErrorObjectOwned>;
+
+    /// Dispute a loom execution with a hex-encoded borsh `EvidenceBundle`.
+    /// The evidence is re-executed immediately and the losing party's bond
+    /// is paid to the winner. Requires challenger signature.
+    #[method(name = "norn_submitDispute")]
+    async fn submit_dispute(
+        &self,
+        evidence_hex: String,
+        challenger_hex: String,
+        pubkey_hex: String,
+        signature_hex: String,
+    ) -> Result<DisputeInfo, ErrorObjectOwned>;
+
+    /// Get a dispute by ID (hex).
+    #[method(name = "norn_getDispute")]
+    async fn get_dispute(
+        &self,
+        dispute_id_hex: String,
+    ) -> Result<Option<DisputeInfo>, ErrorObjectOwned>;
+
+    /// List disputes in submission order, optionally filtered by loom.
+    #[method(name = "norn_listDisputes")]
+    async fn list_disputes(
+        &self,
+        loom_id_hex: Option<String>,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<DisputeInfo>, ErrorObjectOwned>;
+
+    /// Query indexed loom events in emission order.
+    #[method(name = "norn_getEvents")]
+    async fn get_events(
+        &self,
+        filter: EventFilterParams,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<IndexedEventInfo>, ErrorObjectOwned>;
+
     /// Join a loom as a participant.
     #[method(name = "norn_joinLoom")]
     async fn join_loom(
@@ -336,6 +519,26 @@ pub trait NornRpc {
         pubkey_hex: String,
     ) -> Result<SubmitResult, ErrorObjectOwned>;
 
+    /// Set a loom participant's role (operator, submitter, or observer).
+    /// Requires a loom operator's signature.
+    #[method(name = "norn_setParticipantRole")]
+    async fn set_participant_role(
+        &self,
+        loom_id_hex: String,
+        participant_hex: String,
+        role: String,
+        operator_pubkey_hex: String,
+        signature_hex: String,
+    ) -> Result<SubmitResult, ErrorObjectOwned>;
+
+    /// Submit a loom operator key handover (hex-encoded borsh
+    /// `OperatorHandover`) for inclusion in the next block.
+    #[method(name = "norn_submitOperatorHandover")]
+    async fn submit_operator_handover(
+        &self,
+        handover_hex: String,
+    ) -> Result<SubmitResult, ErrorObjectOwned>;
+
     /// Submit a stake operation (hex-encoded borsh StakeOperation).
     #[method(name = "norn_stake")]
     async fn stake(&self, operation_hex: String) -> Result<SubmitResult, ErrorObjectOwned>;
@@ -344,6 +547,69 @@ pub trait NornRpc {
     #[method(name = "norn_unstake")]
     async fn unstake(&self, operation_hex: String) -> Result<SubmitResult, ErrorObjectOwned>;
 
+    /// Submit a delegate operation (hex-encoded borsh StakeOperation).
+    #[method(name = "norn_delegate")]
+    async fn delegate(&self, operation_hex: String) -> Result<SubmitResult, ErrorObjectOwned>;
+
+    /// Submit an undelegate operation (hex-encoded borsh StakeOperation).
+    #[method(name = "norn_undelegate")]
+    async fn undelegate(&self, operation_hex: String) -> Result<SubmitResult, ErrorObjectOwned>;
+
+    /// Get the delegations made by a delegator public key.
+    #[method(name = "norn_getDelegations")]
+    async fn get_delegations(
+        &self,
+        delegator_hex: String,
+    ) -> Result<Vec<DelegationInfo>, ErrorObjectOwned>;
+
+    /// Submit an unjail operation (hex-encoded borsh StakeOperation).
+    #[method(name = "norn_unjail")]
+    async fn unjail(&self, operation_hex: String) -> Result<SubmitResult, ErrorObjectOwned>;
+
+    /// Submit evidence that a validator double-signed (hex-encoded borsh
+    /// `SlashingEvidence`) for inclusion in the next block.
+    #[method(name = "norn_submitSlashingEvidence")]
+    async fn submit_slashing_evidence(
+        &self,
+        evidence_hex: String,
+    ) -> Result<SubmitResult, ErrorObjectOwned>;
+
+    /// Get the slashings applied so far, oldest first (all validators or
+    /// specific).
+    #[method(name = "norn_getSlashingHistory")]
+    async fn get_slashing_history(
+        &self,
+        pubkey_hex: Option<String>,
+    ) -> Result<Vec<SlashRecordInfo>, ErrorObjectOwned>;
+
+    /// Submit a spindle market operation (hex-encoded borsh
+    /// `SpindleOperation`): bond, unbond, subscribe, or unsubscribe.
+    #[method(name = "norn_submitSpindleOperation")]
+    async fn submit_spindle_operation(
+        &self,
+        operation_hex: String,
+    ) -> Result<SubmitResult, ErrorObjectOwned>;
+
+    /// List bonded spindles, cheapest fee first.
+    #[method(name = "norn_getSpindles")]
+    async fn get_spindles(&self) -> Result<Vec<SpindleInfo>, ErrorObjectOwned>;
+
+    /// Get the spindles paid to watch a thread.
+    #[method(name = "norn_getSpindleSubscriptions")]
+    async fn get_spindle_subscriptions(
+        &self,
+        address_hex: String,
+    ) -> Result<Vec<SpindleSubscriptionInfo>, ErrorObjectOwned>;
+
+    /// Get the spindles advertising coverage of a thread, and whether at
+    /// least `redundancy` (default 3) of them watch it.
+    #[method(name = "norn_getSpindleCoverage")]
+    async fn get_spindle_coverage(
+        &self,
+        address_hex: String,
+        redundancy: Option<usize>,
+    ) -> Result<SpindleCoverageInfo, ErrorObjectOwned>;
+
     /// Get validator reward distribution info.
     #[method(name = "norn_getValidatorRewards")]
     async fn get_validator_rewards(&self) -> Result<ValidatorRewardsInfo, ErrorObjectOwned>;
@@ -359,13 +625,14 @@ pub trait NornRpc {
     #[method(name = "norn_getStateRoot")]
     async fn get_state_root(&self) -> Result<String, ErrorObjectOwned>;
 
-    /// Get a state proof for a balance.
+    /// Get a state proof for a balance. Given a list of addresses and/or
+    /// token IDs, returns one multiproof covering every balance.
     #[method(name = "norn_getStateProof")]
     async fn get_state_proof(
         &self,
-        address_hex: String,
-        token_id_hex: Option<String>,
-    ) -> Result<StateProofInfo, ErrorObjectOwned>;
+        address_hex: OneOrMany<String>,
+        token_id_hex: Option<OneOrMany<String>>,
+    ) -> Result<StateProofResult, ErrorObjectOwned>;
 
     /// Get detailed transactions for a block by height.
     #[method(name = "norn_getBlockTransactions")]
@@ -373,6 +640,35 @@ pub trait NornRpc {
         &self,
         height: u64,
     ) -> Result<Option<BlockTransactionsInfo>, ErrorObjectOwned>;
+
+    /// Get consecutive block headers starting at a height, for light clients.
+    #[method(name = "norn_getBlockHeaders")]
+    async fn get_block_headers(
+        &self,
+        from_height: u64,
+        count: u64,
+    ) -> Result<Vec<BlockHeaderInfo>, ErrorObjectOwned>;
+
+    /// Get an SPV proof for an included transfer, for verifying deposits
+    /// without trusting this node.
+    #[method(name = "norn_getTransferProof")]
+    async fn get_transfer_proof(
+        &self,
+        knot_id: String,
+    ) -> Result<Option<TransferProofInfo>, ErrorObjectOwned>;
+
+    /// Convert an address between hex and this network's bech32m form.
+    #[method(name = "norn_convertAddress")]
+    async fn convert_address(&self, address: String) -> Result<AddressInfo, ErrorObjectOwned>;
+
+    /// Get the node's log levels.
+    #[method(name = "norn_getLogLevels")]
+    async fn get_log_levels(&self) -> Result<LogLevels, ErrorObjectOwned>;
+
+    /// Replace the node's log levels until it restarts. Returns the levels
+    /// now in effect.
+    #[method(name = "norn_setLogLevels")]
+    async fn set_log_levels(&self, levels: LogLevels) -> Result<LogLevels, ErrorObjectOwned>;
 }
 
 /// Implementation of the NornRpc trait.
@@ -391,26 +687,83 @@ pub struct NornRpcImpl {
     pub last_block_production_us: Arc<std::sync::Mutex<Option<u64>>>,
     /// In-memory bounded store for chat events (channels, messages, profiles, DMs).
     pub chat_store: Arc<std::sync::RwLock<ChatEventStore>>,
+    /// Multi-party knots waiting for signatures (shared with the node's gossip loop).
+    pub knot_collector: Arc<std::sync::Mutex<KnotCollector>>,
+    /// Liveness and readiness (shared with the node's main loop).
+    pub health: Arc<NodeHealth>,
 }
 
-/// Parse a hex string into a 20-byte address.
-fn parse_address_hex(hex_str: &str) -> Result<[u8; 20], ErrorObjectOwned> {
-    let bytes = hex::decode(hex_str)
-        .map_err(|e| ErrorObjectOwned::owned(-32602, format!("invalid hex: {}", e), None::<()>))?;
-    if bytes.len() != 20 {
-        return Err(ErrorObjectOwned::owned(
-            -32602,
-            format!("address must be 20 bytes, got {}", bytes.len()),
-            None::<()>,
-        ));
+/// JSON-RPC error code for a knot past its expiry.
+pub const KNOT_EXPIRED_CODE: i32 = -32010;
+/// JSON-RPC error code for a knot timestamped too far in the future.
+pub const KNOT_TIMESTAMP_CODE: i32 = -32011;
+/// JSON-RPC error code for a knot below a participant's knot sequence.
+pub const KNOT_OUT_OF_ORDER_CODE: i32 = -32012;
+/// JSON-RPC error code for a knot that was already applied.
+pub const KNOT_REPLAYED_CODE: i32 = -32013;
+
+/// Map a replay-protection failure to a JSON-RPC error with a distinct code.
+fn knot_rejection(e: NornError) -> ErrorObjectOwned {
+    let code = match e {
+        NornError::KnotExpired { .. } => KNOT_EXPIRED_CODE,
+        NornError::TimestampTooFuture { .. } | NornError::TimestampBeforePrevious { .. } => {
+            KNOT_TIMESTAMP_CODE
+        }
+        NornError::KnotOutOfOrder { .. } => KNOT_OUT_OF_ORDER_CODE,
+        NornError::KnotReplayed => KNOT_REPLAYED_CODE,
+        _ => -32602,
+    };
+    ErrorObjectOwned::owned(code, e.to_string(), None::<()>)
+}
+
+/// Describe a multi-party knot collecting signatures.
+fn knot_proposal_info(pending: &PendingKnot) -> KnotProposalInfo {
+    let knot = pending.knot();
+    KnotProposalInfo {
+        knot_id: hex::encode(pending.id()),
+        knot: hex::encode(borsh::to_vec(knot).unwrap_or_default()),
+        participants: knot
+            .before_states
+            .iter()
+            .map(|p| format_address(&p.thread_id))
+            .collect(),
+        missing: pending.missing().iter().map(format_address).collect(),
+        deadline: pending.deadline(),
+        submitted: false,
+        reason: None,
+    }
+}
+
+/// Describe a registered token, including its metadata.
+fn token_info(token_id: &[u8; 32], record: &TokenRecord) -> TokenInfo {
+    let metadata = record.metadata.clone();
+    TokenInfo {
+        token_id: hex::encode(token_id),
+        name: record.name.clone(),
+        symbol: record.symbol.clone(),
+        decimals: record.decimals,
+        max_supply: record.max_supply.to_string(),
+        current_supply: record.current_supply.to_string(),
+        creator: format_address(&record.creator),
+        created_at: record.created_at,
+        description: metadata.description,
+        icon_uri: metadata.icon_uri,
+        project_url: metadata.project_url,
+        tags: metadata.tags,
     }
-    let mut addr = [0u8; 20];
-    addr.copy_from_slice(&bytes);
-    Ok(addr)
+}
+
+/// Parse a hex or bech32m address; bech32m addresses must be for `network`.
+pub(super) fn parse_address(
+    address: &str,
+    network: norn_types::network::NetworkId,
+) -> Result<[u8; 20], ErrorObjectOwned> {
+    norn_crypto::address::parse_address(address, network)
+        .map_err(|e| ErrorObjectOwned::owned(-32602, e.to_string(), None::<()>))
 }
 
 /// Parse a hex string into a 32-byte token ID.
-fn parse_token_hex(hex_str: &str) -> Result<[u8; 32], ErrorObjectOwned> {
+pub(super) fn parse_token_hex(hex_str: &str) -> Result<[u8; 32], ErrorObjectOwned> {
     let bytes = hex::decode(hex_str)
         .map_err(|e| ErrorObjectOwned::owned(-32602, format!("invalid hex: {}", e), None::<()>))?;
     if bytes.len() != 32 {
@@ -426,7 +779,7 @@ fn parse_token_hex(hex_str: &str) -> Result<[u8; 32], ErrorObjectOwned> {
 }
 
 /// Parse a hex-encoded loom ID into a [u8; 32].
-fn parse_loom_hex(hex_str: &str) -> Result<[u8; 32], ErrorObjectOwned> {
+pub(super) fn parse_loom_hex(hex_str: &str) -> Result<[u8; 32], ErrorObjectOwned> {
     let bytes = hex::decode(hex_str)
         .map_err(|e| ErrorObjectOwned::owned(-32602, format!("invalid hex: {}", e), None::<()>))?;
     if bytes.len() != 32 {
@@ -441,6 +794,216 @@ fn parse_loom_hex(hex_str: &str) -> Result<[u8; 32], ErrorObjectOwned> {
     Ok(id)
 }
 
+/// Build the RPC view of a thread's state.
+fn thread_state_info(
+    thread_id_hex: String,
+    state: &norn_types::thread::ThreadState,
+    meta: Option<&crate::state_manager::ThreadMeta>,
+    sm: &StateManager,
+) -> ThreadStateInfo {
+    let owner = meta.map(|m| hex::encode(m.owner)).unwrap_or_default();
+    let version = meta.map(|m| m.version).unwrap_or(0);
+    let nonce = meta.map(|m| m.nonce).unwrap_or(0);
+    let state_hash = meta
+        .map(|m| hex::encode(m.state_hash))
+        .unwrap_or_else(|| hex::encode([0u8; 32]));
+
+    let balances = state
+        .balances
+        .iter()
+        .map(|(token_id, &amount)| super::types::BalanceEntry {
+            token_id: hex::encode(token_id),
+            symbol: if *token_id == NATIVE_TOKEN_ID {
+                Some("NORN".to_string())
+            } else {
+                sm.get_token(token_id).map(|t| t.symbol.clone())
+            },
+            amount: amount.to_string(),
+            human_readable: format_amount_for_token(amount, token_id, sm),
+        })
+        .collect();
+
+    ThreadStateInfo {
+        thread_id: thread_id_hex,
+        owner,
+        version,
+        nonce,
+        state_hash,
+        balances,
+    }
+}
+
+/// Reject historical queries for heights the node has not reached yet or
+/// has already pruned.
+fn check_history_height(sm: &StateManager, height: u64) -> Result<(), ErrorObjectOwned> {
+    let latest = sm.latest_block_height();
+    if height > latest {
+        return Err(ErrorObjectOwned::owned(
+            -32602,
+            format!("height {} is above the latest block {}", height, latest),
+            None::<()>,
+        ));
+    }
+    let earliest = sm
+        .earliest_history_height()
+        .map_err(|e| ErrorObjectOwned::owned(-32603, e.to_string(), None::<()>))?;
+    if height < earliest {
+        return Err(ErrorObjectOwned::owned(
+            -32602,
+            format!(
+                "height {} has been pruned; history starts at {}",
+                height, earliest
+            ),
+            None::<()>,
+        ));
+    }
+    Ok(())
+}
+
+/// Convert a loom query outcome into its RPC representation.
+fn query_result(
+    result: Result<norn_loom::lifecycle::QueryOutcome, norn_loom::error::LoomError>,
+) -> QueryResult {
+    match result {
+        Ok(outcome) => {
+            let events: Vec<EventInfo> = outcome
+                .events
+                .iter()
+                .map(|e| EventInfo {
+                    ty: e.ty.clone(),
+                    attributes: e
+                        .attributes
+                        .iter()
+                        .map(|(k, v)| AttributeInfo {
+                            key: k.clone(),
+                            value: v.clone(),
+                        })
+                        .collect(),
+                })
+                .collect();
+            QueryResult {
+                success: true,
+                output_hex: Some(hex::encode(&outcome.output)),
+                gas_used: outcome.gas_used,
+                logs: outcome.logs,
+                events,
+                reason: None,
+            }
+        }
+        Err(e) => QueryResult {
+            success: false,
+            output_hex: None,
+            gas_used: 0,
+            logs: Vec::new(),
+            events: Vec::new(),
+            reason: Some(e.to_string()),
+        },
+    }
+}
+
+/// Convert RPC event filter parameters into an index filter.
+pub(super) fn event_filter(params: EventFilterParams) -> Result<EventFilter, ErrorObjectOwned> {
+    if params.attribute_value.is_some() && params.attribute_key.is_none() {
+        return Err(ErrorObjectOwned::owned(
+            -32602,
+            "attribute_value requires attribute_key",
+            None::<()>,
+        ));
+    }
+    Ok(EventFilter {
+        loom_id: params.loom_id.as_deref().map(parse_loom_hex).transpose()?,
+        ty: params.ty,
+        attribute: params.attribute_key.map(|k| (k, params.attribute_value)),
+        from_height: params.from_height,
+        to_height: params.to_height,
+        after_seq: None,
+    })
+}
+
+/// Convert an indexed loom event into its RPC representation.
+pub(super) fn indexed_event_info(event: &EventRecord) -> IndexedEventInfo {
+    IndexedEventInfo {
+        seq: event.seq,
+        loom_id: hex::encode(event.loom_id),
+        sender: format_address(&event.sender),
+        block_height: event.block_height,
+        timestamp: event.timestamp,
+        ty: event.ty.clone(),
+        attributes: event
+            .attributes
+            .iter()
+            .map(|(k, v)| AttributeInfo {
+                key: k.clone(),
+                value: v.clone(),
+            })
+            .collect(),
+    }
+}
+
+/// Convert a loom dispute into its RPC representation.
+fn dispute_info(dispute: &norn_loom::dispute::Dispute) -> DisputeInfo {
+    DisputeInfo {
+        dispute_id: hex::encode(dispute.id),
+        loom_id: hex::encode(dispute.evidence.loom_id),
+        challenger: hex::encode(dispute.challenger),
+        defendant: hex::encode(dispute.defendant),
+        bond: dispute.bond.to_string(),
+        status: dispute.status.as_str().to_string(),
+        prior_state_root: hex::encode(dispute.evidence.prior_state_root()),
+        claimed_output_root: hex::encode(dispute.evidence.claimed_output_root),
+        computed_output_root: dispute.computed_output_root.map(hex::encode),
+        reason: dispute.reason.clone(),
+        opened_at: dispute.opened_at,
+        resolved_at: dispute.resolved_at,
+    }
+}
+
+impl NornRpcImpl {
+    /// Apply a key rotation, guardian update or recovery knot and queue it
+    /// for the next block.
+    async fn submit_key_update(
+        &self,
+        knot: norn_types::knot::Knot,
+    ) -> Result<SubmitResult, ErrorObjectOwned> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        norn_thread::validation::validate_knot_window(&knot, now).map_err(knot_rejection)?;
+
+        let mut sm = self.state_manager.write().await;
+        norn_thread::validation::validate_knot_sequence(&knot, |thread_id| {
+            sm.next_knot_sequence(thread_id)
+        })
+        .map_err(knot_rejection)?;
+        if let Err(e) = sm.apply_key_update(&knot) {
+            return Ok(SubmitResult {
+                success: false,
+                reason: Some(e.to_string()),
+            });
+        }
+        sm.advance_knot_sequence(&knot);
+        drop(sm);
+        self.metrics.knots_validated.inc();
+
+        if let Err(e) = self.weave_engine.write().await.add_key_update(knot.clone()) {
+            tracing::warn!("key update not queued for the weave: {}", e);
+        }
+
+        if let Some(ref handle) = self.relay_handle {
+            let h = handle.clone();
+            let msg = NornMessage::KnotProposal(Box::new(knot));
+            tokio::spawn(async move {
+                let _ = h.broadcast(msg).await;
+            });
+        }
+        Ok(SubmitResult {
+            success: true,
+            reason: None,
+        })
+    }
+}
+
 #[async_trait]
 impl NornRpcServer for NornRpcImpl {
     async fn get_block(&self, height: u64) -> Result<Option<BlockInfo>, ErrorObjectOwned> {
@@ -635,7 +1198,7 @@ impl NornRpcServer for NornRpcImpl {
         &self,
         thread_id_hex: String,
     ) -> Result<Option<ThreadInfo>, ErrorObjectOwned> {
-        let thread_id = parse_address_hex(&thread_id_hex)?;
+        let thread_id = parse_address(&thread_id_hex, self.network_id)?;
 
         let sm = self.state_manager.read().await;
         if let Some(meta) = sm.get_thread_meta(&thread_id) {
@@ -643,7 +1206,15 @@ impl NornRpcServer for NornRpcImpl {
                 thread_id: thread_id_hex,
                 owner: hex::encode(meta.owner),
                 version: meta.version,
+                nonce: meta.nonce,
                 state_hash: hex::encode(meta.state_hash),
+                key_epoch: meta.key_epoch,
+                cosigners: meta
+                    .cosigners
+                    .iter()
+                    .flat_map(|set| set.cosigners.iter().map(hex::encode))
+                    .collect(),
+                cosigner_threshold: meta.cosigners.as_ref().map_or(0, |set| set.threshold),
             }))
         } else {
             // Fallback: check WeaveEngine known_threads.
@@ -654,7 +1225,11 @@ impl NornRpcServer for NornRpcImpl {
                     thread_id: thread_id_hex,
                     owner: String::new(),
                     version: 0,
+                    nonce: 0,
                     state_hash: hex::encode([0u8; 32]),
+                    key_epoch: 0,
+                    cosigners: Vec::new(),
+                    cosigner_threshold: 0,
                 }))
             } else {
                 Ok(None)
@@ -667,7 +1242,7 @@ impl NornRpcServer for NornRpcImpl {
         address_hex: String,
         token_id_hex: String,
     ) -> Result<String, ErrorObjectOwned> {
-        let address = parse_address_hex(&address_hex)?;
+        let address = parse_address(&address_hex, self.network_id)?;
         let token_id = parse_token_hex(&token_id_hex)?;
 
         let sm = self.state_manager.read().await;
@@ -679,37 +1254,53 @@ impl NornRpcServer for NornRpcImpl {
         &self,
         thread_id_hex: String,
     ) -> Result<Option<ThreadStateInfo>, ErrorObjectOwned> {
-        let thread_id = parse_address_hex(&thread_id_hex)?;
+        let thread_id = parse_address(&thread_id_hex, self.network_id)?;
 
         let sm = self.state_manager.read().await;
-        if let Some(state) = sm.get_thread_state(&thread_id) {
-            let meta = sm.get_thread_meta(&thread_id);
-            let owner = meta.map(|m| hex::encode(m.owner)).unwrap_or_default();
-            let version = meta.map(|m| m.version).unwrap_or(0);
-            let state_hash = meta
-                .map(|m| hex::encode(m.state_hash))
-                .unwrap_or_else(|| hex::encode([0u8; 32]));
-
-            let balances = state
-                .balances
-                .iter()
-                .map(|(token_id, &amount)| super::types::BalanceEntry {
-                    token_id: hex::encode(token_id),
-                    amount: amount.to_string(),
-                    human_readable: format_amount_for_token(amount, token_id, &sm),
-                })
-                .collect();
+        Ok(sm.get_thread_state(&thread_id).map(|state| {
+            thread_state_info(thread_id_hex, state, sm.get_thread_meta(&thread_id), &sm)
+        }))
+    }
 
-            Ok(Some(ThreadStateInfo {
-                thread_id: thread_id_hex,
-                owner,
-                version,
-                state_hash,
-                balances,
-            }))
-        } else {
-            Ok(None)
-        }
+    async fn get_balance_at(
+        &self,
+        address_hex: String,
+        token_id_hex: String,
+        height: u64,
+    ) -> Result<String, ErrorObjectOwned> {
+        let address = parse_address(&address_hex, self.network_id)?;
+        let token_id = parse_token_hex(&token_id_hex)?;
+
+        let sm = self.state_manager.read().await;
+        check_history_height(&sm, height)?;
+        let balance = sm
+            .balance_at(&address, &token_id, height)
+            .map_err(|e| ErrorObjectOwned::owned(-32603, e.to_string(), None::<()>))?;
+        Ok(balance.to_string())
+    }
+
+    async fn get_thread_state_at(
+        &self,
+        thread_id_hex: String,
+        height: u64,
+    ) -> Result<Option<ThreadStateInfo>, ErrorObjectOwned> {
+        let thread_id = parse_address(&thread_id_hex, self.network_id)?;
+
+        let sm = self.state_manager.read().await;
+        check_history_height(&sm, height)?;
+        let internal = |e: norn_storage::error::StorageError| {
+            ErrorObjectOwned::owned(-32603, e.to_string(), None::<()>)
+        };
+        let Some(state) = sm.thread_state_at(&thread_id, height).map_err(internal)? else {
+            return Ok(None);
+        };
+        let meta = sm.thread_meta_at(&thread_id, height).map_err(internal)?;
+        Ok(Some(thread_state_info(
+            thread_id_hex,
+            &state,
+            meta.as_ref(),
+            &sm,
+        )))
     }
 
     // Faucet: testnet-only endpoint that bypasses signature verification
@@ -738,7 +1329,7 @@ impl NornRpcServer for NornRpcImpl {
                 ));
             }
 
-            let address = parse_address_hex(&address_hex)?;
+            let address = parse_address(&address_hex, self.network_id)?;
 
             // Rate limiting: check cooldown per address.
             let now = std::time::SystemTime::now()
@@ -916,22 +1507,34 @@ impl NornRpcServer for NornRpcImpl {
             ErrorObjectOwned::owned(-32602, format!("invalid knot: {}", e), None::<()>)
         })?;
 
-        // Extract transfer details from the payload.
-        let (from, to, token_id, amount, memo) = match &knot.payload {
-            norn_types::knot::KnotPayload::Transfer(transfer) => (
-                transfer.from,
-                transfer.to,
-                transfer.token_id,
-                transfer.amount,
-                transfer.memo.clone(),
-            ),
+        if norn_thread::rotation::is_key_update(&knot) {
+            return self.submit_key_update(knot).await;
+        }
+
+        // Collect the transfers the knot performs; a multi-transfer is
+        // validated as a whole and applied atomically.
+        let is_multi = match &knot.payload {
+            norn_types::knot::KnotPayload::Transfer(_) => false,
+            norn_types::knot::KnotPayload::MultiTransfer(multi) => {
+                if let Err(e) = norn_thread::validation::validate_multi_transfer_payload(multi) {
+                    return Ok(SubmitResult {
+                        success: false,
+                        reason: Some(e.to_string()),
+                    });
+                }
+                true
+            }
             _ => {
                 return Ok(SubmitResult {
                     success: false,
-                    reason: Some("only Transfer knots are supported via RPC".to_string()),
+                    reason: Some(
+                        "only Transfer and MultiTransfer knots are supported via RPC".to_string(),
+                    ),
                 });
             }
         };
+        let legs = norn_thread::knot::transfer_legs(&knot);
+        let from = legs[0].1.from;
 
         // Validate: at least one signature, and the first before_state pubkey matches the signer.
         if knot.signatures.is_empty() {
@@ -974,129 +1577,286 @@ impl NornRpcServer for NornRpcImpl {
             }
         }
 
-        // Verify the sender's pubkey actually derives the claimed `from` address.
-        let sender_pubkey = knot.before_states[0].pubkey;
-        if norn_crypto::address::pubkey_to_address(&sender_pubkey) != from {
+        // Verify every sender is a signing participant. A multi-transfer may
+        // move funds for several participants (e.g. an atomic swap).
+        if is_multi {
+            if let Err(e) = norn_thread::validation::validate_transfer_senders(&knot) {
+                return Ok(SubmitResult {
+                    success: false,
+                    reason: Some(e.to_string()),
+                });
+            }
+        } else if knot.before_states[0].thread_id != from {
             return Ok(SubmitResult {
                 success: false,
-                reason: Some("sender pubkey does not derive the claimed from address".to_string()),
+                reason: Some("sender thread does not match the claimed from address".to_string()),
             });
         }
 
-        // Validate before-state hash (non-zero means the client is providing a
+        // Replay protection: the knot must be inside its validity window.
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        norn_thread::validation::validate_knot_window(&knot, now).map_err(knot_rejection)?;
+
+        // Validate before-state hashes (non-zero means the client is providing a
         // real state snapshot — reject if it doesn't match the current thread).
-        let submitted_state_hash = knot.before_states[0].state_hash;
-        if submitted_state_hash != [0u8; 32] {
+        {
             let sm_read = self.state_manager.read().await;
-            if let Some(meta) = sm_read.get_thread_meta(&from) {
-                if submitted_state_hash != meta.state_hash {
-                    return Ok(SubmitResult {
-                        success: false,
-                        reason: Some(format!(
-                            "stale before-state: submitted {} but current is {}",
-                            hex::encode(submitted_state_hash),
-                            hex::encode(meta.state_hash),
-                        )),
-                    });
+            for participant in &knot.before_states {
+                let submitted_state_hash = participant.state_hash;
+                if submitted_state_hash == [0u8; 32] {
+                    continue;
+                }
+                if let Some(meta) = sm_read.get_thread_meta(&participant.thread_id) {
+                    if submitted_state_hash != meta.state_hash {
+                        return Ok(SubmitResult {
+                            success: false,
+                            reason: Some(format!(
+                                "stale before-state: submitted {} but current is {}",
+                                hex::encode(submitted_state_hash),
+                                hex::encode(meta.state_hash),
+                            )),
+                        });
+                    }
                 }
             }
-            drop(sm_read);
         }
 
-        // Apply transfer via StateManager (sender_pubkey already extracted above).
+        // Apply the transfers via StateManager. Under the write lock, the knot
+        // must continue each participant's knot sequence and not be applied yet.
         let mut sm = self.state_manager.write().await;
-        sm.auto_register_with_pubkey(from, sender_pubkey);
-        sm.auto_register_if_needed(to);
+        norn_thread::validation::validate_knot_sequence(&knot, |thread_id| {
+            sm.next_knot_sequence(thread_id)
+        })
+        .map_err(knot_rejection)?;
+        if legs.iter().any(|(leg_id, _)| sm.has_transfer(leg_id)) {
+            return Err(knot_rejection(NornError::KnotReplayed));
+        }
+        // Each participant must sign with its thread's current owner key.
+        if let Some(p) = knot
+            .before_states
+            .iter()
+            .find(|p| !sm.is_thread_owner(&p.thread_id, &p.pubkey))
+        {
+            return Ok(SubmitResult {
+                success: false,
+                reason: Some(format!(
+                    "key does not control thread {}",
+                    format_address(&p.thread_id)
+                )),
+            });
+        }
+        // Threads with cosigners also need enough of their signatures.
+        if let Err(e) = sm.check_cosignatures(&knot) {
+            return Ok(SubmitResult {
+                success: false,
+                reason: Some(e.to_string()),
+            });
+        }
+        for participant in &knot.before_states {
+            sm.auto_register_with_pubkey(participant.thread_id, participant.pubkey);
+        }
+        for (_, transfer) in &legs {
+            sm.auto_register_if_needed(transfer.to);
+        }
 
         let knot_id = knot.id;
         let timestamp = knot.timestamp;
-        match sm.apply_transfer(from, to, token_id, amount, knot_id, memo.clone(), timestamp) {
-            Ok(()) => {
-                let token_symbol = if token_id == NATIVE_TOKEN_ID {
+        let applied = if is_multi {
+            sm.apply_multi_transfer(&legs, timestamp)
+        } else {
+            let transfer = legs[0].1;
+            sm.apply_transfer(
+                from,
+                transfer.to,
+                transfer.token_id,
+                transfer.amount,
+                knot_id,
+                transfer.memo.clone(),
+                timestamp,
+            )
+        };
+        if let Err(e) = applied {
+            return Ok(SubmitResult {
+                success: false,
+                reason: Some(e.to_string()),
+            });
+        }
+        sm.advance_knot_sequence(&knot);
+
+        let native = norn_types::primitives::NATIVE_TOKEN_ID;
+        let transfer_events: Vec<TransferEvent> = legs
+            .iter()
+            .map(|(_, transfer)| {
+                let token_id = transfer.token_id;
+                let token_symbol = if token_id == native {
                     "NORN".to_string()
                 } else {
                     sm.get_token(&token_id)
                         .map(|t| t.symbol.clone())
                         .unwrap_or_else(|| hex::encode(&token_id[..4]))
                 };
-                let human_readable = format_amount_for_token(amount, &token_id, &sm);
-                drop(sm);
-                self.metrics.knots_validated.inc();
-
-                // Fire pending transaction event.
-                let _ = self.broadcasters.pending_tx.send(PendingTransactionEvent {
-                    tx_type: "transfer".to_string(),
-                    hash: hex::encode(knot_id),
-                    from: format_address(&from),
-                    timestamp,
-                });
-
-                // Queue BlockTransfer so solo-mode blocks include this transfer.
-                let bt = norn_types::weave::BlockTransfer {
-                    from,
-                    to,
-                    token_id,
-                    amount,
-                    memo: memo.clone(),
-                    knot_id,
-                    timestamp,
-                };
-                let mut engine = self.weave_engine.write().await;
-                let _ = engine.add_transfer(bt);
-                drop(engine);
-
-                // Fire transfer event for subscribers.
-                let native = norn_types::primitives::NATIVE_TOKEN_ID;
-                let _ = self.broadcasters.transfer_tx.send(TransferEvent {
-                    from: format_address(&from),
-                    to: format_address(&to),
-                    amount: amount.to_string(),
-                    human_readable,
+                TransferEvent {
+                    from: format_address(&transfer.from),
+                    to: format_address(&transfer.to),
+                    amount: transfer.amount.to_string(),
+                    human_readable: format_amount_for_token(transfer.amount, &token_id, &sm),
                     token_id: if token_id == native {
                         None
                     } else {
                         Some(hex::encode(token_id))
                     },
                     symbol: Some(token_symbol),
-                    memo: memo
+                    memo: transfer
+                        .memo
                         .as_ref()
                         .and_then(|m| String::from_utf8(m.clone()).ok()),
                     block_height: None, // Pending — not yet in a block.
-                });
-
-                if let Some(ref handle) = self.relay_handle {
-                    let h = handle.clone();
-                    let msg = NornMessage::KnotProposal(Box::new(knot));
-                    tokio::spawn(async move {
-                        let _ = h.broadcast(msg).await;
-                    });
                 }
-                Ok(SubmitResult {
-                    success: true,
-                    reason: None,
-                })
-            }
-            Err(e) => Ok(SubmitResult {
-                success: false,
-                reason: Some(e.to_string()),
-            }),
+            })
+            .collect();
+        drop(sm);
+        self.metrics.knots_validated.inc();
+
+        // Fire pending transaction event.
+        let _ = self.broadcasters.pending_tx.send(PendingTransactionEvent {
+            tx_type: if is_multi {
+                "multi_transfer".to_string()
+            } else {
+                "transfer".to_string()
+            },
+            hash: hex::encode(knot_id),
+            from: format_address(&from),
+            timestamp,
+        });
+
+        // Queue each BlockTransfer so solo-mode blocks include the transfers.
+        let mut engine = self.weave_engine.write().await;
+        for (leg_id, transfer) in &legs {
+            let bt = norn_types::weave::BlockTransfer {
+                from: transfer.from,
+                to: transfer.to,
+                token_id: transfer.token_id,
+                amount: transfer.amount,
+                memo: transfer.memo.clone(),
+                knot_id: *leg_id,
+                timestamp,
+            };
+            let _ = engine.add_transfer(bt);
+        }
+        drop(engine);
+
+        // Fire transfer events for subscribers.
+        for event in transfer_events {
+            let _ = self.broadcasters.transfer_tx.send(event);
+        }
+
+        if let Some(ref handle) = self.relay_handle {
+            let h = handle.clone();
+            let msg = NornMessage::KnotProposal(Box::new(knot));
+            tokio::spawn(async move {
+                let _ = h.broadcast(msg).await;
+            });
+        }
+        Ok(SubmitResult {
+            success: true,
+            reason: None,
+        })
+    }
+
+    async fn propose_knot(&self, knot_hex: String) -> Result<KnotProposalInfo, ErrorObjectOwned> {
+        let bytes = hex::decode(&knot_hex).map_err(|e| {
+            ErrorObjectOwned::owned(-32602, format!("invalid hex: {}", e), None::<()>)
+        })?;
+        let knot: norn_types::knot::Knot = borsh::from_slice(&bytes).map_err(|e| {
+            ErrorObjectOwned::owned(-32602, format!("invalid knot: {}", e), None::<()>)
+        })?;
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let (pending, is_new) = {
+            let mut collector = self
+                .knot_collector
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            let is_new = collector.get(&knot.id).is_none();
+            let pending = collector
+                .collect(&knot, now)
+                .map_err(|e| ErrorObjectOwned::owned(-32602, e.to_string(), None::<()>))?;
+            (pending, is_new)
+        };
+
+        let mut info = knot_proposal_info(&pending);
+        if pending.is_complete() {
+            let complete = pending
+                .into_knot()
+                .map_err(|e| ErrorObjectOwned::owned(-32603, e.to_string(), None::<()>))?;
+            let complete_hex = hex::encode(borsh::to_vec(&complete).map_err(|e| {
+                ErrorObjectOwned::owned(-32603, format!("serialize: {}", e), None::<()>)
+            })?);
+            info.knot = complete_hex.clone();
+            let result = self.submit_knot(complete_hex).await?;
+            info.submitted = result.success;
+            info.reason = result.reason;
+        } else if let Some(ref handle) = self.relay_handle {
+            // Gossip the partially signed knot so the other participants'
+            // nodes collect it and their signatures meet here.
+            let knot = Box::new(pending.knot().clone());
+            let msg = if is_new {
+                NornMessage::KnotProposal(knot)
+            } else {
+                NornMessage::KnotResponse(knot)
+            };
+            let h = handle.clone();
+            tokio::spawn(async move {
+                let _ = h.broadcast(msg).await;
+            });
         }
+        Ok(info)
+    }
+
+    async fn get_knot_proposal(
+        &self,
+        knot_id: String,
+    ) -> Result<Option<KnotProposalInfo>, ErrorObjectOwned> {
+        let id: [u8; 32] = hex::decode(&knot_id)
+            .map_err(|e| {
+                ErrorObjectOwned::owned(-32602, format!("invalid hex: {}", e), None::<()>)
+            })?
+            .try_into()
+            .map_err(|_| ErrorObjectOwned::owned(-32602, "knot_id must be 32 bytes", None::<()>))?;
+        let collector = self
+            .knot_collector
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        Ok(collector.get(&id).map(knot_proposal_info))
     }
 
     async fn health(&self) -> Result<HealthInfo, ErrorObjectOwned> {
         let engine = self.weave_engine.read().await;
         let state = engine.weave_state();
+        let report = self.health.report();
 
         Ok(HealthInfo {
             height: state.height,
             is_validator: self.is_validator,
             thread_count: state.thread_count,
-            status: "ok".to_string(),
+            status: report.status,
             network: self.network_id.as_str().to_string(),
             chain_id: self.network_id.chain_id().to_string(),
             version: env!("CARGO_PKG_VERSION").to_string(),
             block_time_target: norn_types::constants::BLOCK_TIME_TARGET.as_secs(),
             last_block_production_us: self.last_block_production_us.lock().ok().and_then(|v| *v),
+            live: report.live,
+            ready: report.ready,
+            issues: report.issues,
+            blocks_behind: report.blocks_behind,
+            mempool_size: report.mempool_size,
+            peer_count: report.peer_count,
         })
     }
 
@@ -1133,6 +1893,86 @@ impl NornRpcServer for NornRpcImpl {
         })
     }
 
+    async fn estimate_fee(
+        &self,
+        confidence: Option<u8>,
+    ) -> Result<EstimatedFeeInfo, ErrorObjectOwned> {
+        let confidence = confidence.unwrap_or(DEFAULT_FEE_CONFIDENCE);
+        if confidence > 100 {
+            return Err(ErrorObjectOwned::owned(
+                -32602,
+                "confidence must be between 0 and 100",
+                None::<()>,
+            ));
+        }
+        let engine = self.weave_engine.read().await;
+        Ok(EstimatedFeeInfo {
+            fee: engine.estimate_fee(confidence).to_string(),
+            confidence,
+            current_fee: engine.fee_estimate().to_string(),
+            blocks_sampled: engine.fee_history().len().min(FEE_ESTIMATE_WINDOW),
+        })
+    }
+
+    async fn get_fee_history(&self, n_blocks: u64) -> Result<FeeHistoryInfo, ErrorObjectOwned> {
+        if n_blocks == 0 || n_blocks > FEE_HISTORY_BLOCKS as u64 {
+            return Err(ErrorObjectOwned::owned(
+                -32602,
+                format!("n_blocks must be between 1 and {}", FEE_HISTORY_BLOCKS),
+                None::<()>,
+            ));
+        }
+        let n = n_blocks as usize;
+        let engine = self.weave_engine.read().await;
+        let history = engine.fee_history();
+        let percentile = |p: u8| history.percentile(n, p).map(|f| f.to_string());
+        Ok(FeeHistoryInfo {
+            blocks: history
+                .recent(n)
+                .map(|r| BlockFeeInfo {
+                    height: r.height,
+                    fee_per_commitment: r.fee_per_commitment.to_string(),
+                    fee_multiplier: r.fee_multiplier,
+                    priority_fees: r.priority_fees.to_string(),
+                    commitment_count: r.commitment_count,
+                })
+                .collect(),
+            p10: percentile(10),
+            p50: percentile(50),
+            p90: percentile(90),
+        })
+    }
+
+    async fn get_mempool(&self, limit: Option<usize>) -> Result<MempoolInfo, ErrorObjectOwned> {
+        let limit = limit.unwrap_or(100).min(1000);
+        let engine = self.weave_engine.read().await;
+        let mempool = engine.mempool();
+        let stats = mempool.stats();
+        let commitments = mempool
+            .pending_commitments()
+            .into_iter()
+            .take(limit)
+            .map(|p| MempoolCommitmentInfo {
+                thread_id: hex::encode(p.commitment.thread_id),
+                version: p.commitment.version,
+                fee: p.fee.to_string(),
+                timestamp: p.commitment.timestamp,
+            })
+            .collect();
+
+        Ok(MempoolInfo {
+            total: stats.total,
+            capacity: stats.capacity,
+            commitment_count: stats.commitments,
+            transfer_count: stats.transfers,
+            registration_count: stats.registrations,
+            evidence_count: stats.evidence,
+            other_count: stats.other,
+            min_commitment_fee: stats.min_commitment_fee.map(|f| f.to_string()),
+            commitments,
+        })
+    }
+
     async fn subscribe_new_blocks(&self, pending: PendingSubscriptionSink) -> SubscriptionResult {
         let mut rx = self.broadcasters.block_tx.subscribe();
         let sink = pending.accept().await?;
@@ -1244,6 +2084,40 @@ impl NornRpcServer for NornRpcImpl {
         Ok(())
     }
 
+    async fn subscribe_events(
+        &self,
+        pending: PendingSubscriptionSink,
+        filter: EventFilterParams,
+    ) -> SubscriptionResult {
+        let filter = match event_filter(filter) {
+            Ok(filter) => filter,
+            Err(e) => {
+                pending.reject(e).await;
+                return Ok(());
+            }
+        };
+        let mut rx = self.broadcasters.event_tx.subscribe();
+        let sink = pending.accept().await?;
+
+        tokio::spawn(async move {
+            while let Ok(event) = rx.recv().await {
+                if !filter.matches(&event) {
+                    continue;
+                }
+                match jsonrpsee::SubscriptionMessage::from_json(&indexed_event_info(&event)) {
+                    Ok(msg) => {
+                        if sink.send(msg).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(_) => break,
+                }
+            }
+        });
+
+        Ok(())
+    }
+
     async fn subscribe_pending_transactions(
         &self,
         pending: PendingSubscriptionSink,
@@ -1424,7 +2298,7 @@ impl NornRpcServer for NornRpcImpl {
         &self,
         thread_id_hex: String,
     ) -> Result<Option<CommitmentProofInfo>, ErrorObjectOwned> {
-        let thread_id = parse_address_hex(&thread_id_hex)?;
+        let thread_id = parse_address(&thread_id_hex, self.network_id)?;
 
         let engine = self.weave_engine.read().await;
         if !engine.known_threads().contains(&thread_id) {
@@ -1447,7 +2321,7 @@ impl NornRpcServer for NornRpcImpl {
         limit: u64,
         offset: u64,
     ) -> Result<Vec<TransactionHistoryEntry>, ErrorObjectOwned> {
-        let address = parse_address_hex(&address_hex)?;
+        let address = parse_address(&address_hex, self.network_id)?;
 
         // Cap limit to prevent excessive memory use.
         let limit = if limit == 0 { 100 } else { limit.min(1000) } as usize;
@@ -1596,7 +2470,7 @@ impl NornRpcServer for NornRpcImpl {
             })?;
 
         // Verify the owner matches the claimed owner.
-        let owner_address = parse_address_hex(&owner_hex)?;
+        let owner_address = parse_address(&owner_hex, self.network_id)?;
         if name_reg.owner != owner_address {
             return Ok(SubmitResult {
                 success: false,
@@ -1651,7 +2525,7 @@ impl NornRpcServer for NornRpcImpl {
     }
 
     async fn list_names(&self, address_hex: String) -> Result<Vec<NameInfo>, ErrorObjectOwned> {
-        let address = parse_address_hex(&address_hex)?;
+        let address = parse_address(&address_hex, self.network_id)?;
         let sm = self.state_manager.read().await;
         let names = sm.names_for_address(&address);
         let infos = names
@@ -1681,7 +2555,7 @@ impl NornRpcServer for NornRpcImpl {
                 ErrorObjectOwned::owned(-32602, format!("invalid name transfer: {}", e), None::<()>)
             })?;
 
-        let from_address = parse_address_hex(&from_hex)?;
+        let from_address = parse_address(&from_hex, self.network_id)?;
         if name_transfer.from != from_address {
             return Ok(SubmitResult {
                 success: false,
@@ -1722,7 +2596,7 @@ impl NornRpcServer for NornRpcImpl {
     }
 
     async fn reverse_name(&self, address_hex: String) -> Result<Option<String>, ErrorObjectOwned> {
-        let address = parse_address_hex(&address_hex)?;
+        let address = parse_address(&address_hex, self.network_id)?;
         let sm = self.state_manager.read().await;
         let names = sm.names_for_address(&address);
         Ok(names.first().map(|n| n.to_string()))
@@ -1749,7 +2623,7 @@ impl NornRpcServer for NornRpcImpl {
                 )
             })?;
 
-        let owner_address = parse_address_hex(&owner_hex)?;
+        let owner_address = parse_address(&owner_hex, self.network_id)?;
         if record_update.owner != owner_address {
             return Ok(SubmitResult {
                 success: false,
@@ -1801,6 +2675,40 @@ impl NornRpcServer for NornRpcImpl {
         Ok(self.metrics.encode())
     }
 
+    async fn get_peers(&self) -> Result<Vec<PeerInfo>, ErrorObjectOwned> {
+        let Some(ref handle) = self.relay_handle else {
+            return Ok(Vec::new());
+        };
+        let bandwidth: std::collections::HashMap<_, _> = handle
+            .bandwidth_stats()
+            .peers
+            .into_iter()
+            .map(|b| (b.peer_id, b))
+            .collect();
+        let mut peers: Vec<PeerInfo> = handle
+            .peer_stats()
+            .peers
+            .into_iter()
+            .map(|p| {
+                let traffic = bandwidth.get(&p.peer_id);
+                PeerInfo {
+                    peer_id: p.peer_id.to_string(),
+                    score: p.score,
+                    protocol_version: p.protocol_version,
+                    connected_secs: p.connected_secs,
+                    greylisted: p.greylisted,
+                    bytes_in: traffic.map_or(0, |t| t.bytes_in),
+                    bytes_out: traffic.map_or(0, |t| t.bytes_out),
+                    bytes_in_per_sec: traffic.map_or(0, |t| t.bytes_in_per_sec),
+                    bytes_out_per_sec: traffic.map_or(0, |t| t.bytes_out_per_sec),
+                    throttled: traffic.map_or(0, |t| t.throttled),
+                }
+            })
+            .collect();
+        peers.sort_by(|a, b| a.score.cmp(&b.score).then(a.peer_id.cmp(&b.peer_id)));
+        Ok(peers)
+    }
+
     async fn submit_fraud_proof(
         &self,
         fraud_proof_hex: String,
@@ -1822,27 +2730,15 @@ impl NornRpcServer for NornRpcImpl {
             });
         }
 
-        // Verify the submitter's signature over the proof.
-        let proof_bytes = borsh::to_vec(&submission.proof).map_err(|e| {
-            ErrorObjectOwned::owned(
-                -32000,
-                format!("failed to serialize proof for verification: {}", e),
-                None::<()>,
-            )
-        })?;
-        let proof_hash = norn_crypto::hash::blake3_hash(&proof_bytes);
-        if let Err(e) =
-            norn_crypto::keys::verify(&proof_hash, &submission.signature, &submission.submitter)
-        {
+        // Verify the submitter's signature and, for stale-commit proofs, the
+        // proof itself before queueing it for the next block.
+        let mut engine = self.weave_engine.write().await;
+        if let Err(e) = engine.add_fraud_proof(submission.clone()) {
             return Ok(SubmitResult {
                 success: false,
-                reason: Some(format!("invalid submitter signature: {}", e)),
+                reason: Some(e.to_string()),
             });
         }
-
-        let mut engine = self.weave_engine.write().await;
-        let responses =
-            engine.on_network_message(NornMessage::FraudProof(Box::new(submission.clone())));
         drop(engine);
 
         // Broadcast via relay if accepted.
@@ -1858,14 +2754,7 @@ impl NornRpcServer for NornRpcImpl {
 
         Ok(SubmitResult {
             success: true,
-            reason: if responses.is_empty() {
-                Some("fraud proof accepted".to_string())
-            } else {
-                Some(format!(
-                    "fraud proof accepted, {} response(s) generated",
-                    responses.len()
-                ))
-            },
+            reason: Some("fraud proof accepted".to_string()),
         })
     }
 
@@ -2040,6 +2929,67 @@ impl NornRpcServer for NornRpcImpl {
         }
     }
 
+    async fn update_token_metadata(
+        &self,
+        update_hex: String,
+    ) -> Result<SubmitResult, ErrorObjectOwned> {
+        let bytes = hex::decode(&update_hex).map_err(|e| {
+            ErrorObjectOwned::owned(-32602, format!("invalid hex: {}", e), None::<()>)
+        })?;
+
+        let update: norn_types::weave::TokenMetadataUpdate =
+            borsh::from_slice(&bytes).map_err(|e| {
+                ErrorObjectOwned::owned(
+                    -32602,
+                    format!("invalid token metadata update: {}", e),
+                    None::<()>,
+                )
+            })?;
+
+        // Add to WeaveEngine mempool (validates authority, signature and limits).
+        let mut engine = self.weave_engine.write().await;
+        let block_height = engine.weave_state().height;
+        match engine.add_token_metadata_update(update.clone()) {
+            Ok(_) => {
+                // Fire token event.
+                let sm = self.state_manager.read().await;
+                let symbol = sm
+                    .get_token(&update.token_id)
+                    .map(|r| r.symbol.clone())
+                    .unwrap_or_default();
+                drop(sm);
+                let _ = self.broadcasters.token_tx.send(TokenEvent {
+                    event_type: "metadata_updated".to_string(),
+                    token_id: hex::encode(update.token_id),
+                    symbol,
+                    actor: format_address(&update.authority),
+                    amount: None,
+                    human_readable: None,
+                    block_height,
+                });
+                // Broadcast to P2P network.
+                if let Some(ref handle) = self.relay_handle {
+                    let h = handle.clone();
+                    let msg = NornMessage::TokenMetadataUpdate(Box::new(update));
+                    tokio::spawn(async move {
+                        let _ = h.broadcast(msg).await;
+                    });
+                }
+                Ok(SubmitResult {
+                    success: true,
+                    reason: Some(
+                        "token metadata update submitted (will be included in next block)"
+                            .to_string(),
+                    ),
+                })
+            }
+            Err(e) => Ok(SubmitResult {
+                success: false,
+                reason: Some(e.to_string()),
+            }),
+        }
+    }
+
     async fn get_token_info(
         &self,
         token_id_hex: String,
@@ -2058,20 +3008,17 @@ impl NornRpcServer for NornRpcImpl {
                 current_supply: sm.total_supply().to_string(),
                 creator: format_address(&[0u8; 20]),
                 created_at: 0,
+                description: None,
+                icon_uri: None,
+                project_url: None,
+                tags: Vec::new(),
             }));
         }
 
         let sm = self.state_manager.read().await;
-        Ok(sm.get_token(&token_id).map(|record| TokenInfo {
-            token_id: token_id_hex,
-            name: record.name.clone(),
-            symbol: record.symbol.clone(),
-            decimals: record.decimals,
-            max_supply: record.max_supply.to_string(),
-            current_supply: record.current_supply.to_string(),
-            creator: format_address(&record.creator),
-            created_at: record.created_at,
-        }))
+        Ok(sm
+            .get_token(&token_id)
+            .map(|record| token_info(&token_id, record)))
     }
 
     async fn get_token_by_symbol(
@@ -2083,16 +3030,7 @@ impl NornRpcServer for NornRpcImpl {
             Some(pair) => pair,
             None => return Ok(None),
         };
-        Ok(Some(TokenInfo {
-            token_id: hex::encode(token_id),
-            name: record.name.clone(),
-            symbol: record.symbol.clone(),
-            decimals: record.decimals,
-            max_supply: record.max_supply.to_string(),
-            current_supply: record.current_supply.to_string(),
-            creator: format_address(&record.creator),
-            created_at: record.created_at,
-        }))
+        Ok(Some(token_info(token_id, record)))
     }
 
     async fn list_tokens(
@@ -2115,21 +3053,20 @@ impl NornRpcServer for NornRpcImpl {
             current_supply: sm.total_supply().to_string(),
             creator: format_address(&[0u8; 20]),
             created_at: 0,
+            description: None,
+            icon_uri: None,
+            project_url: None,
+            tags: Vec::new(),
         };
 
         let user_tokens = sm.list_tokens();
 
         let result = std::iter::once(native)
-            .chain(user_tokens.into_iter().map(|(token_id, record)| TokenInfo {
-                token_id: hex::encode(token_id),
-                name: record.name.clone(),
-                symbol: record.symbol.clone(),
-                decimals: record.decimals,
-                max_supply: record.max_supply.to_string(),
-                current_supply: record.current_supply.to_string(),
-                creator: format_address(&record.creator),
-                created_at: record.created_at,
-            }))
+            .chain(
+                user_tokens
+                    .into_iter()
+                    .map(|(token_id, record)| token_info(token_id, record)),
+            )
             .skip(offset)
             .take(limit)
             .collect();
@@ -2192,9 +3129,7 @@ impl NornRpcServer for NornRpcImpl {
             active: record.active,
             deployed_at: record.deployed_at,
             has_bytecode: loom_mgr.has_bytecode(&loom_id),
-            code_hash: loom_mgr
-                .get_bytecode(&loom_id)
-                .map(|b| hex::encode(b.wasm_hash)),
+            code_hash: loom_mgr.get_code_id(&loom_id).map(hex::encode),
             participant_count: loom_mgr.participant_count(&loom_id),
         }))
     }
@@ -2218,9 +3153,7 @@ impl NornRpcServer for NornRpcImpl {
                 active: record.active,
                 deployed_at: record.deployed_at,
                 has_bytecode: loom_mgr.has_bytecode(loom_id),
-                code_hash: loom_mgr
-                    .get_bytecode(loom_id)
-                    .map(|b| hex::encode(b.wasm_hash)),
+                code_hash: loom_mgr.get_code_id(loom_id).map(hex::encode),
                 participant_count: loom_mgr.participant_count(loom_id),
             })
             .collect();
@@ -2235,7 +3168,7 @@ impl NornRpcServer for NornRpcImpl {
         init_msg_hex: Option<String>,
         operator_signature_hex: String,
         operator_pubkey_hex: String,
-    ) -> Result<SubmitResult, ErrorObjectOwned> {
+    ) -> Result<UploadBytecodeResult, ErrorObjectOwned> {
         let loom_id = parse_loom_hex(&loom_id_hex)?;
         let bytecode = hex::decode(&bytecode_hex).map_err(|e| {
             ErrorObjectOwned::owned(-32602, format!("invalid hex: {}", e), None::<()>)
@@ -2273,9 +3206,10 @@ impl NornRpcServer for NornRpcImpl {
             let sm = self.state_manager.read().await;
             match sm.get_loom(&loom_id) {
                 None => {
-                    return Ok(SubmitResult {
+                    return Ok(UploadBytecodeResult {
                         success: false,
                         reason: Some(format!("loom {} not found", loom_id_hex)),
+                        diagnostics: Vec::new(),
                     });
                 }
                 Some(record) => {
@@ -2332,8 +3266,13 @@ impl NornRpcServer for NornRpcImpl {
                 // Persist bytecode and initial state.
                 let sm = self.state_manager.read().await;
                 if let Some(store) = sm.store() {
-                    if let Err(e) = store.save_loom_bytecode(&loom_id, &bytecode) {
-                        tracing::warn!("failed to persist loom bytecode: {}", e);
+                    if let Some(code_id) = loom_mgr.get_code_id(&loom_id) {
+                        let saved = store
+                            .save_code(&code_id, &bytecode)
+                            .and_then(|_| store.save_loom_code_id(&loom_id, &code_id));
+                        if let Err(e) = saved {
+                            tracing::warn!("failed to persist loom bytecode: {}", e);
+                        }
                     }
                     if let Some(state_data) = loom_mgr.get_state_data(&loom_id) {
                         let state_bytes = borsh::to_vec(state_data).unwrap_or_default();
@@ -2343,18 +3282,175 @@ impl NornRpcServer for NornRpcImpl {
                     }
                 }
 
-                Ok(SubmitResult {
+                Ok(UploadBytecodeResult {
                     success: true,
                     reason: Some("bytecode uploaded and initialized".to_string()),
+                    diagnostics: Vec::new(),
                 })
             }
-            Err(e) => Ok(SubmitResult {
+            Err(e) => {
+                let diagnostics = match &e {
+                    norn_loom::error::LoomError::InvalidModule { diagnostics } => diagnostics
+                        .iter()
+                        .map(|d| ModuleDiagnosticInfo {
+                            kind: d.kind.as_str().to_string(),
+                            message: d.message.clone(),
+                        })
+                        .collect(),
+                    _ => Vec::new(),
+                };
+                Ok(UploadBytecodeResult {
+                    success: false,
+                    reason: Some(e.to_string()),
+                    diagnostics,
+                })
+            }
+        }
+    }
+
+    async fn store_code(
+        &self,
+        bytecode_hex: String,
+        pubkey_hex: String,
+        signature_hex: String,
+    ) -> Result<StoreCodeResult, ErrorObjectOwned> {
+        let bytecode = hex::decode(&bytecode_hex).map_err(|e| {
+            ErrorObjectOwned::owned(-32602, format!("invalid hex: {}", e), None::<()>)
+        })?;
+
+        let pubkey_bytes = hex::decode(&pubkey_hex).map_err(|e| {
+            ErrorObjectOwned::owned(-32602, format!("invalid pubkey hex: {}", e), None::<()>)
+        })?;
+        if pubkey_bytes.len() != 32 {
+            return Err(ErrorObjectOwned::owned(
+                -32602,
+                format!("pubkey must be 32 bytes, got {}", pubkey_bytes.len()),
+                None::<()>,
+            ));
+        }
+        let mut pubkey = [0u8; 32];
+        pubkey.copy_from_slice(&pubkey_bytes);
+
+        // Verify signature over blake3(b"norn_store_code" || blake3(bytecode)).
+        let bytecode_hash = norn_crypto::hash::blake3_hash(&bytecode);
+        let signing_msg =
+            norn_crypto::hash::blake3_hash_multi(&[b"norn_store_code", &bytecode_hash]);
+
+        let sig_bytes = hex::decode(&signature_hex).map_err(|e| {
+            ErrorObjectOwned::owned(-32602, format!("invalid signature hex: {}", e), None::<()>)
+        })?;
+        if sig_bytes.len() != 64 {
+            return Err(ErrorObjectOwned::owned(
+                -32602,
+                format!("signature must be 64 bytes, got {}", sig_bytes.len()),
+                None::<()>,
+            ));
+        }
+        let mut sig = [0u8; 64];
+        sig.copy_from_slice(&sig_bytes);
+
+        if let Err(e) = norn_crypto::keys::verify(&signing_msg, &sig, &pubkey) {
+            return Err(ErrorObjectOwned::owned(
+                -32602,
+                format!("invalid store_code signature: {}", e),
+                None::<()>,
+            ));
+        }
+
+        if let Err(e) = norn_loom::runtime::validate_module(&bytecode) {
+            return Ok(StoreCodeResult {
                 success: false,
+                code_id: None,
+                reason: Some(e.to_string()),
+            });
+        }
+
+        let mut loom_mgr = self.loom_manager.write().await;
+        match loom_mgr.store_code(bytecode.clone()) {
+            Ok(code_id) => {
+                let sm = self.state_manager.read().await;
+                if let Some(store) = sm.store() {
+                    if let Err(e) = store.save_code(&code_id, &bytecode) {
+                        tracing::warn!("failed to persist code: {}", e);
+                    }
+                }
+
+                Ok(StoreCodeResult {
+                    success: true,
+                    code_id: Some(hex::encode(code_id)),
+                    reason: None,
+                })
+            }
+            Err(e) => Ok(StoreCodeResult {
+                success: false,
+                code_id: None,
                 reason: Some(e.to_string()),
             }),
         }
     }
 
+    async fn list_codes(&self, limit: u64, offset: u64) -> Result<Vec<CodeInfo>, ErrorObjectOwned> {
+        let limit = if limit == 0 { 50 } else { limit.min(200) } as usize;
+        let offset = offset as usize;
+
+        let loom_mgr = self.loom_manager.read().await;
+        let result = loom_mgr
+            .list_codes()
+            .into_iter()
+            .skip(offset)
+            .take(limit)
+            .map(|(code_id, bytecode)| CodeInfo {
+                code_id: hex::encode(code_id),
+                size: bytecode.len(),
+                instance_count: loom_mgr.code_instance_count(code_id),
+                bytecode_hex: None,
+            })
+            .collect();
+
+        Ok(result)
+    }
+
+    async fn get_code(&self, code_id_hex: String) -> Result<Option<CodeInfo>, ErrorObjectOwned> {
+        let code_id = parse_loom_hex(&code_id_hex)?;
+        let loom_mgr = self.loom_manager.read().await;
+        Ok(loom_mgr.get_code(&code_id).map(|bytecode| CodeInfo {
+            code_id: code_id_hex,
+            size: bytecode.len(),
+            instance_count: loom_mgr.code_instance_count(&code_id),
+            bytecode_hex: Some(hex::encode(bytecode)),
+        }))
+    }
+
+    async fn get_gas_schedule(
+        &self,
+        height: Option<u64>,
+    ) -> Result<GasScheduleInfo, ErrorObjectOwned> {
+        let height = match height {
+            Some(h) => h,
+            None => self.weave_engine.read().await.weave_state().height,
+        };
+        let s = norn_loom::gas::schedule_for_height(height);
+        Ok(GasScheduleInfo {
+            version: s.version,
+            activation_height: s.activation_height,
+            instruction: s.instruction,
+            state_read: s.state_read,
+            state_write: s.state_write,
+            byte_read: s.byte_read,
+            byte_write: s.byte_write,
+            balance_query: s.balance_query,
+            derive_address: s.derive_address,
+            vrf_verify: s.vrf_verify,
+            decrypt: s.decrypt,
+            signature_verify: s.signature_verify,
+            transfer: s.transfer,
+            log: s.log,
+            emit_event: s.emit_event,
+            cross_call: s.cross_call,
+            instantiate: s.instantiate,
+        })
+    }
+
     async fn execute_loom(
         &self,
         loom_id_hex: String,
@@ -2367,7 +3463,7 @@ impl NornRpcServer for NornRpcImpl {
         let input = hex::decode(&input_hex).map_err(|e| {
             ErrorObjectOwned::owned(-32602, format!("invalid input hex: {}", e), None::<()>)
         })?;
-        let sender = parse_address_hex(&sender_hex)?;
+        let sender = parse_address(&sender_hex, self.network_id)?;
 
         // Parse and verify sender pubkey + signature.
         let pubkey_bytes = hex::decode(&pubkey_hex).map_err(|e| {
@@ -2431,13 +3527,23 @@ impl NornRpcServer for NornRpcImpl {
             )
         };
 
+        // Take the ledger view before the loom manager, matching the lock order
+        // used by block sync. The view is released when execution finishes.
+        let balances = LedgerBalances(self.state_manager.clone().read_owned().await);
         let mut loom_mgr = self.loom_manager.write().await;
 
         // Auto-join the sender as a participant if not already one.
         // Loom contracts are permissionless — anyone can interact.
         let _ = loom_mgr.join(&loom_id, pubkey, sender, timestamp);
 
-        match loom_mgr.execute(&loom_id, &input, sender, block_height, timestamp) {
+        match loom_mgr.execute_with_balances(
+            &loom_id,
+            &input,
+            sender,
+            block_height,
+            timestamp,
+            Some(Arc::new(balances)),
+        ) {
             Ok(outcome) => {
                 // Persist updated state.
                 let mut sm = self.state_manager.write().await;
@@ -2450,6 +3556,47 @@ impl NornRpcServer for NornRpcImpl {
                     }
                 }
 
+                // Register and persist looms instantiated by the contract.
+                for instance_id in &outcome.instantiated {
+                    if let Some(loom) = loom_mgr.get_loom(instance_id) {
+                        sm.register_instantiated_loom(
+                            *instance_id,
+                            &loom.config.name,
+                            loom.operator,
+                            timestamp,
+                        );
+                        sm.persist_loom_operators(instance_id, &loom.operator_set());
+                    }
+                    if let Some(store) = sm.store() {
+                        if let Some(code_id) = loom_mgr.get_code_id(instance_id) {
+                            if let Err(e) = store.save_loom_code_id(instance_id, &code_id) {
+                                tracing::warn!("failed to persist instance code ID: {}", e);
+                            }
+                        }
+                        if let Some(state_data) = loom_mgr.get_state_data(instance_id) {
+                            let state_bytes = borsh::to_vec(state_data).unwrap_or_default();
+                            if let Err(e) = store.save_loom_state(instance_id, &state_bytes) {
+                                tracing::warn!("failed to persist instance state: {}", e);
+                            }
+                        }
+                    }
+                }
+
+                // Index emitted events for history queries and subscribers.
+                let recorded = sm.record_loom_events(
+                    loom_id,
+                    sender,
+                    block_height,
+                    timestamp,
+                    outcome
+                        .events
+                        .iter()
+                        .map(|e| (e.ty.clone(), e.attributes.clone())),
+                );
+                for event in recorded {
+                    let _ = self.broadcasters.event_tx.send(event.clone());
+                }
+
                 // Apply pending transfers to account balances.
                 let now = std::time::SystemTime::now()
                     .duration_since(std::time::UNIX_EPOCH)
@@ -2483,6 +3630,22 @@ impl NornRpcServer for NornRpcImpl {
                     }
                 }
 
+                // Let the weave engine accept commitments for new instances.
+                // Release the state locks first so the engine lock is never
+                // taken while they are held.
+                let instance_operators: Vec<_> = outcome
+                    .instantiated
+                    .iter()
+                    .filter_map(|id| Some((*id, loom_mgr.get_loom(id)?.operator_set())))
+                    .collect();
+                drop(sm);
+                drop(loom_mgr);
+                if !outcome.instantiated.is_empty() {
+                    let mut engine = self.weave_engine.write().await;
+                    engine.seed_known_looms(outcome.instantiated.iter().copied());
+                    engine.seed_loom_operators(instance_operators);
+                }
+
                 // Build event info for response.
                 let events: Vec<EventInfo> = outcome
                     .events
@@ -2516,6 +3679,7 @@ impl NornRpcServer for NornRpcImpl {
                     logs: outcome.logs,
                     events,
                     reason: None,
+                    instantiated: outcome.instantiated.iter().map(hex::encode).collect(),
                 })
             }
             Err(e) => Ok(ExecutionResult {
@@ -2525,6 +3689,7 @@ impl NornRpcServer for NornRpcImpl {
                 logs: Vec::new(),
                 events: Vec::new(),
                 reason: Some(e.to_string()),
+                instantiated: Vec::new(),
             }),
         }
     }
@@ -2551,77 +3716,481 @@ impl NornRpcServer for NornRpcImpl {
             )
         };
 
+        let balances = LedgerBalances(self.state_manager.clone().read_owned().await);
         let loom_mgr = self.loom_manager.read().await;
-        match loom_mgr.query(&loom_id, &input, [0u8; 20], block_height, timestamp) {
-            Ok(outcome) => {
-                let events: Vec<EventInfo> = outcome
-                    .events
+        Ok(query_result(loom_mgr.query_with_balances(
+            &loom_id,
+            &input,
+            [0u8; 20],
+            block_height,
+            timestamp,
+            Some(Arc::new(balances)),
+        )))
+    }
+
+    async fn query_loom_at(
+        &self,
+        loom_id_hex: String,
+        input_hex: String,
+        height: u64,
+    ) -> Result<QueryResult, ErrorObjectOwned> {
+        let loom_id = parse_loom_hex(&loom_id_hex)?;
+        let input = hex::decode(&input_hex).map_err(|e| {
+            ErrorObjectOwned::owned(-32602, format!("invalid input hex: {}", e), None::<()>)
+        })?;
+
+        let sm = self.state_manager.clone().read_owned().await;
+        check_history_height(&sm, height)?;
+        let Some(state_data) = sm
+            .loom_state_at(&loom_id, height)
+            .map_err(|e| ErrorObjectOwned::owned(-32603, e.to_string(), None::<()>))?
+        else {
+            return Ok(query_result(Err(norn_loom::error::LoomError::StateError {
+                reason: format!("no state recorded for loom at height {}", height),
+            })));
+        };
+        // Run the query with the block's own timestamp where it is known.
+        let timestamp = sm
+            .get_block_by_height(height)
+            .map(|b| b.timestamp)
+            .unwrap_or_default();
+        let balances = HistoricalBalances { state: sm, height };
+
+        let loom_mgr = self.loom_manager.read().await;
+        Ok(query_result(loom_mgr.query_with_state(
+            &loom_id,
+            state_data,
+            &input,
+            [0u8; 20],
+            height,
+            timestamp,
+            Some(Arc::new(balances)),
+        )))
+    }
+
+    async fn trace_loom_execution(
+        &self,
+        loom_id_hex: String,
+        input_hex: String,
+        sender_hex: String,
+    ) -> Result<ExecutionTraceInfo, ErrorObjectOwned> {
+        let loom_id = parse_loom_hex(&loom_id_hex)?;
+        let input = hex::decode(&input_hex).map_err(|e| {
+            ErrorObjectOwned::owned(-32602, format!("invalid input hex: {}", e), None::<()>)
+        })?;
+        let sender = parse_address(&sender_hex, self.network_id)?;
+
+        let (block_height, timestamp) = {
+            let engine = self.weave_engine.read().await;
+            let state = engine.weave_state();
+            (
+                state.height,
+                std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs(),
+            )
+        };
+
+        let balances = LedgerBalances(self.state_manager.clone().read_owned().await);
+        let loom_mgr = self.loom_manager.read().await;
+        match loom_mgr.trace_with_balances(
+            &loom_id,
+            &input,
+            sender,
+            block_height,
+            timestamp,
+            Some(Arc::new(balances)),
+        ) {
+            Ok(trace) => Ok(ExecutionTraceInfo {
+                success: trace.success,
+                output_hex: trace.success.then(|| hex::encode(&trace.output)),
+                gas_used: trace.gas_used,
+                instruction_gas: trace.instruction_gas,
+                host_gas: trace
+                    .host_gas
                     .iter()
+                    .map(|u| HostGasInfo {
+                        host_fn: u.host_fn.to_string(),
+                        calls: u.calls,
+                        gas: u.gas,
+                    })
+                    .collect(),
+                calls: trace
+                    .calls
+                    .into_iter()
+                    .map(|c| HostCallInfo {
+                        depth: c.depth,
+                        host_fn: c.host_fn.to_string(),
+                        gas: c.gas,
+                        detail: c.detail,
+                    })
+                    .collect(),
+                debug_logs: trace.debug_logs,
+                logs: trace.logs,
+                events: trace
+                    .events
+                    .into_iter()
                     .map(|e| EventInfo {
-                        ty: e.ty.clone(),
+                        ty: e.ty,
                         attributes: e
                             .attributes
-                            .iter()
-                            .map(|(k, v)| AttributeInfo {
-                                key: k.clone(),
-                                value: v.clone(),
-                            })
+                            .into_iter()
+                            .map(|(key, value)| AttributeInfo { key, value })
                             .collect(),
                     })
-                    .collect();
-                Ok(QueryResult {
-                    success: true,
-                    output_hex: Some(hex::encode(&outcome.output)),
-                    gas_used: outcome.gas_used,
-                    logs: outcome.logs,
-                    events,
-                    reason: None,
-                })
-            }
-            Err(e) => Ok(QueryResult {
+                    .collect(),
+                truncated: trace.truncated,
+                reason: trace.error,
+            }),
+            Err(e) => Ok(ExecutionTraceInfo {
                 success: false,
                 output_hex: None,
                 gas_used: 0,
+                instruction_gas: 0,
+                host_gas: Vec::new(),
+                calls: Vec::new(),
+                debug_logs: Vec::new(),
                 logs: Vec::new(),
                 events: Vec::new(),
+                truncated: false,
                 reason: Some(e.to_string()),
             }),
         }
     }
 
-    async fn join_loom(
+    async fn export_loom_state(
         &self,
         loom_id_hex: String,
-        participant_hex: String,
-        pubkey_hex: String,
-        signature_hex: String,
+    ) -> Result<LoomStateExportInfo, ErrorObjectOwned> {
+        let loom_id = parse_loom_hex(&loom_id_hex)?;
+        let loom_mgr = self.loom_manager.read().await;
+        let snapshot = loom_mgr
+            .export_state(&loom_id)
+            .map_err(|e| ErrorObjectOwned::owned(-32602, e.to_string(), None::<()>))?;
+        let snapshot_bytes = borsh::to_vec(&snapshot).map_err(|e| {
+            ErrorObjectOwned::owned(
+                -32603,
+                format!("failed to encode snapshot: {}", e),
+                None::<()>,
+            )
+        })?;
+        Ok(LoomStateExportInfo {
+            loom_id: loom_id_hex,
+            state_hash: hex::encode(snapshot.state_hash),
+            merkle_root: hex::encode(snapshot.merkle_root),
+            entry_count: snapshot.entries.len(),
+            snapshot_hex: hex::encode(snapshot_bytes),
+        })
+    }
+
+    async fn import_loom_state(
+        &self,
+        loom_id_hex: String,
+        snapshot_hex: String,
+        operator_signature_hex: String,
+        operator_pubkey_hex: String,
     ) -> Result<SubmitResult, ErrorObjectOwned> {
         let loom_id = parse_loom_hex(&loom_id_hex)?;
-        let address = parse_address_hex(&participant_hex)?;
-        let pubkey_bytes = hex::decode(&pubkey_hex).map_err(|e| {
-            ErrorObjectOwned::owned(-32602, format!("invalid pubkey hex: {}", e), None::<()>)
+        let snapshot_bytes = hex::decode(&snapshot_hex).map_err(|e| {
+            ErrorObjectOwned::owned(-32602, format!("invalid snapshot hex: {}", e), None::<()>)
         })?;
-        if pubkey_bytes.len() != 32 {
-            return Err(ErrorObjectOwned::owned(
+        let snapshot: norn_loom::state::LoomStateSnapshot = borsh::from_slice(&snapshot_bytes)
+            .map_err(|e| {
+                ErrorObjectOwned::owned(-32602, format!("invalid snapshot: {}", e), None::<()>)
+            })?;
+
+        let op_pubkey_bytes = hex::decode(&operator_pubkey_hex).map_err(|e| {
+            ErrorObjectOwned::owned(
                 -32602,
-                format!("pubkey must be 32 bytes, got {}", pubkey_bytes.len()),
+                format!("invalid operator pubkey hex: {}", e),
                 None::<()>,
-            ));
-        }
-        let mut pubkey = [0u8; 32];
-        pubkey.copy_from_slice(&pubkey_bytes);
+            )
+        })?;
+        let op_pubkey: [u8; 32] = op_pubkey_bytes.as_slice().try_into().map_err(|_| {
+            ErrorObjectOwned::owned(
+                -32602,
+                format!(
+                    "operator pubkey must be 32 bytes, got {}",
+                    op_pubkey_bytes.len()
+                ),
+                None::<()>,
+            )
+        })?;
+        let op_sig_bytes = hex::decode(&operator_signature_hex).map_err(|e| {
+            ErrorObjectOwned::owned(
+                -32602,
+                format!("invalid operator signature hex: {}", e),
+                None::<()>,
+            )
+        })?;
+        let op_sig: [u8; 64] = op_sig_bytes.as_slice().try_into().map_err(|_| {
+            ErrorObjectOwned::owned(
+                -32602,
+                format!(
+                    "operator signature must be 64 bytes, got {}",
+                    op_sig_bytes.len()
+                ),
+                None::<()>,
+            )
+        })?;
 
-        // Verify the pubkey actually derives the claimed participant address.
-        if norn_crypto::address::pubkey_to_address(&pubkey) != address {
+        // Verify operator signature over blake3(b"norn_import_loom_state" || loom_id || blake3(snapshot)).
+        let snapshot_hash = norn_crypto::hash::blake3_hash(&snapshot_bytes);
+        let signing_msg = norn_crypto::hash::blake3_hash_multi(&[
+            b"norn_import_loom_state",
+            &loom_id,
+            &snapshot_hash,
+        ]);
+        if let Err(e) = norn_crypto::keys::verify(&signing_msg, &op_sig, &op_pubkey) {
             return Err(ErrorObjectOwned::owned(
                 -32602,
-                "pubkey does not derive the claimed participant address",
+                format!("invalid operator signature: {}", e),
                 None::<()>,
             ));
         }
 
-        // Parse and verify signature over blake3(b"norn_join_loom" || loom_id || address).
-        let sig_bytes = hex::decode(&signature_hex).map_err(|e| {
+        let sm = self.state_manager.read().await;
+        match sm.get_loom(&loom_id) {
+            None => {
+                return Ok(SubmitResult {
+                    success: false,
+                    reason: Some(format!("loom {} not found", loom_id_hex)),
+                });
+            }
+            Some(record) if record.operator != op_pubkey => {
+                return Err(ErrorObjectOwned::owned(
+                    -32602,
+                    "provided pubkey does not match loom operator",
+                    None::<()>,
+                ));
+            }
+            Some(_) => {}
+        }
+
+        let mut loom_mgr = self.loom_manager.write().await;
+        match loom_mgr.import_state(&loom_id, snapshot) {
+            Ok(state_hash) => {
+                if let Some(store) = sm.store() {
+                    if let Some(state_data) = loom_mgr.get_state_data(&loom_id) {
+                        let state_bytes = borsh::to_vec(state_data).unwrap_or_default();
+                        if let Err(e) = store.save_loom_state(&loom_id, &state_bytes) {
+                            tracing::warn!("failed to persist loom state: {}", e);
+                        }
+                    }
+                }
+                Ok(SubmitResult {
+                    success: true,
+                    reason: Some(format!("state imported ({})", hex::encode(state_hash))),
+                })
+            }
+            Err(e) => Ok(SubmitResult {
+                success: false,
+                reason: Some(e.to_string()),
+            }),
+        }
+    }
+
+    async fn submit_dispute(
+        &self,
+        evidence_hex: String,
+        challenger_hex: String,
+        pubkey_hex: String,
+        signature_hex: String,
+    ) -> Result<DisputeInfo, ErrorObjectOwned> {
+        let evidence_bytes = hex::decode(&evidence_hex).map_err(|e| {
+            ErrorObjectOwned::owned(-32602, format!("invalid evidence hex: {}", e), None::<()>)
+        })?;
+        let evidence: norn_loom::dispute::EvidenceBundle = borsh::from_slice(&evidence_bytes)
+            .map_err(|e| {
+                ErrorObjectOwned::owned(-32602, format!("invalid evidence: {}", e), None::<()>)
+            })?;
+        let challenger = parse_address(&challenger_hex, self.network_id)?;
+
+        // Parse and verify challenger pubkey + signature.
+        let pubkey_bytes = hex::decode(&pubkey_hex).map_err(|e| {
+            ErrorObjectOwned::owned(-32602, format!("invalid pubkey hex: {}", e), None::<()>)
+        })?;
+        let pubkey: [u8; 32] = pubkey_bytes.as_slice().try_into().map_err(|_| {
+            ErrorObjectOwned::owned(
+                -32602,
+                format!("pubkey must be 32 bytes, got {}", pubkey_bytes.len()),
+                None::<()>,
+            )
+        })?;
+        if norn_crypto::address::pubkey_to_address(&pubkey) != challenger {
+            return Err(ErrorObjectOwned::owned(
+                -32602,
+                "pubkey does not derive the claimed challenger address",
+                None::<()>,
+            ));
+        }
+        let sig_bytes = hex::decode(&signature_hex).map_err(|e| {
+            ErrorObjectOwned::owned(-32602, format!("invalid signature hex: {}", e), None::<()>)
+        })?;
+        let sig: [u8; 64] = sig_bytes.as_slice().try_into().map_err(|_| {
+            ErrorObjectOwned::owned(
+                -32602,
+                format!("signature must be 64 bytes, got {}", sig_bytes.len()),
+                None::<()>,
+            )
+        })?;
+
+        // Sign: blake3(b"norn_submit_dispute" || evidence_id || challenger)
+        let dispute_id = evidence.id();
+        let signing_msg = norn_crypto::hash::blake3_hash_multi(&[
+            b"norn_submit_dispute",
+            &dispute_id,
+            &challenger,
+        ]);
+        if let Err(e) = norn_crypto::keys::verify(&signing_msg, &sig, &pubkey) {
+            return Err(ErrorObjectOwned::owned(
+                -32602,
+                format!("invalid submit_dispute signature: {}", e),
+                None::<()>,
+            ));
+        }
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let bond = norn_types::loom::LOOM_DISPUTE_BOND;
+
+        let mut sm = self.state_manager.write().await;
+        let available = sm.get_balance(&challenger, &NATIVE_TOKEN_ID);
+        if available < bond + TRANSFER_FEE {
+            return Err(ErrorObjectOwned::owned(
+                -32602,
+                format!(
+                    "insufficient balance for dispute bond: need {}, have {}",
+                    bond + TRANSFER_FEE,
+                    available
+                ),
+                None::<()>,
+            ));
+        }
+
+        let mut loom_mgr = self.loom_manager.write().await;
+        loom_mgr
+            .open_dispute(evidence, challenger, bond, now)
+            .map_err(|e| ErrorObjectOwned::owned(-32602, e.to_string(), None::<()>))?;
+        let dispute = loom_mgr
+            .resolve_dispute(&dispute_id, now)
+            .map_err(|e| ErrorObjectOwned::owned(-32603, e.to_string(), None::<()>))?;
+
+        // Pay the losing party's bond to the winner. A defendant that cannot
+        // cover the full bond forfeits what it has.
+        if let Some((from, to, amount)) = dispute.settlement() {
+            let payable = amount.min(
+                sm.get_balance(&from, &NATIVE_TOKEN_ID)
+                    .saturating_sub(TRANSFER_FEE),
+            );
+            if payable > 0 {
+                sm.auto_register_if_needed(to);
+                let knot_id = norn_crypto::hash::blake3_hash_multi(&[b"loom_dispute", &dispute_id]);
+                if let Err(e) = sm.apply_transfer(
+                    from,
+                    to,
+                    NATIVE_TOKEN_ID,
+                    payable,
+                    knot_id,
+                    Some(b"dispute bond".to_vec()),
+                    now,
+                ) {
+                    tracing::warn!(
+                        "failed to settle dispute {}: {}",
+                        hex::encode(dispute_id),
+                        e
+                    );
+                }
+            }
+        }
+
+        Ok(dispute_info(dispute))
+    }
+
+    async fn get_dispute(
+        &self,
+        dispute_id_hex: String,
+    ) -> Result<Option<DisputeInfo>, ErrorObjectOwned> {
+        let dispute_id: [u8; 32] = hex::decode(&dispute_id_hex)
+            .ok()
+            .and_then(|b| b.try_into().ok())
+            .ok_or_else(|| {
+                ErrorObjectOwned::owned(-32602, "dispute_id must be 32 hex bytes", None::<()>)
+            })?;
+        let loom_mgr = self.loom_manager.read().await;
+        Ok(loom_mgr.get_dispute(&dispute_id).map(dispute_info))
+    }
+
+    async fn list_disputes(
+        &self,
+        loom_id_hex: Option<String>,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<DisputeInfo>, ErrorObjectOwned> {
+        let limit = if limit == 0 { 50 } else { limit.min(200) } as usize;
+        let loom_id = loom_id_hex.as_deref().map(parse_loom_hex).transpose()?;
+        let loom_mgr = self.loom_manager.read().await;
+        Ok(loom_mgr
+            .list_disputes(loom_id.as_ref())
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit)
+            .map(dispute_info)
+            .collect())
+    }
+
+    async fn get_events(
+        &self,
+        filter: EventFilterParams,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<IndexedEventInfo>, ErrorObjectOwned> {
+        let limit = if limit == 0 { 50 } else { limit.min(200) } as usize;
+        let filter = event_filter(filter)?;
+        let sm = self.state_manager.read().await;
+        Ok(sm
+            .get_events(&filter, limit, offset as usize)
+            .into_iter()
+            .map(indexed_event_info)
+            .collect())
+    }
+
+    async fn join_loom(
+        &self,
+        loom_id_hex: String,
+        participant_hex: String,
+        pubkey_hex: String,
+        signature_hex: String,
+    ) -> Result<SubmitResult, ErrorObjectOwned> {
+        let loom_id = parse_loom_hex(&loom_id_hex)?;
+        let address = parse_address(&participant_hex, self.network_id)?;
+        let pubkey_bytes = hex::decode(&pubkey_hex).map_err(|e| {
+            ErrorObjectOwned::owned(-32602, format!("invalid pubkey hex: {}", e), None::<()>)
+        })?;
+        if pubkey_bytes.len() != 32 {
+            return Err(ErrorObjectOwned::owned(
+                -32602,
+                format!("pubkey must be 32 bytes, got {}", pubkey_bytes.len()),
+                None::<()>,
+            ));
+        }
+        let mut pubkey = [0u8; 32];
+        pubkey.copy_from_slice(&pubkey_bytes);
+
+        // Verify the pubkey actually derives the claimed participant address.
+        if norn_crypto::address::pubkey_to_address(&pubkey) != address {
+            return Err(ErrorObjectOwned::owned(
+                -32602,
+                "pubkey does not derive the claimed participant address",
+                None::<()>,
+            ));
+        }
+
+        // Parse and verify signature over blake3(b"norn_join_loom" || loom_id || address).
+        let sig_bytes = hex::decode(&signature_hex).map_err(|e| {
             ErrorObjectOwned::owned(-32602, format!("invalid signature hex: {}", e), None::<()>)
         })?;
         if sig_bytes.len() != 64 {
@@ -2670,7 +4239,7 @@ impl NornRpcServer for NornRpcImpl {
         pubkey_hex: String,
     ) -> Result<SubmitResult, ErrorObjectOwned> {
         let loom_id = parse_loom_hex(&loom_id_hex)?;
-        let address = parse_address_hex(&participant_hex)?;
+        let address = parse_address(&participant_hex, self.network_id)?;
 
         // Parse and validate pubkey.
         let pubkey_bytes = hex::decode(&pubkey_hex).map_err(|e| {
@@ -2733,6 +4302,117 @@ impl NornRpcServer for NornRpcImpl {
         }
     }
 
+    async fn set_participant_role(
+        &self,
+        loom_id_hex: String,
+        participant_hex: String,
+        role: String,
+        operator_pubkey_hex: String,
+        signature_hex: String,
+    ) -> Result<SubmitResult, ErrorObjectOwned> {
+        let loom_id = parse_loom_hex(&loom_id_hex)?;
+        let address = parse_address(&participant_hex, self.network_id)?;
+        let role: norn_types::loom::ParticipantRole = role
+            .parse()
+            .map_err(|e: String| ErrorObjectOwned::owned(-32602, e, None::<()>))?;
+
+        let pubkey_bytes = hex::decode(&operator_pubkey_hex).map_err(|e| {
+            ErrorObjectOwned::owned(-32602, format!("invalid pubkey hex: {}", e), None::<()>)
+        })?;
+        if pubkey_bytes.len() != 32 {
+            return Err(ErrorObjectOwned::owned(
+                -32602,
+                format!("pubkey must be 32 bytes, got {}", pubkey_bytes.len()),
+                None::<()>,
+            ));
+        }
+        let mut pubkey = [0u8; 32];
+        pubkey.copy_from_slice(&pubkey_bytes);
+
+        let sig_bytes = hex::decode(&signature_hex).map_err(|e| {
+            ErrorObjectOwned::owned(-32602, format!("invalid signature hex: {}", e), None::<()>)
+        })?;
+        if sig_bytes.len() != 64 {
+            return Err(ErrorObjectOwned::owned(
+                -32602,
+                format!("signature must be 64 bytes, got {}", sig_bytes.len()),
+                None::<()>,
+            ));
+        }
+        let mut sig = [0u8; 64];
+        sig.copy_from_slice(&sig_bytes);
+
+        // Verify signature over
+        // blake3(b"norn_set_participant_role" || loom_id || address || role).
+        let signing_msg = norn_crypto::hash::blake3_hash_multi(&[
+            b"norn_set_participant_role",
+            &loom_id,
+            &address,
+            role.as_str().as_bytes(),
+        ]);
+        if let Err(e) = norn_crypto::keys::verify(&signing_msg, &sig, &pubkey) {
+            return Err(ErrorObjectOwned::owned(
+                -32602,
+                format!("invalid set_participant_role signature: {}", e),
+                None::<()>,
+            ));
+        }
+
+        let mut loom_mgr = self.loom_manager.write().await;
+        match loom_mgr.set_participant_role(&loom_id, &pubkey, &address, role) {
+            Ok(()) => Ok(SubmitResult {
+                success: true,
+                reason: Some(format!("participant role set to {}", role.as_str())),
+            }),
+            Err(e) => Ok(SubmitResult {
+                success: false,
+                reason: Some(e.to_string()),
+            }),
+        }
+    }
+
+    async fn submit_operator_handover(
+        &self,
+        handover_hex: String,
+    ) -> Result<SubmitResult, ErrorObjectOwned> {
+        let bytes = hex::decode(&handover_hex).map_err(|e| {
+            ErrorObjectOwned::owned(-32602, format!("invalid hex: {}", e), None::<()>)
+        })?;
+        let handover: norn_types::loom::OperatorHandover =
+            borsh::from_slice(&bytes).map_err(|e| {
+                ErrorObjectOwned::owned(
+                    -32602,
+                    format!("invalid operator handover: {}", e),
+                    None::<()>,
+                )
+            })?;
+
+        // Add to WeaveEngine mempool (validates both signatures against the
+        // loom's current operators).
+        let mut engine = self.weave_engine.write().await;
+        match engine.add_operator_handover(handover.clone()) {
+            Ok(()) => {
+                if let Some(ref handle) = self.relay_handle {
+                    let h = handle.clone();
+                    let msg = NornMessage::OperatorHandover(Box::new(handover));
+                    tokio::spawn(async move {
+                        let _ = h.broadcast(msg).await;
+                    });
+                }
+                Ok(SubmitResult {
+                    success: true,
+                    reason: Some(
+                        "operator handover accepted (will be included in next block)".to_string(),
+                    ),
+                })
+            }
+            Err(e) => Ok(SubmitResult {
+                success: false,
+                reason: Some(e.to_string()),
+            }),
+        }
+    }
+
     async fn stake(&self, operation_hex: String) -> Result<SubmitResult, ErrorObjectOwned> {
         let bytes = hex::decode(&operation_hex).map_err(|e| {
             ErrorObjectOwned::owned(-32602, format!("invalid hex: {}", e), None::<()>)
@@ -2763,14 +4443,7 @@ impl NornRpcServer for NornRpcImpl {
         }
 
         // Fire pending transaction event.
-        let (stake_pubkey, stake_timestamp) = match &op {
-            norn_types::weave::StakeOperation::Stake {
-                pubkey, timestamp, ..
-            } => (*pubkey, *timestamp),
-            norn_types::weave::StakeOperation::Unstake {
-                pubkey, timestamp, ..
-            } => (*pubkey, *timestamp),
-        };
+        let (stake_pubkey, stake_timestamp) = (*op.signer(), op.timestamp());
         let _ = self.broadcasters.pending_tx.send(PendingTransactionEvent {
             tx_type: "stake".to_string(),
             hash: hex::encode(norn_crypto::hash::blake3_hash(&bytes)),
@@ -2798,6 +4471,237 @@ impl NornRpcServer for NornRpcImpl {
         self.stake(operation_hex).await
     }
 
+    async fn delegate(&self, operation_hex: String) -> Result<SubmitResult, ErrorObjectOwned> {
+        self.stake(operation_hex).await
+    }
+
+    async fn undelegate(&self, operation_hex: String) -> Result<SubmitResult, ErrorObjectOwned> {
+        self.stake(operation_hex).await
+    }
+
+    async fn get_delegations(
+        &self,
+        delegator_hex: String,
+    ) -> Result<Vec<DelegationInfo>, ErrorObjectOwned> {
+        let delegator: [u8; 32] = hex::decode(&delegator_hex)
+            .map_err(|e| {
+                ErrorObjectOwned::owned(-32602, format!("invalid pubkey hex: {}", e), None::<()>)
+            })?
+            .try_into()
+            .map_err(|_| ErrorObjectOwned::owned(-32602, "pubkey must be 32 bytes", None::<()>))?;
+
+        let engine = self.weave_engine.read().await;
+        let staking = engine.staking();
+        Ok(staking
+            .delegations_of(&delegator)
+            .into_iter()
+            .map(|d| DelegationInfo {
+                validator: hex::encode(d.validator),
+                delegator: hex::encode(d.delegator),
+                amount: d.amount.to_string(),
+                pending: d.pending.to_string(),
+                pending_undelegate: d.pending_undelegate.to_string(),
+                commission_bps: staking.validator_commission(&d.validator).unwrap_or(0),
+            })
+            .collect())
+    }
+
+    async fn unjail(&self, operation_hex: String) -> Result<SubmitResult, ErrorObjectOwned> {
+        self.stake(operation_hex).await
+    }
+
+    async fn submit_slashing_evidence(
+        &self,
+        evidence_hex: String,
+    ) -> Result<SubmitResult, ErrorObjectOwned> {
+        let bytes = hex::decode(&evidence_hex).map_err(|e| {
+            ErrorObjectOwned::owned(-32602, format!("invalid hex: {}", e), None::<()>)
+        })?;
+        let evidence: norn_types::slashing::SlashingEvidence =
+            borsh::from_slice(&bytes).map_err(|e| {
+                ErrorObjectOwned::owned(
+                    -32602,
+                    format!("invalid slashing evidence: {}", e),
+                    None::<()>,
+                )
+            })?;
+
+        let result = self
+            .weave_engine
+            .write()
+            .await
+            .add_slashing_evidence(evidence.clone());
+        match result {
+            Ok(()) => {
+                if let Some(ref handle) = self.relay_handle {
+                    let h = handle.clone();
+                    let msg = NornMessage::SlashingEvidence(Box::new(evidence));
+                    tokio::spawn(async move {
+                        let _ = h.broadcast(msg).await;
+                    });
+                }
+                Ok(SubmitResult {
+                    success: true,
+                    reason: Some(
+                        "slashing evidence accepted (will be included in next block)".to_string(),
+                    ),
+                })
+            }
+            Err(e) => Ok(SubmitResult {
+                success: false,
+                reason: Some(e.to_string()),
+            }),
+        }
+    }
+
+    async fn get_slashing_history(
+        &self,
+        pubkey_hex: Option<String>,
+    ) -> Result<Vec<SlashRecordInfo>, ErrorObjectOwned> {
+        let engine = self.weave_engine.read().await;
+        Ok(engine
+            .staking()
+            .slash_history()
+            .iter()
+            .filter(|r| {
+                pubkey_hex
+                    .as_ref()
+                    .is_none_or(|hex| hex::encode(r.validator) == *hex)
+            })
+            .map(|r| SlashRecordInfo {
+                validator: hex::encode(r.validator),
+                reason: match r.reason {
+                    norn_types::slashing::SlashReason::DoubleSign => "double_sign",
+                    norn_types::slashing::SlashReason::Downtime => "downtime",
+                }
+                .to_string(),
+                amount: r.amount.to_string(),
+                height: r.height,
+                jailed_until: r.jailed_until,
+            })
+            .collect())
+    }
+
+    async fn submit_spindle_operation(
+        &self,
+        operation_hex: String,
+    ) -> Result<SubmitResult, ErrorObjectOwned> {
+        let bytes = hex::decode(&operation_hex).map_err(|e| {
+            ErrorObjectOwned::owned(-32602, format!("invalid hex: {}", e), None::<()>)
+        })?;
+        let op: norn_types::spindle::SpindleOperation = borsh::from_slice(&bytes).map_err(|e| {
+            ErrorObjectOwned::owned(
+                -32602,
+                format!("invalid spindle operation: {}", e),
+                None::<()>,
+            )
+        })?;
+
+        if let Err(e) = norn_weave::spindle::validate_spindle_operation(&op) {
+            return Ok(SubmitResult {
+                success: false,
+                reason: Some(e.to_string()),
+            });
+        }
+
+        {
+            let mut engine = self.weave_engine.write().await;
+            if let Err(e) = engine.mempool_mut().add_spindle_operation(op.clone()) {
+                return Ok(SubmitResult {
+                    success: false,
+                    reason: Some(e.to_string()),
+                });
+            }
+        }
+
+        if let Some(ref handle) = self.relay_handle {
+            let h = handle.clone();
+            let msg = NornMessage::SpindleOperation(op);
+            tokio::spawn(async move {
+                let _ = h.broadcast(msg).await;
+            });
+        }
+
+        Ok(SubmitResult {
+            success: true,
+            reason: Some("spindle operation submitted".to_string()),
+        })
+    }
+
+    async fn get_spindles(&self) -> Result<Vec<SpindleInfo>, ErrorObjectOwned> {
+        let sm = self.state_manager.read().await;
+        let market = sm.spindle_market();
+        let mut bonds: Vec<_> = market.bonds().collect();
+        bonds.sort_by_key(|b| b.fee_per_period);
+        Ok(bonds
+            .into_iter()
+            .map(|b| {
+                let slashed: u128 = market
+                    .slash_history()
+                    .iter()
+                    .filter(|s| s.spindle == b.spindle)
+                    .map(|s| s.amount)
+                    .sum();
+                SpindleInfo {
+                    pubkey: hex::encode(b.spindle),
+                    address: hex::encode(norn_crypto::address::pubkey_to_address(&b.spindle)),
+                    bond: b.amount.to_string(),
+                    fee_per_period: b.fee_per_period.to_string(),
+                    subscriber_count: market.subscriber_count(&b.spindle) as u64,
+                    slashed: slashed.to_string(),
+                }
+            })
+            .collect())
+    }
+
+    async fn get_spindle_subscriptions(
+        &self,
+        address_hex: String,
+    ) -> Result<Vec<SpindleSubscriptionInfo>, ErrorObjectOwned> {
+        let address = parse_address(&address_hex, self.network_id)?;
+        let sm = self.state_manager.read().await;
+        let market = sm.spindle_market();
+        Ok(market
+            .watchers(&address)
+            .into_iter()
+            .map(|sub| SpindleSubscriptionInfo {
+                thread_id: hex::encode(sub.thread_id),
+                spindle: hex::encode(sub.spindle),
+                fee_per_period: sub.fee_per_period.to_string(),
+                start_height: sub.start_height,
+                end_height: sub.end_height(market.period_blocks()),
+                periods: sub.periods,
+                periods_paid: sub.periods_paid,
+            })
+            .collect())
+    }
+
+    async fn get_spindle_coverage(
+        &self,
+        address_hex: String,
+        redundancy: Option<usize>,
+    ) -> Result<SpindleCoverageInfo, ErrorObjectOwned> {
+        let address = parse_address(&address_hex, self.network_id)?;
+        let redundancy = redundancy.unwrap_or(norn_types::constants::DEFAULT_SPINDLE_REDUNDANCY);
+        let watchers: Vec<String> = match self.relay_handle {
+            Some(ref handle) => match handle.spindle_registry().lock() {
+                Ok(registry) => registry
+                    .watchers(&address)
+                    .into_iter()
+                    .map(|c| hex::encode(c.pubkey))
+                    .collect(),
+                Err(_) => Vec::new(),
+            },
+            None => Vec::new(),
+        };
+        Ok(SpindleCoverageInfo {
+            thread_id: hex::encode(address),
+            covered: watchers.len() >= redundancy,
+            watchers,
+            redundancy,
+        })
+    }
+
     async fn get_validator_rewards(&self) -> Result<ValidatorRewardsInfo, ErrorObjectOwned> {
         let engine = self.weave_engine.read().await;
         let vs = engine.validator_set();
@@ -2845,10 +4749,9 @@ impl NornRpcServer for NornRpcImpl {
     ) -> Result<StakingInfo, ErrorObjectOwned> {
         let engine = self.weave_engine.read().await;
         let staking = engine.staking();
-        let vs = staking.active_validators();
 
-        let validators: Vec<ValidatorStakeInfo> = vs
-            .validators
+        let validators: Vec<ValidatorStakeInfo> = staking
+            .candidates()
             .iter()
             .filter(|v| {
                 if let Some(ref hex) = pubkey_hex {
@@ -2860,8 +4763,17 @@ impl NornRpcServer for NornRpcImpl {
             .map(|v| ValidatorStakeInfo {
                 pubkey: hex::encode(v.pubkey),
                 address: hex::encode(v.address),
-                stake: v.stake.to_string(),
+                stake: staking.validator_stake(&v.pubkey).unwrap_or(0).to_string(),
+                delegated: staking.delegated_stake(&v.pubkey).to_string(),
+                delegator_count: staking.delegator_count(&v.pubkey) as u64,
+                commission_bps: staking.validator_commission(&v.pubkey).unwrap_or(0),
+                pending_stake: staking.validator_pending_stake(&v.pubkey).to_string(),
+                pending_unstake: staking.validator_pending_unstake(&v.pubkey).to_string(),
                 active: v.active,
+                jailed: staking.is_jailed(&v.pubkey),
+                jailed_until: staking
+                    .jailed_until(&v.pubkey)
+                    .filter(|_| !staking.is_tombstoned(&v.pubkey)),
             })
             .collect();
 
@@ -2870,6 +4782,8 @@ impl NornRpcServer for NornRpcImpl {
             total_staked: staking.total_staked().to_string(),
             min_stake: staking.min_stake().to_string(),
             bonding_period: staking.bonding_period(),
+            epoch: staking.epoch(),
+            max_validators: staking.max_validators() as u64,
         })
     }
 
@@ -2881,52 +4795,102 @@ impl NornRpcServer for NornRpcImpl {
 
     async fn get_state_proof(
         &self,
-        address_hex: String,
-        token_id_hex: Option<String>,
-    ) -> Result<StateProofInfo, ErrorObjectOwned> {
-        let address_bytes = hex::decode(address_hex.trim_start_matches("0x")).map_err(|e| {
-            ErrorObjectOwned::owned(-32602, format!("invalid address: {}", e), None::<()>)
-        })?;
-        if address_bytes.len() != 20 {
-            return Err(ErrorObjectOwned::owned(
-                -32602,
-                "address must be 20 bytes",
-                None::<()>,
-            ));
-        }
-        let mut address = [0u8; 20];
-        address.copy_from_slice(&address_bytes);
-
-        let token_id = if let Some(ref hex_str) = token_id_hex {
-            let bytes = hex::decode(hex_str.trim_start_matches("0x")).map_err(|e| {
-                ErrorObjectOwned::owned(-32602, format!("invalid token_id: {}", e), None::<()>)
-            })?;
-            if bytes.len() != 32 {
+        address_hex: OneOrMany<String>,
+        token_id_hex: Option<OneOrMany<String>>,
+    ) -> Result<StateProofResult, ErrorObjectOwned> {
+        const MAX_STATE_PROOF_BALANCES: usize = 1_000;
+
+        let single = matches!(
+            (&address_hex, &token_id_hex),
+            (OneOrMany::One(_), None | Some(OneOrMany::One(_)))
+        );
+        let addresses = address_hex
+            .into_vec()
+            .iter()
+            .map(|a| parse_address(a, self.network_id))
+            .collect::<Result<Vec<_>, _>>()?;
+        let token_ids = match token_id_hex {
+            Some(ids) => ids
+                .into_vec()
+                .iter()
+                .map(|t| parse_token_hex(t.trim_start_matches("0x")))
+                .collect::<Result<Vec<_>, _>>()?,
+            None => vec![NATIVE_TOKEN_ID],
+        };
+        // One token applies to every address, one address to every token;
+        // otherwise the lists are paired up.
+        let balances: Vec<([u8; 20], [u8; 32])> = match (addresses.len(), token_ids.len()) {
+            (_, 1) => addresses.iter().map(|a| (*a, token_ids[0])).collect(),
+            (1, _) => token_ids.iter().map(|t| (addresses[0], *t)).collect(),
+            (a, t) if a == t => addresses.into_iter().zip(token_ids).collect(),
+            (a, t) => {
                 return Err(ErrorObjectOwned::owned(
                     -32602,
-                    "token_id must be 32 bytes",
+                    format!("got {} addresses but {} token IDs", a, t),
                     None::<()>,
-                ));
+                ))
             }
-            let mut id = [0u8; 32];
-            id.copy_from_slice(&bytes);
-            id
-        } else {
-            NATIVE_TOKEN_ID
         };
+        if balances.is_empty() {
+            return Err(ErrorObjectOwned::owned(
+                -32602,
+                "no balances requested",
+                None::<()>,
+            ));
+        }
+        if balances.len() > MAX_STATE_PROOF_BALANCES {
+            return Err(ErrorObjectOwned::owned(
+                -32602,
+                format!(
+                    "at most {} balances per proof, got {}",
+                    MAX_STATE_PROOF_BALANCES,
+                    balances.len()
+                ),
+                None::<()>,
+            ));
+        }
 
         let sm = self.state_manager.read().await;
-        let balance = sm.get_balance(&address, &token_id);
-        let proof = sm.state_proof(&address, &token_id);
         let root = sm.state_root();
 
-        Ok(StateProofInfo {
-            address: format!("0x{}", hex::encode(address)),
-            token_id: hex::encode(token_id),
-            balance: balance.to_string(),
+        if single {
+            let (address, token_id) = balances[0];
+            let balance = sm.get_balance(&address, &token_id);
+            let proof = sm.state_proof(&address, &token_id);
+            return Ok(StateProofResult::Single(StateProofInfo {
+                address: format!("0x{}", hex::encode(address)),
+                token_id: hex::encode(token_id),
+                balance: balance.to_string(),
+                state_root: hex::encode(root),
+                proof: proof.siblings.iter().map(hex::encode).collect(),
+            }));
+        }
+
+        let proof = sm.state_multiproof(&balances);
+        let values: std::collections::HashMap<_, _> = proof
+            .leaves
+            .iter()
+            .map(|(key, value)| (*key, value))
+            .collect();
+        let balances = balances
+            .iter()
+            .map(|(address, token_id)| StateProofBalance {
+                address: format!("0x{}", hex::encode(address)),
+                token_id: hex::encode(token_id),
+                balance: sm.get_balance(address, token_id).to_string(),
+                value: values
+                    .get(&sm.smt_key(address, token_id))
+                    .map(hex::encode)
+                    .unwrap_or_default(),
+            })
+            .collect();
+
+        Ok(StateProofResult::Multi(StateMultiProofInfo {
             state_root: hex::encode(root),
-            proof: proof.siblings.iter().map(hex::encode).collect(),
-        })
+            balances,
+            sibling_bitmap: hex::encode(&proof.sibling_bitmap),
+            siblings: proof.siblings.iter().map(hex::encode).collect(),
+        }))
     }
 
     async fn get_block_transactions(
@@ -3084,6 +5048,87 @@ impl NornRpcServer for NornRpcImpl {
             loom_deploys,
         }))
     }
+
+    async fn get_block_headers(
+        &self,
+        from_height: u64,
+        count: u64,
+    ) -> Result<Vec<BlockHeaderInfo>, ErrorObjectOwned> {
+        const MAX_BLOCK_HEADERS: u64 = 1_000;
+        if count == 0 || count > MAX_BLOCK_HEADERS {
+            return Err(ErrorObjectOwned::owned(
+                -32602,
+                format!("count must be between 1 and {}", MAX_BLOCK_HEADERS),
+                None::<()>,
+            ));
+        }
+
+        let sm = self.state_manager.read().await;
+        let mut headers = Vec::new();
+        for height in from_height..from_height.saturating_add(count) {
+            let Some(block) = sm.get_block_by_height(height) else {
+                break;
+            };
+            let header = block.header();
+            let bytes = borsh::to_vec(&header).map_err(|e| {
+                ErrorObjectOwned::owned(-32603, format!("serialization error: {}", e), None::<()>)
+            })?;
+            headers.push(BlockHeaderInfo {
+                height: header.height,
+                hash: hex::encode(header.hash),
+                header_hex: hex::encode(bytes),
+            });
+        }
+        Ok(headers)
+    }
+
+    async fn get_transfer_proof(
+        &self,
+        knot_id: String,
+    ) -> Result<Option<TransferProofInfo>, ErrorObjectOwned> {
+        let knot_bytes: [u8; 32] = hex::decode(&knot_id)
+            .map_err(|e| {
+                ErrorObjectOwned::owned(-32602, format!("invalid hex: {}", e), None::<()>)
+            })?
+            .try_into()
+            .map_err(|_| ErrorObjectOwned::owned(-32602, "knot_id must be 32 bytes", None::<()>))?;
+
+        let sm = self.state_manager.read().await;
+        let Some(proof) = sm.transfer_proof(&knot_bytes) else {
+            return Ok(None);
+        };
+        let bytes = borsh::to_vec(&proof).map_err(|e| {
+            ErrorObjectOwned::owned(-32603, format!("serialization error: {}", e), None::<()>)
+        })?;
+        let block = &proof.headers[0];
+        Ok(Some(TransferProofInfo {
+            knot_id: hex::encode(knot_bytes),
+            block_height: block.height,
+            block_hash: hex::encode(block.hash),
+            confirmations: proof.headers.len() as u64,
+            proof_hex: hex::encode(bytes),
+        }))
+    }
+
+    async fn convert_address(&self, address: String) -> Result<AddressInfo, ErrorObjectOwned> {
+        let addr = parse_address(&address, self.network_id)?;
+        Ok(AddressInfo {
+            hex: hex::encode(addr),
+            bech32: norn_crypto::address::address_to_bech32(&addr, self.network_id),
+            network: self.network_id.as_str().to_string(),
+        })
+    }
+
+    async fn get_log_levels(&self) -> Result<LogLevels, ErrorObjectOwned> {
+        crate::logging::levels().ok_or_else(|| {
+            ErrorObjectOwned::owned(-32603, "logging is not initialized", None::<()>)
+        })
+    }
+
+    async fn set_log_levels(&self, levels: LogLevels) -> Result<LogLevels, ErrorObjectOwned> {
+        crate::logging::set_levels(levels)
+            .map_err(|e| ErrorObjectOwned::owned(-32602, e, None::<()>))
+    }
 }
 
 #[cfg(test)]
@@ -3117,4 +5162,36 @@ mod tests {
             production_us: None,
         };
     }
+
+    #[test]
+    fn test_knot_rejection_codes_are_distinct() {
+        let codes = [
+            knot_rejection(NornError::KnotExpired {
+                expiry: 1,
+                current: 2,
+            })
+            .code(),
+            knot_rejection(NornError::TimestampTooFuture {
+                timestamp: 2,
+                max_allowed: 1,
+            })
+            .code(),
+            knot_rejection(NornError::KnotOutOfOrder {
+                participant_index: 0,
+                version: 0,
+                next: 1,
+            })
+            .code(),
+            knot_rejection(NornError::KnotReplayed).code(),
+        ];
+        assert_eq!(
+            codes,
+            [
+                KNOT_EXPIRED_CODE,
+                KNOT_TIMESTAMP_CODE,
+                KNOT_OUT_OF_ORDER_CODE,
+                KNOT_REPLAYED_CODE
+            ]
+        );
+    }
 }
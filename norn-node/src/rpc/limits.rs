@@ -0,0 +1,419 @@
+//! RPC limits protecting the node from abusive clients.
+//!
+//! Calls without an API key are rate limited per client IP: at most
+//! `rpc.rate_limit_per_ip_per_minute` calls a minute overall, and at most
+//! `rpc.method_rate_limits[method]` calls a minute of each expensive method.
+//! API-key callers are only bound by their key's own limit (see
+//! [`super::auth`]). Every call is further subject to
+//! `rpc.request_timeout_secs`, and calls slower than `rpc.slow_query_ms` are
+//! logged.
+//!
+//! Behind a reverse proxy, every request would come from the proxy's IP.
+//! Requests from the addresses in `rpc.trusted_proxies` are therefore
+//! attributed to the client their `X-Forwarded-For` header names instead.
+//!
+//! An IPv6 client is limited by its /64, the smallest block a host is
+//! usually given, so rotating addresses within it gains nothing.
+
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv6Addr, SocketAddr};
+use std::num::NonZeroUsize;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use lru::LruCache;
+
+use super::auth::RateLimiter;
+use crate::config::RpcConfig;
+use crate::error::NodeError;
+use crate::metrics::RpcRejectReason;
+
+/// Clients tracked before the least recently seen are forgotten.
+const MAX_TRACKED_CLIENTS: NonZeroUsize = match NonZeroUsize::new(10_000) {
+    Some(n) => n,
+    None => unreachable!(),
+};
+
+/// Header in which reverse proxies name the client.
+pub const FORWARDED_FOR: &str = "x-forwarded-for";
+
+/// IP address of the client that sent a request, attached to each request
+/// by the RPC server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientIp(pub IpAddr);
+
+/// Why a call was refused by a rate limit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LimitError {
+    /// The client IP is over its overall limit.
+    IpRateLimited,
+    /// The client IP is over the method's limit.
+    MethodRateLimited { method: String },
+}
+
+impl LimitError {
+    /// JSON-RPC error code, shared with API-key rate limits.
+    pub fn code(&self) -> i32 {
+        -32005
+    }
+
+    /// Label of the rejection in `norn_rpc_requests_rejected`.
+    pub fn reason(&self) -> RpcRejectReason {
+        match self {
+            LimitError::IpRateLimited => RpcRejectReason::IpRateLimited,
+            LimitError::MethodRateLimited { .. } => RpcRejectReason::MethodRateLimited,
+        }
+    }
+}
+
+impl std::fmt::Display for LimitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LimitError::IpRateLimited => {
+                write!(f, "rate limited: too many requests from this address")
+            }
+            LimitError::MethodRateLimited { method } => {
+                write!(
+                    f,
+                    "rate limited: too many {} requests from this address",
+                    method
+                )
+            }
+        }
+    }
+}
+
+/// An IP address, or a range of them in CIDR notation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpRange {
+    addr: IpAddr,
+    prefix: u8,
+}
+
+impl IpRange {
+    /// Whether `ip` is in the range.
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        match (self.addr, ip.to_canonical()) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => {
+                let mask = u32::MAX.checked_shl(32 - self.prefix as u32).unwrap_or(0);
+                u32::from(net) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                let mask = u128::MAX.checked_shl(128 - self.prefix as u32).unwrap_or(0);
+                u128::from(net) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for IpRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (addr, prefix) = s.split_once('/').unwrap_or((s, ""));
+        let addr: IpAddr = addr
+            .parse()
+            .map_err(|_| format!("'{}' is not an IP address or CIDR range", s))?;
+        let max = if addr.is_ipv4() { 32 } else { 128 };
+        let prefix = match prefix {
+            "" => max,
+            prefix => prefix
+                .parse()
+                .ok()
+                .filter(|&p| p <= max)
+                .ok_or_else(|| format!("'{}' has an invalid prefix length", s))?,
+        };
+        Ok(Self {
+            addr: addr.to_canonical(),
+            prefix,
+        })
+    }
+}
+
+/// Rate, size and time limits of the RPC server.
+#[derive(Debug)]
+pub struct RpcLimits {
+    /// Most concurrent connections.
+    pub max_connections: u32,
+    /// Largest request body, in bytes.
+    pub max_request_body_bytes: u32,
+    /// Largest response, in bytes.
+    pub max_response_body_bytes: u32,
+    /// Most calls in one batch; batches are disabled at 0.
+    pub max_batch_size: u32,
+    /// How long a call may run.
+    pub request_timeout: Option<Duration>,
+    /// Calls at least this slow are logged.
+    pub slow_query: Option<Duration>,
+    per_ip: Option<u32>,
+    per_method: HashMap<String, u32>,
+    trusted_proxies: Vec<IpRange>,
+    ip_buckets: Mutex<LruCache<IpAddr, RateLimiter>>,
+    method_buckets: Mutex<LruCache<(IpAddr, String), RateLimiter>>,
+}
+
+impl RpcLimits {
+    /// Build the limits from config.
+    pub fn from_config(config: &RpcConfig) -> Result<Self, NodeError> {
+        let invalid = |reason: String| NodeError::ConfigError { reason };
+        let max_connections = u32::try_from(config.max_connections)
+            .ok()
+            .filter(|&n| n > 0)
+            .ok_or_else(|| invalid("rpc.max_connections must be between 1 and 2^32-1".into()))?;
+        if config.max_request_body_bytes == 0 || config.max_response_body_bytes == 0 {
+            return Err(invalid(
+                "rpc.max_request_body_bytes and rpc.max_response_body_bytes must be positive"
+                    .into(),
+            ));
+        }
+        if let Some((method, _)) = config.method_rate_limits.iter().find(|(_, &l)| l == 0) {
+            return Err(invalid(format!(
+                "rpc.method_rate_limits '{}': limit must be positive",
+                method
+            )));
+        }
+
+        let trusted_proxies = config
+            .trusted_proxies
+            .iter()
+            .map(|proxy| proxy.parse())
+            .collect::<Result<_, String>>()
+            .map_err(|e| invalid(format!("rpc.trusted_proxies: {}", e)))?;
+
+        let millis = |ms: u64| (ms > 0).then(|| Duration::from_millis(ms));
+        Ok(Self {
+            max_connections,
+            max_request_body_bytes: config.max_request_body_bytes,
+            max_response_body_bytes: config.max_response_body_bytes,
+            max_batch_size: config.max_batch_size,
+            request_timeout: millis(config.request_timeout_secs.saturating_mul(1_000)),
+            slow_query: millis(config.slow_query_ms),
+            per_ip: (config.rate_limit_per_ip_per_minute > 0)
+                .then_some(config.rate_limit_per_ip_per_minute),
+            per_method: config
+                .method_rate_limits
+                .iter()
+                .map(|(method, &limit)| (method.clone(), limit))
+                .collect(),
+            trusted_proxies,
+            ip_buckets: Mutex::new(LruCache::new(MAX_TRACKED_CLIENTS)),
+            method_buckets: Mutex::new(LruCache::new(MAX_TRACKED_CLIENTS)),
+        })
+    }
+
+    /// The client a connection from `peer` makes its requests for: `peer`
+    /// itself or, if it is a trusted proxy, the nearest untrusted address in
+    /// the request's `X-Forwarded-For` values.
+    pub fn client_ip<'a>(
+        &self,
+        peer: IpAddr,
+        forwarded_for: impl IntoIterator<Item = &'a str>,
+    ) -> IpAddr {
+        let trusted = |ip: IpAddr| self.trusted_proxies.iter().any(|p| p.contains(ip));
+        if !trusted(peer) {
+            return peer;
+        }
+        let hops: Vec<&str> = forwarded_for
+            .into_iter()
+            .flat_map(|value| value.split(','))
+            .collect();
+        let mut client = peer;
+        for hop in hops.iter().rev().map(|hop| hop.trim()) {
+            let Some(ip) = hop
+                .parse::<IpAddr>()
+                .ok()
+                .or_else(|| hop.parse::<SocketAddr>().ok().map(|a| a.ip()))
+            else {
+                break;
+            };
+            client = ip.to_canonical();
+            if !trusted(client) {
+                break;
+            }
+        }
+        client
+    }
+
+    /// Take one call of `method` from `ip`'s allowance.
+    pub fn check(&self, ip: IpAddr, method: &str, now: Instant) -> Result<(), LimitError> {
+        let ip = client_key(ip);
+        if let Some(&per_minute) = self.per_method.get(method) {
+            if !acquire(
+                &self.method_buckets,
+                (ip, method.to_string()),
+                per_minute,
+                now,
+            ) {
+                return Err(LimitError::MethodRateLimited {
+                    method: method.to_string(),
+                });
+            }
+        }
+        if let Some(per_minute) = self.per_ip {
+            if !acquire(&self.ip_buckets, ip, per_minute, now) {
+                return Err(LimitError::IpRateLimited);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The address `ip` is limited under: IPv6 clients by their /64.
+fn client_key(ip: IpAddr) -> IpAddr {
+    match ip.to_canonical() {
+        IpAddr::V6(v6) => IpAddr::V6(Ipv6Addr::from(u128::from(v6) & !(u64::MAX as u128))),
+        v4 => v4,
+    }
+}
+
+/// Take one call from `key`'s bucket, creating it if needed. At most
+/// [`MAX_TRACKED_CLIENTS`] buckets are kept; a new client evicts the least
+/// recently seen one.
+fn acquire<K: std::hash::Hash + Eq>(
+    buckets: &Mutex<LruCache<K, RateLimiter>>,
+    key: K,
+    per_minute: u32,
+    now: Instant,
+) -> bool {
+    let mut buckets = buckets.lock().unwrap_or_else(|e| e.into_inner());
+    buckets
+        .get_or_insert(key, || RateLimiter::new(per_minute))
+        .try_acquire(now)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::NodeConfig;
+
+    fn limits(per_ip: u32, per_method: &[(&str, u32)]) -> RpcLimits {
+        let mut rpc = NodeConfig::default().rpc;
+        rpc.rate_limit_per_ip_per_minute = per_ip;
+        rpc.method_rate_limits = per_method
+            .iter()
+            .map(|(method, limit)| (method.to_string(), *limit))
+            .collect();
+        RpcLimits::from_config(&rpc).unwrap()
+    }
+
+    #[test]
+    fn test_per_ip_and_per_method_limits() {
+        let limits = limits(3, &[("norn_getTransactionHistory", 1)]);
+        let a: IpAddr = "10.0.0.1".parse().unwrap();
+        let b: IpAddr = "10.0.0.2".parse().unwrap();
+        let now = Instant::now();
+
+        assert_eq!(limits.check(a, "norn_getTransactionHistory", now), Ok(()));
+        assert_eq!(
+            limits.check(a, "norn_getTransactionHistory", now),
+            Err(LimitError::MethodRateLimited {
+                method: "norn_getTransactionHistory".to_string()
+            })
+        );
+        assert_eq!(limits.check(a, "norn_getBalance", now), Ok(()));
+        assert_eq!(limits.check(a, "norn_getBalance", now), Ok(()));
+        assert_eq!(
+            limits.check(a, "norn_getBalance", now),
+            Err(LimitError::IpRateLimited)
+        );
+
+        // Other clients have their own allowance, and allowances refill.
+        assert_eq!(limits.check(b, "norn_getTransactionHistory", now), Ok(()));
+        let later = now + Duration::from_secs(60);
+        assert_eq!(limits.check(a, "norn_getTransactionHistory", later), Ok(()));
+    }
+
+    #[test]
+    fn test_ipv6_clients_limited_per_64() {
+        let limits = limits(1, &[]);
+        let ip = |s: &str| s.parse::<IpAddr>().unwrap();
+        let now = Instant::now();
+
+        assert_eq!(
+            limits.check(ip("2001:db8:1:2::1"), "norn_getBalance", now),
+            Ok(())
+        );
+        assert_eq!(
+            limits.check(ip("2001:db8:1:2:ffff::9"), "norn_getBalance", now),
+            Err(LimitError::IpRateLimited)
+        );
+        assert_eq!(
+            limits.check(ip("2001:db8:1:3::1"), "norn_getBalance", now),
+            Ok(())
+        );
+        // IPv4-mapped addresses are still limited per IPv4 address.
+        assert_eq!(
+            limits.check(ip("::ffff:192.0.2.1"), "norn_getBalance", now),
+            Ok(())
+        );
+        assert_eq!(
+            limits.check(ip("::ffff:192.0.2.2"), "norn_getBalance", now),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_tracked_clients_are_capped() {
+        let limits = limits(1, &[]);
+        let now = Instant::now();
+        let first: IpAddr = "10.0.0.1".parse().unwrap();
+        assert_eq!(limits.check(first, "norn_getBalance", now), Ok(()));
+
+        for n in 0..MAX_TRACKED_CLIENTS.get() as u32 {
+            let ip = IpAddr::V4((0x0b00_0000 + n).into());
+            assert_eq!(limits.check(ip, "norn_getBalance", now), Ok(()));
+        }
+        let buckets = limits.ip_buckets.lock().unwrap();
+        assert_eq!(buckets.len(), MAX_TRACKED_CLIENTS.get());
+        // The least recently seen client was evicted to make room.
+        assert!(!buckets.contains(&first));
+    }
+
+    #[test]
+    fn test_client_ip_behind_trusted_proxies() {
+        let mut rpc = NodeConfig::default().rpc;
+        rpc.trusted_proxies = vec!["10.0.0.0/8".to_string(), "::1".to_string()];
+        let limits = RpcLimits::from_config(&rpc).unwrap();
+        let ip = |s: &str| s.parse::<IpAddr>().unwrap();
+
+        // Untrusted peers cannot claim another address.
+        assert_eq!(
+            limits.client_ip(ip("203.0.113.9"), ["198.51.100.1"]),
+            ip("203.0.113.9")
+        );
+        // The nearest address not of a trusted proxy is the client.
+        assert_eq!(
+            limits.client_ip(ip("10.1.2.3"), ["6.6.6.6, 198.51.100.1", "10.0.0.2"]),
+            ip("198.51.100.1")
+        );
+        assert_eq!(
+            limits.client_ip(ip("::ffff:10.0.0.1"), ["198.51.100.1:4242"]),
+            ip("198.51.100.1")
+        );
+        assert_eq!(limits.client_ip(ip("::1"), []), ip("::1"));
+        assert_eq!(
+            limits.client_ip(ip("::1"), ["garbage", "10.0.0.7"]),
+            ip("10.0.0.7")
+        );
+
+        rpc.trusted_proxies = vec!["10.0.0.0/33".to_string()];
+        assert!(RpcLimits::from_config(&rpc).is_err());
+    }
+
+    #[test]
+    fn test_zero_disables_limits() {
+        let limits = limits(0, &[]);
+        let ip: IpAddr = "::1".parse().unwrap();
+        let now = Instant::now();
+        for _ in 0..1_000 {
+            assert_eq!(limits.check(ip, "norn_getBalance", now), Ok(()));
+        }
+
+        let mut rpc = NodeConfig::default().rpc;
+        rpc.method_rate_limits
+            .insert("norn_getEvents".to_string(), 0);
+        assert!(RpcLimits::from_config(&rpc).is_err());
+    }
+}
@@ -1,5 +1,10 @@
 pub mod auth;
 pub mod chat_store;
+pub mod graphql;
+pub mod grpc;
 pub mod handlers;
+pub mod limits;
+pub mod rest;
 pub mod server;
+pub mod transport;
 pub mod types;
@@ -0,0 +1,475 @@
+//! REST gateway over the JSON-RPC API.
+//!
+//! With `rpc.rest_gateway` enabled, the RPC server also answers `GET /v1/...`
+//! requests for common queries, such as `/v1/blocks/{height}` or
+//! `/v1/accounts/{address}/balance`. Each endpoint maps to one JSON-RPC
+//! method. The request is translated into a call of that method and passed
+//! through the same middleware as any other call, so auth and rate limits
+//! apply unchanged. The result is returned as the response body; a `null`
+//! result is a 404 and an error becomes `{"error":{"code":..,"message":..}}`
+//! with a matching HTTP status.
+//!
+//! [`ENDPOINTS`] defines both the routes and the OpenAPI document served at
+//! `/v1/openapi.json`.
+
+use std::collections::HashMap;
+
+use axum::extract::{Path, Query};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use http::StatusCode;
+use serde_json::{json, Map, Value};
+
+/// Prefix of every gateway path.
+pub const PREFIX: &str = "/v1/";
+
+/// How a parameter is parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamKind {
+    /// Unsigned integer.
+    Integer,
+    /// String, passed through as is.
+    String,
+}
+
+/// A parameter of an endpoint, in the order the JSON-RPC method takes it.
+#[derive(Debug)]
+pub struct Param {
+    pub name: &'static str,
+    /// Taken from the path rather than the query string.
+    pub in_path: bool,
+    pub kind: ParamKind,
+    /// Value of an omitted query parameter; required when `None`.
+    pub default: Option<&'static str>,
+    pub description: &'static str,
+}
+
+/// A REST endpoint and the JSON-RPC method it calls.
+#[derive(Debug)]
+pub struct Endpoint {
+    /// Path with `{param}` placeholders.
+    pub path: &'static str,
+    pub method: &'static str,
+    pub summary: &'static str,
+    pub params: &'static [Param],
+    /// Response type, as named in the RPC types.
+    pub result: &'static str,
+    /// The method may return `null`, answered with 404.
+    pub optional: bool,
+}
+
+const fn path(name: &'static str, kind: ParamKind, description: &'static str) -> Param {
+    Param {
+        name,
+        in_path: true,
+        kind,
+        default: None,
+        description,
+    }
+}
+
+const fn query(
+    name: &'static str,
+    kind: ParamKind,
+    default: &'static str,
+    description: &'static str,
+) -> Param {
+    Param {
+        name,
+        in_path: false,
+        kind,
+        default: Some(default),
+        description,
+    }
+}
+
+const HEIGHT: Param = path("height", ParamKind::Integer, "Block height.");
+const ADDRESS: Param = path("address", ParamKind::String, "Address, hex or bech32m.");
+const LIMIT: Param = query("limit", ParamKind::Integer, "100", "Most items returned.");
+const OFFSET: Param = query("offset", ParamKind::Integer, "0", "Items skipped.");
+
+/// The gateway's endpoints.
+pub const ENDPOINTS: &[Endpoint] = &[
+    Endpoint {
+        path: "/v1/health",
+        method: "norn_health",
+        summary: "Node health and sync status",
+        params: &[],
+        result: "HealthInfo",
+        optional: false,
+    },
+    Endpoint {
+        path: "/v1/weave",
+        method: "norn_getWeaveState",
+        summary: "Current weave state",
+        params: &[],
+        result: "WeaveStateInfo",
+        optional: true,
+    },
+    Endpoint {
+        path: "/v1/blocks/latest",
+        method: "norn_getLatestBlock",
+        summary: "Latest block",
+        params: &[],
+        result: "BlockInfo",
+        optional: true,
+    },
+    Endpoint {
+        path: "/v1/blocks/{height}",
+        method: "norn_getBlock",
+        summary: "Block at a height",
+        params: &[HEIGHT],
+        result: "BlockInfo",
+        optional: true,
+    },
+    Endpoint {
+        path: "/v1/blocks/{height}/transactions",
+        method: "norn_getBlockTransactions",
+        summary: "Transactions in the block at a height",
+        params: &[HEIGHT],
+        result: "BlockTransactionsInfo",
+        optional: true,
+    },
+    Endpoint {
+        path: "/v1/accounts/{address}",
+        method: "norn_getThreadState",
+        summary: "State of an account's thread",
+        params: &[ADDRESS],
+        result: "ThreadStateInfo",
+        optional: true,
+    },
+    Endpoint {
+        path: "/v1/accounts/{address}/balance",
+        method: "norn_getBalance",
+        summary: "Balance of an account in one token",
+        params: &[
+            ADDRESS,
+            query(
+                "token",
+                ParamKind::String,
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "Token ID (hex); NORN by default.",
+            ),
+        ],
+        result: "string",
+        optional: false,
+    },
+    Endpoint {
+        path: "/v1/accounts/{address}/transactions",
+        method: "norn_getTransactionHistory",
+        summary: "Transaction history of an account",
+        params: &[ADDRESS, LIMIT, OFFSET],
+        result: "TransactionHistoryEntry[]",
+        optional: false,
+    },
+    Endpoint {
+        path: "/v1/transactions/{id}",
+        method: "norn_getTransaction",
+        summary: "Transaction by knot ID",
+        params: &[path("id", ParamKind::String, "Knot ID (hex).")],
+        result: "TransactionHistoryEntry",
+        optional: true,
+    },
+    Endpoint {
+        path: "/v1/tokens",
+        method: "norn_listTokens",
+        summary: "Registered tokens",
+        params: &[LIMIT, OFFSET],
+        result: "TokenInfo[]",
+        optional: false,
+    },
+    Endpoint {
+        path: "/v1/tokens/{id}",
+        method: "norn_getTokenInfo",
+        summary: "Token by ID",
+        params: &[path("id", ParamKind::String, "Token ID (hex).")],
+        result: "TokenInfo",
+        optional: true,
+    },
+    Endpoint {
+        path: "/v1/looms",
+        method: "norn_listLooms",
+        summary: "Deployed looms",
+        params: &[LIMIT, OFFSET],
+        result: "LoomInfo[]",
+        optional: false,
+    },
+    Endpoint {
+        path: "/v1/looms/{id}",
+        method: "norn_getLoomInfo",
+        summary: "Loom by ID",
+        params: &[path("id", ParamKind::String, "Loom ID (hex).")],
+        result: "LoomInfo",
+        optional: true,
+    },
+    Endpoint {
+        path: "/v1/names/{name}",
+        method: "norn_resolveName",
+        summary: "Resolve a registered name",
+        params: &[path("name", ParamKind::String, "Registered name.")],
+        result: "NameResolution",
+        optional: true,
+    },
+    Endpoint {
+        path: "/v1/validators",
+        method: "norn_getValidatorSet",
+        summary: "Current validator set",
+        params: &[],
+        result: "ValidatorSetInfo",
+        optional: false,
+    },
+];
+
+/// A JSON-RPC call a REST request translates to. The router answers with it
+/// in the response extensions, for the gateway middleware to make.
+#[derive(Debug, Clone)]
+pub struct RpcCall {
+    pub endpoint: &'static Endpoint,
+    pub params: Vec<Value>,
+}
+
+impl IntoResponse for RpcCall {
+    fn into_response(self) -> Response {
+        let mut response = StatusCode::OK.into_response();
+        response.extensions_mut().insert(self);
+        response
+    }
+}
+
+/// Router translating gateway requests to [`RpcCall`]s, and serving the
+/// OpenAPI document.
+pub fn router() -> Router {
+    let mut router = Router::new().route(
+        "/v1/openapi.json",
+        get(|| async { Json(openapi_document()) }),
+    );
+    for endpoint in ENDPOINTS {
+        router = router.route(
+            &axum_path(endpoint.path),
+            get(
+                move |path: Option<Path<HashMap<String, String>>>,
+                      Query(query): Query<HashMap<String, String>>| async move {
+                    let path = path.map(|Path(p)| p).unwrap_or_default();
+                    translate(endpoint, &path, &query).map_err(|e| error_response(e.0, e.1))
+                },
+            ),
+        );
+    }
+    router
+}
+
+/// The `{param}` placeholders of `path` in axum's `:param` syntax.
+fn axum_path(path: &str) -> String {
+    path.split('/')
+        .map(|segment| match segment.strip_prefix('{') {
+            Some(name) => format!(":{}", name.trim_end_matches('}')),
+            None => segment.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Build the call to `endpoint` from the request's path and query.
+fn translate(
+    endpoint: &'static Endpoint,
+    path: &HashMap<String, String>,
+    query: &HashMap<String, String>,
+) -> Result<RpcCall, (i32, String)> {
+    let params = endpoint
+        .params
+        .iter()
+        .map(|param| {
+            let raw = if param.in_path {
+                path.get(param.name).map(String::as_str)
+            } else {
+                query.get(param.name).map(String::as_str).or(param.default)
+            }
+            .ok_or_else(|| (-32602, format!("missing parameter '{}'", param.name)))?;
+            match param.kind {
+                ParamKind::String => Ok(Value::from(raw)),
+                ParamKind::Integer => raw.parse::<u64>().map(Value::from).map_err(|_| {
+                    (
+                        -32602,
+                        format!("parameter '{}' must be an unsigned integer", param.name),
+                    )
+                }),
+            }
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(RpcCall { endpoint, params })
+}
+
+/// HTTP status of a JSON-RPC error.
+pub fn error_status(code: i32) -> StatusCode {
+    match code {
+        -32602 | -32600 | -32700 => StatusCode::BAD_REQUEST,
+        -32001 => StatusCode::FORBIDDEN,
+        -32005 => StatusCode::TOO_MANY_REQUESTS,
+        -32007 => StatusCode::PAYLOAD_TOO_LARGE,
+        -32008 => StatusCode::GATEWAY_TIMEOUT,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+/// A gateway error response.
+pub fn error_response(code: i32, message: String) -> Response {
+    (
+        error_status(code),
+        Json(json!({ "error": { "code": code, "message": message } })),
+    )
+        .into_response()
+}
+
+/// Turn the JSON-RPC response to `call` into the REST response.
+pub fn rpc_response(call: &RpcCall, body: &[u8]) -> Response {
+    let mut response: Map<String, Value> = match serde_json::from_slice(body) {
+        Ok(Value::Object(response)) => response,
+        _ => return error_response(-32603, "invalid JSON-RPC response".to_string()),
+    };
+    if let Some(error) = response.remove("error") {
+        let code = error.get("code").and_then(Value::as_i64).unwrap_or(-32603) as i32;
+        let message = error
+            .get("message")
+            .and_then(Value::as_str)
+            .unwrap_or("internal error")
+            .to_string();
+        return error_response(code, message);
+    }
+    let result = response.remove("result").unwrap_or(Value::Null);
+    if result.is_null() && call.endpoint.optional {
+        let message = format!("{} not found", call.endpoint.result);
+        return (
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": { "message": message } })),
+        )
+            .into_response();
+    }
+    Json(result).into_response()
+}
+
+/// OpenAPI 3 description of the gateway.
+pub fn openapi_document() -> Value {
+    let mut paths = Map::new();
+    for endpoint in ENDPOINTS {
+        let parameters: Vec<Value> = endpoint
+            .params
+            .iter()
+            .map(|param| {
+                let mut schema = json!({
+                    "type": match param.kind {
+                        ParamKind::Integer => "integer",
+                        ParamKind::String => "string",
+                    },
+                });
+                if let Some(default) = param.default {
+                    schema["default"] = match param.kind {
+                        ParamKind::Integer => {
+                            default.parse::<u64>().map(Value::from).unwrap_or_default()
+                        }
+                        ParamKind::String => Value::from(default),
+                    };
+                }
+                json!({
+                    "name": param.name,
+                    "in": if param.in_path { "path" } else { "query" },
+                    "required": param.in_path,
+                    "description": param.description,
+                    "schema": schema,
+                })
+            })
+            .collect();
+        let mut responses = json!({
+            "200": {
+                "description": format!("`{}`, as returned by `{}`.", endpoint.result, endpoint.method),
+                "content": { "application/json": { "schema": {} } },
+            },
+            "400": { "$ref": "#/components/responses/Error" },
+            "429": { "$ref": "#/components/responses/Error" },
+        });
+        if endpoint.optional {
+            responses["404"] = json!({ "$ref": "#/components/responses/Error" });
+        }
+        paths.insert(
+            endpoint.path.to_string(),
+            json!({
+                "get": {
+                    "operationId": endpoint.method,
+                    "summary": endpoint.summary,
+                    "parameters": parameters,
+                    "responses": responses,
+                },
+            }),
+        );
+    }
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "Norn REST gateway",
+            "version": env!("CARGO_PKG_VERSION"),
+            "description": "Read-only REST mapping of the Norn JSON-RPC API.",
+        },
+        "paths": paths,
+        "components": {
+            "responses": {
+                "Error": {
+                    "description": "JSON-RPC error of the underlying call.",
+                    "content": { "application/json": { "schema": {
+                        "type": "object",
+                        "properties": { "error": {
+                            "type": "object",
+                            "properties": {
+                                "code": { "type": "integer" },
+                                "message": { "type": "string" },
+                            },
+                        } },
+                    } } },
+                },
+            },
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_translate_params() {
+        let endpoint = ENDPOINTS
+            .iter()
+            .find(|e| e.method == "norn_getTransactionHistory")
+            .unwrap();
+        let path = HashMap::from([("address".to_string(), "abc".to_string())]);
+        let query = HashMap::from([("offset".to_string(), "20".to_string())]);
+        let call = translate(endpoint, &path, &query).unwrap();
+        assert_eq!(call.params, vec![json!("abc"), json!(100), json!(20)]);
+
+        let query = HashMap::from([("limit".to_string(), "ten".to_string())]);
+        let (code, message) = translate(endpoint, &path, &query).unwrap_err();
+        assert_eq!(code, -32602);
+        assert!(message.contains("'limit'"), "{}", message);
+    }
+
+    #[test]
+    fn test_openapi_lists_every_endpoint() {
+        let doc = openapi_document();
+        for endpoint in ENDPOINTS {
+            let operation = &doc["paths"][endpoint.path]["get"];
+            assert_eq!(operation["operationId"], endpoint.method);
+            let placeholders = endpoint.path.matches('{').count();
+            let in_path = operation["parameters"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .filter(|p| p["in"] == "path")
+                .count();
+            assert_eq!(placeholders, in_path, "{}", endpoint.path);
+        }
+        assert_eq!(
+            axum_path("/v1/blocks/{height}/transactions"),
+            "/v1/blocks/:height/transactions"
+        );
+    }
+}
@@ -1,21 +1,32 @@
+use std::net::SocketAddr;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
-use jsonrpsee::server::{ServerBuilder, ServerHandle};
+use jsonrpsee::server::middleware::rpc::RpcServiceBuilder;
+use jsonrpsee::server::{
+    serve_with_graceful_shutdown, stop_channel, BatchRequestConfig, Methods, ServerBuilder,
+    ServerHandle,
+};
+use tower::Service;
 
 use norn_loom::lifecycle::LoomManager;
 use norn_relay::relay::RelayHandle;
+use norn_thread::proposal::KnotCollector;
 use norn_types::network::NetworkId;
 use norn_weave::engine::WeaveEngine;
 
+use super::auth::RpcAuth;
 use super::chat_store::ChatEventStore;
 use super::handlers::{NornRpcImpl, NornRpcServer};
+use super::limits::{ClientIp, RpcLimits, FORWARDED_FOR};
+use super::transport::{RpcTls, RpcTransport, TLS_HANDSHAKE_TIMEOUT};
 use super::types::{
     BlockInfo, ChatEvent, LoomExecutionEvent, PendingTransactionEvent, TokenEvent, TransferEvent,
 };
 use crate::error::NodeError;
+use crate::health::NodeHealth;
 use crate::metrics::NodeMetrics;
-use crate::state_manager::StateManager;
+use crate::state_manager::{EventRecord, StateManager};
 
 /// Groups all broadcast channels for WebSocket subscription events.
 #[derive(Clone)]
@@ -24,6 +35,7 @@ pub struct RpcBroadcasters {
     pub transfer_tx: tokio::sync::broadcast::Sender<TransferEvent>,
     pub token_tx: tokio::sync::broadcast::Sender<TokenEvent>,
     pub loom_tx: tokio::sync::broadcast::Sender<LoomExecutionEvent>,
+    pub event_tx: tokio::sync::broadcast::Sender<EventRecord>,
     pub pending_tx: tokio::sync::broadcast::Sender<PendingTransactionEvent>,
     pub chat_tx: tokio::sync::broadcast::Sender<ChatEvent>,
 }
@@ -41,6 +53,7 @@ impl RpcBroadcasters {
         let (transfer_tx, _) = tokio::sync::broadcast::channel::<TransferEvent>(256);
         let (token_tx, _) = tokio::sync::broadcast::channel::<TokenEvent>(64);
         let (loom_tx, _) = tokio::sync::broadcast::channel::<LoomExecutionEvent>(64);
+        let (event_tx, _) = tokio::sync::broadcast::channel::<EventRecord>(256);
         let (pending_tx, _) = tokio::sync::broadcast::channel::<PendingTransactionEvent>(256);
         let (chat_tx, _) = tokio::sync::broadcast::channel::<ChatEvent>(512);
         Self {
@@ -48,6 +61,7 @@ impl RpcBroadcasters {
             transfer_tx,
             token_tx,
             loom_tx,
+            event_tx,
             pending_tx,
             chat_tx,
         }
@@ -65,80 +79,330 @@ pub async fn start_rpc_server(
     relay_handle: Option<RelayHandle>,
     network_id: NetworkId,
     is_validator: bool,
-    api_key: Option<String>,
+    auth: Option<RpcAuth>,
+    limits: RpcLimits,
+    transport: RpcTransport,
+    rest_gateway: bool,
+    graphql: bool,
+    grpc_addr: Option<&str>,
     last_block_production_us: Arc<std::sync::Mutex<Option<u64>>>,
+    knot_collector: Arc<std::sync::Mutex<KnotCollector>>,
+    health: Arc<NodeHealth>,
 ) -> Result<(ServerHandle, RpcBroadcasters), NodeError> {
     let broadcasters = RpcBroadcasters::new();
+    let auth = auth.map(Arc::new);
+    let limits = Arc::new(limits);
 
-    let rpc_impl = NornRpcImpl {
-        weave_engine,
-        state_manager,
-        loom_manager,
-        metrics,
+    let graphql = graphql.then(|| super::graphql::rpc_module(state_manager.clone(), network_id));
+    let chat_store = Arc::new(std::sync::RwLock::new(ChatEventStore::new()));
+    let new_rpc_impl = || NornRpcImpl {
+        weave_engine: weave_engine.clone(),
+        state_manager: state_manager.clone(),
+        loom_manager: loom_manager.clone(),
+        metrics: metrics.clone(),
         broadcasters: broadcasters.clone(),
-        relay_handle,
+        relay_handle: relay_handle.clone(),
         network_id,
         is_validator,
         faucet_tracker: std::sync::Mutex::new(std::collections::HashMap::new()),
-        last_block_production_us,
-        chat_store: Arc::new(std::sync::RwLock::new(ChatEventStore::new())),
-    };
-
-    let handle = if let Some(key) = api_key {
-        // Build server with health + auth middleware.
-        let middleware = tower::ServiceBuilder::new()
-            .layer(health_middleware::HealthLayer)
-            .layer(auth_middleware::AuthLayer::new(key.clone()));
-        let server = ServerBuilder::default()
-            .set_http_middleware(middleware)
-            .build(addr)
-            .await
-            .map_err(|e| NodeError::RpcError {
-                reason: format!("failed to build RPC server: {}", e),
-            })?;
-        tracing::info!(addr = %addr, "RPC server started (API key auth enabled)");
-        server.start(rpc_impl.into_rpc())
-    } else {
-        // Build server with health middleware (open access).
-        let middleware = tower::ServiceBuilder::new().layer(health_middleware::HealthLayer);
-        let server = ServerBuilder::default()
-            .set_http_middleware(middleware)
-            .build(addr)
-            .await
-            .map_err(|e| NodeError::RpcError {
-                reason: format!("failed to build RPC server: {}", e),
-            })?;
-        tracing::info!(addr = %addr, "RPC server started");
-        server.start(rpc_impl.into_rpc())
+        last_block_production_us: last_block_production_us.clone(),
+        chat_store: chat_store.clone(),
+        knot_collector: knot_collector.clone(),
+        health: health.clone(),
     };
+    let rpc_impl = new_rpc_impl();
+    // The gRPC service handles its calls with the same handlers.
+    let grpc = grpc_addr.map(|addr| {
+        let service = super::grpc::NodeGrpc::new(
+            new_rpc_impl(),
+            auth.clone(),
+            limits.clone(),
+            metrics.clone(),
+        );
+        (addr, service)
+    });
 
+    let mut methods = rpc_impl.into_rpc();
+    let graphql_enabled = graphql.is_some();
+    if let Some(graphql) = graphql {
+        methods.merge(graphql).map_err(|e| NodeError::RpcError {
+            reason: format!("failed to register GraphQL methods: {}", e),
+        })?;
+    }
+    let grpc_tls = transport.tls.as_ref().map(RpcTls::grpc_config);
+    let (handle, _) = serve(
+        addr,
+        methods,
+        auth,
+        limits,
+        transport,
+        rest_gateway,
+        graphql_enabled,
+        metrics,
+        health,
+    )
+    .await?;
+    if let Some((addr, service)) = grpc {
+        super::grpc::serve(addr, service, grpc_tls, handle.clone().stopped()).await?;
+    }
     Ok((handle, broadcasters))
 }
 
-/// Tower middleware that intercepts `GET /health` and returns 200 OK
+/// Serve `methods` on `addr` behind the auth and limit middleware, with the
+/// REST gateway if `rest_gateway` is set and `/graphql` if `graphql` is.
+/// `/health`, `/healthz` and `/readyz` are answered before any auth.
+#[allow(clippy::too_many_arguments)]
+async fn serve(
+    addr: &str,
+    methods: impl Into<Methods>,
+    auth: Option<Arc<RpcAuth>>,
+    limits: Arc<RpcLimits>,
+    transport: RpcTransport,
+    rest_gateway: bool,
+    graphql: bool,
+    metrics: Arc<NodeMetrics>,
+    health: Arc<NodeHealth>,
+) -> Result<(ServerHandle, SocketAddr), NodeError> {
+    let http_middleware = tower::ServiceBuilder::new()
+        .option_layer(transport.cors.clone())
+        .layer(body_limit_middleware::BodyLimitLayer::new(
+            limits.max_request_body_bytes,
+            metrics.clone(),
+        ))
+        .layer(health_middleware::HealthLayer::new(health))
+        .option_layer(auth.clone().map(auth_middleware::AuthLayer::new))
+        .option_layer(rest_gateway.then(rest_middleware::RestLayer::new))
+        .option_layer(graphql.then_some(graphql_middleware::GraphqlLayer));
+    let rpc_middleware = RpcServiceBuilder::new()
+        .option_layer(
+            auth.clone()
+                .map(|auth| acl_middleware::AclLayer::new(auth, metrics.clone())),
+        )
+        .layer(limit_middleware::LimitLayer::new(limits.clone(), metrics));
+    let svc_builder = ServerBuilder::default()
+        .max_connections(limits.max_connections)
+        .max_request_body_size(limits.max_request_body_bytes)
+        .max_response_body_size(limits.max_response_body_bytes)
+        .set_batch_request_config(match limits.max_batch_size {
+            0 => BatchRequestConfig::Disabled,
+            n => BatchRequestConfig::Limit(n),
+        })
+        .set_http_middleware(http_middleware)
+        .set_rpc_middleware(rpc_middleware)
+        .to_service_builder();
+
+    let listener = tokio::net::TcpListener::bind(addr)
+        .await
+        .map_err(|e| NodeError::RpcError {
+            reason: format!("failed to bind RPC server to {}: {}", addr, e),
+        })?;
+    let (stop_handle, handle) = stop_channel();
+    let local_addr = listener.local_addr().map_err(|e| NodeError::RpcError {
+        reason: format!("failed to read RPC server address: {}", e),
+    })?;
+    let methods: Methods = methods.into();
+
+    // Serve connections ourselves rather than through `Server::start`, so
+    // that every request carries the client's IP for the rate limits and
+    // connections can be made over TLS.
+    let tls = transport.tls.as_ref().map(|tls| tls.acceptor.clone());
+    let client_limits = limits.clone();
+    tokio::spawn(async move {
+        loop {
+            let (sock, remote_addr) = tokio::select! {
+                res = listener.accept() => match res {
+                    Ok(conn) => conn,
+                    Err(e) => {
+                        tracing::warn!(error = %e, "failed to accept RPC connection");
+                        continue;
+                    }
+                },
+                _ = stop_handle.clone().shutdown() => break,
+            };
+
+            let peer = remote_addr.ip();
+            let rpc_svc = svc_builder
+                .clone()
+                .build(methods.clone(), stop_handle.clone());
+            let limits = client_limits.clone();
+            let svc = tower::service_fn(move |mut req: http::Request<hyper::body::Incoming>| {
+                let forwarded_for = req
+                    .headers()
+                    .get_all(FORWARDED_FOR)
+                    .iter()
+                    .filter_map(|v| v.to_str().ok());
+                let ip = limits.client_ip(peer, forwarded_for);
+                req.extensions_mut().insert(ClientIp(ip));
+                let mut rpc_svc = rpc_svc.clone();
+                async move { rpc_svc.call(req).await }
+            });
+            let shutdown = stop_handle.clone().shutdown();
+            let Some(tls) = tls.clone() else {
+                tokio::spawn(serve_with_graceful_shutdown(sock, svc, shutdown));
+                continue;
+            };
+            tokio::spawn(async move {
+                match tokio::time::timeout(TLS_HANDSHAKE_TIMEOUT, tls.accept(sock)).await {
+                    Ok(Ok(stream)) => {
+                        tokio::spawn(serve_with_graceful_shutdown(stream, svc, shutdown));
+                    }
+                    Ok(Err(e)) => {
+                        tracing::debug!(peer = %peer, error = %e, "TLS handshake failed");
+                    }
+                    Err(_) => tracing::debug!(peer = %peer, "TLS handshake timed out"),
+                }
+            });
+        }
+    });
+    tracing::info!(
+        addr = %addr,
+        auth = auth.is_some(),
+        tls = transport.tls.is_some(),
+        cors = transport.cors.is_some(),
+        rest_gateway,
+        graphql,
+        max_connections = limits.max_connections,
+        "RPC server started"
+    );
+    Ok((handle, local_addr))
+}
+
+/// Tower middleware that rejects request bodies over the size limit with
+/// 413, before any other middleware reads them.
+mod body_limit_middleware {
+    use http::{Request, Response, StatusCode};
+    use http_body_util::{BodyExt, Limited};
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::Arc;
+    use std::task::{Context, Poll};
+    use tower::{Layer, Service};
+
+    use crate::metrics::{NodeMetrics, RpcRejectReason};
+
+    #[derive(Clone)]
+    pub struct BodyLimitLayer {
+        max_bytes: u32,
+        metrics: Arc<NodeMetrics>,
+    }
+
+    impl BodyLimitLayer {
+        pub fn new(max_bytes: u32, metrics: Arc<NodeMetrics>) -> Self {
+            Self { max_bytes, metrics }
+        }
+    }
+
+    impl<S> Layer<S> for BodyLimitLayer {
+        type Service = BodyLimitService<S>;
+
+        fn layer(&self, inner: S) -> Self::Service {
+            BodyLimitService {
+                inner,
+                max_bytes: self.max_bytes,
+                metrics: self.metrics.clone(),
+            }
+        }
+    }
+
+    #[derive(Clone)]
+    pub struct BodyLimitService<S> {
+        inner: S,
+        max_bytes: u32,
+        metrics: Arc<NodeMetrics>,
+    }
+
+    impl<S, B> Service<Request<B>> for BodyLimitService<S>
+    where
+        S: Service<Request<jsonrpsee::server::HttpBody>> + Clone + Send + 'static,
+        S::Response: From<Response<jsonrpsee::server::HttpBody>>,
+        S::Future: Send,
+        S::Error: Send,
+        B: http_body::Body + Send + 'static,
+        B::Data: Send,
+        B::Error: std::error::Error + Send + Sync + 'static,
+    {
+        type Response = S::Response;
+        type Error = S::Error;
+        type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+        fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            self.inner.poll_ready(cx)
+        }
+
+        fn call(&mut self, req: Request<B>) -> Self::Future {
+            let mut inner = self.inner.clone();
+            let max_bytes = self.max_bytes as usize;
+            let metrics = self.metrics.clone();
+
+            Box::pin(async move {
+                let (parts, body) = req.into_parts();
+                let collected = match Limited::new(body, max_bytes).collect().await {
+                    Ok(collected) => collected.to_bytes(),
+                    Err(e) if e.is::<http_body_util::LengthLimitError>() => {
+                        metrics.rpc_rejected(RpcRejectReason::TooLarge);
+                        let body = jsonrpsee::server::HttpBody::from(format!(
+                            r#"{{"jsonrpc":"2.0","error":{{"code":-32007,"message":"request body exceeds {} bytes"}},"id":null}}"#,
+                            max_bytes
+                        ));
+                        let response = Response::builder()
+                            .status(StatusCode::PAYLOAD_TOO_LARGE)
+                            .header("Content-Type", "application/json")
+                            .body(body)
+                            .expect("valid response");
+                        return Ok(response.into());
+                    }
+                    Err(_) => Default::default(),
+                };
+                let new_body = jsonrpsee::server::HttpBody::from(collected.to_vec());
+                inner.call(Request::from_parts(parts, new_body)).await
+            })
+        }
+    }
+}
+
+/// Tower middleware that intercepts `GET /health`, `/healthz` and `/readyz`
 /// before the request reaches jsonrpsee (which only handles POST).
+///
+/// `/health` always returns 200 OK. `/healthz` (liveness) and `/readyz`
+/// (readiness) return the node's health report, with 503 when the node is
+/// not live or not ready.
 mod health_middleware {
     use http::{Request, Response, StatusCode};
     use http_body_util::BodyExt;
     use std::future::Future;
     use std::pin::Pin;
+    use std::sync::Arc;
     use std::task::{Context, Poll};
     use tower::{Layer, Service};
 
-    #[derive(Clone, Copy)]
-    pub struct HealthLayer;
+    use crate::health::NodeHealth;
+
+    #[derive(Clone)]
+    pub struct HealthLayer {
+        health: Arc<NodeHealth>,
+    }
+
+    impl HealthLayer {
+        pub fn new(health: Arc<NodeHealth>) -> Self {
+            Self { health }
+        }
+    }
 
     impl<S> Layer<S> for HealthLayer {
         type Service = HealthService<S>;
 
         fn layer(&self, inner: S) -> Self::Service {
-            HealthService { inner }
+            HealthService {
+                inner,
+                health: self.health.clone(),
+            }
         }
     }
 
     #[derive(Clone)]
     pub struct HealthService<S> {
         inner: S,
+        health: Arc<NodeHealth>,
     }
 
     impl<S, B> Service<Request<B>> for HealthService<S>
@@ -160,15 +424,33 @@ mod health_middleware {
         }
 
         fn call(&mut self, req: Request<B>) -> Self::Future {
-            let is_health = req.method() == http::Method::GET && req.uri().path() == "/health";
+            let probe = match req.uri().path() {
+                _ if req.method() != http::Method::GET => None,
+                "/health" => Some((StatusCode::OK, r#"{"status":"ok"}"#.to_string())),
+                path @ ("/healthz" | "/readyz") => {
+                    let report = self.health.report();
+                    let passed = if path == "/healthz" {
+                        report.live
+                    } else {
+                        report.ready
+                    };
+                    let status = if passed {
+                        StatusCode::OK
+                    } else {
+                        StatusCode::SERVICE_UNAVAILABLE
+                    };
+                    let body = serde_json::to_string(&report).unwrap_or_default();
+                    Some((status, body))
+                }
+                _ => None,
+            };
 
-            if is_health {
+            if let Some((status, body)) = probe {
                 Box::pin(async move {
-                    let body = jsonrpsee::server::HttpBody::from(r#"{"status":"ok"}"#);
                     let response = Response::builder()
-                        .status(StatusCode::OK)
+                        .status(status)
                         .header("Content-Type", "application/json")
-                        .body(body)
+                        .body(jsonrpsee::server::HttpBody::from(body))
                         .expect("valid response");
                     Ok(response.into())
                 })
@@ -189,74 +471,30 @@ mod health_middleware {
     }
 }
 
-/// Tower middleware for API key authentication on RPC mutation methods.
-/// Read-only methods are whitelisted and accessible without authentication.
+/// Tower middleware that identifies the caller from its API key and attaches
+/// it to the request, for [`acl_middleware`] to check each call against.
+/// Requests with an unknown key are rejected with 401.
 mod auth_middleware {
     use http::header::AUTHORIZATION;
     use http::{Request, Response, StatusCode};
     use http_body_util::BodyExt;
     use std::future::Future;
     use std::pin::Pin;
+    use std::sync::Arc;
     use std::task::{Context, Poll};
     use tower::{Layer, Service};
 
-    /// Read-only RPC methods that don't require authentication.
-    const READ_ONLY_METHODS: &[&str] = &[
-        "norn_getBalance",
-        "norn_getBlock",
-        "norn_getLatestBlock",
-        "norn_getWeaveState",
-        "norn_getThread",
-        "norn_getThreadState",
-        "norn_health",
-        "norn_getValidatorSet",
-        "norn_getFeeEstimate",
-        "norn_getCommitmentProof",
-        "norn_getTransactionHistory",
-        "norn_getRecentTransfers",
-        "norn_resolveName",
-        "norn_listNames",
-        "norn_reverseName",
-        "norn_getNameRecords",
-        "norn_getMetrics",
-        "norn_getTokenInfo",
-        "norn_getTokenBySymbol",
-        "norn_listTokens",
-        "norn_getLoomInfo",
-        "norn_listLooms",
-        "norn_queryLoom",
-        "norn_getStakingInfo",
-        "norn_getValidatorRewards",
-        "norn_getStateRoot",
-        "norn_getStateProof",
-        "norn_getBlockTransactions",
-        "norn_getTransaction",
-        // WebSocket subscriptions are read-only.
-        "norn_subscribeNewBlocks",
-        "norn_unsubscribeNewBlocks",
-        "norn_subscribeTransfers",
-        "norn_unsubscribeTransfers",
-        "norn_subscribeTokenEvents",
-        "norn_unsubscribeTokenEvents",
-        "norn_subscribeLoomEvents",
-        "norn_unsubscribeLoomEvents",
-        "norn_subscribePendingTransactions",
-        "norn_unsubscribePendingTransactions",
-        // Chat relay (read-only).
-        "norn_getChatHistory",
-        "norn_subscribeChatEvents",
-        "norn_unsubscribeChatEvents",
-    ];
+    use crate::rpc::auth::RpcAuth;
 
     /// Tower layer that wraps services with API key authentication.
     #[derive(Clone)]
     pub struct AuthLayer {
-        api_key: String,
+        auth: Arc<RpcAuth>,
     }
 
     impl AuthLayer {
-        pub fn new(api_key: String) -> Self {
-            Self { api_key }
+        pub fn new(auth: Arc<RpcAuth>) -> Self {
+            Self { auth }
         }
     }
 
@@ -266,54 +504,17 @@ mod auth_middleware {
         fn layer(&self, inner: S) -> Self::Service {
             AuthService {
                 inner,
-                api_key: self.api_key.clone(),
+                auth: self.auth.clone(),
             }
         }
     }
 
-    /// Tower service that checks the Authorization header on mutation RPC methods.
-    /// Read-only methods are allowed without authentication.
+    /// Tower service that resolves the `Authorization` header to an
+    /// [`RpcCaller`](crate::rpc::auth::RpcCaller).
     #[derive(Clone)]
     pub struct AuthService<S> {
         inner: S,
-        api_key: String,
-    }
-
-    /// Extract all JSON-RPC method names from a request body.
-    /// Handles both single requests and batch requests (JSON arrays).
-    fn extract_method_names(body: &[u8]) -> Vec<String> {
-        let v: serde_json::Value = match serde_json::from_slice(body) {
-            Ok(v) => v,
-            Err(_) => return vec![],
-        };
-        match &v {
-            serde_json::Value::Array(arr) => arr
-                .iter()
-                .filter_map(|item| item.get("method")?.as_str().map(|s| s.to_string()))
-                .collect(),
-            serde_json::Value::Object(_) => v
-                .get("method")
-                .and_then(|m| m.as_str())
-                .map(|s| vec![s.to_string()])
-                .unwrap_or_default(),
-            _ => vec![],
-        }
-    }
-
-    fn is_read_only(method: &str) -> bool {
-        READ_ONLY_METHODS.contains(&method)
-    }
-
-    /// Constant-time string comparison to prevent timing side-channel attacks.
-    fn constant_time_eq(a: &str, b: &str) -> bool {
-        if a.len() != b.len() {
-            return false;
-        }
-        let mut result = 0u8;
-        for (x, y) in a.bytes().zip(b.bytes()) {
-            result |= x ^ y;
-        }
-        result == 0
+        auth: Arc<RpcAuth>,
     }
 
     impl<S, B> Service<Request<B>> for AuthService<S>
@@ -336,71 +537,839 @@ mod auth_middleware {
 
         fn call(&mut self, req: Request<B>) -> Self::Future {
             let mut inner = self.inner.clone();
-            let api_key = self.api_key.clone();
+            let bearer = req
+                .headers()
+                .get(AUTHORIZATION)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.strip_prefix("Bearer "));
+            let caller = self.auth.authenticate(bearer);
 
             Box::pin(async move {
-                // Non-POST requests pass through (e.g., WebSocket upgrades).
-                if req.method() != http::Method::POST {
-                    let (parts, body) = req.into_parts();
-                    let collected = body
-                        .collect()
-                        .await
-                        .map(|c| c.to_bytes())
-                        .unwrap_or_default();
-                    let new_body = jsonrpsee::server::HttpBody::from(collected.to_vec());
-                    return inner.call(Request::from_parts(parts, new_body)).await;
-                }
-
-                // Check auth header upfront.
-                let authorized = req
-                    .headers()
-                    .get(AUTHORIZATION)
-                    .and_then(|v| v.to_str().ok())
-                    .and_then(|v| v.strip_prefix("Bearer "))
-                    .map(|token| constant_time_eq(token, &api_key))
-                    .unwrap_or(false);
-
-                // If authorized, pass through immediately.
-                if authorized {
-                    let (parts, body) = req.into_parts();
-                    let collected = body
-                        .collect()
-                        .await
-                        .map(|c| c.to_bytes())
-                        .unwrap_or_default();
-                    let new_body = jsonrpsee::server::HttpBody::from(collected.to_vec());
-                    return inner.call(Request::from_parts(parts, new_body)).await;
-                }
+                let Some(caller) = caller else {
+                    let body = jsonrpsee::server::HttpBody::from(
+                        r#"{"jsonrpc":"2.0","error":{"code":-32001,"message":"unauthorized: invalid API key"},"id":null}"#,
+                    );
+                    let response = Response::builder()
+                        .status(StatusCode::UNAUTHORIZED)
+                        .header("Content-Type", "application/json")
+                        .body(body)
+                        .expect("valid response");
+                    return Ok(response.into());
+                };
 
-                // Not authorized — collect body and check if it's a read-only method.
-                let (parts, body) = req.into_parts();
+                let (mut parts, body) = req.into_parts();
+                parts.extensions.insert(caller);
                 let collected = body
                     .collect()
                     .await
                     .map(|c| c.to_bytes())
                     .unwrap_or_default();
+                let new_body = jsonrpsee::server::HttpBody::from(collected.to_vec());
+                inner.call(Request::from_parts(parts, new_body)).await
+            })
+        }
+    }
+}
+
+/// Tower middleware serving the REST gateway (see [`super::rest`]).
+/// Requests under `/v1/` are routed, and those naming an RPC method are
+/// made as a JSON-RPC call to the inner service, keeping their headers and
+/// extensions so that the RPC middleware applies to them.
+mod rest_middleware {
+    use http::header::{CONTENT_LENGTH, CONTENT_TYPE};
+    use http::{HeaderValue, Method, Request, Response, Uri};
+    use http_body_util::BodyExt;
+    use jsonrpsee::server::HttpBody;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+    use tower::{Layer, Service, ServiceExt};
 
-                let method_names = extract_method_names(&collected);
-                let is_read =
-                    !method_names.is_empty() && method_names.iter().all(|m| is_read_only(m));
+    use crate::rpc::rest::{self, RpcCall};
 
-                if is_read {
-                    // Read-only method — allow without auth.
-                    let new_body = jsonrpsee::server::HttpBody::from(collected.to_vec());
-                    inner.call(Request::from_parts(parts, new_body)).await
-                } else {
-                    // Mutation method — reject without auth.
-                    let body = jsonrpsee::server::HttpBody::from(
-                        r#"{"jsonrpc":"2.0","error":{"code":-32000,"message":"unauthorized: invalid or missing API key"},"id":null}"#,
+    #[derive(Clone)]
+    pub struct RestLayer {
+        router: axum::Router,
+    }
+
+    impl RestLayer {
+        pub fn new() -> Self {
+            Self {
+                router: rest::router(),
+            }
+        }
+    }
+
+    impl<S> Layer<S> for RestLayer {
+        type Service = RestService<S>;
+
+        fn layer(&self, inner: S) -> Self::Service {
+            RestService {
+                inner,
+                router: self.router.clone(),
+            }
+        }
+    }
+
+    #[derive(Clone)]
+    pub struct RestService<S> {
+        inner: S,
+        router: axum::Router,
+    }
+
+    impl<S> Service<Request<HttpBody>> for RestService<S>
+    where
+        S: Service<Request<HttpBody>, Response = Response<HttpBody>> + Clone + Send + 'static,
+        S::Future: Send,
+        S::Error: Send,
+    {
+        type Response = Response<HttpBody>;
+        type Error = S::Error;
+        type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+        fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            self.inner.poll_ready(cx)
+        }
+
+        fn call(&mut self, req: Request<HttpBody>) -> Self::Future {
+            if !req.uri().path().starts_with(rest::PREFIX) {
+                return Box::pin(self.inner.call(req));
+            }
+            let router = self.router.clone();
+            let mut inner = self.inner.clone();
+
+            Box::pin(async move {
+                let (parts, _) = req.into_parts();
+                let routed = Request::builder()
+                    .method(parts.method.clone())
+                    .uri(parts.uri.clone())
+                    .body(axum::body::Body::empty())
+                    .expect("valid request");
+                let response = match router.oneshot(routed).await {
+                    Ok(response) => response,
+                    Err(never) => match never {},
+                };
+                let Some(call) = response.extensions().get::<RpcCall>().cloned() else {
+                    return Ok(response.map(HttpBody::new));
+                };
+
+                let response = call_rpc(
+                    &mut inner,
+                    parts,
+                    call.endpoint.method,
+                    call.params.clone().into(),
+                )
+                .await?;
+                if !response.status().is_success() {
+                    return Ok(response);
+                }
+                let body = read_body(response.into_body()).await;
+                Ok(rest::rpc_response(&call, &body).map(HttpBody::new))
+            })
+        }
+    }
+
+    /// Make a JSON-RPC call of `method` to `inner`, as a POST with the
+    /// headers and extensions of `parts`.
+    pub(super) async fn call_rpc<S>(
+        inner: &mut S,
+        mut parts: http::request::Parts,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<Response<HttpBody>, S::Error>
+    where
+        S: Service<Request<HttpBody>, Response = Response<HttpBody>>,
+    {
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 0,
+            "method": method,
+            "params": params,
+        })
+        .to_string();
+        parts.method = Method::POST;
+        parts.uri = Uri::from_static("/");
+        parts
+            .headers
+            .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        parts
+            .headers
+            .insert(CONTENT_LENGTH, HeaderValue::from(body.len()));
+        inner
+            .call(Request::from_parts(parts, HttpBody::from(body)))
+            .await
+    }
+
+    /// The whole of `body`, or nothing if it cannot be read.
+    pub(super) async fn read_body(body: HttpBody) -> axum::body::Bytes {
+        body.collect()
+            .await
+            .map(|c| c.to_bytes())
+            .unwrap_or_default()
+    }
+}
+
+/// Tower middleware serving `POST /graphql` (see [`super::graphql`]) as a
+/// `norn_graphql` call to the inner service, so that the RPC middleware
+/// applies to it.
+mod graphql_middleware {
+    use http::{Method, Request, Response};
+    use jsonrpsee::server::HttpBody;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+    use tower::{Layer, Service};
+
+    use super::rest_middleware::{call_rpc, read_body};
+    use crate::rpc::graphql;
+
+    #[derive(Clone)]
+    pub struct GraphqlLayer;
+
+    impl<S> Layer<S> for GraphqlLayer {
+        type Service = GraphqlService<S>;
+
+        fn layer(&self, inner: S) -> Self::Service {
+            GraphqlService { inner }
+        }
+    }
+
+    #[derive(Clone)]
+    pub struct GraphqlService<S> {
+        inner: S,
+    }
+
+    impl<S> Service<Request<HttpBody>> for GraphqlService<S>
+    where
+        S: Service<Request<HttpBody>, Response = Response<HttpBody>> + Clone + Send + 'static,
+        S::Future: Send,
+        S::Error: Send,
+    {
+        type Response = Response<HttpBody>;
+        type Error = S::Error;
+        type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+        fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            self.inner.poll_ready(cx)
+        }
+
+        fn call(&mut self, req: Request<HttpBody>) -> Self::Future {
+            if req.uri().path() != graphql::PATH || req.method() != Method::POST {
+                return Box::pin(self.inner.call(req));
+            }
+            let mut inner = self.inner.clone();
+
+            Box::pin(async move {
+                let (parts, body) = req.into_parts();
+                let request =
+                    match serde_json::from_slice::<serde_json::Value>(&read_body(body).await) {
+                        Ok(request @ serde_json::Value::Object(_)) => request,
+                        _ => {
+                            return Ok(graphql::error_response(
+                                -32700,
+                                "request body must be a GraphQL request object",
+                            )
+                            .map(HttpBody::from))
+                        }
+                    };
+                let response = call_rpc(
+                    &mut inner,
+                    parts,
+                    "norn_graphql",
+                    serde_json::json!([request]),
+                )
+                .await?;
+                if !response.status().is_success() {
+                    return Ok(response);
+                }
+                let body = read_body(response.into_body()).await;
+                Ok(graphql::http_response(&body).map(HttpBody::from))
+            })
+        }
+    }
+}
+
+/// RPC middleware that checks every call, over HTTP or WebSocket and
+/// including each entry of a batch, against the caller's method allowlist
+/// and rate limit.
+mod acl_middleware {
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::Arc;
+
+    use jsonrpsee::server::middleware::rpc::RpcServiceT;
+    use jsonrpsee::server::MethodResponse;
+    use jsonrpsee::types::{ErrorObject, Request};
+
+    use crate::metrics::{NodeMetrics, RpcRejectReason};
+    use crate::rpc::auth::{AuthError, RpcAuth, RpcCaller};
+
+    #[derive(Clone)]
+    pub struct AclLayer {
+        auth: Arc<RpcAuth>,
+        metrics: Arc<NodeMetrics>,
+    }
+
+    impl AclLayer {
+        pub fn new(auth: Arc<RpcAuth>, metrics: Arc<NodeMetrics>) -> Self {
+            Self { auth, metrics }
+        }
+    }
+
+    impl<S> tower::Layer<S> for AclLayer {
+        type Service = AclService<S>;
+
+        fn layer(&self, inner: S) -> Self::Service {
+            AclService {
+                inner,
+                auth: self.auth.clone(),
+                metrics: self.metrics.clone(),
+            }
+        }
+    }
+
+    #[derive(Clone)]
+    pub struct AclService<S> {
+        inner: S,
+        auth: Arc<RpcAuth>,
+        metrics: Arc<NodeMetrics>,
+    }
+
+    impl<'a, S> RpcServiceT<'a> for AclService<S>
+    where
+        S: RpcServiceT<'a> + Send + Sync,
+        S::Future: 'a,
+    {
+        type Future = Pin<Box<dyn Future<Output = MethodResponse> + Send + 'a>>;
+
+        fn call(&self, request: Request<'a>) -> Self::Future {
+            let caller = request
+                .extensions()
+                .get::<RpcCaller>()
+                .cloned()
+                .unwrap_or(RpcCaller::Public);
+            match self.auth.authorize(&caller, request.method_name()) {
+                Ok(()) => Box::pin(self.inner.call(request)),
+                Err(e) => {
+                    tracing::debug!(method = request.method_name(), error = %e, "RPC call refused");
+                    self.metrics.rpc_rejected(match e {
+                        AuthError::Forbidden { .. } => RpcRejectReason::Forbidden,
+                        AuthError::RateLimited { .. } => RpcRejectReason::KeyRateLimited,
+                    });
+                    let response = MethodResponse::error(
+                        request.id().into_owned(),
+                        ErrorObject::owned(e.code(), e.to_string(), None::<()>),
                     );
-                    let response = Response::builder()
-                        .status(StatusCode::UNAUTHORIZED)
-                        .header("Content-Type", "application/json")
-                        .body(body)
-                        .expect("valid response");
-                    Ok(response.into())
+                    Box::pin(std::future::ready(response))
                 }
+            }
+        }
+    }
+}
+
+/// RPC middleware that applies the per-IP rate limits and request timeout
+/// to every call, and logs slow ones.
+mod limit_middleware {
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::Arc;
+    use std::time::Instant;
+
+    use jsonrpsee::server::middleware::rpc::RpcServiceT;
+    use jsonrpsee::server::MethodResponse;
+    use jsonrpsee::types::{ErrorObject, Request};
+
+    use crate::metrics::{NodeMetrics, RpcRejectReason};
+    use crate::rpc::auth::RpcCaller;
+    use crate::rpc::limits::{ClientIp, RpcLimits};
+
+    #[derive(Clone)]
+    pub struct LimitLayer {
+        limits: Arc<RpcLimits>,
+        metrics: Arc<NodeMetrics>,
+    }
+
+    impl LimitLayer {
+        pub fn new(limits: Arc<RpcLimits>, metrics: Arc<NodeMetrics>) -> Self {
+            Self { limits, metrics }
+        }
+    }
+
+    impl<S> tower::Layer<S> for LimitLayer {
+        type Service = LimitService<S>;
+
+        fn layer(&self, inner: S) -> Self::Service {
+            LimitService {
+                inner,
+                limits: self.limits.clone(),
+                metrics: self.metrics.clone(),
+            }
+        }
+    }
+
+    #[derive(Clone)]
+    pub struct LimitService<S> {
+        inner: S,
+        limits: Arc<RpcLimits>,
+        metrics: Arc<NodeMetrics>,
+    }
+
+    impl<'a, S> RpcServiceT<'a> for LimitService<S>
+    where
+        S: RpcServiceT<'a> + Send + Sync,
+        S::Future: Send + 'a,
+    {
+        type Future = Pin<Box<dyn Future<Output = MethodResponse> + Send + 'a>>;
+
+        fn call(&self, request: Request<'a>) -> Self::Future {
+            let started = Instant::now();
+            let method = request.method_name().to_string();
+            let id = request.id().into_owned();
+
+            // API keys carry their own limits.
+            let keyed = matches!(
+                request.extensions().get::<RpcCaller>(),
+                Some(RpcCaller::Key(_))
+            );
+            let client = request.extensions().get::<ClientIp>().copied();
+            if let (false, Some(ClientIp(ip))) = (keyed, client) {
+                if let Err(e) = self.limits.check(ip, &method, started) {
+                    tracing::debug!(method = %method, ip = %ip, error = %e, "RPC call refused");
+                    self.metrics.rpc_rejected(e.reason());
+                    let response = MethodResponse::error(
+                        id,
+                        ErrorObject::owned(e.code(), e.to_string(), None::<()>),
+                    );
+                    return Box::pin(std::future::ready(response));
+                }
+            }
+
+            let call = self.inner.call(request);
+            let limits = self.limits.clone();
+            let metrics = self.metrics.clone();
+            Box::pin(async move {
+                let response = match limits.request_timeout {
+                    Some(timeout) => match tokio::time::timeout(timeout, call).await {
+                        Ok(response) => response,
+                        Err(_) => {
+                            tracing::warn!(method = %method, timeout = ?timeout, "RPC call timed out");
+                            metrics.rpc_rejected(RpcRejectReason::Timeout);
+                            return MethodResponse::error(
+                                id,
+                                ErrorObject::owned(
+                                    -32008,
+                                    format!("request timed out after {:?}", timeout),
+                                    None::<()>,
+                                ),
+                            );
+                        }
+                    },
+                    None => call.await,
+                };
+                let elapsed = started.elapsed();
+                if limits.slow_query.is_some_and(|slow| elapsed >= slow) {
+                    tracing::warn!(method = %method, elapsed_ms = elapsed.as_millis() as u64, "slow RPC call");
+                    metrics.rpc_slow_requests.inc();
+                }
+                response
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ApiKeyConfig, NodeConfig, RpcConfig};
+    use crate::metrics::RpcRejectReason;
+    use jsonrpsee::core::client::ClientT;
+    use jsonrpsee::core::params::BatchRequestBuilder;
+    use jsonrpsee::http_client::{HeaderMap, HeaderValue, HttpClientBuilder};
+    use jsonrpsee::rpc_params;
+    use jsonrpsee::RpcModule;
+
+    /// Serve `norn_health`, `norn_faucet`, `norn_getBlock` (of height 1 only),
+    /// a slow `norn_getEvents` and, if enabled, `norn_graphql` over an empty
+    /// state behind the RPC middleware.
+    async fn serve_test(rpc: &RpcConfig, metrics: Arc<NodeMetrics>) -> (ServerHandle, String) {
+        let mut module = RpcModule::new(());
+        module
+            .register_method("norn_health", |_, _, _| "ok")
+            .unwrap();
+        module
+            .register_method("norn_faucet", |_, _, _| "sent")
+            .unwrap();
+        module
+            .register_method("norn_getBlock", |params, _, _| {
+                let height: u64 = params.one()?;
+                Ok::<_, jsonrpsee::types::ErrorObjectOwned>(
+                    (height == 1).then(|| serde_json::json!({ "height": 1 })),
+                )
+            })
+            .unwrap();
+        module
+            .register_async_method("norn_getEvents", |_, _, _| async {
+                tokio::time::sleep(std::time::Duration::from_millis(1_500)).await;
+                "events"
             })
+            .unwrap();
+        if rpc.graphql {
+            let state = Arc::new(RwLock::new(StateManager::new()));
+            module
+                .merge(crate::rpc::graphql::rpc_module(state, NetworkId::Dev))
+                .unwrap();
+        }
+        let (handle, addr) = serve(
+            "127.0.0.1:0",
+            module,
+            RpcAuth::from_config(rpc).unwrap().map(Arc::new),
+            Arc::new(RpcLimits::from_config(rpc).unwrap()),
+            RpcTransport::from_config(rpc).unwrap(),
+            rpc.rest_gateway,
+            rpc.graphql,
+            metrics,
+            Arc::new(NodeHealth::new(Default::default())),
+        )
+        .await
+        .unwrap();
+        (handle, format!("http://{}", addr))
+    }
+
+    fn client(url: &str, key: Option<&str>) -> jsonrpsee::http_client::HttpClient {
+        let mut headers = HeaderMap::new();
+        if let Some(key) = key {
+            headers.insert(
+                "Authorization",
+                HeaderValue::from_str(&format!("Bearer {}", key)).unwrap(),
+            );
+        }
+        HttpClientBuilder::default()
+            .set_headers(headers)
+            .build(url)
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_auth_middleware() {
+        let mut rpc = NodeConfig::default().rpc;
+        rpc.api_keys = vec![ApiKeyConfig {
+            name: "faucet".to_string(),
+            key: "faucet-secret-key-1".to_string(),
+            methods: vec!["norn_faucet".to_string()],
+            rate_limit_per_minute: Some(1),
+        }];
+        let (handle, url) = serve_test(&rpc, Arc::new(NodeMetrics::new())).await;
+
+        let public = client(&url, None);
+        let health: String = public.request("norn_health", rpc_params![]).await.unwrap();
+        assert_eq!(health, "ok");
+        let err = public
+            .request::<String, _>("norn_faucet", rpc_params![])
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("unauthorized"), "{}", err);
+
+        // Each entry of a batch is checked on its own.
+        let mut batch = BatchRequestBuilder::new();
+        batch.insert("norn_health", rpc_params![]).unwrap();
+        batch.insert("norn_faucet", rpc_params![]).unwrap();
+        let responses = public.batch_request::<String>(batch).await.unwrap();
+        assert_eq!(responses.num_successful_calls(), 1);
+
+        let keyed = client(&url, Some("faucet-secret-key-1"));
+        let sent: String = keyed.request("norn_faucet", rpc_params![]).await.unwrap();
+        assert_eq!(sent, "sent");
+        let err = keyed
+            .request::<String, _>("norn_faucet", rpc_params![])
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("rate limited"), "{}", err);
+
+        let wrong = client(&url, Some("not-a-key"));
+        assert!(wrong
+            .request::<String, _>("norn_health", rpc_params![])
+            .await
+            .is_err());
+
+        handle.stop().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rate_size_and_time_limits() {
+        let mut rpc = NodeConfig::default().rpc;
+        rpc.rate_limit_per_ip_per_minute = 2;
+        rpc.method_rate_limits = [("norn_getEvents".to_string(), 1)].into_iter().collect();
+        rpc.max_request_body_bytes = 1024;
+        rpc.request_timeout_secs = 1;
+        let metrics = Arc::new(NodeMetrics::new());
+        let (handle, url) = serve_test(&rpc, metrics.clone()).await;
+        let rejected = |reason| {
+            metrics
+                .rpc_requests_rejected
+                .get_or_create(&crate::metrics::RpcRejectLabels { reason })
+                .get()
+        };
+        let public = client(&url, None);
+
+        // The slow call times out, and a second one is over the method limit.
+        let err = public
+            .request::<String, _>("norn_getEvents", rpc_params![])
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("timed out"), "{}", err);
+        let err = public
+            .request::<String, _>("norn_getEvents", rpc_params![])
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("too many norn_getEvents"),
+            "{}",
+            err
+        );
+        assert_eq!(rejected(RpcRejectReason::Timeout), 1);
+        assert_eq!(rejected(RpcRejectReason::MethodRateLimited), 1);
+
+        let health: String = public.request("norn_health", rpc_params![]).await.unwrap();
+        assert_eq!(health, "ok");
+        let err = public
+            .request::<String, _>("norn_health", rpc_params![])
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("rate limited"), "{}", err);
+        assert_eq!(rejected(RpcRejectReason::IpRateLimited), 1);
+
+        let err = public
+            .request::<String, _>("norn_health", rpc_params!["x".repeat(2048)])
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("413"), "{}", err);
+        assert_eq!(rejected(RpcRejectReason::TooLarge), 1);
+
+        handle.stop().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rest_gateway_and_batch_limit() {
+        use http_body_util::BodyExt;
+
+        let mut rpc = NodeConfig::default().rpc;
+        rpc.rest_gateway = true;
+        rpc.max_batch_size = 2;
+        rpc.method_rate_limits = [("norn_getBlock".to_string(), 3)].into_iter().collect();
+        let (handle, url) = serve_test(&rpc, Arc::new(NodeMetrics::new())).await;
+
+        let http =
+            hyper_util::client::legacy::Client::builder(hyper_util::rt::TokioExecutor::new())
+                .build_http::<http_body_util::Empty<hyper::body::Bytes>>();
+        let get = |path: &str| {
+            let http = http.clone();
+            let uri: http::Uri = format!("{}{}", url, path).parse().unwrap();
+            async move {
+                let response = http.get(uri).await.unwrap();
+                let status = response.status();
+                let body = response.into_body().collect().await.unwrap().to_bytes();
+                (
+                    status,
+                    serde_json::from_slice::<serde_json::Value>(&body).unwrap(),
+                )
+            }
+        };
+
+        let (status, block) = get("/v1/blocks/1").await;
+        assert_eq!(status, http::StatusCode::OK);
+        assert_eq!(block, serde_json::json!({ "height": 1 }));
+        let (status, _) = get("/v1/blocks/2").await;
+        assert_eq!(status, http::StatusCode::NOT_FOUND);
+        let (status, error) = get("/v1/blocks/two").await;
+        assert_eq!(status, http::StatusCode::BAD_REQUEST);
+        assert_eq!(error["error"]["code"], -32602);
+        let (status, doc) = get("/v1/openapi.json").await;
+        assert_eq!(status, http::StatusCode::OK);
+        assert_eq!(
+            doc["paths"]["/v1/blocks/{height}"]["get"]["operationId"],
+            "norn_getBlock"
+        );
+
+        // Gateway requests go through the RPC rate limits.
+        let (status, _) = get("/v1/blocks/1").await;
+        assert_eq!(status, http::StatusCode::OK);
+        let (status, error) = get("/v1/blocks/1").await;
+        assert_eq!(status, http::StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(error["error"]["code"], -32005);
+
+        let public = client(&url, None);
+        let mut batch = BatchRequestBuilder::new();
+        for _ in 0..3 {
+            batch.insert("norn_health", rpc_params![]).unwrap();
         }
+        assert!(public.batch_request::<String>(batch).await.is_err());
+
+        handle.stop().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_graphql_endpoint() {
+        use http_body_util::BodyExt;
+
+        let mut rpc = NodeConfig::default().rpc;
+        rpc.graphql = true;
+        rpc.method_rate_limits = [("norn_graphql".to_string(), 2)].into_iter().collect();
+        let (handle, url) = serve_test(&rpc, Arc::new(NodeMetrics::new())).await;
+
+        let http =
+            hyper_util::client::legacy::Client::builder(hyper_util::rt::TokioExecutor::new())
+                .build_http::<String>();
+        let post = |body: &str| {
+            let request = http::Request::post(format!("{}/graphql", url))
+                .header(http::header::CONTENT_TYPE, "application/json")
+                .body(body.to_string())
+                .unwrap();
+            let http = http.clone();
+            async move {
+                let response = http.request(request).await.unwrap();
+                let status = response.status();
+                let body = response.into_body().collect().await.unwrap().to_bytes();
+                (
+                    status,
+                    serde_json::from_slice::<serde_json::Value>(&body).unwrap(),
+                )
+            }
+        };
+
+        let (status, response) = post(r#"{"query": "{ tokens { nodes { id } } }"}"#).await;
+        assert_eq!(status, http::StatusCode::OK);
+        assert_eq!(
+            response,
+            serde_json::json!({ "data": { "tokens": { "nodes": [] } } })
+        );
+        let (status, response) = post(r#"{"query": "{ nothing }"}"#).await;
+        assert_eq!(status, http::StatusCode::OK);
+        assert!(response["errors"][0]["message"].is_string());
+        let (status, response) = post("[]").await;
+        assert_eq!(status, http::StatusCode::BAD_REQUEST);
+        assert_eq!(response["errors"][0]["extensions"]["code"], -32700);
+
+        // Queries go through the RPC rate limits.
+        let (status, response) = post(r#"{"query": "{ looms { nodes { id } } }"}"#).await;
+        assert_eq!(status, http::StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response["errors"][0]["extensions"]["code"], -32005);
+
+        handle.stop().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_cors_and_forwarded_for() {
+        use http_body_util::BodyExt;
+
+        let mut rpc = NodeConfig::default().rpc;
+        rpc.cors_allowed_origins = vec!["https://app.example".to_string()];
+        rpc.trusted_proxies = vec!["127.0.0.1".to_string()];
+        rpc.rate_limit_per_ip_per_minute = 1;
+        let (handle, url) = serve_test(&rpc, Arc::new(NodeMetrics::new())).await;
+
+        let http =
+            hyper_util::client::legacy::Client::builder(hyper_util::rt::TokioExecutor::new())
+                .build_http::<String>();
+        let send = |request: http::Request<String>| {
+            let http = http.clone();
+            async move {
+                let response = http.request(request).await.unwrap();
+                let (parts, body) = response.into_parts();
+                let body = body.collect().await.unwrap().to_bytes();
+                (parts, String::from_utf8(body.to_vec()).unwrap())
+            }
+        };
+
+        let preflight = |origin: &str| {
+            http::Request::options(&url)
+                .header(http::header::ORIGIN, origin)
+                .header(http::header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+                .header(http::header::ACCESS_CONTROL_REQUEST_HEADERS, "content-type")
+                .body(String::new())
+                .unwrap()
+        };
+        let (parts, _) = send(preflight("https://app.example")).await;
+        assert_eq!(
+            parts.headers[http::header::ACCESS_CONTROL_ALLOW_ORIGIN],
+            "https://app.example"
+        );
+        let (parts, _) = send(preflight("https://evil.example")).await;
+        assert!(!parts
+            .headers
+            .contains_key(http::header::ACCESS_CONTROL_ALLOW_ORIGIN));
+
+        // Behind a trusted proxy, each forwarded client has its own limit.
+        let health = |client: &str| {
+            http::Request::post(&url)
+                .header(http::header::CONTENT_TYPE, "application/json")
+                .header(FORWARDED_FOR, client)
+                .body(r#"{"jsonrpc":"2.0","id":1,"method":"norn_health"}"#.to_string())
+                .unwrap()
+        };
+        let (_, body) = send(health("203.0.113.1")).await;
+        assert!(body.contains(r#""result":"ok""#), "{}", body);
+        let (_, body) = send(health("203.0.113.1")).await;
+        assert!(body.contains("rate limited"), "{}", body);
+        let (_, body) = send(health("203.0.113.2, 127.0.0.1")).await;
+        assert!(body.contains(r#""result":"ok""#), "{}", body);
+
+        handle.stop().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_liveness_and_readiness_probes() {
+        use http_body_util::BodyExt;
+
+        // Probes are answered without an API key.
+        let mut rpc = NodeConfig::default().rpc;
+        rpc.api_key = Some("probe-secret-key-1".to_string());
+        let health = Arc::new(NodeHealth::new(Default::default()));
+        let (handle, addr) = serve(
+            "127.0.0.1:0",
+            RpcModule::new(()),
+            RpcAuth::from_config(&rpc).unwrap().map(Arc::new),
+            Arc::new(RpcLimits::from_config(&rpc).unwrap()),
+            RpcTransport::default(),
+            false,
+            false,
+            Arc::new(NodeMetrics::new()),
+            health.clone(),
+        )
+        .await
+        .unwrap();
+
+        let http =
+            hyper_util::client::legacy::Client::builder(hyper_util::rt::TokioExecutor::new())
+                .build_http::<http_body_util::Empty<hyper::body::Bytes>>();
+        let get = |path: &str| {
+            let http = http.clone();
+            let uri: http::Uri = format!("http://{}{}", addr, path).parse().unwrap();
+            async move {
+                let response = http.get(uri).await.unwrap();
+                let status = response.status();
+                let body = response.into_body().collect().await.unwrap().to_bytes();
+                (
+                    status,
+                    serde_json::from_slice::<serde_json::Value>(&body).unwrap(),
+                )
+            }
+        };
+
+        health.observe(5, 0, None);
+        let (status, report) = get("/readyz").await;
+        assert_eq!(status, http::StatusCode::OK);
+        assert_eq!(report["status"], "ok");
+
+        // Far behind peers: still live, but not ready.
+        health.observe_network_height(100);
+        let (status, report) = get("/readyz").await;
+        assert_eq!(status, http::StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(report["issues"], serde_json::json!(["behind"]));
+        assert_eq!(report["blocks_behind"], 95);
+        let (status, report) = get("/healthz").await;
+        assert_eq!(status, http::StatusCode::OK);
+        assert_eq!(report["status"], "degraded");
+
+        handle.stop().unwrap();
     }
 }
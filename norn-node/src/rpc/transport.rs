@@ -0,0 +1,151 @@
+//! TLS and CORS of the RPC server, so that it can face browsers and the
+//! internet without a reverse proxy in front.
+//!
+//! With `rpc.tls_cert_path` and `rpc.tls_key_path` set, the JSON-RPC server
+//! (HTTP and WebSocket, with the REST gateway and GraphQL) and the gRPC
+//! server accept only TLS connections. With `rpc.cors_allowed_origins` set,
+//! browsers may call the JSON-RPC server from those origins.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use http::header::{AUTHORIZATION, CONTENT_TYPE};
+use http::{HeaderValue, Method};
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use tokio_rustls::TlsAcceptor;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+
+use crate::config::RpcConfig;
+use crate::error::NodeError;
+
+/// How long a client may take to complete the TLS handshake.
+pub const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// TLS identity of the RPC servers.
+#[derive(Clone)]
+pub struct RpcTls {
+    /// Acceptor for JSON-RPC connections.
+    pub acceptor: TlsAcceptor,
+    cert_pem: Vec<u8>,
+    key_pem: Vec<u8>,
+}
+
+impl RpcTls {
+    /// TLS config of the gRPC server, with the same identity.
+    pub fn grpc_config(&self) -> tonic::transport::ServerTlsConfig {
+        tonic::transport::ServerTlsConfig::new().identity(tonic::transport::Identity::from_pem(
+            &self.cert_pem,
+            &self.key_pem,
+        ))
+    }
+}
+
+/// TLS and CORS settings of the RPC server.
+#[derive(Clone, Default)]
+pub struct RpcTransport {
+    /// Serve over TLS.
+    pub tls: Option<RpcTls>,
+    /// Answer CORS requests.
+    pub cors: Option<CorsLayer>,
+}
+
+impl RpcTransport {
+    /// Build the settings from config, loading the TLS certificate and key.
+    pub fn from_config(config: &RpcConfig) -> Result<Self, NodeError> {
+        let invalid = |reason: String| NodeError::ConfigError { reason };
+        let tls = match (&config.tls_cert_path, &config.tls_key_path) {
+            (Some(cert), Some(key)) => Some(load_tls(cert, key).map_err(invalid)?),
+            (None, None) => None,
+            _ => {
+                return Err(invalid(
+                    "rpc.tls_cert_path and rpc.tls_key_path must be set together".into(),
+                ))
+            }
+        };
+        let cors = cors_layer(&config.cors_allowed_origins).map_err(invalid)?;
+        Ok(Self { tls, cors })
+    }
+}
+
+fn load_tls(cert_path: &str, key_path: &str) -> Result<RpcTls, String> {
+    let cert_pem = std::fs::read(cert_path)
+        .map_err(|e| format!("rpc.tls_cert_path '{}': {}", cert_path, e))?;
+    let key_pem =
+        std::fs::read(key_path).map_err(|e| format!("rpc.tls_key_path '{}': {}", key_path, e))?;
+
+    let certs = CertificateDer::pem_slice_iter(&cert_pem)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("rpc.tls_cert_path '{}': {}", cert_path, e))?;
+    if certs.is_empty() {
+        return Err(format!(
+            "rpc.tls_cert_path '{}': no certificate found",
+            cert_path
+        ));
+    }
+    let key = PrivateKeyDer::from_pem_slice(&key_pem)
+        .map_err(|e| format!("rpc.tls_key_path '{}': {}", key_path, e))?;
+
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let config = rustls::ServerConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .and_then(|builder| builder.with_no_client_auth().with_single_cert(certs, key))
+        .map_err(|e| format!("rpc TLS: {}", e))?;
+    Ok(RpcTls {
+        acceptor: TlsAcceptor::from(Arc::new(config)),
+        cert_pem,
+        key_pem,
+    })
+}
+
+/// CORS for `origins`, or `None` if there are none.
+fn cors_layer(origins: &[String]) -> Result<Option<CorsLayer>, String> {
+    if origins.is_empty() {
+        return Ok(None);
+    }
+    let allow_origin = if origins.iter().any(|o| o == "*") {
+        AllowOrigin::any()
+    } else {
+        let origins = origins
+            .iter()
+            .map(|origin| {
+                HeaderValue::from_str(origin.trim_end_matches('/'))
+                    .map_err(|_| format!("rpc.cors_allowed_origins: invalid origin '{}'", origin))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        AllowOrigin::list(origins)
+    };
+    Ok(Some(
+        CorsLayer::new()
+            .allow_origin(allow_origin)
+            .allow_methods([Method::GET, Method::POST])
+            .allow_headers([CONTENT_TYPE, AUTHORIZATION])
+            .max_age(Duration::from_secs(3600)),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::NodeConfig;
+
+    #[test]
+    fn test_from_config() {
+        let mut rpc = NodeConfig::default().rpc;
+        let transport = RpcTransport::from_config(&rpc).unwrap();
+        assert!(transport.tls.is_none() && transport.cors.is_none());
+
+        rpc.cors_allowed_origins = vec!["https://app.example/".to_string()];
+        assert!(RpcTransport::from_config(&rpc).unwrap().cors.is_some());
+        rpc.cors_allowed_origins = vec!["bad\norigin".to_string()];
+        assert!(RpcTransport::from_config(&rpc).is_err());
+        rpc.cors_allowed_origins.clear();
+
+        rpc.tls_cert_path = Some("/nonexistent/cert.pem".to_string());
+        let err = RpcTransport::from_config(&rpc).err().unwrap();
+        assert!(err.to_string().contains("set together"), "{}", err);
+        rpc.tls_key_path = Some("/nonexistent/key.pem".to_string());
+        let err = RpcTransport::from_config(&rpc).err().unwrap();
+        assert!(err.to_string().contains("rpc.tls_cert_path"), "{}", err);
+    }
+}
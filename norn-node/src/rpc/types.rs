@@ -9,8 +9,21 @@ pub struct ThreadInfo {
     pub owner: String,
     /// Current version number.
     pub version: u64,
+    /// Next knot sequence number: the lowest `before` version the thread's
+    /// next knot may use.
+    #[serde(default)]
+    pub nonce: u64,
     /// Current state hash as hex string.
     pub state_hash: String,
+    /// Number of key updates applied to the thread.
+    #[serde(default)]
+    pub key_epoch: u64,
+    /// Cosigner public keys as hex strings; empty if the thread has none.
+    #[serde(default)]
+    pub cosigners: Vec<String>,
+    /// Number of cosigner signatures each knot of the thread needs.
+    #[serde(default)]
+    pub cosigner_threshold: u8,
 }
 
 /// Information about a weave block.
@@ -95,6 +108,48 @@ pub struct SubmitResult {
     pub reason: Option<String>,
 }
 
+/// A multi-party knot collecting its participants' signatures.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnotProposalInfo {
+    /// Knot ID (hex).
+    pub knot_id: String,
+    /// The knot with the signatures collected so far (hex-encoded borsh);
+    /// unsigned slots are all zeros.
+    pub knot: String,
+    /// Participant addresses (hex), in signing order.
+    pub participants: Vec<String>,
+    /// Participants that have not signed yet (hex).
+    pub missing: Vec<String>,
+    /// When the proposal is dropped if still incomplete.
+    pub deadline: u64,
+    /// Whether every participant signed and the knot was submitted.
+    pub submitted: bool,
+    /// Why submission of the completed knot failed, if it did.
+    pub reason: Option<String>,
+}
+
+/// A problem found while validating uploaded loom bytecode.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleDiagnosticInfo {
+    /// Category: malformed, non_deterministic, disallowed_import,
+    /// excessive_memory or missing_export.
+    pub kind: String,
+    /// Human-readable description.
+    pub message: String,
+}
+
+/// Result of uploading loom bytecode.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadBytecodeResult {
+    /// Whether the bytecode was accepted and initialized.
+    pub success: bool,
+    /// Reason for failure, if any.
+    pub reason: Option<String>,
+    /// Validation problems, if the module was rejected.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub diagnostics: Vec<ModuleDiagnosticInfo>,
+}
+
 /// Thread state info with balance details.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ThreadStateInfo {
@@ -104,6 +159,9 @@ pub struct ThreadStateInfo {
     pub owner: String,
     /// Current version number.
     pub version: u64,
+    /// Next knot sequence number (see `ThreadInfo::nonce`).
+    #[serde(default)]
+    pub nonce: u64,
     /// Current state hash as hex string.
     pub state_hash: String,
     /// Token balances.
@@ -115,6 +173,9 @@ pub struct ThreadStateInfo {
 pub struct BalanceEntry {
     /// Token ID as hex string.
     pub token_id: String,
+    /// Token symbol, if the token is known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
     /// Raw amount as string.
     pub amount: String,
     /// Human-readable formatted amount.
@@ -130,7 +191,8 @@ pub struct HealthInfo {
     pub is_validator: bool,
     /// Number of registered threads.
     pub thread_count: u64,
-    /// Node uptime status.
+    /// `ok`, `syncing`, `degraded` (live but not ready) or `unhealthy` (not
+    /// live).
     pub status: String,
     /// Network identifier (e.g., "dev", "testnet", "mainnet").
     pub network: String,
@@ -142,6 +204,49 @@ pub struct HealthInfo {
     pub block_time_target: u64,
     /// Last measured block production time in microseconds (None if no blocks produced yet).
     pub last_block_production_us: Option<u64>,
+    /// Whether the node's main loop is running.
+    #[serde(default)]
+    pub live: bool,
+    /// Whether the node should receive traffic.
+    #[serde(default)]
+    pub ready: bool,
+    /// Why the node is not ready.
+    #[serde(default)]
+    pub issues: Vec<crate::health::HealthIssue>,
+    /// Blocks below the highest height peers have shown.
+    #[serde(default)]
+    pub blocks_behind: u64,
+    /// Items in the mempool.
+    #[serde(default)]
+    pub mempool_size: u64,
+    /// Connected peers (None without networking).
+    #[serde(default)]
+    pub peer_count: Option<u64>,
+}
+
+/// A connected peer, its reputation and its traffic.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeerInfo {
+    /// libp2p peer ID.
+    pub peer_id: String,
+    /// Reputation score; 0 is neutral, and the peer is banned at -100.
+    pub score: i32,
+    /// The peer's protocol version, if known.
+    pub protocol_version: Option<u8>,
+    /// Seconds since the peer connected.
+    pub connected_secs: u64,
+    /// Whether the peer is greylisted for repeated bans.
+    pub greylisted: bool,
+    /// Bytes received from the peer.
+    pub bytes_in: u64,
+    /// Bytes of direct messages sent to the peer.
+    pub bytes_out: u64,
+    /// Current receive rate in bytes per second.
+    pub bytes_in_per_sec: u64,
+    /// Current send rate in bytes per second.
+    pub bytes_out_per_sec: u64,
+    /// Messages from the peer dropped for exceeding its rate limits.
+    pub throttled: u64,
 }
 
 /// Information about a validator.
@@ -181,6 +286,84 @@ pub struct FeeEstimateInfo {
     pub transfer_fee: String,
 }
 
+/// A fee estimate at a given confidence.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EstimatedFeeInfo {
+    /// Estimated fee per commitment in nits.
+    pub fee: String,
+    /// Confidence (percent) the fee is enough.
+    pub confidence: u8,
+    /// Current fee per commitment in nits.
+    pub current_fee: String,
+    /// Number of recent blocks the estimate is based on.
+    pub blocks_sampled: usize,
+}
+
+/// Fees charged in recent blocks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeeHistoryInfo {
+    /// Per-block fees, oldest first.
+    pub blocks: Vec<BlockFeeInfo>,
+    /// 10th percentile fee per commitment over the blocks, in nits.
+    pub p10: Option<String>,
+    /// Median fee per commitment over the blocks, in nits.
+    pub p50: Option<String>,
+    /// 90th percentile fee per commitment over the blocks, in nits.
+    pub p90: Option<String>,
+}
+
+/// The fee charged in one block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockFeeInfo {
+    /// Block height.
+    pub height: u64,
+    /// Fee charged per commitment in nits.
+    pub fee_per_commitment: String,
+    /// Fee multiplier (scaled by 1000).
+    pub fee_multiplier: u64,
+    /// Total priority fees paid to the proposer in nits.
+    pub priority_fees: String,
+    /// Number of commitments included.
+    pub commitment_count: u64,
+}
+
+/// Summary of a node's mempool.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MempoolInfo {
+    /// Total number of pending items.
+    pub total: usize,
+    /// Maximum number of pending items.
+    pub capacity: usize,
+    /// Pending commitment updates.
+    pub commitment_count: usize,
+    /// Pending transfers.
+    pub transfer_count: usize,
+    /// Pending thread registrations.
+    pub registration_count: usize,
+    /// Pending fraud proofs and slashing evidence.
+    pub evidence_count: usize,
+    /// Every other pending item.
+    pub other_count: usize,
+    /// Lowest priority fee among pending commitments in nits, if any. A commitment
+    /// must pay more to get in while the mempool is full.
+    pub min_commitment_fee: Option<String>,
+    /// Pending commitments, highest priority first.
+    pub commitments: Vec<MempoolCommitmentInfo>,
+}
+
+/// A pending commitment in the mempool.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MempoolCommitmentInfo {
+    /// Thread ID as hex string.
+    pub thread_id: String,
+    /// Committed version.
+    pub version: u64,
+    /// Priority fee (tip) offered in nits.
+    pub fee: String,
+    /// Commitment timestamp.
+    pub timestamp: u64,
+}
+
 /// Merkle proof for a thread commitment.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommitmentProofInfo {
@@ -256,6 +439,18 @@ pub struct TokenInfo {
     pub creator: String,
     /// Creation timestamp.
     pub created_at: u64,
+    /// Short description, set by the creator.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Icon URI (https, ipfs or ar), set by the creator.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon_uri: Option<String>,
+    /// Project URL, set by the creator.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_url: Option<String>,
+    /// Tags, set by the creator.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
 }
 
 /// Information about a deployed loom (smart contract).
@@ -282,6 +477,53 @@ pub struct LoomInfo {
     pub participant_count: usize,
 }
 
+/// A verifiable export of a loom's state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoomStateExportInfo {
+    /// Loom ID as hex string.
+    pub loom_id: String,
+    /// State hash anchored by the loom, as hex string.
+    pub state_hash: String,
+    /// Merkle root over the state entries, as hex string.
+    pub merkle_root: String,
+    /// Number of key-value entries.
+    pub entry_count: usize,
+    /// Hex-encoded borsh `LoomStateSnapshot`.
+    pub snapshot_hex: String,
+}
+
+/// A dispute over a loom execution.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DisputeInfo {
+    /// Dispute ID (hash of the evidence bundle) as hex string.
+    pub dispute_id: String,
+    /// Loom ID as hex string.
+    pub loom_id: String,
+    /// Challenger address as hex string.
+    pub challenger: String,
+    /// Defendant (loom operator) address as hex string.
+    pub defendant: String,
+    /// Bond at stake on each side, as raw amount string.
+    pub bond: String,
+    /// "pending", "upheld", or "rejected".
+    pub status: String,
+    /// State root before the disputed execution, as hex string.
+    pub prior_state_root: String,
+    /// Output root the defendant claimed, as hex string.
+    pub claimed_output_root: String,
+    /// Output root produced by re-execution, as hex string.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub computed_output_root: Option<String>,
+    /// Why the dispute was resolved the way it was.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// When the dispute was opened.
+    pub opened_at: u64,
+    /// When the dispute was resolved.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_at: Option<u64>,
+}
+
 /// A key-value attribute in a structured event.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AttributeInfo {
@@ -301,6 +543,116 @@ pub struct EventInfo {
     pub attributes: Vec<AttributeInfo>,
 }
 
+/// Filter for querying indexed loom events. Unset fields match all events.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EventFilterParams {
+    /// Loom ID (hex).
+    pub loom_id: Option<String>,
+    /// Event type.
+    #[serde(rename = "type")]
+    pub ty: Option<String>,
+    /// Attribute key the event must carry.
+    pub attribute_key: Option<String>,
+    /// Required value for `attribute_key`.
+    pub attribute_value: Option<String>,
+    /// Inclusive lower bound on block height.
+    pub from_height: Option<u64>,
+    /// Inclusive upper bound on block height.
+    pub to_height: Option<u64>,
+}
+
+/// A loom event from the node's event index.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexedEventInfo {
+    /// Position in the node's event log.
+    pub seq: u64,
+    pub loom_id: String,
+    /// Address whose execution emitted the event.
+    pub sender: String,
+    pub block_height: u64,
+    pub timestamp: u64,
+    /// Event type (e.g., "Transfer", "Approval").
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// Key-value attributes.
+    pub attributes: Vec<AttributeInfo>,
+}
+
+/// An address in both of its encodings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddressInfo {
+    /// Hex-encoded address, without `0x`.
+    pub hex: String,
+    /// bech32m address with the node's network prefix.
+    pub bech32: String,
+    /// Network the bech32m form is for.
+    pub network: String,
+}
+
+/// Loom gas schedule active at a block height.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GasScheduleInfo {
+    /// Schedule version.
+    pub version: u32,
+    /// First block height the schedule applies to.
+    pub activation_height: u64,
+    /// Cost per Wasm instruction.
+    pub instruction: u64,
+    /// Cost per state read.
+    pub state_read: u64,
+    /// Cost per state write.
+    pub state_write: u64,
+    /// Cost per byte read from state.
+    pub byte_read: u64,
+    /// Cost per byte written to state.
+    pub byte_write: u64,
+    /// Cost per balance lookup.
+    pub balance_query: u64,
+    /// Cost per sub-address derivation.
+    pub derive_address: u64,
+    /// Cost per VRF proof verification.
+    pub vrf_verify: u64,
+    /// Base cost per sealed-box decryption.
+    pub decrypt: u64,
+    /// Cost per Ed25519 signature verification.
+    pub signature_verify: u64,
+    /// Cost per token transfer.
+    pub transfer: u64,
+    /// Cost per log message.
+    pub log: u64,
+    /// Cost per structured event.
+    pub emit_event: u64,
+    /// Base cost of a cross-contract call.
+    pub cross_call: u64,
+    /// Base cost of instantiating a loom from stored code.
+    pub instantiate: u64,
+}
+
+/// Stored, content-addressed loom bytecode.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CodeInfo {
+    /// Code ID (hex blake3 hash of the bytecode).
+    pub code_id: String,
+    /// Bytecode size in bytes.
+    pub size: usize,
+    /// Number of looms running this code.
+    pub instance_count: usize,
+    /// Bytecode as hex string (only returned by `norn_getCode`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bytecode_hex: Option<String>,
+}
+
+/// Result of storing reusable bytecode.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoreCodeResult {
+    /// Whether the code was stored.
+    pub success: bool,
+    /// Code ID (hex blake3 hash of the bytecode).
+    pub code_id: Option<String>,
+    /// Reason for failure, if any.
+    pub reason: Option<String>,
+}
+
 /// Result of executing a loom contract.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExecutionResult {
@@ -317,6 +669,9 @@ pub struct ExecutionResult {
     pub events: Vec<EventInfo>,
     /// Reason for failure, if any.
     pub reason: Option<String>,
+    /// Hex IDs of looms instantiated during execution.
+    #[serde(default)]
+    pub instantiated: Vec<String>,
 }
 
 /// Result of querying a loom contract (read-only).
@@ -337,6 +692,57 @@ pub struct QueryResult {
     pub reason: Option<String>,
 }
 
+/// A host function call recorded by `norn_traceLoomExecution`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostCallInfo {
+    /// Cross-call depth (0 for the traced loom).
+    pub depth: u32,
+    /// Host function name.
+    pub host_fn: String,
+    /// Gas charged by the call, including nested execution.
+    pub gas: u64,
+    /// Arguments and result.
+    pub detail: String,
+}
+
+/// Gas charged to one host function by the traced loom.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostGasInfo {
+    /// Host function name.
+    pub host_fn: String,
+    /// Number of calls.
+    pub calls: u64,
+    /// Total gas charged.
+    pub gas: u64,
+}
+
+/// Result of tracing a loom execution (nothing is committed).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionTraceInfo {
+    /// Whether execution succeeded.
+    pub success: bool,
+    /// Output data as hex string.
+    pub output_hex: Option<String>,
+    /// Total gas consumed.
+    pub gas_used: u64,
+    /// Gas spent on Wasm instructions.
+    pub instruction_gas: u64,
+    /// Gas per host function.
+    pub host_gas: Vec<HostGasInfo>,
+    /// Host calls in completion order.
+    pub calls: Vec<HostCallInfo>,
+    /// Messages from `norn_debug_log`.
+    pub debug_logs: Vec<String>,
+    /// Log messages from execution.
+    pub logs: Vec<String>,
+    /// Structured events from execution.
+    pub events: Vec<EventInfo>,
+    /// Whether trace entries were dropped after a limit was reached.
+    pub truncated: bool,
+    /// Reason for failure, if any.
+    pub reason: Option<String>,
+}
+
 /// Information about a name owned by an address.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NameInfo {
@@ -357,6 +763,10 @@ pub struct StakingInfo {
     pub min_stake: String,
     /// Bonding period in blocks.
     pub bonding_period: u64,
+    /// Current epoch number.
+    pub epoch: u64,
+    /// Maximum number of validators in an epoch's active set.
+    pub max_validators: u64,
 }
 
 /// Per-validator staking details.
@@ -366,10 +776,107 @@ pub struct ValidatorStakeInfo {
     pub pubkey: String,
     /// Address as hex string.
     pub address: String,
-    /// Staked amount as string.
+    /// Self-staked amount as string.
     pub stake: String,
-    /// Whether the validator is active.
+    /// Stake delegated to the validator as string.
+    pub delegated: String,
+    /// Number of delegators.
+    pub delegator_count: u64,
+    /// Commission on delegators' rewards in basis points.
+    pub commission_bps: u16,
+    /// Stake bonded at the next epoch boundary, as string.
+    pub pending_stake: String,
+    /// Stake waiting to be released after the bonding period, as string.
+    pub pending_unstake: String,
+    /// Whether the validator is in the current epoch's active set.
     pub active: bool,
+    /// Whether the validator is jailed.
+    pub jailed: bool,
+    /// Height at which a validator jailed for downtime may unjail. Absent
+    /// if not jailed or if it was slashed for double-signing.
+    pub jailed_until: Option<u64>,
+}
+
+/// A slashing applied to a validator.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlashRecordInfo {
+    /// Slashed validator public key as hex string.
+    pub validator: String,
+    /// Why it was slashed: "double_sign" or "downtime".
+    pub reason: String,
+    /// Stake burned, including delegations, as string.
+    pub amount: String,
+    /// Block height at which the slashing was applied.
+    pub height: u64,
+    /// Height from which the validator may unjail. Absent if it can never
+    /// rejoin.
+    pub jailed_until: Option<u64>,
+}
+
+/// A delegation from a delegator to a validator.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DelegationInfo {
+    /// Validator public key as hex string.
+    pub validator: String,
+    /// Delegator public key as hex string.
+    pub delegator: String,
+    /// Bonded amount as string.
+    pub amount: String,
+    /// Amount bonded at the next epoch boundary, as string.
+    pub pending: String,
+    /// Amount waiting to be released after the bonding period, as string.
+    pub pending_undelegate: String,
+    /// The validator's commission in basis points.
+    pub commission_bps: u16,
+}
+
+/// A bonded spindle on the subscription market.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpindleInfo {
+    /// Spindle public key as hex string.
+    pub pubkey: String,
+    /// Spindle address as hex string.
+    pub address: String,
+    /// Bonded amount as string.
+    pub bond: String,
+    /// Fee charged per fee period, as string.
+    pub fee_per_period: String,
+    /// Number of threads the spindle is paid to watch.
+    pub subscriber_count: u64,
+    /// Total bond slashed for missed fraud, as string.
+    pub slashed: String,
+}
+
+/// A thread's paid subscription to a spindle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpindleSubscriptionInfo {
+    /// Watched thread (subscriber address) as hex string.
+    pub thread_id: String,
+    /// Spindle public key as hex string.
+    pub spindle: String,
+    /// Fee per period locked in when subscribing, as string.
+    pub fee_per_period: String,
+    /// Block height at which the subscription started.
+    pub start_height: u64,
+    /// Block height at which the last prepaid period ends.
+    pub end_height: u64,
+    /// Number of prepaid periods.
+    pub periods: u64,
+    /// Number of periods already paid out to the spindle.
+    pub periods_paid: u64,
+}
+
+/// Spindle coverage of a thread, from gossiped coverage advertisements.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpindleCoverageInfo {
+    /// Thread ID as hex string.
+    pub thread_id: String,
+    /// Public keys (hex) of the spindles advertising coverage of the thread.
+    pub watchers: Vec<String>,
+    /// Required number of watching spindles.
+    pub redundancy: usize,
+    /// Whether at least `redundancy` spindles watch the thread.
+    pub covered: bool,
 }
 
 /// Validator reward distribution info.
@@ -413,6 +920,87 @@ pub struct StateProofInfo {
     pub proof: Vec<String>,
 }
 
+/// An RPC parameter that accepts either a single value or a list.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum OneOrMany<T> {
+    One(T),
+    Many(Vec<T>),
+}
+
+impl<T> OneOrMany<T> {
+    /// The values as a list.
+    pub fn into_vec(self) -> Vec<T> {
+        match self {
+            OneOrMany::One(value) => vec![value],
+            OneOrMany::Many(values) => values,
+        }
+    }
+}
+
+/// Response to `norn_getStateProof`: a single proof when one balance was
+/// requested, a multiproof when a list was.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum StateProofResult {
+    Single(StateProofInfo),
+    Multi(StateMultiProofInfo),
+}
+
+/// One compact Merkle proof covering several balances.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateMultiProofInfo {
+    /// State root as hex string.
+    pub state_root: String,
+    /// The proved balances, in the order requested.
+    pub balances: Vec<StateProofBalance>,
+    /// One bit per sibling the verifier consumes (MSB-first), as hex: set if
+    /// the sibling is the next entry of `siblings`, clear if it is empty.
+    pub sibling_bitmap: String,
+    /// Non-empty sibling hashes as hex strings, in the order consumed.
+    pub siblings: Vec<String>,
+}
+
+/// A balance covered by a state multiproof.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateProofBalance {
+    /// Address as hex string.
+    pub address: String,
+    /// Token ID as hex string.
+    pub token_id: String,
+    /// Balance as string.
+    pub balance: String,
+    /// The proved leaf value as hex: the balance as u128 little-endian, or
+    /// empty if the balance has never been set.
+    pub value: String,
+}
+
+/// A block header for light clients.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockHeaderInfo {
+    /// Block height.
+    pub height: u64,
+    /// Block hash as hex string.
+    pub hash: String,
+    /// Hex-encoded borsh `BlockHeader`.
+    pub header_hex: String,
+}
+
+/// An SPV proof that a transfer is included in the weave.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferProofInfo {
+    /// Knot ID of the transfer as hex string.
+    pub knot_id: String,
+    /// Height of the block that included the transfer.
+    pub block_height: u64,
+    /// Hash of that block as hex string.
+    pub block_hash: String,
+    /// Headers in the proof, from the including block towards the tip.
+    pub confirmations: u64,
+    /// Hex-encoded borsh `TransferProof`.
+    pub proof_hex: String,
+}
+
 /// A real-time transfer event for WebSocket subscribers.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransferEvent {
@@ -440,7 +1028,7 @@ pub struct TransferEvent {
 /// A real-time token event for WebSocket subscribers.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenEvent {
-    /// Event type: "created", "minted", or "burned".
+    /// Event type: "created", "minted", "burned", or "metadata_updated".
     pub event_type: String,
     /// Token ID as hex string.
     pub token_id: String,
@@ -674,4 +1262,12 @@ mod tests {
         assert_eq!(deserialized.height, 100);
         assert_eq!(deserialized.thread_count, 50);
     }
+
+    #[test]
+    fn test_one_or_many_deserialization() {
+        let one: OneOrMany<String> = serde_json::from_str("\"ab\"").unwrap();
+        assert_eq!(one.into_vec(), vec!["ab".to_string()]);
+        let many: OneOrMany<String> = serde_json::from_str("[\"ab\", \"cd\"]").unwrap();
+        assert_eq!(many.into_vec().len(), 2);
+    }
 }
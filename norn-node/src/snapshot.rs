@@ -0,0 +1,320 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use norn_crypto::hash::{blake3_hash, blake3_hash_domain};
+use norn_crypto::keys::{verify, Keypair};
+use norn_storage::memory::MemoryStore;
+use norn_types::constants::SNAPSHOT_CHUNK_SIZE;
+use norn_types::primitives::Hash;
+use norn_types::snapshot::SnapshotManifest;
+use norn_types::weave::{ValidatorSet, ValidatorSignature, WeaveBlock};
+
+use crate::error::NodeError;
+use crate::state_store::StateStore;
+
+/// State entries (key, value) carried by a snapshot.
+pub type SnapshotEntries = Vec<(Vec<u8>, Vec<u8>)>;
+
+/// A state snapshot this node can serve to peers.
+pub struct Snapshot {
+    pub manifest: SnapshotManifest,
+    /// The block the snapshot is anchored to.
+    pub block: WeaveBlock,
+    chunks: HashMap<Hash, Vec<u8>>,
+}
+
+impl Snapshot {
+    /// Build a snapshot of `entries`, the state committed to by
+    /// `block.state_root`, and sign its manifest.
+    pub fn new(
+        entries: &SnapshotEntries,
+        block: WeaveBlock,
+        keypair: &Keypair,
+    ) -> Result<Self, NodeError> {
+        let data = borsh::to_vec(entries).map_err(|e| NodeError::SnapshotError {
+            reason: format!("failed to serialize snapshot: {}", e),
+        })?;
+        let chunks: Vec<Vec<u8>> = data
+            .chunks(SNAPSHOT_CHUNK_SIZE)
+            .map(<[u8]>::to_vec)
+            .collect();
+        let chunk_hashes: Vec<Hash> = chunks.iter().map(|chunk| blake3_hash(chunk)).collect();
+        let mut manifest = SnapshotManifest {
+            height: block.height,
+            block_hash: block.hash,
+            state_root: block.state_root,
+            chunk_hashes: chunk_hashes.clone(),
+            signatures: Vec::new(),
+        };
+        let digest = manifest_digest(&manifest);
+        manifest.signatures.push(ValidatorSignature {
+            validator: keypair.public_key(),
+            signature: keypair.sign(&digest),
+        });
+        Ok(Self {
+            manifest,
+            block,
+            chunks: chunk_hashes.into_iter().zip(chunks).collect(),
+        })
+    }
+
+    /// Look up a chunk by its content hash.
+    pub fn chunk(&self, chunk_hash: &Hash) -> Option<&[u8]> {
+        self.chunks.get(chunk_hash).map(Vec::as_slice)
+    }
+}
+
+/// The digest validators sign: everything in the manifest but the signatures.
+pub fn manifest_digest(manifest: &SnapshotManifest) -> Hash {
+    let mut data = Vec::with_capacity(8 + 32 * (2 + manifest.chunk_hashes.len()));
+    data.extend_from_slice(&manifest.height.to_le_bytes());
+    data.extend_from_slice(&manifest.block_hash);
+    data.extend_from_slice(&manifest.state_root);
+    for chunk_hash in &manifest.chunk_hashes {
+        data.extend_from_slice(chunk_hash);
+    }
+    blake3_hash_domain("norn-snapshot-manifest", &data)
+}
+
+/// Add the signatures on `other` to `manifest` if both describe the same
+/// snapshot. Returns whether they did.
+pub fn merge_signatures(manifest: &mut SnapshotManifest, other: &SnapshotManifest) -> bool {
+    if manifest_digest(manifest) != manifest_digest(other) {
+        return false;
+    }
+    for sig in &other.signatures {
+        if !manifest
+            .signatures
+            .iter()
+            .any(|s| s.validator == sig.validator)
+        {
+            manifest.signatures.push(sig.clone());
+        }
+    }
+    true
+}
+
+/// Check that a manifest matches a valid anchor block and is signed by a
+/// quorum of validators.
+pub fn verify_manifest(
+    manifest: &SnapshotManifest,
+    block: &WeaveBlock,
+    validator_set: &ValidatorSet,
+) -> Result<(), NodeError> {
+    if block.height != manifest.height
+        || block.hash != manifest.block_hash
+        || block.state_root != manifest.state_root
+    {
+        return Err(NodeError::SnapshotError {
+            reason: "manifest does not match its anchor block".to_string(),
+        });
+    }
+    if manifest.chunk_hashes.is_empty() {
+        return Err(NodeError::SnapshotError {
+            reason: "manifest has no chunks".to_string(),
+        });
+    }
+    norn_weave::block::verify_block(block, validator_set).map_err(|e| {
+        NodeError::SnapshotError {
+            reason: format!("invalid anchor block: {}", e),
+        }
+    })?;
+
+    let digest = manifest_digest(manifest);
+    let signers: HashSet<_> = manifest
+        .signatures
+        .iter()
+        .filter(|sig| {
+            validator_set.contains(&sig.validator)
+                && verify(&digest, &sig.signature, &sig.validator).is_ok()
+        })
+        .map(|sig| sig.validator)
+        .collect();
+    let quorum = validator_set.quorum_size();
+    if signers.len() < quorum {
+        return Err(NodeError::SnapshotError {
+            reason: format!(
+                "manifest signed by {} validators, need {}",
+                signers.len(),
+                quorum
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// Reassemble snapshot entries from downloaded chunks, checking each chunk
+/// against the manifest.
+pub fn assemble(
+    manifest: &SnapshotManifest,
+    chunks: &[Vec<u8>],
+) -> Result<SnapshotEntries, NodeError> {
+    if chunks.len() != manifest.chunk_hashes.len() {
+        return Err(NodeError::SnapshotError {
+            reason: format!(
+                "expected {} chunks, got {}",
+                manifest.chunk_hashes.len(),
+                chunks.len()
+            ),
+        });
+    }
+    let mut data = Vec::with_capacity(chunks.iter().map(Vec::len).sum());
+    for (index, (chunk, expected)) in chunks.iter().zip(&manifest.chunk_hashes).enumerate() {
+        if blake3_hash(chunk) != *expected {
+            return Err(NodeError::SnapshotError {
+                reason: format!("chunk {} does not match its hash", index),
+            });
+        }
+        data.extend_from_slice(chunk);
+    }
+    borsh::from_slice(&data).map_err(|e| NodeError::SnapshotError {
+        reason: format!("failed to decode snapshot: {}", e),
+    })
+}
+
+/// Rebuild the snapshot state in a scratch store and check that its state
+/// root matches the manifest.
+pub fn verify_state(
+    entries: &SnapshotEntries,
+    manifest: &SnapshotManifest,
+) -> Result<(), NodeError> {
+    let scratch = StateStore::new(Arc::new(MemoryStore::new()));
+    scratch.import_snapshot(entries, manifest.height)?;
+    let state_root = scratch.rebuild()?.state_root();
+    if state_root != manifest.state_root {
+        return Err(NodeError::SnapshotError {
+            reason: format!(
+                "state root mismatch: manifest {}, snapshot {}",
+                hex::encode(manifest.state_root),
+                hex::encode(state_root)
+            ),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_manager::StateManager;
+    use norn_crypto::bls::BlsKeypair;
+    use norn_types::constants::ONE_NORN;
+    use norn_types::primitives::NATIVE_TOKEN_ID;
+    use norn_types::thread::ThreadState;
+    use norn_types::weave::{BlockTransfer, FeeState, Validator, WeaveState};
+    use norn_weave::engine::WeaveEngine;
+
+    /// A funded state and a solo-validator block anchored to it.
+    fn snapshot_fixture(keypair: &Keypair) -> (SnapshotEntries, WeaveBlock, ValidatorSet) {
+        let mut sm = StateManager::new();
+        sm.set_store(StateStore::new(Arc::new(MemoryStore::new())));
+        let (a, b) = ([1u8; 20], [2u8; 20]);
+        sm.register_thread(a, [1u8; 32]);
+        sm.register_thread(b, [2u8; 32]);
+        sm.credit(a, NATIVE_TOKEN_ID, 10 * ONE_NORN).unwrap();
+        sm.apply_transfer(a, b, NATIVE_TOKEN_ID, ONE_NORN, [7u8; 32], None, 1)
+            .unwrap();
+
+        let validator_set = ValidatorSet {
+            validators: vec![Validator {
+                pubkey: keypair.public_key(),
+                bls_pubkey: BlsKeypair::from_keypair(keypair).public_key(),
+                address: [0u8; 20],
+                stake: 1000,
+                active: true,
+            }],
+            total_stake: 1000,
+            epoch: 0,
+        };
+        let mut engine = WeaveEngine::new(
+            Keypair::from_seed(&keypair.seed()),
+            validator_set.clone(),
+            WeaveState {
+                height: 0,
+                latest_hash: [0u8; 32],
+                threads_root: [0u8; 32],
+                thread_count: 0,
+                fee_state: FeeState {
+                    base_fee: 100,
+                    fee_multiplier: 1000,
+                    epoch_fees: 0,
+                },
+            },
+        );
+        engine
+            .add_transfer(BlockTransfer {
+                from: a,
+                to: b,
+                token_id: NATIVE_TOKEN_ID,
+                amount: ONE_NORN,
+                memo: None,
+                knot_id: [7u8; 32],
+                timestamp: 1,
+            })
+            .unwrap();
+        let block = engine.produce_block(1, sm.state_root(), &sm).unwrap();
+        let entries = sm.store().unwrap().snapshot_entries().unwrap();
+        (entries, block, validator_set)
+    }
+
+    #[test]
+    fn test_snapshot_roundtrip_verifies() {
+        let keypair = Keypair::generate();
+        let (entries, block, validator_set) = snapshot_fixture(&keypair);
+        let snapshot = Snapshot::new(&entries, block.clone(), &keypair).unwrap();
+        let manifest = &snapshot.manifest;
+        verify_manifest(manifest, &block, &validator_set).unwrap();
+
+        let chunks: Vec<Vec<u8>> = manifest
+            .chunk_hashes
+            .iter()
+            .map(|h| snapshot.chunk(h).unwrap().to_vec())
+            .collect();
+        let assembled = assemble(manifest, &chunks).unwrap();
+        assert_eq!(assembled, entries);
+        verify_state(&assembled, manifest).unwrap();
+    }
+
+    #[test]
+    fn test_snapshot_rejects_tampering() {
+        let keypair = Keypair::generate();
+        let (mut entries, block, validator_set) = snapshot_fixture(&keypair);
+
+        // Signed by a key outside the validator set.
+        let outsider = Snapshot::new(&entries, block.clone(), &Keypair::generate()).unwrap();
+        assert!(verify_manifest(&outsider.manifest, &block, &validator_set).is_err());
+
+        // A chunk that does not match its hash.
+        let snapshot = Snapshot::new(&entries, block.clone(), &keypair).unwrap();
+        assert!(assemble(&snapshot.manifest, &[vec![0u8; 4]]).is_err());
+
+        // Validly chunked and signed state that does not match the anchored root.
+        let (_, value) = entries
+            .iter_mut()
+            .find(|(key, _)| key.starts_with(b"state:thread:"))
+            .unwrap();
+        let mut state: ThreadState = borsh::from_slice(value).unwrap();
+        state.credit(NATIVE_TOKEN_ID, ONE_NORN).unwrap();
+        *value = borsh::to_vec(&state).unwrap();
+        let forged = Snapshot::new(&entries, block.clone(), &keypair).unwrap();
+        verify_manifest(&forged.manifest, &block, &validator_set).unwrap();
+        assert!(verify_state(&entries, &forged.manifest).is_err());
+    }
+
+    #[test]
+    fn test_merge_signatures() {
+        let (kp_a, kp_b) = (Keypair::generate(), Keypair::generate());
+        let (entries, block, _) = snapshot_fixture(&kp_a);
+        let mut a = Snapshot::new(&entries, block.clone(), &kp_a)
+            .unwrap()
+            .manifest;
+        let b = Snapshot::new(&entries, block, &kp_b).unwrap().manifest;
+        assert!(merge_signatures(&mut a, &b));
+        assert!(merge_signatures(&mut a, &b));
+        assert_eq!(a.signatures.len(), 2);
+
+        let mut other = a.clone();
+        other.height += 1;
+        assert!(!merge_signatures(&mut a, &other));
+    }
+}
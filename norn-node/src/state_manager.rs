@@ -1,21 +1,29 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 use borsh::{BorshDeserialize, BorshSerialize};
 
 use norn_crypto::address::pubkey_to_address;
 use norn_crypto::merkle::SparseMerkleTree;
+use norn_crypto::spv::TransferProof;
+use norn_loom::host::BalanceView;
+use norn_spindle::subscription::{Settlement, SubscriptionRegistry};
+use norn_storage::error::StorageError;
 use norn_types::constants::{MAX_SUPPLY, TRANSFER_FEE};
 use norn_types::error::NornError;
+use norn_types::knot::{Knot, TransferPayload};
 use norn_types::loom::LOOM_DEPLOY_FEE;
 use norn_types::name::NAME_REGISTRATION_FEE;
 use norn_types::primitives::{Address, Amount, Hash, LoomId, PublicKey, TokenId, NATIVE_TOKEN_ID};
-use norn_types::thread::ThreadState;
+use norn_types::thread::{CosignerSet, GuardianSet, ThreadKeys, ThreadState};
 use norn_types::token::TOKEN_CREATION_FEE;
-use norn_types::weave::WeaveBlock;
+use norn_types::weave::{TokenMetadata, TokenMetadataUpdate, WeaveBlock};
 
 // Re-export for backward compatibility (used by wallet CLI and state_store).
 pub use norn_types::name::validate_name;
 
+/// Raw key-value state of a loom.
+pub type LoomStateData = HashMap<Vec<u8>, Vec<u8>>;
+
 /// A record of a registered name.
 #[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
 pub struct NameRecord {
@@ -36,6 +44,10 @@ pub struct TokenRecord {
     pub current_supply: Amount,
     pub creator: Address,
     pub created_at: u64,
+    /// Description, icon and links set by the creator.
+    pub metadata: TokenMetadata,
+    /// Timestamp of the last metadata update (0 if never updated).
+    pub metadata_updated_at: u64,
 }
 
 /// A record of a deployed loom (smart contract).
@@ -51,13 +63,21 @@ pub struct LoomRecord {
 
 /// Metadata tracked per thread beyond its ThreadState.
 #[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]
-#[allow(dead_code)] // Fields accessed via borsh serialization and pattern matching; nonce reserved for future use
+#[allow(dead_code)] // Fields accessed via borsh serialization and pattern matching
 pub struct ThreadMeta {
     pub owner: PublicKey,
     pub version: u64,
+    /// Next knot sequence number: the lowest `before.version` the thread's
+    /// next knot may use.
     pub nonce: u64,
     pub state_hash: Hash,
     pub last_commit_hash: Hash,
+    /// Guardians allowed to recover the thread's owner key.
+    pub guardians: Option<GuardianSet>,
+    /// Keys that must co-sign the thread's knots besides its owner.
+    pub cosigners: Option<CosignerSet>,
+    /// Number of key updates applied to the thread.
+    pub key_epoch: u64,
 }
 
 /// A record of a token transfer (for history queries).
@@ -73,12 +93,149 @@ pub struct TransferRecord {
     pub block_height: Option<u64>,
 }
 
+/// A loom event recorded for history queries.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct EventRecord {
+    /// Position in the event log, starting at 0.
+    pub seq: u64,
+    pub loom_id: LoomId,
+    /// Address whose execution emitted the event.
+    pub sender: Address,
+    pub ty: String,
+    pub attributes: Vec<(String, String)>,
+    pub block_height: u64,
+    pub timestamp: u64,
+}
+
+/// Criteria for event queries. Unset fields match every event.
+#[derive(Debug, Clone, Default)]
+pub struct EventFilter {
+    pub loom_id: Option<LoomId>,
+    pub ty: Option<String>,
+    /// Attribute key, optionally with the value it must have.
+    pub attribute: Option<(String, Option<String>)>,
+    /// Inclusive lower bound on block height.
+    pub from_height: Option<u64>,
+    /// Inclusive upper bound on block height.
+    pub to_height: Option<u64>,
+    /// Only events after this sequence number.
+    pub after_seq: Option<u64>,
+}
+
+impl EventFilter {
+    /// Whether the event satisfies every criterion in the filter.
+    pub fn matches(&self, event: &EventRecord) -> bool {
+        self.loom_id.is_none_or(|id| event.loom_id == id)
+            && self.ty.as_ref().is_none_or(|ty| event.ty == *ty)
+            && self.attribute.as_ref().is_none_or(|(key, value)| {
+                event
+                    .attributes
+                    .iter()
+                    .any(|(k, v)| k == key && value.as_ref().is_none_or(|value| v == value))
+            })
+            && self.from_height.is_none_or(|h| event.block_height >= h)
+            && self.to_height.is_none_or(|h| event.block_height <= h)
+            && self.after_seq.is_none_or(|seq| event.seq > seq)
+    }
+}
+
+/// Indexes over the event log by loom, type, attribute, and block height.
+/// Each index maps to event sequence numbers in ascending order.
+#[derive(Debug, Default)]
+struct EventIndex {
+    events: Vec<EventRecord>,
+    by_loom: HashMap<LoomId, Vec<u64>>,
+    by_type: HashMap<String, Vec<u64>>,
+    by_attribute_key: HashMap<String, Vec<u64>>,
+    by_attribute: HashMap<(String, String), Vec<u64>>,
+    by_height: BTreeMap<u64, Vec<u64>>,
+}
+
+impl EventIndex {
+    fn insert(&mut self, event: EventRecord) {
+        let seq = event.seq;
+        self.by_loom.entry(event.loom_id).or_default().push(seq);
+        self.by_type.entry(event.ty.clone()).or_default().push(seq);
+        for (key, value) in &event.attributes {
+            let keys = self.by_attribute_key.entry(key.clone()).or_default();
+            if keys.last() != Some(&seq) {
+                keys.push(seq);
+            }
+            let pairs = self
+                .by_attribute
+                .entry((key.clone(), value.clone()))
+                .or_default();
+            if pairs.last() != Some(&seq) {
+                pairs.push(seq);
+            }
+        }
+        self.by_height
+            .entry(event.block_height)
+            .or_default()
+            .push(seq);
+        self.events.push(event);
+    }
+
+    /// Scan the most selective index for the filter, then check the
+    /// remaining criteria on each candidate.
+    fn query(&self, filter: &EventFilter, limit: usize, offset: usize) -> Vec<&EventRecord> {
+        const EMPTY: &[u64] = &[];
+        let mut lists: Vec<&[u64]> = Vec::new();
+        if let Some(loom_id) = &filter.loom_id {
+            lists.push(self.by_loom.get(loom_id).map_or(EMPTY, Vec::as_slice));
+        }
+        if let Some(ty) = &filter.ty {
+            lists.push(self.by_type.get(ty).map_or(EMPTY, Vec::as_slice));
+        }
+        match &filter.attribute {
+            Some((key, Some(value))) => lists.push(
+                self.by_attribute
+                    .get(&(key.clone(), value.clone()))
+                    .map_or(EMPTY, Vec::as_slice),
+            ),
+            Some((key, None)) => {
+                lists.push(self.by_attribute_key.get(key).map_or(EMPTY, Vec::as_slice))
+            }
+            None => {}
+        }
+
+        let candidates: Box<dyn Iterator<Item = u64> + '_> =
+            if let Some(list) = lists.into_iter().min_by_key(|l| l.len()) {
+                Box::new(list.iter().copied())
+            } else if filter.from_height.is_some() || filter.to_height.is_some() {
+                let from = filter.from_height.unwrap_or(0);
+                let to = filter.to_height.unwrap_or(u64::MAX);
+                if from > to {
+                    return Vec::new();
+                }
+                Box::new(
+                    self.by_height
+                        .range(from..=to)
+                        .flat_map(|(_, seqs)| seqs.iter().copied()),
+                )
+            } else {
+                let start = filter.after_seq.map_or(0, |seq| seq.saturating_add(1));
+                Box::new(start..self.events.len() as u64)
+            };
+
+        candidates
+            .filter_map(|seq| self.events.get(seq as usize))
+            .filter(|event| filter.matches(event))
+            .skip(offset)
+            .take(limit)
+            .collect()
+    }
+}
+
 /// Maximum number of blocks kept in memory (older blocks available via SQLite).
 const MAX_BLOCK_ARCHIVE: usize = 1000;
 /// Maximum number of transfer records kept in memory.
 const MAX_TRANSFER_LOG: usize = 10_000;
 /// Maximum number of knot IDs tracked for dedup.
 const MAX_KNOWN_KNOT_IDS: usize = 50_000;
+/// Maximum number of headers in a transfer proof, including the block that
+/// holds the transfer.
+const MAX_TRANSFER_PROOF_HEADERS: u64 = 1_000;
 
 /// Node-side state manager that tracks balances, history, and blocks
 /// alongside the WeaveEngine's consensus-level tracking.
@@ -104,6 +261,43 @@ pub struct StateManager {
     state_smt: SparseMerkleTree,
     /// Block production timing (height → microseconds). Persisted alongside blocks.
     block_production_times: HashMap<u64, u64>,
+    /// Loom events emitted by executions, indexed for queries.
+    event_index: EventIndex,
+    /// Spindle bonds and paid subscriptions.
+    spindle_market: SubscriptionRegistry,
+}
+
+/// Read-locked view of the ledger handed to loom executions so contracts can
+/// query balances. The lock is held until the execution drops the view.
+pub struct LedgerBalances(pub tokio::sync::OwnedRwLockReadGuard<StateManager>);
+
+impl BalanceView for LedgerBalances {
+    fn balance(&self, address: &Address, token_id: &TokenId) -> Amount {
+        self.0.get_balance(address, token_id)
+    }
+}
+
+/// Read-locked view of ledger balances as of a past block, for historical
+/// loom queries.
+pub struct HistoricalBalances {
+    pub state: tokio::sync::OwnedRwLockReadGuard<StateManager>,
+    pub height: u64,
+}
+
+impl BalanceView for HistoricalBalances {
+    fn balance(&self, address: &Address, token_id: &TokenId) -> Amount {
+        self.state
+            .balance_at(address, token_id, self.height)
+            .unwrap_or(0)
+    }
+}
+
+/// Current balances, for checking that committers can pay their fees
+/// before a block includes their commitments.
+impl BalanceView for StateManager {
+    fn balance(&self, address: &Address, token_id: &TokenId) -> Amount {
+        self.get_balance(address, token_id)
+    }
 }
 
 impl Default for StateManager {
@@ -129,6 +323,8 @@ impl StateManager {
             loom_registry: HashMap::new(),
             state_smt: SparseMerkleTree::new(),
             block_production_times: HashMap::new(),
+            event_index: EventIndex::default(),
+            spindle_market: SubscriptionRegistry::new(),
         }
     }
 
@@ -177,11 +373,14 @@ impl StateManager {
             loom_registry: HashMap::new(),
             state_smt,
             block_production_times: HashMap::new(),
+            event_index: EventIndex::default(),
+            spindle_market: SubscriptionRegistry::new(),
         }
     }
 
     /// Attach a state store for write-through persistence.
     pub fn set_store(&mut self, store: crate::state_store::StateStore) {
+        store.set_version_height(self.block_archive.last().map_or(0, |b| b.height + 1));
         self.state_store = Some(store);
     }
 
@@ -203,6 +402,9 @@ impl StateManager {
             nonce: 0,
             state_hash,
             last_commit_hash: [0u8; 32],
+            guardians: None,
+            cosigners: None,
+            key_epoch: 0,
         };
         self.thread_states.insert(address, state.clone());
         self.thread_meta.insert(address, meta.clone());
@@ -244,6 +446,9 @@ impl StateManager {
     /// If the pubkey is non-zero and does not derive the claimed address, skip
     /// registration (defense-in-depth against spoofed sender pubkeys).
     pub fn auto_register_with_pubkey(&mut self, address: Address, pubkey: PublicKey) {
+        if self.is_registered(&address) {
+            return;
+        }
         if pubkey != [0u8; 32] && pubkey_to_address(&pubkey) != address {
             tracing::warn!(
                 "auto_register_with_pubkey: pubkey does not derive address {}; skipping",
@@ -251,9 +456,49 @@ impl StateManager {
             );
             return;
         }
-        if !self.is_registered(&address) {
-            self.register_thread(address, pubkey);
+        self.register_thread(address, pubkey);
+    }
+
+    /// The keys currently controlling a registered thread.
+    pub fn thread_keys(&self, address: &Address) -> Option<ThreadKeys> {
+        self.thread_meta.get(address).map(|meta| ThreadKeys {
+            owner: meta.owner,
+            guardians: meta.guardians.clone(),
+            cosigners: meta.cosigners.clone(),
+            epoch: meta.key_epoch,
+        })
+    }
+
+    /// Keys of every thread that has applied at least one key update.
+    pub fn updated_thread_keys(&self) -> impl Iterator<Item = (Address, ThreadKeys)> + '_ {
+        self.thread_meta
+            .iter()
+            .filter(|(_, meta)| meta.key_epoch > 0)
+            .filter_map(|(address, _)| Some((*address, self.thread_keys(address)?)))
+    }
+
+    /// Whether `pubkey` may sign for `address`: the recorded owner key, or
+    /// the key the address derives from while no owner is recorded.
+    pub fn is_thread_owner(&self, address: &Address, pubkey: &PublicKey) -> bool {
+        match self.thread_meta.get(address) {
+            Some(meta) if meta.owner != [0u8; 32] => meta.owner == *pubkey,
+            _ => pubkey_to_address(pubkey) == *address,
+        }
+    }
+
+    /// Check that every participant thread with registered cosigners has
+    /// enough of their signatures on the knot.
+    pub fn check_cosignatures(&self, knot: &Knot) -> Result<(), NornError> {
+        for participant in &knot.before_states {
+            let cosigners = self
+                .thread_meta
+                .get(&participant.thread_id)
+                .and_then(|meta| meta.cosigners.as_ref());
+            if let Some(cosigners) = cosigners {
+                norn_thread::rotation::validate_cosignatures(knot, cosigners)?;
+            }
         }
+        Ok(())
     }
 
     /// Check if a transfer with the given knot_id has already been applied.
@@ -261,6 +506,32 @@ impl StateManager {
         self.known_knot_ids.contains(knot_id)
     }
 
+    /// The lowest `before.version` the thread's next knot may use; 0 for
+    /// unknown threads.
+    pub fn next_knot_sequence(&self, thread_id: &Address) -> u64 {
+        self.thread_meta.get(thread_id).map_or(0, |meta| meta.nonce)
+    }
+
+    /// Move each participant's knot sequence past an applied knot, so the
+    /// knot (or an older one) cannot be applied again.
+    pub fn advance_knot_sequence(&mut self, knot: &Knot) {
+        for participant in &knot.before_states {
+            let Some(meta) = self.thread_meta.get_mut(&participant.thread_id) else {
+                continue;
+            };
+            let next = participant.version.saturating_add(1);
+            if meta.nonce >= next {
+                continue;
+            }
+            meta.nonce = next;
+            if let Some(ref store) = self.state_store {
+                if let Err(e) = store.save_thread_meta(&participant.thread_id, meta) {
+                    tracing::warn!("Failed to persist thread meta: {}", e);
+                }
+            }
+        }
+    }
+
     /// Get the total circulating supply of native tokens.
     #[allow(dead_code)] // Used in tests and as a pub API
     pub fn total_supply(&self) -> Amount {
@@ -282,8 +553,35 @@ impl StateManager {
         self.state_smt.prove(&smt_key)
     }
 
+    /// Generate one compact Merkle proof for several balances.
+    pub fn state_multiproof(
+        &self,
+        balances: &[(Address, TokenId)],
+    ) -> norn_crypto::merkle::MerkleMultiProof {
+        let keys: Vec<Hash> = balances
+            .iter()
+            .map(|(address, token_id)| self.smt_key(address, token_id))
+            .collect();
+        self.state_smt.prove_many(&keys)
+    }
+
+    /// Build an SPV proof for an included transfer: its inclusion proof and
+    /// the headers from its block towards the tip. Returns `None` until the
+    /// transfer is in a block.
+    pub fn transfer_proof(&self, knot_id: &Hash) -> Option<TransferProof> {
+        let height = self.get_transfer_by_knot_id(knot_id)?.block_height?;
+        let block = self.get_block_by_height(height)?;
+        let last = self
+            .latest_block_height()
+            .min(height + MAX_TRANSFER_PROOF_HEADERS - 1);
+        let later = (height + 1..=last)
+            .map_while(|h| self.get_block_by_height(h).map(|b| b.header()))
+            .collect();
+        TransferProof::build(&block, knot_id, later)
+    }
+
     /// Compute the SMT key for a balance entry: BLAKE3(address ++ token_id).
-    fn smt_key(&self, address: &Address, token_id: &TokenId) -> Hash {
+    pub fn smt_key(&self, address: &Address, token_id: &TokenId) -> Hash {
         let mut data = Vec::with_capacity(20 + 32);
         data.extend_from_slice(address);
         data.extend_from_slice(token_id);
@@ -497,6 +795,66 @@ impl StateManager {
         Ok(())
     }
 
+    /// Apply every transfer of a multi-transfer knot, or none of them.
+    /// `legs` pairs each transfer with the ID it is recorded under (see
+    /// `norn_thread::knot::transfer_legs`). Legs may come from several senders
+    /// (e.g. both sides of a swap); each leg's fee is charged to its sender.
+    pub fn apply_multi_transfer(
+        &mut self,
+        legs: &[(Hash, &TransferPayload)],
+        timestamp: u64,
+    ) -> Result<(), NornError> {
+        // Check every sender covers its transfers and fees before applying any.
+        let mut required: BTreeMap<(Address, TokenId), Amount> = BTreeMap::new();
+        for (_, transfer) in legs {
+            if transfer.amount == 0 {
+                return Err(NornError::InvalidAmount);
+            }
+            if !self.thread_states.contains_key(&transfer.from) {
+                return Err(NornError::ThreadNotFound(transfer.from));
+            }
+            if !self.thread_states.contains_key(&transfer.to) {
+                return Err(NornError::ThreadNotFound(transfer.to));
+            }
+            for (token_id, amount) in [
+                (transfer.token_id, transfer.amount),
+                (NATIVE_TOKEN_ID, TRANSFER_FEE),
+            ] {
+                let total = required.entry((transfer.from, token_id)).or_insert(0);
+                *total = total
+                    .checked_add(amount)
+                    .ok_or(NornError::PayloadInconsistent {
+                        reason: "transfer amounts overflow".to_string(),
+                    })?;
+            }
+        }
+        for ((from, token_id), amount) in &required {
+            let sender_state = self
+                .thread_states
+                .get(from)
+                .ok_or(NornError::ThreadNotFound(*from))?;
+            if !sender_state.has_balance(token_id, *amount) {
+                return Err(NornError::InsufficientBalance {
+                    available: sender_state.balance(token_id),
+                    required: *amount,
+                });
+            }
+        }
+
+        for (leg_id, transfer) in legs {
+            self.apply_transfer(
+                transfer.from,
+                transfer.to,
+                transfer.token_id,
+                transfer.amount,
+                *leg_id,
+                transfer.memo.clone(),
+                timestamp,
+            )?;
+        }
+        Ok(())
+    }
+
     /// Apply a transfer received from a peer block or P2P gossip.
     /// Debits the sender (best-effort — warns on insufficient balance) and
     /// credits the recipient so that balances converge across nodes.
@@ -644,11 +1002,12 @@ impl StateManager {
             .unwrap_or(0)
     }
 
-    /// Debit a commitment fee from an address. Logs a warning if the address
-    /// has insufficient balance (does not fail the block).
-    pub fn debit_fee(&mut self, address: Address, fee: Amount) {
+    /// Debit a fee from an address. Logs a warning if the address has
+    /// insufficient balance (does not fail the block). Returns whether the
+    /// fee was debited.
+    pub fn debit_fee(&mut self, address: Address, fee: Amount) -> bool {
         if fee == 0 {
-            return;
+            return true;
         }
         let state = match self.thread_states.get_mut(&address) {
             Some(s) => s,
@@ -657,7 +1016,7 @@ impl StateManager {
                     "fee debit: address {} not registered, skipping",
                     hex::encode(address)
                 );
-                return;
+                return false;
             }
         };
         if !state.has_balance(&NATIVE_TOKEN_ID, fee) {
@@ -666,12 +1025,12 @@ impl StateManager {
                 hex::encode(address),
                 fee
             );
-            return;
+            return false;
         }
         state.debit(&NATIVE_TOKEN_ID, fee);
 
-        // Fees are collected for redistribution to validators at epoch boundaries.
-        // Supply is conserved — do not decrement total_supply_cache.
+        // The caller decides whether the fee is burned or redistributed, so
+        // total_supply_cache is left unchanged here.
 
         // Update state hash in meta.
         if let Some(meta) = self.thread_meta.get_mut(&address) {
@@ -695,6 +1054,47 @@ impl StateManager {
                 }
             }
         }
+        true
+    }
+
+    /// Charge the commitment fees of `block`: each committer pays the
+    /// block's base fee plus its priority fee. Base fees are burned and
+    /// priority fees are paid to the block's proposer. Blocks only include
+    /// commitments whose committers can pay, so none is skipped unless the
+    /// ledger has diverged.
+    pub fn apply_commitment_fees(&mut self, block: &WeaveBlock) {
+        let mut collected: Amount = 0;
+        let mut tips: Amount = 0;
+        for commit in &block.commitments {
+            let fee = block.base_fee.saturating_add(commit.priority_fee);
+            if self.debit_fee(commit.thread_id, fee) {
+                collected = collected.saturating_add(fee);
+                tips = tips.saturating_add(commit.priority_fee);
+            }
+        }
+        // The whole fee leaves circulation; tips re-enter it when credited.
+        self.total_supply_cache = self.total_supply_cache.saturating_sub(collected);
+        if tips == 0 {
+            return;
+        }
+        let proposer = pubkey_to_address(&block.proposer);
+        self.auto_register_if_needed(proposer);
+        if let Err(e) = self.credit(proposer, NATIVE_TOKEN_ID, tips) {
+            tracing::warn!(
+                "failed to pay priority fees to proposer {}: {}",
+                hex::encode(proposer),
+                e
+            );
+            return;
+        }
+        self.log_synthetic_transfer(
+            [0u8; 20],
+            proposer,
+            NATIVE_TOKEN_ID,
+            tips,
+            Some("Block priority fees"),
+            block.timestamp,
+        );
     }
 
     /// Log a synthetic transfer record for operations that don't go through
@@ -781,6 +1181,23 @@ impl StateManager {
             .collect()
     }
 
+    /// Transfers from newest to oldest, starting with the one before the
+    /// transfer with knot ID `before`, or `None` if that transfer is no
+    /// longer in the log.
+    pub fn transfers_before(
+        &self,
+        before: Option<&Hash>,
+    ) -> Option<impl Iterator<Item = &TransferRecord>> {
+        let end = match before {
+            Some(knot_id) => self
+                .transfer_log
+                .iter()
+                .rposition(|r| r.knot_id == *knot_id)?,
+            None => self.transfer_log.len(),
+        };
+        Some(self.transfer_log[..end].iter().rev())
+    }
+
     /// Look up a single transfer by its knot ID.
     pub fn get_transfer_by_knot_id(&self, knot_id: &Hash) -> Option<&TransferRecord> {
         self.transfer_log
@@ -805,16 +1222,45 @@ impl StateManager {
         }
     }
 
+    /// Roll thread metadata forward for stale commitments proven by the
+    /// block's fraud proofs, mirroring the weave's threads tree.
+    pub fn apply_stale_commit_proofs(&mut self, block: &WeaveBlock) {
+        for fp in &block.fraud_proofs {
+            let Some(restore) = norn_weave::fraud::stale_commit_restore(fp) else {
+                continue;
+            };
+            let Some(meta) = self.thread_meta.get_mut(&restore.thread_id) else {
+                continue;
+            };
+            if meta.version >= restore.version {
+                continue;
+            }
+            tracing::warn!(
+                thread = %hex::encode(restore.thread_id),
+                version = restore.version,
+                "stale commitment rolled forward by fraud proof"
+            );
+            meta.version = restore.version;
+            meta.state_hash = restore.state_hash;
+            if let Some(ref store) = self.state_store {
+                if let Err(e) = store.save_thread_meta(&restore.thread_id, meta) {
+                    tracing::warn!("Failed to persist thread meta: {}", e);
+                }
+            }
+        }
+    }
+
     /// Archive a produced block. Evicts oldest blocks from memory when the
     /// archive exceeds `MAX_BLOCK_ARCHIVE` (older blocks remain in SQLite).
     pub fn archive_block(&mut self, block: WeaveBlock, production_us: Option<u64>) {
         let block_height = block.height;
 
-        // Persist block.
+        // Persist block. Later state writes belong to the next block.
         if let Some(ref store) = self.state_store {
             if let Err(e) = store.save_block(&block) {
                 tracing::warn!("Failed to persist block {}: {}", block.height, e);
             }
+            store.set_version_height(block_height + 1);
         }
 
         // Persist block production timing if available.
@@ -910,6 +1356,112 @@ impl StateManager {
         self.block_production_times = timings;
     }
 
+    /// Seed the event log (used during state rebuild). Events must be in
+    /// sequence order.
+    pub fn seed_events(&mut self, events: Vec<EventRecord>) {
+        for event in events {
+            self.event_index.insert(event);
+        }
+    }
+
+    /// Record events emitted by a loom execution and persist them.
+    /// Returns the newly recorded events.
+    pub fn record_loom_events(
+        &mut self,
+        loom_id: LoomId,
+        sender: Address,
+        block_height: u64,
+        timestamp: u64,
+        events: impl IntoIterator<Item = (String, Vec<(String, String)>)>,
+    ) -> &[EventRecord] {
+        let start = self.event_index.events.len();
+        for (ty, attributes) in events {
+            let record = EventRecord {
+                seq: self.event_index.events.len() as u64,
+                loom_id,
+                sender,
+                ty,
+                attributes,
+                block_height,
+                timestamp,
+            };
+            if let Some(ref store) = self.state_store {
+                if let Err(e) = store.save_event(&record) {
+                    tracing::warn!("failed to persist loom event: {}", e);
+                }
+            }
+            self.event_index.insert(record);
+        }
+        &self.event_index.events[start..]
+    }
+
+    /// Query recorded loom events in emission order.
+    pub fn get_events(
+        &self,
+        filter: &EventFilter,
+        limit: usize,
+        offset: usize,
+    ) -> Vec<&EventRecord> {
+        self.event_index.query(filter, limit, offset)
+    }
+
+    fn history_store(&self) -> Result<&crate::state_store::StateStore, StorageError> {
+        self.state_store.as_ref().ok_or(StorageError::ReadError {
+            reason: "historical state requires a persistent state store".to_string(),
+        })
+    }
+
+    /// Lowest block height that historical queries can be answered for.
+    pub fn earliest_history_height(&self) -> Result<u64, StorageError> {
+        self.history_store()?.pruned_height()
+    }
+
+    /// Get a thread's state as it was after the block at `height`.
+    pub fn thread_state_at(
+        &self,
+        address: &Address,
+        height: u64,
+    ) -> Result<Option<ThreadState>, StorageError> {
+        self.history_store()?.load_thread_state_at(address, height)
+    }
+
+    /// Get a thread's metadata as it was after the block at `height`.
+    pub fn thread_meta_at(
+        &self,
+        address: &Address,
+        height: u64,
+    ) -> Result<Option<ThreadMeta>, StorageError> {
+        self.history_store()?.load_thread_meta_at(address, height)
+    }
+
+    /// Get a balance as it was after the block at `height`.
+    pub fn balance_at(
+        &self,
+        address: &Address,
+        token_id: &TokenId,
+        height: u64,
+    ) -> Result<Amount, StorageError> {
+        Ok(self
+            .thread_state_at(address, height)?
+            .map_or(0, |state| state.balance(token_id)))
+    }
+
+    /// Get a loom's state data as it was after the block at `height`.
+    pub fn loom_state_at(
+        &self,
+        loom_id: &LoomId,
+        height: u64,
+    ) -> Result<Option<LoomStateData>, StorageError> {
+        self.history_store()?
+            .load_loom_state_at(loom_id, height)?
+            .map(|bytes| {
+                borsh::from_slice(&bytes).map_err(|e| StorageError::DeserializationError {
+                    reason: e.to_string(),
+                })
+            })
+            .transpose()
+    }
+
     /// Get the latest block height.
     pub fn latest_block_height(&self) -> u64 {
         self.block_archive.last().map(|b| b.height).unwrap_or(0)
@@ -1350,6 +1902,8 @@ impl StateManager {
             current_supply: initial_supply,
             creator,
             created_at: timestamp,
+            metadata: TokenMetadata::default(),
+            metadata_updated_at: 0,
         };
         self.token_registry.insert(token_id, record.clone());
         self.symbol_index.insert(symbol.to_string(), token_id);
@@ -1455,6 +2009,8 @@ impl StateManager {
             current_supply: initial_supply,
             creator,
             created_at: timestamp,
+            metadata: TokenMetadata::default(),
+            metadata_updated_at: 0,
         };
         self.token_registry.insert(token_id, record.clone());
         self.symbol_index.insert(symbol.to_string(), token_id);
@@ -1663,6 +2219,44 @@ impl StateManager {
         Ok(())
     }
 
+    /// Replace a token's metadata. The weave engine checks the update's
+    /// signature and limits; this only requires the authority to be the
+    /// creator and the update to be newer than the last one.
+    pub fn update_token_metadata(&mut self, update: &TokenMetadataUpdate) -> Result<(), NornError> {
+        let record = self
+            .token_registry
+            .get_mut(&update.token_id)
+            .ok_or_else(|| NornError::TokenNotFound(hex::encode(update.token_id)))?;
+        if record.creator != update.authority {
+            return Err(NornError::NotTokenAuthority);
+        }
+        if update.timestamp <= record.metadata_updated_at {
+            return Err(NornError::InvalidTokenDefinition(format!(
+                "metadata update at {} is not after the last update at {}",
+                update.timestamp, record.metadata_updated_at
+            )));
+        }
+        record.metadata = update.metadata.clone();
+        record.metadata_updated_at = update.timestamp;
+
+        if let Some(ref store) = self.state_store {
+            if let Err(e) = store.save_token(&update.token_id, record) {
+                tracing::warn!("Failed to persist token record: {}", e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply a block's token metadata updates. Updates that were already
+    /// applied fail the timestamp check and are skipped.
+    pub fn apply_token_metadata_updates(&mut self, block: &WeaveBlock) {
+        for update in &block.token_metadata_updates {
+            if let Err(e) = self.update_token_metadata(update) {
+                tracing::debug!("token metadata update skipped: {}", e);
+            }
+        }
+    }
+
     /// Get a token record by ID.
     pub fn get_token(&self, token_id: &TokenId) -> Option<&TokenRecord> {
         self.token_registry.get(token_id)
@@ -1807,6 +2401,72 @@ impl StateManager {
         }
     }
 
+    /// Register a loom created by another loom from stored code (no fee).
+    pub fn register_instantiated_loom(
+        &mut self,
+        loom_id: LoomId,
+        name: &str,
+        operator: PublicKey,
+        timestamp: u64,
+    ) {
+        let record = LoomRecord {
+            name: name.to_string(),
+            operator,
+            max_participants: 1000,
+            min_participants: 1,
+            active: true,
+            deployed_at: timestamp,
+        };
+
+        self.loom_registry.insert(loom_id, record.clone());
+
+        // Persist.
+        if let Some(ref store) = self.state_store {
+            if let Err(e) = store.save_loom(&loom_id, &record) {
+                tracing::warn!("failed to persist instantiated loom: {}", e);
+            }
+        }
+    }
+
+    /// Record that a loom's primary operator key was handed over.
+    /// Rotations of co-operator keys leave the record unchanged.
+    pub fn rotate_loom_operator(
+        &mut self,
+        loom_id: &LoomId,
+        old_operator: &PublicKey,
+        new_operator: PublicKey,
+    ) {
+        let Some(record) = self.loom_registry.get_mut(loom_id) else {
+            return;
+        };
+        if record.operator != *old_operator {
+            return;
+        }
+        record.operator = new_operator;
+        if let Some(ref store) = self.state_store {
+            if let Err(e) = store.save_loom(loom_id, record) {
+                tracing::warn!("failed to persist loom operator handover: {}", e);
+            }
+        }
+    }
+
+    /// Persist the operator set of a multi-operator loom. Single-operator
+    /// looms are fully described by their record and are not stored.
+    pub fn persist_loom_operators(
+        &self,
+        loom_id: &LoomId,
+        operators: &norn_types::loom::OperatorSet,
+    ) {
+        if operators.operators.len() <= 1 {
+            return;
+        }
+        if let Some(ref store) = self.state_store {
+            if let Err(e) = store.save_loom_operators(loom_id, operators) {
+                tracing::warn!("failed to persist loom operators: {}", e);
+            }
+        }
+    }
+
     /// Get a loom record by ID.
     pub fn get_loom(&self, loom_id: &LoomId) -> Option<&LoomRecord> {
         self.loom_registry.get(loom_id)
@@ -1826,6 +2486,144 @@ impl StateManager {
     pub fn seed_loom(&mut self, loom_id: LoomId, record: LoomRecord) {
         self.loom_registry.insert(loom_id, record);
     }
+
+    // ── Key Updates ──────────────────────────────────────────────────────
+
+    /// Validate and apply a key rotation, guardian update or recovery knot,
+    /// registering the thread if needed.
+    pub fn apply_key_update(&mut self, knot: &Knot) -> Result<(), NornError> {
+        let invalid = |reason: &str| NornError::InvalidKeyUpdate {
+            reason: reason.to_string(),
+        };
+        let (address, _) = norn_thread::rotation::key_update_target(knot)
+            .ok_or_else(|| invalid("not a key update knot"))?;
+        let mut keys = norn_thread::rotation::current_keys(knot, self.thread_keys(&address))
+            .ok_or_else(|| invalid("claimed key does not control the thread"))?;
+        norn_thread::rotation::validate_key_update(knot, &keys)?;
+        norn_thread::rotation::apply_key_update(&mut keys, knot);
+
+        self.register_thread(address, keys.owner);
+        let meta = self
+            .thread_meta
+            .get_mut(&address)
+            .expect("thread registered above");
+        meta.owner = keys.owner;
+        meta.guardians = keys.guardians;
+        meta.cosigners = keys.cosigners;
+        meta.key_epoch = keys.epoch;
+        if let Some(ref store) = self.state_store {
+            if let Err(e) = store.save_thread_meta(&address, meta) {
+                tracing::warn!("Failed to persist thread meta: {}", e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply a block's key updates. Updates that were already applied when
+    /// their knot was submitted fail the epoch check and are skipped.
+    pub fn apply_key_updates(&mut self, block: &WeaveBlock) {
+        for knot in &block.key_updates {
+            if let Err(e) = self.apply_key_update(knot) {
+                tracing::debug!("key update skipped: {}", e);
+            }
+        }
+    }
+
+    // ── Spindle Market ───────────────────────────────────────────────────
+
+    /// Apply a block's spindle market operations and fraud proofs, then pay
+    /// spindles for fee periods that ended at this height. Operations that
+    /// cannot be applied (unbonded spindle, insufficient balance) are skipped.
+    pub fn apply_spindle_market(&mut self, block: &WeaveBlock) {
+        let mut settlement = Settlement::default();
+        for op in &block.spindle_operations {
+            let signer = pubkey_to_address(op.signer());
+            let balance = self.get_balance(&signer, &NATIVE_TOKEN_ID);
+            match self
+                .spindle_market
+                .apply_operation(op, balance, block.height)
+            {
+                // Apply each operation's debits before checking the next
+                // operation's balance.
+                Ok(s) => self.apply_spindle_settlement(s),
+                Err(e) => tracing::debug!("spindle operation skipped: {}", e),
+            }
+        }
+        for fp in &block.fraud_proofs {
+            settlement.extend(self.spindle_market.on_fraud_proof(fp, block.height));
+        }
+        settlement.extend(self.spindle_market.settle(block.height));
+
+        let changed = !block.spindle_operations.is_empty()
+            || !block.fraud_proofs.is_empty()
+            || !settlement.is_empty();
+        self.apply_spindle_settlement(settlement);
+        if changed {
+            if let Some(ref store) = self.state_store {
+                if let Err(e) = store.save_spindle_market(&self.spindle_market) {
+                    tracing::warn!("Failed to persist spindle market: {}", e);
+                }
+            }
+        }
+    }
+
+    /// Move funds into and out of spindle bonds and prepaid fees.
+    fn apply_spindle_settlement(&mut self, settlement: Settlement) {
+        for (address, amount) in settlement.debits {
+            let Some(state) = self.thread_states.get_mut(&address) else {
+                continue;
+            };
+            if !state.debit(&NATIVE_TOKEN_ID, amount) {
+                tracing::warn!(
+                    "spindle market: {} has insufficient balance for {}",
+                    hex::encode(address),
+                    amount
+                );
+                continue;
+            }
+            // Locked funds leave circulation until they are paid out.
+            self.total_supply_cache = self.total_supply_cache.saturating_sub(amount);
+            if let Some(meta) = self.thread_meta.get_mut(&address) {
+                meta.state_hash = norn_thread::state::compute_state_hash(
+                    self.thread_states.get(&address).unwrap(),
+                );
+            }
+            self.update_smt(&address, &NATIVE_TOKEN_ID);
+            if let Some(ref store) = self.state_store {
+                if let Err(e) =
+                    store.save_thread_state(&address, self.thread_states.get(&address).unwrap())
+                {
+                    tracing::warn!("Failed to persist thread state: {}", e);
+                }
+                if let Some(meta) = self.thread_meta.get(&address) {
+                    if let Err(e) = store.save_thread_meta(&address, meta) {
+                        tracing::warn!("Failed to persist thread meta: {}", e);
+                    }
+                }
+            }
+        }
+        for (address, amount) in settlement.credits {
+            self.auto_register_if_needed(address);
+            if let Err(e) = self.credit(address, NATIVE_TOKEN_ID, amount) {
+                tracing::warn!(
+                    "spindle market: failed to credit {} to {}: {}",
+                    amount,
+                    hex::encode(address),
+                    e
+                );
+            }
+        }
+    }
+
+    /// The spindle subscription registry.
+    pub fn spindle_market(&self) -> &SubscriptionRegistry {
+        &self.spindle_market
+    }
+
+    /// Seed the spindle subscription registry from persisted data.
+    pub fn seed_spindle_market(&mut self, registry: SubscriptionRegistry) {
+        self.spindle_market = registry;
+    }
 }
 
 #[cfg(test)]
@@ -1904,6 +2702,201 @@ mod tests {
         assert_eq!(sm.get_balance(&bob, &NATIVE_TOKEN_ID), transfer_amount);
     }
 
+    #[test]
+    fn test_multi_transfer() {
+        let mut sm = StateManager::new();
+        let alice = test_address(1);
+        let bob = test_address(2);
+        let carol = test_address(3);
+        sm.register_thread(alice, test_pubkey(1));
+        sm.register_thread(bob, test_pubkey(2));
+        sm.register_thread(carol, test_pubkey(3));
+        sm.credit(alice, NATIVE_TOKEN_ID, 2 * ONE_NORN).unwrap();
+
+        let transfer = |to, amount| TransferPayload {
+            token_id: NATIVE_TOKEN_ID,
+            amount,
+            from: alice,
+            to,
+            memo: None,
+        };
+        let to_bob = transfer(bob, ONE_NORN / 2);
+        let to_carol = transfer(carol, ONE_NORN / 4);
+        sm.apply_multi_transfer(&[([1u8; 32], &to_bob), ([2u8; 32], &to_carol)], 1000)
+            .unwrap();
+
+        assert_eq!(
+            sm.get_balance(&alice, &NATIVE_TOKEN_ID),
+            2 * ONE_NORN - ONE_NORN / 2 - ONE_NORN / 4 - 2 * TRANSFER_FEE
+        );
+        assert_eq!(sm.get_balance(&bob, &NATIVE_TOKEN_ID), ONE_NORN / 2);
+        assert_eq!(sm.get_balance(&carol, &NATIVE_TOKEN_ID), ONE_NORN / 4);
+        assert!(sm.has_transfer(&[1u8; 32]));
+        assert!(sm.has_transfer(&[2u8; 32]));
+    }
+
+    #[test]
+    fn test_multi_transfer_is_atomic() {
+        let mut sm = StateManager::new();
+        let alice = test_address(1);
+        let bob = test_address(2);
+        let carol = test_address(3);
+        sm.register_thread(alice, test_pubkey(1));
+        sm.register_thread(bob, test_pubkey(2));
+        sm.register_thread(carol, test_pubkey(3));
+        sm.credit(alice, NATIVE_TOKEN_ID, ONE_NORN).unwrap();
+
+        // Each transfer fits the balance on its own, but not both together.
+        let transfer = |to| TransferPayload {
+            token_id: NATIVE_TOKEN_ID,
+            amount: ONE_NORN / 2,
+            from: alice,
+            to,
+            memo: None,
+        };
+        let to_bob = transfer(bob);
+        let to_carol = transfer(carol);
+        let result = sm.apply_multi_transfer(&[([1u8; 32], &to_bob), ([2u8; 32], &to_carol)], 1000);
+        assert!(matches!(result, Err(NornError::InsufficientBalance { .. })));
+        assert_eq!(sm.get_balance(&alice, &NATIVE_TOKEN_ID), ONE_NORN);
+        assert_eq!(sm.get_balance(&bob, &NATIVE_TOKEN_ID), 0);
+        assert!(!sm.has_transfer(&[1u8; 32]));
+    }
+
+    #[test]
+    fn test_multi_transfer_swap() {
+        let mut sm = StateManager::new();
+        let alice = test_address(1);
+        let bob = test_address(2);
+        let token = [7u8; 32];
+        sm.register_thread(alice, test_pubkey(1));
+        sm.register_thread(bob, test_pubkey(2));
+        sm.credit(alice, NATIVE_TOKEN_ID, ONE_NORN).unwrap();
+        sm.credit(bob, NATIVE_TOKEN_ID, ONE_NORN).unwrap();
+        sm.credit(bob, token, 500).unwrap();
+
+        let a_to_b = TransferPayload {
+            token_id: NATIVE_TOKEN_ID,
+            amount: ONE_NORN / 2,
+            from: alice,
+            to: bob,
+            memo: None,
+        };
+        let b_to_a = TransferPayload {
+            token_id: token,
+            amount: 500,
+            from: bob,
+            to: alice,
+            memo: None,
+        };
+        sm.apply_multi_transfer(&[([1u8; 32], &a_to_b), ([2u8; 32], &b_to_a)], 1000)
+            .unwrap();
+
+        assert_eq!(
+            sm.get_balance(&alice, &NATIVE_TOKEN_ID),
+            ONE_NORN / 2 - TRANSFER_FEE
+        );
+        assert_eq!(sm.get_balance(&alice, &token), 500);
+        assert_eq!(
+            sm.get_balance(&bob, &NATIVE_TOKEN_ID),
+            ONE_NORN + ONE_NORN / 2 - TRANSFER_FEE
+        );
+        assert_eq!(sm.get_balance(&bob, &token), 0);
+    }
+
+    #[test]
+    fn test_knot_sequence_advances() {
+        let mut sm = StateManager::new();
+        let alice = test_address(1);
+        sm.register_thread(alice, test_pubkey(1));
+        assert_eq!(sm.next_knot_sequence(&alice), 0);
+
+        let state = norn_types::thread::ThreadState::new();
+        let knot_at = |version| {
+            norn_thread::knot::KnotBuilder::transfer(1000)
+                .add_before_state(alice, test_pubkey(1), version, &state)
+                .with_payload(norn_types::knot::KnotPayload::Transfer(TransferPayload {
+                    token_id: NATIVE_TOKEN_ID,
+                    amount: 1,
+                    from: alice,
+                    to: test_address(2),
+                    memo: None,
+                }))
+                .build()
+                .unwrap()
+        };
+
+        sm.advance_knot_sequence(&knot_at(4));
+        assert_eq!(sm.next_knot_sequence(&alice), 5);
+        // An older knot never moves the sequence back.
+        sm.advance_knot_sequence(&knot_at(1));
+        assert_eq!(sm.next_knot_sequence(&alice), 5);
+        assert_eq!(sm.next_knot_sequence(&test_address(9)), 0);
+    }
+
+    #[test]
+    fn test_apply_key_update() {
+        use norn_crypto::keys::Keypair;
+        use norn_thread::knot::sign_knot;
+        use norn_thread::rotation::build_key_rotation;
+
+        let mut sm = StateManager::new();
+        let old = Keypair::generate();
+        let new = Keypair::generate();
+        let alice = pubkey_to_address(&old.public_key());
+        sm.register_thread(alice, old.public_key());
+        assert!(sm.is_thread_owner(&alice, &old.public_key()));
+
+        let keys = sm.thread_keys(&alice).unwrap();
+        let mut knot = build_key_rotation(alice, &keys, new.public_key(), 0, 1000);
+        knot.signatures.push(sign_knot(&knot, &old));
+        sm.apply_key_update(&knot).unwrap();
+
+        assert!(sm.is_thread_owner(&alice, &new.public_key()));
+        assert!(!sm.is_thread_owner(&alice, &old.public_key()));
+        assert_eq!(sm.get_thread_meta(&alice).unwrap().key_epoch, 1);
+        assert_eq!(sm.updated_thread_keys().count(), 1);
+        // Replaying the rotation fails the epoch check.
+        assert!(sm.apply_key_update(&knot).is_err());
+    }
+
+    #[test]
+    fn test_cosigned_thread_needs_cosignatures() {
+        use norn_crypto::keys::Keypair;
+        use norn_thread::knot::{add_cosignature, sign_knot};
+        use norn_thread::rotation::{build_key_rotation, build_set_cosigners};
+
+        let mut sm = StateManager::new();
+        let owner = Keypair::generate();
+        let cosigner = Keypair::generate();
+        let alice = pubkey_to_address(&owner.public_key());
+        sm.register_thread(alice, owner.public_key());
+
+        let keys = sm.thread_keys(&alice).unwrap();
+        let set = CosignerSet {
+            cosigners: vec![cosigner.public_key()],
+            threshold: 1,
+        };
+        let mut knot = build_set_cosigners(alice, &keys, set.clone(), 0, 1000);
+        knot.signatures.push(sign_knot(&knot, &owner));
+        sm.apply_key_update(&knot).unwrap();
+        assert_eq!(sm.thread_keys(&alice).unwrap().cosigners, Some(set));
+
+        // Any knot of the thread now needs the cosigner too.
+        let keys = sm.thread_keys(&alice).unwrap();
+        let mut rotation = build_key_rotation(alice, &keys, [9u8; 32], 1, 1001);
+        rotation.signatures.push(sign_knot(&rotation, &owner));
+        assert!(matches!(
+            sm.check_cosignatures(&rotation),
+            Err(NornError::InsufficientCosignatures { .. })
+        ));
+        assert!(sm.apply_key_update(&rotation).is_err());
+
+        add_cosignature(&mut rotation, &cosigner);
+        sm.check_cosignatures(&rotation).unwrap();
+        sm.apply_key_update(&rotation).unwrap();
+    }
+
     #[test]
     fn test_transfer_insufficient_balance() {
         let mut sm = StateManager::new();
@@ -2061,11 +3054,9 @@ mod tests {
         assert!(sm.is_registered(&addr));
     }
 
-    #[test]
-    fn test_archive_and_get_block() {
-        let mut sm = StateManager::new();
-        let block = WeaveBlock {
-            height: 1,
+    fn empty_block(height: u64) -> WeaveBlock {
+        WeaveBlock {
+            height,
             hash: [1u8; 32],
             prev_hash: [0u8; 32],
             commitments_root: [0u8; 32],
@@ -2089,22 +3080,124 @@ mod tests {
             token_mints: vec![],
             token_mints_root: [0u8; 32],
             token_burns: vec![],
+            token_metadata_updates: vec![],
             token_burns_root: [0u8; 32],
+            token_metadata_updates_root: [0u8; 32],
             loom_deploys: vec![],
             loom_deploys_root: [0u8; 32],
+            operator_handovers: vec![],
+            operator_handovers_root: [0u8; 32],
+            key_updates: vec![],
+            key_updates_root: [0u8; 32],
             stake_operations: vec![],
             stake_operations_root: [0u8; 32],
+            slashing_evidence: vec![],
+            slashing_evidence_root: [0u8; 32],
+            spindle_operations: vec![],
+            spindle_operations_root: [0u8; 32],
+            base_fee: 0,
             state_root: [0u8; 32],
+            validator_set_hash: [0u8; 32],
             timestamp: 1000,
             proposer: [0u8; 32],
-            validator_signatures: vec![],
-        };
-        sm.archive_block(block, None);
+            proposer_signature: [0u8; 64],
+            certificate: None,
+        }
+    }
+
+    #[test]
+    fn test_archive_and_get_block() {
+        let mut sm = StateManager::new();
+        sm.archive_block(empty_block(1), None);
         assert!(sm.get_block(1).is_some());
         assert!(sm.get_block(2).is_none());
         assert_eq!(sm.latest_block_height(), 1);
     }
 
+    #[test]
+    fn test_transfer_proof_for_included_transfer() {
+        let mut sm = StateManager::new();
+        let alice = test_address(1);
+        let bob = test_address(2);
+        sm.register_thread(alice, test_pubkey(1));
+        sm.register_thread(bob, test_pubkey(2));
+        sm.credit(alice, NATIVE_TOKEN_ID, 2 * ONE_NORN).unwrap();
+        let knot_id = [7u8; 32];
+        sm.apply_transfer(alice, bob, NATIVE_TOKEN_ID, ONE_NORN, knot_id, None, 1000)
+            .unwrap();
+        assert!(sm.transfer_proof(&knot_id).is_none());
+
+        let transfer = norn_types::weave::BlockTransfer {
+            from: alice,
+            to: bob,
+            token_id: NATIVE_TOKEN_ID,
+            amount: ONE_NORN,
+            memo: None,
+            knot_id,
+            timestamp: 1000,
+        };
+        let bytes = borsh::to_vec(&transfer).unwrap();
+        let mut tree = SparseMerkleTree::new();
+        tree.insert(norn_crypto::hash::blake3_hash(&bytes), bytes);
+        let mut block = empty_block(1);
+        block.transfers = vec![transfer.clone()];
+        block.transfers_root = tree.root();
+        sm.archive_block(block, None);
+        sm.archive_block(empty_block(2), None);
+        sm.archive_block(empty_block(3), None);
+
+        let proof = sm.transfer_proof(&knot_id).unwrap();
+        assert_eq!(proof.transfer, transfer);
+        let heights: Vec<u64> = proof.headers.iter().map(|h| h.height).collect();
+        assert_eq!(heights, vec![1, 2, 3]);
+        norn_crypto::spv::verify_transfer_inclusion(
+            &proof.transfer,
+            &proof.inclusion,
+            &proof.headers[0].transfers_root,
+        )
+        .unwrap();
+        assert!(sm.transfer_proof(&[8u8; 32]).is_none());
+    }
+
+    #[test]
+    fn test_balance_at_follows_archived_blocks() {
+        let mut sm = StateManager::new();
+        sm.set_store(crate::state_store::StateStore::new(std::sync::Arc::new(
+            norn_storage::memory::MemoryStore::new(),
+        )));
+        let addr = test_address(1);
+        sm.register_thread(addr, test_pubkey(1));
+        sm.credit(addr, NATIVE_TOKEN_ID, 100).unwrap();
+        sm.archive_block(empty_block(0), None);
+        sm.credit(addr, NATIVE_TOKEN_ID, 50).unwrap();
+        sm.archive_block(empty_block(1), None);
+        sm.archive_block(empty_block(2), None);
+        // Pending changes are not visible at the latest archived height.
+        sm.credit(addr, NATIVE_TOKEN_ID, 25).unwrap();
+
+        assert_eq!(sm.balance_at(&addr, &NATIVE_TOKEN_ID, 0).unwrap(), 100);
+        assert_eq!(sm.balance_at(&addr, &NATIVE_TOKEN_ID, 1).unwrap(), 150);
+        assert_eq!(sm.balance_at(&addr, &NATIVE_TOKEN_ID, 2).unwrap(), 150);
+        assert_eq!(sm.get_balance(&addr, &NATIVE_TOKEN_ID), 175);
+        assert_eq!(
+            sm.thread_meta_at(&addr, 1).unwrap().unwrap().owner,
+            test_pubkey(1)
+        );
+        assert_eq!(
+            sm.balance_at(&test_address(2), &NATIVE_TOKEN_ID, 2)
+                .unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_balance_at_requires_store() {
+        let sm = StateManager::new();
+        assert!(sm
+            .balance_at(&test_address(1), &NATIVE_TOKEN_ID, 0)
+            .is_err());
+    }
+
     // ─── Name Registry Tests ────────────────────────────────────────────────
 
     #[test]
@@ -2381,6 +3474,48 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_update_token_metadata() {
+        let mut sm = StateManager::new();
+        let creator = test_address(1);
+        sm.register_thread(creator, test_pubkey(1));
+        sm.credit(creator, NATIVE_TOKEN_ID, 100 * ONE_NORN).unwrap();
+        let token_id = sm
+            .create_token("Test", "TST", 8, 0, 0, creator, 100)
+            .unwrap();
+
+        let mut update = TokenMetadataUpdate {
+            token_id,
+            metadata: TokenMetadata {
+                description: Some("A test token.".to_string()),
+                icon_uri: None,
+                project_url: Some("https://example.com".to_string()),
+                tags: vec!["test".to_string()],
+            },
+            authority: test_address(2),
+            authority_pubkey: test_pubkey(2),
+            timestamp: 200,
+            signature: [0u8; 64],
+        };
+        // Only the creator may update.
+        assert!(sm.update_token_metadata(&update).is_err());
+
+        update.authority = creator;
+        update.authority_pubkey = test_pubkey(1);
+        sm.update_token_metadata(&update).unwrap();
+        let record = sm.get_token(&token_id).unwrap();
+        assert_eq!(record.metadata, update.metadata);
+        assert_eq!(record.metadata_updated_at, 200);
+
+        // An update that is not newer is skipped.
+        update.metadata = TokenMetadata::default();
+        assert!(sm.update_token_metadata(&update).is_err());
+        assert_eq!(
+            sm.get_token(&token_id).unwrap().metadata.tags,
+            vec!["test".to_string()]
+        );
+    }
+
     #[test]
     fn test_burn_token() {
         let mut sm = StateManager::new();
@@ -2468,4 +3603,104 @@ mod tests {
         assert_eq!(record.symbol, "PTK");
         assert_eq!(record.current_supply, 500);
     }
+
+    #[test]
+    fn test_event_index_queries() {
+        let mut sm = StateManager::new();
+        let ev = |ty: &str, attrs: &[(&str, &str)]| {
+            (
+                ty.to_string(),
+                attrs
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect::<Vec<_>>(),
+            )
+        };
+        sm.record_loom_events(
+            [1u8; 32],
+            test_address(1),
+            5,
+            100,
+            vec![
+                ev("transfer", &[("to", "bob"), ("amount", "10")]),
+                ev("mint", &[("amount", "10")]),
+            ],
+        );
+        sm.record_loom_events(
+            [2u8; 32],
+            test_address(2),
+            7,
+            200,
+            vec![ev("transfer", &[("to", "carol")])],
+        );
+
+        let seqs = |filter: EventFilter, limit, offset| {
+            sm.get_events(&filter, limit, offset)
+                .iter()
+                .map(|e| e.seq)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(seqs(EventFilter::default(), 10, 0), vec![0, 1, 2]);
+        assert_eq!(seqs(EventFilter::default(), 1, 1), vec![1]);
+        let by_loom = EventFilter {
+            loom_id: Some([1u8; 32]),
+            ..Default::default()
+        };
+        assert_eq!(seqs(by_loom, 10, 0), vec![0, 1]);
+        let by_type = EventFilter {
+            ty: Some("transfer".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(seqs(by_type, 10, 0), vec![0, 2]);
+        let by_key = EventFilter {
+            attribute: Some(("amount".to_string(), None)),
+            ..Default::default()
+        };
+        assert_eq!(seqs(by_key, 10, 0), vec![0, 1]);
+        let by_pair = EventFilter {
+            ty: Some("transfer".to_string()),
+            attribute: Some(("to".to_string(), Some("carol".to_string()))),
+            ..Default::default()
+        };
+        assert_eq!(seqs(by_pair, 10, 0), vec![2]);
+        let by_height = EventFilter {
+            from_height: Some(6),
+            ..Default::default()
+        };
+        assert_eq!(seqs(by_height, 10, 0), vec![2]);
+        let combined = EventFilter {
+            loom_id: Some([1u8; 32]),
+            to_height: Some(4),
+            ..Default::default()
+        };
+        assert!(seqs(combined, 10, 0).is_empty());
+        let after = EventFilter {
+            ty: Some("transfer".to_string()),
+            after_seq: Some(0),
+            ..Default::default()
+        };
+        assert_eq!(seqs(after, 10, 0), vec![2]);
+    }
+
+    #[test]
+    fn test_state_multiproof_covers_requested_balances() {
+        let mut sm = StateManager::new();
+        let (a, b) = (test_address(1), test_address(2));
+        sm.register_thread(a, test_pubkey(1));
+        sm.register_thread(b, test_pubkey(2));
+        sm.credit(a, NATIVE_TOKEN_ID, 1000).unwrap();
+        sm.credit(b, NATIVE_TOKEN_ID, 500).unwrap();
+
+        let requested = [
+            (a, NATIVE_TOKEN_ID),
+            (b, NATIVE_TOKEN_ID),
+            (test_address(3), NATIVE_TOKEN_ID),
+        ];
+        let proof = sm.state_multiproof(&requested);
+        assert_eq!(proof.leaves.len(), 3);
+        let a_key = sm.smt_key(&a, &NATIVE_TOKEN_ID);
+        let (_, a_value) = proof.leaves.iter().find(|(k, _)| *k == a_key).unwrap();
+        assert_eq!(*a_value, 1000u128.to_le_bytes().to_vec());
+        norn_crypto::merkle::SparseMerkleTree::verify_multiproof(&sm.state_root(), &proof).unwrap();
+    }
 }
@@ -1,15 +1,21 @@
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 use borsh::BorshDeserialize;
 
+use norn_spindle::subscription::SubscriptionRegistry;
 use norn_storage::error::StorageError;
-use norn_storage::traits::KvStore;
+use norn_storage::traits::{KvPairs, KvStore, WriteBatch};
+use norn_storage::versioned_store::VersionedStore;
+use norn_types::loom::OperatorSet;
 use norn_types::primitives::{Address, Hash, LoomId, TokenId};
 use norn_types::thread::ThreadState;
 use norn_types::weave::WeaveBlock;
 
-use crate::state_manager::{LoomRecord, NameRecord, ThreadMeta, TokenRecord, TransferRecord};
+use crate::state_manager::{
+    EventRecord, LoomRecord, NameRecord, ThreadMeta, TokenRecord, TransferRecord,
+};
 
 // Key prefixes for each data bucket.
 const THREAD_STATE_PREFIX: &[u8] = b"state:thread:";
@@ -23,21 +29,140 @@ const TOKEN_PREFIX: &[u8] = b"state:token:";
 const LOOM_PREFIX: &[u8] = b"state:loom:";
 const LOOM_BYTECODE_PREFIX: &[u8] = b"state:loom_bytecode:";
 const LOOM_STATE_PREFIX: &[u8] = b"state:loom_state:";
+const CODE_PREFIX: &[u8] = b"state:code:";
+const LOOM_CODE_PREFIX: &[u8] = b"state:loom_code:";
+const LOOM_OPERATORS_PREFIX: &[u8] = b"state:loom_operators:";
 const BLOCK_TIMING_PREFIX: &[u8] = b"state:block_timing:";
+const EVENT_PREFIX: &[u8] = b"state:event:";
+const SPINDLE_MARKET_KEY: &[u8] = b"state:spindle_market";
 const SCHEMA_VERSION_KEY: &[u8] = b"meta:schema_version";
+const PRUNED_HEIGHT_KEY: &[u8] = b"meta:pruned_height";
+/// Prefix shared by every data bucket.
+const SNAPSHOT_PREFIX: &[u8] = b"state:";
 
 /// Current schema version. Bump this whenever a breaking change is made to any
 /// borsh-serialized type persisted through StateStore.
-pub const SCHEMA_VERSION: u32 = 8;
+pub const SCHEMA_VERSION: u32 = 14;
 
 /// Persistent store for StateManager data backed by a KvStore.
+///
+/// Thread states, thread metadata, and loom states are also written to a
+/// height-versioned history so they can be read as of a past block.
 pub struct StateStore {
     store: Arc<dyn KvStore>,
+    history: VersionedStore<Arc<dyn KvStore>>,
+    /// Height of the block that pending writes will land in.
+    version_height: AtomicU64,
 }
 
 impl StateStore {
     pub fn new(store: Arc<dyn KvStore>) -> Self {
-        Self { store }
+        Self {
+            history: VersionedStore::new(store.clone()),
+            store,
+            version_height: AtomicU64::new(0),
+        }
+    }
+
+    // ── History ─────────────────────────────────────────────────────────
+
+    /// Set the block height that subsequent state writes are recorded at.
+    pub fn set_version_height(&self, height: u64) {
+        self.version_height.store(height, Ordering::Relaxed);
+    }
+
+    /// Atomically write a value to the latest view and to the history at the
+    /// pending height.
+    fn put_versioned(&self, key: &[u8], value: &[u8]) -> Result<(), StorageError> {
+        let mut batch = WriteBatch::new();
+        batch.put(key, value);
+        VersionedStore::<Arc<dyn KvStore>>::stage_put_at(
+            &mut batch,
+            key,
+            self.version_height.load(Ordering::Relaxed),
+            value,
+        );
+        self.store.write_batch(batch.into_ops())
+    }
+
+    /// Lowest height whose blocks and state history are still retained.
+    pub fn pruned_height(&self) -> Result<u64, StorageError> {
+        match self.store.get(PRUNED_HEIGHT_KEY)? {
+            Some(bytes) => {
+                u64::try_from_slice(&bytes).map_err(|e| StorageError::DeserializationError {
+                    reason: e.to_string(),
+                })
+            }
+            None => Ok(0),
+        }
+    }
+
+    /// Delete archived blocks, block timings, and state history below
+    /// `height`, keeping what is needed to read state at `height` and above.
+    /// Returns the number of entries removed.
+    pub fn prune_below(&self, height: u64) -> Result<u64, StorageError> {
+        let mut removed = self.history.prune_below(height)?;
+        let mut batch = WriteBatch::new();
+        for prefix in [BLOCK_PREFIX, BLOCK_TIMING_PREFIX] {
+            for (key, _) in self.store.prefix_scan(prefix)? {
+                let Ok(height_bytes) = <[u8; 8]>::try_from(&key[prefix.len()..]) else {
+                    continue;
+                };
+                if u64::from_be_bytes(height_bytes) < height {
+                    batch.delete(&key);
+                    removed += 1;
+                }
+            }
+        }
+        let value = borsh::to_vec(&height).map_err(|e| StorageError::SerializationError {
+            reason: e.to_string(),
+        })?;
+        batch.put(PRUNED_HEIGHT_KEY, &value);
+        self.store.write_batch(batch.into_ops())?;
+        Ok(removed)
+    }
+
+    /// Load a thread state as it was after the block at `height`.
+    pub fn load_thread_state_at(
+        &self,
+        address: &Address,
+        height: u64,
+    ) -> Result<Option<ThreadState>, StorageError> {
+        self.history
+            .get_at(&self.thread_state_key(address), height)?
+            .map(|bytes| {
+                ThreadState::try_from_slice(&bytes).map_err(|e| {
+                    StorageError::DeserializationError {
+                        reason: e.to_string(),
+                    }
+                })
+            })
+            .transpose()
+    }
+
+    /// Load thread metadata as it was after the block at `height`.
+    pub fn load_thread_meta_at(
+        &self,
+        address: &Address,
+        height: u64,
+    ) -> Result<Option<ThreadMeta>, StorageError> {
+        self.history
+            .get_at(&self.thread_meta_key(address), height)?
+            .map(|bytes| {
+                ThreadMeta::try_from_slice(&bytes).map_err(|e| StorageError::DeserializationError {
+                    reason: e.to_string(),
+                })
+            })
+            .transpose()
+    }
+
+    /// Load a loom's raw state as it was after the block at `height`.
+    pub fn load_loom_state_at(
+        &self,
+        loom_id: &LoomId,
+        height: u64,
+    ) -> Result<Option<Vec<u8>>, StorageError> {
+        self.history.get_at(&self.loom_state_key(loom_id), height)
     }
 
     // ── Schema Version ─────────────────────────────────────────────────
@@ -100,7 +225,7 @@ impl StateStore {
         let value = borsh::to_vec(state).map_err(|e| StorageError::SerializationError {
             reason: e.to_string(),
         })?;
-        self.store.put(&key, &value)
+        self.put_versioned(&key, &value)
     }
 
     pub fn load_all_thread_states(&self) -> Result<Vec<(Address, ThreadState)>, StorageError> {
@@ -129,7 +254,7 @@ impl StateStore {
         let value = borsh::to_vec(meta).map_err(|e| StorageError::SerializationError {
             reason: e.to_string(),
         })?;
-        self.store.put(&key, &value)
+        self.put_versioned(&key, &value)
     }
 
     pub fn load_all_thread_metas(&self) -> Result<Vec<(Address, ThreadMeta)>, StorageError> {
@@ -155,14 +280,16 @@ impl StateStore {
         let value = borsh::to_vec(record).map_err(|e| StorageError::SerializationError {
             reason: e.to_string(),
         })?;
-        self.store.put(&key, &value)?;
+        let mut batch = WriteBatch::new();
+        batch.put(&key, &value);
 
         // Update counter
         let count_bytes =
             borsh::to_vec(&(seq + 1)).map_err(|e| StorageError::SerializationError {
                 reason: e.to_string(),
             })?;
-        self.store.put(TRANSFER_COUNT_KEY, &count_bytes)
+        batch.put(TRANSFER_COUNT_KEY, &count_bytes);
+        self.store.write_batch(batch.into_ops())
     }
 
     pub fn load_all_transfers(&self) -> Result<Vec<TransferRecord>, StorageError> {
@@ -339,6 +466,34 @@ impl StateStore {
         Ok(timings)
     }
 
+    // ── Loom events ─────────────────────────────────────────────────────
+
+    pub fn save_event(&self, record: &EventRecord) -> Result<(), StorageError> {
+        let mut key = Vec::with_capacity(EVENT_PREFIX.len() + 8);
+        key.extend_from_slice(EVENT_PREFIX);
+        key.extend_from_slice(&record.seq.to_be_bytes());
+        let value = borsh::to_vec(record).map_err(|e| StorageError::SerializationError {
+            reason: e.to_string(),
+        })?;
+        self.store.put(&key, &value)
+    }
+
+    /// Load all events in sequence order.
+    pub fn load_all_events(&self) -> Result<Vec<EventRecord>, StorageError> {
+        let pairs = self.store.prefix_scan(EVENT_PREFIX)?;
+        let mut results = Vec::with_capacity(pairs.len());
+        for (_, value) in pairs {
+            let record = EventRecord::try_from_slice(&value).map_err(|e| {
+                StorageError::DeserializationError {
+                    reason: e.to_string(),
+                }
+            })?;
+            results.push(record);
+        }
+        results.sort_by_key(|r| r.seq);
+        Ok(results)
+    }
+
     // ── Tokens ──────────────────────────────────────────────────────────
 
     pub fn save_token(&self, token_id: &TokenId, record: &TokenRecord) -> Result<(), StorageError> {
@@ -374,18 +529,9 @@ impl StateStore {
         self.store.put(&key, &value)
     }
 
-    pub fn save_loom_bytecode(
-        &self,
-        loom_id: &LoomId,
-        bytecode: &[u8],
-    ) -> Result<(), StorageError> {
-        let key = self.loom_bytecode_key(loom_id);
-        self.store.put(&key, bytecode)
-    }
-
     pub fn save_loom_state(&self, loom_id: &LoomId, state_data: &[u8]) -> Result<(), StorageError> {
         let key = self.loom_state_key(loom_id);
-        self.store.put(&key, state_data)
+        self.put_versioned(&key, state_data)
     }
 
     #[allow(dead_code)]
@@ -400,6 +546,76 @@ impl StateStore {
         self.store.get(&key)
     }
 
+    pub fn save_code(&self, code_id: &Hash, bytecode: &[u8]) -> Result<(), StorageError> {
+        let key = self.code_key(code_id);
+        self.store.put(&key, bytecode)
+    }
+
+    pub fn load_all_codes(&self) -> Result<Vec<(Hash, Vec<u8>)>, StorageError> {
+        let pairs = self.store.prefix_scan(CODE_PREFIX)?;
+        let mut results = Vec::with_capacity(pairs.len());
+        for (key, value) in pairs {
+            let code_id = self.loom_id_from_key(&key, CODE_PREFIX.len());
+            results.push((code_id, value));
+        }
+        Ok(results)
+    }
+
+    /// Record which stored code a loom runs.
+    pub fn save_loom_code_id(&self, loom_id: &LoomId, code_id: &Hash) -> Result<(), StorageError> {
+        let key = self.loom_code_key(loom_id);
+        self.store.put(&key, code_id)
+    }
+
+    pub fn load_all_loom_code_ids(&self) -> Result<Vec<(LoomId, Hash)>, StorageError> {
+        let pairs = self.store.prefix_scan(LOOM_CODE_PREFIX)?;
+        let mut results = Vec::with_capacity(pairs.len());
+        for (key, value) in pairs {
+            if value.len() != 32 {
+                continue;
+            }
+            let loom_id = self.loom_id_from_key(&key, LOOM_CODE_PREFIX.len());
+            let mut code_id = [0u8; 32];
+            code_id.copy_from_slice(&value);
+            results.push((loom_id, code_id));
+        }
+        Ok(results)
+    }
+
+    /// Record a loom's operator set and signing threshold.
+    pub fn save_loom_operators(
+        &self,
+        loom_id: &LoomId,
+        operators: &OperatorSet,
+    ) -> Result<(), StorageError> {
+        let key = self.loom_operators_key(loom_id);
+        let value = borsh::to_vec(operators).map_err(|e| StorageError::SerializationError {
+            reason: e.to_string(),
+        })?;
+        self.store.put(&key, &value)
+    }
+
+    pub fn load_all_loom_operators(&self) -> Result<Vec<(LoomId, OperatorSet)>, StorageError> {
+        let pairs = self.store.prefix_scan(LOOM_OPERATORS_PREFIX)?;
+        let mut results = Vec::with_capacity(pairs.len());
+        for (key, value) in pairs {
+            let loom_id = self.loom_id_from_key(&key, LOOM_OPERATORS_PREFIX.len());
+            let operators = OperatorSet::try_from_slice(&value).map_err(|e| {
+                StorageError::DeserializationError {
+                    reason: e.to_string(),
+                }
+            })?;
+            results.push((loom_id, operators));
+        }
+        Ok(results)
+    }
+
+    /// Remove a per-loom bytecode copy (legacy layout, before code IDs).
+    pub fn delete_loom_bytecode(&self, loom_id: &LoomId) -> Result<(), StorageError> {
+        let key = self.loom_bytecode_key(loom_id);
+        self.store.delete(&key)
+    }
+
     pub fn load_all_loom_bytecodes(&self) -> Result<Vec<(LoomId, Vec<u8>)>, StorageError> {
         let pairs = self.store.prefix_scan(LOOM_BYTECODE_PREFIX)?;
         let mut results = Vec::with_capacity(pairs.len());
@@ -435,6 +651,86 @@ impl StateStore {
         Ok(results)
     }
 
+    // ── Snapshots ───────────────────────────────────────────────────────
+
+    /// Entries that make up a state snapshot, sorted by key. Archived blocks,
+    /// block timings, and state history are left out.
+    pub fn snapshot_entries(&self) -> Result<KvPairs, StorageError> {
+        let mut entries: Vec<_> = self
+            .store
+            .prefix_scan(SNAPSHOT_PREFIX)?
+            .into_iter()
+            .filter(|(key, _)| in_snapshot(key))
+            .collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(entries)
+    }
+
+    /// Replace the current state with snapshot entries taken before the block
+    /// at `height` was applied, in a single atomic write. History starts at
+    /// the snapshot: versioned entries are recorded as of `height - 1`, and
+    /// nothing older is readable.
+    pub fn import_snapshot(
+        &self,
+        entries: &[(Vec<u8>, Vec<u8>)],
+        height: u64,
+    ) -> Result<(), StorageError> {
+        if let Some((key, _)) = entries.iter().find(|(key, _)| !in_snapshot(key)) {
+            return Err(StorageError::WriteError {
+                reason: format!(
+                    "snapshot entry outside the state keyspace: {}",
+                    String::from_utf8_lossy(key)
+                ),
+            });
+        }
+        let mut batch = WriteBatch::new();
+        for (key, _) in self.store.prefix_scan(SNAPSHOT_PREFIX)? {
+            if in_snapshot(&key) {
+                batch.delete(&key);
+            }
+        }
+        let base_height = height.saturating_sub(1);
+        for (key, value) in entries {
+            batch.put(key, value);
+            if [THREAD_STATE_PREFIX, THREAD_META_PREFIX, LOOM_STATE_PREFIX]
+                .iter()
+                .any(|prefix| key.starts_with(prefix))
+            {
+                VersionedStore::<Arc<dyn KvStore>>::stage_put_at(
+                    &mut batch,
+                    key,
+                    base_height,
+                    value,
+                );
+            }
+        }
+        let value = borsh::to_vec(&base_height).map_err(|e| StorageError::SerializationError {
+            reason: e.to_string(),
+        })?;
+        batch.put(PRUNED_HEIGHT_KEY, &value);
+        self.store.write_batch(batch.into_ops())
+    }
+
+    // ── Spindle Market ──────────────────────────────────────────────────
+
+    pub fn save_spindle_market(&self, registry: &SubscriptionRegistry) -> Result<(), StorageError> {
+        let value = borsh::to_vec(registry).map_err(|e| StorageError::SerializationError {
+            reason: e.to_string(),
+        })?;
+        self.store.put(SPINDLE_MARKET_KEY, &value)
+    }
+
+    pub fn load_spindle_market(&self) -> Result<Option<SubscriptionRegistry>, StorageError> {
+        match self.store.get(SPINDLE_MARKET_KEY)? {
+            Some(bytes) => SubscriptionRegistry::try_from_slice(&bytes)
+                .map(Some)
+                .map_err(|e| StorageError::DeserializationError {
+                    reason: e.to_string(),
+                }),
+            None => Ok(None),
+        }
+    }
+
     // ── Rebuild ─────────────────────────────────────────────────────────
 
     /// Rebuild a full StateManager from persisted data.
@@ -474,6 +770,16 @@ impl StateStore {
             sm.seed_loom(loom_id, record);
         }
 
+        // Seed the spindle market from persisted data.
+        if let Some(market) = self.load_spindle_market()? {
+            sm.seed_spindle_market(market);
+        }
+
+        // Seed the loom event log from persisted data.
+        let events = self.load_all_events()?;
+        let event_count = events.len();
+        sm.seed_events(events);
+
         // Seed block production timings from persisted data.
         let timings = self.load_all_block_timings().unwrap_or_default();
         let timing_count = timings.len();
@@ -496,6 +802,7 @@ impl StateStore {
                 tokens = token_count,
                 looms = loom_count,
                 timings = timing_count,
+                events = event_count,
                 "state rebuilt from disk"
             );
         }
@@ -584,6 +891,27 @@ impl StateStore {
         key
     }
 
+    fn code_key(&self, code_id: &Hash) -> Vec<u8> {
+        let mut key = Vec::with_capacity(CODE_PREFIX.len() + 32);
+        key.extend_from_slice(CODE_PREFIX);
+        key.extend_from_slice(code_id);
+        key
+    }
+
+    fn loom_code_key(&self, loom_id: &LoomId) -> Vec<u8> {
+        let mut key = Vec::with_capacity(LOOM_CODE_PREFIX.len() + 32);
+        key.extend_from_slice(LOOM_CODE_PREFIX);
+        key.extend_from_slice(loom_id);
+        key
+    }
+
+    fn loom_operators_key(&self, loom_id: &LoomId) -> Vec<u8> {
+        let mut key = Vec::with_capacity(LOOM_OPERATORS_PREFIX.len() + 32);
+        key.extend_from_slice(LOOM_OPERATORS_PREFIX);
+        key.extend_from_slice(loom_id);
+        key
+    }
+
     fn loom_state_key(&self, loom_id: &LoomId) -> Vec<u8> {
         let mut key = Vec::with_capacity(LOOM_STATE_PREFIX.len() + 32);
         key.extend_from_slice(LOOM_STATE_PREFIX);
@@ -621,11 +949,19 @@ impl StateStore {
     }
 }
 
+/// Whether a key belongs in a state snapshot.
+fn in_snapshot(key: &[u8]) -> bool {
+    key.starts_with(SNAPSHOT_PREFIX)
+        && !key.starts_with(BLOCK_PREFIX)
+        && !key.starts_with(BLOCK_TIMING_PREFIX)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use norn_storage::memory::MemoryStore;
     use norn_types::primitives::NATIVE_TOKEN_ID;
+    use norn_types::thread::{CosignerSet, GuardianSet};
     use norn_types::weave::WeaveBlock;
 
     fn make_store() -> StateStore {
@@ -650,6 +986,85 @@ mod tests {
         assert_eq!(loaded[0].1, state);
     }
 
+    #[test]
+    fn test_state_history_by_height() {
+        let store = make_store();
+        let addr = test_address(1);
+        let mut state = ThreadState::new();
+        state.credit(NATIVE_TOKEN_ID, 10).unwrap();
+        store.set_version_height(2);
+        store.save_thread_state(&addr, &state).unwrap();
+        store.save_loom_state(&[5u8; 32], b"v2").unwrap();
+
+        let mut later = state.clone();
+        later.credit(NATIVE_TOKEN_ID, 5).unwrap();
+        store.set_version_height(4);
+        store.save_thread_state(&addr, &later).unwrap();
+        store.save_loom_state(&[5u8; 32], b"v4").unwrap();
+
+        assert_eq!(store.load_thread_state_at(&addr, 1).unwrap(), None);
+        assert_eq!(store.load_thread_state_at(&addr, 3).unwrap(), Some(state));
+        assert_eq!(store.load_thread_state_at(&addr, 4).unwrap(), Some(later));
+        assert_eq!(
+            store.load_loom_state_at(&[5u8; 32], 3).unwrap(),
+            Some(b"v2".to_vec())
+        );
+        assert_eq!(store.load_all_thread_states().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_prune_below_keeps_state_at_cutoff() {
+        let store = make_store();
+        let addr = test_address(1);
+        let mut state = ThreadState::new();
+        for height in 1..=4u64 {
+            state.credit(NATIVE_TOKEN_ID, 10).unwrap();
+            store.set_version_height(height);
+            store.save_thread_state(&addr, &state).unwrap();
+            store.save_block_timing(height, 100).unwrap();
+        }
+        assert_eq!(store.pruned_height().unwrap(), 0);
+
+        // One superseded state version and two block timings go.
+        assert_eq!(store.prune_below(3).unwrap(), 3);
+        assert_eq!(store.pruned_height().unwrap(), 3);
+        let at_cutoff = store.load_thread_state_at(&addr, 3).unwrap().unwrap();
+        assert_eq!(at_cutoff.balance(&NATIVE_TOKEN_ID), 30);
+        assert_eq!(
+            store.load_all_block_timings().unwrap().len(),
+            2,
+            "timings at and above the cutoff are kept"
+        );
+    }
+
+    #[test]
+    fn test_snapshot_import_replaces_state() {
+        let source = make_store();
+        let addr = test_address(1);
+        let mut state = ThreadState::new();
+        state.credit(NATIVE_TOKEN_ID, 42).unwrap();
+        source.save_thread_state(&addr, &state).unwrap();
+        source.save_block_timing(3, 100).unwrap();
+        let entries = source.snapshot_entries().unwrap();
+        assert_eq!(entries.len(), 1, "block timings are not snapshotted");
+
+        let target = make_store();
+        target
+            .save_thread_state(&test_address(9), &ThreadState::new())
+            .unwrap();
+        target.import_snapshot(&entries, 8).unwrap();
+        assert_eq!(
+            target.load_all_thread_states().unwrap(),
+            vec![(addr, state)]
+        );
+        assert_eq!(target.pruned_height().unwrap(), 7);
+        let at_base = target.load_thread_state_at(&addr, 7).unwrap().unwrap();
+        assert_eq!(at_base.balance(&NATIVE_TOKEN_ID), 42);
+
+        let foreign = vec![(b"weave:state".to_vec(), vec![1])];
+        assert!(target.import_snapshot(&foreign, 8).is_err());
+    }
+
     #[test]
     fn test_thread_meta_roundtrip() {
         let store = make_store();
@@ -660,6 +1075,15 @@ mod tests {
             nonce: 7,
             state_hash: [11u8; 32],
             last_commit_hash: [22u8; 32],
+            guardians: Some(GuardianSet {
+                guardians: vec![[5u8; 32], [6u8; 32]],
+                threshold: 2,
+            }),
+            cosigners: Some(CosignerSet {
+                cosigners: vec![[7u8; 32]],
+                threshold: 1,
+            }),
+            key_epoch: 2,
         };
 
         store.save_thread_meta(&addr, &meta).unwrap();
@@ -720,6 +1144,60 @@ mod tests {
         assert_eq!(loaded[0].1, names);
     }
 
+    #[test]
+    fn test_code_roundtrip() {
+        let store = make_store();
+        let code_id = [7u8; 32];
+        store.save_code(&code_id, b"wasm").unwrap();
+        store.save_loom_code_id(&[1u8; 32], &code_id).unwrap();
+        store.save_loom_code_id(&[2u8; 32], &code_id).unwrap();
+
+        assert_eq!(
+            store.load_all_codes().unwrap(),
+            vec![(code_id, b"wasm".to_vec())]
+        );
+        let mut looms = store.load_all_loom_code_ids().unwrap();
+        looms.sort();
+        assert_eq!(looms, vec![([1u8; 32], code_id), ([2u8; 32], code_id)]);
+    }
+
+    #[test]
+    fn test_loom_operators_roundtrip() {
+        let store = make_store();
+        let operators = OperatorSet {
+            operators: vec![[1u8; 32], [2u8; 32], [3u8; 32]],
+            threshold: 2,
+        };
+        store.save_loom_operators(&[9u8; 32], &operators).unwrap();
+        assert_eq!(
+            store.load_all_loom_operators().unwrap(),
+            vec![([9u8; 32], operators)]
+        );
+    }
+
+    #[test]
+    fn test_event_roundtrip() {
+        let store = make_store();
+        let events: Vec<EventRecord> = (0..3u64)
+            .rev()
+            .map(|seq| EventRecord {
+                seq,
+                loom_id: [4u8; 32],
+                sender: test_address(1),
+                ty: "transfer".to_string(),
+                attributes: vec![("amount".to_string(), seq.to_string())],
+                block_height: 10 + seq,
+                timestamp: 1000,
+            })
+            .collect();
+        for event in &events {
+            store.save_event(event).unwrap();
+        }
+        let loaded = store.load_all_events().unwrap();
+        assert_eq!(loaded.iter().map(|e| e.seq).collect::<Vec<_>>(), [0, 1, 2]);
+        assert_eq!(loaded[2], events[0]);
+    }
+
     #[test]
     fn test_block_roundtrip() {
         let store = make_store();
@@ -748,15 +1226,28 @@ mod tests {
             token_mints: vec![],
             token_mints_root: [0u8; 32],
             token_burns: vec![],
+            token_metadata_updates: vec![],
             token_burns_root: [0u8; 32],
+            token_metadata_updates_root: [0u8; 32],
             loom_deploys: vec![],
             loom_deploys_root: [0u8; 32],
+            operator_handovers: vec![],
+            operator_handovers_root: [0u8; 32],
+            key_updates: vec![],
+            key_updates_root: [0u8; 32],
             stake_operations: vec![],
             stake_operations_root: [0u8; 32],
+            slashing_evidence: vec![],
+            slashing_evidence_root: [0u8; 32],
+            spindle_operations: vec![],
+            spindle_operations_root: [0u8; 32],
+            base_fee: 0,
             state_root: [0u8; 32],
+            validator_set_hash: [0u8; 32],
             timestamp: 1000,
             proposer: [0u8; 32],
-            validator_signatures: vec![],
+            proposer_signature: [0u8; 64],
+            certificate: None,
         };
 
         store.save_block(&block).unwrap();
@@ -9,23 +9,44 @@ pub enum WalletCommand {
         #[arg(long)]
         name: String,
         /// Optional BIP-39 passphrase (NOT the encryption password)
-        #[arg(long)]
+        #[arg(long, conflicts_with = "ask_passphrase")]
         passphrase: Option<String>,
+        /// Prompt for a BIP-39 passphrase instead of passing it on the command line
+        #[arg(long)]
+        ask_passphrase: bool,
+        /// Account derivation path (defaults to m/44'/1313821262'/0'/0')
+        #[arg(long)]
+        derivation_path: Option<String>,
     },
-    /// Import a wallet from mnemonic or private key
+    /// Import a wallet from mnemonic or private key, or watch a thread without its key
     Import {
         /// Import from mnemonic phrase
-        #[arg(long, conflicts_with = "private_key")]
+        #[arg(long, conflicts_with_all = ["private_key", "watch_only", "ledger"])]
         mnemonic: bool,
         /// Import from hex-encoded private key (32 bytes)
-        #[arg(long, conflicts_with = "mnemonic")]
+        #[arg(long, conflicts_with_all = ["mnemonic", "watch_only", "ledger"])]
         private_key: Option<String>,
+        /// Watch a thread by public key or thread ID; the wallet can build knots but not sign
+        #[arg(long, value_name = "PUBKEY_OR_ADDRESS", conflicts_with = "ledger")]
+        watch_only: Option<String>,
+        /// Use a key held on a connected Ledger; knots are signed on the device
+        #[arg(long)]
+        ledger: bool,
+        /// Ledger account (last derivation path component)
+        #[arg(long, default_value = "0", requires = "ledger")]
+        account: u32,
         /// Wallet name
         #[arg(long, default_value = "imported")]
         name: String,
         /// Optional BIP-39 passphrase for mnemonic import
-        #[arg(long)]
+        #[arg(long, requires = "mnemonic", conflicts_with = "ask_passphrase")]
         passphrase: Option<String>,
+        /// Prompt for the BIP-39 passphrase of the imported mnemonic
+        #[arg(long, requires = "mnemonic")]
+        ask_passphrase: bool,
+        /// Account derivation path for mnemonic import (defaults to m/44'/1313821262'/0'/0')
+        #[arg(long, requires = "mnemonic")]
+        derivation_path: Option<String>,
     },
     /// Export wallet secrets (mnemonic or private key)
     Export {
@@ -66,10 +87,13 @@ pub enum WalletCommand {
         /// Output as JSON
         #[arg(long)]
         json: bool,
+        /// Show the address on the wallet's Ledger to check it
+        #[arg(long)]
+        verify: bool,
     },
     /// Query balance for an address
     Balance {
-        /// Address to query (defaults to active wallet)
+        /// Address, contact or name to query (defaults to active wallet)
         #[arg(long)]
         address: Option<String>,
         /// Token ID (defaults to native NORN)
@@ -85,7 +109,7 @@ pub enum WalletCommand {
     /// Transfer tokens to another address
     #[command(alias = "send")]
     Transfer {
-        /// Recipient address
+        /// Recipient address, contact or name
         #[arg(long)]
         to: String,
         /// Amount to transfer (human-readable, e.g. "10.5")
@@ -97,6 +121,162 @@ pub enum WalletCommand {
         /// Optional memo
         #[arg(long)]
         memo: Option<String>,
+        /// Label for the local transaction journal (not sent to the network)
+        #[arg(long)]
+        label: Option<String>,
+        /// Write the knot to a file for other devices to sign instead of submitting it
+        #[arg(long, value_name = "FILE")]
+        out: Option<String>,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        yes: bool,
+        /// Override RPC URL for this command
+        #[arg(long)]
+        rpc_url: Option<String>,
+    },
+    /// Send payouts listed in a CSV file, in as few knots as possible
+    BatchTransfer {
+        /// CSV file of `<ADDRESS_OR_NAME>,<AMOUNT>[,<TOKEN>]` rows
+        #[arg(long, value_name = "FILE")]
+        csv: String,
+        /// Token for rows that name none (defaults to native NORN)
+        #[arg(long)]
+        token: Option<String>,
+        /// Optional memo, attached to every transfer
+        #[arg(long)]
+        memo: Option<String>,
+        /// Label for the local transaction journal (not sent to the network)
+        #[arg(long)]
+        label: Option<String>,
+        /// Write per-row results to this CSV file
+        #[arg(long, value_name = "FILE")]
+        report: Option<String>,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        yes: bool,
+        /// Override RPC URL for this command
+        #[arg(long)]
+        rpc_url: Option<String>,
+    },
+    /// Send tokens to several recipients in one atomic knot
+    MultiTransfer {
+        /// Recipient and amount as `<ADDRESS_OR_NAME>:<AMOUNT>`; repeat for each recipient
+        #[arg(long = "to", required = true)]
+        recipients: Vec<String>,
+        /// Token ID (defaults to native NORN)
+        #[arg(long)]
+        token: Option<String>,
+        /// Optional memo, attached to every transfer
+        #[arg(long)]
+        memo: Option<String>,
+        /// Label for the local transaction journal (not sent to the network)
+        #[arg(long)]
+        label: Option<String>,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        yes: bool,
+        /// Override RPC URL for this command
+        #[arg(long)]
+        rpc_url: Option<String>,
+    },
+    /// Propose an atomic swap with another thread; it applies once both sides sign
+    ProposeSwap {
+        /// Counterparty address, contact or name
+        #[arg(long)]
+        with: String,
+        /// Amount you give (e.g. "10.5")
+        #[arg(long)]
+        give: String,
+        /// Token you give (defaults to native NORN)
+        #[arg(long)]
+        give_token: Option<String>,
+        /// Amount you receive
+        #[arg(long)]
+        receive: String,
+        /// Token you receive (defaults to native NORN)
+        #[arg(long)]
+        receive_token: Option<String>,
+        /// Seconds the counterparty has to sign (default 3600)
+        #[arg(long)]
+        expiry: Option<u64>,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        yes: bool,
+        /// Override RPC URL for this command
+        #[arg(long)]
+        rpc_url: Option<String>,
+    },
+    /// Sign a multi-party knot proposal, or a knot file from another device
+    SignKnot {
+        /// Knot ID (hex) of a proposal waiting at the node
+        #[arg(required_unless_present = "file", conflicts_with = "file")]
+        knot_id: Option<String>,
+        /// Sign a knot file instead, offline
+        #[arg(long, value_name = "FILE")]
+        file: Option<String>,
+        /// Where to write the signed knot file (defaults to overwriting --file)
+        #[arg(long, value_name = "FILE", requires = "file")]
+        out: Option<String>,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        yes: bool,
+        /// Override RPC URL for this command
+        #[arg(long)]
+        rpc_url: Option<String>,
+    },
+    /// Submit a signed knot file
+    SubmitKnot {
+        /// Path to the knot file
+        file: String,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        yes: bool,
+        /// Override RPC URL for this command
+        #[arg(long)]
+        rpc_url: Option<String>,
+    },
+    /// Sign a transaction file (knot or commitment), e.g. on an air-gapped machine
+    SignTx {
+        /// Path to the transaction file, or to scanned QR chunks one per line
+        file: String,
+        /// Never contact a node: write the signed file instead of broadcasting it
+        #[arg(long)]
+        offline: bool,
+        /// Write the signed transaction here instead of back to FILE
+        #[arg(long, value_name = "FILE")]
+        out: Option<String>,
+        /// Also show the signed transaction as QR codes
+        #[arg(long, requires = "offline")]
+        qr: bool,
+        /// Skip confirmation prompts
+        #[arg(long)]
+        yes: bool,
+        /// Override RPC URL for this command
+        #[arg(long)]
+        rpc_url: Option<String>,
+    },
+    /// Submit a signed transaction file (knot or commitment)
+    Broadcast {
+        /// Path to the transaction file, or to scanned QR chunks one per line
+        file: String,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        yes: bool,
+        /// Override RPC URL for this command
+        #[arg(long)]
+        rpc_url: Option<String>,
+    },
+    /// Require cosigner signatures on every knot of your thread
+    SetCosigners {
+        /// Cosigner public key (hex); repeat for each cosigner. Omit all to remove cosigners
+        #[arg(long = "cosigner")]
+        cosigners: Vec<String>,
+        /// Number of cosigners that must sign each knot
+        #[arg(long, default_value_t = 0)]
+        threshold: u8,
+        /// Write the knot to a file for other devices to sign instead of submitting it
+        #[arg(long, value_name = "FILE")]
+        out: Option<String>,
         /// Skip confirmation prompt
         #[arg(long)]
         yes: bool,
@@ -118,6 +298,12 @@ pub enum WalletCommand {
         /// Wallet name (defaults to active wallet)
         #[arg(long)]
         name: Option<String>,
+        /// Priority fee paid to the block proposer (human-readable, e.g. "0.01")
+        #[arg(long)]
+        tip: Option<String>,
+        /// Write the commitment to a file instead of submitting it, signed if the wallet holds the key
+        #[arg(long, value_name = "FILE")]
+        out: Option<String>,
         /// Override RPC URL for this command
         #[arg(long)]
         rpc_url: Option<String>,
@@ -142,13 +328,30 @@ pub enum WalletCommand {
         /// Output as JSON
         #[arg(long)]
         json: bool,
+        /// Show the local transaction journal, reconciled with the node
+        #[arg(long)]
+        local: bool,
+        /// Export the whole local journal to a CSV file
+        #[arg(long, value_name = "FILE", requires = "local")]
+        csv: Option<String>,
         /// Override RPC URL for this command
         #[arg(long)]
         rpc_url: Option<String>,
     },
+    /// Label a journalled transaction, or clear its label
+    Label {
+        /// Knot ID, or a unique prefix of it
+        knot_id: String,
+        /// Label text
+        #[arg(required_unless_present = "clear")]
+        label: Option<String>,
+        /// Remove the label
+        #[arg(long, conflicts_with = "label")]
+        clear: bool,
+    },
     /// Request testnet tokens from faucet
     Faucet {
-        /// Address to fund (defaults to active wallet)
+        /// Address, contact or name to fund (defaults to active wallet)
         #[arg(long)]
         address: Option<String>,
         /// Override RPC URL for this command
@@ -183,9 +386,23 @@ pub enum WalletCommand {
         /// Set network: "dev", "testnet", "mainnet"
         #[arg(long)]
         network: Option<String>,
+        /// Unit to show NORN amounts in: "norn", "mnorn", "nit"
+        #[arg(long)]
+        denomination: Option<String>,
+        /// Endpoint returning the NORN price as JSON, or "none" to turn prices off
+        #[arg(long)]
+        price_url: Option<String>,
+        /// JSON pointer to the price in the endpoint's response (default "/price")
+        #[arg(long)]
+        price_pointer: Option<String>,
+        /// Currency the price is quoted in (default "USD")
+        #[arg(long)]
+        price_currency: Option<String>,
         /// Show current config as JSON
         #[arg(long)]
         json: bool,
+        #[command(subcommand)]
+        action: Option<ConfigAction>,
     },
     /// Register a name for the active wallet (costs 1 NORN)
     RegisterName {
@@ -216,7 +433,7 @@ pub enum WalletCommand {
         /// Name to transfer
         #[arg(long)]
         name: String,
-        /// Recipient address (hex)
+        /// Recipient address, contact or name
         #[arg(long)]
         to: String,
         /// Skip confirmation prompt
@@ -319,6 +536,9 @@ pub enum WalletCommand {
         /// Wallet name (defaults to active wallet)
         #[arg(long)]
         name: Option<String>,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
     },
     /// Verify a signed message
     VerifyMessage {
@@ -331,6 +551,36 @@ pub enum WalletCommand {
         /// Hex-encoded public key (32 bytes)
         #[arg(long)]
         pubkey: String,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Sign typed structured data (permits, off-chain votes, approvals)
+    SignTyped {
+        /// JSON file with the domain, type and message to sign
+        #[arg(value_name = "FILE")]
+        file: String,
+        /// Wallet name (defaults to active wallet)
+        #[arg(long)]
+        name: Option<String>,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Verify a typed structured-data signature
+    VerifyTyped {
+        /// JSON file with the domain, type and message that was signed
+        #[arg(value_name = "FILE")]
+        file: String,
+        /// Hex-encoded signature (64 bytes)
+        #[arg(long)]
+        signature: String,
+        /// Hex-encoded public key (32 bytes)
+        #[arg(long)]
+        pubkey: String,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
     },
     /// Rename a wallet
     Rename {
@@ -347,6 +597,34 @@ pub enum WalletCommand {
         #[arg(long)]
         name: Option<String>,
     },
+    /// Re-encrypt a wallet with a fresh salt and stronger KDF parameters
+    Rekey {
+        /// Wallet name (defaults to active wallet)
+        #[arg(long)]
+        name: Option<String>,
+        /// Argon2id memory cost in MiB (default 64)
+        #[arg(long)]
+        memory_mib: Option<u32>,
+        /// Argon2id passes (default 3)
+        #[arg(long)]
+        iterations: Option<u32>,
+        /// Argon2id lanes (default 4)
+        #[arg(long)]
+        parallelism: Option<u32>,
+        /// Also set a new password
+        #[arg(long)]
+        new_password: bool,
+    },
+    /// Manage the address book of named recipients
+    Contacts {
+        #[command(subcommand)]
+        command: ContactsCommand,
+    },
+    /// Manage the accounts derived from a mnemonic wallet
+    Accounts {
+        #[command(subcommand)]
+        command: AccountsCommand,
+    },
     /// Create a new fungible token (costs 10 NORN)
     CreateToken {
         /// Token name (e.g., "My Token")
@@ -376,7 +654,7 @@ pub enum WalletCommand {
         /// Token symbol or hex ID
         #[arg(long)]
         token: String,
-        /// Recipient address
+        /// Recipient address, contact or name
         #[arg(long)]
         to: String,
         /// Amount to mint
@@ -404,6 +682,33 @@ pub enum WalletCommand {
         #[arg(long)]
         rpc_url: Option<String>,
     },
+    /// Set a token's description, icon, website and tags (creator only)
+    SetTokenMetadata {
+        /// Token symbol or hex ID
+        #[arg(long)]
+        token: String,
+        /// Short description (empty to remove)
+        #[arg(long)]
+        description: Option<String>,
+        /// Icon URI: https://, ipfs:// or ar:// (empty to remove)
+        #[arg(long)]
+        icon_uri: Option<String>,
+        /// Project website (empty to remove)
+        #[arg(long)]
+        project_url: Option<String>,
+        /// Comma-separated lowercase tags (empty to remove all)
+        #[arg(long)]
+        tags: Option<String>,
+        /// Start from empty metadata instead of the current one
+        #[arg(long)]
+        clear: bool,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        yes: bool,
+        /// Override RPC URL for this command
+        #[arg(long)]
+        rpc_url: Option<String>,
+    },
     /// Get information about a token
     TokenInfo {
         /// Token symbol or hex ID
@@ -441,6 +746,12 @@ pub enum WalletCommand {
         /// Loom name
         #[arg(long)]
         name: String,
+        /// Additional operator public key (hex); repeat for each co-operator
+        #[arg(long = "co-operator")]
+        co_operators: Vec<String>,
+        /// Operator signatures required on state anchors (default: 1)
+        #[arg(long)]
+        threshold: Option<u16>,
         /// Skip confirmation prompt
         #[arg(long)]
         yes: bool,
@@ -483,6 +794,33 @@ pub enum WalletCommand {
         #[arg(long)]
         rpc_url: Option<String>,
     },
+    /// Export a loom's state to a snapshot file
+    ExportLoomState {
+        /// Loom ID (hex)
+        #[arg(long)]
+        loom_id: String,
+        /// Path to write the snapshot to
+        #[arg(long)]
+        output: String,
+        /// Override RPC URL for this command
+        #[arg(long)]
+        rpc_url: Option<String>,
+    },
+    /// Import a loom's state from a snapshot file (operator only)
+    ImportLoomState {
+        /// Loom ID (hex)
+        #[arg(long)]
+        loom_id: String,
+        /// Path to the snapshot file
+        #[arg(long)]
+        input: String,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        yes: bool,
+        /// Override RPC URL for this command
+        #[arg(long)]
+        rpc_url: Option<String>,
+    },
     /// Execute a loom contract
     ExecuteLoom {
         /// Loom ID (hex)
@@ -528,6 +866,36 @@ pub enum WalletCommand {
         #[arg(long)]
         rpc_url: Option<String>,
     },
+    /// Set a loom participant's role (operator only)
+    SetParticipantRole {
+        /// Loom ID (hex)
+        #[arg(long)]
+        loom_id: String,
+        /// Participant address, contact or name
+        #[arg(long)]
+        participant: String,
+        /// Role: operator, submitter, or observer
+        #[arg(long)]
+        role: String,
+        /// Override RPC URL for this command
+        #[arg(long)]
+        rpc_url: Option<String>,
+    },
+    /// Hand a loom's operator key over to another wallet
+    RotateLoomOperator {
+        /// Loom ID (hex)
+        #[arg(long)]
+        loom_id: String,
+        /// Wallet holding the new operator key
+        #[arg(long)]
+        new_wallet: String,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        yes: bool,
+        /// Override RPC URL for this command
+        #[arg(long)]
+        rpc_url: Option<String>,
+    },
     /// Scaffold a new loom smart contract project
     NewLoom {
         /// Project name (lowercase alphanumeric + hyphens)
@@ -555,11 +923,69 @@ pub enum WalletCommand {
         #[arg(long)]
         rpc_url: Option<String>,
     },
+    /// Delegate tokens to a validator
+    Delegate {
+        /// Validator public key (hex)
+        validator: String,
+        /// Amount to delegate (in base units)
+        amount: String,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        yes: bool,
+        /// Override RPC URL for this command
+        #[arg(long)]
+        rpc_url: Option<String>,
+    },
+    /// Withdraw delegated tokens (subject to bonding period)
+    Undelegate {
+        /// Validator public key (hex)
+        validator: String,
+        /// Amount to undelegate (in base units)
+        amount: String,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        yes: bool,
+        /// Override RPC URL for this command
+        #[arg(long)]
+        rpc_url: Option<String>,
+    },
+    /// List the active wallet's delegations
+    Delegations {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+        /// Override RPC URL for this command
+        #[arg(long)]
+        rpc_url: Option<String>,
+    },
+    /// Set the commission taken from delegators' rewards
+    SetCommission {
+        /// Commission in basis points (e.g. 500 = 5%)
+        commission_bps: u16,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        yes: bool,
+        /// Override RPC URL for this command
+        #[arg(long)]
+        rpc_url: Option<String>,
+    },
+    /// Release the active wallet's validator from jail after downtime
+    Unjail {
+        /// Skip confirmation prompt
+        #[arg(long)]
+        yes: bool,
+        /// Override RPC URL for this command
+        #[arg(long)]
+        rpc_url: Option<String>,
+    },
     /// Get staking information
     StakingInfo {
         /// Filter by validator public key (hex)
         #[arg(long)]
         validator: Option<String>,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
         /// Override RPC URL for this command
         #[arg(long)]
         rpc_url: Option<String>,
@@ -573,4 +999,171 @@ pub enum WalletCommand {
         #[arg(long)]
         rpc_url: Option<String>,
     },
+    /// List bonded spindles and the spindles watching the active wallet
+    Spindles {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+        /// Override RPC URL for this command
+        #[arg(long)]
+        rpc_url: Option<String>,
+    },
+    /// Post or top up a spindle bond and set the monitoring fee
+    SpindleBond {
+        /// Amount to add to the bond (in base units)
+        amount: String,
+        /// Fee charged per fee period (in base units)
+        #[arg(long)]
+        fee: String,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        yes: bool,
+        /// Override RPC URL for this command
+        #[arg(long)]
+        rpc_url: Option<String>,
+    },
+    /// Withdraw part of a spindle bond
+    SpindleUnbond {
+        /// Amount to withdraw (in base units)
+        amount: String,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        yes: bool,
+        /// Override RPC URL for this command
+        #[arg(long)]
+        rpc_url: Option<String>,
+    },
+    /// Pay a spindle to watch the active wallet's thread
+    SpindleSubscribe {
+        /// Spindle public key (hex)
+        spindle: String,
+        /// Number of fee periods to prepay
+        periods: u64,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        yes: bool,
+        /// Override RPC URL for this command
+        #[arg(long)]
+        rpc_url: Option<String>,
+    },
+    /// Cancel a spindle subscription and refund unused periods
+    SpindleUnsubscribe {
+        /// Spindle public key (hex)
+        spindle: String,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        yes: bool,
+        /// Override RPC URL for this command
+        #[arg(long)]
+        rpc_url: Option<String>,
+    },
+}
+
+/// Network profile subcommands of `config`.
+#[derive(Subcommand)]
+pub enum ConfigAction {
+    /// Select the network profile to use, or "none" for the plain RPC URL
+    UseProfile {
+        /// Profile name, e.g. "mainnet", "testnet", "localnet"
+        name: String,
+    },
+    /// List network profiles
+    Profiles {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Add a profile, or replace one of the same name
+    AddProfile {
+        /// Profile name (lowercase letters, digits, '-')
+        name: String,
+        /// RPC URL of the profile's node
+        #[arg(long)]
+        rpc_url: String,
+        /// Network the node must be on: "dev", "testnet", "mainnet"
+        #[arg(long)]
+        network: String,
+        /// Keep this profile's wallets in their own directory
+        #[arg(long)]
+        keystore_dir: Option<String>,
+    },
+    /// Remove a profile you added
+    RemoveProfile {
+        /// Profile name
+        name: String,
+    },
+}
+
+/// Address book subcommands.
+#[derive(Subcommand)]
+pub enum ContactsCommand {
+    /// Save an address under a name
+    Add {
+        /// Contact name (letters, digits, '-', '_', '.'; up to 32 chars)
+        name: String,
+        /// Address, or a registered name to pin its current owner
+        address: String,
+        /// Note to show in the contact list
+        #[arg(long)]
+        note: Option<String>,
+        /// Override RPC URL for this command
+        #[arg(long)]
+        rpc_url: Option<String>,
+    },
+    /// List saved contacts
+    List {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Remove a contact
+    Remove {
+        /// Contact name
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum AccountsCommand {
+    /// Derive another account from the wallet's mnemonic
+    Add {
+        /// Account index (defaults to the next unused one)
+        #[arg(long)]
+        index: Option<u32>,
+        /// Label to refer to the account by
+        #[arg(long)]
+        label: Option<String>,
+        /// Wallet name (defaults to active wallet)
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// List the wallet's accounts
+    List {
+        /// Wallet name (defaults to active wallet)
+        #[arg(long)]
+        name: Option<String>,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Sign with another account of the wallet
+    Use {
+        /// Account index or label
+        account: String,
+        /// Wallet name (defaults to active wallet)
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// Find accounts with on-chain activity and add them to the wallet
+    Discover {
+        /// Stop after this many consecutive unused accounts
+        #[arg(long, default_value = "20")]
+        gap_limit: u32,
+        /// Wallet name (defaults to active wallet)
+        #[arg(long)]
+        name: Option<String>,
+        /// Override RPC URL for this command
+        #[arg(long)]
+        rpc_url: Option<String>,
+    },
 }
@@ -0,0 +1,228 @@
+use norn_crypto::hd::format_derivation_path;
+use norn_types::primitives::NATIVE_TOKEN_ID;
+
+use crate::wallet::config::WalletConfig;
+use crate::wallet::error::WalletError;
+use crate::wallet::format::{print_success, style_bold, style_dim};
+use crate::wallet::keystore::{Keystore, WalletAccount};
+use crate::wallet::prompt::prompt_password;
+use crate::wallet::rpc_client::RpcClient;
+use crate::wallet::signer::unlock_secret;
+use crate::wallet::ui::{cell, cell_dim, cell_green, data_table, info_table, print_table};
+
+fn load_wallet(config: &WalletConfig, name: Option<&str>) -> Result<Keystore, WalletError> {
+    let wallet_name = match name {
+        Some(n) => n,
+        None => config.active_wallet_name()?,
+    };
+    Keystore::load(wallet_name)
+}
+
+pub fn add(name: Option<&str>, index: Option<u32>, label: Option<&str>) -> Result<(), WalletError> {
+    let config = WalletConfig::load()?;
+    let mut ks = load_wallet(&config, name)?;
+
+    let index = match index {
+        Some(i) => i,
+        None => ks
+            .accounts()
+            .iter()
+            .map(|a| a.index)
+            .max()
+            .map_or(0, |i| i + 1),
+    };
+
+    let password = prompt_password("Enter password")?;
+    let seed = unlock_secret(&mut ks, &password, Keystore::decrypt_hd_seed)?;
+    let mut account = ks.derive_account(&seed, index)?;
+    account.label = label.map(str::to_string);
+    ks.add_account(account.clone())?;
+    ks.save()?;
+
+    println!();
+    print_success(&format!("Account {} added to wallet '{}'", index, ks.name));
+    let mut table = info_table();
+    table.add_row(vec![cell("Address"), cell(&account.address)]);
+    table.add_row(vec![
+        cell("Path"),
+        cell(format!(
+            "{}/{}'",
+            format_derivation_path(&ks.account_path()?),
+            index
+        )),
+    ]);
+    if let Some(label) = &account.label {
+        table.add_row(vec![cell("Label"), cell(label)]);
+    }
+    print_table(&table);
+    println!(
+        "  {}",
+        style_dim().apply_to(format!(
+            "Switch to it with: norn wallet accounts use {}",
+            index
+        ))
+    );
+    println!();
+
+    Ok(())
+}
+
+pub fn list(name: Option<&str>, json: bool) -> Result<(), WalletError> {
+    let config = WalletConfig::load()?;
+    let ks = load_wallet(&config, name)?;
+    let accounts = ks.accounts();
+    let active = ks.file.derivation_index;
+
+    if json {
+        let accounts: Vec<serde_json::Value> = accounts
+            .iter()
+            .map(|a| {
+                serde_json::json!({
+                    "index": a.index,
+                    "address": a.address,
+                    "public_key": a.public_key,
+                    "label": a.label,
+                    "active": a.index == active,
+                })
+            })
+            .collect();
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&accounts).unwrap_or_default()
+        );
+        return Ok(());
+    }
+
+    println!();
+    println!(
+        "  {} {}",
+        style_bold().apply_to("Accounts of"),
+        style_bold().apply_to(&ks.name)
+    );
+    if ks.file.has_mnemonic {
+        println!(
+            "  {}",
+            style_dim().apply_to(format!(
+                "Path: {}/<index>'",
+                format_derivation_path(&ks.account_path()?)
+            ))
+        );
+    }
+
+    let mut table = data_table(&["Index", "Label", "Address", "Status"]);
+    for account in &accounts {
+        table.add_row(vec![
+            cell(account.index),
+            cell_dim(account.label.as_deref().unwrap_or("")),
+            cell(&account.address),
+            if account.index == active {
+                cell_green("\u{25cf} active")
+            } else {
+                cell("")
+            },
+        ]);
+    }
+    print_table(&table);
+    println!();
+
+    Ok(())
+}
+
+pub fn use_account(name: Option<&str>, account: &str) -> Result<(), WalletError> {
+    let config = WalletConfig::load()?;
+    let mut ks = load_wallet(&config, name)?;
+    let found = ks
+        .find_account(account)
+        .ok_or_else(|| WalletError::Other(format!("account '{}' not found", account)))?;
+    ks.use_account(found.index)?;
+    ks.save()?;
+
+    println!();
+    print_success(&format!(
+        "Wallet '{}' now signs with account {} ({})",
+        ks.name, found.index, found.address
+    ));
+    println!();
+
+    Ok(())
+}
+
+/// Derive accounts in order and keep those with on-chain activity, until
+/// `gap_limit` consecutive accounts turn out unused.
+pub async fn discover(
+    name: Option<&str>,
+    gap_limit: u32,
+    rpc_url: Option<&str>,
+) -> Result<(), WalletError> {
+    if gap_limit == 0 {
+        return Err(WalletError::Other(
+            "gap limit must be at least 1".to_string(),
+        ));
+    }
+    let config = WalletConfig::load()?;
+    let url = rpc_url.unwrap_or(&config.rpc_url);
+    let rpc = RpcClient::new(url)?;
+    let mut ks = load_wallet(&config, name)?;
+
+    let password = prompt_password("Enter password")?;
+    let seed = unlock_secret(&mut ks, &password, Keystore::decrypt_hd_seed)?;
+    let token_hex = hex::encode(NATIVE_TOKEN_ID);
+
+    let mut found: Vec<WalletAccount> = Vec::new();
+    let mut unused = 0;
+    let mut index = 0u32;
+    while unused < gap_limit {
+        let account = ks.derive_account(&seed, index)?;
+        if account_is_used(&rpc, &account, &token_hex).await? {
+            unused = 0;
+            if ks.find_account(&index.to_string()).is_none() {
+                found.push(account.clone());
+                ks.add_account(account)?;
+            }
+        } else {
+            unused += 1;
+        }
+        index = index
+            .checked_add(1)
+            .ok_or_else(|| WalletError::Other("ran out of account indexes".to_string()))?;
+    }
+
+    println!();
+    if found.is_empty() {
+        println!(
+            "  No new accounts with activity among the first {} indexes.",
+            index
+        );
+        println!();
+        return Ok(());
+    }
+    ks.save()?;
+
+    print_success(&format!(
+        "Found {} new account(s) in wallet '{}'",
+        found.len(),
+        ks.name
+    ));
+    let mut table = data_table(&["Index", "Address"]);
+    for account in &found {
+        table.add_row(vec![cell(account.index), cell(&account.address)]);
+    }
+    print_table(&table);
+    println!();
+
+    Ok(())
+}
+
+/// An account is used once its thread is registered or it holds NORN.
+async fn account_is_used(
+    rpc: &RpcClient,
+    account: &WalletAccount,
+    token_hex: &str,
+) -> Result<bool, WalletError> {
+    let addr_hex = account.address.trim_start_matches("0x");
+    if rpc.get_thread(addr_hex).await?.is_some() {
+        return Ok(true);
+    }
+    let balance = rpc.get_balance(addr_hex, token_hex).await?;
+    Ok(balance != "0")
+}
@@ -1,10 +1,14 @@
 use crate::wallet::config::WalletConfig;
 use crate::wallet::error::WalletError;
-use crate::wallet::format::{format_address, format_pubkey, style_bold};
+use crate::wallet::format::{
+    format_address, format_address_bech32, format_pubkey, print_success, style_bold,
+};
 use crate::wallet::keystore::Keystore;
+use crate::wallet::ledger::Ledger;
+use crate::wallet::output::{print_json, WalletAddress};
 use crate::wallet::ui::{cell, info_table, print_table};
 
-pub fn run(name: Option<&str>, json: bool) -> Result<(), WalletError> {
+pub fn run(name: Option<&str>, json: bool, verify: bool) -> Result<(), WalletError> {
     let config = WalletConfig::load()?;
     let wallet_name = match name {
         Some(n) => n,
@@ -13,17 +17,17 @@ pub fn run(name: Option<&str>, json: bool) -> Result<(), WalletError> {
 
     let ks = Keystore::load(wallet_name)?;
 
+    if verify {
+        return verify_on_ledger(&ks);
+    }
+
     if json {
-        let info = serde_json::json!({
-            "name": wallet_name,
-            "address": format_address(&ks.address),
-            "public_key": format_pubkey(&ks.public_key),
+        return print_json(&WalletAddress {
+            wallet: wallet_name.to_string(),
+            address: format_address(&ks.address),
+            bech32: format_address_bech32(&ks.address),
+            public_key: format_pubkey(&ks.public_key),
         });
-        println!(
-            "{}",
-            serde_json::to_string_pretty(&info).unwrap_or_default()
-        );
-        return Ok(());
     }
 
     println!();
@@ -31,6 +35,10 @@ pub fn run(name: Option<&str>, json: bool) -> Result<(), WalletError> {
 
     let mut table = info_table();
     table.add_row(vec![cell("Address"), cell(format_address(&ks.address))]);
+    table.add_row(vec![
+        cell("Bech32"),
+        cell(format_address_bech32(&ks.address)),
+    ]);
     table.add_row(vec![
         cell("Public key"),
         cell(format_pubkey(&ks.public_key)),
@@ -41,3 +49,25 @@ pub fn run(name: Option<&str>, json: bool) -> Result<(), WalletError> {
 
     Ok(())
 }
+
+/// Show the wallet's address on its Ledger, so it can be checked against a
+/// screen the host cannot tamper with.
+fn verify_on_ledger(ks: &Keystore) -> Result<(), WalletError> {
+    let account = ks.file.ledger.as_ref().map(|l| l.account).ok_or_else(|| {
+        WalletError::Other(format!("wallet '{}' is not a Ledger wallet", ks.name))
+    })?;
+
+    println!();
+    println!("  Address: {}", format_address(&ks.address));
+    println!("  Check that your Ledger shows the same address, then approve it.");
+    let public_key = Ledger::connect()?.public_key(account, true)?;
+    if public_key != ks.public_key {
+        return Err(WalletError::Ledger(format!(
+            "account {} of this device is not the wallet's key",
+            account
+        )));
+    }
+    print_success("Address confirmed on the device.");
+    println!();
+    Ok(())
+}
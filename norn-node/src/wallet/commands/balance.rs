@@ -1,11 +1,13 @@
 use norn_types::primitives::NATIVE_TOKEN_ID;
 
+use super::transfer::resolve_recipient;
 use crate::wallet::config::WalletConfig;
 use crate::wallet::error::WalletError;
 use crate::wallet::format::{
-    format_address, format_token_amount_with_name, parse_address, style_bold, style_dim,
+    format_address, format_token_amount_with_name, load_price, style_bold, style_dim,
 };
 use crate::wallet::keystore::Keystore;
+use crate::wallet::output::{balance_entry, print_json, Balance};
 use crate::wallet::rpc_client::RpcClient;
 use crate::wallet::ui::{cell, cell_dim, info_table, print_table};
 
@@ -16,11 +18,14 @@ pub async fn run(
     rpc_url: Option<&str>,
 ) -> Result<(), WalletError> {
     let config = WalletConfig::load()?;
+    if !json {
+        load_price(&config).await;
+    }
     let url = rpc_url.unwrap_or(&config.rpc_url);
     let rpc = RpcClient::new(url)?;
 
     let addr = if let Some(a) = address {
-        parse_address(a)?
+        resolve_recipient(&rpc, &config, a).await?
     } else {
         let name = config.active_wallet_name()?;
         let ks = Keystore::load(name)?;
@@ -63,21 +68,11 @@ pub async fn run(
         .map(|b| b.height);
 
     if json {
-        let mut info = serde_json::json!({
-            "address": format_address(&addr),
-            "token_id": hex::encode(token_id),
-            "token_symbol": token_symbol,
-            "balance": balance_str,
-            "human_readable": format_token_amount_with_name(balance, token_decimals, &token_symbol),
+        return print_json(&Balance {
+            address: format_address(&addr),
+            balance: balance_entry(&token_hex, &token_symbol, balance, token_decimals),
+            block_height,
         });
-        if let Some(h) = block_height {
-            info["block_height"] = serde_json::json!(h);
-        }
-        println!(
-            "{}",
-            serde_json::to_string_pretty(&info).unwrap_or_default()
-        );
-        return Ok(());
     }
 
     println!();
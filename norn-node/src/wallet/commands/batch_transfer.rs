@@ -0,0 +1,477 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use norn_types::constants::{MAX_MULTI_TRANSFERS, TRANSFER_FEE};
+use norn_types::primitives::{Address, TokenId, NATIVE_TOKEN_ID};
+
+use super::multi_transfer::build_multi_transfer;
+use super::transfer::{
+    format_recipient, next_knot_sequence, resolve_recipient, resolve_transfer_token,
+};
+use crate::wallet::config::WalletConfig;
+use crate::wallet::error::WalletError;
+use crate::wallet::format::{
+    format_address, format_amount_with_symbol, format_token_amount_with_name, parse_token_amount,
+    print_divider, print_error, print_success, style_bold, style_dim, truncate_hex_string,
+};
+use crate::wallet::journal::record_submission;
+use crate::wallet::keystore::Keystore;
+use crate::wallet::prompt::confirm;
+use crate::wallet::rpc_client::RpcClient;
+use crate::wallet::signer::unlock;
+use crate::wallet::ui::{cell, cell_green, cell_right, cell_yellow, data_table, print_table};
+
+/// Largest number of rows accepted from one CSV file.
+const MAX_BATCH_ROWS: usize = 10_000;
+
+/// One row of a batch CSV file, as written.
+#[derive(Debug, PartialEq)]
+struct CsvRow {
+    /// Line number in the file, for error messages and the report.
+    line: usize,
+    recipient: String,
+    amount: String,
+    token: Option<String>,
+}
+
+/// A token paid out by the batch.
+struct BatchToken {
+    id: TokenId,
+    symbol: String,
+    decimals: u8,
+}
+
+/// A resolved row.
+struct Payout {
+    row: CsvRow,
+    to: Address,
+    /// Index into the batch's tokens.
+    token: usize,
+    amount: u128,
+}
+
+/// What happened to a row.
+#[derive(Clone)]
+enum Outcome {
+    NotSent,
+    Sent([u8; 32]),
+    Failed(String),
+}
+
+/// Parse `<recipient>,<amount>[,<token>]` rows. Blank lines, lines starting
+/// with `#` and a header row are skipped.
+fn parse_csv(text: &str) -> Result<Vec<CsvRow>, WalletError> {
+    let mut rows = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let line_no = i + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        let invalid = || {
+            WalletError::Other(format!(
+                "line {}: expected <recipient>,<amount>[,<token>]",
+                line_no
+            ))
+        };
+        let (recipient, amount, token) = match fields.as_slice() {
+            [r, a] => (*r, *a, None),
+            [r, a, t] => (*r, *a, Some(*t).filter(|t| !t.is_empty())),
+            _ => return Err(invalid()),
+        };
+        if recipient.is_empty() || amount.is_empty() {
+            return Err(invalid());
+        }
+        // A header row is one whose amount column is not a number.
+        if rows.is_empty() && !amount.starts_with(|c: char| c.is_ascii_digit() || c == '.') {
+            continue;
+        }
+        rows.push(CsvRow {
+            line: line_no,
+            recipient: recipient.to_string(),
+            amount: amount.to_string(),
+            token: token.map(str::to_string),
+        });
+    }
+    if rows.is_empty() {
+        return Err(WalletError::Other("no payout rows found".to_string()));
+    }
+    if rows.len() > MAX_BATCH_ROWS {
+        return Err(WalletError::Other(format!(
+            "too many rows: {} (max {})",
+            rows.len(),
+            MAX_BATCH_ROWS
+        )));
+    }
+    Ok(rows)
+}
+
+/// Group payouts into knots: one token per knot, at most
+/// `MAX_MULTI_TRANSFERS` transfers each, in file order within a token.
+fn plan_knots(payouts: &[Payout], token_count: usize) -> Vec<Vec<usize>> {
+    let mut knots = Vec::new();
+    for token in 0..token_count {
+        let rows: Vec<usize> = (0..payouts.len())
+            .filter(|&i| payouts[i].token == token)
+            .collect();
+        knots.extend(rows.chunks(MAX_MULTI_TRANSFERS).map(<[usize]>::to_vec));
+    }
+    knots
+}
+
+pub async fn run(
+    csv: &Path,
+    default_token: Option<&str>,
+    memo: Option<&str>,
+    label: Option<&str>,
+    report: Option<&Path>,
+    yes: bool,
+    rpc_url: Option<&str>,
+) -> Result<(), WalletError> {
+    let rows = parse_csv(&std::fs::read_to_string(csv)?)?;
+
+    let config = WalletConfig::load()?;
+    let wallet_name = config.active_wallet_name()?;
+    let ks = Keystore::load(wallet_name)?;
+
+    let url = rpc_url.unwrap_or(&config.rpc_url);
+    let rpc = RpcClient::new(url)?;
+
+    // Resolve tokens and recipients up front, so a bad row stops the batch
+    // before anything is sent.
+    let mut tokens: Vec<BatchToken> = Vec::new();
+    let mut token_index: HashMap<String, usize> = HashMap::new();
+    let mut payouts = Vec::with_capacity(rows.len());
+    for row in rows {
+        let key = row.token.as_deref().or(default_token);
+        let token = match token_index.get(key.unwrap_or("NORN")) {
+            Some(&i) => i,
+            None => {
+                let (id, symbol, decimals) = resolve_transfer_token(&rpc, key).await?;
+                let i = match tokens.iter().position(|t| t.id == id) {
+                    Some(i) => i,
+                    None => {
+                        tokens.push(BatchToken {
+                            id,
+                            symbol,
+                            decimals,
+                        });
+                        tokens.len() - 1
+                    }
+                };
+                token_index.insert(key.unwrap_or("NORN").to_string(), i);
+                i
+            }
+        };
+        let amount = parse_token_amount(&row.amount, tokens[token].decimals)
+            .map_err(|e| WalletError::Other(format!("line {}: {}", row.line, e)))?;
+        if amount == 0 {
+            return Err(WalletError::InvalidAmount(format!(
+                "line {}: amount must be greater than zero",
+                row.line
+            )));
+        }
+        let to = resolve_recipient(&rpc, &config, &row.recipient)
+            .await
+            .map_err(|e| WalletError::Other(format!("line {}: {}", row.line, e)))?;
+        payouts.push(Payout {
+            row,
+            to,
+            token,
+            amount,
+        });
+    }
+
+    // Totals per token, checked against balances. Every transfer pays the
+    // transfer fee in NORN.
+    let mut totals = vec![0u128; tokens.len()];
+    for p in &payouts {
+        totals[p.token] = totals[p.token]
+            .checked_add(p.amount)
+            .ok_or_else(|| WalletError::InvalidAmount("total amount overflows".to_string()))?;
+    }
+    let total_fee = TRANSFER_FEE * payouts.len() as u128;
+
+    let addr_hex = hex::encode(ks.address);
+    let mut balances = Vec::with_capacity(tokens.len());
+    for (token, total) in tokens.iter().zip(&totals) {
+        let balance: u128 = rpc
+            .get_balance(&addr_hex, &hex::encode(token.id))
+            .await?
+            .parse()
+            .unwrap_or(0);
+        let required = if token.id == NATIVE_TOKEN_ID {
+            total.saturating_add(total_fee)
+        } else {
+            *total
+        };
+        if balance < required {
+            return Err(WalletError::InsufficientBalance {
+                available: format_token_amount_with_name(balance, token.decimals, &token.symbol),
+                required: format_token_amount_with_name(required, token.decimals, &token.symbol),
+            });
+        }
+        balances.push(balance);
+    }
+    if !tokens.iter().any(|t| t.id == NATIVE_TOKEN_ID) {
+        let norn_balance: u128 = rpc
+            .get_balance(&addr_hex, &hex::encode(NATIVE_TOKEN_ID))
+            .await?
+            .parse()
+            .unwrap_or(0);
+        if norn_balance < total_fee {
+            return Err(WalletError::InsufficientBalance {
+                available: format_amount_with_symbol(norn_balance, &NATIVE_TOKEN_ID),
+                required: format_amount_with_symbol(total_fee, &NATIVE_TOKEN_ID),
+            });
+        }
+    }
+
+    let knots = plan_knots(&payouts, tokens.len());
+    let amount_display = |p: &Payout| {
+        let token = &tokens[p.token];
+        format_token_amount_with_name(p.amount, token.decimals, &token.symbol)
+    };
+
+    // Show confirmation
+    if !yes {
+        println!();
+        println!("  {}", style_bold().apply_to("Batch Transfer Summary"));
+        print_divider();
+        println!(
+            "  From:    {} ({})",
+            format_address(&ks.address),
+            wallet_name
+        );
+        let mut table = data_table(&["Line", "To", "Amount"]);
+        for p in &payouts {
+            table.add_row(vec![
+                cell(p.row.line),
+                cell(format_recipient(&config, &p.to)),
+                cell_right(amount_display(p)),
+            ]);
+        }
+        print_table(&table);
+
+        let mut table = data_table(&["Token", "Rows", "Total", "Balance"]);
+        for (i, token) in tokens.iter().enumerate() {
+            table.add_row(vec![
+                cell(&token.symbol),
+                cell_right(payouts.iter().filter(|p| p.token == i).count()),
+                cell_right(format_token_amount_with_name(
+                    totals[i],
+                    token.decimals,
+                    &token.symbol,
+                )),
+                cell_right(format_token_amount_with_name(
+                    balances[i],
+                    token.decimals,
+                    &token.symbol,
+                )),
+            ]);
+        }
+        print_table(&table);
+        println!("  Knots:   {}", knots.len());
+        println!(
+            "  Fees:    {}",
+            style_dim().apply_to(format_amount_with_symbol(total_fee, &NATIVE_TOKEN_ID))
+        );
+        if let Some(m) = memo {
+            println!("  Memo:    \"{}\"", m);
+        }
+        println!();
+
+        if !confirm(&format!(
+            "Send {} transfers in {} knots?",
+            payouts.len(),
+            knots.len()
+        ))? {
+            println!("  Cancelled.");
+            return Ok(());
+        }
+    }
+
+    let mut signer = unlock(&ks)?;
+    let sender_addr = norn_crypto::address::pubkey_to_address(&signer.public_key());
+    let mut sequence = next_knot_sequence(&rpc, &sender_addr).await?;
+
+    // Knots go out one at a time; a failed knot leaves the sequence where it
+    // was for the next one.
+    let mut outcomes: Vec<Outcome> = payouts.iter().map(|_| Outcome::NotSent).collect();
+    for knot_rows in &knots {
+        let token_id = tokens[payouts[knot_rows[0]].token].id;
+        let legs: Vec<(Address, u128)> = knot_rows
+            .iter()
+            .map(|&i| (payouts[i].to, payouts[i].amount))
+            .collect();
+        let knot = build_multi_transfer(signer.as_mut(), token_id, &legs, memo, sequence)?;
+        let bytes =
+            borsh::to_vec(&knot).map_err(|e| WalletError::SerializationError(e.to_string()))?;
+        let submitted = rpc.submit_knot(&hex::encode(&bytes)).await;
+        if let Ok(result) = &submitted {
+            let token = &tokens[payouts[knot_rows[0]].token];
+            record_submission(
+                &sender_addr,
+                &knot,
+                result,
+                label,
+                Some((&token.symbol, token.decimals)),
+            );
+        }
+        let outcome = match submitted {
+            Ok(result) if result.success => {
+                sequence += 1;
+                Outcome::Sent(knot.id)
+            }
+            Ok(result) => Outcome::Failed(result.reason.unwrap_or_else(|| "unknown".to_string())),
+            Err(e) => Outcome::Failed(e.to_string()),
+        };
+        for &i in knot_rows {
+            outcomes[i] = outcome.clone();
+        }
+    }
+
+    // Per-row results.
+    println!();
+    let mut table = data_table(&["Line", "To", "Amount", "Result"]);
+    for (p, outcome) in payouts.iter().zip(&outcomes) {
+        let result = match outcome {
+            Outcome::Sent(id) => cell_green(format!(
+                "sent ({})",
+                truncate_hex_string(&hex::encode(id), 6)
+            )),
+            Outcome::Failed(reason) => cell_yellow(format!("failed: {}", reason)),
+            Outcome::NotSent => cell_yellow("not sent"),
+        };
+        table.add_row(vec![
+            cell(p.row.line),
+            cell(format_recipient(&config, &p.to)),
+            cell_right(amount_display(p)),
+            result,
+        ]);
+    }
+    print_table(&table);
+
+    if let Some(path) = report {
+        write_report(path, &payouts, &outcomes, &tokens)?;
+        println!(
+            "  {}",
+            style_dim().apply_to(format!("Report written to {}", path.display()))
+        );
+    }
+
+    let sent = outcomes
+        .iter()
+        .filter(|o| matches!(o, Outcome::Sent(_)))
+        .count();
+    if sent == payouts.len() {
+        print_success(&format!(
+            "{} transfers sent in {} knots!",
+            sent,
+            knots.len()
+        ));
+    } else {
+        print_error(
+            &format!("{} of {} transfers sent", sent, payouts.len()),
+            Some("Failed rows were not applied. Fix them and run the batch again with only those rows."),
+        );
+    }
+    println!();
+
+    Ok(())
+}
+
+/// Write per-row results as CSV.
+fn write_report(
+    path: &Path,
+    payouts: &[Payout],
+    outcomes: &[Outcome],
+    tokens: &[BatchToken],
+) -> Result<(), WalletError> {
+    let mut out = String::from("line,recipient,address,amount,token,status,knot_id\n");
+    for (p, outcome) in payouts.iter().zip(outcomes) {
+        let (status, knot_id) = match outcome {
+            Outcome::Sent(id) => ("sent".to_string(), hex::encode(id)),
+            // Reasons may contain commas.
+            Outcome::Failed(reason) => (
+                format!("failed: {}", reason.replace(',', ";")),
+                String::new(),
+            ),
+            Outcome::NotSent => ("not sent".to_string(), String::new()),
+        };
+        out.push_str(&format!(
+            "{},{},{},{},{},{},{}\n",
+            p.row.line,
+            p.row.recipient,
+            format_address(&p.to),
+            p.row.amount,
+            tokens[p.token].symbol,
+            status,
+            knot_id
+        ));
+    }
+    std::fs::write(path, out)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csv_rows() {
+        let text = "recipient,amount,token\n\
+                    # March payroll\n\
+                    alice, 10.5\n\
+                    \n\
+                    0xabababababababababababababababababababab,2,USDN\n\
+                    bob,3,\n";
+        let rows = parse_csv(text).unwrap();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(
+            rows[0],
+            CsvRow {
+                line: 3,
+                recipient: "alice".to_string(),
+                amount: "10.5".to_string(),
+                token: None,
+            }
+        );
+        assert_eq!(rows[1].token.as_deref(), Some("USDN"));
+        assert_eq!(rows[2].token, None);
+
+        assert!(parse_csv("alice\n").is_err());
+        assert!(parse_csv("alice,1,NORN,extra\n").is_err());
+        assert!(parse_csv("recipient,amount\n").is_err());
+        // Only the first row can be a header.
+        assert!(parse_csv("alice,1\nbob,ten\n").is_ok());
+    }
+
+    #[test]
+    fn test_plan_knots_chunks_per_token() {
+        let payout = |token| Payout {
+            row: CsvRow {
+                line: 1,
+                recipient: String::new(),
+                amount: String::new(),
+                token: None,
+            },
+            to: [0u8; 20],
+            token,
+            amount: 1,
+        };
+        // 70 rows of token 0 interleaved with 3 of token 1.
+        let mut payouts: Vec<Payout> = (0..70).map(|_| payout(0)).collect();
+        payouts.insert(5, payout(1));
+        payouts.insert(40, payout(1));
+        payouts.push(payout(1));
+
+        let knots = plan_knots(&payouts, 2);
+        assert_eq!(knots.len(), 3);
+        assert_eq!(knots[0].len(), MAX_MULTI_TRANSFERS);
+        assert_eq!(knots[1].len(), 70 - MAX_MULTI_TRANSFERS);
+        assert_eq!(knots[2], vec![5, 40, 72]);
+        assert!(knots[..2].iter().flatten().all(|&i| payouts[i].token == 0));
+    }
+}
@@ -73,7 +73,11 @@ pub async fn run(
             println!();
         }
         None => {
-            println!("  Block not found.");
+            if json {
+                println!("null");
+            } else {
+                println!("  Block not found.");
+            }
         }
     }
 
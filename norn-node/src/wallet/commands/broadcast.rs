@@ -0,0 +1,99 @@
+use std::path::Path;
+
+use norn_types::weave::CommitmentUpdate;
+
+use super::sign_knot::print_knot_summary;
+use super::submit_knot;
+use crate::wallet::config::WalletConfig;
+use crate::wallet::error::WalletError;
+use crate::wallet::format::{
+    format_address, format_native, format_pubkey, print_divider, print_error, print_success,
+    style_bold, style_dim,
+};
+use crate::wallet::prompt::confirm;
+use crate::wallet::rpc_client::RpcClient;
+use crate::wallet::tx_file::{read_tx_file, Transaction};
+
+pub async fn run(file: &Path, yes: bool, rpc_url: Option<&str>) -> Result<(), WalletError> {
+    let config = WalletConfig::load()?;
+    let url = rpc_url.unwrap_or(&config.rpc_url);
+    let rpc = RpcClient::new(url)?;
+
+    let tx = read_tx_file(file)?;
+    match &tx {
+        Transaction::Knot(knot) => submit_knot::check_submittable(knot, file)?,
+        Transaction::Commitment(commitment) => {
+            if commitment.signature == [0u8; 64] {
+                return Err(WalletError::Other(format!(
+                    "commitment is not signed; sign it with `norn wallet sign-tx --offline {}`",
+                    file.display()
+                )));
+            }
+        }
+    }
+
+    if !yes {
+        print_tx_summary(&tx);
+        println!();
+        if !confirm("Broadcast this transaction?")? {
+            println!("  Cancelled.");
+            return Ok(());
+        }
+    }
+
+    broadcast(&rpc, &tx).await
+}
+
+/// Submit a signed transaction to the node.
+pub(super) async fn broadcast(rpc: &RpcClient, tx: &Transaction) -> Result<(), WalletError> {
+    match tx {
+        Transaction::Knot(knot) => submit_knot::submit(rpc, knot).await,
+        Transaction::Commitment(commitment) => {
+            let bytes = borsh::to_vec(commitment)
+                .map_err(|e| WalletError::SerializationError(e.to_string()))?;
+            let result = rpc.submit_commitment(&hex::encode(&bytes)).await?;
+            if result.success {
+                print_success("Commitment submitted successfully!");
+            } else {
+                print_error(
+                    &format!(
+                        "Commitment failed: {}",
+                        result.reason.unwrap_or_else(|| "unknown".to_string())
+                    ),
+                    Some("The thread may have moved on since the commitment was built."),
+                );
+            }
+            println!();
+            Ok(())
+        }
+    }
+}
+
+/// Print what a transaction does and whether it is signed.
+pub(super) fn print_tx_summary(tx: &Transaction) {
+    match tx {
+        Transaction::Knot(knot) => print_knot_summary(knot),
+        Transaction::Commitment(commitment) => print_commitment_summary(commitment),
+    }
+}
+
+fn print_commitment_summary(commitment: &CommitmentUpdate) {
+    println!();
+    println!("  {}", style_bold().apply_to("Commitment"));
+    print_divider();
+    println!("  Thread:  {}", format_address(&commitment.thread_id));
+    println!("  Owner:   {}", format_pubkey(&commitment.owner));
+    println!("  Version: {}", commitment.version);
+    println!("  State:   {}", hex::encode(commitment.state_hash));
+    if commitment.priority_fee > 0 {
+        println!("  Tip:     {}", format_native(commitment.priority_fee));
+    }
+    println!(
+        "  Signed:  {}",
+        style_dim().apply_to(if commitment.signature == [0u8; 64] {
+            "no"
+        } else {
+            "yes"
+        })
+    );
+}
@@ -7,6 +7,7 @@ use crate::wallet::format::{
 use crate::wallet::keystore::Keystore;
 use crate::wallet::prompt::{confirm, prompt_password};
 use crate::wallet::rpc_client::RpcClient;
+use crate::wallet::signer::unlock_keypair;
 
 pub async fn run(
     token: &str,
@@ -63,7 +64,7 @@ pub async fn run(
     }
 
     let password = prompt_password("Enter password")?;
-    let keypair = ks.decrypt_keypair(&password)?;
+    let keypair = unlock_keypair(&ks, &password)?;
 
     let now = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
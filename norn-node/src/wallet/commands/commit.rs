@@ -1,29 +1,50 @@
-use borsh::BorshSerialize;
+use std::path::Path;
 
-use norn_crypto::address::pubkey_to_address;
 use norn_thread::state::compute_state_hash;
 use norn_types::thread::ThreadState;
 use norn_types::weave::CommitmentUpdate;
+use norn_weave::commitment::commitment_signing_data;
 
 use crate::wallet::config::WalletConfig;
 use crate::wallet::error::WalletError;
-use crate::wallet::format::{format_address, print_error, print_success, style_bold};
+use crate::wallet::format::{
+    format_address, format_native, parse_amount, print_error, print_success, style_bold, style_dim,
+};
 use crate::wallet::keystore::Keystore;
-use crate::wallet::prompt::prompt_password;
 use crate::wallet::rpc_client::RpcClient;
+use crate::wallet::signer::unlock;
+use crate::wallet::tx_file::write_commitment_file;
 
-pub async fn run(name: Option<&str>, rpc_url: Option<&str>) -> Result<(), WalletError> {
+pub async fn run(
+    name: Option<&str>,
+    tip: Option<&str>,
+    out: Option<&Path>,
+    rpc_url: Option<&str>,
+) -> Result<(), WalletError> {
     let config = WalletConfig::load()?;
+    let priority_fee = match tip {
+        Some(t) => parse_amount(t)?,
+        None => 0,
+    };
     let wallet_name = match name {
         Some(n) => n,
         None => config.active_wallet_name()?,
     };
 
     let ks = Keystore::load(wallet_name)?;
-    let password = prompt_password("Enter password")?;
-    let keypair = ks.decrypt_keypair(&password)?;
+    if ks.public_key == [0u8; 32] {
+        return Err(WalletError::Other(format!(
+            "wallet '{}' has no public key; import it with --watch-only <PUBKEY>",
+            wallet_name
+        )));
+    }
+    // Commitments written to a file are signed later, possibly offline.
+    let mut signer = match out {
+        Some(_) if ks.is_watch_only() => None,
+        _ => Some(unlock(&ks)?),
+    };
 
-    let address = pubkey_to_address(&keypair.public_key());
+    let address = ks.address;
 
     let url = rpc_url.unwrap_or(&config.rpc_url);
     let rpc = RpcClient::new(url)?;
@@ -70,46 +91,39 @@ pub async fn run(name: Option<&str>, rpc_url: Option<&str>) -> Result<(), Wallet
     let new_version = current_version + 1;
     let mut commitment = CommitmentUpdate {
         thread_id: address,
-        owner: keypair.public_key(),
+        owner: ks.public_key,
         version: new_version,
         state_hash,
         prev_commitment_hash: prev_hash,
         knot_count: 0,
         timestamp: now,
+        priority_fee,
         signature: [0u8; 64],
     };
 
     // Sign the commitment
-    let mut sig_data = Vec::new();
-    commitment
-        .thread_id
-        .serialize(&mut sig_data)
-        .expect("serialize");
-    commitment
-        .owner
-        .serialize(&mut sig_data)
-        .expect("serialize");
-    commitment
-        .version
-        .serialize(&mut sig_data)
-        .expect("serialize");
-    commitment
-        .state_hash
-        .serialize(&mut sig_data)
-        .expect("serialize");
-    commitment
-        .prev_commitment_hash
-        .serialize(&mut sig_data)
-        .expect("serialize");
-    commitment
-        .knot_count
-        .serialize(&mut sig_data)
-        .expect("serialize");
-    commitment
-        .timestamp
-        .serialize(&mut sig_data)
-        .expect("serialize");
-    commitment.signature = keypair.sign(&sig_data);
+    if let Some(signer) = signer.as_mut() {
+        commitment.signature = signer.sign(&commitment_signing_data(&commitment))?;
+    }
+
+    if let Some(path) = out {
+        write_commitment_file(path, &commitment)?;
+        print_success(&format!(
+            "Commitment for version {} written to {}",
+            new_version,
+            path.display()
+        ));
+        println!(
+            "  {}",
+            style_dim().apply_to(format!(
+                "Sign it with `norn wallet sign-tx --offline {}` if needed, then submit it with `norn wallet broadcast {}`.",
+                path.display(),
+                path.display()
+            ))
+        );
+        println!();
+        return Ok(());
+    }
 
     let bytes =
         borsh::to_vec(&commitment).map_err(|e| WalletError::SerializationError(e.to_string()))?;
@@ -122,6 +136,9 @@ pub async fn run(name: Option<&str>, rpc_url: Option<&str>) -> Result<(), Wallet
         format_address(&address),
         new_version
     );
+    if priority_fee > 0 {
+        println!("  Tip: {}", format_native(priority_fee));
+    }
 
     let result = rpc.submit_commitment(&hex_data).await?;
 
@@ -1,31 +1,90 @@
-use crate::wallet::config::WalletConfig;
+use std::path::Path;
+
+use crate::wallet::config::{validate_network, Profile, WalletConfig};
 use crate::wallet::error::WalletError;
-use crate::wallet::format::{print_success, style_bold};
-use crate::wallet::ui::{cell, info_table, print_table};
+use crate::wallet::format::{print_success, style_bold, style_dim, Denomination};
+use crate::wallet::output::print_json;
+use crate::wallet::ui::{cell, cell_dim, cell_green, data_table, info_table, print_table};
+
+/// Settings given to `norn wallet config`.
+#[derive(Default)]
+pub struct ConfigUpdate<'a> {
+    pub rpc_url: Option<&'a str>,
+    pub network: Option<&'a str>,
+    pub denomination: Option<&'a str>,
+    /// `"none"` turns prices off.
+    pub price_url: Option<&'a str>,
+    pub price_pointer: Option<&'a str>,
+    pub price_currency: Option<&'a str>,
+}
 
-pub fn run(rpc_url: Option<&str>, network: Option<&str>, json: bool) -> Result<(), WalletError> {
+pub fn run(update: ConfigUpdate<'_>, json: bool) -> Result<(), WalletError> {
     let mut config = WalletConfig::load()?;
+    let mut changes = Vec::new();
+
+    // With a profile in effect these come from the profile; setting them
+    // here would be silently ignored.
+    if let Some(profile) = config.active_profile() {
+        if update.rpc_url.is_some() || update.network.is_some() {
+            return Err(WalletError::ConfigError(format!(
+                "profile '{}' is in use; change it with `config add-profile`, or run `config use-profile none` first",
+                profile
+            )));
+        }
+    }
 
-    if let Some(url) = rpc_url {
+    if let Some(url) = update.rpc_url {
         config.rpc_url = url.to_string();
-        config.save()?;
-        print_success(&format!("RPC URL set to {}", url));
-        return Ok(());
+        changes.push(format!("RPC URL set to {}", url));
     }
 
-    if let Some(net) = network {
-        match net {
-            "dev" | "testnet" | "mainnet" => {
-                config.network = net.to_string();
-                config.save()?;
-                print_success(&format!("Network set to {}", net));
-            }
-            _ => {
+    if let Some(net) = update.network {
+        validate_network(net)?;
+        config.network = net.to_string();
+        changes.push(format!("Network set to {}", net));
+    }
+
+    if let Some(denomination) = update.denomination {
+        config.denomination = denomination.parse::<Denomination>()?;
+        changes.push(format!("Amounts shown in {}", config.denomination.symbol()));
+    }
+
+    if let Some(url) = update.price_url {
+        if url == "none" {
+            config.price_url = None;
+            changes.push("Prices turned off".to_string());
+        } else {
+            if !url.starts_with("http://") && !url.starts_with("https://") {
                 return Err(WalletError::ConfigError(format!(
-                    "unknown network '{}', expected 'dev', 'testnet', or 'mainnet'",
-                    net
+                    "price URL '{}' must start with http:// or https://",
+                    url
                 )));
             }
+            config.price_url = Some(url.to_string());
+            changes.push(format!("Price endpoint set to {}", url));
+        }
+    }
+
+    if let Some(pointer) = update.price_pointer {
+        if !pointer.is_empty() && !pointer.starts_with('/') {
+            return Err(WalletError::ConfigError(format!(
+                "price pointer '{}' must be a JSON pointer such as /price",
+                pointer
+            )));
+        }
+        config.price_pointer = pointer.to_string();
+        changes.push(format!("Price read from {}", pointer));
+    }
+
+    if let Some(currency) = update.price_currency {
+        config.price_currency = currency.to_uppercase();
+        changes.push(format!("Prices quoted in {}", config.price_currency));
+    }
+
+    if !changes.is_empty() {
+        config.save()?;
+        for change in changes {
+            print_success(&change);
         }
         return Ok(());
     }
@@ -34,8 +93,13 @@ pub fn run(rpc_url: Option<&str>, network: Option<&str>, json: bool) -> Result<(
     if json {
         let info = serde_json::json!({
             "active_wallet": config.active_wallet,
+            "profile": config.active_profile(),
             "rpc_url": config.rpc_url,
             "network": config.network,
+            "denomination": config.denomination,
+            "price_url": config.price_url,
+            "price_pointer": config.price_pointer,
+            "price_currency": config.price_currency,
             "wallets": config.wallets,
             "data_dir": WalletConfig::data_dir()?.to_string_lossy(),
         });
@@ -54,12 +118,31 @@ pub fn run(rpc_url: Option<&str>, network: Option<&str>, json: bool) -> Result<(
         cell("Active wallet"),
         cell(config.active_wallet.as_deref().unwrap_or("(none)")),
     ]);
+    table.add_row(vec![
+        cell("Profile"),
+        cell(config.active_profile().unwrap_or("(none)")),
+    ]);
     table.add_row(vec![cell("RPC URL"), cell(&config.rpc_url)]);
     table.add_row(vec![cell("Network"), cell(&config.network)]);
+    table.add_row(vec![cell("Amounts in"), cell(config.denomination.symbol())]);
+    table.add_row(vec![
+        cell("Prices"),
+        cell(match &config.price_url {
+            Some(url) => format!(
+                "{} from {} ({})",
+                config.price_currency, url, config.price_pointer
+            ),
+            None => "off".to_string(),
+        }),
+    ]);
     table.add_row(vec![
         cell("Data dir"),
         cell(WalletConfig::data_dir()?.display()),
     ]);
+    let keystore_dir = WalletConfig::keystore_dir()?;
+    if keystore_dir != WalletConfig::data_dir()? {
+        table.add_row(vec![cell("Keystores"), cell(keystore_dir.display())]);
+    }
     table.add_row(vec![cell("Wallets"), cell(config.wallets.len())]);
 
     print_table(&table);
@@ -67,3 +150,106 @@ pub fn run(rpc_url: Option<&str>, network: Option<&str>, json: bool) -> Result<(
 
     Ok(())
 }
+
+pub fn use_profile(name: &str) -> Result<(), WalletError> {
+    let mut config = WalletConfig::load()?;
+    if name == "none" {
+        config.use_profile(None)?;
+        config.save()?;
+        print_success(&format!(
+            "Profile cleared; using RPC URL {}",
+            style_bold().apply_to(config.base_rpc_url())
+        ));
+        return Ok(());
+    }
+    config.use_profile(Some(name))?;
+    config.save()?;
+    let profile = config.find_profile(name)?;
+    print_success(&format!(
+        "Using profile '{}': {} ({})",
+        name, profile.rpc_url, profile.network
+    ));
+    Ok(())
+}
+
+pub fn profiles(json: bool) -> Result<(), WalletError> {
+    let config = WalletConfig::load()?;
+    let profiles = config.all_profiles();
+
+    if json {
+        return print_json(&profiles);
+    }
+
+    println!();
+    println!("  {}", style_bold().apply_to("Network Profiles"));
+
+    let mut table = data_table(&["", "Profile", "RPC URL", "Network", "Keystores"]);
+    for (name, profile) in &profiles {
+        let active = config.active_profile() == Some(name.as_str());
+        table.add_row(vec![
+            if active { cell_green("*") } else { cell("") },
+            cell(name),
+            cell(&profile.rpc_url),
+            cell(&profile.network),
+            cell_dim(
+                profile
+                    .keystore_dir
+                    .as_ref()
+                    .map(|d| d.display().to_string())
+                    .unwrap_or_else(|| "shared".to_string()),
+            ),
+        ]);
+    }
+    print_table(&table);
+    if config.active_profile().is_none() {
+        println!(
+            "  {}",
+            style_dim().apply_to(
+                "No profile selected; select one with: norn wallet config use-profile <NAME>"
+            )
+        );
+    }
+    println!();
+
+    Ok(())
+}
+
+pub fn add_profile(
+    name: &str,
+    rpc_url: &str,
+    network: &str,
+    keystore_dir: Option<&Path>,
+) -> Result<(), WalletError> {
+    if !rpc_url.starts_with("http://") && !rpc_url.starts_with("https://") {
+        return Err(WalletError::ConfigError(format!(
+            "RPC URL '{}' must start with http:// or https://",
+            rpc_url
+        )));
+    }
+    let keystore_dir = match keystore_dir {
+        Some(dir) if dir.is_relative() => Some(std::env::current_dir()?.join(dir)),
+        Some(dir) => Some(dir.to_path_buf()),
+        None => None,
+    };
+
+    let mut config = WalletConfig::load()?;
+    config.add_profile(
+        name,
+        Profile {
+            rpc_url: rpc_url.to_string(),
+            network: network.to_string(),
+            keystore_dir,
+        },
+    )?;
+    config.save()?;
+    print_success(&format!("Profile '{}' saved", name));
+    Ok(())
+}
+
+pub fn remove_profile(name: &str) -> Result<(), WalletError> {
+    let mut config = WalletConfig::load()?;
+    config.remove_profile(name)?;
+    config.save()?;
+    print_success(&format!("Profile '{}' removed", name));
+    Ok(())
+}
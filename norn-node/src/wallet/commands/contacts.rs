@@ -0,0 +1,158 @@
+use norn_types::primitives::Address;
+
+use crate::wallet::config::WalletConfig;
+use crate::wallet::error::WalletError;
+use crate::wallet::format::{
+    format_address, is_address_literal, parse_address, print_success, style_bold, style_dim,
+    style_warn,
+};
+use crate::wallet::rpc_client::RpcClient;
+use crate::wallet::ui::{cell, cell_dim, data_table, info_table, print_table};
+
+pub async fn add(
+    name: &str,
+    address: &str,
+    note: Option<&str>,
+    rpc_url: Option<&str>,
+) -> Result<(), WalletError> {
+    let mut config = WalletConfig::load()?;
+    let url = rpc_url.unwrap_or(&config.rpc_url).to_string();
+
+    // A registered name is pinned to its current owner, so later transfers
+    // of the name do not redirect payments.
+    let (addr, pinned_from) = if is_address_literal(address) {
+        (parse_address(address)?, None)
+    } else {
+        let rpc = RpcClient::new(&url)?;
+        match rpc.resolve_name(address).await? {
+            Some(resolution) => (parse_address(&resolution.owner)?, Some(address)),
+            None => {
+                return Err(WalletError::InvalidAddress(format!(
+                    "'{}' is not an address or a registered name",
+                    address
+                )))
+            }
+        }
+    };
+
+    let existing = config.contact_name(&addr).map(str::to_string);
+    config.add_contact(name, &addr, note)?;
+    config.save()?;
+    let name = name.to_lowercase();
+
+    println!();
+    print_success(&format!("Contact '{}' saved", name));
+    let mut table = info_table();
+    table.add_row(vec![cell("Address"), cell(format_address(&addr))]);
+    if let Some(registered) = pinned_from {
+        table.add_row(vec![cell("Pinned from"), cell(registered)]);
+    }
+    if let Some(note) = note {
+        table.add_row(vec![cell("Note"), cell(note)]);
+    }
+    print_table(&table);
+
+    if let Some(other) = existing {
+        println!(
+            "  {}",
+            style_dim().apply_to(format!("This address is also saved as '{}'.", other))
+        );
+    }
+
+    // Cross-check against the name registry; a node may not be reachable
+    // from the machine keeping the address book.
+    if pinned_from != Some(name.as_str()) {
+        if let Ok(rpc) = RpcClient::new(&url) {
+            if let Ok(Some(resolution)) = rpc.resolve_name(&name).await {
+                if let Ok(owner) = parse_address(&resolution.owner) {
+                    if owner != addr {
+                        warn_name_mismatch(&name, &addr, &owner);
+                    }
+                }
+            }
+        }
+    }
+    println!();
+
+    Ok(())
+}
+
+pub fn list(json: bool) -> Result<(), WalletError> {
+    let config = WalletConfig::load()?;
+
+    if json {
+        let contacts: Vec<serde_json::Value> = config
+            .contacts
+            .iter()
+            .map(|(name, contact)| {
+                serde_json::json!({
+                    "name": name,
+                    "address": format!("0x{}", contact.address),
+                    "note": contact.note,
+                })
+            })
+            .collect();
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&contacts).unwrap_or_default()
+        );
+        return Ok(());
+    }
+
+    if config.contacts.is_empty() {
+        println!("  No contacts.");
+        println!(
+            "  {}",
+            style_dim().apply_to("Add one with: norn wallet contacts add <NAME> <ADDRESS>")
+        );
+        return Ok(());
+    }
+
+    println!();
+    println!("  {}", style_bold().apply_to("Contacts"));
+
+    let mut table = data_table(&["Name", "Address", "Note"]);
+    for (name, contact) in &config.contacts {
+        table.add_row(vec![
+            cell(name),
+            cell(format!("0x{}", contact.address)),
+            cell_dim(contact.note.as_deref().unwrap_or("")),
+        ]);
+    }
+    print_table(&table);
+    println!();
+
+    Ok(())
+}
+
+pub fn remove(name: &str) -> Result<(), WalletError> {
+    let mut config = WalletConfig::load()?;
+    let contact = config.remove_contact(name)?;
+    config.save()?;
+    print_success(&format!(
+        "Contact '{}' (0x{}) removed",
+        name.to_lowercase(),
+        contact.address
+    ));
+    Ok(())
+}
+
+/// Warn that a contact and the registered name it shares point elsewhere.
+pub(super) fn warn_name_mismatch(name: &str, contact: &Address, owner: &Address) {
+    println!(
+        "  {}",
+        style_warn().apply_to(format!(
+            "The registered name '{}' belongs to {}, not this contact.",
+            name,
+            format_address(owner)
+        ))
+    );
+    println!(
+        "  {}",
+        style_dim().apply_to(format!(
+            "Payments to '{}' from this wallet go to the contact, {}.",
+            name,
+            format_address(contact)
+        ))
+    );
+}
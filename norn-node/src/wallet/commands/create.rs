@@ -1,18 +1,30 @@
+use norn_crypto::hd::{default_account_path, format_derivation_path, parse_derivation_path};
+
 use crate::wallet::config::WalletConfig;
 use crate::wallet::error::WalletError;
 use crate::wallet::format::{
     format_address, format_pubkey, print_mnemonic_box, print_success, style_bold,
 };
-use crate::wallet::keystore::Keystore;
-use crate::wallet::prompt::prompt_new_password;
+use crate::wallet::keystore::{KdfParams, Keystore};
+use crate::wallet::prompt::{prompt_new_password, prompt_passphrase};
 
-pub fn run(name: &str, passphrase: Option<&str>) -> Result<(), WalletError> {
+pub fn run(
+    name: &str,
+    passphrase: Option<&str>,
+    ask_passphrase: bool,
+    derivation_path: Option<&str>,
+) -> Result<(), WalletError> {
     // Check if wallet already exists
     let mut config = WalletConfig::load()?;
     if config.wallets.contains(&name.to_string()) {
         return Err(WalletError::WalletAlreadyExists(name.to_string()));
     }
 
+    let account_path = match derivation_path {
+        Some(path) => parse_derivation_path(path)?,
+        None => default_account_path(),
+    };
+
     // Generate mnemonic
     let mnemonic = norn_crypto::seed::generate_mnemonic();
     let phrase = mnemonic.to_string();
@@ -20,9 +32,17 @@ pub fn run(name: &str, passphrase: Option<&str>) -> Result<(), WalletError> {
 
     // Prompt for password
     let password = prompt_new_password()?;
+    let passphrase = prompt_passphrase(passphrase, ask_passphrase)?;
 
     // Create keystore
-    let ks = Keystore::create(name, &mnemonic, passphrase.unwrap_or(""), &password)?;
+    let ks = Keystore::create_at_path(
+        name,
+        &mnemonic,
+        &passphrase,
+        &password,
+        &account_path,
+        KdfParams::default(),
+    )?;
     ks.save()?;
 
     // Update config
@@ -41,6 +61,12 @@ pub fn run(name: &str, passphrase: Option<&str>) -> Result<(), WalletError> {
     );
     println!("  Address:    {}", format_address(&ks.address));
     println!("  Public key: {}", format_pubkey(&ks.public_key));
+    if derivation_path.is_some() {
+        println!("  Path:       {}/0'", format_derivation_path(&account_path));
+    }
+    if ks.file.has_passphrase {
+        println!("  Passphrase: yes (not part of the mnemonic below)");
+    }
 
     print_mnemonic_box(&words);
     print_success("Wallet saved and encrypted.");
@@ -0,0 +1,63 @@
+use norn_types::primitives::NATIVE_TOKEN_ID;
+
+use crate::wallet::config::WalletConfig;
+use crate::wallet::error::WalletError;
+use crate::wallet::format::{
+    format_amount_with_symbol, style_bold, style_dim, truncate_hex_string,
+};
+use crate::wallet::keystore::Keystore;
+use crate::wallet::rpc_client::RpcClient;
+use crate::wallet::ui::{cell, cell_right, data_table, print_table};
+
+pub async fn run(json: bool, rpc_url: Option<&str>) -> Result<(), WalletError> {
+    let config = WalletConfig::load()?;
+    let wallet_name = config.active_wallet_name()?;
+    let ks = Keystore::load(wallet_name)?;
+
+    let url = rpc_url.unwrap_or(&config.rpc_url);
+    let rpc = RpcClient::new(url)?;
+
+    let delegations = rpc.get_delegations(&hex::encode(ks.public_key)).await?;
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&delegations).unwrap_or_default()
+        );
+        return Ok(());
+    }
+
+    println!();
+    println!(
+        "  {} — {}",
+        style_bold().apply_to("Delegations"),
+        wallet_name
+    );
+    println!();
+
+    if delegations.is_empty() {
+        println!("  {}", style_dim().apply_to("No delegations."));
+    } else {
+        let mut table = data_table(&["Validator", "Delegated", "Pending", "Leaving", "Commission"]);
+
+        for d in &delegations {
+            let amount: u128 = d.amount.parse().unwrap_or(0);
+            let pending: u128 = d.pending.parse().unwrap_or(0);
+            let leaving: u128 = d.pending_undelegate.parse().unwrap_or(0);
+
+            table.add_row(vec![
+                cell(truncate_hex_string(&d.validator, 6)),
+                cell_right(format_amount_with_symbol(amount, &NATIVE_TOKEN_ID)),
+                cell_right(format_amount_with_symbol(pending, &NATIVE_TOKEN_ID)),
+                cell_right(format_amount_with_symbol(leaving, &NATIVE_TOKEN_ID)),
+                cell_right(format!("{}%", d.commission_bps as f64 / 100.0)),
+            ]);
+        }
+
+        print_table(&table);
+    }
+
+    println!();
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use norn_types::loom::{LoomRegistration, LOOM_DEPLOY_FEE};
+use norn_types::loom::{LoomRegistration, OperatorSet, LOOM_DEPLOY_FEE};
 use norn_types::primitives::NATIVE_TOKEN_ID;
 
 use crate::wallet::config::WalletConfig;
@@ -10,8 +10,15 @@ use crate::wallet::format::{
 use crate::wallet::keystore::Keystore;
 use crate::wallet::prompt::{confirm, prompt_password};
 use crate::wallet::rpc_client::RpcClient;
-
-pub async fn run(name: &str, yes: bool, rpc_url: Option<&str>) -> Result<(), WalletError> {
+use crate::wallet::signer::unlock_keypair;
+
+pub async fn run(
+    name: &str,
+    co_operators: &[String],
+    threshold: Option<u16>,
+    yes: bool,
+    rpc_url: Option<&str>,
+) -> Result<(), WalletError> {
     // Validate name locally.
     norn_types::loom::validate_loom_name(name).map_err(|e| WalletError::Other(e.to_string()))?;
 
@@ -19,6 +26,21 @@ pub async fn run(name: &str, yes: bool, rpc_url: Option<&str>) -> Result<(), Wal
     let wallet_name = config.active_wallet_name()?;
     let ks = Keystore::load(wallet_name)?;
 
+    let co_operators = co_operators
+        .iter()
+        .map(|hex_str| parse_pubkey(hex_str))
+        .collect::<Result<Vec<_>, _>>()?;
+    let operator_threshold = threshold.unwrap_or(1);
+    let operators = OperatorSet {
+        operators: std::iter::once(ks.public_key)
+            .chain(co_operators.iter().copied())
+            .collect(),
+        threshold: operator_threshold,
+    };
+    operators
+        .validate()
+        .map_err(|e| WalletError::Other(e.to_string()))?;
+
     let url = rpc_url.unwrap_or(&config.rpc_url);
     let rpc = RpcClient::new(url)?;
 
@@ -46,6 +68,16 @@ pub async fn run(name: &str, yes: bool, rpc_url: Option<&str>) -> Result<(), Wal
             format_address(&ks.address),
             wallet_name
         );
+        if !co_operators.is_empty() {
+            for op in &co_operators {
+                println!("  Co-operator: {}", style_dim().apply_to(hex::encode(op)));
+            }
+            println!(
+                "  Threshold: {} of {}",
+                operator_threshold,
+                operators.operators.len()
+            );
+        }
         println!(
             "  Fee:      {}",
             style_bold().apply_to(format_amount_with_symbol(LOOM_DEPLOY_FEE, &NATIVE_TOKEN_ID))
@@ -63,7 +95,7 @@ pub async fn run(name: &str, yes: bool, rpc_url: Option<&str>) -> Result<(), Wal
     }
 
     let password = prompt_password("Enter password")?;
-    let keypair = ks.decrypt_keypair(&password)?;
+    let keypair = unlock_keypair(&ks, &password)?;
 
     let now = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
@@ -78,6 +110,8 @@ pub async fn run(name: &str, yes: bool, rpc_url: Option<&str>) -> Result<(), Wal
         min_participants: 1,
         accepted_tokens: vec![NATIVE_TOKEN_ID],
         config_data: vec![],
+        co_operators,
+        operator_threshold,
     };
 
     let mut loom_reg = LoomRegistration {
@@ -124,3 +158,18 @@ pub async fn run(name: &str, yes: bool, rpc_url: Option<&str>) -> Result<(), Wal
 
     Ok(())
 }
+
+fn parse_pubkey(hex_str: &str) -> Result<[u8; 32], WalletError> {
+    let hex_str = hex_str.strip_prefix("0x").unwrap_or(hex_str);
+    let bytes = hex::decode(hex_str)
+        .map_err(|e| WalletError::Other(format!("invalid co-operator pubkey hex: {}", e)))?;
+    if bytes.len() != 32 {
+        return Err(WalletError::Other(format!(
+            "co-operator public key must be 32 bytes, got {}",
+            bytes.len()
+        )));
+    }
+    let mut pubkey = [0u8; 32];
+    pubkey.copy_from_slice(&bytes);
+    Ok(pubkey)
+}
@@ -2,8 +2,8 @@ use crate::wallet::config::WalletConfig;
 use crate::wallet::error::WalletError;
 use crate::wallet::format::{print_error, print_success, style_dim};
 use crate::wallet::keystore::Keystore;
-use crate::wallet::prompt::prompt_password;
 use crate::wallet::rpc_client::RpcClient;
+use crate::wallet::signer::unlock;
 use crate::wallet::ui::{cell, cell_bold, info_table, print_table};
 
 pub async fn run(loom_id: &str, input_hex: &str, rpc_url: Option<&str>) -> Result<(), WalletError> {
@@ -18,12 +18,11 @@ pub async fn run(loom_id: &str, input_hex: &str, rpc_url: Option<&str>) -> Resul
     let input_bytes = hex::decode(input_hex)
         .map_err(|e| WalletError::Other(format!("invalid input hex: {}", e)))?;
 
-    let password = prompt_password("Enter password")?;
-    let keypair = ks.decrypt_keypair(&password)?;
+    let mut signer = unlock(&ks)?;
 
-    let sender = norn_crypto::address::pubkey_to_address(&keypair.public_key());
+    let sender = norn_crypto::address::pubkey_to_address(&signer.public_key());
     let sender_hex = hex::encode(sender);
-    let pubkey_hex = hex::encode(keypair.public_key());
+    let pubkey_hex = hex::encode(signer.public_key());
 
     // Parse loom_id for signing message.
     let loom_id_bytes = hex::decode(loom_id.strip_prefix("0x").unwrap_or(loom_id))
@@ -35,7 +34,7 @@ pub async fn run(loom_id: &str, input_hex: &str, rpc_url: Option<&str>) -> Resul
         &input_bytes,
         &sender,
     ]);
-    let signature = keypair.sign(&signing_msg);
+    let signature = signer.sign(&signing_msg)?;
     let signature_hex = hex::encode(signature);
 
     let result = rpc
@@ -3,6 +3,7 @@ use crate::wallet::error::WalletError;
 use crate::wallet::format::{print_error, style_bold, style_warn};
 use crate::wallet::keystore::Keystore;
 use crate::wallet::prompt::{confirm, prompt_password};
+use crate::wallet::signer::{unlock_keypair, unlock_secret};
 
 pub fn run(
     name: Option<&str>,
@@ -15,7 +16,7 @@ pub fn run(
         None => config.active_wallet_name()?,
     };
 
-    let ks = Keystore::load(wallet_name)?;
+    let mut ks = Keystore::load(wallet_name)?;
 
     if !show_mnemonic && !show_private_key {
         print_error(
@@ -38,11 +39,20 @@ pub fn run(
     let password = prompt_password("Enter password")?;
 
     if show_mnemonic {
-        match ks.decrypt_mnemonic(&password)? {
+        match unlock_secret(&mut ks, &password, Keystore::decrypt_mnemonic)? {
             Some(phrase) => {
                 println!();
                 println!("  {}", style_bold().apply_to("Mnemonic:"));
                 println!("  {}", phrase);
+                if ks.file.has_passphrase {
+                    println!(
+                        "  {}",
+                        style_warn().apply_to(
+                            "This wallet also uses a BIP-39 passphrase, which is not shown. \
+                             The mnemonic alone restores a different wallet."
+                        )
+                    );
+                }
                 println!();
             }
             None => {
@@ -55,7 +65,7 @@ pub fn run(
     }
 
     if show_private_key {
-        let keypair = ks.decrypt_keypair(&password)?;
+        let keypair = unlock_keypair(&ks, &password)?;
         let sk_bytes = keypair.signing_key().to_bytes();
         println!();
         println!("  {}", style_bold().apply_to("Private key:"));
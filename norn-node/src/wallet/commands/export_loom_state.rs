@@ -0,0 +1,45 @@
+use norn_loom::state::LoomStateSnapshot;
+
+use crate::wallet::config::WalletConfig;
+use crate::wallet::error::WalletError;
+use crate::wallet::format::{print_success, style_bold, style_dim};
+use crate::wallet::rpc_client::RpcClient;
+
+pub async fn run(loom_id: &str, output: &str, rpc_url: Option<&str>) -> Result<(), WalletError> {
+    let config = WalletConfig::load()?;
+    let url = rpc_url.unwrap_or(&config.rpc_url);
+    let rpc = RpcClient::new(url)?;
+
+    let export = rpc.export_loom_state(loom_id).await?;
+    let snapshot_bytes = hex::decode(&export.snapshot_hex)
+        .map_err(|e| WalletError::Other(format!("invalid snapshot hex: {}", e)))?;
+
+    // Verify the snapshot locally so the backup is known-good before it is written.
+    let snapshot: LoomStateSnapshot = borsh::from_slice(&snapshot_bytes)
+        .map_err(|e| WalletError::Other(format!("invalid snapshot: {}", e)))?;
+    snapshot
+        .verify()
+        .map_err(|e| WalletError::Other(e.to_string()))?;
+
+    std::fs::write(output, &snapshot_bytes).map_err(|e| {
+        WalletError::Other(format!("failed to write snapshot file '{}': {}", output, e))
+    })?;
+
+    println!();
+    println!("  {}", style_bold().apply_to("Export Loom State"));
+    println!("  Loom ID:     {}", style_dim().apply_to(loom_id));
+    println!("  Entries:     {}", export.entry_count);
+    println!(
+        "  State hash:  {}",
+        style_dim().apply_to(&export.state_hash)
+    );
+    println!(
+        "  Merkle root: {}",
+        style_dim().apply_to(&export.merkle_root)
+    );
+    println!();
+    print_success(&format!("Snapshot written to {}", output));
+    println!();
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
+use super::transfer::resolve_recipient;
 use crate::wallet::config::WalletConfig;
 use crate::wallet::error::WalletError;
-use crate::wallet::format::{format_address, parse_address, print_error, print_success};
+use crate::wallet::format::{format_address, print_error, print_success};
 use crate::wallet::keystore::Keystore;
 use crate::wallet::rpc_client::RpcClient;
 
@@ -10,7 +11,7 @@ pub async fn run(address: Option<&str>, rpc_url: Option<&str>) -> Result<(), Wal
     let rpc = RpcClient::new(url)?;
 
     let addr = if let Some(a) = address {
-        parse_address(a)?
+        resolve_recipient(&rpc, &config, a).await?
     } else {
         let name = config.active_wallet_name()?;
         let ks = Keystore::load(name)?;
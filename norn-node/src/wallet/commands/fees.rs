@@ -3,12 +3,15 @@ use norn_types::primitives::NATIVE_TOKEN_ID;
 
 use crate::wallet::config::WalletConfig;
 use crate::wallet::error::WalletError;
-use crate::wallet::format::{format_amount_with_symbol, style_bold};
+use crate::wallet::format::{format_amount_with_symbol, load_price, style_bold};
 use crate::wallet::rpc_client::RpcClient;
 use crate::wallet::ui::{info_table, print_table};
 
 pub async fn run(json: bool, rpc_url: Option<&str>) -> Result<(), WalletError> {
     let config = WalletConfig::load()?;
+    if !json {
+        load_price(&config).await;
+    }
     let url = rpc_url.unwrap_or(&config.rpc_url);
     let rpc = RpcClient::new(url)?;
 
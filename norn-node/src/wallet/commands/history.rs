@@ -1,12 +1,26 @@
+use std::path::Path;
+
+use norn_types::primitives::NATIVE_TOKEN_ID;
+
 use crate::wallet::config::WalletConfig;
 use crate::wallet::error::WalletError;
-use crate::wallet::format::{style_bold, style_dim, truncate_hex_string};
+use crate::wallet::format::{
+    format_native, load_price, parse_address, print_success, style_bold, style_dim,
+    truncate_hex_string,
+};
+use crate::wallet::journal::{self, Journal, STATUS_CONFIRMED, STATUS_REJECTED};
 use crate::wallet::keystore::Keystore;
 use crate::wallet::rpc_client::RpcClient;
-use crate::wallet::ui::{cell, cell_green, cell_yellow, data_table, print_table};
+use crate::wallet::ui::{cell, cell_dim, cell_green, cell_yellow, data_table, print_table};
+
+/// Most recent node history entries reconciled into the local journal.
+const RECONCILE_WINDOW: u64 = 500;
 
 pub async fn run(limit: usize, json: bool, rpc_url: Option<&str>) -> Result<(), WalletError> {
     let config = WalletConfig::load()?;
+    if !json {
+        load_price(&config).await;
+    }
     let wallet_name = config.active_wallet_name()?;
     let ks = Keystore::load(wallet_name)?;
 
@@ -72,7 +86,11 @@ pub async fn run(limit: usize, json: bool, rpc_url: Option<&str>) -> Result<(),
         table.add_row(vec![
             cell(&time_str),
             dir_cell,
-            cell(&entry.human_readable),
+            cell(display_amount(
+                &entry.token_id,
+                &entry.amount,
+                &entry.human_readable,
+            )),
             cell(truncate_hex_string(counterparty, 6)),
             cell(memo),
         ]);
@@ -84,6 +102,128 @@ pub async fn run(limit: usize, json: bool, rpc_url: Option<&str>) -> Result<(),
     Ok(())
 }
 
+/// Show the local journal, after reconciling it with the node's history
+/// when the node is reachable; or export it as CSV.
+pub async fn run_local(
+    limit: usize,
+    json: bool,
+    csv: Option<&Path>,
+    rpc_url: Option<&str>,
+) -> Result<(), WalletError> {
+    let config = WalletConfig::load()?;
+    if !json {
+        load_price(&config).await;
+    }
+    let wallet_name = config.active_wallet_name()?;
+    let ks = Keystore::load(wallet_name)?;
+    let mut journal = Journal::open()?;
+
+    let url = rpc_url.unwrap_or(&config.rpc_url);
+    let history = match RpcClient::new(url) {
+        Ok(rpc) => rpc
+            .get_transaction_history(&hex::encode(ks.address), RECONCILE_WINDOW, 0)
+            .await
+            .ok(),
+        Err(_) => None,
+    };
+    let note = match history {
+        Some(history) => {
+            let stats = journal.reconcile(&ks.address, &history)?;
+            (stats.confirmed > 0 || stats.imported > 0).then(|| {
+                format!(
+                    "Reconciled with the node: {} confirmed, {} imported.",
+                    stats.confirmed, stats.imported
+                )
+            })
+        }
+        None => Some("Node unreachable; showing the journal as recorded.".to_string()),
+    };
+
+    if let Some(path) = csv {
+        let entries = journal.entries(&ks.address, None)?;
+        journal::write_csv(path, &entries)?;
+        print_success(&format!(
+            "{} journal entries exported to {}",
+            entries.len(),
+            path.display()
+        ));
+        return Ok(());
+    }
+
+    let entries = journal.entries(&ks.address, Some(limit))?;
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&entries).unwrap_or_default()
+        );
+        return Ok(());
+    }
+
+    println!();
+    if let Some(note) = note {
+        println!("  {}", style_dim().apply_to(note));
+    }
+    if entries.is_empty() {
+        println!("  {}", style_dim().apply_to("No journal entries."));
+        println!(
+            "  {}",
+            style_dim().apply_to("Transfers sent from this machine are recorded here.")
+        );
+        println!();
+        return Ok(());
+    }
+
+    println!(
+        "  {} ({})",
+        style_bold().apply_to("Local Transaction Journal"),
+        entries.len()
+    );
+
+    let mut table = data_table(&[
+        "Time",
+        "Dir",
+        "Amount",
+        "Counterparty",
+        "Status",
+        "Label",
+        "Memo",
+    ]);
+    for entry in &entries {
+        let dir_cell = if entry.direction == "sent" {
+            cell_yellow("SENT")
+        } else {
+            cell_green("RCVD")
+        };
+        let counterparty = parse_address(&entry.counterparty)
+            .ok()
+            .and_then(|addr| config.contact_name(&addr).map(str::to_string))
+            .unwrap_or_else(|| truncate_hex_string(&entry.counterparty, 6));
+        let status_cell = match entry.status.as_str() {
+            STATUS_CONFIRMED => cell_green(&entry.status),
+            STATUS_REJECTED => cell_yellow(&entry.status),
+            _ => cell(&entry.status),
+        };
+        table.add_row(vec![
+            cell(format_timestamp(entry.timestamp)),
+            dir_cell,
+            cell(display_amount(
+                &entry.token_id,
+                &entry.amount,
+                &entry.display,
+            )),
+            cell(counterparty),
+            status_cell,
+            cell(entry.label.as_deref().unwrap_or("")),
+            cell_dim(entry.memo.as_deref().unwrap_or("\u{2014}")),
+        ]);
+    }
+
+    print_table(&table);
+    println!();
+
+    Ok(())
+}
+
 /// Format a UNIX timestamp into a human-readable date string.
 fn format_timestamp(ts: u64) -> String {
     if ts == 0 {
@@ -95,3 +235,14 @@ fn format_timestamp(ts: u64) -> String {
         None => ts.to_string(),
     }
 }
+
+/// Native amounts are re-rendered in the configured denomination; other
+/// tokens keep the text they came with.
+fn display_amount(token_id: &str, amount: &str, fallback: &str) -> String {
+    if token_id == hex::encode(NATIVE_TOKEN_ID) {
+        if let Ok(raw) = amount.parse() {
+            return format_native(raw);
+        }
+    }
+    fallback.to_string()
+}
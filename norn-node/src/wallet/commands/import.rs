@@ -1,22 +1,39 @@
+use norn_crypto::hd::{default_account_path, parse_derivation_path};
+
 use crate::wallet::config::WalletConfig;
 use crate::wallet::error::WalletError;
-use crate::wallet::format::{format_address, format_pubkey, print_success, style_bold};
-use crate::wallet::keystore::Keystore;
-use crate::wallet::prompt::prompt_new_password;
+use crate::wallet::format::{
+    format_address, format_pubkey, parse_address, parse_pubkey, print_success, style_bold,
+};
+use crate::wallet::keystore::{KdfParams, Keystore};
+use crate::wallet::ledger::Ledger;
+use crate::wallet::prompt::{prompt_new_password, prompt_passphrase};
 
 use dialoguer::Password;
 
+#[allow(clippy::too_many_arguments)]
 pub fn run(
     use_mnemonic: bool,
     private_key: Option<&str>,
+    watch_only: Option<&str>,
+    ledger_account: Option<u32>,
     name: &str,
     passphrase: Option<&str>,
+    ask_passphrase: bool,
+    derivation_path: Option<&str>,
 ) -> Result<(), WalletError> {
     let mut config = WalletConfig::load()?;
     if config.wallets.contains(&name.to_string()) {
         return Err(WalletError::WalletAlreadyExists(name.to_string()));
     }
 
+    if let Some(watched) = watch_only {
+        return import_watch_only(&mut config, watched, name);
+    }
+    if let Some(account) = ledger_account {
+        return import_ledger(&mut config, account, name);
+    }
+
     let password = prompt_new_password()?;
 
     let ks = if use_mnemonic {
@@ -26,7 +43,19 @@ pub fn run(
             .interact()
             .map_err(|e| WalletError::IoError(std::io::Error::other(e)))?;
         let mnemonic = norn_crypto::seed::parse_mnemonic(&phrase)?;
-        Keystore::create(name, &mnemonic, passphrase.unwrap_or(""), &password)?
+        let passphrase = prompt_passphrase(passphrase, ask_passphrase)?;
+        let account_path = match derivation_path {
+            Some(path) => parse_derivation_path(path)?,
+            None => default_account_path(),
+        };
+        Keystore::create_at_path(
+            name,
+            &mnemonic,
+            &passphrase,
+            &password,
+            &account_path,
+            KdfParams::default(),
+        )?
     } else if let Some(pk_hex) = private_key {
         let hex_str = pk_hex.strip_prefix("0x").unwrap_or(pk_hex);
         let bytes = hex::decode(hex_str)
@@ -39,10 +68,10 @@ pub fn run(
         }
         let mut seed = [0u8; 32];
         seed.copy_from_slice(&bytes);
-        Keystore::from_private_key(name, &seed, &password)?
+        Keystore::from_private_key(name, &seed, &password, KdfParams::default())?
     } else {
         return Err(WalletError::Other(
-            "specify --mnemonic or --private-key".to_string(),
+            "specify --mnemonic, --private-key, --watch-only or --ledger".to_string(),
         ));
     };
 
@@ -66,3 +95,75 @@ pub fn run(
 
     Ok(())
 }
+
+/// Import a thread by public key or thread ID, without its key.
+fn import_watch_only(
+    config: &mut WalletConfig,
+    watched: &str,
+    name: &str,
+) -> Result<(), WalletError> {
+    let hex_str = watched.strip_prefix("0x").unwrap_or(watched);
+    let ks = if hex_str.len() == 64 {
+        let pubkey = parse_pubkey(watched)?;
+        let address = norn_crypto::address::pubkey_to_address(&pubkey);
+        Keystore::watch_only(name, address, Some(pubkey))?
+    } else {
+        Keystore::watch_only(name, parse_address(watched)?, None)?
+    };
+
+    ks.save()?;
+
+    config.add_wallet(name);
+    if config.active_wallet.is_none() {
+        config.active_wallet = Some(name.to_string());
+    }
+    config.save()?;
+
+    println!();
+    println!(
+        "  {} {}",
+        style_bold().apply_to("Watch-only wallet imported:"),
+        style_bold().apply_to(name)
+    );
+    println!("  Address:    {}", format_address(&ks.address));
+    if ks.public_key != [0u8; 32] {
+        println!("  Public key: {}", format_pubkey(&ks.public_key));
+    }
+    print_success(
+        "Wallet saved. Knots built with it must be signed on the device holding its key.",
+    );
+
+    Ok(())
+}
+
+/// Import the key of a Ledger account, after the user checks its address on
+/// the device.
+fn import_ledger(config: &mut WalletConfig, account: u32, name: &str) -> Result<(), WalletError> {
+    let mut ledger = Ledger::connect()?;
+    let public_key = ledger.public_key(account, false)?;
+    let ks = Keystore::ledger(name, public_key, account)?;
+
+    println!();
+    println!("  Address:    {}", format_address(&ks.address));
+    println!("  Public key: {}", format_pubkey(&ks.public_key));
+    println!("  Check that your Ledger shows the same address, then approve it.");
+    ledger.public_key(account, true)?;
+
+    ks.save()?;
+
+    config.add_wallet(name);
+    if config.active_wallet.is_none() {
+        config.active_wallet = Some(name.to_string());
+    }
+    config.save()?;
+
+    println!();
+    println!(
+        "  {} {}",
+        style_bold().apply_to("Ledger wallet imported:"),
+        style_bold().apply_to(name)
+    );
+    print_success("Wallet saved. Its key stays on the Ledger; knots are approved on the device.");
+
+    Ok(())
+}
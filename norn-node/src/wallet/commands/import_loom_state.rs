@@ -0,0 +1,98 @@
+use norn_loom::state::LoomStateSnapshot;
+
+use crate::wallet::config::WalletConfig;
+use crate::wallet::error::WalletError;
+use crate::wallet::format::{print_error, print_success, style_bold, style_dim};
+use crate::wallet::keystore::Keystore;
+use crate::wallet::prompt::{confirm, prompt_password};
+use crate::wallet::rpc_client::RpcClient;
+use crate::wallet::signer::unlock_keypair;
+
+pub async fn run(
+    loom_id: &str,
+    input: &str,
+    yes: bool,
+    rpc_url: Option<&str>,
+) -> Result<(), WalletError> {
+    let config = WalletConfig::load()?;
+    let wallet_name = config.active_wallet_name()?;
+    let ks = Keystore::load(wallet_name)?;
+
+    let url = rpc_url.unwrap_or(&config.rpc_url);
+    let rpc = RpcClient::new(url)?;
+
+    let snapshot_bytes = std::fs::read(input).map_err(|e| {
+        WalletError::Other(format!("failed to read snapshot file '{}': {}", input, e))
+    })?;
+    let snapshot: LoomStateSnapshot = borsh::from_slice(&snapshot_bytes)
+        .map_err(|e| WalletError::Other(format!("invalid snapshot: {}", e)))?;
+    snapshot
+        .verify()
+        .map_err(|e| WalletError::Other(e.to_string()))?;
+
+    let loom_id_bytes = hex::decode(loom_id.strip_prefix("0x").unwrap_or(loom_id))
+        .map_err(|e| WalletError::Other(format!("invalid loom_id hex: {}", e)))?;
+    if snapshot.loom_id.as_slice() != loom_id_bytes.as_slice() {
+        return Err(WalletError::Other(format!(
+            "snapshot belongs to loom {}",
+            hex::encode(snapshot.loom_id)
+        )));
+    }
+
+    println!();
+    println!("  {}", style_bold().apply_to("Import Loom State"));
+    println!("  Loom ID:     {}", style_dim().apply_to(loom_id));
+    println!("  File:        {}", style_dim().apply_to(input));
+    println!("  Entries:     {}", snapshot.entries.len());
+    println!(
+        "  State hash:  {}",
+        style_dim().apply_to(hex::encode(snapshot.state_hash))
+    );
+    println!(
+        "  Merkle root: {}",
+        style_dim().apply_to(hex::encode(snapshot.merkle_root))
+    );
+    println!();
+
+    if !yes && !confirm("Replace the loom's current state with this snapshot?")? {
+        println!("  Cancelled.");
+        return Ok(());
+    }
+
+    let password = prompt_password("Enter password")?;
+    let keypair = unlock_keypair(&ks, &password)?;
+    let pubkey_hex = hex::encode(keypair.public_key());
+
+    // Sign: blake3(b"norn_import_loom_state" || loom_id || blake3(snapshot))
+    let snapshot_hash = norn_crypto::hash::blake3_hash(&snapshot_bytes);
+    let signing_msg = norn_crypto::hash::blake3_hash_multi(&[
+        b"norn_import_loom_state",
+        &loom_id_bytes,
+        &snapshot_hash,
+    ]);
+    let signature_hex = hex::encode(keypair.sign(&signing_msg));
+
+    let result = rpc
+        .import_loom_state(
+            loom_id,
+            &hex::encode(&snapshot_bytes),
+            &signature_hex,
+            &pubkey_hex,
+        )
+        .await?;
+
+    if result.success {
+        print_success("Loom state imported!");
+    } else {
+        print_error(
+            &format!(
+                "State import failed: {}",
+                result.reason.unwrap_or_else(|| "unknown".to_string())
+            ),
+            None,
+        );
+    }
+    println!();
+
+    Ok(())
+}
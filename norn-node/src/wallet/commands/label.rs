@@ -0,0 +1,20 @@
+use crate::wallet::config::WalletConfig;
+use crate::wallet::error::WalletError;
+use crate::wallet::format::{print_success, truncate_hex_string};
+use crate::wallet::journal::Journal;
+use crate::wallet::keystore::Keystore;
+
+pub fn run(knot_id: &str, label: Option<&str>) -> Result<(), WalletError> {
+    let config = WalletConfig::load()?;
+    let wallet_name = config.active_wallet_name()?;
+    let ks = Keystore::load(wallet_name)?;
+
+    let journal = Journal::open()?;
+    let full_id = journal.set_label(&ks.address, knot_id, label)?;
+    let short_id = truncate_hex_string(&full_id, 8);
+    match label {
+        Some(label) => print_success(&format!("Labelled {} \"{}\"", short_id, label)),
+        None => print_success(&format!("Label removed from {}", short_id)),
+    }
+    Ok(())
+}
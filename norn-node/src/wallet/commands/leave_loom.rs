@@ -4,6 +4,7 @@ use crate::wallet::format::{format_address, print_error, print_success, style_bo
 use crate::wallet::keystore::Keystore;
 use crate::wallet::prompt::prompt_password;
 use crate::wallet::rpc_client::RpcClient;
+use crate::wallet::signer::unlock_keypair;
 
 pub async fn run(loom_id: &str, rpc_url: Option<&str>) -> Result<(), WalletError> {
     let config = WalletConfig::load()?;
@@ -24,7 +25,7 @@ pub async fn run(loom_id: &str, rpc_url: Option<&str>) -> Result<(), WalletError
     println!();
 
     let password = prompt_password("Enter password")?;
-    let keypair = ks.decrypt_keypair(&password)?;
+    let keypair = unlock_keypair(&ks, &password)?;
 
     let participant_hex = hex::encode(ks.address);
     let pubkey_hex = hex::encode(keypair.public_key());
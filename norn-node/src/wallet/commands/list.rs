@@ -2,6 +2,7 @@ use crate::wallet::config::WalletConfig;
 use crate::wallet::error::WalletError;
 use crate::wallet::format::{format_address, style_bold, style_dim};
 use crate::wallet::keystore::Keystore;
+use crate::wallet::output::{print_json, WalletSummary};
 use crate::wallet::ui::{cell, cell_green, data_table, print_table};
 
 pub fn run(json: bool) -> Result<(), WalletError> {
@@ -22,22 +23,20 @@ pub fn run(json: bool) -> Result<(), WalletError> {
     }
 
     if json {
-        let wallets: Vec<serde_json::Value> = names
+        let wallets: Vec<WalletSummary> = names
             .iter()
             .filter_map(|name| {
                 let ks = Keystore::load(name).ok()?;
-                Some(serde_json::json!({
-                    "name": name,
-                    "address": format_address(&ks.address),
-                    "active": config.active_wallet.as_deref() == Some(name.as_str()),
-                }))
+                Some(WalletSummary {
+                    name: name.clone(),
+                    address: format_address(&ks.address),
+                    active: config.active_wallet.as_deref() == Some(name.as_str()),
+                    watch_only: ks.is_watch_only(),
+                    ledger: ks.is_ledger(),
+                })
             })
             .collect();
-        println!(
-            "{}",
-            serde_json::to_string_pretty(&wallets).unwrap_or_default()
-        );
-        return Ok(());
+        return print_json(&wallets);
     }
 
     println!();
@@ -50,10 +49,18 @@ pub fn run(json: bool) -> Result<(), WalletError> {
 
         match Keystore::load(name) {
             Ok(ks) => {
-                let status_cell = if active {
-                    cell_green("\u{25cf} active")
+                let kind = if ks.is_ledger() {
+                    Some("ledger")
+                } else if ks.is_watch_only() {
+                    Some("watch-only")
                 } else {
-                    cell("")
+                    None
+                };
+                let status_cell = match (active, kind) {
+                    (true, Some(kind)) => cell_green(format!("\u{25cf} active ({})", kind)),
+                    (true, None) => cell_green("\u{25cf} active"),
+                    (false, Some(kind)) => cell(kind),
+                    (false, None) => cell(""),
                 };
                 table.add_row(vec![
                     cell(name),
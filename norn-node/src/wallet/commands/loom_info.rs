@@ -12,7 +12,11 @@ pub async fn run(loom_id: &str, json: bool, rpc_url: Option<&str>) -> Result<(),
     let loom_info = match rpc.get_loom_info(loom_id).await? {
         Some(info) => info,
         None => {
-            print_error(&format!("loom '{}' not found", loom_id), None);
+            if json {
+                println!("null");
+            } else {
+                print_error(&format!("loom '{}' not found", loom_id), None);
+            }
             return Ok(());
         }
     };
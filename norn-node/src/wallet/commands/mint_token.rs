@@ -1,3 +1,4 @@
+use super::transfer::resolve_recipient;
 use crate::wallet::config::WalletConfig;
 use crate::wallet::error::WalletError;
 use crate::wallet::format::{
@@ -7,6 +8,7 @@ use crate::wallet::format::{
 use crate::wallet::keystore::Keystore;
 use crate::wallet::prompt::{confirm, prompt_password};
 use crate::wallet::rpc_client::RpcClient;
+use crate::wallet::signer::unlock_keypair;
 
 pub async fn run(
     token: &str,
@@ -32,17 +34,7 @@ pub async fn run(
         return Err(WalletError::Other("amount must be > 0".to_string()));
     }
 
-    // Parse recipient address.
-    let to_hex = to.strip_prefix("0x").unwrap_or(to);
-    let to_bytes = hex::decode(to_hex)
-        .map_err(|_| WalletError::Other("invalid recipient address".to_string()))?;
-    if to_bytes.len() != 20 {
-        return Err(WalletError::Other(
-            "recipient address must be 20 bytes".to_string(),
-        ));
-    }
-    let mut to_addr = [0u8; 20];
-    to_addr.copy_from_slice(&to_bytes);
+    let to_addr = resolve_recipient(&rpc, &config, to).await?;
 
     // Verify caller is the token creator.
     let authority = norn_crypto::address::pubkey_to_address(&ks.public_key);
@@ -91,7 +83,7 @@ pub async fn run(
     }
 
     let password = prompt_password("Enter password")?;
-    let keypair = ks.decrypt_keypair(&password)?;
+    let keypair = unlock_keypair(&ks, &password)?;
 
     let now = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
@@ -1,51 +1,80 @@
+pub mod accounts;
 pub mod address;
 pub mod balance;
+pub mod batch_transfer;
 pub mod block;
+pub mod broadcast;
 pub mod burn_token;
 pub mod change_password;
 pub mod commit;
 pub mod config_cmd;
+pub mod contacts;
 pub mod create;
 pub mod create_token;
+pub mod delegate;
+pub mod delegations;
 pub mod delete;
 pub mod deploy_loom;
 pub mod execute_loom;
 pub mod export;
+pub mod export_loom_state;
 pub mod faucet;
 pub mod fees;
 pub mod history;
 pub mod import;
+pub mod import_loom_state;
 pub mod join_loom;
+pub mod label;
 pub mod leave_loom;
 pub mod list;
 pub mod list_looms;
 pub mod list_tokens;
 pub mod loom_info;
 pub mod mint_token;
+pub mod multi_transfer;
 pub mod name_records;
 pub mod names;
 pub mod new_loom;
 pub mod node_info;
+pub mod propose_swap;
 pub mod query_loom;
 pub mod register;
 pub mod register_name;
+pub mod rekey;
 pub mod rename;
 pub mod resolve;
 pub mod reverse_resolve;
 pub mod rewards;
+pub mod rotate_loom_operator;
+pub mod set_commission;
+pub mod set_cosigners;
 pub mod set_name_record;
+pub mod set_participant_role;
+pub mod set_token_metadata;
+pub mod sign_knot;
 pub mod sign_message;
+pub mod sign_tx;
+pub mod sign_typed;
+pub mod spindle_bond;
+pub mod spindle_subscribe;
+pub mod spindle_unbond;
+pub mod spindle_unsubscribe;
+pub mod spindles;
 pub mod stake;
 pub mod staking_info;
 pub mod status;
+pub mod submit_knot;
 pub mod token_balances;
 pub mod token_info;
 pub mod transfer;
 pub mod transfer_name;
+pub mod undelegate;
+pub mod unjail;
 pub mod unstake;
 pub mod upload_bytecode;
 pub mod use_wallet;
 pub mod validators;
 pub mod verify_message;
+pub mod verify_typed;
 pub mod weave_state;
 pub mod whoami;
@@ -0,0 +1,289 @@
+use norn_types::constants::{MAX_MULTI_TRANSFERS, TRANSFER_FEE};
+use norn_types::knot::{Knot, KnotPayload, MultiTransferPayload, TransferPayload};
+use norn_types::primitives::{Address, TokenId, NATIVE_TOKEN_ID};
+
+use super::transfer::{
+    format_recipient, next_knot_sequence, resolve_recipient, resolve_transfer_token,
+};
+use crate::wallet::config::WalletConfig;
+use crate::wallet::error::WalletError;
+use crate::wallet::format::{
+    format_address, format_amount_with_symbol, format_token_amount_with_name, parse_token_amount,
+    print_divider, print_error, print_success, style_bold, style_dim, style_info,
+};
+use crate::wallet::journal::record_submission;
+use crate::wallet::keystore::Keystore;
+use crate::wallet::prompt::confirm;
+use crate::wallet::rpc_client::RpcClient;
+use crate::wallet::signer::{unlock, Signer};
+use crate::wallet::ui::{cell, cell_right, data_table, print_table};
+
+/// Split a `--to` argument of the form `<ADDRESS_OR_NAME>:<AMOUNT>`.
+fn parse_recipient_arg(arg: &str) -> Result<(&str, &str), WalletError> {
+    match arg.rsplit_once(':') {
+        Some((to, amount)) if !to.is_empty() && !amount.is_empty() => Ok((to, amount)),
+        _ => Err(WalletError::Other(format!(
+            "invalid recipient '{}': expected <ADDRESS_OR_NAME>:<AMOUNT>",
+            arg
+        ))),
+    }
+}
+
+pub async fn run(
+    recipients: &[String],
+    token: Option<&str>,
+    memo: Option<&str>,
+    label: Option<&str>,
+    yes: bool,
+    rpc_url: Option<&str>,
+) -> Result<(), WalletError> {
+    if recipients.is_empty() {
+        return Err(WalletError::Other(
+            "at least one --to recipient is required".to_string(),
+        ));
+    }
+    if recipients.len() > MAX_MULTI_TRANSFERS {
+        return Err(WalletError::Other(format!(
+            "too many recipients: {} (max {})",
+            recipients.len(),
+            MAX_MULTI_TRANSFERS
+        )));
+    }
+
+    let config = WalletConfig::load()?;
+    let wallet_name = config.active_wallet_name()?;
+    let ks = Keystore::load(wallet_name)?;
+
+    let url = rpc_url.unwrap_or(&config.rpc_url);
+    let rpc = RpcClient::new(url)?;
+
+    let (token_id, token_symbol, token_decimals) = resolve_transfer_token(&rpc, token).await?;
+
+    let mut legs: Vec<(Address, u128)> = Vec::with_capacity(recipients.len());
+    for arg in recipients {
+        let (to, amount_str) = parse_recipient_arg(arg)?;
+        let amount = parse_token_amount(amount_str, token_decimals)?;
+        if amount == 0 {
+            return Err(WalletError::InvalidAmount(format!(
+                "amount for '{}' must be greater than zero",
+                to
+            )));
+        }
+        legs.push((resolve_recipient(&rpc, &config, to).await?, amount));
+    }
+
+    let total = legs
+        .iter()
+        .try_fold(0u128, |acc, (_, amount)| acc.checked_add(*amount))
+        .ok_or_else(|| WalletError::InvalidAmount("total amount overflows".to_string()))?;
+    // Every transfer in the batch pays the transfer fee.
+    let total_fee = TRANSFER_FEE * legs.len() as u128;
+
+    // Pre-check sender balance.
+    let addr_hex = hex::encode(ks.address);
+    let balance_str = rpc.get_balance(&addr_hex, &hex::encode(token_id)).await?;
+    let current_balance: u128 = balance_str.parse().unwrap_or(0);
+
+    let required = if token_id == NATIVE_TOKEN_ID {
+        total.saturating_add(total_fee)
+    } else {
+        total
+    };
+    if current_balance < required {
+        return Err(WalletError::InsufficientBalance {
+            available: format_token_amount_with_name(
+                current_balance,
+                token_decimals,
+                &token_symbol,
+            ),
+            required: format_token_amount_with_name(required, token_decimals, &token_symbol),
+        });
+    }
+    if token_id != NATIVE_TOKEN_ID {
+        // Also check NORN balance for fees.
+        let norn_balance_str = rpc
+            .get_balance(&addr_hex, &hex::encode(NATIVE_TOKEN_ID))
+            .await?;
+        let norn_balance: u128 = norn_balance_str.parse().unwrap_or(0);
+        if norn_balance < total_fee {
+            return Err(WalletError::InsufficientBalance {
+                available: format_amount_with_symbol(norn_balance, &NATIVE_TOKEN_ID),
+                required: format_amount_with_symbol(total_fee, &NATIVE_TOKEN_ID),
+            });
+        }
+    }
+
+    // Show confirmation
+    if !yes {
+        println!();
+        println!("  {}", style_bold().apply_to("Multi-Transfer Summary"));
+        print_divider();
+        println!(
+            "  From:    {} ({})",
+            format_address(&ks.address),
+            wallet_name
+        );
+        let mut table = data_table(&["#", "To", "Amount"]);
+        for (i, (to, amount)) in legs.iter().enumerate() {
+            table.add_row(vec![
+                cell(i + 1),
+                cell(format_recipient(&config, to)),
+                cell_right(format_token_amount_with_name(
+                    *amount,
+                    token_decimals,
+                    &token_symbol,
+                )),
+            ]);
+        }
+        print_table(&table);
+        println!(
+            "  Total:   {}",
+            style_bold().apply_to(format_token_amount_with_name(
+                total,
+                token_decimals,
+                &token_symbol
+            ))
+        );
+        println!(
+            "  Fees:    {}",
+            style_dim().apply_to(format_amount_with_symbol(total_fee, &NATIVE_TOKEN_ID))
+        );
+        println!(
+            "  Balance: {}",
+            style_dim().apply_to(format_token_amount_with_name(
+                current_balance,
+                token_decimals,
+                &token_symbol
+            ))
+        );
+        if let Some(m) = memo {
+            println!("  Memo:    \"{}\"", m);
+        }
+        println!();
+
+        if !confirm(&format!("Confirm {} transfers?", legs.len()))? {
+            println!("  Cancelled.");
+            return Ok(());
+        }
+    }
+
+    let mut signer = unlock(&ks)?;
+    let sender_addr = norn_crypto::address::pubkey_to_address(&signer.public_key());
+    let sequence = next_knot_sequence(&rpc, &sender_addr).await?;
+    let signed_knot = build_multi_transfer(signer.as_mut(), token_id, &legs, memo, sequence)?;
+
+    // Serialize and submit
+    let bytes =
+        borsh::to_vec(&signed_knot).map_err(|e| WalletError::SerializationError(e.to_string()))?;
+    let result = rpc.submit_knot(&hex::encode(&bytes)).await?;
+    record_submission(
+        &sender_addr,
+        &signed_knot,
+        &result,
+        label,
+        Some((&token_symbol, token_decimals)),
+    );
+
+    if result.success {
+        print_success(&format!(
+            "{} transfers totalling {} sent!",
+            legs.len(),
+            format_token_amount_with_name(total, token_decimals, &token_symbol)
+        ));
+        println!(
+            "  Knot ID: {}",
+            style_info().apply_to(hex::encode(signed_knot.id))
+        );
+        println!(
+            "  {}",
+            style_dim().apply_to(format!(
+                "Remaining balance: {}",
+                format_token_amount_with_name(
+                    current_balance.saturating_sub(required),
+                    token_decimals,
+                    &token_symbol
+                )
+            ))
+        );
+    } else {
+        print_error(
+            &format!(
+                "Multi-transfer failed: {}",
+                result.reason.unwrap_or_else(|| "unknown".to_string())
+            ),
+            Some("No transfers were applied. Ensure your thread is registered and has sufficient balance."),
+        );
+    }
+    println!();
+
+    Ok(())
+}
+
+/// Build and sign a multi-transfer knot of one token from the signer's
+/// thread, at the given knot sequence.
+pub(super) fn build_multi_transfer(
+    signer: &mut dyn Signer,
+    token_id: TokenId,
+    legs: &[(Address, u128)],
+    memo: Option<&str>,
+    sequence: u64,
+) -> Result<Knot, WalletError> {
+    let sender_addr = norn_crypto::address::pubkey_to_address(&signer.public_key());
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    let memo_bytes = memo.map(|m| m.as_bytes().to_vec());
+    let payload = KnotPayload::MultiTransfer(MultiTransferPayload {
+        transfers: legs
+            .iter()
+            .map(|(to, amount)| TransferPayload {
+                token_id,
+                amount: *amount,
+                from: sender_addr,
+                to: *to,
+                memo: memo_bytes.clone(),
+            })
+            .collect(),
+    });
+
+    // Build knot with sender as sole participant (transfers are unilateral),
+    // continuing the sender's knot sequence.
+    let sender_state = norn_types::thread::ThreadState::new();
+    let knot = norn_thread::knot::KnotBuilder::multi_transfer(now)
+        .add_before_state(sender_addr, signer.public_key(), sequence, &sender_state)
+        .add_after_state(
+            sender_addr,
+            signer.public_key(),
+            sequence + 1,
+            &sender_state,
+        )
+        .with_payload(payload)
+        .build()?;
+
+    let sig = signer.sign(&knot.id)?;
+    let mut signed_knot = knot;
+    norn_thread::knot::add_signature(&mut signed_knot, sig);
+    Ok(signed_knot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_recipient_arg() {
+        assert_eq!(
+            parse_recipient_arg("alice:10.5").unwrap(),
+            ("alice", "10.5")
+        );
+        assert_eq!(
+            parse_recipient_arg("0xabcdef:1").unwrap(),
+            ("0xabcdef", "1")
+        );
+        assert!(parse_recipient_arg("alice").is_err());
+        assert!(parse_recipient_arg("alice:").is_err());
+        assert!(parse_recipient_arg(":5").is_err());
+    }
+}
@@ -0,0 +1,160 @@
+use norn_thread::proposal::{build_swap, PendingKnot, SwapSide, PROPOSAL_TTL};
+use norn_types::constants::TRANSFER_FEE;
+use norn_types::primitives::NATIVE_TOKEN_ID;
+
+use super::transfer::{
+    format_recipient, next_knot_sequence, resolve_recipient, resolve_transfer_token,
+};
+use crate::wallet::config::WalletConfig;
+use crate::wallet::error::WalletError;
+use crate::wallet::format::{
+    format_address, format_amount_with_symbol, format_token_amount_with_name, parse_pubkey,
+    parse_token_amount, print_divider, print_success, style_bold, style_dim, style_info,
+};
+use crate::wallet::keystore::Keystore;
+use crate::wallet::prompt::{confirm, prompt_password};
+use crate::wallet::rpc_client::RpcClient;
+use crate::wallet::signer::unlock_keypair;
+
+#[allow(clippy::too_many_arguments)]
+pub async fn run(
+    with: &str,
+    give: &str,
+    give_token: Option<&str>,
+    receive: &str,
+    receive_token: Option<&str>,
+    expiry: Option<u64>,
+    yes: bool,
+    rpc_url: Option<&str>,
+) -> Result<(), WalletError> {
+    let config = WalletConfig::load()?;
+    let wallet_name = config.active_wallet_name()?;
+    let ks = Keystore::load(wallet_name)?;
+
+    let url = rpc_url.unwrap_or(&config.rpc_url);
+    let rpc = RpcClient::new(url)?;
+
+    let (give_id, give_symbol, give_decimals) = resolve_transfer_token(&rpc, give_token).await?;
+    let (receive_id, receive_symbol, receive_decimals) =
+        resolve_transfer_token(&rpc, receive_token).await?;
+    let give_amount = parse_token_amount(give, give_decimals)?;
+    let receive_amount = parse_token_amount(receive, receive_decimals)?;
+    if give_amount == 0 || receive_amount == 0 {
+        return Err(WalletError::InvalidAmount(
+            "amounts must be greater than zero".to_string(),
+        ));
+    }
+
+    // The counterparty signs with the key registered for its thread.
+    let counterparty = resolve_recipient(&rpc, &config, with).await?;
+    if counterparty == ks.address {
+        return Err(WalletError::Other("cannot swap with yourself".to_string()));
+    }
+    let (counterparty_pubkey, counterparty_sequence) =
+        match rpc.get_thread(&hex::encode(counterparty)).await? {
+            Some(thread) => (parse_pubkey(&thread.owner)?, thread.nonce),
+            None => {
+                return Err(WalletError::Other(format!(
+                    "counterparty {} has no registered thread",
+                    format_address(&counterparty)
+                )))
+            }
+        };
+
+    // Pre-check our side of the swap.
+    let addr_hex = hex::encode(ks.address);
+    let balance_str = rpc.get_balance(&addr_hex, &hex::encode(give_id)).await?;
+    let current_balance: u128 = balance_str.parse().unwrap_or(0);
+    let required = if give_id == NATIVE_TOKEN_ID {
+        give_amount.saturating_add(TRANSFER_FEE)
+    } else {
+        give_amount
+    };
+    if current_balance < required {
+        return Err(WalletError::InsufficientBalance {
+            available: format_token_amount_with_name(current_balance, give_decimals, &give_symbol),
+            required: format_token_amount_with_name(required, give_decimals, &give_symbol),
+        });
+    }
+
+    let ttl = expiry.unwrap_or(PROPOSAL_TTL);
+
+    // Show confirmation
+    if !yes {
+        println!();
+        println!("  {}", style_bold().apply_to("Swap Proposal"));
+        print_divider();
+        println!("  With:    {}", format_recipient(&config, &counterparty));
+        println!(
+            "  Give:    {}",
+            style_bold().apply_to(format_token_amount_with_name(
+                give_amount,
+                give_decimals,
+                &give_symbol
+            ))
+        );
+        println!(
+            "  Receive: {}",
+            style_bold().apply_to(format_token_amount_with_name(
+                receive_amount,
+                receive_decimals,
+                &receive_symbol
+            ))
+        );
+        println!(
+            "  Fee:     {}",
+            style_dim().apply_to(format_amount_with_symbol(TRANSFER_FEE, &NATIVE_TOKEN_ID))
+        );
+        println!("  Expires: in {}s", ttl);
+        println!();
+
+        if !confirm("Propose this swap?")? {
+            println!("  Cancelled.");
+            return Ok(());
+        }
+    }
+
+    let password = prompt_password("Enter password")?;
+    let keypair = unlock_keypair(&ks, &password)?;
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    let ours = SwapSide {
+        address: ks.address,
+        pubkey: keypair.public_key(),
+        version: next_knot_sequence(&rpc, &ks.address).await?,
+        token_id: give_id,
+        amount: give_amount,
+    };
+    let theirs = SwapSide {
+        address: counterparty,
+        pubkey: counterparty_pubkey,
+        version: counterparty_sequence,
+        token_id: receive_id,
+        amount: receive_amount,
+    };
+    let knot = build_swap(&ours, &theirs, now, Some(now + ttl))?;
+    let mut pending = PendingKnot::new(knot, now)?;
+    pending.sign(&keypair)?;
+
+    let bytes = borsh::to_vec(pending.knot())
+        .map_err(|e| WalletError::SerializationError(e.to_string()))?;
+    let info = rpc.propose_knot(&hex::encode(&bytes)).await?;
+
+    print_success("Swap proposed!");
+    println!("  Knot ID: {}", style_info().apply_to(&info.knot_id));
+    println!(
+        "  {}",
+        style_dim().apply_to(format!(
+            "Ask {} to run `norn wallet sign-knot {}` before it expires.",
+            format_address(&counterparty),
+            info.knot_id
+        ))
+    );
+    println!();
+
+    Ok(())
+}
@@ -9,6 +9,7 @@ use crate::wallet::format::{format_address, print_error, print_success, style_bo
 use crate::wallet::keystore::Keystore;
 use crate::wallet::prompt::prompt_password;
 use crate::wallet::rpc_client::RpcClient;
+use crate::wallet::signer::unlock_keypair;
 
 pub async fn run(name: Option<&str>, rpc_url: Option<&str>) -> Result<(), WalletError> {
     let config = WalletConfig::load()?;
@@ -19,7 +20,7 @@ pub async fn run(name: Option<&str>, rpc_url: Option<&str>) -> Result<(), Wallet
 
     let ks = Keystore::load(wallet_name)?;
     let password = prompt_password("Enter password")?;
-    let keypair = ks.decrypt_keypair(&password)?;
+    let keypair = unlock_keypair(&ks, &password)?;
 
     let address = pubkey_to_address(&keypair.public_key());
     let initial_state = ThreadState::new();
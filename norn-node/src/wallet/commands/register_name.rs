@@ -11,6 +11,7 @@ use crate::wallet::format::{
 use crate::wallet::keystore::Keystore;
 use crate::wallet::prompt::{confirm, prompt_password};
 use crate::wallet::rpc_client::RpcClient;
+use crate::wallet::signer::unlock_keypair;
 
 pub async fn run(name: &str, yes: bool, rpc_url: Option<&str>) -> Result<(), WalletError> {
     // Validate name format locally first.
@@ -77,7 +78,7 @@ pub async fn run(name: &str, yes: bool, rpc_url: Option<&str>) -> Result<(), Wal
     }
 
     let password = prompt_password("Enter password")?;
-    let keypair = ks.decrypt_keypair(&password)?;
+    let keypair = unlock_keypair(&ks, &password)?;
     let sender_addr = norn_crypto::address::pubkey_to_address(&keypair.public_key());
 
     // Build a consensus-level NameRegistration (signed by the wallet keypair).
@@ -0,0 +1,63 @@
+use crate::wallet::config::WalletConfig;
+use crate::wallet::error::WalletError;
+use crate::wallet::format::print_success;
+use crate::wallet::keystore::{KdfParams, Keystore, WALLET_VERSION};
+use crate::wallet::prompt::{prompt_new_password, prompt_password};
+use crate::wallet::ui::{cell, info_table, print_table};
+
+pub fn run(
+    name: Option<&str>,
+    memory_mib: Option<u32>,
+    iterations: Option<u32>,
+    parallelism: Option<u32>,
+    new_password: bool,
+) -> Result<(), WalletError> {
+    let config = WalletConfig::load()?;
+    let wallet_name = match name {
+        Some(n) => n,
+        None => config.active_wallet_name()?,
+    };
+
+    let mut ks = Keystore::load(wallet_name)?;
+
+    let defaults = KdfParams::default();
+    let kdf = KdfParams {
+        memory_kib: memory_mib.map_or(defaults.memory_kib, |m| m.saturating_mul(1024)),
+        iterations: iterations.unwrap_or(defaults.iterations),
+        parallelism: parallelism.unwrap_or(defaults.parallelism),
+    };
+    kdf.validate()?;
+
+    let old_password = prompt_password("Enter current password")?;
+    let new_password = if new_password {
+        // Verify old password works before prompting for new one
+        ks.decrypt_keypair(&old_password)?;
+        prompt_new_password()?
+    } else {
+        old_password.clone()
+    };
+
+    let old_version = ks.file.version;
+    ks.rekey(&old_password, &new_password, kdf)?;
+
+    println!();
+    print_success(&format!("Wallet '{}' re-encrypted", wallet_name));
+    let mut table = info_table();
+    table.add_row(vec![
+        cell("Format"),
+        cell(format!("version {} (was {})", WALLET_VERSION, old_version)),
+    ]);
+    table.add_row(vec![
+        cell("KDF"),
+        cell(format!(
+            "Argon2id, {} MiB, {} passes, {} lanes",
+            kdf.memory_kib / 1024,
+            kdf.iterations,
+            kdf.parallelism
+        )),
+    ]);
+    print_table(&table);
+    println!();
+
+    Ok(())
+}
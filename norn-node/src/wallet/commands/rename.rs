@@ -6,7 +6,7 @@ use crate::wallet::keystore::validate_wallet_name;
 pub fn run(from: &str, to: &str) -> Result<(), WalletError> {
     validate_wallet_name(from)?;
     validate_wallet_name(to)?;
-    let wallet_dir = WalletConfig::data_dir()?;
+    let wallet_dir = WalletConfig::keystore_dir()?;
     let source = wallet_dir.join(format!("{}.json", from));
     let target = wallet_dir.join(format!("{}.json", to));
 
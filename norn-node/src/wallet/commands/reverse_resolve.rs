@@ -1,6 +1,7 @@
 use crate::wallet::config::WalletConfig;
 use crate::wallet::error::WalletError;
 use crate::wallet::format::{print_error, style_bold, style_info};
+use crate::wallet::output::{print_json, ReverseName};
 use crate::wallet::rpc_client::RpcClient;
 
 pub async fn run(address_hex: &str, json: bool, rpc_url: Option<&str>) -> Result<(), WalletError> {
@@ -11,10 +12,10 @@ pub async fn run(address_hex: &str, json: bool, rpc_url: Option<&str>) -> Result
     match rpc.reverse_name(address_hex).await? {
         Some(name) => {
             if json {
-                println!(
-                    "{}",
-                    serde_json::json!({ "address": address_hex, "name": name })
-                );
+                print_json(&ReverseName {
+                    address: address_hex.to_string(),
+                    name,
+                })?;
             } else {
                 println!();
                 println!(
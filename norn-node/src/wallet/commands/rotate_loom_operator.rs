@@ -0,0 +1,99 @@
+use norn_types::loom::{operator_handover_signing_data, OperatorHandover};
+
+use crate::wallet::config::WalletConfig;
+use crate::wallet::error::WalletError;
+use crate::wallet::format::{print_error, print_success, style_bold, style_dim};
+use crate::wallet::keystore::Keystore;
+use crate::wallet::prompt::{confirm, prompt_password};
+use crate::wallet::rpc_client::RpcClient;
+use crate::wallet::signer::unlock_keypair;
+
+pub async fn run(
+    loom_id: &str,
+    new_wallet: &str,
+    yes: bool,
+    rpc_url: Option<&str>,
+) -> Result<(), WalletError> {
+    let config = WalletConfig::load()?;
+    let wallet_name = config.active_wallet_name()?;
+    let old_ks = Keystore::load(wallet_name)?;
+    let new_ks = Keystore::load(new_wallet)?;
+
+    let url = rpc_url.unwrap_or(&config.rpc_url);
+    let rpc = RpcClient::new(url)?;
+
+    let loom_id_bytes = hex::decode(loom_id.strip_prefix("0x").unwrap_or(loom_id))
+        .map_err(|e| WalletError::Other(format!("invalid loom_id hex: {}", e)))?;
+    if loom_id_bytes.len() != 32 {
+        return Err(WalletError::Other("loom ID must be 32 bytes".to_string()));
+    }
+    let mut loom_id_arr = [0u8; 32];
+    loom_id_arr.copy_from_slice(&loom_id_bytes);
+
+    println!();
+    println!("  {}", style_bold().apply_to("Rotate Loom Operator"));
+    println!("  Loom ID: {}", style_dim().apply_to(loom_id));
+    println!(
+        "  From:    {} ({})",
+        style_dim().apply_to(hex::encode(old_ks.public_key)),
+        wallet_name
+    );
+    println!(
+        "  To:      {} ({})",
+        style_dim().apply_to(hex::encode(new_ks.public_key)),
+        new_wallet
+    );
+    println!();
+
+    if !yes && !confirm("Hand this loom's operator key over?")? {
+        println!("  Cancelled.");
+        return Ok(());
+    }
+
+    let old_keypair = unlock_keypair(
+        &old_ks,
+        &prompt_password(&format!("Password for '{}'", wallet_name))?,
+    )?;
+    let new_keypair = unlock_keypair(
+        &new_ks,
+        &prompt_password(&format!("Password for '{}'", new_wallet))?,
+    )?;
+
+    let mut handover = OperatorHandover {
+        loom_id: loom_id_arr,
+        old_operator: old_keypair.public_key(),
+        new_operator: new_keypair.public_key(),
+        timestamp: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+        old_signature: [0u8; 64],
+        new_signature: [0u8; 64],
+    };
+    let sig_data = operator_handover_signing_data(&handover);
+    handover.old_signature = old_keypair.sign(&sig_data);
+    handover.new_signature = new_keypair.sign(&sig_data);
+
+    let bytes =
+        borsh::to_vec(&handover).map_err(|e| WalletError::SerializationError(e.to_string()))?;
+    let result = rpc.submit_operator_handover(&hex::encode(&bytes)).await?;
+
+    if result.success {
+        print_success("Operator handover submitted!");
+        println!(
+            "  {}",
+            style_dim().apply_to("The new key takes over once the handover is in a block.")
+        );
+    } else {
+        print_error(
+            &format!(
+                "Operator handover failed: {}",
+                result.reason.unwrap_or_else(|| "unknown".to_string())
+            ),
+            None,
+        );
+    }
+    println!();
+
+    Ok(())
+}
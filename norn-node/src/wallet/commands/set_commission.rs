@@ -0,0 +1,91 @@
+use norn_types::constants::MAX_COMMISSION_BPS;
+use norn_types::weave::StakeOperation;
+
+use crate::wallet::config::WalletConfig;
+use crate::wallet::error::WalletError;
+use crate::wallet::format::{
+    format_address, print_divider, print_success, style_bold, style_dim, style_info,
+};
+use crate::wallet::keystore::Keystore;
+use crate::wallet::prompt::{confirm, prompt_password};
+use crate::wallet::rpc_client::RpcClient;
+use crate::wallet::signer::unlock_keypair;
+
+pub async fn run(commission_bps: u16, yes: bool, rpc_url: Option<&str>) -> Result<(), WalletError> {
+    if commission_bps > MAX_COMMISSION_BPS {
+        return Err(WalletError::Other(format!(
+            "commission must be at most {} bps",
+            MAX_COMMISSION_BPS
+        )));
+    }
+
+    let config = WalletConfig::load()?;
+    let wallet_name = config.active_wallet_name()?;
+    let ks = Keystore::load(wallet_name)?;
+
+    let url = rpc_url.unwrap_or(&config.rpc_url);
+    let rpc = RpcClient::new(url)?;
+
+    // Show confirmation.
+    if !yes {
+        println!();
+        println!("  {}", style_bold().apply_to("Set Validator Commission"));
+        print_divider();
+        println!(
+            "  Commission: {}",
+            style_info().apply_to(format!("{}%", commission_bps as f64 / 100.0))
+        );
+        println!(
+            "  Validator:  {} ({})",
+            format_address(&ks.address),
+            wallet_name
+        );
+        println!(
+            "  {}",
+            style_dim().apply_to("The new rate applies from the next epoch")
+        );
+        println!();
+
+        if !confirm("Set this commission?")? {
+            println!("  Cancelled.");
+            return Ok(());
+        }
+    }
+
+    let password = prompt_password("Enter password")?;
+    let keypair = unlock_keypair(&ks, &password)?;
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    let mut op = StakeOperation::SetCommission {
+        pubkey: keypair.public_key(),
+        commission_bps,
+        timestamp: now,
+        signature: [0u8; 64],
+    };
+
+    // Sign.
+    let sig_data = norn_weave::staking::stake_operation_signing_data(&op);
+    let signature = keypair.sign(&sig_data);
+    match &mut op {
+        StakeOperation::SetCommission { signature: s, .. } => *s = signature,
+        _ => unreachable!(),
+    }
+
+    // Submit via RPC.
+    let hex_data = hex::encode(borsh::to_vec(&op).map_err(|e| WalletError::Other(e.to_string()))?);
+    let result = rpc.submit_stake(&hex_data).await?;
+
+    if result.success {
+        print_success("Commission update submitted successfully");
+    } else {
+        return Err(WalletError::Other(
+            result.reason.unwrap_or_else(|| "unknown error".to_string()),
+        ));
+    }
+
+    Ok(())
+}
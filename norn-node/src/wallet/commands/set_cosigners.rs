@@ -0,0 +1,121 @@
+use std::path::Path;
+
+use norn_thread::rotation::{build_set_cosigners, validate_cosigner_set};
+use norn_types::thread::{CosignerSet, ThreadKeys};
+
+use super::transfer::{next_knot_sequence, thread_signer, write_pending_knot};
+use crate::wallet::config::WalletConfig;
+use crate::wallet::error::WalletError;
+use crate::wallet::format::{
+    format_address, format_pubkey, parse_pubkey, print_divider, print_error, print_success,
+    style_bold, style_info,
+};
+use crate::wallet::keystore::Keystore;
+use crate::wallet::prompt::confirm;
+use crate::wallet::rpc_client::RpcClient;
+use crate::wallet::signer::unlock;
+
+pub async fn run(
+    cosigners: &[String],
+    threshold: u8,
+    out: Option<&Path>,
+    yes: bool,
+    rpc_url: Option<&str>,
+) -> Result<(), WalletError> {
+    let config = WalletConfig::load()?;
+    let wallet_name = config.active_wallet_name()?;
+    let ks = Keystore::load(wallet_name)?;
+
+    let url = rpc_url.unwrap_or(&config.rpc_url);
+    let rpc = RpcClient::new(url)?;
+
+    let set = CosignerSet {
+        cosigners: cosigners
+            .iter()
+            .map(|pk| parse_pubkey(pk))
+            .collect::<Result<_, _>>()?,
+        threshold,
+    };
+    // Replacing existing cosigners needs their signatures, so it goes
+    // through a knot file like any other cosigned knot.
+    let owner = thread_signer(&rpc, &ks, out.is_some()).await?;
+    validate_cosigner_set(&set, &owner)?;
+    let epoch = rpc
+        .get_thread(&hex::encode(ks.address))
+        .await?
+        .map_or(0, |thread| thread.key_epoch);
+
+    if !yes {
+        println!();
+        println!("  {}", style_bold().apply_to("Set Cosigners"));
+        print_divider();
+        println!("  Thread:    {}", format_address(&ks.address));
+        if set.cosigners.is_empty() {
+            println!("  Cosigners: none (removes the thread's cosigners)");
+        } else {
+            println!(
+                "  Cosigners: {} of {} must sign each knot",
+                set.threshold,
+                set.cosigners.len()
+            );
+            for cosigner in &set.cosigners {
+                println!("    {}", format_pubkey(cosigner));
+            }
+        }
+        println!();
+
+        if !confirm("Set these cosigners?")? {
+            println!("  Cancelled.");
+            return Ok(());
+        }
+    }
+
+    let signer = if ks.is_watch_only() {
+        None
+    } else {
+        Some(unlock(&ks)?)
+    };
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let keys = ThreadKeys {
+        epoch,
+        ..ThreadKeys::new(owner)
+    };
+    let version = next_knot_sequence(&rpc, &ks.address).await?;
+    let knot = build_set_cosigners(ks.address, &keys, set, version, now);
+
+    if let Some(path) = out {
+        return write_pending_knot(path, knot, signer, now);
+    }
+    let mut signer = signer.ok_or_else(|| WalletError::WatchOnly(wallet_name.to_string()))?;
+
+    let mut signed_knot = knot;
+    let sig = signer.sign(&signed_knot.id)?;
+    norn_thread::knot::add_signature(&mut signed_knot, sig);
+
+    let bytes =
+        borsh::to_vec(&signed_knot).map_err(|e| WalletError::SerializationError(e.to_string()))?;
+    let result = rpc.submit_knot(&hex::encode(&bytes)).await?;
+
+    if result.success {
+        print_success("Cosigners set!");
+        println!(
+            "  Knot ID: {}",
+            style_info().apply_to(hex::encode(signed_knot.id))
+        );
+    } else {
+        print_error(
+            &format!(
+                "Setting cosigners failed: {}",
+                result.reason.unwrap_or_else(|| "unknown".to_string())
+            ),
+            None,
+        );
+    }
+    println!();
+
+    Ok(())
+}
@@ -8,6 +8,7 @@ use crate::wallet::format::{
 use crate::wallet::keystore::Keystore;
 use crate::wallet::prompt::{confirm, prompt_password};
 use crate::wallet::rpc_client::RpcClient;
+use crate::wallet::signer::unlock_keypair;
 
 pub async fn run(
     name: &str,
@@ -89,7 +90,7 @@ pub async fn run(
     }
 
     let password = prompt_password("Enter password")?;
-    let keypair = ks.decrypt_keypair(&password)?;
+    let keypair = unlock_keypair(&ks, &password)?;
     let sender_addr = norn_crypto::address::pubkey_to_address(&keypair.public_key());
 
     let now = std::time::SystemTime::now()
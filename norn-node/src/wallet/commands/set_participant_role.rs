@@ -0,0 +1,73 @@
+use norn_types::loom::ParticipantRole;
+
+use super::transfer::resolve_recipient;
+use crate::wallet::config::WalletConfig;
+use crate::wallet::error::WalletError;
+use crate::wallet::format::{format_address, print_error, print_success, style_bold, style_dim};
+use crate::wallet::keystore::Keystore;
+use crate::wallet::prompt::prompt_password;
+use crate::wallet::rpc_client::RpcClient;
+use crate::wallet::signer::unlock_keypair;
+
+pub async fn run(
+    loom_id: &str,
+    participant: &str,
+    role: &str,
+    rpc_url: Option<&str>,
+) -> Result<(), WalletError> {
+    let role: ParticipantRole = role.parse().map_err(WalletError::Other)?;
+
+    let config = WalletConfig::load()?;
+    let wallet_name = config.active_wallet_name()?;
+    let ks = Keystore::load(wallet_name)?;
+
+    let url = rpc_url.unwrap_or(&config.rpc_url);
+    let rpc = RpcClient::new(url)?;
+    let address = resolve_recipient(&rpc, &config, participant).await?;
+
+    println!();
+    println!("  {}", style_bold().apply_to("Set Participant Role"));
+    println!("  Loom ID:     {}", style_dim().apply_to(loom_id));
+    println!("  Participant: {}", format_address(&address));
+    println!("  Role:        {}", role.as_str());
+    println!();
+
+    let password = prompt_password("Enter password")?;
+    let keypair = unlock_keypair(&ks, &password)?;
+
+    // Sign: blake3(b"norn_set_participant_role" || loom_id || address || role)
+    let loom_id_bytes = hex::decode(loom_id)
+        .map_err(|e| WalletError::Other(format!("invalid loom_id hex: {}", e)))?;
+    let signing_msg = norn_crypto::hash::blake3_hash_multi(&[
+        b"norn_set_participant_role",
+        &loom_id_bytes,
+        &address,
+        role.as_str().as_bytes(),
+    ]);
+    let signature_hex = hex::encode(keypair.sign(&signing_msg));
+
+    let result = rpc
+        .set_participant_role(
+            loom_id,
+            &hex::encode(address),
+            role.as_str(),
+            &hex::encode(keypair.public_key()),
+            &signature_hex,
+        )
+        .await?;
+
+    if result.success {
+        print_success(&format!("Participant role set to {}", role.as_str()));
+    } else {
+        print_error(
+            &format!(
+                "Setting role failed: {}",
+                result.reason.unwrap_or_else(|| "unknown".to_string())
+            ),
+            None,
+        );
+    }
+    println!();
+
+    Ok(())
+}
@@ -0,0 +1,163 @@
+use norn_types::token::validate_token_metadata;
+use norn_types::weave::{TokenMetadata, TokenMetadataUpdate};
+
+use super::mint_token::{hex_to_token_id, resolve_token};
+use crate::wallet::config::WalletConfig;
+use crate::wallet::error::WalletError;
+use crate::wallet::format::{
+    format_address, print_divider, print_error, print_success, style_bold, style_dim, style_info,
+};
+use crate::wallet::keystore::Keystore;
+use crate::wallet::prompt::{confirm, prompt_password};
+use crate::wallet::rpc_client::RpcClient;
+use crate::wallet::signer::unlock_keypair;
+
+/// Fields not given keep their current value; an empty value removes the
+/// field. `clear` starts from empty metadata instead of the current one.
+#[allow(clippy::too_many_arguments)]
+pub async fn run(
+    token: &str,
+    description: Option<&str>,
+    icon_uri: Option<&str>,
+    project_url: Option<&str>,
+    tags: Option<&str>,
+    clear: bool,
+    yes: bool,
+    rpc_url: Option<&str>,
+) -> Result<(), WalletError> {
+    let config = WalletConfig::load()?;
+    let wallet_name = config.active_wallet_name()?;
+    let ks = Keystore::load(wallet_name)?;
+
+    let url = rpc_url.unwrap_or(&config.rpc_url);
+    let rpc = RpcClient::new(url)?;
+
+    let token_info = resolve_token(&rpc, token).await?;
+    let token_id = hex_to_token_id(&token_info.token_id)?;
+
+    // Verify caller is the token creator.
+    let authority = norn_crypto::address::pubkey_to_address(&ks.public_key);
+    let creator_hex = token_info
+        .creator
+        .strip_prefix("0x")
+        .unwrap_or(&token_info.creator);
+    if hex::encode(authority) != creator_hex {
+        print_error(
+            &format!(
+                "only the token creator ({}) can update metadata; your address is {}",
+                token_info.creator,
+                format_address(&authority)
+            ),
+            None,
+        );
+        return Ok(());
+    }
+
+    let mut metadata = if clear {
+        TokenMetadata::default()
+    } else {
+        TokenMetadata {
+            description: token_info.description.clone(),
+            icon_uri: token_info.icon_uri.clone(),
+            project_url: token_info.project_url.clone(),
+            tags: token_info.tags.clone(),
+        }
+    };
+    let non_empty = |v: &str| (!v.is_empty()).then(|| v.to_string());
+    if let Some(v) = description {
+        metadata.description = non_empty(v);
+    }
+    if let Some(v) = icon_uri {
+        metadata.icon_uri = non_empty(v);
+    }
+    if let Some(v) = project_url {
+        metadata.project_url = non_empty(v);
+    }
+    if let Some(v) = tags {
+        metadata.tags = v
+            .split(',')
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect();
+    }
+
+    if let Err(e) = validate_token_metadata(&metadata) {
+        print_error(&e.to_string(), None);
+        return Ok(());
+    }
+
+    // Show confirmation.
+    if !yes {
+        let show = |v: &Option<String>| v.clone().unwrap_or_else(|| "-".to_string());
+        println!();
+        println!("  {}", style_bold().apply_to("Set Token Metadata"));
+        print_divider();
+        println!(
+            "  Token:        {} ({})",
+            style_info().apply_to(&token_info.symbol),
+            &token_info.token_id[..16]
+        );
+        println!("  Description:  {}", show(&metadata.description));
+        println!("  Icon:         {}", show(&metadata.icon_uri));
+        println!("  Website:      {}", show(&metadata.project_url));
+        println!(
+            "  Tags:         {}",
+            if metadata.tags.is_empty() {
+                "-".to_string()
+            } else {
+                metadata.tags.join(", ")
+            }
+        );
+        println!(
+            "  {}",
+            style_dim().apply_to("This replaces the token's current metadata.")
+        );
+        println!();
+
+        if !confirm("Update token metadata?")? {
+            println!("  Cancelled.");
+            return Ok(());
+        }
+    }
+
+    let password = prompt_password("Enter password")?;
+    let keypair = unlock_keypair(&ks, &password)?;
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    let mut update = TokenMetadataUpdate {
+        token_id,
+        metadata,
+        authority,
+        authority_pubkey: keypair.public_key(),
+        timestamp: now,
+        signature: [0u8; 64],
+    };
+
+    let sig_data = norn_weave::token::token_metadata_update_signing_data(&update);
+    update.signature = keypair.sign(&sig_data);
+
+    let bytes =
+        borsh::to_vec(&update).map_err(|e| WalletError::SerializationError(e.to_string()))?;
+    let hex_data = hex::encode(&bytes);
+
+    let result = rpc.update_token_metadata(&hex_data).await?;
+
+    if result.success {
+        print_success(&format!("Metadata of {} updated", token_info.symbol));
+    } else {
+        print_error(
+            &format!(
+                "Metadata update failed: {}",
+                result.reason.unwrap_or_else(|| "unknown".to_string())
+            ),
+            None,
+        );
+    }
+    println!();
+
+    Ok(())
+}
@@ -0,0 +1,215 @@
+use std::path::Path;
+
+use norn_thread::proposal::{PendingKnot, UNSIGNED};
+use norn_types::knot::{Knot, KnotPayload};
+
+use crate::wallet::config::WalletConfig;
+use crate::wallet::error::WalletError;
+use crate::wallet::format::{
+    format_address, format_amount_with_symbol, format_pubkey, print_divider, print_error,
+    print_success, style_bold, style_dim, style_info,
+};
+use crate::wallet::keystore::Keystore;
+use crate::wallet::prompt::confirm;
+use crate::wallet::rpc_client::RpcClient;
+use crate::wallet::signer::unlock;
+use crate::wallet::tx_file::{read_knot_file, sign_pending, write_knot_file};
+use crate::wallet::ui::{cell, cell_right, data_table, print_table};
+
+pub async fn run(
+    knot_id: Option<&str>,
+    file: Option<&Path>,
+    out: Option<&Path>,
+    yes: bool,
+    rpc_url: Option<&str>,
+) -> Result<(), WalletError> {
+    let config = WalletConfig::load()?;
+    let wallet_name = config.active_wallet_name()?;
+    let ks = Keystore::load(wallet_name)?;
+    if ks.is_watch_only() {
+        return Err(WalletError::WatchOnly(wallet_name.to_string()));
+    }
+
+    // Knot files are signed offline; proposals are fetched from the node.
+    if let Some(path) = file {
+        let knot = read_knot_file(path)?;
+        return sign_file(&ks, knot, out.unwrap_or(path), yes);
+    }
+    let knot_id = knot_id
+        .ok_or_else(|| WalletError::Other("specify a knot ID or --file <FILE>".to_string()))?;
+
+    let url = rpc_url.unwrap_or(&config.rpc_url);
+    let rpc = RpcClient::new(url)?;
+
+    let knot_id = knot_id.strip_prefix("0x").unwrap_or(knot_id);
+    let info = rpc.get_knot_proposal(knot_id).await?.ok_or_else(|| {
+        WalletError::Other(format!(
+            "no knot proposal {} is waiting for signatures",
+            knot_id
+        ))
+    })?;
+    let bytes =
+        hex::decode(&info.knot).map_err(|e| WalletError::SerializationError(e.to_string()))?;
+    let knot: Knot =
+        borsh::from_slice(&bytes).map_err(|e| WalletError::SerializationError(e.to_string()))?;
+
+    // Show what signing commits us to.
+    if !yes {
+        print_knot_summary(&knot);
+        println!(
+            "  Missing: {}",
+            style_dim().apply_to(info.missing.join(", "))
+        );
+        println!();
+
+        if !confirm("Sign this knot?")? {
+            println!("  Cancelled.");
+            return Ok(());
+        }
+    }
+
+    let mut signer = unlock(&ks)?;
+    let mut pending = PendingKnot::new(knot, unix_now())?;
+    sign_pending(&mut pending, signer.as_mut())?;
+
+    let bytes = borsh::to_vec(pending.knot())
+        .map_err(|e| WalletError::SerializationError(e.to_string()))?;
+    let result = rpc.propose_knot(&hex::encode(&bytes)).await?;
+
+    if result.submitted {
+        print_success("All participants signed; knot submitted!");
+    } else if let Some(reason) = result.reason {
+        print_error(
+            &format!("Knot submission failed: {}", reason),
+            Some("No transfers were applied."),
+        );
+    } else {
+        print_success(&format!(
+            "Signed. Waiting for {} more signature(s).",
+            result.missing.len()
+        ));
+    }
+    println!();
+
+    Ok(())
+}
+
+/// Add this wallet's signature to a knot file and write it back.
+fn sign_file(ks: &Keystore, knot: Knot, out: &Path, yes: bool) -> Result<(), WalletError> {
+    let mut pending = PendingKnot::new(knot, unix_now())?;
+
+    if !yes {
+        print_knot_summary(pending.knot());
+        println!();
+        if !confirm("Sign this knot?")? {
+            println!("  Cancelled.");
+            return Ok(());
+        }
+    }
+
+    let mut signer = unlock(ks)?;
+    let cosigned = sign_pending(&mut pending, signer.as_mut())?;
+    write_knot_file(out, pending.knot())?;
+
+    print_success(&format!(
+        "{} and written to {}",
+        if cosigned { "Co-signed" } else { "Signed" },
+        out.display()
+    ));
+    if pending.is_complete() {
+        println!(
+            "  {}",
+            style_dim().apply_to(format!(
+                "Once every cosigner has signed, submit it with `norn wallet submit-knot {}`.",
+                out.display()
+            ))
+        );
+    } else {
+        println!(
+            "  {}",
+            style_dim().apply_to(format!(
+                "Still missing participant signatures from: {}",
+                pending
+                    .missing()
+                    .iter()
+                    .map(format_address)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+        );
+    }
+    println!();
+    Ok(())
+}
+
+/// Print what a knot does and who has signed it.
+pub(super) fn print_knot_summary(knot: &Knot) {
+    println!();
+    println!("  {}", style_bold().apply_to("Knot"));
+    print_divider();
+    println!("  Knot ID: {}", style_info().apply_to(hex::encode(knot.id)));
+    match &knot.payload {
+        KnotPayload::KeyRotation(p) => {
+            println!("  Rotate:  {}", format_address(&p.thread_id));
+            println!("  New key: {}", format_pubkey(&p.new_pubkey));
+        }
+        KnotPayload::SetGuardians(p) => {
+            println!(
+                "  Set {}-of-{} guardians for {}",
+                p.guardians.threshold,
+                p.guardians.guardians.len(),
+                format_address(&p.thread_id)
+            );
+        }
+        KnotPayload::SetCosigners(p) => {
+            println!(
+                "  Set {}-of-{} cosigners for {}",
+                p.cosigners.threshold,
+                p.cosigners.cosigners.len(),
+                format_address(&p.thread_id)
+            );
+            for cosigner in &p.cosigners.cosigners {
+                println!("    {}", format_pubkey(cosigner));
+            }
+        }
+        KnotPayload::Recovery(p) => {
+            println!("  Recover: {}", format_address(&p.thread_id));
+            println!("  New key: {}", format_pubkey(&p.new_pubkey));
+        }
+        _ => {
+            let mut table = data_table(&["From", "To", "Amount"]);
+            for (_, transfer) in norn_thread::knot::transfer_legs(knot) {
+                table.add_row(vec![
+                    cell(format_address(&transfer.from)),
+                    cell(format_address(&transfer.to)),
+                    cell_right(format_amount_with_symbol(
+                        transfer.amount,
+                        &transfer.token_id,
+                    )),
+                ]);
+            }
+            print_table(&table);
+        }
+    }
+    let signed = knot
+        .signatures
+        .iter()
+        .filter(|sig| **sig != UNSIGNED)
+        .count();
+    println!(
+        "  Signed:  {}",
+        style_dim().apply_to(format!(
+            "{}/{} participants, {} cosigner(s)",
+            signed,
+            knot.before_states.len(),
+            knot.cosignatures.len()
+        ))
+    );
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
@@ -2,10 +2,12 @@ use crate::wallet::config::WalletConfig;
 use crate::wallet::error::WalletError;
 use crate::wallet::format::{format_pubkey, style_bold};
 use crate::wallet::keystore::Keystore;
+use crate::wallet::output::{print_json, SignedMessage};
 use crate::wallet::prompt::prompt_password;
+use crate::wallet::signer::unlock_keypair;
 use crate::wallet::ui::{cell, cell_cyan, info_table, print_table};
 
-pub fn run(message: &str, name: Option<&str>) -> Result<(), WalletError> {
+pub fn run(message: &str, name: Option<&str>, json: bool) -> Result<(), WalletError> {
     let config = WalletConfig::load()?;
     let wallet_name = match name {
         Some(n) => n,
@@ -14,7 +16,7 @@ pub fn run(message: &str, name: Option<&str>) -> Result<(), WalletError> {
 
     let ks = Keystore::load(wallet_name)?;
     let password = prompt_password("Enter password")?;
-    let keypair = ks.decrypt_keypair(&password)?;
+    let keypair = unlock_keypair(&ks, &password)?;
 
     // Hash the message with BLAKE3
     let hash = norn_crypto::hash::blake3_hash(message.as_bytes());
@@ -22,6 +24,15 @@ pub fn run(message: &str, name: Option<&str>) -> Result<(), WalletError> {
     // Sign the hash
     let signature = keypair.sign(&hash);
 
+    if json {
+        return print_json(&SignedMessage {
+            message: message.to_string(),
+            message_hash: hex::encode(hash),
+            signature: hex::encode(signature),
+            public_key: hex::encode(keypair.public_key()),
+        });
+    }
+
     println!();
     println!("  {}", style_bold().apply_to("Signed Message"));
 
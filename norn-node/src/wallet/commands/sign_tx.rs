@@ -0,0 +1,111 @@
+use std::path::Path;
+
+use norn_thread::proposal::PendingKnot;
+use norn_weave::commitment::commitment_signing_data;
+
+use super::broadcast::{broadcast, print_tx_summary};
+use crate::wallet::config::WalletConfig;
+use crate::wallet::error::WalletError;
+use crate::wallet::format::{format_pubkey, print_success, style_bold, style_dim};
+use crate::wallet::keystore::Keystore;
+use crate::wallet::prompt::confirm;
+use crate::wallet::qr;
+use crate::wallet::rpc_client::RpcClient;
+use crate::wallet::signer::unlock;
+use crate::wallet::tx_file::{read_tx_file, sign_pending, write_tx_file, Transaction};
+
+pub async fn run(
+    file: &Path,
+    offline: bool,
+    out: Option<&Path>,
+    show_qr: bool,
+    yes: bool,
+    rpc_url: Option<&str>,
+) -> Result<(), WalletError> {
+    let config = WalletConfig::load()?;
+    let wallet_name = config.active_wallet_name()?;
+    let ks = Keystore::load(wallet_name)?;
+    if ks.is_watch_only() {
+        return Err(WalletError::WatchOnly(wallet_name.to_string()));
+    }
+
+    let mut tx = read_tx_file(file)?;
+
+    if !yes {
+        print_tx_summary(&tx);
+        println!();
+        if !confirm("Sign this transaction?")? {
+            println!("  Cancelled.");
+            return Ok(());
+        }
+    }
+
+    let mut signer = unlock(&ks)?;
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    // Whether the transaction can go to the node as it is.
+    let complete = match &mut tx {
+        Transaction::Knot(knot) => {
+            let mut pending = PendingKnot::new(knot.clone(), now)?;
+            sign_pending(&mut pending, signer.as_mut())?;
+            *knot = pending.knot().clone();
+            pending.is_complete()
+        }
+        Transaction::Commitment(commitment) => {
+            if commitment.owner != signer.public_key() {
+                return Err(WalletError::Other(format!(
+                    "commitment must be signed by {}, not this wallet",
+                    format_pubkey(&commitment.owner)
+                )));
+            }
+            commitment.signature = signer.sign(&commitment_signing_data(commitment))?;
+            true
+        }
+    };
+
+    if !offline && out.is_none() && complete {
+        let url = rpc_url.unwrap_or(&config.rpc_url);
+        let rpc = RpcClient::new(url)?;
+        return broadcast(&rpc, &tx).await;
+    }
+
+    let path = out.unwrap_or(file);
+    write_tx_file(path, &tx)?;
+    print_success(&format!("Signed and written to {}", path.display()));
+    if show_qr {
+        print_qr_chunks(&tx, yes)?;
+    }
+    println!(
+        "  {}",
+        style_dim().apply_to(format!(
+            "Submit it from an online machine with `norn wallet broadcast {}`.",
+            path.display()
+        ))
+    );
+    println!();
+
+    Ok(())
+}
+
+/// Print a signed transaction as QR codes, to carry it off an air-gapped
+/// machine. On the other side, save the scanned texts one per line and pass
+/// the file to `norn wallet broadcast`.
+fn print_qr_chunks(tx: &Transaction, yes: bool) -> Result<(), WalletError> {
+    let chunks = qr::encode_chunks(&tx.to_qr_payload()?);
+    let total = chunks.len();
+    for (i, chunk) in chunks.iter().enumerate() {
+        println!();
+        println!(
+            "  {}",
+            style_bold().apply_to(format!("QR chunk {}/{}", i + 1, total))
+        );
+        println!("{}", qr::render(chunk)?);
+        if i + 1 < total && !yes && !confirm("Show the next chunk?")? {
+            break;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use std::path::Path;
+
+use norn_crypto::address::pubkey_to_address;
+use norn_types::typed_data::TypedData;
+
+use crate::wallet::config::WalletConfig;
+use crate::wallet::error::WalletError;
+use crate::wallet::format::{format_address, format_pubkey, style_bold};
+use crate::wallet::keystore::Keystore;
+use crate::wallet::output::{print_json, SignedTypedData};
+use crate::wallet::prompt::prompt_password;
+use crate::wallet::signer::unlock_keypair;
+use crate::wallet::typed_data::{self, display_value};
+use crate::wallet::ui::{cell, cell_cyan, cell_dim, info_table, print_table};
+
+pub fn run(file: &str, name: Option<&str>, json: bool) -> Result<(), WalletError> {
+    let config = WalletConfig::load()?;
+    let wallet_name = match name {
+        Some(n) => n,
+        None => config.active_wallet_name()?,
+    };
+    let data = typed_data::load(Path::new(file))?;
+    let hash = data.signing_hash()?;
+
+    let ks = Keystore::load(wallet_name)?;
+    if !json {
+        println!();
+        println!("  {}", style_bold().apply_to("Sign Typed Data"));
+        print_typed_data(&data);
+    }
+    let password = prompt_password("Enter password")?;
+    let keypair = unlock_keypair(&ks, &password)?;
+    let signature = norn_crypto::typed_data::sign_typed_data(&keypair, &data)?;
+    let signer = pubkey_to_address(&keypair.public_key());
+
+    if json {
+        return print_json(&SignedTypedData {
+            message_type: data.message_type.to_string(),
+            domain_separator: hex::encode(data.domain.separator()),
+            signing_hash: hex::encode(hash),
+            signature: hex::encode(signature),
+            public_key: hex::encode(keypair.public_key()),
+            signer: format_address(&signer),
+        });
+    }
+
+    let mut table = info_table();
+    table.add_row(vec![cell("Signing hash"), cell_cyan(hex::encode(hash))]);
+    table.add_row(vec![cell("Signature"), cell_cyan(hex::encode(signature))]);
+    table.add_row(vec![
+        cell("Public key"),
+        cell(format_pubkey(&keypair.public_key())),
+    ]);
+    table.add_row(vec![cell("Signer"), cell(format_address(&signer))]);
+    print_table(&table);
+    println!();
+
+    Ok(())
+}
+
+/// Show the domain and message fields of typed data.
+pub fn print_typed_data(data: &TypedData) {
+    let mut table = info_table();
+    table.add_row(vec![cell("Domain"), cell(&data.domain.name)]);
+    table.add_row(vec![cell("Version"), cell(&data.domain.version)]);
+    table.add_row(vec![cell("Chain ID"), cell(&data.domain.chain_id)]);
+    if data.domain.loom == [0u8; 20] {
+        table.add_row(vec![cell("Loom"), cell_dim("none")]);
+    } else {
+        table.add_row(vec![cell("Loom"), cell(format_address(&data.domain.loom))]);
+    }
+    table.add_row(vec![cell("Type"), cell(&data.message_type.name)]);
+    for ((field, ty), value) in data.message_type.fields.iter().zip(&data.values) {
+        table.add_row(vec![
+            cell(format!("  {} ({})", field, ty.name())),
+            cell(display_value(value)),
+        ]);
+    }
+    print_table(&table);
+}
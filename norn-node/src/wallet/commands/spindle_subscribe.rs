@@ -0,0 +1,116 @@
+use norn_types::spindle::SpindleOperation;
+
+use crate::wallet::config::WalletConfig;
+use crate::wallet::error::WalletError;
+use crate::wallet::format::{
+    format_address, format_amount_with_symbol, parse_pubkey, print_divider, print_success,
+    style_bold, style_dim, style_info,
+};
+use crate::wallet::keystore::Keystore;
+use crate::wallet::prompt::{confirm, prompt_password};
+use crate::wallet::rpc_client::RpcClient;
+use crate::wallet::signer::unlock_keypair;
+use norn_types::primitives::NATIVE_TOKEN_ID;
+
+pub async fn run(
+    spindle_hex: &str,
+    periods: u64,
+    yes: bool,
+    rpc_url: Option<&str>,
+) -> Result<(), WalletError> {
+    if periods == 0 {
+        return Err(WalletError::Other("periods must be positive".to_string()));
+    }
+    let spindle = parse_pubkey(spindle_hex)?;
+
+    let config = WalletConfig::load()?;
+    let wallet_name = config.active_wallet_name()?;
+    let ks = Keystore::load(wallet_name)?;
+
+    let url = rpc_url.unwrap_or(&config.rpc_url);
+    let rpc = RpcClient::new(url)?;
+
+    // Lock in the spindle's current fee.
+    let spindles = rpc.get_spindles().await?;
+    let info = spindles
+        .iter()
+        .find(|s| s.pubkey == hex::encode(spindle))
+        .ok_or_else(|| WalletError::Other("spindle is not bonded".to_string()))?;
+    let fee_per_period: u128 = info.fee_per_period.parse().unwrap_or(0);
+    let cost = fee_per_period.saturating_mul(periods as u128);
+
+    // Show confirmation.
+    if !yes {
+        println!();
+        println!("  {}", style_bold().apply_to("Subscribe to Spindle"));
+        print_divider();
+        println!("  Spindle:   {}", hex::encode(spindle));
+        println!(
+            "  Bond:      {}",
+            format_amount_with_symbol(info.bond.parse().unwrap_or(0), &NATIVE_TOKEN_ID)
+        );
+        println!(
+            "  Fee:       {} per period",
+            format_amount_with_symbol(fee_per_period, &NATIVE_TOKEN_ID)
+        );
+        println!(
+            "  Prepaid:   {} ({} periods)",
+            style_info().apply_to(format_amount_with_symbol(cost, &NATIVE_TOKEN_ID)),
+            periods
+        );
+        println!(
+            "  Thread:    {} ({})",
+            format_address(&ks.address),
+            wallet_name
+        );
+        println!(
+            "  {}",
+            style_dim().apply_to("Unused periods are refunded if you unsubscribe")
+        );
+        println!();
+
+        if !confirm("Subscribe to this spindle?")? {
+            println!("  Cancelled.");
+            return Ok(());
+        }
+    }
+
+    let password = prompt_password("Enter password")?;
+    let keypair = unlock_keypair(&ks, &password)?;
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    let mut op = SpindleOperation::Subscribe {
+        subscriber: keypair.public_key(),
+        spindle,
+        periods,
+        max_fee_per_period: fee_per_period,
+        timestamp: now,
+        signature: [0u8; 64],
+    };
+
+    // Sign.
+    let sig_data = norn_weave::spindle::spindle_operation_signing_data(&op);
+    let signature = keypair.sign(&sig_data);
+    match &mut op {
+        SpindleOperation::Subscribe { signature: s, .. } => *s = signature,
+        _ => unreachable!(),
+    }
+
+    // Submit via RPC.
+    let hex_data = hex::encode(borsh::to_vec(&op).map_err(|e| WalletError::Other(e.to_string()))?);
+    let result = rpc.submit_spindle_operation(&hex_data).await?;
+
+    if result.success {
+        print_success("Spindle subscription submitted successfully");
+    } else {
+        return Err(WalletError::Other(
+            result.reason.unwrap_or_else(|| "unknown error".to_string()),
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use norn_types::primitives::NATIVE_TOKEN_ID;
+
+use crate::wallet::config::WalletConfig;
+use crate::wallet::error::WalletError;
+use crate::wallet::format::{
+    format_amount_with_symbol, style_bold, style_dim, style_success, style_warn,
+    truncate_hex_string,
+};
+use crate::wallet::keystore::Keystore;
+use crate::wallet::output::{print_json, SpindleMarket};
+use crate::wallet::rpc_client::RpcClient;
+use crate::wallet::ui::{cell, cell_right, data_table, print_table};
+
+pub async fn run(json: bool, rpc_url: Option<&str>) -> Result<(), WalletError> {
+    let config = WalletConfig::load()?;
+    let wallet_name = config.active_wallet_name()?;
+    let ks = Keystore::load(wallet_name)?;
+
+    let url = rpc_url.unwrap_or(&config.rpc_url);
+    let rpc = RpcClient::new(url)?;
+
+    let spindles = rpc.get_spindles().await?;
+    let subscriptions = rpc
+        .get_spindle_subscriptions(&hex::encode(ks.address))
+        .await?;
+    let coverage = rpc.get_spindle_coverage(&hex::encode(ks.address)).await?;
+
+    if json {
+        return print_json(&SpindleMarket {
+            spindles,
+            subscriptions,
+            coverage,
+        });
+    }
+
+    println!();
+    println!("  {}", style_bold().apply_to("Spindle Market"));
+    println!();
+
+    if spindles.is_empty() {
+        println!("  {}", style_dim().apply_to("No bonded spindles."));
+    } else {
+        let mut table = data_table(&["Spindle", "Bond", "Fee / Period", "Subscribers", "Slashed"]);
+
+        for s in &spindles {
+            let bond: u128 = s.bond.parse().unwrap_or(0);
+            let fee: u128 = s.fee_per_period.parse().unwrap_or(0);
+            let slashed: u128 = s.slashed.parse().unwrap_or(0);
+
+            table.add_row(vec![
+                cell(truncate_hex_string(&s.pubkey, 6)),
+                cell_right(format_amount_with_symbol(bond, &NATIVE_TOKEN_ID)),
+                cell_right(format_amount_with_symbol(fee, &NATIVE_TOKEN_ID)),
+                cell_right(s.subscriber_count.to_string()),
+                cell_right(format_amount_with_symbol(slashed, &NATIVE_TOKEN_ID)),
+            ]);
+        }
+
+        print_table(&table);
+    }
+
+    println!();
+    println!(
+        "  {} — {}",
+        style_bold().apply_to("Watching This Wallet"),
+        wallet_name
+    );
+    println!();
+
+    if subscriptions.is_empty() {
+        println!("  {}", style_dim().apply_to("No spindle subscriptions."));
+    } else {
+        let mut table = data_table(&["Spindle", "Fee / Period", "Paid", "Ends At"]);
+
+        for sub in &subscriptions {
+            let fee: u128 = sub.fee_per_period.parse().unwrap_or(0);
+
+            table.add_row(vec![
+                cell(truncate_hex_string(&sub.spindle, 6)),
+                cell_right(format_amount_with_symbol(fee, &NATIVE_TOKEN_ID)),
+                cell_right(format!("{}/{}", sub.periods_paid, sub.periods)),
+                cell_right(format!("#{}", sub.end_height)),
+            ]);
+        }
+
+        print_table(&table);
+    }
+
+    println!();
+    let status = format!(
+        "{} of {} spindles",
+        coverage.watchers.len(),
+        coverage.redundancy
+    );
+    if coverage.covered {
+        println!("  Coverage:  {}", style_success().apply_to(status));
+    } else {
+        println!("  Coverage:  {}", style_warn().apply_to(status));
+    }
+    println!();
+
+    Ok(())
+}
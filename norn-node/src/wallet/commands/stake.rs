@@ -7,8 +7,9 @@ use crate::wallet::format::{
     style_info,
 };
 use crate::wallet::keystore::Keystore;
-use crate::wallet::prompt::{confirm, prompt_password};
+use crate::wallet::prompt::confirm;
 use crate::wallet::rpc_client::RpcClient;
+use crate::wallet::signer::unlock;
 use norn_types::primitives::NATIVE_TOKEN_ID;
 
 pub async fn run(amount: u128, yes: bool, rpc_url: Option<&str>) -> Result<(), WalletError> {
@@ -51,8 +52,8 @@ pub async fn run(amount: u128, yes: bool, rpc_url: Option<&str>) -> Result<(), W
         }
     }
 
-    let password = prompt_password("Enter password")?;
-    let keypair = ks.decrypt_keypair(&password)?;
+    let mut signer = unlock(&ks)?;
+    let bls_keypair = signer.bls_keypair()?;
 
     let now = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
@@ -60,7 +61,9 @@ pub async fn run(amount: u128, yes: bool, rpc_url: Option<&str>) -> Result<(), W
         .as_secs();
 
     let mut op = StakeOperation::Stake {
-        pubkey: keypair.public_key(),
+        pubkey: signer.public_key(),
+        bls_pubkey: bls_keypair.public_key(),
+        bls_pop: bls_keypair.prove_possession(),
         amount,
         timestamp: now,
         signature: [0u8; 64],
@@ -68,7 +71,7 @@ pub async fn run(amount: u128, yes: bool, rpc_url: Option<&str>) -> Result<(), W
 
     // Sign.
     let sig_data = norn_weave::staking::stake_operation_signing_data(&op);
-    let signature = keypair.sign(&sig_data);
+    let signature = signer.sign(&sig_data)?;
     match &mut op {
         StakeOperation::Stake { signature: s, .. } => *s = signature,
         _ => unreachable!(),
@@ -1,15 +1,24 @@
 use crate::wallet::config::WalletConfig;
 use crate::wallet::error::WalletError;
-use crate::wallet::format::{print_divider, style_bold, style_dim, style_info};
+use crate::wallet::format::{print_divider, style_bold, style_dim, style_error, style_info};
+use crate::wallet::output::print_json;
 use crate::wallet::rpc_client::RpcClient;
 
-pub async fn run(validator_hex: Option<&str>, rpc_url: Option<&str>) -> Result<(), WalletError> {
+pub async fn run(
+    validator_hex: Option<&str>,
+    json: bool,
+    rpc_url: Option<&str>,
+) -> Result<(), WalletError> {
     let config = WalletConfig::load()?;
     let url = rpc_url.unwrap_or(&config.rpc_url);
     let rpc = RpcClient::new(url)?;
 
     let info = rpc.get_staking_info(validator_hex).await?;
 
+    if json {
+        return print_json(&info);
+    }
+
     println!();
     println!("  {}", style_bold().apply_to("Staking Information"));
     print_divider();
@@ -25,6 +34,11 @@ pub async fn run(validator_hex: Option<&str>, rpc_url: Option<&str>) -> Result<(
         "  Bonding Period:  {} blocks",
         style_dim().apply_to(info.bonding_period)
     );
+    println!("  Epoch:           {}", style_dim().apply_to(info.epoch));
+    println!(
+        "  Max Validators:  {}",
+        style_dim().apply_to(info.max_validators)
+    );
     println!(
         "  Validators:      {}",
         style_info().apply_to(info.validators.len())
@@ -41,12 +55,30 @@ pub async fn run(validator_hex: Option<&str>, rpc_url: Option<&str>) -> Result<(
                 style_bold().apply_to(&v.pubkey[..16]),
                 if v.active {
                     style_info().apply_to("active")
+                } else if v.jailed {
+                    style_error().apply_to("jailed")
                 } else {
                     style_dim().apply_to("inactive")
                 }
             );
             println!("     Address: {}", &v.address[..16]);
             println!("     Stake:   {}", v.stake);
+            if v.delegator_count > 0 {
+                println!(
+                    "     Delegated: {} ({} delegators)",
+                    v.delegated, v.delegator_count
+                );
+            }
+            println!("     Commission: {}%", v.commission_bps as f64 / 100.0);
+            if v.pending_stake != "0" {
+                println!("     Pending: +{} next epoch", v.pending_stake);
+            }
+            if v.pending_unstake != "0" {
+                println!("     Leaving: {}", v.pending_unstake);
+            }
+            if let Some(height) = v.jailed_until {
+                println!("     Jailed until height {}", height);
+            }
             println!();
         }
     }
@@ -2,6 +2,7 @@ use crate::wallet::config::WalletConfig;
 use crate::wallet::error::WalletError;
 use crate::wallet::format::{format_address, style_bold, style_dim, truncate_hex_string};
 use crate::wallet::keystore::Keystore;
+use crate::wallet::output::{print_json, WalletStatus};
 use crate::wallet::rpc_client::RpcClient;
 use crate::wallet::ui::{cell, cell_green, cell_yellow, info_table, print_table};
 
@@ -20,16 +21,11 @@ pub async fn run(name: Option<&str>, json: bool, rpc_url: Option<&str>) -> Resul
     let thread_info = rpc.get_thread(&thread_id).await?;
 
     if json {
-        let info = serde_json::json!({
-            "wallet": wallet_name,
-            "address": format_address(&ks.address),
-            "thread": thread_info,
+        return print_json(&WalletStatus {
+            wallet: wallet_name.to_string(),
+            address: format_address(&ks.address),
+            thread: thread_info,
         });
-        println!(
-            "{}",
-            serde_json::to_string_pretty(&info).unwrap_or_default()
-        );
-        return Ok(());
     }
 
     println!();
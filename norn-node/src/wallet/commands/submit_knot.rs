@@ -0,0 +1,94 @@
+use std::path::Path;
+
+use norn_thread::proposal::is_partially_signed;
+use norn_types::knot::Knot;
+
+use super::sign_knot::print_knot_summary;
+use crate::wallet::config::WalletConfig;
+use crate::wallet::error::WalletError;
+use crate::wallet::format::{format_address, print_error, print_success, style_dim};
+use crate::wallet::journal::record_submission;
+use crate::wallet::prompt::confirm;
+use crate::wallet::rpc_client::RpcClient;
+use crate::wallet::tx_file::read_knot_file;
+
+pub async fn run(file: &Path, yes: bool, rpc_url: Option<&str>) -> Result<(), WalletError> {
+    let config = WalletConfig::load()?;
+    let url = rpc_url.unwrap_or(&config.rpc_url);
+    let rpc = RpcClient::new(url)?;
+
+    let knot = read_knot_file(file)?;
+    check_submittable(&knot, file)?;
+
+    if !yes {
+        print_knot_summary(&knot);
+        println!();
+        if !confirm("Submit this knot?")? {
+            println!("  Cancelled.");
+            return Ok(());
+        }
+    }
+
+    submit(&rpc, &knot).await
+}
+
+/// Refuse a knot that the node could not take: one signed by none of the
+/// participants it still needs. Only multi-party knots can wait at the node
+/// for missing signatures.
+pub(super) fn check_submittable(knot: &Knot, file: &Path) -> Result<(), WalletError> {
+    if is_partially_signed(knot) && knot.before_states.len() < 2 {
+        return Err(WalletError::Other(format!(
+            "knot is not signed by {}; sign it with `norn wallet sign-tx {}`",
+            format_address(&knot.before_states[0].thread_id),
+            file.display()
+        )));
+    }
+    Ok(())
+}
+
+/// Submit a knot, or propose it if participants still have to sign.
+pub(super) async fn submit(rpc: &RpcClient, knot: &Knot) -> Result<(), WalletError> {
+    let bytes = borsh::to_vec(knot).map_err(|e| WalletError::SerializationError(e.to_string()))?;
+    let hex_data = hex::encode(&bytes);
+
+    if is_partially_signed(knot) {
+        let result = rpc.propose_knot(&hex_data).await?;
+        if let Some(reason) = result.reason {
+            print_error(
+                &format!("Knot submission failed: {}", reason),
+                Some("No transfers were applied."),
+            );
+        } else {
+            print_success(&format!(
+                "Proposed. Waiting for {} more signature(s).",
+                result.missing.len()
+            ));
+            println!(
+                "  {}",
+                style_dim().apply_to(format!(
+                    "Other participants can sign with `norn wallet sign-knot {}`.",
+                    result.knot_id
+                ))
+            );
+        }
+    } else {
+        let result = rpc.submit_knot(&hex_data).await?;
+        for state in &knot.before_states {
+            record_submission(&state.thread_id, knot, &result, None, None);
+        }
+        if result.success {
+            print_success("Knot submitted!");
+        } else {
+            print_error(
+                &format!(
+                    "Knot submission failed: {}",
+                    result.reason.unwrap_or_else(|| "unknown".to_string())
+                ),
+                Some("Check that every participant and enough cosigners have signed."),
+            );
+        }
+    }
+    println!();
+
+    Ok(())
+}
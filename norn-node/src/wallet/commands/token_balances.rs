@@ -1,17 +1,22 @@
+use norn_types::constants::NORN_DECIMALS;
 use norn_types::primitives::NATIVE_TOKEN_ID;
 
 use crate::wallet::config::WalletConfig;
 use crate::wallet::error::WalletError;
 use crate::wallet::format::{
-    format_address, format_amount, format_amount_with_symbol, format_token_amount, style_bold,
+    format_address, format_amount_with_symbol, format_token_amount, load_price, style_bold,
     style_dim,
 };
 use crate::wallet::keystore::Keystore;
+use crate::wallet::output::{balance_entry, print_json, Balances};
 use crate::wallet::rpc_client::RpcClient;
 use crate::wallet::ui::{cell_right, data_table, print_table};
 
 pub async fn run(json: bool, rpc_url: Option<&str>) -> Result<(), WalletError> {
     let config = WalletConfig::load()?;
+    if !json {
+        load_price(&config).await;
+    }
     let wallet_name = config.active_wallet_name()?;
     let ks = Keystore::load(wallet_name)?;
 
@@ -47,32 +52,20 @@ pub async fn run(json: bool, rpc_url: Option<&str>) -> Result<(), WalletError> {
     }
 
     if json {
-        let mut entries = vec![serde_json::json!({
-            "symbol": "NORN",
-            "token_id": native_hex,
-            "balance": native_str,
-            "human_readable": format_amount_with_symbol(native_bal, &NATIVE_TOKEN_ID),
-        })];
+        let mut balances = vec![balance_entry(
+            &native_hex,
+            "NORN",
+            native_bal,
+            NORN_DECIMALS as u8,
+        )];
         for (sym, tid, bal, decimals) in &holdings {
-            entries.push(serde_json::json!({
-                "symbol": sym,
-                "token_id": tid,
-                "balance": bal.to_string(),
-                "human_readable": format!("{} {}", format_token_amount(*bal, *decimals), sym),
-            }));
+            balances.push(balance_entry(tid, sym, *bal, *decimals));
         }
-        let mut info = serde_json::json!({
-            "address": format_address(&ks.address),
-            "balances": entries,
+        return print_json(&Balances {
+            address: format_address(&ks.address),
+            balances,
+            block_height,
         });
-        if let Some(h) = block_height {
-            info["block_height"] = serde_json::json!(h);
-        }
-        println!(
-            "{}",
-            serde_json::to_string_pretty(&info).unwrap_or_default()
-        );
-        return Ok(());
     }
 
     let height_suffix = block_height
@@ -91,7 +84,7 @@ pub async fn run(json: bool, rpc_url: Option<&str>) -> Result<(), WalletError> {
     // NORN first.
     table.add_row(vec![
         comfy_table::Cell::new("NORN"),
-        cell_right(format!("{} NORN", format_amount(native_bal))),
+        cell_right(format_amount_with_symbol(native_bal, &NATIVE_TOKEN_ID)),
     ]);
 
     // Custom tokens.
@@ -33,13 +33,21 @@ pub async fn run(token: &str, json: bool, rpc_url: Option<&str>) -> Result<(), W
             current_supply,
             creator: "protocol (native)".to_string(),
             created_at: 0,
+            description: None,
+            icon_uri: None,
+            project_url: None,
+            tags: Vec::new(),
         }
     } else {
         // Resolve custom token (by symbol or hex ID).
         match super::mint_token::resolve_token(&rpc, token).await {
             Ok(info) => info,
             Err(_) => {
-                print_error(&format!("token '{}' not found", token), None);
+                if json {
+                    println!("null");
+                } else {
+                    print_error(&format!("token '{}' not found", token), None);
+                }
                 return Ok(());
             }
         }
@@ -92,6 +100,19 @@ pub async fn run(token: &str, json: bool, rpc_url: Option<&str>) -> Result<(), W
         };
         table.add_row(vec![cell("Token ID"), cell_dim(id_display)]);
 
+        if let Some(description) = &token_info.description {
+            table.add_row(vec![cell("Description"), cell(description)]);
+        }
+        if let Some(icon_uri) = &token_info.icon_uri {
+            table.add_row(vec![cell("Icon"), cell(icon_uri)]);
+        }
+        if let Some(project_url) = &token_info.project_url {
+            table.add_row(vec![cell("Website"), cell(project_url)]);
+        }
+        if !token_info.tags.is_empty() {
+            table.add_row(vec![cell("Tags"), cell(token_info.tags.join(", "))]);
+        }
+
         print_table(&table);
         println!();
     }
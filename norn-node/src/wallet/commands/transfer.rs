@@ -1,22 +1,35 @@
+use std::path::Path;
+
+use norn_thread::proposal::PendingKnot;
 use norn_types::constants::TRANSFER_FEE;
-use norn_types::primitives::NATIVE_TOKEN_ID;
+use norn_types::primitives::{Address, PublicKey, TokenId, NATIVE_TOKEN_ID};
 
+use super::contacts::warn_name_mismatch;
 use crate::wallet::config::WalletConfig;
 use crate::wallet::error::WalletError;
 use crate::wallet::format::{
-    format_address, format_amount_with_symbol, format_token_amount_with_name, parse_address,
-    parse_token_amount, print_divider, print_error, print_success, style_bold, style_dim,
-    style_info,
+    format_address, format_amount_with_symbol, format_token_amount_with_name, is_address_literal,
+    parse_address, parse_pubkey, parse_token_amount, print_divider, print_error, print_success,
+    style_bold, style_dim, style_info,
 };
+use crate::wallet::journal::record_submission;
 use crate::wallet::keystore::Keystore;
-use crate::wallet::prompt::{confirm, prompt_password};
+use crate::wallet::prompt::confirm;
 use crate::wallet::rpc_client::RpcClient;
+use crate::wallet::signer::{unlock, Signer};
+use crate::wallet::tx_file::write_knot_file;
+
+/// Confidence (percent) of the commitment fee estimate shown before a transfer.
+const TRANSFER_FEE_CONFIDENCE: u8 = 90;
 
+#[allow(clippy::too_many_arguments)]
 pub async fn run(
     to: &str,
     amount_str: &str,
     token: Option<&str>,
     memo: Option<&str>,
+    label: Option<&str>,
+    out: Option<&Path>,
     yes: bool,
     rpc_url: Option<&str>,
 ) -> Result<(), WalletError> {
@@ -27,24 +40,11 @@ pub async fn run(
     let url = rpc_url.unwrap_or(&config.rpc_url);
     let rpc = RpcClient::new(url)?;
 
+    // Knots that need another device's signature go to a file instead.
+    let owner = thread_signer(&rpc, &ks, out.is_some()).await?;
+
     // Resolve token first so we know the correct decimals for amount parsing.
-    let (token_id, token_symbol, token_decimals) = match token {
-        Some(t) if t.eq_ignore_ascii_case("norn") || t == "native" => (
-            NATIVE_TOKEN_ID,
-            "NORN".to_string(),
-            norn_types::constants::NORN_DECIMALS as u8,
-        ),
-        Some(t) => {
-            let info = super::mint_token::resolve_token(&rpc, t).await?;
-            let id = super::mint_token::hex_to_token_id(&info.token_id)?;
-            (id, info.symbol, info.decimals)
-        }
-        None => (
-            NATIVE_TOKEN_ID,
-            "NORN".to_string(),
-            norn_types::constants::NORN_DECIMALS as u8,
-        ),
-    };
+    let (token_id, token_symbol, token_decimals) = resolve_transfer_token(&rpc, token).await?;
 
     let amount = parse_token_amount(amount_str, token_decimals)?;
 
@@ -54,20 +54,7 @@ pub async fn run(
         ));
     }
 
-    // Resolve `to` — try as address first, otherwise resolve as a name.
-    let to_addr = if to.starts_with("0x") || (to.len() == 40 && hex::decode(to).is_ok()) {
-        parse_address(to)?
-    } else {
-        match rpc.resolve_name(to).await? {
-            Some(resolution) => parse_address(&resolution.owner)?,
-            None => {
-                return Err(WalletError::InvalidAddress(format!(
-                    "name '{}' not registered",
-                    to
-                )));
-            }
-        }
-    };
+    let to_addr = resolve_recipient(&rpc, &config, to).await?;
 
     // Pre-check sender balance.
     let addr_hex = hex::encode(ks.address);
@@ -116,6 +103,20 @@ pub async fn run(
     }
 
     let fee_display = Some(format_amount_with_symbol(TRANSFER_FEE, &NATIVE_TOKEN_ID));
+    // The thread's next commitment also pays the weave's dynamic fee. Older
+    // nodes may not offer estimates, so this is best effort.
+    let commit_fee_display = rpc
+        .estimate_fee(TRANSFER_FEE_CONFIDENCE)
+        .await
+        .ok()
+        .and_then(|est| {
+            let fee: u128 = est.fee.parse().ok()?;
+            Some(format!(
+                "~{} ({}% confidence)",
+                format_amount_with_symbol(fee, &NATIVE_TOKEN_ID),
+                est.confidence
+            ))
+        });
 
     // Show confirmation
     if !yes {
@@ -129,7 +130,7 @@ pub async fn run(
         );
         println!(
             "  To:      {}",
-            style_info().apply_to(format_address(&to_addr))
+            style_info().apply_to(format_recipient(&config, &to_addr))
         );
         println!(
             "  Amount:  {}",
@@ -142,6 +143,9 @@ pub async fn run(
         if let Some(ref fee_str) = fee_display {
             println!("  Fee:     {}", style_dim().apply_to(fee_str));
         }
+        if let Some(ref commit_str) = commit_fee_display {
+            println!("  Commit:  {}", style_dim().apply_to(commit_str));
+        }
         println!(
             "  Balance: {}",
             style_dim().apply_to(format_token_amount_with_name(
@@ -161,10 +165,13 @@ pub async fn run(
         }
     }
 
-    let password = prompt_password("Enter password")?;
-    let keypair = ks.decrypt_keypair(&password)?;
+    let signer = if ks.is_watch_only() {
+        None
+    } else {
+        Some(unlock(&ks)?)
+    };
 
-    let sender_addr = norn_crypto::address::pubkey_to_address(&keypair.public_key());
+    let sender_addr = ks.address;
 
     // Build the transfer knot
     let now = std::time::SystemTime::now()
@@ -181,16 +188,23 @@ pub async fn run(
         memo: memo_bytes,
     });
 
-    // Build knot with sender as sole participant (transfers are unilateral).
+    // Build knot with sender as sole participant (transfers are unilateral),
+    // continuing the sender's knot sequence.
     let sender_state = norn_types::thread::ThreadState::new();
+    let sequence = next_knot_sequence(&rpc, &sender_addr).await?;
 
     let knot = norn_thread::knot::KnotBuilder::transfer(now)
-        .add_before_state(sender_addr, keypair.public_key(), 0, &sender_state)
-        .add_after_state(sender_addr, keypair.public_key(), 1, &sender_state)
+        .add_before_state(sender_addr, owner, sequence, &sender_state)
+        .add_after_state(sender_addr, owner, sequence + 1, &sender_state)
         .with_payload(payload)
         .build()?;
 
-    let sig = norn_thread::knot::sign_knot(&knot, &keypair);
+    if let Some(path) = out {
+        return write_pending_knot(path, knot, signer, now);
+    }
+    let mut signer = signer.ok_or_else(|| WalletError::WatchOnly(wallet_name.to_string()))?;
+
+    let sig = signer.sign(&knot.id)?;
     let mut signed_knot = knot;
     norn_thread::knot::add_signature(&mut signed_knot, sig);
 
@@ -200,6 +214,13 @@ pub async fn run(
     let hex_data = hex::encode(&bytes);
 
     let result = rpc.submit_knot(&hex_data).await?;
+    record_submission(
+        &sender_addr,
+        &signed_knot,
+        &result,
+        label,
+        Some((&token_symbol, token_decimals)),
+    );
 
     if result.success {
         print_success(&format!(
@@ -238,3 +259,141 @@ pub async fn run(
 
     Ok(())
 }
+
+/// The key that signs for the wallet's thread: the owner key the node has on
+/// record, else the wallet's own. Unless the knot goes to a file, refuses
+/// wallets that cannot sign alone: watch-only wallets, and threads with
+/// cosigners.
+pub(super) async fn thread_signer(
+    rpc: &RpcClient,
+    ks: &Keystore,
+    to_file: bool,
+) -> Result<PublicKey, WalletError> {
+    let thread = rpc.get_thread(&hex::encode(ks.address)).await?;
+    if !to_file {
+        if ks.is_watch_only() {
+            return Err(WalletError::Other(format!(
+                "wallet '{}' is watch-only; use --out to write the unsigned knot to a file",
+                ks.name
+            )));
+        }
+        if let Some(t) = thread.as_ref().filter(|t| t.cosigner_threshold > 0) {
+            return Err(WalletError::Other(format!(
+                "thread needs {} cosigner signature(s); use --out to write the knot to a file",
+                t.cosigner_threshold
+            )));
+        }
+    }
+    let recorded = match thread.filter(|t| !t.owner.is_empty()) {
+        Some(t) => Some(parse_pubkey(&t.owner)?),
+        None => None,
+    };
+    recorded
+        .into_iter()
+        .chain([ks.public_key])
+        .find(|pk| *pk != [0u8; 32])
+        .ok_or_else(|| {
+            WalletError::Other(
+                "thread is not registered and the wallet has no public key".to_string(),
+            )
+        })
+}
+
+/// Sign a knot with the wallet key, if it has one, and write it to a knot
+/// file for the remaining signers.
+pub(super) fn write_pending_knot(
+    path: &Path,
+    knot: norn_types::knot::Knot,
+    signer: Option<Box<dyn Signer>>,
+    now: u64,
+) -> Result<(), WalletError> {
+    let mut pending = PendingKnot::new(knot, now)?;
+    if let Some(mut signer) = signer {
+        crate::wallet::tx_file::sign_pending(&mut pending, signer.as_mut())?;
+    }
+    write_knot_file(path, pending.knot())?;
+    print_success(&format!("Knot written to {}", path.display()));
+    println!(
+        "  Knot ID: {}",
+        style_info().apply_to(hex::encode(pending.id()))
+    );
+    println!(
+        "  {}",
+        style_dim().apply_to(format!(
+            "Sign it with `norn wallet sign-knot --file {}` on each device, then submit it with `norn wallet submit-knot {}`.",
+            path.display(),
+            path.display()
+        ))
+    );
+    println!();
+    Ok(())
+}
+
+/// Resolve a `--token` argument to its ID, symbol and decimals. Defaults to NORN.
+pub(super) async fn resolve_transfer_token(
+    rpc: &RpcClient,
+    token: Option<&str>,
+) -> Result<(TokenId, String, u8), WalletError> {
+    match token {
+        Some(t) if !t.eq_ignore_ascii_case("norn") && t != "native" => {
+            let info = super::mint_token::resolve_token(rpc, t).await?;
+            let id = super::mint_token::hex_to_token_id(&info.token_id)?;
+            Ok((id, info.symbol, info.decimals))
+        }
+        _ => Ok((
+            NATIVE_TOKEN_ID,
+            "NORN".to_string(),
+            norn_types::constants::NORN_DECIMALS as u8,
+        )),
+    }
+}
+
+/// The `before` version a thread's next knot must use (its knot sequence).
+pub(super) async fn next_knot_sequence(
+    rpc: &RpcClient,
+    address: &Address,
+) -> Result<u64, WalletError> {
+    Ok(rpc
+        .get_thread(&hex::encode(address))
+        .await?
+        .map_or(0, |thread| thread.nonce))
+}
+
+/// Resolve a recipient: a literal address, then a contact, then a NornName.
+/// A contact whose name is also registered to a different owner resolves to
+/// the contact, with a warning.
+pub(super) async fn resolve_recipient(
+    rpc: &RpcClient,
+    config: &WalletConfig,
+    to: &str,
+) -> Result<Address, WalletError> {
+    if is_address_literal(to) {
+        return parse_address(to);
+    }
+    if let Some(contact) = config.contact(to)? {
+        // Cross-check only; contact names need not be valid NornNames.
+        if let Ok(Some(resolution)) = rpc.resolve_name(to).await {
+            if let Ok(owner) = parse_address(&resolution.owner) {
+                if owner != contact {
+                    warn_name_mismatch(to, &contact, &owner);
+                }
+            }
+        }
+        return Ok(contact);
+    }
+    match rpc.resolve_name(to).await? {
+        Some(resolution) => parse_address(&resolution.owner),
+        None => Err(WalletError::InvalidAddress(format!(
+            "'{}' is not a contact or a registered name",
+            to
+        ))),
+    }
+}
+
+/// Recipient line for summaries: the address, with its contact name if any.
+pub(super) fn format_recipient(config: &WalletConfig, address: &Address) -> String {
+    match config.contact_name(address) {
+        Some(name) => format!("{} ({})", format_address(address), name),
+        None => format_address(address),
+    }
+}
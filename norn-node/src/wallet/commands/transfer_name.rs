@@ -1,3 +1,4 @@
+use super::transfer::resolve_recipient;
 use crate::wallet::config::WalletConfig;
 use crate::wallet::error::WalletError;
 use crate::wallet::format::{
@@ -6,10 +7,11 @@ use crate::wallet::format::{
 use crate::wallet::keystore::Keystore;
 use crate::wallet::prompt::{confirm, prompt_password};
 use crate::wallet::rpc_client::RpcClient;
+use crate::wallet::signer::unlock_keypair;
 
 pub async fn run(
     name: &str,
-    to_hex: &str,
+    to: &str,
     yes: bool,
     rpc_url: Option<&str>,
 ) -> Result<(), WalletError> {
@@ -42,13 +44,7 @@ pub async fn run(
         return Ok(());
     }
 
-    // Validate recipient address.
-    let to_bytes: [u8; 20] = hex::decode(to_hex)
-        .ok()
-        .and_then(|b| b.try_into().ok())
-        .ok_or_else(|| {
-            WalletError::Other("invalid recipient address (expected 40 hex chars)".to_string())
-        })?;
+    let to_bytes = resolve_recipient(&rpc, &config, to).await?;
 
     // Show confirmation.
     if !yes {
@@ -71,7 +67,7 @@ pub async fn run(
     }
 
     let password = prompt_password("Enter password")?;
-    let keypair = ks.decrypt_keypair(&password)?;
+    let keypair = unlock_keypair(&ks, &password)?;
     let sender_addr = norn_crypto::address::pubkey_to_address(&keypair.public_key());
 
     let now = std::time::SystemTime::now()
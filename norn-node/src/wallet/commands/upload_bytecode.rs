@@ -4,6 +4,7 @@ use crate::wallet::format::{print_error, print_success, style_bold, style_dim};
 use crate::wallet::keystore::Keystore;
 use crate::wallet::prompt::prompt_password;
 use crate::wallet::rpc_client::RpcClient;
+use crate::wallet::signer::unlock_keypair;
 
 pub async fn run(loom_id: &str, path: &str, rpc_url: Option<&str>) -> Result<(), WalletError> {
     let config = WalletConfig::load()?;
@@ -33,7 +34,7 @@ pub async fn run(loom_id: &str, path: &str, rpc_url: Option<&str>) -> Result<(),
     println!();
 
     let password = prompt_password("Enter password")?;
-    let keypair = ks.decrypt_keypair(&password)?;
+    let keypair = unlock_keypair(&ks, &password)?;
     let pubkey_hex = hex::encode(keypair.public_key());
 
     // Parse loom_id for signing message.
@@ -66,6 +67,9 @@ pub async fn run(loom_id: &str, path: &str, rpc_url: Option<&str>) -> Result<(),
             ),
             None,
         );
+        for diagnostic in &result.diagnostics {
+            println!("  {}: {}", diagnostic.kind, diagnostic.message);
+        }
     }
     println!();
 
@@ -1,7 +1,13 @@
 use crate::wallet::error::WalletError;
 use crate::wallet::format::{print_error, print_success};
+use crate::wallet::output::{print_json, MessageVerification};
 
-pub fn run(message: &str, signature_hex: &str, pubkey_hex: &str) -> Result<(), WalletError> {
+pub fn run(
+    message: &str,
+    signature_hex: &str,
+    pubkey_hex: &str,
+    json: bool,
+) -> Result<(), WalletError> {
     // Parse hex signature (64 bytes)
     let sig_bytes = hex::decode(signature_hex)
         .map_err(|e| WalletError::Other(format!("invalid signature hex: {}", e)))?;
@@ -30,18 +36,22 @@ pub fn run(message: &str, signature_hex: &str, pubkey_hex: &str) -> Result<(), W
     let hash = norn_crypto::hash::blake3_hash(message.as_bytes());
 
     // Verify the signature
-    match norn_crypto::keys::verify(&hash, &signature, &pubkey) {
-        Ok(()) => {
-            println!();
-            print_success("Signature valid");
-            println!();
-        }
-        Err(_) => {
-            println!();
-            print_error("Signature INVALID", None);
-            println!();
-        }
+    let valid = norn_crypto::keys::verify(&hash, &signature, &pubkey).is_ok();
+    if json {
+        return print_json(&MessageVerification {
+            message: message.to_string(),
+            public_key: hex::encode(pubkey),
+            valid,
+        });
     }
 
+    println!();
+    if valid {
+        print_success("Signature valid");
+    } else {
+        print_error("Signature INVALID", None);
+    }
+    println!();
+
     Ok(())
 }
@@ -0,0 +1,50 @@
+use std::path::Path;
+
+use norn_crypto::address::pubkey_to_address;
+
+use crate::wallet::error::WalletError;
+use crate::wallet::format::{format_address, parse_pubkey, print_error, print_success};
+use crate::wallet::output::{print_json, TypedDataVerification};
+use crate::wallet::typed_data;
+
+use super::sign_typed::print_typed_data;
+
+pub fn run(
+    file: &str,
+    signature_hex: &str,
+    pubkey_hex: &str,
+    json: bool,
+) -> Result<(), WalletError> {
+    let data = typed_data::load(Path::new(file))?;
+
+    let sig_bytes = hex::decode(signature_hex.strip_prefix("0x").unwrap_or(signature_hex))
+        .map_err(|e| WalletError::Other(format!("invalid signature hex: {}", e)))?;
+    let signature: [u8; 64] = sig_bytes.try_into().map_err(|b: Vec<u8>| {
+        WalletError::Other(format!("signature must be 64 bytes, got {}", b.len()))
+    })?;
+    let pubkey = parse_pubkey(pubkey_hex)?;
+
+    let hash = data.signing_hash()?;
+    let valid = norn_crypto::typed_data::verify_typed_data(&data, &signature, &pubkey).is_ok();
+    let signer = format_address(&pubkey_to_address(&pubkey));
+    if json {
+        return print_json(&TypedDataVerification {
+            message_type: data.message_type.to_string(),
+            signing_hash: hex::encode(hash),
+            public_key: hex::encode(pubkey),
+            signer,
+            valid,
+        });
+    }
+
+    println!();
+    print_typed_data(&data);
+    if valid {
+        print_success(&format!("Signature valid, signed by {}", signer));
+    } else {
+        print_error("Signature INVALID", None);
+    }
+    println!();
+
+    Ok(())
+}
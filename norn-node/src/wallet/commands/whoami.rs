@@ -1,11 +1,14 @@
+use norn_types::constants::NORN_DECIMALS;
 use norn_types::primitives::NATIVE_TOKEN_ID;
 
 use crate::wallet::config::WalletConfig;
 use crate::wallet::error::WalletError;
 use crate::wallet::format::{
-    format_address, format_amount_with_symbol, format_token_amount, style_bold, style_info,
+    format_address, format_amount_with_symbol, format_token_amount, load_price, style_bold,
+    style_info,
 };
 use crate::wallet::keystore::Keystore;
+use crate::wallet::output::{balance_entry, print_json, Whoami};
 use crate::wallet::rpc_client::RpcClient;
 use crate::wallet::ui::{
     cell, cell_cyan, cell_dim, cell_green, cell_right, cell_yellow, data_table, info_table,
@@ -14,6 +17,9 @@ use crate::wallet::ui::{
 
 pub async fn run(json: bool, rpc_url: Option<&str>) -> Result<(), WalletError> {
     let config = WalletConfig::load()?;
+    if !json {
+        load_price(&config).await;
+    }
     let wallet_name = config.active_wallet_name()?;
     let ks = Keystore::load(wallet_name)?;
 
@@ -38,47 +44,36 @@ pub async fn run(json: bool, rpc_url: Option<&str>) -> Result<(), WalletError> {
         .map(|b| b.height);
 
     // Fetch custom token balances (non-zero only).
-    let mut token_balances: Vec<(String, u128, u8)> = Vec::new(); // (symbol, balance, decimals)
+    let mut token_balances: Vec<(String, String, u128, u8)> = Vec::new(); // (symbol, token_id_hex, balance, decimals)
     if let Ok(tokens) = rpc.list_tokens(200, 0).await {
         for t in &tokens {
             if let Ok(bal_str) = rpc.get_balance(&addr_hex, &t.token_id).await {
                 let bal: u128 = bal_str.parse().unwrap_or(0);
                 if bal > 0 {
-                    token_balances.push((t.symbol.clone(), bal, t.decimals));
+                    token_balances.push((t.symbol.clone(), t.token_id.clone(), bal, t.decimals));
                 }
             }
         }
     }
 
     if json {
-        let token_holdings: Vec<serde_json::Value> = token_balances
-            .iter()
-            .map(|(sym, bal, decimals)| {
-                serde_json::json!({
-                    "symbol": sym,
-                    "balance": bal.to_string(),
-                    "human_readable": format!("{} {}", format_token_amount(*bal, *decimals), sym),
-                })
-            })
-            .collect();
-
-        let mut info = serde_json::json!({
-            "wallet": wallet_name,
-            "address": format_address(&ks.address),
-            "balance": balance_str,
-            "human_readable": format_amount_with_symbol(balance, &NATIVE_TOKEN_ID),
-            "names": names.iter().map(|n| &n.name).collect::<Vec<_>>(),
-            "thread_registered": thread_info.is_some(),
-            "token_balances": token_holdings,
-        });
-        if let Some(h) = block_height {
-            info["block_height"] = serde_json::json!(h);
+        let mut balances = vec![balance_entry(
+            &token_hex,
+            "NORN",
+            balance,
+            NORN_DECIMALS as u8,
+        )];
+        for (sym, tid, bal, decimals) in &token_balances {
+            balances.push(balance_entry(tid, sym, *bal, *decimals));
         }
-        println!(
-            "{}",
-            serde_json::to_string_pretty(&info).unwrap_or_default()
-        );
-        return Ok(());
+        return print_json(&Whoami {
+            wallet: wallet_name.to_string(),
+            address: format_address(&ks.address),
+            names: names.into_iter().map(|n| n.name).collect(),
+            thread_registered: thread_info.is_some(),
+            balances,
+            block_height,
+        });
     }
 
     println!();
@@ -120,7 +115,7 @@ pub async fn run(json: bool, rpc_url: Option<&str>) -> Result<(), WalletError> {
         println!("  {}", style_bold().apply_to("Token Holdings"));
 
         let mut ttable = data_table(&["Token", "Balance"]);
-        for (sym, bal, decimals) in &token_balances {
+        for (sym, _, bal, decimals) in &token_balances {
             ttable.add_row(vec![
                 cell(sym),
                 cell_right(format!("{} {}", format_token_amount(*bal, *decimals), sym)),
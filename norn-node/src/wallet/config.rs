@@ -1,11 +1,35 @@
+use std::collections::BTreeMap;
 use std::path::PathBuf;
+use std::sync::OnceLock;
 
 use serde::{Deserialize, Serialize};
 
+use norn_types::network::NetworkId;
+use norn_types::primitives::Address;
+
 use super::error::WalletError;
+use super::format::{is_address_literal, parse_address, Denomination};
 
 const DEFAULT_RPC_URL: &str = "http://127.0.0.1:9741";
 
+/// Longest contact name accepted.
+const MAX_CONTACT_NAME_LEN: usize = 32;
+
+/// Networks a profile may name.
+const NETWORKS: &[&str] = &["dev", "testnet", "mainnet"];
+
+/// Profile chosen with `--profile` for this process.
+static PROFILE_OVERRIDE: OnceLock<String> = OnceLock::new();
+
+/// Keystore directory of the profile in effect, if it has its own.
+static KEYSTORE_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Profile in effect and the network it expects the node to be on.
+static EXPECTED_NETWORK: OnceLock<(String, String)> = OnceLock::new();
+
+/// Network of the loaded config, which bech32m addresses must be for.
+static ADDRESS_NETWORK: OnceLock<NetworkId> = OnceLock::new();
+
 /// Wallet configuration stored in ~/.norn/wallets/config.json
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WalletConfig {
@@ -18,12 +42,78 @@ pub struct WalletConfig {
     pub network: String,
     /// List of known wallet names.
     pub wallets: Vec<String>,
+    /// Unit native NORN amounts are displayed in.
+    #[serde(default)]
+    pub denomination: Denomination,
+    /// Endpoint returning the NORN price as JSON; prices are shown only if
+    /// set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_url: Option<String>,
+    /// JSON pointer to the price in the endpoint's response.
+    #[serde(default = "default_price_pointer")]
+    pub price_pointer: String,
+    /// Currency the price is quoted in.
+    #[serde(default = "default_price_currency")]
+    pub price_currency: String,
+    /// Address book, keyed by contact name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub contacts: BTreeMap<String, Contact>,
+    /// Selected network profile. Without one, `rpc_url` and `network`
+    /// above are used as they are.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    /// User-defined network profiles; these replace built-in profiles of
+    /// the same name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, Profile>,
+    /// The file's own `rpc_url` and `network` while a profile's are in
+    /// effect, so saving does not write the profile's values over them.
+    #[serde(skip)]
+    overlay: Option<Overlay>,
+}
+
+/// A named network: where to send RPC requests and which network the node
+/// there must be on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Profile {
+    pub rpc_url: String,
+    /// "dev", "testnet" or "mainnet".
+    pub network: String,
+    /// Directory holding this profile's keystores, instead of the shared
+    /// wallet directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keystore_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone)]
+struct Overlay {
+    profile: String,
+    rpc_url: String,
+    network: String,
+}
+
+/// An address book entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Contact {
+    /// Address as 40 hex chars.
+    pub address: String,
+    /// Free-form note shown by `norn wallet contacts list`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
 }
 
 fn default_network() -> String {
     "dev".to_string()
 }
 
+fn default_price_pointer() -> String {
+    "/price".to_string()
+}
+
+fn default_price_currency() -> String {
+    "USD".to_string()
+}
+
 impl Default for WalletConfig {
     fn default() -> Self {
         Self {
@@ -31,10 +121,46 @@ impl Default for WalletConfig {
             rpc_url: DEFAULT_RPC_URL.to_string(),
             network: default_network(),
             wallets: Vec::new(),
+            denomination: Denomination::default(),
+            price_url: None,
+            price_pointer: default_price_pointer(),
+            price_currency: default_price_currency(),
+            contacts: BTreeMap::new(),
+            profile: None,
+            profiles: BTreeMap::new(),
+            overlay: None,
         }
     }
 }
 
+/// Profiles available without any configuration.
+pub fn builtin_profiles() -> BTreeMap<String, Profile> {
+    [
+        ("mainnet", "https://rpc.norn.network", "mainnet"),
+        ("testnet", "https://testnet.norn.network", "testnet"),
+        ("devnet", "https://seed.norn.network", "dev"),
+        ("localnet", DEFAULT_RPC_URL, "dev"),
+    ]
+    .into_iter()
+    .map(|(name, rpc_url, network)| {
+        (
+            name.to_string(),
+            Profile {
+                rpc_url: rpc_url.to_string(),
+                network: network.to_string(),
+                keystore_dir: None,
+            },
+        )
+    })
+    .collect()
+}
+
+/// Use a profile for this process regardless of the configured one
+/// (`norn wallet --profile`). Only the first call has an effect.
+pub fn set_profile_override(name: &str) {
+    let _ = PROFILE_OVERRIDE.set(name.to_string());
+}
+
 impl WalletConfig {
     /// Get the wallet data directory (~/.norn/wallets/).
     pub fn data_dir() -> Result<PathBuf, WalletError> {
@@ -44,23 +170,146 @@ impl WalletConfig {
         Ok(home.join(".norn").join("wallets"))
     }
 
+    /// Directory keystores are read from and written to: the profile's
+    /// own, if it has one, or the wallet data directory.
+    pub fn keystore_dir() -> Result<PathBuf, WalletError> {
+        match KEYSTORE_DIR.get() {
+            Some(dir) => Ok(dir.clone()),
+            None => Self::data_dir(),
+        }
+    }
+
+    /// Profile in effect and the network the node must report before
+    /// signed data is sent to it.
+    pub fn expected_network() -> Option<(&'static str, &'static str)> {
+        EXPECTED_NETWORK
+            .get()
+            .map(|(profile, network)| (profile.as_str(), network.as_str()))
+    }
+
+    /// Network whose bech32m addresses the wallet shows and accepts: the
+    /// loaded config's network, or dev before any config is loaded.
+    pub fn address_network() -> NetworkId {
+        ADDRESS_NETWORK.get().copied().unwrap_or(NetworkId::Dev)
+    }
+
     /// Get the config file path.
     fn config_path() -> Result<PathBuf, WalletError> {
         Ok(Self::data_dir()?.join("config.json"))
     }
 
-    /// Load config from disk, or create default if it doesn't exist.
+    /// Load config from disk, or create default if it doesn't exist, and
+    /// apply the profile in effect.
     pub fn load() -> Result<Self, WalletError> {
         let path = Self::config_path()?;
-        if path.exists() {
+        let mut config = if path.exists() {
             let data = std::fs::read_to_string(&path)?;
-            let config: WalletConfig = serde_json::from_str(&data)?;
-            Ok(config)
+            serde_json::from_str::<WalletConfig>(&data)?
         } else {
             let config = Self::default();
             config.save()?;
-            Ok(config)
+            config
+        };
+        let selected = PROFILE_OVERRIDE.get().map(String::as_str);
+        if let Some(profile) = config.apply_profile(selected)? {
+            if let Some(dir) = profile.keystore_dir {
+                let _ = KEYSTORE_DIR.set(dir);
+            }
+            let name = config.active_profile().unwrap_or_default().to_string();
+            let _ = EXPECTED_NETWORK.set((name, profile.network));
+        }
+        if let Some(network) = NetworkId::parse(&config.network) {
+            let _ = ADDRESS_NETWORK.set(network);
+        }
+        Ok(config)
+    }
+
+    /// Use the RPC URL and network of `selected`, or of the configured
+    /// profile if none is given. Returns the profile applied.
+    fn apply_profile(&mut self, selected: Option<&str>) -> Result<Option<Profile>, WalletError> {
+        let Some(name) = selected.or(self.profile.as_deref()).map(str::to_string) else {
+            return Ok(None);
+        };
+        let profile = self.find_profile(&name)?;
+        self.overlay = Some(Overlay {
+            profile: name,
+            rpc_url: std::mem::replace(&mut self.rpc_url, profile.rpc_url.clone()),
+            network: std::mem::replace(&mut self.network, profile.network.clone()),
+        });
+        Ok(Some(profile))
+    }
+
+    /// Name of the profile in effect.
+    pub fn active_profile(&self) -> Option<&str> {
+        self.overlay.as_ref().map(|o| o.profile.as_str())
+    }
+
+    /// RPC URL used when no profile is selected.
+    pub fn base_rpc_url(&self) -> &str {
+        match &self.overlay {
+            Some(overlay) => &overlay.rpc_url,
+            None => &self.rpc_url,
+        }
+    }
+
+    /// All profiles: built-in ones, replaced or extended by the user's.
+    pub fn all_profiles(&self) -> BTreeMap<String, Profile> {
+        let mut profiles = builtin_profiles();
+        profiles.extend(self.profiles.clone());
+        profiles
+    }
+
+    /// Look up a profile by name.
+    pub fn find_profile(&self, name: &str) -> Result<Profile, WalletError> {
+        self.all_profiles().remove(name).ok_or_else(|| {
+            WalletError::ConfigError(format!(
+                "unknown profile '{}', expected one of: {}",
+                name,
+                self.all_profiles()
+                    .into_keys()
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+        })
+    }
+
+    /// Add or replace a user-defined profile.
+    pub fn add_profile(&mut self, name: &str, profile: Profile) -> Result<(), WalletError> {
+        let valid_name = !name.is_empty()
+            && name.len() <= MAX_CONTACT_NAME_LEN
+            && name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+        if !valid_name {
+            return Err(WalletError::ConfigError(format!(
+                "invalid profile name '{}': use 1-{} lowercase letters, digits or '-'",
+                name, MAX_CONTACT_NAME_LEN
+            )));
+        }
+        validate_network(&profile.network)?;
+        self.profiles.insert(name.to_string(), profile);
+        Ok(())
+    }
+
+    /// Remove a user-defined profile. Built-in profiles cannot be removed,
+    /// only replaced.
+    pub fn remove_profile(&mut self, name: &str) -> Result<Profile, WalletError> {
+        let profile = self.profiles.remove(name).ok_or_else(|| {
+            WalletError::ConfigError(format!("no user-defined profile '{}'", name))
+        })?;
+        if self.profile.as_deref() == Some(name) && !builtin_profiles().contains_key(name) {
+            self.profile = None;
         }
+        Ok(profile)
+    }
+
+    /// Select the profile used from now on, or none.
+    pub fn use_profile(&mut self, name: Option<&str>) -> Result<(), WalletError> {
+        if let Some(name) = name {
+            self.find_profile(name)?;
+        }
+        self.profile = name.map(str::to_string);
+        Ok(())
     }
 
     /// Save config to disk.
@@ -68,7 +317,12 @@ impl WalletConfig {
         let dir = Self::data_dir()?;
         std::fs::create_dir_all(&dir)?;
         let path = Self::config_path()?;
-        let data = serde_json::to_string_pretty(self)?;
+        let mut stored = self.clone();
+        if let Some(overlay) = &self.overlay {
+            stored.rpc_url = overlay.rpc_url.clone();
+            stored.network = overlay.network.clone();
+        }
+        let data = serde_json::to_string_pretty(&stored)?;
 
         #[cfg(unix)]
         {
@@ -121,4 +375,172 @@ impl WalletConfig {
         self.active_wallet = Some(name.to_string());
         Ok(())
     }
+
+    /// Add a contact. Names are case-insensitive and stored lowercase.
+    pub fn add_contact(
+        &mut self,
+        name: &str,
+        address: &Address,
+        note: Option<&str>,
+    ) -> Result<(), WalletError> {
+        let name = validate_contact_name(name)?;
+        if self.contacts.contains_key(&name) {
+            return Err(WalletError::ContactAlreadyExists(name));
+        }
+        self.contacts.insert(
+            name,
+            Contact {
+                address: hex::encode(address),
+                note: note.map(str::to_string),
+            },
+        );
+        Ok(())
+    }
+
+    /// Remove a contact, returning it.
+    pub fn remove_contact(&mut self, name: &str) -> Result<Contact, WalletError> {
+        self.contacts
+            .remove(&name.to_lowercase())
+            .ok_or_else(|| WalletError::ContactNotFound(name.to_string()))
+    }
+
+    /// Look up a contact's address by name.
+    pub fn contact(&self, name: &str) -> Result<Option<Address>, WalletError> {
+        self.contacts
+            .get(&name.to_lowercase())
+            .map(|c| parse_address(&c.address))
+            .transpose()
+    }
+
+    /// Name of the contact holding an address, if any.
+    pub fn contact_name(&self, address: &Address) -> Option<&str> {
+        let hex_addr = hex::encode(address);
+        self.contacts
+            .iter()
+            .find(|(_, c)| c.address == hex_addr)
+            .map(|(name, _)| name.as_str())
+    }
+}
+
+/// Check a network name.
+pub fn validate_network(network: &str) -> Result<(), WalletError> {
+    if NETWORKS.contains(&network) {
+        Ok(())
+    } else {
+        Err(WalletError::ConfigError(format!(
+            "unknown network '{}', expected 'dev', 'testnet', or 'mainnet'",
+            network
+        )))
+    }
+}
+
+/// Check a contact name and normalize it to lowercase. Names must not be
+/// mistakable for addresses.
+fn validate_contact_name(name: &str) -> Result<String, WalletError> {
+    let name = name.to_lowercase();
+    let valid_chars = name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if name.is_empty() || name.len() > MAX_CONTACT_NAME_LEN || !valid_chars {
+        return Err(WalletError::ConfigError(format!(
+            "invalid contact name '{}': use 1-{} letters, digits, '-', '_' or '.'",
+            name, MAX_CONTACT_NAME_LEN
+        )));
+    }
+    if is_address_literal(&name) {
+        return Err(WalletError::ConfigError(format!(
+            "contact name '{}' looks like an address",
+            name
+        )));
+    }
+    Ok(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contacts_resolve_and_remove() {
+        let mut config = WalletConfig::default();
+        let alice = [0xaa; 20];
+        config.add_contact("Alice", &alice, Some("coffee")).unwrap();
+
+        assert_eq!(config.contact("alice").unwrap(), Some(alice));
+        assert_eq!(config.contact("ALICE").unwrap(), Some(alice));
+        assert_eq!(config.contact_name(&alice), Some("alice"));
+        assert_eq!(config.contact("bob").unwrap(), None);
+        let bob = [0xbb; 20];
+
+        assert!(matches!(
+            config.add_contact("alice", &bob, None),
+            Err(WalletError::ContactAlreadyExists(_))
+        ));
+        assert_eq!(
+            config.remove_contact("alice").unwrap().note.as_deref(),
+            Some("coffee")
+        );
+        assert!(config.remove_contact("alice").is_err());
+    }
+
+    #[test]
+    fn test_contact_names_validated() {
+        let mut config = WalletConfig::default();
+        let addr = [1u8; 20];
+        assert!(config.add_contact("", &addr, None).is_err());
+        assert!(config.add_contact("has space", &addr, None).is_err());
+        assert!(config.add_contact("0xabc", &addr, None).is_err());
+        assert!(config.add_contact(&"n".repeat(33), &addr, None).is_err());
+        assert!(config.add_contact("cold-storage_2", &addr, None).is_ok());
+    }
+
+    #[test]
+    fn test_profiles() {
+        let mut config = WalletConfig::default();
+        assert!(config.apply_profile(None).unwrap().is_none());
+        assert!(config.apply_profile(Some("nowhere")).is_err());
+
+        config
+            .add_profile(
+                "staging",
+                Profile {
+                    rpc_url: "http://10.0.0.5:9741".to_string(),
+                    network: "testnet".to_string(),
+                    keystore_dir: Some(PathBuf::from("/tmp/staging-keys")),
+                },
+            )
+            .unwrap();
+        let bad_network = Profile {
+            rpc_url: "http://x".to_string(),
+            network: "moon".to_string(),
+            keystore_dir: None,
+        };
+        assert!(config.add_profile("moon", bad_network.clone()).is_err());
+        assert!(config.add_profile("Bad Name", bad_network).is_err());
+
+        config.use_profile(Some("staging")).unwrap();
+        assert!(config.use_profile(Some("nowhere")).is_err());
+        let applied = config.apply_profile(None).unwrap().unwrap();
+        assert_eq!(applied.network, "testnet");
+        assert_eq!(config.active_profile(), Some("staging"));
+        assert_eq!(config.rpc_url, "http://10.0.0.5:9741");
+        assert_eq!(config.network, "testnet");
+        // The file keeps its own values.
+        assert_eq!(config.overlay.as_ref().unwrap().rpc_url, DEFAULT_RPC_URL);
+
+        // --profile wins over the configured profile.
+        let mut config = WalletConfig::default();
+        config.use_profile(Some("testnet")).unwrap();
+        config.apply_profile(Some("mainnet")).unwrap();
+        assert_eq!(config.network, "mainnet");
+        assert!(config.remove_profile("mainnet").is_err());
+    }
+
+    #[test]
+    fn test_config_without_contacts_loads() {
+        let json = r#"{"active_wallet":null,"rpc_url":"http://x","wallets":[]}"#;
+        let config: WalletConfig = serde_json::from_str(json).unwrap();
+        assert!(config.contacts.is_empty());
+        assert!(!serde_json::to_string(&config).unwrap().contains("contacts"));
+    }
 }
@@ -13,6 +13,21 @@ pub enum WalletError {
     #[error("wallet '{0}' already exists")]
     WalletAlreadyExists(String),
 
+    #[error("contact '{0}' not found")]
+    ContactNotFound(String),
+
+    #[error("contact '{0}' already exists")]
+    ContactAlreadyExists(String),
+
+    #[error("wallet '{0}' is watch-only and cannot sign; sign on the device holding its key")]
+    WatchOnly(String),
+
+    #[error("wallet '{0}' keeps its key on a Ledger, which this command cannot use")]
+    HardwareWallet(String),
+
+    #[error("ledger: {0}")]
+    Ledger(String),
+
     #[error("invalid password: decryption failed")]
     InvalidPassword,
 
@@ -28,6 +43,13 @@ pub enum WalletError {
     #[error("rpc error: {0}")]
     RpcError(String),
 
+    #[error("node is on network '{actual}', but profile '{profile}' expects '{expected}'; check --rpc-url or --profile")]
+    NetworkMismatch {
+        profile: String,
+        expected: String,
+        actual: String,
+    },
+
     #[error("crypto error: {0}")]
     CryptoError(#[from] norn_types::error::NornError),
 
@@ -37,6 +59,9 @@ pub enum WalletError {
     #[error("serialization error: {0}")]
     SerializationError(String),
 
+    #[error("journal error: {0}")]
+    Journal(#[from] rusqlite::Error),
+
     #[error("config error: {0}")]
     ConfigError(String),
 
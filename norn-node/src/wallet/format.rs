@@ -1,8 +1,16 @@
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
 use console::Style;
+use serde::{Deserialize, Serialize};
+
 use norn_types::constants::NORN_DECIMALS;
 use norn_types::primitives::{Address, Amount, PublicKey, TokenId, NATIVE_TOKEN_ID};
 
+use super::config::WalletConfig;
 use super::error::WalletError;
+use super::price::{self, Price};
 
 // ── Styles ──────────────────────────────────────────────────────────────────
 
@@ -55,8 +63,12 @@ pub fn format_token_amount(amount: Amount, decimals: u8) -> String {
     format!("{}.{}", whole_str, trimmed)
 }
 
-/// Format a token amount with its symbol name.
+/// Format a token amount with its symbol name. Native NORN amounts follow
+/// the display denomination (see `format_native`).
 pub fn format_token_amount_with_name(amount: Amount, decimals: u8, symbol: &str) -> String {
+    if symbol == "NORN" && decimals == NORN_DECIMALS as u8 {
+        return format_native(amount);
+    }
     format!("{} {}", format_token_amount(amount, decimals), symbol)
 }
 
@@ -67,18 +79,144 @@ pub fn format_amount(amount: Amount) -> String {
 
 /// Format amount with token symbol (native NORN only — uses NORN decimals).
 pub fn format_amount_with_symbol(amount: Amount, token_id: &TokenId) -> String {
-    let formatted = format_amount(amount);
     if *token_id == NATIVE_TOKEN_ID {
-        format!("{} NORN", formatted)
+        format_native(amount)
     } else {
         format!(
             "{} (token:{})",
-            formatted,
+            format_amount(amount),
             truncate_hex(&hex::encode(token_id), 8)
         )
     }
 }
 
+// ── Denominations and prices ────────────────────────────────────────────────
+
+/// Unit native NORN amounts are displayed in. Amounts typed on the command
+/// line are always in NORN.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Denomination {
+    /// Whole NORN.
+    #[default]
+    Norn,
+    /// Thousandths of a NORN.
+    Mnorn,
+    /// Base units (nits).
+    Nit,
+}
+
+impl Denomination {
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Denomination::Norn => "NORN",
+            Denomination::Mnorn => "mNORN",
+            Denomination::Nit => "nits",
+        }
+    }
+
+    fn decimals(self) -> u8 {
+        match self {
+            Denomination::Norn => NORN_DECIMALS as u8,
+            Denomination::Mnorn => NORN_DECIMALS as u8 - 3,
+            Denomination::Nit => 0,
+        }
+    }
+
+    /// Format a native amount in this unit, with its symbol.
+    pub fn format(self, amount: Amount) -> String {
+        format!(
+            "{} {}",
+            format_token_amount(amount, self.decimals()),
+            self.symbol()
+        )
+    }
+}
+
+impl fmt::Display for Denomination {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Denomination::Norn => "norn",
+            Denomination::Mnorn => "mnorn",
+            Denomination::Nit => "nit",
+        })
+    }
+}
+
+impl FromStr for Denomination {
+    type Err = WalletError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "norn" => Ok(Denomination::Norn),
+            "mnorn" => Ok(Denomination::Mnorn),
+            "nit" | "nits" => Ok(Denomination::Nit),
+            _ => Err(WalletError::ConfigError(format!(
+                "unknown denomination '{}', expected 'norn', 'mnorn', or 'nit'",
+                s
+            ))),
+        }
+    }
+}
+
+/// Display denomination for this process, from the wallet config.
+static DENOMINATION: OnceLock<Denomination> = OnceLock::new();
+
+/// NORN price for this process, once a command has loaded it.
+static PRICE: OnceLock<Price> = OnceLock::new();
+
+/// Set the unit native amounts are displayed in. Only the first call has
+/// an effect.
+pub fn set_denomination(denomination: Denomination) {
+    let _ = DENOMINATION.set(denomination);
+}
+
+/// Fetch the configured NORN price so native amounts show their value.
+/// Best effort: without a price endpoint, or if it fails, amounts are shown
+/// without one.
+pub async fn load_price(config: &WalletConfig) {
+    let Some(url) = &config.price_url else {
+        return;
+    };
+    match price::fetch(url, &config.price_pointer).await {
+        Ok(per_norn) => {
+            let _ = PRICE.set(Price {
+                per_norn,
+                currency: config.price_currency.clone(),
+            });
+        }
+        Err(e) => println!(
+            "  {}",
+            style_dim().apply_to(format!("Price unavailable: {}", e))
+        ),
+    }
+}
+
+/// Format a native amount in the display denomination, with its value if a
+/// price is loaded, e.g. `"1,500 mNORN (~0.75 USD)"`.
+pub fn format_native(amount: Amount) -> String {
+    let formatted = DENOMINATION
+        .get()
+        .copied()
+        .unwrap_or_default()
+        .format(amount);
+    match PRICE.get() {
+        Some(price) => format!("{} (~{})", formatted, format_value(amount, price)),
+        None => formatted,
+    }
+}
+
+/// Value of a native amount at a price, to the cent.
+fn format_value(amount: Amount, price: &Price) -> String {
+    let norn = amount as f64 / 10f64.powi(NORN_DECIMALS as i32);
+    let value = norn * price.per_norn;
+    if value > 0.0 && value < 0.01 {
+        format!("<0.01 {}", price.currency)
+    } else {
+        format!("{:.2} {}", value, price.currency)
+    }
+}
+
 /// Parse a human-readable amount string using a specific number of decimal places.
 /// For example, `parse_token_amount("10.5", 8)` => `1_050_000_000`.
 pub fn parse_token_amount(s: &str, decimals: u8) -> Result<Amount, WalletError> {
@@ -152,8 +290,25 @@ pub fn format_address(addr: &Address) -> String {
     format!("0x{}", hex::encode(addr))
 }
 
-/// Parse a hex address string (with or without 0x prefix) into an Address.
+/// Format an Address as bech32m for the wallet's network.
+pub fn format_address_bech32(addr: &Address) -> String {
+    norn_crypto::address::address_to_bech32(addr, WalletConfig::address_network())
+}
+
+/// Whether a string is meant as a literal address rather than a name.
+pub fn is_address_literal(s: &str) -> bool {
+    s.starts_with("0x")
+        || (s.len() == 40 && hex::decode(s).is_ok())
+        || norn_crypto::address::is_bech32_address(s)
+}
+
+/// Parse an address given as hex (with or without 0x prefix) or as bech32m
+/// for the wallet's network.
 pub fn parse_address(s: &str) -> Result<Address, WalletError> {
+    if norn_crypto::address::is_bech32_address(s) {
+        return norn_crypto::address::parse_address(s, WalletConfig::address_network())
+            .map_err(|e| WalletError::InvalidAddress(e.to_string()));
+    }
     let hex_str = s.strip_prefix("0x").unwrap_or(s);
     if hex_str.len() != 40 {
         return Err(WalletError::InvalidAddress(format!(
@@ -196,6 +351,22 @@ pub fn format_pubkey(pk: &PublicKey) -> String {
     hex::encode(pk)
 }
 
+/// Parse a hex public key string (with or without 0x prefix) into a PublicKey.
+pub fn parse_pubkey(s: &str) -> Result<PublicKey, WalletError> {
+    let hex_str = s.strip_prefix("0x").unwrap_or(s);
+    if hex_str.len() != 64 {
+        return Err(WalletError::Other(format!(
+            "public key: expected 64 hex chars, got {}",
+            hex_str.len()
+        )));
+    }
+    let bytes = hex::decode(hex_str)
+        .map_err(|e| WalletError::Other(format!("invalid public key hex: {}", e)))?;
+    let mut pk = [0u8; 32];
+    pk.copy_from_slice(&bytes);
+    Ok(pk)
+}
+
 fn truncate_hex(hex: &str, len: usize) -> String {
     if hex.len() <= len * 2 {
         hex.to_string()
@@ -321,6 +492,38 @@ pub fn print_mnemonic_box(words: &[&str]) {
 mod tests {
     use super::*;
     use norn_types::constants::ONE_NORN;
+    use norn_types::network::NetworkId;
+
+    #[test]
+    fn test_denominations() {
+        let amount = 1_234 * ONE_NORN + ONE_NORN / 2;
+        assert_eq!(Denomination::Norn.format(amount), "1,234.5 NORN");
+        assert_eq!(Denomination::Mnorn.format(amount), "1,234,500 mNORN");
+        assert_eq!(Denomination::Nit.format(7), "7 nits");
+        assert_eq!(
+            "mNORN".parse::<Denomination>().unwrap(),
+            Denomination::Mnorn
+        );
+        assert_eq!(
+            Denomination::Nit
+                .to_string()
+                .parse::<Denomination>()
+                .unwrap(),
+            Denomination::Nit
+        );
+        assert!("sats".parse::<Denomination>().is_err());
+    }
+
+    #[test]
+    fn test_format_value() {
+        let price = Price {
+            per_norn: 0.5,
+            currency: "USD".to_string(),
+        };
+        assert_eq!(format_value(3 * ONE_NORN, &price), "1.50 USD");
+        assert_eq!(format_value(ONE_NORN / 1000, &price), "<0.01 USD");
+        assert_eq!(format_value(0, &price), "0.00 USD");
+    }
 
     #[test]
     fn test_format_amount_zero() {
@@ -397,6 +600,21 @@ mod tests {
         assert!(parse_address("0xdeadbeef").is_err());
     }
 
+    #[test]
+    fn test_parse_address_bech32() {
+        let addr = [0xab; 20];
+        let encoded = format_address_bech32(&addr);
+        assert!(is_address_literal(&encoded));
+        assert_eq!(parse_address(&encoded).unwrap(), addr);
+
+        let other_network = match WalletConfig::address_network() {
+            NetworkId::Mainnet => NetworkId::Testnet,
+            _ => NetworkId::Mainnet,
+        };
+        let other = norn_crypto::address::address_to_bech32(&addr, other_network);
+        assert!(parse_address(&other).is_err());
+    }
+
     #[test]
     fn test_parse_token_id_native() {
         assert_eq!(parse_token_id("NORN").unwrap(), NATIVE_TOKEN_ID);
@@ -0,0 +1,579 @@
+//! Local transaction journal.
+//!
+//! The node only remembers what reached it, and has nowhere to keep a
+//! user's notes. The journal is a SQLite database in the wallet directory
+//! recording every transfer the wallet submits, one row per transfer leg,
+//! with its status, memo and a user label. Reconciling it against the
+//! node's history marks submitted transfers as confirmed and imports
+//! transfers the journal has not seen, such as incoming ones or those sent
+//! from another device.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+
+use norn_types::knot::{Knot, KnotPayload, TransferPayload};
+use norn_types::primitives::Address;
+
+use super::config::WalletConfig;
+use super::error::WalletError;
+use super::format::{format_amount_with_symbol, format_token_amount_with_name, style_dim};
+use crate::rpc::types::{SubmitResult, TransactionHistoryEntry};
+
+/// Journal file name in the wallet data directory.
+const JOURNAL_FILE: &str = "journal.sqlite";
+
+/// Status of a transfer submitted and accepted, but not yet seen in a block.
+pub const STATUS_SUBMITTED: &str = "submitted";
+/// Status of a transfer the node refused.
+pub const STATUS_REJECTED: &str = "rejected";
+/// Status of a transfer seen in a block.
+pub const STATUS_CONFIRMED: &str = "confirmed";
+
+/// One transfer leg in the journal.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct JournalEntry {
+    /// Knot ID as hex.
+    pub knot_id: String,
+    /// Position of the transfer in its knot.
+    pub leg: u32,
+    /// "sent" or "received", relative to the wallet.
+    pub direction: String,
+    /// The other side's address as hex.
+    pub counterparty: String,
+    /// Token ID as hex.
+    pub token_id: String,
+    /// Raw amount.
+    pub amount: String,
+    /// Human-readable amount with its token symbol.
+    pub display: String,
+    pub memo: Option<String>,
+    pub label: Option<String>,
+    /// `submitted`, `rejected` or `confirmed`.
+    pub status: String,
+    /// Why the node rejected the transfer.
+    pub reason: Option<String>,
+    pub timestamp: u64,
+    pub block_height: Option<u64>,
+}
+
+/// What a reconciliation changed.
+#[derive(Debug, Default, PartialEq)]
+pub struct ReconcileStats {
+    /// Journal entries newly seen in a block.
+    pub confirmed: usize,
+    /// Node entries added to the journal.
+    pub imported: usize,
+}
+
+/// The journal database.
+pub struct Journal {
+    conn: Connection,
+}
+
+impl Journal {
+    /// Open the journal in the wallet data directory.
+    pub fn open() -> Result<Self, WalletError> {
+        let dir = WalletConfig::data_dir()?;
+        std::fs::create_dir_all(&dir)?;
+        Self::open_at(&dir.join(JOURNAL_FILE))
+    }
+
+    /// Open or create a journal at a path.
+    pub fn open_at(path: &Path) -> Result<Self, WalletError> {
+        let conn = Connection::open(path)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+        }
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS journal (
+                wallet TEXT NOT NULL,
+                knot_id TEXT NOT NULL,
+                leg INTEGER NOT NULL,
+                direction TEXT NOT NULL,
+                counterparty TEXT NOT NULL,
+                token_id TEXT NOT NULL,
+                amount TEXT NOT NULL,
+                display TEXT NOT NULL,
+                memo TEXT,
+                label TEXT,
+                status TEXT NOT NULL,
+                reason TEXT,
+                timestamp INTEGER NOT NULL,
+                block_height INTEGER,
+                PRIMARY KEY (wallet, knot_id, leg)
+            );
+            CREATE INDEX IF NOT EXISTS journal_time ON journal (wallet, timestamp);",
+        )?;
+        Ok(Self { conn })
+    }
+
+    /// Record the transfers of a knot the wallet submitted, with the node's
+    /// answer. `token` gives the symbol and decimals to show amounts with;
+    /// without it, amounts are shown in NORN units.
+    pub fn record_knot(
+        &self,
+        wallet: &Address,
+        knot: &Knot,
+        result: &SubmitResult,
+        label: Option<&str>,
+        token: Option<(&str, u8)>,
+    ) -> Result<usize, WalletError> {
+        let (status, reason) = if result.success {
+            (STATUS_SUBMITTED, None)
+        } else {
+            (STATUS_REJECTED, result.reason.as_deref())
+        };
+        let wallet_hex = hex::encode(wallet);
+        let knot_id = hex::encode(knot.id);
+        let mut recorded = 0;
+        for (leg, transfer) in transfers(knot).iter().enumerate() {
+            let sent = transfer.from == *wallet;
+            if !sent && transfer.to != *wallet {
+                continue;
+            }
+            let display = match token {
+                Some((symbol, decimals)) => {
+                    format_token_amount_with_name(transfer.amount, decimals, symbol)
+                }
+                None => format_amount_with_symbol(transfer.amount, &transfer.token_id),
+            };
+            self.conn.execute(
+                "INSERT OR REPLACE INTO journal (wallet, knot_id, leg, direction, counterparty,
+                    token_id, amount, display, memo, label, status, reason, timestamp)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+                params![
+                    wallet_hex,
+                    knot_id,
+                    leg as u32,
+                    if sent { "sent" } else { "received" },
+                    hex::encode(if sent { transfer.to } else { transfer.from }),
+                    hex::encode(transfer.token_id),
+                    transfer.amount.to_string(),
+                    display,
+                    transfer
+                        .memo
+                        .as_ref()
+                        .map(|m| String::from_utf8_lossy(m).into_owned()),
+                    label,
+                    status,
+                    reason,
+                    knot.timestamp as i64,
+                ],
+            )?;
+            recorded += 1;
+        }
+        Ok(recorded)
+    }
+
+    /// Set or clear the label of a knot, found by a unique prefix of its ID.
+    /// Returns the full knot ID.
+    pub fn set_label(
+        &self,
+        wallet: &Address,
+        knot_id_prefix: &str,
+        label: Option<&str>,
+    ) -> Result<String, WalletError> {
+        let prefix = knot_id_prefix
+            .strip_prefix("0x")
+            .unwrap_or(knot_id_prefix)
+            .to_lowercase();
+        if prefix.is_empty() || !prefix.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(WalletError::Other(format!(
+                "invalid knot ID '{}'",
+                knot_id_prefix
+            )));
+        }
+        let wallet_hex = hex::encode(wallet);
+        let mut stmt = self.conn.prepare(
+            "SELECT DISTINCT knot_id FROM journal WHERE wallet = ?1 AND knot_id LIKE ?2 || '%' LIMIT 2",
+        )?;
+        let ids: Vec<String> = stmt
+            .query_map(params![wallet_hex, prefix], |row| row.get(0))?
+            .collect::<Result<_, _>>()?;
+        let knot_id = match ids.as_slice() {
+            [id] => id.clone(),
+            [] => {
+                return Err(WalletError::Other(format!(
+                    "no journal entry for knot '{}'",
+                    knot_id_prefix
+                )))
+            }
+            _ => {
+                return Err(WalletError::Other(format!(
+                    "knot ID '{}' is ambiguous; give more digits",
+                    knot_id_prefix
+                )))
+            }
+        };
+        self.conn.execute(
+            "UPDATE journal SET label = ?3 WHERE wallet = ?1 AND knot_id = ?2",
+            params![wallet_hex, knot_id, label],
+        )?;
+        Ok(knot_id)
+    }
+
+    /// Entries of a wallet, newest first; all of them if `limit` is `None`.
+    pub fn entries(
+        &self,
+        wallet: &Address,
+        limit: Option<usize>,
+    ) -> Result<Vec<JournalEntry>, WalletError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT knot_id, leg, direction, counterparty, token_id, amount, display, memo,
+                    label, status, reason, timestamp, block_height
+             FROM journal WHERE wallet = ?1
+             ORDER BY timestamp DESC, knot_id, leg LIMIT ?2",
+        )?;
+        let limit = limit.map_or(-1, |l| l as i64);
+        let entries = stmt
+            .query_map(params![hex::encode(wallet), limit], |row| {
+                Ok(JournalEntry {
+                    knot_id: row.get(0)?,
+                    leg: row.get(1)?,
+                    direction: row.get(2)?,
+                    counterparty: row.get(3)?,
+                    token_id: row.get(4)?,
+                    amount: row.get(5)?,
+                    display: row.get(6)?,
+                    memo: row.get(7)?,
+                    label: row.get(8)?,
+                    status: row.get(9)?,
+                    reason: row.get(10)?,
+                    timestamp: row.get::<_, i64>(11)? as u64,
+                    block_height: row.get::<_, Option<i64>>(12)?.map(|h| h as u64),
+                })
+            })?
+            .collect::<Result<_, _>>()?;
+        Ok(entries)
+    }
+
+    /// Bring the journal in line with the node's history of the wallet:
+    /// entries seen in a block become confirmed, and node entries missing
+    /// from the journal are imported.
+    pub fn reconcile(
+        &mut self,
+        wallet: &Address,
+        history: &[TransactionHistoryEntry],
+    ) -> Result<ReconcileStats, WalletError> {
+        let wallet_hex = hex::encode(wallet);
+        let tx = self.conn.transaction()?;
+        let mut stats = ReconcileStats::default();
+        // Legs already matched in this pass, so repeated transfers of the
+        // same amount in one knot each match their own leg.
+        let mut matched: HashSet<(String, u32)> = HashSet::new();
+
+        for entry in history {
+            let counterparty = if entry.direction == "sent" {
+                &entry.to
+            } else {
+                &entry.from
+            };
+            let legs: Vec<(u32, String)> = {
+                let mut stmt = tx.prepare(
+                    "SELECT leg, status FROM journal
+                     WHERE wallet = ?1 AND knot_id = ?2 AND direction = ?3
+                       AND counterparty = ?4 AND token_id = ?5 AND amount = ?6
+                     ORDER BY leg",
+                )?;
+                let rows = stmt.query_map(
+                    params![
+                        wallet_hex,
+                        entry.knot_id,
+                        entry.direction,
+                        counterparty,
+                        entry.token_id,
+                        entry.amount
+                    ],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )?;
+                rows.collect::<Result<_, _>>()?
+            };
+            let status = if entry.block_height.is_some() {
+                STATUS_CONFIRMED
+            } else {
+                STATUS_SUBMITTED
+            };
+
+            match legs
+                .into_iter()
+                .find(|(leg, _)| !matched.contains(&(entry.knot_id.clone(), *leg)))
+            {
+                Some((leg, old_status)) => {
+                    matched.insert((entry.knot_id.clone(), leg));
+                    if old_status != STATUS_CONFIRMED && status == STATUS_CONFIRMED {
+                        tx.execute(
+                            "UPDATE journal SET status = ?4, reason = NULL, block_height = ?5
+                             WHERE wallet = ?1 AND knot_id = ?2 AND leg = ?3",
+                            params![
+                                wallet_hex,
+                                entry.knot_id,
+                                leg,
+                                status,
+                                entry.block_height.map(|h| h as i64)
+                            ],
+                        )?;
+                        stats.confirmed += 1;
+                    }
+                }
+                None => {
+                    let leg: u32 = tx
+                        .query_row(
+                            "SELECT MAX(leg) + 1 FROM journal WHERE wallet = ?1 AND knot_id = ?2",
+                            params![wallet_hex, entry.knot_id],
+                            |row| row.get::<_, Option<u32>>(0),
+                        )
+                        .optional()?
+                        .flatten()
+                        .unwrap_or(0);
+                    tx.execute(
+                        "INSERT INTO journal (wallet, knot_id, leg, direction, counterparty,
+                            token_id, amount, display, memo, status, timestamp, block_height)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                        params![
+                            wallet_hex,
+                            entry.knot_id,
+                            leg,
+                            entry.direction,
+                            counterparty,
+                            entry.token_id,
+                            entry.amount,
+                            format!("{} {}", entry.human_readable, entry.symbol),
+                            entry.memo,
+                            status,
+                            entry.timestamp as i64,
+                            entry.block_height.map(|h| h as i64)
+                        ],
+                    )?;
+                    matched.insert((entry.knot_id.clone(), leg));
+                    stats.imported += 1;
+                }
+            }
+        }
+        tx.commit()?;
+        Ok(stats)
+    }
+}
+
+/// The transfers a knot makes.
+fn transfers(knot: &Knot) -> Vec<&TransferPayload> {
+    match &knot.payload {
+        KnotPayload::Transfer(transfer) => vec![transfer],
+        KnotPayload::MultiTransfer(multi) => multi.transfers.iter().collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Record a submitted knot in the default journal. The transfer has
+/// already happened, so a journal failure is only reported.
+pub fn record_submission(
+    wallet: &Address,
+    knot: &Knot,
+    result: &SubmitResult,
+    label: Option<&str>,
+    token: Option<(&str, u8)>,
+) {
+    let recorded =
+        Journal::open().and_then(|journal| journal.record_knot(wallet, knot, result, label, token));
+    if let Err(e) = recorded {
+        println!(
+            "  {}",
+            style_dim().apply_to(format!(
+                "Could not record the transfer in the journal: {}",
+                e
+            ))
+        );
+    }
+}
+
+/// Quote a CSV field if it needs it.
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// Write entries as CSV.
+pub fn write_csv(path: &Path, entries: &[JournalEntry]) -> Result<(), WalletError> {
+    let mut out = String::from(
+        "timestamp,knot_id,leg,direction,counterparty,token_id,amount,display,memo,label,status,reason,block_height\n",
+    );
+    for e in entries {
+        let fields = [
+            e.timestamp.to_string(),
+            e.knot_id.clone(),
+            e.leg.to_string(),
+            e.direction.clone(),
+            e.counterparty.clone(),
+            e.token_id.clone(),
+            e.amount.clone(),
+            e.display.clone(),
+            e.memo.clone().unwrap_or_default(),
+            e.label.clone().unwrap_or_default(),
+            e.status.clone(),
+            e.reason.clone().unwrap_or_default(),
+            e.block_height.map(|h| h.to_string()).unwrap_or_default(),
+        ];
+        let line: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        out.push_str(&line.join(","));
+        out.push('\n');
+    }
+    std::fs::write(path, out)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use norn_crypto::address::pubkey_to_address;
+    use norn_crypto::keys::Keypair;
+    use norn_types::knot::MultiTransferPayload;
+    use norn_types::primitives::NATIVE_TOKEN_ID;
+    use norn_types::thread::ThreadState;
+
+    fn payout(owner: &Keypair, amounts: &[u128]) -> Knot {
+        let from = pubkey_to_address(&owner.public_key());
+        let state = ThreadState::new();
+        norn_thread::knot::KnotBuilder::multi_transfer(1000)
+            .add_before_state(from, owner.public_key(), 0, &state)
+            .add_after_state(from, owner.public_key(), 1, &state)
+            .with_payload(KnotPayload::MultiTransfer(MultiTransferPayload {
+                transfers: amounts
+                    .iter()
+                    .map(|&amount| TransferPayload {
+                        token_id: NATIVE_TOKEN_ID,
+                        amount,
+                        from,
+                        to: [9u8; 20],
+                        memo: Some(b"rent, march".to_vec()),
+                    })
+                    .collect(),
+            }))
+            .build()
+            .unwrap()
+    }
+
+    fn history_entry(
+        knot: &Knot,
+        from: &Address,
+        amount: u128,
+        height: u64,
+    ) -> TransactionHistoryEntry {
+        TransactionHistoryEntry {
+            knot_id: hex::encode(knot.id),
+            from: hex::encode(from),
+            to: hex::encode([9u8; 20]),
+            token_id: hex::encode(NATIVE_TOKEN_ID),
+            symbol: "NORN".to_string(),
+            amount: amount.to_string(),
+            human_readable: amount.to_string(),
+            memo: None,
+            timestamp: 1000,
+            block_height: Some(height),
+            direction: "sent".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_record_label_and_reconcile() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut journal = Journal::open_at(&dir.path().join(JOURNAL_FILE)).unwrap();
+        let owner = Keypair::generate();
+        let wallet = pubkey_to_address(&owner.public_key());
+
+        // Two legs of the same amount, then one more transfer elsewhere.
+        let knot = payout(&owner, &[5, 5]);
+        let accepted = SubmitResult {
+            success: true,
+            reason: None,
+        };
+        assert_eq!(
+            journal
+                .record_knot(&wallet, &knot, &accepted, Some("payroll"), None)
+                .unwrap(),
+            2
+        );
+        let id = hex::encode(knot.id);
+        assert_eq!(
+            journal
+                .set_label(&wallet, &id[..8], Some("march payroll"))
+                .unwrap(),
+            id
+        );
+        assert!(journal.set_label(&wallet, "ffff", None).is_err());
+
+        let other = payout(&owner, &[7]);
+        let stats = journal
+            .reconcile(
+                &wallet,
+                &[
+                    history_entry(&knot, &wallet, 5, 3),
+                    history_entry(&knot, &wallet, 5, 3),
+                    history_entry(&other, &wallet, 7, 4),
+                ],
+            )
+            .unwrap();
+        assert_eq!(
+            stats,
+            ReconcileStats {
+                confirmed: 2,
+                imported: 1
+            }
+        );
+
+        let entries = journal.entries(&wallet, None).unwrap();
+        assert_eq!(entries.len(), 3);
+        let legs: Vec<&JournalEntry> = entries.iter().filter(|e| e.knot_id == id).collect();
+        assert!(legs.iter().all(|e| e.status == STATUS_CONFIRMED));
+        assert!(legs
+            .iter()
+            .all(|e| e.label.as_deref() == Some("march payroll")));
+        assert_eq!(legs[0].memo.as_deref(), Some("rent, march"));
+
+        // Reconciling again changes nothing.
+        let again = journal
+            .reconcile(&wallet, &[history_entry(&other, &wallet, 7, 4)])
+            .unwrap();
+        assert_eq!(again, ReconcileStats::default());
+        assert_eq!(journal.entries(&wallet, Some(1)).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_rejected_knot_and_csv_export() {
+        let dir = tempfile::tempdir().unwrap();
+        let journal = Journal::open_at(&dir.path().join(JOURNAL_FILE)).unwrap();
+        let owner = Keypair::generate();
+        let wallet = pubkey_to_address(&owner.public_key());
+
+        let rejected = SubmitResult {
+            success: false,
+            reason: Some("insufficient balance".to_string()),
+        };
+        journal
+            .record_knot(
+                &wallet,
+                &payout(&owner, &[3]),
+                &rejected,
+                None,
+                Some(("USDN", 2)),
+            )
+            .unwrap();
+        let entries = journal.entries(&wallet, None).unwrap();
+        assert_eq!(entries[0].status, STATUS_REJECTED);
+        assert_eq!(entries[0].display, "0.03 USDN");
+
+        let csv = dir.path().join("history.csv");
+        write_csv(&csv, &entries).unwrap();
+        let text = std::fs::read_to_string(&csv).unwrap();
+        assert_eq!(text.lines().count(), 2);
+        assert!(text.contains("\"rent, march\""));
+        // Another wallet's journal is empty.
+        assert!(journal.entries(&[1u8; 20], None).unwrap().is_empty());
+    }
+}
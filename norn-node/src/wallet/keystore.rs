@@ -3,7 +3,9 @@ use serde::{Deserialize, Serialize};
 use norn_crypto::address::pubkey_to_address;
 use norn_crypto::encryption::{decrypt, encrypt_for_keypair, EncryptedMessage};
 use norn_crypto::hash::blake3_kdf;
-use norn_crypto::hd::derive_default_keypair;
+use norn_crypto::hd::{
+    default_account_path, derive_account_keypair, format_derivation_path, parse_derivation_path,
+};
 use norn_crypto::keys::Keypair;
 use norn_types::primitives::Address;
 
@@ -101,41 +103,148 @@ pub struct WalletFile {
     pub public_key: String,
     pub derivation_index: u32,
     pub has_mnemonic: bool,
-    pub encrypted_seed: EncryptedBlob,
+    /// Whether the seed was derived with a BIP-39 passphrase, without which
+    /// the mnemonic alone restores a different wallet.
+    #[serde(default)]
+    pub has_passphrase: bool,
+    /// Absent for watch-only wallets, which hold no key.
+    #[serde(default)]
+    pub encrypted_seed: Option<EncryptedBlob>,
     pub encrypted_mnemonic: Option<EncryptedBlob>,
     /// Per-wallet random salt for Argon2id KDF (v3+). Hex-encoded 16 bytes.
     /// Absent in v1/v2 wallets (which used fixed salts).
     #[serde(default)]
     pub salt: Option<String>,
+    /// Argon2id parameters the encryption key was derived with (v4+).
+    /// Absent in older wallets, which used `KdfParams::legacy()`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kdf: Option<KdfParams>,
+    /// Set for wallets whose key lives on a Ledger.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ledger: Option<LedgerKey>,
+    /// Account path of a mnemonic wallet, to which the account index is
+    /// appended. Absent for the default m/44'/NORN'/0'/0'.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub derivation_path: Option<String>,
+    /// Accounts derived from the mnemonic. Empty until a second account is
+    /// added; `derivation_index` selects the active one.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub accounts: Vec<WalletAccount>,
+}
+
+/// An account derived from a mnemonic wallet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WalletAccount {
+    /// Last component of the derivation path.
+    pub index: u32,
+    pub address: String,
+    pub public_key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+/// Where a Ledger wallet's key lives on the device.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LedgerKey {
+    /// Last component of the derivation path m/44'/NORN'/0'/0'/account'.
+    pub account: u32,
+}
+
+/// Argon2id hardness parameters of a keystore.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    /// Memory cost in KiB.
+    pub memory_kib: u32,
+    /// Number of passes.
+    pub iterations: u32,
+    /// Degree of parallelism.
+    pub parallelism: u32,
+}
+
+impl KdfParams {
+    /// Parameters of v2 and v3 wallets: the argon2 crate defaults.
+    pub const fn legacy() -> Self {
+        Self {
+            memory_kib: 19 * 1024,
+            iterations: 2,
+            parallelism: 1,
+        }
+    }
+
+    /// Check the parameters before deriving a key with them, so a tampered
+    /// wallet file cannot make unlocking exhaust memory or CPU.
+    pub fn validate(&self) -> Result<(), WalletError> {
+        if !(MIN_KDF_MEMORY_KIB..=MAX_KDF_MEMORY_KIB).contains(&self.memory_kib)
+            || !(1..=MAX_KDF_ITERATIONS).contains(&self.iterations)
+            || !(1..=MAX_KDF_PARALLELISM).contains(&self.parallelism)
+        {
+            return Err(WalletError::Other(format!(
+                "unsupported KDF parameters: {} KiB, {} iterations, parallelism {} \
+                 (memory {}..={} KiB, iterations 1..={}, parallelism 1..={})",
+                self.memory_kib,
+                self.iterations,
+                self.parallelism,
+                MIN_KDF_MEMORY_KIB,
+                MAX_KDF_MEMORY_KIB,
+                MAX_KDF_ITERATIONS,
+                MAX_KDF_PARALLELISM
+            )));
+        }
+        Ok(())
+    }
+
+    /// Whether these parameters are weaker than `other` in any dimension.
+    fn weaker_than(&self, other: &KdfParams) -> bool {
+        self.memory_kib < other.memory_kib || self.iterations < other.iterations
+    }
+}
+
+impl Default for KdfParams {
+    /// Parameters for new and upgraded wallets: the second recommended
+    /// option of RFC 9106 (64 MiB, 3 passes, 4 lanes).
+    fn default() -> Self {
+        Self {
+            memory_kib: 64 * 1024,
+            iterations: 3,
+            parallelism: 4,
+        }
+    }
 }
 
 /// In-memory representation of a loaded wallet.
+#[derive(Clone)]
 pub struct Keystore {
     pub name: String,
     pub address: Address,
     pub public_key: [u8; 32],
     pub file: WalletFile,
+    /// Whether the wallet was loaded from disk, and so can be migrated there
+    /// when unlocked with an outdated format.
+    on_disk: bool,
 }
 
 impl Keystore {
-    /// Create a new wallet from a mnemonic and password.
-    pub fn create(
+    /// Create a new wallet from a mnemonic and password, with accounts
+    /// derived under `account_path` and the key encrypted with `kdf`.
+    pub fn create_at_path(
         name: &str,
         mnemonic: &bip39::Mnemonic,
         passphrase: &str,
         password: &str,
+        account_path: &[u32],
+        kdf: KdfParams,
     ) -> Result<Self, WalletError> {
         validate_wallet_name(name)?;
         let seed = norn_crypto::seed::mnemonic_to_seed(mnemonic, passphrase);
-        let keypair = derive_default_keypair(&seed)?;
+        let keypair = derive_account_keypair(&seed, account_path, 0)?;
         let address = pubkey_to_address(&keypair.public_key());
         let public_key = keypair.public_key();
 
-        // Generate a random per-wallet salt for Argon2id KDF (v3).
+        // Generate a random per-wallet salt for the Argon2id KDF.
         let salt = generate_random_salt();
 
         // Derive a password-based keypair for encryption
-        let password_keypair = password_to_keypair_argon2_with_salt(password, &salt)?;
+        let password_keypair = password_to_keypair_argon2(password, &salt, &kdf)?;
 
         // Encrypt the 64-byte seed
         let encrypted_seed = encrypt_for_keypair(&password_keypair, &seed)?;
@@ -150,16 +259,22 @@ impl Keystore {
             .as_secs();
 
         let file = WalletFile {
-            version: WALLET_VERSION_RANDOM_SALT,
+            version: WALLET_VERSION,
             name: name.to_string(),
             created_at: now,
             address: format!("0x{}", hex::encode(address)),
             public_key: hex::encode(public_key),
             derivation_index: 0,
             has_mnemonic: true,
-            encrypted_seed: EncryptedBlob::from_encrypted(&encrypted_seed),
+            has_passphrase: !passphrase.is_empty(),
+            encrypted_seed: Some(EncryptedBlob::from_encrypted(&encrypted_seed)),
             encrypted_mnemonic: Some(EncryptedBlob::from_encrypted(&encrypted_mnemonic)),
             salt: Some(hex::encode(salt)),
+            kdf: Some(kdf),
+            ledger: None,
+            derivation_path: (account_path != default_account_path().as_slice())
+                .then(|| format_derivation_path(account_path)),
+            accounts: Vec::new(),
         };
 
         Ok(Self {
@@ -167,23 +282,26 @@ impl Keystore {
             address,
             public_key,
             file,
+            on_disk: false,
         })
     }
 
-    /// Create a wallet from a private key seed (32 bytes).
+    /// Create a wallet from a private key seed (32 bytes), encrypted with
+    /// `kdf`.
     pub fn from_private_key(
         name: &str,
         seed_bytes: &[u8; 32],
         password: &str,
+        kdf: KdfParams,
     ) -> Result<Self, WalletError> {
         validate_wallet_name(name)?;
         let keypair = Keypair::from_seed(seed_bytes);
         let address = pubkey_to_address(&keypair.public_key());
         let public_key = keypair.public_key();
 
-        // Generate a random per-wallet salt for Argon2id KDF (v3).
+        // Generate a random per-wallet salt for the Argon2id KDF.
         let salt = generate_random_salt();
-        let password_keypair = password_to_keypair_argon2_with_salt(password, &salt)?;
+        let password_keypair = password_to_keypair_argon2(password, &salt, &kdf)?;
 
         // For a private key import, we store the 32-byte seed padded to 64 bytes
         // (only first 32 are meaningful)
@@ -197,16 +315,21 @@ impl Keystore {
             .as_secs();
 
         let file = WalletFile {
-            version: WALLET_VERSION_RANDOM_SALT,
+            version: WALLET_VERSION,
             name: name.to_string(),
             created_at: now,
             address: format!("0x{}", hex::encode(address)),
             public_key: hex::encode(public_key),
             derivation_index: 0,
             has_mnemonic: false,
-            encrypted_seed: EncryptedBlob::from_encrypted(&encrypted_seed),
+            has_passphrase: false,
+            encrypted_seed: Some(EncryptedBlob::from_encrypted(&encrypted_seed)),
             encrypted_mnemonic: None,
             salt: Some(hex::encode(salt)),
+            kdf: Some(kdf),
+            ledger: None,
+            derivation_path: None,
+            accounts: Vec::new(),
         };
 
         Ok(Self {
@@ -214,12 +337,83 @@ impl Keystore {
             address,
             public_key,
             file,
+            on_disk: false,
         })
     }
 
+    /// Create a watch-only wallet for a thread whose key lives elsewhere,
+    /// such as a cold-storage device. It can build knots and follow the
+    /// thread, but not sign. The public key is all zeros if only the
+    /// thread ID is known.
+    pub fn watch_only(
+        name: &str,
+        address: Address,
+        public_key: Option<[u8; 32]>,
+    ) -> Result<Self, WalletError> {
+        validate_wallet_name(name)?;
+        if let Some(pk) = public_key {
+            if pubkey_to_address(&pk) != address {
+                return Err(WalletError::InvalidAddress(
+                    "public key does not match the thread ID".to_string(),
+                ));
+            }
+        }
+        let public_key = public_key.unwrap_or([0u8; 32]);
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        let file = WalletFile {
+            version: WALLET_VERSION,
+            name: name.to_string(),
+            created_at: now,
+            address: format!("0x{}", hex::encode(address)),
+            public_key: hex::encode(public_key),
+            derivation_index: 0,
+            has_mnemonic: false,
+            has_passphrase: false,
+            encrypted_seed: None,
+            encrypted_mnemonic: None,
+            salt: None,
+            kdf: None,
+            ledger: None,
+            derivation_path: None,
+            accounts: Vec::new(),
+        };
+
+        Ok(Self {
+            name: name.to_string(),
+            address,
+            public_key,
+            file,
+            on_disk: false,
+        })
+    }
+
+    /// Create a wallet for a key held on a Ledger. The keystore only
+    /// records which account to ask the device for.
+    pub fn ledger(name: &str, public_key: [u8; 32], account: u32) -> Result<Self, WalletError> {
+        let mut ks = Self::watch_only(name, pubkey_to_address(&public_key), Some(public_key))?;
+        ks.file.derivation_index = account;
+        ks.file.ledger = Some(LedgerKey { account });
+        Ok(ks)
+    }
+
+    /// Whether the wallet holds no key and cannot sign.
+    pub fn is_watch_only(&self) -> bool {
+        self.file.encrypted_seed.is_none() && self.file.ledger.is_none()
+    }
+
+    /// Whether the wallet's key lives on a Ledger.
+    pub fn is_ledger(&self) -> bool {
+        self.file.ledger.is_some()
+    }
+
     /// Save the wallet file to disk.
     pub fn save(&self) -> Result<(), WalletError> {
-        let dir = WalletConfig::data_dir()?;
+        let dir = WalletConfig::keystore_dir()?;
         std::fs::create_dir_all(&dir)?;
 
         // Set directory permissions to 0o700 on Unix.
@@ -258,7 +452,7 @@ impl Keystore {
     /// Load a wallet from disk by name.
     pub fn load(name: &str) -> Result<Self, WalletError> {
         validate_wallet_name(name)?;
-        let path = WalletConfig::data_dir()?.join(format!("{}.json", name));
+        let path = WalletConfig::keystore_dir()?.join(format!("{}.json", name));
         if !path.exists() {
             return Err(WalletError::WalletNotFound(name.to_string()));
         }
@@ -281,13 +475,14 @@ impl Keystore {
             address,
             public_key,
             file,
+            on_disk: true,
         })
     }
 
     /// Delete a wallet file from disk.
     pub fn delete(name: &str) -> Result<(), WalletError> {
         validate_wallet_name(name)?;
-        let path = WalletConfig::data_dir()?.join(format!("{}.json", name));
+        let path = WalletConfig::keystore_dir()?.join(format!("{}.json", name));
         if !path.exists() {
             return Err(WalletError::WalletNotFound(name.to_string()));
         }
@@ -297,7 +492,7 @@ impl Keystore {
 
     /// List all wallet names on disk.
     pub fn list_names() -> Result<Vec<String>, WalletError> {
-        let dir = WalletConfig::data_dir()?;
+        let dir = WalletConfig::keystore_dir()?;
         if !dir.exists() {
             return Ok(Vec::new());
         }
@@ -320,23 +515,166 @@ impl Keystore {
     /// Decrypt the keypair using a password.
     /// Automatically selects the correct KDF based on wallet version.
     pub fn decrypt_keypair(&self, password: &str) -> Result<Keypair, WalletError> {
+        let encrypted_seed = self.encrypted_seed()?;
         let password_keypair = self.password_keypair(password)?;
-        let (eph, nonce, ct) = self.file.encrypted_seed.to_parts()?;
+        let (eph, nonce, ct) = encrypted_seed.to_parts()?;
         let seed_bytes = decrypt(&password_keypair, &eph, &nonce, &ct)
             .map_err(|_| WalletError::InvalidPassword)?;
 
         // Derive the keypair from the seed
-        if self.file.has_mnemonic && seed_bytes.len() == 64 {
+        let keypair = if self.file.has_mnemonic && seed_bytes.len() == 64 {
             let mut seed = [0u8; 64];
             seed.copy_from_slice(&seed_bytes);
-            let keypair = derive_default_keypair(&seed)?;
-            Ok(keypair)
+            derive_account_keypair(&seed, &self.account_path()?, self.file.derivation_index)?
         } else {
             // Private key import — first 32 bytes are the seed
             let mut seed32 = [0u8; 32];
             seed32.copy_from_slice(&seed_bytes[..32]);
-            Ok(Keypair::from_seed(&seed32))
+            Keypair::from_seed(&seed32)
+        };
+
+        Ok(keypair)
+    }
+
+    /// Account path of a mnemonic wallet, without the account index.
+    pub fn account_path(&self) -> Result<Vec<u32>, WalletError> {
+        match &self.file.derivation_path {
+            Some(path) => Ok(parse_derivation_path(path)?),
+            None => Ok(default_account_path()),
+        }
+    }
+
+    /// Decrypt the BIP-39 seed of a mnemonic wallet, to derive accounts from.
+    pub fn decrypt_hd_seed(&self, password: &str) -> Result<[u8; 64], WalletError> {
+        let encrypted_seed = self.encrypted_seed()?;
+        if !self.file.has_mnemonic {
+            return Err(WalletError::Other(format!(
+                "wallet '{}' was imported from a private key and has a single account",
+                self.name
+            )));
+        }
+        let password_keypair = self.password_keypair(password)?;
+        let (eph, nonce, ct) = encrypted_seed.to_parts()?;
+        let seed_bytes = decrypt(&password_keypair, &eph, &nonce, &ct)
+            .map_err(|_| WalletError::InvalidPassword)?;
+        <[u8; 64]>::try_from(seed_bytes.as_slice())
+            .map_err(|_| WalletError::SerializationError("invalid seed length".to_string()))
+    }
+
+    /// Derive account `index` from a seed returned by [`Self::decrypt_hd_seed`].
+    pub fn derive_account(
+        &self,
+        seed: &[u8; 64],
+        index: u32,
+    ) -> Result<WalletAccount, WalletError> {
+        let public_key = derive_account_keypair(seed, &self.account_path()?, index)?.public_key();
+        Ok(WalletAccount {
+            index,
+            address: format!("0x{}", hex::encode(pubkey_to_address(&public_key))),
+            public_key: hex::encode(public_key),
+            label: None,
+        })
+    }
+
+    /// Known accounts, sorted by index. A wallet that never added an
+    /// account has just its active one.
+    pub fn accounts(&self) -> Vec<WalletAccount> {
+        if self.file.accounts.is_empty() {
+            return vec![WalletAccount {
+                index: self.file.derivation_index,
+                address: self.file.address.clone(),
+                public_key: self.file.public_key.clone(),
+                label: None,
+            }];
+        }
+        let mut accounts = self.file.accounts.clone();
+        accounts.sort_by_key(|a| a.index);
+        accounts
+    }
+
+    /// Record a derived account. Rejects indexes and labels already in use.
+    pub fn add_account(&mut self, account: WalletAccount) -> Result<(), WalletError> {
+        let mut accounts = self.accounts();
+        if accounts.iter().any(|a| a.index == account.index) {
+            return Err(WalletError::Other(format!(
+                "account {} already exists",
+                account.index
+            )));
+        }
+        if let Some(label) = &account.label {
+            if accounts.iter().any(|a| a.label.as_ref() == Some(label)) {
+                return Err(WalletError::Other(format!(
+                    "account label '{label}' already exists"
+                )));
+            }
+        }
+        accounts.push(account);
+        accounts.sort_by_key(|a| a.index);
+        self.file.accounts = accounts;
+        Ok(())
+    }
+
+    /// Find an account by index or label.
+    pub fn find_account(&self, account: &str) -> Option<WalletAccount> {
+        let accounts = self.accounts();
+        match account.parse::<u32>() {
+            Ok(index) => accounts.into_iter().find(|a| a.index == index),
+            Err(_) => accounts
+                .into_iter()
+                .find(|a| a.label.as_deref() == Some(account)),
+        }
+    }
+
+    /// Make a known account the one the wallet signs with.
+    pub fn use_account(&mut self, index: u32) -> Result<(), WalletError> {
+        let account = self
+            .accounts()
+            .into_iter()
+            .find(|a| a.index == index)
+            .ok_or_else(|| WalletError::Other(format!("account {index} not found")))?;
+        let address = hex::decode(account.address.trim_start_matches("0x"))
+            .ok()
+            .and_then(|b| Address::try_from(b.as_slice()).ok())
+            .ok_or_else(|| WalletError::InvalidAddress(account.address.clone()))?;
+        let public_key = hex::decode(&account.public_key)
+            .ok()
+            .and_then(|b| <[u8; 32]>::try_from(b.as_slice()).ok())
+            .ok_or_else(|| {
+                WalletError::SerializationError("invalid account public key".to_string())
+            })?;
+        self.address = address;
+        self.public_key = public_key;
+        self.file.address = account.address;
+        self.file.public_key = account.public_key;
+        self.file.derivation_index = index;
+        Ok(())
+    }
+
+    /// Whether the wallet uses an older format or weaker KDF parameters
+    /// than new wallets.
+    pub fn needs_upgrade(&self) -> bool {
+        self.file.encrypted_seed.is_some()
+            && (self.file.version < WALLET_VERSION
+                || self.kdf_params().weaker_than(&KdfParams::default()))
+    }
+
+    /// KDF parameters the wallet's key is derived with.
+    pub fn kdf_params(&self) -> KdfParams {
+        self.file.kdf.unwrap_or_else(KdfParams::legacy)
+    }
+
+    /// Re-encrypt an outdated wallet on disk in the current format,
+    /// keeping its password. Returns whether the file was upgraded; wallets
+    /// that are current or not loaded from disk are left alone. On failure
+    /// both the old file and `self` keep working.
+    pub fn migrate(&mut self, password: &str) -> Result<bool, WalletError> {
+        if !self.on_disk || !self.needs_upgrade() {
+            return Ok(false);
         }
+        let mut upgraded = self.clone();
+        upgraded.rekey(password, password, KdfParams::default())?;
+        *self = upgraded;
+        Ok(true)
     }
 
     /// Decrypt the mnemonic phrase if available.
@@ -355,15 +693,45 @@ impl Keystore {
     }
 
     /// Change the encryption password, re-encrypting seed and mnemonic.
-    /// Also upgrades v1/v2 wallets to v3 format (Argon2id + random salt).
+    /// Also upgrades older wallets to the current format, keeping KDF
+    /// parameters that are stronger than the defaults.
     pub fn change_password(
         &mut self,
         old_password: &str,
         new_password: &str,
     ) -> Result<(), WalletError> {
+        let kdf = if self.kdf_params().weaker_than(&KdfParams::default()) {
+            KdfParams::default()
+        } else {
+            self.kdf_params()
+        };
+        self.rekey(old_password, new_password, kdf)
+    }
+
+    /// Re-encrypt seed and mnemonic under a new password, which may be the
+    /// old one, with a fresh salt and the given KDF parameters, and save the
+    /// wallet in the current format.
+    pub fn rekey(
+        &mut self,
+        old_password: &str,
+        new_password: &str,
+        kdf: KdfParams,
+    ) -> Result<(), WalletError> {
+        self.reencrypt(old_password, new_password, kdf)?;
+        self.save()
+    }
+
+    fn reencrypt(
+        &mut self,
+        old_password: &str,
+        new_password: &str,
+        kdf: KdfParams,
+    ) -> Result<(), WalletError> {
+        kdf.validate()?;
+
         // Decrypt seed with old password
         let old_keypair = self.password_keypair(old_password)?;
-        let (eph, nonce, ct) = self.file.encrypted_seed.to_parts()?;
+        let (eph, nonce, ct) = self.encrypted_seed()?.to_parts()?;
         let seed_bytes =
             decrypt(&old_keypair, &eph, &nonce, &ct).map_err(|_| WalletError::InvalidPassword)?;
 
@@ -379,7 +747,7 @@ impl Keystore {
 
         // Generate new salt and derive new encryption keypair
         let new_salt = generate_random_salt();
-        let new_keypair = password_to_keypair_argon2_with_salt(new_password, &new_salt)?;
+        let new_keypair = password_to_keypair_argon2(new_password, &new_salt, &kdf)?;
 
         // Re-encrypt seed
         let encrypted_seed = encrypt_for_keypair(&new_keypair, &seed_bytes)
@@ -395,27 +763,51 @@ impl Keystore {
             None => None,
         };
 
-        // Update file fields (upgrades to v3 if needed)
-        self.file.encrypted_seed = EncryptedBlob::from_encrypted(&encrypted_seed);
+        // Update file fields (upgrades to the current version if needed)
+        self.file.encrypted_seed = Some(EncryptedBlob::from_encrypted(&encrypted_seed));
         self.file.encrypted_mnemonic = encrypted_mnemonic;
         self.file.salt = Some(hex::encode(new_salt));
-        self.file.version = WALLET_VERSION_RANDOM_SALT;
+        self.file.kdf = Some(kdf);
+        self.file.version = WALLET_VERSION;
+        Ok(())
+    }
 
-        self.save()
+    fn encrypted_seed(&self) -> Result<&EncryptedBlob, WalletError> {
+        match (&self.file.encrypted_seed, &self.file.ledger) {
+            (Some(blob), _) => Ok(blob),
+            (None, Some(_)) => Err(WalletError::HardwareWallet(self.name.clone())),
+            (None, None) => Err(WalletError::WatchOnly(self.name.clone())),
+        }
     }
 
     /// Derive the password keypair for this wallet, choosing KDF based on version and salt.
     fn password_keypair(&self, password: &str) -> Result<Keypair, WalletError> {
-        password_to_keypair_for_version(password, self.file.version, self.file.salt.as_deref())
+        password_to_keypair_for_version(
+            password,
+            self.file.version,
+            self.file.salt.as_deref(),
+            &self.kdf_params(),
+        )
     }
 }
 
 /// Wallet version 1 uses BLAKE3 KDF, version 2 uses Argon2id with fixed salt,
-/// version 3 uses Argon2id with per-wallet random salt.
+/// version 3 uses Argon2id with per-wallet random salt, and version 4 also
+/// records the Argon2id parameters.
 #[allow(dead_code)]
 const WALLET_VERSION_BLAKE3: u32 = 1;
 const WALLET_VERSION_ARGON2: u32 = 2;
 const WALLET_VERSION_RANDOM_SALT: u32 = 3;
+const WALLET_VERSION_KDF_PARAMS: u32 = 4;
+
+/// Version of new and upgraded wallets.
+pub const WALLET_VERSION: u32 = WALLET_VERSION_KDF_PARAMS;
+
+/// Bounds on the KDF parameters a wallet may declare.
+const MIN_KDF_MEMORY_KIB: u32 = 8 * 1024;
+const MAX_KDF_MEMORY_KIB: u32 = 4 * 1024 * 1024;
+const MAX_KDF_ITERATIONS: u32 = 64;
+const MAX_KDF_PARALLELISM: u32 = 16;
 
 /// Generate a cryptographically random 16-byte salt.
 fn generate_random_salt() -> [u8; 16] {
@@ -425,14 +817,19 @@ fn generate_random_salt() -> [u8; 16] {
     salt
 }
 
-/// Derive a keypair from a password using Argon2id with a caller-provided salt (v3 wallets).
-fn password_to_keypair_argon2_with_salt(
+/// Derive a keypair from a password using Argon2id with a caller-provided
+/// salt and parameters (v3+ wallets).
+fn password_to_keypair_argon2(
     password: &str,
     salt: &[u8; 16],
+    kdf: &KdfParams,
 ) -> Result<Keypair, WalletError> {
-    use argon2::Argon2;
+    use argon2::{Algorithm, Argon2, Params, Version};
+    kdf.validate()?;
+    let params = Params::new(kdf.memory_kib, kdf.iterations, kdf.parallelism, None)
+        .map_err(|e| WalletError::Other(format!("invalid KDF parameters: {}", e)))?;
     let mut seed = [0u8; 32];
-    Argon2::default()
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
         .hash_password_into(password.as_bytes(), salt, &mut seed)
         .map_err(|e| WalletError::SerializationError(format!("argon2 hash failed: {}", e)))?;
     Ok(Keypair::from_seed(&seed))
@@ -441,7 +838,7 @@ fn password_to_keypair_argon2_with_salt(
 /// Derive a keypair from a password using Argon2id with fixed salt (v2 wallets, legacy).
 fn password_to_keypair_argon2_fixed(password: &str) -> Result<Keypair, WalletError> {
     let salt: [u8; 16] = *b"norn-keystore-v2";
-    password_to_keypair_argon2_with_salt(password, &salt)
+    password_to_keypair_argon2(password, &salt, &KdfParams::legacy())
 }
 
 /// Derive a keypair from a password using BLAKE3 KDF (v1 wallets, legacy).
@@ -455,6 +852,7 @@ fn password_to_keypair_for_version(
     password: &str,
     version: u32,
     salt_hex: Option<&str>,
+    kdf: &KdfParams,
 ) -> Result<Keypair, WalletError> {
     if version >= WALLET_VERSION_RANDOM_SALT {
         // v3+: per-wallet random salt (required)
@@ -470,7 +868,7 @@ fn password_to_keypair_for_version(
         }
         let mut salt = [0u8; 16];
         salt.copy_from_slice(&salt_bytes);
-        password_to_keypair_argon2_with_salt(password, &salt)
+        password_to_keypair_argon2(password, &salt, kdf)
     } else if version >= WALLET_VERSION_ARGON2 {
         // v2: Argon2id with fixed salt
         password_to_keypair_argon2_fixed(password)
@@ -485,10 +883,34 @@ mod tests {
     use super::*;
     use norn_crypto::seed::generate_mnemonic;
 
+    /// Cheap parameters for tests that don't exercise the KDF itself.
+    const TEST_KDF: KdfParams = KdfParams {
+        memory_kib: MIN_KDF_MEMORY_KIB,
+        iterations: 1,
+        parallelism: 1,
+    };
+
+    /// A mnemonic wallet at the default path, with cheap KDF parameters.
+    fn create(
+        name: &str,
+        mnemonic: &bip39::Mnemonic,
+        passphrase: &str,
+        password: &str,
+    ) -> Result<Keystore, WalletError> {
+        Keystore::create_at_path(
+            name,
+            mnemonic,
+            passphrase,
+            password,
+            &default_account_path(),
+            TEST_KDF,
+        )
+    }
+
     #[test]
     fn test_create_and_decrypt_roundtrip() {
         let mnemonic = generate_mnemonic();
-        let ks = Keystore::create("test", &mnemonic, "", "mypassword").unwrap();
+        let ks = create("test", &mnemonic, "", "mypassword").unwrap();
 
         let keypair = ks.decrypt_keypair("mypassword").unwrap();
         assert_eq!(keypair.public_key(), ks.public_key);
@@ -497,7 +919,7 @@ mod tests {
     #[test]
     fn test_wrong_password_fails() {
         let mnemonic = generate_mnemonic();
-        let ks = Keystore::create("test", &mnemonic, "", "correct").unwrap();
+        let ks = create("test", &mnemonic, "", "correct").unwrap();
 
         let result = ks.decrypt_keypair("wrong");
         assert!(result.is_err());
@@ -507,7 +929,7 @@ mod tests {
     fn test_mnemonic_decrypt_roundtrip() {
         let mnemonic = generate_mnemonic();
         let phrase = mnemonic.to_string();
-        let ks = Keystore::create("test", &mnemonic, "", "pass").unwrap();
+        let ks = create("test", &mnemonic, "", "pass").unwrap();
 
         let recovered = ks.decrypt_mnemonic("pass").unwrap().unwrap();
         assert_eq!(recovered, phrase);
@@ -516,7 +938,7 @@ mod tests {
     #[test]
     fn test_from_private_key_roundtrip() {
         let seed = [42u8; 32];
-        let ks = Keystore::from_private_key("pk-test", &seed, "pass").unwrap();
+        let ks = Keystore::from_private_key("pk-test", &seed, "pass", TEST_KDF).unwrap();
 
         let keypair = ks.decrypt_keypair("pass").unwrap();
         let expected = Keypair::from_seed(&seed);
@@ -526,7 +948,7 @@ mod tests {
     #[test]
     fn test_private_key_no_mnemonic() {
         let seed = [42u8; 32];
-        let ks = Keystore::from_private_key("pk-test", &seed, "pass").unwrap();
+        let ks = Keystore::from_private_key("pk-test", &seed, "pass", TEST_KDF).unwrap();
 
         let mnemonic = ks.decrypt_mnemonic("pass").unwrap();
         assert!(mnemonic.is_none());
@@ -535,7 +957,7 @@ mod tests {
     #[test]
     fn test_encrypted_blob_roundtrip() {
         let salt = generate_random_salt();
-        let password_kp = password_to_keypair_argon2_with_salt("test", &salt).unwrap();
+        let password_kp = password_to_keypair_argon2("test", &salt, &TEST_KDF).unwrap();
         let plaintext = b"hello world";
         let encrypted = encrypt_for_keypair(&password_kp, plaintext).unwrap();
         let blob = EncryptedBlob::from_encrypted(&encrypted);
@@ -552,19 +974,20 @@ mod tests {
     #[test]
     fn test_v3_random_salt_unique_per_wallet() {
         let mnemonic = generate_mnemonic();
-        let ks1 = Keystore::create("test1", &mnemonic, "", "same-password").unwrap();
-        let ks2 = Keystore::create("test2", &mnemonic, "", "same-password").unwrap();
+        let ks1 = create("test1", &mnemonic, "", "same-password").unwrap();
+        let ks2 = create("test2", &mnemonic, "", "same-password").unwrap();
 
         // Different wallets with the same password should have different salts.
         assert_ne!(ks1.file.salt, ks2.file.salt);
-        assert_eq!(ks1.file.version, WALLET_VERSION_RANDOM_SALT);
+        assert_eq!(ks1.file.version, WALLET_VERSION);
+        assert_eq!(ks1.file.kdf, Some(TEST_KDF));
     }
 
     #[test]
     fn test_change_password() {
         let mnemonic = generate_mnemonic();
         let phrase = mnemonic.to_string();
-        let mut ks = Keystore::create("test", &mnemonic, "", "old-pass").unwrap();
+        let mut ks = create("test", &mnemonic, "", "old-pass").unwrap();
 
         let kp_before = ks.decrypt_keypair("old-pass").unwrap();
 
@@ -582,14 +1005,14 @@ mod tests {
         let recovered = ks.decrypt_mnemonic("new-pass").unwrap().unwrap();
         assert_eq!(recovered, phrase);
 
-        // Should be v3 format
-        assert_eq!(ks.file.version, WALLET_VERSION_RANDOM_SALT);
+        // Should be in the current format
+        assert_eq!(ks.file.version, WALLET_VERSION);
     }
 
     #[test]
     fn test_change_password_wrong_old_password() {
         let mnemonic = generate_mnemonic();
-        let mut ks = Keystore::create("test", &mnemonic, "", "correct").unwrap();
+        let mut ks = create("test", &mnemonic, "", "correct").unwrap();
 
         let result = ks.change_password("wrong", "new-pass");
         assert!(result.is_err());
@@ -600,24 +1023,24 @@ mod tests {
         let mnemonic = generate_mnemonic();
 
         // Path separators
-        assert!(Keystore::create("../evil", &mnemonic, "", "pass").is_err());
-        assert!(Keystore::create("foo/bar", &mnemonic, "", "pass").is_err());
-        assert!(Keystore::create("foo\\bar", &mnemonic, "", "pass").is_err());
+        assert!(create("../evil", &mnemonic, "", "pass").is_err());
+        assert!(create("foo/bar", &mnemonic, "", "pass").is_err());
+        assert!(create("foo\\bar", &mnemonic, "", "pass").is_err());
 
         // Dotdot sequences
-        assert!(Keystore::create("..", &mnemonic, "", "pass").is_err());
-        assert!(Keystore::create("a..b", &mnemonic, "", "pass").is_err());
+        assert!(create("..", &mnemonic, "", "pass").is_err());
+        assert!(create("a..b", &mnemonic, "", "pass").is_err());
 
         // Null byte
-        assert!(Keystore::create("evil\0name", &mnemonic, "", "pass").is_err());
+        assert!(create("evil\0name", &mnemonic, "", "pass").is_err());
 
         // Empty and reserved names
-        assert!(Keystore::create("", &mnemonic, "", "pass").is_err());
-        assert!(Keystore::create("config", &mnemonic, "", "pass").is_err());
+        assert!(create("", &mnemonic, "", "pass").is_err());
+        assert!(create("config", &mnemonic, "", "pass").is_err());
 
         // Valid names should succeed
-        assert!(Keystore::create("my-wallet", &mnemonic, "", "pass").is_ok());
-        assert!(Keystore::create("wallet_v2", &mnemonic, "", "pass").is_ok());
+        assert!(create("my-wallet", &mnemonic, "", "pass").is_ok());
+        assert!(create("wallet_v2", &mnemonic, "", "pass").is_ok());
     }
 
     #[test]
@@ -632,6 +1055,45 @@ mod tests {
         assert!(Keystore::delete("foo/bar").is_err());
     }
 
+    #[test]
+    fn test_ledger_wallet_has_no_seed() {
+        let keypair = Keypair::from_seed(&[8u8; 32]);
+        let ks = Keystore::ledger("nano", keypair.public_key(), 3).unwrap();
+        assert!(ks.is_ledger());
+        assert!(!ks.is_watch_only());
+        assert_eq!(ks.address, pubkey_to_address(&keypair.public_key()));
+        assert!(matches!(
+            ks.decrypt_keypair("any"),
+            Err(WalletError::HardwareWallet(_))
+        ));
+
+        let json = serde_json::to_string(&ks.file).unwrap();
+        let file: WalletFile = serde_json::from_str(&json).unwrap();
+        assert_eq!(file.ledger.unwrap().account, 3);
+        assert!(file.encrypted_seed.is_none());
+    }
+
+    #[test]
+    fn test_watch_only_cannot_sign() {
+        let keypair = Keypair::from_seed(&[7u8; 32]);
+        let address = pubkey_to_address(&keypair.public_key());
+        let ks = Keystore::watch_only("cold", address, Some(keypair.public_key())).unwrap();
+        assert!(ks.is_watch_only());
+        assert!(matches!(
+            ks.decrypt_keypair("any"),
+            Err(WalletError::WatchOnly(_))
+        ));
+
+        // The file round-trips without an encrypted seed.
+        let json = serde_json::to_string(&ks.file).unwrap();
+        let file: WalletFile = serde_json::from_str(&json).unwrap();
+        assert!(file.encrypted_seed.is_none());
+
+        // Thread ID only, or a key that does not match it.
+        assert!(Keystore::watch_only("cold", address, None).is_ok());
+        assert!(Keystore::watch_only("cold", [1u8; 20], Some(keypair.public_key())).is_err());
+    }
+
     #[test]
     fn test_v2_backward_compat() {
         // Simulate a v2 wallet file (fixed salt, no salt field).
@@ -647,9 +1109,14 @@ mod tests {
             public_key: hex::encode([0u8; 32]),
             derivation_index: 0,
             has_mnemonic: false,
-            encrypted_seed: EncryptedBlob::from_encrypted(&encrypted_seed),
+            has_passphrase: false,
+            encrypted_seed: Some(EncryptedBlob::from_encrypted(&encrypted_seed)),
             encrypted_mnemonic: None,
             salt: None,
+            kdf: None,
+            ledger: None,
+            derivation_path: None,
+            accounts: Vec::new(),
         };
 
         let ks = Keystore {
@@ -657,6 +1124,7 @@ mod tests {
             address: [0u8; 20],
             public_key: [0u8; 32],
             file,
+            on_disk: false,
         };
 
         // v2 wallet should still decrypt with the fixed salt path.
@@ -665,5 +1133,203 @@ mod tests {
             kp.public_key(),
             Keypair::from_seed(&[42u8; 32]).public_key()
         );
+
+        // Migration re-encrypts it in the current format.
+        let mut ks = ks;
+        assert!(ks.needs_upgrade());
+        ks.reencrypt("testpass", "testpass", KdfParams::default())
+            .unwrap();
+        assert!(!ks.needs_upgrade());
+        assert_eq!(ks.file.version, WALLET_VERSION);
+        assert_eq!(ks.kdf_params(), KdfParams::default());
+        assert!(ks.file.salt.is_some());
+        let kp = ks.decrypt_keypair("testpass").unwrap();
+        assert_eq!(
+            kp.public_key(),
+            Keypair::from_seed(&[42u8; 32]).public_key()
+        );
+    }
+
+    #[test]
+    fn test_legacy_params_match_v3_derivation() {
+        // v3 wallets were derived with `Argon2::default()`.
+        let salt = [9u8; 16];
+        let mut seed = [0u8; 32];
+        argon2::Argon2::default()
+            .hash_password_into(b"pass", &salt, &mut seed)
+            .unwrap();
+        let kp = password_to_keypair_argon2("pass", &salt, &KdfParams::legacy()).unwrap();
+        assert_eq!(kp.public_key(), Keypair::from_seed(&seed).public_key());
+    }
+
+    #[test]
+    fn test_new_wallets_use_default_kdf_params() {
+        assert_eq!(
+            KdfParams::default(),
+            KdfParams {
+                memory_kib: 64 * 1024,
+                iterations: 3,
+                parallelism: 4,
+            }
+        );
+
+        let ks = Keystore::from_private_key("defaults", &[8u8; 32], "pass", KdfParams::default())
+            .unwrap();
+        assert_eq!(ks.file.kdf, Some(KdfParams::default()));
+        assert!(!ks.needs_upgrade());
+        assert_eq!(
+            ks.decrypt_keypair("pass").unwrap().public_key(),
+            Keypair::from_seed(&[8u8; 32]).public_key()
+        );
+    }
+
+    #[test]
+    fn test_weaker_kdf_params_migrate_to_defaults() {
+        let mnemonic = generate_mnemonic();
+        let path = default_account_path();
+        let mut ks =
+            Keystore::create_at_path("weak", &mnemonic, "", "pass", &path, KdfParams::legacy())
+                .unwrap();
+        let address = ks.address;
+        assert!(ks.needs_upgrade());
+
+        ks.reencrypt("pass", "pass", KdfParams::default()).unwrap();
+        assert_eq!(ks.kdf_params(), KdfParams::default());
+        assert!(!ks.needs_upgrade());
+        assert_eq!(
+            ks.decrypt_keypair("pass").unwrap().public_key(),
+            ks.public_key
+        );
+        assert_eq!(ks.address, address);
+        assert_eq!(
+            ks.decrypt_mnemonic("pass").unwrap(),
+            Some(mnemonic.to_string())
+        );
+    }
+
+    #[test]
+    fn test_migrate_reports_outcome() {
+        let mut ks = Keystore::from_private_key("migrate", &[6u8; 32], "pass", TEST_KDF).unwrap();
+        assert!(ks.needs_upgrade());
+
+        // Wallets that were never saved are not written out.
+        assert!(!ks.migrate("pass").unwrap());
+
+        // A failed upgrade is returned rather than swallowed.
+        ks.on_disk = true;
+        assert!(matches!(
+            ks.migrate("wrong"),
+            Err(WalletError::InvalidPassword)
+        ));
+    }
+
+    #[test]
+    fn test_declared_kdf_params() {
+        let mut ks = Keystore::from_private_key("kdf", &[5u8; 32], "pass", TEST_KDF).unwrap();
+        let declared = KdfParams {
+            memory_kib: 32 * 1024,
+            iterations: 3,
+            parallelism: 2,
+        };
+        ks.reencrypt("pass", "pass", declared).unwrap();
+
+        // The parameters round-trip through the file and are used to unlock.
+        let json = serde_json::to_string(&ks.file).unwrap();
+        ks.file = serde_json::from_str(&json).unwrap();
+        assert_eq!(ks.kdf_params(), declared);
+        assert!(ks.needs_upgrade());
+        assert!(ks.decrypt_keypair("pass").is_ok());
+
+        // A tampered file cannot declare absurd parameters.
+        ks.file.kdf = Some(KdfParams {
+            memory_kib: u32::MAX,
+            ..declared
+        });
+        assert!(ks.decrypt_keypair("pass").is_err());
+        assert!(ks
+            .reencrypt(
+                "pass",
+                "pass",
+                KdfParams {
+                    iterations: 0,
+                    ..declared
+                }
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_accounts_add_and_use() {
+        let mnemonic = generate_mnemonic();
+        let mut ks = create("multi", &mnemonic, "", "pass").unwrap();
+        let first = ks.address;
+        assert_eq!(ks.accounts().len(), 1);
+
+        let seed = ks.decrypt_hd_seed("pass").unwrap();
+        let mut account = ks.derive_account(&seed, 1).unwrap();
+        account.label = Some("savings".to_string());
+        ks.add_account(account.clone()).unwrap();
+        assert!(ks.add_account(account.clone()).is_err());
+        assert_eq!(ks.accounts().len(), 2);
+        assert_eq!(ks.find_account("savings").unwrap().index, 1);
+
+        ks.use_account(1).unwrap();
+        assert_ne!(ks.address, first);
+        let keypair = ks.decrypt_keypair("pass").unwrap();
+        assert_eq!(keypair.public_key(), ks.public_key);
+        assert_eq!(format!("0x{}", hex::encode(ks.address)), account.address);
+
+        // The selection survives a round trip through the file.
+        let json = serde_json::to_string(&ks.file).unwrap();
+        let file: WalletFile = serde_json::from_str(&json).unwrap();
+        assert_eq!(file.derivation_index, 1);
+        assert_eq!(file.accounts.len(), 2);
+
+        ks.use_account(0).unwrap();
+        assert_eq!(ks.address, first);
+        assert!(ks.use_account(5).is_err());
+    }
+
+    #[test]
+    fn test_custom_account_path() {
+        let mnemonic = generate_mnemonic();
+        let default = create("a", &mnemonic, "", "pass").unwrap();
+        let path = parse_derivation_path("m/44'/1337'/1'/0'").unwrap();
+        let custom = Keystore::create_at_path("b", &mnemonic, "", "pass", &path, TEST_KDF).unwrap();
+        assert_ne!(default.address, custom.address);
+        assert_eq!(custom.account_path().unwrap(), path);
+        assert_eq!(
+            custom.decrypt_keypair("pass").unwrap().public_key(),
+            custom.public_key
+        );
+
+        // Private-key wallets have a single account.
+        let imported = Keystore::from_private_key("c", &[3u8; 32], "pass", TEST_KDF).unwrap();
+        assert!(imported.decrypt_hd_seed("pass").is_err());
+    }
+
+    #[test]
+    fn test_passphrase_derives_a_different_wallet() {
+        let mnemonic = generate_mnemonic();
+        let plain = create("plain", &mnemonic, "", "pass").unwrap();
+        let hidden = create("hidden", &mnemonic, "25th word", "pass").unwrap();
+        assert!(!plain.file.has_passphrase);
+        assert!(hidden.file.has_passphrase);
+        assert_ne!(plain.address, hidden.address);
+
+        let seed = norn_crypto::seed::mnemonic_to_seed(&mnemonic, "25th word");
+        assert_eq!(hidden.decrypt_hd_seed("pass").unwrap(), seed);
+        assert_eq!(
+            hidden.decrypt_keypair("pass").unwrap().public_key(),
+            norn_crypto::hd::derive_default_keypair(&seed)
+                .unwrap()
+                .public_key()
+        );
+
+        // Wallets written before the flag existed load as passphrase-less.
+        let mut json = serde_json::to_value(&hidden.file).unwrap();
+        json.as_object_mut().unwrap().remove("has_passphrase");
+        let file: WalletFile = serde_json::from_value(json).unwrap();
+        assert!(!file.has_passphrase);
     }
 }
@@ -0,0 +1,417 @@
+//! Ledger hardware wallet support.
+//!
+//! The Norn Ledger app keeps the Ed25519 key on the device and signs there,
+//! after the user confirms on its screen. The wallet talks to it with APDUs
+//! over USB HID, using Ledger's HID framing. Keys are derived on the device
+//! along the same SLIP-0010 path as mnemonic wallets, with the account as
+//! the last component, so a Ledger holding a wallet's mnemonic shows the same
+//! address for account 0.
+
+use norn_types::constants::NORN_COIN_TYPE;
+use norn_types::primitives::{PublicKey, Signature};
+
+use super::error::WalletError;
+
+/// USB vendor ID of Ledger devices.
+pub const LEDGER_VENDOR_ID: u16 = 0x2c97;
+
+/// APDU class of the Norn app.
+const CLA: u8 = 0xe0;
+/// Return the public key for a derivation path.
+const INS_GET_PUBLIC_KEY: u8 = 0x02;
+/// Sign a message for a derivation path.
+const INS_SIGN: u8 = 0x03;
+
+/// `INS_GET_PUBLIC_KEY`: return the key silently.
+const P1_SILENT: u8 = 0x00;
+/// `INS_GET_PUBLIC_KEY`: show the address and wait for the user to confirm.
+const P1_CONFIRM: u8 = 0x01;
+/// `INS_SIGN`: first chunk, starting with the derivation path.
+const P1_FIRST: u8 = 0x00;
+/// `INS_SIGN`: continuation chunk.
+const P1_MORE: u8 = 0x80;
+/// `INS_SIGN`: this is the last chunk.
+const P2_LAST: u8 = 0x00;
+/// `INS_SIGN`: more chunks follow.
+const P2_MORE: u8 = 0x80;
+
+/// Largest APDU data field.
+const MAX_APDU_DATA: usize = 255;
+/// Largest message the app signs.
+const MAX_SIGN_MESSAGE: usize = 4096;
+
+const SW_OK: u16 = 0x9000;
+const SW_DENIED: u16 = 0x6985;
+const SW_WRONG_LENGTH: u16 = 0x6700;
+const SW_INS_NOT_SUPPORTED: u16 = 0x6d00;
+const SW_CLA_NOT_SUPPORTED: u16 = 0x6e00;
+const SW_LOCKED: u16 = 0x5515;
+
+/// Size of a HID report.
+const HID_PACKET_SIZE: usize = 64;
+/// Channel ID used for every HID packet.
+const HID_CHANNEL: u16 = 0x0101;
+/// HID packet tag for APDU frames.
+const HID_TAG_APDU: u8 = 0x05;
+
+/// Hardened derivation path of an account: m/44'/NORN'/0'/0'/account'.
+pub fn derivation_path(account: u32) -> [u32; 5] {
+    [44, NORN_COIN_TYPE, 0, 0, account].map(|c| c | 0x8000_0000)
+}
+
+/// Encode a derivation path as the app expects: a length byte, then each
+/// component as big-endian u32.
+fn encode_path(account: u32) -> Vec<u8> {
+    let path = derivation_path(account);
+    let mut out = Vec::with_capacity(1 + path.len() * 4);
+    out.push(path.len() as u8);
+    for component in path {
+        out.extend_from_slice(&component.to_be_bytes());
+    }
+    out
+}
+
+/// Encode a command APDU.
+fn encode_apdu(ins: u8, p1: u8, p2: u8, data: &[u8]) -> Vec<u8> {
+    debug_assert!(data.len() <= MAX_APDU_DATA);
+    let mut apdu = Vec::with_capacity(5 + data.len());
+    apdu.extend_from_slice(&[CLA, ins, p1, p2, data.len() as u8]);
+    apdu.extend_from_slice(data);
+    apdu
+}
+
+/// Split a response APDU into its data, checking the status word.
+fn check_status(response: &[u8]) -> Result<&[u8], WalletError> {
+    if response.len() < 2 {
+        return Err(WalletError::Ledger("truncated response".to_string()));
+    }
+    let (data, sw) = response.split_at(response.len() - 2);
+    match u16::from_be_bytes([sw[0], sw[1]]) {
+        SW_OK => Ok(data),
+        SW_DENIED => Err(WalletError::Ledger("rejected on the device".to_string())),
+        SW_LOCKED => Err(WalletError::Ledger(
+            "device is locked; unlock it with your PIN".to_string(),
+        )),
+        SW_CLA_NOT_SUPPORTED | SW_INS_NOT_SUPPORTED => Err(WalletError::Ledger(
+            "open the Norn app on the device".to_string(),
+        )),
+        SW_WRONG_LENGTH => Err(WalletError::Ledger(
+            "message too large for the device".to_string(),
+        )),
+        sw => Err(WalletError::Ledger(format!("device error 0x{:04x}", sw))),
+    }
+}
+
+/// Frame an APDU into HID packets: channel, tag, sequence number, and on the
+/// first packet the APDU length, padded to the report size.
+fn hid_frames(apdu: &[u8]) -> Vec<[u8; HID_PACKET_SIZE]> {
+    let mut payload = Vec::with_capacity(2 + apdu.len());
+    payload.extend_from_slice(&(apdu.len() as u16).to_be_bytes());
+    payload.extend_from_slice(apdu);
+
+    payload
+        .chunks(HID_PACKET_SIZE - 5)
+        .enumerate()
+        .map(|(seq, chunk)| {
+            let mut packet = [0u8; HID_PACKET_SIZE];
+            packet[..2].copy_from_slice(&HID_CHANNEL.to_be_bytes());
+            packet[2] = HID_TAG_APDU;
+            packet[3..5].copy_from_slice(&(seq as u16).to_be_bytes());
+            packet[5..5 + chunk.len()].copy_from_slice(chunk);
+            packet
+        })
+        .collect()
+}
+
+/// Reassembles a response APDU from HID packets.
+#[derive(Default)]
+struct HidReader {
+    expected: Option<usize>,
+    data: Vec<u8>,
+    seq: u16,
+}
+
+impl HidReader {
+    /// Add a packet. Returns the response once it is complete.
+    fn push(&mut self, packet: &[u8]) -> Result<Option<Vec<u8>>, WalletError> {
+        if packet.len() < 5
+            || packet[..2] != HID_CHANNEL.to_be_bytes()
+            || packet[2] != HID_TAG_APDU
+            || packet[3..5] != self.seq.to_be_bytes()
+        {
+            return Err(WalletError::Ledger("unexpected HID packet".to_string()));
+        }
+        let mut body = &packet[5..];
+        if self.expected.is_none() {
+            if body.len() < 2 {
+                return Err(WalletError::Ledger("unexpected HID packet".to_string()));
+            }
+            self.expected = Some(u16::from_be_bytes([body[0], body[1]]) as usize);
+            body = &body[2..];
+        }
+        let expected = self.expected.unwrap_or_default();
+        let take = body.len().min(expected - self.data.len());
+        self.data.extend_from_slice(&body[..take]);
+        self.seq = self.seq.wrapping_add(1);
+        if self.data.len() == expected {
+            Ok(Some(std::mem::take(&mut self.data)))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// Sends an APDU to a device and returns its response, status word included.
+pub trait Transport {
+    fn exchange(&mut self, apdu: &[u8]) -> Result<Vec<u8>, WalletError>;
+}
+
+/// USB HID transport to the first connected Ledger, through Linux hidraw.
+pub struct HidTransport {
+    #[cfg(target_os = "linux")]
+    device: std::fs::File,
+}
+
+impl HidTransport {
+    /// Open the APDU interface of the first connected Ledger.
+    #[cfg(target_os = "linux")]
+    pub fn open() -> Result<Self, WalletError> {
+        let not_found = || {
+            WalletError::Ledger(
+                "no Ledger found; connect it, unlock it and open the Norn app".to_string(),
+            )
+        };
+        let entries = std::fs::read_dir("/sys/class/hidraw").map_err(|_| not_found())?;
+        for entry in entries.flatten() {
+            let sys = entry.path().join("device");
+            let uevent = std::fs::read_to_string(sys.join("uevent")).unwrap_or_default();
+            let vendor = format!(":{:08X}:", LEDGER_VENDOR_ID);
+            if !uevent
+                .lines()
+                .any(|l| l.starts_with("HID_ID=") && l.contains(&vendor))
+            {
+                continue;
+            }
+            // Only the APDU interface uses the vendor usage page 0xFFA0.
+            let descriptor = std::fs::read(sys.join("report_descriptor")).unwrap_or_default();
+            if !descriptor.starts_with(&[0x06, 0xa0, 0xff]) {
+                continue;
+            }
+            let node = std::path::Path::new("/dev").join(entry.file_name());
+            let device = std::fs::OpenOptions::new()
+                .read(true)
+                .write(true)
+                .open(&node)
+                .map_err(|e| {
+                    WalletError::Ledger(format!(
+                        "cannot open {}: {} (check the udev rules for Ledger devices)",
+                        node.display(),
+                        e
+                    ))
+                })?;
+            return Ok(Self { device });
+        }
+        Err(not_found())
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn open() -> Result<Self, WalletError> {
+        Err(WalletError::Ledger(
+            "USB transport is only supported on Linux".to_string(),
+        ))
+    }
+}
+
+impl Transport for HidTransport {
+    #[cfg(target_os = "linux")]
+    fn exchange(&mut self, apdu: &[u8]) -> Result<Vec<u8>, WalletError> {
+        use std::io::{Read, Write};
+
+        for frame in hid_frames(apdu) {
+            // hidraw expects the report ID (0) before the report.
+            let mut report = [0u8; HID_PACKET_SIZE + 1];
+            report[1..].copy_from_slice(&frame);
+            self.device.write_all(&report)?;
+        }
+        let mut reader = HidReader::default();
+        loop {
+            let mut packet = [0u8; HID_PACKET_SIZE];
+            let n = self.device.read(&mut packet)?;
+            if let Some(response) = reader.push(&packet[..n])? {
+                return Ok(response);
+            }
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn exchange(&mut self, _apdu: &[u8]) -> Result<Vec<u8>, WalletError> {
+        Err(WalletError::Ledger(
+            "USB transport is only supported on Linux".to_string(),
+        ))
+    }
+}
+
+/// A Ledger running the Norn app.
+pub struct Ledger<T: Transport = HidTransport> {
+    transport: T,
+}
+
+impl Ledger {
+    /// Connect to the first Ledger over USB.
+    pub fn connect() -> Result<Self, WalletError> {
+        Ok(Self::new(HidTransport::open()?))
+    }
+}
+
+impl<T: Transport> Ledger<T> {
+    pub fn new(transport: T) -> Self {
+        Self { transport }
+    }
+
+    fn exchange(&mut self, apdu: &[u8]) -> Result<Vec<u8>, WalletError> {
+        let response = self.transport.exchange(apdu)?;
+        Ok(check_status(&response)?.to_vec())
+    }
+
+    /// Public key of an account. With `confirm`, the device shows the
+    /// address and returns only once the user approves it.
+    pub fn public_key(&mut self, account: u32, confirm: bool) -> Result<PublicKey, WalletError> {
+        let p1 = if confirm { P1_CONFIRM } else { P1_SILENT };
+        let data = self.exchange(&encode_apdu(
+            INS_GET_PUBLIC_KEY,
+            p1,
+            0,
+            &encode_path(account),
+        ))?;
+        data.get(..32)
+            .and_then(|pk| pk.try_into().ok())
+            .ok_or_else(|| WalletError::Ledger("malformed public key response".to_string()))
+    }
+
+    /// Sign a message with an account key, once the user approves it on
+    /// the device. The path and message are sent in chunks; the signature
+    /// comes back with the last one.
+    pub fn sign(&mut self, account: u32, message: &[u8]) -> Result<Signature, WalletError> {
+        if message.len() > MAX_SIGN_MESSAGE {
+            return Err(WalletError::Ledger(format!(
+                "message of {} bytes exceeds the device limit of {}",
+                message.len(),
+                MAX_SIGN_MESSAGE
+            )));
+        }
+        let mut payload = encode_path(account);
+        payload.extend_from_slice(message);
+
+        let chunks: Vec<&[u8]> = payload.chunks(MAX_APDU_DATA).collect();
+        let mut response = Vec::new();
+        for (i, chunk) in chunks.iter().enumerate() {
+            let p1 = if i == 0 { P1_FIRST } else { P1_MORE };
+            let p2 = if i + 1 == chunks.len() {
+                P2_LAST
+            } else {
+                P2_MORE
+            };
+            response = self.exchange(&encode_apdu(INS_SIGN, p1, p2, chunk))?;
+        }
+        response
+            .get(..64)
+            .and_then(|sig| sig.try_into().ok())
+            .ok_or_else(|| WalletError::Ledger("malformed signature response".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use norn_crypto::keys::Keypair;
+
+    /// Device stand-in: answers APDUs with a software key after passing
+    /// them through the HID framing.
+    struct MockDevice {
+        keypair: Keypair,
+        message: Vec<u8>,
+        apdus: usize,
+    }
+
+    impl Transport for MockDevice {
+        fn exchange(&mut self, apdu: &[u8]) -> Result<Vec<u8>, WalletError> {
+            let mut reader = HidReader::default();
+            let mut framed = None;
+            for frame in hid_frames(apdu) {
+                framed = reader.push(&frame)?;
+            }
+            let apdu = framed.unwrap();
+            assert_eq!(apdu[0], CLA);
+            assert_eq!(apdu[4] as usize, apdu.len() - 5);
+            self.apdus += 1;
+
+            let (ins, p1, p2, data) = (apdu[1], apdu[2], apdu[3], &apdu[5..]);
+            let mut response = match ins {
+                INS_GET_PUBLIC_KEY => {
+                    assert_eq!(data, encode_path(0).as_slice());
+                    self.keypair.public_key().to_vec()
+                }
+                INS_SIGN => {
+                    if p1 == P1_FIRST {
+                        self.message = data[1 + 5 * 4..].to_vec();
+                    } else {
+                        self.message.extend_from_slice(data);
+                    }
+                    if p2 == P2_MORE {
+                        Vec::new()
+                    } else {
+                        self.keypair.sign(&self.message).to_vec()
+                    }
+                }
+                _ => return Ok(SW_INS_NOT_SUPPORTED.to_be_bytes().to_vec()),
+            };
+            response.extend_from_slice(&SW_OK.to_be_bytes());
+            Ok(response)
+        }
+    }
+
+    #[test]
+    fn test_hid_framing_roundtrip() {
+        let apdu: Vec<u8> = (0..300u32).map(|i| i as u8).collect();
+        let frames = hid_frames(&apdu);
+        assert_eq!(frames.len(), 6);
+        let mut reader = HidReader::default();
+        let mut out = None;
+        for frame in &frames {
+            assert!(out.is_none());
+            out = reader.push(frame).unwrap();
+        }
+        assert_eq!(out.unwrap(), apdu);
+    }
+
+    #[test]
+    fn test_sign_with_device() {
+        let keypair = Keypair::generate();
+        let mut ledger = Ledger::new(MockDevice {
+            keypair: Keypair::from_seed(&keypair.seed()),
+            message: Vec::new(),
+            apdus: 0,
+        });
+        assert_eq!(ledger.public_key(0, true).unwrap(), keypair.public_key());
+
+        // Long messages are split across APDUs.
+        let message = vec![7u8; 600];
+        let sig = ledger.sign(0, &message).unwrap();
+        assert!(norn_crypto::keys::verify(&message, &sig, &keypair.public_key()).is_ok());
+        assert_eq!(ledger.transport.apdus, 1 + 3);
+    }
+
+    #[test]
+    fn test_status_words() {
+        assert_eq!(check_status(&[1, 2, 0x90, 0x00]).unwrap(), &[1, 2]);
+        assert!(matches!(
+            check_status(&[0x69, 0x85]),
+            Err(WalletError::Ledger(msg)) if msg.contains("rejected")
+        ));
+        assert!(matches!(
+            check_status(&[0x6e, 0x00]),
+            Err(WalletError::Ledger(msg)) if msg.contains("Norn app")
+        ));
+        assert!(check_status(&[0x90]).is_err());
+    }
+}
@@ -3,30 +3,65 @@ pub mod commands;
 pub mod config;
 pub mod error;
 pub mod format;
+pub mod journal;
 pub mod keystore;
+pub mod ledger;
+pub mod output;
+pub mod price;
 pub mod prompt;
+pub mod qr;
 pub mod rpc_client;
+pub mod signer;
+pub mod tx_file;
+pub mod typed_data;
 pub mod ui;
 
-use cli::WalletCommand;
+use std::path::Path;
+
+use cli::{AccountsCommand, ConfigAction, ContactsCommand, WalletCommand};
 use error::WalletError;
 
-/// Run a wallet subcommand.
-pub async fn run(command: WalletCommand) -> Result<(), WalletError> {
+/// Run a wallet subcommand, optionally under a network profile other than
+/// the configured one.
+pub async fn run(command: WalletCommand, profile: Option<&str>) -> Result<(), WalletError> {
+    if let Some(name) = profile {
+        config::set_profile_override(name);
+    }
+    // Loading applies the profile in effect for the rest of the process.
+    if let Ok(config) = config::WalletConfig::load() {
+        format::set_denomination(config.denomination);
+    }
     match command {
-        WalletCommand::Create { name, passphrase } => {
-            commands::create::run(&name, passphrase.as_deref())
-        }
+        WalletCommand::Create {
+            name,
+            passphrase,
+            ask_passphrase,
+            derivation_path,
+        } => commands::create::run(
+            &name,
+            passphrase.as_deref(),
+            ask_passphrase,
+            derivation_path.as_deref(),
+        ),
         WalletCommand::Import {
             mnemonic,
             private_key,
+            watch_only,
+            ledger,
+            account,
             name,
             passphrase,
+            ask_passphrase,
+            derivation_path,
         } => commands::import::run(
             mnemonic,
             private_key.as_deref(),
+            watch_only.as_deref(),
+            ledger.then_some(account),
             &name,
             passphrase.as_deref(),
+            ask_passphrase,
+            derivation_path.as_deref(),
         ),
         WalletCommand::Export {
             name,
@@ -36,7 +71,9 @@ pub async fn run(command: WalletCommand) -> Result<(), WalletError> {
         WalletCommand::List { json } => commands::list::run(json),
         WalletCommand::Use { name } => commands::use_wallet::run(&name),
         WalletCommand::Delete { name, force } => commands::delete::run(&name, force),
-        WalletCommand::Address { name, json } => commands::address::run(name.as_deref(), json),
+        WalletCommand::Address { name, json, verify } => {
+            commands::address::run(name.as_deref(), json, verify)
+        }
         WalletCommand::Balance {
             address,
             token,
@@ -56,6 +93,8 @@ pub async fn run(command: WalletCommand) -> Result<(), WalletError> {
             amount,
             token,
             memo,
+            label,
+            out,
             yes,
             rpc_url,
         } => {
@@ -64,6 +103,124 @@ pub async fn run(command: WalletCommand) -> Result<(), WalletError> {
                 &amount,
                 token.as_deref(),
                 memo.as_deref(),
+                label.as_deref(),
+                out.as_deref().map(Path::new),
+                yes,
+                rpc_url.as_deref(),
+            )
+            .await
+        }
+        WalletCommand::MultiTransfer {
+            recipients,
+            token,
+            memo,
+            label,
+            yes,
+            rpc_url,
+        } => {
+            commands::multi_transfer::run(
+                &recipients,
+                token.as_deref(),
+                memo.as_deref(),
+                label.as_deref(),
+                yes,
+                rpc_url.as_deref(),
+            )
+            .await
+        }
+        WalletCommand::BatchTransfer {
+            csv,
+            token,
+            memo,
+            label,
+            report,
+            yes,
+            rpc_url,
+        } => {
+            commands::batch_transfer::run(
+                Path::new(&csv),
+                token.as_deref(),
+                memo.as_deref(),
+                label.as_deref(),
+                report.as_deref().map(Path::new),
+                yes,
+                rpc_url.as_deref(),
+            )
+            .await
+        }
+        WalletCommand::ProposeSwap {
+            with,
+            give,
+            give_token,
+            receive,
+            receive_token,
+            expiry,
+            yes,
+            rpc_url,
+        } => {
+            commands::propose_swap::run(
+                &with,
+                &give,
+                give_token.as_deref(),
+                &receive,
+                receive_token.as_deref(),
+                expiry,
+                yes,
+                rpc_url.as_deref(),
+            )
+            .await
+        }
+        WalletCommand::SignKnot {
+            knot_id,
+            file,
+            out,
+            yes,
+            rpc_url,
+        } => {
+            commands::sign_knot::run(
+                knot_id.as_deref(),
+                file.as_deref().map(Path::new),
+                out.as_deref().map(Path::new),
+                yes,
+                rpc_url.as_deref(),
+            )
+            .await
+        }
+        WalletCommand::SubmitKnot { file, yes, rpc_url } => {
+            commands::submit_knot::run(Path::new(&file), yes, rpc_url.as_deref()).await
+        }
+        WalletCommand::SignTx {
+            file,
+            offline,
+            out,
+            qr,
+            yes,
+            rpc_url,
+        } => {
+            commands::sign_tx::run(
+                Path::new(&file),
+                offline,
+                out.as_deref().map(Path::new),
+                qr,
+                yes,
+                rpc_url.as_deref(),
+            )
+            .await
+        }
+        WalletCommand::Broadcast { file, yes, rpc_url } => {
+            commands::broadcast::run(Path::new(&file), yes, rpc_url.as_deref()).await
+        }
+        WalletCommand::SetCosigners {
+            cosigners,
+            threshold,
+            out,
+            yes,
+            rpc_url,
+        } => {
+            commands::set_cosigners::run(
+                &cosigners,
+                threshold,
+                out.as_deref().map(Path::new),
                 yes,
                 rpc_url.as_deref(),
             )
@@ -72,8 +229,19 @@ pub async fn run(command: WalletCommand) -> Result<(), WalletError> {
         WalletCommand::Register { name, rpc_url } => {
             commands::register::run(name.as_deref(), rpc_url.as_deref()).await
         }
-        WalletCommand::Commit { name, rpc_url } => {
-            commands::commit::run(name.as_deref(), rpc_url.as_deref()).await
+        WalletCommand::Commit {
+            name,
+            tip,
+            out,
+            rpc_url,
+        } => {
+            commands::commit::run(
+                name.as_deref(),
+                tip.as_deref(),
+                out.as_deref().map(Path::new),
+                rpc_url.as_deref(),
+            )
+            .await
         }
         WalletCommand::Status {
             name,
@@ -83,8 +251,27 @@ pub async fn run(command: WalletCommand) -> Result<(), WalletError> {
         WalletCommand::History {
             limit,
             json,
+            local,
+            csv,
             rpc_url,
-        } => commands::history::run(limit, json, rpc_url.as_deref()).await,
+        } => {
+            if local {
+                commands::history::run_local(
+                    limit,
+                    json,
+                    csv.as_deref().map(Path::new),
+                    rpc_url.as_deref(),
+                )
+                .await
+            } else {
+                commands::history::run(limit, json, rpc_url.as_deref()).await
+            }
+        }
+        WalletCommand::Label {
+            knot_id,
+            label,
+            clear: _,
+        } => commands::label::run(&knot_id, label.as_deref()),
         WalletCommand::Faucet { address, rpc_url } => {
             commands::faucet::run(address.as_deref(), rpc_url.as_deref()).await
         }
@@ -96,11 +283,45 @@ pub async fn run(command: WalletCommand) -> Result<(), WalletError> {
         WalletCommand::WeaveState { json, rpc_url } => {
             commands::weave_state::run(json, rpc_url.as_deref()).await
         }
+        WalletCommand::Config {
+            action: Some(action),
+            ..
+        } => match action {
+            ConfigAction::UseProfile { name } => commands::config_cmd::use_profile(&name),
+            ConfigAction::Profiles { json } => commands::config_cmd::profiles(json),
+            ConfigAction::AddProfile {
+                name,
+                rpc_url,
+                network,
+                keystore_dir,
+            } => commands::config_cmd::add_profile(
+                &name,
+                &rpc_url,
+                &network,
+                keystore_dir.as_deref().map(Path::new),
+            ),
+            ConfigAction::RemoveProfile { name } => commands::config_cmd::remove_profile(&name),
+        },
         WalletCommand::Config {
             rpc_url,
             network,
+            denomination,
+            price_url,
+            price_pointer,
+            price_currency,
+            json,
+            action: None,
+        } => commands::config_cmd::run(
+            commands::config_cmd::ConfigUpdate {
+                rpc_url: rpc_url.as_deref(),
+                network: network.as_deref(),
+                denomination: denomination.as_deref(),
+                price_url: price_url.as_deref(),
+                price_pointer: price_pointer.as_deref(),
+                price_currency: price_currency.as_deref(),
+            },
             json,
-        } => commands::config_cmd::run(rpc_url.as_deref(), network.as_deref(), json),
+        ),
         WalletCommand::RegisterName { name, yes, rpc_url } => {
             commands::register_name::run(&name, yes, rpc_url.as_deref()).await
         }
@@ -147,16 +368,67 @@ pub async fn run(command: WalletCommand) -> Result<(), WalletError> {
         WalletCommand::Whoami { json, rpc_url } => {
             commands::whoami::run(json, rpc_url.as_deref()).await
         }
-        WalletCommand::SignMessage { message, name } => {
-            commands::sign_message::run(&message, name.as_deref())
-        }
+        WalletCommand::SignMessage {
+            message,
+            name,
+            json,
+        } => commands::sign_message::run(&message, name.as_deref(), json),
         WalletCommand::VerifyMessage {
             message,
             signature,
             pubkey,
-        } => commands::verify_message::run(&message, &signature, &pubkey),
+            json,
+        } => commands::verify_message::run(&message, &signature, &pubkey, json),
+        WalletCommand::SignTyped { file, name, json } => {
+            commands::sign_typed::run(&file, name.as_deref(), json)
+        }
+        WalletCommand::VerifyTyped {
+            file,
+            signature,
+            pubkey,
+            json,
+        } => commands::verify_typed::run(&file, &signature, &pubkey, json),
         WalletCommand::Rename { from, to } => commands::rename::run(&from, &to),
         WalletCommand::ChangePassword { name } => commands::change_password::run(name.as_deref()),
+        WalletCommand::Contacts { command } => match command {
+            ContactsCommand::Add {
+                name,
+                address,
+                note,
+                rpc_url,
+            } => {
+                commands::contacts::add(&name, &address, note.as_deref(), rpc_url.as_deref()).await
+            }
+            ContactsCommand::List { json } => commands::contacts::list(json),
+            ContactsCommand::Remove { name } => commands::contacts::remove(&name),
+        },
+        WalletCommand::Accounts { command } => match command {
+            AccountsCommand::Add { index, label, name } => {
+                commands::accounts::add(name.as_deref(), index, label.as_deref())
+            }
+            AccountsCommand::List { name, json } => commands::accounts::list(name.as_deref(), json),
+            AccountsCommand::Use { account, name } => {
+                commands::accounts::use_account(name.as_deref(), &account)
+            }
+            AccountsCommand::Discover {
+                gap_limit,
+                name,
+                rpc_url,
+            } => commands::accounts::discover(name.as_deref(), gap_limit, rpc_url.as_deref()).await,
+        },
+        WalletCommand::Rekey {
+            name,
+            memory_mib,
+            iterations,
+            parallelism,
+            new_password,
+        } => commands::rekey::run(
+            name.as_deref(),
+            memory_mib,
+            iterations,
+            parallelism,
+            new_password,
+        ),
         WalletCommand::CreateToken {
             name,
             symbol,
@@ -190,6 +462,28 @@ pub async fn run(command: WalletCommand) -> Result<(), WalletError> {
             yes,
             rpc_url,
         } => commands::burn_token::run(&token, &amount, yes, rpc_url.as_deref()).await,
+        WalletCommand::SetTokenMetadata {
+            token,
+            description,
+            icon_uri,
+            project_url,
+            tags,
+            clear,
+            yes,
+            rpc_url,
+        } => {
+            commands::set_token_metadata::run(
+                &token,
+                description.as_deref(),
+                icon_uri.as_deref(),
+                project_url.as_deref(),
+                tags.as_deref(),
+                clear,
+                yes,
+                rpc_url.as_deref(),
+            )
+            .await
+        }
         WalletCommand::TokenInfo {
             token,
             json,
@@ -203,8 +497,15 @@ pub async fn run(command: WalletCommand) -> Result<(), WalletError> {
         WalletCommand::TokenBalances { json, rpc_url } => {
             commands::token_balances::run(json, rpc_url.as_deref()).await
         }
-        WalletCommand::DeployLoom { name, yes, rpc_url } => {
-            commands::deploy_loom::run(&name, yes, rpc_url.as_deref()).await
+        WalletCommand::DeployLoom {
+            name,
+            co_operators,
+            threshold,
+            yes,
+            rpc_url,
+        } => {
+            commands::deploy_loom::run(&name, &co_operators, threshold, yes, rpc_url.as_deref())
+                .await
         }
         WalletCommand::LoomInfo {
             loom_id,
@@ -221,6 +522,17 @@ pub async fn run(command: WalletCommand) -> Result<(), WalletError> {
             bytecode,
             rpc_url,
         } => commands::upload_bytecode::run(&loom_id, &bytecode, rpc_url.as_deref()).await,
+        WalletCommand::ExportLoomState {
+            loom_id,
+            output,
+            rpc_url,
+        } => commands::export_loom_state::run(&loom_id, &output, rpc_url.as_deref()).await,
+        WalletCommand::ImportLoomState {
+            loom_id,
+            input,
+            yes,
+            rpc_url,
+        } => commands::import_loom_state::run(&loom_id, &input, yes, rpc_url.as_deref()).await,
         WalletCommand::ExecuteLoom {
             loom_id,
             input,
@@ -238,6 +550,24 @@ pub async fn run(command: WalletCommand) -> Result<(), WalletError> {
         WalletCommand::LeaveLoom { loom_id, rpc_url } => {
             commands::leave_loom::run(&loom_id, rpc_url.as_deref()).await
         }
+        WalletCommand::SetParticipantRole {
+            loom_id,
+            participant,
+            role,
+            rpc_url,
+        } => {
+            commands::set_participant_role::run(&loom_id, &participant, &role, rpc_url.as_deref())
+                .await
+        }
+        WalletCommand::RotateLoomOperator {
+            loom_id,
+            new_wallet,
+            yes,
+            rpc_url,
+        } => {
+            commands::rotate_loom_operator::run(&loom_id, &new_wallet, yes, rpc_url.as_deref())
+                .await
+        }
         WalletCommand::NewLoom { name } => commands::new_loom::run(&name),
         WalletCommand::Stake {
             amount,
@@ -259,11 +589,84 @@ pub async fn run(command: WalletCommand) -> Result<(), WalletError> {
             })?;
             commands::unstake::run(amount, yes, rpc_url.as_deref()).await
         }
-        WalletCommand::StakingInfo { validator, rpc_url } => {
-            commands::staking_info::run(validator.as_deref(), rpc_url.as_deref()).await
+        WalletCommand::Delegate {
+            validator,
+            amount,
+            yes,
+            rpc_url,
+        } => {
+            let amount: u128 = amount.parse().map_err(|_| {
+                crate::wallet::error::WalletError::Other("invalid amount".to_string())
+            })?;
+            commands::delegate::run(&validator, amount, yes, rpc_url.as_deref()).await
+        }
+        WalletCommand::Undelegate {
+            validator,
+            amount,
+            yes,
+            rpc_url,
+        } => {
+            let amount: u128 = amount.parse().map_err(|_| {
+                crate::wallet::error::WalletError::Other("invalid amount".to_string())
+            })?;
+            commands::undelegate::run(&validator, amount, yes, rpc_url.as_deref()).await
+        }
+        WalletCommand::Delegations { json, rpc_url } => {
+            commands::delegations::run(json, rpc_url.as_deref()).await
+        }
+        WalletCommand::SetCommission {
+            commission_bps,
+            yes,
+            rpc_url,
+        } => commands::set_commission::run(commission_bps, yes, rpc_url.as_deref()).await,
+        WalletCommand::Unjail { yes, rpc_url } => {
+            commands::unjail::run(yes, rpc_url.as_deref()).await
         }
+        WalletCommand::StakingInfo {
+            validator,
+            json,
+            rpc_url,
+        } => commands::staking_info::run(validator.as_deref(), json, rpc_url.as_deref()).await,
         WalletCommand::Rewards { json, rpc_url } => {
             commands::rewards::run(json, rpc_url.as_deref()).await
         }
+        WalletCommand::Spindles { json, rpc_url } => {
+            commands::spindles::run(json, rpc_url.as_deref()).await
+        }
+        WalletCommand::SpindleBond {
+            amount,
+            fee,
+            yes,
+            rpc_url,
+        } => {
+            let amount: u128 = amount.parse().map_err(|_| {
+                crate::wallet::error::WalletError::Other("invalid amount".to_string())
+            })?;
+            let fee: u128 = fee
+                .parse()
+                .map_err(|_| crate::wallet::error::WalletError::Other("invalid fee".to_string()))?;
+            commands::spindle_bond::run(amount, fee, yes, rpc_url.as_deref()).await
+        }
+        WalletCommand::SpindleUnbond {
+            amount,
+            yes,
+            rpc_url,
+        } => {
+            let amount: u128 = amount.parse().map_err(|_| {
+                crate::wallet::error::WalletError::Other("invalid amount".to_string())
+            })?;
+            commands::spindle_unbond::run(amount, yes, rpc_url.as_deref()).await
+        }
+        WalletCommand::SpindleSubscribe {
+            spindle,
+            periods,
+            yes,
+            rpc_url,
+        } => commands::spindle_subscribe::run(&spindle, periods, yes, rpc_url.as_deref()).await,
+        WalletCommand::SpindleUnsubscribe {
+            spindle,
+            yes,
+            rpc_url,
+        } => commands::spindle_unsubscribe::run(&spindle, yes, rpc_url.as_deref()).await,
     }
 }
@@ -0,0 +1,170 @@
+//! JSON output of wallet commands.
+//!
+//! Every read command accepts `--json`. Where a command prints what the node
+//! returned, it prints the RPC type as is; where it combines several results
+//! or local data, it prints one of the structs below. Field names are part
+//! of the CLI's interface: add fields, but do not rename or remove them.
+
+use serde::Serialize;
+
+use crate::rpc::types::{
+    BalanceEntry, SpindleCoverageInfo, SpindleInfo, SpindleSubscriptionInfo, ThreadInfo,
+};
+
+use super::error::WalletError;
+use super::format::format_token_amount;
+
+/// Print a value as pretty JSON on stdout.
+pub fn print_json<T: Serialize + ?Sized>(value: &T) -> Result<(), WalletError> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+/// A balance in the same shape the node uses for thread state.
+/// `human_readable` has the token's decimals applied and no symbol.
+pub fn balance_entry(token_id: &str, symbol: &str, amount: u128, decimals: u8) -> BalanceEntry {
+    BalanceEntry {
+        token_id: token_id.to_string(),
+        symbol: Some(symbol.to_string()),
+        amount: amount.to_string(),
+        human_readable: format_token_amount(amount, decimals),
+    }
+}
+
+/// `norn wallet address`.
+#[derive(Debug, Serialize)]
+pub struct WalletAddress {
+    pub wallet: String,
+    pub address: String,
+    pub bech32: String,
+    pub public_key: String,
+}
+
+/// One wallet in `norn wallet list`.
+#[derive(Debug, Serialize)]
+pub struct WalletSummary {
+    pub name: String,
+    pub address: String,
+    pub active: bool,
+    pub watch_only: bool,
+    pub ledger: bool,
+}
+
+/// `norn wallet status`.
+#[derive(Debug, Serialize)]
+pub struct WalletStatus {
+    pub wallet: String,
+    pub address: String,
+    /// `null` if the thread is not registered.
+    pub thread: Option<ThreadInfo>,
+}
+
+/// `norn wallet balance`: one token's balance of one address.
+#[derive(Debug, Serialize)]
+pub struct Balance {
+    pub address: String,
+    #[serde(flatten)]
+    pub balance: BalanceEntry,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_height: Option<u64>,
+}
+
+/// `norn wallet token-balances`: NORN first, then every non-zero token.
+#[derive(Debug, Serialize)]
+pub struct Balances {
+    pub address: String,
+    pub balances: Vec<BalanceEntry>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_height: Option<u64>,
+}
+
+/// `norn wallet whoami`.
+#[derive(Debug, Serialize)]
+pub struct Whoami {
+    pub wallet: String,
+    pub address: String,
+    pub names: Vec<String>,
+    pub thread_registered: bool,
+    /// NORN first, then every non-zero token.
+    pub balances: Vec<BalanceEntry>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_height: Option<u64>,
+}
+
+/// `norn wallet reverse-name`.
+#[derive(Debug, Serialize)]
+pub struct ReverseName {
+    pub address: String,
+    pub name: String,
+}
+
+/// `norn wallet spindles`.
+#[derive(Debug, Serialize)]
+pub struct SpindleMarket {
+    pub spindles: Vec<SpindleInfo>,
+    pub subscriptions: Vec<SpindleSubscriptionInfo>,
+    pub coverage: SpindleCoverageInfo,
+}
+
+/// `norn wallet sign-message`.
+#[derive(Debug, Serialize)]
+pub struct SignedMessage {
+    pub message: String,
+    pub message_hash: String,
+    pub signature: String,
+    pub public_key: String,
+}
+
+/// `norn wallet verify-message`.
+#[derive(Debug, Serialize)]
+pub struct MessageVerification {
+    pub message: String,
+    pub public_key: String,
+    pub valid: bool,
+}
+
+/// `norn wallet sign-typed`.
+#[derive(Debug, Serialize)]
+pub struct SignedTypedData {
+    pub message_type: String,
+    pub domain_separator: String,
+    pub signing_hash: String,
+    pub signature: String,
+    pub public_key: String,
+    pub signer: String,
+}
+
+/// `norn wallet verify-typed`.
+#[derive(Debug, Serialize)]
+pub struct TypedDataVerification {
+    pub message_type: String,
+    pub signing_hash: String,
+    pub public_key: String,
+    pub signer: String,
+    pub valid: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_balance_field_names() {
+        let out = Balance {
+            address: "0xab".to_string(),
+            balance: balance_entry("00", "NORN", 1_500_000_000_000, 12),
+            block_height: None,
+        };
+        let json = serde_json::to_value(&out).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "address": "0xab",
+                "token_id": "00",
+                "symbol": "NORN",
+                "amount": "1500000000000",
+                "human_readable": "1.5",
+            })
+        );
+    }
+}
@@ -0,0 +1,118 @@
+//! Price annotation for NORN amounts.
+//!
+//! The wallet can show what NORN amounts are worth in a currency of the
+//! user's choice. The price comes from an HTTP(S) endpoint the user
+//! configures (`norn wallet config --price-url`); the wallet reads the
+//! number at a JSON pointer in the response, so most public price APIs
+//! work as they are. Prices are fetched only by commands that show
+//! balances, and never affect what is signed.
+
+use std::time::Duration;
+
+use http_body_util::{BodyExt, Empty, Limited};
+use hyper::body::Bytes;
+use hyper_util::client::legacy::connect::HttpConnector;
+use hyper_util::client::legacy::Client;
+use hyper_util::rt::TokioExecutor;
+
+use super::error::WalletError;
+
+/// How long to wait for the price endpoint.
+const FETCH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Largest price response accepted.
+const MAX_RESPONSE_BYTES: usize = 64 * 1024;
+
+/// The value of one NORN in some currency.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Price {
+    pub per_norn: f64,
+    pub currency: String,
+}
+
+/// Fetch the price of one NORN: GET `url` and read the number at the JSON
+/// `pointer` (RFC 6901) in the response.
+pub async fn fetch(url: &str, pointer: &str) -> Result<f64, WalletError> {
+    let uri: hyper::Uri = url
+        .parse()
+        .map_err(|e| WalletError::ConfigError(format!("invalid price URL: {}", e)))?;
+
+    // Several rustls providers may be compiled in; pick one if none is set.
+    let _ = rustls::crypto::ring::default_provider().install_default();
+    let mut http = HttpConnector::new();
+    http.enforce_http(false);
+    let https = {
+        use rustls_platform_verifier::ConfigVerifierExt;
+        hyper_rustls::HttpsConnectorBuilder::new()
+            .with_tls_config(rustls::ClientConfig::with_platform_verifier())
+            .https_or_http()
+            .enable_http1()
+            .wrap_connector(http)
+    };
+    let client: Client<_, Empty<Bytes>> = Client::builder(TokioExecutor::new()).build(https);
+
+    let request = hyper::Request::get(uri)
+        .header(hyper::header::ACCEPT, "application/json")
+        .body(Empty::new())
+        .map_err(|e| WalletError::RpcError(format!("price request: {}", e)))?;
+    let fetch = async {
+        let response = client
+            .request(request)
+            .await
+            .map_err(|e| WalletError::RpcError(format!("price endpoint: {}", e)))?;
+        if !response.status().is_success() {
+            return Err(WalletError::RpcError(format!(
+                "price endpoint returned {}",
+                response.status()
+            )));
+        }
+        Limited::new(response.into_body(), MAX_RESPONSE_BYTES)
+            .collect()
+            .await
+            .map(|body| body.to_bytes())
+            .map_err(|e| WalletError::RpcError(format!("price endpoint: {}", e)))
+    };
+    let body = tokio::time::timeout(FETCH_TIMEOUT, fetch)
+        .await
+        .map_err(|_| WalletError::RpcError("price endpoint timed out".to_string()))??;
+
+    let json: serde_json::Value = serde_json::from_slice(&body)?;
+    extract(&json, pointer)
+}
+
+/// Read a price at a JSON pointer; numeric strings are accepted.
+fn extract(json: &serde_json::Value, pointer: &str) -> Result<f64, WalletError> {
+    let value = json.pointer(pointer).ok_or_else(|| {
+        WalletError::RpcError(format!("price response has nothing at '{}'", pointer))
+    })?;
+    let price = match value {
+        serde_json::Value::Number(n) => n.as_f64(),
+        serde_json::Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    };
+    match price {
+        Some(p) if p.is_finite() && p >= 0.0 => Ok(p),
+        _ => Err(WalletError::RpcError(format!(
+            "price at '{}' is not a number: {}",
+            pointer, value
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_price() {
+        let json = serde_json::json!({
+            "price": 1.25,
+            "norn": { "usd": "0.5", "eur": -1 },
+        });
+        assert_eq!(extract(&json, "/price").unwrap(), 1.25);
+        assert_eq!(extract(&json, "/norn/usd").unwrap(), 0.5);
+        assert!(extract(&json, "/norn/eur").is_err());
+        assert!(extract(&json, "/norn").is_err());
+        assert!(extract(&json, "/missing").is_err());
+    }
+}
@@ -1,6 +1,7 @@
 use dialoguer::{Confirm, Password};
 
 use super::error::WalletError;
+use super::format::style_warn;
 
 /// Prompt the user for a password (hidden input).
 /// If `NORN_WALLET_PASSWORD` env var is set, uses that instead of prompting.
@@ -27,6 +28,36 @@ pub fn prompt_new_password() -> Result<String, WalletError> {
         .map_err(|e| WalletError::IoError(std::io::Error::other(e)))
 }
 
+/// Get the BIP-39 passphrase for a new or imported wallet: from
+/// `--passphrase`, from a hidden prompt if `ask` is set, or none. Warns
+/// about the ways a passphrase can lose funds.
+pub fn prompt_passphrase(passphrase: Option<&str>, ask: bool) -> Result<String, WalletError> {
+    let passphrase = match passphrase {
+        Some(p) => {
+            warn("A passphrase given with --passphrase may be kept in your shell history; prefer --ask-passphrase.");
+            p.to_string()
+        }
+        None if ask => Password::new()
+            .with_prompt("Enter BIP-39 passphrase")
+            .with_confirmation("Confirm passphrase", "Passphrases do not match")
+            .allow_empty_password(true)
+            .interact()
+            .map_err(|e| WalletError::IoError(std::io::Error::other(e)))?,
+        None => String::new(),
+    };
+    if !passphrase.is_empty() {
+        warn("The passphrase is not part of the mnemonic. Back it up separately: the mnemonic without it, or with a mistyped one, restores a different, empty wallet.");
+        if passphrase.trim() != passphrase {
+            warn("The passphrase starts or ends with whitespace, which counts.");
+        }
+    }
+    Ok(passphrase)
+}
+
+fn warn(msg: &str) {
+    println!("  {}", style_warn().apply_to(msg));
+}
+
 /// Ask the user to confirm an action.
 pub fn confirm(prompt: &str) -> Result<bool, WalletError> {
     Confirm::new()
@@ -0,0 +1,170 @@
+//! QR-code transport for transaction files.
+//!
+//! An air-gapped machine has no network, so transactions reach it and leave
+//! it as a series of QR codes. Each code holds one text chunk,
+//! `NORNTX:<index>/<total>:<checksum>:<data>`, with the data as uppercase
+//! hex so the whole chunk fits QR alphanumeric mode. The checksum is the
+//! first 4 bytes of the BLAKE3 hash of the full payload: it ties the chunks
+//! of one payload together and catches misreads. Chunks can be scanned in
+//! any order.
+
+use std::collections::BTreeMap;
+
+use qrcode::render::unicode::Dense1x2;
+use qrcode::{EcLevel, QrCode};
+
+use super::error::WalletError;
+
+/// Prefix of every chunk.
+const CHUNK_PREFIX: &str = "NORNTX";
+
+/// Payload bytes per chunk; with the header, a chunk fits a version 10 QR
+/// code at low error correction.
+const CHUNK_BYTES: usize = 150;
+
+/// Largest number of chunks a payload may be split into.
+const MAX_CHUNKS: usize = 1000;
+
+fn checksum(payload: &[u8]) -> String {
+    hex::encode_upper(&norn_crypto::hash::blake3_hash(payload)[..4])
+}
+
+/// Split a payload into QR text chunks.
+pub fn encode_chunks(payload: &[u8]) -> Vec<String> {
+    let check = checksum(payload);
+    let chunks: Vec<&[u8]> = if payload.is_empty() {
+        vec![&[]]
+    } else {
+        payload.chunks(CHUNK_BYTES).collect()
+    };
+    let total = chunks.len();
+    chunks
+        .iter()
+        .enumerate()
+        .map(|(i, chunk)| {
+            format!(
+                "{}:{}/{}:{}:{}",
+                CHUNK_PREFIX,
+                i + 1,
+                total,
+                check,
+                hex::encode_upper(chunk)
+            )
+        })
+        .collect()
+}
+
+/// Whether a text looks like QR chunks rather than a transaction file.
+pub fn is_chunk_text(text: &str) -> bool {
+    text.trim_start().starts_with(CHUNK_PREFIX)
+}
+
+/// Reassemble a payload from chunks, one per line, in any order. Blank
+/// lines and repeated chunks are ignored.
+pub fn decode_chunks(text: &str) -> Result<Vec<u8>, WalletError> {
+    let invalid =
+        |line: &str| WalletError::SerializationError(format!("invalid QR chunk: {}", line));
+
+    let mut expected: Option<(usize, String)> = None;
+    let mut chunks = BTreeMap::new();
+    for line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let mut parts = line.splitn(4, ':');
+        let (Some(CHUNK_PREFIX), Some(position), Some(check), Some(data)) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid(line));
+        };
+        let (index, total) = position.split_once('/').ok_or_else(|| invalid(line))?;
+        let index: usize = index.parse().map_err(|_| invalid(line))?;
+        let total: usize = total.parse().map_err(|_| invalid(line))?;
+        if total == 0 || total > MAX_CHUNKS || index == 0 || index > total {
+            return Err(invalid(line));
+        }
+        match &expected {
+            None => expected = Some((total, check.to_string())),
+            Some((t, c)) if *t == total && c == check => {}
+            Some(_) => {
+                return Err(WalletError::SerializationError(
+                    "QR chunks belong to different transactions".to_string(),
+                ))
+            }
+        }
+        let data = hex::decode(data).map_err(|_| invalid(line))?;
+        chunks.insert(index, data);
+    }
+
+    let (total, check) = expected
+        .ok_or_else(|| WalletError::SerializationError("no QR chunks found".to_string()))?;
+    let missing: Vec<String> = (1..=total)
+        .filter(|i| !chunks.contains_key(i))
+        .map(|i| i.to_string())
+        .collect();
+    if !missing.is_empty() {
+        return Err(WalletError::SerializationError(format!(
+            "missing QR chunk(s) {} of {}",
+            missing.join(", "),
+            total
+        )));
+    }
+    let payload: Vec<u8> = chunks.into_values().flatten().collect();
+    if checksum(&payload) != check {
+        return Err(WalletError::SerializationError(
+            "QR chunks do not match their checksum".to_string(),
+        ));
+    }
+    Ok(payload)
+}
+
+/// Render a chunk as a QR code for the terminal.
+pub fn render(chunk: &str) -> Result<String, WalletError> {
+    let code = QrCode::with_error_correction_level(chunk, EcLevel::L)
+        .map_err(|e| WalletError::Other(format!("QR encoding failed: {}", e)))?;
+    Ok(code
+        .render::<Dense1x2>()
+        .dark_color(Dense1x2::Light)
+        .light_color(Dense1x2::Dark)
+        .quiet_zone(true)
+        .build())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunks_roundtrip_in_any_order() {
+        let payload: Vec<u8> = (0..400u32).map(|i| (i * 7) as u8).collect();
+        let chunks = encode_chunks(&payload);
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks.iter().all(|c| is_chunk_text(c)));
+
+        // Scanned out of order, with a repeat.
+        let text = [&chunks[2], &chunks[0], &chunks[2], &chunks[1]]
+            .map(|c| c.as_str())
+            .join("\n");
+        assert_eq!(decode_chunks(&text).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_incomplete_or_mixed_chunks_rejected() {
+        let a = encode_chunks(&[1u8; 400]);
+        let b = encode_chunks(&[2u8; 400]);
+
+        let missing = decode_chunks(&format!("{}\n{}", a[0], a[2])).unwrap_err();
+        assert!(missing.to_string().contains("missing QR chunk(s) 2 of 3"));
+        assert!(decode_chunks(&format!("{}\n{}\n{}", a[0], b[1], a[2])).is_err());
+
+        // A misread digit fails the checksum.
+        let mut corrupt = a[1].clone();
+        let last = corrupt.pop().unwrap();
+        corrupt.push(if last == '0' { '1' } else { '0' });
+        assert!(decode_chunks(&format!("{}\n{}\n{}", a[0], corrupt, a[2])).is_err());
+    }
+
+    #[test]
+    fn test_render_fits_alphanumeric_mode() {
+        let chunk = &encode_chunks(&[0xab; CHUNK_BYTES * 2])[0];
+        let art = render(chunk).unwrap();
+        assert!(art.lines().count() > 10);
+    }
+}
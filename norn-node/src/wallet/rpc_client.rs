@@ -1,14 +1,19 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
 use indicatif::{ProgressBar, ProgressStyle};
 use jsonrpsee::core::client::ClientT;
 use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
 use jsonrpsee::rpc_params;
 
 use crate::rpc::types::{
-    BlockInfo, ExecutionResult, FeeEstimateInfo, HealthInfo, LoomInfo, NameInfo, NameResolution,
-    QueryResult, StakingInfo, SubmitResult, TokenInfo, TransactionHistoryEntry,
-    ValidatorRewardsInfo, ValidatorSetInfo, WeaveStateInfo,
+    BlockInfo, DelegationInfo, EstimatedFeeInfo, ExecutionResult, FeeEstimateInfo, HealthInfo,
+    KnotProposalInfo, LoomInfo, LoomStateExportInfo, NameInfo, NameResolution, QueryResult,
+    SpindleCoverageInfo, SpindleInfo, SpindleSubscriptionInfo, StakingInfo, SubmitResult,
+    TokenInfo, TransactionHistoryEntry, UploadBytecodeResult, ValidatorRewardsInfo,
+    ValidatorSetInfo, WeaveStateInfo,
 };
 
+use super::config::WalletConfig;
 use super::error::WalletError;
 
 /// Default RPC request timeout in seconds.
@@ -17,6 +22,10 @@ const DEFAULT_RPC_TIMEOUT_SECS: u64 = 10;
 /// JSON-RPC client for the Norn node.
 pub struct RpcClient {
     client: HttpClient,
+    /// Profile in effect and the network it expects; checked once before
+    /// the first signed submission.
+    expected_network: Option<(&'static str, &'static str)>,
+    network_checked: AtomicBool,
 }
 
 impl RpcClient {
@@ -26,7 +35,36 @@ impl RpcClient {
             .request_timeout(std::time::Duration::from_secs(DEFAULT_RPC_TIMEOUT_SECS))
             .build(url)
             .map_err(|e| WalletError::RpcError(format!("failed to connect: {}", e)))?;
-        Ok(Self { client })
+        Ok(Self {
+            client,
+            expected_network: WalletConfig::expected_network(),
+            network_checked: AtomicBool::new(false),
+        })
+    }
+
+    /// Make sure the node is on the network the profile in effect expects,
+    /// so a mistyped `--rpc-url` cannot send signed data to another network.
+    async fn check_network(&self) -> Result<(), WalletError> {
+        let Some((profile, expected)) = self.expected_network else {
+            return Ok(());
+        };
+        if self.network_checked.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        let health: HealthInfo = self
+            .client
+            .request("norn_health", rpc_params![])
+            .await
+            .map_err(|e| Self::map_rpc_error(&e))?;
+        if health.network != expected {
+            return Err(WalletError::NetworkMismatch {
+                profile: profile.to_string(),
+                expected: expected.to_string(),
+                actual: health.network,
+            });
+        }
+        self.network_checked.store(true, Ordering::Relaxed);
+        Ok(())
     }
 
     /// Create a spinner for an RPC operation.
@@ -98,6 +136,7 @@ impl RpcClient {
 
     /// Submit a commitment (hex-encoded borsh bytes).
     pub async fn submit_commitment(&self, hex_data: &str) -> Result<SubmitResult, WalletError> {
+        self.check_network().await?;
         let pb = Self::spinner("Submitting commitment...");
         let result: SubmitResult = self
             .client
@@ -110,6 +149,7 @@ impl RpcClient {
 
     /// Submit a registration (hex-encoded borsh bytes).
     pub async fn submit_registration(&self, hex_data: &str) -> Result<SubmitResult, WalletError> {
+        self.check_network().await?;
         let pb = Self::spinner("Submitting registration...");
         let result: SubmitResult = self
             .client
@@ -176,6 +216,7 @@ impl RpcClient {
 
     /// Submit a knot (hex-encoded borsh bytes).
     pub async fn submit_knot(&self, hex_data: &str) -> Result<SubmitResult, WalletError> {
+        self.check_network().await?;
         let pb = Self::spinner("Submitting knot...");
         let result: SubmitResult = self
             .client
@@ -186,6 +227,34 @@ impl RpcClient {
         Ok(result)
     }
 
+    /// Propose or sign a multi-party knot.
+    pub async fn propose_knot(&self, hex_data: &str) -> Result<KnotProposalInfo, WalletError> {
+        self.check_network().await?;
+        let pb = Self::spinner("Submitting signatures...");
+        let result: KnotProposalInfo = self
+            .client
+            .request("norn_proposeKnot", rpc_params![hex_data])
+            .await
+            .map_err(|e| Self::map_rpc_error(&e))?;
+        pb.finish_and_clear();
+        Ok(result)
+    }
+
+    /// Get a multi-party knot still waiting for signatures.
+    pub async fn get_knot_proposal(
+        &self,
+        knot_id: &str,
+    ) -> Result<Option<KnotProposalInfo>, WalletError> {
+        let pb = Self::spinner("Fetching knot proposal...");
+        let result: Option<KnotProposalInfo> = self
+            .client
+            .request("norn_getKnotProposal", rpc_params![knot_id])
+            .await
+            .map_err(|e| Self::map_rpc_error(&e))?;
+        pb.finish_and_clear();
+        Ok(result)
+    }
+
     /// Get transaction history for an address.
     pub async fn get_transaction_history(
         &self,
@@ -213,6 +282,7 @@ impl RpcClient {
         owner_hex: &str,
         knot_hex: &str,
     ) -> Result<SubmitResult, WalletError> {
+        self.check_network().await?;
         let pb = Self::spinner("Registering name...");
         let result: SubmitResult = self
             .client
@@ -283,8 +353,19 @@ impl RpcClient {
         Ok(result)
     }
 
+    /// Estimate the commitment fee at the given confidence (percent).
+    pub async fn estimate_fee(&self, confidence: u8) -> Result<EstimatedFeeInfo, WalletError> {
+        let result: EstimatedFeeInfo = self
+            .client
+            .request("norn_estimateFee", rpc_params![confidence])
+            .await
+            .map_err(|e| Self::map_rpc_error(&e))?;
+        Ok(result)
+    }
+
     /// Create a new token (hex-encoded borsh TokenDefinition).
     pub async fn create_token(&self, hex_data: &str) -> Result<SubmitResult, WalletError> {
+        self.check_network().await?;
         let pb = Self::spinner("Creating token...");
         let result: SubmitResult = self
             .client
@@ -297,6 +378,7 @@ impl RpcClient {
 
     /// Mint tokens (hex-encoded borsh TokenMint).
     pub async fn mint_token(&self, hex_data: &str) -> Result<SubmitResult, WalletError> {
+        self.check_network().await?;
         let pb = Self::spinner("Minting tokens...");
         let result: SubmitResult = self
             .client
@@ -307,8 +389,22 @@ impl RpcClient {
         Ok(result)
     }
 
+    /// Update a token's metadata (hex-encoded borsh TokenMetadataUpdate).
+    pub async fn update_token_metadata(&self, hex_data: &str) -> Result<SubmitResult, WalletError> {
+        self.check_network().await?;
+        let pb = Self::spinner("Updating token metadata...");
+        let result: SubmitResult = self
+            .client
+            .request("norn_updateTokenMetadata", rpc_params![hex_data])
+            .await
+            .map_err(|e| Self::map_rpc_error(&e))?;
+        pb.finish_and_clear();
+        Ok(result)
+    }
+
     /// Burn tokens (hex-encoded borsh TokenBurn).
     pub async fn burn_token(&self, hex_data: &str) -> Result<SubmitResult, WalletError> {
+        self.check_network().await?;
         let pb = Self::spinner("Burning tokens...");
         let result: SubmitResult = self
             .client
@@ -367,6 +463,7 @@ impl RpcClient {
 
     /// Deploy a loom (smart contract).
     pub async fn deploy_loom(&self, hex_data: &str) -> Result<SubmitResult, WalletError> {
+        self.check_network().await?;
         let pb = Self::spinner("Deploying loom...");
         let result: SubmitResult = self
             .client
@@ -409,9 +506,10 @@ impl RpcClient {
         init_msg_hex: Option<&str>,
         operator_signature_hex: &str,
         operator_pubkey_hex: &str,
-    ) -> Result<SubmitResult, WalletError> {
+    ) -> Result<UploadBytecodeResult, WalletError> {
+        self.check_network().await?;
         let pb = Self::spinner("Uploading bytecode...");
-        let result: SubmitResult = self
+        let result: UploadBytecodeResult = self
             .client
             .request(
                 "norn_uploadLoomBytecode",
@@ -429,6 +527,48 @@ impl RpcClient {
         Ok(result)
     }
 
+    /// Export a loom's state snapshot.
+    pub async fn export_loom_state(
+        &self,
+        loom_id_hex: &str,
+    ) -> Result<LoomStateExportInfo, WalletError> {
+        let pb = Self::spinner("Exporting loom state...");
+        let result: LoomStateExportInfo = self
+            .client
+            .request("norn_exportLoomState", rpc_params![loom_id_hex])
+            .await
+            .map_err(|e| Self::map_rpc_error(&e))?;
+        pb.finish_and_clear();
+        Ok(result)
+    }
+
+    /// Import a loom state snapshot with operator authentication.
+    pub async fn import_loom_state(
+        &self,
+        loom_id_hex: &str,
+        snapshot_hex: &str,
+        operator_signature_hex: &str,
+        operator_pubkey_hex: &str,
+    ) -> Result<SubmitResult, WalletError> {
+        self.check_network().await?;
+        let pb = Self::spinner("Importing loom state...");
+        let result: SubmitResult = self
+            .client
+            .request(
+                "norn_importLoomState",
+                rpc_params![
+                    loom_id_hex,
+                    snapshot_hex,
+                    operator_signature_hex,
+                    operator_pubkey_hex
+                ],
+            )
+            .await
+            .map_err(|e| Self::map_rpc_error(&e))?;
+        pb.finish_and_clear();
+        Ok(result)
+    }
+
     /// Execute a loom contract with sender authentication.
     pub async fn execute_loom(
         &self,
@@ -438,6 +578,7 @@ impl RpcClient {
         signature_hex: &str,
         pubkey_hex: &str,
     ) -> Result<ExecutionResult, WalletError> {
+        self.check_network().await?;
         let pb = Self::spinner("Executing loom...");
         let result: ExecutionResult = self
             .client
@@ -481,6 +622,7 @@ impl RpcClient {
         pubkey_hex: &str,
         signature_hex: &str,
     ) -> Result<SubmitResult, WalletError> {
+        self.check_network().await?;
         let pb = Self::spinner("Joining loom...");
         let result: SubmitResult = self
             .client
@@ -502,6 +644,7 @@ impl RpcClient {
         signature_hex: &str,
         pubkey_hex: &str,
     ) -> Result<SubmitResult, WalletError> {
+        self.check_network().await?;
         let pb = Self::spinner("Leaving loom...");
         let result: SubmitResult = self
             .client
@@ -515,7 +658,53 @@ impl RpcClient {
         Ok(result)
     }
 
+    /// Set a loom participant's role.
+    pub async fn set_participant_role(
+        &self,
+        loom_id_hex: &str,
+        participant_hex: &str,
+        role: &str,
+        operator_pubkey_hex: &str,
+        signature_hex: &str,
+    ) -> Result<SubmitResult, WalletError> {
+        self.check_network().await?;
+        let pb = Self::spinner("Setting participant role...");
+        let result: SubmitResult = self
+            .client
+            .request(
+                "norn_setParticipantRole",
+                rpc_params![
+                    loom_id_hex,
+                    participant_hex,
+                    role,
+                    operator_pubkey_hex,
+                    signature_hex
+                ],
+            )
+            .await
+            .map_err(|e| Self::map_rpc_error(&e))?;
+        pb.finish_and_clear();
+        Ok(result)
+    }
+
+    /// Submit a loom operator handover (hex-encoded borsh).
+    pub async fn submit_operator_handover(
+        &self,
+        handover_hex: &str,
+    ) -> Result<SubmitResult, WalletError> {
+        self.check_network().await?;
+        let pb = Self::spinner("Submitting operator handover...");
+        let result: SubmitResult = self
+            .client
+            .request("norn_submitOperatorHandover", rpc_params![handover_hex])
+            .await
+            .map_err(|e| Self::map_rpc_error(&e))?;
+        pb.finish_and_clear();
+        Ok(result)
+    }
+
     pub async fn submit_stake(&self, hex_data: &str) -> Result<SubmitResult, WalletError> {
+        self.check_network().await?;
         let pb = Self::spinner("Submitting stake operation...");
         let result: SubmitResult = self
             .client
@@ -526,6 +715,110 @@ impl RpcClient {
         Ok(result)
     }
 
+    pub async fn submit_delegate(&self, hex_data: &str) -> Result<SubmitResult, WalletError> {
+        self.check_network().await?;
+        let pb = Self::spinner("Submitting delegate operation...");
+        let result: SubmitResult = self
+            .client
+            .request("norn_delegate", rpc_params![hex_data])
+            .await
+            .map_err(|e| Self::map_rpc_error(&e))?;
+        pb.finish_and_clear();
+        Ok(result)
+    }
+
+    pub async fn submit_undelegate(&self, hex_data: &str) -> Result<SubmitResult, WalletError> {
+        self.check_network().await?;
+        let pb = Self::spinner("Submitting undelegate operation...");
+        let result: SubmitResult = self
+            .client
+            .request("norn_undelegate", rpc_params![hex_data])
+            .await
+            .map_err(|e| Self::map_rpc_error(&e))?;
+        pb.finish_and_clear();
+        Ok(result)
+    }
+
+    pub async fn submit_unjail(&self, hex_data: &str) -> Result<SubmitResult, WalletError> {
+        self.check_network().await?;
+        let pb = Self::spinner("Submitting unjail operation...");
+        let result: SubmitResult = self
+            .client
+            .request("norn_unjail", rpc_params![hex_data])
+            .await
+            .map_err(|e| Self::map_rpc_error(&e))?;
+        pb.finish_and_clear();
+        Ok(result)
+    }
+
+    pub async fn submit_spindle_operation(
+        &self,
+        hex_data: &str,
+    ) -> Result<SubmitResult, WalletError> {
+        self.check_network().await?;
+        let pb = Self::spinner("Submitting spindle operation...");
+        let result: SubmitResult = self
+            .client
+            .request("norn_submitSpindleOperation", rpc_params![hex_data])
+            .await
+            .map_err(|e| Self::map_rpc_error(&e))?;
+        pb.finish_and_clear();
+        Ok(result)
+    }
+
+    pub async fn get_spindles(&self) -> Result<Vec<SpindleInfo>, WalletError> {
+        let pb = Self::spinner("Fetching spindles...");
+        let result: Vec<SpindleInfo> = self
+            .client
+            .request("norn_getSpindles", rpc_params![])
+            .await
+            .map_err(|e| Self::map_rpc_error(&e))?;
+        pb.finish_and_clear();
+        Ok(result)
+    }
+
+    pub async fn get_spindle_subscriptions(
+        &self,
+        address_hex: &str,
+    ) -> Result<Vec<SpindleSubscriptionInfo>, WalletError> {
+        let pb = Self::spinner("Fetching spindle subscriptions...");
+        let result: Vec<SpindleSubscriptionInfo> = self
+            .client
+            .request("norn_getSpindleSubscriptions", rpc_params![address_hex])
+            .await
+            .map_err(|e| Self::map_rpc_error(&e))?;
+        pb.finish_and_clear();
+        Ok(result)
+    }
+
+    pub async fn get_spindle_coverage(
+        &self,
+        address_hex: &str,
+    ) -> Result<SpindleCoverageInfo, WalletError> {
+        let pb = Self::spinner("Fetching spindle coverage...");
+        let result: SpindleCoverageInfo = self
+            .client
+            .request("norn_getSpindleCoverage", rpc_params![address_hex])
+            .await
+            .map_err(|e| Self::map_rpc_error(&e))?;
+        pb.finish_and_clear();
+        Ok(result)
+    }
+
+    pub async fn get_delegations(
+        &self,
+        delegator_hex: &str,
+    ) -> Result<Vec<DelegationInfo>, WalletError> {
+        let pb = Self::spinner("Fetching delegations...");
+        let result: Vec<DelegationInfo> = self
+            .client
+            .request("norn_getDelegations", rpc_params![delegator_hex])
+            .await
+            .map_err(|e| Self::map_rpc_error(&e))?;
+        pb.finish_and_clear();
+        Ok(result)
+    }
+
     pub async fn get_staking_info(
         &self,
         pubkey_hex: Option<&str>,
@@ -550,6 +843,7 @@ impl RpcClient {
         from_hex: &str,
         transfer_hex: &str,
     ) -> Result<SubmitResult, WalletError> {
+        self.check_network().await?;
         let pb = Self::spinner("Transferring name...");
         let result: SubmitResult = self
             .client
@@ -584,6 +878,7 @@ impl RpcClient {
         owner_hex: &str,
         knot_hex: &str,
     ) -> Result<SubmitResult, WalletError> {
+        self.check_network().await?;
         let pb = Self::spinner("Setting name record...");
         let result: SubmitResult = self
             .client
@@ -0,0 +1,146 @@
+//! Signing backends for wallets.
+//!
+//! Commands sign through `Signer` so they work the same whether the key is
+//! decrypted from the keystore or held on a Ledger.
+
+use norn_crypto::bls::BlsKeypair;
+use norn_crypto::keys::Keypair;
+use norn_types::primitives::{PublicKey, Signature};
+
+use super::error::WalletError;
+use super::format::{format_address, style_dim, style_warn};
+use super::keystore::{Keystore, WALLET_VERSION};
+use super::ledger::Ledger;
+use super::prompt::prompt_password;
+
+/// Something that signs with a wallet's key.
+pub trait Signer {
+    /// Public key the signatures verify against.
+    fn public_key(&self) -> PublicKey;
+
+    /// Sign a message. Hardware signers wait for the user to approve it.
+    fn sign(&mut self, message: &[u8]) -> Result<Signature, WalletError>;
+
+    /// The BLS consensus key that goes with this key, for staking.
+    fn bls_keypair(&self) -> Result<BlsKeypair, WalletError>;
+}
+
+impl Signer for Keypair {
+    fn public_key(&self) -> PublicKey {
+        Keypair::public_key(self)
+    }
+
+    fn sign(&mut self, message: &[u8]) -> Result<Signature, WalletError> {
+        Ok(Keypair::sign(self, message))
+    }
+
+    fn bls_keypair(&self) -> Result<BlsKeypair, WalletError> {
+        Ok(BlsKeypair::from_keypair(self))
+    }
+}
+
+/// Signs on a Ledger with one of its accounts.
+pub struct LedgerSigner {
+    ledger: Ledger,
+    account: u32,
+    public_key: PublicKey,
+}
+
+impl LedgerSigner {
+    /// Connect to the Ledger and check it holds the expected key.
+    pub fn connect(account: u32, expected: &PublicKey) -> Result<Self, WalletError> {
+        let mut ledger = Ledger::connect()?;
+        let public_key = ledger.public_key(account, false)?;
+        if public_key != *expected {
+            return Err(WalletError::Ledger(format!(
+                "account {} of this device is not the wallet's key; is the right device connected?",
+                account
+            )));
+        }
+        Ok(Self {
+            ledger,
+            account,
+            public_key,
+        })
+    }
+}
+
+impl Signer for LedgerSigner {
+    fn public_key(&self) -> PublicKey {
+        self.public_key
+    }
+
+    fn sign(&mut self, message: &[u8]) -> Result<Signature, WalletError> {
+        println!(
+            "  {}",
+            style_dim().apply_to("Review and approve the signature on your Ledger...")
+        );
+        self.ledger.sign(self.account, message)
+    }
+
+    fn bls_keypair(&self) -> Result<BlsKeypair, WalletError> {
+        Err(WalletError::Ledger(
+            "validator BLS keys cannot be derived from a Ledger key; stake from a keystore wallet"
+                .to_string(),
+        ))
+    }
+}
+
+/// Open the signer for a wallet: its Ledger, or its keystore key after
+/// asking for the password. Watch-only wallets cannot sign.
+pub fn unlock(ks: &Keystore) -> Result<Box<dyn Signer>, WalletError> {
+    if let Some(ledger) = &ks.file.ledger {
+        let signer = LedgerSigner::connect(ledger.account, &ks.public_key)?;
+        println!(
+            "  {}",
+            style_dim().apply_to(format!(
+                "Signing with Ledger account {} ({})",
+                ledger.account,
+                format_address(&ks.address)
+            ))
+        );
+        return Ok(Box::new(signer));
+    }
+    if ks.is_watch_only() {
+        return Err(WalletError::WatchOnly(ks.name.clone()));
+    }
+    let password = prompt_password("Enter password")?;
+    Ok(Box::new(unlock_keypair(ks, &password)?))
+}
+
+/// Decrypt a keystore wallet's key, upgrading the keystore on disk if it
+/// is in an outdated format. See [`unlock_secret`].
+pub fn unlock_keypair(ks: &Keystore, password: &str) -> Result<Keypair, WalletError> {
+    unlock_secret(&mut ks.clone(), password, Keystore::decrypt_keypair)
+}
+
+/// Decrypt a secret from a keystore wallet with `decrypt`, then upgrade
+/// the keystore on disk (and `ks`) if it is in an outdated format. Every
+/// decryption goes through here so outdated wallets are migrated whichever
+/// command unlocks them. The outcome goes to stderr so `--json` output
+/// stays clean; if the upgrade fails, the old file keeps working and the
+/// next unlock retries.
+pub fn unlock_secret<T>(
+    ks: &mut Keystore,
+    password: &str,
+    decrypt: impl FnOnce(&Keystore, &str) -> Result<T, WalletError>,
+) -> Result<T, WalletError> {
+    let secret = decrypt(ks, password)?;
+    match ks.migrate(password) {
+        Ok(true) => eprintln!(
+            "  {}",
+            style_dim().apply_to(format!(
+                "Upgraded the keystore of wallet '{}' to version {}.",
+                ks.name, WALLET_VERSION
+            ))
+        ),
+        Ok(false) => {}
+        Err(e) => eprintln!(
+            "  {} could not upgrade the keystore of wallet '{}': {}",
+            style_warn().apply_to("Warning:"),
+            ks.name,
+            e
+        ),
+    }
+    Ok(secret)
+}
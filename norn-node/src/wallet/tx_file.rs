@@ -0,0 +1,333 @@
+//! Transaction files.
+//!
+//! Transactions signed away from the machine that builds or submits them
+//! travel as files, much like Bitcoin PSBTs: a knot that needs keys held on
+//! different devices, or a knot or commitment built by a watch-only wallet
+//! and signed on an air-gapped machine. One device builds the file, each key
+//! holder adds its signature, and any online device submits it. Files can
+//! also be carried as QR chunks (see `qr`).
+
+use std::path::Path;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+use norn_thread::proposal::{PendingKnot, UNSIGNED};
+use norn_types::knot::{Cosignature, Knot};
+use norn_types::weave::CommitmentUpdate;
+
+use super::error::WalletError;
+use super::qr;
+use super::signer::Signer;
+
+/// A transaction carried in a file.
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+pub enum Transaction {
+    Knot(Knot),
+    Commitment(CommitmentUpdate),
+}
+
+/// Value of the `format` field of a knot file.
+const KNOT_FILE_FORMAT: &str = "norn-partial-knot";
+
+/// Current knot file version.
+const KNOT_FILE_VERSION: u32 = 1;
+
+/// Value of the `format` field of a commitment file.
+const COMMITMENT_FILE_FORMAT: &str = "norn-commitment";
+
+/// Current commitment file version.
+const COMMITMENT_FILE_VERSION: u32 = 1;
+
+/// On-disk knot file format.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct KnotFile {
+    format: String,
+    version: u32,
+    /// Knot ID as hex, so the file can be matched to a proposal at a glance.
+    knot_id: String,
+    /// Borsh-encoded knot as hex, with zeroed signatures for missing signers.
+    knot: String,
+}
+
+/// On-disk commitment file format.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CommitmentFile {
+    format: String,
+    version: u32,
+    /// Thread ID as hex, to tell files apart at a glance.
+    thread_id: String,
+    /// Borsh-encoded commitment as hex; the signature is zero until signed.
+    commitment: String,
+}
+
+/// Only the format tag, to tell which kind of file was read.
+#[derive(Deserialize)]
+struct FileHeader {
+    format: String,
+}
+
+/// Write a transaction to a file.
+pub fn write_tx_file(path: &Path, tx: &Transaction) -> Result<(), WalletError> {
+    match tx {
+        Transaction::Knot(knot) => write_knot_file(path, knot),
+        Transaction::Commitment(commitment) => write_commitment_file(path, commitment),
+    }
+}
+
+/// Read a transaction file, or the QR chunks of one, one per line.
+pub fn read_tx_file(path: &Path) -> Result<Transaction, WalletError> {
+    let data = std::fs::read_to_string(path)?;
+    if qr::is_chunk_text(&data) {
+        return Transaction::from_qr_payload(&qr::decode_chunks(&data)?);
+    }
+    let header: FileHeader = serde_json::from_str(&data)?;
+    match header.format.as_str() {
+        KNOT_FILE_FORMAT => parse_knot_file(&data).map(Transaction::Knot),
+        COMMITMENT_FILE_FORMAT => parse_commitment_file(&data).map(Transaction::Commitment),
+        _ => Err(WalletError::SerializationError(format!(
+            "{} is not a transaction file",
+            path.display()
+        ))),
+    }
+}
+
+impl Transaction {
+    /// Encoding carried by QR chunks: the Borsh-encoded transaction.
+    pub fn to_qr_payload(&self) -> Result<Vec<u8>, WalletError> {
+        borsh::to_vec(self).map_err(|e| WalletError::SerializationError(e.to_string()))
+    }
+
+    fn from_qr_payload(bytes: &[u8]) -> Result<Self, WalletError> {
+        let tx: Transaction =
+            borsh::from_slice(bytes).map_err(|e| WalletError::SerializationError(e.to_string()))?;
+        if let Transaction::Knot(knot) = &tx {
+            if norn_thread::knot::compute_knot_id(knot) != knot.id {
+                return Err(WalletError::SerializationError(
+                    "knot ID does not match its knot".to_string(),
+                ));
+            }
+        }
+        Ok(tx)
+    }
+}
+
+/// Write a commitment, signed or not, to a file.
+pub fn write_commitment_file(
+    path: &Path,
+    commitment: &CommitmentUpdate,
+) -> Result<(), WalletError> {
+    let bytes =
+        borsh::to_vec(commitment).map_err(|e| WalletError::SerializationError(e.to_string()))?;
+    let file = CommitmentFile {
+        format: COMMITMENT_FILE_FORMAT.to_string(),
+        version: COMMITMENT_FILE_VERSION,
+        thread_id: hex::encode(commitment.thread_id),
+        commitment: hex::encode(bytes),
+    };
+    std::fs::write(path, serde_json::to_string_pretty(&file)?)?;
+    Ok(())
+}
+
+fn parse_commitment_file(data: &str) -> Result<CommitmentUpdate, WalletError> {
+    let file: CommitmentFile = serde_json::from_str(data)?;
+    if file.version > COMMITMENT_FILE_VERSION {
+        return Err(WalletError::SerializationError(format!(
+            "unsupported commitment file version {}",
+            file.version
+        )));
+    }
+    let bytes = hex::decode(&file.commitment)
+        .map_err(|e| WalletError::SerializationError(e.to_string()))?;
+    let commitment: CommitmentUpdate =
+        borsh::from_slice(&bytes).map_err(|e| WalletError::SerializationError(e.to_string()))?;
+    if hex::encode(commitment.thread_id) != file.thread_id {
+        return Err(WalletError::SerializationError(
+            "commitment file thread ID does not match its commitment".to_string(),
+        ));
+    }
+    Ok(commitment)
+}
+
+/// Write a (partially) signed knot to a file.
+pub fn write_knot_file(path: &Path, knot: &Knot) -> Result<(), WalletError> {
+    let bytes = borsh::to_vec(knot).map_err(|e| WalletError::SerializationError(e.to_string()))?;
+    let file = KnotFile {
+        format: KNOT_FILE_FORMAT.to_string(),
+        version: KNOT_FILE_VERSION,
+        knot_id: hex::encode(knot.id),
+        knot: hex::encode(bytes),
+    };
+    std::fs::write(path, serde_json::to_string_pretty(&file)?)?;
+    Ok(())
+}
+
+/// Read a knot written by `write_knot_file`, or its QR chunks.
+pub fn read_knot_file(path: &Path) -> Result<Knot, WalletError> {
+    match read_tx_file(path)? {
+        Transaction::Knot(knot) => Ok(knot),
+        Transaction::Commitment(_) => Err(WalletError::SerializationError(format!(
+            "{} holds a commitment, not a knot",
+            path.display()
+        ))),
+    }
+}
+
+fn parse_knot_file(data: &str) -> Result<Knot, WalletError> {
+    let file: KnotFile = serde_json::from_str(data)?;
+    if file.version > KNOT_FILE_VERSION {
+        return Err(WalletError::SerializationError(format!(
+            "unsupported knot file version {}",
+            file.version
+        )));
+    }
+    let bytes =
+        hex::decode(&file.knot).map_err(|e| WalletError::SerializationError(e.to_string()))?;
+    let knot: Knot =
+        borsh::from_slice(&bytes).map_err(|e| WalletError::SerializationError(e.to_string()))?;
+    if hex::encode(knot.id) != file.knot_id {
+        return Err(WalletError::SerializationError(
+            "knot file ID does not match its knot".to_string(),
+        ));
+    }
+    Ok(knot)
+}
+
+/// Sign a pending knot with a wallet key: as a participant if the key is
+/// one, otherwise as a cosigner. Returns true if it co-signed.
+pub fn sign_pending(
+    pending: &mut PendingKnot,
+    signer: &mut dyn Signer,
+) -> Result<bool, WalletError> {
+    let pubkey = signer.public_key();
+    let signature = signer.sign(&pending.id())?;
+
+    // Merge a copy carrying only the new signature, which checks it.
+    let mut signed = pending.knot().clone();
+    let mut participant = false;
+    for (slot, state) in signed.signatures.iter_mut().zip(&signed.before_states) {
+        if state.pubkey == pubkey {
+            *slot = signature;
+            participant = true;
+        } else {
+            *slot = UNSIGNED;
+        }
+    }
+    signed.cosignatures = if participant {
+        Vec::new()
+    } else {
+        vec![Cosignature {
+            signer: pubkey,
+            signature,
+        }]
+    };
+    pending.merge(&signed)?;
+    Ok(!participant)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use norn_crypto::address::pubkey_to_address;
+    use norn_crypto::keys::Keypair;
+    use norn_types::knot::{KnotPayload, TransferPayload};
+    use norn_types::primitives::NATIVE_TOKEN_ID;
+    use norn_types::thread::ThreadState;
+
+    fn transfer(owner: &Keypair) -> Knot {
+        let from = pubkey_to_address(&owner.public_key());
+        let state = ThreadState::new();
+        norn_thread::knot::KnotBuilder::transfer(1000)
+            .add_before_state(from, owner.public_key(), 0, &state)
+            .add_after_state(from, owner.public_key(), 1, &state)
+            .with_payload(KnotPayload::Transfer(TransferPayload {
+                token_id: NATIVE_TOKEN_ID,
+                amount: 5,
+                from,
+                to: [9u8; 20],
+                memo: None,
+            }))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_knot_file_signing_round() {
+        let mut owner = Keypair::generate();
+        let mut cosigner = Keypair::generate();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("transfer.knot");
+
+        // A watch-only wallet writes the unsigned knot.
+        let pending = PendingKnot::new(transfer(&owner), 1000).unwrap();
+        write_knot_file(&path, pending.knot()).unwrap();
+
+        // The cold device signs, then the cosigner's device co-signs.
+        let mut pending = PendingKnot::new(read_knot_file(&path).unwrap(), 1000).unwrap();
+        assert!(!sign_pending(&mut pending, &mut owner).unwrap());
+        write_knot_file(&path, pending.knot()).unwrap();
+        let mut pending = PendingKnot::new(read_knot_file(&path).unwrap(), 1000).unwrap();
+        assert!(sign_pending(&mut pending, &mut cosigner).unwrap());
+        write_knot_file(&path, pending.knot()).unwrap();
+
+        let knot = read_knot_file(&path).unwrap();
+        assert_ne!(knot.signatures[0], UNSIGNED);
+        assert_eq!(knot.cosignatures.len(), 1);
+        assert_eq!(knot.cosignatures[0].signer, cosigner.public_key());
+    }
+
+    #[test]
+    fn test_rejects_foreign_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("other.json");
+        std::fs::write(
+            &path,
+            r#"{"format":"psbt","version":1,"knot_id":"","knot":""}"#,
+        )
+        .unwrap();
+        assert!(read_knot_file(&path).is_err());
+    }
+
+    #[test]
+    fn test_offline_commitment_via_qr_chunks() {
+        let owner = Keypair::generate();
+        let dir = tempfile::tempdir().unwrap();
+        let unsigned = dir.path().join("commit.json");
+        let scanned = dir.path().join("scanned.txt");
+
+        // The online machine writes the unsigned commitment.
+        let mut commitment = CommitmentUpdate {
+            thread_id: pubkey_to_address(&owner.public_key()),
+            owner: owner.public_key(),
+            version: 4,
+            state_hash: [1u8; 32],
+            prev_commitment_hash: [2u8; 32],
+            knot_count: 0,
+            timestamp: 1000,
+            priority_fee: 0,
+            signature: [0u8; 64],
+        };
+        write_commitment_file(&unsigned, &commitment).unwrap();
+
+        // The air-gapped machine signs it and shows it as QR chunks.
+        let Transaction::Commitment(read) = read_tx_file(&unsigned).unwrap() else {
+            panic!("expected a commitment");
+        };
+        assert_eq!(read.version, 4);
+        let data = norn_weave::commitment::commitment_signing_data(&read);
+        commitment.signature = owner.sign(&data);
+        let chunks = qr::encode_chunks(
+            &Transaction::Commitment(commitment.clone())
+                .to_qr_payload()
+                .unwrap(),
+        );
+
+        // The online machine reads the scanned chunks back.
+        std::fs::write(&scanned, chunks.join("\n")).unwrap();
+        let Transaction::Commitment(signed) = read_tx_file(&scanned).unwrap() else {
+            panic!("expected a commitment");
+        };
+        assert_eq!(signed.signature, commitment.signature);
+        assert!(read_knot_file(&scanned).is_err());
+    }
+}
@@ -0,0 +1,238 @@
+//! Typed-data documents.
+//!
+//! `sign-typed` and `verify-typed` read the typed message to sign as JSON:
+//!
+//! ```json
+//! {
+//!   "domain": {
+//!     "name": "norn-governance",
+//!     "version": "1",
+//!     "chain_id": "norn-testnet-1",
+//!     "loom": "<contract address or loom ID>"
+//!   },
+//!   "type": "Vote(u64 proposal,bool support)",
+//!   "message": { "proposal": 3, "support": true }
+//! }
+//! ```
+//!
+//! `chain_id` defaults to the wallet's network and `loom` to no loom. A
+//! 64-hex-char `loom` is a loom ID and is turned into its contract address.
+//! Integers may be JSON numbers or decimal strings; `u128` amounts above
+//! 2^53 should be strings. `address` fields take hex or bech32m,
+//! `bytes32` and `bytes` take hex.
+
+use std::path::Path;
+
+use serde_json::{Map, Value};
+
+use norn_types::primitives::{derive_contract_address, Address};
+use norn_types::typed_data::{FieldType, StructType, TypedData, TypedDomain, TypedValue};
+
+use super::config::WalletConfig;
+use super::error::WalletError;
+use super::format::{format_address, parse_address};
+
+/// Read a typed-data document from `path`.
+pub fn load(path: &Path) -> Result<TypedData, WalletError> {
+    let text = std::fs::read_to_string(path)?;
+    let doc: Value = serde_json::from_str(&text)
+        .map_err(|e| WalletError::SerializationError(format!("invalid JSON: {}", e)))?;
+    parse(&doc)
+}
+
+/// Parse a typed-data document.
+pub fn parse(doc: &Value) -> Result<TypedData, WalletError> {
+    let domain = doc
+        .get("domain")
+        .and_then(Value::as_object)
+        .ok_or_else(|| invalid("missing \"domain\" object"))?;
+    let type_string = doc
+        .get("type")
+        .and_then(Value::as_str)
+        .ok_or_else(|| invalid("missing \"type\" string"))?;
+    let message = doc
+        .get("message")
+        .and_then(Value::as_object)
+        .ok_or_else(|| invalid("missing \"message\" object"))?;
+
+    let message_type = StructType::parse(type_string)?;
+    if message.len() != message_type.fields.len() {
+        return Err(invalid(&format!(
+            "message has {} fields, type {} has {}",
+            message.len(),
+            message_type.name,
+            message_type.fields.len()
+        )));
+    }
+    let values = message_type
+        .fields
+        .iter()
+        .map(|(name, ty)| {
+            let value = message
+                .get(name)
+                .ok_or_else(|| invalid(&format!("message is missing field \"{}\"", name)))?;
+            parse_value(*ty, value).map_err(|e| invalid(&format!("field \"{}\": {}", name, e)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(TypedData {
+        domain: parse_domain(domain)?,
+        message_type,
+        values,
+    })
+}
+
+fn parse_domain(domain: &Map<String, Value>) -> Result<TypedDomain, WalletError> {
+    let string = |key: &str| -> Result<Option<String>, WalletError> {
+        match domain.get(key) {
+            None => Ok(None),
+            Some(Value::String(s)) => Ok(Some(s.clone())),
+            Some(_) => Err(invalid(&format!("domain \"{}\" must be a string", key))),
+        }
+    };
+    let name = string("name")?.ok_or_else(|| invalid("domain is missing \"name\""))?;
+    let version = string("version")?.ok_or_else(|| invalid("domain is missing \"version\""))?;
+    let chain_id = string("chain_id")?
+        .unwrap_or_else(|| WalletConfig::address_network().chain_id().to_string());
+    let loom = match string("loom")? {
+        Some(loom) => parse_loom(&loom)?,
+        None => [0u8; 20],
+    };
+    Ok(TypedDomain {
+        name,
+        version,
+        chain_id,
+        loom,
+    })
+}
+
+/// A loom's contract address, given directly or as its loom ID.
+fn parse_loom(s: &str) -> Result<Address, WalletError> {
+    let hex_str = s.strip_prefix("0x").unwrap_or(s);
+    if hex_str.len() == 64 {
+        return Ok(derive_contract_address(&parse_hex_array(hex_str)?));
+    }
+    parse_address(s)
+}
+
+/// A field value as shown to the signer.
+pub fn display_value(value: &TypedValue) -> String {
+    match value {
+        TypedValue::Bool(b) => b.to_string(),
+        TypedValue::U8(n) => n.to_string(),
+        TypedValue::U32(n) => n.to_string(),
+        TypedValue::U64(n) => n.to_string(),
+        TypedValue::U128(n) => n.to_string(),
+        TypedValue::Address(a) => format_address(a),
+        TypedValue::Bytes32(b) => hex::encode(b),
+        TypedValue::Bytes(b) => format!("0x{}", hex::encode(b)),
+        TypedValue::String(s) => format!("{:?}", s),
+    }
+}
+
+fn parse_value(ty: FieldType, value: &Value) -> Result<TypedValue, String> {
+    Ok(match ty {
+        FieldType::Bool => TypedValue::Bool(value.as_bool().ok_or("expected true or false")?),
+        FieldType::U8 => TypedValue::U8(parse_uint(value)?),
+        FieldType::U32 => TypedValue::U32(parse_uint(value)?),
+        FieldType::U64 => TypedValue::U64(parse_uint(value)?),
+        FieldType::U128 => TypedValue::U128(parse_uint(value)?),
+        FieldType::Address => {
+            TypedValue::Address(parse_address(as_str(value)?).map_err(|e| e.to_string())?)
+        }
+        FieldType::Bytes32 => TypedValue::Bytes32(
+            parse_hex_array(strip_0x(as_str(value)?)).map_err(|e| e.to_string())?,
+        ),
+        FieldType::Bytes => TypedValue::Bytes(
+            hex::decode(strip_0x(as_str(value)?)).map_err(|e| format!("invalid hex: {}", e))?,
+        ),
+        FieldType::String => TypedValue::String(as_str(value)?.to_string()),
+    })
+}
+
+fn parse_uint<T: TryFrom<u128>>(value: &Value) -> Result<T, String> {
+    let n: u128 = match value {
+        Value::Number(n) => n
+            .as_u64()
+            .map(u128::from)
+            .ok_or("expected an unsigned integer")?,
+        Value::String(s) => s.parse().map_err(|_| "expected an unsigned integer")?,
+        _ => return Err("expected an unsigned integer".to_string()),
+    };
+    T::try_from(n).map_err(|_| "integer out of range".to_string())
+}
+
+fn as_str(value: &Value) -> Result<&str, String> {
+    value
+        .as_str()
+        .ok_or_else(|| "expected a string".to_string())
+}
+
+fn strip_0x(s: &str) -> &str {
+    s.strip_prefix("0x").unwrap_or(s)
+}
+
+fn parse_hex_array(hex_str: &str) -> Result<[u8; 32], WalletError> {
+    let bytes = hex::decode(hex_str).map_err(|e| invalid(&format!("invalid hex: {}", e)))?;
+    bytes
+        .try_into()
+        .map_err(|_| invalid("expected 32 bytes of hex"))
+}
+
+fn invalid(reason: &str) -> WalletError {
+    WalletError::Other(format!("invalid typed data: {}", reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_vote() {
+        let doc = serde_json::json!({
+            "domain": {
+                "name": "norn-governance",
+                "version": "1",
+                "chain_id": "norn-dev",
+                "loom": format!("0x{}", "ab".repeat(20)),
+            },
+            "type": "Vote(u64 proposal,bool support)",
+            "message": { "support": true, "proposal": "3" },
+        });
+        let data = parse(&doc).unwrap();
+        assert_eq!(data.domain.loom, [0xab; 20]);
+        assert_eq!(
+            data.values,
+            vec![TypedValue::U64(3), TypedValue::Bool(true)]
+        );
+
+        let loom_id = [7u8; 32];
+        let mut by_id = doc.clone();
+        by_id["domain"]["loom"] = Value::String(hex::encode(loom_id));
+        assert_eq!(
+            parse(&by_id).unwrap().domain.loom,
+            derive_contract_address(&loom_id)
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_mismatched_message() {
+        let doc = serde_json::json!({
+            "domain": { "name": "app", "version": "1" },
+            "type": "Transfer(u8 kind,u128 amount)",
+            "message": { "kind": 300, "amount": "1" },
+        });
+        let err = parse(&doc).unwrap_err().to_string();
+        assert!(
+            err.contains("field \"kind\": integer out of range"),
+            "{}",
+            err
+        );
+
+        let mut extra = doc.clone();
+        extra["message"]["kind"] = Value::from(1);
+        extra["message"]["memo"] = Value::from("hi");
+        let err = parse(&extra).unwrap_err().to_string();
+        assert!(err.contains("message has 3 fields"), "{}", err);
+    }
+}
@@ -0,0 +1,409 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use libp2p::PeerId;
+use norn_types::constants::{
+    DEFAULT_PEER_INBOUND_BYTES_PER_SEC, DEFAULT_PEER_INBOUND_MESSAGES_PER_SEC,
+    DEFAULT_PEER_OUTBOUND_BYTES_PER_SEC, MAX_MESSAGE_SIZE,
+};
+
+/// How often traffic rates are recomputed and published.
+pub const BANDWIDTH_STATS_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How often direct messages held back by the outbound limit are retried.
+pub const OUTBOUND_FLUSH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Direct messages held back per peer; beyond this the oldest is dropped.
+pub const MAX_QUEUED_PER_PEER: usize = 256;
+
+/// Seconds of traffic a bucket holds, so short bursts pass unthrottled.
+const BURST_SECS: u64 = 2;
+
+/// Per-peer traffic limits. A limit of 0 disables it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimits {
+    /// Bytes per second accepted from one peer.
+    pub inbound_bytes_per_sec: u64,
+    /// Messages per second accepted from one peer.
+    pub inbound_messages_per_sec: u64,
+    /// Bytes per second of direct messages sent to one peer.
+    pub outbound_bytes_per_sec: u64,
+}
+
+impl Default for RateLimits {
+    fn default() -> Self {
+        Self {
+            inbound_bytes_per_sec: DEFAULT_PEER_INBOUND_BYTES_PER_SEC,
+            inbound_messages_per_sec: DEFAULT_PEER_INBOUND_MESSAGES_PER_SEC,
+            outbound_bytes_per_sec: DEFAULT_PEER_OUTBOUND_BYTES_PER_SEC,
+        }
+    }
+}
+
+/// Token bucket refilled at `rate` tokens per second.
+#[derive(Debug, Clone)]
+struct TokenBucket {
+    rate: u64,
+    capacity: u64,
+    tokens: u64,
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    /// A full bucket. It holds at least `min_capacity` tokens, so the
+    /// largest single message always fits.
+    fn new(rate: u64, min_capacity: u64, now: Instant) -> Self {
+        let capacity = rate.saturating_mul(BURST_SECS).max(min_capacity);
+        Self {
+            rate,
+            capacity,
+            tokens: capacity,
+            refilled_at: now,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed_ms = now.duration_since(self.refilled_at).as_millis() as u64;
+        let added = self.rate.saturating_mul(elapsed_ms) / 1_000;
+        // Keep the remainder for the next refill instead of losing it.
+        if added > 0 {
+            self.tokens = self.tokens.saturating_add(added).min(self.capacity);
+            self.refilled_at = now;
+        }
+    }
+
+    /// Whether `amount` tokens are available without taking them.
+    fn has(&mut self, amount: u64, now: Instant) -> bool {
+        if self.rate == 0 {
+            return true;
+        }
+        self.refill(now);
+        self.tokens >= amount
+    }
+
+    /// Take `amount` tokens, going no lower than empty.
+    fn take(&mut self, amount: u64) {
+        self.tokens = self.tokens.saturating_sub(amount);
+    }
+}
+
+/// Traffic counters and limits for one peer.
+#[derive(Debug, Clone)]
+struct PeerTraffic {
+    bytes_in: u64,
+    bytes_out: u64,
+    messages_in: u64,
+    messages_out: u64,
+    throttled: u64,
+    /// Bytes since the last `stats` call, for rates.
+    window_in: u64,
+    window_out: u64,
+    bytes_in_per_sec: u64,
+    bytes_out_per_sec: u64,
+    inbound_bytes: TokenBucket,
+    inbound_messages: TokenBucket,
+    outbound_bytes: TokenBucket,
+}
+
+impl PeerTraffic {
+    fn new(limits: &RateLimits, now: Instant) -> Self {
+        let max_message = MAX_MESSAGE_SIZE as u64;
+        Self {
+            bytes_in: 0,
+            bytes_out: 0,
+            messages_in: 0,
+            messages_out: 0,
+            throttled: 0,
+            window_in: 0,
+            window_out: 0,
+            bytes_in_per_sec: 0,
+            bytes_out_per_sec: 0,
+            inbound_bytes: TokenBucket::new(limits.inbound_bytes_per_sec, max_message, now),
+            inbound_messages: TokenBucket::new(limits.inbound_messages_per_sec, 1, now),
+            outbound_bytes: TokenBucket::new(limits.outbound_bytes_per_sec, max_message, now),
+        }
+    }
+}
+
+/// Traffic exchanged with one peer, for reporting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeerBandwidth {
+    /// The libp2p peer ID.
+    pub peer_id: PeerId,
+    /// Bytes received from the peer.
+    pub bytes_in: u64,
+    /// Bytes of direct messages sent to the peer.
+    pub bytes_out: u64,
+    /// Messages received from the peer.
+    pub messages_in: u64,
+    /// Direct messages sent to the peer.
+    pub messages_out: u64,
+    /// Messages from the peer dropped for exceeding its inbound limits.
+    pub throttled: u64,
+    /// Receive rate over the last stats interval.
+    pub bytes_in_per_sec: u64,
+    /// Send rate over the last stats interval.
+    pub bytes_out_per_sec: u64,
+}
+
+/// Relay traffic totals and per-peer breakdown, for metrics.
+#[derive(Debug, Clone, Default)]
+pub struct BandwidthStats {
+    /// Connected peers and their traffic.
+    pub peers: Vec<PeerBandwidth>,
+    /// Bytes received since start, including from disconnected peers.
+    pub bytes_in: u64,
+    /// Bytes sent since start: direct messages and gossip publications.
+    pub bytes_out: u64,
+    /// Messages dropped by inbound rate limiting since start.
+    pub throttled: u64,
+    /// Receive rate over the last stats interval.
+    pub bytes_in_per_sec: u64,
+    /// Send rate over the last stats interval.
+    pub bytes_out_per_sec: u64,
+}
+
+/// Counts traffic per peer and enforces `RateLimits`.
+///
+/// Inbound messages over a peer's limits are dropped rather than the peer
+/// being disconnected; only a peer that keeps flooding is penalized, via
+/// `PeerManager::record_throttled`. Outbound direct messages over the limit
+/// are held back by the relay and sent once the peer's budget refills.
+pub struct BandwidthTracker {
+    limits: RateLimits,
+    peers: HashMap<PeerId, PeerTraffic>,
+    bytes_in: u64,
+    bytes_out: u64,
+    throttled: u64,
+    /// Bytes since the last `stats` call, for rates.
+    window_in: u64,
+    window_out: u64,
+    window_started: Instant,
+    bytes_in_per_sec: u64,
+    bytes_out_per_sec: u64,
+}
+
+impl BandwidthTracker {
+    /// Create a tracker enforcing `limits`.
+    pub fn new(limits: RateLimits) -> Self {
+        Self {
+            limits,
+            peers: HashMap::new(),
+            bytes_in: 0,
+            bytes_out: 0,
+            throttled: 0,
+            window_in: 0,
+            window_out: 0,
+            window_started: Instant::now(),
+            bytes_in_per_sec: 0,
+            bytes_out_per_sec: 0,
+        }
+    }
+
+    fn peer(&mut self, peer_id: &PeerId) -> &mut PeerTraffic {
+        let limits = &self.limits;
+        self.peers
+            .entry(*peer_id)
+            .or_insert_with(|| PeerTraffic::new(limits, Instant::now()))
+    }
+
+    /// Count a message of `bytes` received from a peer. Returns false if the
+    /// peer is over its inbound limits and the message should be dropped.
+    pub fn admit_inbound(&mut self, peer_id: &PeerId, bytes: usize) -> bool {
+        let now = Instant::now();
+        let bytes = bytes as u64;
+        self.bytes_in += bytes;
+        self.window_in += bytes;
+        let peer = self.peer(peer_id);
+        peer.bytes_in += bytes;
+        peer.messages_in += 1;
+        peer.window_in += bytes;
+        if !peer.inbound_bytes.has(bytes, now) || !peer.inbound_messages.has(1, now) {
+            peer.throttled += 1;
+            self.throttled += 1;
+            return false;
+        }
+        peer.inbound_bytes.take(bytes);
+        peer.inbound_messages.take(1);
+        true
+    }
+
+    /// Count a message received from a peer without limiting it, e.g. a
+    /// response to our own request.
+    pub fn record_inbound(&mut self, peer_id: &PeerId, bytes: usize) {
+        let bytes = bytes as u64;
+        self.bytes_in += bytes;
+        self.window_in += bytes;
+        let peer = self.peer(peer_id);
+        peer.bytes_in += bytes;
+        peer.messages_in += 1;
+        peer.window_in += bytes;
+    }
+
+    /// Count a direct message of `bytes` to a peer if its outbound budget
+    /// allows it. Returns false if the message must wait.
+    pub fn admit_outbound(&mut self, peer_id: &PeerId, bytes: usize) -> bool {
+        let now = Instant::now();
+        if !self.peer(peer_id).outbound_bytes.has(bytes as u64, now) {
+            return false;
+        }
+        self.record_outbound(peer_id, bytes);
+        true
+    }
+
+    /// Count a message sent to a peer that cannot wait, e.g. a response.
+    /// It still uses up the peer's outbound budget.
+    pub fn record_outbound(&mut self, peer_id: &PeerId, bytes: usize) {
+        let bytes = bytes as u64;
+        self.bytes_out += bytes;
+        self.window_out += bytes;
+        let peer = self.peer(peer_id);
+        peer.bytes_out += bytes;
+        peer.messages_out += 1;
+        peer.window_out += bytes;
+        peer.outbound_bytes.take(bytes);
+    }
+
+    /// Count a gossip publication. Gossip fans out to the mesh, so it only
+    /// adds to the totals.
+    pub fn record_gossip_out(&mut self, bytes: usize) {
+        self.bytes_out += bytes as u64;
+        self.window_out += bytes as u64;
+    }
+
+    /// Forget a disconnected peer. Totals keep its traffic.
+    pub fn remove_peer(&mut self, peer_id: &PeerId) {
+        self.peers.remove(peer_id);
+    }
+
+    /// Recompute rates over the time since the last call, and return a
+    /// snapshot of all counters.
+    pub fn stats(&mut self) -> BandwidthStats {
+        let elapsed_ms = (self.window_started.elapsed().as_millis() as u64).max(1);
+        let rate = |bytes: u64| bytes.saturating_mul(1_000) / elapsed_ms;
+        self.bytes_in_per_sec = rate(self.window_in);
+        self.bytes_out_per_sec = rate(self.window_out);
+        self.window_in = 0;
+        self.window_out = 0;
+        self.window_started = Instant::now();
+
+        let mut peers: Vec<_> = self
+            .peers
+            .iter_mut()
+            .map(|(peer_id, traffic)| {
+                traffic.bytes_in_per_sec = rate(traffic.window_in);
+                traffic.bytes_out_per_sec = rate(traffic.window_out);
+                traffic.window_in = 0;
+                traffic.window_out = 0;
+                PeerBandwidth {
+                    peer_id: *peer_id,
+                    bytes_in: traffic.bytes_in,
+                    bytes_out: traffic.bytes_out,
+                    messages_in: traffic.messages_in,
+                    messages_out: traffic.messages_out,
+                    throttled: traffic.throttled,
+                    bytes_in_per_sec: traffic.bytes_in_per_sec,
+                    bytes_out_per_sec: traffic.bytes_out_per_sec,
+                }
+            })
+            .collect();
+        peers.sort_by_key(|p| std::cmp::Reverse(p.bytes_in));
+
+        BandwidthStats {
+            peers,
+            bytes_in: self.bytes_in,
+            bytes_out: self.bytes_out,
+            throttled: self.throttled,
+            bytes_in_per_sec: self.bytes_in_per_sec,
+            bytes_out_per_sec: self.bytes_out_per_sec,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(bytes: u64, messages: u64) -> RateLimits {
+        RateLimits {
+            inbound_bytes_per_sec: bytes,
+            inbound_messages_per_sec: messages,
+            outbound_bytes_per_sec: bytes,
+        }
+    }
+
+    #[test]
+    fn test_bucket_refills_over_time() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(100, 1, start);
+        assert_eq!(bucket.capacity, 100 * BURST_SECS);
+        bucket.take(bucket.capacity);
+        assert!(!bucket.has(1, start));
+        assert!(bucket.has(50, start + Duration::from_millis(500)));
+        assert!(!bucket.has(51, start + Duration::from_millis(500)));
+        // Never more than the capacity.
+        assert!(!bucket.has(bucket.capacity + 1, start + Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn test_chatty_peer_throttled_not_others() {
+        let mut tracker = BandwidthTracker::new(limits(0, 10));
+        let chatty = PeerId::random();
+        let quiet = PeerId::random();
+
+        let admitted = (0..100)
+            .filter(|_| tracker.admit_inbound(&chatty, 100))
+            .count();
+        assert_eq!(admitted as u64, 10 * BURST_SECS);
+        assert!(tracker.admit_inbound(&quiet, 100));
+
+        let stats = tracker.stats();
+        assert_eq!(stats.throttled, 100 - admitted as u64);
+        assert_eq!(stats.bytes_in, 101 * 100, "throttled bytes still count");
+        assert_eq!(stats.peers[0].peer_id, chatty);
+        assert_eq!(stats.peers[0].messages_in, 100);
+    }
+
+    #[test]
+    fn test_large_message_always_fits() {
+        let mut tracker = BandwidthTracker::new(limits(1_000, 0));
+        let peer = PeerId::random();
+        assert!(tracker.admit_inbound(&peer, MAX_MESSAGE_SIZE));
+        assert!(!tracker.admit_inbound(&peer, 1_000));
+    }
+
+    #[test]
+    fn test_outbound_waits_for_budget() {
+        let mut tracker = BandwidthTracker::new(limits(1_000, 0));
+        let peer = PeerId::random();
+        assert!(tracker.admit_outbound(&peer, MAX_MESSAGE_SIZE));
+        assert!(!tracker.admit_outbound(&peer, 1_000));
+
+        // Responses go out regardless but still count.
+        tracker.record_outbound(&peer, 10);
+        let stats = tracker.stats();
+        assert_eq!(stats.bytes_out, MAX_MESSAGE_SIZE as u64 + 10);
+        assert_eq!(stats.peers[0].messages_out, 2);
+    }
+
+    #[test]
+    fn test_unlimited() {
+        let mut tracker = BandwidthTracker::new(limits(0, 0));
+        let peer = PeerId::random();
+        assert!((0..10_000).all(|_| tracker.admit_inbound(&peer, MAX_MESSAGE_SIZE)));
+        assert!(tracker.admit_outbound(&peer, MAX_MESSAGE_SIZE));
+    }
+
+    #[test]
+    fn test_totals_outlive_peers() {
+        let mut tracker = BandwidthTracker::new(RateLimits::default());
+        let peer = PeerId::random();
+        tracker.record_inbound(&peer, 500);
+        tracker.record_gossip_out(300);
+        tracker.remove_peer(&peer);
+        let stats = tracker.stats();
+        assert!(stats.peers.is_empty());
+        assert_eq!(stats.bytes_in, 500);
+        assert_eq!(stats.bytes_out, 300);
+    }
+}
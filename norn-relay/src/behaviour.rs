@@ -1,12 +1,15 @@
 use libp2p::gossipsub;
 use libp2p::identity::Keypair;
 use libp2p::request_response;
+use libp2p::swarm::behaviour::toggle::Toggle;
 use libp2p::swarm::NetworkBehaviour;
-use libp2p::StreamProtocol;
+use libp2p::{autonat, dcutr, relay, StreamProtocol};
 use std::time::Duration;
 
 use crate::codec::NornCodec;
-use crate::protocol::DIRECT_PROTOCOL;
+use crate::config::NatConfig;
+use crate::handshake::HandshakeCodec;
+use crate::protocol::{DIRECT_PROTOCOL, HANDSHAKE_PROTOCOL};
 
 /// Combined network behaviour for the Norn relay.
 #[derive(NetworkBehaviour)]
@@ -15,10 +18,20 @@ pub struct NornBehaviour {
     pub gossipsub: gossipsub::Behaviour,
     /// Request-response for direct messaging.
     pub request_response: request_response::Behaviour<NornCodec>,
+    /// Request-response for the connection handshake.
+    pub handshake: request_response::Behaviour<HandshakeCodec>,
     /// Identify protocol for peer identification.
     pub identify: libp2p::identify::Behaviour,
     /// mDNS for automatic local network peer discovery.
     pub mdns: libp2p::mdns::tokio::Behaviour,
+    /// AutoNAT for learning whether this node is publicly reachable.
+    pub autonat: Toggle<autonat::Behaviour>,
+    /// Circuit relay v2 client, for listening and dialing through relays.
+    pub relay_client: relay::client::Behaviour,
+    /// Circuit relay v2 server, relaying for peers behind NAT.
+    pub relay_server: Toggle<relay::Behaviour>,
+    /// DCUtR hole punching, upgrading relayed connections to direct ones.
+    pub dcutr: Toggle<dcutr::Behaviour>,
 }
 
 /// Build a NornBehaviour from a keypair.
@@ -28,10 +41,16 @@ pub struct NornBehaviour {
 ///
 /// The `protocol_version` is advertised via the identify protocol's agent version
 /// string as `"norn/{version}"`, allowing peers to detect version mismatches.
+/// The `relay_client` comes from `SwarmBuilder::with_relay_client`; `nat`
+/// selects which NAT traversal behaviours are enabled.
 pub fn build_behaviour(
     keypair: &Keypair,
     protocol_version: u8,
+    relay_client: relay::client::Behaviour,
+    nat: &NatConfig,
 ) -> Result<NornBehaviour, Box<dyn std::error::Error + Send + Sync>> {
+    let local_peer_id = keypair.public().to_peer_id();
+
     // --- Gossipsub ---
     let message_id_fn = |message: &gossipsub::Message| {
         // Deduplicate based on content hash.
@@ -62,6 +81,15 @@ pub fn build_behaviour(
         request_response::Config::default(),
     );
 
+    // --- Handshake ---
+    let handshake_protocol = StreamProtocol::try_from_owned(HANDSHAKE_PROTOCOL.to_string())
+        .map_err(|e| format!("invalid protocol: {:?}", e))?;
+    let handshake = request_response::Behaviour::with_codec(
+        HandshakeCodec,
+        [(handshake_protocol, request_response::ProtocolSupport::Full)],
+        request_response::Config::default(),
+    );
+
     // --- Identify ---
     let identify_config =
         libp2p::identify::Config::new("/norn/1.0.0".to_string(), keypair.public())
@@ -69,15 +97,28 @@ pub fn build_behaviour(
     let identify = libp2p::identify::Behaviour::new(identify_config);
 
     // --- mDNS ---
-    let mdns = libp2p::mdns::tokio::Behaviour::new(
-        libp2p::mdns::Config::default(),
-        keypair.public().to_peer_id(),
-    )?;
+    let mdns = libp2p::mdns::tokio::Behaviour::new(libp2p::mdns::Config::default(), local_peer_id)?;
+
+    // --- NAT traversal ---
+    let autonat = nat
+        .autonat
+        .then(|| autonat::Behaviour::new(local_peer_id, autonat::Config::default()));
+    let relay_server = nat
+        .relay_server
+        .then(|| relay::Behaviour::new(local_peer_id, relay::Config::default()));
+    let dcutr = nat
+        .hole_punching
+        .then(|| dcutr::Behaviour::new(local_peer_id));
 
     Ok(NornBehaviour {
         gossipsub,
         request_response,
+        handshake,
         identify,
         mdns,
+        autonat: Toggle::from(autonat),
+        relay_client,
+        relay_server: Toggle::from(relay_server),
+        dcutr: Toggle::from(dcutr),
     })
 }
@@ -0,0 +1,128 @@
+use std::collections::{HashMap, VecDeque};
+
+use norn_thread::chain::verify_checkpoint;
+use norn_types::primitives::{PublicKey, ThreadId};
+use norn_types::thread::ThreadCheckpoint;
+
+use crate::error::RelayError;
+
+/// Maximum checkpoints kept to answer `GetCheckpoint` requests.
+const MAX_STORED_CHECKPOINTS: usize = 10_000;
+
+/// The latest thread checkpoints seen gossiped, kept so peers can fetch a
+/// recent checkpoint with `GetCheckpoint` instead of a thread's full knot
+/// history.
+///
+/// Checkpoints are keyed by thread and signing key: the relay cannot tell
+/// which key controls a thread, so a requester names the owner it trusts.
+pub struct CheckpointStore {
+    checkpoints: HashMap<(ThreadId, PublicKey), ThreadCheckpoint>,
+    order: VecDeque<(ThreadId, PublicKey)>,
+}
+
+impl CheckpointStore {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self {
+            checkpoints: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    /// Record a checkpoint. Returns `Ok(false)` if a checkpoint at the same
+    /// or a later version is already stored for its thread and owner, and an
+    /// error if its signature is invalid.
+    pub fn insert(&mut self, checkpoint: &ThreadCheckpoint) -> Result<bool, RelayError> {
+        let key = (checkpoint.thread_id, checkpoint.owner);
+        if self
+            .checkpoints
+            .get(&key)
+            .is_some_and(|c| c.version >= checkpoint.version)
+        {
+            return Ok(false);
+        }
+        verify_checkpoint(checkpoint).map_err(|e| RelayError::InvalidCheckpoint {
+            reason: e.to_string(),
+        })?;
+
+        if self.checkpoints.insert(key, checkpoint.clone()).is_none() {
+            self.order.push_back(key);
+            while self.order.len() > MAX_STORED_CHECKPOINTS {
+                if let Some(oldest) = self.order.pop_front() {
+                    self.checkpoints.remove(&oldest);
+                }
+            }
+        }
+        Ok(true)
+    }
+
+    /// The latest checkpoint `owner` signed for a thread.
+    pub fn get(&self, thread_id: &ThreadId, owner: &PublicKey) -> Option<&ThreadCheckpoint> {
+        self.checkpoints.get(&(*thread_id, *owner))
+    }
+
+    /// Number of stored checkpoints.
+    pub fn len(&self) -> usize {
+        self.checkpoints.len()
+    }
+
+    /// Whether the store is empty.
+    pub fn is_empty(&self) -> bool {
+        self.checkpoints.is_empty()
+    }
+}
+
+impl Default for CheckpointStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use norn_crypto::address::pubkey_to_address;
+    use norn_crypto::keys::Keypair;
+    use norn_thread::chain::create_checkpoint;
+    use norn_types::thread::ThreadState;
+
+    fn checkpoint(kp: &Keypair, version: u64) -> ThreadCheckpoint {
+        let thread_id = pubkey_to_address(&kp.public_key());
+        create_checkpoint(
+            thread_id,
+            &ThreadState::new(),
+            version,
+            [0u8; 32],
+            None,
+            1000,
+            kp,
+        )
+    }
+
+    #[test]
+    fn test_keeps_latest_checkpoint() {
+        let kp = Keypair::generate();
+        let thread_id = pubkey_to_address(&kp.public_key());
+        let mut store = CheckpointStore::new();
+
+        assert!(store.insert(&checkpoint(&kp, 1_000)).unwrap());
+        assert!(store.insert(&checkpoint(&kp, 2_000)).unwrap());
+        assert!(!store.insert(&checkpoint(&kp, 1_500)).unwrap());
+        assert_eq!(store.len(), 1);
+        assert_eq!(
+            store.get(&thread_id, &kp.public_key()).unwrap().version,
+            2_000
+        );
+        assert!(store.get(&thread_id, &[9u8; 32]).is_none());
+    }
+
+    #[test]
+    fn test_rejects_forged_checkpoint() {
+        let kp = Keypair::generate();
+        let mut store = CheckpointStore::new();
+        let mut forged = checkpoint(&kp, 1_000);
+        forged.version = 5_000;
+        assert!(store.insert(&forged).is_err());
+        assert!(store.is_empty());
+    }
+}
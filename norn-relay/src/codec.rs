@@ -413,6 +413,8 @@ mod tests {
     fn test_legacy_encode_rejects_new_variants() {
         let msg = NornMessage::StakeOperation(norn_types::weave::StakeOperation::Stake {
             pubkey: [0u8; 32],
+            bls_pubkey: [0u8; 48],
+            bls_pop: [0u8; 96],
             amount: 1000,
             timestamp: 1000,
             signature: [0u8; 64],
@@ -421,6 +423,19 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_full_snapshot_chunk_fits_in_one_message() {
+        let msg = NornMessage::SnapshotChunk {
+            chunk_hash: [7u8; 32],
+            data: vec![0xAB; norn_types::constants::SNAPSHOT_CHUNK_SIZE],
+        };
+        let encoded = encode_message(&msg).expect("encode failed");
+        match decode_message(&encoded).expect("decode failed") {
+            DecodedMessage::Known(m) => assert_eq!(msg, *m),
+            DecodedMessage::Unknown { .. } => panic!("expected Known"),
+        }
+    }
+
     #[test]
     fn test_envelope_unknown_message_type() {
         // Manually construct an envelope with an unknown message type.
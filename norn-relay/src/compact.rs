@@ -0,0 +1,452 @@
+use std::collections::{HashMap, VecDeque};
+
+use libp2p::PeerId;
+use norn_types::network::CompactBlock;
+use norn_types::primitives::Hash;
+use norn_types::weave::{CommitmentUpdate, WeaveBlock};
+
+use crate::error::RelayError;
+
+/// Maximum commitments kept for rebuilding compact blocks.
+const MAX_POOLED_COMMITMENTS: usize = 50_000;
+
+/// Number of recent full blocks kept to answer commitment requests.
+const MAX_RECENT_BLOCKS: usize = 64;
+
+/// Maximum compact blocks waiting on missing commitments.
+const MAX_PENDING_BLOCKS: usize = 16;
+
+/// Short ID of a commitment within a block: the first 8 bytes of the
+/// commitment's hash, keyed with the block hash so that IDs which collide
+/// in one block do not collide in the next.
+pub fn short_id(block_hash: &Hash, commitment: &CommitmentUpdate) -> u64 {
+    let keyed = blake3::keyed_hash(block_hash, &commitment_hash(commitment));
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&keyed.as_bytes()[..8]);
+    u64::from_le_bytes(bytes)
+}
+
+fn commitment_hash(commitment: &CommitmentUpdate) -> Hash {
+    let data = borsh::to_vec(commitment).unwrap_or_default();
+    *blake3::hash(&data).as_bytes()
+}
+
+/// Outcome of rebuilding a compact block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reconstruction {
+    /// Every commitment was found; the block is complete.
+    Complete(Box<WeaveBlock>),
+    /// The block is waiting on the commitments at these positions.
+    Missing(Vec<u32>),
+    /// The block is already known.
+    Duplicate,
+}
+
+/// A compact block waiting on commitments.
+struct PendingBlock {
+    header: WeaveBlock,
+    short_ids: Vec<u64>,
+    commitments: Vec<Option<CommitmentUpdate>>,
+    /// Peer to ask if the first peer asked cannot serve the commitments.
+    fallback: Option<PeerId>,
+}
+
+impl PendingBlock {
+    fn missing(&self) -> Vec<u32> {
+        self.commitments
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| c.is_none())
+            .map(|(i, _)| i as u32)
+            .collect()
+    }
+}
+
+/// State for compact block relay.
+///
+/// Blocks are gossiped as a header plus one short ID per commitment. The
+/// pool remembers the commitments this node has seen gossiped, so most of a
+/// compact block can be rebuilt locally; the rest are fetched from a peer
+/// with `GetBlockCommitments`. Recent full blocks are kept to answer those
+/// requests from other peers.
+pub struct CompactBlockPool {
+    commitments: HashMap<Hash, CommitmentUpdate>,
+    commitment_order: VecDeque<Hash>,
+    blocks: HashMap<Hash, WeaveBlock>,
+    block_order: VecDeque<Hash>,
+    pending: HashMap<Hash, PendingBlock>,
+    pending_order: VecDeque<Hash>,
+}
+
+impl CompactBlockPool {
+    /// Create an empty pool.
+    pub fn new() -> Self {
+        Self {
+            commitments: HashMap::new(),
+            commitment_order: VecDeque::new(),
+            blocks: HashMap::new(),
+            block_order: VecDeque::new(),
+            pending: HashMap::new(),
+            pending_order: VecDeque::new(),
+        }
+    }
+
+    /// Record a gossiped commitment so later blocks including it can be
+    /// rebuilt without fetching it.
+    pub fn observe_commitment(&mut self, commitment: &CommitmentUpdate) {
+        let hash = commitment_hash(commitment);
+        if self.commitments.insert(hash, commitment.clone()).is_some() {
+            return;
+        }
+        self.commitment_order.push_back(hash);
+        while self.commitment_order.len() > MAX_POOLED_COMMITMENTS {
+            if let Some(oldest) = self.commitment_order.pop_front() {
+                self.commitments.remove(&oldest);
+            }
+        }
+    }
+
+    /// Record a full block, so its commitments can be served to peers.
+    /// Its commitments leave the pool, since no later block will include them.
+    pub fn remember_block(&mut self, block: &WeaveBlock) {
+        let pooled = self.commitments.len();
+        for commitment in &block.commitments {
+            self.commitments.remove(&commitment_hash(commitment));
+        }
+        if self.commitments.len() < pooled {
+            let commitments = &self.commitments;
+            self.commitment_order
+                .retain(|hash| commitments.contains_key(hash));
+        }
+        if self.blocks.insert(block.hash, block.clone()).is_some() {
+            return;
+        }
+        self.block_order.push_back(block.hash);
+        while self.block_order.len() > MAX_RECENT_BLOCKS {
+            if let Some(oldest) = self.block_order.pop_front() {
+                self.blocks.remove(&oldest);
+            }
+        }
+    }
+
+    /// Whether a full block is known.
+    pub fn knows_block(&self, block_hash: &Hash) -> bool {
+        self.blocks.contains_key(block_hash)
+    }
+
+    /// Number of compact blocks waiting on commitments.
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Build the compact form of a block and remember the block.
+    pub fn compact(&mut self, block: &WeaveBlock) -> CompactBlock {
+        self.remember_block(block);
+        let short_ids = block
+            .commitments
+            .iter()
+            .map(|c| short_id(&block.hash, c))
+            .collect();
+        let mut header = block.clone();
+        header.commitments.clear();
+        CompactBlock { header, short_ids }
+    }
+
+    /// Rebuild a compact block from pooled commitments. If any are missing
+    /// the block is held until `fill` supplies them; `fallback` is the peer
+    /// to ask if the first one cannot.
+    pub fn receive(
+        &mut self,
+        compact: CompactBlock,
+        fallback: Option<PeerId>,
+    ) -> Result<Reconstruction, RelayError> {
+        let block_hash = compact.header.hash;
+        if self.knows_block(&block_hash) || self.pending.contains_key(&block_hash) {
+            return Ok(Reconstruction::Duplicate);
+        }
+        if !compact.header.commitments.is_empty() {
+            return Err(RelayError::ProtocolError {
+                reason: "compact block header carries commitments".to_string(),
+            });
+        }
+
+        // Index the pool by this block's short IDs. An ID shared by two
+        // pooled commitments is ambiguous and treated as missing.
+        let mut by_short_id: HashMap<u64, Option<&Hash>> = HashMap::new();
+        for hash in &self.commitment_order {
+            let id = short_id(&block_hash, &self.commitments[hash]);
+            by_short_id
+                .entry(id)
+                .and_modify(|found| *found = None)
+                .or_insert(Some(hash));
+        }
+        let commitments: Vec<Option<CommitmentUpdate>> = compact
+            .short_ids
+            .iter()
+            .map(|id| {
+                by_short_id
+                    .get(id)
+                    .copied()
+                    .flatten()
+                    .map(|hash| self.commitments[hash].clone())
+            })
+            .collect();
+
+        let pending = PendingBlock {
+            header: compact.header,
+            short_ids: compact.short_ids,
+            commitments,
+            fallback,
+        };
+        let missing = pending.missing();
+        if missing.is_empty() {
+            return Ok(Reconstruction::Complete(Box::new(self.complete(pending))));
+        }
+
+        self.pending.insert(block_hash, pending);
+        self.pending_order.push_back(block_hash);
+        while self.pending_order.len() > MAX_PENDING_BLOCKS {
+            if let Some(oldest) = self.pending_order.pop_front() {
+                self.pending.remove(&oldest);
+            }
+        }
+        Ok(Reconstruction::Missing(missing))
+    }
+
+    /// Supply commitments fetched for a pending block, in the order they
+    /// were requested. Commitments whose short ID does not match the slot
+    /// they were requested for are ignored. Returns `None` if the block is
+    /// not pending.
+    pub fn fill(
+        &mut self,
+        block_hash: &Hash,
+        commitments: Vec<CommitmentUpdate>,
+    ) -> Option<Reconstruction> {
+        let pending = self.pending.get_mut(block_hash)?;
+        let missing = pending.missing();
+        for (index, commitment) in missing.into_iter().zip(commitments) {
+            let slot = index as usize;
+            if short_id(block_hash, &commitment) == pending.short_ids[slot] {
+                pending.commitments[slot] = Some(commitment);
+            }
+        }
+
+        let missing = pending.missing();
+        if !missing.is_empty() {
+            return Some(Reconstruction::Missing(missing));
+        }
+        let pending = self.pending.remove(block_hash)?;
+        self.pending_order.retain(|hash| hash != block_hash);
+        Some(Reconstruction::Complete(Box::new(self.complete(pending))))
+    }
+
+    /// Take the fallback peer of a pending block, so it is asked at most once.
+    pub fn take_fallback(&mut self, block_hash: &Hash) -> Option<PeerId> {
+        self.pending.get_mut(block_hash)?.fallback.take()
+    }
+
+    /// The commitments of a known block at the given positions. Empty if the
+    /// block is unknown or any position is out of range.
+    pub fn serve(&self, block_hash: &Hash, indexes: &[u32]) -> Vec<CommitmentUpdate> {
+        let Some(block) = self.blocks.get(block_hash) else {
+            return Vec::new();
+        };
+        indexes
+            .iter()
+            .map(|&i| block.commitments.get(i as usize).cloned())
+            .collect::<Option<Vec<_>>>()
+            .unwrap_or_default()
+    }
+
+    fn complete(&mut self, pending: PendingBlock) -> WeaveBlock {
+        let mut block = pending.header;
+        block.commitments = pending.commitments.into_iter().flatten().collect();
+        self.remember_block(&block);
+        block
+    }
+}
+
+impl Default for CompactBlockPool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commitment(n: u8) -> CommitmentUpdate {
+        CommitmentUpdate {
+            thread_id: [n; 20],
+            owner: [n; 32],
+            version: n as u64,
+            state_hash: [n; 32],
+            prev_commitment_hash: [0u8; 32],
+            knot_count: 1,
+            timestamp: 1000,
+            priority_fee: 0,
+            signature: [n; 64],
+        }
+    }
+
+    fn block(commitments: Vec<CommitmentUpdate>) -> WeaveBlock {
+        WeaveBlock {
+            height: 1,
+            hash: [7u8; 32],
+            prev_hash: [0u8; 32],
+            commitments_root: [0u8; 32],
+            registrations_root: [0u8; 32],
+            anchors_root: [0u8; 32],
+            commitments,
+            registrations: vec![],
+            anchors: vec![],
+            name_registrations: vec![],
+            name_registrations_root: [0u8; 32],
+            name_transfers: vec![],
+            name_transfers_root: [0u8; 32],
+            name_record_updates: vec![],
+            name_record_updates_root: [0u8; 32],
+            fraud_proofs: vec![],
+            fraud_proofs_root: [0u8; 32],
+            transfers: vec![],
+            transfers_root: [0u8; 32],
+            token_definitions: vec![],
+            token_definitions_root: [0u8; 32],
+            token_mints: vec![],
+            token_mints_root: [0u8; 32],
+            token_burns: vec![],
+            token_metadata_updates: vec![],
+            token_burns_root: [0u8; 32],
+            token_metadata_updates_root: [0u8; 32],
+            loom_deploys: vec![],
+            loom_deploys_root: [0u8; 32],
+            operator_handovers: vec![],
+            operator_handovers_root: [0u8; 32],
+            key_updates: vec![],
+            key_updates_root: [0u8; 32],
+            stake_operations: vec![],
+            stake_operations_root: [0u8; 32],
+            slashing_evidence: vec![],
+            slashing_evidence_root: [0u8; 32],
+            spindle_operations: vec![],
+            spindle_operations_root: [0u8; 32],
+            base_fee: 0,
+            state_root: [0u8; 32],
+            validator_set_hash: [0u8; 32],
+            timestamp: 1000,
+            proposer: [0u8; 32],
+            proposer_signature: [0u8; 64],
+            certificate: None,
+        }
+    }
+
+    #[test]
+    fn test_short_id_depends_on_block() {
+        let c = commitment(1);
+        assert_eq!(short_id(&[1u8; 32], &c), short_id(&[1u8; 32], &c));
+        assert_ne!(short_id(&[1u8; 32], &c), short_id(&[2u8; 32], &c));
+    }
+
+    #[test]
+    fn test_rebuild_from_pool() {
+        let full = block(vec![commitment(1), commitment(2), commitment(3)]);
+        let compact = CompactBlockPool::new().compact(&full);
+        assert!(compact.header.commitments.is_empty());
+        assert_eq!(compact.short_ids.len(), 3);
+
+        let mut peer = CompactBlockPool::new();
+        for n in [3, 1, 2, 9] {
+            peer.observe_commitment(&commitment(n));
+        }
+        let result = peer.receive(compact.clone(), None).unwrap();
+        assert_eq!(result, Reconstruction::Complete(Box::new(full.clone())));
+        assert!(peer.knows_block(&full.hash));
+        assert_eq!(
+            peer.receive(compact, None).unwrap(),
+            Reconstruction::Duplicate
+        );
+    }
+
+    #[test]
+    fn test_fetch_missing_commitments() {
+        let full = block(vec![commitment(1), commitment(2), commitment(3)]);
+        let mut proposer = CompactBlockPool::new();
+        let compact = proposer.compact(&full);
+
+        let mut peer = CompactBlockPool::new();
+        peer.observe_commitment(&commitment(2));
+        let missing = match peer.receive(compact, None).unwrap() {
+            Reconstruction::Missing(missing) => missing,
+            other => panic!("expected missing commitments, got {:?}", other),
+        };
+        assert_eq!(missing, vec![0, 2]);
+        assert_eq!(peer.pending_count(), 1);
+
+        let served = proposer.serve(&full.hash, &missing);
+        assert_eq!(served, vec![commitment(1), commitment(3)]);
+        assert_eq!(
+            peer.fill(&full.hash, served),
+            Some(Reconstruction::Complete(Box::new(full.clone())))
+        );
+        assert_eq!(peer.pending_count(), 0);
+
+        // The rebuilt block can now be served onward.
+        assert_eq!(peer.serve(&full.hash, &[1]), vec![commitment(2)]);
+    }
+
+    #[test]
+    fn test_fill_rejects_wrong_commitments() {
+        let full = block(vec![commitment(1), commitment(2)]);
+        let compact = CompactBlockPool::new().compact(&full);
+        let mut peer = CompactBlockPool::new();
+        let fallback = PeerId::random();
+        assert!(matches!(
+            peer.receive(compact, Some(fallback)).unwrap(),
+            Reconstruction::Missing(_)
+        ));
+
+        // A peer answering with the wrong commitments does not complete it.
+        assert_eq!(
+            peer.fill(&full.hash, vec![commitment(2), commitment(1)]),
+            Some(Reconstruction::Missing(vec![0, 1]))
+        );
+        assert_eq!(peer.take_fallback(&full.hash), Some(fallback));
+        assert_eq!(peer.take_fallback(&full.hash), None);
+        assert_eq!(peer.fill(&[9u8; 32], vec![]), None);
+    }
+
+    #[test]
+    fn test_serve_unknown_or_out_of_range() {
+        let full = block(vec![commitment(1)]);
+        let mut pool = CompactBlockPool::new();
+        pool.remember_block(&full);
+        assert!(pool.serve(&[9u8; 32], &[0]).is_empty());
+        assert!(pool.serve(&full.hash, &[0, 1]).is_empty());
+    }
+
+    #[test]
+    fn test_header_with_commitments_rejected() {
+        let full = block(vec![commitment(1)]);
+        let compact = CompactBlock {
+            header: full,
+            short_ids: vec![],
+        };
+        assert!(CompactBlockPool::new().receive(compact, None).is_err());
+    }
+
+    #[test]
+    fn test_included_commitments_leave_pool() {
+        let mut pool = CompactBlockPool::new();
+        pool.observe_commitment(&commitment(1));
+        pool.remember_block(&block(vec![commitment(1)]));
+
+        let mut next = block(vec![commitment(1)]);
+        next.hash = [8u8; 32];
+        let compact = CompactBlockPool::new().compact(&next);
+        assert_eq!(
+            pool.receive(compact, None).unwrap(),
+            Reconstruction::Missing(vec![0])
+        );
+    }
+}
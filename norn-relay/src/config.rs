@@ -1,6 +1,11 @@
 use std::net::SocketAddr;
+use std::path::PathBuf;
 
 use norn_types::constants::{DEFAULT_RELAY_PORT, MAX_RELAY_CONNECTIONS};
+use norn_types::primitives::Hash;
+
+use crate::bandwidth::RateLimits;
+use crate::handshake::Capabilities;
 
 /// Configuration for a relay node.
 #[derive(Debug, Clone)]
@@ -9,10 +14,62 @@ pub struct RelayConfig {
     pub listen_addr: SocketAddr,
     /// Bootstrap node addresses (multiaddr strings).
     pub boot_nodes: Vec<String>,
+    /// DNS seeds (`host` or `host:port`) whose records list peers to
+    /// bootstrap from.
+    pub dns_seeds: Vec<String>,
+    /// Network this node belongs to ("dev", "testnet" or "mainnet"). Keeps
+    /// the peer store of each network apart.
+    pub network_id: String,
+    /// Genesis hash sent in the handshake; all zeros if unknown. Peers with
+    /// another genesis are rejected.
+    pub genesis_hash: Hash,
+    /// Services this node offers, sent in the handshake. Compact block relay
+    /// and, with `nat.relay_server`, circuit relay are added by the relay.
+    pub capabilities: Capabilities,
     /// Maximum number of connections.
     pub max_connections: usize,
     /// Optional keypair seed (32 bytes). If None, generates random.
     pub keypair_seed: Option<[u8; 32]>,
+    /// Directory for relay state that outlives restarts, such as the peer
+    /// greylist. If None, nothing is persisted.
+    pub data_dir: Option<PathBuf>,
+    /// NAT traversal settings.
+    pub nat: NatConfig,
+    /// Per-peer traffic limits.
+    pub rate_limits: RateLimits,
+}
+
+/// NAT traversal settings.
+///
+/// A node behind NAT learns that it is not publicly reachable through
+/// AutoNAT, then listens through circuit relays (relay v2) so peers can
+/// reach it. Connections made through a relay are upgraded to direct ones
+/// by hole punching (DCUtR) where the NATs allow it.
+#[derive(Debug, Clone)]
+pub struct NatConfig {
+    /// Probe whether this node is publicly reachable, and answer probes
+    /// from peers.
+    pub autonat: bool,
+    /// Serve as a circuit relay for peers behind NAT. Only useful on
+    /// publicly reachable nodes.
+    pub relay_server: bool,
+    /// Relays to listen through when AutoNAT finds this node unreachable,
+    /// as multiaddrs ending in `/p2p/<peer id>`. If empty, boot nodes whose
+    /// address includes a peer ID are used.
+    pub relays: Vec<String>,
+    /// Upgrade relayed connections to direct ones by hole punching.
+    pub hole_punching: bool,
+}
+
+impl Default for NatConfig {
+    fn default() -> Self {
+        Self {
+            autonat: true,
+            relay_server: false,
+            relays: Vec::new(),
+            hole_punching: true,
+        }
+    }
 }
 
 impl Default for RelayConfig {
@@ -20,8 +77,15 @@ impl Default for RelayConfig {
         Self {
             listen_addr: ([0, 0, 0, 0], DEFAULT_RELAY_PORT).into(),
             boot_nodes: Vec::new(),
+            dns_seeds: Vec::new(),
+            network_id: "dev".to_string(),
+            genesis_hash: [0u8; 32],
+            capabilities: Capabilities::default(),
             max_connections: MAX_RELAY_CONNECTIONS,
             keypair_seed: None,
+            data_dir: None,
+            nat: NatConfig::default(),
+            rate_limits: RateLimits::default(),
         }
     }
 }
@@ -1,8 +1,60 @@
-use libp2p::Multiaddr;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-/// Handles bootstrap peer discovery.
+use libp2p::multiaddr::Protocol;
+use libp2p::{Multiaddr, PeerId};
+
+use norn_types::constants::DEFAULT_RELAY_PORT;
+
+/// How often the relay checks whether it needs more peers.
+pub const BOOTSTRAP_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Number of addresses dialed per bootstrap round.
+pub const BOOTSTRAP_BATCH: usize = 8;
+
+/// Below this many connected peers, the relay dials another batch.
+pub const MIN_PEERS: usize = 4;
+
+/// Maximum number of addresses kept in the peer store.
+const MAX_STORED_PEERS: usize = 1_000;
+
+/// Consecutive failed dials after which a stored address is dropped.
+const MAX_DIAL_FAILURES: u32 = 5;
+
+/// Peer store file name in the data directory; the network ID is appended.
+const PEER_STORE_FILE: &str = "known_peers";
+
+/// Time allowed for resolving one DNS seed.
+const DNS_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A stored peer address.
+#[derive(Debug, Clone, Default)]
+struct KnownPeer {
+    /// Unix time of the last successful dial, or 0 if never reached.
+    last_seen: u64,
+    /// Failed dials since the last success.
+    failures: u32,
+}
+
+/// Finds peers to connect to.
+///
+/// Candidates come from three sources: configured boot nodes, addresses
+/// resolved from DNS seeds, and a peer store of addresses that were reached
+/// before. The store is saved to the data directory, so a restarted node can
+/// rejoin even when every boot node and seed is down. Each bootstrap round
+/// dials the next batch of candidates, so a few dead peers cannot stall
+/// startup.
 pub struct Discovery {
     boot_nodes: Vec<Multiaddr>,
+    dns_seeds: Vec<String>,
+    known: HashMap<Multiaddr, KnownPeer>,
+    store_path: Option<PathBuf>,
+    /// Whether `known` changed since it was last saved.
+    dirty: bool,
+    /// Rotation offset into the candidate list.
+    cursor: usize,
 }
 
 impl Discovery {
@@ -21,19 +73,236 @@ impl Discovery {
             })
             .collect();
 
-        Self { boot_nodes: addrs }
+        Self {
+            boot_nodes: addrs,
+            dns_seeds: Vec::new(),
+            known: HashMap::new(),
+            store_path: None,
+            dirty: false,
+            cursor: 0,
+        }
+    }
+
+    /// Resolve peers from these DNS seeds, as `host` or `host:port`.
+    pub fn with_dns_seeds(mut self, dns_seeds: Vec<String>) -> Self {
+        self.dns_seeds = dns_seeds;
+        self
+    }
+
+    /// Load and save the peer store in `data_dir`, kept apart per network.
+    pub fn with_peer_store(mut self, data_dir: &Path, network_id: &str) -> Self {
+        let path = data_dir.join(format!("{}_{}", PEER_STORE_FILE, network_id));
+        if let Ok(contents) = std::fs::read_to_string(&path) {
+            self.known = contents.lines().filter_map(parse_store_line).collect();
+        }
+        self.store_path = Some(path);
+        self
     }
 
     /// Return the parsed bootstrap addresses.
     pub fn boot_addrs(&self) -> &[Multiaddr] {
         &self.boot_nodes
     }
+
+    /// Return the configured DNS seeds.
+    pub fn dns_seeds(&self) -> &[String] {
+        &self.dns_seeds
+    }
+
+    /// Number of addresses in the peer store.
+    pub fn known_count(&self) -> usize {
+        self.known.len()
+    }
+
+    /// Add addresses that have not been tried yet, e.g. from a DNS seed.
+    pub fn add_candidates(&mut self, addrs: impl IntoIterator<Item = Multiaddr>) {
+        for addr in addrs {
+            if let Entry::Vacant(entry) = self.known.entry(addr) {
+                entry.insert(KnownPeer::default());
+                self.dirty = true;
+            }
+        }
+    }
+
+    /// Record a successful dial of `addr`, which reached `peer_id`.
+    pub fn record_success(&mut self, addr: &Multiaddr, peer_id: PeerId) {
+        // A resolved seed address is superseded by the same address with
+        // the peer ID that answered on it.
+        let addr = without_peer_id(addr);
+        self.known.remove(&addr);
+        let addr = addr.with(Protocol::P2p(peer_id));
+        self.known.insert(
+            addr,
+            KnownPeer {
+                last_seen: now_secs(),
+                failures: 0,
+            },
+        );
+        self.dirty = true;
+    }
+
+    /// Record a failed dial of `addr`. Stored addresses that keep failing
+    /// are dropped.
+    pub fn record_failure(&mut self, addr: &Multiaddr) {
+        let mut keys = vec![addr.clone()];
+        let bare = without_peer_id(addr);
+        if bare != *addr {
+            keys.push(bare);
+        }
+        for key in keys {
+            if let Some(peer) = self.known.get_mut(&key) {
+                peer.failures += 1;
+                if peer.failures >= MAX_DIAL_FAILURES {
+                    self.known.remove(&key);
+                }
+                self.dirty = true;
+            }
+        }
+    }
+
+    /// Drop every stored address of `peer_id`, e.g. after it turned out to
+    /// be incompatible.
+    pub fn forget_peer(&mut self, peer_id: &PeerId) {
+        let before = self.known.len();
+        self.known
+            .retain(|addr, _| addr.iter().last() != Some(Protocol::P2p(*peer_id)));
+        if self.known.len() != before {
+            self.dirty = true;
+        }
+    }
+
+    /// Return the next `n` addresses to dial.
+    ///
+    /// Boot nodes come first, then stored addresses with the fewest failures
+    /// and the most recent success. Each call starts where the last one
+    /// stopped, wrapping around, so repeated rounds try every candidate.
+    pub fn next_batch(&mut self, n: usize) -> Vec<Multiaddr> {
+        let mut stored: Vec<_> = self.known.iter().collect();
+        stored.sort_by(|(a_addr, a), (b_addr, b)| {
+            a.failures
+                .cmp(&b.failures)
+                .then(b.last_seen.cmp(&a.last_seen))
+                .then(a_addr.to_string().cmp(&b_addr.to_string()))
+        });
+        let mut candidates = self.boot_nodes.clone();
+        for (addr, _) in stored {
+            if !candidates.contains(addr) {
+                candidates.push(addr.clone());
+            }
+        }
+        if candidates.is_empty() {
+            return Vec::new();
+        }
+
+        let start = self.cursor % candidates.len();
+        let batch: Vec<_> = candidates
+            .iter()
+            .cycle()
+            .skip(start)
+            .take(n.min(candidates.len()))
+            .cloned()
+            .collect();
+        self.cursor = start + batch.len();
+        batch
+    }
+
+    /// Save the peer store if it changed. Only the most reliable
+    /// `MAX_STORED_PEERS` addresses are kept.
+    pub fn save(&mut self) {
+        let Some(path) = &self.store_path else {
+            return;
+        };
+        if !self.dirty {
+            return;
+        }
+        let mut peers: Vec<_> = self.known.iter().collect();
+        peers.sort_by(|(_, a), (_, b)| {
+            a.failures
+                .cmp(&b.failures)
+                .then(b.last_seen.cmp(&a.last_seen))
+        });
+        let contents: String = peers
+            .into_iter()
+            .take(MAX_STORED_PEERS)
+            .map(|(addr, peer)| format!("{} {} {}\n", addr, peer.last_seen, peer.failures))
+            .collect();
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        match std::fs::write(path, contents) {
+            Ok(()) => self.dirty = false,
+            Err(e) => tracing::warn!("failed to save peer store: {}", e),
+        }
+    }
+}
+
+/// Resolve DNS seeds to dialable addresses. Seeds that fail to resolve are
+/// logged and skipped.
+pub async fn resolve_dns_seeds(seeds: &[String]) -> Vec<Multiaddr> {
+    let mut addrs = Vec::new();
+    for seed in seeds {
+        let target = if seed.contains(':') {
+            seed.clone()
+        } else {
+            format!("{}:{}", seed, DEFAULT_RELAY_PORT)
+        };
+        match tokio::time::timeout(DNS_TIMEOUT, tokio::net::lookup_host(target)).await {
+            Ok(Ok(resolved)) => {
+                let before = addrs.len();
+                for socket in resolved {
+                    let addr = Multiaddr::from(socket.ip()).with(Protocol::Tcp(socket.port()));
+                    if !addrs.contains(&addr) {
+                        addrs.push(addr);
+                    }
+                }
+                tracing::debug!(%seed, found = addrs.len() - before, "resolved DNS seed");
+            }
+            Ok(Err(e)) => tracing::warn!(%seed, "failed to resolve DNS seed: {}", e),
+            Err(_) => tracing::warn!(%seed, "DNS seed lookup timed out"),
+        }
+    }
+    addrs
+}
+
+/// Parse one `<multiaddr> <last_seen> <failures>` line of the peer store.
+fn parse_store_line(line: &str) -> Option<(Multiaddr, KnownPeer)> {
+    let mut parts = line.split_whitespace();
+    let addr = parts.next()?.parse().ok()?;
+    let last_seen = parts.next()?.parse().ok()?;
+    let failures = parts.next()?.parse().ok()?;
+    Some((
+        addr,
+        KnownPeer {
+            last_seen,
+            failures,
+        },
+    ))
+}
+
+/// `addr` without a trailing `/p2p/<peer id>`.
+fn without_peer_id(addr: &Multiaddr) -> Multiaddr {
+    let mut addr = addr.clone();
+    if let Some(Protocol::P2p(_)) = addr.iter().last() {
+        addr.pop();
+    }
+    addr
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn addr(port: u16) -> Multiaddr {
+        format!("/ip4/10.0.0.1/tcp/{}", port).parse().unwrap()
+    }
+
     #[test]
     fn test_parse_valid_multiaddr() {
         let addrs = vec!["/ip4/127.0.0.1/tcp/9740".to_string()];
@@ -50,7 +319,91 @@ mod tests {
 
     #[test]
     fn test_empty() {
-        let disc = Discovery::new(vec![]);
+        let mut disc = Discovery::new(vec![]);
         assert!(disc.boot_addrs().is_empty());
+        assert!(disc.next_batch(BOOTSTRAP_BATCH).is_empty());
+    }
+
+    #[test]
+    fn test_batches_rotate_through_candidates() {
+        let mut disc = Discovery::new(vec![addr(1).to_string()]);
+        disc.add_candidates([addr(2), addr(3)]);
+
+        let first = disc.next_batch(2);
+        assert_eq!(first[0], addr(1), "boot nodes are tried first");
+        let second = disc.next_batch(2);
+        assert_eq!(
+            second,
+            vec![addr(3), addr(1)],
+            "the next round wraps around"
+        );
+
+        // Three rounds of one cover every candidate.
+        let mut seen: Vec<_> = (0..3).flat_map(|_| disc.next_batch(1)).collect();
+        seen.sort_by_key(|a| a.to_string());
+        assert_eq!(seen, vec![addr(1), addr(2), addr(3)]);
+    }
+
+    #[test]
+    fn test_failing_peers_are_dropped() {
+        let mut disc = Discovery::new(vec![]);
+        disc.add_candidates([addr(1)]);
+        for _ in 0..MAX_DIAL_FAILURES - 1 {
+            disc.record_failure(&addr(1));
+        }
+        assert_eq!(disc.known_count(), 1);
+        disc.record_failure(&addr(1));
+        assert_eq!(disc.known_count(), 0);
+    }
+
+    #[test]
+    fn test_success_replaces_seed_address() {
+        let mut disc = Discovery::new(vec![]);
+        disc.add_candidates([addr(1)]);
+        let peer_id = PeerId::random();
+        let with_id = addr(1).with(Protocol::P2p(peer_id));
+        disc.record_success(&addr(1), peer_id);
+        assert_eq!(disc.known_count(), 1);
+        assert_eq!(disc.next_batch(1), vec![with_id.clone()]);
+
+        // A failure reported without the peer ID still counts.
+        disc.record_failure(&addr(1));
+        disc.record_failure(&with_id);
+        assert_eq!(disc.known.get(&with_id).unwrap().failures, 1);
+
+        disc.forget_peer(&peer_id);
+        assert_eq!(disc.known_count(), 0);
+    }
+
+    #[test]
+    fn test_peer_store_survives_restart() {
+        let dir = std::env::temp_dir().join(format!("norn-peer-store-{}", std::process::id()));
+        let peer_id = PeerId::random();
+        let reached = addr(1).with(Protocol::P2p(peer_id));
+        {
+            let mut disc = Discovery::new(vec![]).with_peer_store(&dir, "testnet");
+            disc.add_candidates([addr(2)]);
+            disc.record_success(&reached, peer_id);
+            disc.save();
+        }
+
+        let mut disc = Discovery::new(vec![]).with_peer_store(&dir, "testnet");
+        assert_eq!(disc.known_count(), 2);
+        assert_eq!(
+            disc.next_batch(1),
+            vec![reached],
+            "reached peers come first"
+        );
+
+        // Each network keeps its own store.
+        let other = Discovery::new(vec![]).with_peer_store(&dir, "mainnet");
+        assert_eq!(other.known_count(), 0);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_resolve_ip_seed() {
+        let addrs = resolve_dns_seeds(&["127.0.0.1:9999".to_string()]).await;
+        assert_eq!(addrs, vec!["/ip4/127.0.0.1/tcp/9999".parse().unwrap()]);
     }
 }
@@ -31,6 +31,14 @@ pub enum RelayError {
     #[error("channel error: {reason}")]
     ChannelError { reason: String },
 
+    /// A spindle coverage advertisement failed validation.
+    #[error("invalid spindle coverage: {reason}")]
+    InvalidSpindleCoverage { reason: String },
+
+    /// A thread checkpoint failed validation.
+    #[error("invalid checkpoint: {reason}")]
+    InvalidCheckpoint { reason: String },
+
     /// Peer is running an incompatible protocol version.
     #[error("protocol version mismatch: peer sent v{peer}, we run v{ours}")]
     VersionMismatch { peer: u8, ours: u8 },
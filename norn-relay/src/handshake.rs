@@ -0,0 +1,308 @@
+use async_trait::async_trait;
+use borsh::{BorshDeserialize, BorshSerialize};
+use futures::prelude::*;
+use libp2p::swarm::StreamProtocol;
+use norn_types::primitives::Hash;
+use std::io;
+use thiserror::Error;
+
+use crate::relay::hex_encode;
+
+/// Maximum encoded size of a handshake in bytes.
+const MAX_HANDSHAKE_SIZE: usize = 1_024;
+
+/// Optional services a node offers, as a bit set.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct Capabilities(pub u64);
+
+impl Capabilities {
+    /// Serves missing block commitments for compact block relay.
+    pub const COMPACT_BLOCKS: Capabilities = Capabilities(1);
+    /// Relays connections for peers behind NAT (circuit relay v2).
+    pub const CIRCUIT_RELAY: Capabilities = Capabilities(1 << 1);
+    /// Takes state snapshots and serves them for fast sync.
+    pub const STATE_SNAPSHOTS: Capabilities = Capabilities(1 << 2);
+    /// Keeps every block and historical state (no pruning).
+    pub const ARCHIVE: Capabilities = Capabilities(1 << 3);
+
+    /// Whether every capability in `other` is set.
+    pub fn contains(self, other: Capabilities) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl std::ops::BitOr for Capabilities {
+    type Output = Capabilities;
+
+    fn bitor(self, rhs: Capabilities) -> Capabilities {
+        Capabilities(self.0 | rhs.0)
+    }
+}
+
+impl std::ops::BitOrAssign for Capabilities {
+    fn bitor_assign(&mut self, rhs: Capabilities) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// What a node tells each peer when they connect.
+///
+/// Both sides send one and run `negotiate` on the other's. Peers on another
+/// network or chain, or without a protocol version in common, are
+/// disconnected before any gossip flows between them.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct Handshake {
+    /// Network identifier ("dev", "testnet" or "mainnet").
+    pub network_id: String,
+    /// Oldest protocol version the node can speak.
+    pub min_version: u8,
+    /// Newest protocol version the node can speak.
+    pub max_version: u8,
+    /// Hash of the node's genesis configuration; all zeros if unknown.
+    pub genesis_hash: Hash,
+    /// Optional services the node offers.
+    pub capabilities: Capabilities,
+}
+
+/// Why a peer's handshake was rejected.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum Incompatibility {
+    /// The peer belongs to another network.
+    #[error("peer is on network {theirs}, we are on {ours}")]
+    NetworkMismatch { ours: String, theirs: String },
+
+    /// The peer follows another chain.
+    #[error(
+        "peer has genesis {}, we have {}",
+        hex_encode(theirs),
+        hex_encode(ours)
+    )]
+    GenesisMismatch { ours: Hash, theirs: Hash },
+
+    /// The version ranges do not overlap.
+    #[error("peer speaks protocol v{}-v{}, we speak v{}-v{}", theirs.0, theirs.1, ours.0, ours.1)]
+    NoCommonVersion { ours: (u8, u8), theirs: (u8, u8) },
+
+    /// The peer does not support the handshake protocol, so it predates
+    /// every version we speak.
+    #[error("peer does not support the handshake protocol")]
+    NoHandshake,
+}
+
+impl Handshake {
+    /// Check a peer's handshake against ours. Returns the protocol version
+    /// to use with the peer: the newest one both sides speak.
+    ///
+    /// An all-zero genesis hash on either side means the hash is not known
+    /// yet and matches any genesis.
+    pub fn negotiate(&self, remote: &Handshake) -> Result<u8, Incompatibility> {
+        if remote.network_id != self.network_id {
+            return Err(Incompatibility::NetworkMismatch {
+                ours: self.network_id.clone(),
+                theirs: remote.network_id.clone(),
+            });
+        }
+        if self.genesis_hash != [0u8; 32]
+            && remote.genesis_hash != [0u8; 32]
+            && remote.genesis_hash != self.genesis_hash
+        {
+            return Err(Incompatibility::GenesisMismatch {
+                ours: self.genesis_hash,
+                theirs: remote.genesis_hash,
+            });
+        }
+        let version = self.max_version.min(remote.max_version);
+        if version < self.min_version.max(remote.min_version) {
+            return Err(Incompatibility::NoCommonVersion {
+                ours: (self.min_version, self.max_version),
+                theirs: (remote.min_version, remote.max_version),
+            });
+        }
+        Ok(version)
+    }
+}
+
+/// Borsh length-prefixed codec for the handshake request-response protocol.
+///
+/// Wire format: `[4-byte length][borsh Handshake]`, both ways.
+#[derive(Debug, Clone, Default)]
+pub struct HandshakeCodec;
+
+#[async_trait]
+impl libp2p::request_response::Codec for HandshakeCodec {
+    type Protocol = StreamProtocol;
+    type Request = Handshake;
+    type Response = Handshake;
+
+    async fn read_request<T>(
+        &mut self,
+        _protocol: &Self::Protocol,
+        io: &mut T,
+    ) -> io::Result<Self::Request>
+    where
+        T: AsyncRead + Unpin + Send,
+    {
+        read_handshake(io).await
+    }
+
+    async fn read_response<T>(
+        &mut self,
+        _protocol: &Self::Protocol,
+        io: &mut T,
+    ) -> io::Result<Self::Response>
+    where
+        T: AsyncRead + Unpin + Send,
+    {
+        read_handshake(io).await
+    }
+
+    async fn write_request<T>(
+        &mut self,
+        _protocol: &Self::Protocol,
+        io: &mut T,
+        req: Self::Request,
+    ) -> io::Result<()>
+    where
+        T: AsyncWrite + Unpin + Send,
+    {
+        write_handshake(io, &req).await
+    }
+
+    async fn write_response<T>(
+        &mut self,
+        _protocol: &Self::Protocol,
+        io: &mut T,
+        res: Self::Response,
+    ) -> io::Result<()>
+    where
+        T: AsyncWrite + Unpin + Send,
+    {
+        write_handshake(io, &res).await
+    }
+}
+
+async fn read_handshake<T>(io: &mut T) -> io::Result<Handshake>
+where
+    T: AsyncRead + Unpin + Send,
+{
+    let mut len_buf = [0u8; 4];
+    io.read_exact(&mut len_buf).await?;
+    let len = u32::from_be_bytes(len_buf) as usize;
+    if len > MAX_HANDSHAKE_SIZE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("handshake too large: {} bytes", len),
+        ));
+    }
+    let mut buf = vec![0u8; len];
+    io.read_exact(&mut buf).await?;
+    Handshake::try_from_slice(&buf).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+async fn write_handshake<T>(io: &mut T, handshake: &Handshake) -> io::Result<()>
+where
+    T: AsyncWrite + Unpin + Send,
+{
+    let data = borsh::to_vec(handshake)?;
+    io.write_all(&(data.len() as u32).to_be_bytes()).await?;
+    io.write_all(&data).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn handshake(min_version: u8, max_version: u8) -> Handshake {
+        Handshake {
+            network_id: "testnet".to_string(),
+            min_version,
+            max_version,
+            genesis_hash: [7u8; 32],
+            capabilities: Capabilities::COMPACT_BLOCKS,
+        }
+    }
+
+    #[test]
+    fn test_version_compatibility_matrix() {
+        let no_overlap = |ours: (u8, u8), theirs: (u8, u8)| {
+            Err(Incompatibility::NoCommonVersion { ours, theirs })
+        };
+        // (ours, theirs, expected)
+        let matrix = [
+            ((13, 13), (13, 13), Ok(13)),
+            ((13, 14), (13, 13), Ok(13)),
+            ((13, 13), (13, 14), Ok(13)),
+            ((13, 15), (14, 16), Ok(15)),
+            ((13, 15), (15, 15), Ok(15)),
+            ((13, 13), (14, 14), no_overlap((13, 13), (14, 14))),
+            ((14, 15), (12, 13), no_overlap((14, 15), (12, 13))),
+            ((13, 14), (15, 16), no_overlap((13, 14), (15, 16))),
+        ];
+        for (ours, theirs, expected) in matrix {
+            let local = handshake(ours.0, ours.1);
+            let remote = handshake(theirs.0, theirs.1);
+            assert_eq!(
+                local.negotiate(&remote),
+                expected,
+                "ours v{:?}, theirs v{:?}",
+                ours,
+                theirs
+            );
+            // Negotiation is symmetric: both sides reach the same verdict.
+            assert_eq!(
+                local.negotiate(&remote).is_ok(),
+                remote.negotiate(&local).is_ok()
+            );
+        }
+    }
+
+    #[test]
+    fn test_network_mismatch_rejected() {
+        let local = handshake(13, 13);
+        let mut remote = handshake(13, 13);
+        remote.network_id = "mainnet".to_string();
+        assert_eq!(
+            local.negotiate(&remote),
+            Err(Incompatibility::NetworkMismatch {
+                ours: "testnet".to_string(),
+                theirs: "mainnet".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_genesis_mismatch_rejected() {
+        let local = handshake(13, 13);
+        let mut remote = handshake(13, 13);
+        remote.genesis_hash = [8u8; 32];
+        assert!(matches!(
+            local.negotiate(&remote),
+            Err(Incompatibility::GenesisMismatch { .. })
+        ));
+
+        // An unknown genesis matches any chain.
+        remote.genesis_hash = [0u8; 32];
+        assert_eq!(local.negotiate(&remote), Ok(13));
+    }
+
+    #[test]
+    fn test_capabilities() {
+        let caps = Capabilities::COMPACT_BLOCKS | Capabilities::ARCHIVE;
+        assert!(caps.contains(Capabilities::ARCHIVE));
+        assert!(!caps.contains(Capabilities::CIRCUIT_RELAY));
+        assert!(!caps.contains(Capabilities::ARCHIVE | Capabilities::CIRCUIT_RELAY));
+    }
+
+    #[tokio::test]
+    async fn test_codec_roundtrip() {
+        let original = handshake(13, 14);
+        let mut buf = Vec::new();
+        write_handshake(&mut futures::io::Cursor::new(&mut buf), &original)
+            .await
+            .unwrap();
+        let decoded = read_handshake(&mut futures::io::Cursor::new(buf))
+            .await
+            .unwrap();
+        assert_eq!(decoded, original);
+    }
+}
@@ -1,14 +1,19 @@
 //! P2P networking and message relay for the Norn Protocol.
 //!
 //! Built on libp2p with gossipsub for message propagation, request-response for
-//! direct communication, peer discovery, and a Spindle registry for watchtower
-//! service coordination.
+//! direct communication, a versioned connection handshake, compact block relay,
+//! thread checkpoint exchange, peer discovery, and a Spindle registry for
+//! watchtower service coordination.
 
+pub mod bandwidth;
 pub mod behaviour;
+pub mod checkpoint;
 pub mod codec;
+pub mod compact;
 pub mod config;
 pub mod discovery;
 pub mod error;
+pub mod handshake;
 pub mod peer_manager;
 pub mod protocol;
 pub mod relay;
@@ -1,6 +1,97 @@
 use libp2p::PeerId;
 use norn_types::primitives::Address;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::handshake::Capabilities;
+
+/// Score at or below which a peer is banned.
+pub const BAN_THRESHOLD: i32 = -100;
+
+/// How long a ban lasts.
+pub const BAN_DURATION: Duration = Duration::from_secs(3600);
+
+/// Bans after which a peer is greylisted.
+pub const GREYLIST_AFTER_BANS: u32 = 3;
+
+/// Score a greylisted peer starts from, so that it is banned again sooner.
+pub const GREYLIST_SCORE: i32 = BAN_THRESHOLD / 2;
+
+/// How often the relay calls `decay_scores`.
+pub const SCORE_DECAY_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Points a negative score recovers per `SCORE_DECAY_INTERVAL`.
+pub const SCORE_DECAY: i32 = 5;
+
+/// Messages from a peer that may be dropped by rate limiting per
+/// `SCORE_DECAY_INTERVAL` before it is penalized for spam.
+pub const MAX_THROTTLED_PER_INTERVAL: u32 = 5_000;
+
+/// File, under the relay data directory, listing greylisted peers.
+const GREYLIST_FILE: &str = "peer_greylist";
+
+/// Ways a peer can misbehave, each with a score penalty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Misbehavior {
+    /// Sent a message that could not be decoded or failed validation.
+    InvalidMessage,
+    /// Kept exceeding its rate limits: more than `MAX_THROTTLED_PER_INTERVAL`
+    /// messages dropped in one interval.
+    Spam,
+    /// Broke the protocol, e.g. a malformed compact block or a response
+    /// for the wrong chain.
+    ProtocolViolation,
+}
+
+impl Misbehavior {
+    /// Score penalty for this misbehavior.
+    pub fn penalty(self) -> i32 {
+        match self {
+            Misbehavior::InvalidMessage => 20,
+            Misbehavior::Spam => 10,
+            Misbehavior::ProtocolViolation => 50,
+        }
+    }
+}
+
+/// Reputation of a peer, kept across reconnects.
+#[derive(Debug, Clone, Default)]
+struct Reputation {
+    score: i32,
+    bans: u32,
+    banned_until: Option<Instant>,
+    /// Messages dropped by rate limiting since the last `decay_scores`.
+    throttled: u32,
+}
+
+/// A connected peer and its score, for reporting.
+#[derive(Debug, Clone)]
+pub struct PeerScore {
+    /// The libp2p peer ID.
+    pub peer_id: PeerId,
+    /// Current score; 0 is neutral and lower is worse.
+    pub score: i32,
+    /// The peer's protocol version, if known.
+    pub protocol_version: Option<u8>,
+    /// Seconds since the peer connected.
+    pub connected_secs: u64,
+    /// Whether the peer is greylisted.
+    pub greylisted: bool,
+}
+
+/// Peer reputation totals, for metrics.
+#[derive(Debug, Clone, Default)]
+pub struct PeerStats {
+    /// Connected peers and their scores.
+    pub peers: Vec<PeerScore>,
+    /// Peers currently banned.
+    pub banned: usize,
+    /// Greylisted peers.
+    pub greylisted: usize,
+    /// Total penalties applied since start.
+    pub penalties: u64,
+}
 
 /// Information about a connected peer.
 pub struct PeerInfo {
@@ -8,17 +99,30 @@ pub struct PeerInfo {
     pub peer_id: PeerId,
     /// Optional Norn address (set after registration).
     pub address: Option<Address>,
-    /// The peer's protocol version (set via identify).
+    /// The peer's protocol version: the one negotiated in the handshake, or
+    /// the one it reports via identify until then.
     pub protocol_version: Option<u8>,
+    /// Services the peer offers (set by the handshake).
+    pub capabilities: Capabilities,
     /// When this peer connected.
     pub connected_at: std::time::Instant,
 }
 
-/// Tracks connected peers and their Norn addresses.
+/// Tracks connected peers, their Norn addresses, and their reputation.
+///
+/// Misbehaving peers lose score; at `BAN_THRESHOLD` they are banned for
+/// `BAN_DURATION`. Scores recover over time and survive reconnects. A peer
+/// banned `GREYLIST_AFTER_BANS` times is greylisted: it starts from
+/// `GREYLIST_SCORE` whenever it connects, and the greylist is saved to the
+/// data directory so it outlives restarts.
 pub struct PeerManager {
     peers: HashMap<PeerId, PeerInfo>,
     address_to_peer: HashMap<Address, PeerId>,
     max_connections: usize,
+    reputation: HashMap<PeerId, Reputation>,
+    greylist: HashSet<PeerId>,
+    greylist_path: Option<PathBuf>,
+    penalties: u64,
 }
 
 impl PeerManager {
@@ -28,18 +132,46 @@ impl PeerManager {
             peers: HashMap::new(),
             address_to_peer: HashMap::new(),
             max_connections,
+            reputation: HashMap::new(),
+            greylist: HashSet::new(),
+            greylist_path: None,
+            penalties: 0,
         }
     }
 
-    /// Add a peer. Returns false if the connection limit is reached.
+    /// Create a PeerManager that persists its greylist under `data_dir`,
+    /// loading any greylist already there.
+    pub fn with_data_dir(max_connections: usize, data_dir: &Path) -> Self {
+        let path = data_dir.join(GREYLIST_FILE);
+        let greylist = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents
+                .lines()
+                .filter_map(|line| line.trim().parse().ok())
+                .collect(),
+            Err(_) => HashSet::new(),
+        };
+        Self {
+            greylist,
+            greylist_path: Some(path),
+            ..Self::new(max_connections)
+        }
+    }
+
+    /// Add a peer. Returns false if the connection limit is reached or the
+    /// peer is banned.
     pub fn add_peer(&mut self, peer_id: PeerId) -> bool {
-        if self.peers.len() >= self.max_connections {
+        if self.peers.len() >= self.max_connections || self.is_banned(&peer_id) {
             return false;
         }
+        if self.greylist.contains(&peer_id) {
+            let reputation = self.reputation.entry(peer_id).or_default();
+            reputation.score = reputation.score.min(GREYLIST_SCORE);
+        }
         self.peers.entry(peer_id).or_insert_with(|| PeerInfo {
             peer_id,
             address: None,
             protocol_version: None,
+            capabilities: Capabilities::default(),
             connected_at: std::time::Instant::now(),
         });
         true
@@ -96,6 +228,27 @@ impl PeerManager {
         }
     }
 
+    /// Record the outcome of a successful handshake with a peer.
+    pub fn set_peer_handshake(
+        &mut self,
+        peer_id: &PeerId,
+        version: u8,
+        capabilities: Capabilities,
+    ) {
+        if let Some(info) = self.peers.get_mut(peer_id) {
+            info.protocol_version = Some(version);
+            info.capabilities = capabilities;
+        }
+    }
+
+    /// Get the capabilities a peer announced in its handshake.
+    pub fn peer_capabilities(&self, peer_id: &PeerId) -> Capabilities {
+        self.peers
+            .get(peer_id)
+            .map(|info| info.capabilities)
+            .unwrap_or_default()
+    }
+
     /// Get the protocol version of a specific peer.
     pub fn peer_version(&self, peer_id: &PeerId) -> Option<u8> {
         self.peers
@@ -103,6 +256,122 @@ impl PeerManager {
             .and_then(|info| info.protocol_version)
     }
 
+    /// Whether a peer is currently banned.
+    pub fn is_banned(&self, peer_id: &PeerId) -> bool {
+        self.reputation
+            .get(peer_id)
+            .and_then(|r| r.banned_until)
+            .is_some_and(|until| until > Instant::now())
+    }
+
+    /// Whether a peer is greylisted.
+    pub fn is_greylisted(&self, peer_id: &PeerId) -> bool {
+        self.greylist.contains(peer_id)
+    }
+
+    /// A peer's current score; 0 for peers with no record.
+    pub fn score(&self, peer_id: &PeerId) -> i32 {
+        self.reputation.get(peer_id).map_or(0, |r| r.score)
+    }
+
+    /// Penalize a peer for misbehavior. Returns true if this bans the peer,
+    /// in which case the caller should disconnect it.
+    pub fn penalize(&mut self, peer_id: &PeerId, misbehavior: Misbehavior) -> bool {
+        if self.is_banned(peer_id) {
+            return false;
+        }
+        self.penalties += 1;
+        let reputation = self.reputation.entry(*peer_id).or_default();
+        reputation.score = reputation.score.saturating_sub(misbehavior.penalty());
+        if reputation.score > BAN_THRESHOLD {
+            return false;
+        }
+
+        reputation.bans += 1;
+        reputation.banned_until = Some(Instant::now() + BAN_DURATION);
+        tracing::warn!(
+            %peer_id,
+            ?misbehavior,
+            bans = reputation.bans,
+            "banning misbehaving peer"
+        );
+        if reputation.bans >= GREYLIST_AFTER_BANS && self.greylist.insert(*peer_id) {
+            self.save_greylist();
+        }
+        true
+    }
+
+    /// Count a message from a peer that was dropped by rate limiting.
+    /// Returns true if the peer has exceeded `MAX_THROTTLED_PER_INTERVAL` and
+    /// was banned for it.
+    pub fn record_throttled(&mut self, peer_id: &PeerId) -> bool {
+        let reputation = self.reputation.entry(*peer_id).or_default();
+        reputation.throttled += 1;
+        if reputation.throttled <= MAX_THROTTLED_PER_INTERVAL {
+            return false;
+        }
+        // Penalize once per interval, not once per message.
+        reputation.throttled = 0;
+        self.penalize(peer_id, Misbehavior::Spam)
+    }
+
+    /// Recover scores towards zero, reset message counts, and lift expired
+    /// bans. Called every `SCORE_DECAY_INTERVAL`.
+    pub fn decay_scores(&mut self) {
+        let now = Instant::now();
+        self.reputation.retain(|_, reputation| {
+            reputation.throttled = 0;
+            reputation.score = (reputation.score + SCORE_DECAY).min(0);
+            if reputation.banned_until.is_some_and(|until| until <= now) {
+                reputation.banned_until = None;
+            }
+            // Ban counts are kept so repeat offenders are greylisted.
+            reputation.score < 0 || reputation.banned_until.is_some() || reputation.bans > 0
+        });
+    }
+
+    /// Connected peers and their scores, with reputation totals.
+    pub fn stats(&self) -> PeerStats {
+        let now = Instant::now();
+        PeerStats {
+            peers: self
+                .peers
+                .values()
+                .map(|info| PeerScore {
+                    peer_id: info.peer_id,
+                    score: self.score(&info.peer_id),
+                    protocol_version: info.protocol_version,
+                    connected_secs: now.duration_since(info.connected_at).as_secs(),
+                    greylisted: self.greylist.contains(&info.peer_id),
+                })
+                .collect(),
+            banned: self
+                .reputation
+                .values()
+                .filter(|r| r.banned_until.is_some_and(|until| until > now))
+                .count(),
+            greylisted: self.greylist.len(),
+            penalties: self.penalties,
+        }
+    }
+
+    fn save_greylist(&self) {
+        let Some(path) = &self.greylist_path else {
+            return;
+        };
+        let contents: String = self
+            .greylist
+            .iter()
+            .map(|peer_id| format!("{}\n", peer_id))
+            .collect();
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        if let Err(e) = std::fs::write(path, contents) {
+            tracing::warn!("failed to save peer greylist: {}", e);
+        }
+    }
+
     /// Return the highest protocol version seen among all connected peers,
     /// or `None` if no peer has reported its version yet.
     pub fn highest_peer_version(&self) -> Option<u8> {
@@ -208,6 +477,10 @@ mod tests {
         assert_eq!(pm.peer_version(&peer), None);
         pm.set_peer_version(&peer, 4);
         assert_eq!(pm.peer_version(&peer), Some(4));
+
+        pm.set_peer_handshake(&peer, 3, Capabilities::ARCHIVE);
+        assert_eq!(pm.peer_version(&peer), Some(3));
+        assert!(pm.peer_capabilities(&peer).contains(Capabilities::ARCHIVE));
     }
 
     #[test]
@@ -227,6 +500,80 @@ mod tests {
         assert_eq!(pm.highest_peer_version(), Some(5));
     }
 
+    #[test]
+    fn test_penalties_ban_peer() {
+        let mut pm = PeerManager::new(10);
+        let peer = make_peer_id();
+        assert!(pm.add_peer(peer));
+        assert!(!pm.penalize(&peer, Misbehavior::ProtocolViolation));
+        assert_eq!(pm.score(&peer), -50);
+        assert!(pm.penalize(&peer, Misbehavior::ProtocolViolation));
+        assert!(pm.is_banned(&peer));
+
+        // Banned peers cannot reconnect.
+        pm.remove_peer(&peer);
+        assert!(!pm.add_peer(peer));
+        let stats = pm.stats();
+        assert_eq!(stats.banned, 1);
+        assert_eq!(stats.penalties, 2);
+    }
+
+    #[test]
+    fn test_scores_decay_and_survive_reconnect() {
+        let mut pm = PeerManager::new(10);
+        let peer = make_peer_id();
+        pm.add_peer(peer);
+        pm.penalize(&peer, Misbehavior::Spam);
+        pm.remove_peer(&peer);
+        pm.add_peer(peer);
+        assert_eq!(pm.score(&peer), -10);
+
+        pm.decay_scores();
+        assert_eq!(pm.score(&peer), -5);
+        pm.decay_scores();
+        pm.decay_scores();
+        assert_eq!(pm.score(&peer), 0);
+        assert!(pm.reputation.is_empty());
+    }
+
+    #[test]
+    fn test_message_flood_is_spam() {
+        let mut pm = PeerManager::new(10);
+        let peer = make_peer_id();
+        pm.add_peer(peer);
+        for _ in 0..MAX_THROTTLED_PER_INTERVAL {
+            assert!(!pm.record_throttled(&peer));
+        }
+        assert_eq!(pm.score(&peer), 0);
+        assert!(!pm.record_throttled(&peer));
+        assert_eq!(pm.score(&peer), -Misbehavior::Spam.penalty());
+
+        // The count resets each interval.
+        pm.decay_scores();
+        assert!(!pm.record_throttled(&peer));
+        assert_eq!(pm.score(&peer), -Misbehavior::Spam.penalty() + SCORE_DECAY);
+    }
+
+    #[test]
+    fn test_repeat_offender_greylisted_and_persisted() {
+        let dir = std::env::temp_dir().join(format!("norn-greylist-{}", std::process::id()));
+        let peer = make_peer_id();
+        let mut pm = PeerManager::with_data_dir(10, &dir);
+        for _ in 0..GREYLIST_AFTER_BANS {
+            while !pm.penalize(&peer, Misbehavior::ProtocolViolation) {}
+            // Lift the ban as if it had expired.
+            pm.reputation.get_mut(&peer).unwrap().banned_until = None;
+        }
+        assert!(pm.is_greylisted(&peer));
+
+        let mut restarted = PeerManager::with_data_dir(10, &dir);
+        assert!(restarted.is_greylisted(&peer));
+        assert!(restarted.add_peer(peer));
+        assert_eq!(restarted.score(&peer), GREYLIST_SCORE);
+        assert!(restarted.stats().peers[0].greylisted);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn test_set_version_unknown_peer_is_noop() {
         let mut pm = PeerManager::new(10);
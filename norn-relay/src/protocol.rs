@@ -1,6 +1,12 @@
 /// Wire protocol version. Bump this whenever a breaking change is made to
 /// NornMessage variants or any borsh-serialized P2P type.
-pub const PROTOCOL_VERSION: u8 = 11;
+pub const PROTOCOL_VERSION: u8 = 15;
+
+/// Oldest wire protocol version this node still speaks. Peers whose version
+/// range ends below it are rejected during the handshake.
+pub const MIN_PROTOCOL_VERSION: u8 = 15;
+
+const _: () = assert!(MIN_PROTOCOL_VERSION <= PROTOCOL_VERSION);
 
 /// Envelope wire header version. The first byte after the 4-byte length prefix.
 /// Since this is 1 and the old LEGACY_PROTOCOL_VERSION was 3, the codec can
@@ -17,6 +23,9 @@ pub const GOSSIP_PROTOCOL: &str = "/norn/gossip/1.0.0";
 /// Direct message protocol.
 pub const DIRECT_PROTOCOL: &str = "/norn/direct/1.0.0";
 
+/// Handshake protocol, run once when a connection is established.
+pub const HANDSHAKE_PROTOCOL: &str = "/norn/handshake/1.0.0";
+
 /// Spindle registration protocol.
 pub const SPINDLE_PROTOCOL: &str = "/norn/spindle/1.0.0";
 
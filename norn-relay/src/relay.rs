@@ -1,25 +1,36 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::{Arc, Mutex as StdMutex};
 
 use futures::StreamExt;
 use libp2p::gossipsub::{self, IdentTopic};
+use libp2p::multiaddr::Protocol;
 use libp2p::request_response;
-use libp2p::swarm::SwarmEvent;
-use libp2p::{Multiaddr, PeerId, Swarm, SwarmBuilder};
-use norn_types::network::{NornMessage, UpgradeNotice};
-use norn_types::primitives::Address;
+use libp2p::swarm::{DialError, SwarmEvent};
+use libp2p::{autonat, dcutr, relay as circuit_relay, Multiaddr, PeerId, Swarm, SwarmBuilder};
+use norn_types::network::{CompactBlock, NornMessage, UpgradeNotice};
+use norn_types::primitives::{Address, Hash};
+use norn_types::weave::CommitmentUpdate;
 use tokio::sync::{broadcast, mpsc};
 use tracing::{debug, info, warn};
 
+use crate::bandwidth::{
+    BandwidthStats, BandwidthTracker, BANDWIDTH_STATS_INTERVAL, MAX_QUEUED_PER_PEER,
+    OUTBOUND_FLUSH_INTERVAL,
+};
 use crate::behaviour::{build_behaviour, NornBehaviour, NornBehaviourEvent};
+use crate::checkpoint::CheckpointStore;
 use crate::codec::{self, DecodedMessage};
+use crate::compact::{CompactBlockPool, Reconstruction};
 use crate::config::RelayConfig;
-use crate::discovery::Discovery;
+use crate::discovery::{
+    resolve_dns_seeds, Discovery, BOOTSTRAP_BATCH, BOOTSTRAP_INTERVAL, MIN_PEERS,
+};
 use crate::error::RelayError;
-use crate::peer_manager::PeerManager;
+use crate::handshake::{Capabilities, Handshake, Incompatibility};
+use crate::peer_manager::{Misbehavior, PeerManager, PeerStats, SCORE_DECAY_INTERVAL};
 use crate::protocol::{
     versioned_topic, BLOCKS_TOPIC, COMMITMENTS_TOPIC, FRAUD_PROOFS_TOPIC, GENERAL_TOPIC,
-    PROTOCOL_VERSION,
+    MIN_PROTOCOL_VERSION, PROTOCOL_VERSION,
 };
 use crate::spindle_registry::SpindleRegistry;
 
@@ -29,6 +40,8 @@ enum OutboundMessage {
     Broadcast(NornMessage),
     /// Send directly to a specific peer via request-response.
     SendToPeer(PeerId, NornMessage),
+    /// Penalize a peer for misbehavior the node detected.
+    ReportPeer(PeerId, Misbehavior),
 }
 
 /// A cloneable handle for sending messages through the relay after `run()` is spawned.
@@ -36,6 +49,9 @@ enum OutboundMessage {
 pub struct RelayHandle {
     outbound_tx: mpsc::Sender<OutboundMessage>,
     connected_peers: Arc<StdMutex<HashSet<PeerId>>>,
+    spindle_registry: Arc<StdMutex<SpindleRegistry>>,
+    peer_stats: Arc<StdMutex<PeerStats>>,
+    bandwidth_stats: Arc<StdMutex<BandwidthStats>>,
 }
 
 impl RelayHandle {
@@ -59,6 +75,37 @@ impl RelayHandle {
             })
     }
 
+    /// Penalize a peer for misbehavior detected above the relay, such as
+    /// an invalid block. Enough penalties get the peer banned.
+    pub async fn report_peer(
+        &self,
+        peer_id: PeerId,
+        misbehavior: Misbehavior,
+    ) -> Result<(), RelayError> {
+        self.outbound_tx
+            .send(OutboundMessage::ReportPeer(peer_id, misbehavior))
+            .await
+            .map_err(|_| RelayError::ChannelError {
+                reason: "relay outbound channel closed".to_string(),
+            })
+    }
+
+    /// Get connected peers with their scores, and reputation totals.
+    pub fn peer_stats(&self) -> PeerStats {
+        self.peer_stats
+            .lock()
+            .map(|guard| guard.clone())
+            .unwrap_or_default()
+    }
+
+    /// Get relay traffic totals and per-peer traffic.
+    pub fn bandwidth_stats(&self) -> BandwidthStats {
+        self.bandwidth_stats
+            .lock()
+            .map(|guard| guard.clone())
+            .unwrap_or_default()
+    }
+
     /// Get the currently connected peer IDs.
     pub fn connected_peers(&self) -> Vec<PeerId> {
         self.connected_peers
@@ -66,6 +113,12 @@ impl RelayHandle {
             .map(|guard| guard.iter().copied().collect())
             .unwrap_or_default()
     }
+
+    /// Get the shared spindle registry, updated from gossiped spindle
+    /// coverage advertisements.
+    pub fn spindle_registry(&self) -> Arc<StdMutex<SpindleRegistry>> {
+        self.spindle_registry.clone()
+    }
 }
 
 /// The main relay node that handles networking.
@@ -73,15 +126,36 @@ pub struct RelayNode {
     config: RelayConfig,
     swarm: Swarm<NornBehaviour>,
     peer_manager: PeerManager,
-    _spindle_registry: SpindleRegistry,
-    _discovery: Discovery,
+    /// Spindle registry, shared with `RelayHandle`.
+    spindle_registry: Arc<StdMutex<SpindleRegistry>>,
+    /// Commitments and blocks for compact block relay.
+    compact_pool: CompactBlockPool,
+    /// Latest thread checkpoints, to answer `GetCheckpoint` requests.
+    checkpoints: CheckpointStore,
+    discovery: Discovery,
+    /// Relays to listen through if this node turns out to be behind NAT.
+    relay_addrs: Vec<Multiaddr>,
+    /// Whether the node is already listening through `relay_addrs`.
+    listening_via_relays: bool,
     message_tx: broadcast::Sender<(NornMessage, Option<PeerId>)>,
     outbound_tx: mpsc::Sender<OutboundMessage>,
     outbound_rx: Option<mpsc::Receiver<OutboundMessage>>,
     /// Shared set of connected peer IDs, readable from `RelayHandle`.
     connected_peers_shared: Arc<StdMutex<HashSet<PeerId>>>,
+    /// Snapshot of peer scores, readable from `RelayHandle`.
+    peer_stats_shared: Arc<StdMutex<PeerStats>>,
+    /// Per-peer traffic counters and rate limits.
+    bandwidth: BandwidthTracker,
+    /// Snapshot of traffic counters, readable from `RelayHandle`.
+    bandwidth_stats_shared: Arc<StdMutex<BandwidthStats>>,
+    /// Direct messages held back until their peer's outbound budget refills.
+    outbound_queues: HashMap<PeerId, VecDeque<NornMessage>>,
     /// Protocol versions for which we've already broadcast an upgrade notice.
     notified_versions: HashSet<u8>,
+    /// Handshake sent to every peer.
+    local_handshake: Handshake,
+    /// Peers rejected by the handshake, awaiting disconnection.
+    rejected_peers: HashSet<PeerId>,
 }
 
 impl RelayNode {
@@ -112,7 +186,13 @@ impl RelayNode {
             .map_err(|e| RelayError::NetworkError {
                 reason: format!("dns transport: {}", e),
             })?
-            .with_behaviour(|kp| build_behaviour(kp, PROTOCOL_VERSION))
+            .with_relay_client(libp2p::noise::Config::new, libp2p::yamux::Config::default)
+            .map_err(|e| RelayError::NetworkError {
+                reason: format!("relay client transport: {}", e),
+            })?
+            .with_behaviour(|kp, relay_client| {
+                build_behaviour(kp, PROTOCOL_VERSION, relay_client, &config.nat)
+            })
             .map_err(|e| RelayError::NetworkError {
                 reason: format!("behaviour: {}", e),
             })?
@@ -169,21 +249,43 @@ impl RelayNode {
                 reason: format!("listen: {}", e),
             })?;
 
-        // Set up discovery for boot nodes.
-        let discovery = Discovery::new(config.boot_nodes.clone());
-        for addr in discovery.boot_addrs() {
-            swarm
-                .dial(addr.clone())
-                .map_err(|e| RelayError::ConnectionError {
-                    reason: format!("dial boot node {}: {}", addr, e),
-                })?;
+        // Dial the first batch of boot nodes and stored peers. DNS seeds are
+        // resolved once the relay runs.
+        let mut discovery =
+            Discovery::new(config.boot_nodes.clone()).with_dns_seeds(config.dns_seeds.clone());
+        if let Some(dir) = &config.data_dir {
+            discovery = discovery.with_peer_store(dir, &config.network_id);
+        }
+        for addr in discovery.next_batch(BOOTSTRAP_BATCH) {
+            if let Err(e) = swarm.dial(addr.clone()) {
+                warn!(%addr, "failed to dial peer: {}", e);
+            }
         }
 
-        let peer_manager = PeerManager::new(config.max_connections);
-        let spindle_registry = SpindleRegistry::new();
+        let relay_addrs = relay_candidates(&config.nat.relays, discovery.boot_addrs());
+
+        let mut capabilities = config.capabilities | Capabilities::COMPACT_BLOCKS;
+        if config.nat.relay_server {
+            capabilities |= Capabilities::CIRCUIT_RELAY;
+        }
+        let local_handshake = Handshake {
+            network_id: config.network_id.clone(),
+            min_version: MIN_PROTOCOL_VERSION,
+            max_version: PROTOCOL_VERSION,
+            genesis_hash: config.genesis_hash,
+            capabilities,
+        };
+
+        let peer_manager = match &config.data_dir {
+            Some(dir) => PeerManager::with_data_dir(config.max_connections, dir),
+            None => PeerManager::new(config.max_connections),
+        };
+        let spindle_registry = Arc::new(StdMutex::new(SpindleRegistry::new()));
         let (message_tx, _) = broadcast::channel(1024);
         let (outbound_tx, outbound_rx) = mpsc::channel(256);
         let connected_peers_shared = Arc::new(StdMutex::new(HashSet::new()));
+        let peer_stats_shared = Arc::new(StdMutex::new(peer_manager.stats()));
+        let bandwidth = BandwidthTracker::new(config.rate_limits.clone());
 
         info!(
             peer_id = %swarm.local_peer_id(),
@@ -196,13 +298,23 @@ impl RelayNode {
             config,
             swarm,
             peer_manager,
-            _spindle_registry: spindle_registry,
-            _discovery: discovery,
+            spindle_registry,
+            compact_pool: CompactBlockPool::new(),
+            checkpoints: CheckpointStore::new(),
+            discovery,
+            relay_addrs,
+            listening_via_relays: false,
             message_tx,
             outbound_tx,
             outbound_rx: Some(outbound_rx),
             connected_peers_shared,
+            peer_stats_shared,
+            bandwidth,
+            bandwidth_stats_shared: Arc::new(StdMutex::new(BandwidthStats::default())),
+            outbound_queues: HashMap::new(),
             notified_versions: HashSet::new(),
+            local_handshake,
+            rejected_peers: HashSet::new(),
         })
     }
 
@@ -223,6 +335,9 @@ impl RelayNode {
         RelayHandle {
             outbound_tx: self.outbound_tx.clone(),
             connected_peers: self.connected_peers_shared.clone(),
+            spindle_registry: self.spindle_registry.clone(),
+            peer_stats: self.peer_stats_shared.clone(),
+            bandwidth_stats: self.bandwidth_stats_shared.clone(),
         }
     }
 
@@ -236,10 +351,7 @@ impl RelayNode {
                 peer: hex_encode(&addr),
             })?;
 
-        self.swarm
-            .behaviour_mut()
-            .request_response
-            .send_request(&peer_id, msg);
+        self.send_direct(peer_id, msg);
 
         Ok(())
     }
@@ -249,10 +361,12 @@ impl RelayNode {
     /// Dual-publishes: envelope format on the versioned topic, and legacy format
     /// on the unversioned topic (for backward compatibility during rolling upgrades).
     pub async fn broadcast(&mut self, msg: NornMessage) -> Result<(), RelayError> {
+        let msg = self.prepare_gossip(msg);
         // Publish on versioned topic with envelope format.
         let v_topic_name = versioned_topic_for_message(&msg);
         let data = codec::encode_message(&msg)?;
         let topic = IdentTopic::new(&v_topic_name);
+        self.bandwidth.record_gossip_out(data.len());
 
         self.swarm
             .behaviour_mut()
@@ -276,6 +390,7 @@ impl RelayNode {
     ) -> Result<(), RelayError> {
         let data = codec::encode_message(&msg)?;
         let topic = IdentTopic::new(topic_name);
+        self.bandwidth.record_gossip_out(data.len());
 
         self.swarm
             .behaviour_mut()
@@ -288,12 +403,33 @@ impl RelayNode {
         Ok(())
     }
 
+    /// Record outbound gossip for compact block relay, replacing a full
+    /// block with its compact form. Compact blocks have no legacy encoding,
+    /// so blocks are no longer published on the legacy topic.
+    fn prepare_gossip(&mut self, msg: NornMessage) -> NornMessage {
+        match msg {
+            NornMessage::Block(block) => {
+                NornMessage::CompactBlock(Box::new(self.compact_pool.compact(&block)))
+            }
+            NornMessage::Commitment(ref commitment) => {
+                self.compact_pool.observe_commitment(commitment);
+                msg
+            }
+            NornMessage::Checkpoint(ref checkpoint) => {
+                let _ = self.checkpoints.insert(checkpoint);
+                msg
+            }
+            msg => msg,
+        }
+    }
+
     /// Best-effort publish on the legacy (unversioned) topic for backward compatibility.
     fn publish_legacy(&mut self, msg: &NornMessage) {
         let legacy_topic_name = legacy_topic_for_message(msg);
         match codec::encode_message_legacy(msg) {
             Ok(data) => {
                 let topic = IdentTopic::new(legacy_topic_name);
+                self.bandwidth.record_gossip_out(data.len());
                 if let Err(e) = self.swarm.behaviour_mut().gossipsub.publish(topic, data) {
                     debug!("legacy publish failed (non-fatal): {}", e);
                 }
@@ -314,34 +450,107 @@ impl RelayNode {
                 reason: "outbound channel already consumed (run called twice?)".to_string(),
             })?;
 
+        let mut decay = tokio::time::interval(SCORE_DECAY_INTERVAL);
+        let mut bootstrap = tokio::time::interval_at(
+            tokio::time::Instant::now() + BOOTSTRAP_INTERVAL,
+            BOOTSTRAP_INTERVAL,
+        );
+        let mut bandwidth_stats = tokio::time::interval(BANDWIDTH_STATS_INTERVAL);
+        let mut flush = tokio::time::interval(OUTBOUND_FLUSH_INTERVAL);
+        let (dns_tx, mut dns_rx) = mpsc::channel(1);
+        let mut resolving = self.resolve_dns_seeds(&dns_tx);
+
+        // Without AutoNAT there is no reachability signal, so explicitly
+        // configured relays are used from the start.
+        if !self.config.nat.autonat && !self.config.nat.relays.is_empty() {
+            self.listen_via_relays();
+        }
+
         loop {
             tokio::select! {
+                _ = decay.tick() => {
+                    self.peer_manager.decay_scores();
+                    self.publish_peer_stats();
+                }
+                _ = bandwidth_stats.tick() => {
+                    self.publish_bandwidth_stats();
+                }
+                _ = flush.tick(), if !self.outbound_queues.is_empty() => {
+                    self.flush_outbound_queues();
+                }
+                _ = bootstrap.tick() => {
+                    let peers = self.peer_manager.peer_count();
+                    if peers < MIN_PEERS {
+                        self.dial_next_batch();
+                    }
+                    // Without any peer, the seeds may know of new ones.
+                    if peers == 0 && !resolving {
+                        resolving = self.resolve_dns_seeds(&dns_tx);
+                    }
+                    self.discovery.save();
+                }
+                Some(addrs) = dns_rx.recv() => {
+                    resolving = false;
+                    info!(found = addrs.len(), "resolved DNS seeds");
+                    self.discovery.add_candidates(addrs);
+                    if self.peer_manager.peer_count() < MIN_PEERS {
+                        self.dial_next_batch();
+                    }
+                }
                 event = self.swarm.next() => {
                     match event {
                         Some(SwarmEvent::Behaviour(event)) => {
                             self.handle_behaviour_event(event);
                         }
                         Some(SwarmEvent::ConnectionEstablished {
-                            peer_id, endpoint, ..
+                            peer_id, endpoint, num_established, ..
                         }) => {
                             info!(%peer_id, ?endpoint, "peer connected");
-                            if !self.peer_manager.add_peer(peer_id) {
+                            if endpoint.is_dialer() && !self.peer_manager.is_banned(&peer_id) {
+                                self.discovery
+                                    .record_success(endpoint.get_remote_address(), peer_id);
+                            }
+                            if self.peer_manager.is_banned(&peer_id) {
+                                debug!(%peer_id, "refusing banned peer");
+                                let _ = self.swarm.disconnect_peer_id(peer_id);
+                            } else if !self.peer_manager.add_peer(peer_id) {
                                 warn!(
                                     %peer_id,
                                     max = self.config.max_connections,
                                     "peer limit reached, disconnecting peer"
                                 );
                                 let _ = self.swarm.disconnect_peer_id(peer_id);
-                            } else if let Ok(mut peers) = self.connected_peers_shared.lock() {
-                                peers.insert(peer_id);
+                            } else {
+                                if num_established.get() == 1 {
+                                    self.swarm
+                                        .behaviour_mut()
+                                        .handshake
+                                        .send_request(&peer_id, self.local_handshake.clone());
+                                }
+                                if let Ok(mut peers) = self.connected_peers_shared.lock() {
+                                    peers.insert(peer_id);
+                                }
                             }
+                            self.publish_peer_stats();
                         }
                         Some(SwarmEvent::ConnectionClosed { peer_id, .. }) => {
                             info!(%peer_id, "peer disconnected");
                             self.peer_manager.remove_peer(&peer_id);
+                            self.rejected_peers.remove(&peer_id);
+                            self.bandwidth.remove_peer(&peer_id);
+                            self.outbound_queues.remove(&peer_id);
                             if let Ok(mut peers) = self.connected_peers_shared.lock() {
                                 peers.remove(&peer_id);
                             }
+                            self.publish_peer_stats();
+                        }
+                        Some(SwarmEvent::OutgoingConnectionError { peer_id, error, .. }) => {
+                            debug!(?peer_id, "outgoing connection failed: {}", error);
+                            if let DialError::Transport(attempts) = &error {
+                                for (addr, _) in attempts {
+                                    self.discovery.record_failure(addr);
+                                }
+                            }
                         }
                         Some(SwarmEvent::NewListenAddr { address, .. }) => {
                             info!(%address, "listening on new address");
@@ -359,11 +568,13 @@ impl RelayNode {
                 Some(outbound) = outbound_rx.recv() => {
                     match outbound {
                         OutboundMessage::Broadcast(msg) => {
+                            let msg = self.prepare_gossip(msg);
                             // Dual-publish: envelope on versioned topic, legacy on unversioned.
                             let v_topic_name = versioned_topic_for_message(&msg);
                             match codec::encode_message(&msg) {
                                 Ok(data) => {
                                     let topic = IdentTopic::new(&v_topic_name);
+                                    self.bandwidth.record_gossip_out(data.len());
                                     if let Err(e) = self.swarm
                                         .behaviour_mut()
                                         .gossipsub
@@ -381,10 +592,10 @@ impl RelayNode {
                         }
                         OutboundMessage::SendToPeer(peer_id, msg) => {
                             debug!(%peer_id, "sending direct message to peer");
-                            self.swarm
-                                .behaviour_mut()
-                                .request_response
-                                .send_request(&peer_id, msg);
+                            self.send_direct(peer_id, msg);
+                        }
+                        OutboundMessage::ReportPeer(peer_id, misbehavior) => {
+                            self.penalize(peer_id, misbehavior);
                         }
                     }
                 }
@@ -399,15 +610,33 @@ impl RelayNode {
                 message,
                 ..
             }) => {
+                let _span = tracing::info_span!("peer", peer = %propagation_source).entered();
                 debug!(
                     %propagation_source,
                     topic = %message.topic,
                     "received gossipsub message"
                 );
+                if self.rejected_peers.contains(&propagation_source)
+                    || self.throttled(propagation_source, message.data.len())
+                {
+                    return;
+                }
                 match codec::decode_message(&message.data) {
-                    Ok(DecodedMessage::Known(msg)) => {
-                        let _ = self.message_tx.send((*msg, Some(propagation_source)));
-                    }
+                    Ok(DecodedMessage::Known(msg)) => match *msg {
+                        NornMessage::CompactBlock(compact) => {
+                            self.receive_compact_block(
+                                *compact,
+                                propagation_source,
+                                message.source,
+                            );
+                        }
+                        msg => {
+                            self.observe_spindle_coverage(&msg, propagation_source);
+                            self.observe_checkpoint(&msg, propagation_source);
+                            self.observe_block_relay(&msg);
+                            let _ = self.message_tx.send((msg, Some(propagation_source)));
+                        }
+                    },
                     Ok(DecodedMessage::Unknown {
                         protocol_version,
                         message_type,
@@ -421,31 +650,158 @@ impl RelayNode {
                         }
                     }
                     Err(e) => {
-                        warn!("failed to decode gossipsub message: {}", e);
+                        warn!(%propagation_source, "failed to decode gossipsub message: {}", e);
+                        self.penalize(propagation_source, Misbehavior::InvalidMessage);
                     }
                 }
             }
             NornBehaviourEvent::RequestResponse(request_response::Event::Message {
                 peer,
                 message,
-            }) => match message {
-                request_response::Message::Request {
-                    request, channel, ..
-                } => {
-                    debug!(%peer, "received direct request");
-                    let _ = self.message_tx.send((request.clone(), Some(peer)));
-                    // Send back an echo response (acknowledgement).
-                    let _ = self
-                        .swarm
-                        .behaviour_mut()
-                        .request_response
-                        .send_response(channel, request);
+            }) => {
+                let _span = tracing::info_span!("peer", %peer).entered();
+                match message {
+                    request_response::Message::Request { ref request, .. }
+                        if self.throttled(peer, message_size(request)) => {}
+                    request_response::Message::Request {
+                        request:
+                            NornMessage::GetBlockCommitments {
+                                block_hash,
+                                indexes,
+                            },
+                        channel,
+                        ..
+                    } => {
+                        debug!(%peer, missing = indexes.len(), "serving block commitments");
+                        let commitments = self.compact_pool.serve(&block_hash, &indexes);
+                        let response = NornMessage::BlockCommitments {
+                            block_hash,
+                            commitments,
+                        };
+                        self.bandwidth
+                            .record_outbound(&peer, message_size(&response));
+                        let _ = self
+                            .swarm
+                            .behaviour_mut()
+                            .request_response
+                            .send_response(channel, response);
+                    }
+                    request_response::Message::Request {
+                        request: NornMessage::GetCheckpoint { thread_id, owner },
+                        channel,
+                        ..
+                    } => {
+                        debug!(%peer, "serving thread checkpoint");
+                        let response = NornMessage::CheckpointResponse {
+                            thread_id,
+                            checkpoint: self
+                                .checkpoints
+                                .get(&thread_id, &owner)
+                                .cloned()
+                                .map(Box::new),
+                        };
+                        self.bandwidth
+                            .record_outbound(&peer, message_size(&response));
+                        let _ = self
+                            .swarm
+                            .behaviour_mut()
+                            .request_response
+                            .send_response(channel, response);
+                    }
+                    request_response::Message::Request {
+                        request, channel, ..
+                    } => {
+                        debug!(%peer, "received direct request");
+                        let _ = self.message_tx.send((request.clone(), Some(peer)));
+                        // Send back an echo response (acknowledgement).
+                        self.bandwidth
+                            .record_outbound(&peer, message_size(&request));
+                        let _ = self
+                            .swarm
+                            .behaviour_mut()
+                            .request_response
+                            .send_response(channel, request);
+                    }
+                    request_response::Message::Response {
+                        response:
+                            NornMessage::BlockCommitments {
+                                block_hash,
+                                commitments,
+                            },
+                        ..
+                    } => {
+                        self.bandwidth
+                            .record_inbound(&peer, commitments.iter().map(message_size).sum());
+                        self.fill_compact_block(block_hash, commitments, peer);
+                    }
+                    request_response::Message::Response {
+                        response:
+                            NornMessage::CheckpointResponse {
+                                thread_id,
+                                checkpoint,
+                            },
+                        ..
+                    } => {
+                        self.bandwidth
+                            .record_inbound(&peer, checkpoint.as_deref().map_or(0, message_size));
+                        match checkpoint {
+                            Some(checkpoint) if checkpoint.thread_id == thread_id => {
+                                let msg = NornMessage::Checkpoint(checkpoint);
+                                self.observe_checkpoint(&msg, peer);
+                                let _ = self.message_tx.send((msg, Some(peer)));
+                            }
+                            Some(_) => self.penalize(peer, Misbehavior::ProtocolViolation),
+                            None => debug!(%peer, "peer has no checkpoint for the thread"),
+                        }
+                    }
+                    request_response::Message::Response { response, .. } => {
+                        debug!(%peer, "received direct response");
+                        self.bandwidth
+                            .record_inbound(&peer, message_size(&response));
+                        let _ = self.message_tx.send((response, Some(peer)));
+                    }
                 }
-                request_response::Message::Response { response, .. } => {
-                    debug!(%peer, "received direct response");
-                    let _ = self.message_tx.send((response, Some(peer)));
+            }
+            NornBehaviourEvent::Handshake(request_response::Event::Message { peer, message }) => {
+                match message {
+                    request_response::Message::Request {
+                        request, channel, ..
+                    } => {
+                        // Always answer, so the peer learns why it is rejected.
+                        let _ = self
+                            .swarm
+                            .behaviour_mut()
+                            .handshake
+                            .send_response(channel, self.local_handshake.clone());
+                        match self.local_handshake.negotiate(&request) {
+                            Ok(version) => self.accept_peer(peer, version, request.capabilities),
+                            Err(reason) => self.reject_peer(peer, reason),
+                        }
+                    }
+                    request_response::Message::Response { response, .. } => {
+                        match self.local_handshake.negotiate(&response) {
+                            Ok(version) => self.accept_peer(peer, version, response.capabilities),
+                            Err(reason) => {
+                                self.reject_peer(peer, reason);
+                                let _ = self.swarm.disconnect_peer_id(peer);
+                            }
+                        }
+                    }
                 }
-            },
+            }
+            NornBehaviourEvent::Handshake(request_response::Event::ResponseSent {
+                peer, ..
+            }) if self.rejected_peers.contains(&peer) => {
+                let _ = self.swarm.disconnect_peer_id(peer);
+            }
+            NornBehaviourEvent::Handshake(request_response::Event::OutboundFailure {
+                peer,
+                error: request_response::OutboundFailure::UnsupportedProtocols,
+                ..
+            }) => {
+                self.reject_peer(peer, Incompatibility::NoHandshake);
+                let _ = self.swarm.disconnect_peer_id(peer);
+            }
             NornBehaviourEvent::Identify(libp2p::identify::Event::Received {
                 peer_id,
                 info,
@@ -459,7 +815,10 @@ impl RelayNode {
                 );
                 // Parse protocol version from agent_version "norn/{version}".
                 if let Some(version) = parse_agent_version(&info.agent_version) {
-                    self.peer_manager.set_peer_version(&peer_id, version);
+                    // The handshake's negotiated version takes precedence.
+                    if self.peer_manager.peer_version(&peer_id).is_none() {
+                        self.peer_manager.set_peer_version(&peer_id, version);
+                    }
                     if version > PROTOCOL_VERSION {
                         warn!(
                             %peer_id,
@@ -480,6 +839,30 @@ impl RelayNode {
                         .add_explicit_peer(&peer_id);
                 }
             }
+            NornBehaviourEvent::Autonat(autonat::Event::StatusChanged { old, new }) => {
+                info!(?old, ?new, "NAT status changed");
+                if new == autonat::NatStatus::Private {
+                    self.listen_via_relays();
+                }
+            }
+            NornBehaviourEvent::RelayClient(
+                circuit_relay::client::Event::ReservationReqAccepted { relay_peer_id, .. },
+            ) => {
+                info!(%relay_peer_id, "reserved a slot on relay; reachable through it");
+            }
+            NornBehaviourEvent::RelayServer(circuit_relay::Event::ReservationReqAccepted {
+                src_peer_id,
+                ..
+            }) => {
+                debug!(%src_peer_id, "accepted relay reservation");
+            }
+            NornBehaviourEvent::Dcutr(dcutr::Event {
+                remote_peer_id,
+                result,
+            }) => match result {
+                Ok(_) => info!(%remote_peer_id, "hole punch succeeded; connection is direct"),
+                Err(e) => debug!(%remote_peer_id, "hole punch failed: {}", e),
+            },
             NornBehaviourEvent::Mdns(libp2p::mdns::Event::Expired(peers)) => {
                 for (peer_id, addr) in peers {
                     debug!(%peer_id, %addr, "mDNS: peer expired");
@@ -493,6 +876,274 @@ impl RelayNode {
         }
     }
 
+    /// Listen through each relay candidate, so that peers can reach this
+    /// node over a relayed circuit. Done at most once.
+    /// Record a completed handshake.
+    fn accept_peer(&mut self, peer_id: PeerId, version: u8, capabilities: Capabilities) {
+        debug!(%peer_id, version, ?capabilities, "handshake complete");
+        self.peer_manager
+            .set_peer_handshake(&peer_id, version, capabilities);
+        self.publish_peer_stats();
+    }
+
+    /// Reject a peer whose handshake is incompatible. Its gossip is ignored
+    /// until it disconnects, and its addresses are dropped from the peer
+    /// store so it is not dialed again.
+    fn reject_peer(&mut self, peer_id: PeerId, reason: Incompatibility) {
+        if !self.rejected_peers.insert(peer_id) {
+            return;
+        }
+        warn!(%peer_id, %reason, "rejecting incompatible peer");
+        self.discovery.forget_peer(&peer_id);
+        if let Incompatibility::NoCommonVersion {
+            theirs: (min_version, _),
+            ..
+        } = reason
+        {
+            if min_version > PROTOCOL_VERSION {
+                self.maybe_broadcast_upgrade_notice(min_version);
+            }
+        }
+    }
+
+    /// Dial the next batch of discovery candidates, skipping peers that are
+    /// already connected.
+    fn dial_next_batch(&mut self) {
+        for addr in self.discovery.next_batch(BOOTSTRAP_BATCH) {
+            if let Some(Protocol::P2p(peer_id)) = addr.iter().last() {
+                if self.swarm.is_connected(&peer_id) {
+                    continue;
+                }
+            }
+            if let Err(e) = self.swarm.dial(addr.clone()) {
+                debug!(%addr, "failed to dial peer: {}", e);
+            }
+        }
+    }
+
+    /// Resolve the DNS seeds in the background and send the addresses to
+    /// `tx`. Returns false if there are no seeds.
+    fn resolve_dns_seeds(&self, tx: &mpsc::Sender<Vec<Multiaddr>>) -> bool {
+        let seeds = self.discovery.dns_seeds().to_vec();
+        if seeds.is_empty() {
+            return false;
+        }
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            let _ = tx.send(resolve_dns_seeds(&seeds).await).await;
+        });
+        true
+    }
+
+    fn listen_via_relays(&mut self) {
+        if self.listening_via_relays {
+            return;
+        }
+        self.listening_via_relays = true;
+        if self.relay_addrs.is_empty() {
+            warn!("node is not publicly reachable and no relays are configured");
+            return;
+        }
+        for addr in self.relay_addrs.clone() {
+            let circuit = addr.with(Protocol::P2pCircuit);
+            match self.swarm.listen_on(circuit.clone()) {
+                Ok(_) => info!(%circuit, "listening through relay"),
+                Err(e) => warn!(%circuit, "failed to listen through relay: {}", e),
+            }
+        }
+    }
+
+    /// Record gossiped spindle coverage in the registry. Invalid
+    /// advertisements count against the peer that relayed them.
+    fn observe_spindle_coverage(&mut self, msg: &NornMessage, source: PeerId) {
+        if let NornMessage::SpindleCoverage(coverage) = msg {
+            let result = match self.spindle_registry.lock() {
+                Ok(mut registry) => registry.advertise_coverage(coverage.clone()),
+                Err(_) => return,
+            };
+            if let Err(e) = result {
+                debug!("ignoring spindle coverage: {}", e);
+                self.penalize(source, Misbehavior::InvalidMessage);
+            }
+        }
+    }
+
+    /// Record a gossiped or fetched thread checkpoint. Checkpoints with an
+    /// invalid signature count against the peer that relayed them.
+    fn observe_checkpoint(&mut self, msg: &NornMessage, source: PeerId) {
+        if let NornMessage::Checkpoint(checkpoint) = msg {
+            if let Err(e) = self.checkpoints.insert(checkpoint) {
+                debug!("ignoring checkpoint: {}", e);
+                self.penalize(source, Misbehavior::InvalidMessage);
+            }
+        }
+    }
+
+    /// Penalize a peer, disconnecting it if that bans it.
+    fn penalize(&mut self, peer_id: PeerId, misbehavior: Misbehavior) {
+        if self.peer_manager.penalize(&peer_id, misbehavior) {
+            self.disconnect_banned(peer_id);
+        } else {
+            self.publish_peer_stats();
+        }
+    }
+
+    /// Count a message from a peer, returning true (and dropping the
+    /// peer) if it is now banned for flooding.
+    /// Account for a message of `bytes` from a peer. Returns true if it must
+    /// be dropped because the peer is over its rate limits. A peer that
+    /// keeps flooding is penalized, and disconnected once banned.
+    fn throttled(&mut self, peer_id: PeerId, bytes: usize) -> bool {
+        if self.bandwidth.admit_inbound(&peer_id, bytes) {
+            return false;
+        }
+        debug!(%peer_id, bytes, "peer over its rate limits, dropping message");
+        if self.peer_manager.record_throttled(&peer_id) {
+            self.disconnect_banned(peer_id);
+        }
+        true
+    }
+
+    /// Send a direct request, or queue it while the peer is over its
+    /// outbound limit. A full queue drops its oldest message.
+    fn send_direct(&mut self, peer_id: PeerId, msg: NornMessage) {
+        let queue = self.outbound_queues.entry(peer_id).or_default();
+        if queue.is_empty() && self.bandwidth.admit_outbound(&peer_id, message_size(&msg)) {
+            self.swarm
+                .behaviour_mut()
+                .request_response
+                .send_request(&peer_id, msg);
+            return;
+        }
+        if queue.len() >= MAX_QUEUED_PER_PEER {
+            queue.pop_front();
+            warn!(%peer_id, "outbound queue full, dropping oldest direct message");
+        }
+        queue.push_back(msg);
+    }
+
+    /// Send queued direct messages whose peers have outbound budget again.
+    fn flush_outbound_queues(&mut self) {
+        for (peer_id, queue) in self.outbound_queues.iter_mut() {
+            while let Some(msg) = queue.front() {
+                if !self.bandwidth.admit_outbound(peer_id, message_size(msg)) {
+                    break;
+                }
+                if let Some(msg) = queue.pop_front() {
+                    self.swarm
+                        .behaviour_mut()
+                        .request_response
+                        .send_request(peer_id, msg);
+                }
+            }
+        }
+        self.outbound_queues.retain(|_, queue| !queue.is_empty());
+    }
+
+    fn publish_bandwidth_stats(&mut self) {
+        let stats = self.bandwidth.stats();
+        if let Ok(mut shared) = self.bandwidth_stats_shared.lock() {
+            *shared = stats;
+        }
+    }
+
+    fn disconnect_banned(&mut self, peer_id: PeerId) {
+        let _ = self.swarm.disconnect_peer_id(peer_id);
+        self.publish_peer_stats();
+    }
+
+    /// Refresh the peer stats snapshot shared with `RelayHandle`.
+    fn publish_peer_stats(&self) {
+        if let Ok(mut stats) = self.peer_stats_shared.lock() {
+            *stats = self.peer_manager.stats();
+        }
+    }
+
+    /// Record gossiped commitments and full blocks for compact block relay.
+    fn observe_block_relay(&mut self, msg: &NornMessage) {
+        match msg {
+            NornMessage::Commitment(commitment) => {
+                self.compact_pool.observe_commitment(commitment);
+            }
+            NornMessage::Block(block) => self.compact_pool.remember_block(block),
+            _ => {}
+        }
+    }
+
+    /// Rebuild a gossiped compact block, asking the peer that relayed it for
+    /// any commitments not already seen. The block's original publisher is
+    /// asked if that peer cannot serve them.
+    fn receive_compact_block(
+        &mut self,
+        compact: CompactBlock,
+        propagation_source: PeerId,
+        publisher: Option<PeerId>,
+    ) {
+        let block_hash = compact.header.hash;
+        let fallback = publisher.filter(|p| *p != propagation_source);
+        match self.compact_pool.receive(compact, fallback) {
+            Ok(Reconstruction::Complete(block)) => {
+                let _ = self
+                    .message_tx
+                    .send((NornMessage::Block(block), Some(propagation_source)));
+            }
+            Ok(Reconstruction::Missing(indexes)) => {
+                debug!(
+                    %propagation_source,
+                    missing = indexes.len(),
+                    "fetching missing commitments for compact block"
+                );
+                self.swarm.behaviour_mut().request_response.send_request(
+                    &propagation_source,
+                    NornMessage::GetBlockCommitments {
+                        block_hash,
+                        indexes,
+                    },
+                );
+            }
+            Ok(Reconstruction::Duplicate) => {}
+            Err(e) => {
+                warn!(%propagation_source, "invalid compact block: {}", e);
+                self.penalize(propagation_source, Misbehavior::ProtocolViolation);
+            }
+        }
+    }
+
+    /// Apply commitments a peer returned for a pending compact block.
+    fn fill_compact_block(
+        &mut self,
+        block_hash: Hash,
+        commitments: Vec<CommitmentUpdate>,
+        peer: PeerId,
+    ) {
+        match self.compact_pool.fill(&block_hash, commitments) {
+            Some(Reconstruction::Complete(block)) => {
+                let _ = self
+                    .message_tx
+                    .send((NornMessage::Block(block), Some(peer)));
+            }
+            Some(Reconstruction::Missing(indexes)) => {
+                match self.compact_pool.take_fallback(&block_hash) {
+                    Some(fallback) => {
+                        self.swarm.behaviour_mut().request_response.send_request(
+                            &fallback,
+                            NornMessage::GetBlockCommitments {
+                                block_hash,
+                                indexes,
+                            },
+                        );
+                    }
+                    None => debug!(
+                        %peer,
+                        missing = indexes.len(),
+                        "could not fetch commitments for compact block"
+                    ),
+                }
+            }
+            Some(Reconstruction::Duplicate) | None => {}
+        }
+    }
+
     /// Rate-limited upgrade notice: broadcast once per observed version.
     fn maybe_broadcast_upgrade_notice(&mut self, detected_version: u8) {
         if !self.notified_versions.insert(detected_version) {
@@ -536,8 +1187,8 @@ pub fn versioned_topic_for_message(msg: &NornMessage) -> String {
 /// Returns the legacy (unversioned) gossipsub topic name for the given message type.
 pub fn legacy_topic_for_message(msg: &NornMessage) -> &'static str {
     match msg {
-        NornMessage::Block(_) => BLOCKS_TOPIC,
-        NornMessage::Commitment(_) => COMMITMENTS_TOPIC,
+        NornMessage::Block(_) | NornMessage::CompactBlock(_) => BLOCKS_TOPIC,
+        NornMessage::Commitment(_) | NornMessage::Checkpoint(_) => COMMITMENTS_TOPIC,
         NornMessage::FraudProof(_) => FRAUD_PROOFS_TOPIC,
         _ => GENERAL_TOPIC,
     }
@@ -548,27 +1199,69 @@ pub fn topic_for_message(msg: &NornMessage) -> &'static str {
     legacy_topic_for_message(msg)
 }
 
+/// Relays to listen through: the configured ones, or else the boot nodes
+/// whose address names a peer ID. A relay address must end in
+/// `/p2p/<peer id>`; others are logged and skipped.
+fn relay_candidates(relays: &[String], boot_addrs: &[Multiaddr]) -> Vec<Multiaddr> {
+    let has_peer_id = |addr: &Multiaddr| matches!(addr.iter().last(), Some(Protocol::P2p(_)));
+    if relays.is_empty() {
+        return boot_addrs
+            .iter()
+            .filter(|a| has_peer_id(a))
+            .cloned()
+            .collect();
+    }
+    relays
+        .iter()
+        .filter_map(|s| match s.parse::<Multiaddr>() {
+            Ok(addr) if has_peer_id(&addr) => Some(addr),
+            Ok(_) => {
+                warn!("relay address '{}' has no /p2p/ peer ID, skipping", s);
+                None
+            }
+            Err(e) => {
+                warn!("invalid relay multiaddr '{}': {}", s, e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Encoded size of a message, for bandwidth accounting.
+fn message_size<T: borsh::BorshSerialize>(msg: &T) -> usize {
+    borsh::object_length(msg).unwrap_or(0)
+}
+
 /// Parse `"norn/{version}"` from an identify agent_version string.
 fn parse_agent_version(agent: &str) -> Option<u8> {
     agent.strip_prefix("norn/")?.parse().ok()
 }
 
 /// Simple hex encoder to avoid adding a dependency.
-fn hex_encode(bytes: &[u8]) -> String {
+pub(crate) fn hex_encode(bytes: &[u8]) -> String {
     bytes.iter().map(|b| format!("{:02x}", b)).collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::bandwidth::RateLimits;
+    use crate::config::NatConfig;
     use std::net::SocketAddr;
 
     fn test_config(port: u16) -> RelayConfig {
         RelayConfig {
             listen_addr: SocketAddr::from(([127, 0, 0, 1], port)),
             boot_nodes: vec![],
+            dns_seeds: vec![],
+            network_id: "dev".to_string(),
+            genesis_hash: [0u8; 32],
+            capabilities: Capabilities::default(),
             max_connections: 50,
             keypair_seed: None,
+            data_dir: None,
+            nat: NatConfig::default(),
+            rate_limits: RateLimits::default(),
         }
     }
 
@@ -584,8 +1277,15 @@ mod tests {
         let config = RelayConfig {
             listen_addr: SocketAddr::from(([127, 0, 0, 1], 0)),
             boot_nodes: vec![],
+            dns_seeds: vec![],
+            network_id: "dev".to_string(),
+            genesis_hash: [0u8; 32],
+            capabilities: Capabilities::default(),
             max_connections: 50,
             keypair_seed: Some([42u8; 32]),
+            data_dir: None,
+            nat: NatConfig::default(),
+            rate_limits: RateLimits::default(),
         };
         let node1 = RelayNode::new(config.clone()).await.unwrap();
         let node2 = RelayNode::new(config).await.unwrap();
@@ -642,15 +1342,28 @@ mod tests {
             token_mints: vec![],
             token_mints_root: [0u8; 32],
             token_burns: vec![],
+            token_metadata_updates: vec![],
             token_burns_root: [0u8; 32],
+            token_metadata_updates_root: [0u8; 32],
             loom_deploys: vec![],
             loom_deploys_root: [0u8; 32],
+            operator_handovers: vec![],
+            operator_handovers_root: [0u8; 32],
+            key_updates: vec![],
+            key_updates_root: [0u8; 32],
             stake_operations: vec![],
             stake_operations_root: [0u8; 32],
+            slashing_evidence: vec![],
+            slashing_evidence_root: [0u8; 32],
+            spindle_operations: vec![],
+            spindle_operations_root: [0u8; 32],
+            base_fee: 0,
             state_root: [0u8; 32],
+            validator_set_hash: [0u8; 32],
             timestamp: 1000,
             proposer: [0u8; 32],
-            validator_signatures: vec![],
+            proposer_signature: [0u8; 64],
+            certificate: None,
         }));
         assert_eq!(legacy_topic_for_message(&block), BLOCKS_TOPIC);
         assert_eq!(
@@ -669,6 +1382,7 @@ mod tests {
             prev_commitment_hash: [0u8; 32],
             knot_count: 5,
             timestamp: 1000,
+            priority_fee: 0,
             signature: [4u8; 64],
         });
         assert_eq!(legacy_topic_for_message(&commitment), COMMITMENTS_TOPIC);
@@ -698,6 +1412,7 @@ mod tests {
                         },
                     ),
                     signatures: vec![],
+                    cosignatures: Vec::new(),
                 }),
                 reason: "test".to_string(),
             },
@@ -732,6 +1447,25 @@ mod tests {
         assert_eq!(legacy_topic_for_message(&relay), GENERAL_TOPIC);
     }
 
+    #[test]
+    fn test_relay_candidates() {
+        let relay =
+            "/ip4/1.2.3.4/tcp/9740/p2p/12D3KooWDpJ7As7BWAwRMfu1VU2WCqNjvq387JEYKDBj4kx6nXTN";
+        let boot: Vec<Multiaddr> = vec![
+            relay.parse().unwrap(),
+            "/dns4/seed.norn.network/tcp/9740".parse().unwrap(),
+        ];
+        // Boot nodes with a peer ID are used when no relays are configured.
+        assert_eq!(relay_candidates(&[], &boot), vec![boot[0].clone()]);
+
+        let configured = vec![
+            relay.to_string(),
+            "/ip4/5.6.7.8/tcp/9740".to_string(),
+            "not-a-multiaddr".to_string(),
+        ];
+        assert_eq!(relay_candidates(&configured, &[]), vec![boot[0].clone()]);
+    }
+
     #[test]
     fn test_parse_agent_version() {
         assert_eq!(parse_agent_version("norn/4"), Some(4));
@@ -753,6 +1487,50 @@ mod tests {
         );
     }
 
+    /// Integration test: nodes on the same network complete the handshake,
+    /// a node on another network is disconnected.
+    /// Marked as ignored because it requires real networking and may be flaky in CI.
+    #[tokio::test]
+    #[ignore]
+    async fn test_handshake_rejects_other_network() {
+        use tokio::time::{sleep, timeout, Duration};
+
+        async fn spawn_node(
+            seed: u8,
+            network_id: &str,
+            boot_nodes: Vec<String>,
+        ) -> (RelayHandle, String) {
+            let mut config = test_config(0);
+            config.keypair_seed = Some([seed; 32]);
+            config.network_id = network_id.to_string();
+            config.boot_nodes = boot_nodes;
+            let mut node = RelayNode::new(config).await.unwrap();
+            let listen_addr = loop {
+                match timeout(Duration::from_secs(5), node.swarm.next()).await {
+                    Ok(Some(SwarmEvent::NewListenAddr { address, .. })) => break address,
+                    Ok(Some(_)) => continue,
+                    _ => panic!("node did not start listening"),
+                }
+            };
+            let addr = format!("{}/p2p/{}", listen_addr, node.local_peer_id());
+            let handle = node.handle();
+            tokio::spawn(async move { node.run().await });
+            (handle, addr)
+        }
+
+        let (_hub, hub_addr) = spawn_node(11, "testnet", vec![]).await;
+        let (same, _) = spawn_node(12, "testnet", vec![hub_addr.clone()]).await;
+        let (other, _) = spawn_node(13, "mainnet", vec![hub_addr]).await;
+        sleep(Duration::from_secs(3)).await;
+
+        assert!(same
+            .peer_stats()
+            .peers
+            .iter()
+            .any(|peer| peer.protocol_version == Some(PROTOCOL_VERSION)));
+        assert!(other.connected_peers().is_empty());
+    }
+
     /// Integration test: two relay nodes exchange a direct message.
     /// Marked as ignored because it requires real networking and may be flaky in CI.
     #[tokio::test]
@@ -764,8 +1542,15 @@ mod tests {
         let config1 = RelayConfig {
             listen_addr: SocketAddr::from(([127, 0, 0, 1], 0)),
             boot_nodes: vec![],
+            dns_seeds: vec![],
+            network_id: "dev".to_string(),
+            genesis_hash: [0u8; 32],
+            capabilities: Capabilities::default(),
             max_connections: 50,
             keypair_seed: Some([1u8; 32]),
+            data_dir: None,
+            nat: NatConfig::default(),
+            rate_limits: RateLimits::default(),
         };
         let mut node1 = RelayNode::new(config1).await.unwrap();
         let peer1 = node1.local_peer_id();
@@ -785,8 +1570,15 @@ mod tests {
         let config2 = RelayConfig {
             listen_addr: SocketAddr::from(([127, 0, 0, 1], 0)),
             boot_nodes: vec![format!("{}/p2p/{}", listen_addr1, peer1)],
+            dns_seeds: vec![],
+            network_id: "dev".to_string(),
+            genesis_hash: [0u8; 32],
+            capabilities: Capabilities::default(),
             max_connections: 50,
             keypair_seed: Some([2u8; 32]),
+            data_dir: None,
+            nat: NatConfig::default(),
+            rate_limits: RateLimits::default(),
         };
         let mut node2 = RelayNode::new(config2).await.unwrap();
         let _rx2 = node2.subscribe();
@@ -1,10 +1,23 @@
-use norn_types::network::SpindleRegistration;
-use norn_types::primitives::Address;
+use norn_crypto::address::pubkey_to_address;
+use norn_crypto::keys::verify;
+use norn_types::constants::{MAX_SPINDLE_COVERAGE_RANGES, SPINDLE_COVERAGE_TTL_SECS};
+use norn_types::network::{
+    spindle_coverage_signing_data, SpindleCoverage, SpindleRegistration, ThreadRange,
+};
+use norn_types::primitives::{Address, ThreadId, Timestamp};
 use std::collections::HashMap;
 
+use crate::error::RelayError;
+
 /// Registry of spindles known to this relay node.
+///
+/// Besides registrations, the registry keeps the latest coverage
+/// advertisement from each spindle. Spindles use it to split the thread ID
+/// space so that every thread is watched by `k` of them, and wallets use it
+/// to check how many spindles watch their thread.
 pub struct SpindleRegistry {
     spindles: HashMap<Address, SpindleRegistration>,
+    coverage: HashMap<Address, SpindleCoverage>,
 }
 
 impl SpindleRegistry {
@@ -12,6 +25,7 @@ impl SpindleRegistry {
     pub fn new() -> Self {
         Self {
             spindles: HashMap::new(),
+            coverage: HashMap::new(),
         }
     }
 
@@ -39,6 +53,180 @@ impl SpindleRegistry {
     pub fn is_registered(&self, address: &Address) -> bool {
         self.spindles.contains_key(address)
     }
+
+    /// Record a spindle's coverage advertisement. Returns `Ok(false)` if an
+    /// advertisement at least as recent is already known.
+    pub fn advertise_coverage(&mut self, coverage: SpindleCoverage) -> Result<bool, RelayError> {
+        if pubkey_to_address(&coverage.pubkey) != coverage.address {
+            return Err(invalid_coverage("address does not match public key"));
+        }
+        if coverage.ranges.len() > MAX_SPINDLE_COVERAGE_RANGES {
+            return Err(invalid_coverage(&format!(
+                "{} ranges exceeds maximum {}",
+                coverage.ranges.len(),
+                MAX_SPINDLE_COVERAGE_RANGES
+            )));
+        }
+        if coverage.ranges.iter().any(|r| r.start > r.end) {
+            return Err(invalid_coverage("range start is after its end"));
+        }
+        verify(
+            &spindle_coverage_signing_data(&coverage),
+            &coverage.signature,
+            &coverage.pubkey,
+        )
+        .map_err(|_| invalid_coverage("invalid signature"))?;
+
+        if let Some(existing) = self.coverage.get(&coverage.address) {
+            if existing.timestamp >= coverage.timestamp {
+                return Ok(false);
+            }
+        }
+        self.coverage.insert(coverage.address, coverage);
+        Ok(true)
+    }
+
+    /// Drop coverage advertisements not refreshed within
+    /// `SPINDLE_COVERAGE_TTL_SECS` of `now`.
+    pub fn prune_stale_coverage(&mut self, now: Timestamp) {
+        self.coverage
+            .retain(|_, c| c.timestamp.saturating_add(SPINDLE_COVERAGE_TTL_SECS) >= now);
+    }
+
+    /// Get a spindle's latest coverage advertisement.
+    pub fn coverage(&self, address: &Address) -> Option<&SpindleCoverage> {
+        self.coverage.get(address)
+    }
+
+    /// Spindles whose advertised coverage includes `thread_id`.
+    pub fn watchers(&self, thread_id: &ThreadId) -> Vec<&SpindleCoverage> {
+        let mut watchers: Vec<_> = self
+            .coverage
+            .values()
+            .filter(|c| c.ranges.iter().any(|r| r.contains(thread_id)))
+            .collect();
+        watchers.sort_by_key(|c| c.address);
+        watchers
+    }
+
+    /// Ranges of the thread ID space advertised by fewer than `k` spindles.
+    pub fn under_covered(&self, k: usize) -> Vec<ThreadRange> {
+        // Every point where some range starts or ends splits the space into
+        // intervals whose watcher count is constant.
+        let mut bounds = vec![[0u8; 20]];
+        for coverage in self.coverage.values() {
+            for range in &coverage.ranges {
+                bounds.push(range.start);
+                if let Some(next) = next_thread_id(&range.end) {
+                    bounds.push(next);
+                }
+            }
+        }
+        bounds.sort();
+        bounds.dedup();
+
+        let mut gaps: Vec<ThreadRange> = Vec::new();
+        for (i, start) in bounds.iter().enumerate() {
+            if self.watchers(start).len() >= k {
+                continue;
+            }
+            let end = match bounds.get(i + 1) {
+                Some(next) => prev_thread_id(next),
+                None => [0xffu8; 20],
+            };
+            match gaps.last_mut() {
+                Some(last) if next_thread_id(&last.end) == Some(*start) => last.end = end,
+                _ => gaps.push(ThreadRange { start: *start, end }),
+            }
+        }
+        gaps
+    }
+
+    /// Compute the ranges `spindle` should cover so that every thread is
+    /// watched by `k` spindles.
+    ///
+    /// The thread ID space is split into one segment per spindle with live
+    /// coverage (including `spindle` itself), ordered by address; each
+    /// spindle takes its own segment and the `k - 1` that follow. Every
+    /// spindle derives the same assignment from the same registry, so the
+    /// set converges as advertisements gossip. With fewer than `k` spindles,
+    /// each covers everything.
+    pub fn assign_coverage(&self, spindle: &Address, k: usize) -> Vec<ThreadRange> {
+        let mut members: Vec<Address> = self.coverage.keys().copied().collect();
+        if !members.contains(spindle) {
+            members.push(*spindle);
+        }
+        members.sort();
+        let n = members.len();
+        if k >= n {
+            return vec![ThreadRange::full()];
+        }
+        let index = members.iter().position(|a| a == spindle).unwrap_or(0);
+
+        let mut ranges: Vec<ThreadRange> = Vec::new();
+        let mut segments: Vec<usize> = (0..k.max(1)).map(|j| (index + j) % n).collect();
+        segments.sort_unstable();
+        for segment in segments {
+            let range = segment_range(segment, n);
+            match ranges.last_mut() {
+                Some(last) if next_thread_id(&last.end) == Some(range.start) => {
+                    last.end = range.end
+                }
+                _ => ranges.push(range),
+            }
+        }
+        ranges
+    }
+}
+
+/// The `index`th of `n` equal segments of the thread ID space, split on the
+/// leading 8 bytes.
+fn segment_range(index: usize, n: usize) -> ThreadRange {
+    let boundary = |i: usize| (((i as u128) << 64) / n as u128) as u64;
+    let mut start = [0u8; 20];
+    start[..8].copy_from_slice(&boundary(index).to_be_bytes());
+    let end = if index + 1 >= n {
+        [0xffu8; 20]
+    } else {
+        let mut end = [0xffu8; 20];
+        end[..8].copy_from_slice(&(boundary(index + 1) - 1).to_be_bytes());
+        end
+    };
+    ThreadRange { start, end }
+}
+
+/// The thread ID after `id`, or `None` at the end of the space.
+fn next_thread_id(id: &ThreadId) -> Option<ThreadId> {
+    let mut next = *id;
+    for byte in next.iter_mut().rev() {
+        if *byte == 0xff {
+            *byte = 0;
+        } else {
+            *byte += 1;
+            return Some(next);
+        }
+    }
+    None
+}
+
+/// The thread ID before `id`. `id` must not be all zeros.
+fn prev_thread_id(id: &ThreadId) -> ThreadId {
+    let mut prev = *id;
+    for byte in prev.iter_mut().rev() {
+        if *byte == 0 {
+            *byte = 0xff;
+        } else {
+            *byte -= 1;
+            break;
+        }
+    }
+    prev
+}
+
+fn invalid_coverage(reason: &str) -> RelayError {
+    RelayError::InvalidSpindleCoverage {
+        reason: reason.to_string(),
+    }
 }
 
 impl Default for SpindleRegistry {
@@ -50,6 +238,7 @@ impl Default for SpindleRegistry {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use norn_crypto::keys::Keypair;
     use norn_types::network::SpindleRegistration;
 
     fn make_registration(addr_byte: u8) -> SpindleRegistration {
@@ -116,4 +305,85 @@ mod tests {
         assert!(registry.get(&addr).is_none());
         assert!(!registry.is_registered(&addr));
     }
+
+    fn make_coverage(kp: &Keypair, ranges: Vec<ThreadRange>, timestamp: u64) -> SpindleCoverage {
+        let mut coverage = SpindleCoverage {
+            pubkey: kp.public_key(),
+            address: pubkey_to_address(&kp.public_key()),
+            ranges,
+            timestamp,
+            signature: [0u8; 64],
+        };
+        coverage.signature = kp.sign(&spindle_coverage_signing_data(&coverage));
+        coverage
+    }
+
+    #[test]
+    fn test_advertise_coverage() {
+        let mut registry = SpindleRegistry::new();
+        let kp = Keypair::generate();
+        let coverage = make_coverage(&kp, vec![ThreadRange::full()], 1000);
+        assert!(registry.advertise_coverage(coverage.clone()).unwrap());
+        // Replays and older advertisements are ignored.
+        assert!(!registry.advertise_coverage(coverage.clone()).unwrap());
+        assert!(!registry
+            .advertise_coverage(make_coverage(&kp, vec![], 999))
+            .unwrap());
+        assert_eq!(registry.watchers(&[7u8; 20]).len(), 1);
+
+        let mut forged = make_coverage(&kp, vec![], 2000);
+        forged.ranges.push(ThreadRange::full());
+        assert!(registry.advertise_coverage(forged).is_err());
+
+        registry.prune_stale_coverage(1000 + SPINDLE_COVERAGE_TTL_SECS + 1);
+        assert!(registry.watchers(&[7u8; 20]).is_empty());
+    }
+
+    #[test]
+    fn test_assignment_gives_k_of_n_redundancy() {
+        let keypairs: Vec<Keypair> = (0..5).map(|_| Keypair::generate()).collect();
+        let mut registry = SpindleRegistry::new();
+
+        // Each spindle joins, then every spindle re-advertises its share of
+        // the new membership.
+        for (i, kp) in keypairs.iter().enumerate() {
+            let address = pubkey_to_address(&kp.public_key());
+            let ranges = registry.assign_coverage(&address, 3);
+            registry
+                .advertise_coverage(make_coverage(kp, ranges, 1000 + i as u64))
+                .unwrap();
+        }
+        for kp in &keypairs {
+            let address = pubkey_to_address(&kp.public_key());
+            let ranges = registry.assign_coverage(&address, 3);
+            registry
+                .advertise_coverage(make_coverage(kp, ranges, 2000))
+                .unwrap();
+        }
+
+        assert!(registry.under_covered(3).is_empty());
+        assert_eq!(registry.under_covered(4).len(), 1);
+        for thread_id in [[0u8; 20], [0x80u8; 20], [0xffu8; 20], [0x33u8; 20]] {
+            assert_eq!(registry.watchers(&thread_id).len(), 3);
+        }
+    }
+
+    #[test]
+    fn test_under_covered_reports_gaps() {
+        let mut registry = SpindleRegistry::new();
+        let kp = Keypair::generate();
+        let low = ThreadRange {
+            start: [0u8; 20],
+            end: [0x7fu8; 20],
+        };
+        registry
+            .advertise_coverage(make_coverage(&kp, vec![low], 1000))
+            .unwrap();
+
+        let gaps = registry.under_covered(1);
+        assert_eq!(gaps.len(), 1);
+        assert_eq!(gaps[0].start, next_thread_id(&[0x7fu8; 20]).unwrap());
+        assert_eq!(gaps[0].end, [0xffu8; 20]);
+        assert_eq!(registry.under_covered(2), vec![ThreadRange::full()]);
+    }
 }
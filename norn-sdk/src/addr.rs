@@ -1,6 +1,7 @@
 //! Address utility functions for loom contracts.
 
 use alloc::string::String;
+use alloc::vec::Vec;
 
 use crate::error::ContractError;
 use crate::types::Address;
@@ -26,6 +27,14 @@ pub fn addr_to_hex(addr: &Address) -> String {
     s
 }
 
+/// Address of the thread owned by an Ed25519 public key: the first 20
+/// bytes of its blake3 hash.
+pub fn pubkey_to_addr(public_key: &[u8; 32]) -> Address {
+    let mut addr = [0u8; 20];
+    addr.copy_from_slice(&blake3::hash(public_key).as_bytes()[..20]);
+    addr
+}
+
 /// Parse a hex string (with or without `0x` prefix) into an address.
 ///
 /// Returns `ContractError::InvalidInput` if the string is not valid hex
@@ -47,6 +56,130 @@ pub fn hex_to_addr(hex: &str) -> Result<Address, ContractError> {
     Ok(addr)
 }
 
+/// bech32m human-readable part of mainnet addresses.
+pub const HRP_MAINNET: &str = "norn";
+/// bech32m human-readable part of testnet addresses.
+pub const HRP_TESTNET: &str = "tnorn";
+/// bech32m human-readable part of dev network addresses.
+pub const HRP_DEV: &str = "dnorn";
+
+const BECH32_CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const BECH32M_CONST: u32 = 0x2bc8_30a3;
+
+/// Convert an address to its bech32m form, e.g. `norn1...` for [`HRP_MAINNET`].
+///
+/// A `no_std` port of `norn_crypto::address::address_to_bech32`.
+pub fn addr_to_bech32(addr: &Address, hrp: &str) -> String {
+    let data = regroup(addr, 8, 5, true).unwrap_or_default();
+    let mut values = hrp_expand(hrp);
+    values.extend_from_slice(&data);
+    values.extend_from_slice(&[0; 6]);
+    let pm = bech32_polymod(&values) ^ BECH32M_CONST;
+
+    let mut s = String::with_capacity(hrp.len() + 1 + data.len() + 6);
+    s.push_str(hrp);
+    s.push('1');
+    for d in data {
+        s.push(BECH32_CHARSET[d as usize] as char);
+    }
+    for i in 0..6 {
+        s.push(BECH32_CHARSET[((pm >> (5 * (5 - i))) & 31) as usize] as char);
+    }
+    s
+}
+
+/// Parse a bech32m address, which must use the human-readable part `hrp`.
+///
+/// Returns `ContractError::InvalidInput` on a bad checksum, a different
+/// network's prefix, or a payload that is not 20 bytes.
+pub fn bech32_to_addr(s: &str, hrp: &str) -> Result<Address, ContractError> {
+    let lower = s.to_ascii_lowercase();
+    if lower != s && s.to_ascii_uppercase() != s {
+        return Err(ContractError::invalid_input("mixed-case address"));
+    }
+    let (found, data_part) = lower
+        .rsplit_once('1')
+        .ok_or_else(|| ContractError::invalid_input("missing bech32 separator"))?;
+    if found != hrp {
+        return Err(ContractError::invalid_input(
+            "address is for another network",
+        ));
+    }
+    let mut data = Vec::with_capacity(data_part.len());
+    for c in data_part.bytes() {
+        let v = BECH32_CHARSET
+            .iter()
+            .position(|&x| x == c)
+            .ok_or_else(|| ContractError::invalid_input("invalid bech32 character"))?;
+        data.push(v as u8);
+    }
+    if data.len() != 38 {
+        return Err(ContractError::invalid_input("address must be 20 bytes"));
+    }
+    let mut values = hrp_expand(hrp);
+    values.extend_from_slice(&data);
+    if bech32_polymod(&values) != BECH32M_CONST {
+        return Err(ContractError::invalid_input("invalid address checksum"));
+    }
+    let bytes = regroup(&data[..32], 5, 8, false)
+        .ok_or_else(|| ContractError::invalid_input("invalid address padding"))?;
+    let mut addr = [0u8; 20];
+    addr.copy_from_slice(&bytes);
+    Ok(addr)
+}
+
+fn bech32_polymod(values: &[u8]) -> u32 {
+    const GEN: [u32; 5] = [
+        0x3b6a_57b2,
+        0x2650_8e6d,
+        0x1ea1_19fa,
+        0x3d42_33dd,
+        0x2a14_62b3,
+    ];
+    let mut chk: u32 = 1;
+    for &v in values {
+        let top = chk >> 25;
+        chk = ((chk & 0x01ff_ffff) << 5) ^ v as u32;
+        for (i, g) in GEN.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= g;
+            }
+        }
+    }
+    chk
+}
+
+fn hrp_expand(hrp: &str) -> Vec<u8> {
+    let bytes = hrp.as_bytes();
+    let mut out: Vec<u8> = bytes.iter().map(|b| b >> 5).collect();
+    out.push(0);
+    out.extend(bytes.iter().map(|b| b & 31));
+    out
+}
+
+fn regroup(data: &[u8], from: u32, to: u32, pad: bool) -> Option<Vec<u8>> {
+    let mut acc: u32 = 0;
+    let mut bits: u32 = 0;
+    let max = (1u32 << to) - 1;
+    let mut out = Vec::new();
+    for &value in data {
+        acc = (acc << from) | value as u32;
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            out.push(((acc >> bits) & max) as u8);
+        }
+    }
+    if pad {
+        if bits > 0 {
+            out.push(((acc << (to - bits)) & max) as u8);
+        }
+    } else if bits >= from || (acc << (to - bits)) & max != 0 {
+        return None;
+    }
+    Some(out)
+}
+
 fn hex_nibble(c: u8) -> Result<u8, ContractError> {
     match c {
         b'0'..=b'9' => Ok(c - b'0'),
@@ -55,3 +188,27 @@ fn hex_nibble(c: u8) -> Result<u8, ContractError> {
         _ => Err(ContractError::invalid_input("invalid hex character")),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bech32_matches_norn_crypto() {
+        let addr = [0x5au8; 20];
+        let encoded = addr_to_bech32(&addr, HRP_MAINNET);
+        let (network, decoded) = norn_crypto::address::bech32_to_address(&encoded).unwrap();
+        assert_eq!(network.address_hrp(), HRP_MAINNET);
+        assert_eq!(decoded, addr);
+        assert_eq!(bech32_to_addr(&encoded, HRP_MAINNET).unwrap(), addr);
+        assert!(bech32_to_addr(&encoded, HRP_TESTNET).is_err());
+    }
+
+    #[test]
+    fn test_bech32_rejects_bad_checksum() {
+        let mut encoded = addr_to_bech32(&[1u8; 20], HRP_DEV);
+        let last = encoded.pop().unwrap();
+        encoded.push(if last == 'q' { 'p' } else { 'q' });
+        assert!(bech32_to_addr(&encoded, HRP_DEV).is_err());
+    }
+}
@@ -2,14 +2,14 @@
 //!
 //! The `Contract` trait defines the interface every loom contract implements.
 //! The `Context` struct provides access to host functions (sender, block info,
-//! logging, token transfers).
+//! logging, token transfers, balances).
 
 use alloc::vec::Vec;
 use borsh::{BorshDeserialize, BorshSerialize};
 
 use crate::error::ContractError;
 use crate::response::ContractResult;
-use crate::types::{Address, LoomId, TokenId};
+use crate::types::{Address, CodeId, LoomId, TokenId};
 
 /// The core contract interface. Implement this trait to define your loom.
 ///
@@ -78,9 +78,29 @@ impl Context {
         crate::host::log(msg);
     }
 
+    /// Emit a debug message. Free, and only recorded when the execution is
+    /// traced with `norn_traceLoomExecution`.
+    pub fn debug_log(&self, msg: &str) {
+        crate::host::debug_log(msg);
+    }
+
     /// Transfer tokens between accounts.
-    pub fn transfer(&self, from: &Address, to: &Address, token: &TokenId, amount: u128) {
-        crate::host::transfer(from, to, token, amount);
+    ///
+    /// Returns the amount actually credited to `to`, measured from its balance
+    /// before and after the transfer.
+    pub fn transfer(&self, from: &Address, to: &Address, token: &TokenId, amount: u128) -> u128 {
+        credited_transfer(from, to, token, amount)
+    }
+
+    /// Transfer tokens, failing unless exactly `amount` reaches `to`.
+    pub fn transfer_checked(
+        &self,
+        from: &Address,
+        to: &Address,
+        token: &TokenId,
+        amount: u128,
+    ) -> Result<(), ContractError> {
+        check_credited(self.transfer(from, to, token, amount), amount)
     }
 
     /// Assert that the sender matches `expected`, returning `Unauthorized` if not.
@@ -106,10 +126,77 @@ impl Context {
         crate::host::contract_address()
     }
 
-    /// Transfer tokens from the contract's own balance.
-    pub fn transfer_from_contract(&self, to: &Address, token: &TokenId, amount: u128) {
+    /// Transfer tokens from the contract's own balance, returning the amount
+    /// credited to `to`.
+    pub fn transfer_from_contract(&self, to: &Address, token: &TokenId, amount: u128) -> u128 {
         let contract = self.contract_address();
-        self.transfer(&contract, to, token, amount);
+        self.transfer(&contract, to, token, amount)
+    }
+
+    /// Derive a deterministic sub-address of this contract from `salt`.
+    ///
+    /// Use one per deal or pool to custody funds under distinct addresses.
+    pub fn derive_address(&self, salt: &[u8]) -> Address {
+        crate::host::derive_address(salt)
+    }
+
+    /// Transfer tokens out of the sub-address derived from `salt`, returning
+    /// the amount credited to `to`.
+    pub fn transfer_from_sub_address(
+        &self,
+        salt: &[u8],
+        to: &Address,
+        token: &TokenId,
+        amount: u128,
+    ) -> u128 {
+        let sub = self.derive_address(salt);
+        self.transfer(&sub, to, token, amount)
+    }
+
+    /// Verify a VRF `proof` of `alpha` under `public_key`, returning the
+    /// 64-byte VRF output, or `None` if the proof does not verify.
+    pub fn vrf_verify(
+        &self,
+        public_key: &[u8; 32],
+        alpha: &[u8],
+        proof: &[u8; 80],
+    ) -> Option<[u8; 64]> {
+        crate::host::vrf_verify(public_key, alpha, proof)
+    }
+
+    /// Open an XChaCha20-Poly1305 sealed box, returning the plaintext, or
+    /// `None` if the key is wrong or the ciphertext was tampered with.
+    pub fn decrypt(&self, key: &[u8; 32], nonce: &[u8; 24], ciphertext: &[u8]) -> Option<Vec<u8>> {
+        crate::host::decrypt(key, nonce, ciphertext)
+    }
+
+    /// Whether `signature` is a valid Ed25519 signature of `message` under
+    /// `public_key`. See [`crate::typed_data`] for signatures over typed
+    /// messages.
+    pub fn verify_signature(
+        &self,
+        public_key: &[u8; 32],
+        message: &[u8],
+        signature: &[u8; 64],
+    ) -> bool {
+        crate::host::verify_signature(public_key, message, signature)
+    }
+
+    /// Whether `signature` signs `message` in `domain` under `public_key`.
+    pub fn verify_typed(
+        &self,
+        domain: &crate::typed_data::TypedDomain,
+        message: &crate::typed_data::TypedStruct,
+        public_key: &[u8; 32],
+        signature: &[u8; 64],
+    ) -> bool {
+        self.verify_signature(public_key, &domain.signing_hash(message), signature)
+    }
+
+    /// On-ledger balance of `address` for `token`, including transfers
+    /// already made during this execution.
+    pub fn balance_of(&self, address: &Address, token: &TokenId) -> u128 {
+        crate::host::balance_of(address, token)
     }
 
     /// Call another contract (cross-contract call).
@@ -119,6 +206,14 @@ impl Context {
     pub fn call_contract_raw(&self, target: &LoomId, input: &[u8]) -> Option<Vec<u8>> {
         crate::host::call_contract(target, input)
     }
+
+    /// Instantiate a new loom from stored code, passing `init_msg` to its
+    /// `init`. The loom ID is derived from this contract, `code_id`, and
+    /// `salt`. Returns `None` if the code is unknown, the ID is taken, or
+    /// `init` fails.
+    pub fn instantiate(&self, code_id: &CodeId, init_msg: &[u8], salt: &[u8]) -> Option<LoomId> {
+        crate::host::instantiate(code_id, init_msg, salt)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -188,9 +283,29 @@ impl Context {
         crate::host::log(msg);
     }
 
+    /// Emit a debug message (captured in thread-local storage, accessible via
+    /// `TestEnv::debug_logs()`).
+    pub fn debug_log(&self, msg: &str) {
+        crate::host::debug_log(msg);
+    }
+
     /// Transfer tokens (captured in thread-local log for test assertions).
-    pub fn transfer(&self, from: &Address, to: &Address, token: &TokenId, amount: u128) {
-        crate::host::transfer(from, to, token, amount);
+    ///
+    /// Returns the amount actually credited to `to`, measured from its mock
+    /// balance before and after the transfer.
+    pub fn transfer(&self, from: &Address, to: &Address, token: &TokenId, amount: u128) -> u128 {
+        credited_transfer(from, to, token, amount)
+    }
+
+    /// Transfer tokens, failing unless exactly `amount` reaches `to`.
+    pub fn transfer_checked(
+        &self,
+        from: &Address,
+        to: &Address,
+        token: &TokenId,
+        amount: u128,
+    ) -> Result<(), ContractError> {
+        check_credited(self.transfer(from, to, token, amount), amount)
     }
 
     /// Assert that the sender matches `expected`, returning `Unauthorized` if not.
@@ -216,10 +331,77 @@ impl Context {
         crate::host::contract_address()
     }
 
-    /// Transfer tokens from the contract's own balance.
-    pub fn transfer_from_contract(&self, to: &Address, token: &TokenId, amount: u128) {
+    /// Transfer tokens from the contract's own balance, returning the amount
+    /// credited to `to`.
+    pub fn transfer_from_contract(&self, to: &Address, token: &TokenId, amount: u128) -> u128 {
         let contract = self.contract_address();
-        self.transfer(&contract, to, token, amount);
+        self.transfer(&contract, to, token, amount)
+    }
+
+    /// Derive a deterministic sub-address of this contract from `salt`.
+    ///
+    /// Use one per deal or pool to custody funds under distinct addresses.
+    pub fn derive_address(&self, salt: &[u8]) -> Address {
+        crate::host::derive_address(salt)
+    }
+
+    /// Transfer tokens out of the sub-address derived from `salt`, returning
+    /// the amount credited to `to`.
+    pub fn transfer_from_sub_address(
+        &self,
+        salt: &[u8],
+        to: &Address,
+        token: &TokenId,
+        amount: u128,
+    ) -> u128 {
+        let sub = self.derive_address(salt);
+        self.transfer(&sub, to, token, amount)
+    }
+
+    /// Verify a VRF `proof` of `alpha` under `public_key`, returning the
+    /// 64-byte VRF output, or `None` if the proof does not verify.
+    pub fn vrf_verify(
+        &self,
+        public_key: &[u8; 32],
+        alpha: &[u8],
+        proof: &[u8; 80],
+    ) -> Option<[u8; 64]> {
+        crate::host::vrf_verify(public_key, alpha, proof)
+    }
+
+    /// Open an XChaCha20-Poly1305 sealed box, returning the plaintext, or
+    /// `None` if the key is wrong or the ciphertext was tampered with.
+    pub fn decrypt(&self, key: &[u8; 32], nonce: &[u8; 24], ciphertext: &[u8]) -> Option<Vec<u8>> {
+        crate::host::decrypt(key, nonce, ciphertext)
+    }
+
+    /// Whether `signature` is a valid Ed25519 signature of `message` under
+    /// `public_key`. See [`crate::typed_data`] for signatures over typed
+    /// messages.
+    pub fn verify_signature(
+        &self,
+        public_key: &[u8; 32],
+        message: &[u8],
+        signature: &[u8; 64],
+    ) -> bool {
+        crate::host::verify_signature(public_key, message, signature)
+    }
+
+    /// Whether `signature` signs `message` in `domain` under `public_key`.
+    pub fn verify_typed(
+        &self,
+        domain: &crate::typed_data::TypedDomain,
+        message: &crate::typed_data::TypedStruct,
+        public_key: &[u8; 32],
+        signature: &[u8; 64],
+    ) -> bool {
+        self.verify_signature(public_key, &domain.signing_hash(message), signature)
+    }
+
+    /// On-ledger balance of `address` for `token`, including transfers
+    /// already made during this execution.
+    pub fn balance_of(&self, address: &Address, token: &TokenId) -> u128 {
+        crate::host::balance_of(address, token)
     }
 
     /// Call another contract (cross-contract call).
@@ -229,6 +411,14 @@ impl Context {
     pub fn call_contract_raw(&self, target: &LoomId, input: &[u8]) -> Option<Vec<u8>> {
         crate::host::call_contract(target, input)
     }
+
+    /// Instantiate a new loom from stored code, passing `init_msg` to its
+    /// `init`. The loom ID is derived from this contract, `code_id`, and
+    /// `salt`. Returns `None` if the code is unknown, the ID is taken, or
+    /// `init` fails.
+    pub fn instantiate(&self, code_id: &CodeId, init_msg: &[u8], salt: &[u8]) -> Option<LoomId> {
+        crate::host::instantiate(code_id, init_msg, salt)
+    }
 }
 
 /// Builder for constructing a mock [`Context`] in unit tests.
@@ -285,6 +475,29 @@ impl MockContextBuilder {
     }
 }
 
+/// Queue a transfer and measure how much the recipient's balance grew.
+///
+/// A self-transfer leaves the balance unchanged, so it is reported as fully
+/// credited.
+fn credited_transfer(from: &Address, to: &Address, token: &TokenId, amount: u128) -> u128 {
+    let before = crate::host::balance_of(to, token);
+    crate::host::transfer(from, to, token, amount);
+    if from == to {
+        return amount;
+    }
+    crate::host::balance_of(to, token).saturating_sub(before)
+}
+
+fn check_credited(credited: u128, expected: u128) -> Result<(), ContractError> {
+    if credited == expected {
+        Ok(())
+    } else {
+        Err(ContractError::Custom(alloc::format!(
+            "transfer credited {credited}, expected {expected}"
+        )))
+    }
+}
+
 /// Helper: serialize a contract error into bytes for the output buffer.
 /// Used by the `norn_entry!` macro for error output.
 pub fn error_to_bytes(err: &ContractError) -> alloc::vec::Vec<u8> {
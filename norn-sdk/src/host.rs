@@ -9,12 +9,16 @@
 use alloc::vec;
 use alloc::vec::Vec;
 
+/// Largest amount a single transfer can move — the host ABI carries it as `i64`.
+pub const MAX_TRANSFER_AMOUNT: u128 = i64::MAX as u128;
+
 // ── Raw extern declarations (wasm32 only) ──────────────────────────────────
 
 #[cfg(target_arch = "wasm32")]
 #[link(wasm_import_module = "norn")]
 extern "C" {
     fn norn_log(msg_ptr: i32, msg_len: i32);
+    fn norn_debug_log(msg_ptr: i32, msg_len: i32);
     fn norn_state_get(key_ptr: i32, key_len: i32, out_ptr: i32, out_max_len: i32) -> i32;
     fn norn_state_set(key_ptr: i32, key_len: i32, val_ptr: i32, val_len: i32);
     fn norn_transfer(from_ptr: i32, to_ptr: i32, token_ptr: i32, amount: i64);
@@ -31,6 +35,25 @@ extern "C" {
         output_max_len: i32,
     ) -> i32;
     fn norn_contract_address(out_ptr: i32);
+    fn norn_balance_of(addr_ptr: i32, token_ptr: i32, out_ptr: i32);
+    fn norn_derive_address(salt_ptr: i32, salt_len: i32, out_ptr: i32);
+    fn norn_vrf_verify(
+        pk_ptr: i32,
+        alpha_ptr: i32,
+        alpha_len: i32,
+        proof_ptr: i32,
+        out_ptr: i32,
+    ) -> i32;
+    fn norn_decrypt(key_ptr: i32, nonce_ptr: i32, ct_ptr: i32, ct_len: i32, out_ptr: i32) -> i32;
+    fn norn_verify_signature(pk_ptr: i32, msg_ptr: i32, msg_len: i32, sig_ptr: i32) -> i32;
+    fn norn_instantiate(
+        code_id_ptr: i32,
+        init_ptr: i32,
+        init_len: i32,
+        salt_ptr: i32,
+        salt_len: i32,
+        out_ptr: i32,
+    ) -> i32;
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -45,6 +68,14 @@ pub fn log(msg: &str) {
     }
 }
 
+/// Emit a debug message, recorded only when the execution is traced.
+#[cfg(target_arch = "wasm32")]
+pub fn debug_log(msg: &str) {
+    unsafe {
+        norn_debug_log(msg.as_ptr() as i32, msg.len() as i32);
+    }
+}
+
 /// Read a value from contract state.
 #[cfg(target_arch = "wasm32")]
 pub fn state_get(key: &[u8]) -> Option<Vec<u8>> {
@@ -90,9 +121,13 @@ pub fn state_remove(key: &[u8]) {
     state_set(key, &[]);
 }
 
-/// Transfer tokens.
+/// Transfer tokens. Traps if `amount` exceeds [`MAX_TRANSFER_AMOUNT`].
 #[cfg(target_arch = "wasm32")]
 pub fn transfer(from: &[u8; 20], to: &[u8; 20], token_id: &[u8; 32], amount: u128) {
+    assert!(
+        amount <= MAX_TRANSFER_AMOUNT,
+        "transfer amount exceeds host limit"
+    );
     unsafe {
         norn_transfer(
             from.as_ptr() as i32,
@@ -182,6 +217,113 @@ pub fn contract_address() -> [u8; 20] {
     addr
 }
 
+/// Get the on-ledger balance of `address` for `token_id`, including transfers
+/// already queued during this execution.
+#[cfg(target_arch = "wasm32")]
+pub fn balance_of(address: &[u8; 20], token_id: &[u8; 32]) -> u128 {
+    let mut out = [0u8; 16];
+    unsafe {
+        norn_balance_of(
+            address.as_ptr() as i32,
+            token_id.as_ptr() as i32,
+            out.as_mut_ptr() as i32,
+        );
+    }
+    u128::from_le_bytes(out)
+}
+
+/// Derive a sub-address of this contract from `salt`.
+#[cfg(target_arch = "wasm32")]
+pub fn derive_address(salt: &[u8]) -> [u8; 20] {
+    let mut addr = [0u8; 20];
+    unsafe {
+        norn_derive_address(
+            salt.as_ptr() as i32,
+            salt.len() as i32,
+            addr.as_mut_ptr() as i32,
+        );
+    }
+    addr
+}
+
+/// Verify a VRF `proof` of `alpha` under `public_key`.
+///
+/// Returns the 64-byte VRF output, or `None` if the proof does not verify.
+#[cfg(target_arch = "wasm32")]
+pub fn vrf_verify(public_key: &[u8; 32], alpha: &[u8], proof: &[u8; 80]) -> Option<[u8; 64]> {
+    let mut output = [0u8; 64];
+    let valid = unsafe {
+        norn_vrf_verify(
+            public_key.as_ptr() as i32,
+            alpha.as_ptr() as i32,
+            alpha.len() as i32,
+            proof.as_ptr() as i32,
+            output.as_mut_ptr() as i32,
+        )
+    };
+    (valid == 1).then_some(output)
+}
+
+/// Open an XChaCha20-Poly1305 sealed box with `key` and `nonce`.
+///
+/// Returns the plaintext, or `None` if the key is wrong or the ciphertext
+/// was tampered with.
+#[cfg(target_arch = "wasm32")]
+pub fn decrypt(key: &[u8; 32], nonce: &[u8; 24], ciphertext: &[u8]) -> Option<Vec<u8>> {
+    if ciphertext.len() < 16 {
+        return None;
+    }
+    let mut plaintext = vec![0u8; ciphertext.len() - 16];
+    let opened = unsafe {
+        norn_decrypt(
+            key.as_ptr() as i32,
+            nonce.as_ptr() as i32,
+            ciphertext.as_ptr() as i32,
+            ciphertext.len() as i32,
+            plaintext.as_mut_ptr() as i32,
+        )
+    };
+    (opened == 1).then_some(plaintext)
+}
+
+/// Verify an Ed25519 `signature` of `message` under `public_key`.
+#[cfg(target_arch = "wasm32")]
+pub fn verify_signature(public_key: &[u8; 32], message: &[u8], signature: &[u8; 64]) -> bool {
+    let valid = unsafe {
+        norn_verify_signature(
+            public_key.as_ptr() as i32,
+            message.as_ptr() as i32,
+            message.len() as i32,
+            signature.as_ptr() as i32,
+        )
+    };
+    valid == 1
+}
+
+/// Instantiate a new loom from stored code, running its `init` with `init_msg`.
+///
+/// Returns the new loom ID, or `None` if the code is unknown, the ID derived
+/// from `salt` is taken, or `init` fails.
+#[cfg(target_arch = "wasm32")]
+pub fn instantiate(code_id: &[u8; 32], init_msg: &[u8], salt: &[u8]) -> Option<[u8; 32]> {
+    let mut loom_id = [0u8; 32];
+    let result = unsafe {
+        norn_instantiate(
+            code_id.as_ptr() as i32,
+            init_msg.as_ptr() as i32,
+            init_msg.len() as i32,
+            salt.as_ptr() as i32,
+            salt.len() as i32,
+            loom_id.as_mut_ptr() as i32,
+        )
+    };
+    if result == 0 {
+        Some(loom_id)
+    } else {
+        None
+    }
+}
+
 // ═══════════════════════════════════════════════════════════════════════════
 // Native implementations — thread-local mock storage for `cargo test`
 // ═══════════════════════════════════════════════════════════════════════════
@@ -194,6 +336,7 @@ mod mock {
     use std::vec::Vec;
 
     type TransferRecord = (Vec<u8>, Vec<u8>, Vec<u8>, u128);
+    type BalanceKey = ([u8; 20], [u8; 32]);
 
     /// A captured structured event (type + attributes).
     #[derive(Debug, Clone)]
@@ -202,12 +345,21 @@ mod mock {
         pub attributes: Vec<(String, String)>,
     }
 
+    /// A captured instantiation request.
+    #[derive(Debug, Clone)]
+    pub struct MockInstantiation {
+        pub loom_id: [u8; 32],
+        pub code_id: [u8; 32],
+        pub init_msg: Vec<u8>,
+    }
+
     /// Type alias for a cross-contract call handler function.
     pub type CrossCallHandler = std::boxed::Box<dyn Fn(&[u8; 32], &[u8]) -> Option<Vec<u8>>>;
 
     std::thread_local! {
         static STATE: RefCell<BTreeMap<Vec<u8>, Vec<u8>>> = const { RefCell::new(BTreeMap::new()) };
         static LOGS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+        static DEBUG_LOGS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
         static SENDER: RefCell<[u8; 20]> = const { RefCell::new([0u8; 20]) };
         static BLOCK_HEIGHT: RefCell<u64> = const { RefCell::new(0) };
         static TIMESTAMP: RefCell<u64> = const { RefCell::new(0) };
@@ -215,6 +367,8 @@ mod mock {
         static EVENTS: RefCell<Vec<MockEvent>> = const { RefCell::new(Vec::new()) };
         static CROSS_CALL_HANDLER: RefCell<Option<CrossCallHandler>> = const { RefCell::new(None) };
         static CONTRACT_ADDRESS: RefCell<[u8; 20]> = const { RefCell::new([0u8; 20]) };
+        static BALANCES: RefCell<BTreeMap<BalanceKey, u128>> = const { RefCell::new(BTreeMap::new()) };
+        static INSTANTIATIONS: RefCell<Vec<MockInstantiation>> = const { RefCell::new(Vec::new()) };
     }
 
     // ── Host function implementations ──────────────────────────────────────
@@ -223,6 +377,10 @@ mod mock {
         LOGS.with(|logs| logs.borrow_mut().push(String::from(msg)));
     }
 
+    pub fn debug_log(msg: &str) {
+        DEBUG_LOGS.with(|logs| logs.borrow_mut().push(String::from(msg)));
+    }
+
     pub fn state_get(key: &[u8]) -> Option<Vec<u8>> {
         STATE.with(|state| state.borrow().get(key).cloned())
     }
@@ -244,10 +402,71 @@ mod mock {
     }
 
     pub fn transfer(from: &[u8; 20], to: &[u8; 20], token_id: &[u8; 32], amount: u128) {
+        assert!(
+            amount <= super::MAX_TRANSFER_AMOUNT,
+            "transfer amount exceeds host limit"
+        );
         TRANSFERS.with(|t| {
             t.borrow_mut()
                 .push((from.to_vec(), to.to_vec(), token_id.to_vec(), amount));
         });
+        // Mock transfers settle immediately so `balance_of` reflects them.
+        BALANCES.with(|b| {
+            let mut b = b.borrow_mut();
+            let from_bal = b.entry((*from, *token_id)).or_insert(0);
+            *from_bal = from_bal.saturating_sub(amount);
+            let to_bal = b.entry((*to, *token_id)).or_insert(0);
+            *to_bal = to_bal.saturating_add(amount);
+        });
+    }
+
+    pub fn balance_of(address: &[u8; 20], token_id: &[u8; 32]) -> u128 {
+        BALANCES.with(|b| b.borrow().get(&(*address, *token_id)).copied().unwrap_or(0))
+    }
+
+    pub fn derive_address(salt: &[u8]) -> [u8; 20] {
+        // The mock has no loom ID, so derive from the mock contract address.
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(b"norn_sub_address");
+        hasher.update(&contract_address());
+        hasher.update(salt);
+        let mut addr = [0u8; 20];
+        addr.copy_from_slice(&hasher.finalize().as_bytes()[..20]);
+        addr
+    }
+
+    pub fn vrf_verify(public_key: &[u8; 32], alpha: &[u8], proof: &[u8; 80]) -> Option<[u8; 64]> {
+        norn_crypto::vrf::verify(public_key, alpha, proof).ok()
+    }
+
+    pub fn decrypt(key: &[u8; 32], nonce: &[u8; 24], ciphertext: &[u8]) -> Option<Vec<u8>> {
+        norn_crypto::encryption::open(key, nonce, ciphertext).ok()
+    }
+
+    pub fn verify_signature(public_key: &[u8; 32], message: &[u8], signature: &[u8; 64]) -> bool {
+        norn_crypto::keys::verify(message, signature, public_key).is_ok()
+    }
+
+    pub fn instantiate(code_id: &[u8; 32], init_msg: &[u8], salt: &[u8]) -> Option<[u8; 32]> {
+        // The mock has no loom ID, so the mock contract address is the creator.
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(b"norn_instance");
+        hasher.update(&contract_address());
+        hasher.update(code_id);
+        hasher.update(salt);
+        let loom_id = *hasher.finalize().as_bytes();
+        INSTANTIATIONS.with(|i| {
+            let mut list = i.borrow_mut();
+            if list.iter().any(|inst| inst.loom_id == loom_id) {
+                return None;
+            }
+            list.push(MockInstantiation {
+                loom_id,
+                code_id: *code_id,
+                init_msg: init_msg.to_vec(),
+            });
+            Some(loom_id)
+        })
     }
 
     pub fn sender() -> [u8; 20] {
@@ -291,6 +510,7 @@ mod mock {
     pub fn mock_reset() {
         STATE.with(|s| s.borrow_mut().clear());
         LOGS.with(|l| l.borrow_mut().clear());
+        DEBUG_LOGS.with(|l| l.borrow_mut().clear());
         SENDER.with(|s| *s.borrow_mut() = [0u8; 20]);
         BLOCK_HEIGHT.with(|h| *h.borrow_mut() = 0);
         TIMESTAMP.with(|t| *t.borrow_mut() = 0);
@@ -298,6 +518,8 @@ mod mock {
         EVENTS.with(|e| e.borrow_mut().clear());
         CROSS_CALL_HANDLER.with(|h| *h.borrow_mut() = None);
         CONTRACT_ADDRESS.with(|a| *a.borrow_mut() = [0u8; 20]);
+        BALANCES.with(|b| b.borrow_mut().clear());
+        INSTANTIATIONS.with(|i| i.borrow_mut().clear());
     }
 
     pub fn mock_set_cross_call_handler<F>(handler: F)
@@ -315,6 +537,10 @@ mod mock {
         CONTRACT_ADDRESS.with(|a| *a.borrow_mut() = addr);
     }
 
+    pub fn mock_set_balance(address: [u8; 20], token_id: [u8; 32], amount: u128) {
+        BALANCES.with(|b| b.borrow_mut().insert((address, token_id), amount));
+    }
+
     pub fn mock_set_block_height(h: u64) {
         BLOCK_HEIGHT.with(|bh| *bh.borrow_mut() = h);
     }
@@ -331,6 +557,10 @@ mod mock {
         LOGS.with(|l| l.borrow_mut().clear());
     }
 
+    pub fn mock_get_debug_logs() -> Vec<String> {
+        DEBUG_LOGS.with(|l| l.borrow().clone())
+    }
+
     pub fn mock_get_events() -> Vec<MockEvent> {
         EVENTS.with(|e| e.borrow().clone())
     }
@@ -346,6 +576,10 @@ mod mock {
     pub fn mock_reset_transfers() {
         TRANSFERS.with(|t| t.borrow_mut().clear());
     }
+
+    pub fn mock_get_instantiations() -> Vec<MockInstantiation> {
+        INSTANTIATIONS.with(|i| i.borrow().clone())
+    }
 }
 
 // ── Re-export native stubs as public module-level functions ────────────────
@@ -355,6 +589,11 @@ pub fn log(msg: &str) {
     mock::log(msg);
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+pub fn debug_log(msg: &str) {
+    mock::debug_log(msg);
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 pub fn state_get(key: &[u8]) -> Option<Vec<u8>> {
     mock::state_get(key)
@@ -406,6 +645,46 @@ pub fn contract_address() -> [u8; 20] {
     mock::contract_address()
 }
 
+/// Derive a mock sub-address from the mock contract address and `salt`.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn derive_address(salt: &[u8]) -> [u8; 20] {
+    mock::derive_address(salt)
+}
+
+/// Verify a VRF `proof` of `alpha` under `public_key`, as the host would.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn vrf_verify(public_key: &[u8; 32], alpha: &[u8], proof: &[u8; 80]) -> Option<[u8; 64]> {
+    mock::vrf_verify(public_key, alpha, proof)
+}
+
+/// Open an XChaCha20-Poly1305 sealed box, as the host would.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn decrypt(key: &[u8; 32], nonce: &[u8; 24], ciphertext: &[u8]) -> Option<Vec<u8>> {
+    mock::decrypt(key, nonce, ciphertext)
+}
+
+/// Verify an Ed25519 signature, as the host would.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn verify_signature(public_key: &[u8; 32], message: &[u8], signature: &[u8; 64]) -> bool {
+    mock::verify_signature(public_key, message, signature)
+}
+
+/// Record a mock instantiation and return the derived loom ID.
+///
+/// Returns `None` if the same code and salt were already instantiated.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn instantiate(code_id: &[u8; 32], init_msg: &[u8], salt: &[u8]) -> Option<[u8; 32]> {
+    mock::instantiate(code_id, init_msg, salt)
+}
+
+/// Get the mock balance of `address` for `token_id`.
+///
+/// Seed balances with `mock_set_balance()`; mock transfers move them immediately.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn balance_of(address: &[u8; 20], token_id: &[u8; 32]) -> u128 {
+    mock::balance_of(address, token_id)
+}
+
 // ── Mock control (native only, public) ─────────────────────────────────────
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -423,6 +702,11 @@ pub fn mock_set_contract_address(addr: [u8; 20]) {
     mock::mock_set_contract_address(addr);
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+pub fn mock_set_balance(address: [u8; 20], token_id: [u8; 32], amount: u128) {
+    mock::mock_set_balance(address, token_id, amount);
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 pub fn mock_set_block_height(h: u64) {
     mock::mock_set_block_height(h);
@@ -443,6 +727,11 @@ pub fn mock_reset_logs() {
     mock::mock_reset_logs();
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+pub fn mock_get_debug_logs() -> Vec<alloc::string::String> {
+    mock::mock_get_debug_logs()
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 pub fn emit_event(ty: &str, attributes: &[crate::response::Attribute]) {
     mock::emit_event(ty, attributes);
@@ -481,6 +770,15 @@ pub fn mock_reset_transfers() {
     mock::mock_reset_transfers();
 }
 
+/// Instantiation captured during mock execution.
+#[cfg(not(target_arch = "wasm32"))]
+pub use mock::MockInstantiation;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn mock_get_instantiations() -> alloc::vec::Vec<MockInstantiation> {
+    mock::mock_get_instantiations()
+}
+
 /// Set a mock handler for cross-contract calls in tests.
 ///
 /// The handler receives `(target_loom_id, input_bytes)` and returns
@@ -66,6 +66,9 @@ pub mod storage;
 
 // -- SDK v6 modules --
 pub mod math;
+pub mod merkle;
+pub mod sealed_box;
+pub mod typed_data;
 
 // -- SDK v3 standard library --
 pub mod stdlib;
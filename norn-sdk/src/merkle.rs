@@ -0,0 +1,62 @@
+//! Merkle proof verification for contracts.
+//!
+//! A `no_std` port of the binary Merkle helpers in `norn_crypto::merkle`:
+//! leaves are `H(0x00 || data)`, parents hash their children in sorted order
+//! as `H(0x01 || min || max)`, and odd nodes are promoted unchanged. Trees
+//! built off-chain with `norn_crypto::merkle::build_merkle_root` verify here.
+
+/// A 32-byte BLAKE3 hash.
+pub type Hash = [u8; 32];
+
+/// Hash a list leaf: H(0x00 || data).
+pub fn hash_list_leaf(data: &[u8]) -> Hash {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[0x00]);
+    hasher.update(data);
+    *hasher.finalize().as_bytes()
+}
+
+/// Hash two sibling nodes in sorted order: H(0x01 || min || max).
+pub fn hash_sorted_pair(a: &Hash, b: &Hash) -> Hash {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[0x01]);
+    hasher.update(left);
+    hasher.update(right);
+    *hasher.finalize().as_bytes()
+}
+
+/// Verify that `leaf` is included under `root` given its sibling path.
+pub fn verify_merkle_proof(root: &Hash, leaf: &Hash, proof: &[Hash]) -> bool {
+    let computed = proof
+        .iter()
+        .fold(*leaf, |acc, sibling| hash_sorted_pair(&acc, sibling));
+    &computed == root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+    use norn_crypto::merkle;
+
+    #[test]
+    fn test_matches_norn_crypto() {
+        assert_eq!(hash_list_leaf(b"leaf"), merkle::hash_list_leaf(b"leaf"));
+
+        let leaves: Vec<Hash> = (0..7u8).map(|i| merkle::hash_list_leaf(&[i])).collect();
+        let root = merkle::build_merkle_root(&leaves);
+        for (i, leaf) in leaves.iter().enumerate() {
+            let proof = merkle::build_merkle_proof(&leaves, i).unwrap();
+            assert!(verify_merkle_proof(&root, leaf, &proof));
+        }
+    }
+
+    #[test]
+    fn test_rejects_wrong_leaf() {
+        let leaves: Vec<Hash> = (0..4u8).map(|i| hash_list_leaf(&[i])).collect();
+        let root = norn_crypto::merkle::build_merkle_root(&leaves);
+        let proof = norn_crypto::merkle::build_merkle_proof(&leaves, 0).unwrap();
+        assert!(!verify_merkle_proof(&root, &leaves[1], &proof));
+    }
+}
@@ -10,17 +10,30 @@ pub use crate::error::ContractError;
 pub use crate::response::{
     ok, ok_bytes, ok_empty, Attribute, ContractResult, Event, Response, ToAttributeValue,
 };
-pub use crate::types::{Address, Empty, LoomId, TokenId};
+pub use crate::types::{Address, CodeId, Empty, LoomId, TokenId, NATIVE_TOKEN_ID};
 
 // SDK v3 — storage, guards, address helpers
-pub use crate::addr::{addr_to_hex, hex_to_addr, ZERO_ADDRESS};
+pub use crate::addr::{
+    addr_to_bech32, addr_to_hex, bech32_to_addr, hex_to_addr, pubkey_to_addr, ZERO_ADDRESS,
+};
 pub use crate::storage::{IndexedMap, Item, Map, StorageKey};
 
 // SDK v6 — safe math
 pub use crate::math::{safe_add, safe_add_u64, safe_mul, safe_mul_u64, safe_sub, safe_sub_u64};
 
+// Merkle proof verification
+pub use crate::merkle::{hash_list_leaf, verify_merkle_proof};
+
+// Sealed (encrypted) state fields
+pub use crate::sealed_box::{EncryptedItem, EncryptedMap, SealedBox};
+
+// Typed structured-data signatures
+pub use crate::typed_data::{TypedDomain, TypedStruct};
+
 // SDK v3 — standard library
-pub use crate::stdlib::{Norn20, Norn20Info, Ownable, Pausable};
+pub use crate::stdlib::{
+    Norn20, Norn20Compliance, Norn20Info, Norn20Permit, Ownable, Pausable, TransferRestriction,
+};
 
 // Guard macros (exported at crate root by #[macro_export])
 #[doc(hidden)]
@@ -0,0 +1,323 @@
+//! Encrypted state fields for commit-reveal contracts.
+//!
+//! A [`SealedBox`] is an XChaCha20-Poly1305 ciphertext stored on-chain until
+//! its key is revealed. [`EncryptedItem<T>`] and [`EncryptedMap<K, V>`] wrap
+//! the [`Item`] and [`Map`] primitives so a contract can hold sealed values
+//! during a commit phase and swap them for the decrypted value on reveal:
+//!
+//! ```ignore
+//! use norn_sdk::prelude::*;
+//!
+//! const BIDS: EncryptedMap<Address, u128> = EncryptedMap::new("bids");
+//!
+//! // Commit phase: store the ciphertext.
+//! BIDS.seal(&ctx.sender(), &sealed_bid)?;
+//!
+//! // Reveal phase: the bidder submits the key, the contract decrypts.
+//! let amount = BIDS.reveal(&ctx.sender(), &key)?;
+//! ```
+//!
+//! # Key delivery
+//!
+//! - The sealer generates a fresh random 32-byte key for every box and keeps
+//!   it off-chain until the reveal phase. Keys are never reused: once revealed
+//!   in a transaction, a key is public.
+//! - By default the sealer reveals its own box by submitting the key.
+//! - To let someone else reveal (an auctioneer, or a keeper that reveals on
+//!   the sealer's behalf), encrypt the key to the revealer's X25519 public key
+//!   with `norn_crypto::encryption::encrypt` and deliver it off-chain or in an
+//!   event. Anyone holding the key can reveal, so choose revealers the sealer
+//!   trusts to do so.
+//! - Each box carries a commitment to its key, so a box opens under exactly
+//!   one key and the sealer cannot pick between plaintexts at reveal time.
+//!
+//! The ciphertext length reveals the plaintext length. Fixed-size values such
+//! as `u128` bids leak nothing; pad variable-length values if size matters.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::error::ContractError;
+use crate::host;
+use crate::storage::{Item, Map, StorageKey};
+
+/// Domain separation context for sealed-box key commitments.
+const KEY_COMMITMENT_CONTEXT: &str = "norn-sdk 2026-10 sealed box key commitment";
+
+/// Commit to a sealed-box key.
+pub fn key_commitment(key: &[u8; 32]) -> [u8; 32] {
+    blake3::derive_key(KEY_COMMITMENT_CONTEXT, key)
+}
+
+/// An XChaCha20-Poly1305 ciphertext bound to a single key.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct SealedBox {
+    /// Random 24-byte nonce.
+    pub nonce: [u8; 24],
+    /// Ciphertext with its 16-byte authentication tag.
+    pub ciphertext: alloc::vec::Vec<u8>,
+    /// Commitment to the key, see [`key_commitment`].
+    pub key_commitment: [u8; 32],
+}
+
+impl SealedBox {
+    /// Borsh-encode `value` and seal it under `key` with a random nonce.
+    ///
+    /// Sealing happens off-chain (wallets, tests); contracts only open boxes.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn seal<T: BorshSerialize>(key: &[u8; 32], value: &T) -> Result<Self, ContractError> {
+        let plaintext = borsh::to_vec(value)
+            .map_err(|e| ContractError::Custom(alloc::format!("serialize: {e}")))?;
+        let (nonce, ciphertext) = norn_crypto::encryption::seal(key, &plaintext)
+            .map_err(|e| ContractError::Custom(alloc::format!("seal: {e}")))?;
+        Ok(SealedBox {
+            nonce,
+            ciphertext,
+            key_commitment: key_commitment(key),
+        })
+    }
+
+    /// Whether this box was sealed under `key`.
+    pub fn commits_to(&self, key: &[u8; 32]) -> bool {
+        self.key_commitment == key_commitment(key)
+    }
+
+    /// Decrypt the box with `key` and borsh-decode the plaintext.
+    pub fn open<T: BorshDeserialize>(&self, key: &[u8; 32]) -> Result<T, ContractError> {
+        if !self.commits_to(key) {
+            return Err(ContractError::InvalidInput(
+                "key does not match sealed box".into(),
+            ));
+        }
+        let plaintext = host::decrypt(key, &self.nonce, &self.ciphertext)
+            .ok_or_else(|| ContractError::InvalidInput("sealed box decryption failed".into()))?;
+        BorshDeserialize::try_from_slice(&plaintext)
+            .map_err(|e| ContractError::Custom(alloc::format!("deserialize: {e}")))
+    }
+}
+
+/// Stored state of an encrypted field.
+#[derive(BorshSerialize, BorshDeserialize)]
+enum Slot<T> {
+    Sealed(SealedBox),
+    Revealed(T),
+}
+
+fn already_revealed() -> ContractError {
+    ContractError::Custom("value already revealed".into())
+}
+
+fn not_revealed() -> ContractError {
+    ContractError::Custom("value not yet revealed".into())
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// EncryptedItem<T> — a single sealed value
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// A single value stored sealed until revealed.
+///
+/// ```ignore
+/// const SECRET: EncryptedItem<u64> = EncryptedItem::new("secret");
+///
+/// SECRET.seal(&sealed)?;
+/// let value = SECRET.reveal(&key)?;
+/// ```
+pub struct EncryptedItem<T> {
+    item: Item<Slot<T>>,
+}
+
+impl<T> EncryptedItem<T> {
+    /// Create a new `EncryptedItem` with the given namespace.
+    pub const fn new(namespace: &'static str) -> Self {
+        EncryptedItem {
+            item: Item::new(namespace),
+        }
+    }
+}
+
+impl<T: BorshSerialize + BorshDeserialize> EncryptedItem<T> {
+    /// Store a sealed box, replacing any earlier one that is still sealed.
+    pub fn seal(&self, sealed: &SealedBox) -> Result<(), ContractError> {
+        if self.is_revealed() {
+            return Err(already_revealed());
+        }
+        self.item.save(&Slot::Sealed(sealed.clone()))
+    }
+
+    /// Load the sealed box, failing if the value was already revealed.
+    pub fn sealed(&self) -> Result<SealedBox, ContractError> {
+        match self.item.load()? {
+            Slot::Sealed(sealed) => Ok(sealed),
+            Slot::Revealed(_) => Err(already_revealed()),
+        }
+    }
+
+    /// Decrypt the sealed box with `key`, store the plaintext and return it.
+    pub fn reveal(&self, key: &[u8; 32]) -> Result<T, ContractError> {
+        let value: T = self.sealed()?.open(key)?;
+        self.item.save(&Slot::Revealed(value))?;
+        self.load_revealed()
+    }
+
+    /// Load the revealed value, failing if it is still sealed.
+    pub fn load_revealed(&self) -> Result<T, ContractError> {
+        match self.item.load()? {
+            Slot::Revealed(value) => Ok(value),
+            Slot::Sealed(_) => Err(not_revealed()),
+        }
+    }
+
+    /// Check if the value has been revealed.
+    pub fn is_revealed(&self) -> bool {
+        matches!(self.item.load(), Ok(Slot::Revealed(_)))
+    }
+
+    /// Check if a sealed or revealed value exists in storage.
+    pub fn exists(&self) -> bool {
+        self.item.exists()
+    }
+
+    /// Remove the value from storage.
+    pub fn remove(&self) {
+        self.item.remove();
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// EncryptedMap<K, V> — keyed sealed values
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Values stored sealed until revealed, keyed by an arbitrary [`StorageKey`].
+///
+/// ```ignore
+/// const BIDS: EncryptedMap<Address, u128> = EncryptedMap::new("bids");
+///
+/// BIDS.seal(&bidder, &sealed)?;
+/// let amount = BIDS.reveal(&bidder, &key)?;
+/// ```
+pub struct EncryptedMap<K, V> {
+    map: Map<K, Slot<V>>,
+}
+
+impl<K, V> EncryptedMap<K, V> {
+    /// Create a new `EncryptedMap` with the given namespace.
+    pub const fn new(namespace: &'static str) -> Self {
+        EncryptedMap {
+            map: Map::new(namespace),
+        }
+    }
+}
+
+impl<K: StorageKey, V: BorshSerialize + BorshDeserialize> EncryptedMap<K, V> {
+    /// Store a sealed box at `key`, replacing any earlier one that is still sealed.
+    pub fn seal(&self, key: &K, sealed: &SealedBox) -> Result<(), ContractError> {
+        if self.is_revealed(key) {
+            return Err(already_revealed());
+        }
+        self.map.save(key, &Slot::Sealed(sealed.clone()))
+    }
+
+    /// Load the sealed box at `key`, failing if it was already revealed.
+    pub fn sealed(&self, key: &K) -> Result<SealedBox, ContractError> {
+        match self.map.load(key)? {
+            Slot::Sealed(sealed) => Ok(sealed),
+            Slot::Revealed(_) => Err(already_revealed()),
+        }
+    }
+
+    /// Decrypt the sealed box at `key` with `secret`, store the plaintext and
+    /// return it.
+    pub fn reveal(&self, key: &K, secret: &[u8; 32]) -> Result<V, ContractError> {
+        let value: V = self.sealed(key)?.open(secret)?;
+        self.map.save(key, &Slot::Revealed(value))?;
+        self.load_revealed(key)
+    }
+
+    /// Load the revealed value at `key`, failing if it is still sealed.
+    pub fn load_revealed(&self, key: &K) -> Result<V, ContractError> {
+        match self.map.load(key)? {
+            Slot::Revealed(value) => Ok(value),
+            Slot::Sealed(_) => Err(not_revealed()),
+        }
+    }
+
+    /// Check if the value at `key` has been revealed.
+    pub fn is_revealed(&self, key: &K) -> bool {
+        matches!(self.map.load(key), Ok(Slot::Revealed(_)))
+    }
+
+    /// Check if a sealed or revealed value exists at `key`.
+    pub fn has(&self, key: &K) -> bool {
+        self.map.has(key)
+    }
+
+    /// Remove the value at `key`.
+    pub fn remove(&self, key: &K) {
+        self.map.remove(key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+
+    const SECRET: EncryptedItem<u64> = EncryptedItem::new("secret");
+    const BIDS: EncryptedMap<[u8; 20], u128> = EncryptedMap::new("bids");
+
+    #[test]
+    fn test_item_seal_and_reveal() {
+        let _env = TestEnv::new();
+        let key = [7u8; 32];
+        SECRET
+            .seal(&SealedBox::seal(&key, &42u64).unwrap())
+            .unwrap();
+        assert!(SECRET.exists());
+        assert!(!SECRET.is_revealed());
+        assert_err_contains(&SECRET.load_revealed().unwrap_err(), "not yet revealed");
+
+        assert_eq!(SECRET.reveal(&key).unwrap(), 42);
+        assert!(SECRET.is_revealed());
+        assert_eq!(SECRET.load_revealed().unwrap(), 42);
+        assert_err_contains(&SECRET.reveal(&key).unwrap_err(), "already revealed");
+        assert!(SECRET.seal(&SealedBox::seal(&key, &1u64).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_wrong_key_rejected() {
+        let _env = TestEnv::new();
+        let sealed = SealedBox::seal(&[7u8; 32], &42u64).unwrap();
+        SECRET.seal(&sealed).unwrap();
+        assert_err_contains(&SECRET.reveal(&[8u8; 32]).unwrap_err(), "does not match");
+        assert!(!SECRET.is_revealed());
+    }
+
+    #[test]
+    fn test_tampered_ciphertext_rejected() {
+        let _env = TestEnv::new();
+        let key = [7u8; 32];
+        let mut sealed = SealedBox::seal(&key, &42u64).unwrap();
+        sealed.ciphertext[0] ^= 1;
+        assert_err_contains(&sealed.open::<u64>(&key).unwrap_err(), "decryption failed");
+    }
+
+    #[test]
+    fn test_map_reveal_per_key() {
+        let _env = TestEnv::new();
+        let (alice, bob) = ([1u8; 20], [2u8; 20]);
+        let (alice_key, bob_key) = ([3u8; 32], [4u8; 32]);
+        BIDS.seal(&alice, &SealedBox::seal(&alice_key, &500u128).unwrap())
+            .unwrap();
+        BIDS.seal(&bob, &SealedBox::seal(&bob_key, &700u128).unwrap())
+            .unwrap();
+
+        assert!(BIDS.reveal(&alice, &bob_key).is_err());
+        assert_eq!(BIDS.reveal(&alice, &alice_key).unwrap(), 500);
+        assert!(BIDS.is_revealed(&alice));
+        assert!(!BIDS.is_revealed(&bob));
+        assert!(BIDS.load_revealed(&bob).is_err());
+
+        BIDS.remove(&bob);
+        assert!(!BIDS.has(&bob));
+    }
+}
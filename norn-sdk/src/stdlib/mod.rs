@@ -4,11 +4,14 @@
 //! - [`Ownable`] — single-owner access control
 //! - [`Pausable`] — emergency pause/unpause
 //! - [`Norn20`] — ERC20-equivalent fungible token
+//! - [`Norn20Compliance`] — account/global freezes and transfer restrictions for `Norn20`
 
 pub mod norn20;
+pub mod norn20_compliance;
 pub mod ownable;
 pub mod pausable;
 
-pub use norn20::{Norn20, Norn20Info};
+pub use norn20::{Norn20, Norn20Info, Norn20Permit, Norn20TransferFee};
+pub use norn20_compliance::{Norn20Compliance, TransferRestriction};
 pub use ownable::Ownable;
 pub use pausable::Pausable;
@@ -3,6 +3,13 @@
 //! Provides `Norn20` with mint, burn, transfer, approve, and transfer_from.
 //! All methods are static — state lives in storage under the `__n20:` prefix.
 //!
+//! Allowances can also be granted off-chain: the owner signs a typed
+//! [`Norn20Permit`] and anyone submits it through [`Norn20::permit`].
+//!
+//! An optional transfer fee (in basis points) can be skimmed from every
+//! `transfer`/`transfer_from` and credited to a fee recipient; see
+//! [`Norn20::set_transfer_fee`].
+//!
 //! ```ignore
 //! use norn_sdk::prelude::*;
 //!
@@ -20,12 +27,13 @@ use alloc::string::String;
 
 use borsh::{BorshDeserialize, BorshSerialize};
 
-use crate::addr::ZERO_ADDRESS;
+use crate::addr::{pubkey_to_addr, ZERO_ADDRESS};
 use crate::contract::Context;
 use crate::error::ContractError;
 use crate::math::safe_add;
 use crate::response::{ContractResult, Event, Response};
 use crate::storage::{Item, Map};
+use crate::typed_data::{TypedDomain, TypedStruct};
 use crate::types::Address;
 use crate::{ensure, ensure_ne};
 
@@ -38,6 +46,20 @@ const N20_TOTAL_SUPPLY: Item<u128> = Item::new("__n20:total_supply");
 const N20_BALANCES: Map<Address, u128> = Map::new("__n20:bal");
 /// Allowance key = `owner_address ++ spender_address` (40 bytes).
 const N20_ALLOWANCES: Map<[u8; 40], u128> = Map::new("__n20:allow");
+const N20_TRANSFER_FEE: Item<Norn20TransferFee> = Item::new("__n20:fee");
+const N20_FEE_EXEMPT: Map<Address, bool> = Map::new("__n20:fee_exempt");
+const N20_NONCES: Map<Address, u64> = Map::new("__n20:nonce");
+
+/// Typed-data domain name of permit signatures.
+pub const PERMIT_DOMAIN_NAME: &str = "norn20";
+/// Typed-data domain version of permit signatures.
+pub const PERMIT_DOMAIN_VERSION: &str = "1";
+/// Typed-data type signed by a permit.
+pub const PERMIT_TYPE: &str =
+    "Permit(address owner,address spender,u128 amount,u64 nonce,u64 deadline)";
+
+/// Basis-point denominator for transfer fees (100% = 10,000 bps).
+pub const FEE_BPS_DENOMINATOR: u16 = 10_000;
 
 // ── Helpers ────────────────────────────────────────────────────────────────
 
@@ -57,6 +79,31 @@ pub struct Norn20Info {
     pub total_supply: u128,
 }
 
+/// Transfer fee configuration returned by [`Norn20::transfer_fee()`].
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct Norn20TransferFee {
+    /// Fee charged on each transfer, in basis points of the gross amount.
+    pub fee_bps: u16,
+    /// Address credited with the fee.
+    pub recipient: Address,
+}
+
+/// An allowance signed off-chain by the token owner.
+///
+/// The owner signs [`PERMIT_TYPE`] with their current [`Norn20::nonce`] in
+/// the domain `norn20`/`1`, the network's chain ID and the token's contract
+/// address.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct Norn20Permit {
+    /// Ed25519 public key of the owner; the owner address is derived from it.
+    pub owner_pubkey: [u8; 32],
+    pub spender: Address,
+    pub amount: u128,
+    /// Last timestamp (unix seconds) the permit can be used at.
+    pub deadline: u64,
+    pub signature: [u8; 64],
+}
+
 /// ERC20-equivalent fungible token.
 ///
 /// All methods are static — no instance needed. State is stored under
@@ -108,6 +155,11 @@ impl Norn20 {
         N20_ALLOWANCES.load_or(&key, 0)
     }
 
+    /// Get the nonce the next permit signed by `owner` must use.
+    pub fn nonce(owner: &Address) -> u64 {
+        N20_NONCES.load_or(owner, 0)
+    }
+
     /// Get full token metadata.
     pub fn info() -> Result<Norn20Info, ContractError> {
         Ok(Norn20Info {
@@ -118,6 +170,91 @@ impl Norn20 {
         })
     }
 
+    /// Get the transfer fee configuration, if a fee is set.
+    pub fn transfer_fee() -> Option<Norn20TransferFee> {
+        N20_TRANSFER_FEE.load().ok()
+    }
+
+    /// Check if an address is exempt from transfer fees.
+    pub fn is_fee_exempt(addr: &Address) -> bool {
+        N20_FEE_EXEMPT.load_or(addr, false)
+    }
+
+    /// Fee charged on a transfer of `amount` from `from` to `to`.
+    ///
+    /// Zero when no fee is set or either side is exempt. The fee recipient is
+    /// always exempt. Rounds down.
+    pub fn transfer_fee_amount(from: &Address, to: &Address, amount: u128) -> u128 {
+        let Some(fee) = Self::transfer_fee() else {
+            return 0;
+        };
+        if *from == fee.recipient
+            || *to == fee.recipient
+            || Self::is_fee_exempt(from)
+            || Self::is_fee_exempt(to)
+        {
+            return 0;
+        }
+        let bps = u128::from(fee.fee_bps);
+        let denom = u128::from(FEE_BPS_DENOMINATOR);
+        // Split to avoid overflowing on large amounts.
+        amount / denom * bps + amount % denom * bps / denom
+    }
+
+    // ── Fee configuration ──────────────────────────────────────────────
+
+    /// Charge `fee_bps` basis points on every transfer, credited to `recipient`.
+    ///
+    /// **Note**: Does not check authorization — the caller should enforce
+    /// who is allowed to configure fees (e.g., `Ownable::require_owner(ctx)?`).
+    pub fn set_transfer_fee(fee_bps: u16, recipient: &Address) -> ContractResult {
+        ensure!(fee_bps > 0, "fee must be positive");
+        ensure!(
+            fee_bps <= FEE_BPS_DENOMINATOR,
+            "fee cannot exceed 10000 bps"
+        );
+        ensure_ne!(
+            *recipient,
+            ZERO_ADDRESS,
+            "fee recipient cannot be zero address"
+        );
+        N20_TRANSFER_FEE.save(&Norn20TransferFee {
+            fee_bps,
+            recipient: *recipient,
+        })?;
+
+        Ok(Response::new().add_event(
+            Event::new("TransferFeeUpdated")
+                .add_u128("fee_bps", u128::from(fee_bps))
+                .add_address("recipient", recipient),
+        ))
+    }
+
+    /// Stop charging transfer fees. Exemptions are kept.
+    ///
+    /// **Note**: Does not check authorization.
+    pub fn clear_transfer_fee() -> ContractResult {
+        ensure!(Self::transfer_fee().is_some(), "no transfer fee set");
+        N20_TRANSFER_FEE.remove();
+        Ok(Response::new().add_event(Event::new("TransferFeeUpdated").add_u128("fee_bps", 0)))
+    }
+
+    /// Exempt an address from (or subject it to) transfer fees.
+    ///
+    /// **Note**: Does not check authorization.
+    pub fn set_fee_exempt(addr: &Address, exempt: bool) -> ContractResult {
+        if exempt {
+            N20_FEE_EXEMPT.save(addr, &true)?;
+        } else {
+            N20_FEE_EXEMPT.remove(addr);
+        }
+        Ok(Response::new().add_event(
+            Event::new("FeeExemptionUpdated")
+                .add_address("account", addr)
+                .add_attribute("exempt", if exempt { "true" } else { "false" }),
+        ))
+    }
+
     // ── Mutations ──────────────────────────────────────────────────────
 
     /// Mint tokens to an address. Returns a `Response` with a `Mint` event.
@@ -176,20 +313,7 @@ impl Norn20 {
         let sender = ctx.sender();
         ensure_ne!(sender, *to, "cannot transfer to self");
 
-        let from_bal = N20_BALANCES.load_or(&sender, 0);
-        ensure!(amount <= from_bal, ContractError::InsufficientFunds);
-
-        let to_bal = N20_BALANCES.load_or(to, 0);
-        let new_to_bal = safe_add(to_bal, amount)?;
-        N20_BALANCES.save(&sender, &(from_bal - amount))?;
-        N20_BALANCES.save(to, &new_to_bal)?;
-
-        Ok(Response::new().add_event(
-            Event::new("Transfer")
-                .add_address("from", &sender)
-                .add_address("to", to)
-                .add_u128("amount", amount),
-        ))
+        move_balance(&sender, to, amount)
     }
 
     /// Approve `spender` to spend `amount` on behalf of the sender.
@@ -222,22 +346,98 @@ impl Norn20 {
         let allowance = N20_ALLOWANCES.load_or(&key, 0);
         ensure!(amount <= allowance, "insufficient allowance");
 
-        let from_bal = N20_BALANCES.load_or(from, 0);
-        ensure!(amount <= from_bal, ContractError::InsufficientFunds);
-
-        let to_bal = N20_BALANCES.load_or(to, 0);
-        let new_to_bal = safe_add(to_bal, amount)?;
-        N20_BALANCES.save(from, &(from_bal - amount))?;
-        N20_BALANCES.save(to, &new_to_bal)?;
+        let resp = move_balance(from, to, amount)?;
         N20_ALLOWANCES.save(&key, &(allowance - amount))?;
+        Ok(resp)
+    }
+
+    /// Set an allowance from a permit signed by the owner.
+    ///
+    /// `chain_id` is the network the token is deployed on. Each permit is
+    /// valid once: the owner's nonce is bumped when it is used.
+    pub fn permit(ctx: &Context, chain_id: &str, permit: &Norn20Permit) -> ContractResult {
+        ensure_ne!(permit.spender, ZERO_ADDRESS, "cannot approve zero address");
+        ensure!(ctx.timestamp() <= permit.deadline, "permit expired");
+
+        let owner = pubkey_to_addr(&permit.owner_pubkey);
+        let nonce = Self::nonce(&owner);
+        let domain = TypedDomain::new(
+            PERMIT_DOMAIN_NAME,
+            PERMIT_DOMAIN_VERSION,
+            chain_id,
+            ctx.contract_address(),
+        );
+        let message = TypedStruct::new(PERMIT_TYPE)
+            .address(&owner)
+            .address(&permit.spender)
+            .u128(permit.amount)
+            .u64(nonce)
+            .u64(permit.deadline);
+        ensure!(
+            ctx.verify_typed(&domain, &message, &permit.owner_pubkey, &permit.signature),
+            "invalid permit signature"
+        );
+
+        N20_NONCES.save(&owner, &(nonce + 1))?;
+        let key = allowance_key(&owner, &permit.spender);
+        N20_ALLOWANCES.save(&key, &permit.amount)?;
 
         Ok(Response::new().add_event(
+            Event::new("Approval")
+                .add_address("owner", &owner)
+                .add_address("spender", &permit.spender)
+                .add_u128("amount", permit.amount),
+        ))
+    }
+}
+
+/// Debit `amount` from `from` and credit it to `to`, less any transfer fee.
+///
+/// Emits a `Transfer` for the net amount. When a fee is charged, also emits a
+/// `Transfer` to the fee recipient and a `TransferFee` event with the gross
+/// and net amounts.
+fn move_balance(from: &Address, to: &Address, amount: u128) -> ContractResult {
+    let from_bal = N20_BALANCES.load_or(from, 0);
+    ensure!(amount <= from_bal, ContractError::InsufficientFunds);
+
+    let fee = Norn20::transfer_fee_amount(from, to, amount);
+    let net = amount - fee;
+
+    let to_bal = N20_BALANCES.load_or(to, 0);
+    let new_to_bal = safe_add(to_bal, net)?;
+    N20_BALANCES.save(from, &(from_bal - amount))?;
+    N20_BALANCES.save(to, &new_to_bal)?;
+
+    let resp = Response::new().add_event(
+        Event::new("Transfer")
+            .add_address("from", from)
+            .add_address("to", to)
+            .add_u128("amount", net),
+    );
+    if fee == 0 {
+        return Ok(resp);
+    }
+
+    // `transfer_fee_amount` is only non-zero when a fee is configured.
+    let recipient = N20_TRANSFER_FEE.load()?.recipient;
+    let fee_bal = N20_BALANCES.load_or(&recipient, 0);
+    N20_BALANCES.save(&recipient, &safe_add(fee_bal, fee)?)?;
+
+    Ok(resp
+        .add_event(
             Event::new("Transfer")
+                .add_address("from", from)
+                .add_address("to", &recipient)
+                .add_u128("amount", fee),
+        )
+        .add_event(
+            Event::new("TransferFee")
                 .add_address("from", from)
                 .add_address("to", to)
-                .add_u128("amount", amount),
+                .add_u128("gross_amount", amount)
+                .add_u128("net_amount", net)
+                .add_u128("fee", fee),
         ))
-    }
 }
 
 #[cfg(test)]
@@ -378,6 +578,70 @@ mod tests {
         assert_eq!(err.message(), "insufficient allowance");
     }
 
+    fn signed_permit(chain_id: &str, nonce: u64, deadline: u64) -> Norn20Permit {
+        use norn_types::typed_data::{StructType, TypedData, TypedDomain, TypedValue};
+
+        let keypair = norn_crypto::keys::Keypair::from_seed(&[7u8; 32]);
+        let owner_pubkey = keypair.public_key();
+        let data = TypedData {
+            domain: TypedDomain {
+                name: PERMIT_DOMAIN_NAME.into(),
+                version: PERMIT_DOMAIN_VERSION.into(),
+                chain_id: chain_id.into(),
+                loom: DAVE,
+            },
+            message_type: StructType::parse(PERMIT_TYPE).unwrap(),
+            values: alloc::vec![
+                TypedValue::Address(pubkey_to_addr(&owner_pubkey)),
+                TypedValue::Address(BOB),
+                TypedValue::U128(300),
+                TypedValue::U64(nonce),
+                TypedValue::U64(deadline),
+            ],
+        };
+        Norn20Permit {
+            owner_pubkey,
+            spender: BOB,
+            amount: 300,
+            deadline,
+            signature: norn_crypto::typed_data::sign_typed_data(&keypair, &data).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_permit() {
+        let env = setup().with_contract_address(DAVE).with_timestamp(100);
+        let permit = signed_permit("norn-dev", 0, 1_000);
+        let owner = pubkey_to_addr(&permit.owner_pubkey);
+
+        env.set_sender(CHARLIE);
+        let resp = Norn20::permit(&env.ctx(), "norn-dev", &permit).unwrap();
+        assert_event(&resp, "Approval");
+        assert_eq!(Norn20::allowance(&owner, &BOB), 300);
+        assert_eq!(Norn20::nonce(&owner), 1);
+
+        // Replaying the same permit fails once the nonce has moved on.
+        let err = Norn20::permit(&env.ctx(), "norn-dev", &permit).unwrap_err();
+        assert_eq!(err.message(), "invalid permit signature");
+    }
+
+    #[test]
+    fn test_permit_rejects_wrong_domain_and_expiry() {
+        let env = setup().with_contract_address(DAVE).with_timestamp(100);
+        let permit = signed_permit("norn-dev", 0, 1_000);
+        let err = Norn20::permit(&env.ctx(), "norn-mainnet", &permit).unwrap_err();
+        assert_eq!(err.message(), "invalid permit signature");
+
+        env.set_contract_address(CHARLIE);
+        let err = Norn20::permit(&env.ctx(), "norn-dev", &permit).unwrap_err();
+        assert_eq!(err.message(), "invalid permit signature");
+
+        env.set_contract_address(DAVE);
+        env.set_timestamp(1_001);
+        let err = Norn20::permit(&env.ctx(), "norn-dev", &permit).unwrap_err();
+        assert_eq!(err.message(), "permit expired");
+    }
+
     #[test]
     fn test_transfer_from_insufficient_balance() {
         let env = setup();
@@ -406,6 +670,89 @@ mod tests {
         assert_eq!(Norn20::total_supply(), 350);
     }
 
+    #[test]
+    fn test_transfer_with_fee() {
+        let env = setup();
+        Norn20::mint(&ALICE, 10_000).unwrap();
+        let resp = Norn20::set_transfer_fee(250, &DAVE).unwrap();
+        assert_event(&resp, "TransferFeeUpdated");
+
+        let resp = Norn20::transfer(&env.ctx(), &BOB, 1_000).unwrap();
+        assert_event_attribute(&resp, "TransferFee", "gross_amount", "1000");
+        assert_event_attribute(&resp, "TransferFee", "net_amount", "975");
+        assert_eq!(Norn20::balance_of(&ALICE), 9_000);
+        assert_eq!(Norn20::balance_of(&BOB), 975);
+        assert_eq!(Norn20::balance_of(&DAVE), 25);
+        assert_eq!(Norn20::total_supply(), 10_000);
+    }
+
+    #[test]
+    fn test_transfer_from_with_fee() {
+        let env = setup();
+        Norn20::mint(&ALICE, 1_000).unwrap();
+        Norn20::set_transfer_fee(100, &DAVE).unwrap();
+        Norn20::approve(&env.ctx(), &BOB, 500).unwrap();
+
+        env.set_sender(BOB);
+        Norn20::transfer_from(&env.ctx(), &ALICE, &CHARLIE, 500).unwrap();
+        assert_eq!(Norn20::balance_of(&ALICE), 500);
+        assert_eq!(Norn20::balance_of(&CHARLIE), 495);
+        assert_eq!(Norn20::balance_of(&DAVE), 5);
+        assert_eq!(Norn20::allowance(&ALICE, &BOB), 0);
+    }
+
+    #[test]
+    fn test_fee_exemptions() {
+        let env = setup();
+        Norn20::mint(&ALICE, 1_000).unwrap();
+        Norn20::set_transfer_fee(1_000, &DAVE).unwrap();
+        Norn20::set_fee_exempt(&BOB, true).unwrap();
+        assert!(Norn20::is_fee_exempt(&BOB));
+
+        let resp = Norn20::transfer(&env.ctx(), &BOB, 100).unwrap();
+        assert_eq!(resp.events().len(), 1);
+        assert_eq!(Norn20::balance_of(&BOB), 100);
+
+        // The fee recipient itself is never charged.
+        Norn20::transfer(&env.ctx(), &DAVE, 100).unwrap();
+        assert_eq!(Norn20::balance_of(&DAVE), 100);
+
+        Norn20::set_fee_exempt(&BOB, false).unwrap();
+        Norn20::transfer(&env.ctx(), &BOB, 100).unwrap();
+        assert_eq!(Norn20::balance_of(&BOB), 190);
+        assert_eq!(Norn20::balance_of(&DAVE), 110);
+    }
+
+    #[test]
+    fn test_fee_rounds_down_and_clears() {
+        let env = setup();
+        Norn20::mint(&ALICE, 1_000).unwrap();
+        Norn20::set_transfer_fee(30, &DAVE).unwrap();
+        assert_eq!(Norn20::transfer_fee_amount(&ALICE, &BOB, 33), 0);
+        assert_eq!(
+            Norn20::transfer_fee_amount(&ALICE, &BOB, u128::MAX),
+            u128::MAX / 10_000 * 30 + 4
+        );
+
+        Norn20::clear_transfer_fee().unwrap();
+        assert!(Norn20::transfer_fee().is_none());
+        Norn20::transfer(&env.ctx(), &BOB, 100).unwrap();
+        assert_eq!(Norn20::balance_of(&BOB), 100);
+        let err = Norn20::clear_transfer_fee().unwrap_err();
+        assert_eq!(err.message(), "no transfer fee set");
+    }
+
+    #[test]
+    fn test_set_transfer_fee_bounds() {
+        let _env = setup();
+        let err = Norn20::set_transfer_fee(0, &DAVE).unwrap_err();
+        assert_eq!(err.message(), "fee must be positive");
+        let err = Norn20::set_transfer_fee(10_001, &DAVE).unwrap_err();
+        assert_eq!(err.message(), "fee cannot exceed 10000 bps");
+        let err = Norn20::set_transfer_fee(100, &ZERO_ADDRESS).unwrap_err();
+        assert_eq!(err.message(), "fee recipient cannot be zero address");
+    }
+
     #[test]
     fn test_approve_zero_address_fails() {
         let env = setup();
@@ -0,0 +1,350 @@
+//! Compliance controls for regulated [`Norn20`](super::Norn20) tokens.
+//!
+//! Adds a compliance admin role that can freeze individual accounts, freeze
+//! all transfers at once, and switch on an issuer-defined transfer
+//! restriction hook. Balances and allowances stay in the regular `Norn20`
+//! storage — route token movements through the wrappers here instead of
+//! calling `Norn20` directly. State lives under the `__n20c:` prefix.
+//!
+//! ```ignore
+//! use norn_sdk::prelude::*;
+//!
+//! fn kyc_only(_from: &Address, to: &Address, _amount: u128) -> Result<(), ContractError> {
+//!     ensure!(KYC.load_or(to, false), "recipient is not verified");
+//!     Ok(())
+//! }
+//!
+//! fn init(ctx: &Context, msg: InitMsg) -> Self {
+//!     Norn20::init(&msg.name, &msg.symbol, msg.decimals).unwrap();
+//!     Norn20Compliance::init(&ctx.sender()).unwrap();
+//!     MyToken
+//! }
+//!
+//! fn execute(&mut self, ctx: &Context, msg: Exec) -> ContractResult {
+//!     match msg {
+//!         Exec::Transfer { to, amount } => {
+//!             Norn20Compliance::transfer(ctx, &to, amount, Some(kyc_only))
+//!         }
+//!         Exec::Freeze { account } => Norn20Compliance::freeze(ctx, &account),
+//!         // ...
+//!     }
+//! }
+//! ```
+
+use crate::addr::{addr_to_hex, ZERO_ADDRESS};
+use crate::contract::Context;
+use crate::error::ContractError;
+use crate::response::{ContractResult, Event, Response};
+use crate::stdlib::norn20::Norn20;
+use crate::storage::{Item, Map};
+use crate::types::Address;
+use crate::{ensure, ensure_ne};
+
+// ── Storage layout ─────────────────────────────────────────────────────────
+
+const N20C_ADMIN: Item<Address> = Item::new("__n20c:admin");
+const N20C_GLOBAL_FREEZE: Item<bool> = Item::new("__n20c:global_freeze");
+const N20C_RESTRICTED: Item<bool> = Item::new("__n20c:restricted");
+const N20C_FROZEN: Map<Address, bool> = Map::new("__n20c:frozen");
+
+/// Issuer-defined check run on every transfer while restrictions are enabled.
+///
+/// Receives `(from, to, amount)`; return an error to reject the transfer.
+pub type TransferRestriction = fn(&Address, &Address, u128) -> Result<(), ContractError>;
+
+/// Freezing and transfer restrictions on top of [`Norn20`].
+///
+/// All methods are static — no instance needed. The compliance admin is
+/// independent of [`Ownable`](super::Ownable), so issuers can hand
+/// compliance duties to a separate key.
+pub struct Norn20Compliance;
+
+impl Norn20Compliance {
+    /// Set the initial compliance admin. Call in your contract's `init()`.
+    pub fn init(admin: &Address) -> Result<(), ContractError> {
+        N20C_ADMIN.save(admin)?;
+        N20C_GLOBAL_FREEZE.save(&false)?;
+        N20C_RESTRICTED.save(&false)
+    }
+
+    // ── Queries ────────────────────────────────────────────────────────
+
+    /// Get the current compliance admin.
+    pub fn admin() -> Result<Address, ContractError> {
+        N20C_ADMIN.load()
+    }
+
+    /// Check if an account is frozen.
+    pub fn is_frozen(addr: &Address) -> bool {
+        N20C_FROZEN.load_or(addr, false)
+    }
+
+    /// Check if all transfers are frozen.
+    pub fn is_globally_frozen() -> bool {
+        N20C_GLOBAL_FREEZE.load_or(false)
+    }
+
+    /// Check if the transfer restriction hook is enabled.
+    pub fn restrictions_enabled() -> bool {
+        N20C_RESTRICTED.load_or(false)
+    }
+
+    /// Assert that the sender is the compliance admin.
+    pub fn require_admin(ctx: &Context) -> Result<(), ContractError> {
+        let admin = N20C_ADMIN.load()?;
+        if ctx.sender() != admin {
+            return Err(ContractError::Unauthorized);
+        }
+        Ok(())
+    }
+
+    /// Assert that a transfer of `amount` from `from` to `to` is allowed.
+    ///
+    /// Fails if transfers are globally frozen or either side is frozen. The
+    /// `restriction` hook only runs while restrictions are enabled.
+    pub fn check_transfer(
+        from: &Address,
+        to: &Address,
+        amount: u128,
+        restriction: Option<TransferRestriction>,
+    ) -> Result<(), ContractError> {
+        ensure!(!Self::is_globally_frozen(), "transfers are frozen");
+        ensure!(!Self::is_frozen(from), "sender account is frozen");
+        ensure!(!Self::is_frozen(to), "recipient account is frozen");
+        if let Some(check) = restriction {
+            if Self::restrictions_enabled() {
+                check(from, to, amount)?;
+            }
+        }
+        Ok(())
+    }
+
+    // ── Admin ──────────────────────────────────────────────────────────
+
+    /// Hand the compliance admin role to a new address (admin-only).
+    pub fn set_admin(ctx: &Context, new_admin: &Address) -> ContractResult {
+        Self::require_admin(ctx)?;
+        ensure_ne!(*new_admin, ZERO_ADDRESS, "new admin cannot be zero address");
+        let prev = N20C_ADMIN.load()?;
+        N20C_ADMIN.save(new_admin)?;
+        Ok(Response::new().add_event(
+            Event::new("ComplianceAdminChanged")
+                .add_attribute("previous_admin", addr_to_hex(&prev))
+                .add_attribute("new_admin", addr_to_hex(new_admin)),
+        ))
+    }
+
+    /// Freeze an account, blocking transfers to and from it (admin-only).
+    pub fn freeze(ctx: &Context, addr: &Address) -> ContractResult {
+        Self::require_admin(ctx)?;
+        ensure!(!Self::is_frozen(addr), "account is already frozen");
+        N20C_FROZEN.save(addr, &true)?;
+        Ok(Response::new().add_event(Event::new("Frozen").add_address("account", addr)))
+    }
+
+    /// Unfreeze a previously frozen account (admin-only).
+    pub fn unfreeze(ctx: &Context, addr: &Address) -> ContractResult {
+        Self::require_admin(ctx)?;
+        ensure!(Self::is_frozen(addr), "account is not frozen");
+        N20C_FROZEN.remove(addr);
+        Ok(Response::new().add_event(Event::new("Unfrozen").add_address("account", addr)))
+    }
+
+    /// Freeze all transfers (admin-only).
+    pub fn freeze_all(ctx: &Context) -> ContractResult {
+        Self::require_admin(ctx)?;
+        ensure!(!Self::is_globally_frozen(), "transfers are already frozen");
+        N20C_GLOBAL_FREEZE.save(&true)?;
+        Ok(Response::new().add_event(Event::new("GlobalFreeze")))
+    }
+
+    /// Lift a global freeze (admin-only).
+    pub fn unfreeze_all(ctx: &Context) -> ContractResult {
+        Self::require_admin(ctx)?;
+        ensure!(Self::is_globally_frozen(), "transfers are not frozen");
+        N20C_GLOBAL_FREEZE.save(&false)?;
+        Ok(Response::new().add_event(Event::new("GlobalUnfreeze")))
+    }
+
+    /// Turn the transfer restriction hook on or off (admin-only).
+    pub fn set_restrictions_enabled(ctx: &Context, enabled: bool) -> ContractResult {
+        Self::require_admin(ctx)?;
+        N20C_RESTRICTED.save(&enabled)?;
+        Ok(Response::new().add_event(
+            Event::new("TransferRestrictions")
+                .add_attribute("enabled", if enabled { "true" } else { "false" }),
+        ))
+    }
+
+    // ── Token movements ────────────────────────────────────────────────
+
+    /// [`Norn20::mint`] that refuses to credit a frozen account.
+    ///
+    /// **Note**: Does not check authorization, same as `Norn20::mint`.
+    pub fn mint(to: &Address, amount: u128) -> ContractResult {
+        ensure!(!Self::is_frozen(to), "recipient account is frozen");
+        Norn20::mint(to, amount)
+    }
+
+    /// [`Norn20::transfer`] gated by [`check_transfer`](Self::check_transfer).
+    pub fn transfer(
+        ctx: &Context,
+        to: &Address,
+        amount: u128,
+        restriction: Option<TransferRestriction>,
+    ) -> ContractResult {
+        Self::check_transfer(&ctx.sender(), to, amount, restriction)?;
+        Norn20::transfer(ctx, to, amount)
+    }
+
+    /// [`Norn20::transfer_from`] gated by [`check_transfer`](Self::check_transfer).
+    ///
+    /// A frozen spender cannot move funds on anyone's behalf either.
+    pub fn transfer_from(
+        ctx: &Context,
+        from: &Address,
+        to: &Address,
+        amount: u128,
+        restriction: Option<TransferRestriction>,
+    ) -> ContractResult {
+        ensure!(!Self::is_frozen(&ctx.sender()), "spender account is frozen");
+        Self::check_transfer(from, to, amount, restriction)?;
+        Norn20::transfer_from(ctx, from, to, amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+
+    fn setup() -> TestEnv {
+        let env = TestEnv::new().with_sender(ALICE);
+        Norn20::init("Regulated", "REG", 6).unwrap();
+        Norn20Compliance::init(&ALICE).unwrap();
+        Norn20::mint(&ALICE, 1_000).unwrap();
+        env
+    }
+
+    fn block_charlie(_from: &Address, to: &Address, _amount: u128) -> Result<(), ContractError> {
+        ensure_ne!(*to, CHARLIE, "recipient is not allowed");
+        Ok(())
+    }
+
+    #[test]
+    fn test_init() {
+        let _env = setup();
+        assert_eq!(Norn20Compliance::admin().unwrap(), ALICE);
+        assert!(!Norn20Compliance::is_globally_frozen());
+        assert!(!Norn20Compliance::restrictions_enabled());
+        assert!(!Norn20Compliance::is_frozen(&BOB));
+    }
+
+    #[test]
+    fn test_transfer_passes_through() {
+        let env = setup();
+        let resp = Norn20Compliance::transfer(&env.ctx(), &BOB, 100, None).unwrap();
+        assert_event(&resp, "Transfer");
+        assert_eq!(Norn20::balance_of(&BOB), 100);
+    }
+
+    #[test]
+    fn test_freeze_blocks_both_directions() {
+        let env = setup();
+        Norn20Compliance::transfer(&env.ctx(), &BOB, 100, None).unwrap();
+        let resp = Norn20Compliance::freeze(&env.ctx(), &BOB).unwrap();
+        assert_event(&resp, "Frozen");
+
+        let err = Norn20Compliance::transfer(&env.ctx(), &BOB, 10, None).unwrap_err();
+        assert_eq!(err.message(), "recipient account is frozen");
+
+        env.set_sender(BOB);
+        let err = Norn20Compliance::transfer(&env.ctx(), &ALICE, 10, None).unwrap_err();
+        assert_eq!(err.message(), "sender account is frozen");
+
+        env.set_sender(ALICE);
+        let resp = Norn20Compliance::unfreeze(&env.ctx(), &BOB).unwrap();
+        assert_event(&resp, "Unfrozen");
+        env.set_sender(BOB);
+        Norn20Compliance::transfer(&env.ctx(), &ALICE, 10, None).unwrap();
+        assert_eq!(Norn20::balance_of(&BOB), 90);
+    }
+
+    #[test]
+    fn test_frozen_spender_and_mint() {
+        let env = setup();
+        env.set_sender(ALICE);
+        Norn20::approve(&env.ctx(), &BOB, 50).unwrap();
+        Norn20Compliance::freeze(&env.ctx(), &BOB).unwrap();
+
+        env.set_sender(BOB);
+        let err =
+            Norn20Compliance::transfer_from(&env.ctx(), &ALICE, &CHARLIE, 10, None).unwrap_err();
+        assert_eq!(err.message(), "spender account is frozen");
+
+        let err = Norn20Compliance::mint(&BOB, 10).unwrap_err();
+        assert_eq!(err.message(), "recipient account is frozen");
+    }
+
+    #[test]
+    fn test_global_freeze() {
+        let env = setup();
+        let resp = Norn20Compliance::freeze_all(&env.ctx()).unwrap();
+        assert_event(&resp, "GlobalFreeze");
+        let err = Norn20Compliance::transfer(&env.ctx(), &BOB, 10, None).unwrap_err();
+        assert_eq!(err.message(), "transfers are frozen");
+
+        let err = Norn20Compliance::freeze_all(&env.ctx()).unwrap_err();
+        assert_eq!(err.message(), "transfers are already frozen");
+
+        let resp = Norn20Compliance::unfreeze_all(&env.ctx()).unwrap();
+        assert_event(&resp, "GlobalUnfreeze");
+        Norn20Compliance::transfer(&env.ctx(), &BOB, 10, None).unwrap();
+    }
+
+    #[test]
+    fn test_restriction_hook_only_when_enabled() {
+        let env = setup();
+        Norn20Compliance::transfer(&env.ctx(), &CHARLIE, 10, Some(block_charlie)).unwrap();
+
+        Norn20Compliance::set_restrictions_enabled(&env.ctx(), true).unwrap();
+        let err =
+            Norn20Compliance::transfer(&env.ctx(), &CHARLIE, 10, Some(block_charlie)).unwrap_err();
+        assert_eq!(err.message(), "recipient is not allowed");
+        Norn20Compliance::transfer(&env.ctx(), &BOB, 10, Some(block_charlie)).unwrap();
+        assert_eq!(Norn20::balance_of(&CHARLIE), 10);
+    }
+
+    #[test]
+    fn test_admin_only() {
+        let env = setup();
+        env.set_sender(BOB);
+        let ctx = env.ctx();
+        assert_eq!(
+            Norn20Compliance::freeze(&ctx, &CHARLIE).unwrap_err(),
+            ContractError::Unauthorized
+        );
+        assert_eq!(
+            Norn20Compliance::freeze_all(&ctx).unwrap_err(),
+            ContractError::Unauthorized
+        );
+        assert_eq!(
+            Norn20Compliance::set_restrictions_enabled(&ctx, true).unwrap_err(),
+            ContractError::Unauthorized
+        );
+    }
+
+    #[test]
+    fn test_set_admin() {
+        let env = setup();
+        let resp = Norn20Compliance::set_admin(&env.ctx(), &BOB).unwrap();
+        assert_event(&resp, "ComplianceAdminChanged");
+        assert_eq!(
+            Norn20Compliance::freeze(&env.ctx(), &CHARLIE).unwrap_err(),
+            ContractError::Unauthorized
+        );
+        env.set_sender(BOB);
+        Norn20Compliance::freeze(&env.ctx(), &CHARLIE).unwrap();
+        assert!(Norn20Compliance::is_frozen(&CHARLIE));
+    }
+}
@@ -27,7 +27,7 @@ use crate::contract::Context;
 use crate::error::ContractError;
 use crate::host;
 use crate::response::Response;
-use crate::types::Address;
+use crate::types::{Address, TokenId};
 
 // ═══════════════════════════════════════════════════════════════════════════
 // Test address constants
@@ -106,6 +106,17 @@ impl TestEnv {
         host::mock_set_contract_address(addr);
     }
 
+    /// Seed the ledger balance of `addr` for `token` (builder, consuming).
+    pub fn with_balance(self, addr: Address, token: TokenId, amount: u128) -> Self {
+        host::mock_set_balance(addr, token, amount);
+        self
+    }
+
+    /// Set the ledger balance of `addr` for `token` mid-test (non-consuming).
+    pub fn set_balance(&self, addr: Address, token: TokenId, amount: u128) {
+        host::mock_set_balance(addr, token, amount);
+    }
+
     /// Build a `Context` from the current mock state.
     pub fn ctx(&self) -> Context {
         Context::new()
@@ -121,6 +132,11 @@ impl TestEnv {
         host::mock_reset_logs();
     }
 
+    /// Get all debug messages captured since the last reset.
+    pub fn debug_logs(&self) -> Vec<String> {
+        host::mock_get_debug_logs()
+    }
+
     /// Get all events captured since the last reset.
     pub fn events(&self) -> Vec<host::MockEvent> {
         host::mock_get_events()
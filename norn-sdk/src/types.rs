@@ -8,9 +8,15 @@ pub type Address = [u8; 20];
 /// A 32-byte token identifier.
 pub type TokenId = [u8; 32];
 
+/// Token ID of the native NORN token.
+pub const NATIVE_TOKEN_ID: TokenId = [0u8; 32];
+
 /// A 32-byte loom (smart contract) identifier.
 pub type LoomId = [u8; 32];
 
+/// A 32-byte code identifier (blake3 hash of stored bytecode).
+pub type CodeId = [u8; 32];
+
 /// Unit type for contracts that don't need constructor arguments.
 ///
 /// Use `type Init = Empty;` in your `Contract` impl when the init
@@ -15,6 +15,9 @@ pub enum SpindleError {
     #[error("Service error: {reason}")]
     ServiceError { reason: String },
 
+    #[error("Subscription error: {reason}")]
+    SubscriptionError { reason: String },
+
     #[error("Rate limit exceeded for peer: {peer}")]
     RateLimitExceeded { peer: String },
 
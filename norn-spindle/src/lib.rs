@@ -1,10 +1,13 @@
 //! Watchtower service for the Norn Protocol.
 //!
 //! Monitors the Weave on behalf of offline users, detects fraudulent activity
-//! (double-knots, stale commits), constructs fraud proofs, and manages rate
-//! limiting for proof submission.
+//! (double-knots, stale commits) and validator double-signing, constructs
+//! fraud proofs and slashing evidence, and manages rate limiting for their
+//! submission. Spindles are paid through a subscription registry and post
+//! bonds that are slashed if they miss fraud against a subscriber.
 
 pub mod error;
 pub mod monitor;
 pub mod rate_limit;
 pub mod service;
+pub mod subscription;
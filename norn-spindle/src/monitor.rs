@@ -1,11 +1,16 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
-use norn_crypto::keys::Keypair;
+use norn_crypto::address::pubkey_to_address;
+use norn_crypto::keys::{verify, Keypair};
+use norn_thread::chain::verify_checkpoint;
 use norn_thread::knot::compute_knot_id;
+use norn_types::consensus::{vote_signing_data, Vote};
 use norn_types::fraud::{FraudProof, FraudProofSubmission};
 use norn_types::knot::Knot;
-use norn_types::primitives::{KnotId, PublicKey, ThreadId, Timestamp, Version};
-use norn_types::thread::ThreadHeader;
+use norn_types::primitives::{KnotId, PublicKey, Signature, ThreadId, Timestamp, Version};
+use norn_types::slashing::SlashingEvidence;
+use norn_types::thread::ThreadCheckpoint;
+use norn_types::weave::{BlockHeader, CommitmentUpdate};
 
 /// Alert from the monitor about detected issues.
 #[derive(Debug, Clone)]
@@ -17,7 +22,12 @@ pub enum MonitorAlert {
     },
     StaleCommit {
         thread_id: ThreadId,
-        commitment: Box<ThreadHeader>,
+        commitment: Box<CommitmentUpdate>,
+        /// An owner-signed checkpoint past the commitment, if one is known.
+        checkpoint: Option<Box<ThreadCheckpoint>>,
+        /// Owner-signed knots from the archive that the commitment (or the
+        /// checkpoint) omits.
+        missing_knots: Vec<Knot>,
         expected_version: u64,
         actual_version: u64,
     },
@@ -32,6 +42,10 @@ struct ThreadWatch {
     /// Map: version -> Vec<(KnotId, Knot)>
     /// If more than one knot maps to the same version for a given thread, it is a double-knot.
     knots_by_version: HashMap<Version, Vec<(KnotId, Knot)>>,
+    /// Signatures of commitments already reported as stale.
+    reported_commitments: HashSet<Signature>,
+    /// Latest checkpoint signed by each key that signs for the thread.
+    checkpoints: HashMap<PublicKey, ThreadCheckpoint>,
 }
 
 impl ThreadWatch {
@@ -39,8 +53,62 @@ impl ThreadWatch {
         Self {
             thread_id,
             knots_by_version: HashMap::new(),
+            reported_commitments: HashSet::new(),
+            checkpoints: HashMap::new(),
         }
     }
+
+    /// Whether `key` signs for the thread: it derives the thread ID, or it
+    /// signed one of the archived knots.
+    fn signs_for_thread(&self, key: &PublicKey) -> bool {
+        pubkey_to_address(key) == self.thread_id
+            || self.knots_by_version.values().flatten().any(|(id, knot)| {
+                knot.before_states
+                    .iter()
+                    .zip(&knot.signatures)
+                    .any(|(s, sig)| {
+                        s.thread_id == self.thread_id
+                            && s.pubkey == *key
+                            && verify(id, sig, key).is_ok()
+                    })
+            })
+    }
+
+    /// Archived knots signed by `owner` at or past `version` and no later
+    /// than `timestamp`, one per version, in version order.
+    fn signed_knots_since(
+        &self,
+        owner: &PublicKey,
+        version: Version,
+        timestamp: Timestamp,
+    ) -> Vec<Knot> {
+        let mut versions: Vec<_> = self
+            .knots_by_version
+            .keys()
+            .filter(|v| **v >= version)
+            .copied()
+            .collect();
+        versions.sort_unstable();
+
+        versions
+            .into_iter()
+            .filter_map(|v| {
+                self.knots_by_version[&v].iter().find_map(|(id, knot)| {
+                    let index = knot
+                        .before_states
+                        .iter()
+                        .position(|s| s.thread_id == self.thread_id)?;
+                    let signed = knot.before_states[index].pubkey == *owner
+                        && knot.timestamp <= timestamp
+                        && knot
+                            .signatures
+                            .get(index)
+                            .is_some_and(|sig| verify(id, sig, owner).is_ok());
+                    signed.then(|| knot.clone())
+                })
+            })
+            .collect()
+    }
 }
 
 /// Monitors threads for fraudulent activity such as double-knots.
@@ -117,30 +185,86 @@ impl ThreadMonitor {
         None
     }
 
-    /// Process a new commitment and check for stale commits (version regression).
-    ///
-    /// Returns `Some(MonitorAlert::StaleCommit{...})` if `header.version < known_version`,
-    /// indicating the commitment references a version older than what is already known.
-    pub fn on_commitment(
-        &mut self,
-        thread_id: ThreadId,
-        header: &ThreadHeader,
-        known_version: u64,
-    ) -> Option<MonitorAlert> {
-        if !self.is_watching(&thread_id) {
-            return None;
+    /// Record a checkpoint for a watched thread. Knots its owner signed
+    /// below the checkpoint's version are dropped from the archive, since the
+    /// checkpoint proves them. Returns whether the checkpoint was recorded.
+    pub fn on_checkpoint(&mut self, checkpoint: &ThreadCheckpoint) -> bool {
+        let Some(watch) = self.watched.get_mut(&checkpoint.thread_id) else {
+            return false;
+        };
+        let newer = watch
+            .checkpoints
+            .get(&checkpoint.owner)
+            .is_none_or(|c| c.version < checkpoint.version);
+        if !newer
+            || verify_checkpoint(checkpoint).is_err()
+            || !watch.signs_for_thread(&checkpoint.owner)
+        {
+            return false;
         }
 
-        if header.version < known_version {
-            return Some(MonitorAlert::StaleCommit {
-                thread_id,
-                commitment: Box::new(header.clone()),
-                expected_version: known_version,
-                actual_version: header.version,
+        for entries in watch.knots_by_version.values_mut() {
+            entries.retain(|(_, knot)| {
+                !knot.before_states.iter().any(|s| {
+                    s.thread_id == checkpoint.thread_id
+                        && s.pubkey == checkpoint.owner
+                        && s.version < checkpoint.version
+                })
             });
         }
+        watch
+            .knots_by_version
+            .retain(|_, entries| !entries.is_empty());
+        watch
+            .checkpoints
+            .insert(checkpoint.owner, checkpoint.clone());
+        true
+    }
 
-        None
+    /// Process a commitment posted for a watched thread and check it against
+    /// the archive of knots seen for that thread.
+    ///
+    /// Returns `Some(MonitorAlert::StaleCommit{...})` if the archive holds
+    /// knots or a checkpoint signed by the thread's owner before the
+    /// commitment that move the thread past the committed version. Each
+    /// commitment is reported once.
+    pub fn on_commitment(&mut self, commitment: &CommitmentUpdate) -> Option<MonitorAlert> {
+        let watch = self.watched.get_mut(&commitment.thread_id)?;
+        if watch.reported_commitments.contains(&commitment.signature) {
+            return None;
+        }
+
+        // Start from the owner's checkpoint if it proves the commitment stale.
+        let checkpoint = watch
+            .checkpoints
+            .get(&commitment.owner)
+            .filter(|c| c.timestamp <= commitment.timestamp && c.version > commitment.version)
+            .cloned();
+        let floor = checkpoint
+            .as_ref()
+            .map_or(commitment.version, |c| c.version);
+        let missing_knots =
+            watch.signed_knots_since(&commitment.owner, floor, commitment.timestamp);
+        let expected_version = missing_knots
+            .iter()
+            .filter_map(|k| {
+                k.after_states
+                    .iter()
+                    .find(|s| s.thread_id == commitment.thread_id)
+            })
+            .map(|s| s.version)
+            .chain(checkpoint.as_ref().map(|c| c.version))
+            .max()?;
+
+        watch.reported_commitments.insert(commitment.signature);
+        Some(MonitorAlert::StaleCommit {
+            thread_id: commitment.thread_id,
+            commitment: Box::new(commitment.clone()),
+            checkpoint: checkpoint.map(Box::new),
+            missing_knots,
+            expected_version,
+            actual_version: commitment.version,
+        })
     }
 
     /// Build a `FraudProofSubmission` from a `MonitorAlert`.
@@ -166,11 +290,14 @@ impl ThreadMonitor {
             MonitorAlert::StaleCommit {
                 thread_id,
                 commitment,
+                checkpoint,
+                missing_knots,
                 ..
             } => FraudProof::StaleCommit {
                 thread_id: *thread_id,
                 commitment: commitment.clone(),
-                missing_knots: vec![],
+                checkpoint: checkpoint.clone(),
+                missing_knots: missing_knots.clone(),
             },
         };
 
@@ -198,9 +325,101 @@ impl Default for ThreadMonitor {
     }
 }
 
+/// Maximum number of heights (and views) tracked before evicting the oldest.
+const MAX_TRACKED_ROUNDS: usize = 1_000;
+
+/// Watches validator signatures on block headers and consensus votes, across
+/// forks, for validators signing two different blocks in the same round.
+pub struct ValidatorMonitor {
+    /// Height -> validator -> the first header it was seen signing.
+    headers: BTreeMap<u64, HashMap<PublicKey, BlockHeader>>,
+    /// View -> validator -> its first vote.
+    votes: BTreeMap<u64, HashMap<PublicKey, Vote>>,
+}
+
+impl ValidatorMonitor {
+    /// Create a new validator monitor.
+    pub fn new() -> Self {
+        Self {
+            headers: BTreeMap::new(),
+            votes: BTreeMap::new(),
+        }
+    }
+
+    /// Record the proposer's signature on `header`. Returns evidence if the
+    /// proposer was already seen signing a different header at the same
+    /// height.
+    pub fn on_header(&mut self, header: &BlockHeader) -> Vec<SlashingEvidence> {
+        if header.compute_hash() != header.hash
+            || verify(&header.hash, &header.proposer_signature, &header.proposer).is_err()
+        {
+            return Vec::new();
+        }
+
+        let seen = self.headers.entry(header.height).or_default();
+        let mut evidence = Vec::new();
+        match seen.get(&header.proposer) {
+            Some(earlier) if earlier.hash != header.hash => {
+                evidence.push(SlashingEvidence::DoubleSign {
+                    validator: header.proposer,
+                    header_a: Box::new(earlier.clone()),
+                    header_b: Box::new(header.clone()),
+                });
+            }
+            Some(_) => {}
+            None => {
+                seen.insert(header.proposer, header.clone());
+            }
+        }
+
+        evict_oldest(&mut self.headers);
+        evidence
+    }
+
+    /// Record a consensus vote. Returns evidence if its voter already voted
+    /// for a different block in the same view.
+    pub fn on_vote(&mut self, vote: &Vote) -> Option<SlashingEvidence> {
+        let sig_data = vote_signing_data(vote.phase, vote.view, &vote.block_hash);
+        if verify(&sig_data, &vote.signature, &vote.voter).is_err() {
+            return None;
+        }
+
+        let seen = self.votes.entry(vote.view).or_default();
+        let evidence = match seen.get(&vote.voter) {
+            Some(earlier) if earlier.block_hash != vote.block_hash => {
+                Some(SlashingEvidence::DoubleVote {
+                    vote_a: Box::new(earlier.clone()),
+                    vote_b: Box::new(vote.clone()),
+                })
+            }
+            Some(_) => None,
+            None => {
+                seen.insert(vote.voter, vote.clone());
+                None
+            }
+        };
+
+        evict_oldest(&mut self.votes);
+        evidence
+    }
+}
+
+impl Default for ValidatorMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn evict_oldest<T>(rounds: &mut BTreeMap<u64, T>) {
+    while rounds.len() > MAX_TRACKED_ROUNDS {
+        rounds.pop_first();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use norn_types::consensus::ConsensusPhase;
     use norn_types::knot::{KnotPayload, KnotType, ParticipantState, TransferPayload};
     use norn_types::primitives::NATIVE_TOKEN_ID;
 
@@ -242,6 +461,7 @@ mod tests {
             after_states: vec![after_state],
             payload,
             signatures: vec![],
+            cosignatures: Vec::new(),
         };
 
         knot.id = compute_knot_id(&knot);
@@ -372,84 +592,173 @@ mod tests {
         assert!(monitor.on_knot(&knot).is_none());
     }
 
-    /// Helper: create a test ThreadHeader with the given thread_id and version.
-    fn make_test_header(thread_id: ThreadId, version: Version) -> ThreadHeader {
-        ThreadHeader {
-            thread_id,
-            owner: [1u8; 32],
+    /// Helper: a transfer knot from `owner`'s thread at `version`, signed by
+    /// the owner.
+    fn make_signed_knot(owner: &Keypair, version: Version, timestamp: Timestamp) -> Knot {
+        let thread_id = norn_crypto::address::pubkey_to_address(&owner.public_key());
+        let mut knot = make_test_knot(thread_id, version, timestamp);
+        knot.before_states[0].pubkey = owner.public_key();
+        knot.after_states[0].pubkey = owner.public_key();
+        knot.after_states[0].state_hash = [version as u8 + 1; 32];
+        knot.id = compute_knot_id(&knot);
+        knot.signatures = vec![owner.sign(&knot.id)];
+        knot
+    }
+
+    /// Helper: a commitment for `owner`'s thread at `version`.
+    fn make_commitment(
+        owner: &Keypair,
+        version: Version,
+        timestamp: Timestamp,
+    ) -> CommitmentUpdate {
+        CommitmentUpdate {
+            thread_id: norn_crypto::address::pubkey_to_address(&owner.public_key()),
+            owner: owner.public_key(),
             version,
-            state_hash: [0u8; 32],
-            last_knot_hash: [0u8; 32],
-            prev_header_hash: [0u8; 32],
-            timestamp: 1000,
-            signature: [0u8; 64],
+            state_hash: [version as u8; 32],
+            prev_commitment_hash: [0u8; 32],
+            knot_count: version,
+            timestamp,
+            priority_fee: 0,
+            signature: [version as u8; 64],
         }
     }
 
     #[test]
-    fn test_stale_commit_detected_for_version_regression() {
+    fn test_stale_commit_detected_from_archive() {
+        let owner = Keypair::generate();
+        let thread_id = norn_crypto::address::pubkey_to_address(&owner.public_key());
         let mut monitor = ThreadMonitor::new();
-        let thread_id = [60u8; 20];
         monitor.watch(thread_id);
 
-        // Header at version 3, but known version is 5 — stale.
-        let header = make_test_header(thread_id, 3);
-        let alert = monitor.on_commitment(thread_id, &header, 5);
-        assert!(alert.is_some(), "stale commit should be detected");
+        // The owner signs knots taking the thread from version 3 to 6.
+        for version in 3..6 {
+            assert!(monitor
+                .on_knot(&make_signed_knot(&owner, version, 1000 + version))
+                .is_none());
+        }
 
-        match alert.unwrap() {
-            MonitorAlert::StaleCommit {
+        // A commitment at version 4 omits the knots at versions 4 and 5.
+        let commitment = make_commitment(&owner, 4, 2000);
+        match monitor.on_commitment(&commitment) {
+            Some(MonitorAlert::StaleCommit {
                 thread_id: tid,
+                missing_knots,
                 expected_version,
                 actual_version,
                 ..
-            } => {
+            }) => {
                 assert_eq!(tid, thread_id);
-                assert_eq!(expected_version, 5);
-                assert_eq!(actual_version, 3);
+                assert_eq!(expected_version, 6);
+                assert_eq!(actual_version, 4);
+                let versions: Vec<_> = missing_knots
+                    .iter()
+                    .map(|k| k.before_states[0].version)
+                    .collect();
+                assert_eq!(versions, vec![4, 5]);
             }
-            _ => panic!("expected StaleCommit alert"),
+            other => panic!("expected StaleCommit alert, got {:?}", other),
         }
+
+        // The same commitment is only reported once.
+        assert!(monitor.on_commitment(&commitment).is_none());
     }
 
     #[test]
-    fn test_stale_commit_not_triggered_for_valid_commitment() {
+    fn test_checkpoint_prunes_archive_and_proves_stale_commit() {
+        use norn_thread::chain::create_checkpoint;
+        use norn_types::thread::ThreadState;
+
+        let owner = Keypair::generate();
+        let thread_id = pubkey_to_address(&owner.public_key());
         let mut monitor = ThreadMonitor::new();
-        let thread_id = [70u8; 20];
         monitor.watch(thread_id);
+        for version in 0..10 {
+            monitor.on_knot(&make_signed_knot(&owner, version, 1000 + version));
+        }
 
-        // Header at version 5, known version is 5 — not stale (equal).
-        let header = make_test_header(thread_id, 5);
-        assert!(
-            monitor.on_commitment(thread_id, &header, 5).is_none(),
-            "equal version should not trigger stale commit"
-        );
-
-        // Header at version 7, known version is 5 — not stale (ahead).
-        let header = make_test_header(thread_id, 7);
-        assert!(
-            monitor.on_commitment(thread_id, &header, 5).is_none(),
-            "ahead version should not trigger stale commit"
-        );
+        // Checkpoints from other keys, or unsigned, are ignored.
+        let state = ThreadState::new();
+        let stranger = Keypair::generate();
+        assert!(!monitor.on_checkpoint(&create_checkpoint(
+            thread_id, &state, 8, [0u8; 32], None, 1500, &stranger
+        )));
+        let mut checkpoint = create_checkpoint(thread_id, &state, 8, [0u8; 32], None, 1500, &owner);
+        let mut forged = checkpoint.clone();
+        forged.version = 9;
+        assert!(!monitor.on_checkpoint(&forged));
+
+        assert!(monitor.on_checkpoint(&checkpoint));
+        assert_eq!(monitor.watched[&thread_id].knots_by_version.len(), 2);
+        // Older checkpoints do not replace it.
+        checkpoint.version = 5;
+        assert!(!monitor.on_checkpoint(&checkpoint));
+
+        // A commitment at version 4 is proven stale by the checkpoint and the
+        // knots after it, without the pruned history.
+        match monitor.on_commitment(&make_commitment(&owner, 4, 2000)) {
+            Some(MonitorAlert::StaleCommit {
+                checkpoint: Some(checkpoint),
+                missing_knots,
+                expected_version,
+                ..
+            }) => {
+                assert_eq!(checkpoint.version, 8);
+                assert_eq!(missing_knots.len(), 2);
+                assert_eq!(expected_version, 10);
+            }
+            other => panic!("expected StaleCommit alert, got {:?}", other),
+        }
+    }
 
-        // Unwatched thread should not trigger either.
-        let other_thread = [71u8; 20];
-        let header = make_test_header(other_thread, 1);
-        assert!(
-            monitor.on_commitment(other_thread, &header, 10).is_none(),
-            "unwatched thread should not trigger stale commit"
-        );
+    #[test]
+    fn test_stale_commit_not_triggered_for_valid_commitment() {
+        let owner = Keypair::generate();
+        let thread_id = norn_crypto::address::pubkey_to_address(&owner.public_key());
+        let mut monitor = ThreadMonitor::new();
+        monitor.watch(thread_id);
+        monitor.on_knot(&make_signed_knot(&owner, 3, 1000));
+        monitor.on_knot(&make_signed_knot(&owner, 4, 1500));
+
+        // A commitment covering every archived knot is not stale.
+        assert!(monitor
+            .on_commitment(&make_commitment(&owner, 5, 2000))
+            .is_none());
+
+        // Knots signed after the commitment are not missing from it.
+        assert!(monitor
+            .on_commitment(&make_commitment(&owner, 4, 1200))
+            .is_none());
+
+        // Unsigned knots prove nothing.
+        let mut unsigned = make_signed_knot(&owner, 7, 1000);
+        unsigned.signatures.clear();
+        monitor.on_knot(&unsigned);
+        assert!(monitor
+            .on_commitment(&make_commitment(&owner, 6, 2000))
+            .is_none());
+
+        // Unwatched threads are ignored.
+        let other = Keypair::generate();
+        let mut unwatched = ThreadMonitor::new();
+        unwatched.on_knot(&make_signed_knot(&other, 3, 1000));
+        assert!(unwatched
+            .on_commitment(&make_commitment(&other, 2, 2000))
+            .is_none());
     }
 
     #[test]
     fn test_build_fraud_proof_handles_stale_commit() {
-        let thread_id = [80u8; 20];
-        let header = make_test_header(thread_id, 3);
+        let owner = Keypair::generate();
+        let thread_id = norn_crypto::address::pubkey_to_address(&owner.public_key());
+        let knot = make_signed_knot(&owner, 3, 1000);
 
         let alert = MonitorAlert::StaleCommit {
             thread_id,
-            commitment: Box::new(header),
-            expected_version: 5,
+            commitment: Box::new(make_commitment(&owner, 3, 2000)),
+            checkpoint: None,
+            missing_knots: vec![knot.clone()],
+            expected_version: 4,
             actual_version: 3,
         };
 
@@ -482,12 +791,112 @@ mod tests {
                 ..
             } => {
                 assert_eq!(*tid, thread_id);
-                assert!(
-                    missing_knots.is_empty(),
-                    "missing_knots should be empty for now"
-                );
+                assert_eq!(missing_knots, &vec![knot]);
             }
             _ => panic!("expected StaleCommit proof"),
         }
     }
+
+    fn make_header(kp: &Keypair, height: u64, state_root: u8) -> BlockHeader {
+        let mut header = BlockHeader {
+            height,
+            hash: [0u8; 32],
+            prev_hash: [0u8; 32],
+            commitments_root: [0u8; 32],
+            registrations_root: [0u8; 32],
+            anchors_root: [0u8; 32],
+            name_registrations_root: [0u8; 32],
+            name_transfers_root: [0u8; 32],
+            name_record_updates_root: [0u8; 32],
+            fraud_proofs_root: [0u8; 32],
+            transfers_root: [0u8; 32],
+            token_definitions_root: [0u8; 32],
+            token_mints_root: [0u8; 32],
+            token_burns_root: [0u8; 32],
+            token_metadata_updates_root: [0u8; 32],
+            loom_deploys_root: [0u8; 32],
+            operator_handovers_root: [0u8; 32],
+            key_updates_root: [0u8; 32],
+            stake_operations_root: [0u8; 32],
+            slashing_evidence_root: [0u8; 32],
+            spindle_operations_root: [0u8; 32],
+            base_fee: 0,
+            state_root: [state_root; 32],
+            validator_set_hash: [0u8; 32],
+            timestamp: 1000,
+            proposer: kp.public_key(),
+            body_hashes: vec![],
+            proposer_signature: [0u8; 64],
+            certificate: None,
+        };
+        header.hash = header.compute_hash();
+        header.proposer_signature = kp.sign(&header.hash);
+        header
+    }
+
+    #[test]
+    fn test_detect_double_sign_across_forks() {
+        let kp = Keypair::generate();
+        let mut monitor = ValidatorMonitor::new();
+
+        assert!(monitor.on_header(&make_header(&kp, 5, 1)).is_empty());
+        // The same header again, and a different height, are fine.
+        assert!(monitor.on_header(&make_header(&kp, 5, 1)).is_empty());
+        assert!(monitor.on_header(&make_header(&kp, 6, 2)).is_empty());
+
+        let evidence = monitor.on_header(&make_header(&kp, 5, 2));
+        assert_eq!(evidence.len(), 1);
+        match &evidence[0] {
+            SlashingEvidence::DoubleSign {
+                validator,
+                header_a,
+                header_b,
+            } => {
+                assert_eq!(*validator, kp.public_key());
+                assert_eq!(header_a.height, header_b.height);
+                assert_ne!(header_a.hash, header_b.hash);
+            }
+            _ => panic!("expected DoubleSign evidence"),
+        }
+
+        // Forged signatures are ignored.
+        let mut forged = make_header(&kp, 7, 1);
+        monitor.on_header(&forged);
+        forged.state_root = [9u8; 32];
+        forged.hash = forged.compute_hash();
+        assert!(monitor.on_header(&forged).is_empty());
+    }
+
+    #[test]
+    fn test_detect_double_vote() {
+        let kp = Keypair::generate();
+        let vote = |view: u64, block_hash: [u8; 32]| Vote {
+            view,
+            phase: ConsensusPhase::Prepare,
+            block_hash,
+            voter: kp.public_key(),
+            signature: kp.sign(&vote_signing_data(
+                ConsensusPhase::Prepare,
+                view,
+                &block_hash,
+            )),
+            bls_signature: [0u8; 96],
+        };
+        let mut monitor = ValidatorMonitor::new();
+
+        assert!(monitor.on_vote(&vote(3, [1u8; 32])).is_none());
+        assert!(monitor.on_vote(&vote(3, [1u8; 32])).is_none());
+        assert!(monitor.on_vote(&vote(4, [2u8; 32])).is_none());
+        match monitor.on_vote(&vote(3, [2u8; 32])) {
+            Some(SlashingEvidence::DoubleVote { vote_a, vote_b }) => {
+                assert_eq!(vote_a.block_hash, [1u8; 32]);
+                assert_eq!(vote_b.block_hash, [2u8; 32]);
+            }
+            other => panic!("expected DoubleVote evidence, got {:?}", other),
+        }
+
+        let mut unsigned = vote(5, [1u8; 32]);
+        unsigned.signature = [0u8; 64];
+        assert!(monitor.on_vote(&unsigned).is_none());
+    }
 }
@@ -1,20 +1,44 @@
 use norn_crypto::address::pubkey_to_address;
 use norn_crypto::keys::Keypair;
+use norn_types::consensus::{ConsensusMessage, Vote};
+use norn_types::constants::SPINDLE_COVERAGE_REFRESH_SECS;
 use norn_types::fraud::FraudProofSubmission;
-use norn_types::network::NornMessage;
+use norn_types::network::{
+    spindle_coverage_signing_data, NornMessage, SpindleCoverage, ThreadRange,
+};
 use norn_types::primitives::{Address, ThreadId, Timestamp};
+use norn_types::slashing::SlashingEvidence;
+use norn_types::weave::{BlockHeader, CommitmentUpdate, WeaveBlock};
 
-use crate::monitor::ThreadMonitor;
+use crate::monitor::{ThreadMonitor, ValidatorMonitor};
+use crate::rate_limit::RateLimiter;
 
-/// The spindle service manages thread monitoring and fraud proof generation.
+/// Slashing evidence submissions allowed per offending validator. The
+/// per-validator bucket never refills: one piece of evidence is enough.
+const EVIDENCE_PER_VALIDATOR: u64 = 1;
+
+/// Slashing evidence submissions allowed in a burst across all validators.
+const EVIDENCE_BURST: u64 = 10;
+
+/// Slashing evidence submissions allowed per second across all validators.
+const EVIDENCE_PER_SECOND: u64 = 1;
+
+/// The spindle service manages thread and validator monitoring, fraud proof
+/// generation, and slashing evidence submission.
 ///
-/// It processes incoming `NornMessage`s, detects fraud via the `ThreadMonitor`,
-/// and produces fraud proof messages for broadcast.
+/// It processes incoming `NornMessage`s, detects fraud via the `ThreadMonitor`
+/// and validator double-signing via the `ValidatorMonitor`, and produces fraud
+/// proof and slashing evidence messages for broadcast.
 pub struct SpindleService {
     monitor: ThreadMonitor,
+    validator_monitor: ValidatorMonitor,
+    evidence_limiter: RateLimiter,
     keypair: Keypair,
     address: Address,
     pending_fraud_proofs: Vec<FraudProofSubmission>,
+    pending_slashing_evidence: Vec<SlashingEvidence>,
+    /// The last coverage advertised, and when.
+    last_coverage: Option<(Vec<ThreadRange>, Timestamp)>,
 }
 
 impl SpindleService {
@@ -23,9 +47,18 @@ impl SpindleService {
         let address = pubkey_to_address(&keypair.public_key());
         Self {
             monitor: ThreadMonitor::new(),
+            validator_monitor: ValidatorMonitor::new(),
+            evidence_limiter: RateLimiter::new(
+                EVIDENCE_PER_VALIDATOR,
+                0,
+                EVIDENCE_BURST,
+                EVIDENCE_PER_SECOND,
+            ),
             keypair,
             address,
             pending_fraud_proofs: Vec::new(),
+            pending_slashing_evidence: Vec::new(),
+            last_coverage: None,
         }
     }
 
@@ -49,12 +82,32 @@ impl SpindleService {
     /// If the message is a `KnotProposal` or `KnotResponse`, extract the knot
     /// and pass it to the monitor. If fraud is detected, build a fraud proof,
     /// add it to the pending queue, and return a `FraudProof` `NornMessage`.
+    ///
+    /// Commitments are checked against the knots seen for watched threads,
+    /// and blocks and consensus votes are passed to the validator monitor;
+    /// see [`SpindleService::on_block`] and [`SpindleService::on_vote`].
     pub fn on_message(&mut self, msg: &NornMessage, timestamp: Timestamp) -> Vec<NornMessage> {
         let mut responses = Vec::new();
 
         let knot = match msg {
             NornMessage::KnotProposal(knot) => Some(knot.as_ref()),
             NornMessage::KnotResponse(knot) => Some(knot.as_ref()),
+            NornMessage::Block(block) => return self.on_block(block, timestamp),
+            NornMessage::Checkpoint(checkpoint) => {
+                self.monitor.on_checkpoint(checkpoint);
+                return responses;
+            }
+            NornMessage::Commitment(commitment) => {
+                return self
+                    .on_commitment(commitment, timestamp)
+                    .into_iter()
+                    .collect()
+            }
+            NornMessage::Consensus(
+                ConsensusMessage::PrepareVote(vote)
+                | ConsensusMessage::PreCommitVote(vote)
+                | ConsensusMessage::CommitVote(vote),
+            ) => return self.on_vote(vote),
             _ => None,
         };
 
@@ -80,6 +133,106 @@ impl SpindleService {
     pub fn drain_fraud_proofs(&mut self) -> Vec<FraudProofSubmission> {
         std::mem::take(&mut self.pending_fraud_proofs)
     }
+
+    /// Check a block's header for double-signing and its commitments for
+    /// stale commits against watched threads.
+    pub fn on_block(&mut self, block: &WeaveBlock, timestamp: Timestamp) -> Vec<NornMessage> {
+        let mut responses = self.on_block_header(&block.header());
+        for commitment in &block.commitments {
+            responses.extend(self.on_commitment(commitment, timestamp));
+        }
+        responses
+    }
+
+    /// Check a commitment posted for a watched thread against the archive of
+    /// knots seen for it. If the commitment omits knots the owner already
+    /// signed, build a stale-commit fraud proof, add it to the pending
+    /// queue, and return a `FraudProof` `NornMessage`.
+    pub fn on_commitment(
+        &mut self,
+        commitment: &CommitmentUpdate,
+        timestamp: Timestamp,
+    ) -> Option<NornMessage> {
+        let alert = self.monitor.on_commitment(commitment)?;
+        tracing::warn!(
+            thread = %hex::encode(commitment.thread_id),
+            version = commitment.version,
+            "stale commitment detected"
+        );
+        let submission = ThreadMonitor::build_fraud_proof(
+            &alert,
+            self.keypair.public_key(),
+            timestamp,
+            &self.keypair,
+        );
+        self.pending_fraud_proofs.push(submission.clone());
+        Some(NornMessage::FraudProof(Box::new(submission)))
+    }
+
+    /// Check the validator signatures on a block header for double-signing.
+    /// Returns a `SlashingEvidence` message for each offender that passes the
+    /// rate limiter.
+    pub fn on_block_header(&mut self, header: &BlockHeader) -> Vec<NornMessage> {
+        let evidence = self.validator_monitor.on_header(header);
+        evidence
+            .into_iter()
+            .filter_map(|e| self.submit_evidence(e))
+            .collect()
+    }
+
+    /// Check a consensus vote for double-voting. Returns a `SlashingEvidence`
+    /// message if the voter double-voted and passes the rate limiter.
+    pub fn on_vote(&mut self, vote: &Vote) -> Vec<NornMessage> {
+        self.validator_monitor
+            .on_vote(vote)
+            .and_then(|e| self.submit_evidence(e))
+            .into_iter()
+            .collect()
+    }
+
+    /// Drain all pending slashing evidence that has been generated.
+    pub fn drain_slashing_evidence(&mut self) -> Vec<SlashingEvidence> {
+        std::mem::take(&mut self.pending_slashing_evidence)
+    }
+
+    /// Build a signed coverage advertisement for `ranges` if they differ from
+    /// the last one advertised, or if that one is due for a refresh.
+    pub fn coverage_advertisement(
+        &mut self,
+        ranges: Vec<ThreadRange>,
+        timestamp: Timestamp,
+    ) -> Option<SpindleCoverage> {
+        if let Some((last_ranges, last_at)) = &self.last_coverage {
+            if *last_ranges == ranges
+                && timestamp < last_at.saturating_add(SPINDLE_COVERAGE_REFRESH_SECS)
+            {
+                return None;
+            }
+        }
+        let mut coverage = SpindleCoverage {
+            pubkey: self.keypair.public_key(),
+            address: self.address,
+            ranges: ranges.clone(),
+            timestamp,
+            signature: [0u8; 64],
+        };
+        coverage.signature = self.keypair.sign(&spindle_coverage_signing_data(&coverage));
+        self.last_coverage = Some((ranges, timestamp));
+        Some(coverage)
+    }
+
+    /// Queue evidence for submission unless the rate limiter has already
+    /// spent this offender's (or the global) budget.
+    fn submit_evidence(&mut self, evidence: SlashingEvidence) -> Option<NornMessage> {
+        let offender = hex::encode(evidence.offender());
+        if !self.evidence_limiter.check_rate_limit(&offender, 1) {
+            tracing::debug!(validator = %offender, "slashing evidence rate limited");
+            return None;
+        }
+        tracing::warn!(validator = %offender, "validator double-signing detected");
+        self.pending_slashing_evidence.push(evidence.clone());
+        Some(NornMessage::SlashingEvidence(Box::new(evidence)))
+    }
 }
 
 #[cfg(test)]
@@ -127,6 +280,7 @@ mod tests {
             after_states: vec![after_state],
             payload,
             signatures: vec![],
+            cosignatures: Vec::new(),
         };
 
         knot.id = compute_knot_id(&knot);
@@ -239,6 +393,54 @@ mod tests {
             .is_empty());
     }
 
+    #[test]
+    fn test_service_challenges_stale_commitment() {
+        let mut service = SpindleService::new(Keypair::generate());
+        let owner = Keypair::generate();
+        let thread_id = pubkey_to_address(&owner.public_key());
+        service.watch_thread(thread_id);
+
+        // The owner signs a knot moving the thread from version 2 to 3.
+        let mut knot = make_test_knot(thread_id, 2, 1000);
+        knot.before_states[0].pubkey = owner.public_key();
+        knot.after_states[0].pubkey = owner.public_key();
+        knot.id = compute_knot_id(&knot);
+        knot.signatures = vec![owner.sign(&knot.id)];
+        assert!(service
+            .on_message(&NornMessage::KnotProposal(Box::new(knot)), 1000)
+            .is_empty());
+
+        // It then commits to version 2.
+        let commitment = norn_types::weave::CommitmentUpdate {
+            thread_id,
+            owner: owner.public_key(),
+            version: 2,
+            state_hash: [0u8; 32],
+            prev_commitment_hash: [0u8; 32],
+            knot_count: 2,
+            timestamp: 2000,
+            priority_fee: 0,
+            signature: [7u8; 64],
+        };
+        let responses = service.on_message(&NornMessage::Commitment(commitment.clone()), 2000);
+        assert_eq!(responses.len(), 1);
+        match &responses[0] {
+            NornMessage::FraudProof(submission) => match &submission.proof {
+                FraudProof::StaleCommit { missing_knots, .. } => {
+                    assert_eq!(missing_knots.len(), 1);
+                }
+                _ => panic!("expected StaleCommit fraud proof"),
+            },
+            _ => panic!("expected FraudProof message"),
+        }
+
+        // Seeing the same commitment again in a block does not re-report it.
+        assert!(service
+            .on_message(&NornMessage::Commitment(commitment), 2100)
+            .is_empty());
+        assert_eq!(service.drain_fraud_proofs().len(), 1);
+    }
+
     #[test]
     fn test_service_address() {
         let keypair = Keypair::generate();
@@ -246,4 +448,63 @@ mod tests {
         let service = SpindleService::new(keypair);
         assert_eq!(*service.address(), expected_address);
     }
+
+    #[test]
+    fn test_coverage_advertisement_refresh() {
+        let mut service = SpindleService::new(Keypair::generate());
+        let full = vec![ThreadRange::full()];
+
+        let coverage = service.coverage_advertisement(full.clone(), 1000).unwrap();
+        assert_eq!(coverage.address, *service.address());
+        assert!(norn_crypto::keys::verify(
+            &spindle_coverage_signing_data(&coverage),
+            &coverage.signature,
+            &coverage.pubkey
+        )
+        .is_ok());
+
+        // Unchanged coverage is only re-advertised once the refresh is due.
+        assert!(service.coverage_advertisement(full.clone(), 1001).is_none());
+        assert!(service
+            .coverage_advertisement(full, 1000 + SPINDLE_COVERAGE_REFRESH_SECS)
+            .is_some());
+        assert!(service.coverage_advertisement(vec![], 1301).is_some());
+    }
+
+    #[test]
+    fn test_service_submits_double_vote_evidence_once() {
+        use norn_types::consensus::{vote_signing_data, ConsensusMessage, ConsensusPhase, Vote};
+
+        let mut service = SpindleService::new(Keypair::generate());
+        let validator = Keypair::generate();
+        let vote = |block_hash: [u8; 32]| {
+            NornMessage::Consensus(ConsensusMessage::PrepareVote(Vote {
+                view: 9,
+                phase: ConsensusPhase::Prepare,
+                block_hash,
+                voter: validator.public_key(),
+                signature: validator.sign(&vote_signing_data(
+                    ConsensusPhase::Prepare,
+                    9,
+                    &block_hash,
+                )),
+                bls_signature: [0u8; 96],
+            }))
+        };
+
+        assert!(service.on_message(&vote([1u8; 32]), 1000).is_empty());
+        let responses = service.on_message(&vote([2u8; 32]), 1000);
+        assert_eq!(responses.len(), 1);
+        match &responses[0] {
+            NornMessage::SlashingEvidence(evidence) => {
+                assert_eq!(*evidence.offender(), validator.public_key());
+            }
+            _ => panic!("expected SlashingEvidence message"),
+        }
+
+        // A third conflicting vote is caught by the rate limiter.
+        assert!(service.on_message(&vote([3u8; 32]), 1000).is_empty());
+        assert_eq!(service.drain_slashing_evidence().len(), 1);
+        assert!(service.drain_slashing_evidence().is_empty());
+    }
 }
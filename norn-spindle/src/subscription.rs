@@ -0,0 +1,557 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use norn_crypto::address::pubkey_to_address;
+use norn_crypto::hash::blake3_hash;
+use norn_types::constants::{
+    MIN_SPINDLE_BOND, SPINDLE_FEE_PERIOD_BLOCKS, SPINDLE_MISSED_FRAUD_SLASH_BPS,
+};
+use norn_types::fraud::FraudProofSubmission;
+use norn_types::primitives::{Address, Amount, Hash, PublicKey, ThreadId};
+use norn_types::spindle::{SpindleBond, SpindleOperation, SpindleSlash, SpindleSubscription};
+
+use crate::error::SpindleError;
+
+/// Funds moved by the subscription registry, for the caller to apply to
+/// account balances.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Settlement {
+    /// Amounts taken from accounts into bonds or prepaid fees.
+    pub debits: Vec<(Address, Amount)>,
+    /// Amounts paid out of bonds or prepaid fees to accounts.
+    pub credits: Vec<(Address, Amount)>,
+}
+
+impl Settlement {
+    fn debit(&mut self, address: Address, amount: Amount) {
+        if amount > 0 {
+            self.debits.push((address, amount));
+        }
+    }
+
+    fn credit(&mut self, address: Address, amount: Amount) {
+        if amount > 0 {
+            self.credits.push((address, amount));
+        }
+    }
+
+    /// Append another settlement's movements to this one.
+    pub fn extend(&mut self, other: Settlement) {
+        self.debits.extend(other.debits);
+        self.credits.extend(other.credits);
+    }
+
+    /// Whether no funds move.
+    pub fn is_empty(&self) -> bool {
+        self.debits.is_empty() && self.credits.is_empty()
+    }
+}
+
+/// Registry of spindle bonds and paid subscriptions.
+///
+/// Spindles post a bond and set a fee per period; subscribers prepay a
+/// number of periods, which are paid out to the spindle as each period
+/// ends. If a fraud proof against a subscriber lands in a block and was not
+/// submitted by the subscriber's spindle, part of the spindle's bond is paid
+/// to the subscriber and the subscription ends. The registry only tracks
+/// amounts: every change returns a [`Settlement`] the caller applies to
+/// balances.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct SubscriptionRegistry {
+    bonds: BTreeMap<PublicKey, SpindleBond>,
+    subscriptions: BTreeMap<(ThreadId, PublicKey), SpindleSubscription>,
+    slash_history: Vec<SpindleSlash>,
+    /// Hashes of fraud proofs already checked against subscriptions, so the
+    /// same fraud reported twice only slashes once.
+    seen_fraud: BTreeSet<Hash>,
+    period_blocks: u64,
+}
+
+impl SubscriptionRegistry {
+    /// Create an empty registry with the default fee period.
+    pub fn new() -> Self {
+        Self {
+            bonds: BTreeMap::new(),
+            subscriptions: BTreeMap::new(),
+            slash_history: Vec::new(),
+            seen_fraud: BTreeSet::new(),
+            period_blocks: SPINDLE_FEE_PERIOD_BLOCKS,
+        }
+    }
+
+    /// Set the number of blocks in a fee period.
+    pub fn with_period_blocks(mut self, period_blocks: u64) -> Self {
+        self.period_blocks = period_blocks.max(1);
+        self
+    }
+
+    /// Number of blocks in a fee period.
+    pub fn period_blocks(&self) -> u64 {
+        self.period_blocks
+    }
+
+    /// Apply a spindle market operation at `height`. `balance` is the
+    /// signer's spendable native balance. Signatures are checked by the
+    /// weave before the operation is included in a block.
+    pub fn apply_operation(
+        &mut self,
+        op: &SpindleOperation,
+        balance: Amount,
+        height: u64,
+    ) -> Result<Settlement, SpindleError> {
+        let mut settlement = Settlement::default();
+        match op {
+            SpindleOperation::Bond {
+                spindle,
+                amount,
+                fee_per_period,
+                ..
+            } => {
+                if *amount > balance {
+                    return Err(insufficient_balance(*amount, balance));
+                }
+                let current = self.bonds.get(spindle).map(|b| b.amount).unwrap_or(0);
+                let total = current.saturating_add(*amount);
+                if total < MIN_SPINDLE_BOND {
+                    return Err(subscription_error(format!(
+                        "bond {} below minimum {}",
+                        total, MIN_SPINDLE_BOND
+                    )));
+                }
+                self.bonds.insert(
+                    *spindle,
+                    SpindleBond {
+                        spindle: *spindle,
+                        amount: total,
+                        fee_per_period: *fee_per_period,
+                    },
+                );
+                settlement.debit(pubkey_to_address(spindle), *amount);
+            }
+            SpindleOperation::Unbond {
+                spindle, amount, ..
+            } => {
+                let has_subscribers = self.subscriber_count(spindle) > 0;
+                let bond = self
+                    .bonds
+                    .get_mut(spindle)
+                    .ok_or_else(|| subscription_error("spindle has no bond".to_string()))?;
+                if *amount > bond.amount {
+                    return Err(subscription_error(format!(
+                        "unbond amount {} exceeds bond {}",
+                        amount, bond.amount
+                    )));
+                }
+                let remaining = bond.amount - amount;
+                if has_subscribers && remaining < MIN_SPINDLE_BOND {
+                    return Err(subscription_error(format!(
+                        "spindle has subscribers: bond must stay at least {}",
+                        MIN_SPINDLE_BOND
+                    )));
+                }
+                bond.amount = remaining;
+                if remaining == 0 {
+                    self.bonds.remove(spindle);
+                }
+                settlement.credit(pubkey_to_address(spindle), *amount);
+            }
+            SpindleOperation::Subscribe {
+                subscriber,
+                spindle,
+                periods,
+                max_fee_per_period,
+                ..
+            } => {
+                let bond = self
+                    .bonds
+                    .get(spindle)
+                    .filter(|b| b.amount >= MIN_SPINDLE_BOND)
+                    .ok_or_else(|| subscription_error("spindle is not bonded".to_string()))?;
+                if bond.fee_per_period > *max_fee_per_period {
+                    return Err(subscription_error(format!(
+                        "spindle fee {} exceeds maximum {}",
+                        bond.fee_per_period, max_fee_per_period
+                    )));
+                }
+                let thread_id = pubkey_to_address(subscriber);
+                if self.subscriptions.contains_key(&(thread_id, *spindle)) {
+                    return Err(subscription_error(
+                        "already subscribed to this spindle".to_string(),
+                    ));
+                }
+                let cost = bond
+                    .fee_per_period
+                    .checked_mul(*periods as Amount)
+                    .ok_or_else(|| subscription_error("subscription cost overflows".to_string()))?;
+                if cost > balance {
+                    return Err(insufficient_balance(cost, balance));
+                }
+                self.subscriptions.insert(
+                    (thread_id, *spindle),
+                    SpindleSubscription {
+                        subscriber: *subscriber,
+                        thread_id,
+                        spindle: *spindle,
+                        fee_per_period: bond.fee_per_period,
+                        start_height: height,
+                        periods: *periods,
+                        periods_paid: 0,
+                    },
+                );
+                settlement.debit(thread_id, cost);
+            }
+            SpindleOperation::Unsubscribe {
+                subscriber,
+                spindle,
+                ..
+            } => {
+                let thread_id = pubkey_to_address(subscriber);
+                let mut sub = self
+                    .subscriptions
+                    .remove(&(thread_id, *spindle))
+                    .ok_or_else(|| subscription_error("not subscribed".to_string()))?;
+                let earned = pay_out(&mut sub, height, self.period_blocks);
+                settlement.credit(pubkey_to_address(spindle), earned);
+                settlement.credit(thread_id, unpaid(&sub));
+            }
+        }
+        Ok(settlement)
+    }
+
+    /// Pay spindles for every fee period that has ended by `height`, and drop
+    /// subscriptions whose prepaid periods are all paid out.
+    pub fn settle(&mut self, height: u64) -> Settlement {
+        let mut settlement = Settlement::default();
+        let period_blocks = self.period_blocks;
+        self.subscriptions.retain(|_, sub| {
+            let earned = pay_out(sub, height, period_blocks);
+            settlement.credit(pubkey_to_address(&sub.spindle), earned);
+            sub.periods_paid < sub.periods
+        });
+        settlement
+    }
+
+    /// Slash the spindles that were paid to watch a thread harmed by the
+    /// fraud in `submission`, unless they submitted it themselves. Each
+    /// slashed bond share goes to the subscriber, whose unpaid fees are
+    /// refunded and whose subscription ends.
+    pub fn on_fraud_proof(&mut self, submission: &FraudProofSubmission, height: u64) -> Settlement {
+        let mut settlement = Settlement::default();
+        let proof_hash = match borsh::to_vec(&submission.proof) {
+            Ok(bytes) => blake3_hash(&bytes),
+            Err(_) => return settlement,
+        };
+        if !self.seen_fraud.insert(proof_hash) {
+            return settlement;
+        }
+
+        let missed: Vec<(ThreadId, PublicKey)> = self
+            .subscriptions
+            .iter()
+            .filter(|(_, sub)| {
+                sub.spindle != submission.submitter && submission.proof.harms(&sub.thread_id)
+            })
+            .map(|(key, _)| *key)
+            .collect();
+
+        for key in missed {
+            let Some(mut sub) = self.subscriptions.remove(&key) else {
+                continue;
+            };
+            let earned = pay_out(&mut sub, height, self.period_blocks);
+            settlement.credit(pubkey_to_address(&sub.spindle), earned);
+            settlement.credit(sub.thread_id, unpaid(&sub));
+
+            let Some(bond) = self.bonds.get_mut(&sub.spindle) else {
+                continue;
+            };
+            let amount = bond.amount * SPINDLE_MISSED_FRAUD_SLASH_BPS as Amount / 10_000;
+            bond.amount -= amount;
+            if bond.amount == 0 {
+                self.bonds.remove(&sub.spindle);
+            }
+            settlement.credit(sub.thread_id, amount);
+            self.slash_history.push(SpindleSlash {
+                spindle: sub.spindle,
+                thread_id: sub.thread_id,
+                amount,
+                height,
+            });
+        }
+        settlement
+    }
+
+    /// Get a spindle's bond.
+    pub fn bond(&self, spindle: &PublicKey) -> Option<&SpindleBond> {
+        self.bonds.get(spindle)
+    }
+
+    /// All spindle bonds.
+    pub fn bonds(&self) -> impl Iterator<Item = &SpindleBond> {
+        self.bonds.values()
+    }
+
+    /// Subscriptions paying spindles to watch `thread_id`.
+    pub fn watchers(&self, thread_id: &ThreadId) -> Vec<&SpindleSubscription> {
+        self.subscriptions
+            .range((*thread_id, [0u8; 32])..=(*thread_id, [0xffu8; 32]))
+            .map(|(_, sub)| sub)
+            .collect()
+    }
+
+    /// Subscriptions a spindle is paid to serve.
+    pub fn subscriptions_of(&self, spindle: &PublicKey) -> Vec<&SpindleSubscription> {
+        self.subscriptions
+            .values()
+            .filter(|sub| sub.spindle == *spindle)
+            .collect()
+    }
+
+    /// Number of threads a spindle is paid to watch.
+    pub fn subscriber_count(&self, spindle: &PublicKey) -> usize {
+        self.subscriptions
+            .values()
+            .filter(|sub| sub.spindle == *spindle)
+            .count()
+    }
+
+    /// Every spindle slashing applied so far, oldest first.
+    pub fn slash_history(&self) -> &[SpindleSlash] {
+        &self.slash_history
+    }
+}
+
+impl Default for SubscriptionRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Mark every period ended by `height` as paid, returning the fees now owed
+/// to the spindle.
+fn pay_out(sub: &mut SpindleSubscription, height: u64, period_blocks: u64) -> Amount {
+    let elapsed = (height.saturating_sub(sub.start_height) / period_blocks).min(sub.periods);
+    let due = elapsed.saturating_sub(sub.periods_paid);
+    sub.periods_paid = sub.periods_paid.max(elapsed);
+    sub.fee_per_period * due as Amount
+}
+
+/// Prepaid fees not yet paid out to the spindle.
+fn unpaid(sub: &SpindleSubscription) -> Amount {
+    sub.fee_per_period * (sub.periods - sub.periods_paid) as Amount
+}
+
+fn subscription_error(reason: String) -> SpindleError {
+    SpindleError::SubscriptionError { reason }
+}
+
+fn insufficient_balance(needed: Amount, available: Amount) -> SpindleError {
+    subscription_error(format!(
+        "insufficient balance: need {}, have {}",
+        needed, available
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use norn_types::constants::ONE_NORN;
+    use norn_types::fraud::FraudProof;
+    use norn_types::knot::{Knot, KnotPayload, KnotType, ParticipantState, TransferPayload};
+    use norn_types::primitives::NATIVE_TOKEN_ID;
+
+    const SPINDLE: PublicKey = [1u8; 32];
+    const OTHER_SPINDLE: PublicKey = [2u8; 32];
+    const SUBSCRIBER: PublicKey = [3u8; 32];
+    const FEE: Amount = 10;
+
+    fn bond(spindle: PublicKey, amount: Amount) -> SpindleOperation {
+        SpindleOperation::Bond {
+            spindle,
+            amount,
+            fee_per_period: FEE,
+            timestamp: 1000,
+            signature: [0u8; 64],
+        }
+    }
+
+    fn subscribe(spindle: PublicKey, periods: u64) -> SpindleOperation {
+        SpindleOperation::Subscribe {
+            subscriber: SUBSCRIBER,
+            spindle,
+            periods,
+            max_fee_per_period: FEE,
+            timestamp: 1000,
+            signature: [0u8; 64],
+        }
+    }
+
+    fn bonded_registry() -> SubscriptionRegistry {
+        let mut registry = SubscriptionRegistry::new().with_period_blocks(10);
+        registry
+            .apply_operation(&bond(SPINDLE, MIN_SPINDLE_BOND), MIN_SPINDLE_BOND, 0)
+            .unwrap();
+        registry
+    }
+
+    /// A double knot by `cheater` against the subscriber, reported by `submitter`.
+    fn double_knot(cheater: ThreadId, submitter: PublicKey) -> FraudProofSubmission {
+        let knot = |timestamp| {
+            Box::new(Knot {
+                id: [0u8; 32],
+                knot_type: KnotType::Transfer,
+                timestamp,
+                expiry: None,
+                before_states: [cheater, pubkey_to_address(&SUBSCRIBER)]
+                    .iter()
+                    .map(|thread_id| ParticipantState {
+                        thread_id: *thread_id,
+                        pubkey: [0u8; 32],
+                        version: 5,
+                        state_hash: [0u8; 32],
+                    })
+                    .collect(),
+                after_states: vec![],
+                payload: KnotPayload::Transfer(TransferPayload {
+                    token_id: NATIVE_TOKEN_ID,
+                    amount: 100,
+                    from: cheater,
+                    to: pubkey_to_address(&SUBSCRIBER),
+                    memo: None,
+                }),
+                signatures: vec![],
+                cosignatures: Vec::new(),
+            })
+        };
+        FraudProofSubmission {
+            proof: FraudProof::DoubleKnot {
+                thread_id: cheater,
+                knot_a: knot(1000),
+                knot_b: knot(2000),
+            },
+            submitter,
+            timestamp: 3000,
+            signature: [0u8; 64],
+        }
+    }
+
+    #[test]
+    fn test_subscription_fees_paid_per_period() {
+        let mut registry = bonded_registry();
+        let thread_id = pubkey_to_address(&SUBSCRIBER);
+
+        assert!(registry
+            .apply_operation(&subscribe(SPINDLE, 3), 29, 100)
+            .is_err());
+        let settlement = registry
+            .apply_operation(&subscribe(SPINDLE, 3), 30, 100)
+            .unwrap();
+        assert_eq!(settlement.debits, vec![(thread_id, 30)]);
+        assert_eq!(registry.watchers(&thread_id).len(), 1);
+        assert!(registry
+            .apply_operation(&subscribe(SPINDLE, 3), 30, 100)
+            .is_err());
+
+        // Nothing is owed until a full period has passed.
+        assert!(registry.settle(109).is_empty());
+        let spindle_addr = pubkey_to_address(&SPINDLE);
+        assert_eq!(registry.settle(125).credits, vec![(spindle_addr, 20)]);
+        assert_eq!(registry.settle(200).credits, vec![(spindle_addr, 10)]);
+        assert!(registry.watchers(&thread_id).is_empty());
+    }
+
+    #[test]
+    fn test_unsubscribe_refunds_unpaid_periods() {
+        let mut registry = bonded_registry();
+        registry
+            .apply_operation(&subscribe(SPINDLE, 5), 50, 0)
+            .unwrap();
+        let unsubscribe = SpindleOperation::Unsubscribe {
+            subscriber: SUBSCRIBER,
+            spindle: SPINDLE,
+            timestamp: 1000,
+            signature: [0u8; 64],
+        };
+        let settlement = registry.apply_operation(&unsubscribe, 0, 15).unwrap();
+        assert_eq!(
+            settlement.credits,
+            vec![
+                (pubkey_to_address(&SPINDLE), 10),
+                (pubkey_to_address(&SUBSCRIBER), 40)
+            ]
+        );
+        assert!(registry.apply_operation(&unsubscribe, 0, 16).is_err());
+    }
+
+    #[test]
+    fn test_unbond_keeps_minimum_while_subscribed() {
+        let mut registry = bonded_registry();
+        assert!(registry
+            .apply_operation(&bond(OTHER_SPINDLE, ONE_NORN), ONE_NORN, 0)
+            .is_err());
+        registry
+            .apply_operation(&subscribe(SPINDLE, 1), 10, 0)
+            .unwrap();
+
+        let unbond = |amount| SpindleOperation::Unbond {
+            spindle: SPINDLE,
+            amount,
+            timestamp: 1000,
+            signature: [0u8; 64],
+        };
+        assert!(registry.apply_operation(&unbond(1), 0, 5).is_err());
+
+        registry.settle(10);
+        let settlement = registry
+            .apply_operation(&unbond(MIN_SPINDLE_BOND), 0, 10)
+            .unwrap();
+        assert_eq!(
+            settlement.credits,
+            vec![(pubkey_to_address(&SPINDLE), MIN_SPINDLE_BOND)]
+        );
+        assert!(registry.bond(&SPINDLE).is_none());
+    }
+
+    #[test]
+    fn test_missed_fraud_slashes_bond() {
+        let mut registry = bonded_registry();
+        registry
+            .apply_operation(&bond(OTHER_SPINDLE, MIN_SPINDLE_BOND), MIN_SPINDLE_BOND, 0)
+            .unwrap();
+        registry
+            .apply_operation(&subscribe(SPINDLE, 4), 40, 0)
+            .unwrap();
+        registry
+            .apply_operation(&subscribe(OTHER_SPINDLE, 4), 40, 0)
+            .unwrap();
+        let thread_id = pubkey_to_address(&SUBSCRIBER);
+
+        // The subscriber's own fraud is not held against its spindles.
+        assert!(registry
+            .on_fraud_proof(&double_knot(thread_id, [9u8; 32]), 15)
+            .is_empty());
+
+        // Fraud reported by SPINDLE: only OTHER_SPINDLE missed it.
+        let submission = double_knot([7u8; 20], SPINDLE);
+        let slash = MIN_SPINDLE_BOND * SPINDLE_MISSED_FRAUD_SLASH_BPS as Amount / 10_000;
+        let settlement = registry.on_fraud_proof(&submission, 15);
+        assert_eq!(
+            settlement.credits,
+            vec![
+                (pubkey_to_address(&OTHER_SPINDLE), 10),
+                (thread_id, 30),
+                (thread_id, slash)
+            ]
+        );
+        assert_eq!(
+            registry.bond(&OTHER_SPINDLE).unwrap().amount,
+            MIN_SPINDLE_BOND - slash
+        );
+        assert_eq!(registry.bond(&SPINDLE).unwrap().amount, MIN_SPINDLE_BOND);
+        assert_eq!(registry.slash_history().len(), 1);
+        assert_eq!(registry.watchers(&thread_id).len(), 1);
+
+        // The same fraud included again does not slash twice.
+        assert!(registry.on_fraud_proof(&submission, 16).is_empty());
+    }
+}
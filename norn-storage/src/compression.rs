@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+#[cfg(feature = "sqlite")]
+use crate::error::StorageError;
+
+/// Default zstd level: a good ratio at close to lz4 write speed.
+pub const DEFAULT_ZSTD_LEVEL: i32 = 3;
+
+/// Codec tag stored alongside a value that was written uncompressed.
+pub const CODEC_RAW: u8 = 0;
+/// Codec tag stored alongside an lz4-compressed value.
+pub const CODEC_LZ4: u8 = 1;
+/// Codec tag stored alongside a zstd-compressed value.
+pub const CODEC_ZSTD: u8 = 2;
+
+/// Compression applied to stored values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Compression {
+    /// Store values as-is.
+    None,
+    /// Fast compression for hot, frequently rewritten data.
+    #[default]
+    Lz4,
+    /// Higher-ratio compression at the given level (1-22), suited to cold,
+    /// append-only data such as blocks.
+    Zstd(i32),
+}
+
+impl Compression {
+    /// The codec tag values written with this compression carry.
+    pub fn codec(&self) -> u8 {
+        match self {
+            Compression::None => CODEC_RAW,
+            Compression::Lz4 => CODEC_LZ4,
+            Compression::Zstd(_) => CODEC_ZSTD,
+        }
+    }
+
+    /// Compress `data`, returning the codec tag and the bytes to store.
+    /// Values that do not shrink are stored raw.
+    #[cfg(feature = "sqlite")]
+    pub fn compress(&self, data: &[u8]) -> Result<(u8, Vec<u8>), StorageError> {
+        let compressed = match self {
+            Compression::None => return Ok((CODEC_RAW, data.to_vec())),
+            Compression::Lz4 => lz4_flex::compress_prepend_size(data),
+            Compression::Zstd(level) => {
+                zstd::bulk::compress(data, *level).map_err(|e| StorageError::CompressionError {
+                    reason: e.to_string(),
+                })?
+            }
+        };
+        if compressed.len() < data.len() {
+            Ok((self.codec(), compressed))
+        } else {
+            Ok((CODEC_RAW, data.to_vec()))
+        }
+    }
+}
+
+/// Decompress a stored value written with the given codec tag.
+#[cfg(feature = "sqlite")]
+pub fn decompress(codec: u8, data: &[u8]) -> Result<Vec<u8>, StorageError> {
+    match codec {
+        CODEC_RAW => Ok(data.to_vec()),
+        CODEC_LZ4 => {
+            lz4_flex::decompress_size_prepended(data).map_err(|e| StorageError::CompressionError {
+                reason: format!("lz4: {}", e),
+            })
+        }
+        CODEC_ZSTD => zstd::decode_all(data).map_err(|e| StorageError::CompressionError {
+            reason: format!("zstd: {}", e),
+        }),
+        other => Err(StorageError::CompressionError {
+            reason: format!("unknown codec {}", other),
+        }),
+    }
+}
+
+impl fmt::Display for Compression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Compression::None => write!(f, "none"),
+            Compression::Lz4 => write!(f, "lz4"),
+            Compression::Zstd(level) => write!(f, "zstd:{}", level),
+        }
+    }
+}
+
+impl FromStr for Compression {
+    type Err = String;
+
+    /// Parse `none`, `lz4`, `zstd`, or `zstd:<level>`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Compression::None),
+            "lz4" => Ok(Compression::Lz4),
+            "zstd" => Ok(Compression::Zstd(DEFAULT_ZSTD_LEVEL)),
+            _ => {
+                let level = s.strip_prefix("zstd:").ok_or_else(|| {
+                    format!(
+                        "unknown compression '{}', expected 'none', 'lz4', 'zstd', or 'zstd:<level>'",
+                        s
+                    )
+                })?;
+                match level.parse::<i32>() {
+                    Ok(n) if (1..=22).contains(&n) => Ok(Compression::Zstd(n)),
+                    _ => Err(format!(
+                        "invalid zstd level '{}', expected an integer from 1 to 22",
+                        level
+                    )),
+                }
+            }
+        }
+    }
+}
+
+/// Compression settings for a store, with per-column-family overrides for
+/// backends that have column families.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompressionConfig {
+    /// Compression for data without an override.
+    pub compression: Compression,
+    /// Per-column-family overrides, keyed by column family name.
+    pub column_families: HashMap<String, Compression>,
+}
+
+impl CompressionConfig {
+    /// Use `compression` everywhere.
+    pub fn uniform(compression: Compression) -> Self {
+        Self {
+            compression,
+            column_families: HashMap::new(),
+        }
+    }
+
+    /// The compression for the named column family.
+    pub fn for_cf(&self, name: &str) -> Compression {
+        self.column_families
+            .get(name)
+            .copied()
+            .unwrap_or(self.compression)
+    }
+}
+
+impl Default for CompressionConfig {
+    /// lz4 for hot data, zstd for the append-only blocks column family.
+    fn default() -> Self {
+        Self {
+            compression: Compression::Lz4,
+            column_families: HashMap::from([(
+                "blocks".to_string(),
+                Compression::Zstd(DEFAULT_ZSTD_LEVEL),
+            )]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "sqlite")]
+    fn test_compression_roundtrip() {
+        let data = b"norn".repeat(256);
+        for compression in [
+            Compression::None,
+            Compression::Lz4,
+            Compression::Zstd(DEFAULT_ZSTD_LEVEL),
+        ] {
+            let (codec, stored) = compression.compress(&data).unwrap();
+            assert_eq!(codec, compression.codec());
+            assert_eq!(decompress(codec, &stored).unwrap(), data);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "sqlite")]
+    fn test_incompressible_values_stored_raw() {
+        let (codec, stored) = Compression::Lz4.compress(b"ab").unwrap();
+        assert_eq!(codec, CODEC_RAW);
+        assert_eq!(stored, b"ab");
+        assert!(decompress(9, b"ab").is_err());
+    }
+
+    #[test]
+    fn test_compression_parse_and_display() {
+        for s in ["none", "lz4", "zstd:3", "zstd:19"] {
+            assert_eq!(s.parse::<Compression>().unwrap().to_string(), s);
+        }
+        assert_eq!(
+            "zstd".parse::<Compression>().unwrap(),
+            Compression::Zstd(DEFAULT_ZSTD_LEVEL)
+        );
+        assert!("zstd:0".parse::<Compression>().is_err());
+        assert!("snappy".parse::<Compression>().is_err());
+    }
+
+    #[test]
+    fn test_column_family_overrides() {
+        let config = CompressionConfig::default();
+        assert_eq!(config.for_cf("merkle"), Compression::Lz4);
+        assert_eq!(
+            config.for_cf("blocks"),
+            Compression::Zstd(DEFAULT_ZSTD_LEVEL)
+        );
+        assert_eq!(
+            CompressionConfig::uniform(Compression::None).for_cf("blocks"),
+            Compression::None
+        );
+    }
+}
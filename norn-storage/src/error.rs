@@ -18,6 +18,12 @@ pub enum StorageError {
     #[error("RocksDB error: {reason}")]
     RocksDbError { reason: String },
 
+    #[error("sled error: {reason}")]
+    SledError { reason: String },
+
+    #[error("IndexedDB error: {reason}")]
+    IndexedDbError { reason: String },
+
     #[error("Serialization error: {reason}")]
     SerializationError { reason: String },
 
@@ -26,8 +32,15 @@ pub enum StorageError {
 
     #[error("Batch error: {reason}")]
     BatchError { reason: String },
+
+    #[error("Pruning error: {reason}")]
+    PruningError { reason: String },
+
+    #[error("Compression error: {reason}")]
+    CompressionError { reason: String },
 }
 
+#[cfg(feature = "sqlite")]
 impl From<rusqlite::Error> for StorageError {
     fn from(err: rusqlite::Error) -> Self {
         StorageError::SqliteError {
@@ -36,6 +49,7 @@ impl From<rusqlite::Error> for StorageError {
     }
 }
 
+#[cfg(feature = "rocksdb")]
 impl From<rocksdb::Error> for StorageError {
     fn from(err: rocksdb::Error) -> Self {
         StorageError::RocksDbError {
@@ -43,3 +57,12 @@ impl From<rocksdb::Error> for StorageError {
         }
     }
 }
+
+#[cfg(feature = "sled")]
+impl From<sled::Error> for StorageError {
+    fn from(err: sled::Error) -> Self {
+        StorageError::SledError {
+            reason: err.to_string(),
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use js_sys::{Array, Promise, Uint8Array};
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Event, IdbDatabase, IdbFactory, IdbRequest, IdbTransaction, IdbTransactionMode};
+
+use crate::error::StorageError;
+use crate::traits::BatchOp;
+use crate::write_back::WriteBackStore;
+
+/// Object store holding the key-value pairs.
+const OBJECT_STORE: &str = "kv";
+/// Schema version of the IndexedDB database.
+const DB_VERSION: u32 = 1;
+
+/// Browser persistence for wasm builds, backed by IndexedDB.
+///
+/// IndexedDB is asynchronous, so it does not implement [`KvStore`] itself.
+/// [`load`](Self::load) reads the database into a [`WriteBackStore`] that
+/// serves the synchronous API, and [`flush`](Self::flush) writes back what
+/// changed since the last flush in one transaction.
+///
+/// [`KvStore`]: crate::traits::KvStore
+pub struct IndexedDbStore {
+    db: IdbDatabase,
+}
+
+impl IndexedDbStore {
+    /// Open (or create) the named database. Works in windows and workers.
+    pub async fn open(name: &str) -> Result<Self, StorageError> {
+        let factory = js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str("indexedDB"))
+            .ok()
+            .and_then(|value| value.dyn_into::<IdbFactory>().ok())
+            .ok_or_else(|| StorageError::IndexedDbError {
+                reason: "IndexedDB is not available in this environment".to_string(),
+            })?;
+        let request = factory.open_with_u32(name, DB_VERSION).map_err(js_error)?;
+
+        let on_upgrade = Closure::<dyn FnMut(Event)>::new(|event: Event| {
+            let db = event
+                .target()
+                .and_then(|target| target.dyn_into::<IdbRequest>().ok())
+                .and_then(|request| request.result().ok())
+                .and_then(|result| result.dyn_into::<IdbDatabase>().ok());
+            if let Some(db) = db {
+                if !db.object_store_names().contains(OBJECT_STORE) {
+                    let _ = db.create_object_store(OBJECT_STORE);
+                }
+            }
+        });
+        request.set_onupgradeneeded(Some(on_upgrade.as_ref().unchecked_ref()));
+        let opened = request_future(&request).await;
+        request.set_onupgradeneeded(None);
+        opened.map_err(js_error)?;
+
+        let db = request
+            .result()
+            .and_then(|result| result.dyn_into::<IdbDatabase>())
+            .map_err(js_error)?;
+        Ok(Self { db })
+    }
+
+    /// Read every entry into a [`WriteBackStore`] with nothing pending.
+    pub async fn load(&self) -> Result<WriteBackStore, StorageError> {
+        let tx = self
+            .db
+            .transaction_with_str(OBJECT_STORE)
+            .map_err(js_error)?;
+        let store = tx.object_store(OBJECT_STORE).map_err(js_error)?;
+        // Both requests are issued, and their handlers attached, before
+        // either is awaited, so neither can complete unobserved.
+        let keys_request = store.get_all_keys().map_err(js_error)?;
+        let values_request = store.get_all().map_err(js_error)?;
+        let keys_done = request_future(&keys_request);
+        let values_done = request_future(&values_request);
+        keys_done.await.map_err(js_error)?;
+        values_done.await.map_err(js_error)?;
+
+        let keys = Array::from(&keys_request.result().map_err(js_error)?);
+        let values = Array::from(&values_request.result().map_err(js_error)?);
+        if keys.length() != values.length() {
+            return Err(StorageError::IndexedDbError {
+                reason: format!("read {} keys but {} values", keys.length(), values.length()),
+            });
+        }
+        let entries = keys
+            .iter()
+            .zip(values.iter())
+            .map(|(key, value)| {
+                (
+                    Uint8Array::new(&key).to_vec(),
+                    Uint8Array::new(&value).to_vec(),
+                )
+            })
+            .collect();
+        WriteBackStore::with_entries(entries)
+    }
+
+    /// Persist the writes pending in `store` atomically. On failure they are
+    /// requeued so the next flush retries them. Returns the number written.
+    pub async fn flush(&self, store: &WriteBackStore) -> Result<usize, StorageError> {
+        let ops = store.take_pending()?;
+        if ops.is_empty() {
+            return Ok(0);
+        }
+        match self.write(&ops).await {
+            Ok(()) => Ok(ops.len()),
+            Err(e) => {
+                store.requeue(ops)?;
+                Err(e)
+            }
+        }
+    }
+
+    async fn write(&self, ops: &[BatchOp]) -> Result<(), StorageError> {
+        let tx = self
+            .db
+            .transaction_with_str_and_mode(OBJECT_STORE, IdbTransactionMode::Readwrite)
+            .map_err(js_error)?;
+        let complete = transaction_future(&tx);
+        let store = tx.object_store(OBJECT_STORE).map_err(js_error)?;
+        for op in ops {
+            let queued = match op {
+                BatchOp::Put { key, value } => store.put_with_key(
+                    &Uint8Array::from(value.as_slice()),
+                    &Uint8Array::from(key.as_slice()),
+                ),
+                BatchOp::Delete { key } => store.delete(&Uint8Array::from(key.as_slice())),
+            };
+            if let Err(e) = queued {
+                let _ = tx.abort();
+                return Err(js_error(e));
+            }
+        }
+        complete.await.map(|_| ()).map_err(js_error)
+    }
+}
+
+/// A future that settles when `request` succeeds or fails. Handlers are
+/// attached immediately, before the caller yields.
+fn request_future(request: &IdbRequest) -> JsFuture {
+    JsFuture::from(Promise::new(&mut |resolve, reject| {
+        request.set_onsuccess(Some(&resolve));
+        request.set_onerror(Some(&reject));
+    }))
+}
+
+/// A future that settles when `tx` commits, fails, or is aborted.
+fn transaction_future(tx: &IdbTransaction) -> JsFuture {
+    JsFuture::from(Promise::new(&mut |resolve, reject| {
+        tx.set_oncomplete(Some(&resolve));
+        tx.set_onerror(Some(&reject));
+        tx.set_onabort(Some(&reject));
+    }))
+}
+
+fn js_error(err: JsValue) -> StorageError {
+    StorageError::IndexedDbError {
+        reason: err.as_string().unwrap_or_else(|| format!("{:?}", err)),
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use crate::traits::KvStore;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    async fn test_flush_persists_across_loads() {
+        let name = format!("norn-test-{}", js_sys::Math::random());
+        let db = IndexedDbStore::open(&name).await.unwrap();
+        let store = db.load().await.unwrap();
+        assert!(store.prefix_scan(b"").unwrap().is_empty());
+
+        store.put(b"prefix:a", b"1").unwrap();
+        store.put(b"prefix:b", b"2").unwrap();
+        store.put(b"other", b"3").unwrap();
+        store.delete(b"other").unwrap();
+        assert_eq!(db.flush(&store).await.unwrap(), 4);
+        assert_eq!(db.flush(&store).await.unwrap(), 0);
+
+        // A fresh connection sees exactly what was flushed.
+        let reopened = IndexedDbStore::open(&name).await.unwrap();
+        let loaded = reopened.load().await.unwrap();
+        assert_eq!(loaded.get(b"prefix:a").unwrap(), Some(b"1".to_vec()));
+        assert_eq!(loaded.prefix_scan(b"prefix:").unwrap().len(), 2);
+        assert!(!loaded.exists(b"other").unwrap());
+        assert!(!loaded.has_pending().unwrap());
+    }
+}
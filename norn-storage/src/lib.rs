@@ -1,13 +1,31 @@
 //! Storage abstraction for the Norn Protocol.
 //!
-//! Provides a [`KvStore`](traits::KvStore) trait with memory, SQLite, and RocksDB
-//! backends, plus specialized stores for Merkle trees, Threads, and Weave state.
+//! Provides a [`KvStore`](traits::KvStore) trait with memory, SQLite, RocksDB,
+//! and sled backends, plus specialized stores for Merkle trees, Threads, Weave state, and
+//! height-versioned values for historical queries, with configurable pruning and
+//! transparent value compression.
+//!
+//! The SQLite and RocksDB backends sit behind the default `sqlite` and
+//! `rocksdb` features. The pure-Rust sled backend needs no C or C++
+//! toolchain and is enabled with the `sled` feature. Builds without native
+//! backends, such as wasm builds for the browser, can persist a [`WriteBackStore`](write_back::WriteBackStore) to
+//! IndexedDB with the `indexeddb` feature.
 
+pub mod compression;
 pub mod error;
+#[cfg(feature = "indexeddb")]
+pub mod indexeddb;
 pub mod memory;
 pub mod merkle_store;
+pub mod pruning;
+#[cfg(feature = "rocksdb")]
 pub mod rocksdb;
+#[cfg(feature = "sled")]
+pub mod sled;
+#[cfg(feature = "sqlite")]
 pub mod sqlite;
 pub mod thread_store;
 pub mod traits;
+pub mod versioned_store;
 pub mod weave_store;
+pub mod write_back;
@@ -2,7 +2,7 @@ use std::collections::BTreeMap;
 use std::sync::RwLock;
 
 use crate::error::StorageError;
-use crate::traits::{BatchOp, BatchWriter, KvPairs, KvStore};
+use crate::traits::{BatchOp, KvPairs, KvSnapshot, KvStore};
 
 /// In-memory key-value store backed by a BTreeMap.
 /// Uses BTreeMap so that prefix_scan can leverage ordered iteration.
@@ -60,16 +60,9 @@ impl KvStore for MemoryStore {
         let data = self.data.read().map_err(|e| StorageError::ReadError {
             reason: e.to_string(),
         })?;
-        let results: KvPairs = data
-            .range(prefix.to_vec()..)
-            .take_while(|(k, _)| k.starts_with(prefix))
-            .map(|(k, v)| (k.clone(), v.clone()))
-            .collect();
-        Ok(results)
+        Ok(scan_prefix(&data, prefix))
     }
-}
 
-impl BatchWriter for MemoryStore {
     fn write_batch(&self, ops: Vec<BatchOp>) -> Result<(), StorageError> {
         let mut data = self.data.write().map_err(|e| StorageError::BatchError {
             reason: e.to_string(),
@@ -86,11 +79,42 @@ impl BatchWriter for MemoryStore {
         }
         Ok(())
     }
+
+    fn snapshot(&self) -> Result<Box<dyn KvSnapshot + '_>, StorageError> {
+        let data = self.data.read().map_err(|e| StorageError::ReadError {
+            reason: e.to_string(),
+        })?;
+        Ok(Box::new(MemorySnapshot { data: data.clone() }))
+    }
+}
+
+/// Read snapshot of a [`MemoryStore`]: a copy of its contents.
+pub(crate) struct MemorySnapshot {
+    pub(crate) data: BTreeMap<Vec<u8>, Vec<u8>>,
+}
+
+impl KvSnapshot for MemorySnapshot {
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, StorageError> {
+        Ok(self.data.get(key).cloned())
+    }
+
+    fn prefix_scan(&self, prefix: &[u8]) -> Result<KvPairs, StorageError> {
+        Ok(scan_prefix(&self.data, prefix))
+    }
+}
+
+/// Collect the entries of `data` whose keys start with `prefix`, in key order.
+fn scan_prefix(data: &BTreeMap<Vec<u8>, Vec<u8>>, prefix: &[u8]) -> KvPairs {
+    data.range(prefix.to_vec()..)
+        .take_while(|(k, _)| k.starts_with(prefix))
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::traits::WriteBatch;
 
     #[test]
     fn test_basic_crud() {
@@ -175,6 +199,23 @@ mod tests {
         assert_eq!(store.get(b"to_delete").unwrap(), None);
     }
 
+    #[test]
+    fn test_snapshot_isolated_from_later_writes() {
+        let store = MemoryStore::new();
+        store.put(b"prefix:a", b"1").unwrap();
+        let snapshot = store.snapshot().unwrap();
+
+        let mut batch = WriteBatch::new();
+        batch.put(b"prefix:a", b"2");
+        batch.put(b"prefix:b", b"3");
+        store.write_batch(batch.into_ops()).unwrap();
+
+        assert_eq!(snapshot.get(b"prefix:a").unwrap(), Some(b"1".to_vec()));
+        assert!(!snapshot.exists(b"prefix:b").unwrap());
+        assert_eq!(snapshot.prefix_scan(b"prefix:").unwrap().len(), 1);
+        assert_eq!(store.prefix_scan(b"prefix:").unwrap().len(), 2);
+    }
+
     #[test]
     fn test_get_nonexistent() {
         let store = MemoryStore::new();
@@ -2,7 +2,8 @@ use std::collections::HashMap;
 
 use norn_crypto::hash::blake3_hash;
 use norn_crypto::merkle::{
-    get_bit, hash_internal, hash_leaf, MerkleProof, SparseMerkleTree, EMPTY_HASH, TREE_DEPTH,
+    get_bit, hash_internal, hash_leaf, MerkleMultiProof, MerkleProof, SparseMerkleTree, EMPTY_HASH,
+    TREE_DEPTH,
 };
 use norn_types::primitives::Hash;
 
@@ -153,6 +154,22 @@ impl<S: KvStore> PersistentMerkleTree<S> {
         })
     }
 
+    /// Generate one compact proof for a set of keys.
+    /// Loads all data into an in-memory tree and proves from it.
+    pub fn prove_many(&self, keys: &[Hash]) -> Result<MerkleMultiProof, StorageError> {
+        let mut tree = SparseMerkleTree::new();
+        tree.insert_batch(self.load_all_data()?.into_iter().collect());
+        Ok(tree.prove_many(keys))
+    }
+
+    /// Verify a Merkle multiproof against a given root.
+    /// Delegates to SparseMerkleTree::verify_multiproof.
+    pub fn verify_multiproof(root: &Hash, proof: &MerkleMultiProof) -> Result<(), StorageError> {
+        SparseMerkleTree::verify_multiproof(root, proof).map_err(|e| StorageError::ReadError {
+            reason: e.to_string(),
+        })
+    }
+
     /// Verify a Merkle proof against a given root.
     /// Delegates to SparseMerkleTree::verify_proof.
     pub fn verify_proof(root: &Hash, proof: &MerkleProof) -> Result<(), StorageError> {
@@ -393,4 +410,24 @@ mod tests {
         assert_eq!(p_proof.value, m_proof.value);
         assert_eq!(p_proof.siblings, m_proof.siblings);
     }
+
+    #[test]
+    fn test_multiproof_matches_in_memory_tree() {
+        let mut persistent = make_tree();
+        let mut in_memory = SparseMerkleTree::new();
+        for i in 0..8u8 {
+            persistent.insert(blake3_hash(&[i]), vec![i; 4]).unwrap();
+            in_memory.insert(blake3_hash(&[i]), vec![i; 4]);
+        }
+
+        let keys: Vec<Hash> = [1u8, 5, 100].iter().map(|i| blake3_hash(&[*i])).collect();
+        let proof = persistent.prove_many(&keys).unwrap();
+        assert_eq!(proof, in_memory.prove_many(&keys));
+
+        let root = persistent.root().unwrap();
+        PersistentMerkleTree::<MemoryStore>::verify_multiproof(&root, &proof).unwrap();
+        assert!(
+            PersistentMerkleTree::<MemoryStore>::verify_multiproof(&EMPTY_HASH, &proof).is_err()
+        );
+    }
 }
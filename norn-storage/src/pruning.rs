@@ -0,0 +1,116 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::StorageError;
+
+/// How much block and state history a node retains.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PruningMode {
+    /// Keep every block and every historical state version.
+    #[default]
+    Archive,
+    /// Keep the given number of most recent blocks and their state history.
+    KeepRecent(u64),
+    /// Keep only the last finalized block and the state it produced.
+    Minimal,
+}
+
+impl PruningMode {
+    /// The lowest height whose data must be kept, given the height of the
+    /// last finalized snapshot. Data strictly below it may be pruned.
+    /// Returns `None` when nothing may be pruned.
+    pub fn prune_height(&self, finalized_height: u64) -> Option<u64> {
+        let retained = match self {
+            PruningMode::Archive => return None,
+            PruningMode::KeepRecent(n) => (*n).max(1),
+            PruningMode::Minimal => 1,
+        };
+        let height = finalized_height.saturating_sub(retained - 1);
+        (height > 0).then_some(height)
+    }
+}
+
+impl fmt::Display for PruningMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PruningMode::Archive => write!(f, "archive"),
+            PruningMode::KeepRecent(n) => write!(f, "keep-recent:{}", n),
+            PruningMode::Minimal => write!(f, "minimal"),
+        }
+    }
+}
+
+impl FromStr for PruningMode {
+    type Err = String;
+
+    /// Parse `archive`, `minimal`, or `keep-recent:<blocks>`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "archive" => Ok(PruningMode::Archive),
+            "minimal" => Ok(PruningMode::Minimal),
+            _ => {
+                let blocks = s.strip_prefix("keep-recent:").ok_or_else(|| {
+                    format!(
+                        "unknown pruning mode '{}', expected 'archive', 'minimal', or 'keep-recent:<blocks>'",
+                        s
+                    )
+                })?;
+                match blocks.parse::<u64>() {
+                    Ok(n) if n > 0 => Ok(PruningMode::KeepRecent(n)),
+                    _ => Err(format!(
+                        "invalid block count '{}' in pruning mode, expected a positive integer",
+                        blocks
+                    )),
+                }
+            }
+        }
+    }
+}
+
+/// Refuse to prune data at or above the last finalized snapshot, which the
+/// node needs to restart and to serve peers.
+pub fn check_prune_height(prune_height: u64, finalized_height: u64) -> Result<(), StorageError> {
+    if prune_height > finalized_height {
+        return Err(StorageError::PruningError {
+            reason: format!(
+                "refusing to prune below height {}: last finalized snapshot is at height {}",
+                prune_height, finalized_height
+            ),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_modes() {
+        assert_eq!("archive".parse(), Ok(PruningMode::Archive));
+        assert_eq!("minimal".parse(), Ok(PruningMode::Minimal));
+        assert_eq!("keep-recent:500".parse(), Ok(PruningMode::KeepRecent(500)));
+        assert!("keep-recent:0".parse::<PruningMode>().is_err());
+        assert!("keep-recent".parse::<PruningMode>().is_err());
+        assert!("full".parse::<PruningMode>().is_err());
+        assert_eq!(
+            PruningMode::KeepRecent(7).to_string().parse(),
+            Ok(PruningMode::KeepRecent(7))
+        );
+    }
+
+    #[test]
+    fn test_prune_height() {
+        assert_eq!(PruningMode::Archive.prune_height(1000), None);
+        assert_eq!(PruningMode::Minimal.prune_height(1000), Some(1000));
+        assert_eq!(PruningMode::Minimal.prune_height(0), None);
+        assert_eq!(PruningMode::KeepRecent(100).prune_height(1000), Some(901));
+        assert_eq!(PruningMode::KeepRecent(100).prune_height(50), None);
+    }
+
+    #[test]
+    fn test_check_prune_height() {
+        assert!(check_prune_height(10, 10).is_ok());
+        assert!(check_prune_height(11, 10).is_err());
+    }
+}
@@ -1,9 +1,12 @@
 use rocksdb::{
-    ColumnFamilyDescriptor, DBWithThreadMode, MultiThreaded, Options, WriteBatchWithTransaction,
+    BottommostLevelCompaction, ColumnFamilyDescriptor, CompactOptions, DBCompressionType,
+    DBWithThreadMode, Direction, IteratorMode, MultiThreaded, Options, SnapshotWithThreadMode,
+    WriteBatchWithTransaction,
 };
 
+use crate::compression::{Compression, CompressionConfig};
 use crate::error::StorageError;
-use crate::traits::{BatchOp, BatchWriter, KvPairs, KvStore};
+use crate::traits::{BatchOp, KvPairs, KvSnapshot, KvStore};
 
 /// Default column families for the Norn storage.
 pub const DEFAULT_CF: &str = "default";
@@ -14,12 +17,24 @@ pub const COMMITMENTS_CF: &str = "commitments";
 /// RocksDB-backed key-value store with column family support.
 pub struct RocksDbStore {
     db: DBWithThreadMode<MultiThreaded>,
+    cf_names: Vec<String>,
 }
 
 impl RocksDbStore {
     /// Open a RocksDB store at the given path with the specified column families.
     /// If `cf_names` is None, uses the default set of column families.
     pub fn new(path: &str, cf_names: Option<&[&str]>) -> Result<Self, StorageError> {
+        Self::with_compression(path, cf_names, &CompressionConfig::default())
+    }
+
+    /// Open a RocksDB store whose column families compress their SST files
+    /// as configured. Compression is native to RocksDB, so reads and writes
+    /// are unaffected and files written under other settings stay readable.
+    pub fn with_compression(
+        path: &str,
+        cf_names: Option<&[&str]>,
+        compression: &CompressionConfig,
+    ) -> Result<Self, StorageError> {
         let cfs = cf_names.unwrap_or(&[DEFAULT_CF, MERKLE_CF, BLOCKS_CF, COMMITMENTS_CF]);
 
         let mut opts = Options::default();
@@ -28,16 +43,35 @@ impl RocksDbStore {
 
         let cf_descriptors: Vec<ColumnFamilyDescriptor> = cfs
             .iter()
-            .map(|name| {
-                let cf_opts = Options::default();
-                ColumnFamilyDescriptor::new(*name, cf_opts)
-            })
+            .map(|name| ColumnFamilyDescriptor::new(*name, cf_options(compression.for_cf(name))))
             .collect();
 
         let db =
             DBWithThreadMode::<MultiThreaded>::open_cf_descriptors(&opts, path, cf_descriptors)?;
 
-        Ok(Self { db })
+        Ok(Self {
+            db,
+            cf_names: cfs.iter().map(|name| name.to_string()).collect(),
+        })
+    }
+
+    /// Rewrite every column family under its current compression settings by
+    /// compacting it fully, migrating data written before compression was
+    /// enabled or changed.
+    pub fn recompress(&self) -> Result<(), StorageError> {
+        let mut compact_opts = CompactOptions::default();
+        compact_opts.set_bottommost_level_compaction(BottommostLevelCompaction::ForceOptimized);
+        for name in &self.cf_names {
+            let cf = self
+                .db
+                .cf_handle(name)
+                .ok_or_else(|| StorageError::WriteError {
+                    reason: format!("Column family '{}' not found", name),
+                })?;
+            self.db
+                .compact_range_cf_opt(&cf, None::<&[u8]>, None::<&[u8]>, &compact_opts);
+        }
+        Ok(())
     }
 
     /// Get a value from a specific column family.
@@ -99,6 +133,21 @@ impl RocksDbStore {
     }
 }
 
+/// Column family options for the given compression.
+fn cf_options(compression: Compression) -> Options {
+    let mut opts = Options::default();
+    match compression {
+        Compression::None => opts.set_compression_type(DBCompressionType::None),
+        Compression::Lz4 => opts.set_compression_type(DBCompressionType::Lz4),
+        Compression::Zstd(level) => {
+            opts.set_compression_type(DBCompressionType::Zstd);
+            // Window bits and strategy keep RocksDB's defaults.
+            opts.set_compression_options(-14, level, 0, 0);
+        }
+    }
+    opts
+}
+
 impl KvStore for RocksDbStore {
     fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, StorageError> {
         let result = self.db.get(key)?;
@@ -134,9 +183,7 @@ impl KvStore for RocksDbStore {
         }
         Ok(results)
     }
-}
 
-impl BatchWriter for RocksDbStore {
     fn write_batch(&self, ops: Vec<BatchOp>) -> Result<(), StorageError> {
         let mut batch = WriteBatchWithTransaction::<false>::default();
         for op in ops {
@@ -152,6 +199,40 @@ impl BatchWriter for RocksDbStore {
         self.db.write(batch)?;
         Ok(())
     }
+
+    fn snapshot(&self) -> Result<Box<dyn KvSnapshot + '_>, StorageError> {
+        Ok(Box::new(RocksDbSnapshot {
+            snapshot: self.db.snapshot(),
+        }))
+    }
+}
+
+/// Read snapshot of a [`RocksDbStore`], backed by a native RocksDB snapshot.
+struct RocksDbSnapshot<'a> {
+    snapshot: SnapshotWithThreadMode<'a, DBWithThreadMode<MultiThreaded>>,
+}
+
+impl KvSnapshot for RocksDbSnapshot<'_> {
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, StorageError> {
+        Ok(self.snapshot.get(key)?)
+    }
+
+    fn prefix_scan(&self, prefix: &[u8]) -> Result<KvPairs, StorageError> {
+        let iter = self
+            .snapshot
+            .iterator(IteratorMode::From(prefix, Direction::Forward));
+        let mut results = Vec::new();
+        for item in iter {
+            let (key, value) = item.map_err(|e| StorageError::ReadError {
+                reason: e.to_string(),
+            })?;
+            if !key.starts_with(prefix) {
+                break;
+            }
+            results.push((key.to_vec(), value.to_vec()));
+        }
+        Ok(results)
+    }
 }
 
 #[cfg(test)]
@@ -257,6 +338,24 @@ mod tests {
         let _ = rocksdb::DB::destroy(&Options::default(), &path);
     }
 
+    #[test]
+    fn test_snapshot_isolated_from_later_writes() {
+        let path = temp_dir();
+        let store = RocksDbStore::new(&path, None).unwrap();
+        store.put(b"prefix:a", b"1").unwrap();
+        let snapshot = store.snapshot().unwrap();
+        store.put(b"prefix:a", b"2").unwrap();
+        store.put(b"prefix:b", b"3").unwrap();
+
+        assert_eq!(snapshot.get(b"prefix:a").unwrap(), Some(b"1".to_vec()));
+        assert_eq!(snapshot.prefix_scan(b"prefix:").unwrap().len(), 1);
+        assert_eq!(store.prefix_scan(b"prefix:").unwrap().len(), 2);
+
+        drop(snapshot);
+        drop(store);
+        let _ = rocksdb::DB::destroy(&Options::default(), &path);
+    }
+
     #[test]
     fn test_column_family_ops() {
         let path = temp_dir();
@@ -280,6 +379,28 @@ mod tests {
         let _ = rocksdb::DB::destroy(&Options::default(), &path);
     }
 
+    #[test]
+    fn test_recompress_keeps_data_readable() {
+        let path = temp_dir();
+        let store = RocksDbStore::with_compression(
+            &path,
+            None,
+            &CompressionConfig::uniform(Compression::Zstd(19)),
+        )
+        .unwrap();
+        store.put(b"key", b"value").unwrap();
+        store.put_cf(BLOCKS_CF, b"block", &[7u8; 4096]).unwrap();
+        store.recompress().unwrap();
+        assert_eq!(store.get(b"key").unwrap(), Some(b"value".to_vec()));
+        assert_eq!(
+            store.get_cf(BLOCKS_CF, b"block").unwrap(),
+            Some(vec![7u8; 4096])
+        );
+
+        drop(store);
+        let _ = rocksdb::DB::destroy(&Options::default(), &path);
+    }
+
     #[test]
     fn test_get_nonexistent() {
         let path = temp_dir();
@@ -0,0 +1,274 @@
+use std::collections::BTreeMap;
+use std::sync::{RwLock, RwLockReadGuard};
+
+use crate::error::StorageError;
+use crate::memory::MemorySnapshot;
+use crate::traits::{BatchOp, KvPairs, KvSnapshot, KvStore};
+
+/// Pure-Rust key-value store backed by sled, for builds without the C++
+/// toolchain RocksDB needs.
+///
+/// Values are stored as given: sled's own compression links zstd from C, so
+/// the `compression` setting does not apply to this backend.
+pub struct SledStore {
+    db: sled::Db,
+    /// Writers share the gate; taking a snapshot holds it exclusively while
+    /// the contents are copied, so the copy sees no half-applied write.
+    gate: RwLock<()>,
+}
+
+impl SledStore {
+    /// Open (or create) a sled database at the given path.
+    pub fn new(path: &str) -> Result<Self, StorageError> {
+        Self::from_config(sled::Config::new().path(path))
+    }
+
+    /// Open a database that lives only as long as the store, for tests.
+    pub fn temporary() -> Result<Self, StorageError> {
+        Self::from_config(sled::Config::new().temporary(true))
+    }
+
+    fn from_config(config: sled::Config) -> Result<Self, StorageError> {
+        Ok(Self {
+            db: config.open()?,
+            gate: RwLock::new(()),
+        })
+    }
+
+    /// Write everything buffered so far to disk. sled also flushes on its
+    /// own every half second.
+    pub fn flush(&self) -> Result<(), StorageError> {
+        self.db.flush()?;
+        Ok(())
+    }
+
+    fn write_gate(&self) -> Result<RwLockReadGuard<'_, ()>, StorageError> {
+        self.gate.read().map_err(|e| StorageError::WriteError {
+            reason: e.to_string(),
+        })
+    }
+}
+
+impl KvStore for SledStore {
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, StorageError> {
+        Ok(self.db.get(key)?.map(|value| value.to_vec()))
+    }
+
+    fn put(&self, key: &[u8], value: &[u8]) -> Result<(), StorageError> {
+        let _gate = self.write_gate()?;
+        self.db.insert(key, value)?;
+        Ok(())
+    }
+
+    fn delete(&self, key: &[u8]) -> Result<(), StorageError> {
+        let _gate = self.write_gate()?;
+        self.db.remove(key)?;
+        Ok(())
+    }
+
+    fn exists(&self, key: &[u8]) -> Result<bool, StorageError> {
+        Ok(self.db.contains_key(key)?)
+    }
+
+    fn prefix_scan(&self, prefix: &[u8]) -> Result<KvPairs, StorageError> {
+        scan_prefix(&self.db, prefix)
+    }
+
+    fn write_batch(&self, ops: Vec<BatchOp>) -> Result<(), StorageError> {
+        let mut batch = sled::Batch::default();
+        for op in ops {
+            match op {
+                BatchOp::Put { key, value } => batch.insert(key, value),
+                BatchOp::Delete { key } => batch.remove(key),
+            }
+        }
+        let _gate = self.write_gate()?;
+        self.db.apply_batch(batch)?;
+        Ok(())
+    }
+
+    /// sled has no native snapshots, so the snapshot is a point-in-time copy
+    /// of the store, like [`crate::memory::MemoryStore`]'s. Writes wait only
+    /// while it is taken.
+    fn snapshot(&self) -> Result<Box<dyn KvSnapshot + '_>, StorageError> {
+        let _gate = self.gate.write().map_err(|e| StorageError::ReadError {
+            reason: e.to_string(),
+        })?;
+        let data = self
+            .db
+            .iter()
+            .map(|item| {
+                let (key, value) = item?;
+                Ok((key.to_vec(), value.to_vec()))
+            })
+            .collect::<Result<BTreeMap<_, _>, StorageError>>()?;
+        Ok(Box::new(MemorySnapshot { data }))
+    }
+}
+
+/// Collect the entries whose keys start with `prefix`, in key order.
+fn scan_prefix(db: &sled::Db, prefix: &[u8]) -> Result<KvPairs, StorageError> {
+    db.scan_prefix(prefix)
+        .map(|item| {
+            let (key, value) = item?;
+            Ok((key.to_vec(), value.to_vec()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    fn make_store() -> SledStore {
+        SledStore::temporary().unwrap()
+    }
+
+    #[test]
+    fn test_basic_crud() {
+        let store = make_store();
+        let key = b"test_key";
+        let value = b"test_value";
+
+        // Put and get
+        store.put(key, value).unwrap();
+        assert_eq!(store.get(key).unwrap(), Some(value.to_vec()));
+
+        // Exists
+        assert!(store.exists(key).unwrap());
+        assert!(!store.exists(b"nonexistent").unwrap());
+
+        // Delete
+        store.delete(key).unwrap();
+        assert_eq!(store.get(key).unwrap(), None);
+        assert!(!store.exists(key).unwrap());
+    }
+
+    #[test]
+    fn test_overwrite() {
+        let store = make_store();
+        let key = b"key";
+        store.put(key, b"value1").unwrap();
+        store.put(key, b"value2").unwrap();
+        assert_eq!(store.get(key).unwrap(), Some(b"value2".to_vec()));
+    }
+
+    #[test]
+    fn test_prefix_scan() {
+        let store = make_store();
+        store.put(b"prefix:b", b"2").unwrap();
+        store.put(b"prefix:a", b"1").unwrap();
+        store.put(b"prefix:c", b"3").unwrap();
+        store.put(b"other:d", b"4").unwrap();
+
+        let results = store.prefix_scan(b"prefix:").unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0], (b"prefix:a".to_vec(), b"1".to_vec()));
+        assert!(store.prefix_scan(b"none:").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_batch_put_and_delete() {
+        let store = make_store();
+        store.put(b"to_delete", b"value").unwrap();
+
+        let ops = vec![
+            BatchOp::Put {
+                key: b"batch_key1".to_vec(),
+                value: b"batch_val1".to_vec(),
+            },
+            BatchOp::Put {
+                key: b"batch_key2".to_vec(),
+                value: b"batch_val2".to_vec(),
+            },
+            BatchOp::Delete {
+                key: b"to_delete".to_vec(),
+            },
+        ];
+
+        store.write_batch(ops).unwrap();
+
+        assert_eq!(
+            store.get(b"batch_key1").unwrap(),
+            Some(b"batch_val1".to_vec())
+        );
+        assert_eq!(
+            store.get(b"batch_key2").unwrap(),
+            Some(b"batch_val2".to_vec())
+        );
+        assert_eq!(store.get(b"to_delete").unwrap(), None);
+    }
+
+    #[test]
+    fn test_snapshot_isolated_from_later_writes() {
+        let store = std::sync::Arc::new(make_store());
+        store.put(b"prefix:a", b"1").unwrap();
+        let snapshot = store.snapshot().unwrap();
+
+        // Another thread writes while the snapshot is alive.
+        let (done_tx, done_rx) = mpsc::channel();
+        let writer = {
+            let store = store.clone();
+            std::thread::spawn(move || {
+                store.put(b"prefix:a", b"2").unwrap();
+                store.put(b"prefix:b", b"3").unwrap();
+                done_tx.send(()).unwrap();
+            })
+        };
+        done_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        writer.join().unwrap();
+
+        assert_eq!(snapshot.get(b"prefix:a").unwrap(), Some(b"1".to_vec()));
+        assert_eq!(snapshot.prefix_scan(b"prefix:").unwrap().len(), 1);
+        assert_eq!(store.prefix_scan(b"prefix:").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_write_on_snapshot_thread() {
+        let store = make_store();
+        store.put(b"key", b"old").unwrap();
+        let snapshot = store.snapshot().unwrap();
+        store.put(b"key", b"new").unwrap();
+        store.delete(b"key").unwrap();
+        store
+            .write_batch(vec![BatchOp::Put {
+                key: b"other".to_vec(),
+                value: b"value".to_vec(),
+            }])
+            .unwrap();
+        assert_eq!(snapshot.get(b"key").unwrap(), Some(b"old".to_vec()));
+        assert!(!snapshot.exists(b"other").unwrap());
+    }
+
+    #[test]
+    fn test_reopen_keeps_data() {
+        let path = std::env::temp_dir().join(format!(
+            "norn_sled_test_{}",
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        let path = path.to_string_lossy().to_string();
+        {
+            let store = SledStore::new(&path).unwrap();
+            store.put(b"key", b"value").unwrap();
+            store.flush().unwrap();
+        }
+        let store = SledStore::new(&path).unwrap();
+        assert_eq!(store.get(b"key").unwrap(), Some(b"value".to_vec()));
+
+        drop(store);
+        let _ = std::fs::remove_dir_all(&path);
+    }
+
+    #[test]
+    fn test_get_nonexistent() {
+        let store = make_store();
+        assert_eq!(store.get(b"no_such_key").unwrap(), None);
+        // Deleting a non-existent key should not error.
+        store.delete(b"no_such_key").unwrap();
+    }
+}
@@ -1,30 +1,99 @@
-use std::sync::Mutex;
+use std::sync::{Mutex, MutexGuard};
 
 use rusqlite::{params, Connection};
 
+use crate::compression::{decompress, Compression};
 use crate::error::StorageError;
-use crate::traits::{BatchOp, BatchWriter, KvPairs, KvStore};
+use crate::traits::{BatchOp, KvPairs, KvSnapshot, KvStore};
+
+/// Rows rewritten per transaction by [`SqliteStore::recompress`].
+const RECOMPRESS_PAGE_SIZE: i64 = 1000;
 
 /// SQLite-backed key-value store.
-/// Uses a single `kv` table with BLOB key and BLOB value columns.
+/// Uses a single `kv` table with BLOB key and BLOB value columns, plus a
+/// `codec` column recording how each value is compressed.
 pub struct SqliteStore {
     conn: Mutex<Connection>,
+    compression: Compression,
 }
 
 impl SqliteStore {
     /// Create a new SQLite store at the given path.
     /// Use `:memory:` for an in-memory database (useful for tests).
     pub fn new(path: &str) -> Result<Self, StorageError> {
+        Self::with_compression(path, Compression::default())
+    }
+
+    /// Create a SQLite store that compresses values it writes. Values written
+    /// under other settings, including databases created before compression
+    /// existed, stay readable.
+    pub fn with_compression(path: &str, compression: Compression) -> Result<Self, StorageError> {
         let conn = Connection::open(path)?;
         conn.execute_batch("PRAGMA journal_mode=WAL; PRAGMA synchronous=NORMAL;")?;
         conn.execute(
-            "CREATE TABLE IF NOT EXISTS kv (key BLOB PRIMARY KEY, value BLOB NOT NULL)",
+            "CREATE TABLE IF NOT EXISTS kv (key BLOB PRIMARY KEY, value BLOB NOT NULL, codec INTEGER NOT NULL DEFAULT 0)",
             [],
         )?;
+        // Databases created before compression have no codec column; their
+        // rows are uncompressed, which the column default records.
+        let has_codec: bool = conn.query_row(
+            "SELECT COUNT(*) > 0 FROM pragma_table_info('kv') WHERE name = 'codec'",
+            [],
+            |row| row.get(0),
+        )?;
+        if !has_codec {
+            conn.execute(
+                "ALTER TABLE kv ADD COLUMN codec INTEGER NOT NULL DEFAULT 0",
+                [],
+            )?;
+        }
         Ok(Self {
             conn: Mutex::new(conn),
+            compression,
         })
     }
+
+    /// Rewrite values stored with a different codec under the configured
+    /// compression, migrating data written before compression was enabled or
+    /// changed. Returns the number of values rewritten.
+    pub fn recompress(&self) -> Result<u64, StorageError> {
+        let conn = self.conn.lock().map_err(|e| StorageError::WriteError {
+            reason: e.to_string(),
+        })?;
+        let target = self.compression.codec();
+        let mut rewritten = 0;
+        let mut last_key: Vec<u8> = Vec::new();
+        loop {
+            let rows: Vec<(Vec<u8>, Vec<u8>, u8)> = {
+                let mut stmt = conn.prepare_cached(
+                    "SELECT key, value, codec FROM kv WHERE key > ?1 AND codec != ?2 ORDER BY key LIMIT ?3",
+                )?;
+                let rows = stmt
+                    .query_map(params![last_key, target, RECOMPRESS_PAGE_SIZE], |row| {
+                        Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+                    })?;
+                rows.collect::<Result<_, _>>()?
+            };
+            let Some((key, _, _)) = rows.last() else {
+                break;
+            };
+            last_key = key.clone();
+
+            let tx = conn.unchecked_transaction()?;
+            for (key, stored, codec) in rows {
+                let (new_codec, value) = self.compression.compress(&decompress(codec, &stored)?)?;
+                if new_codec != codec {
+                    tx.execute(
+                        "UPDATE kv SET value = ?2, codec = ?3 WHERE key = ?1",
+                        params![key, value, new_codec],
+                    )?;
+                    rewritten += 1;
+                }
+            }
+            tx.commit()?;
+        }
+        Ok(rewritten)
+    }
 }
 
 impl KvStore for SqliteStore {
@@ -32,24 +101,17 @@ impl KvStore for SqliteStore {
         let conn = self.conn.lock().map_err(|e| StorageError::ReadError {
             reason: e.to_string(),
         })?;
-        let mut stmt = conn.prepare_cached("SELECT value FROM kv WHERE key = ?1")?;
-        let mut rows = stmt.query(params![key])?;
-        match rows.next()? {
-            Some(row) => {
-                let value: Vec<u8> = row.get(0)?;
-                Ok(Some(value))
-            }
-            None => Ok(None),
-        }
+        read_value(&conn, key)
     }
 
     fn put(&self, key: &[u8], value: &[u8]) -> Result<(), StorageError> {
         let conn = self.conn.lock().map_err(|e| StorageError::WriteError {
             reason: e.to_string(),
         })?;
+        let (codec, value) = self.compression.compress(value)?;
         conn.execute(
-            "INSERT OR REPLACE INTO kv (key, value) VALUES (?1, ?2)",
-            params![key, value],
+            "INSERT OR REPLACE INTO kv (key, value, codec) VALUES (?1, ?2, ?3)",
+            params![key, value, codec],
         )?;
         Ok(())
     }
@@ -75,45 +137,9 @@ impl KvStore for SqliteStore {
         let conn = self.conn.lock().map_err(|e| StorageError::ReadError {
             reason: e.to_string(),
         })?;
-
-        // Compute the upper bound for the prefix range.
-        // Increment the last byte of the prefix; if it overflows, drop it and increment the
-        // previous byte, etc. If all bytes overflow we just scan to the end.
-        let upper_bound = increment_prefix(prefix);
-
-        let mut results = Vec::new();
-        match upper_bound {
-            Some(ref ub) => {
-                let mut stmt = conn.prepare_cached(
-                    "SELECT key, value FROM kv WHERE key >= ?1 AND key < ?2 ORDER BY key",
-                )?;
-                let mut rows = stmt.query(params![prefix, ub])?;
-                while let Some(row) = rows.next()? {
-                    let k: Vec<u8> = row.get(0)?;
-                    let v: Vec<u8> = row.get(1)?;
-                    results.push((k, v));
-                }
-            }
-            None => {
-                let mut stmt =
-                    conn.prepare_cached("SELECT key, value FROM kv WHERE key >= ?1 ORDER BY key")?;
-                let mut rows = stmt.query(params![prefix])?;
-                while let Some(row) = rows.next()? {
-                    let k: Vec<u8> = row.get(0)?;
-                    if !k.starts_with(prefix) {
-                        break;
-                    }
-                    let v: Vec<u8> = row.get(1)?;
-                    results.push((k, v));
-                }
-            }
-        }
-
-        Ok(results)
+        scan_prefix(&conn, prefix)
     }
-}
 
-impl BatchWriter for SqliteStore {
     fn write_batch(&self, ops: Vec<BatchOp>) -> Result<(), StorageError> {
         let conn = self.conn.lock().map_err(|e| StorageError::BatchError {
             reason: e.to_string(),
@@ -122,9 +148,10 @@ impl BatchWriter for SqliteStore {
         for op in ops {
             match op {
                 BatchOp::Put { key, value } => {
+                    let (codec, value) = self.compression.compress(&value)?;
                     tx.execute(
-                        "INSERT OR REPLACE INTO kv (key, value) VALUES (?1, ?2)",
-                        params![key, value],
+                        "INSERT OR REPLACE INTO kv (key, value, codec) VALUES (?1, ?2, ?3)",
+                        params![key, value, codec],
                     )?;
                 }
                 BatchOp::Delete { key } => {
@@ -135,6 +162,80 @@ impl BatchWriter for SqliteStore {
         tx.commit()?;
         Ok(())
     }
+
+    /// The snapshot holds the connection, so writes wait until it is dropped.
+    fn snapshot(&self) -> Result<Box<dyn KvSnapshot + '_>, StorageError> {
+        let conn = self.conn.lock().map_err(|e| StorageError::ReadError {
+            reason: e.to_string(),
+        })?;
+        Ok(Box::new(SqliteSnapshot { conn }))
+    }
+}
+
+/// Read snapshot of a [`SqliteStore`].
+struct SqliteSnapshot<'a> {
+    conn: MutexGuard<'a, Connection>,
+}
+
+impl KvSnapshot for SqliteSnapshot<'_> {
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, StorageError> {
+        read_value(&self.conn, key)
+    }
+
+    fn prefix_scan(&self, prefix: &[u8]) -> Result<KvPairs, StorageError> {
+        scan_prefix(&self.conn, prefix)
+    }
+}
+
+/// Read and decompress the value stored under `key`.
+fn read_value(conn: &Connection, key: &[u8]) -> Result<Option<Vec<u8>>, StorageError> {
+    let mut stmt = conn.prepare_cached("SELECT value, codec FROM kv WHERE key = ?1")?;
+    let mut rows = stmt.query(params![key])?;
+    match rows.next()? {
+        Some(row) => {
+            let value: Vec<u8> = row.get(0)?;
+            Ok(Some(decompress(row.get(1)?, &value)?))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Read and decompress every entry whose key starts with `prefix`, in key order.
+fn scan_prefix(conn: &Connection, prefix: &[u8]) -> Result<KvPairs, StorageError> {
+    // Compute the upper bound for the prefix range.
+    // Increment the last byte of the prefix; if it overflows, drop it and increment the
+    // previous byte, etc. If all bytes overflow we just scan to the end.
+    let upper_bound = increment_prefix(prefix);
+
+    let mut results = Vec::new();
+    match upper_bound {
+        Some(ref ub) => {
+            let mut stmt = conn.prepare_cached(
+                "SELECT key, value, codec FROM kv WHERE key >= ?1 AND key < ?2 ORDER BY key",
+            )?;
+            let mut rows = stmt.query(params![prefix, ub])?;
+            while let Some(row) = rows.next()? {
+                let k: Vec<u8> = row.get(0)?;
+                let v: Vec<u8> = row.get(1)?;
+                results.push((k, decompress(row.get(2)?, &v)?));
+            }
+        }
+        None => {
+            let mut stmt = conn
+                .prepare_cached("SELECT key, value, codec FROM kv WHERE key >= ?1 ORDER BY key")?;
+            let mut rows = stmt.query(params![prefix])?;
+            while let Some(row) = rows.next()? {
+                let k: Vec<u8> = row.get(0)?;
+                if !k.starts_with(prefix) {
+                    break;
+                }
+                let v: Vec<u8> = row.get(1)?;
+                results.push((k, decompress(row.get(2)?, &v)?));
+            }
+        }
+    }
+
+    Ok(results)
 }
 
 /// Increment a byte prefix to compute an exclusive upper bound.
@@ -248,6 +349,82 @@ mod tests {
         assert_eq!(store.get(b"no_such_key").unwrap(), None);
     }
 
+    #[test]
+    fn test_values_are_compressed_transparently() {
+        let store = make_store();
+        let value = b"norn".repeat(256);
+        store.put(b"big", &value).unwrap();
+        store.put(b"small", b"v").unwrap();
+        assert_eq!(store.get(b"big").unwrap(), Some(value.clone()));
+        assert_eq!(store.get(b"small").unwrap(), Some(b"v".to_vec()));
+        assert_eq!(store.prefix_scan(b"big").unwrap()[0].1, value);
+
+        let conn = store.conn.lock().unwrap();
+        let stored: Vec<u8> = conn
+            .query_row(
+                "SELECT value FROM kv WHERE key = ?1",
+                params![b"big"],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(stored.len() < value.len());
+    }
+
+    #[test]
+    fn test_migrates_uncompressed_database() {
+        let tmp = std::env::temp_dir().join(format!("norn_sqlite_migrate_{}", std::process::id()));
+        let path = tmp.to_str().unwrap();
+        let value = b"norn".repeat(256);
+        {
+            // A database written before the codec column existed.
+            let conn = Connection::open(path).unwrap();
+            conn.execute(
+                "CREATE TABLE kv (key BLOB PRIMARY KEY, value BLOB NOT NULL)",
+                [],
+            )
+            .unwrap();
+            conn.execute(
+                "INSERT INTO kv (key, value) VALUES (?1, ?2)",
+                params![b"old", value],
+            )
+            .unwrap();
+        }
+
+        let store = SqliteStore::with_compression(path, Compression::Zstd(3)).unwrap();
+        assert_eq!(store.get(b"old").unwrap(), Some(value.clone()));
+        assert_eq!(store.recompress().unwrap(), 1);
+        assert_eq!(store.recompress().unwrap(), 0);
+        assert_eq!(store.get(b"old").unwrap(), Some(value.clone()));
+        drop(store);
+
+        // Switching codecs rewrites again and keeps everything readable.
+        let store = SqliteStore::with_compression(path, Compression::Lz4).unwrap();
+        assert_eq!(store.recompress().unwrap(), 1);
+        assert_eq!(store.get(b"old").unwrap(), Some(value));
+        drop(store);
+        let _ = std::fs::remove_file(&tmp);
+    }
+
+    #[test]
+    fn test_snapshot_and_atomic_batch() {
+        let store = make_store();
+        store.put(b"prefix:a", b"1").unwrap();
+        {
+            let snapshot = store.snapshot().unwrap();
+            assert_eq!(snapshot.get(b"prefix:a").unwrap(), Some(b"1".to_vec()));
+            assert_eq!(snapshot.prefix_scan(b"prefix:").unwrap().len(), 1);
+        }
+
+        let mut batch = crate::traits::WriteBatch::new();
+        batch.put(b"prefix:b", b"2");
+        batch.delete(b"prefix:a");
+        store.write_batch(batch.into_ops()).unwrap();
+        assert_eq!(
+            store.prefix_scan(b"prefix:").unwrap(),
+            vec![(b"prefix:b".to_vec(), b"2".to_vec())]
+        );
+    }
+
     #[test]
     fn test_delete_nonexistent() {
         let store = make_store();
@@ -5,7 +5,7 @@ use norn_types::primitives::ThreadId;
 use norn_types::thread::{ThreadHeader, ThreadState};
 
 use crate::error::StorageError;
-use crate::traits::KvStore;
+use crate::traits::{KvStore, WriteBatch};
 
 const THREAD_HEADER_PREFIX: &[u8] = b"thread:header:";
 const THREAD_STATE_PREFIX: &[u8] = b"thread:state:";
@@ -30,17 +30,36 @@ impl<S: KvStore> ThreadStore<S> {
         key
     }
 
+    /// Apply the writes queued in `batch` atomically, e.g. every thread
+    /// update made by one block.
+    pub fn commit(&self, batch: WriteBatch) -> Result<(), StorageError> {
+        self.store.write_batch(batch.into_ops())
+    }
+
     /// Save a thread header.
     pub fn save_header(
         &self,
         thread_id: &ThreadId,
         header: &ThreadHeader,
+    ) -> Result<(), StorageError> {
+        let mut batch = WriteBatch::new();
+        self.stage_header(&mut batch, thread_id, header)?;
+        self.commit(batch)
+    }
+
+    /// Queue a thread header in `batch`.
+    pub fn stage_header(
+        &self,
+        batch: &mut WriteBatch,
+        thread_id: &ThreadId,
+        header: &ThreadHeader,
     ) -> Result<(), StorageError> {
         let key = Self::make_key(THREAD_HEADER_PREFIX, thread_id);
         let value = borsh::to_vec(header).map_err(|e| StorageError::SerializationError {
             reason: e.to_string(),
         })?;
-        self.store.put(&key, &value)
+        batch.put(&key, &value);
+        Ok(())
     }
 
     /// Load a thread header by thread ID.
@@ -64,12 +83,25 @@ impl<S: KvStore> ThreadStore<S> {
         &self,
         thread_id: &ThreadId,
         state: &ThreadState,
+    ) -> Result<(), StorageError> {
+        let mut batch = WriteBatch::new();
+        self.stage_state(&mut batch, thread_id, state)?;
+        self.commit(batch)
+    }
+
+    /// Queue a thread state in `batch`.
+    pub fn stage_state(
+        &self,
+        batch: &mut WriteBatch,
+        thread_id: &ThreadId,
+        state: &ThreadState,
     ) -> Result<(), StorageError> {
         let key = Self::make_key(THREAD_STATE_PREFIX, thread_id);
         let value = borsh::to_vec(state).map_err(|e| StorageError::SerializationError {
             reason: e.to_string(),
         })?;
-        self.store.put(&key, &value)
+        batch.put(&key, &value);
+        Ok(())
     }
 
     /// Load a thread state by thread ID.
@@ -93,13 +125,26 @@ impl<S: KvStore> ThreadStore<S> {
         &self,
         thread_id: &ThreadId,
         knots: &[Knot],
+    ) -> Result<(), StorageError> {
+        let mut batch = WriteBatch::new();
+        self.stage_uncommitted_knots(&mut batch, thread_id, knots)?;
+        self.commit(batch)
+    }
+
+    /// Queue the uncommitted knots of a thread in `batch`.
+    pub fn stage_uncommitted_knots(
+        &self,
+        batch: &mut WriteBatch,
+        thread_id: &ThreadId,
+        knots: &[Knot],
     ) -> Result<(), StorageError> {
         let key = Self::make_key(THREAD_KNOTS_PREFIX, thread_id);
         let value =
             borsh::to_vec(&knots.to_vec()).map_err(|e| StorageError::SerializationError {
                 reason: e.to_string(),
             })?;
-        self.store.put(&key, &value)
+        batch.put(&key, &value);
+        Ok(())
     }
 
     /// Load uncommitted knots for a thread.
@@ -190,6 +235,7 @@ mod tests {
                 memo: None,
             }),
             signatures: vec![[99u8; 64]],
+            cosignatures: Vec::new(),
         }
     }
 
@@ -265,6 +311,24 @@ mod tests {
         assert_eq!(threads, vec![id1, id2, id3]);
     }
 
+    #[test]
+    fn test_staged_updates_commit_together() {
+        let ts = make_store();
+        let (a, b) = ([1u8; 20], [2u8; 20]);
+        let mut batch = WriteBatch::new();
+        ts.stage_header(&mut batch, &a, &sample_header(a)).unwrap();
+        ts.stage_state(&mut batch, &a, &sample_state()).unwrap();
+        ts.stage_header(&mut batch, &b, &sample_header(b)).unwrap();
+        ts.stage_uncommitted_knots(&mut batch, &b, &[sample_knot()])
+            .unwrap();
+        assert!(ts.list_threads().unwrap().is_empty());
+
+        ts.commit(batch).unwrap();
+        assert_eq!(ts.list_threads().unwrap().len(), 2);
+        assert!(ts.load_state(&a).unwrap().is_some());
+        assert_eq!(ts.load_uncommitted_knots(&b).unwrap().len(), 1);
+    }
+
     #[test]
     fn test_list_threads_empty() {
         let ts = make_store();
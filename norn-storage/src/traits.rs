@@ -12,6 +12,59 @@ pub enum BatchOp {
     Delete { key: Vec<u8> },
 }
 
+/// Builder for a set of writes that are committed atomically with
+/// [`KvStore::write_batch`]. Operations apply in the order they were added.
+#[derive(Debug, Clone, Default)]
+pub struct WriteBatch {
+    ops: Vec<BatchOp>,
+}
+
+impl WriteBatch {
+    /// Create an empty batch.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a write of `value` to `key`.
+    pub fn put(&mut self, key: &[u8], value: &[u8]) {
+        self.ops.push(BatchOp::Put {
+            key: key.to_vec(),
+            value: value.to_vec(),
+        });
+    }
+
+    /// Queue a deletion of `key`.
+    pub fn delete(&mut self, key: &[u8]) {
+        self.ops.push(BatchOp::Delete { key: key.to_vec() });
+    }
+
+    /// Number of queued operations.
+    pub fn len(&self) -> usize {
+        self.ops.len()
+    }
+
+    /// Whether no operations are queued.
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    /// The queued operations, in order.
+    pub fn into_ops(self) -> Vec<BatchOp> {
+        self.ops
+    }
+}
+
+/// A consistent, read-only view of a store as of the moment it was taken.
+/// Writes made after the snapshot was taken are not visible through it.
+pub trait KvSnapshot {
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, StorageError>;
+    fn prefix_scan(&self, prefix: &[u8]) -> Result<KvPairs, StorageError>;
+
+    fn exists(&self, key: &[u8]) -> Result<bool, StorageError> {
+        Ok(self.get(key)?.is_some())
+    }
+}
+
 /// Core key-value store trait.
 pub trait KvStore: Send + Sync {
     fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, StorageError>;
@@ -19,11 +72,16 @@ pub trait KvStore: Send + Sync {
     fn delete(&self, key: &[u8]) -> Result<(), StorageError>;
     fn exists(&self, key: &[u8]) -> Result<bool, StorageError>;
     fn prefix_scan(&self, prefix: &[u8]) -> Result<KvPairs, StorageError>;
-}
 
-/// Atomic batch writer trait.
-pub trait BatchWriter: KvStore {
+    /// Apply every operation or none of them.
     fn write_batch(&self, ops: Vec<BatchOp>) -> Result<(), StorageError>;
+
+    /// Take a read snapshot of the store.
+    ///
+    /// A backend may hold a lock for the snapshot's lifetime (SQLite holds its
+    /// connection), so writes wait until it is dropped: never write to the
+    /// store from the thread holding a snapshot.
+    fn snapshot(&self) -> Result<Box<dyn KvSnapshot + '_>, StorageError>;
 }
 
 /// Blanket implementation of KvStore for `Arc<S>` so that a store can be shared
@@ -48,6 +106,14 @@ impl<S: KvStore + ?Sized> KvStore for Arc<S> {
     fn prefix_scan(&self, prefix: &[u8]) -> Result<KvPairs, StorageError> {
         (**self).prefix_scan(prefix)
     }
+
+    fn write_batch(&self, ops: Vec<BatchOp>) -> Result<(), StorageError> {
+        (**self).write_batch(ops)
+    }
+
+    fn snapshot(&self) -> Result<Box<dyn KvSnapshot + '_>, StorageError> {
+        (**self).snapshot()
+    }
 }
 
 /// Blanket implementation of KvStore for `Box<dyn KvStore>` so that a
@@ -72,4 +138,12 @@ impl KvStore for Box<dyn KvStore> {
     fn prefix_scan(&self, prefix: &[u8]) -> Result<KvPairs, StorageError> {
         (**self).prefix_scan(prefix)
     }
+
+    fn write_batch(&self, ops: Vec<BatchOp>) -> Result<(), StorageError> {
+        (**self).write_batch(ops)
+    }
+
+    fn snapshot(&self) -> Result<Box<dyn KvSnapshot + '_>, StorageError> {
+        (**self).snapshot()
+    }
 }
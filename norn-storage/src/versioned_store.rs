@@ -0,0 +1,230 @@
+use crate::error::StorageError;
+use crate::traits::{KvStore, WriteBatch};
+
+const VERSION_PREFIX: &[u8] = b"version:";
+
+/// Marker byte for a live value.
+const TAG_VALUE: u8 = 1;
+/// Marker byte for a deletion.
+const TAG_DELETED: u8 = 0;
+
+/// Storage layer that keeps every version of a key, tagged with the block
+/// height at which it was written, so values can be read as of any height.
+///
+/// Versions are stored under `version:` + key length + key + height (big
+/// endian), which keeps the versions of one key contiguous and ordered.
+pub struct VersionedStore<S: KvStore> {
+    store: S,
+}
+
+impl<S: KvStore> VersionedStore<S> {
+    /// Create a new VersionedStore wrapping the given KvStore.
+    pub fn new(store: S) -> Self {
+        Self { store }
+    }
+
+    /// Build the prefix shared by every version of a key.
+    fn key_prefix(key: &[u8]) -> Vec<u8> {
+        let mut prefix = Vec::with_capacity(VERSION_PREFIX.len() + 4 + key.len() + 8);
+        prefix.extend_from_slice(VERSION_PREFIX);
+        prefix.extend_from_slice(&(key.len() as u32).to_be_bytes());
+        prefix.extend_from_slice(key);
+        prefix
+    }
+
+    fn version_key(key: &[u8], height: u64) -> Vec<u8> {
+        let mut version_key = Self::key_prefix(key);
+        version_key.extend_from_slice(&height.to_be_bytes());
+        version_key
+    }
+
+    /// Record the value of a key as of a block height. A later write at the
+    /// same height replaces the earlier one.
+    pub fn put_at(&self, key: &[u8], height: u64, value: &[u8]) -> Result<(), StorageError> {
+        let mut batch = WriteBatch::new();
+        Self::stage_put_at(&mut batch, key, height, value);
+        self.store.write_batch(batch.into_ops())
+    }
+
+    /// Record that a key was deleted at a block height.
+    pub fn delete_at(&self, key: &[u8], height: u64) -> Result<(), StorageError> {
+        let mut batch = WriteBatch::new();
+        Self::stage_delete_at(&mut batch, key, height);
+        self.store.write_batch(batch.into_ops())
+    }
+
+    /// Queue a version of a key in `batch`, so it can be committed together
+    /// with the latest value.
+    pub fn stage_put_at(batch: &mut WriteBatch, key: &[u8], height: u64, value: &[u8]) {
+        let mut tagged = Vec::with_capacity(1 + value.len());
+        tagged.push(TAG_VALUE);
+        tagged.extend_from_slice(value);
+        batch.put(&Self::version_key(key, height), &tagged);
+    }
+
+    /// Queue a deletion marker for a key in `batch`.
+    pub fn stage_delete_at(batch: &mut WriteBatch, key: &[u8], height: u64) {
+        batch.put(&Self::version_key(key, height), &[TAG_DELETED]);
+    }
+
+    /// Read the value of a key as of a block height: the latest version
+    /// written at or below it. Returns `None` if the key had no value then.
+    pub fn get_at(&self, key: &[u8], height: u64) -> Result<Option<Vec<u8>>, StorageError> {
+        let mut latest: Option<(u64, Vec<u8>)> = None;
+        for (version_height, value) in self.versions(key)? {
+            if version_height > height {
+                continue;
+            }
+            if latest.as_ref().is_none_or(|(h, _)| version_height >= *h) {
+                latest = Some((version_height, value));
+            }
+        }
+        match latest {
+            Some((_, value)) => match value.split_first() {
+                Some((&TAG_VALUE, data)) => Ok(Some(data.to_vec())),
+                Some((&TAG_DELETED, _)) => Ok(None),
+                _ => Err(StorageError::DeserializationError {
+                    reason: "invalid version tag".to_string(),
+                }),
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// Heights at which versions of a key were written, in ascending order.
+    pub fn heights(&self, key: &[u8]) -> Result<Vec<u64>, StorageError> {
+        let mut heights: Vec<u64> = self.versions(key)?.into_iter().map(|(h, _)| h).collect();
+        heights.sort_unstable();
+        Ok(heights)
+    }
+
+    /// Drop versions that are no longer needed to answer reads at or above
+    /// `height`. For each key the newest version below `height` is kept, since
+    /// it is still the value at `height` when nothing newer was written.
+    /// Returns the number of versions removed.
+    pub fn prune_below(&self, height: u64) -> Result<u64, StorageError> {
+        let mut pairs = self.store.prefix_scan(VERSION_PREFIX)?;
+        pairs.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut batch = WriteBatch::new();
+        let mut removed = 0;
+        // Newest version below the cutoff seen so far for the current key,
+        // and whether it is a deletion marker.
+        let mut kept: Option<(Vec<u8>, bool)> = None;
+        for (version_key, value) in pairs {
+            let Some(split) = version_key.len().checked_sub(8) else {
+                continue;
+            };
+            let same_key = kept.as_ref().is_some_and(|(k, _)| {
+                k.len() == version_key.len() && k[..split] == version_key[..split]
+            });
+            if !same_key {
+                removed += drop_deletion_marker(&mut batch, kept.take());
+            }
+            let mut suffix = [0u8; 8];
+            suffix.copy_from_slice(&version_key[split..]);
+            if u64::from_be_bytes(suffix) >= height {
+                continue;
+            }
+            // Versions of a key sort by height, so this one supersedes the
+            // previously kept version.
+            if let Some((superseded, _)) = kept.take() {
+                batch.delete(&superseded);
+                removed += 1;
+            }
+            kept = Some((version_key, value.first() == Some(&TAG_DELETED)));
+        }
+        removed += drop_deletion_marker(&mut batch, kept);
+        self.store.write_batch(batch.into_ops())?;
+        Ok(removed)
+    }
+
+    fn versions(&self, key: &[u8]) -> Result<Vec<(u64, Vec<u8>)>, StorageError> {
+        let prefix = Self::key_prefix(key);
+        let pairs = self.store.prefix_scan(&prefix)?;
+        let mut versions = Vec::with_capacity(pairs.len());
+        for (version_key, value) in pairs {
+            let suffix: [u8; 8] = version_key[prefix.len()..].try_into().map_err(|_| {
+                StorageError::DeserializationError {
+                    reason: "malformed version key".to_string(),
+                }
+            })?;
+            versions.push((u64::from_be_bytes(suffix), value));
+        }
+        Ok(versions)
+    }
+}
+
+/// A deletion marker left below the cutoff carries no information once the
+/// versions before it are gone.
+fn drop_deletion_marker(batch: &mut WriteBatch, kept: Option<(Vec<u8>, bool)>) -> u64 {
+    match kept {
+        Some((version_key, true)) => {
+            batch.delete(&version_key);
+            1
+        }
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::MemoryStore;
+
+    #[test]
+    fn test_get_at_returns_latest_version_at_or_below_height() {
+        let store = VersionedStore::new(MemoryStore::new());
+        store.put_at(b"balance", 3, b"10").unwrap();
+        store.put_at(b"balance", 7, b"25").unwrap();
+
+        assert_eq!(store.get_at(b"balance", 2).unwrap(), None);
+        assert_eq!(store.get_at(b"balance", 3).unwrap(), Some(b"10".to_vec()));
+        assert_eq!(store.get_at(b"balance", 6).unwrap(), Some(b"10".to_vec()));
+        assert_eq!(store.get_at(b"balance", 7).unwrap(), Some(b"25".to_vec()));
+        assert_eq!(
+            store.get_at(b"balance", u64::MAX).unwrap(),
+            Some(b"25".to_vec())
+        );
+        assert_eq!(store.heights(b"balance").unwrap(), vec![3, 7]);
+    }
+
+    #[test]
+    fn test_delete_at_hides_value_from_later_heights() {
+        let store = VersionedStore::new(MemoryStore::new());
+        store.put_at(b"k", 1, b"v").unwrap();
+        store.delete_at(b"k", 4).unwrap();
+
+        assert_eq!(store.get_at(b"k", 3).unwrap(), Some(b"v".to_vec()));
+        assert_eq!(store.get_at(b"k", 4).unwrap(), None);
+    }
+
+    #[test]
+    fn test_keys_sharing_a_prefix_are_isolated() {
+        let store = VersionedStore::new(MemoryStore::new());
+        store.put_at(b"ab", 1, b"short").unwrap();
+        store.put_at(b"abc", 2, b"long").unwrap();
+
+        assert_eq!(store.get_at(b"ab", 5).unwrap(), Some(b"short".to_vec()));
+        assert_eq!(store.heights(b"ab").unwrap(), vec![1]);
+    }
+
+    #[test]
+    fn test_prune_below_keeps_value_at_cutoff() {
+        let store = VersionedStore::new(MemoryStore::new());
+        store.put_at(b"a", 1, b"a1").unwrap();
+        store.put_at(b"a", 3, b"a3").unwrap();
+        store.put_at(b"a", 8, b"a8").unwrap();
+        store.put_at(b"b", 2, b"b2").unwrap();
+        store.delete_at(b"b", 4).unwrap();
+        store.put_at(b"c", 9, b"c9").unwrap();
+
+        assert_eq!(store.prune_below(5).unwrap(), 3);
+        assert_eq!(store.heights(b"a").unwrap(), vec![3, 8]);
+        assert!(store.heights(b"b").unwrap().is_empty());
+        assert_eq!(store.heights(b"c").unwrap(), vec![9]);
+        assert_eq!(store.get_at(b"a", 5).unwrap(), Some(b"a3".to_vec()));
+        assert_eq!(store.get_at(b"a", 8).unwrap(), Some(b"a8".to_vec()));
+        assert_eq!(store.get_at(b"b", 5).unwrap(), None);
+        assert_eq!(store.prune_below(5).unwrap(), 0);
+    }
+}
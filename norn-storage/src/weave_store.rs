@@ -4,7 +4,7 @@ use norn_types::primitives::Hash;
 use norn_types::weave::{WeaveBlock, WeaveState};
 
 use crate::error::StorageError;
-use crate::traits::KvStore;
+use crate::traits::{KvStore, WriteBatch};
 
 const WEAVE_BLOCK_HEIGHT_PREFIX: &[u8] = b"weave:block:height:";
 const WEAVE_BLOCK_HASH_PREFIX: &[u8] = b"weave:block:hash:";
@@ -23,6 +23,17 @@ impl<S: KvStore> WeaveStore<S> {
 
     /// Save a weave block. Stores it by both height and hash for dual-index lookup.
     pub fn save_block(&self, block: &WeaveBlock) -> Result<(), StorageError> {
+        let mut batch = WriteBatch::new();
+        self.stage_block(&mut batch, block)?;
+        self.commit(batch)
+    }
+
+    /// Queue a weave block, under both of its index keys, in `batch`.
+    pub fn stage_block(
+        &self,
+        batch: &mut WriteBatch,
+        block: &WeaveBlock,
+    ) -> Result<(), StorageError> {
         let value = borsh::to_vec(block).map_err(|e| StorageError::SerializationError {
             reason: e.to_string(),
         })?;
@@ -32,17 +43,31 @@ impl<S: KvStore> WeaveStore<S> {
             Vec::with_capacity(WEAVE_BLOCK_HEIGHT_PREFIX.len() + std::mem::size_of::<u64>());
         height_key.extend_from_slice(WEAVE_BLOCK_HEIGHT_PREFIX);
         height_key.extend_from_slice(&block.height.to_be_bytes());
-        self.store.put(&height_key, &value)?;
+        batch.put(&height_key, &value);
 
         // Save by hash
         let mut hash_key = Vec::with_capacity(WEAVE_BLOCK_HASH_PREFIX.len() + 32);
         hash_key.extend_from_slice(WEAVE_BLOCK_HASH_PREFIX);
         hash_key.extend_from_slice(&block.hash);
-        self.store.put(&hash_key, &value)?;
+        batch.put(&hash_key, &value);
 
         Ok(())
     }
 
+    /// Atomically save a block together with the weave state it produced, so
+    /// a crash never leaves the state pointing at a block that was not stored.
+    pub fn commit_block(&self, block: &WeaveBlock, state: &WeaveState) -> Result<(), StorageError> {
+        let mut batch = WriteBatch::new();
+        self.stage_block(&mut batch, block)?;
+        self.stage_weave_state(&mut batch, state)?;
+        self.commit(batch)
+    }
+
+    /// Apply the writes queued in `batch` atomically.
+    pub fn commit(&self, batch: WriteBatch) -> Result<(), StorageError> {
+        self.store.write_batch(batch.into_ops())
+    }
+
     /// Load a weave block by height.
     pub fn load_block(&self, height: u64) -> Result<Option<WeaveBlock>, StorageError> {
         let mut key =
@@ -84,10 +109,22 @@ impl<S: KvStore> WeaveStore<S> {
 
     /// Save the global weave state.
     pub fn save_weave_state(&self, state: &WeaveState) -> Result<(), StorageError> {
+        let mut batch = WriteBatch::new();
+        self.stage_weave_state(&mut batch, state)?;
+        self.commit(batch)
+    }
+
+    /// Queue the global weave state in `batch`.
+    pub fn stage_weave_state(
+        &self,
+        batch: &mut WriteBatch,
+        state: &WeaveState,
+    ) -> Result<(), StorageError> {
         let value = borsh::to_vec(state).map_err(|e| StorageError::SerializationError {
             reason: e.to_string(),
         })?;
-        self.store.put(WEAVE_STATE_KEY, &value)
+        batch.put(WEAVE_STATE_KEY, &value);
+        Ok(())
     }
 
     /// Load the global weave state.
@@ -105,6 +142,36 @@ impl<S: KvStore> WeaveStore<S> {
         }
     }
 
+    /// Delete every block below `height`, from both the height and hash
+    /// indexes. Returns the number of blocks removed.
+    pub fn prune_blocks_below(&self, height: u64) -> Result<u64, StorageError> {
+        let pairs = self.store.prefix_scan(WEAVE_BLOCK_HEIGHT_PREFIX)?;
+        let mut batch = WriteBatch::new();
+        let mut removed = 0;
+        for (key, value) in pairs {
+            let Ok(height_bytes) = <[u8; 8]>::try_from(&key[WEAVE_BLOCK_HEIGHT_PREFIX.len()..])
+            else {
+                continue;
+            };
+            if u64::from_be_bytes(height_bytes) >= height {
+                continue;
+            }
+            let block = WeaveBlock::try_from_slice(&value).map_err(|e| {
+                StorageError::DeserializationError {
+                    reason: e.to_string(),
+                }
+            })?;
+            let mut hash_key = Vec::with_capacity(WEAVE_BLOCK_HASH_PREFIX.len() + 32);
+            hash_key.extend_from_slice(WEAVE_BLOCK_HASH_PREFIX);
+            hash_key.extend_from_slice(&block.hash);
+            batch.delete(&hash_key);
+            batch.delete(&key);
+            removed += 1;
+        }
+        self.commit(batch)?;
+        Ok(removed)
+    }
+
     /// Get the latest block height from the weave state.
     pub fn latest_height(&self) -> Result<Option<u64>, StorageError> {
         match self.load_weave_state()? {
@@ -150,15 +217,28 @@ mod tests {
             token_mints: vec![],
             token_mints_root: [0u8; 32],
             token_burns: vec![],
+            token_metadata_updates: vec![],
             token_burns_root: [0u8; 32],
+            token_metadata_updates_root: [0u8; 32],
             loom_deploys: vec![],
             loom_deploys_root: [0u8; 32],
+            operator_handovers: vec![],
+            operator_handovers_root: [0u8; 32],
+            key_updates: vec![],
+            key_updates_root: [0u8; 32],
             stake_operations: vec![],
             stake_operations_root: [0u8; 32],
+            slashing_evidence: vec![],
+            slashing_evidence_root: [0u8; 32],
+            spindle_operations: vec![],
+            spindle_operations_root: [0u8; 32],
+            base_fee: 0,
             state_root: [0u8; 32],
+            validator_set_hash: [0u8; 32],
             timestamp: 1000 + height,
             proposer: [4u8; 32],
-            validator_signatures: vec![],
+            proposer_signature: [0u8; 64],
+            certificate: None,
         }
     }
 
@@ -258,4 +338,29 @@ mod tests {
         let loaded = ws.load_weave_state().unwrap().unwrap();
         assert_eq!(loaded.height, 2);
     }
+
+    #[test]
+    fn test_commit_block_saves_block_and_state_together() {
+        let ws = make_store();
+        let block = sample_block(3, [3u8; 32]);
+        ws.commit_block(&block, &sample_weave_state(3)).unwrap();
+        assert_eq!(ws.load_block(3).unwrap(), Some(block.clone()));
+        assert_eq!(ws.load_block_by_hash(&[3u8; 32]).unwrap(), Some(block));
+        assert_eq!(ws.latest_height().unwrap(), Some(3));
+    }
+
+    #[test]
+    fn test_prune_blocks_below() {
+        let ws = make_store();
+        for height in 1..=4u64 {
+            ws.save_block(&sample_block(height, [height as u8; 32]))
+                .unwrap();
+        }
+
+        assert_eq!(ws.prune_blocks_below(3).unwrap(), 2);
+        assert!(ws.load_block(2).unwrap().is_none());
+        assert!(ws.load_block_by_hash(&[1u8; 32]).unwrap().is_none());
+        assert!(ws.load_block(3).unwrap().is_some());
+        assert!(ws.load_block_by_hash(&[4u8; 32]).unwrap().is_some());
+    }
 }
@@ -0,0 +1,142 @@
+use std::sync::Mutex;
+
+use crate::error::StorageError;
+use crate::memory::MemoryStore;
+use crate::traits::{BatchOp, KvPairs, KvSnapshot, KvStore};
+
+/// In-memory store that records every write so it can be persisted later.
+///
+/// Backends that can only be reached asynchronously, such as IndexedDB in the
+/// browser, load their contents into a `WriteBackStore`, serve the synchronous
+/// [`KvStore`] API from it, and periodically flush the recorded writes.
+pub struct WriteBackStore {
+    cache: MemoryStore,
+    /// Writes not yet persisted, in the order they were applied.
+    pending: Mutex<Vec<BatchOp>>,
+}
+
+impl WriteBackStore {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self {
+            cache: MemoryStore::new(),
+            pending: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Create a store holding already-persisted entries, with nothing pending.
+    pub fn with_entries(entries: KvPairs) -> Result<Self, StorageError> {
+        let store = Self::new();
+        store.cache.write_batch(
+            entries
+                .into_iter()
+                .map(|(key, value)| BatchOp::Put { key, value })
+                .collect(),
+        )?;
+        Ok(store)
+    }
+
+    /// Take the writes recorded since the last call, leaving none pending.
+    pub fn take_pending(&self) -> Result<Vec<BatchOp>, StorageError> {
+        let mut pending = self.lock_pending()?;
+        Ok(std::mem::take(&mut *pending))
+    }
+
+    /// Put writes back at the front of the queue after they failed to
+    /// persist, ahead of anything recorded since they were taken.
+    pub fn requeue(&self, mut ops: Vec<BatchOp>) -> Result<(), StorageError> {
+        let mut pending = self.lock_pending()?;
+        ops.append(&mut pending);
+        *pending = ops;
+        Ok(())
+    }
+
+    /// Whether any writes are waiting to be persisted.
+    pub fn has_pending(&self) -> Result<bool, StorageError> {
+        Ok(!self.lock_pending()?.is_empty())
+    }
+
+    fn lock_pending(&self) -> Result<std::sync::MutexGuard<'_, Vec<BatchOp>>, StorageError> {
+        self.pending.lock().map_err(|e| StorageError::WriteError {
+            reason: e.to_string(),
+        })
+    }
+}
+
+impl Default for WriteBackStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KvStore for WriteBackStore {
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, StorageError> {
+        self.cache.get(key)
+    }
+
+    fn put(&self, key: &[u8], value: &[u8]) -> Result<(), StorageError> {
+        self.write_batch(vec![BatchOp::Put {
+            key: key.to_vec(),
+            value: value.to_vec(),
+        }])
+    }
+
+    fn delete(&self, key: &[u8]) -> Result<(), StorageError> {
+        self.write_batch(vec![BatchOp::Delete { key: key.to_vec() }])
+    }
+
+    fn exists(&self, key: &[u8]) -> Result<bool, StorageError> {
+        self.cache.exists(key)
+    }
+
+    fn prefix_scan(&self, prefix: &[u8]) -> Result<KvPairs, StorageError> {
+        self.cache.prefix_scan(prefix)
+    }
+
+    fn write_batch(&self, ops: Vec<BatchOp>) -> Result<(), StorageError> {
+        // Hold the queue while applying so writes are recorded in the order
+        // they reached the cache.
+        let mut pending = self.lock_pending()?;
+        self.cache.write_batch(ops.clone())?;
+        pending.extend(ops);
+        Ok(())
+    }
+
+    fn snapshot(&self) -> Result<Box<dyn KvSnapshot + '_>, StorageError> {
+        self.cache.snapshot()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_writes_are_recorded_in_order() {
+        let store = WriteBackStore::with_entries(vec![(b"a".to_vec(), b"1".to_vec())]).unwrap();
+        assert_eq!(store.get(b"a").unwrap(), Some(b"1".to_vec()));
+        assert!(!store.has_pending().unwrap());
+
+        store.put(b"b", b"2").unwrap();
+        store.delete(b"a").unwrap();
+        assert_eq!(store.prefix_scan(b"").unwrap().len(), 1);
+
+        let ops = store.take_pending().unwrap();
+        assert!(matches!(&ops[0], BatchOp::Put { key, .. } if key == b"b"));
+        assert!(matches!(&ops[1], BatchOp::Delete { key } if key == b"a"));
+        assert!(!store.has_pending().unwrap());
+    }
+
+    #[test]
+    fn test_requeue_keeps_failed_writes_first() {
+        let store = WriteBackStore::new();
+        store.put(b"a", b"1").unwrap();
+        let failed = store.take_pending().unwrap();
+        store.put(b"b", b"2").unwrap();
+
+        store.requeue(failed).unwrap();
+        let ops = store.take_pending().unwrap();
+        assert_eq!(ops.len(), 2);
+        assert!(matches!(&ops[0], BatchOp::Put { key, .. } if key == b"a"));
+    }
+}
@@ -1,9 +1,13 @@
+use norn_crypto::hash::blake3_hash;
+use norn_crypto::keys::{verify, Keypair};
+use norn_types::constants::CHECKPOINT_INTERVAL;
 use norn_types::error::NornError;
 use norn_types::knot::Knot;
 use norn_types::primitives::*;
-use norn_types::thread::ThreadHeader;
+use norn_types::thread::{ThreadCheckpoint, ThreadHeader, ThreadState};
 
 use crate::knot::compute_knot_id;
+use crate::state::compute_state_hash;
 
 /// Verify a chain of knots from a known commitment header for a specific thread.
 ///
@@ -19,8 +23,30 @@ pub fn verify_knot_chain(
     thread_id: &ThreadId,
     knots: &[Knot],
 ) -> Result<(Version, Hash), NornError> {
-    let mut current_version = commitment.version;
-    let mut current_state_hash = commitment.state_hash;
+    verify_chain_from(thread_id, commitment.version, commitment.state_hash, knots)
+}
+
+/// Verify a chain of knots starting at a checkpoint instead of a commitment
+/// header, so only the knots since the checkpoint need to be replayed.
+///
+/// The checkpoint's signature and state are verified first. Whether its
+/// owner controls the thread is up to the caller.
+pub fn verify_knot_chain_from_checkpoint(
+    checkpoint: &ThreadCheckpoint,
+    knots: &[Knot],
+) -> Result<(Version, Hash), NornError> {
+    let state_hash = verify_checkpoint(checkpoint)?;
+    verify_chain_from(&checkpoint.thread_id, checkpoint.version, state_hash, knots)
+}
+
+fn verify_chain_from(
+    thread_id: &ThreadId,
+    version: Version,
+    state_hash: Hash,
+    knots: &[Knot],
+) -> Result<(Version, Hash), NornError> {
+    let mut current_version = version;
+    let mut current_state_hash = state_hash;
 
     for (i, knot) in knots.iter().enumerate() {
         // Verify knot ID
@@ -64,11 +90,99 @@ pub fn verify_knot_chain(
     Ok((current_version, current_state_hash))
 }
 
+/// Whether a thread at `version` is due for a checkpoint, given the version
+/// of its last checkpoint (0 if it has none).
+pub fn is_checkpoint_due(version: Version, last_checkpoint_version: Version) -> bool {
+    version.saturating_sub(last_checkpoint_version) >= CHECKPOINT_INTERVAL
+}
+
+/// Create a checkpoint of a thread's state signed by `keypair`, linked to
+/// the thread's previous checkpoint.
+pub fn create_checkpoint(
+    thread_id: ThreadId,
+    state: &ThreadState,
+    version: Version,
+    last_knot_hash: Hash,
+    prev: Option<&ThreadCheckpoint>,
+    timestamp: Timestamp,
+    keypair: &Keypair,
+) -> ThreadCheckpoint {
+    let mut checkpoint = ThreadCheckpoint {
+        thread_id,
+        owner: keypair.public_key(),
+        version,
+        state: state.clone(),
+        last_knot_hash,
+        prev_checkpoint_hash: prev.map(checkpoint_hash).unwrap_or([0u8; 32]),
+        timestamp,
+        signature: [0u8; 64],
+    };
+    checkpoint.signature = keypair.sign(&checkpoint_signing_bytes(&checkpoint));
+    checkpoint
+}
+
+/// Hash of a checkpoint over all fields except the signature. The next
+/// checkpoint links to it.
+pub fn checkpoint_hash(checkpoint: &ThreadCheckpoint) -> Hash {
+    blake3_hash(&checkpoint_signing_bytes(checkpoint))
+}
+
+/// Verify a checkpoint's owner signature, returning the hash of its state.
+pub fn verify_checkpoint(checkpoint: &ThreadCheckpoint) -> Result<Hash, NornError> {
+    verify(
+        &checkpoint_signing_bytes(checkpoint),
+        &checkpoint.signature,
+        &checkpoint.owner,
+    )
+    .map_err(|_| NornError::InvalidCheckpoint {
+        reason: "invalid owner signature".to_string(),
+    })?;
+    Ok(compute_state_hash(&checkpoint.state))
+}
+
+/// Verify that `next` is the checkpoint after `prev` for the same thread.
+pub fn verify_checkpoint_link(
+    prev: &ThreadCheckpoint,
+    next: &ThreadCheckpoint,
+) -> Result<(), NornError> {
+    let invalid = |reason: &str| {
+        Err(NornError::InvalidCheckpoint {
+            reason: reason.to_string(),
+        })
+    };
+    if next.thread_id != prev.thread_id {
+        return invalid("checkpoints are for different threads");
+    }
+    if next.prev_checkpoint_hash != checkpoint_hash(prev) {
+        return invalid("checkpoint does not link to the previous checkpoint");
+    }
+    if next.version <= prev.version {
+        return invalid("checkpoint version does not advance");
+    }
+    Ok(())
+}
+
+/// Get the bytes to sign for a checkpoint (all fields except signature).
+fn checkpoint_signing_bytes(checkpoint: &ThreadCheckpoint) -> Vec<u8> {
+    use borsh::BorshSerialize;
+    let mut data = Vec::new();
+    checkpoint.thread_id.serialize(&mut data).unwrap();
+    checkpoint.owner.serialize(&mut data).unwrap();
+    checkpoint.version.serialize(&mut data).unwrap();
+    checkpoint.state.serialize(&mut data).unwrap();
+    checkpoint.last_knot_hash.serialize(&mut data).unwrap();
+    checkpoint
+        .prev_checkpoint_hash
+        .serialize(&mut data)
+        .unwrap();
+    checkpoint.timestamp.serialize(&mut data).unwrap();
+    data
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::knot::{add_signature, sign_knot, KnotBuilder};
-    use crate::state::compute_state_hash;
     use norn_crypto::address::pubkey_to_address;
     use norn_crypto::keys::Keypair;
     use norn_types::knot::{KnotPayload, TransferPayload};
@@ -195,4 +309,52 @@ mod tests {
             Err(NornError::InvalidKnotChain { index: 0 })
         ));
     }
+
+    #[test]
+    fn test_chain_from_checkpoint() {
+        let (commitment, thread_id, knots, sender_kp, _) = make_chain(10);
+        // Checkpoint the sender after the first 6 knots.
+        let mut state = ThreadState::new();
+        state.credit(NATIVE_TOKEN_ID, 1_000_000 - 6 * 10).unwrap();
+        assert_eq!(
+            compute_state_hash(&state),
+            knots[5].after_states[0].state_hash
+        );
+        let checkpoint =
+            create_checkpoint(thread_id, &state, 6, knots[5].id, None, 2000, &sender_kp);
+
+        let from_start = verify_knot_chain(&commitment, &thread_id, &knots).unwrap();
+        let from_checkpoint = verify_knot_chain_from_checkpoint(&checkpoint, &knots[6..]).unwrap();
+        assert_eq!(from_start, from_checkpoint);
+
+        // Knots the checkpoint already covers do not follow it.
+        assert!(verify_knot_chain_from_checkpoint(&checkpoint, &knots[5..]).is_err());
+
+        let mut forged = checkpoint.clone();
+        forged.state.credit(NATIVE_TOKEN_ID, 1).unwrap();
+        assert!(matches!(
+            verify_knot_chain_from_checkpoint(&forged, &knots[6..]),
+            Err(NornError::InvalidCheckpoint { .. })
+        ));
+    }
+
+    #[test]
+    fn test_checkpoint_link() {
+        let kp = Keypair::generate();
+        let thread_id = pubkey_to_address(&kp.public_key());
+        let state = ThreadState::new();
+        let first = create_checkpoint(thread_id, &state, 1_000, [1u8; 32], None, 1000, &kp);
+        let second =
+            create_checkpoint(thread_id, &state, 2_000, [2u8; 32], Some(&first), 2000, &kp);
+        assert_eq!(first.prev_checkpoint_hash, [0u8; 32]);
+        assert!(verify_checkpoint_link(&first, &second).is_ok());
+        assert!(verify_checkpoint_link(&second, &first).is_err());
+
+        let unlinked = create_checkpoint(thread_id, &state, 2_000, [2u8; 32], None, 2000, &kp);
+        assert!(verify_checkpoint_link(&first, &unlinked).is_err());
+
+        assert!(!is_checkpoint_due(999, 0));
+        assert!(is_checkpoint_due(1_000, 0));
+        assert!(!is_checkpoint_due(1_500, 1_000));
+    }
 }
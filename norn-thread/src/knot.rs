@@ -95,6 +95,29 @@ impl KnotBuilder {
         self
     }
 
+    /// Add a participant whose thread state the builder does not know. Both
+    /// state hashes are left zero, which nodes treat as "not provided".
+    pub fn add_participant(
+        mut self,
+        thread_id: ThreadId,
+        pubkey: PublicKey,
+        version: Version,
+    ) -> Self {
+        self.before_states.push(ParticipantState {
+            thread_id,
+            pubkey,
+            version,
+            state_hash: [0u8; 32],
+        });
+        self.after_states.push(ParticipantState {
+            thread_id,
+            pubkey,
+            version: version + 1,
+            state_hash: [0u8; 32],
+        });
+        self
+    }
+
     /// Set the payload.
     pub fn with_payload(mut self, payload: KnotPayload) -> Self {
         self.payload = Some(payload);
@@ -117,6 +140,7 @@ impl KnotBuilder {
             after_states: self.after_states,
             payload,
             signatures: Vec::new(),
+            cosignatures: Vec::new(),
         };
 
         // Compute the knot ID
@@ -161,6 +185,46 @@ pub fn add_signature(knot: &mut Knot, signature: Signature) {
     knot.signatures.push(signature);
 }
 
+/// Add a cosigner's signature to a knot, replacing any earlier one by the
+/// same key.
+pub fn add_cosignature(knot: &mut Knot, keypair: &Keypair) {
+    let signer = keypair.public_key();
+    let signature = keypair.sign(&knot.id);
+    knot.cosignatures.retain(|c| c.signer != signer);
+    knot.cosignatures.push(Cosignature { signer, signature });
+}
+
+/// ID under which the transfer at `index` of a multi-transfer knot is
+/// recorded and included in blocks: BLAKE3(knot ID || index).
+///
+/// Transfers are deduplicated by ID, so each transfer of a multi-transfer
+/// needs its own.
+pub fn multi_transfer_leg_id(knot_id: &KnotId, index: usize) -> Hash {
+    let mut data = knot_id.to_vec();
+    data.extend_from_slice(&(index as u32).to_le_bytes());
+    blake3_hash(&data)
+}
+
+/// The transfers a knot performs, each with the ID it is recorded under.
+/// A plain transfer uses the knot ID. Empty for loom interactions and key
+/// updates.
+pub fn transfer_legs(knot: &Knot) -> Vec<(Hash, &TransferPayload)> {
+    match &knot.payload {
+        KnotPayload::Transfer(transfer) => vec![(knot.id, transfer)],
+        KnotPayload::MultiTransfer(multi) => multi
+            .transfers
+            .iter()
+            .enumerate()
+            .map(|(i, transfer)| (multi_transfer_leg_id(&knot.id, i), transfer))
+            .collect(),
+        KnotPayload::LoomInteraction(_)
+        | KnotPayload::KeyRotation(_)
+        | KnotPayload::SetGuardians(_)
+        | KnotPayload::Recovery(_)
+        | KnotPayload::SetCosigners(_) => Vec::new(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -256,6 +320,29 @@ mod tests {
         assert_eq!(knot.expiry, Some(2000));
     }
 
+    #[test]
+    fn test_transfer_legs() {
+        let (knot, _, _) = make_test_transfer_knot();
+        let legs = transfer_legs(&knot);
+        assert_eq!(legs.len(), 1);
+        assert_eq!(legs[0].0, knot.id);
+
+        let KnotPayload::Transfer(transfer) = knot.payload.clone() else {
+            unreachable!()
+        };
+        let multi = KnotBuilder::multi_transfer(1000)
+            .with_payload(KnotPayload::MultiTransfer(MultiTransferPayload {
+                transfers: vec![transfer.clone(), transfer],
+            }))
+            .build()
+            .unwrap();
+        let legs = transfer_legs(&multi);
+        assert_eq!(legs.len(), 2);
+        assert_eq!(legs[0].0, multi_transfer_leg_id(&multi.id, 0));
+        assert_ne!(legs[0].0, legs[1].0);
+        assert_ne!(legs[0].0, multi.id);
+    }
+
     #[test]
     fn test_builder_without_payload_fails() {
         let result = KnotBuilder::transfer(1000).build();
@@ -5,6 +5,8 @@
 
 pub mod chain;
 pub mod knot;
+pub mod proposal;
+pub mod rotation;
 pub mod state;
 pub mod thread;
 pub mod validation;
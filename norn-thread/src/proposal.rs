@@ -0,0 +1,465 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+use norn_crypto::keys::{verify, Keypair};
+use norn_types::error::NornError;
+use norn_types::knot::*;
+use norn_types::primitives::*;
+
+use crate::knot::KnotBuilder;
+use crate::validation::{effective_expiry, validate_rule_2_knot_id};
+
+/// Placeholder in `Knot::signatures` for a participant that has not signed yet.
+pub const UNSIGNED: Signature = [0u8; 64];
+
+/// How long a proposal waits for signatures, in seconds, unless the knot
+/// expires sooner.
+pub const PROPOSAL_TTL: u64 = 3_600;
+
+/// Maximum number of cosignatures a pending knot holds.
+pub const MAX_KNOT_COSIGNATURES: usize = 64;
+
+/// Maximum number of proposals a `KnotCollector` holds.
+pub const MAX_PENDING_PROPOSALS: usize = 1_000;
+
+/// One side of an atomic swap: who gives what.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwapSide {
+    /// The side's thread ID.
+    pub address: Address,
+    /// The side's public key.
+    pub pubkey: PublicKey,
+    /// The side's next knot sequence number (see `validate_knot_sequence`).
+    pub version: Version,
+    /// Token this side gives.
+    pub token_id: TokenId,
+    /// Amount this side gives.
+    pub amount: Amount,
+}
+
+/// Build an unsigned knot in which `a` gives its tokens to `b` and `b` gives
+/// its tokens to `a`. Both transfers apply together or not at all, and the
+/// knot is only valid once both sides have signed it.
+pub fn build_swap(
+    a: &SwapSide,
+    b: &SwapSide,
+    timestamp: Timestamp,
+    expiry: Option<Timestamp>,
+) -> Result<Knot, NornError> {
+    let leg = |from: &SwapSide, to: &SwapSide| TransferPayload {
+        token_id: from.token_id,
+        amount: from.amount,
+        from: from.address,
+        to: to.address,
+        memo: None,
+    };
+    let mut builder = KnotBuilder::multi_transfer(timestamp)
+        .add_participant(a.address, a.pubkey, a.version)
+        .add_participant(b.address, b.pubkey, b.version)
+        .with_payload(KnotPayload::MultiTransfer(MultiTransferPayload {
+            transfers: vec![leg(a, b), leg(b, a)],
+        }));
+    if let Some(expiry) = expiry {
+        builder = builder.with_expiry(expiry);
+    }
+    builder.build()
+}
+
+/// Whether some participant of the knot has not signed it yet.
+pub fn is_partially_signed(knot: &Knot) -> bool {
+    knot.signatures.len() < knot.before_states.len() || knot.signatures.contains(&UNSIGNED)
+}
+
+/// A knot collecting its participants' signatures, and its cosigners'.
+///
+/// Unsigned slots hold `UNSIGNED`, so a partially signed knot can travel in
+/// `KnotProposal` and `KnotResponse` messages, or in a file between devices,
+/// as is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingKnot {
+    knot: Knot,
+    /// When the proposal is dropped if still incomplete.
+    deadline: Timestamp,
+}
+
+impl PendingKnot {
+    /// Start collecting signatures for `knot`. Signatures and cosignatures
+    /// it already carries are kept after they are verified.
+    pub fn new(mut knot: Knot, now: Timestamp) -> Result<Self, NornError> {
+        if knot.before_states.is_empty() {
+            return Err(NornError::InsufficientParticipants {
+                required: 1,
+                actual: 0,
+            });
+        }
+        validate_rule_2_knot_id(&knot)?;
+        let expiry = effective_expiry(&knot);
+        if now >= expiry {
+            return Err(NornError::KnotExpired {
+                expiry,
+                current: now,
+            });
+        }
+        let deadline = expiry.min(now + PROPOSAL_TTL);
+        let signatures = std::mem::replace(
+            &mut knot.signatures,
+            vec![UNSIGNED; knot.before_states.len()],
+        );
+        let cosignatures = std::mem::take(&mut knot.cosignatures);
+        let mut pending = Self { knot, deadline };
+        pending.merge_signatures(&signatures)?;
+        pending.merge_cosignatures(&cosignatures)?;
+        Ok(pending)
+    }
+
+    /// The knot's ID.
+    pub fn id(&self) -> KnotId {
+        self.knot.id
+    }
+
+    /// The knot, with `UNSIGNED` for missing signatures.
+    pub fn knot(&self) -> &Knot {
+        &self.knot
+    }
+
+    /// When the proposal is dropped if still incomplete.
+    pub fn deadline(&self) -> Timestamp {
+        self.deadline
+    }
+
+    /// Sign for every participant whose public key is the keypair's.
+    pub fn sign(&mut self, keypair: &Keypair) -> Result<(), NornError> {
+        let pubkey = keypair.public_key();
+        let mut signed = false;
+        for (i, participant) in self.knot.before_states.iter().enumerate() {
+            if participant.pubkey == pubkey {
+                self.knot.signatures[i] = keypair.sign(&self.knot.id);
+                signed = true;
+            }
+        }
+        if !signed {
+            return Err(NornError::NotKnotParticipant);
+        }
+        Ok(())
+    }
+
+    /// Co-sign the knot. Whether the keypair is a cosigner of some
+    /// participant is only known to the nodes that check it.
+    pub fn cosign(&mut self, keypair: &Keypair) {
+        crate::knot::add_cosignature(&mut self.knot, keypair);
+    }
+
+    /// Add the signatures and cosignatures carried by another copy of the
+    /// same knot. Returns how many were new.
+    pub fn merge(&mut self, other: &Knot) -> Result<usize, NornError> {
+        if other.id != self.knot.id {
+            return Err(NornError::KnotIdMismatch {
+                expected: self.knot.id,
+                actual: other.id,
+            });
+        }
+        Ok(self.merge_signatures(&other.signatures)?
+            + self.merge_cosignatures(&other.cosignatures)?)
+    }
+
+    fn merge_signatures(&mut self, signatures: &[Signature]) -> Result<usize, NornError> {
+        if signatures.len() > self.knot.before_states.len() {
+            return Err(NornError::InvalidSignature {
+                signer_index: self.knot.before_states.len(),
+            });
+        }
+        let mut added = 0;
+        for (i, sig) in signatures.iter().enumerate() {
+            if *sig == UNSIGNED || self.knot.signatures[i] != UNSIGNED {
+                continue;
+            }
+            verify(&self.knot.id, sig, &self.knot.before_states[i].pubkey)
+                .map_err(|_| NornError::InvalidSignature { signer_index: i })?;
+            self.knot.signatures[i] = *sig;
+            added += 1;
+        }
+        Ok(added)
+    }
+
+    fn merge_cosignatures(&mut self, cosignatures: &[Cosignature]) -> Result<usize, NornError> {
+        let mut added = 0;
+        for (i, cosignature) in cosignatures.iter().enumerate() {
+            if self
+                .knot
+                .cosignatures
+                .iter()
+                .any(|c| c.signer == cosignature.signer)
+            {
+                continue;
+            }
+            if self.knot.cosignatures.len() >= MAX_KNOT_COSIGNATURES {
+                return Err(NornError::InvalidSignature {
+                    signer_index: self.knot.before_states.len() + i,
+                });
+            }
+            verify(&self.knot.id, &cosignature.signature, &cosignature.signer).map_err(|_| {
+                NornError::InvalidSignature {
+                    signer_index: self.knot.before_states.len() + i,
+                }
+            })?;
+            self.knot.cosignatures.push(cosignature.clone());
+            added += 1;
+        }
+        Ok(added)
+    }
+
+    /// Thread IDs of the participants that have not signed yet.
+    pub fn missing(&self) -> Vec<ThreadId> {
+        self.knot
+            .before_states
+            .iter()
+            .zip(&self.knot.signatures)
+            .filter(|(_, sig)| **sig == UNSIGNED)
+            .map(|(participant, _)| participant.thread_id)
+            .collect()
+    }
+
+    /// Whether every participant has signed.
+    pub fn is_complete(&self) -> bool {
+        !self.knot.signatures.contains(&UNSIGNED)
+    }
+
+    /// Whether the proposal has run out of time.
+    pub fn is_expired(&self, now: Timestamp) -> bool {
+        now >= self.deadline
+    }
+
+    /// The fully signed knot, ready for `norn_submitKnot`.
+    pub fn into_knot(self) -> Result<Knot, NornError> {
+        if let Some(i) = self.knot.signatures.iter().position(|s| *s == UNSIGNED) {
+            return Err(NornError::InvalidSignature { signer_index: i });
+        }
+        Ok(self.knot)
+    }
+}
+
+/// Proposals waiting for signatures, keyed by knot ID.
+///
+/// Each node keeps one and feeds it every partially signed knot it sees,
+/// from RPC or gossip, so signatures added at different nodes meet.
+#[derive(Debug, Default)]
+pub struct KnotCollector {
+    pending: HashMap<KnotId, PendingKnot>,
+}
+
+impl KnotCollector {
+    /// Create an empty collector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a proposal, or merge its signatures into the one already held,
+    /// and return the result. A proposal that becomes complete is removed;
+    /// the caller submits it.
+    pub fn collect(&mut self, knot: &Knot, now: Timestamp) -> Result<PendingKnot, NornError> {
+        self.prune(now);
+        let held = self.pending.len();
+        let pending = match self.pending.entry(knot.id) {
+            Entry::Occupied(mut entry) => {
+                entry.get_mut().merge(knot)?;
+                entry.get().clone()
+            }
+            Entry::Vacant(entry) => {
+                if knot.before_states.len() < 2 {
+                    return Err(NornError::InsufficientParticipants {
+                        required: 2,
+                        actual: knot.before_states.len(),
+                    });
+                }
+                if held >= MAX_PENDING_PROPOSALS {
+                    return Err(NornError::TooManyPendingKnots {
+                        max: MAX_PENDING_PROPOSALS,
+                    });
+                }
+                entry.insert(PendingKnot::new(knot.clone(), now)?).clone()
+            }
+        };
+        if pending.is_complete() {
+            self.pending.remove(&knot.id);
+        }
+        Ok(pending)
+    }
+
+    /// Look up a proposal still waiting for signatures.
+    pub fn get(&self, knot_id: &KnotId) -> Option<&PendingKnot> {
+        self.pending.get(knot_id)
+    }
+
+    /// Number of proposals waiting for signatures.
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Whether no proposals are waiting.
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Drop proposals that ran out of time.
+    pub fn prune(&mut self, now: Timestamp) {
+        self.pending.retain(|_, pending| !pending.is_expired(now));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validation::{validate_rule_1_signatures, validate_transfer_senders};
+    use norn_crypto::address::pubkey_to_address;
+    use norn_types::primitives::NATIVE_TOKEN_ID;
+
+    fn side(keypair: &Keypair, token_id: TokenId, amount: Amount) -> SwapSide {
+        SwapSide {
+            address: pubkey_to_address(&keypair.public_key()),
+            pubkey: keypair.public_key(),
+            version: 0,
+            token_id,
+            amount,
+        }
+    }
+
+    fn swap() -> (Knot, Keypair, Keypair) {
+        let alice = Keypair::generate();
+        let bob = Keypair::generate();
+        let knot = build_swap(
+            &side(&alice, NATIVE_TOKEN_ID, 100),
+            &side(&bob, [7u8; 32], 5),
+            1000,
+            Some(2000),
+        )
+        .unwrap();
+        (knot, alice, bob)
+    }
+
+    #[test]
+    fn test_swap_collects_both_signatures() {
+        let (knot, alice, bob) = swap();
+        assert!(is_partially_signed(&knot));
+
+        let mut pending = PendingKnot::new(knot, 1000).unwrap();
+        assert_eq!(pending.missing().len(), 2);
+        pending.sign(&alice).unwrap();
+        assert_eq!(
+            pending.missing(),
+            vec![pubkey_to_address(&bob.public_key())]
+        );
+        assert!(!pending.is_complete());
+        assert!(pending.clone().into_knot().is_err());
+
+        pending.sign(&bob).unwrap();
+        assert!(pending.is_complete());
+        let knot = pending.into_knot().unwrap();
+        assert!(!is_partially_signed(&knot));
+        assert!(validate_rule_1_signatures(&knot).is_ok());
+        assert!(validate_transfer_senders(&knot).is_ok());
+    }
+
+    #[test]
+    fn test_merge_partial_copies() {
+        let (knot, alice, bob) = swap();
+        let mut from_alice = PendingKnot::new(knot.clone(), 1000).unwrap();
+        from_alice.sign(&alice).unwrap();
+        let mut from_bob = PendingKnot::new(knot, 1000).unwrap();
+        from_bob.sign(&bob).unwrap();
+
+        assert_eq!(from_alice.merge(from_bob.knot()).unwrap(), 1);
+        assert!(from_alice.is_complete());
+        // Merging again adds nothing.
+        assert_eq!(from_alice.merge(from_bob.knot()).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_rejects_bad_signatures_and_strangers() {
+        let (knot, alice, _) = swap();
+        let mut pending = PendingKnot::new(knot.clone(), 1000).unwrap();
+        assert_eq!(
+            pending.sign(&Keypair::generate()),
+            Err(NornError::NotKnotParticipant)
+        );
+
+        // Alice's signature in Bob's slot does not verify.
+        let mut forged = knot.clone();
+        forged.signatures = vec![UNSIGNED, alice.sign(&knot.id)];
+        assert_eq!(
+            pending.merge(&forged),
+            Err(NornError::InvalidSignature { signer_index: 1 })
+        );
+
+        let (other, _, _) = swap();
+        assert!(matches!(
+            pending.merge(&other),
+            Err(NornError::KnotIdMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_expiry() {
+        let (knot, _, _) = swap();
+        assert!(matches!(
+            PendingKnot::new(knot.clone(), 2000),
+            Err(NornError::KnotExpired { .. })
+        ));
+        let pending = PendingKnot::new(knot, 1000).unwrap();
+        assert_eq!(pending.deadline(), 2000);
+        assert!(!pending.is_expired(1999));
+        assert!(pending.is_expired(2000));
+    }
+
+    #[test]
+    fn test_collector_completes_and_prunes() {
+        let (knot, alice, bob) = swap();
+        let mut collector = KnotCollector::new();
+
+        let mut signed = knot.clone();
+        signed.signatures = vec![alice.sign(&knot.id)];
+        let pending = collector.collect(&signed, 1000).unwrap();
+        assert!(!pending.is_complete());
+        assert_eq!(collector.len(), 1);
+
+        signed.signatures = vec![UNSIGNED, bob.sign(&knot.id)];
+        let pending = collector.collect(&signed, 1001).unwrap();
+        assert!(pending.is_complete());
+        assert!(collector.is_empty());
+
+        collector.collect(&knot, 1000).unwrap();
+        collector.prune(2000);
+        assert!(collector.get(&knot.id).is_none());
+    }
+
+    #[test]
+    fn test_cosignatures_travel_with_the_knot() {
+        let (knot, alice, bob) = swap();
+        let cosigner = Keypair::generate();
+
+        // A cosigner signs on its own device; the owner merges its copy.
+        let mut from_cosigner = PendingKnot::new(knot.clone(), 1000).unwrap();
+        from_cosigner.cosign(&cosigner);
+        from_cosigner.cosign(&cosigner);
+        assert_eq!(from_cosigner.knot().cosignatures.len(), 1);
+
+        let mut pending = PendingKnot::new(knot.clone(), 1000).unwrap();
+        pending.sign(&alice).unwrap();
+        assert_eq!(pending.merge(from_cosigner.knot()).unwrap(), 1);
+        assert_eq!(pending.merge(from_cosigner.knot()).unwrap(), 0);
+
+        // Cosignatures survive a round trip through a fresh pending knot.
+        let copy = PendingKnot::new(pending.knot().clone(), 1000).unwrap();
+        assert_eq!(copy.knot().cosignatures, pending.knot().cosignatures);
+
+        // A forged cosignature is refused.
+        let mut forged = knot;
+        forged.cosignatures = vec![Cosignature {
+            signer: bob.public_key(),
+            signature: alice.sign(&forged.id),
+        }];
+        assert!(matches!(
+            pending.merge(&forged),
+            Err(NornError::InvalidSignature { .. })
+        ));
+    }
+}
@@ -0,0 +1,687 @@
+use std::collections::HashSet;
+
+use norn_crypto::address::pubkey_to_address;
+use norn_crypto::keys::{verify, Keypair};
+use norn_types::constants::{MAX_COSIGNERS, MAX_GUARDIANS};
+use norn_types::error::NornError;
+use norn_types::knot::*;
+use norn_types::primitives::*;
+use norn_types::thread::{CosignerSet, GuardianSet, ThreadKeys};
+
+use crate::knot::compute_knot_id;
+use crate::validation::{validate_rule_1_signatures, validate_rule_2_knot_id};
+
+/// Whether a knot changes the keys that control its thread rather than moving
+/// tokens.
+pub fn is_key_update(knot: &Knot) -> bool {
+    matches!(
+        knot.payload,
+        KnotPayload::KeyRotation(_)
+            | KnotPayload::SetGuardians(_)
+            | KnotPayload::Recovery(_)
+            | KnotPayload::SetCosigners(_)
+    )
+}
+
+/// The thread a key update applies to and the key epoch it names.
+pub fn key_update_target(knot: &Knot) -> Option<(ThreadId, u64)> {
+    match &knot.payload {
+        KnotPayload::KeyRotation(p) => Some((p.thread_id, p.epoch)),
+        KnotPayload::SetGuardians(p) => Some((p.thread_id, p.epoch)),
+        KnotPayload::Recovery(p) => Some((p.thread_id, p.epoch)),
+        KnotPayload::SetCosigners(p) => Some((p.thread_id, p.epoch)),
+        _ => None,
+    }
+}
+
+/// The keys to check a key update against, given the keys recorded for its
+/// thread. A thread with no recorded owner key has never updated its keys, so
+/// it is owned by the key its ID derives from; that must be the key the update
+/// names as the current owner. Returns `None` if the owner cannot be known.
+pub fn current_keys(knot: &Knot, recorded: Option<ThreadKeys>) -> Option<ThreadKeys> {
+    if let Some(keys) = recorded.as_ref().filter(|k| k.owner != [0u8; 32]) {
+        return Some(keys.clone());
+    }
+    let (thread_id, claimed) = match &knot.payload {
+        KnotPayload::KeyRotation(p) => (p.thread_id, p.old_pubkey),
+        KnotPayload::SetGuardians(p) => (p.thread_id, knot.before_states.first()?.pubkey),
+        KnotPayload::Recovery(p) => (p.thread_id, p.old_pubkey),
+        KnotPayload::SetCosigners(p) => (p.thread_id, knot.before_states.first()?.pubkey),
+        _ => return None,
+    };
+    if pubkey_to_address(&claimed) != thread_id {
+        return None;
+    }
+    Some(ThreadKeys {
+        owner: claimed,
+        ..recorded.unwrap_or_else(|| ThreadKeys::new(claimed))
+    })
+}
+
+/// The data each guardian signs to approve a recovery.
+pub fn recovery_signing_data(payload: &RecoveryPayload) -> Vec<u8> {
+    let mut data = Vec::with_capacity(20 + 8 + 32 + 32);
+    data.extend_from_slice(&payload.thread_id);
+    data.extend_from_slice(&payload.epoch.to_le_bytes());
+    data.extend_from_slice(&payload.old_pubkey);
+    data.extend_from_slice(&payload.new_pubkey);
+    data
+}
+
+/// Approve a recovery as one of the thread's guardians.
+pub fn approve_recovery(payload: &RecoveryPayload, guardian: &Keypair) -> GuardianApproval {
+    GuardianApproval {
+        guardian: guardian.public_key(),
+        signature: guardian.sign(&recovery_signing_data(payload)),
+    }
+}
+
+/// Build an unsigned knot rotating `thread_id` from its current owner key to
+/// `new_pubkey`. The current owner signs it.
+pub fn build_key_rotation(
+    thread_id: ThreadId,
+    keys: &ThreadKeys,
+    new_pubkey: PublicKey,
+    version: Version,
+    timestamp: Timestamp,
+) -> Knot {
+    let payload = KnotPayload::KeyRotation(KeyRotationPayload {
+        thread_id,
+        epoch: keys.epoch,
+        old_pubkey: keys.owner,
+        new_pubkey,
+    });
+    key_update_knot(
+        KnotType::KeyRotation,
+        thread_id,
+        (keys.owner, new_pubkey),
+        version,
+        timestamp,
+        payload,
+    )
+}
+
+/// Build an unsigned knot replacing the guardians of `thread_id`. The current
+/// owner signs it.
+pub fn build_set_guardians(
+    thread_id: ThreadId,
+    keys: &ThreadKeys,
+    guardians: GuardianSet,
+    version: Version,
+    timestamp: Timestamp,
+) -> Knot {
+    let payload = KnotPayload::SetGuardians(SetGuardiansPayload {
+        thread_id,
+        epoch: keys.epoch,
+        guardians,
+    });
+    key_update_knot(
+        KnotType::SetGuardians,
+        thread_id,
+        (keys.owner, keys.owner),
+        version,
+        timestamp,
+        payload,
+    )
+}
+
+/// Build an unsigned knot replacing the cosigners of `thread_id`. The current
+/// owner signs it, and so must enough of the current cosigners, if any.
+pub fn build_set_cosigners(
+    thread_id: ThreadId,
+    keys: &ThreadKeys,
+    cosigners: CosignerSet,
+    version: Version,
+    timestamp: Timestamp,
+) -> Knot {
+    let payload = KnotPayload::SetCosigners(SetCosignersPayload {
+        thread_id,
+        epoch: keys.epoch,
+        cosigners,
+    });
+    key_update_knot(
+        KnotType::SetCosigners,
+        thread_id,
+        (keys.owner, keys.owner),
+        version,
+        timestamp,
+        payload,
+    )
+}
+
+/// Build an unsigned knot recovering `thread_id` to `new_pubkey` with the
+/// given guardian approvals. The new key signs it.
+pub fn build_recovery(payload: RecoveryPayload, version: Version, timestamp: Timestamp) -> Knot {
+    let (thread_id, new_pubkey) = (payload.thread_id, payload.new_pubkey);
+    key_update_knot(
+        KnotType::Recovery,
+        thread_id,
+        (new_pubkey, new_pubkey),
+        version,
+        timestamp,
+        KnotPayload::Recovery(payload),
+    )
+}
+
+fn key_update_knot(
+    knot_type: KnotType,
+    thread_id: ThreadId,
+    (before_key, after_key): (PublicKey, PublicKey),
+    version: Version,
+    timestamp: Timestamp,
+    payload: KnotPayload,
+) -> Knot {
+    let participant = |pubkey, version| ParticipantState {
+        thread_id,
+        pubkey,
+        version,
+        state_hash: [0u8; 32],
+    };
+    let mut knot = Knot {
+        id: [0u8; 32],
+        knot_type,
+        timestamp,
+        expiry: None,
+        before_states: vec![participant(before_key, version)],
+        after_states: vec![participant(after_key, version + 1)],
+        payload,
+        signatures: Vec::new(),
+        cosignatures: Vec::new(),
+    };
+    knot.id = compute_knot_id(&knot);
+    knot
+}
+
+/// Check that a guardian set can be installed for a thread owned by `owner`.
+/// An empty set with threshold 0 removes the thread's guardians.
+pub fn validate_guardian_set(set: &GuardianSet, owner: &PublicKey) -> Result<(), NornError> {
+    let invalid = |reason: &str| {
+        Err(NornError::InvalidKeyUpdate {
+            reason: reason.to_string(),
+        })
+    };
+    if set.guardians.is_empty() {
+        if set.threshold != 0 {
+            return invalid("an empty guardian set must have threshold 0");
+        }
+        return Ok(());
+    }
+    if set.guardians.len() > MAX_GUARDIANS {
+        return invalid(&format!("at most {} guardians allowed", MAX_GUARDIANS));
+    }
+    if set.threshold == 0 || set.threshold as usize > set.guardians.len() {
+        return invalid("threshold must be between 1 and the number of guardians");
+    }
+    let mut seen = HashSet::new();
+    for guardian in &set.guardians {
+        if guardian == owner {
+            return invalid("the owner key cannot be its own guardian");
+        }
+        if !seen.insert(guardian) {
+            return invalid("duplicate guardian");
+        }
+    }
+    Ok(())
+}
+
+/// Check that a cosigner set can be installed for a thread owned by `owner`.
+/// An empty set with threshold 0 removes the thread's cosigners.
+pub fn validate_cosigner_set(set: &CosignerSet, owner: &PublicKey) -> Result<(), NornError> {
+    let invalid = |reason: &str| {
+        Err(NornError::InvalidKeyUpdate {
+            reason: reason.to_string(),
+        })
+    };
+    if set.cosigners.is_empty() {
+        if set.threshold != 0 {
+            return invalid("an empty cosigner set must have threshold 0");
+        }
+        return Ok(());
+    }
+    if set.cosigners.len() > MAX_COSIGNERS {
+        return invalid(&format!("at most {} cosigners allowed", MAX_COSIGNERS));
+    }
+    if set.threshold == 0 || set.threshold as usize > set.cosigners.len() {
+        return invalid("threshold must be between 1 and the number of cosigners");
+    }
+    let mut seen = HashSet::new();
+    for cosigner in &set.cosigners {
+        if cosigner == owner {
+            return invalid("the owner key cannot be its own cosigner");
+        }
+        if !seen.insert(cosigner) {
+            return invalid("duplicate cosigner");
+        }
+    }
+    Ok(())
+}
+
+/// Check that a knot carries valid signatures from at least `threshold`
+/// distinct members of the cosigner set. Other cosignatures are not counted;
+/// they may belong to another participant's cosigners.
+pub fn validate_cosignatures(knot: &Knot, set: &CosignerSet) -> Result<(), NornError> {
+    let signed: HashSet<&PublicKey> = knot
+        .cosignatures
+        .iter()
+        .filter(|c| set.cosigners.contains(&c.signer))
+        .filter(|c| verify(&knot.id, &c.signature, &c.signer).is_ok())
+        .map(|c| &c.signer)
+        .collect();
+    if signed.len() < set.threshold as usize {
+        return Err(NornError::InsufficientCosignatures {
+            required: set.threshold as usize,
+            actual: signed.len(),
+        });
+    }
+    Ok(())
+}
+
+/// Structural checks on a key update knot that do not depend on the thread's
+/// current keys: one participant, the thread the payload names, and before and
+/// after keys matching the update.
+pub fn validate_key_update_payload(knot: &Knot) -> Result<(), NornError> {
+    let invalid = |reason: &str| {
+        Err(NornError::InvalidKeyUpdate {
+            reason: reason.to_string(),
+        })
+    };
+    let (thread_id, before_key, after_key) = match (&knot.knot_type, &knot.payload) {
+        (KnotType::KeyRotation, KnotPayload::KeyRotation(p)) => {
+            if p.new_pubkey == p.old_pubkey {
+                return invalid("new key must differ from the old key");
+            }
+            (p.thread_id, p.old_pubkey, p.new_pubkey)
+        }
+        (KnotType::SetGuardians, KnotPayload::SetGuardians(p)) => {
+            let owner = knot.before_states.first().map_or([0u8; 32], |s| s.pubkey);
+            (p.thread_id, owner, owner)
+        }
+        (KnotType::SetCosigners, KnotPayload::SetCosigners(p)) => {
+            let owner = knot.before_states.first().map_or([0u8; 32], |s| s.pubkey);
+            (p.thread_id, owner, owner)
+        }
+        (KnotType::Recovery, KnotPayload::Recovery(p)) => {
+            if p.new_pubkey == p.old_pubkey {
+                return invalid("new key must differ from the old key");
+            }
+            (p.thread_id, p.new_pubkey, p.new_pubkey)
+        }
+        _ if is_key_update(knot) => {
+            return invalid("knot type does not match payload");
+        }
+        _ => return invalid("not a key update"),
+    };
+    let ([before], [after]) = (&knot.before_states[..], &knot.after_states[..]) else {
+        return invalid("a key update has exactly one participant");
+    };
+    if before.thread_id != thread_id || after.thread_id != thread_id {
+        return invalid("participant is not the thread being updated");
+    }
+    if before.pubkey != before_key || after.pubkey != after_key {
+        return invalid("participant keys do not match the update");
+    }
+    if after_key == [0u8; 32] {
+        return invalid("new key must be non-zero");
+    }
+    Ok(())
+}
+
+/// Verify a key update knot against the thread's current keys: its ID and
+/// signature, that it applies to the current epoch, and that it is authorized
+/// by the current owner (rotation, guardian and cosigner changes) or by at
+/// least `threshold` of the registered guardians (recovery). Updates other
+/// than recovery also need the thread's cosigners, if it has any.
+pub fn validate_key_update(knot: &Knot, keys: &ThreadKeys) -> Result<(), NornError> {
+    validate_rule_2_knot_id(knot)?;
+    validate_rule_1_signatures(knot)?;
+    validate_key_update_payload(knot)?;
+
+    let invalid = |reason: &str| {
+        Err(NornError::InvalidKeyUpdate {
+            reason: reason.to_string(),
+        })
+    };
+    let Some((_, epoch)) = key_update_target(knot) else {
+        return invalid("not a key update");
+    };
+    if epoch != keys.epoch {
+        return Err(NornError::KeyEpochMismatch {
+            expected: keys.epoch,
+            actual: epoch,
+        });
+    }
+    match &knot.payload {
+        KnotPayload::KeyRotation(p) if p.old_pubkey != keys.owner => {
+            return invalid("old key is not the thread's owner key");
+        }
+        KnotPayload::SetGuardians(p) => {
+            if knot.before_states[0].pubkey != keys.owner {
+                return invalid("guardians must be set by the thread's owner key");
+            }
+            validate_guardian_set(&p.guardians, &keys.owner)?;
+        }
+        KnotPayload::SetCosigners(p) => {
+            if knot.before_states[0].pubkey != keys.owner {
+                return invalid("cosigners must be set by the thread's owner key");
+            }
+            validate_cosigner_set(&p.cosigners, &keys.owner)?;
+        }
+        KnotPayload::Recovery(p) => {
+            if p.old_pubkey != keys.owner {
+                return invalid("old key is not the thread's owner key");
+            }
+            let Some(guardians) = keys.guardians.as_ref() else {
+                return invalid("thread has no guardians");
+            };
+            let sig_data = recovery_signing_data(p);
+            let mut approved = HashSet::new();
+            for approval in &p.approvals {
+                if !guardians.guardians.contains(&approval.guardian) {
+                    return invalid("approval from a key that is not a guardian");
+                }
+                verify(&sig_data, &approval.signature, &approval.guardian).map_err(|_| {
+                    NornError::InvalidKeyUpdate {
+                        reason: "invalid guardian signature".to_string(),
+                    }
+                })?;
+                approved.insert(approval.guardian);
+            }
+            if approved.len() < guardians.threshold as usize {
+                return Err(NornError::InsufficientGuardianApprovals {
+                    required: guardians.threshold as usize,
+                    actual: approved.len(),
+                });
+            }
+        }
+        _ => {}
+    }
+    // Guardians recover a thread without its owner device, so recovery does
+    // not need cosigners; anything else the owner signs does.
+    if let Some(cosigners) = keys.cosigners.as_ref() {
+        if !matches!(knot.payload, KnotPayload::Recovery(_)) {
+            validate_cosignatures(knot, cosigners)?;
+        }
+    }
+    Ok(())
+}
+
+/// Apply a validated key update to the thread's keys and advance its epoch.
+/// Knots that are not key updates leave the keys untouched.
+pub fn apply_key_update(keys: &mut ThreadKeys, knot: &Knot) {
+    match &knot.payload {
+        KnotPayload::KeyRotation(p) => keys.owner = p.new_pubkey,
+        KnotPayload::SetGuardians(p) => {
+            keys.guardians = (!p.guardians.guardians.is_empty()).then(|| p.guardians.clone());
+        }
+        KnotPayload::Recovery(p) => keys.owner = p.new_pubkey,
+        KnotPayload::SetCosigners(p) => {
+            keys.cosigners = (!p.cosigners.cosigners.is_empty()).then(|| p.cosigners.clone());
+        }
+        _ => return,
+    }
+    keys.epoch += 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knot::{add_cosignature, sign_knot};
+
+    fn signed(mut knot: Knot, signer: &Keypair) -> Knot {
+        let sig = sign_knot(&knot, signer);
+        knot.signatures.push(sig);
+        knot
+    }
+
+    fn guardians(kps: &[Keypair], threshold: u8) -> GuardianSet {
+        GuardianSet {
+            guardians: kps.iter().map(|kp| kp.public_key()).collect(),
+            threshold,
+        }
+    }
+
+    #[test]
+    fn test_key_rotation() {
+        let old = Keypair::generate();
+        let new = Keypair::generate();
+        let thread_id = pubkey_to_address(&old.public_key());
+        let mut keys = ThreadKeys::new(old.public_key());
+
+        let knot = signed(
+            build_key_rotation(thread_id, &keys, new.public_key(), 1, 1000),
+            &old,
+        );
+        validate_key_update(&knot, &keys).unwrap();
+        apply_key_update(&mut keys, &knot);
+        assert_eq!(keys.owner, new.public_key());
+        assert_eq!(keys.epoch, 1);
+
+        // The same rotation cannot be applied again.
+        assert!(matches!(
+            validate_key_update(&knot, &keys),
+            Err(NornError::KeyEpochMismatch {
+                expected: 1,
+                actual: 0
+            })
+        ));
+    }
+
+    #[test]
+    fn test_current_keys() {
+        let old = Keypair::generate();
+        let new = Keypair::generate();
+        let thread_id = pubkey_to_address(&old.public_key());
+        let keys = ThreadKeys::new(old.public_key());
+        let knot = build_key_rotation(thread_id, &keys, new.public_key(), 1, 1000);
+
+        // Unknown or keyless threads are owned by the key they derive from.
+        assert_eq!(current_keys(&knot, None), Some(keys.clone()));
+        assert_eq!(
+            current_keys(&knot, Some(ThreadKeys::new([0u8; 32]))),
+            Some(keys.clone())
+        );
+
+        // Recorded keys win once the thread has rotated.
+        let rotated = ThreadKeys {
+            owner: new.public_key(),
+            guardians: None,
+            cosigners: None,
+            epoch: 1,
+        };
+        assert_eq!(current_keys(&knot, Some(rotated.clone())), Some(rotated));
+
+        // A key that does not derive the thread cannot claim it.
+        let stranger = build_key_rotation(
+            thread_id,
+            &ThreadKeys::new(new.public_key()),
+            old.public_key(),
+            1,
+            1000,
+        );
+        assert_eq!(current_keys(&stranger, None), None);
+    }
+
+    #[test]
+    fn test_key_rotation_requires_old_key_signature() {
+        let old = Keypair::generate();
+        let new = Keypair::generate();
+        let thread_id = pubkey_to_address(&old.public_key());
+        let keys = ThreadKeys::new(old.public_key());
+
+        let knot = signed(
+            build_key_rotation(thread_id, &keys, new.public_key(), 1, 1000),
+            &new,
+        );
+        assert!(matches!(
+            validate_key_update(&knot, &keys),
+            Err(NornError::InvalidSignature { .. })
+        ));
+
+        // A rotation by anyone but the current owner is refused even if signed.
+        let attacker = Keypair::generate();
+        let forged = signed(
+            build_key_rotation(
+                thread_id,
+                &ThreadKeys::new(attacker.public_key()),
+                new.public_key(),
+                1,
+                1000,
+            ),
+            &attacker,
+        );
+        assert!(validate_key_update(&forged, &keys).is_err());
+    }
+
+    #[test]
+    fn test_validate_guardian_set() {
+        let owner = Keypair::generate().public_key();
+        let kps: Vec<Keypair> = (0..3).map(|_| Keypair::generate()).collect();
+        assert!(validate_guardian_set(&guardians(&kps, 2), &owner).is_ok());
+        assert!(validate_guardian_set(&guardians(&[], 0), &owner).is_ok());
+        assert!(validate_guardian_set(&guardians(&kps, 0), &owner).is_err());
+        assert!(validate_guardian_set(&guardians(&kps, 4), &owner).is_err());
+        assert!(validate_guardian_set(&guardians(&[], 1), &owner).is_err());
+
+        let mut dup = guardians(&kps, 2);
+        dup.guardians.push(dup.guardians[0]);
+        assert!(validate_guardian_set(&dup, &owner).is_err());
+
+        let mut with_owner = guardians(&kps, 2);
+        with_owner.guardians.push(owner);
+        assert!(validate_guardian_set(&with_owner, &owner).is_err());
+    }
+
+    #[test]
+    fn test_guardian_recovery() {
+        let owner = Keypair::generate();
+        let new = Keypair::generate();
+        let thread_id = pubkey_to_address(&owner.public_key());
+        let kps: Vec<Keypair> = (0..3).map(|_| Keypair::generate()).collect();
+        let mut keys = ThreadKeys::new(owner.public_key());
+
+        // The owner registers 2-of-3 guardians.
+        let set = signed(
+            build_set_guardians(thread_id, &keys, guardians(&kps, 2), 1, 1000),
+            &owner,
+        );
+        validate_key_update(&set, &keys).unwrap();
+        apply_key_update(&mut keys, &set);
+        assert_eq!(keys.epoch, 1);
+
+        let mut payload = RecoveryPayload {
+            thread_id,
+            epoch: keys.epoch,
+            old_pubkey: owner.public_key(),
+            new_pubkey: new.public_key(),
+            approvals: Vec::new(),
+        };
+        payload.approvals.push(approve_recovery(&payload, &kps[0]));
+
+        // One approval is not enough, even if repeated.
+        payload.approvals.push(payload.approvals[0].clone());
+        let short = signed(build_recovery(payload.clone(), 2, 1001), &new);
+        assert!(matches!(
+            validate_key_update(&short, &keys),
+            Err(NornError::InsufficientGuardianApprovals {
+                required: 2,
+                actual: 1
+            })
+        ));
+
+        payload.approvals[1] = approve_recovery(&payload, &kps[2]);
+        let recovery = signed(build_recovery(payload.clone(), 2, 1001), &new);
+        validate_key_update(&recovery, &keys).unwrap();
+        apply_key_update(&mut keys, &recovery);
+        assert_eq!(keys.owner, new.public_key());
+        assert_eq!(keys.epoch, 2);
+    }
+
+    #[test]
+    fn test_recovery_rejects_outsiders() {
+        let owner = Keypair::generate();
+        let new = Keypair::generate();
+        let thread_id = pubkey_to_address(&owner.public_key());
+        let kps: Vec<Keypair> = (0..2).map(|_| Keypair::generate()).collect();
+        let keys = ThreadKeys {
+            owner: owner.public_key(),
+            guardians: Some(guardians(&kps, 1)),
+            cosigners: None,
+            epoch: 1,
+        };
+        let mut payload = RecoveryPayload {
+            thread_id,
+            epoch: 1,
+            old_pubkey: owner.public_key(),
+            new_pubkey: new.public_key(),
+            approvals: Vec::new(),
+        };
+
+        // An approval from a non-guardian.
+        payload.approvals = vec![approve_recovery(&payload, &Keypair::generate())];
+        let knot = signed(build_recovery(payload.clone(), 2, 1001), &new);
+        assert!(validate_key_update(&knot, &keys).is_err());
+
+        // A guardian approval for a different new key.
+        let other = RecoveryPayload {
+            new_pubkey: Keypair::generate().public_key(),
+            ..payload.clone()
+        };
+        payload.approvals = vec![approve_recovery(&other, &kps[0])];
+        let knot = signed(build_recovery(payload.clone(), 2, 1001), &new);
+        assert!(validate_key_update(&knot, &keys).is_err());
+
+        // Without guardians, recovery is impossible.
+        payload.approvals = vec![approve_recovery(&payload, &kps[0])];
+        let knot = signed(build_recovery(payload, 2, 1001), &new);
+        validate_key_update(&knot, &keys).unwrap();
+        let unguarded = ThreadKeys {
+            guardians: None,
+            ..keys
+        };
+        assert!(validate_key_update(&knot, &unguarded).is_err());
+    }
+
+    #[test]
+    fn test_cosigned_key_updates() {
+        let owner = Keypair::generate();
+        let thread_id = pubkey_to_address(&owner.public_key());
+        let kps: Vec<Keypair> = (0..3).map(|_| Keypair::generate()).collect();
+        let mut keys = ThreadKeys::new(owner.public_key());
+        let set = CosignerSet {
+            cosigners: kps.iter().map(|kp| kp.public_key()).collect(),
+            threshold: 2,
+        };
+        assert!(validate_cosigner_set(&set, &owner.public_key()).is_ok());
+        let mut with_owner = set.clone();
+        with_owner.cosigners.push(owner.public_key());
+        assert!(validate_cosigner_set(&with_owner, &owner.public_key()).is_err());
+
+        // The owner alone registers 2-of-3 cosigners.
+        let register = signed(
+            build_set_cosigners(thread_id, &keys, set.clone(), 1, 1000),
+            &owner,
+        );
+        validate_key_update(&register, &keys).unwrap();
+        apply_key_update(&mut keys, &register);
+        assert_eq!(keys.cosigners, Some(set));
+
+        // From now on the owner cannot rotate away without them.
+        let new = Keypair::generate();
+        let mut rotation = signed(
+            build_key_rotation(thread_id, &keys, new.public_key(), 2, 1001),
+            &owner,
+        );
+        add_cosignature(&mut rotation, &kps[0]);
+        add_cosignature(&mut rotation, &kps[0]);
+        add_cosignature(&mut rotation, &Keypair::generate());
+        assert_eq!(
+            validate_key_update(&rotation, &keys),
+            Err(NornError::InsufficientCosignatures {
+                required: 2,
+                actual: 1
+            })
+        );
+        add_cosignature(&mut rotation, &kps[2]);
+        validate_key_update(&rotation, &keys).unwrap();
+    }
+}
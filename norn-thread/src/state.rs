@@ -156,6 +156,11 @@ pub fn apply_payload(
             })
         }
         KnotPayload::LoomInteraction(loom) => apply_loom_interaction(sender_state, loom),
+        // Key updates change who controls the thread, not its balances.
+        KnotPayload::KeyRotation(_)
+        | KnotPayload::SetGuardians(_)
+        | KnotPayload::Recovery(_)
+        | KnotPayload::SetCosigners(_) => Ok(()),
     }
 }
 
@@ -5,8 +5,9 @@ use norn_types::constants::MAX_UNCOMMITTED_KNOTS;
 use norn_types::error::NornError;
 use norn_types::knot::Knot;
 use norn_types::primitives::*;
-use norn_types::thread::{ThreadHeader, ThreadState};
+use norn_types::thread::{ThreadCheckpoint, ThreadHeader, ThreadState};
 
+use crate::chain::{create_checkpoint, is_checkpoint_due};
 use crate::state::compute_state_hash;
 
 /// A thread with its full state, knot history since last commitment, and header.
@@ -25,6 +26,8 @@ pub struct Thread {
     uncommitted_knots: Vec<Knot>,
     /// Timestamp of the last knot applied.
     last_knot_timestamp: Timestamp,
+    /// The latest full-state checkpoint, if any.
+    checkpoint: Option<ThreadCheckpoint>,
 }
 
 impl Thread {
@@ -55,6 +58,7 @@ impl Thread {
             version: 0,
             uncommitted_knots: Vec::new(),
             last_knot_timestamp: 0,
+            checkpoint: None,
         }
     }
 
@@ -157,6 +161,38 @@ impl Thread {
     pub fn uncommitted_knots(&self) -> &[Knot] {
         &self.uncommitted_knots
     }
+
+    /// Whether `CHECKPOINT_INTERVAL` knots have been applied since the
+    /// latest checkpoint.
+    pub fn checkpoint_due(&self) -> bool {
+        let last = self.checkpoint.as_ref().map_or(0, |c| c.version);
+        is_checkpoint_due(self.version, last)
+    }
+
+    /// Create a full-state checkpoint of the thread, linked to the previous
+    /// one, and keep it as the latest.
+    pub fn checkpoint(&mut self, timestamp: Timestamp) -> ThreadCheckpoint {
+        let last_knot_hash = self
+            .uncommitted_knots
+            .last()
+            .map_or(self.header.last_knot_hash, |k| k.id);
+        let checkpoint = create_checkpoint(
+            self.address,
+            &self.state,
+            self.version,
+            last_knot_hash,
+            self.checkpoint.as_ref(),
+            timestamp,
+            &self.keypair,
+        );
+        self.checkpoint = Some(checkpoint.clone());
+        checkpoint
+    }
+
+    /// Get the latest checkpoint.
+    pub fn latest_checkpoint(&self) -> Option<&ThreadCheckpoint> {
+        self.checkpoint.as_ref()
+    }
 }
 
 /// Compute the hash of a thread header for chaining.
@@ -282,4 +318,26 @@ mod tests {
         let second_header = thread.commit(2000);
         assert_eq!(second_header.prev_header_hash, first_hash);
     }
+
+    #[test]
+    fn test_checkpoints_chain() {
+        let kp = Keypair::generate();
+        let mut thread = Thread::new(kp, 1000);
+        assert!(!thread.checkpoint_due());
+        assert!(thread.latest_checkpoint().is_none());
+
+        let first = thread.checkpoint(1000);
+        assert_eq!(first.version, 0);
+        assert_eq!(thread.latest_checkpoint(), Some(&first));
+
+        thread.version = norn_types::constants::CHECKPOINT_INTERVAL;
+        assert!(thread.checkpoint_due());
+        let second = thread.checkpoint(2000);
+        assert!(!thread.checkpoint_due());
+        crate::chain::verify_checkpoint_link(&first, &second).unwrap();
+        assert_eq!(
+            crate::chain::verify_checkpoint(&second).unwrap(),
+            compute_state_hash(thread.current_state())
+        );
+    }
 }
@@ -1,11 +1,11 @@
 use norn_crypto::keys::verify;
-use norn_types::constants::MAX_TIMESTAMP_DRIFT;
+use norn_types::constants::{DEFAULT_KNOT_EXPIRY, MAX_TIMESTAMP_DRIFT};
 use norn_types::error::NornError;
 use norn_types::knot::*;
 use norn_types::primitives::*;
 use norn_types::thread::ThreadState;
 
-use crate::knot::compute_knot_id;
+use crate::knot::{compute_knot_id, transfer_legs};
 use crate::state::compute_state_hash;
 
 /// Context needed to validate a knot against current thread states.
@@ -174,46 +174,12 @@ pub fn validate_rule_6_after_state_hashes(
 pub fn validate_rule_7_payload_consistency(knot: &Knot) -> Result<(), NornError> {
     match &knot.payload {
         KnotPayload::Transfer(transfer) => {
-            if transfer.amount == 0 {
-                return Err(NornError::InvalidAmount);
-            }
-            if let Some(memo) = &transfer.memo {
-                if memo.len() > norn_types::constants::MAX_MEMO_SIZE {
-                    return Err(NornError::PayloadInconsistent {
-                        reason: format!(
-                            "memo too large: {} > {}",
-                            memo.len(),
-                            norn_types::constants::MAX_MEMO_SIZE
-                        ),
-                    });
-                }
-            }
+            validate_transfer_payload(transfer)?;
             // Verify from/to match participants
             validate_transfer_participants(transfer, knot)?;
             Ok(())
         }
-        KnotPayload::MultiTransfer(multi) => {
-            if multi.transfers.is_empty() {
-                return Err(NornError::PayloadInconsistent {
-                    reason: "multi-transfer has no transfers".to_string(),
-                });
-            }
-            if multi.transfers.len() > norn_types::constants::MAX_MULTI_TRANSFERS {
-                return Err(NornError::PayloadInconsistent {
-                    reason: format!(
-                        "too many transfers: {} > {}",
-                        multi.transfers.len(),
-                        norn_types::constants::MAX_MULTI_TRANSFERS
-                    ),
-                });
-            }
-            for transfer in &multi.transfers {
-                if transfer.amount == 0 {
-                    return Err(NornError::InvalidAmount);
-                }
-            }
-            Ok(())
-        }
+        KnotPayload::MultiTransfer(multi) => validate_multi_transfer_payload(multi),
         KnotPayload::LoomInteraction(loom) => {
             match loom.interaction_type {
                 LoomInteractionType::Deposit | LoomInteractionType::Withdraw => {
@@ -230,6 +196,10 @@ pub fn validate_rule_7_payload_consistency(knot: &Knot) -> Result<(), NornError>
             }
             Ok(())
         }
+        KnotPayload::KeyRotation(_)
+        | KnotPayload::SetGuardians(_)
+        | KnotPayload::Recovery(_)
+        | KnotPayload::SetCosigners(_) => crate::rotation::validate_key_update_payload(knot),
     }
 }
 
@@ -253,13 +223,116 @@ pub fn validate_rule_8_timestamp(knot: &Knot, ctx: &ValidationContext) -> Result
     Ok(())
 }
 
-/// Rule 9: If expiry is set, current time < expiry.
+/// Rule 9: Current time < the knot's effective expiry.
 pub fn validate_rule_9_expiry(knot: &Knot, ctx: &ValidationContext) -> Result<(), NornError> {
-    if let Some(expiry) = knot.expiry {
-        if ctx.current_time >= expiry {
-            return Err(NornError::KnotExpired {
-                expiry,
-                current: ctx.current_time,
+    let expiry = effective_expiry(knot);
+    if ctx.current_time >= expiry {
+        return Err(NornError::KnotExpired {
+            expiry,
+            current: ctx.current_time,
+        });
+    }
+    Ok(())
+}
+
+/// The time from which a knot is no longer accepted: its `expiry` if set,
+/// otherwise `DEFAULT_KNOT_EXPIRY` after its timestamp. Bounding every knot's
+/// lifetime bounds how long a node must remember applied knot IDs.
+pub fn effective_expiry(knot: &Knot) -> Timestamp {
+    knot.expiry
+        .unwrap_or_else(|| knot.timestamp.saturating_add(DEFAULT_KNOT_EXPIRY))
+}
+
+/// Check a knot admitted without a full `ValidationContext` (RPC, gossip) is
+/// inside its validity window at `now`: not timestamped beyond the allowed
+/// drift (rule 8) and not expired (rule 9).
+pub fn validate_knot_window(knot: &Knot, now: Timestamp) -> Result<(), NornError> {
+    let ctx = ValidationContext {
+        versions: Vec::new(),
+        state_hashes: Vec::new(),
+        expected_after_hashes: Vec::new(),
+        current_time: now,
+        previous_knot_timestamp: 0,
+    };
+    validate_rule_8_timestamp(knot, &ctx)?;
+    validate_rule_9_expiry(knot, &ctx)
+}
+
+/// Check a knot does not reuse a sequence number of any participant's
+/// thread. `next_sequence` gives the lowest `before.version` a thread's next
+/// knot may use; a knot below it was replayed or reordered behind a later
+/// one. Gaps are allowed, since a node may not have seen every knot of a
+/// thread. A version of 0 is unsequenced: such knots rely on the expiry
+/// window and knot ID deduplication alone.
+pub fn validate_knot_sequence(
+    knot: &Knot,
+    next_sequence: impl Fn(&ThreadId) -> Version,
+) -> Result<(), NornError> {
+    for (i, participant) in knot.before_states.iter().enumerate() {
+        let next = next_sequence(&participant.thread_id);
+        if participant.version != 0 && participant.version < next {
+            return Err(NornError::KnotOutOfOrder {
+                participant_index: i,
+                version: participant.version,
+                next,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Validate a single transfer on its own: non-zero amount and memo size.
+pub fn validate_transfer_payload(transfer: &TransferPayload) -> Result<(), NornError> {
+    if transfer.amount == 0 {
+        return Err(NornError::InvalidAmount);
+    }
+    if let Some(memo) = &transfer.memo {
+        if memo.len() > norn_types::constants::MAX_MEMO_SIZE {
+            return Err(NornError::PayloadInconsistent {
+                reason: format!(
+                    "memo too large: {} > {}",
+                    memo.len(),
+                    norn_types::constants::MAX_MEMO_SIZE
+                ),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Validate a multi-transfer: between 1 and MAX_MULTI_TRANSFERS transfers,
+/// each valid on its own.
+pub fn validate_multi_transfer_payload(multi: &MultiTransferPayload) -> Result<(), NornError> {
+    if multi.transfers.is_empty() {
+        return Err(NornError::PayloadInconsistent {
+            reason: "multi-transfer has no transfers".to_string(),
+        });
+    }
+    if multi.transfers.len() > norn_types::constants::MAX_MULTI_TRANSFERS {
+        return Err(NornError::PayloadInconsistent {
+            reason: format!(
+                "too many transfers: {} > {}",
+                multi.transfers.len(),
+                norn_types::constants::MAX_MULTI_TRANSFERS
+            ),
+        });
+    }
+    multi
+        .transfers
+        .iter()
+        .try_for_each(validate_transfer_payload)
+}
+
+/// Validate that every transfer in the knot is sent by a participant: its
+/// `from` must be a participant's thread. Signatures (rule 1) and that each
+/// participant's key currently owns its thread are checked separately, since
+/// a thread's owner key can be rotated away from the key its ID derives from.
+pub fn validate_transfer_senders(knot: &Knot) -> Result<(), NornError> {
+    let signers: Vec<Address> = knot.before_states.iter().map(|p| p.thread_id).collect();
+    for (_, transfer) in transfer_legs(knot) {
+        if !signers.contains(&transfer.from) {
+            return Err(NornError::PayloadInconsistent {
+                reason: "transfer sender is not a signer of the knot".to_string(),
             });
         }
     }
@@ -537,6 +610,47 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_rule_7_multi_transfer() {
+        let s = setup();
+        let transfer = TransferPayload {
+            token_id: NATIVE_TOKEN_ID,
+            amount: 500,
+            from: s.sender_addr,
+            to: s.receiver_addr,
+            memo: None,
+        };
+        let mut knot = s.knot.clone();
+        knot.payload = KnotPayload::MultiTransfer(MultiTransferPayload {
+            transfers: vec![transfer.clone(); 3],
+        });
+        assert!(validate_rule_7_payload_consistency(&knot).is_ok());
+
+        let mut oversized_memo = transfer.clone();
+        oversized_memo.memo = Some(vec![0u8; 1000]);
+        knot.payload = KnotPayload::MultiTransfer(MultiTransferPayload {
+            transfers: vec![transfer.clone(), oversized_memo],
+        });
+        assert!(matches!(
+            validate_rule_7_payload_consistency(&knot),
+            Err(NornError::PayloadInconsistent { .. })
+        ));
+
+        knot.payload = KnotPayload::MultiTransfer(MultiTransferPayload {
+            transfers: vec![transfer; norn_types::constants::MAX_MULTI_TRANSFERS + 1],
+        });
+        assert!(matches!(
+            validate_rule_7_payload_consistency(&knot),
+            Err(NornError::PayloadInconsistent { .. })
+        ));
+
+        knot.payload = KnotPayload::MultiTransfer(MultiTransferPayload { transfers: vec![] });
+        assert!(matches!(
+            validate_rule_7_payload_consistency(&knot),
+            Err(NornError::PayloadInconsistent { .. })
+        ));
+    }
+
     #[test]
     fn test_rule_8_timestamp_too_future() {
         let s = setup();
@@ -592,6 +706,57 @@ mod tests {
         assert!(validate_rule_9_expiry(&good_knot, &ctx).is_ok());
     }
 
+    #[test]
+    fn test_rule_9_default_expiry() {
+        let s = setup();
+        assert_eq!(s.knot.expiry, None);
+        assert_eq!(effective_expiry(&s.knot), 1000 + DEFAULT_KNOT_EXPIRY);
+        let ctx = ValidationContext {
+            current_time: 1000 + DEFAULT_KNOT_EXPIRY,
+            ..make_context(&s)
+        };
+        assert!(matches!(
+            validate_rule_9_expiry(&s.knot, &ctx),
+            Err(NornError::KnotExpired { .. })
+        ));
+    }
+
+    #[test]
+    fn test_knot_window() {
+        let s = setup();
+        assert!(validate_knot_window(&s.knot, 1000).is_ok());
+        assert!(matches!(
+            validate_knot_window(&s.knot, 1000 - MAX_TIMESTAMP_DRIFT - 1),
+            Err(NornError::TimestampTooFuture { .. })
+        ));
+        assert!(matches!(
+            validate_knot_window(&s.knot, 1000 + DEFAULT_KNOT_EXPIRY),
+            Err(NornError::KnotExpired { .. })
+        ));
+    }
+
+    #[test]
+    fn test_knot_sequence() {
+        let s = setup();
+        // Version 0 is unsequenced.
+        assert!(validate_knot_sequence(&s.knot, |_| 5).is_ok());
+
+        let mut knot = s.knot.clone();
+        knot.before_states[1].version = 3;
+        let receiver = s.receiver_addr;
+        let next = |tid: &ThreadId| if *tid == receiver { 4 } else { 0 };
+        assert_eq!(
+            validate_knot_sequence(&knot, next),
+            Err(NornError::KnotOutOfOrder {
+                participant_index: 1,
+                version: 3,
+                next: 4,
+            })
+        );
+        knot.before_states[1].version = 4;
+        assert!(validate_knot_sequence(&knot, next).is_ok());
+    }
+
     #[test]
     fn test_version_overflow_returns_error() {
         let s = setup();
@@ -657,4 +822,97 @@ mod tests {
             Err(NornError::InsufficientParticipants { .. })
         ));
     }
+
+    mod replay {
+        use super::*;
+        use proptest::prelude::*;
+
+        fn knot_at(version: Version) -> Knot {
+            let state = ThreadState::new();
+            KnotBuilder::transfer(1000)
+                .add_before_state([1u8; 20], [2u8; 32], version, &state)
+                .with_payload(KnotPayload::Transfer(TransferPayload {
+                    token_id: NATIVE_TOKEN_ID,
+                    amount: 1,
+                    from: [1u8; 20],
+                    to: [3u8; 20],
+                    memo: None,
+                }))
+                .build()
+                .unwrap()
+        }
+
+        /// Deliver knots of one thread the way a node admits them, moving the
+        /// thread's next sequence past each accepted knot. Returns the
+        /// versions accepted.
+        fn admit(versions: &[Version]) -> Vec<Version> {
+            let mut next = 0;
+            let mut accepted = Vec::new();
+            for &version in versions {
+                if validate_knot_sequence(&knot_at(version), |_| next).is_ok() {
+                    next = version + 1;
+                    accepted.push(version);
+                }
+            }
+            accepted
+        }
+
+        proptest! {
+            #![proptest_config(ProptestConfig::with_cases(64))]
+
+            #[test]
+            fn accepted_versions_strictly_increase(
+                versions in prop::collection::vec(1u64..32, 0..48),
+            ) {
+                let accepted = admit(&versions);
+                prop_assert!(accepted.windows(2).all(|w| w[0] < w[1]));
+            }
+
+            #[test]
+            fn in_order_delivery_accepts_every_knot(
+                start in 1u64..1_000,
+                gaps in prop::collection::vec(1u64..4, 0..32),
+            ) {
+                let versions: Vec<Version> = gaps
+                    .iter()
+                    .scan(start, |v, gap| {
+                        *v += gap;
+                        Some(*v)
+                    })
+                    .collect();
+                prop_assert_eq!(admit(&versions), versions);
+            }
+
+            #[test]
+            fn reordered_knots_behind_a_later_one_are_rejected(
+                order in Just((1u64..25).collect::<Vec<_>>()).prop_shuffle(),
+            ) {
+                // Only knots newer than every knot accepted before them get in.
+                let mut highest = None;
+                let expected: Vec<Version> = order
+                    .iter()
+                    .copied()
+                    .filter(|&v| {
+                        let newer = highest.is_none_or(|h| v > h);
+                        if newer {
+                            highest = Some(v);
+                        }
+                        newer
+                    })
+                    .collect();
+                prop_assert_eq!(admit(&order), expected);
+            }
+
+            #[test]
+            fn replayed_knots_are_rejected(
+                versions in prop::collection::vec(1u64..32, 1..32),
+                replays in prop::collection::vec(any::<prop::sample::Index>(), 1..8),
+            ) {
+                let accepted = admit(&versions);
+                let mut stream = versions.clone();
+                stream.extend(replays.iter().map(|i| *i.get(&versions)));
+                prop_assert_eq!(admit(&stream), accepted);
+            }
+        }
+    }
 }
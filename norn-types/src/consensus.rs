@@ -8,16 +8,40 @@ use crate::primitives::*;
 pub struct Vote {
     /// The view number this vote is for.
     pub view: u64,
+    /// The phase this vote is cast in.
+    pub phase: ConsensusPhase,
     /// The block hash being voted on.
     pub block_hash: Hash,
     /// The voter's public key.
     pub voter: PublicKey,
-    /// Signature over (view, block_hash).
+    /// Signature over [`vote_signing_data`]`(phase, view, block_hash)`.
     #[serde(with = "crate::primitives::serde_sig")]
     pub signature: Signature,
+    /// BLS signature over the same data, aggregated into quorum certificates.
+    #[serde(with = "crate::primitives::serde_bls_sig")]
+    pub bls_signature: BlsSignature,
+}
+
+/// Domain tag for vote signatures, so they cannot pass for signatures over
+/// anything else the validator key signs.
+const VOTE_DOMAIN: &[u8] = b"norn-protocol vote v1";
+
+/// Compute the data a validator signs for a vote:
+/// blake3(domain || phase || view_le || block_hash).
+///
+/// The phase is signed so that a certificate for one phase cannot be
+/// relabelled as another, e.g. a Prepare QC passed off as a Commit QC.
+pub fn vote_signing_data(phase: ConsensusPhase, view: u64, block_hash: &Hash) -> Vec<u8> {
+    let mut data = Vec::with_capacity(VOTE_DOMAIN.len() + 41);
+    data.extend_from_slice(VOTE_DOMAIN);
+    data.push(phase as u8);
+    data.extend_from_slice(&view.to_le_bytes());
+    data.extend_from_slice(block_hash);
+    blake3::hash(&data).as_bytes().to_vec()
 }
 
-/// A quorum certificate — 2f+1 votes for a block at a given phase.
+/// A quorum certificate — 2f+1 votes for a block at a given phase, with
+/// their BLS signatures aggregated into one.
 #[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
 pub struct QuorumCertificate {
     /// The view number.
@@ -26,8 +50,32 @@ pub struct QuorumCertificate {
     pub block_hash: Hash,
     /// The phase this QC certifies.
     pub phase: ConsensusPhase,
-    /// The votes forming the quorum.
-    pub votes: Vec<Vote>,
+    /// Bitmap of the signers' positions in the validator set, least
+    /// significant bit first.
+    pub signers: Vec<u8>,
+    /// Aggregate of the signers' BLS signatures over
+    /// [`vote_signing_data`]`(phase, view, block_hash)`.
+    #[serde(with = "crate::primitives::serde_bls_sig")]
+    pub signature: BlsSignature,
+}
+
+impl QuorumCertificate {
+    /// Positions of the signers in the validator set, in ascending order.
+    pub fn signer_indices(&self) -> Vec<usize> {
+        (0..self.signers.len() * 8)
+            .filter(|i| self.signers[i / 8] & (1 << (i % 8)) != 0)
+            .collect()
+    }
+}
+
+/// Build a signer bitmap for a validator set of `validator_count` members
+/// from the signers' positions. Positions out of range are ignored.
+pub fn signer_bitmap(indices: impl IntoIterator<Item = usize>, validator_count: usize) -> Vec<u8> {
+    let mut bitmap = vec![0u8; validator_count.div_ceil(8)];
+    for i in indices.into_iter().filter(|i| *i < validator_count) {
+        bitmap[i / 8] |= 1 << (i % 8);
+    }
+    bitmap
 }
 
 /// Consensus phases in 3-phase HotStuff.
@@ -29,11 +29,21 @@ pub const MAX_TIMESTAMP_DRIFT: u64 = 300; // 5 minutes
 /// Default knot expiry duration (seconds).
 pub const DEFAULT_KNOT_EXPIRY: u64 = 3600; // 1 hour
 
+/// Maximum number of guardians a thread may register for key recovery.
+pub const MAX_GUARDIANS: usize = 16;
+
+/// Maximum number of cosigners a thread may register.
+pub const MAX_COSIGNERS: usize = 16;
+
 // ─── Weave Parameters ────────────────────────────────────────────────────────
 
 /// Target time between weave blocks.
 pub const BLOCK_TIME_TARGET: Duration = Duration::from_secs(3);
 
+/// How many times the consensus view timeout doubles over consecutive
+/// timeouts without a committed block.
+pub const MAX_VIEW_TIMEOUT_DOUBLINGS: u32 = 3;
+
 /// Maximum number of commitment updates per weave block.
 pub const MAX_COMMITMENTS_PER_BLOCK: usize = 10_000;
 
@@ -43,6 +53,22 @@ pub const COMMITMENT_FINALITY_DEPTH: u64 = 10;
 /// Maximum age of a commitment before it's considered stale (seconds).
 pub const MAX_COMMITMENT_AGE: u64 = 86_400; // 24 hours
 
+/// Number of recent blocks whose fees a node keeps for fee estimation.
+pub const FEE_HISTORY_BLOCKS: usize = 1_024;
+
+/// Number of recent blocks sampled for a fee estimate.
+pub const FEE_ESTIMATE_WINDOW: usize = 100;
+
+/// Default confidence (percent) of a fee estimate.
+pub const DEFAULT_FEE_CONFIDENCE: u8 = 50;
+
+/// Maximum number of pending items in a node's mempool.
+pub const MEMPOOL_MAX_SIZE: usize = 100_000;
+
+/// Fee increase (percent) a commitment must pay to replace a pending
+/// commitment for the same thread and version.
+pub const MEMPOOL_REPLACEMENT_FEE_BUMP_PERCENT: u128 = 10;
+
 // ─── Loom Parameters ─────────────────────────────────────────────────────────
 
 /// Maximum number of participants in a loom.
@@ -59,14 +85,24 @@ pub const MAX_LOOM_STATE_SIZE: usize = 1_048_576; // 1 MB
 /// Maximum message size in bytes.
 pub const MAX_MESSAGE_SIZE: usize = 2_097_152; // 2 MB
 
+/// Size of a state snapshot chunk in bytes. Kept well below
+/// `MAX_MESSAGE_SIZE` so a chunk always fits in one message.
+pub const SNAPSHOT_CHUNK_SIZE: usize = 1_048_576; // 1 MB
+
 /// Default relay port.
 pub const DEFAULT_RELAY_PORT: u16 = 9740;
 
 /// Maximum number of relay connections per spindle.
 pub const MAX_RELAY_CONNECTIONS: usize = 50;
 
-/// Default bootstrap nodes for the public Norn network.
-pub const DEFAULT_BOOT_NODES: &[&str] = &["/dns4/seed.norn.network/tcp/9740"];
+/// Default bytes per second a relay accepts from one peer.
+pub const DEFAULT_PEER_INBOUND_BYTES_PER_SEC: u64 = 2_097_152; // 2 MB
+
+/// Default messages per second a relay accepts from one peer.
+pub const DEFAULT_PEER_INBOUND_MESSAGES_PER_SEC: u64 = 100;
+
+/// Default bytes per second of direct messages a relay sends to one peer.
+pub const DEFAULT_PEER_OUTBOUND_BYTES_PER_SEC: u64 = 2_097_152; // 2 MB
 
 // ─── Thread Parameters ───────────────────────────────────────────────────────
 
@@ -76,11 +112,37 @@ pub const MAX_UNCOMMITTED_KNOTS: usize = 1_000;
 /// Thread header size in bytes (fixed).
 pub const THREAD_HEADER_SIZE: usize = 208;
 
+/// Number of knots between full-state thread checkpoints.
+pub const CHECKPOINT_INTERVAL: u64 = 1_000;
+
 // ─── Epoch Parameters ───────────────────────────────────────────────────────
 
 /// Number of blocks per epoch (validator set rotation period).
 pub const BLOCKS_PER_EPOCH: u64 = 1_000;
 
+/// Default maximum number of validators in an epoch's active set.
+pub const MAX_VALIDATORS: usize = 100;
+
+/// Commission (in basis points) a validator takes from delegators' rewards
+/// until it sets its own.
+pub const DEFAULT_COMMISSION_BPS: u16 = 1_000;
+
+/// Maximum validator commission in basis points (100%).
+pub const MAX_COMMISSION_BPS: u16 = 10_000;
+
+/// Share of stake (in basis points) burned when a validator double-signs.
+pub const DOUBLE_SIGN_SLASH_BPS: u16 = 500;
+
+/// Share of stake (in basis points) burned when a validator is jailed for downtime.
+pub const DOWNTIME_SLASH_BPS: u16 = 10;
+
+/// Full leader rotations a validator may go without proposing a block
+/// before it is jailed for downtime.
+pub const DOWNTIME_WINDOW_ROUNDS: u64 = 100;
+
+/// Blocks a validator jailed for downtime must wait before unjailing.
+pub const DOWNTIME_JAIL_BLOCKS: u64 = 1_000;
+
 // ─── Fraud Proof Parameters ──────────────────────────────────────────────────
 
 /// Time window for submitting a fraud proof after a commitment (seconds).
@@ -89,6 +151,35 @@ pub const FRAUD_PROOF_WINDOW: u64 = 86_400; // 24 hours
 /// Minimum stake required to submit a fraud proof.
 pub const FRAUD_PROOF_MIN_STAKE: Amount = ONE_NORN;
 
+// ─── Spindle Market Parameters ───────────────────────────────────────────────
+
+/// Blocks in one spindle fee period (~1 day at the target block time).
+pub const SPINDLE_FEE_PERIOD_BLOCKS: u64 = 28_800;
+
+/// Minimum bond a spindle must hold to take on subscribers.
+pub const MIN_SPINDLE_BOND: Amount = 100 * ONE_NORN;
+
+/// Maximum number of fee periods a subscription can prepay.
+pub const MAX_SPINDLE_SUBSCRIPTION_PERIODS: u64 = 365;
+
+/// Share of bond (in basis points) paid to a subscriber when its spindle
+/// fails to report fraud against it.
+pub const SPINDLE_MISSED_FRAUD_SLASH_BPS: u16 = 1_000;
+
+// ─── Spindle Coverage Parameters ─────────────────────────────────────────────
+
+/// Number of spindles that should watch every thread.
+pub const DEFAULT_SPINDLE_REDUNDANCY: usize = 3;
+
+/// Maximum number of thread ranges in one coverage advertisement.
+pub const MAX_SPINDLE_COVERAGE_RANGES: usize = 64;
+
+/// Seconds between coverage re-advertisements when nothing changes.
+pub const SPINDLE_COVERAGE_REFRESH_SECS: u64 = 300;
+
+/// Seconds after which an unrefreshed coverage advertisement is dropped.
+pub const SPINDLE_COVERAGE_TTL_SECS: u64 = 900;
+
 // ─── Derivation Path ─────────────────────────────────────────────────────────
 
 /// Coin type for SLIP-44 registration (placeholder — not yet registered).
@@ -40,9 +40,27 @@ pub enum NornError {
     #[error("knot expired at {expiry}, current time is {current}")]
     KnotExpired { expiry: u64, current: u64 },
 
+    #[error(
+        "knot out of order for participant {participant_index}: version {version} is below the thread's next sequence {next}"
+    )]
+    KnotOutOfOrder {
+        participant_index: usize,
+        version: u64,
+        next: u64,
+    },
+
+    #[error("knot has already been applied")]
+    KnotReplayed,
+
     #[error("payload internally inconsistent: {reason}")]
     PayloadInconsistent { reason: String },
 
+    #[error("key is not a participant of this knot")]
+    NotKnotParticipant,
+
+    #[error("too many knots awaiting signatures (max {max})")]
+    TooManyPendingKnots { max: usize },
+
     // ─── Thread Errors ───────────────────────────────────────────────────────
     #[error("thread not found: {0:?}")]
     ThreadNotFound([u8; 20]),
@@ -56,6 +74,21 @@ pub enum NornError {
     #[error("invalid knot chain: gap at index {index}")]
     InvalidKnotChain { index: usize },
 
+    #[error("invalid key update: {reason}")]
+    InvalidKeyUpdate { reason: String },
+
+    #[error("key update is for epoch {actual}, thread is at epoch {expected}")]
+    KeyEpochMismatch { expected: u64, actual: u64 },
+
+    #[error("insufficient guardian approvals: need {required}, got {actual}")]
+    InsufficientGuardianApprovals { required: usize, actual: usize },
+
+    #[error("insufficient cosignatures: need {required}, got {actual}")]
+    InsufficientCosignatures { required: usize, actual: usize },
+
+    #[error("invalid thread checkpoint: {reason}")]
+    InvalidCheckpoint { reason: String },
+
     // ─── Crypto Errors ───────────────────────────────────────────────────────
     #[error("invalid key material")]
     InvalidKeyMaterial,
@@ -75,6 +108,18 @@ pub enum NornError {
     #[error("decryption failed: {reason}")]
     DecryptionFailed { reason: String },
 
+    #[error("invalid VRF proof")]
+    InvalidVrfProof,
+
+    #[error("threshold signing error: {reason}")]
+    ThresholdSigningError { reason: String },
+
+    #[error("invalid address: {reason}")]
+    InvalidAddress { reason: String },
+
+    #[error("invalid typed data: {reason}")]
+    InvalidTypedData { reason: String },
+
     // ─── Merkle Tree Errors ──────────────────────────────────────────────────
     #[error("merkle proof verification failed")]
     MerkleProofInvalid,
@@ -86,6 +131,12 @@ pub enum NornError {
     #[error("invalid weave block: {reason}")]
     InvalidWeaveBlock { reason: String },
 
+    #[error("invalid quorum certificate: {reason}")]
+    InvalidQuorumCertificate { reason: String },
+
+    #[error("insufficient quorum: have {have}, need {need}")]
+    InsufficientQuorum { have: usize, need: usize },
+
     #[error("stale commitment: age {age}s exceeds max {max_age}s")]
     StaleCommitment { age: u64, max_age: u64 },
 
@@ -99,6 +150,12 @@ pub enum NornError {
     #[error("not a loom participant")]
     NotLoomParticipant,
 
+    #[error("invalid loom operator set: {reason}")]
+    InvalidOperatorSet { reason: String },
+
+    #[error("invalid loom transfer: {reason}")]
+    InvalidLoomTransfer { reason: String },
+
     // ─── Name Registry Errors ─────────────────────────────────────────────────
     #[error("name already registered: {0}")]
     NameAlreadyRegistered(String),
@@ -3,7 +3,8 @@ use serde::{Deserialize, Serialize};
 
 use crate::knot::Knot;
 use crate::primitives::*;
-use crate::thread::ThreadHeader;
+use crate::thread::ThreadCheckpoint;
+use crate::weave::CommitmentUpdate;
 
 /// Fraud proof variants that can be submitted to the weave.
 #[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
@@ -22,9 +23,14 @@ pub enum FraudProof {
     StaleCommit {
         /// The thread with the stale commitment.
         thread_id: ThreadId,
-        /// The stale commitment header.
-        commitment: Box<ThreadHeader>,
-        /// The knot(s) that should have been included.
+        /// The stale commitment, as posted to the weave.
+        commitment: Box<CommitmentUpdate>,
+        /// A checkpoint signed by the thread owner before the commitment
+        /// that is past the committed version. It stands in for the knots up
+        /// to its version.
+        checkpoint: Option<Box<ThreadCheckpoint>>,
+        /// Knots signed by the thread owner before the commitment that move
+        /// the thread past the committed version (or past the checkpoint).
         missing_knots: Vec<Knot>,
     },
 
@@ -39,6 +45,27 @@ pub enum FraudProof {
     },
 }
 
+impl FraudProof {
+    /// Whether the fraud was against `thread_id`: the thread took part in
+    /// one of the proof's knots but is not the thread that cheated.
+    pub fn harms(&self, thread_id: &ThreadId) -> bool {
+        let in_knot = |knot: &Knot| knot.before_states.iter().any(|p| p.thread_id == *thread_id);
+        match self {
+            FraudProof::DoubleKnot {
+                thread_id: cheater,
+                knot_a,
+                knot_b,
+            } => cheater != thread_id && (in_knot(knot_a) || in_knot(knot_b)),
+            FraudProof::StaleCommit {
+                thread_id: cheater,
+                missing_knots,
+                ..
+            } => cheater != thread_id && missing_knots.iter().any(in_knot),
+            FraudProof::InvalidLoomTransition { knot, .. } => in_knot(knot),
+        }
+    }
+}
+
 /// Metadata about a submitted fraud proof.
 #[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
 pub struct FraudProofSubmission {
@@ -37,6 +37,12 @@ fn default_genesis_version() -> u32 {
 pub struct GenesisValidator {
     /// Validator's public key.
     pub pubkey: PublicKey,
+    /// Validator's BLS public key.
+    #[serde(with = "crate::primitives::serde_bls_pubkey")]
+    pub bls_pubkey: BlsPublicKey,
+    /// Proof of possession of the BLS secret key.
+    #[serde(with = "crate::primitives::serde_bls_sig")]
+    pub bls_pop: BlsSignature,
     /// Validator's address.
     pub address: Address,
     /// Initial stake amount.
@@ -81,8 +87,15 @@ pub struct GenesisParameters {
     /// Bonding period for unstaking (in blocks). Defaults to 100 blocks (~5 min at 3s blocks).
     #[serde(default = "default_bonding_period")]
     pub bonding_period: u64,
+    /// Maximum number of validators in an epoch's active set.
+    #[serde(default = "default_max_validators")]
+    pub max_validators: u64,
 }
 
 fn default_bonding_period() -> u64 {
     100
 }
+
+fn default_max_validators() -> u64 {
+    crate::constants::MAX_VALIDATORS as u64
+}
@@ -2,6 +2,7 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
 
 use crate::primitives::*;
+use crate::thread::{CosignerSet, GuardianSet};
 
 /// The type of operation a knot performs.
 #[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
@@ -12,6 +13,14 @@ pub enum KnotType {
     MultiTransfer,
     /// Interaction with a loom (deposit, withdraw, state update).
     LoomInteraction,
+    /// Owner rotates the thread to a new key, signed by the old key.
+    KeyRotation,
+    /// Owner registers (or clears) the thread's recovery guardians.
+    SetGuardians,
+    /// Guardians move a thread to a new key without the old one.
+    Recovery,
+    /// Owner registers (or clears) the keys that must co-sign the thread's knots.
+    SetCosigners,
 }
 
 /// Snapshot of a participant's thread state before or after a knot.
@@ -75,12 +84,90 @@ pub struct LoomInteractionPayload {
     pub data: Vec<u8>,
 }
 
+/// Payload of a key rotation knot. The single participant's before state
+/// carries `old_pubkey` (which signs) and its after state `new_pubkey`.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct KeyRotationPayload {
+    /// The thread being rotated.
+    pub thread_id: ThreadId,
+    /// The thread's key epoch this rotation applies to.
+    pub epoch: u64,
+    /// The key being retired; must be the thread's current owner.
+    pub old_pubkey: PublicKey,
+    /// The key taking over.
+    pub new_pubkey: PublicKey,
+}
+
+/// Payload of a guardian registration knot, signed by the current owner.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct SetGuardiansPayload {
+    /// The thread whose guardians are set.
+    pub thread_id: ThreadId,
+    /// The thread's key epoch this update applies to.
+    pub epoch: u64,
+    /// The new guardian set. An empty set with threshold 0 removes guardians.
+    pub guardians: GuardianSet,
+}
+
+/// A guardian's approval of a recovery, signed over
+/// `recovery_signing_data` of the recovery payload.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct GuardianApproval {
+    /// The approving guardian's public key.
+    pub guardian: PublicKey,
+    /// The guardian's signature.
+    #[serde(with = "crate::primitives::serde_sig")]
+    pub signature: Signature,
+}
+
+/// Payload of a guardian recovery knot. The single participant's before and
+/// after states carry `new_pubkey`, which signs the knot to prove possession.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct RecoveryPayload {
+    /// The thread being recovered.
+    pub thread_id: ThreadId,
+    /// The thread's key epoch this recovery applies to.
+    pub epoch: u64,
+    /// The owner key being replaced.
+    pub old_pubkey: PublicKey,
+    /// The key taking over.
+    pub new_pubkey: PublicKey,
+    /// Approvals from at least `threshold` distinct guardians.
+    pub approvals: Vec<GuardianApproval>,
+}
+
+/// Payload of a cosigner registration knot, signed by the current owner and,
+/// if the thread already has cosigners, co-signed by enough of them.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct SetCosignersPayload {
+    /// The thread whose cosigners are set.
+    pub thread_id: ThreadId,
+    /// The thread's key epoch this update applies to.
+    pub epoch: u64,
+    /// The new cosigner set. An empty set with threshold 0 removes cosigners.
+    pub cosigners: CosignerSet,
+}
+
+/// A cosigner's signature over a knot ID.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct Cosignature {
+    /// The cosigner's public key.
+    pub signer: PublicKey,
+    /// The cosigner's signature.
+    #[serde(with = "crate::primitives::serde_sig")]
+    pub signature: Signature,
+}
+
 /// The payload of a knot — varies by knot type.
 #[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
 pub enum KnotPayload {
     Transfer(TransferPayload),
     MultiTransfer(MultiTransferPayload),
     LoomInteraction(LoomInteractionPayload),
+    KeyRotation(KeyRotationPayload),
+    SetGuardians(SetGuardiansPayload),
+    Recovery(RecoveryPayload),
+    SetCosigners(SetCosignersPayload),
 }
 
 /// A knot is the fundamental unit of state transition in Norn.
@@ -104,4 +191,8 @@ pub struct Knot {
     /// Signatures from all participants (one per participant, in order).
     #[serde(with = "crate::primitives::serde_sig_vec")]
     pub signatures: Vec<Signature>,
+    /// Signatures from the cosigners of participant threads that require
+    /// them. Not covered by the knot ID.
+    #[serde(default)]
+    pub cosignatures: Vec<Cosignature>,
 }
@@ -1,8 +1,9 @@
 //! Shared type definitions for the Norn Protocol.
 //!
 //! This crate defines all core data structures used across the protocol:
-//! Threads, Knots, Weave blocks, Looms, fraud proofs, consensus messages,
-//! genesis configuration, and primitive types.
+//! Threads, Knots, Weave blocks, Looms, inter-loom transfers, fraud proofs,
+//! consensus messages, slashing evidence, spindle subscriptions, genesis
+//! configuration, state snapshots, typed signing data, and primitive types.
 
 pub mod consensus;
 pub mod constants;
@@ -11,11 +12,16 @@ pub mod fraud;
 pub mod genesis;
 pub mod knot;
 pub mod loom;
+pub mod loom_transfer;
 pub mod name;
 pub mod network;
 pub mod primitives;
+pub mod slashing;
+pub mod snapshot;
+pub mod spindle;
 pub mod thread;
 pub mod token;
+pub mod typed_data;
 pub mod weave;
 
 #[cfg(test)]
@@ -87,6 +93,7 @@ mod tests {
                 memo: Some(b"hello".to_vec()),
             }),
             signatures: vec![[99u8; 64]],
+            cosignatures: Vec::new(),
         };
         borsh_roundtrip(&knot);
     }
@@ -141,6 +148,7 @@ mod tests {
             prev_commitment_hash: [4u8; 32],
             knot_count: 5,
             timestamp: 3000,
+            priority_fee: 0,
             signature: [6u8; 64],
         };
         borsh_roundtrip(&cu);
@@ -167,7 +175,10 @@ mod tests {
             state_hash: [2u8; 32],
             block_height: 100,
             timestamp: 2000,
-            signature: [3u8; 64],
+            signatures: vec![crate::loom::OperatorSignature {
+                pubkey: [4u8; 32],
+                signature: [3u8; 64],
+            }],
         };
         borsh_roundtrip(&anchor);
     }
@@ -187,6 +198,7 @@ mod tests {
         use crate::weave::Validator;
         let v = Validator {
             pubkey: [1u8; 32],
+            bls_pubkey: [3u8; 48],
             address: [2u8; 20],
             stake: 1_000_000,
             active: true,
@@ -243,6 +255,7 @@ mod tests {
                 memo: None,
             }),
             signatures: vec![],
+            cosignatures: Vec::new(),
         };
 
         let fp = FraudProof::DoubleKnot {
@@ -253,6 +266,43 @@ mod tests {
         borsh_roundtrip(&fp);
     }
 
+    #[test]
+    fn test_slashing_evidence_roundtrip() {
+        use crate::consensus::{ConsensusPhase, Vote};
+        use crate::slashing::SlashingEvidence;
+
+        let vote = |hash: u8| Vote {
+            view: 7,
+            phase: ConsensusPhase::Prepare,
+            block_hash: [hash; 32],
+            voter: [1u8; 32],
+            signature: [hash; 64],
+            bls_signature: [hash; 96],
+        };
+        let evidence = SlashingEvidence::DoubleVote {
+            vote_a: Box::new(vote(2)),
+            vote_b: Box::new(vote(3)),
+        };
+        assert_eq!(*evidence.offender(), [1u8; 32]);
+        borsh_roundtrip(&evidence);
+    }
+
+    #[test]
+    fn test_quorum_certificate_signers() {
+        use crate::consensus::{signer_bitmap, ConsensusPhase, QuorumCertificate};
+
+        let qc = QuorumCertificate {
+            view: 3,
+            block_hash: [1u8; 32],
+            phase: ConsensusPhase::Commit,
+            signers: signer_bitmap([0, 2, 9, 12], 10),
+            signature: [2u8; 96],
+        };
+        assert_eq!(qc.signers.len(), 2);
+        assert_eq!(qc.signer_indices(), vec![0, 2, 9]);
+        borsh_roundtrip(&qc);
+    }
+
     #[test]
     fn test_loom_config_roundtrip() {
         use crate::loom::LoomConfig;
@@ -264,6 +314,8 @@ mod tests {
             min_participants: 2,
             accepted_tokens: vec![NATIVE_TOKEN_ID],
             config_data: vec![1, 2, 3],
+            co_operators: vec![],
+            operator_threshold: 1,
         };
         borsh_roundtrip(&config);
     }
@@ -276,6 +328,7 @@ mod tests {
             address: [2u8; 20],
             joined_at: 1000,
             active: true,
+            role: crate::loom::ParticipantRole::Observer,
         };
         borsh_roundtrip(&p);
     }
@@ -292,6 +345,8 @@ mod tests {
                 min_participants: 2,
                 accepted_tokens: vec![NATIVE_TOKEN_ID],
                 config_data: vec![],
+                co_operators: vec![],
+                operator_threshold: 1,
             },
             operator: [2u8; 32],
             participants: vec![],
@@ -357,6 +412,10 @@ mod tests {
         borsh_roundtrip(&KnotType::Transfer);
         borsh_roundtrip(&KnotType::MultiTransfer);
         borsh_roundtrip(&KnotType::LoomInteraction);
+        borsh_roundtrip(&KnotType::KeyRotation);
+        borsh_roundtrip(&KnotType::SetGuardians);
+        borsh_roundtrip(&KnotType::Recovery);
+        borsh_roundtrip(&KnotType::SetCosigners);
     }
 
     #[test]
@@ -8,16 +8,46 @@ use crate::primitives::*;
 /// Higher than token creation (10 NORN) because bytecode is resource-heavy.
 pub const LOOM_DEPLOY_FEE: Amount = 50 * ONE_NORN;
 
+/// Bond at stake on each side of a loom dispute (10 NORN). The losing party's
+/// bond is paid to the winner.
+pub const LOOM_DISPUTE_BOND: Amount = 10 * ONE_NORN;
+
 /// Maximum length of a loom name.
 pub const MAX_LOOM_NAME_LEN: usize = 64;
 
+/// Maximum number of operators (primary plus co-operators) of one loom.
+pub const MAX_LOOM_OPERATORS: usize = 16;
+
 /// Compute the data that should be signed for a loom deployment.
-/// Canonical bytes: name + operator + timestamp.
+/// Canonical bytes: name + operator + timestamp, followed by the
+/// co-operators and threshold for multi-operator looms.
 pub fn loom_deploy_signing_data(reg: &LoomRegistration) -> Vec<u8> {
     let mut data = Vec::new();
     data.extend_from_slice(reg.config.name.as_bytes());
     data.extend_from_slice(&reg.operator);
     data.extend_from_slice(&reg.timestamp.to_le_bytes());
+    if !reg.config.co_operators.is_empty() {
+        for op in &reg.config.co_operators {
+            data.extend_from_slice(op);
+        }
+        data.extend_from_slice(&reg.config.operator_threshold.to_le_bytes());
+    }
+    data
+}
+
+/// Compute the data loom operators sign to approve a state update.
+/// Canonical bytes: loom_id + state_hash + block_height + timestamp.
+pub fn loom_state_signing_data(
+    loom_id: &LoomId,
+    state_hash: &Hash,
+    block_height: u64,
+    timestamp: Timestamp,
+) -> Vec<u8> {
+    let mut data = Vec::with_capacity(32 + 32 + 8 + 8);
+    data.extend_from_slice(loom_id);
+    data.extend_from_slice(state_hash);
+    data.extend_from_slice(&block_height.to_le_bytes());
+    data.extend_from_slice(&timestamp.to_le_bytes());
     data
 }
 
@@ -49,6 +79,20 @@ pub fn compute_loom_id(reg: &LoomRegistration) -> LoomId {
     *hasher.finalize().as_bytes()
 }
 
+/// Derive the loom ID of an instance created from stored code.
+///
+/// Depends only on the creating contract's address, the code ID, and a salt,
+/// so a factory can predict an instance's ID before creating it.
+pub fn derive_instance_id(creator: &Address, code_id: &Hash, salt: &[u8]) -> LoomId {
+    use blake3::Hasher;
+    let mut hasher = Hasher::new();
+    hasher.update(b"norn_instance");
+    hasher.update(creator);
+    hasher.update(code_id);
+    hasher.update(salt);
+    *hasher.finalize().as_bytes()
+}
+
 /// Configuration for a loom.
 #[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
 pub struct LoomConfig {
@@ -64,6 +108,158 @@ pub struct LoomConfig {
     pub accepted_tokens: Vec<TokenId>,
     /// Opaque loom-specific configuration data.
     pub config_data: Vec<u8>,
+    /// Operators besides the deploying operator. Empty for single-operator looms.
+    #[serde(default)]
+    pub co_operators: Vec<PublicKey>,
+    /// Operator signatures required on a state update (0 is treated as 1).
+    #[serde(default)]
+    pub operator_threshold: u16,
+}
+
+/// The operators of a loom and how many of them must sign a state update.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct OperatorSet {
+    /// Operator public keys; the deploying operator is first.
+    pub operators: Vec<PublicKey>,
+    /// Number of distinct operator signatures required.
+    pub threshold: u16,
+}
+
+impl OperatorSet {
+    /// A single operator whose signature alone approves updates.
+    pub fn single(operator: PublicKey) -> Self {
+        Self {
+            operators: vec![operator],
+            threshold: 1,
+        }
+    }
+
+    /// Build the operator set from a deploying operator and loom config.
+    pub fn from_config(operator: &PublicKey, config: &LoomConfig) -> Self {
+        let mut operators = vec![*operator];
+        operators.extend_from_slice(&config.co_operators);
+        Self {
+            operators,
+            threshold: config.operator_threshold.max(1),
+        }
+    }
+
+    /// Check the set is non-empty, bounded, free of duplicates, and has a
+    /// reachable threshold.
+    pub fn validate(&self) -> Result<(), crate::error::NornError> {
+        let invalid = |reason: String| crate::error::NornError::InvalidOperatorSet { reason };
+        if self.operators.is_empty() || self.operators.len() > MAX_LOOM_OPERATORS {
+            return Err(invalid(format!(
+                "loom must have 1-{MAX_LOOM_OPERATORS} operators, got {}",
+                self.operators.len()
+            )));
+        }
+        for (i, op) in self.operators.iter().enumerate() {
+            if self.operators[..i].contains(op) {
+                return Err(invalid("duplicate operator".to_string()));
+            }
+        }
+        if self.threshold == 0 || self.threshold as usize > self.operators.len() {
+            return Err(invalid(format!(
+                "threshold must be 1-{}, got {}",
+                self.operators.len(),
+                self.threshold
+            )));
+        }
+        Ok(())
+    }
+
+    /// Whether `pubkey` is one of the operators.
+    pub fn contains(&self, pubkey: &PublicKey) -> bool {
+        self.operators.contains(pubkey)
+    }
+
+    /// Replace operator `old` with `new`, keeping its position (the
+    /// primary operator stays first) and the threshold.
+    pub fn rotate(
+        &mut self,
+        old: &PublicKey,
+        new: PublicKey,
+    ) -> Result<(), crate::error::NornError> {
+        let invalid = |reason: &str| crate::error::NornError::InvalidOperatorSet {
+            reason: reason.to_string(),
+        };
+        if self.contains(&new) {
+            return Err(invalid("new operator is already an operator"));
+        }
+        let slot = self
+            .operators
+            .iter_mut()
+            .find(|op| *op == old)
+            .ok_or_else(|| invalid("old operator is not an operator"))?;
+        *slot = new;
+        Ok(())
+    }
+}
+
+/// An operator's signature over a loom state update.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct OperatorSignature {
+    /// The signing operator's public key.
+    pub pubkey: PublicKey,
+    /// Signature over [`loom_state_signing_data`].
+    #[serde(with = "crate::primitives::serde_sig")]
+    pub signature: Signature,
+}
+
+/// What a loom participant is permitted to do.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum ParticipantRole {
+    /// A loom operator: may execute and manage participant roles.
+    Operator,
+    /// May submit executions.
+    #[default]
+    Submitter,
+    /// May only query loom state.
+    Observer,
+}
+
+impl ParticipantRole {
+    /// Whether this role may submit state-changing executions.
+    pub fn can_execute(&self) -> bool {
+        !matches!(self, ParticipantRole::Observer)
+    }
+
+    /// Lowercase name of the role.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ParticipantRole::Operator => "operator",
+            ParticipantRole::Submitter => "submitter",
+            ParticipantRole::Observer => "observer",
+        }
+    }
+}
+
+impl std::str::FromStr for ParticipantRole {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "operator" => Ok(ParticipantRole::Operator),
+            "submitter" => Ok(ParticipantRole::Submitter),
+            "observer" => Ok(ParticipantRole::Observer),
+            other => Err(format!(
+                "unknown participant role '{other}' (expected operator, submitter, or observer)"
+            )),
+        }
+    }
 }
 
 /// A participant in a loom.
@@ -77,6 +273,42 @@ pub struct Participant {
     pub joined_at: Timestamp,
     /// Whether the participant is currently active.
     pub active: bool,
+    /// The participant's permission level.
+    #[serde(default)]
+    pub role: ParticipantRole,
+}
+
+/// Compute the data both keys sign for an operator handover.
+/// Canonical bytes: loom_id + old_operator + new_operator + timestamp.
+pub fn operator_handover_signing_data(handover: &OperatorHandover) -> Vec<u8> {
+    let mut data = Vec::with_capacity(32 + 32 + 32 + 8);
+    data.extend_from_slice(&handover.loom_id);
+    data.extend_from_slice(&handover.old_operator);
+    data.extend_from_slice(&handover.new_operator);
+    data.extend_from_slice(&handover.timestamp.to_le_bytes());
+    data
+}
+
+/// A signed handover that replaces one of a loom's operator keys.
+///
+/// The outgoing key authorizes the rotation and the incoming key accepts it,
+/// proving possession, so a handover can never name a key nobody controls.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct OperatorHandover {
+    /// The loom whose operator is rotated.
+    pub loom_id: LoomId,
+    /// The operator key being retired.
+    pub old_operator: PublicKey,
+    /// The key taking its place.
+    pub new_operator: PublicKey,
+    /// Timestamp of the handover.
+    pub timestamp: Timestamp,
+    /// Signature by `old_operator` over [`operator_handover_signing_data`].
+    #[serde(with = "crate::primitives::serde_sig")]
+    pub old_signature: Signature,
+    /// Signature by `new_operator` over [`operator_handover_signing_data`].
+    #[serde(with = "crate::primitives::serde_sig")]
+    pub new_signature: Signature,
 }
 
 /// A loom registration request.
@@ -112,6 +344,13 @@ pub struct Loom {
     pub last_updated: Timestamp,
 }
 
+impl Loom {
+    /// The loom's operators and signing threshold.
+    pub fn operator_set(&self) -> OperatorSet {
+        OperatorSet::from_config(&self.operator, &self.config)
+    }
+}
+
 /// Deployed loom bytecode (Wasm module).
 #[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
 pub struct LoomBytecode {
@@ -167,6 +406,8 @@ mod tests {
                 min_participants: 1,
                 accepted_tokens: vec![],
                 config_data: vec![],
+                co_operators: vec![],
+                operator_threshold: 1,
             },
             operator: [1u8; 32],
             timestamp: 12345,
@@ -216,4 +457,74 @@ mod tests {
         let long_name = "A".repeat(65);
         assert!(validate_loom_name(&long_name).is_err());
     }
+
+    #[test]
+    fn test_signing_data_binds_co_operators() {
+        let single = make_registration();
+        let mut multi = make_registration();
+        multi.config.co_operators = vec![[2u8; 32]];
+        multi.config.operator_threshold = 2;
+        assert_ne!(
+            loom_deploy_signing_data(&single),
+            loom_deploy_signing_data(&multi)
+        );
+    }
+
+    #[test]
+    fn test_operator_set_rotate() {
+        let mut set = OperatorSet {
+            operators: vec![[1u8; 32], [2u8; 32]],
+            threshold: 2,
+        };
+        set.rotate(&[2u8; 32], [3u8; 32]).unwrap();
+        assert_eq!(set.operators, vec![[1u8; 32], [3u8; 32]]);
+        assert!(set.rotate(&[2u8; 32], [4u8; 32]).is_err());
+        assert!(set.rotate(&[1u8; 32], [3u8; 32]).is_err());
+    }
+
+    #[test]
+    fn test_participant_role() {
+        assert_eq!(ParticipantRole::default(), ParticipantRole::Submitter);
+        assert!(!ParticipantRole::Observer.can_execute());
+        for role in [
+            ParticipantRole::Operator,
+            ParticipantRole::Submitter,
+            ParticipantRole::Observer,
+        ] {
+            assert_eq!(role.as_str().parse::<ParticipantRole>(), Ok(role));
+        }
+        assert!("admin".parse::<ParticipantRole>().is_err());
+    }
+
+    #[test]
+    fn test_operator_set_validate() {
+        let mut reg = make_registration();
+        reg.config.co_operators = vec![[2u8; 32], [3u8; 32]];
+        reg.config.operator_threshold = 2;
+        let set = OperatorSet::from_config(&reg.operator, &reg.config);
+        assert_eq!(set.operators[0], reg.operator);
+        assert!(set.validate().is_ok());
+        assert!(set.contains(&[3u8; 32]));
+
+        let mut high = set.clone();
+        high.threshold = 4;
+        assert!(high.validate().is_err());
+
+        let mut dup = set.clone();
+        dup.operators.push([2u8; 32]);
+        assert!(dup.validate().is_err());
+
+        let too_many = OperatorSet {
+            operators: (0..=MAX_LOOM_OPERATORS as u8).map(|i| [i; 32]).collect(),
+            threshold: 1,
+        };
+        assert!(too_many.validate().is_err());
+
+        // An unset threshold means a single signature suffices.
+        reg.config.operator_threshold = 0;
+        assert_eq!(
+            OperatorSet::from_config(&reg.operator, &reg.config).threshold,
+            1
+        );
+    }
 }
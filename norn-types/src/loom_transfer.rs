@@ -0,0 +1,318 @@
+//! Inter-loom token transfer standard, modelled on IBC ICS-20.
+//!
+//! Two transfer looms are joined by a pair of channels, one on each side.
+//! A token moves by lock-and-mint: the source loom escrows it and stores a
+//! commitment to a [`LoomTransferPacket`]; a relayer submits the packet to the
+//! destination loom, which checks it against the source's commitment and
+//! credits a voucher. Sending the voucher back over the same channel burns it
+//! and releases the escrow.
+//!
+//! Denominations record the path they travelled. Each hop prepends
+//! `transfer/<channel>/`, so `transfer/channel-0/<base>` is a voucher for
+//! `<base>` received over `channel-0`. A native token's base denom is the
+//! lowercase hex of its token ID.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::error::NornError;
+use crate::primitives::*;
+
+/// Port name used in denom trace prefixes.
+pub const TRANSFER_PORT: &str = "transfer";
+
+/// Prefix of every channel ID (`channel-<n>`).
+pub const CHANNEL_PREFIX: &str = "channel-";
+
+/// Maximum length of a full denom path.
+pub const MAX_DENOM_LEN: usize = 512;
+
+/// Maximum length of a packet memo.
+pub const MAX_MEMO_LEN: usize = 256;
+
+/// Token payload of a transfer packet.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct TokenPacketData {
+    /// Full denom path as seen by the sending loom.
+    pub denom: String,
+    /// Amount to transfer.
+    pub amount: Amount,
+    /// Account debited on the source loom.
+    pub sender: Address,
+    /// Account credited on the destination loom.
+    pub receiver: Address,
+    /// Free-form note passed through to the destination.
+    pub memo: String,
+}
+
+/// A token transfer in flight between two looms.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct LoomTransferPacket {
+    /// Per-channel send sequence on the source loom, starting at 1.
+    pub sequence: u64,
+    /// Loom that sent the packet.
+    pub source_loom: LoomId,
+    /// Channel the packet left through, on the source loom.
+    pub source_channel: String,
+    /// Loom the packet is addressed to.
+    pub dest_loom: LoomId,
+    /// Channel the packet arrives on, on the destination loom.
+    pub dest_channel: String,
+    /// Token payload.
+    pub data: TokenPacketData,
+    /// Unix timestamp after which the packet can no longer be received and
+    /// the sender may be refunded. Zero disables the timeout.
+    pub timeout_timestamp: Timestamp,
+}
+
+/// A denom split into the hops it travelled and its base denom.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct DenomTrace {
+    /// `transfer/<channel>` hops, most recent first, joined by `/`. Empty for
+    /// a token on its home loom.
+    pub path: String,
+    /// Denom on the token's home loom.
+    pub base_denom: String,
+}
+
+impl DenomTrace {
+    /// Split a full denom path into its hops and base denom.
+    pub fn parse(denom: &str) -> Self {
+        let parts: Vec<&str> = denom.split('/').collect();
+        let mut hops = 0;
+        // Stop before the last segment: the base denom is never empty.
+        while hops + 2 < parts.len()
+            && parts[hops] == TRANSFER_PORT
+            && is_channel_id(parts[hops + 1])
+        {
+            hops += 2;
+        }
+        DenomTrace {
+            path: parts[..hops].join("/"),
+            base_denom: parts[hops..].join("/"),
+        }
+    }
+
+    /// Reassemble the full denom path.
+    pub fn full_path(&self) -> String {
+        if self.path.is_empty() {
+            self.base_denom.clone()
+        } else {
+            format!("{}/{}", self.path, self.base_denom)
+        }
+    }
+
+    /// Whether the token is on its home loom.
+    pub fn is_native(&self) -> bool {
+        self.path.is_empty()
+    }
+
+    /// Stable identifier of the voucher: blake3("norn_denom" || full path).
+    pub fn hash(&self) -> Hash {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(b"norn_denom");
+        hasher.update(self.full_path().as_bytes());
+        *hasher.finalize().as_bytes()
+    }
+}
+
+/// Format the channel ID with number `n`.
+pub fn channel_id(n: u64) -> String {
+    format!("{CHANNEL_PREFIX}{n}")
+}
+
+/// Whether `s` is a well-formed channel ID.
+pub fn is_channel_id(s: &str) -> bool {
+    s.strip_prefix(CHANNEL_PREFIX).is_some_and(|n| {
+        !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()) && (n == "0" || !n.starts_with('0'))
+    })
+}
+
+/// Base denom of a native token: the lowercase hex of its ID.
+pub fn native_denom(token_id: &TokenId) -> String {
+    token_id.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Token ID of a native base denom, if `denom` is one.
+pub fn parse_native_denom(denom: &str) -> Option<TokenId> {
+    if denom.len() != 64
+        || !denom
+            .bytes()
+            .all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+    {
+        return None;
+    }
+    let mut id = [0u8; 32];
+    for (i, byte) in id.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&denom[2 * i..2 * i + 2], 16).ok()?;
+    }
+    Some(id)
+}
+
+/// Denom a token takes after arriving over `channel`.
+pub fn prefix_denom(channel: &str, denom: &str) -> String {
+    format!("{TRANSFER_PORT}/{channel}/{denom}")
+}
+
+/// If `denom` arrived over `channel`, the denom it had before that hop.
+///
+/// A packet whose denom is prefixed with its own source channel is a voucher
+/// going home: the destination releases escrow instead of minting.
+pub fn strip_channel_prefix<'a>(channel: &str, denom: &'a str) -> Option<&'a str> {
+    denom
+        .strip_prefix(TRANSFER_PORT)?
+        .strip_prefix('/')?
+        .strip_prefix(channel)?
+        .strip_prefix('/')
+}
+
+/// Commitment a source loom stores for a sent packet.
+///
+/// `blake3(0x00 || borsh(packet))`, the same leaf hash as
+/// `norn_crypto::merkle::hash_list_leaf`, so contracts can compute it with
+/// `norn_sdk::merkle::hash_list_leaf` and commitments can be merklized.
+pub fn packet_commitment(packet: &LoomTransferPacket) -> Hash {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[0x00]);
+    hasher.update(&borsh::to_vec(packet).expect("packet serialization cannot fail"));
+    *hasher.finalize().as_bytes()
+}
+
+/// Check a packet's fields are well formed.
+pub fn validate_transfer_packet(packet: &LoomTransferPacket) -> Result<(), NornError> {
+    let invalid = |reason: String| Err(NornError::InvalidLoomTransfer { reason });
+    if packet.sequence == 0 {
+        return invalid("sequence must start at 1".to_string());
+    }
+    for channel in [&packet.source_channel, &packet.dest_channel] {
+        if !is_channel_id(channel) {
+            return invalid(format!("invalid channel id '{channel}'"));
+        }
+    }
+    let data = &packet.data;
+    if data.amount == 0 {
+        return invalid("amount must be positive".to_string());
+    }
+    if data.denom.is_empty() || data.denom.len() > MAX_DENOM_LEN {
+        return invalid(format!(
+            "denom must be 1-{MAX_DENOM_LEN} bytes, got {}",
+            data.denom.len()
+        ));
+    }
+    if data.memo.len() > MAX_MEMO_LEN {
+        return invalid(format!(
+            "memo exceeds {MAX_MEMO_LEN} bytes, got {}",
+            data.memo.len()
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_packet() -> LoomTransferPacket {
+        LoomTransferPacket {
+            sequence: 1,
+            source_loom: [1u8; 32],
+            source_channel: channel_id(0),
+            dest_loom: [2u8; 32],
+            dest_channel: channel_id(3),
+            data: TokenPacketData {
+                denom: native_denom(&NATIVE_TOKEN_ID),
+                amount: 500,
+                sender: [3u8; 20],
+                receiver: [4u8; 20],
+                memo: String::new(),
+            },
+            timeout_timestamp: 1_000,
+        }
+    }
+
+    #[test]
+    fn test_denom_trace() {
+        let base = native_denom(&[0xab; 32]);
+        let once = prefix_denom("channel-0", &base);
+        let twice = prefix_denom("channel-7", &once);
+
+        let trace = DenomTrace::parse(&twice);
+        assert_eq!(trace.path, "transfer/channel-7/transfer/channel-0");
+        assert_eq!(trace.base_denom, base);
+        assert_eq!(trace.full_path(), twice);
+        assert!(!trace.is_native());
+        assert!(DenomTrace::parse(&base).is_native());
+        assert_ne!(trace.hash(), DenomTrace::parse(&once).hash());
+
+        // A base denom that merely looks like a hop is kept whole.
+        let odd = DenomTrace::parse("transfer/channel-1");
+        assert!(odd.is_native());
+        assert_eq!(odd.base_denom, "transfer/channel-1");
+    }
+
+    #[test]
+    fn test_strip_channel_prefix() {
+        let base = native_denom(&NATIVE_TOKEN_ID);
+        let voucher = prefix_denom("channel-2", &base);
+        assert_eq!(
+            strip_channel_prefix("channel-2", &voucher),
+            Some(base.as_str())
+        );
+        assert_eq!(strip_channel_prefix("channel-20", &voucher), None);
+        assert_eq!(strip_channel_prefix("channel-2", &base), None);
+    }
+
+    #[test]
+    fn test_channel_and_native_denoms() {
+        assert!(is_channel_id("channel-0"));
+        assert!(is_channel_id("channel-42"));
+        assert!(!is_channel_id("channel-"));
+        assert!(!is_channel_id("channel-01"));
+        assert!(!is_channel_id("chan-1"));
+
+        let id = [0x5au8; 32];
+        assert_eq!(parse_native_denom(&native_denom(&id)), Some(id));
+        assert_eq!(parse_native_denom("transfer/channel-0/ab"), None);
+        assert_eq!(parse_native_denom(&"A".repeat(64)), None);
+    }
+
+    #[test]
+    fn test_packet_commitment_is_list_leaf() {
+        let packet = sample_packet();
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(&[0x00]);
+        hasher.update(&borsh::to_vec(&packet).unwrap());
+        assert_eq!(packet_commitment(&packet), *hasher.finalize().as_bytes());
+
+        // Fixed vector shared with `examples/bridge`, which mirrors these types.
+        assert_eq!(
+            native_denom(&packet_commitment(&packet)),
+            "cc52d73ae533d00cea5b0fee3cc0c74e171c01116365a1586b6b2a17209f8228"
+        );
+        let mut other = packet.clone();
+        other.sequence = 2;
+        assert_ne!(packet_commitment(&packet), packet_commitment(&other));
+    }
+
+    #[test]
+    fn test_validate_transfer_packet() {
+        let packet = sample_packet();
+        assert!(validate_transfer_packet(&packet).is_ok());
+
+        let mut bad = packet.clone();
+        bad.data.amount = 0;
+        assert!(validate_transfer_packet(&bad).is_err());
+
+        let mut bad = packet.clone();
+        bad.sequence = 0;
+        assert!(validate_transfer_packet(&bad).is_err());
+
+        let mut bad = packet.clone();
+        bad.dest_channel = "elsewhere".to_string();
+        assert!(validate_transfer_packet(&bad).is_err());
+
+        let mut bad = packet;
+        bad.data.memo = "m".repeat(MAX_MEMO_LEN + 1);
+        assert!(validate_transfer_packet(&bad).is_err());
+    }
+}
@@ -4,11 +4,15 @@ use serde::{Deserialize, Serialize};
 use crate::consensus::ConsensusMessage;
 use crate::fraud::FraudProofSubmission;
 use crate::knot::Knot;
-use crate::loom::{LoomRegistration, LoomStateTransition};
+use crate::loom::{LoomRegistration, LoomStateTransition, OperatorHandover};
 use crate::primitives::*;
+use crate::slashing::SlashingEvidence;
+use crate::snapshot::SnapshotManifest;
+use crate::spindle::SpindleOperation;
+use crate::thread::ThreadCheckpoint;
 use crate::weave::{
     CommitmentUpdate, NameRecordUpdate, NameRegistration, NameTransfer, Registration,
-    StakeOperation, TokenBurn, TokenDefinition, TokenMint, WeaveBlock,
+    StakeOperation, TokenBurn, TokenDefinition, TokenMetadataUpdate, TokenMint, WeaveBlock,
 };
 
 /// A faucet credit for devnet/testnet token distribution.
@@ -41,6 +45,15 @@ impl NetworkId {
         }
     }
 
+    /// DNS seeds that list bootstrap peers for this network.
+    pub fn dns_seeds(&self) -> &'static [&'static str] {
+        match self {
+            NetworkId::Dev => &["seed.norn.network"],
+            NetworkId::Testnet => &["seed.testnet.norn.network"],
+            NetworkId::Mainnet => &["seed.mainnet.norn.network"],
+        }
+    }
+
     /// Whether the faucet is available on this network.
     pub fn faucet_enabled(&self) -> bool {
         matches!(self, NetworkId::Dev | NetworkId::Testnet)
@@ -74,6 +87,25 @@ impl NetworkId {
         }
     }
 
+    /// Human-readable part of bech32m addresses on this network.
+    pub fn address_hrp(&self) -> &'static str {
+        match self {
+            NetworkId::Dev => "dnorn",
+            NetworkId::Testnet => "tnorn",
+            NetworkId::Mainnet => "norn",
+        }
+    }
+
+    /// Network whose addresses use the given human-readable part.
+    pub fn from_address_hrp(hrp: &str) -> Option<Self> {
+        match hrp {
+            "dnorn" => Some(NetworkId::Dev),
+            "tnorn" => Some(NetworkId::Testnet),
+            "norn" => Some(NetworkId::Mainnet),
+            _ => None,
+        }
+    }
+
     /// Default faucet cooldown in seconds for this network.
     pub fn faucet_cooldown(&self) -> u64 {
         match self {
@@ -154,6 +186,74 @@ pub struct SpindleAlert {
     pub signature: Signature,
 }
 
+/// An inclusive range of thread IDs, ordered as big-endian byte strings.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct ThreadRange {
+    /// First thread ID in the range.
+    pub start: ThreadId,
+    /// Last thread ID in the range.
+    pub end: ThreadId,
+}
+
+impl ThreadRange {
+    /// The range covering every thread ID.
+    pub fn full() -> Self {
+        Self {
+            start: [0u8; 20],
+            end: [0xffu8; 20],
+        }
+    }
+
+    /// Check whether a thread ID falls within the range.
+    pub fn contains(&self, thread_id: &ThreadId) -> bool {
+        self.start <= *thread_id && *thread_id <= self.end
+    }
+}
+
+/// The thread ID ranges a spindle commits to watching, gossiped so spindles
+/// can divide the thread space with overlapping redundancy.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct SpindleCoverage {
+    /// The spindle's public key.
+    pub pubkey: PublicKey,
+    /// The spindle's address.
+    pub address: Address,
+    /// Thread ranges the spindle watches.
+    pub ranges: Vec<ThreadRange>,
+    /// Timestamp of this advertisement.
+    pub timestamp: Timestamp,
+    /// Signature by the spindle.
+    #[serde(with = "crate::primitives::serde_sig")]
+    pub signature: Signature,
+}
+
+/// Compute the signing data for a spindle coverage advertisement.
+pub fn spindle_coverage_signing_data(coverage: &SpindleCoverage) -> Vec<u8> {
+    let mut data = Vec::with_capacity(60 + coverage.ranges.len() * 40);
+    data.extend_from_slice(&coverage.pubkey);
+    data.extend_from_slice(&coverage.address);
+    for range in &coverage.ranges {
+        data.extend_from_slice(&range.start);
+        data.extend_from_slice(&range.end);
+    }
+    data.extend_from_slice(&coverage.timestamp.to_le_bytes());
+    data.extend_from_slice(b"spindle_coverage");
+    blake3::hash(&data).as_bytes().to_vec()
+}
+
+/// A block announced by its header and the short IDs of its commitments.
+/// Peers rebuild the block from commitments they have already seen and
+/// fetch only the ones they are missing.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct CompactBlock {
+    /// The block with its commitments removed.
+    pub header: WeaveBlock,
+    /// Short ID of each commitment, in block order.
+    pub short_ids: Vec<u64>,
+}
+
 /// Upgrade notice broadcast when a peer running a newer protocol version is detected.
 #[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
 pub struct UpgradeNotice {
@@ -271,6 +371,77 @@ pub enum NornMessage {
     NameTransfer(NameTransfer),
     /// A name record update (NNS — Norn Name Service).
     NameRecordUpdate(NameRecordUpdate),
+    /// A loom operator key handover.
+    OperatorHandover(Box<OperatorHandover>),
+    /// Request the latest state snapshot from peers (used for fast sync).
+    SnapshotRequest {
+        /// Genesis hash for chain identity validation.
+        genesis_hash: Hash,
+        /// Random nonce to ensure retries are treated as unique messages.
+        nonce: u64,
+    },
+    /// Offer of a state snapshot, with the block it is anchored to.
+    SnapshotOffer {
+        /// The snapshot manifest.
+        manifest: Box<SnapshotManifest>,
+        /// The anchor block.
+        block: Box<WeaveBlock>,
+        /// Genesis hash for chain identity validation.
+        genesis_hash: Hash,
+    },
+    /// Request a snapshot chunk by its content hash.
+    SnapshotChunkRequest {
+        /// BLAKE3 hash of the requested chunk.
+        chunk_hash: Hash,
+    },
+    /// A snapshot chunk.
+    SnapshotChunk {
+        /// BLAKE3 hash of the chunk.
+        chunk_hash: Hash,
+        /// Chunk contents.
+        data: Vec<u8>,
+    },
+    /// Evidence of validator misbehavior.
+    SlashingEvidence(Box<SlashingEvidence>),
+    /// A spindle market operation (bond/unbond/subscribe/unsubscribe).
+    SpindleOperation(SpindleOperation),
+    /// The thread ranges a spindle watches.
+    SpindleCoverage(SpindleCoverage),
+    /// A block header with commitment short IDs (compact block relay).
+    CompactBlock(Box<CompactBlock>),
+    /// Request the commitments of a compact block by position.
+    GetBlockCommitments {
+        /// Hash of the block.
+        block_hash: Hash,
+        /// Positions of the requested commitments in the block.
+        indexes: Vec<u32>,
+    },
+    /// Commitments of a block, answering `GetBlockCommitments`.
+    BlockCommitments {
+        /// Hash of the block.
+        block_hash: Hash,
+        /// The requested commitments, in the order requested. Empty if the
+        /// peer does not know the block.
+        commitments: Vec<CommitmentUpdate>,
+    },
+    /// A full-state thread checkpoint signed by the thread owner.
+    Checkpoint(Box<ThreadCheckpoint>),
+    /// Request the latest checkpoint a key signed for a thread.
+    GetCheckpoint {
+        /// The checkpointed thread.
+        thread_id: ThreadId,
+        /// The owner key the checkpoint must be signed by.
+        owner: PublicKey,
+    },
+    /// The latest known checkpoint, answering `GetCheckpoint`.
+    CheckpointResponse {
+        /// The checkpointed thread.
+        thread_id: ThreadId,
+        /// The checkpoint, or `None` if the peer has none for that owner.
+        checkpoint: Option<Box<ThreadCheckpoint>>,
+    },
+    /// A token metadata update (NT-1 metadata).
+    TokenMetadataUpdate(Box<TokenMetadataUpdate>),
 }
 
 impl NornMessage {
@@ -302,6 +473,21 @@ impl NornMessage {
             NornMessage::FaucetCredit(_) => 21,
             NornMessage::NameTransfer(_) => 22,
             NornMessage::NameRecordUpdate(_) => 23,
+            NornMessage::OperatorHandover(_) => 24,
+            NornMessage::SnapshotRequest { .. } => 25,
+            NornMessage::SnapshotOffer { .. } => 26,
+            NornMessage::SnapshotChunkRequest { .. } => 27,
+            NornMessage::SnapshotChunk { .. } => 28,
+            NornMessage::SlashingEvidence(_) => 29,
+            NornMessage::SpindleOperation(_) => 30,
+            NornMessage::SpindleCoverage(_) => 31,
+            NornMessage::CompactBlock(_) => 32,
+            NornMessage::GetBlockCommitments { .. } => 33,
+            NornMessage::BlockCommitments { .. } => 34,
+            NornMessage::Checkpoint(_) => 35,
+            NornMessage::GetCheckpoint { .. } => 36,
+            NornMessage::CheckpointResponse { .. } => 37,
+            NornMessage::TokenMetadataUpdate(_) => 38,
         }
     }
 }
@@ -352,5 +538,9 @@ mod tests {
             nonce: 0,
         };
         assert_eq!(msg.discriminant(), 11);
+        let msg = NornMessage::SnapshotChunkRequest {
+            chunk_hash: [0u8; 32],
+        };
+        assert_eq!(msg.discriminant(), 27);
     }
 }
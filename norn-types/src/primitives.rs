@@ -10,6 +10,12 @@ pub type PublicKey = [u8; 32];
 /// 64-byte Ed25519 signature.
 pub type Signature = [u8; 64];
 
+/// 48-byte compressed BLS12-381 public key (G1).
+pub type BlsPublicKey = [u8; 48];
+
+/// 96-byte compressed BLS12-381 signature (G2).
+pub type BlsSignature = [u8; 96];
+
 /// 20-byte address derived from BLAKE3(pubkey)[0..20].
 pub type Address = [u8; 20];
 
@@ -85,6 +91,48 @@ pub mod serde_sig_vec {
     }
 }
 
+/// Serde helper for [u8; 48] BLS public key fields.
+pub mod serde_bls_pubkey {
+    use serde::{self, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S>(value: &[u8; 48], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        value.as_slice().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<[u8; 48], D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let v: Vec<u8> = Vec::deserialize(deserializer)?;
+        v.try_into()
+            .map_err(|_| serde::de::Error::custom("expected 48 bytes for BLS public key"))
+    }
+}
+
+/// Serde helper for [u8; 96] BLS signature fields.
+pub mod serde_bls_sig {
+    use serde::{self, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S>(value: &[u8; 96], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        value.as_slice().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<[u8; 96], D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let v: Vec<u8> = Vec::deserialize(deserializer)?;
+        v.try_into()
+            .map_err(|_| serde::de::Error::custom("expected 96 bytes for BLS signature"))
+    }
+}
+
 /// Derive a 20-byte contract address from a 32-byte loom ID.
 ///
 /// Uses blake3 hash of the loom_id, truncated to 20 bytes. This gives each
@@ -96,6 +144,21 @@ pub fn derive_contract_address(loom_id: &LoomId) -> Address {
     addr
 }
 
+/// Derive a 20-byte sub-address of a loom from a contract-chosen salt.
+///
+/// Lets a contract custody funds for each deal or pool under a distinct,
+/// auditable address. The domain prefix keeps sub-addresses disjoint from
+/// the contract's own address.
+pub fn derive_sub_address(loom_id: &LoomId, salt: &[u8]) -> Address {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(b"norn_sub_address");
+    hasher.update(loom_id);
+    hasher.update(salt);
+    let mut addr = [0u8; 20];
+    addr.copy_from_slice(&hasher.finalize().as_bytes()[..20]);
+    addr
+}
+
 /// A signed amount that can represent debits (negative) and credits (positive).
 #[derive(
     Debug, Clone, Copy, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
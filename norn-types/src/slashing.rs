@@ -0,0 +1,68 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::consensus::Vote;
+use crate::primitives::*;
+use crate::weave::BlockHeader;
+
+/// Evidence that a validator signed two conflicting messages, included in
+/// weave blocks so every node slashes the offender at the same height.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub enum SlashingEvidence {
+    /// Two consensus votes by the same validator for different blocks in the
+    /// same view.
+    DoubleVote {
+        /// First vote.
+        vote_a: Box<Vote>,
+        /// Conflicting vote in the same view.
+        vote_b: Box<Vote>,
+    },
+
+    /// The same validator's signature on two different blocks at the same
+    /// height.
+    DoubleSign {
+        /// The validator that signed both headers.
+        validator: PublicKey,
+        /// First signed header.
+        header_a: Box<BlockHeader>,
+        /// Conflicting header at the same height.
+        header_b: Box<BlockHeader>,
+    },
+}
+
+impl SlashingEvidence {
+    /// The validator the evidence accuses.
+    pub fn offender(&self) -> &PublicKey {
+        match self {
+            SlashingEvidence::DoubleVote { vote_a, .. } => &vote_a.voter,
+            SlashingEvidence::DoubleSign { validator, .. } => validator,
+        }
+    }
+}
+
+/// Why a validator was slashed.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub enum SlashReason {
+    /// Signed conflicting votes or blocks.
+    DoubleSign,
+    /// Stopped proposing blocks for too long.
+    Downtime,
+}
+
+/// A slashing applied to a validator.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct SlashRecord {
+    /// The slashed validator.
+    pub validator: PublicKey,
+    /// Why it was slashed.
+    pub reason: SlashReason,
+    /// Stake burned, including the validator's delegators' share.
+    pub amount: Amount,
+    /// Block height at which the slashing was applied.
+    pub height: u64,
+    /// Height until which the validator is jailed, or `None` if it can never
+    /// rejoin.
+    pub jailed_until: Option<u64>,
+}
@@ -0,0 +1,25 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::primitives::*;
+use crate::weave::ValidatorSignature;
+
+/// Describes a state snapshot used for fast sync.
+///
+/// A snapshot captures the node state committed to by the `state_root` of the
+/// block at `height`, i.e. the state before that block is applied. The
+/// serialized state is split into chunks that are addressed by their BLAKE3
+/// hash, so each chunk can be fetched from any peer and checked on arrival.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    /// Height of the block the snapshot is anchored to.
+    pub height: u64,
+    /// Hash of the anchor block.
+    pub block_hash: Hash,
+    /// State root of the snapshot, equal to the anchor block's state root.
+    pub state_root: Hash,
+    /// Content hashes of the chunks, in order.
+    pub chunk_hashes: Vec<Hash>,
+    /// Validator signatures over the manifest digest.
+    pub signatures: Vec<ValidatorSignature>,
+}
@@ -0,0 +1,140 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::primitives::*;
+
+/// An operation on the spindle subscription market, included in weave blocks
+/// so every node moves bonds and fees at the same height.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub enum SpindleOperation {
+    /// Post or top up a spindle's bond and set the fee it charges per period.
+    Bond {
+        /// Spindle public key.
+        spindle: PublicKey,
+        /// Amount added to the bond (may be zero to only change the fee).
+        amount: Amount,
+        /// Fee charged to each subscriber per fee period.
+        fee_per_period: Amount,
+        /// Timestamp.
+        timestamp: Timestamp,
+        /// Signature by the spindle.
+        #[serde(with = "crate::primitives::serde_sig")]
+        signature: Signature,
+    },
+    /// Withdraw part of a spindle's bond.
+    Unbond {
+        /// Spindle public key.
+        spindle: PublicKey,
+        /// Amount to withdraw.
+        amount: Amount,
+        /// Timestamp.
+        timestamp: Timestamp,
+        /// Signature by the spindle.
+        #[serde(with = "crate::primitives::serde_sig")]
+        signature: Signature,
+    },
+    /// Subscribe the subscriber's thread to a spindle, prepaying `periods`
+    /// fee periods at the spindle's current fee.
+    Subscribe {
+        /// Subscriber public key. The watched thread is its address.
+        subscriber: PublicKey,
+        /// Spindle public key.
+        spindle: PublicKey,
+        /// Number of fee periods to prepay.
+        periods: u64,
+        /// Highest fee per period the subscriber agrees to pay.
+        max_fee_per_period: Amount,
+        /// Timestamp.
+        timestamp: Timestamp,
+        /// Signature by the subscriber.
+        #[serde(with = "crate::primitives::serde_sig")]
+        signature: Signature,
+    },
+    /// Cancel a subscription, refunding the periods not yet paid out.
+    Unsubscribe {
+        /// Subscriber public key.
+        subscriber: PublicKey,
+        /// Spindle public key.
+        spindle: PublicKey,
+        /// Timestamp.
+        timestamp: Timestamp,
+        /// Signature by the subscriber.
+        #[serde(with = "crate::primitives::serde_sig")]
+        signature: Signature,
+    },
+}
+
+impl SpindleOperation {
+    /// The key that must sign the operation.
+    pub fn signer(&self) -> &PublicKey {
+        match self {
+            SpindleOperation::Bond { spindle, .. } | SpindleOperation::Unbond { spindle, .. } => {
+                spindle
+            }
+            SpindleOperation::Subscribe { subscriber, .. }
+            | SpindleOperation::Unsubscribe { subscriber, .. } => subscriber,
+        }
+    }
+
+    /// The operation's signature.
+    pub fn signature(&self) -> &Signature {
+        match self {
+            SpindleOperation::Bond { signature, .. }
+            | SpindleOperation::Unbond { signature, .. }
+            | SpindleOperation::Subscribe { signature, .. }
+            | SpindleOperation::Unsubscribe { signature, .. } => signature,
+        }
+    }
+}
+
+/// A spindle's bond on the subscription market.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct SpindleBond {
+    /// Spindle public key.
+    pub spindle: PublicKey,
+    /// Bonded amount, slashable if the spindle misses fraud against a
+    /// subscriber.
+    pub amount: Amount,
+    /// Fee charged to new subscribers per fee period.
+    pub fee_per_period: Amount,
+}
+
+/// A thread's paid subscription to a spindle.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct SpindleSubscription {
+    /// Subscriber public key.
+    pub subscriber: PublicKey,
+    /// The watched thread (the subscriber's address).
+    pub thread_id: ThreadId,
+    /// Spindle public key.
+    pub spindle: PublicKey,
+    /// Fee per period locked in when subscribing.
+    pub fee_per_period: Amount,
+    /// Block height at which the subscription started.
+    pub start_height: u64,
+    /// Number of prepaid periods.
+    pub periods: u64,
+    /// Number of periods already paid out to the spindle.
+    pub periods_paid: u64,
+}
+
+impl SpindleSubscription {
+    /// Block height at which the last prepaid period ends.
+    pub fn end_height(&self, period_blocks: u64) -> u64 {
+        self.start_height
+            .saturating_add(self.periods.saturating_mul(period_blocks))
+    }
+}
+
+/// A spindle bond slashed for failing to report fraud against a subscriber.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct SpindleSlash {
+    /// The slashed spindle.
+    pub spindle: PublicKey,
+    /// The subscriber's thread the fraud was against.
+    pub thread_id: ThreadId,
+    /// Bond paid to the subscriber as compensation.
+    pub amount: Amount,
+    /// Block height of the block that included the fraud proof.
+    pub height: u64,
+}
@@ -95,3 +95,75 @@ impl Default for ThreadState {
         Self::new()
     }
 }
+
+/// Guardian keys that may jointly recover a thread whose owner key is lost
+/// or compromised.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct GuardianSet {
+    /// The guardians' public keys (at most MAX_GUARDIANS, no duplicates).
+    pub guardians: Vec<PublicKey>,
+    /// Number of distinct guardian approvals a recovery needs.
+    pub threshold: u8,
+}
+
+/// Keys that must co-sign every knot of a thread besides its owner, so that
+/// no single device can move the thread's funds.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct CosignerSet {
+    /// The cosigners' public keys (at most MAX_COSIGNERS, no duplicates).
+    pub cosigners: Vec<PublicKey>,
+    /// Number of distinct cosigner signatures each knot needs.
+    pub threshold: u8,
+}
+
+/// The keys that currently control a thread.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct ThreadKeys {
+    /// The key that signs for the thread. Starts as the key its ID derives
+    /// from and changes with each rotation or recovery.
+    pub owner: PublicKey,
+    /// Registered guardians, if any.
+    pub guardians: Option<GuardianSet>,
+    /// Registered cosigners, if any.
+    pub cosigners: Option<CosignerSet>,
+    /// Number of key updates applied so far. Each key update names the epoch
+    /// it applies to, so it cannot be replayed.
+    pub epoch: u64,
+}
+
+impl ThreadKeys {
+    /// Keys of a thread that has never updated them.
+    pub fn new(owner: PublicKey) -> Self {
+        Self {
+            owner,
+            guardians: None,
+            cosigners: None,
+            epoch: 0,
+        }
+    }
+}
+
+/// A full snapshot of a thread's state, signed by its owner. Knot chains
+/// and fraud proofs can start from a checkpoint instead of replaying the
+/// thread's whole history. Checkpoints chain to each other, so each one
+/// only covers the knots since the previous.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct ThreadCheckpoint {
+    /// The checkpointed thread.
+    pub thread_id: ThreadId,
+    /// The owner key that signed the checkpoint.
+    pub owner: PublicKey,
+    /// Thread version at the checkpoint.
+    pub version: Version,
+    /// The thread's full state at `version`.
+    pub state: ThreadState,
+    /// ID of the last knot covered by the checkpoint (zeros if none).
+    pub last_knot_hash: Hash,
+    /// Hash of the previous checkpoint (zeros for the first).
+    pub prev_checkpoint_hash: Hash,
+    /// Timestamp of the checkpoint.
+    pub timestamp: Timestamp,
+    /// Signature by the owner over all other fields.
+    #[serde(with = "crate::primitives::serde_sig")]
+    pub signature: Signature,
+}
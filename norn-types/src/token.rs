@@ -1,6 +1,7 @@
 use crate::constants::ONE_NORN;
 use crate::error::NornError;
 use crate::primitives::{Amount, TokenId};
+use crate::weave::TokenMetadata;
 
 /// Fee for creating a token (10 NORN, burned).
 pub const TOKEN_CREATION_FEE: Amount = 10 * ONE_NORN;
@@ -14,6 +15,21 @@ pub const MAX_TOKEN_SYMBOL_LEN: usize = 12;
 /// Maximum decimals for a token.
 pub const MAX_TOKEN_DECIMALS: u8 = 18;
 
+/// Maximum length of a token description, in bytes.
+pub const MAX_TOKEN_DESCRIPTION_LEN: usize = 280;
+
+/// Maximum length of a token icon URI or project URL.
+pub const MAX_TOKEN_URI_LEN: usize = 256;
+
+/// Maximum number of tags on a token.
+pub const MAX_TOKEN_TAGS: usize = 8;
+
+/// Maximum length of a token tag.
+pub const MAX_TOKEN_TAG_LEN: usize = 32;
+
+/// URI schemes accepted for token icons.
+pub const TOKEN_ICON_SCHEMES: &[&str] = &["https://", "ipfs://", "ar://"];
+
 /// Validate a token symbol: uppercase alphanumeric, 1-12 chars.
 pub fn validate_token_symbol(symbol: &str) -> Result<(), NornError> {
     if symbol.is_empty() || symbol.len() > MAX_TOKEN_SYMBOL_LEN {
@@ -50,6 +66,85 @@ pub fn validate_token_name(name: &str) -> Result<(), NornError> {
     Ok(())
 }
 
+/// Validate token metadata: a description of at most 280 bytes without
+/// control characters, an https/ipfs/ar icon URI, an http(s) project URL,
+/// and at most 8 distinct lowercase tags (`a-z`, `0-9`, `-`) of 1-32 chars.
+pub fn validate_token_metadata(metadata: &TokenMetadata) -> Result<(), NornError> {
+    if let Some(description) = &metadata.description {
+        if description.len() > MAX_TOKEN_DESCRIPTION_LEN {
+            return Err(NornError::InvalidTokenDefinition(format!(
+                "description must be at most {MAX_TOKEN_DESCRIPTION_LEN} bytes, got {}",
+                description.len()
+            )));
+        }
+        if description.chars().any(|c| c.is_control() && c != '\n') {
+            return Err(NornError::InvalidTokenDefinition(
+                "description must not contain control characters".to_string(),
+            ));
+        }
+    }
+    if let Some(icon_uri) = &metadata.icon_uri {
+        validate_token_uri("icon URI", icon_uri, TOKEN_ICON_SCHEMES)?;
+    }
+    if let Some(project_url) = &metadata.project_url {
+        validate_token_uri("project URL", project_url, &["https://", "http://"])?;
+    }
+    if metadata.tags.len() > MAX_TOKEN_TAGS {
+        return Err(NornError::InvalidTokenDefinition(format!(
+            "at most {MAX_TOKEN_TAGS} tags allowed, got {}",
+            metadata.tags.len()
+        )));
+    }
+    for (i, tag) in metadata.tags.iter().enumerate() {
+        if tag.is_empty() || tag.len() > MAX_TOKEN_TAG_LEN {
+            return Err(NornError::InvalidTokenDefinition(format!(
+                "tag must be 1-{MAX_TOKEN_TAG_LEN} characters, got {}",
+                tag.len()
+            )));
+        }
+        if let Some(c) = tag
+            .chars()
+            .find(|c| !c.is_ascii_lowercase() && !c.is_ascii_digit() && *c != '-')
+        {
+            return Err(NornError::InvalidTokenDefinition(format!(
+                "tag must be lowercase alphanumeric or '-', found '{c}'"
+            )));
+        }
+        if metadata.tags[..i].contains(tag) {
+            return Err(NornError::InvalidTokenDefinition(format!(
+                "duplicate tag '{tag}'"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Validate a URI in token metadata: printable ASCII without spaces, one of
+/// the given schemes, and something after the scheme.
+fn validate_token_uri(field: &str, uri: &str, schemes: &[&str]) -> Result<(), NornError> {
+    if uri.len() > MAX_TOKEN_URI_LEN {
+        return Err(NornError::InvalidTokenDefinition(format!(
+            "{field} must be at most {MAX_TOKEN_URI_LEN} characters, got {}",
+            uri.len()
+        )));
+    }
+    if !uri.chars().all(|c| c.is_ascii_graphic()) {
+        return Err(NornError::InvalidTokenDefinition(format!(
+            "{field} must be printable ASCII without spaces"
+        )));
+    }
+    match schemes.iter().find(|s| uri.starts_with(*s)) {
+        Some(scheme) if uri.len() > scheme.len() => Ok(()),
+        Some(_) => Err(NornError::InvalidTokenDefinition(format!(
+            "{field} is missing a location after the scheme"
+        ))),
+        None => Err(NornError::InvalidTokenDefinition(format!(
+            "{field} must start with one of: {}",
+            schemes.join(", ")
+        ))),
+    }
+}
+
 /// Compute the deterministic token ID from a token definition's fields.
 ///
 /// Each variable-length field (name, symbol) is length-prefixed with its
@@ -113,6 +208,39 @@ mod tests {
         assert!(validate_token_name(&long_name).is_err()); // too long
     }
 
+    #[test]
+    fn test_validate_token_metadata_valid() {
+        assert!(validate_token_metadata(&TokenMetadata::default()).is_ok());
+        let metadata = TokenMetadata {
+            description: Some("A stablecoin.\nBacked 1:1.".to_string()),
+            icon_uri: Some(
+                "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_string(),
+            ),
+            project_url: Some("https://example.com".to_string()),
+            tags: vec!["stablecoin".to_string(), "usd-backed".to_string()],
+        };
+        assert!(validate_token_metadata(&metadata).is_ok());
+    }
+
+    #[test]
+    fn test_validate_token_metadata_invalid() {
+        let with = |f: fn(&mut TokenMetadata)| {
+            let mut metadata = TokenMetadata::default();
+            f(&mut metadata);
+            validate_token_metadata(&metadata)
+        };
+        assert!(with(|m| m.description = Some("A".repeat(281))).is_err());
+        assert!(with(|m| m.description = Some("bell\u{7}".to_string())).is_err());
+        assert!(with(|m| m.icon_uri = Some("http://example.com/a.png".to_string())).is_err());
+        assert!(with(|m| m.icon_uri = Some("ipfs://".to_string())).is_err());
+        assert!(with(|m| m.project_url = Some("https://exa mple.com".to_string())).is_err());
+        assert!(with(|m| m.project_url = Some(format!("https://{}", "a".repeat(250)))).is_err());
+        assert!(with(|m| m.tags = vec!["DeFi".to_string()]).is_err());
+        assert!(with(|m| m.tags = vec![String::new()]).is_err());
+        assert!(with(|m| m.tags = vec!["defi".to_string(), "defi".to_string()]).is_err());
+        assert!(with(|m| m.tags = (0..9).map(|i| format!("tag{i}")).collect()).is_err());
+    }
+
     #[test]
     fn test_compute_token_id_deterministic() {
         let creator = [1u8; 20];
@@ -1,9 +1,13 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
 
+use crate::consensus::QuorumCertificate;
 use crate::fraud::FraudProofSubmission;
-use crate::loom::LoomRegistration;
+use crate::knot::Knot;
+use crate::loom::{LoomRegistration, OperatorHandover};
 use crate::primitives::*;
+use crate::slashing::SlashingEvidence;
+use crate::spindle::SpindleOperation;
 
 /// A commitment update submitted by a thread to the weave.
 #[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
@@ -22,6 +26,9 @@ pub struct CommitmentUpdate {
     pub knot_count: u64,
     /// Timestamp of this commitment.
     pub timestamp: Timestamp,
+    /// Priority fee (tip) paid to the block proposer on top of the burned
+    /// base fee. Block producers include the highest tips first.
+    pub priority_fee: Amount,
     /// Signature by the thread owner.
     #[serde(with = "crate::primitives::serde_sig")]
     pub signature: Signature,
@@ -54,9 +61,9 @@ pub struct LoomAnchor {
     pub block_height: u64,
     /// Timestamp of this anchor.
     pub timestamp: Timestamp,
-    /// Signature by the loom operator.
-    #[serde(with = "crate::primitives::serde_sig")]
-    pub signature: Signature,
+    /// Operator signatures over the state update; at least the loom's
+    /// operator threshold must be valid.
+    pub signatures: Vec<crate::loom::OperatorSignature>,
 }
 
 /// A name registration on the weave.
@@ -159,6 +166,45 @@ pub struct TokenMint {
     pub signature: Signature,
 }
 
+/// Descriptive metadata of a token, rendered by wallets and explorers.
+///
+/// Limits are checked by [`crate::token::validate_token_metadata`].
+#[derive(
+    Debug, Clone, Default, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct TokenMetadata {
+    /// Short description of the token.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// URI of the token's icon (https, ipfs or ar).
+    #[serde(default)]
+    pub icon_uri: Option<String>,
+    /// URL of the project behind the token.
+    #[serde(default)]
+    pub project_url: Option<String>,
+    /// Lowercase tags (e.g. "stablecoin", "governance").
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// A token metadata update — replaces a token's metadata (creator-only).
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct TokenMetadataUpdate {
+    /// The token to update.
+    pub token_id: TokenId,
+    /// The new metadata, replacing the current one as a whole.
+    pub metadata: TokenMetadata,
+    /// Authority (must be token creator).
+    pub authority: Address,
+    /// Authority's public key.
+    pub authority_pubkey: PublicKey,
+    /// Timestamp. Must be later than the token's last metadata update.
+    pub timestamp: Timestamp,
+    /// Signature by the authority.
+    #[serde(with = "crate::primitives::serde_sig")]
+    pub signature: Signature,
+}
+
 /// A token burn operation — destroys tokens from the burner's balance.
 #[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
 pub struct TokenBurn {
@@ -204,6 +250,9 @@ pub struct ValidatorSignature {
 pub struct Validator {
     /// Validator's public key.
     pub pubkey: PublicKey,
+    /// Validator's BLS public key, which signs consensus votes.
+    #[serde(with = "crate::primitives::serde_bls_pubkey")]
+    pub bls_pubkey: BlsPublicKey,
     /// Validator's address.
     pub address: Address,
     /// Stake amount.
@@ -265,22 +314,218 @@ pub struct WeaveBlock {
     pub token_burns: Vec<TokenBurn>,
     /// Merkle root of all token burns in this block.
     pub token_burns_root: Hash,
+    /// Token metadata updates included in this block.
+    pub token_metadata_updates: Vec<TokenMetadataUpdate>,
+    /// Merkle root of all token metadata updates in this block.
+    pub token_metadata_updates_root: Hash,
     /// Loom deployments included in this block.
     pub loom_deploys: Vec<LoomRegistration>,
     /// Merkle root of all loom deployments in this block.
     pub loom_deploys_root: Hash,
+    /// Loom operator handovers included in this block.
+    pub operator_handovers: Vec<OperatorHandover>,
+    /// Merkle root of all operator handovers in this block.
+    pub operator_handovers_root: Hash,
+    /// Thread key updates (rotations, guardian changes, recoveries) included
+    /// in this block.
+    pub key_updates: Vec<Knot>,
+    /// Merkle root of all key updates in this block.
+    pub key_updates_root: Hash,
     /// Stake operations included in this block.
     pub stake_operations: Vec<StakeOperation>,
     /// Merkle root of all stake operations in this block.
     pub stake_operations_root: Hash,
+    /// Validator slashing evidence included in this block.
+    pub slashing_evidence: Vec<SlashingEvidence>,
+    /// Merkle root of all slashing evidence in this block.
+    pub slashing_evidence_root: Hash,
+    /// Spindle market operations included in this block.
+    pub spindle_operations: Vec<SpindleOperation>,
+    /// Merkle root of all spindle market operations in this block.
+    pub spindle_operations_root: Hash,
+    /// Base fee each commitment in this block pays, which is burned.
+    pub base_fee: Amount,
     /// Cumulative state root at this block height.
     pub state_root: Hash,
+    /// Hash of the validator set that certifies the next block
+    /// (see [`ValidatorSet::hash`]).
+    pub validator_set_hash: Hash,
     /// Block timestamp.
     pub timestamp: Timestamp,
     /// Block proposer's public key.
     pub proposer: PublicKey,
-    /// Validator signatures.
-    pub validator_signatures: Vec<ValidatorSignature>,
+    /// The proposer's signature over the block hash.
+    #[serde(with = "crate::primitives::serde_sig")]
+    pub proposer_signature: Signature,
+    /// Commit certificate for the block, aggregating the validators' BLS
+    /// signatures. `None` until the block is committed.
+    pub certificate: Option<QuorumCertificate>,
+}
+
+impl WeaveBlock {
+    /// The block's header: everything its hash commits to, with the bodies
+    /// replaced by their hashes.
+    pub fn header(&self) -> BlockHeader {
+        let mut body_hashes = Vec::with_capacity(18);
+        let mut push_body = |body: Result<Vec<u8>, std::io::Error>| {
+            if let Ok(bytes) = body {
+                body_hashes.push(*blake3::hash(&bytes).as_bytes());
+            }
+        };
+        push_body(borsh::to_vec(&self.commitments));
+        push_body(borsh::to_vec(&self.registrations));
+        push_body(borsh::to_vec(&self.anchors));
+        push_body(borsh::to_vec(&self.name_registrations));
+        push_body(borsh::to_vec(&self.name_transfers));
+        push_body(borsh::to_vec(&self.name_record_updates));
+        push_body(borsh::to_vec(&self.fraud_proofs));
+        push_body(borsh::to_vec(&self.transfers));
+        push_body(borsh::to_vec(&self.token_definitions));
+        push_body(borsh::to_vec(&self.token_mints));
+        push_body(borsh::to_vec(&self.token_burns));
+        push_body(borsh::to_vec(&self.loom_deploys));
+        push_body(borsh::to_vec(&self.stake_operations));
+        push_body(borsh::to_vec(&self.operator_handovers));
+        push_body(borsh::to_vec(&self.slashing_evidence));
+        push_body(borsh::to_vec(&self.spindle_operations));
+        push_body(borsh::to_vec(&self.key_updates));
+        push_body(borsh::to_vec(&self.token_metadata_updates));
+
+        BlockHeader {
+            height: self.height,
+            hash: self.hash,
+            prev_hash: self.prev_hash,
+            commitments_root: self.commitments_root,
+            registrations_root: self.registrations_root,
+            anchors_root: self.anchors_root,
+            name_registrations_root: self.name_registrations_root,
+            name_transfers_root: self.name_transfers_root,
+            name_record_updates_root: self.name_record_updates_root,
+            fraud_proofs_root: self.fraud_proofs_root,
+            transfers_root: self.transfers_root,
+            token_definitions_root: self.token_definitions_root,
+            token_mints_root: self.token_mints_root,
+            token_burns_root: self.token_burns_root,
+            token_metadata_updates_root: self.token_metadata_updates_root,
+            loom_deploys_root: self.loom_deploys_root,
+            operator_handovers_root: self.operator_handovers_root,
+            key_updates_root: self.key_updates_root,
+            stake_operations_root: self.stake_operations_root,
+            slashing_evidence_root: self.slashing_evidence_root,
+            spindle_operations_root: self.spindle_operations_root,
+            base_fee: self.base_fee,
+            state_root: self.state_root,
+            validator_set_hash: self.validator_set_hash,
+            timestamp: self.timestamp,
+            proposer: self.proposer,
+            body_hashes,
+            proposer_signature: self.proposer_signature,
+            certificate: self.certificate.clone(),
+        }
+    }
+}
+
+/// A weave block without its bodies: enough to check the block hash, its
+/// certificate, and proofs against its Merkle roots.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct BlockHeader {
+    /// Block height.
+    pub height: u64,
+    /// Hash of this block.
+    pub hash: Hash,
+    /// Hash of the previous block.
+    pub prev_hash: Hash,
+    /// Merkle root of all commitment updates in the block.
+    pub commitments_root: Hash,
+    /// Merkle root of all registrations in the block.
+    pub registrations_root: Hash,
+    /// Merkle root of all loom anchors in the block.
+    pub anchors_root: Hash,
+    /// Merkle root of all name registrations in the block.
+    pub name_registrations_root: Hash,
+    /// Merkle root of all name transfers in the block.
+    pub name_transfers_root: Hash,
+    /// Merkle root of all name record updates in the block.
+    pub name_record_updates_root: Hash,
+    /// Merkle root of all fraud proofs in the block.
+    pub fraud_proofs_root: Hash,
+    /// Merkle root of all transfers in the block.
+    pub transfers_root: Hash,
+    /// Merkle root of all token definitions in the block.
+    pub token_definitions_root: Hash,
+    /// Merkle root of all token mints in the block.
+    pub token_mints_root: Hash,
+    /// Merkle root of all token burns in the block.
+    pub token_burns_root: Hash,
+    /// Merkle root of all token metadata updates in the block.
+    pub token_metadata_updates_root: Hash,
+    /// Merkle root of all loom deployments in the block.
+    pub loom_deploys_root: Hash,
+    /// Merkle root of all operator handovers in the block.
+    pub operator_handovers_root: Hash,
+    /// Merkle root of all key updates in the block.
+    pub key_updates_root: Hash,
+    /// Merkle root of all stake operations in the block.
+    pub stake_operations_root: Hash,
+    /// Merkle root of all slashing evidence in the block.
+    pub slashing_evidence_root: Hash,
+    /// Merkle root of all spindle market operations in the block.
+    pub spindle_operations_root: Hash,
+    /// Base fee each commitment in the block pays.
+    pub base_fee: Amount,
+    /// Cumulative state root at this block height.
+    pub state_root: Hash,
+    /// Hash of the validator set that certifies the next block
+    /// (see [`ValidatorSet::hash`]).
+    pub validator_set_hash: Hash,
+    /// Block timestamp.
+    pub timestamp: Timestamp,
+    /// Block proposer's public key.
+    pub proposer: PublicKey,
+    /// BLAKE3 hashes of the borsh-encoded block bodies, in the order the
+    /// block hash includes them.
+    pub body_hashes: Vec<Hash>,
+    /// The proposer's signature over the block hash.
+    #[serde(with = "crate::primitives::serde_sig")]
+    pub proposer_signature: Signature,
+    /// Commit certificate for the block.
+    pub certificate: Option<QuorumCertificate>,
+}
+
+impl BlockHeader {
+    /// Recompute the block hash from the header fields.
+    pub fn compute_hash(&self) -> Hash {
+        let mut data = Vec::with_capacity(32 + 32 * (22 + self.body_hashes.len()));
+        data.extend_from_slice(&self.height.to_le_bytes());
+        data.extend_from_slice(&self.prev_hash);
+        data.extend_from_slice(&self.commitments_root);
+        data.extend_from_slice(&self.registrations_root);
+        data.extend_from_slice(&self.anchors_root);
+        data.extend_from_slice(&self.name_registrations_root);
+        data.extend_from_slice(&self.name_transfers_root);
+        data.extend_from_slice(&self.name_record_updates_root);
+        data.extend_from_slice(&self.fraud_proofs_root);
+        data.extend_from_slice(&self.transfers_root);
+        data.extend_from_slice(&self.token_definitions_root);
+        data.extend_from_slice(&self.token_mints_root);
+        data.extend_from_slice(&self.token_burns_root);
+        data.extend_from_slice(&self.token_metadata_updates_root);
+        data.extend_from_slice(&self.loom_deploys_root);
+        data.extend_from_slice(&self.operator_handovers_root);
+        data.extend_from_slice(&self.key_updates_root);
+        data.extend_from_slice(&self.stake_operations_root);
+        data.extend_from_slice(&self.slashing_evidence_root);
+        data.extend_from_slice(&self.spindle_operations_root);
+        data.extend_from_slice(&self.base_fee.to_le_bytes());
+        data.extend_from_slice(&self.state_root);
+        data.extend_from_slice(&self.validator_set_hash);
+        data.extend_from_slice(&self.timestamp.to_le_bytes());
+        data.extend_from_slice(&self.proposer);
+        for body_hash in &self.body_hashes {
+            data.extend_from_slice(body_hash);
+        }
+        *blake3::hash(&data).as_bytes()
+    }
 }
 
 /// Global weave state tracking.
@@ -354,6 +599,14 @@ impl ValidatorSet {
         2 * self.max_faults() + 1
     }
 
+    /// The hash block headers commit to for this set:
+    /// `blake3_domain("norn-validator-set", borsh(self))`.
+    pub fn hash(&self) -> Hash {
+        let mut hasher = blake3::Hasher::new_derive_key("norn-validator-set");
+        hasher.update(&borsh::to_vec(self).unwrap_or_default());
+        *hasher.finalize().as_bytes()
+    }
+
     /// Check if a public key is in the validator set.
     pub fn contains(&self, pubkey: &PublicKey) -> bool {
         self.validators.iter().any(|v| v.pubkey == *pubkey)
@@ -363,6 +616,12 @@ impl ValidatorSet {
     pub fn get(&self, pubkey: &PublicKey) -> Option<&Validator> {
         self.validators.iter().find(|v| v.pubkey == *pubkey)
     }
+
+    /// Position of a validator in the set, as used by quorum certificate
+    /// signer bitmaps.
+    pub fn index_of(&self, pubkey: &PublicKey) -> Option<usize> {
+        self.validators.iter().position(|v| v.pubkey == *pubkey)
+    }
 }
 
 /// A staking operation.
@@ -372,6 +631,12 @@ pub enum StakeOperation {
     Stake {
         /// Validator public key.
         pubkey: PublicKey,
+        /// Validator BLS public key for consensus votes.
+        #[serde(with = "crate::primitives::serde_bls_pubkey")]
+        bls_pubkey: BlsPublicKey,
+        /// Proof of possession of the BLS secret key.
+        #[serde(with = "crate::primitives::serde_bls_sig")]
+        bls_pop: BlsSignature,
         /// Amount to stake.
         amount: Amount,
         /// Timestamp.
@@ -392,4 +657,92 @@ pub enum StakeOperation {
         #[serde(with = "crate::primitives::serde_sig")]
         signature: Signature,
     },
+    /// Delegate tokens to a validator.
+    Delegate {
+        /// Delegator public key.
+        delegator: PublicKey,
+        /// Validator public key.
+        validator: PublicKey,
+        /// Amount to delegate.
+        amount: Amount,
+        /// Timestamp.
+        timestamp: Timestamp,
+        /// Signature by the delegator.
+        #[serde(with = "crate::primitives::serde_sig")]
+        signature: Signature,
+    },
+    /// Withdraw delegated tokens (subject to bonding period).
+    Undelegate {
+        /// Delegator public key.
+        delegator: PublicKey,
+        /// Validator public key.
+        validator: PublicKey,
+        /// Amount to undelegate.
+        amount: Amount,
+        /// Timestamp.
+        timestamp: Timestamp,
+        /// Signature by the delegator.
+        #[serde(with = "crate::primitives::serde_sig")]
+        signature: Signature,
+    },
+    /// Set the commission a validator takes from delegators' rewards.
+    SetCommission {
+        /// Validator public key.
+        pubkey: PublicKey,
+        /// Commission in basis points (at most 10,000).
+        commission_bps: u16,
+        /// Timestamp.
+        timestamp: Timestamp,
+        /// Signature by the validator.
+        #[serde(with = "crate::primitives::serde_sig")]
+        signature: Signature,
+    },
+    /// Release a validator from jail once its jail period is over.
+    Unjail {
+        /// Validator public key.
+        pubkey: PublicKey,
+        /// Timestamp.
+        timestamp: Timestamp,
+        /// Signature by the validator.
+        #[serde(with = "crate::primitives::serde_sig")]
+        signature: Signature,
+    },
+}
+
+impl StakeOperation {
+    /// The key that signs this operation.
+    pub fn signer(&self) -> &PublicKey {
+        match self {
+            StakeOperation::Stake { pubkey, .. }
+            | StakeOperation::Unstake { pubkey, .. }
+            | StakeOperation::SetCommission { pubkey, .. }
+            | StakeOperation::Unjail { pubkey, .. } => pubkey,
+            StakeOperation::Delegate { delegator, .. }
+            | StakeOperation::Undelegate { delegator, .. } => delegator,
+        }
+    }
+
+    /// The operation's timestamp.
+    pub fn timestamp(&self) -> Timestamp {
+        match self {
+            StakeOperation::Stake { timestamp, .. }
+            | StakeOperation::Unstake { timestamp, .. }
+            | StakeOperation::Delegate { timestamp, .. }
+            | StakeOperation::Undelegate { timestamp, .. }
+            | StakeOperation::SetCommission { timestamp, .. }
+            | StakeOperation::Unjail { timestamp, .. } => *timestamp,
+        }
+    }
+
+    /// The signer's signature over the operation.
+    pub fn signature(&self) -> &Signature {
+        match self {
+            StakeOperation::Stake { signature, .. }
+            | StakeOperation::Unstake { signature, .. }
+            | StakeOperation::Delegate { signature, .. }
+            | StakeOperation::Undelegate { signature, .. }
+            | StakeOperation::SetCommission { signature, .. }
+            | StakeOperation::Unjail { signature, .. } => signature,
+        }
+    }
 }
@@ -1,11 +1,14 @@
 use borsh::BorshSerialize;
 
+use norn_crypto::bls::{verify_commit_certificate, BlsKeypair};
 use norn_crypto::hash::blake3_hash;
-use norn_crypto::keys::{batch_verify, Keypair};
+use norn_crypto::keys::{verify, Keypair};
 use norn_crypto::merkle::SparseMerkleTree;
+use norn_types::consensus::{signer_bitmap, vote_signing_data, ConsensusPhase, QuorumCertificate};
 use norn_types::constants::MAX_COMMITMENTS_PER_BLOCK;
+use norn_types::error::NornError;
 use norn_types::primitives::*;
-use norn_types::weave::{ValidatorSet, ValidatorSignature, WeaveBlock};
+use norn_types::weave::{ValidatorSet, WeaveBlock};
 
 use crate::error::WeaveError;
 use crate::mempool::BlockContents;
@@ -13,7 +16,11 @@ use crate::mempool::BlockContents;
 /// Build a weave block from the given contents.
 ///
 /// Computes Merkle roots for each content category, produces the block hash,
-/// and signs the block with the proposer's keypair.
+/// and signs the block with the proposer's keypair. `validator_set_hash`
+/// commits to the validator set that certifies the next block, and
+/// `base_fee` to the fee each of its commitments pays. The block carries no
+/// commit certificate until consensus commits it.
+#[allow(clippy::too_many_arguments)]
 pub fn build_block(
     prev_hash: Hash,
     prev_height: u64,
@@ -21,6 +28,8 @@ pub fn build_block(
     proposer_keypair: &Keypair,
     timestamp: Timestamp,
     state_root: Hash,
+    validator_set_hash: Hash,
+    base_fee: Amount,
 ) -> WeaveBlock {
     let commitments_root = compute_merkle_root_borsh(&contents.commitments);
     let registrations_root = compute_merkle_root_borsh(&contents.registrations);
@@ -33,8 +42,13 @@ pub fn build_block(
     let token_definitions_root = compute_merkle_root_borsh(&contents.token_definitions);
     let token_mints_root = compute_merkle_root_borsh(&contents.token_mints);
     let token_burns_root = compute_merkle_root_borsh(&contents.token_burns);
+    let token_metadata_updates_root = compute_merkle_root_borsh(&contents.token_metadata_updates);
     let loom_deploys_root = compute_merkle_root_borsh(&contents.loom_deploys);
+    let operator_handovers_root = compute_merkle_root_borsh(&contents.operator_handovers);
+    let key_updates_root = compute_merkle_root_borsh(&contents.key_updates);
     let stake_operations_root = compute_merkle_root_borsh(&contents.stake_operations);
+    let slashing_evidence_root = compute_merkle_root_borsh(&contents.slashing_evidence);
+    let spindle_operations_root = compute_merkle_root_borsh(&contents.spindle_operations);
 
     let mut block = WeaveBlock {
         height: prev_height + 1,
@@ -62,103 +76,76 @@ pub fn build_block(
         token_mints_root,
         token_burns: contents.token_burns,
         token_burns_root,
+        token_metadata_updates: contents.token_metadata_updates,
+        token_metadata_updates_root,
         loom_deploys: contents.loom_deploys,
         loom_deploys_root,
+        operator_handovers: contents.operator_handovers,
+        operator_handovers_root,
+        key_updates: contents.key_updates,
+        key_updates_root,
         stake_operations: contents.stake_operations,
         stake_operations_root,
+        slashing_evidence: contents.slashing_evidence,
+        slashing_evidence_root,
+        spindle_operations: contents.spindle_operations,
+        spindle_operations_root,
+        base_fee,
         state_root,
+        validator_set_hash,
         timestamp,
         proposer: proposer_keypair.public_key(),
-        validator_signatures: Vec::new(),
+        proposer_signature: [0u8; 64],
+        certificate: None,
     };
 
     block.hash = compute_block_hash(&block);
 
     // The proposer signs the block hash.
-    let sig = proposer_keypair.sign(&block.hash);
-    block.validator_signatures.push(ValidatorSignature {
-        validator: proposer_keypair.public_key(),
-        signature: sig,
-    });
+    block.proposer_signature = proposer_keypair.sign(&block.hash);
 
     block
 }
 
-/// Compute a deterministic block hash from all fields except the hash itself
-/// and the validator signatures.
-pub fn compute_block_hash(block: &WeaveBlock) -> Hash {
-    let mut data = Vec::new();
-    data.extend_from_slice(&block.height.to_le_bytes());
-    data.extend_from_slice(&block.prev_hash);
-    data.extend_from_slice(&block.commitments_root);
-    data.extend_from_slice(&block.registrations_root);
-    data.extend_from_slice(&block.anchors_root);
-    data.extend_from_slice(&block.name_registrations_root);
-    data.extend_from_slice(&block.name_transfers_root);
-    data.extend_from_slice(&block.name_record_updates_root);
-    data.extend_from_slice(&block.fraud_proofs_root);
-    data.extend_from_slice(&block.transfers_root);
-    data.extend_from_slice(&block.token_definitions_root);
-    data.extend_from_slice(&block.token_mints_root);
-    data.extend_from_slice(&block.token_burns_root);
-    data.extend_from_slice(&block.loom_deploys_root);
-    data.extend_from_slice(&block.stake_operations_root);
-    data.extend_from_slice(&block.state_root);
-    data.extend_from_slice(&block.timestamp.to_le_bytes());
-    data.extend_from_slice(&block.proposer);
-
-    // Include content hashes for determinism.
-    if let Ok(c_bytes) = borsh::to_vec(&block.commitments) {
-        data.extend_from_slice(&blake3_hash(&c_bytes));
-    }
-    if let Ok(r_bytes) = borsh::to_vec(&block.registrations) {
-        data.extend_from_slice(&blake3_hash(&r_bytes));
-    }
-    if let Ok(a_bytes) = borsh::to_vec(&block.anchors) {
-        data.extend_from_slice(&blake3_hash(&a_bytes));
-    }
-    if let Ok(nr_bytes) = borsh::to_vec(&block.name_registrations) {
-        data.extend_from_slice(&blake3_hash(&nr_bytes));
-    }
-    if let Ok(nt_bytes) = borsh::to_vec(&block.name_transfers) {
-        data.extend_from_slice(&blake3_hash(&nt_bytes));
-    }
-    if let Ok(nru_bytes) = borsh::to_vec(&block.name_record_updates) {
-        data.extend_from_slice(&blake3_hash(&nru_bytes));
-    }
-    if let Ok(f_bytes) = borsh::to_vec(&block.fraud_proofs) {
-        data.extend_from_slice(&blake3_hash(&f_bytes));
-    }
-    if let Ok(t_bytes) = borsh::to_vec(&block.transfers) {
-        data.extend_from_slice(&blake3_hash(&t_bytes));
-    }
-    if let Ok(td_bytes) = borsh::to_vec(&block.token_definitions) {
-        data.extend_from_slice(&blake3_hash(&td_bytes));
-    }
-    if let Ok(tm_bytes) = borsh::to_vec(&block.token_mints) {
-        data.extend_from_slice(&blake3_hash(&tm_bytes));
-    }
-    if let Ok(tb_bytes) = borsh::to_vec(&block.token_burns) {
-        data.extend_from_slice(&blake3_hash(&tb_bytes));
-    }
-    if let Ok(ld_bytes) = borsh::to_vec(&block.loom_deploys) {
-        data.extend_from_slice(&blake3_hash(&ld_bytes));
-    }
-    if let Ok(so_bytes) = borsh::to_vec(&block.stake_operations) {
-        data.extend_from_slice(&blake3_hash(&so_bytes));
-    }
+/// Certify a block with the proposer's commit vote alone, for a validator
+/// set in which the proposer is a quorum by itself (solo mode). Leaves the
+/// block uncertified if the proposer is not in `validator_set`.
+pub fn self_certify(
+    block: &mut WeaveBlock,
+    bls_keypair: &BlsKeypair,
+    validator_set: &ValidatorSet,
+) {
+    let Some(index) = validator_set.index_of(&block.proposer) else {
+        return;
+    };
+    block.certificate = Some(QuorumCertificate {
+        view: 0,
+        block_hash: block.hash,
+        phase: ConsensusPhase::Commit,
+        signers: signer_bitmap([index], validator_set.len()),
+        signature: bls_keypair.sign(&vote_signing_data(ConsensusPhase::Commit, 0, &block.hash)),
+    });
+}
 
-    blake3_hash(&data)
+/// Compute a deterministic block hash from all fields except the hash itself,
+/// the proposer signature, and the commit certificate.
+pub fn compute_block_hash(block: &WeaveBlock) -> Hash {
+    block.header().compute_hash()
 }
 
-/// Verify a block's hash, proposer membership, Merkle roots, and validator signatures.
+/// Verify a block's hash, proposer membership, Merkle roots, proposer
+/// signature, and commit certificate.
 pub fn verify_block(block: &WeaveBlock, validator_set: &ValidatorSet) -> Result<(), WeaveError> {
     // 0. Reject oversized blocks — enforce per-category limits.
     const MAX_REGISTRATIONS: usize = 1_000;
     const MAX_TRANSFERS: usize = 10_000;
     const MAX_TOKEN_OPS: usize = 1_000;
     const MAX_LOOM_DEPLOYS: usize = 100;
+    const MAX_OPERATOR_HANDOVERS: usize = 100;
+    const MAX_KEY_UPDATES: usize = 1_000;
     const MAX_STAKE_OPS: usize = 100;
+    const MAX_SLASHING_EVIDENCE: usize = 100;
+    const MAX_SPINDLE_OPS: usize = 1_000;
     const MAX_NAME_REGS: usize = 1_000;
 
     if block.commitments.len() > MAX_COMMITMENTS_PER_BLOCK {
@@ -178,8 +165,13 @@ pub fn verify_block(block: &WeaveBlock, validator_set: &ValidatorSet) -> Result<
         || block.token_definitions.len() > MAX_TOKEN_OPS
         || block.token_mints.len() > MAX_TOKEN_OPS
         || block.token_burns.len() > MAX_TOKEN_OPS
+        || block.token_metadata_updates.len() > MAX_TOKEN_OPS
         || block.loom_deploys.len() > MAX_LOOM_DEPLOYS
+        || block.operator_handovers.len() > MAX_OPERATOR_HANDOVERS
+        || block.key_updates.len() > MAX_KEY_UPDATES
         || block.stake_operations.len() > MAX_STAKE_OPS
+        || block.slashing_evidence.len() > MAX_SLASHING_EVIDENCE
+        || block.spindle_operations.len() > MAX_SPINDLE_OPS
         || block.name_registrations.len() > MAX_NAME_REGS
         || block.name_transfers.len() > MAX_NAME_TRANSFERS
         || block.name_record_updates.len() > MAX_NAME_RECORD_UPDATES
@@ -282,6 +274,14 @@ pub fn verify_block(block: &WeaveBlock, validator_set: &ValidatorSet) -> Result<
         });
     }
 
+    let expected_token_metadata_updates_root =
+        compute_merkle_root_borsh(&block.token_metadata_updates);
+    if block.token_metadata_updates_root != expected_token_metadata_updates_root {
+        return Err(WeaveError::InvalidBlock {
+            reason: "token metadata updates merkle root mismatch".to_string(),
+        });
+    }
+
     let expected_loom_deploys_root = compute_merkle_root_borsh(&block.loom_deploys);
     if block.loom_deploys_root != expected_loom_deploys_root {
         return Err(WeaveError::InvalidBlock {
@@ -289,6 +289,20 @@ pub fn verify_block(block: &WeaveBlock, validator_set: &ValidatorSet) -> Result<
         });
     }
 
+    let expected_operator_handovers_root = compute_merkle_root_borsh(&block.operator_handovers);
+    if block.operator_handovers_root != expected_operator_handovers_root {
+        return Err(WeaveError::InvalidBlock {
+            reason: "operator handovers merkle root mismatch".to_string(),
+        });
+    }
+
+    let expected_key_updates_root = compute_merkle_root_borsh(&block.key_updates);
+    if block.key_updates_root != expected_key_updates_root {
+        return Err(WeaveError::InvalidBlock {
+            reason: "key updates merkle root mismatch".to_string(),
+        });
+    }
+
     let expected_stake_operations_root = compute_merkle_root_borsh(&block.stake_operations);
     if block.stake_operations_root != expected_stake_operations_root {
         return Err(WeaveError::InvalidBlock {
@@ -296,34 +310,40 @@ pub fn verify_block(block: &WeaveBlock, validator_set: &ValidatorSet) -> Result<
         });
     }
 
-    // 4. Verify validator signatures (need at least quorum_size) using batch verification.
-    let quorum = validator_set.quorum_size();
-
-    // Filter to only signatures from known validators.
-    let valid_entries: Vec<_> = block
-        .validator_signatures
-        .iter()
-        .filter(|vs| validator_set.contains(&vs.validator))
-        .collect();
-
-    if valid_entries.len() < quorum {
-        return Err(WeaveError::InsufficientQuorum {
-            have: valid_entries.len(),
-            need: quorum,
+    let expected_slashing_evidence_root = compute_merkle_root_borsh(&block.slashing_evidence);
+    if block.slashing_evidence_root != expected_slashing_evidence_root {
+        return Err(WeaveError::InvalidBlock {
+            reason: "slashing evidence merkle root mismatch".to_string(),
         });
     }
 
-    // Use batch verification for all validator signatures at once.
-    let messages: Vec<&[u8]> = valid_entries
-        .iter()
-        .map(|_| block.hash.as_slice())
-        .collect();
-    let signatures: Vec<_> = valid_entries.iter().map(|vs| vs.signature).collect();
-    let pubkeys: Vec<_> = valid_entries.iter().map(|vs| vs.validator).collect();
+    let expected_spindle_operations_root = compute_merkle_root_borsh(&block.spindle_operations);
+    if block.spindle_operations_root != expected_spindle_operations_root {
+        return Err(WeaveError::InvalidBlock {
+            reason: "spindle operations merkle root mismatch".to_string(),
+        });
+    }
 
-    batch_verify(&messages, &signatures, &pubkeys).map_err(|_| WeaveError::InsufficientQuorum {
-        have: 0,
-        need: quorum,
+    // 4. Verify the proposer signature and the commit certificate, whose
+    // aggregate signature stands in for a quorum of validator signatures.
+    if verify(&block.hash, &block.proposer_signature, &block.proposer).is_err() {
+        return Err(WeaveError::InvalidBlock {
+            reason: "invalid proposer signature".to_string(),
+        });
+    }
+    let certificate = block
+        .certificate
+        .as_ref()
+        .ok_or_else(|| WeaveError::InvalidBlock {
+            reason: "block has no commit certificate".to_string(),
+        })?;
+    verify_commit_certificate(certificate, &block.hash, validator_set).map_err(|e| match e {
+        NornError::InsufficientQuorum { have, need } => {
+            WeaveError::InsufficientQuorum { have, need }
+        }
+        e => WeaveError::InvalidBlock {
+            reason: e.to_string(),
+        },
     })?;
 
     Ok(())
@@ -352,6 +372,7 @@ mod tests {
             .iter()
             .map(|kp| Validator {
                 pubkey: kp.public_key(),
+                bls_pubkey: BlsKeypair::from_keypair(kp).public_key(),
                 address: [0u8; 20],
                 stake: 1000,
                 active: true,
@@ -380,21 +401,85 @@ mod tests {
             token_definitions: vec![],
             token_mints: vec![],
             token_burns: vec![],
+            token_metadata_updates: vec![],
             loom_deploys: vec![],
+            operator_handovers: vec![],
+            key_updates: vec![],
             stake_operations: vec![],
+            slashing_evidence: vec![],
+            spindle_operations: vec![],
         };
 
-        let block = build_block([0u8; 32], 0, contents, &kp, 1000, [0u8; 32]);
+        let block = build_block([0u8; 32], 0, contents, &kp, 1000, [0u8; 32], [0u8; 32], 100);
 
         assert_eq!(block.height, 1);
         assert_ne!(block.hash, [0u8; 32]);
         assert_eq!(block.proposer, kp.public_key());
-        assert_eq!(block.validator_signatures.len(), 1);
+        assert!(block.certificate.is_none());
 
         let vs = make_validator_set(&[&kp]);
+        assert!(verify_block(&block, &vs).is_err());
+        let mut block = block;
+        self_certify(&mut block, &BlsKeypair::from_keypair(&kp), &vs);
         assert!(verify_block(&block, &vs).is_ok());
     }
 
+    #[test]
+    fn test_verify_requires_quorum_certificate() {
+        use norn_crypto::bls::aggregate_votes;
+        use norn_types::consensus::Vote;
+
+        let keypairs: Vec<Keypair> = (0..4).map(|_| Keypair::generate()).collect();
+        let vs = make_validator_set(&keypairs.iter().collect::<Vec<_>>());
+        let mut block = build_block(
+            [0u8; 32],
+            0,
+            crate::mempool::BlockContents::default(),
+            &keypairs[0],
+            1000,
+            [0u8; 32],
+            [0u8; 32],
+            100,
+        );
+        let hash = block.hash;
+        let certify = |signers: &[Keypair]| {
+            let signing_data = vote_signing_data(ConsensusPhase::Commit, 3, &hash);
+            let votes: Vec<Vote> = signers
+                .iter()
+                .map(|kp| Vote {
+                    view: 3,
+                    phase: ConsensusPhase::Commit,
+                    block_hash: hash,
+                    voter: kp.public_key(),
+                    signature: kp.sign(&signing_data),
+                    bls_signature: BlsKeypair::from_keypair(kp).sign(&signing_data),
+                })
+                .collect();
+            aggregate_votes(ConsensusPhase::Commit, &votes, &vs).unwrap()
+        };
+
+        // The proposer alone is not a quorum of four.
+        self_certify(&mut block, &BlsKeypair::from_keypair(&keypairs[0]), &vs);
+        assert!(matches!(
+            verify_block(&block, &vs),
+            Err(WeaveError::InsufficientQuorum { have: 1, need: 3 })
+        ));
+
+        block.certificate = Some(certify(&keypairs[1..]));
+        assert!(verify_block(&block, &vs).is_ok());
+
+        // A prepare certificate does not commit the block.
+        let mut prepare = certify(&keypairs[1..]);
+        prepare.phase = ConsensusPhase::Prepare;
+        block.certificate = Some(prepare);
+        assert!(verify_block(&block, &vs).is_err());
+
+        // Nor does the proposer signature of another validator.
+        block.certificate = Some(certify(&keypairs[1..]));
+        block.proposer_signature = keypairs[1].sign(&block.hash);
+        assert!(verify_block(&block, &vs).is_err());
+    }
+
     #[test]
     fn test_block_hash_deterministic() {
         let kp = Keypair::generate();
@@ -410,14 +495,24 @@ mod tests {
             token_definitions: vec![],
             token_mints: vec![],
             token_burns: vec![],
+            token_metadata_updates: vec![],
             loom_deploys: vec![],
+            operator_handovers: vec![],
+            key_updates: vec![],
             stake_operations: vec![],
+            slashing_evidence: vec![],
+            spindle_operations: vec![],
         };
-        let block = build_block([0u8; 32], 0, contents, &kp, 1000, [0u8; 32]);
+        let block = build_block([0u8; 32], 0, contents, &kp, 1000, [0u8; 32], [0u8; 32], 100);
 
         let hash1 = compute_block_hash(&block);
         let hash2 = compute_block_hash(&block);
         assert_eq!(hash1, hash2);
+
+        // The header alone is enough to recompute the hash.
+        let header: norn_types::weave::BlockHeader =
+            borsh::from_slice(&borsh::to_vec(&block.header()).unwrap()).unwrap();
+        assert_eq!(header.compute_hash(), block.hash);
     }
 
     #[test]
@@ -435,10 +530,15 @@ mod tests {
             token_definitions: vec![],
             token_mints: vec![],
             token_burns: vec![],
+            token_metadata_updates: vec![],
             loom_deploys: vec![],
+            operator_handovers: vec![],
+            key_updates: vec![],
             stake_operations: vec![],
+            slashing_evidence: vec![],
+            spindle_operations: vec![],
         };
-        let mut block = build_block([0u8; 32], 0, contents, &kp, 1000, [0u8; 32]);
+        let mut block = build_block([0u8; 32], 0, contents, &kp, 1000, [0u8; 32], [0u8; 32], 100);
         block.hash[0] ^= 0xff;
 
         let vs = make_validator_set(&[&kp]);
@@ -461,10 +561,15 @@ mod tests {
             token_definitions: vec![],
             token_mints: vec![],
             token_burns: vec![],
+            token_metadata_updates: vec![],
             loom_deploys: vec![],
+            operator_handovers: vec![],
+            key_updates: vec![],
             stake_operations: vec![],
+            slashing_evidence: vec![],
+            spindle_operations: vec![],
         };
-        let block = build_block([0u8; 32], 0, contents, &kp, 1000, [0u8; 32]);
+        let block = build_block([0u8; 32], 0, contents, &kp, 1000, [0u8; 32], [0u8; 32], 100);
 
         // Validator set only has other_kp.
         let vs = make_validator_set(&[&other_kp]);
@@ -483,6 +588,7 @@ mod tests {
             prev_commitment_hash: [0u8; 32],
             knot_count: 1,
             timestamp: 1000,
+            priority_fee: 0,
             signature: [0u8; 64],
         };
         let contents = crate::mempool::BlockContents {
@@ -497,10 +603,15 @@ mod tests {
             token_definitions: vec![],
             token_mints: vec![],
             token_burns: vec![],
+            token_metadata_updates: vec![],
             loom_deploys: vec![],
+            operator_handovers: vec![],
+            key_updates: vec![],
             stake_operations: vec![],
+            slashing_evidence: vec![],
+            spindle_operations: vec![],
         };
-        let block = build_block([0u8; 32], 0, contents, &kp, 1000, [0u8; 32]);
+        let block = build_block([0u8; 32], 0, contents, &kp, 1000, [0u8; 32], [0u8; 32], 100);
 
         // The commitments root should not be the empty hash.
         assert_ne!(block.commitments_root, [0u8; 32]);
@@ -522,10 +633,15 @@ mod tests {
             token_definitions: vec![],
             token_mints: vec![],
             token_burns: vec![],
+            token_metadata_updates: vec![],
             loom_deploys: vec![],
+            operator_handovers: vec![],
+            key_updates: vec![],
             stake_operations: vec![],
+            slashing_evidence: vec![],
+            spindle_operations: vec![],
         };
-        let mut block = build_block([0u8; 32], 0, contents, &kp, 1000, [0u8; 32]);
+        let mut block = build_block([0u8; 32], 0, contents, &kp, 1000, [0u8; 32], [0u8; 32], 100);
         let vs = make_validator_set(&[&kp]);
 
         // Inject more commitments than allowed directly into the block.
@@ -538,6 +654,7 @@ mod tests {
                 prev_commitment_hash: [0u8; 32],
                 knot_count: 0,
                 timestamp: 0,
+                priority_fee: 0,
                 signature: [0u8; 64],
             })
             .collect();
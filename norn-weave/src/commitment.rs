@@ -5,15 +5,19 @@ use norn_crypto::merkle::SparseMerkleTree;
 use norn_types::constants::{MAX_COMMITMENT_AGE, MAX_TIMESTAMP_DRIFT};
 use norn_types::primitives::*;
 use norn_types::weave::{CommitmentUpdate, WeaveState};
+use rayon::prelude::*;
 
 use crate::error::WeaveError;
 
 /// Validate a commitment update.
 ///
-/// Checks: signature validity, version monotonicity, and staleness.
+/// Checks: signature validity, ownership, version monotonicity, and
+/// staleness. `owner` is the thread's owner key if it has been rotated;
+/// otherwise the signing key must derive the thread ID.
 pub fn validate_commitment(
     commitment: &CommitmentUpdate,
     current_version: Option<Version>,
+    owner: Option<&PublicKey>,
     current_time: Timestamp,
 ) -> Result<(), WeaveError> {
     // Verify signature over all fields except the signature itself.
@@ -24,12 +28,21 @@ pub fn validate_commitment(
         }
     })?;
 
-    // Verify the owner pubkey actually derives the claimed thread_id.
-    let expected_address = pubkey_to_address(&commitment.owner);
-    if commitment.thread_id != expected_address {
-        return Err(WeaveError::InvalidCommitment {
-            reason: "owner pubkey does not derive thread_id".to_string(),
-        });
+    // Verify the signer owns the thread: its rotated owner key, or else the
+    // key that derives the claimed thread_id.
+    match owner {
+        Some(owner) if *owner != commitment.owner => {
+            return Err(WeaveError::InvalidCommitment {
+                reason: "owner pubkey is not the thread's owner key".to_string(),
+            });
+        }
+        Some(_) => {}
+        None if pubkey_to_address(&commitment.owner) != commitment.thread_id => {
+            return Err(WeaveError::InvalidCommitment {
+                reason: "owner pubkey does not derive thread_id".to_string(),
+            });
+        }
+        None => {}
     }
 
     // Check version monotonicity.
@@ -65,6 +78,31 @@ pub fn validate_commitment(
     Ok(())
 }
 
+/// Validate a batch of commitment updates in parallel.
+///
+/// Each commitment is checked with [`validate_commitment`] against the
+/// version `committed` and the rotated owner key `owner` report for its
+/// thread. Results are returned in input order, so callers see the same first
+/// failure regardless of scheduling.
+pub fn validate_commitments<F, G>(
+    commitments: &[CommitmentUpdate],
+    committed: F,
+    owner: G,
+    current_time: Timestamp,
+) -> Vec<Result<(), WeaveError>>
+where
+    F: Fn(&ThreadId) -> Option<Version> + Sync,
+    G: Fn(&ThreadId) -> Option<PublicKey> + Sync,
+{
+    commitments
+        .par_iter()
+        .map(|c| {
+            let owner = owner(&c.thread_id);
+            validate_commitment(c, committed(&c.thread_id), owner.as_ref(), current_time)
+        })
+        .collect()
+}
+
 /// Apply a validated commitment to the global weave state.
 ///
 /// Updates the threads Merkle tree and the threads_root in state.
@@ -89,8 +127,74 @@ pub fn apply_commitment(
     Ok(())
 }
 
+/// Apply a block's validated commitments to the global weave state.
+///
+/// Leaves are encoded in parallel and inserted in block order, so a thread
+/// committed twice ends at its later commitment, exactly as applying them one
+/// by one with [`apply_commitment`].
+pub fn apply_commitments(
+    state: &mut WeaveState,
+    merkle_tree: &mut SparseMerkleTree,
+    commitments: &[CommitmentUpdate],
+) -> Result<(), WeaveError> {
+    if commitments.is_empty() {
+        return Ok(());
+    }
+    let entries = commitments
+        .par_iter()
+        .map(|c| {
+            let value = borsh::to_vec(&(c.state_hash, c.version)).map_err(|e| {
+                WeaveError::InvalidCommitment {
+                    reason: format!("serialization error: {}", e),
+                }
+            })?;
+            Ok((blake3_hash(&c.thread_id), value))
+        })
+        .collect::<Result<Vec<_>, WeaveError>>()?;
+
+    merkle_tree.insert_batch(entries);
+    state.threads_root = merkle_tree.root();
+
+    Ok(())
+}
+
+/// The version `thread_id` last committed to in the threads tree, if any.
+pub fn committed_version(merkle_tree: &SparseMerkleTree, thread_id: &ThreadId) -> Option<Version> {
+    let bytes = merkle_tree.get(&blake3_hash(thread_id))?;
+    borsh::from_slice::<(Hash, Version)>(bytes)
+        .ok()
+        .map(|(_, version)| version)
+}
+
+/// Roll a thread's committed state forward to the latest state proven by a
+/// stale-commit fraud proof. Does nothing if the thread's recorded version is
+/// already at or past `restore.version`.
+pub fn restore_thread_state(
+    state: &mut WeaveState,
+    merkle_tree: &mut SparseMerkleTree,
+    restore: &crate::fraud::ThreadRestore,
+) -> Result<bool, WeaveError> {
+    let key = blake3_hash(&restore.thread_id);
+    if let Some(bytes) = merkle_tree.get(&key) {
+        if let Ok((_, version)) = borsh::from_slice::<(Hash, Version)>(bytes) {
+            if version >= restore.version {
+                return Ok(false);
+            }
+        }
+    }
+
+    let value = borsh::to_vec(&(restore.state_hash, restore.version)).map_err(|e| {
+        WeaveError::InvalidFraudProof {
+            reason: format!("serialization error: {}", e),
+        }
+    })?;
+    merkle_tree.insert(key, value);
+    state.threads_root = merkle_tree.root();
+    Ok(true)
+}
+
 /// Compute the data that should be signed for a commitment.
-fn commitment_signing_data(commitment: &CommitmentUpdate) -> Vec<u8> {
+pub fn commitment_signing_data(commitment: &CommitmentUpdate) -> Vec<u8> {
     let mut data = Vec::new();
     data.extend_from_slice(&commitment.thread_id);
     data.extend_from_slice(&commitment.owner);
@@ -99,6 +203,7 @@ fn commitment_signing_data(commitment: &CommitmentUpdate) -> Vec<u8> {
     data.extend_from_slice(&commitment.prev_commitment_hash);
     data.extend_from_slice(&commitment.knot_count.to_le_bytes());
     data.extend_from_slice(&commitment.timestamp.to_le_bytes());
+    data.extend_from_slice(&commitment.priority_fee.to_le_bytes());
     data
 }
 
@@ -123,6 +228,7 @@ mod tests {
             prev_commitment_hash: [0u8; 32],
             knot_count: 1,
             timestamp,
+            priority_fee: 0,
             signature: [0u8; 64],
         };
         let sig_data = commitment_signing_data(&c);
@@ -148,7 +254,7 @@ mod tests {
     fn test_valid_commitment() {
         let kp = Keypair::generate();
         let c = make_signed_commitment(&kp, 1, 1000);
-        assert!(validate_commitment(&c, None, 1000).is_ok());
+        assert!(validate_commitment(&c, None, None, 1000).is_ok());
     }
 
     #[test]
@@ -156,7 +262,12 @@ mod tests {
         let kp = Keypair::generate();
         let mut c = make_signed_commitment(&kp, 1, 1000);
         c.signature[0] ^= 0xff;
-        assert!(validate_commitment(&c, None, 1000).is_err());
+        assert!(validate_commitment(&c, None, None, 1000).is_err());
+
+        // The priority fee is signed, so a relayer cannot lower the tip.
+        let mut c = make_signed_commitment(&kp, 1, 1000);
+        c.priority_fee = 1;
+        assert!(validate_commitment(&c, None, None, 1000).is_err());
     }
 
     #[test]
@@ -164,9 +275,9 @@ mod tests {
         let kp = Keypair::generate();
         let c = make_signed_commitment(&kp, 1, 1000);
         // Current version 1, commitment version 1 => not strictly increasing.
-        assert!(validate_commitment(&c, Some(1), 1000).is_err());
+        assert!(validate_commitment(&c, Some(1), None, 1000).is_err());
         // Current version 0, commitment version 1 => ok.
-        assert!(validate_commitment(&c, Some(0), 1000).is_ok());
+        assert!(validate_commitment(&c, Some(0), None, 1000).is_ok());
     }
 
     #[test]
@@ -175,7 +286,7 @@ mod tests {
         let c = make_signed_commitment(&kp, 1, 1000);
         // Current time far in the future.
         let stale_time = 1000 + MAX_COMMITMENT_AGE + 1;
-        assert!(validate_commitment(&c, None, stale_time).is_err());
+        assert!(validate_commitment(&c, None, None, stale_time).is_err());
     }
 
     #[test]
@@ -192,11 +303,12 @@ mod tests {
             prev_commitment_hash: [0u8; 32],
             knot_count: 1,
             timestamp: 1000,
+            priority_fee: 0,
             signature: [0u8; 64],
         };
         let sig_data = commitment_signing_data(&c);
         c.signature = kp_a.sign(&sig_data);
-        let result = validate_commitment(&c, None, 1000);
+        let result = validate_commitment(&c, None, None, 1000);
         assert!(result.is_err());
         let err_msg = result.unwrap_err().to_string();
         assert!(
@@ -206,6 +318,74 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_rotated_owner_commitment() {
+        let original = Keypair::generate();
+        let rotated = Keypair::generate();
+        let thread_id = pubkey_to_address(&original.public_key());
+
+        // After rotation, the new key commits for the thread...
+        let mut c = make_signed_commitment(&original, 1, 1000);
+        c.owner = rotated.public_key();
+        c.signature = rotated.sign(&commitment_signing_data(&c));
+        assert_eq!(c.thread_id, thread_id);
+        assert!(validate_commitment(&c, None, None, 1000).is_err());
+        assert!(validate_commitment(&c, None, Some(&rotated.public_key()), 1000).is_ok());
+
+        // ...and the retired key no longer can.
+        let old = make_signed_commitment(&original, 1, 1000);
+        assert!(validate_commitment(&old, None, Some(&rotated.public_key()), 1000).is_err());
+    }
+
+    #[test]
+    fn test_validate_commitments_in_order() {
+        let keypairs: Vec<Keypair> = (0..8).map(|_| Keypair::generate()).collect();
+        let mut commitments: Vec<CommitmentUpdate> = keypairs
+            .iter()
+            .map(|kp| make_signed_commitment(kp, 2, 1000))
+            .collect();
+        commitments[5].signature[0] ^= 0xff;
+        let stale_thread = commitments[2].thread_id;
+
+        let results = validate_commitments(
+            &commitments,
+            |thread_id| (*thread_id == stale_thread).then_some(2),
+            |_| None,
+            1000,
+        );
+        let failed: Vec<usize> = results
+            .iter()
+            .enumerate()
+            .filter(|(_, r)| r.is_err())
+            .map(|(i, _)| i)
+            .collect();
+        assert_eq!(failed, vec![2, 5]);
+    }
+
+    #[test]
+    fn test_apply_commitments_matches_sequential() {
+        let kp = Keypair::generate();
+        let mut commitments: Vec<CommitmentUpdate> = (0..6)
+            .map(|_| make_signed_commitment(&Keypair::generate(), 1, 1000))
+            .collect();
+        // The same thread twice: the later commitment wins.
+        commitments.push(make_signed_commitment(&kp, 1, 1000));
+        commitments.push(make_signed_commitment(&kp, 2, 1000));
+
+        let mut seq_state = make_weave_state();
+        let mut seq_tree = SparseMerkleTree::new();
+        for c in &commitments {
+            apply_commitment(&mut seq_state, &mut seq_tree, c).unwrap();
+        }
+        let mut state = make_weave_state();
+        let mut tree = SparseMerkleTree::new();
+        apply_commitments(&mut state, &mut tree, &commitments).unwrap();
+
+        assert_eq!(state.threads_root, seq_state.threads_root);
+        let thread_id = pubkey_to_address(&kp.public_key());
+        assert_eq!(committed_version(&tree, &thread_id), Some(2));
+    }
+
     #[test]
     fn test_apply_commitment() {
         let kp = Keypair::generate();
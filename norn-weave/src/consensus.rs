@@ -1,12 +1,18 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::Duration;
 
+use norn_crypto::bls::{self, BlsKeypair};
 use norn_crypto::hash::blake3_hash;
 use norn_crypto::keys::{verify, Keypair};
 use norn_types::consensus::*;
+use norn_types::constants::MAX_VIEW_TIMEOUT_DOUBLINGS;
 use norn_types::primitives::*;
+use norn_types::slashing::SlashingEvidence;
 use norn_types::weave::ValidatorSet;
 
+use crate::error::WeaveError;
 use crate::leader::LeaderRotation;
+use crate::wal::{ConsensusRecord, ConsensusWal};
 
 /// Actions that the HotStuff engine requests the outer layer to perform.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -15,8 +21,8 @@ pub enum ConsensusAction {
     Broadcast(ConsensusMessage),
     /// Send a consensus message to a specific validator.
     SendTo(PublicKey, ConsensusMessage),
-    /// Commit a block with the given hash.
-    CommitBlock(Hash),
+    /// Commit the block certified by the given commit QC.
+    CommitBlock(QuorumCertificate),
     /// Request a view change (timeout occurred).
     RequestViewChange,
 }
@@ -24,6 +30,7 @@ pub enum ConsensusAction {
 /// 3-phase HotStuff consensus engine (pure state machine).
 pub struct HotStuffEngine {
     keypair: Keypair,
+    bls_keypair: BlsKeypair,
     my_key: PublicKey,
     validator_set: ValidatorSet,
     leader_rotation: LeaderRotation,
@@ -33,13 +40,22 @@ pub struct HotStuffEngine {
     prepare_votes: HashMap<Hash, Vec<Vote>>,
     precommit_votes: HashMap<Hash, Vec<Vote>>,
     commit_votes: HashMap<Hash, Vec<Vote>>,
-    /// Timeout tracking.
-    timeout_votes: Vec<TimeoutVote>,
+    /// Timeout votes for the current view and later ones, keeping only
+    /// each validator's latest.
+    timeout_votes: BTreeMap<u64, Vec<TimeoutVote>>,
+    /// Local timeouts since the last committed block.
+    consecutive_timeouts: u32,
     /// QCs.
     prepare_qc: Option<QuorumCertificate>,
     locked_qc: Option<QuorumCertificate>,
     /// Pending block hash for the current view.
     pending_block_hash: Option<Hash>,
+    /// Double votes seen while collecting votes, not yet taken.
+    evidence: Vec<SlashingEvidence>,
+    /// The latest view this validator voted in, and the block it voted for.
+    last_vote: Option<(u64, Hash)>,
+    /// Where consensus state is persisted, if anywhere.
+    wal: Option<ConsensusWal>,
 }
 
 impl HotStuffEngine {
@@ -51,6 +67,7 @@ impl HotStuffEngine {
         let leader_rotation = LeaderRotation::new(validators);
 
         Self {
+            bls_keypair: BlsKeypair::from_keypair(&keypair),
             keypair,
             my_key,
             validator_set,
@@ -60,13 +77,39 @@ impl HotStuffEngine {
             prepare_votes: HashMap::new(),
             precommit_votes: HashMap::new(),
             commit_votes: HashMap::new(),
-            timeout_votes: Vec::new(),
+            timeout_votes: BTreeMap::new(),
+            consecutive_timeouts: 0,
             prepare_qc: None,
             locked_qc: None,
             pending_block_hash: None,
+            evidence: Vec::new(),
+            last_vote: None,
+            wal: None,
         }
     }
 
+    /// Persist consensus state to `wal`, first restoring whatever it already
+    /// holds. After a restart the engine resumes in the view it was in and
+    /// never votes for a different block in a view it already voted in.
+    pub fn attach_wal(&mut self, wal: ConsensusWal) -> Result<(), WeaveError> {
+        if let Some(record) = wal.load()? {
+            tracing::info!(
+                view = record.view,
+                last_vote_view = record.last_vote.map(|(view, _)| view),
+                "restored consensus state"
+            );
+            if record.view > self.current_view {
+                self.enter_view(record.view);
+            }
+            self.last_vote = record.last_vote;
+            self.prepare_qc = record.prepare_qc;
+            self.locked_qc = record.locked_qc;
+        }
+        self.wal = Some(wal);
+        self.persist()?;
+        Ok(())
+    }
+
     /// Update the validator set (e.g., after staking changes).
     pub fn update_validator_set(&mut self, new_vs: ValidatorSet) {
         let validators: Vec<PublicKey> = new_vs.validators.iter().map(|v| v.pubkey).collect();
@@ -79,11 +122,28 @@ impl HotStuffEngine {
         self.current_view
     }
 
+    /// How long to wait in the current view before timing out: `base`,
+    /// doubled for each consecutive timeout since the last committed block,
+    /// up to `MAX_VIEW_TIMEOUT_DOUBLINGS` times.
+    pub fn view_timeout(&self, base: Duration) -> Duration {
+        base * 2u32.pow(self.consecutive_timeouts.min(MAX_VIEW_TIMEOUT_DOUBLINGS))
+    }
+
+    /// Get the validator set votes are counted against.
+    pub fn validator_set(&self) -> &ValidatorSet {
+        &self.validator_set
+    }
+
     /// Get the leader rotation.
     pub fn leader_rotation(&self) -> &LeaderRotation {
         &self.leader_rotation
     }
 
+    /// Take the slashing evidence collected since the last call.
+    pub fn take_evidence(&mut self) -> Vec<SlashingEvidence> {
+        std::mem::take(&mut self.evidence)
+    }
+
     /// Check if this node is the leader for the current view.
     pub fn is_leader(&self) -> bool {
         self.leader_rotation
@@ -100,6 +160,12 @@ impl HotStuffEngine {
         if !self.is_leader() {
             return vec![];
         }
+        // Proposing is a vote; after a restart mid-view the leader may
+        // already have proposed a different block.
+        let Some(own_vote) = self.sign_vote(ConsensusPhase::Prepare, self.current_view, block_hash)
+        else {
+            return vec![];
+        };
 
         self.pending_block_hash = Some(block_hash);
         self.phase = ConsensusPhase::Prepare;
@@ -115,7 +181,6 @@ impl HotStuffEngine {
 
         // Leader also casts its own PrepareVote (a node doesn't receive its own
         // gossipsub broadcasts, so we must process the vote locally).
-        let own_vote = self.make_vote(self.current_view, block_hash, ConsensusPhase::Prepare);
         actions.extend(self.handle_prepare_vote(own_vote));
 
         actions
@@ -156,8 +221,15 @@ impl HotStuffEngine {
         }
     }
 
-    /// Handle a timeout event.
+    /// Handle a timeout event. Called again while the view is stuck, this
+    /// re-broadcasts the timeout vote for validators that missed it.
     pub fn on_timeout(&mut self) -> Vec<ConsensusAction> {
+        self.consecutive_timeouts = self.consecutive_timeouts.saturating_add(1);
+        self.cast_timeout_vote()
+    }
+
+    /// Sign, broadcast, and locally record a timeout vote for the current view.
+    fn cast_timeout_vote(&mut self) -> Vec<ConsensusAction> {
         let highest_qc_view = self
             .locked_qc
             .as_ref()
@@ -185,15 +257,67 @@ impl HotStuffEngine {
         actions
     }
 
+    /// Record slashing evidence if the voter already voted for a different
+    /// block in this view. An honest validator votes for one block per view,
+    /// so two such votes conflict whatever their phases.
+    fn check_double_vote(&mut self, vote: &Vote) {
+        let earlier = self
+            .prepare_votes
+            .iter()
+            .chain(&self.precommit_votes)
+            .chain(&self.commit_votes)
+            .filter(|(hash, _)| **hash != vote.block_hash)
+            .flat_map(|(_, votes)| votes)
+            .find(|v| v.voter == vote.voter)
+            .cloned();
+        let Some(earlier) = earlier else {
+            return;
+        };
+        if self.evidence.iter().any(|e| *e.offender() == vote.voter) {
+            return;
+        }
+        self.evidence.push(SlashingEvidence::DoubleVote {
+            vote_a: Box::new(earlier),
+            vote_b: Box::new(vote.clone()),
+        });
+    }
+
     /// Advance to the next view and reset per-view state.
     fn advance_view(&mut self) {
-        self.current_view += 1;
+        self.enter_view(self.current_view + 1);
+    }
+
+    /// Move to `view` and reset per-view state. Timeout votes for `view` and
+    /// later views are kept.
+    fn enter_view(&mut self, view: u64) {
+        self.current_view = view;
         self.phase = ConsensusPhase::Prepare;
         self.prepare_votes.clear();
         self.precommit_votes.clear();
         self.commit_votes.clear();
-        self.timeout_votes.clear();
+        self.timeout_votes = self.timeout_votes.split_off(&view);
         self.pending_block_hash = None;
+        if let Err(e) = self.persist() {
+            tracing::warn!("failed to persist consensus view: {}", e);
+        }
+    }
+
+    /// Catch up with a leader message for a later view. Its certificate
+    /// proves a quorum reached that view, so a lagging replica can join it.
+    /// Returns whether the message is for the (possibly new) current view.
+    fn sync_to_certified_view(&mut self, view: u64, qc: &QuorumCertificate) -> bool {
+        if view < self.current_view || qc.view != view || !self.verify_qc(qc) {
+            return false;
+        }
+        if view > self.current_view {
+            tracing::debug!(
+                from = self.current_view,
+                to = view,
+                "catching up to certified view"
+            );
+            self.enter_view(view);
+        }
+        true
     }
 
     // ─── Message Handlers ───────────────────────────────────────────────────
@@ -212,10 +336,11 @@ impl HotStuffEngine {
             return vec![];
         }
 
-        self.pending_block_hash = Some(block_hash);
-
         // Vote PrepareVote.
-        let vote = self.make_vote(view, block_hash, ConsensusPhase::Prepare);
+        let Some(vote) = self.sign_vote(ConsensusPhase::Prepare, view, block_hash) else {
+            return vec![];
+        };
+        self.pending_block_hash = Some(block_hash);
         let leader = match self.leader_rotation.leader_for_view(view) {
             Some(l) => *l,
             None => return vec![],
@@ -235,12 +360,11 @@ impl HotStuffEngine {
             return vec![];
         }
 
-        // Verify vote signature.
-        let sig_data = vote_signing_data(vote.view, &vote.block_hash);
-        if verify(&sig_data, &vote.signature, &vote.voter).is_err() {
+        if !self.verify_vote(&vote, ConsensusPhase::Prepare) {
             return vec![];
         }
 
+        self.check_double_vote(&vote);
         let block_hash = vote.block_hash;
         let votes = self.prepare_votes.entry(block_hash).or_default();
 
@@ -252,11 +376,9 @@ impl HotStuffEngine {
 
         if votes.len() >= self.validator_set.quorum_size() {
             // Create prepare QC.
-            let qc = QuorumCertificate {
-                view: self.current_view,
-                block_hash,
-                phase: ConsensusPhase::Prepare,
-                votes: votes.clone(),
+            let Ok(qc) = bls::aggregate_votes(ConsensusPhase::Prepare, votes, &self.validator_set)
+            else {
+                return vec![];
             };
             self.prepare_qc = Some(qc.clone());
             self.phase = ConsensusPhase::PreCommit;
@@ -268,8 +390,11 @@ impl HotStuffEngine {
             let mut actions = vec![ConsensusAction::Broadcast(msg)];
 
             // Leader also casts its own PreCommitVote (gossipsub self-delivery).
-            let own_vote = self.make_vote(self.current_view, block_hash, ConsensusPhase::PreCommit);
-            actions.extend(self.handle_precommit_vote(own_vote));
+            if let Some(own_vote) =
+                self.sign_vote(ConsensusPhase::PreCommit, self.current_view, block_hash)
+            {
+                actions.extend(self.handle_precommit_vote(own_vote));
+            }
 
             return actions;
         }
@@ -286,7 +411,9 @@ impl HotStuffEngine {
         if !self.leader_rotation.is_leader(view, &from) {
             return vec![];
         }
-        if view != self.current_view {
+        if prepare_qc.phase != ConsensusPhase::Prepare
+            || !self.sync_to_certified_view(view, &prepare_qc)
+        {
             return vec![];
         }
 
@@ -294,7 +421,9 @@ impl HotStuffEngine {
         self.prepare_qc = Some(prepare_qc);
 
         // Vote PreCommitVote.
-        let vote = self.make_vote(view, block_hash, ConsensusPhase::PreCommit);
+        let Some(vote) = self.sign_vote(ConsensusPhase::PreCommit, view, block_hash) else {
+            return vec![];
+        };
         let leader = match self.leader_rotation.leader_for_view(view) {
             Some(l) => *l,
             None => return vec![],
@@ -314,11 +443,11 @@ impl HotStuffEngine {
             return vec![];
         }
 
-        let sig_data = vote_signing_data(vote.view, &vote.block_hash);
-        if verify(&sig_data, &vote.signature, &vote.voter).is_err() {
+        if !self.verify_vote(&vote, ConsensusPhase::PreCommit) {
             return vec![];
         }
 
+        self.check_double_vote(&vote);
         let block_hash = vote.block_hash;
         let votes = self.precommit_votes.entry(block_hash).or_default();
 
@@ -329,11 +458,10 @@ impl HotStuffEngine {
 
         if votes.len() >= self.validator_set.quorum_size() {
             // Create precommit QC (this becomes the locked QC).
-            let qc = QuorumCertificate {
-                view: self.current_view,
-                block_hash,
-                phase: ConsensusPhase::PreCommit,
-                votes: votes.clone(),
+            let Ok(qc) =
+                bls::aggregate_votes(ConsensusPhase::PreCommit, votes, &self.validator_set)
+            else {
+                return vec![];
             };
             self.locked_qc = Some(qc.clone());
             self.phase = ConsensusPhase::Commit;
@@ -345,8 +473,11 @@ impl HotStuffEngine {
             let mut actions = vec![ConsensusAction::Broadcast(msg)];
 
             // Leader also casts its own CommitVote (gossipsub self-delivery).
-            let own_vote = self.make_vote(self.current_view, block_hash, ConsensusPhase::Commit);
-            actions.extend(self.handle_commit_vote(own_vote));
+            if let Some(own_vote) =
+                self.sign_vote(ConsensusPhase::Commit, self.current_view, block_hash)
+            {
+                actions.extend(self.handle_commit_vote(own_vote));
+            }
 
             return actions;
         }
@@ -363,7 +494,9 @@ impl HotStuffEngine {
         if !self.leader_rotation.is_leader(view, &from) {
             return vec![];
         }
-        if view != self.current_view {
+        if precommit_qc.phase != ConsensusPhase::PreCommit
+            || !self.sync_to_certified_view(view, &precommit_qc)
+        {
             return vec![];
         }
 
@@ -371,12 +504,21 @@ impl HotStuffEngine {
         self.locked_qc = Some(precommit_qc);
 
         // Vote CommitVote.
-        let vote = self.make_vote(view, block_hash, ConsensusPhase::Commit);
+        let Some(vote) = self.sign_vote(ConsensusPhase::Commit, view, block_hash) else {
+            return vec![];
+        };
         let leader = match self.leader_rotation.leader_for_view(view) {
             Some(l) => *l,
             None => return vec![],
         };
 
+        // Replicas move on once they have voted; the leader moves on when it
+        // has collected a quorum of commit votes.
+        if !self.is_leader() {
+            self.consecutive_timeouts = 0;
+            self.advance_view();
+        }
+
         vec![ConsensusAction::SendTo(
             leader,
             ConsensusMessage::CommitVote(vote),
@@ -391,11 +533,11 @@ impl HotStuffEngine {
             return vec![];
         }
 
-        let sig_data = vote_signing_data(vote.view, &vote.block_hash);
-        if verify(&sig_data, &vote.signature, &vote.voter).is_err() {
+        if !self.verify_vote(&vote, ConsensusPhase::Commit) {
             return vec![];
         }
 
+        self.check_double_vote(&vote);
         let block_hash = vote.block_hash;
         let votes = self.commit_votes.entry(block_hash).or_default();
 
@@ -406,7 +548,12 @@ impl HotStuffEngine {
 
         if votes.len() >= self.validator_set.quorum_size() {
             // Commit the block and advance view.
-            let action = ConsensusAction::CommitBlock(block_hash);
+            let Ok(qc) = bls::aggregate_votes(ConsensusPhase::Commit, votes, &self.validator_set)
+            else {
+                return vec![];
+            };
+            let action = ConsensusAction::CommitBlock(qc);
+            self.consecutive_timeouts = 0;
             self.advance_view();
             return vec![action];
         }
@@ -415,136 +562,204 @@ impl HotStuffEngine {
     }
 
     fn handle_view_change(&mut self, timeout_vote: TimeoutVote) -> Vec<ConsensusAction> {
+        if !self.validator_set.contains(&timeout_vote.voter) {
+            return vec![];
+        }
         // Verify timeout vote signature.
         let sig_data = timeout_signing_data(timeout_vote.view, timeout_vote.highest_qc_view);
         if verify(&sig_data, &timeout_vote.signature, &timeout_vote.voter).is_err() {
             return vec![];
         }
 
-        // Only collect for current view.
-        if timeout_vote.view != self.current_view {
+        // Collect for the current view and later ones.
+        let view = timeout_vote.view;
+        if view < self.current_view {
             return vec![];
         }
 
-        // Avoid duplicates.
-        if self
+        // Keep only the voter's latest vote; ignore duplicates and older ones.
+        let voter = timeout_vote.voter;
+        let superseded = self
             .timeout_votes
-            .iter()
-            .any(|tv| tv.voter == timeout_vote.voter)
-        {
+            .range(view..)
+            .any(|(_, votes)| votes.iter().any(|tv| tv.voter == voter));
+        if superseded {
             return vec![];
         }
+        for votes in self.timeout_votes.values_mut() {
+            votes.retain(|tv| tv.voter != voter);
+        }
+        self.timeout_votes.retain(|_, votes| !votes.is_empty());
+        let votes = self.timeout_votes.entry(view).or_default();
+        votes.push(timeout_vote);
+        let count = votes.len();
+
+        // Once f+1 validators time out in a later view, at least one honest
+        // validator has given up on every view before it: skip ahead and
+        // time out there too.
+        if view > self.current_view && count > self.validator_set.max_faults() {
+            tracing::debug!(
+                from = self.current_view,
+                to = view,
+                "joining later view change"
+            );
+            self.enter_view(view);
+            return self.cast_timeout_vote();
+        }
+
+        if view == self.current_view && count >= self.validator_set.quorum_size() {
+            return self.form_timeout_certificate();
+        }
 
-        self.timeout_votes.push(timeout_vote);
+        vec![]
+    }
 
-        if self.timeout_votes.len() >= self.validator_set.quorum_size() {
-            let new_view = self.current_view + 1;
+    /// Aggregate the current view's timeout votes into a timeout certificate
+    /// and move to the next view. Every validator that forms a certificate
+    /// broadcasts it, so replicas that missed some timeout votes catch up.
+    fn form_timeout_certificate(&mut self) -> Vec<ConsensusAction> {
+        let old_view = self.current_view;
+        let proof = ViewChangeProof {
+            old_view,
+            new_view: old_view + 1,
+            timeout_votes: self
+                .timeout_votes
+                .get(&old_view)
+                .cloned()
+                .unwrap_or_default(),
+            highest_qc: self.locked_qc.clone().or_else(|| self.prepare_qc.clone()),
+        };
+        self.advance_view();
 
-            // Find the highest QC among the timeout votes.
-            let highest_qc = self.locked_qc.clone().or_else(|| self.prepare_qc.clone());
+        let msg = ConsensusMessage::NewView {
+            view: self.current_view,
+            proof,
+        };
+        vec![ConsensusAction::Broadcast(msg)]
+    }
 
-            let proof = ViewChangeProof {
-                old_view: self.current_view,
-                new_view,
-                timeout_votes: self.timeout_votes.clone(),
-                highest_qc: highest_qc.clone(),
-            };
+    fn handle_new_view(&mut self, view: u64, proof: ViewChangeProof) -> Vec<ConsensusAction> {
+        // Accept NewView only for a later view, and only with a valid
+        // certificate for the view before it.
+        if view <= self.current_view || view != proof.new_view {
+            return vec![];
+        }
+        if !self.verify_timeout_certificate(&proof) {
+            return vec![];
+        }
 
-            self.advance_view();
+        // Update state to the new view.
+        self.enter_view(view);
 
-            // If I'm the next leader, broadcast NewView.
-            if self.is_leader() {
-                let msg = ConsensusMessage::NewView {
-                    view: self.current_view,
-                    proof,
-                };
-                return vec![ConsensusAction::Broadcast(msg)];
+        // Use the highest QC from the proof.
+        if let Some(qc) = proof.highest_qc {
+            if qc.phase == ConsensusPhase::PreCommit {
+                self.locked_qc = Some(qc.clone());
             }
+            self.prepare_qc = Some(qc);
+        }
+        if let Err(e) = self.persist() {
+            tracing::warn!("failed to persist consensus view: {}", e);
         }
 
         vec![]
     }
 
-    fn handle_new_view(&mut self, view: u64, proof: ViewChangeProof) -> Vec<ConsensusAction> {
-        // Accept NewView only if it matches the expected new view.
-        if view <= self.current_view {
-            return vec![];
-        }
+    // ─── Helpers ────────────────────────────────────────────────────────────
 
-        // Verify the ViewChangeProof:
-        // 1. Must have >= quorum_size timeout votes.
-        if proof.timeout_votes.len() < self.validator_set.quorum_size() {
-            return vec![];
+    /// Check a timeout certificate: a quorum of distinct validators signed
+    /// timeouts for `old_view`, `new_view` follows it, and its highest QC
+    /// (if any) is valid.
+    fn verify_timeout_certificate(&self, proof: &ViewChangeProof) -> bool {
+        if proof.new_view != proof.old_view + 1 {
+            return false;
         }
-
-        // 2. All votes must reference the correct old view.
-        if proof.old_view >= proof.new_view {
-            return vec![];
+        if proof.timeout_votes.len() < self.validator_set.quorum_size() {
+            return false;
         }
-
-        // 3. Verify each timeout vote: valid signature, in validator set, no duplicates.
         let mut seen_voters = HashSet::new();
         for tv in &proof.timeout_votes {
-            // Must reference the correct old view.
-            if tv.view != proof.old_view {
-                return vec![];
-            }
-            // Must be a known validator.
-            if !self.validator_set.contains(&tv.voter) {
-                return vec![];
-            }
-            // No duplicate voters.
-            if !seen_voters.insert(tv.voter) {
-                return vec![];
+            if tv.view != proof.old_view
+                || !self.validator_set.contains(&tv.voter)
+                || !seen_voters.insert(tv.voter)
+            {
+                return false;
             }
-            // Verify signature.
             let sig_data = timeout_signing_data(tv.view, tv.highest_qc_view);
             if verify(&sig_data, &tv.signature, &tv.voter).is_err() {
-                return vec![];
+                return false;
             }
         }
+        proof
+            .highest_qc
+            .as_ref()
+            .is_none_or(|qc| self.verify_qc(qc))
+    }
 
-        // Update state to the new view.
-        self.current_view = view;
-        self.phase = ConsensusPhase::Prepare;
-        self.prepare_votes.clear();
-        self.precommit_votes.clear();
-        self.commit_votes.clear();
-        self.timeout_votes.clear();
-        self.pending_block_hash = None;
+    /// Check that a QC's aggregate signature covers its view and block and
+    /// comes from a quorum of distinct validators.
+    fn verify_qc(&self, qc: &QuorumCertificate) -> bool {
+        bls::verify_quorum_certificate(qc, &self.validator_set).is_ok()
+    }
 
-        // Use the highest QC from the proof.
-        if let Some(qc) = proof.highest_qc {
-            if qc.phase == ConsensusPhase::PreCommit {
-                self.locked_qc = Some(qc.clone());
-            }
-            self.prepare_qc = Some(qc);
+    /// Check that a vote is for `phase` and carries both signatures of a
+    /// member of the validator set. The BLS signature is checked here so
+    /// that one bad vote cannot spoil the aggregate signature of the QC it
+    /// joins.
+    fn verify_vote(&self, vote: &Vote, phase: ConsensusPhase) -> bool {
+        if vote.phase != phase {
+            return false;
         }
-
-        vec![]
+        let Some(validator) = self.validator_set.get(&vote.voter) else {
+            return false;
+        };
+        let sig_data = vote_signing_data(phase, vote.view, &vote.block_hash);
+        verify(&sig_data, &vote.signature, &vote.voter).is_ok()
+            && bls::verify(&sig_data, &vote.bls_signature, &validator.bls_pubkey).is_ok()
     }
 
-    // ─── Helpers ────────────────────────────────────────────────────────────
+    /// Sign a `phase` vote for `block_hash` in `view`, recording it in the WAL
+    /// before it is returned. Refuses (returning `None`) to vote in a view
+    /// older than the last one voted in, or for a different block in the
+    /// same view, since both could be used as double-vote evidence.
+    fn sign_vote(&mut self, phase: ConsensusPhase, view: u64, block_hash: Hash) -> Option<Vote> {
+        if let Some((last_view, last_hash)) = self.last_vote {
+            if view < last_view || (view == last_view && block_hash != last_hash) {
+                tracing::warn!(view, last_view, "refusing conflicting vote");
+                return None;
+            }
+        }
+        let previous = self.last_vote.replace((view, block_hash));
+        if let Err(e) = self.persist() {
+            tracing::warn!("failed to persist vote, not voting: {}", e);
+            self.last_vote = previous;
+            return None;
+        }
 
-    fn make_vote(&self, view: u64, block_hash: Hash, _phase: ConsensusPhase) -> Vote {
-        let sig_data = vote_signing_data(view, &block_hash);
-        let signature = self.keypair.sign(&sig_data);
-        Vote {
+        let sig_data = vote_signing_data(phase, view, &block_hash);
+        Some(Vote {
             view,
+            phase,
             block_hash,
             voter: self.my_key,
-            signature,
-        }
+            signature: self.keypair.sign(&sig_data),
+            bls_signature: self.bls_keypair.sign(&sig_data),
+        })
     }
-}
 
-/// Compute the data to be signed for a vote: blake3(borsh(view, block_hash)).
-fn vote_signing_data(view: u64, block_hash: &Hash) -> Vec<u8> {
-    let mut data = Vec::new();
-    data.extend_from_slice(&view.to_le_bytes());
-    data.extend_from_slice(block_hash);
-    let hash = blake3_hash(&data);
-    hash.to_vec()
+    /// Write the current consensus state to the WAL, if one is attached.
+    fn persist(&self) -> Result<(), WeaveError> {
+        let Some(wal) = &self.wal else {
+            return Ok(());
+        };
+        wal.save(&ConsensusRecord {
+            view: self.current_view,
+            last_vote: self.last_vote,
+            prepare_qc: self.prepare_qc.clone(),
+            locked_qc: self.locked_qc.clone(),
+        })?;
+        Ok(())
+    }
 }
 
 /// Compute the data to be signed for a timeout vote.
@@ -559,7 +774,10 @@ fn timeout_signing_data(view: u64, highest_qc_view: u64) -> Vec<u8> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use norn_storage::memory::MemoryStore;
+    use norn_storage::traits::KvStore;
     use norn_types::weave::Validator;
+    use std::sync::Arc;
 
     fn make_keypairs(n: usize) -> Vec<Keypair> {
         (0..n).map(|i| Keypair::from_seed(&[i as u8; 32])).collect()
@@ -570,6 +788,7 @@ mod tests {
             .iter()
             .map(|kp| Validator {
                 pubkey: kp.public_key(),
+                bls_pubkey: BlsKeypair::from_keypair(kp).public_key(),
                 address: [0u8; 20],
                 stake: 1000,
                 active: true,
@@ -727,8 +946,10 @@ mod tests {
             };
             let actions = engines[0].on_message(voter_key, vote_msg.clone());
             for action in &actions {
-                if let ConsensusAction::CommitBlock(hash) = action {
-                    assert_eq!(*hash, block_hash);
+                if let ConsensusAction::CommitBlock(qc) = action {
+                    assert_eq!(qc.block_hash, block_hash);
+                    assert_eq!(qc.phase, ConsensusPhase::Commit);
+                    assert!(engines[1].verify_qc(qc));
                     committed = true;
                 }
             }
@@ -952,4 +1173,392 @@ mod tests {
         let actions = engine.on_timeout();
         assert_eq!(actions.len(), 1);
     }
+
+    #[test]
+    fn test_double_vote_produces_evidence() {
+        let keypairs = make_keypairs(4);
+        let vs = make_validator_set(&keypairs);
+        let mut leader = HotStuffEngine::new(Keypair::from_seed(&[0u8; 32]), vs);
+        assert!(leader.is_leader());
+
+        let voter = &keypairs[1];
+        let vote_for = |block_hash: Hash| {
+            let sig_data = vote_signing_data(ConsensusPhase::Prepare, 0, &block_hash);
+            Vote {
+                view: 0,
+                phase: ConsensusPhase::Prepare,
+                block_hash,
+                voter: voter.public_key(),
+                signature: voter.sign(&sig_data),
+                bls_signature: BlsKeypair::from_keypair(voter).sign(&sig_data),
+            }
+        };
+        leader.on_message(
+            voter.public_key(),
+            ConsensusMessage::PrepareVote(vote_for([1u8; 32])),
+        );
+        assert!(leader.take_evidence().is_empty());
+
+        leader.on_message(
+            voter.public_key(),
+            ConsensusMessage::PrepareVote(vote_for([2u8; 32])),
+        );
+        let evidence = leader.take_evidence();
+        assert_eq!(evidence.len(), 1);
+        assert_eq!(*evidence[0].offender(), voter.public_key());
+        assert!(crate::slashing::verify_slashing_evidence(&evidence[0]).is_ok());
+        assert!(leader.take_evidence().is_empty());
+    }
+
+    fn timeout_vote(kp: &Keypair, view: u64) -> TimeoutVote {
+        TimeoutVote {
+            view,
+            voter: kp.public_key(),
+            highest_qc_view: 0,
+            signature: kp.sign(&timeout_signing_data(view, 0)),
+        }
+    }
+
+    #[test]
+    fn test_view_timeout_backoff() {
+        let keypairs = make_keypairs(4);
+        let vs = make_validator_set(&keypairs);
+        let mut engine = HotStuffEngine::new(Keypair::from_seed(&[0u8; 32]), vs);
+        let base = Duration::from_secs(3);
+
+        assert_eq!(engine.view_timeout(base), base);
+        engine.on_timeout();
+        assert_eq!(engine.view_timeout(base), base * 2);
+        engine.on_timeout();
+        assert_eq!(engine.view_timeout(base), base * 4);
+        for _ in 0..10 {
+            engine.on_timeout();
+        }
+        assert_eq!(
+            engine.view_timeout(base),
+            base * 2u32.pow(MAX_VIEW_TIMEOUT_DOUBLINGS)
+        );
+    }
+
+    #[test]
+    fn test_joins_later_view_after_f_plus_one_timeouts() {
+        let keypairs = make_keypairs(4);
+        let vs = make_validator_set(&keypairs);
+        let mut engine = HotStuffEngine::new(Keypair::from_seed(&[0u8; 32]), vs);
+
+        // One validator (f = 1) timing out in view 5 could be faulty.
+        let actions = engine.on_message(
+            keypairs[1].public_key(),
+            ConsensusMessage::ViewChange(timeout_vote(&keypairs[1], 5)),
+        );
+        assert!(actions.is_empty());
+        assert_eq!(engine.current_view(), 0);
+
+        // A second one means an honest validator gave up on views 0-4.
+        let actions = engine.on_message(
+            keypairs[2].public_key(),
+            ConsensusMessage::ViewChange(timeout_vote(&keypairs[2], 5)),
+        );
+        assert_eq!(engine.current_view(), 6);
+        assert!(matches!(
+            &actions[..],
+            [
+                ConsensusAction::Broadcast(ConsensusMessage::ViewChange(tv)),
+                ConsensusAction::Broadcast(ConsensusMessage::NewView { view: 6, .. }),
+            ] if tv.view == 5
+        ));
+    }
+
+    #[test]
+    fn test_new_view_rejects_mismatched_view() {
+        let keypairs = make_keypairs(4);
+        let vs = make_validator_set(&keypairs);
+        let mut engine = HotStuffEngine::new(Keypair::from_seed(&[0u8; 32]), vs);
+
+        let proof = ViewChangeProof {
+            old_view: 0,
+            new_view: 1,
+            timeout_votes: keypairs[0..3]
+                .iter()
+                .map(|kp| timeout_vote(kp, 0))
+                .collect(),
+            highest_qc: None,
+        };
+        let msg = ConsensusMessage::NewView {
+            view: 7,
+            proof: proof.clone(),
+        };
+        assert!(engine.on_message(keypairs[3].public_key(), msg).is_empty());
+        assert_eq!(engine.current_view(), 0);
+
+        let msg = ConsensusMessage::NewView { view: 1, proof };
+        engine.on_message(keypairs[1].public_key(), msg);
+        assert_eq!(engine.current_view(), 1);
+    }
+
+    fn prepare(view: u64, block_hash: Hash) -> ConsensusMessage {
+        ConsensusMessage::Prepare {
+            view,
+            block_hash,
+            block_data: vec![],
+            justify: None,
+        }
+    }
+
+    #[test]
+    fn test_restarted_replica_does_not_double_vote() {
+        let keypairs = make_keypairs(4);
+        let vs = make_validator_set(&keypairs);
+        let leader = keypairs[0].public_key();
+        let store: Arc<dyn KvStore> = Arc::new(MemoryStore::new());
+        let start = |store: &Arc<dyn KvStore>| {
+            let mut engine = HotStuffEngine::new(Keypair::from_seed(&[1u8; 32]), vs.clone());
+            engine.attach_wal(ConsensusWal::new(store.clone())).unwrap();
+            engine
+        };
+
+        let mut replica = start(&store);
+        assert_eq!(replica.on_message(leader, prepare(0, [1u8; 32])).len(), 1);
+
+        // Killed after voting; an equivocating leader then proposes another
+        // block in the same view.
+        drop(replica);
+        let mut replica = start(&store);
+        assert_eq!(replica.current_view(), 0);
+        assert!(replica.on_message(leader, prepare(0, [2u8; 32])).is_empty());
+
+        // Re-sending the vote it already cast is safe.
+        let actions = replica.on_message(leader, prepare(0, [1u8; 32]));
+        assert!(matches!(
+            &actions[..],
+            [ConsensusAction::SendTo(_, ConsensusMessage::PrepareVote(v))]
+                if v.block_hash == [1u8; 32]
+        ));
+
+        // Without the WAL the same restart would have double voted.
+        let mut amnesiac = HotStuffEngine::new(Keypair::from_seed(&[1u8; 32]), vs.clone());
+        assert_eq!(amnesiac.on_message(leader, prepare(0, [2u8; 32])).len(), 1);
+    }
+
+    /// A simulated validator network. Messages are delivered in rounds, and
+    /// crashed validators and partitions drop them.
+    struct Simulation {
+        keys: Vec<PublicKey>,
+        engines: Vec<HotStuffEngine>,
+        /// Each validator's WAL store, kept across restarts.
+        stores: Vec<Arc<dyn KvStore>>,
+        crashed: HashSet<usize>,
+        /// Partition group of each validator; messages only flow within a group.
+        groups: Vec<u8>,
+        inbox: Vec<(usize, usize, ConsensusMessage)>,
+        committed: Vec<Hash>,
+    }
+
+    impl Simulation {
+        fn new(n: usize) -> Self {
+            let keypairs = make_keypairs(n);
+            let vs = make_validator_set(&keypairs);
+            let stores: Vec<Arc<dyn KvStore>> = (0..n)
+                .map(|_| Arc::new(MemoryStore::new()) as Arc<dyn KvStore>)
+                .collect();
+            Self {
+                keys: keypairs.iter().map(|kp| kp.public_key()).collect(),
+                engines: keypairs
+                    .into_iter()
+                    .zip(&stores)
+                    .map(|(kp, store)| {
+                        let mut engine = HotStuffEngine::new(kp, vs.clone());
+                        engine.attach_wal(ConsensusWal::new(store.clone())).unwrap();
+                        engine
+                    })
+                    .collect(),
+                stores,
+                crashed: HashSet::new(),
+                groups: vec![0; n],
+                inbox: Vec::new(),
+                committed: Vec::new(),
+            }
+        }
+
+        /// Replace a validator with a freshly started engine in view 0.
+        fn restart(&mut self, i: usize) {
+            let vs = self.engines[i].validator_set.clone();
+            self.engines[i] = HotStuffEngine::new(Keypair::from_seed(&[i as u8; 32]), vs);
+            self.crashed.remove(&i);
+        }
+
+        /// Replace a validator with a fresh engine that recovers from its WAL.
+        fn recover(&mut self, i: usize) {
+            self.restart(i);
+            let wal = ConsensusWal::new(self.stores[i].clone());
+            self.engines[i].attach_wal(wal).unwrap();
+        }
+
+        fn connected(&self, a: usize, b: usize) -> bool {
+            !self.crashed.contains(&a)
+                && !self.crashed.contains(&b)
+                && self.groups[a] == self.groups[b]
+        }
+
+        fn live(&self) -> Vec<usize> {
+            (0..self.engines.len())
+                .filter(|i| !self.crashed.contains(i))
+                .collect()
+        }
+
+        fn dispatch(&mut self, from: usize, actions: Vec<ConsensusAction>) {
+            for action in actions {
+                match action {
+                    ConsensusAction::Broadcast(msg) => {
+                        for to in 0..self.engines.len() {
+                            if to != from && self.connected(from, to) {
+                                self.inbox.push((from, to, msg.clone()));
+                            }
+                        }
+                    }
+                    ConsensusAction::SendTo(key, msg) => {
+                        let to = self.keys.iter().position(|k| *k == key).unwrap();
+                        if to != from && self.connected(from, to) {
+                            self.inbox.push((from, to, msg));
+                        }
+                    }
+                    ConsensusAction::CommitBlock(qc) => self.committed.push(qc.block_hash),
+                    ConsensusAction::RequestViewChange => {}
+                }
+            }
+        }
+
+        /// Deliver messages until the network is quiet.
+        fn deliver(&mut self) {
+            while !self.inbox.is_empty() {
+                for (from, to, msg) in std::mem::take(&mut self.inbox) {
+                    if self.connected(from, to) {
+                        let actions = self.engines[to].on_message(self.keys[from], msg);
+                        self.dispatch(to, actions);
+                    }
+                }
+            }
+        }
+
+        /// Live leaders propose; if nothing commits, every live validator
+        /// times out. Returns whether a block was committed.
+        fn round(&mut self) -> bool {
+            let before = self.committed.len();
+            for i in self.live() {
+                if self.engines[i].is_leader() {
+                    let view = self.engines[i].current_view();
+                    let block_hash = blake3_hash(&view.to_le_bytes());
+                    let actions = self.engines[i].propose_block(block_hash, vec![], 0);
+                    self.dispatch(i, actions);
+                }
+            }
+            self.deliver();
+            if self.committed.len() > before {
+                return true;
+            }
+            for i in self.live() {
+                let actions = self.engines[i].on_timeout();
+                self.dispatch(i, actions);
+            }
+            self.deliver();
+            false
+        }
+
+        fn views(&self) -> HashSet<u64> {
+            self.live()
+                .into_iter()
+                .map(|i| self.engines[i].current_view())
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_simulation_leader_killed_mid_round() {
+        let mut sim = Simulation::new(4);
+        for _ in 0..3 {
+            assert!(sim.round());
+        }
+        assert_eq!(sim.views(), HashSet::from([3]));
+
+        // Validator 3 leads view 3. It proposes, collects prepare votes, and
+        // is killed before forming a QC.
+        let actions = sim.engines[3].propose_block([0xaa; 32], vec![], 0);
+        sim.dispatch(3, actions);
+        for (from, to, msg) in std::mem::take(&mut sim.inbox) {
+            let actions = sim.engines[to].on_message(sim.keys[from], msg);
+            sim.dispatch(to, actions);
+        }
+        sim.crashed.insert(3);
+        sim.inbox.clear();
+
+        // It comes back in view 3 and will not propose a different block.
+        sim.recover(3);
+        assert_eq!(sim.engines[3].current_view(), 3);
+        assert!(sim.engines[3]
+            .propose_block([0xbb; 32], vec![], 0)
+            .is_empty());
+
+        // The view times out and the network carries on, with no double
+        // votes seen anywhere.
+        let commits = (0..5).filter(|_| sim.round()).count();
+        assert!(commits >= 3, "only {} commits after recovery", commits);
+        assert_eq!(sim.views().len(), 1);
+        for engine in &mut sim.engines {
+            assert!(engine.take_evidence().is_empty());
+        }
+    }
+
+    #[test]
+    fn test_simulation_skips_crashed_leader() {
+        let mut sim = Simulation::new(4);
+        sim.crashed.insert(1);
+
+        let commits = (0..12).filter(|_| sim.round()).count();
+        // Every view led by validator 1 times out; the others commit.
+        assert_eq!(commits, 9);
+        assert_eq!(sim.views(), HashSet::from([12]));
+        assert_eq!(sim.committed.len(), 9);
+    }
+
+    #[test]
+    fn test_simulation_recovers_from_partition() {
+        let mut sim = Simulation::new(4);
+        assert!(sim.round());
+
+        // Split 2/2: neither side has a quorum.
+        sim.groups = vec![0, 0, 1, 1];
+        for _ in 0..5 {
+            assert!(!sim.round());
+        }
+        let base = Duration::from_secs(3);
+        assert_eq!(
+            sim.engines[0].view_timeout(base),
+            base * 2u32.pow(MAX_VIEW_TIMEOUT_DOUBLINGS)
+        );
+
+        // Heal: the retransmitted timeout votes form a certificate, and the
+        // next leader commits.
+        sim.groups = vec![0; 4];
+        let commits = (0..8).filter(|_| sim.round()).count();
+        assert!(commits >= 6, "only {} commits after healing", commits);
+        assert_eq!(sim.views().len(), 1);
+        assert_eq!(sim.engines[0].view_timeout(base), base);
+    }
+
+    #[test]
+    fn test_simulation_lagging_replica_catches_up() {
+        let mut sim = Simulation::new(4);
+        sim.crashed.insert(3);
+        for _ in 0..6 {
+            sim.round();
+        }
+        let view = sim.engines[0].current_view();
+        assert!(view >= 6);
+
+        // Validator 3 restarts in view 0 and joins at the next certificate.
+        sim.restart(3);
+        sim.round();
+        assert_eq!(sim.views().len(), 1);
+        assert!(sim.engines[3].current_view() > view);
+    }
 }
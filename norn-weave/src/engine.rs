@@ -1,22 +1,29 @@
 use std::collections::{HashMap, HashSet};
 
+use norn_crypto::bls::BlsKeypair;
 use norn_crypto::keys::Keypair;
 use norn_crypto::merkle::SparseMerkleTree;
-use norn_types::constants::MAX_COMMITMENTS_PER_BLOCK;
-use norn_types::loom::LoomRegistration;
+use norn_loom::host::BalanceView;
+use norn_types::constants::{
+    FEE_ESTIMATE_WINDOW, FEE_HISTORY_BLOCKS, MAX_COMMITMENTS_PER_BLOCK, MEMPOOL_MAX_SIZE,
+};
+use norn_types::fraud::{FraudProof, FraudProofSubmission};
+use norn_types::knot::Knot;
+use norn_types::loom::{LoomRegistration, OperatorHandover, OperatorSet};
 use norn_types::network::NornMessage;
 use norn_types::primitives::*;
+use norn_types::slashing::SlashingEvidence;
+use norn_types::thread::ThreadKeys;
 use norn_types::weave::{
-    BlockTransfer, CommitmentUpdate, NameRecordUpdate, NameRegistration, NameTransfer,
-    Registration, StakeOperation, TokenBurn, TokenDefinition, TokenMint, ValidatorSet, WeaveBlock,
-    WeaveState,
+    BlockTransfer, CommitmentUpdate, LoomAnchor, NameRecordUpdate, NameRegistration, NameTransfer,
+    Registration, StakeOperation, TokenBurn, TokenDefinition, TokenMetadataUpdate, TokenMint,
+    ValidatorSet, WeaveBlock, WeaveState,
 };
-use rayon::prelude::*;
 
 use crate::block;
 use crate::commitment;
 use crate::consensus::{ConsensusAction, HotStuffEngine};
-use crate::mempool::Mempool;
+use crate::mempool::{BlockContents, Mempool};
 use crate::registration;
 use crate::staking::StakingState;
 
@@ -28,6 +35,8 @@ pub struct WeaveEngine {
     weave_state: WeaveState,
     merkle_tree: SparseMerkleTree,
     keypair: Keypair,
+    /// BLS key derived from `keypair`, used to self-certify solo blocks.
+    bls_keypair: BlsKeypair,
     /// Known thread IDs for duplicate detection.
     known_threads: HashSet<[u8; 20]>,
     /// Known names for duplicate detection.
@@ -40,8 +49,15 @@ pub struct WeaveEngine {
     known_symbols: HashSet<String>,
     /// Known loom IDs for duplicate detection.
     known_looms: HashSet<LoomId>,
+    /// Operator sets of known looms, used to validate anchors.
+    loom_operators: HashMap<LoomId, OperatorSet>,
+    /// Keys of threads that have updated them, used to validate key updates
+    /// and commitments signed by rotated owner keys.
+    thread_keys: HashMap<ThreadId, ThreadKeys>,
     /// Pending validator rewards to be distributed by the node.
     pending_rewards: Option<Vec<(Address, Amount)>>,
+    /// Fees charged in recent blocks, for fee estimation.
+    fee_history: crate::fees::FeeHistory,
     /// Last committed block (for RPC queries).
     last_block: Option<WeaveBlock>,
     /// Current timestamp, set by the node before each tick.
@@ -60,7 +76,7 @@ impl WeaveEngine {
         let staking = StakingState::new(1000, 100);
         let consensus_keypair = Keypair::from_seed(&keypair_seed(&keypair));
         let consensus = HotStuffEngine::new(consensus_keypair, validator_set);
-        let mempool = Mempool::new(100_000);
+        let mempool = Mempool::new(MEMPOOL_MAX_SIZE);
         let merkle_tree = SparseMerkleTree::new();
 
         Self {
@@ -69,6 +85,7 @@ impl WeaveEngine {
             staking,
             weave_state: initial_state,
             merkle_tree,
+            bls_keypair: BlsKeypair::from_keypair(&keypair),
             keypair,
             known_threads: HashSet::new(),
             known_names: HashSet::new(),
@@ -76,7 +93,10 @@ impl WeaveEngine {
             known_tokens: HashMap::new(),
             known_symbols: HashSet::new(),
             known_looms: HashSet::new(),
+            loom_operators: HashMap::new(),
+            thread_keys: HashMap::new(),
             pending_rewards: None,
+            fee_history: crate::fees::FeeHistory::new(FEE_HISTORY_BLOCKS),
             last_block: None,
             current_timestamp: 0,
             pending_blocks: HashMap::new(),
@@ -85,14 +105,16 @@ impl WeaveEngine {
         }
     }
 
-    /// Handle an incoming network message.
-    pub fn on_network_message(&mut self, msg: NornMessage) -> Vec<NornMessage> {
+    /// Handle an incoming network message. `balances` are the state before a
+    /// peer block, checked so every commitment in it can pay its fee.
+    pub fn on_network_message(
+        &mut self,
+        msg: NornMessage,
+        balances: &dyn BalanceView,
+    ) -> Vec<NornMessage> {
         match msg {
             NornMessage::Commitment(c) => {
-                // Validate and add to mempool using real current timestamp.
-                if commitment::validate_commitment(&c, None, self.current_timestamp).is_ok() {
-                    let _ = self.mempool.add_commitment(c);
-                }
+                let _ = self.add_commitment(c);
                 vec![]
             }
 
@@ -126,9 +148,7 @@ impl WeaveEngine {
             }
 
             NornMessage::FraudProof(fp) => {
-                if crate::fraud::validate_fraud_proof(&fp).is_ok() {
-                    let _ = self.mempool.add_fraud_proof(*fp);
-                }
+                let _ = self.add_fraud_proof(*fp);
                 vec![]
             }
 
@@ -159,6 +179,11 @@ impl WeaveEngine {
                 vec![]
             }
 
+            NornMessage::TokenMetadataUpdate(tmu) => {
+                let _ = self.add_token_metadata_update(*tmu);
+                vec![]
+            }
+
             NornMessage::LoomDeploy(ld) => {
                 if crate::loom::validate_loom_registration(&ld, &self.known_looms).is_ok() {
                     let _ = self.mempool.add_loom_deploy(*ld);
@@ -166,6 +191,11 @@ impl WeaveEngine {
                 vec![]
             }
 
+            NornMessage::OperatorHandover(h) => {
+                let _ = self.add_operator_handover(*h);
+                vec![]
+            }
+
             NornMessage::StakeOperation(op) => {
                 if crate::staking::validate_stake_operation(&op, &self.staking).is_ok() {
                     let _ = self.mempool.add_stake_operation(op);
@@ -173,6 +203,18 @@ impl WeaveEngine {
                 vec![]
             }
 
+            NornMessage::SlashingEvidence(evidence) => {
+                let _ = self.add_slashing_evidence(*evidence);
+                vec![]
+            }
+
+            NornMessage::SpindleOperation(op) => {
+                if crate::spindle::validate_spindle_operation(&op).is_ok() {
+                    let _ = self.mempool.add_spindle_operation(op);
+                }
+                vec![]
+            }
+
             NornMessage::Consensus(consensus_msg) => {
                 // Extract the sender from the consensus message.
                 let from = match extract_sender(&consensus_msg, self.consensus.leader_rotation()) {
@@ -180,7 +222,9 @@ impl WeaveEngine {
                     None => return vec![], // Cannot determine sender (empty validator set)
                 };
                 let actions = self.consensus.on_message(from, consensus_msg);
-                self.process_actions(actions)
+                let messages = self.process_actions(actions);
+                self.collect_consensus_evidence();
+                messages
             }
 
             NornMessage::Block(weave_block) => {
@@ -212,14 +256,48 @@ impl WeaveEngine {
                     return vec![];
                 }
 
-                // Reject entire block if ANY commitment is invalid.
-                let current_ts = self.current_timestamp;
-                let all_commitments_valid = weave_block
-                    .commitments
-                    .par_iter()
-                    .all(|c| commitment::validate_commitment(c, None, current_ts).is_ok());
+                // Reject block if it charges a base fee other than ours.
+                if weave_block.base_fee != self.fee_estimate() {
+                    tracing::debug!(
+                        height = weave_block.height,
+                        base_fee = weave_block.base_fee,
+                        expected = self.fee_estimate(),
+                        "rejecting peer block: base fee mismatch"
+                    );
+                    return vec![];
+                }
 
-                if !all_commitments_valid {
+                // Reject block if any commitment's thread cannot pay the base fee.
+                let unfunded = crate::fees::unfunded_commitments(
+                    &weave_block.commitments,
+                    weave_block.base_fee,
+                    balances,
+                );
+                if !unfunded.is_empty() {
+                    tracing::debug!(
+                        height = weave_block.height,
+                        unfunded = unfunded.len(),
+                        "rejecting peer block: commitments cannot pay their fees"
+                    );
+                    return vec![];
+                }
+
+                // Reject entire block if ANY commitment is invalid.
+                let tree = &self.merkle_tree;
+                let thread_keys = &self.thread_keys;
+                let results = commitment::validate_commitments(
+                    &weave_block.commitments,
+                    |thread_id| commitment::committed_version(tree, thread_id),
+                    |thread_id| thread_keys.get(thread_id).map(|k| k.owner),
+                    self.current_timestamp,
+                );
+                if let Some((i, Err(e))) = results.iter().enumerate().find(|(_, r)| r.is_err()) {
+                    tracing::debug!(
+                        height = weave_block.height,
+                        index = i,
+                        "rejecting peer block: invalid commitment: {}",
+                        e
+                    );
                     return vec![];
                 }
 
@@ -324,6 +402,25 @@ impl WeaveEngine {
                     }
                 }
 
+                // Reject block if any token metadata update is invalid or not
+                // newer than an earlier update to the same token in the block.
+                {
+                    let mut updated_at: HashMap<TokenId, u64> = HashMap::new();
+                    for tmu in &weave_block.token_metadata_updates {
+                        if crate::token::validate_token_metadata_update(tmu, &self.known_tokens)
+                            .is_err()
+                        {
+                            return vec![];
+                        }
+                        if updated_at
+                            .insert(tmu.token_id, tmu.timestamp)
+                            .is_some_and(|prev| tmu.timestamp <= prev)
+                        {
+                            return vec![];
+                        }
+                    }
+                }
+
                 // Reject block if any loom deploy is invalid or duplicated.
                 {
                     let mut seen_loom_ids: HashSet<LoomId> = HashSet::new();
@@ -338,6 +435,57 @@ impl WeaveEngine {
                     }
                 }
 
+                // Reject block if any loom anchor lacks its operators' threshold signatures.
+                for anchor in &weave_block.anchors {
+                    let valid = self
+                        .loom_operators
+                        .get(&anchor.loom_id)
+                        .is_some_and(|ops| crate::loom::validate_loom_anchor(anchor, ops).is_ok());
+                    if !valid {
+                        return vec![];
+                    }
+                }
+
+                // Reject block if any operator handover is invalid. Handovers
+                // apply in order, so later ones see earlier rotations.
+                {
+                    let mut rotated: HashMap<LoomId, OperatorSet> = HashMap::new();
+                    for h in &weave_block.operator_handovers {
+                        let Some(ops) = rotated
+                            .get(&h.loom_id)
+                            .or_else(|| self.loom_operators.get(&h.loom_id))
+                        else {
+                            return vec![];
+                        };
+                        let mut ops = ops.clone();
+                        if crate::loom::validate_operator_handover(h, &ops).is_err()
+                            || ops.rotate(&h.old_operator, h.new_operator).is_err()
+                        {
+                            return vec![];
+                        }
+                        rotated.insert(h.loom_id, ops);
+                    }
+                }
+
+                // Reject block if any key update is invalid. Like handovers,
+                // they apply in order.
+                {
+                    let mut updated: HashMap<ThreadId, ThreadKeys> = HashMap::new();
+                    for knot in &weave_block.key_updates {
+                        let recorded = norn_thread::rotation::key_update_target(knot)
+                            .and_then(|(tid, _)| {
+                                updated.get(&tid).or_else(|| self.thread_keys.get(&tid))
+                            })
+                            .cloned();
+                        match crate::keys::validate_key_update(knot, recorded) {
+                            Ok((tid, keys)) => {
+                                updated.insert(tid, keys);
+                            }
+                            Err(_) => return vec![],
+                        }
+                    }
+                }
+
                 // Reject block if any stake operation is invalid.
                 for so in &weave_block.stake_operations {
                     if crate::staking::validate_stake_operation(so, &self.staking).is_err() {
@@ -345,6 +493,50 @@ impl WeaveEngine {
                     }
                 }
 
+                // Reject block if any stale-commit proof is invalid: valid
+                // ones roll the thread's commitment forward.
+                for fp in &weave_block.fraud_proofs {
+                    if matches!(fp.proof, FraudProof::StaleCommit { .. })
+                        && crate::fraud::stale_commit_restore(fp).is_none()
+                    {
+                        return vec![];
+                    }
+                }
+
+                // Reject block if any spindle operation is badly signed.
+                for op in &weave_block.spindle_operations {
+                    if crate::spindle::validate_spindle_operation(op).is_err() {
+                        return vec![];
+                    }
+                }
+
+                // Reject block if any slashing evidence is invalid or accuses
+                // the same validator twice.
+                {
+                    let mut offenders: HashSet<PublicKey> = HashSet::new();
+                    for e in &weave_block.slashing_evidence {
+                        match crate::slashing::validate_slashing_evidence(e, &self.staking) {
+                            Ok(offender) if offenders.insert(offender) => {}
+                            _ => return vec![],
+                        }
+                    }
+                }
+
+                // Reject block if it commits to a different next validator set.
+                let next_vs = self.next_validator_set(
+                    &weave_block.stake_operations,
+                    &weave_block.slashing_evidence,
+                    &weave_block.proposer,
+                    weave_block.height,
+                );
+                if next_vs.hash() != weave_block.validator_set_hash {
+                    tracing::debug!(
+                        height = weave_block.height,
+                        "rejecting peer block: validator set hash mismatch"
+                    );
+                    return vec![];
+                }
+
                 // All content is valid — apply block state changes.
                 self.apply_block_to_state(&weave_block);
 
@@ -361,14 +553,31 @@ impl WeaveEngine {
         self.current_timestamp = timestamp;
     }
 
-    /// Handle a periodic tick (multi-validator consensus path).
-    pub fn on_tick(&mut self, timestamp: Timestamp) -> Vec<NornMessage> {
+    /// Handle a periodic tick (multi-validator consensus path). `balances`
+    /// are checked so a proposed block only includes commitments whose
+    /// threads can pay their fees.
+    pub fn on_tick(
+        &mut self,
+        timestamp: Timestamp,
+        balances: &dyn BalanceView,
+    ) -> Vec<NornMessage> {
         self.current_timestamp = timestamp;
         let mut messages = Vec::new();
 
+        self.mempool.evict_expired(timestamp);
+
         // If we are the leader and have items in the mempool, build and propose a block.
         if self.consensus.is_leader() && !self.mempool.is_empty() {
-            let contents = self.mempool.drain_for_block(MAX_COMMITMENTS_PER_BLOCK);
+            let mut contents = self.drain_verified_contents(timestamp);
+            self.drop_unfunded_commitments(&mut contents, balances);
+            let validator_set_hash = self
+                .next_validator_set(
+                    &contents.stake_operations,
+                    &contents.slashing_evidence,
+                    &self.keypair.public_key(),
+                    self.weave_state.height + 1,
+                )
+                .hash();
             let weave_block = block::build_block(
                 self.weave_state.latest_hash,
                 self.weave_state.height,
@@ -376,6 +585,8 @@ impl WeaveEngine {
                 &self.keypair,
                 timestamp,
                 [0u8; 32], // state_root provided by node after state application
+                validator_set_hash,
+                self.fee_estimate(),
             );
 
             let block_hash = weave_block.hash;
@@ -419,9 +630,11 @@ impl WeaveEngine {
                     // For now, treat as broadcast.
                     messages.push(NornMessage::Consensus(msg));
                 }
-                ConsensusAction::CommitBlock(hash) => {
-                    // Finalize: apply state changes and broadcast the block.
-                    if let Some(block) = self.pending_blocks.remove(&hash) {
+                ConsensusAction::CommitBlock(qc) => {
+                    // Finalize: attach the commit certificate, apply state
+                    // changes, and broadcast the block.
+                    if let Some(mut block) = self.pending_blocks.remove(&qc.block_hash) {
+                        block.certificate = Some(qc);
                         self.apply_block_to_state(&block);
                         self.last_finalized_height = block.height;
                         self.finalized_block_count += 1;
@@ -434,7 +647,7 @@ impl WeaveEngine {
                         messages.push(NornMessage::Block(Box::new(block)));
                     } else {
                         tracing::warn!(
-                            hash = hex::encode(hash),
+                            hash = hex::encode(qc.block_hash),
                             "CommitBlock for unknown pending block"
                         );
                     }
@@ -463,20 +676,43 @@ impl WeaveEngine {
 
     /// Produce a block directly, bypassing HotStuff consensus (solo mode).
     /// Drains the mempool, builds a block, applies all state changes, and returns it.
-    /// Returns `None` if the mempool is empty.
-    pub fn produce_block(&mut self, timestamp: Timestamp, state_root: Hash) -> Option<WeaveBlock> {
+    /// Commitments whose threads cannot pay their fees from `balances` are
+    /// dropped. Returns `None` if the mempool is empty.
+    pub fn produce_block(
+        &mut self,
+        timestamp: Timestamp,
+        state_root: Hash,
+        balances: &dyn BalanceView,
+    ) -> Option<WeaveBlock> {
+        self.mempool.evict_expired(timestamp);
         if self.mempool.is_empty() {
             return None;
         }
 
-        let contents = self.mempool.drain_for_block(MAX_COMMITMENTS_PER_BLOCK);
-        let weave_block = block::build_block(
+        let mut contents = self.mempool.drain_for_block(MAX_COMMITMENTS_PER_BLOCK);
+        self.drop_unfunded_commitments(&mut contents, balances);
+        let validator_set_hash = self
+            .next_validator_set(
+                &contents.stake_operations,
+                &contents.slashing_evidence,
+                &self.keypair.public_key(),
+                self.weave_state.height + 1,
+            )
+            .hash();
+        let mut weave_block = block::build_block(
             self.weave_state.latest_hash,
             self.weave_state.height,
             contents,
             &self.keypair,
             timestamp,
             state_root,
+            validator_set_hash,
+            self.fee_estimate(),
+        );
+        block::self_certify(
+            &mut weave_block,
+            &self.bls_keypair,
+            self.consensus.validator_set(),
         );
 
         self.apply_block_to_state(&weave_block);
@@ -489,17 +725,31 @@ impl WeaveEngine {
     /// - `produce_block()` (solo mode)
     /// - `on_network_message(Block)` (peer block reception)
     /// - `process_actions(CommitBlock)` (multi-validator consensus finalization)
+    /// - `replay_block()` (history replay)
+    #[tracing::instrument(name = "block", skip_all, fields(height = block.height))]
     fn apply_block_to_state(&mut self, block: &WeaveBlock) {
         // Apply commitments.
-        for c in &block.commitments {
-            let _ = commitment::apply_commitment(&mut self.weave_state, &mut self.merkle_tree, c);
-        }
+        let _ = commitment::apply_commitments(
+            &mut self.weave_state,
+            &mut self.merkle_tree,
+            &block.commitments,
+        );
         // Apply registrations.
         for r in &block.registrations {
             let _ =
                 registration::apply_registration(&mut self.weave_state, &mut self.merkle_tree, r);
             self.known_threads.insert(r.thread_id);
         }
+        // Roll back stale commitments proven by fraud proofs.
+        for fp in &block.fraud_proofs {
+            if let Some(restore) = crate::fraud::stale_commit_restore(fp) {
+                let _ = commitment::restore_thread_state(
+                    &mut self.weave_state,
+                    &mut self.merkle_tree,
+                    &restore,
+                );
+            }
+        }
         // Apply name registrations.
         for nr in &block.name_registrations {
             self.known_names.insert(nr.name.clone());
@@ -530,6 +780,7 @@ impl WeaveEngine {
                     current_supply: td.initial_supply,
                     creator: td.creator,
                     created_at: td.timestamp,
+                    metadata_updated_at: 0,
                 },
             );
         }
@@ -555,98 +806,236 @@ impl WeaveEngine {
                 }
             }
         }
+        // Apply token metadata updates.
+        for tmu in &block.token_metadata_updates {
+            if let Some(meta) = self.known_tokens.get_mut(&tmu.token_id) {
+                meta.metadata_updated_at = meta.metadata_updated_at.max(tmu.timestamp);
+            }
+        }
         // Apply loom deployments.
         for ld in &block.loom_deploys {
             let loom_id = norn_types::loom::compute_loom_id(ld);
             self.known_looms.insert(loom_id);
+            self.loom_operators
+                .insert(loom_id, OperatorSet::from_config(&ld.operator, &ld.config));
         }
-        // Apply stake operations to staking state.
-        for op in &block.stake_operations {
-            match op {
-                StakeOperation::Stake { pubkey, amount, .. } => {
-                    let addr = norn_crypto::address::pubkey_to_address(pubkey);
-                    if let Err(e) = self.staking.stake(*pubkey, addr, *amount) {
-                        tracing::debug!("stake operation failed: {}", e);
-                    }
-                }
-                StakeOperation::Unstake { pubkey, amount, .. } => {
-                    if let Err(e) = self.staking.unstake(pubkey, *amount, block.height) {
-                        tracing::debug!("unstake operation failed: {}", e);
-                    }
+        // Apply operator handovers.
+        for h in &block.operator_handovers {
+            if let Some(ops) = self.loom_operators.get_mut(&h.loom_id) {
+                if let Err(e) = ops.rotate(&h.old_operator, h.new_operator) {
+                    tracing::debug!("operator handover failed: {}", e);
                 }
             }
         }
-
-        // Process epoch (bonding period completions, validator removal).
-        let removed = self.staking.process_epoch(block.height);
-        if !removed.is_empty() {
-            tracing::info!(
-                count = removed.len(),
-                "validators removed at height {}",
-                block.height
-            );
-        }
-
-        // Update consensus validator set from staking state.
-        let new_vs = self.staking.active_validators();
-        if !new_vs.is_empty() {
-            self.consensus.update_validator_set(new_vs);
+        // Apply thread key updates.
+        for knot in &block.key_updates {
+            if let Err(e) = self.apply_key_update(knot) {
+                tracing::debug!("key update failed: {}", e);
+            }
         }
-
-        // Update weave state.
-        self.weave_state.height = block.height;
-        self.weave_state.latest_hash = block.hash;
-
-        // Accumulate fees and update dynamic fee state.
+        // Record the block's fees and update dynamic fee state. The base fee
+        // is burned and tips go to the proposer, so neither accrues to the
+        // epoch reward pool.
         let commitment_count = block.commitments.len() as u64;
-        let total_fee = crate::fees::compute_fee(&self.weave_state.fee_state, commitment_count);
-        self.weave_state.fee_state.epoch_fees = self
-            .weave_state
-            .fee_state
-            .epoch_fees
-            .saturating_add(total_fee);
+        self.fee_history.record(crate::fees::BlockFeeRecord {
+            height: block.height,
+            fee_per_commitment: block.base_fee,
+            fee_multiplier: self.weave_state.fee_state.fee_multiplier,
+            priority_fees: crate::fees::total_priority_fees(&block.commitments),
+            commitment_count,
+            capacity: MAX_COMMITMENTS_PER_BLOCK as u64,
+        });
         crate::fees::update_fee_state(
             &mut self.weave_state.fee_state,
             commitment_count,
             MAX_COMMITMENTS_PER_BLOCK as u64,
         );
 
-        // Check for epoch boundary — distribute accumulated fees to validators.
+        // Check for epoch boundary — distribute accumulated fees to the
+        // validators that served the ending epoch and their delegators,
+        // before this block's stake operations rotate the set.
         let height = block.height;
-        if height > 0
-            && height.is_multiple_of(norn_types::constants::BLOCKS_PER_EPOCH)
-            && self.weave_state.fee_state.epoch_fees > 0
-        {
-            let vs = self.staking.active_validators();
-            let rewards = crate::fees::compute_reward_distribution(
-                &vs,
-                self.weave_state.fee_state.epoch_fees,
-            );
+        if self.staking.is_epoch_boundary(height) && self.weave_state.fee_state.epoch_fees > 0 {
+            let rewards = self
+                .staking
+                .distribute_rewards(self.weave_state.fee_state.epoch_fees);
             if !rewards.is_empty() {
                 tracing::info!(
                     height,
                     epoch_fees = self.weave_state.fee_state.epoch_fees,
-                    validators = rewards.len(),
-                    "distributing epoch rewards to validators"
+                    recipients = rewards.len(),
+                    "distributing epoch rewards to validators and delegators"
                 );
                 self.pending_rewards = Some(rewards);
             }
             self.weave_state.fee_state.epoch_fees = 0;
         }
 
+        // Apply stake operations and slashings; at an epoch boundary this
+        // also rotates the validator set.
+        let serving = self.staking.active_validators();
+        let removed = self.staking.apply_block(
+            &block.stake_operations,
+            &block.slashing_evidence,
+            &block.proposer,
+            block.height,
+        );
+        if !removed.is_empty() {
+            tracing::info!(
+                count = removed.len(),
+                "validators removed at height {}",
+                block.height
+            );
+        }
+
+        // Update consensus validator set from staking state.
+        let new_vs = self.staking.active_validators();
+        if new_vs.hash() != block.validator_set_hash {
+            tracing::warn!(
+                height = block.height,
+                "block commits to a different validator set than staking computed"
+            );
+        }
+        if new_vs != serving {
+            tracing::info!(
+                height = block.height,
+                epoch = new_vs.epoch,
+                validators = new_vs.len(),
+                "validator set rotated"
+            );
+            if !new_vs.is_empty() {
+                self.consensus.update_validator_set(new_vs);
+            }
+        }
+
+        // Update weave state.
+        self.weave_state.height = block.height;
+        self.weave_state.latest_hash = block.hash;
+
         self.last_block = Some(block.clone());
     }
 
     /// Validate and add a commitment update directly to the mempool.
+    ///
+    /// The commitment must advance the thread past its committed version, and
+    /// competes for block space with its priority fee.
     pub fn add_commitment(
         &mut self,
         c: CommitmentUpdate,
     ) -> Result<bool, crate::error::WeaveError> {
-        commitment::validate_commitment(&c, None, self.current_timestamp)?;
-        self.mempool.add_commitment(c)?;
+        let committed = self.committed_version(&c.thread_id);
+        let owner = self.thread_keys.get(&c.thread_id).map(|k| k.owner);
+        commitment::validate_commitment(&c, committed, owner.as_ref(), self.current_timestamp)?;
+        let tip = c.priority_fee;
+        self.mempool.add_commitment(c, tip)?;
         Ok(true)
     }
 
+    /// The version a thread last committed to in the weave, if any.
+    pub fn committed_version(&self, thread_id: &ThreadId) -> Option<Version> {
+        commitment::committed_version(&self.merkle_tree, thread_id)
+    }
+
+    /// Drain the mempool for a new block, re-verifying its commitments in
+    /// parallel and dropping any that no longer advance their thread or have
+    /// gone stale since they were admitted.
+    fn drain_verified_contents(&mut self, timestamp: Timestamp) -> BlockContents {
+        let mut contents = self.mempool.drain_for_block(MAX_COMMITMENTS_PER_BLOCK);
+        let tree = &self.merkle_tree;
+        let thread_keys = &self.thread_keys;
+        let results = commitment::validate_commitments(
+            &contents.commitments,
+            |thread_id| commitment::committed_version(tree, thread_id),
+            |thread_id| thread_keys.get(thread_id).map(|k| k.owner),
+            timestamp,
+        );
+        if results.iter().any(|r| r.is_err()) {
+            let mut results = results.into_iter();
+            contents.commitments.retain(|c| match results.next() {
+                Some(Err(e)) => {
+                    tracing::debug!(
+                        thread = %hex::encode(c.thread_id),
+                        "dropping commitment from block: {}",
+                        e
+                    );
+                    false
+                }
+                _ => true,
+            });
+        }
+        // Drop key updates superseded since they were admitted; the rest
+        // apply in order, as peers will check them.
+        let mut updated: HashMap<ThreadId, ThreadKeys> = HashMap::new();
+        contents.key_updates.retain(|knot| {
+            let recorded = norn_thread::rotation::key_update_target(knot)
+                .and_then(|(tid, _)| updated.get(&tid).or_else(|| thread_keys.get(&tid)))
+                .cloned();
+            match crate::keys::validate_key_update(knot, recorded) {
+                Ok((tid, keys)) => {
+                    updated.insert(tid, keys);
+                    true
+                }
+                Err(e) => {
+                    tracing::debug!("dropping key update from block: {}", e);
+                    false
+                }
+            }
+        });
+        contents
+    }
+
+    /// Drop the commitments whose threads cannot pay the current base fee
+    /// plus their priority fee, so none is included for free.
+    fn drop_unfunded_commitments(&self, contents: &mut BlockContents, balances: &dyn BalanceView) {
+        let unfunded =
+            crate::fees::unfunded_commitments(&contents.commitments, self.fee_estimate(), balances);
+        if unfunded.is_empty() {
+            return;
+        }
+        let mut index = 0;
+        contents.commitments.retain(|c| {
+            let keep = !unfunded.contains(&index);
+            index += 1;
+            if !keep {
+                tracing::debug!(
+                    thread = %hex::encode(c.thread_id),
+                    "dropping commitment from block: cannot pay its fee"
+                );
+            }
+            keep
+        });
+    }
+
+    /// Validate a thread key update against the thread's current keys and add
+    /// it to the mempool.
+    pub fn add_key_update(&mut self, knot: Knot) -> Result<(), crate::error::WeaveError> {
+        crate::keys::validate_key_update(&knot, self.recorded_keys(&knot))?;
+        self.mempool.add_key_update(knot)
+    }
+
+    /// Validate and record a committed key update.
+    fn apply_key_update(&mut self, knot: &Knot) -> Result<(), crate::error::WeaveError> {
+        let (thread_id, keys) = crate::keys::validate_key_update(knot, self.recorded_keys(knot))?;
+        self.thread_keys.insert(thread_id, keys);
+        Ok(())
+    }
+
+    /// The keys recorded for the thread a key update applies to.
+    fn recorded_keys(&self, knot: &Knot) -> Option<ThreadKeys> {
+        norn_thread::rotation::key_update_target(knot)
+            .and_then(|(tid, _)| self.thread_keys.get(&tid).cloned())
+    }
+
+    /// The keys of a thread that has updated them.
+    pub fn thread_keys(&self, thread_id: &ThreadId) -> Option<&ThreadKeys> {
+        self.thread_keys.get(thread_id)
+    }
+
+    /// Seed thread keys from persisted state.
+    pub fn seed_thread_keys(&mut self, keys: impl IntoIterator<Item = (ThreadId, ThreadKeys)>) {
+        self.thread_keys.extend(keys);
+    }
+
     /// Validate and add a name registration directly to the mempool.
     pub fn add_name_registration(
         &mut self,
@@ -738,6 +1127,16 @@ impl WeaveEngine {
         Ok(true)
     }
 
+    /// Validate and add a token metadata update to the mempool.
+    pub fn add_token_metadata_update(
+        &mut self,
+        tmu: TokenMetadataUpdate,
+    ) -> Result<bool, crate::error::WeaveError> {
+        crate::token::validate_token_metadata_update(&tmu, &self.known_tokens)?;
+        self.mempool.add_token_metadata_update(tmu)?;
+        Ok(true)
+    }
+
     /// Get the known tokens map.
     pub fn known_tokens(&self) -> &HashMap<TokenId, crate::token::TokenMeta> {
         &self.known_tokens
@@ -783,6 +1182,46 @@ impl WeaveEngine {
         self.known_looms.extend(looms);
     }
 
+    /// Seed loom operator sets from persisted state.
+    pub fn seed_loom_operators(
+        &mut self,
+        operators: impl IntoIterator<Item = (LoomId, OperatorSet)>,
+    ) {
+        self.loom_operators.extend(operators);
+    }
+
+    /// Get the operator set of a known loom.
+    pub fn loom_operators(&self, loom_id: &LoomId) -> Option<&OperatorSet> {
+        self.loom_operators.get(loom_id)
+    }
+
+    /// Validate an operator handover against the loom's current operators
+    /// and add it to the mempool.
+    pub fn add_operator_handover(
+        &mut self,
+        handover: OperatorHandover,
+    ) -> Result<(), crate::error::WeaveError> {
+        let operators = self.loom_operators.get(&handover.loom_id).ok_or_else(|| {
+            crate::error::WeaveError::InvalidOperatorHandover {
+                reason: format!("unknown loom: {}", hex::encode(handover.loom_id)),
+            }
+        })?;
+        crate::loom::validate_operator_handover(&handover, operators)?;
+        self.mempool.add_operator_handover(handover)
+    }
+
+    /// Validate a loom anchor against the loom's operator threshold and add
+    /// it to the mempool.
+    pub fn add_loom_anchor(&mut self, anchor: LoomAnchor) -> Result<(), crate::error::WeaveError> {
+        let operators = self.loom_operators.get(&anchor.loom_id).ok_or_else(|| {
+            crate::error::WeaveError::InvalidLoomAnchor {
+                reason: format!("unknown loom: {}", hex::encode(anchor.loom_id)),
+            }
+        })?;
+        crate::loom::validate_loom_anchor(&anchor, operators)?;
+        self.mempool.add_anchor(anchor)
+    }
+
     /// Seed known tokens from persisted state.
     /// Called once at startup so WeaveEngine is in sync with StateManager.
     pub fn seed_known_tokens(
@@ -820,6 +1259,83 @@ impl WeaveEngine {
         self.staking.active_validators()
     }
 
+    /// The validator set that will certify the block after one at `height`
+    /// proposed by `proposer` and carrying `stake_operations` and
+    /// `slashing_evidence`: the current set less any validators slashed or
+    /// jailed by the block, or at an epoch boundary the newly selected one.
+    pub fn next_validator_set(
+        &self,
+        stake_operations: &[StakeOperation],
+        slashing_evidence: &[SlashingEvidence],
+        proposer: &PublicKey,
+        height: u64,
+    ) -> ValidatorSet {
+        let mut staking = self.staking.clone();
+        staking.apply_block(stake_operations, slashing_evidence, proposer, height);
+        staking.active_validators()
+    }
+
+    /// Validate a fraud proof submission and add it to the mempool.
+    ///
+    /// Stale-commit proofs must be fully valid, since including one rolls
+    /// the thread's commitment forward. Other proofs only need a valid
+    /// submitter signature.
+    pub fn add_fraud_proof(
+        &mut self,
+        fp: FraudProofSubmission,
+    ) -> Result<(), crate::error::WeaveError> {
+        let verdict = crate::fraud::validate_fraud_proof(&fp)?;
+        if let (FraudProof::StaleCommit { .. }, crate::fraud::FraudVerdict::Invalid { reason }) =
+            (&fp.proof, verdict)
+        {
+            return Err(crate::error::WeaveError::InvalidFraudProof { reason });
+        }
+        self.mempool.add_fraud_proof(fp)
+    }
+
+    /// Validate slashing evidence against the current staking state and add
+    /// it to the mempool.
+    pub fn add_slashing_evidence(
+        &mut self,
+        evidence: SlashingEvidence,
+    ) -> Result<(), crate::error::WeaveError> {
+        crate::slashing::validate_slashing_evidence(&evidence, &self.staking)?;
+        self.mempool.add_slashing_evidence(evidence)
+    }
+
+    /// Move double votes seen by consensus into the mempool.
+    fn collect_consensus_evidence(&mut self) {
+        for evidence in self.consensus.take_evidence() {
+            if let Err(e) = self.add_slashing_evidence(evidence) {
+                tracing::debug!("dropping consensus slashing evidence: {}", e);
+            }
+        }
+    }
+
+    /// Re-apply an already finalized block, e.g. when replaying stored
+    /// history. The block must extend the current tip; its signatures and
+    /// contents are not re-validated.
+    pub fn replay_block(&mut self, block: &WeaveBlock) -> Result<(), crate::error::WeaveError> {
+        if block.height != self.weave_state.height + 1 {
+            return Err(crate::error::WeaveError::InvalidBlock {
+                reason: format!(
+                    "expected height {}, got {}",
+                    self.weave_state.height + 1,
+                    block.height
+                ),
+            });
+        }
+        if self.weave_state.latest_hash != [0u8; 32]
+            && block.prev_hash != self.weave_state.latest_hash
+        {
+            return Err(crate::error::WeaveError::InvalidBlock {
+                reason: "prev_hash does not match the current tip".to_string(),
+            });
+        }
+        self.apply_block_to_state(block);
+        Ok(())
+    }
+
     /// Take pending validator rewards (if any) after an epoch boundary.
     /// Returns `None` if no rewards are pending.
     pub fn take_pending_rewards(&mut self) -> Option<Vec<(Address, Amount)>> {
@@ -831,22 +1347,44 @@ impl WeaveEngine {
         crate::fees::compute_fee(&self.weave_state.fee_state, 1)
     }
 
+    /// Estimate the per-commitment fee that will be enough with
+    /// `confidence` percent certainty, from recent blocks.
+    pub fn estimate_fee(&self, confidence: u8) -> Amount {
+        crate::fees::estimate_fee(
+            &self.fee_history,
+            self.fee_estimate(),
+            confidence,
+            FEE_ESTIMATE_WINDOW,
+        )
+    }
+
+    /// Fees charged in recent blocks.
+    pub fn fee_history(&self) -> &crate::fees::FeeHistory {
+        &self.fee_history
+    }
+
     /// Get a Merkle inclusion proof for a thread.
     pub fn commitment_proof(&self, thread_id: &[u8; 20]) -> norn_crypto::merkle::MerkleProof {
         let key = norn_crypto::hash::blake3_hash(thread_id);
         self.merkle_tree.prove(&key)
     }
 
-    /// Seed staking state from genesis validators.
+    /// Seed staking state from genesis validators. They are active from the
+    /// current epoch.
     pub fn seed_staking(
         &mut self,
         validators: &[norn_types::weave::Validator],
         min_stake: Amount,
         bonding_period: u64,
+        max_validators: usize,
     ) {
-        self.staking = StakingState::new(min_stake, bonding_period);
+        self.staking =
+            StakingState::new(min_stake, bonding_period).with_max_validators(max_validators);
         for v in validators {
-            if let Err(e) = self.staking.stake(v.pubkey, v.address, v.stake) {
+            if let Err(e) = self
+                .staking
+                .stake(v.pubkey, v.bls_pubkey, v.address, v.stake)
+            {
                 tracing::warn!(
                     validator = hex::encode(v.pubkey),
                     "failed to seed validator stake: {}",
@@ -854,6 +1392,8 @@ impl WeaveEngine {
                 );
             }
         }
+        self.staking
+            .activate_pending_stakes(self.weave_state.height);
         // Update consensus with the seeded validator set.
         let new_vs = self.staking.active_validators();
         if !new_vs.is_empty() {
@@ -871,6 +1411,23 @@ impl WeaveEngine {
         self.finalized_block_count
     }
 
+    /// How long the node should wait for a committed block before calling
+    /// `on_consensus_timeout`, backing off from `base` over consecutive
+    /// timeouts.
+    pub fn consensus_view_timeout(&self, base: std::time::Duration) -> std::time::Duration {
+        self.consensus.view_timeout(base)
+    }
+
+    /// Persist consensus state (view, locked QC, votes sent) in `store`,
+    /// resuming from any state a previous run left there.
+    pub fn attach_consensus_wal(
+        &mut self,
+        store: std::sync::Arc<dyn norn_storage::traits::KvStore>,
+    ) -> Result<(), crate::error::WeaveError> {
+        self.consensus
+            .attach_wal(crate::wal::ConsensusWal::new(store))
+    }
+
     /// Handle a consensus timeout (called by the node when no block is committed
     /// within the expected time). Returns messages to broadcast.
     pub fn on_consensus_timeout(&mut self) -> Vec<NornMessage> {
@@ -938,10 +1495,29 @@ mod tests {
         }
     }
 
+    /// Balances that cover any fee.
+    struct Funded;
+
+    impl BalanceView for Funded {
+        fn balance(&self, _address: &Address, _token_id: &TokenId) -> Amount {
+            Amount::MAX
+        }
+    }
+
+    /// Balances from a fixed table.
+    struct Balances(HashMap<Address, Amount>);
+
+    impl BalanceView for Balances {
+        fn balance(&self, address: &Address, _token_id: &TokenId) -> Amount {
+            self.0.get(address).copied().unwrap_or(0)
+        }
+    }
+
     fn make_validator_set_from_keypair(kp: &Keypair) -> ValidatorSet {
         ValidatorSet {
             validators: vec![Validator {
                 pubkey: kp.public_key(),
+                bls_pubkey: BlsKeypair::from_keypair(kp).public_key(),
                 address: pubkey_to_address(&kp.public_key()),
                 stake: 1000,
                 active: true,
@@ -965,12 +1541,13 @@ mod tests {
             prev_commitment_hash: [0u8; 32],
             knot_count: 1,
             timestamp: 1000,
+            priority_fee: 0,
             signature: [0u8; 64],
         };
 
         // Even with invalid sig, the mempool add happens after validate_commitment
         // which will fail, so mempool stays empty.
-        engine.on_network_message(NornMessage::Commitment(commitment));
+        engine.on_network_message(NornMessage::Commitment(commitment), &Funded);
         // The commitment had an invalid signature, so it should not be in the mempool.
         assert!(engine.mempool().is_empty());
     }
@@ -999,7 +1576,7 @@ mod tests {
         sig_data.extend_from_slice(&reg.timestamp.to_le_bytes());
         reg.signature = reg_kp.sign(&sig_data);
 
-        engine.on_network_message(NornMessage::Registration(reg));
+        engine.on_network_message(NornMessage::Registration(reg), &Funded);
         assert!(!engine.mempool().is_empty());
     }
 
@@ -1029,6 +1606,7 @@ mod tests {
         let kp = Keypair::generate();
         let seed = keypair_seed(&kp);
         let pubkey = kp.public_key();
+        let bls_pubkey = BlsKeypair::from_keypair(&kp).public_key();
         let addr = pubkey_to_address(&pubkey);
         let vs = make_validator_set_from_keypair(&kp);
         let mut state = make_weave_state();
@@ -1041,12 +1619,14 @@ mod tests {
         engine.seed_staking(
             &[Validator {
                 pubkey,
+                bls_pubkey,
                 address: addr,
                 stake: 1000,
                 active: true,
             }],
             1000,
             100,
+            norn_types::constants::MAX_VALIDATORS,
         );
 
         // Build a minimal block at the epoch boundary height.
@@ -1059,6 +1639,8 @@ mod tests {
             &block_kp,
             1000,
             [0u8; 32],
+            [0u8; 32],
+            100,
         );
         assert_eq!(block.height, norn_types::constants::BLOCKS_PER_EPOCH);
         engine.apply_block_to_state(&block);
@@ -1076,6 +1658,7 @@ mod tests {
         let kp = Keypair::generate();
         let seed = keypair_seed(&kp);
         let pubkey = kp.public_key();
+        let bls_pubkey = BlsKeypair::from_keypair(&kp).public_key();
         let addr = pubkey_to_address(&pubkey);
         let vs = make_validator_set_from_keypair(&kp);
         let mut state = make_weave_state();
@@ -1086,12 +1669,14 @@ mod tests {
         engine.seed_staking(
             &[Validator {
                 pubkey,
+                bls_pubkey,
                 address: addr,
                 stake: 1000,
                 active: true,
             }],
             1000,
             100,
+            norn_types::constants::MAX_VALIDATORS,
         );
 
         let block_kp = Keypair::from_seed(&seed);
@@ -1102,6 +1687,8 @@ mod tests {
             &block_kp,
             1000,
             [0u8; 32],
+            [0u8; 32],
+            100,
         );
         assert_eq!(block.height, norn_types::constants::BLOCKS_PER_EPOCH);
         engine.apply_block_to_state(&block);
@@ -1115,6 +1702,7 @@ mod tests {
         let kp = Keypair::generate();
         let seed = keypair_seed(&kp);
         let pubkey = kp.public_key();
+        let bls_pubkey = BlsKeypair::from_keypair(&kp).public_key();
         let addr = pubkey_to_address(&pubkey);
         let vs = make_validator_set_from_keypair(&kp);
         let mut state = make_weave_state();
@@ -1125,12 +1713,14 @@ mod tests {
         engine.seed_staking(
             &[Validator {
                 pubkey,
+                bls_pubkey,
                 address: addr,
                 stake: 1000,
                 active: true,
             }],
             1000,
             100,
+            norn_types::constants::MAX_VALIDATORS,
         );
 
         let block_kp = Keypair::from_seed(&seed);
@@ -1141,6 +1731,8 @@ mod tests {
             &block_kp,
             1000,
             [0u8; 32],
+            [0u8; 32],
+            100,
         );
         engine.apply_block_to_state(&block);
 
@@ -1150,6 +1742,377 @@ mod tests {
         assert!(engine.weave_state().fee_state.epoch_fees >= 5000);
     }
 
+    #[test]
+    fn test_stake_takes_effect_at_epoch_boundary() {
+        let kp = Keypair::generate();
+        let pubkey = kp.public_key();
+        let bls_pubkey = BlsKeypair::from_keypair(&kp).public_key();
+        let vs = make_validator_set_from_keypair(&kp);
+        let mut state = make_weave_state();
+        state.height = norn_types::constants::BLOCKS_PER_EPOCH - 2;
+        let mut engine = WeaveEngine::new(kp, vs, state);
+        engine.seed_staking(
+            &[Validator {
+                pubkey,
+                bls_pubkey,
+                address: pubkey_to_address(&pubkey),
+                stake: 1000,
+                active: true,
+            }],
+            1000,
+            100,
+            norn_types::constants::MAX_VALIDATORS,
+        );
+
+        let newcomer = Keypair::generate();
+        let stake_op = |timestamp: u64| {
+            let bls = BlsKeypair::from_keypair(&newcomer);
+            let mut op = StakeOperation::Stake {
+                pubkey: newcomer.public_key(),
+                bls_pubkey: bls.public_key(),
+                bls_pop: bls.prove_possession(),
+                amount: 2000,
+                timestamp,
+                signature: [0u8; 64],
+            };
+            let sig = newcomer.sign(&crate::staking::stake_operation_signing_data(&op));
+            if let StakeOperation::Stake { signature, .. } = &mut op {
+                *signature = sig;
+            }
+            op
+        };
+
+        // Mid-epoch: the stake is pending and the block commits to the
+        // unchanged set.
+        let before = engine.validator_set();
+        engine
+            .mempool_mut()
+            .add_stake_operation(stake_op(1))
+            .unwrap();
+        let block = engine.produce_block(1000, [0u8; 32], &Funded).unwrap();
+        assert_eq!(block.validator_set_hash, before.hash());
+        assert_eq!(engine.validator_set(), before);
+
+        // The boundary block bonds it and commits to the rotated set.
+        engine
+            .mempool_mut()
+            .add_stake_operation(stake_op(2))
+            .unwrap();
+        let block = engine.produce_block(1003, [0u8; 32], &Funded).unwrap();
+        assert_eq!(block.height, norn_types::constants::BLOCKS_PER_EPOCH);
+        let after = engine.validator_set();
+        assert!(after.contains(&newcomer.public_key()));
+        assert_eq!(after.epoch, 1);
+        assert_eq!(after.validators[0].stake, 4000);
+        assert_eq!(block.validator_set_hash, after.hash());
+    }
+
+    #[test]
+    fn test_stale_commit_attack_is_rolled_back() {
+        use norn_spindle::monitor::{MonitorAlert, ThreadMonitor};
+        use norn_thread::knot::compute_knot_id;
+        use norn_types::knot::{Knot, KnotPayload, KnotType, ParticipantState, TransferPayload};
+
+        let kp = Keypair::generate();
+        let vs = make_validator_set_from_keypair(&kp);
+        let mut engine = WeaveEngine::new(kp, vs, make_weave_state());
+        engine.set_timestamp(2000);
+
+        let owner = Keypair::generate();
+        let thread_id = pubkey_to_address(&owner.public_key());
+        let knot = |version: u64, timestamp: u64| {
+            let state = |version: u64| ParticipantState {
+                thread_id,
+                pubkey: owner.public_key(),
+                version,
+                state_hash: [version as u8; 32],
+            };
+            let mut knot = Knot {
+                id: [0u8; 32],
+                knot_type: KnotType::Transfer,
+                timestamp,
+                expiry: None,
+                before_states: vec![state(version)],
+                after_states: vec![state(version + 1)],
+                payload: KnotPayload::Transfer(TransferPayload {
+                    token_id: NATIVE_TOKEN_ID,
+                    amount: 10,
+                    from: thread_id,
+                    to: [2u8; 20],
+                    memo: None,
+                }),
+                signatures: vec![],
+                cosignatures: Vec::new(),
+            };
+            knot.id = compute_knot_id(&knot);
+            knot.signatures = vec![owner.sign(&knot.id)];
+            knot
+        };
+
+        // The owner signs knots taking the thread from version 2 to 4, then
+        // commits to version 2, hiding the outgoing transfers.
+        let mut spindle = ThreadMonitor::new();
+        spindle.watch(thread_id);
+        for version in 2..4 {
+            spindle.on_knot(&knot(version, 1000 + version));
+        }
+        let mut stale = CommitmentUpdate {
+            thread_id,
+            owner: owner.public_key(),
+            version: 2,
+            state_hash: [2u8; 32],
+            prev_commitment_hash: [0u8; 32],
+            knot_count: 2,
+            timestamp: 2000,
+            priority_fee: 0,
+            signature: [0u8; 64],
+        };
+        stale.signature = owner.sign(&commitment::commitment_signing_data(&stale));
+        engine.add_commitment(stale.clone()).unwrap();
+        engine.produce_block(2000, [0u8; 32], &Funded).unwrap();
+        let committed = |engine: &WeaveEngine| {
+            let proof = engine.commitment_proof(&thread_id);
+            borsh::from_slice::<(Hash, Version)>(&proof.value).unwrap()
+        };
+        assert_eq!(committed(&engine), ([2u8; 32], 2));
+
+        // The spindle spots the stale commitment and challenges it.
+        let spindle_kp = Keypair::generate();
+        let alert = spindle.on_commitment(&stale).unwrap();
+        let proof =
+            ThreadMonitor::build_fraud_proof(&alert, spindle_kp.public_key(), 2100, &spindle_kp);
+        engine.add_fraud_proof(proof).unwrap();
+        let block = engine.produce_block(2100, [0u8; 32], &Funded).unwrap();
+        assert_eq!(block.fraud_proofs.len(), 1);
+
+        // The thread's commitment is rolled forward to the latest signed state.
+        assert_eq!(committed(&engine), ([4u8; 32], 4));
+
+        // A proof built from knots the owner never signed is refused.
+        let mut forged = alert.clone();
+        if let MonitorAlert::StaleCommit { missing_knots, .. } = &mut forged {
+            missing_knots[0].signatures = vec![spindle_kp.sign(&missing_knots[0].id)];
+        }
+        let forged =
+            ThreadMonitor::build_fraud_proof(&forged, spindle_kp.public_key(), 2200, &spindle_kp);
+        assert!(engine.add_fraud_proof(forged).is_err());
+    }
+
+    #[test]
+    fn test_block_orders_commitments_by_tip() {
+        let kp = Keypair::generate();
+        let vs = make_validator_set_from_keypair(&kp);
+        let mut engine = WeaveEngine::new(kp, vs, make_weave_state());
+        engine.set_timestamp(2000);
+
+        for tip in [5, 50, 0] {
+            let owner = Keypair::generate();
+            let mut c = CommitmentUpdate {
+                thread_id: pubkey_to_address(&owner.public_key()),
+                owner: owner.public_key(),
+                version: 1,
+                state_hash: [1u8; 32],
+                prev_commitment_hash: [0u8; 32],
+                knot_count: 1,
+                timestamp: 2000,
+                priority_fee: tip,
+                signature: [0u8; 64],
+            };
+            c.signature = owner.sign(&commitment::commitment_signing_data(&c));
+            engine.add_commitment(c).unwrap();
+        }
+        let block = engine.produce_block(2000, [0u8; 32], &Funded).unwrap();
+        let tips: Vec<Amount> = block.commitments.iter().map(|c| c.priority_fee).collect();
+        assert_eq!(tips, vec![50, 5, 0]);
+
+        // The base fee is burned rather than pooled for epoch rewards.
+        assert_eq!(engine.weave_state().fee_state.epoch_fees, 0);
+        assert_eq!(block.base_fee, 100);
+        let record = engine.fee_history().at_height(block.height).unwrap();
+        assert_eq!(record.fee_per_commitment, 100);
+        assert_eq!(record.priority_fees, 55);
+    }
+
+    #[test]
+    fn test_block_excludes_unfunded_commitments() {
+        let kp = Keypair::generate();
+        let vs = make_validator_set_from_keypair(&kp);
+        let mut engine = WeaveEngine::new(kp, vs, make_weave_state());
+        engine.set_timestamp(2000);
+
+        let mut balances = HashMap::new();
+        for (balance, tip) in [(150, 50), (149, 50), (0, 0)] {
+            let owner = Keypair::generate();
+            let mut c = CommitmentUpdate {
+                thread_id: pubkey_to_address(&owner.public_key()),
+                owner: owner.public_key(),
+                version: 1,
+                state_hash: [1u8; 32],
+                prev_commitment_hash: [0u8; 32],
+                knot_count: 1,
+                timestamp: 2000,
+                priority_fee: tip,
+                signature: [0u8; 64],
+            };
+            c.signature = owner.sign(&commitment::commitment_signing_data(&c));
+            balances.insert(c.thread_id, balance);
+            engine.add_commitment(c).unwrap();
+        }
+        let block = engine
+            .produce_block(2000, [0u8; 32], &Balances(balances))
+            .unwrap();
+        // Only the thread that can pay the base fee of 100 plus its tip is
+        // included.
+        assert_eq!(block.commitments.len(), 1);
+        assert_eq!(block.commitments[0].priority_fee, 50);
+    }
+
+    #[test]
+    fn test_peer_block_with_wrong_base_fee_rejected() {
+        let kp = Keypair::generate();
+        let seed = keypair_seed(&kp);
+        let vs = make_validator_set_from_keypair(&kp);
+        let mut engine = WeaveEngine::new(kp, vs.clone(), make_weave_state());
+        engine.seed_staking(
+            &vs.validators,
+            1000,
+            100,
+            norn_types::constants::MAX_VALIDATORS,
+        );
+
+        let block_kp = Keypair::from_seed(&seed);
+        let validator_set_hash = engine
+            .next_validator_set(&[], &[], &block_kp.public_key(), 1)
+            .hash();
+        let build = |base_fee| {
+            let mut block = crate::block::build_block(
+                [0u8; 32],
+                0,
+                crate::mempool::BlockContents::default(),
+                &block_kp,
+                1000,
+                [0u8; 32],
+                validator_set_hash,
+                base_fee,
+            );
+            crate::block::self_certify(&mut block, &BlsKeypair::from_keypair(&block_kp), &vs);
+            Box::new(block)
+        };
+
+        engine.on_network_message(NornMessage::Block(build(99)), &Funded);
+        assert_eq!(engine.weave_state().height, 0);
+        engine.on_network_message(NornMessage::Block(build(100)), &Funded);
+        assert_eq!(engine.weave_state().height, 1);
+    }
+
+    #[test]
+    fn test_peer_block_with_unfunded_commitment_rejected() {
+        let kp = Keypair::generate();
+        let seed = keypair_seed(&kp);
+        let vs = make_validator_set_from_keypair(&kp);
+        let mut engine = WeaveEngine::new(kp, vs.clone(), make_weave_state());
+        engine.seed_staking(
+            &vs.validators,
+            1000,
+            100,
+            norn_types::constants::MAX_VALIDATORS,
+        );
+        engine.set_timestamp(1000);
+
+        let owner = Keypair::generate();
+        let mut c = CommitmentUpdate {
+            thread_id: pubkey_to_address(&owner.public_key()),
+            owner: owner.public_key(),
+            version: 1,
+            state_hash: [1u8; 32],
+            prev_commitment_hash: [0u8; 32],
+            knot_count: 1,
+            timestamp: 1000,
+            priority_fee: 0,
+            signature: [0u8; 64],
+        };
+        c.signature = owner.sign(&commitment::commitment_signing_data(&c));
+
+        let block_kp = Keypair::from_seed(&seed);
+        let validator_set_hash = engine
+            .next_validator_set(&[], &[], &block_kp.public_key(), 1)
+            .hash();
+        let mut block = crate::block::build_block(
+            [0u8; 32],
+            0,
+            crate::mempool::BlockContents {
+                commitments: vec![c.clone()],
+                ..Default::default()
+            },
+            &block_kp,
+            1000,
+            [0u8; 32],
+            validator_set_hash,
+            100,
+        );
+        crate::block::self_certify(&mut block, &BlsKeypair::from_keypair(&block_kp), &vs);
+
+        // The thread holds 99 of the 100 base fee.
+        let poor = Balances(HashMap::from([(c.thread_id, 99)]));
+        engine.on_network_message(NornMessage::Block(Box::new(block.clone())), &poor);
+        assert_eq!(engine.weave_state().height, 0);
+
+        let funded = Balances(HashMap::from([(c.thread_id, 100)]));
+        engine.on_network_message(NornMessage::Block(Box::new(block)), &funded);
+        assert_eq!(engine.weave_state().height, 1);
+    }
+
+    #[test]
+    fn test_key_rotation_moves_commitment_authority() {
+        use norn_thread::knot::sign_knot;
+        use norn_thread::rotation::build_key_rotation;
+
+        let kp = Keypair::generate();
+        let vs = make_validator_set_from_keypair(&kp);
+        let mut engine = WeaveEngine::new(kp, vs, make_weave_state());
+        engine.set_timestamp(2000);
+
+        let old = Keypair::generate();
+        let new = Keypair::generate();
+        let thread_id = pubkey_to_address(&old.public_key());
+        let mut knot = build_key_rotation(
+            thread_id,
+            &ThreadKeys::new(old.public_key()),
+            new.public_key(),
+            1,
+            2000,
+        );
+        knot.signatures.push(sign_knot(&knot, &old));
+        engine.add_key_update(knot.clone()).unwrap();
+        let block = engine.produce_block(2000, [0u8; 32], &Funded).unwrap();
+        assert_eq!(block.key_updates, vec![knot.clone()]);
+        assert_eq!(
+            engine.thread_keys(&thread_id).unwrap().owner,
+            new.public_key()
+        );
+
+        // The rotation cannot be replayed.
+        assert!(engine.add_key_update(knot).is_err());
+
+        let commit = |signer: &Keypair| {
+            let mut c = CommitmentUpdate {
+                thread_id,
+                owner: signer.public_key(),
+                version: 1,
+                state_hash: [1u8; 32],
+                prev_commitment_hash: [0u8; 32],
+                knot_count: 1,
+                timestamp: 2000,
+                priority_fee: 0,
+                signature: [0u8; 64],
+            };
+            c.signature = signer.sign(&commitment::commitment_signing_data(&c));
+            c
+        };
+        assert!(engine.add_commitment(commit(&old)).is_err());
+        assert!(engine.add_commitment(commit(&new)).is_ok());
+    }
+
     #[test]
     fn test_extract_sender_for_leader_messages() {
         // Bug #4 regression: leader messages must resolve to the leader's key.
@@ -1177,7 +2140,8 @@ mod tests {
                 view: 1,
                 block_hash: [0u8; 32],
                 phase: norn_types::consensus::ConsensusPhase::Prepare,
-                votes: vec![],
+                signers: vec![],
+                signature: [0u8; 96],
             },
         };
         let sender = extract_sender(&msg, &rotation);
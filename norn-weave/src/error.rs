@@ -42,9 +42,27 @@ pub enum WeaveError {
     #[error("invalid token burn: {reason}")]
     InvalidTokenBurn { reason: String },
 
+    #[error("invalid token metadata update: {reason}")]
+    InvalidTokenMetadataUpdate { reason: String },
+
     #[error("invalid loom registration: {reason}")]
     InvalidLoomRegistration { reason: String },
 
+    #[error("invalid loom anchor: {reason}")]
+    InvalidLoomAnchor { reason: String },
+
+    #[error("invalid operator handover: {reason}")]
+    InvalidOperatorHandover { reason: String },
+
+    #[error("invalid key update: {reason}")]
+    InvalidKeyUpdate { reason: String },
+
+    #[error("invalid slashing evidence: {reason}")]
+    InvalidSlashingEvidence { reason: String },
+
+    #[error("invalid spindle operation: {reason}")]
+    InvalidSpindleOperation { reason: String },
+
     #[error("consensus error: {reason}")]
     ConsensusError { reason: String },
 
@@ -54,6 +72,9 @@ pub enum WeaveError {
     #[error("mempool full")]
     MempoolFull,
 
+    #[error("replacement underpriced: fee {fee} below required {min_fee}")]
+    ReplacementUnderpriced { fee: u128, min_fee: u128 },
+
     #[error("not the current leader")]
     NotLeader,
 
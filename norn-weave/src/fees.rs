@@ -1,5 +1,8 @@
-use norn_types::primitives::{Address, Amount};
-use norn_types::weave::{FeeState, ValidatorSet};
+use std::collections::{HashMap, VecDeque};
+
+use norn_loom::host::BalanceView;
+use norn_types::primitives::{Address, Amount, NATIVE_TOKEN_ID};
+use norn_types::weave::{CommitmentUpdate, FeeState, ValidatorSet};
 
 /// Compute the fee for a given number of commitments.
 ///
@@ -12,6 +15,42 @@ pub fn compute_fee(fee_state: &FeeState, commitment_count: u64) -> Amount {
     fee_per.saturating_mul(commitment_count as u128)
 }
 
+/// Total priority fees (tips) offered by `commitments`.
+///
+/// Each commitment pays the base fee, which is burned, plus its tip, which
+/// goes to the proposer of the block that includes it.
+pub fn total_priority_fees(commitments: &[CommitmentUpdate]) -> Amount {
+    commitments
+        .iter()
+        .fold(0, |total: Amount, c| total.saturating_add(c.priority_fee))
+}
+
+/// Indices of the commitments whose threads cannot pay `base_fee` plus
+/// their priority fee from their native balance, charging a thread's
+/// commitments in order. Blocks must not include these, or they would be
+/// ordered for free.
+pub fn unfunded_commitments(
+    commitments: &[CommitmentUpdate],
+    base_fee: Amount,
+    balances: &dyn BalanceView,
+) -> Vec<usize> {
+    let mut charged: HashMap<Address, Amount> = HashMap::new();
+    let mut unfunded = Vec::new();
+    for (i, c) in commitments.iter().enumerate() {
+        let spent = charged.entry(c.thread_id).or_insert(0);
+        let fee = base_fee.saturating_add(c.priority_fee);
+        let available = balances
+            .balance(&c.thread_id, &NATIVE_TOKEN_ID)
+            .saturating_sub(*spent);
+        if available < fee {
+            unfunded.push(i);
+        } else {
+            *spent = spent.saturating_add(fee);
+        }
+    }
+    unfunded
+}
+
 /// Update the fee state based on block utilization.
 ///
 /// Uses integer arithmetic: `2 * utilized > capacity` means > 50% full.
@@ -76,6 +115,115 @@ pub fn compute_reward_distribution(
     rewards
 }
 
+/// The fee charged in one block, kept for fee estimation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockFeeRecord {
+    /// Block height.
+    pub height: u64,
+    /// Fee charged per commitment in the block.
+    pub fee_per_commitment: Amount,
+    /// Fee multiplier the fee was charged at (scaled by 1000).
+    pub fee_multiplier: u64,
+    /// Total priority fees offered by the block's commitments.
+    pub priority_fees: Amount,
+    /// Number of commitments included.
+    pub commitment_count: u64,
+    /// Maximum number of commitments the block could include.
+    pub capacity: u64,
+}
+
+impl BlockFeeRecord {
+    /// Whether the block was more than half full, so the fee rose after it.
+    pub fn congested(&self) -> bool {
+        self.commitment_count.saturating_mul(2) > self.capacity
+    }
+}
+
+/// Fees charged in the most recent blocks, oldest first.
+#[derive(Debug, Clone)]
+pub struct FeeHistory {
+    records: VecDeque<BlockFeeRecord>,
+    capacity: usize,
+}
+
+impl FeeHistory {
+    /// Create a history keeping the last `capacity` blocks.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            records: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Record a block's fee, evicting the oldest record when full.
+    pub fn record(&mut self, record: BlockFeeRecord) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.records.len() == self.capacity {
+            self.records.pop_front();
+        }
+        self.records.push_back(record);
+    }
+
+    /// The last `n` records, oldest first.
+    pub fn recent(&self, n: usize) -> impl Iterator<Item = &BlockFeeRecord> {
+        self.records
+            .iter()
+            .skip(self.records.len().saturating_sub(n))
+    }
+
+    /// The `percentile` (0 to 100, nearest rank) of the per-commitment fee
+    /// over the last `n` blocks. `None` if no blocks were recorded.
+    pub fn percentile(&self, n: usize, percentile: u8) -> Option<Amount> {
+        let mut fees: Vec<Amount> = self.recent(n).map(|r| r.fee_per_commitment).collect();
+        if fees.is_empty() {
+            return None;
+        }
+        fees.sort_unstable();
+        let rank = (fees.len() * percentile.min(100) as usize).div_ceil(100);
+        Some(fees[rank.saturating_sub(1)])
+    }
+
+    /// The record for the block at `height`, if still kept.
+    pub fn at_height(&self, height: u64) -> Option<&BlockFeeRecord> {
+        self.records.iter().rev().find(|r| r.height == height)
+    }
+
+    /// Number of recorded blocks.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Whether no blocks were recorded.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+}
+
+/// Estimate the per-commitment fee that will be enough with `confidence`
+/// percent certainty, from the last `window` blocks.
+///
+/// The estimate is the `confidence` percentile of recent fees, but never
+/// below `current_fee`. If the last block was congested, estimates above the
+/// median also allow for the fee rising by one step (1/8) before inclusion.
+pub fn estimate_fee(
+    history: &FeeHistory,
+    current_fee: Amount,
+    confidence: u8,
+    window: usize,
+) -> Amount {
+    let mut estimate = history
+        .percentile(window, confidence)
+        .unwrap_or(current_fee)
+        .max(current_fee);
+    let congested = history.recent(1).any(|r| r.congested());
+    if congested && confidence > 50 {
+        estimate = estimate.max(current_fee.saturating_add(current_fee / 8));
+    }
+    estimate
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -166,6 +314,7 @@ mod tests {
         address[19] = addr_byte;
         Validator {
             pubkey: [addr_byte; 32],
+            bls_pubkey: [addr_byte; 48],
             address,
             stake,
             active: true,
@@ -235,4 +384,99 @@ mod tests {
         let total: Amount = rewards.iter().map(|r| r.1).sum();
         assert_eq!(total, 1000);
     }
+
+    fn record(height: u64, fee: Amount, commitment_count: u64) -> BlockFeeRecord {
+        BlockFeeRecord {
+            height,
+            fee_per_commitment: fee,
+            fee_multiplier: 1000,
+            priority_fees: 0,
+            commitment_count,
+            capacity: 100,
+        }
+    }
+
+    #[test]
+    fn test_fee_history_percentiles() {
+        let mut history = FeeHistory::new(10);
+        assert_eq!(history.percentile(10, 50), None);
+        for (i, fee) in [50, 10, 40, 20, 30].into_iter().enumerate() {
+            history.record(record(i as u64, fee, 10));
+        }
+        assert_eq!(history.percentile(5, 0), Some(10));
+        assert_eq!(history.percentile(5, 50), Some(30));
+        assert_eq!(history.percentile(5, 90), Some(50));
+        assert_eq!(history.percentile(5, 100), Some(50));
+        // Only the last two blocks (20, 30).
+        assert_eq!(history.percentile(2, 50), Some(20));
+
+        // The oldest records are evicted at capacity.
+        for h in 5..15 {
+            history.record(record(h, 100, 10));
+        }
+        assert_eq!(history.len(), 10);
+        assert_eq!(history.recent(10).next().unwrap().height, 5);
+        assert_eq!(history.percentile(10, 0), Some(100));
+        assert_eq!(history.at_height(7).unwrap().height, 7);
+        assert!(history.at_height(2).is_none());
+    }
+
+    #[test]
+    fn test_estimate_fee() {
+        let mut history = FeeHistory::new(10);
+        // No history: the current fee.
+        assert_eq!(estimate_fee(&history, 100, 90, 10), 100);
+
+        for (h, fee) in [100, 200, 300, 400].into_iter().enumerate() {
+            history.record(record(h as u64, fee, 10));
+        }
+        assert_eq!(estimate_fee(&history, 100, 50, 10), 200);
+        assert_eq!(estimate_fee(&history, 100, 100, 10), 400);
+        // Never below the current fee.
+        assert_eq!(estimate_fee(&history, 500, 50, 10), 500);
+
+        // After a congested block, confident estimates allow for a rise.
+        history.record(record(4, 800, 90));
+        assert_eq!(estimate_fee(&history, 800, 50, 10), 800);
+        assert_eq!(estimate_fee(&history, 800, 90, 10), 900);
+    }
+
+    #[test]
+    fn test_unfunded_commitments() {
+        struct Balances(HashMap<Address, Amount>);
+        impl BalanceView for Balances {
+            fn balance(
+                &self,
+                address: &Address,
+                _token_id: &norn_types::primitives::TokenId,
+            ) -> Amount {
+                self.0.get(address).copied().unwrap_or(0)
+            }
+        }
+        let commit = |thread_id: Address, priority_fee: Amount| CommitmentUpdate {
+            thread_id,
+            owner: [0u8; 32],
+            version: 1,
+            state_hash: [0u8; 32],
+            prev_commitment_hash: [0u8; 32],
+            knot_count: 1,
+            timestamp: 1000,
+            priority_fee,
+            signature: [0u8; 64],
+        };
+        let balances = Balances(HashMap::from([([1u8; 20], 250), ([2u8; 20], 100)]));
+        let commitments = [
+            commit([1u8; 20], 50),
+            commit([2u8; 20], 1),
+            // Only 100 of thread 1's balance is left after its first fee.
+            commit([1u8; 20], 0),
+            commit([1u8; 20], 1),
+            commit([3u8; 20], 0),
+        ];
+        assert_eq!(
+            unfunded_commitments(&commitments, 100, &balances),
+            vec![1, 3, 4]
+        );
+        assert!(unfunded_commitments(&commitments[..1], 100, &balances).is_empty());
+    }
 }
@@ -1,10 +1,15 @@
 use std::collections::HashMap;
 
+use norn_crypto::address::pubkey_to_address;
 use norn_crypto::keys::verify;
+use norn_thread::chain::verify_checkpoint;
+use norn_thread::knot::compute_knot_id;
+use norn_thread::state::compute_state_hash;
 use norn_types::fraud::{FraudProof, FraudProofSubmission};
 use norn_types::loom::LoomBytecode;
-use norn_types::primitives::Address;
+use norn_types::primitives::{Address, Hash, ThreadId, Version};
 
+use crate::commitment::commitment_signing_data;
 use crate::error::WeaveError;
 
 /// The result of validating a fraud proof.
@@ -93,29 +98,117 @@ pub fn validate_fraud_proof(submission: &FraudProofSubmission) -> Result<FraudVe
         }
 
         FraudProof::StaleCommit {
-            thread_id: _,
+            thread_id,
             commitment,
+            checkpoint,
             missing_knots,
         } => {
-            // Verify commitment exists (has a valid signature).
-            if commitment.signature == [0u8; 64] {
+            // The commitment must be genuinely posted by the thread's owner.
+            if commitment.thread_id != *thread_id {
                 return Ok(FraudVerdict::Invalid {
-                    reason: "commitment has no signature".to_string(),
+                    reason: "commitment is for a different thread".to_string(),
                 });
             }
-
-            // Missing knots must form a valid progression (non-empty).
-            if missing_knots.is_empty() {
+            if pubkey_to_address(&commitment.owner) != *thread_id {
+                return Ok(FraudVerdict::Invalid {
+                    reason: "commitment owner does not derive the thread".to_string(),
+                });
+            }
+            if verify(
+                &commitment_signing_data(commitment),
+                &commitment.signature,
+                &commitment.owner,
+            )
+            .is_err()
+            {
                 return Ok(FraudVerdict::Invalid {
-                    reason: "no missing knots provided".to_string(),
+                    reason: "invalid commitment signature".to_string(),
                 });
             }
 
-            // Verify each missing knot has signatures.
+            // A checkpoint signed before the commitment at a later version
+            // proves the commitment stale by itself. Missing knots then only
+            // need to move the thread past the checkpoint.
+            let (floor, covered_by) = match checkpoint {
+                Some(checkpoint) => {
+                    if checkpoint.thread_id != *thread_id || checkpoint.owner != commitment.owner {
+                        return Ok(FraudVerdict::Invalid {
+                            reason: "checkpoint is not from the thread owner".to_string(),
+                        });
+                    }
+                    if verify_checkpoint(checkpoint).is_err() {
+                        return Ok(FraudVerdict::Invalid {
+                            reason: "invalid checkpoint signature".to_string(),
+                        });
+                    }
+                    if checkpoint.timestamp > commitment.timestamp {
+                        return Ok(FraudVerdict::Invalid {
+                            reason: "checkpoint was signed after the commitment".to_string(),
+                        });
+                    }
+                    if checkpoint.version <= commitment.version {
+                        return Ok(FraudVerdict::Invalid {
+                            reason: "checkpoint is already covered by the commitment".to_string(),
+                        });
+                    }
+                    (checkpoint.version, "checkpoint")
+                }
+                None if missing_knots.is_empty() => {
+                    return Ok(FraudVerdict::Invalid {
+                        reason: "no missing knots provided".to_string(),
+                    });
+                }
+                None => (commitment.version, "commitment"),
+            };
+
+            // Each missing knot must be signed by the owner before the
+            // commitment and move the thread past the committed version.
             for (i, knot) in missing_knots.iter().enumerate() {
-                if knot.signatures.is_empty() {
+                if compute_knot_id(knot) != knot.id {
+                    return Ok(FraudVerdict::Invalid {
+                        reason: format!("missing knot {} has a mismatched id", i),
+                    });
+                }
+                let Some(index) = knot
+                    .before_states
+                    .iter()
+                    .position(|s| s.thread_id == *thread_id)
+                else {
+                    return Ok(FraudVerdict::Invalid {
+                        reason: format!("missing knot {} does not involve the thread", i),
+                    });
+                };
+                let before = &knot.before_states[index];
+                let after_version = knot
+                    .after_states
+                    .get(index)
+                    .filter(|s| s.thread_id == *thread_id)
+                    .map(|s| s.version);
+                if before.pubkey != commitment.owner || after_version != Some(before.version + 1) {
+                    return Ok(FraudVerdict::Invalid {
+                        reason: format!("missing knot {} has an inconsistent thread state", i),
+                    });
+                }
+                let signed = knot
+                    .signatures
+                    .get(index)
+                    .is_some_and(|sig| verify(&knot.id, sig, &commitment.owner).is_ok());
+                if !signed {
+                    return Ok(FraudVerdict::Invalid {
+                        reason: format!("missing knot {} is not signed by the thread owner", i),
+                    });
+                }
+                if before.version < floor {
                     return Ok(FraudVerdict::Invalid {
-                        reason: format!("missing knot {} has no signatures", i),
+                        reason: format!(
+                            "missing knot {} is already covered by the {}",
+                            i, covered_by
+                        ),
+                    });
+                }
+                if knot.timestamp > commitment.timestamp {
+                    return Ok(FraudVerdict::Invalid {
+                        reason: format!("missing knot {} was signed after the commitment", i),
                     });
                 }
             }
@@ -219,6 +312,49 @@ pub fn validate_fraud_proof_with_loom(
     }
 }
 
+/// The latest thread state proven by a stale-commit proof. When the proof is
+/// included in a block, the thread's commitment is rolled forward to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThreadRestore {
+    /// The thread whose stale commitment is overridden.
+    pub thread_id: ThreadId,
+    /// Version after the latest missing knot.
+    pub version: Version,
+    /// State hash after the latest missing knot.
+    pub state_hash: Hash,
+}
+
+/// If `submission` is a valid stale-commit proof, return the latest signed
+/// state of the thread it proves.
+pub fn stale_commit_restore(submission: &FraudProofSubmission) -> Option<ThreadRestore> {
+    let FraudProof::StaleCommit {
+        thread_id,
+        checkpoint,
+        missing_knots,
+        ..
+    } = &submission.proof
+    else {
+        return None;
+    };
+    if validate_fraud_proof(submission).ok()? != FraudVerdict::ValidStaleCommit {
+        return None;
+    }
+    let checkpointed = checkpoint
+        .iter()
+        .map(|c| (c.version, compute_state_hash(&c.state)));
+    missing_knots
+        .iter()
+        .filter_map(|knot| knot.after_states.iter().find(|s| s.thread_id == *thread_id))
+        .map(|s| (s.version, s.state_hash))
+        .chain(checkpointed)
+        .max_by_key(|(version, _)| *version)
+        .map(|(version, state_hash)| ThreadRestore {
+            thread_id: *thread_id,
+            version,
+            state_hash,
+        })
+}
+
 /// Compute the data that should be signed for a fraud proof submission.
 fn fraud_proof_signing_data(submission: &FraudProofSubmission) -> Vec<u8> {
     let mut data = Vec::new();
@@ -237,7 +373,7 @@ mod tests {
     use norn_crypto::keys::Keypair;
     use norn_types::knot::*;
     use norn_types::primitives::*;
-    use norn_types::thread::ThreadHeader;
+    use norn_types::weave::CommitmentUpdate;
 
     fn make_signed_submission(kp: &Keypair, proof: FraudProof) -> FraudProofSubmission {
         let mut sub = FraudProofSubmission {
@@ -277,6 +413,7 @@ mod tests {
                 memo: None,
             }),
             signatures: vec![[99u8; 64]],
+            cosignatures: Vec::new(),
         }
     }
 
@@ -316,60 +453,206 @@ mod tests {
         assert!(matches!(result, FraudVerdict::Invalid { .. }));
     }
 
-    #[test]
-    fn test_valid_stale_commit() {
-        let kp = Keypair::generate();
-        let thread_id = [1u8; 20];
+    /// A knot from `owner`'s thread at `version`, signed by the owner.
+    fn make_owner_knot(owner: &Keypair, version: Version, timestamp: Timestamp) -> Knot {
+        let thread_id = pubkey_to_address(&owner.public_key());
+        let mut knot = make_knot(0, thread_id, version);
+        knot.timestamp = timestamp;
+        knot.before_states[0].pubkey = owner.public_key();
+        knot.after_states[0].pubkey = owner.public_key();
+        knot.after_states[0].state_hash = [version as u8 + 1; 32];
+        knot.id = compute_knot_id(&knot);
+        knot.signatures = vec![owner.sign(&knot.id)];
+        knot
+    }
 
-        let commitment = ThreadHeader {
-            thread_id,
-            owner: [0u8; 32],
-            version: 5,
-            state_hash: [1u8; 32],
-            last_knot_hash: [0u8; 32],
-            prev_header_hash: [0u8; 32],
-            timestamp: 1000,
-            signature: [99u8; 64], // non-zero signature
+    /// A commitment for `owner`'s thread at `version`, signed by the owner.
+    fn make_commitment(
+        owner: &Keypair,
+        version: Version,
+        timestamp: Timestamp,
+    ) -> CommitmentUpdate {
+        let mut commitment = CommitmentUpdate {
+            thread_id: pubkey_to_address(&owner.public_key()),
+            owner: owner.public_key(),
+            version,
+            state_hash: [version as u8; 32],
+            prev_commitment_hash: [0u8; 32],
+            knot_count: version,
+            timestamp,
+            priority_fee: 0,
+            signature: [0u8; 64],
         };
+        commitment.signature = owner.sign(&commitment_signing_data(&commitment));
+        commitment
+    }
 
-        let missing = make_knot(1, thread_id, 3);
-
-        let proof = FraudProof::StaleCommit {
-            thread_id,
+    fn stale_commit(commitment: CommitmentUpdate, missing_knots: Vec<Knot>) -> FraudProof {
+        FraudProof::StaleCommit {
+            thread_id: commitment.thread_id,
             commitment: Box::new(commitment),
-            missing_knots: vec![missing],
-        };
+            checkpoint: None,
+            missing_knots,
+        }
+    }
+
+    #[test]
+    fn test_valid_stale_commit() {
+        let kp = Keypair::generate();
+        let owner = Keypair::generate();
+
+        // Committed at version 3 after signing knots up to version 5.
+        let proof = stale_commit(
+            make_commitment(&owner, 3, 2000),
+            vec![
+                make_owner_knot(&owner, 3, 1000),
+                make_owner_knot(&owner, 4, 1500),
+            ],
+        );
 
         let sub = make_signed_submission(&kp, proof);
         let result = validate_fraud_proof(&sub).unwrap();
         assert_eq!(result, FraudVerdict::ValidStaleCommit);
+        assert_eq!(
+            stale_commit_restore(&sub),
+            Some(ThreadRestore {
+                thread_id: pubkey_to_address(&owner.public_key()),
+                version: 5,
+                state_hash: [5u8; 32],
+            })
+        );
     }
 
     #[test]
     fn test_stale_commit_no_missing_knots() {
         let kp = Keypair::generate();
-        let thread_id = [1u8; 20];
-
-        let commitment = ThreadHeader {
-            thread_id,
-            owner: [0u8; 32],
-            version: 5,
-            state_hash: [1u8; 32],
-            last_knot_hash: [0u8; 32],
-            prev_header_hash: [0u8; 32],
-            timestamp: 1000,
-            signature: [99u8; 64],
-        };
+        let owner = Keypair::generate();
 
-        let proof = FraudProof::StaleCommit {
-            thread_id,
-            commitment: Box::new(commitment),
-            missing_knots: vec![],
-        };
+        let proof = stale_commit(make_commitment(&owner, 5, 1000), vec![]);
 
         let sub = make_signed_submission(&kp, proof);
         let result = validate_fraud_proof(&sub).unwrap();
         assert!(matches!(result, FraudVerdict::Invalid { .. }));
+        assert!(stale_commit_restore(&sub).is_none());
+    }
+
+    #[test]
+    fn test_stale_commit_rejects_unproven_knots() {
+        let kp = Keypair::generate();
+        let owner = Keypair::generate();
+        let commitment = make_commitment(&owner, 3, 2000);
+        let is_invalid = |commitment: CommitmentUpdate, knot: Knot| {
+            let sub = make_signed_submission(&kp, stale_commit(commitment, vec![knot]));
+            matches!(
+                validate_fraud_proof(&sub).unwrap(),
+                FraudVerdict::Invalid { .. }
+            )
+        };
+
+        // Already covered by the commitment.
+        assert!(is_invalid(
+            commitment.clone(),
+            make_owner_knot(&owner, 2, 1000)
+        ));
+        // Signed after the commitment.
+        assert!(is_invalid(
+            commitment.clone(),
+            make_owner_knot(&owner, 3, 3000)
+        ));
+        // Signed by someone other than the owner.
+        let other = Keypair::generate();
+        let mut forged = make_owner_knot(&owner, 3, 1000);
+        forged.signatures = vec![other.sign(&forged.id)];
+        assert!(is_invalid(commitment.clone(), forged));
+        // Contents no longer match the signed knot ID.
+        let mut tampered = make_owner_knot(&owner, 3, 1000);
+        tampered.after_states[0].version = 9;
+        assert!(is_invalid(commitment.clone(), tampered));
+
+        // A commitment the owner never signed.
+        let mut unsigned = commitment;
+        unsigned.signature = [99u8; 64];
+        assert!(is_invalid(unsigned, make_owner_knot(&owner, 3, 1000)));
+    }
+
+    #[test]
+    fn test_stale_commit_from_checkpoint() {
+        use norn_thread::chain::create_checkpoint;
+        use norn_types::thread::ThreadState;
+
+        let kp = Keypair::generate();
+        let owner = Keypair::generate();
+        let thread_id = pubkey_to_address(&owner.public_key());
+        let commitment = make_commitment(&owner, 3, 2000);
+        let mut state = ThreadState::new();
+        state.credit(NATIVE_TOKEN_ID, 42).unwrap();
+        let checkpoint = |version, timestamp, signer: &Keypair| {
+            create_checkpoint(
+                thread_id, &state, version, [0u8; 32], None, timestamp, signer,
+            )
+        };
+        let with_checkpoint = |checkpoint, missing_knots| {
+            make_signed_submission(
+                &kp,
+                FraudProof::StaleCommit {
+                    thread_id,
+                    commitment: Box::new(commitment.clone()),
+                    checkpoint: Some(Box::new(checkpoint)),
+                    missing_knots,
+                },
+            )
+        };
+
+        // The checkpoint alone proves the commitment stale.
+        let sub = with_checkpoint(checkpoint(1_000, 1500, &owner), vec![]);
+        assert_eq!(
+            validate_fraud_proof(&sub).unwrap(),
+            FraudVerdict::ValidStaleCommit
+        );
+        assert_eq!(
+            stale_commit_restore(&sub),
+            Some(ThreadRestore {
+                thread_id,
+                version: 1_000,
+                state_hash: compute_state_hash(&state),
+            })
+        );
+
+        // Later knots roll the thread past the checkpoint.
+        let sub = with_checkpoint(
+            checkpoint(1_000, 1500, &owner),
+            vec![make_owner_knot(&owner, 1_000, 1600)],
+        );
+        assert_eq!(
+            validate_fraud_proof(&sub).unwrap(),
+            FraudVerdict::ValidStaleCommit
+        );
+        assert_eq!(stale_commit_restore(&sub).unwrap().version, 1_001);
+
+        let is_invalid = |sub: &FraudProofSubmission| {
+            matches!(
+                validate_fraud_proof(sub).unwrap(),
+                FraudVerdict::Invalid { .. }
+            )
+        };
+        // Knots the checkpoint covers.
+        assert!(is_invalid(&with_checkpoint(
+            checkpoint(1_000, 1500, &owner),
+            vec![make_owner_knot(&owner, 5, 1000)],
+        )));
+        // Signed after the commitment, not past it, or not by the owner.
+        assert!(is_invalid(&with_checkpoint(
+            checkpoint(1_000, 3000, &owner),
+            vec![]
+        )));
+        assert!(is_invalid(&with_checkpoint(
+            checkpoint(3, 1500, &owner),
+            vec![]
+        )));
+        assert!(is_invalid(&with_checkpoint(
+            checkpoint(1_000, 1500, &Keypair::generate()),
+            vec![]
+        )));
     }
 
     #[test]
@@ -447,4 +730,25 @@ mod tests {
         let result = validate_fraud_proof(&submission).unwrap();
         assert_eq!(result, FraudVerdict::ValidDoubleKnot);
     }
+
+    #[test]
+    fn test_spindle_produced_stale_commit_passes_weave_validation() {
+        use norn_spindle::monitor::ThreadMonitor;
+
+        let kp = Keypair::generate();
+        let owner = Keypair::generate();
+        let mut monitor = ThreadMonitor::new();
+        monitor.watch(pubkey_to_address(&owner.public_key()));
+        monitor.on_knot(&make_owner_knot(&owner, 3, 1000));
+        monitor.on_knot(&make_owner_knot(&owner, 4, 1500));
+
+        let alert = monitor
+            .on_commitment(&make_commitment(&owner, 3, 2000))
+            .expect("stale commitment should be detected");
+        let submission = ThreadMonitor::build_fraud_proof(&alert, kp.public_key(), 2500, &kp);
+
+        let result = validate_fraud_proof(&submission).unwrap();
+        assert_eq!(result, FraudVerdict::ValidStaleCommit);
+        assert_eq!(stale_commit_restore(&submission).unwrap().version, 5);
+    }
 }
@@ -0,0 +1,75 @@
+use norn_thread::rotation::{apply_key_update, current_keys, key_update_target};
+use norn_types::knot::Knot;
+use norn_types::primitives::ThreadId;
+use norn_types::thread::ThreadKeys;
+
+use crate::error::WeaveError;
+
+/// Validate a thread key update against the keys recorded for its thread
+/// (`None` if it has never updated them) and return the thread with its keys
+/// after the update.
+pub fn validate_key_update(
+    knot: &Knot,
+    recorded: Option<ThreadKeys>,
+) -> Result<(ThreadId, ThreadKeys), WeaveError> {
+    let invalid = |reason: String| WeaveError::InvalidKeyUpdate { reason };
+    let (thread_id, _) =
+        key_update_target(knot).ok_or_else(|| invalid("not a key update".to_string()))?;
+    let mut keys = current_keys(knot, recorded)
+        .ok_or_else(|| invalid("key is not the thread's owner key".to_string()))?;
+    norn_thread::rotation::validate_key_update(knot, &keys).map_err(|e| invalid(e.to_string()))?;
+    apply_key_update(&mut keys, knot);
+    Ok((thread_id, keys))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use norn_crypto::address::pubkey_to_address;
+    use norn_crypto::keys::Keypair;
+    use norn_thread::knot::sign_knot;
+    use norn_thread::rotation::build_key_rotation;
+
+    #[test]
+    fn test_validate_key_update_chain() {
+        let first = Keypair::generate();
+        let second = Keypair::generate();
+        let third = Keypair::generate();
+        let thread_id = pubkey_to_address(&first.public_key());
+
+        let mut knot = build_key_rotation(
+            thread_id,
+            &ThreadKeys::new(first.public_key()),
+            second.public_key(),
+            1,
+            1000,
+        );
+        knot.signatures.push(sign_knot(&knot, &first));
+        let (tid, keys) = validate_key_update(&knot, None).unwrap();
+        assert_eq!(tid, thread_id);
+        assert_eq!(keys.owner, second.public_key());
+
+        // Once rotated, the original key no longer controls the thread.
+        let mut stale = build_key_rotation(
+            thread_id,
+            &ThreadKeys {
+                epoch: 1,
+                ..ThreadKeys::new(first.public_key())
+            },
+            third.public_key(),
+            2,
+            1001,
+        );
+        stale.signatures.push(sign_knot(&stale, &first));
+        assert!(matches!(
+            validate_key_update(&stale, Some(keys.clone())),
+            Err(WeaveError::InvalidKeyUpdate { .. })
+        ));
+
+        let mut next = build_key_rotation(thread_id, &keys, third.public_key(), 2, 1001);
+        next.signatures.push(sign_knot(&next, &second));
+        let (_, keys) = validate_key_update(&next, Some(keys)).unwrap();
+        assert_eq!(keys.owner, third.public_key());
+        assert_eq!(keys.epoch, 2);
+    }
+}
@@ -1,7 +1,8 @@
 //! Anchor chain engine for the Norn Protocol.
 //!
 //! Implements block production, commitment processing, HotStuff BFT consensus,
-//! EIP-1559-style dynamic fees, fraud proof verification, and validator staking.
+//! EIP-1559-style dynamic fees, fraud proof verification, validator staking,
+//! slashing, and spindle market operations.
 
 pub mod block;
 pub mod commitment;
@@ -10,10 +11,14 @@ pub mod engine;
 pub mod error;
 pub mod fees;
 pub mod fraud;
+pub mod keys;
 pub mod leader;
 pub mod loom;
 pub mod mempool;
 pub mod name;
 pub mod registration;
+pub mod slashing;
+pub mod spindle;
 pub mod staking;
 pub mod token;
+pub mod wal;
@@ -2,16 +2,19 @@ use std::collections::HashSet;
 
 use norn_crypto::keys::verify;
 use norn_types::loom::{
-    compute_loom_id, loom_deploy_signing_data, validate_loom_name, LoomRegistration,
+    compute_loom_id, loom_deploy_signing_data, loom_state_signing_data,
+    operator_handover_signing_data, validate_loom_name, LoomRegistration, OperatorHandover,
+    OperatorSet,
 };
 use norn_types::primitives::LoomId;
+use norn_types::weave::LoomAnchor;
 
 use crate::error::WeaveError;
 
 /// Validate a loom registration (deploy).
 ///
 /// Checks: name format, no duplicate loom_id, valid config,
-/// operator pubkey-address match, and signature.
+/// valid operator set, and signature.
 /// Returns the computed loom ID on success.
 pub fn validate_loom_registration(
     reg: &LoomRegistration,
@@ -37,7 +40,14 @@ pub fn validate_loom_registration(
         });
     }
 
-    // 3. Verify signature.
+    // 3. Validate the operator set (primary operator plus co-operators).
+    OperatorSet::from_config(&reg.operator, &reg.config)
+        .validate()
+        .map_err(|e| WeaveError::InvalidLoomRegistration {
+            reason: e.to_string(),
+        })?;
+
+    // 4. Verify signature.
     let sig_data = loom_deploy_signing_data(reg);
     verify(&sig_data, &reg.signature, &reg.operator).map_err(|_| {
         WeaveError::InvalidLoomRegistration {
@@ -45,7 +55,7 @@ pub fn validate_loom_registration(
         }
     })?;
 
-    // 4. Compute loom_id and check not duplicate.
+    // 5. Compute loom_id and check not duplicate.
     let loom_id = compute_loom_id(reg);
     if known_looms.contains(&loom_id) {
         return Err(WeaveError::InvalidLoomRegistration {
@@ -56,6 +66,77 @@ pub fn validate_loom_registration(
     Ok(loom_id)
 }
 
+/// Validate a loom anchor against the loom's operator set.
+///
+/// Counts distinct operators with a valid signature over the anchored
+/// state; signatures from non-operators are rejected outright. Succeeds
+/// when at least `threshold` operators signed.
+pub fn validate_loom_anchor(
+    anchor: &LoomAnchor,
+    operators: &OperatorSet,
+) -> Result<(), WeaveError> {
+    let sig_data = loom_state_signing_data(
+        &anchor.loom_id,
+        &anchor.state_hash,
+        anchor.block_height,
+        anchor.timestamp,
+    );
+    let mut signers: Vec<&[u8; 32]> = Vec::with_capacity(anchor.signatures.len());
+    for sig in &anchor.signatures {
+        if !operators.contains(&sig.pubkey) {
+            return Err(WeaveError::InvalidLoomAnchor {
+                reason: format!("signer {} is not a loom operator", hex::encode(sig.pubkey)),
+            });
+        }
+        if signers.contains(&&sig.pubkey) {
+            return Err(WeaveError::InvalidLoomAnchor {
+                reason: "duplicate operator signature".to_string(),
+            });
+        }
+        verify(&sig_data, &sig.signature, &sig.pubkey).map_err(|_| {
+            WeaveError::InvalidLoomAnchor {
+                reason: "invalid operator signature".to_string(),
+            }
+        })?;
+        signers.push(&sig.pubkey);
+    }
+    if signers.len() < operators.threshold as usize {
+        return Err(WeaveError::InvalidLoomAnchor {
+            reason: format!(
+                "insufficient operator signatures: have {}, need {}",
+                signers.len(),
+                operators.threshold
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// Validate an operator handover against the loom's current operator set.
+///
+/// The outgoing key must be an operator, the incoming key must not be one
+/// yet, and both must have signed the handover.
+pub fn validate_operator_handover(
+    handover: &OperatorHandover,
+    operators: &OperatorSet,
+) -> Result<(), WeaveError> {
+    let invalid = |reason: &str| WeaveError::InvalidOperatorHandover {
+        reason: reason.to_string(),
+    };
+    if !operators.contains(&handover.old_operator) {
+        return Err(invalid("old operator is not a loom operator"));
+    }
+    if operators.contains(&handover.new_operator) {
+        return Err(invalid("new operator is already a loom operator"));
+    }
+    let sig_data = operator_handover_signing_data(handover);
+    verify(&sig_data, &handover.old_signature, &handover.old_operator)
+        .map_err(|_| invalid("invalid old operator signature"))?;
+    verify(&sig_data, &handover.new_signature, &handover.new_operator)
+        .map_err(|_| invalid("invalid new operator signature"))?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -70,6 +151,8 @@ mod tests {
                 min_participants: 1,
                 accepted_tokens: vec![],
                 config_data: vec![],
+                co_operators: vec![],
+                operator_threshold: 1,
             },
             operator: kp.public_key(),
             timestamp: 1000,
@@ -153,4 +236,116 @@ mod tests {
             Err(WeaveError::InvalidLoomRegistration { .. })
         ));
     }
+
+    #[test]
+    fn test_duplicate_co_operator_rejected() {
+        let kp = Keypair::generate();
+        let mut reg = make_signed_loom_registration(&kp, "counter");
+        reg.config.co_operators = vec![kp.public_key()];
+        reg.config.operator_threshold = 2;
+        let sig_data = loom_deploy_signing_data(&reg);
+        reg.signature = kp.sign(&sig_data);
+        let known = HashSet::new();
+        assert!(matches!(
+            validate_loom_registration(&reg, &known),
+            Err(WeaveError::InvalidLoomRegistration { .. })
+        ));
+    }
+
+    fn sign_anchor(anchor: &mut LoomAnchor, signers: &[&Keypair]) {
+        let sig_data = loom_state_signing_data(
+            &anchor.loom_id,
+            &anchor.state_hash,
+            anchor.block_height,
+            anchor.timestamp,
+        );
+        anchor.signatures = signers
+            .iter()
+            .map(|kp| norn_types::loom::OperatorSignature {
+                pubkey: kp.public_key(),
+                signature: kp.sign(&sig_data),
+            })
+            .collect();
+    }
+
+    #[test]
+    fn test_loom_anchor_threshold() {
+        let kps: Vec<Keypair> = (0..3).map(|_| Keypair::generate()).collect();
+        let operators = OperatorSet {
+            operators: kps.iter().map(|kp| kp.public_key()).collect(),
+            threshold: 2,
+        };
+        let mut anchor = LoomAnchor {
+            loom_id: [7u8; 32],
+            state_hash: [9u8; 32],
+            block_height: 10,
+            timestamp: 1000,
+            signatures: vec![],
+        };
+
+        sign_anchor(&mut anchor, &[&kps[0]]);
+        assert!(matches!(
+            validate_loom_anchor(&anchor, &operators),
+            Err(WeaveError::InvalidLoomAnchor { .. })
+        ));
+
+        sign_anchor(&mut anchor, &[&kps[0], &kps[2]]);
+        assert!(validate_loom_anchor(&anchor, &operators).is_ok());
+
+        // The same operator signing twice does not count twice.
+        sign_anchor(&mut anchor, &[&kps[1], &kps[1]]);
+        assert!(validate_loom_anchor(&anchor, &operators).is_err());
+
+        // Non-operators are rejected.
+        let outsider = Keypair::generate();
+        sign_anchor(&mut anchor, &[&kps[0], &kps[1], &outsider]);
+        assert!(validate_loom_anchor(&anchor, &operators).is_err());
+
+        // Signatures bind the anchored state.
+        sign_anchor(&mut anchor, &[&kps[0], &kps[1]]);
+        anchor.state_hash[0] ^= 1;
+        assert!(validate_loom_anchor(&anchor, &operators).is_err());
+    }
+
+    fn make_handover(old: &Keypair, new: &Keypair) -> OperatorHandover {
+        let mut h = OperatorHandover {
+            loom_id: [7u8; 32],
+            old_operator: old.public_key(),
+            new_operator: new.public_key(),
+            timestamp: 2000,
+            old_signature: [0u8; 64],
+            new_signature: [0u8; 64],
+        };
+        let sig_data = operator_handover_signing_data(&h);
+        h.old_signature = old.sign(&sig_data);
+        h.new_signature = new.sign(&sig_data);
+        h
+    }
+
+    #[test]
+    fn test_operator_handover() {
+        let old = Keypair::generate();
+        let new = Keypair::generate();
+        let operators = OperatorSet::single(old.public_key());
+
+        let h = make_handover(&old, &new);
+        assert!(validate_operator_handover(&h, &operators).is_ok());
+
+        // Both keys must sign.
+        let mut unaccepted = h.clone();
+        unaccepted.new_signature = unaccepted.old_signature;
+        assert!(matches!(
+            validate_operator_handover(&unaccepted, &operators),
+            Err(WeaveError::InvalidOperatorHandover { .. })
+        ));
+
+        // Only a current operator can hand over.
+        let outsider = Keypair::generate();
+        let h = make_handover(&outsider, &new);
+        assert!(validate_operator_handover(&h, &operators).is_err());
+
+        // The incoming key must not already be an operator.
+        let h = make_handover(&old, &old);
+        assert!(validate_operator_handover(&h, &operators).is_err());
+    }
 }
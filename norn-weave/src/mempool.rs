@@ -1,11 +1,17 @@
+use std::cmp::Reverse;
 use std::collections::HashMap;
 
+use norn_thread::rotation::key_update_target;
+use norn_types::constants::{MAX_COMMITMENT_AGE, MEMPOOL_REPLACEMENT_FEE_BUMP_PERCENT};
 use norn_types::fraud::FraudProofSubmission;
-use norn_types::loom::LoomRegistration;
-use norn_types::primitives::ThreadId;
+use norn_types::knot::Knot;
+use norn_types::loom::{LoomRegistration, OperatorHandover};
+use norn_types::primitives::{Amount, ThreadId, Timestamp, TokenId};
+use norn_types::slashing::SlashingEvidence;
+use norn_types::spindle::SpindleOperation;
 use norn_types::weave::{
     BlockTransfer, CommitmentUpdate, LoomAnchor, NameRecordUpdate, NameRegistration, NameTransfer,
-    Registration, StakeOperation, TokenBurn, TokenDefinition, TokenMint,
+    Registration, StakeOperation, TokenBurn, TokenDefinition, TokenMetadataUpdate, TokenMint,
 };
 
 use crate::error::WeaveError;
@@ -24,14 +30,70 @@ pub struct BlockContents {
     pub token_definitions: Vec<TokenDefinition>,
     pub token_mints: Vec<TokenMint>,
     pub token_burns: Vec<TokenBurn>,
+    pub token_metadata_updates: Vec<TokenMetadataUpdate>,
     pub loom_deploys: Vec<LoomRegistration>,
+    pub operator_handovers: Vec<OperatorHandover>,
+    pub key_updates: Vec<Knot>,
     pub stake_operations: Vec<StakeOperation>,
+    pub slashing_evidence: Vec<SlashingEvidence>,
+    pub spindle_operations: Vec<SpindleOperation>,
+}
+
+/// A pending commitment update with the fee it pays.
+#[derive(Debug, Clone)]
+pub struct PendingCommitment {
+    /// The commitment update.
+    pub commitment: CommitmentUpdate,
+    /// Fee the commitment offers on top of the base fee (its priority fee),
+    /// used to order and evict commitments. The base fee is the same for
+    /// every commitment in a block, so it does not affect the order.
+    pub fee: Amount,
+    /// Arrival order, breaking ties between equal fees (earlier first).
+    seq: u64,
+}
+
+impl PendingCommitment {
+    /// Sort key: highest fee first, then earliest arrival.
+    fn priority(&self) -> (Reverse<Amount>, u64) {
+        (Reverse(self.fee), self.seq)
+    }
+}
+
+/// Item counts of the mempool, for inspection.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MempoolStats {
+    /// Total number of pending items.
+    pub total: usize,
+    /// Maximum number of items the mempool holds.
+    pub capacity: usize,
+    /// Pending commitment updates.
+    pub commitments: usize,
+    /// Pending transfers.
+    pub transfers: usize,
+    /// Pending registrations.
+    pub registrations: usize,
+    /// Pending fraud proofs and slashing evidence.
+    pub evidence: usize,
+    /// Every other pending item.
+    pub other: usize,
+    /// Lowest fee among pending commitments: a new commitment must pay more
+    /// to get in while the mempool is full.
+    pub min_commitment_fee: Option<Amount>,
 }
 
 /// Transaction mempool for pending weave transactions.
+///
+/// Commitments are kept one per thread and drained highest fee first. A
+/// pending commitment is replaced by one with a higher version, or by one
+/// with the same version that pays at least
+/// `MEMPOOL_REPLACEMENT_FEE_BUMP_PERCENT` more. When the mempool is full, a
+/// commitment paying more than the cheapest pending one evicts it, and fraud
+/// proofs and slashing evidence evict the cheapest commitment outright.
 pub struct Mempool {
-    /// Commitment updates, deduped by thread_id (latest wins).
-    commitments: HashMap<ThreadId, CommitmentUpdate>,
+    /// Commitment updates, one per thread.
+    commitments: HashMap<ThreadId, PendingCommitment>,
+    /// Arrival counter for pending commitments.
+    next_seq: u64,
     /// Pending registrations.
     registrations: Vec<Registration>,
     /// Pending loom anchors.
@@ -52,10 +114,20 @@ pub struct Mempool {
     token_mints: Vec<TokenMint>,
     /// Pending token burns.
     token_burns: Vec<TokenBurn>,
+    /// Pending token metadata updates.
+    token_metadata_updates: Vec<TokenMetadataUpdate>,
     /// Pending loom deployments.
     loom_deploys: Vec<LoomRegistration>,
+    /// Pending loom operator handovers.
+    operator_handovers: Vec<OperatorHandover>,
+    /// Pending thread key updates.
+    key_updates: Vec<Knot>,
     /// Pending stake operations.
     stake_operations: Vec<StakeOperation>,
+    /// Pending validator slashing evidence.
+    slashing_evidence: Vec<SlashingEvidence>,
+    /// Pending spindle market operations.
+    spindle_operations: Vec<SpindleOperation>,
     /// Maximum total number of items in the mempool.
     max_size: usize,
 }
@@ -65,6 +137,7 @@ impl Mempool {
     pub fn new(max_size: usize) -> Self {
         Self {
             commitments: HashMap::new(),
+            next_seq: 0,
             registrations: Vec::new(),
             anchors: Vec::new(),
             name_registrations: Vec::new(),
@@ -75,8 +148,13 @@ impl Mempool {
             token_definitions: Vec::new(),
             token_mints: Vec::new(),
             token_burns: Vec::new(),
+            token_metadata_updates: Vec::new(),
             loom_deploys: Vec::new(),
+            operator_handovers: Vec::new(),
+            key_updates: Vec::new(),
             stake_operations: Vec::new(),
+            slashing_evidence: Vec::new(),
+            spindle_operations: Vec::new(),
             max_size,
         }
     }
@@ -94,20 +172,99 @@ impl Mempool {
             + self.token_definitions.len()
             + self.token_mints.len()
             + self.token_burns.len()
+            + self.token_metadata_updates.len()
             + self.loom_deploys.len()
+            + self.operator_handovers.len()
+            + self.key_updates.len()
             + self.stake_operations.len()
+            + self.slashing_evidence.len()
+            + self.spindle_operations.len()
     }
 
-    /// Add a commitment update (deduplicates by thread_id; latest wins).
-    pub fn add_commitment(&mut self, c: CommitmentUpdate) -> Result<(), WeaveError> {
-        // Dedup allows replacing, so only check capacity if it's a new thread_id.
-        if !self.commitments.contains_key(&c.thread_id) && self.total_size() >= self.max_size {
-            return Err(WeaveError::MempoolFull);
+    /// Add a commitment update paying `fee`.
+    ///
+    /// A thread's pending commitment is replaced by a higher version, or by
+    /// the same version if the fee is bumped enough. At capacity, the new
+    /// commitment must outbid the cheapest pending commitment, which is
+    /// evicted.
+    pub fn add_commitment(&mut self, c: CommitmentUpdate, fee: Amount) -> Result<(), WeaveError> {
+        if let Some(existing) = self.commitments.get_mut(&c.thread_id) {
+            if c.version < existing.commitment.version {
+                return Err(WeaveError::InvalidCommitment {
+                    reason: format!(
+                        "version {} is below pending version {}",
+                        c.version, existing.commitment.version
+                    ),
+                });
+            }
+            if c.version == existing.commitment.version {
+                if c.signature == existing.commitment.signature {
+                    return Ok(());
+                }
+                let min_fee = replacement_fee(existing.fee);
+                if fee < min_fee {
+                    return Err(WeaveError::ReplacementUnderpriced { fee, min_fee });
+                }
+            }
+            // Replacing keeps the thread's place in the arrival order.
+            existing.commitment = c;
+            existing.fee = fee;
+            return Ok(());
+        }
+
+        if self.total_size() >= self.max_size {
+            match self.lowest_priority_commitment() {
+                Some((thread_id, lowest)) if lowest < fee => {
+                    self.commitments.remove(&thread_id);
+                }
+                _ => return Err(WeaveError::MempoolFull),
+            }
         }
-        self.commitments.insert(c.thread_id, c);
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.commitments.insert(
+            c.thread_id,
+            PendingCommitment {
+                commitment: c,
+                fee,
+                seq,
+            },
+        );
         Ok(())
     }
 
+    /// The thread and fee of the commitment that would be evicted first:
+    /// the cheapest, and the most recent among equally cheap ones.
+    fn lowest_priority_commitment(&self) -> Option<(ThreadId, Amount)> {
+        self.commitments
+            .values()
+            .max_by_key(|p| p.priority())
+            .map(|p| (p.commitment.thread_id, p.fee))
+    }
+
+    /// Make room for a fraud proof or slashing evidence. These are never
+    /// crowded out by commitments: at capacity the cheapest commitment is
+    /// evicted.
+    fn make_room_for_evidence(&mut self) -> Result<(), WeaveError> {
+        if self.total_size() < self.max_size {
+            return Ok(());
+        }
+        let (thread_id, _) = self
+            .lowest_priority_commitment()
+            .ok_or(WeaveError::MempoolFull)?;
+        self.commitments.remove(&thread_id);
+        Ok(())
+    }
+
+    /// Evict commitments older than `MAX_COMMITMENT_AGE` at `now`. They would
+    /// be rejected as stale anyway. Returns the number evicted.
+    pub fn evict_expired(&mut self, now: Timestamp) -> usize {
+        let before = self.commitments.len();
+        self.commitments
+            .retain(|_, p| p.commitment.timestamp.saturating_add(MAX_COMMITMENT_AGE) >= now);
+        before - self.commitments.len()
+    }
+
     /// Add a registration (deduplicated by thread_id).
     pub fn add_registration(&mut self, r: Registration) -> Result<(), WeaveError> {
         if self.total_size() >= self.max_size {
@@ -124,16 +281,16 @@ impl Mempool {
         Ok(())
     }
 
-    /// Add a loom anchor (deduplicated by signature).
+    /// Add a loom anchor (deduplicated by loom, state hash, and height).
     pub fn add_anchor(&mut self, a: LoomAnchor) -> Result<(), WeaveError> {
         if self.total_size() >= self.max_size {
             return Err(WeaveError::MempoolFull);
         }
-        if self
-            .anchors
-            .iter()
-            .any(|existing| existing.signature == a.signature)
-        {
+        if self.anchors.iter().any(|existing| {
+            existing.loom_id == a.loom_id
+                && existing.state_hash == a.state_hash
+                && existing.block_height == a.block_height
+        }) {
             return Ok(());
         }
         self.anchors.push(a);
@@ -188,11 +345,16 @@ impl Mempool {
         Ok(())
     }
 
-    /// Add a fraud proof submission.
+    /// Add a fraud proof submission (deduplicated by signature).
     pub fn add_fraud_proof(&mut self, fp: FraudProofSubmission) -> Result<(), WeaveError> {
-        if self.total_size() >= self.max_size {
-            return Err(WeaveError::MempoolFull);
+        if self
+            .fraud_proofs
+            .iter()
+            .any(|existing| existing.signature == fp.signature)
+        {
+            return Ok(());
         }
+        self.make_room_for_evidence()?;
         self.fraud_proofs.push(fp);
         Ok(())
     }
@@ -261,29 +423,73 @@ impl Mempool {
         Ok(())
     }
 
+    /// Add a token metadata update for block inclusion (deduplicated by signature).
+    pub fn add_token_metadata_update(
+        &mut self,
+        tmu: TokenMetadataUpdate,
+    ) -> Result<(), WeaveError> {
+        if self.total_size() >= self.max_size {
+            return Err(WeaveError::MempoolFull);
+        }
+        if self
+            .token_metadata_updates
+            .iter()
+            .any(|existing| existing.signature == tmu.signature)
+        {
+            return Ok(());
+        }
+        self.token_metadata_updates.push(tmu);
+        Ok(())
+    }
+
     /// Add a stake operation for block inclusion (deduplicated by signature).
     pub fn add_stake_operation(&mut self, op: StakeOperation) -> Result<(), WeaveError> {
         if self.total_size() >= self.max_size {
             return Err(WeaveError::MempoolFull);
         }
-        let op_sig = match &op {
-            StakeOperation::Stake { signature, .. } | StakeOperation::Unstake { signature, .. } => {
-                *signature
-            }
-        };
-        if self.stake_operations.iter().any(|existing| {
-            let existing_sig = match existing {
-                StakeOperation::Stake { signature, .. }
-                | StakeOperation::Unstake { signature, .. } => signature,
-            };
-            *existing_sig == op_sig
-        }) {
+        let op_sig = *op.signature();
+        if self
+            .stake_operations
+            .iter()
+            .any(|existing| *existing.signature() == op_sig)
+        {
             return Ok(());
         }
         self.stake_operations.push(op);
         Ok(())
     }
 
+    /// Add slashing evidence for block inclusion (one piece per offender).
+    pub fn add_slashing_evidence(&mut self, evidence: SlashingEvidence) -> Result<(), WeaveError> {
+        if self
+            .slashing_evidence
+            .iter()
+            .any(|existing| existing.offender() == evidence.offender())
+        {
+            return Ok(());
+        }
+        self.make_room_for_evidence()?;
+        self.slashing_evidence.push(evidence);
+        Ok(())
+    }
+
+    /// Add a spindle market operation for block inclusion (deduplicated by
+    /// signature).
+    pub fn add_spindle_operation(&mut self, op: SpindleOperation) -> Result<(), WeaveError> {
+        if self.total_size() >= self.max_size {
+            return Err(WeaveError::MempoolFull);
+        }
+        if self
+            .spindle_operations
+            .iter()
+            .any(|existing| existing.signature() == op.signature())
+        {
+            return Ok(());
+        }
+        self.spindle_operations.push(op);
+        Ok(())
+    }
+
     /// Add a loom deployment for block inclusion (deduplicated by signature).
     pub fn add_loom_deploy(&mut self, ld: LoomRegistration) -> Result<(), WeaveError> {
         if self.total_size() >= self.max_size {
@@ -300,25 +506,55 @@ impl Mempool {
         Ok(())
     }
 
+    /// Add an operator handover for block inclusion. Only one handover per
+    /// retiring key is kept, since a second one could never apply.
+    pub fn add_operator_handover(&mut self, h: OperatorHandover) -> Result<(), WeaveError> {
+        if self.total_size() >= self.max_size {
+            return Err(WeaveError::MempoolFull);
+        }
+        if self.operator_handovers.iter().any(|existing| {
+            existing.loom_id == h.loom_id && existing.old_operator == h.old_operator
+        }) {
+            return Ok(());
+        }
+        self.operator_handovers.push(h);
+        Ok(())
+    }
+
+    /// Add a thread key update for block inclusion. Only one update per
+    /// thread and key epoch is kept, since a second one could never apply.
+    pub fn add_key_update(&mut self, knot: Knot) -> Result<(), WeaveError> {
+        if self.total_size() >= self.max_size {
+            return Err(WeaveError::MempoolFull);
+        }
+        let target = key_update_target(&knot);
+        if self
+            .key_updates
+            .iter()
+            .any(|existing| key_update_target(existing) == target)
+        {
+            return Ok(());
+        }
+        self.key_updates.push(knot);
+        Ok(())
+    }
+
     /// Drain items from the mempool for block building.
-    /// Takes up to `max_commitments` commitment updates, and all registrations,
-    /// anchors, and fraud proofs.
+    /// Takes the `max_commitments` highest-priority commitment updates, and
+    /// all registrations, anchors, and fraud proofs. Transfers are ordered by
+    /// knot timestamp, so each thread's transfers apply in order.
     pub fn drain_for_block(&mut self, max_commitments: usize) -> BlockContents {
-        let commitments: Vec<CommitmentUpdate> = if self.commitments.len() <= max_commitments {
-            // Take all — drain is safe here since we want everything.
-            self.commitments.drain().map(|(_, v)| v).collect()
-        } else {
-            // Only remove the first `max_commitments` entries, preserving the rest.
-            let keys: Vec<ThreadId> = self
-                .commitments
-                .keys()
-                .take(max_commitments)
-                .copied()
-                .collect();
-            keys.into_iter()
-                .filter_map(|k| self.commitments.remove(&k))
-                .collect()
-        };
+        let selected: Vec<ThreadId> = self
+            .pending_commitments()
+            .into_iter()
+            .take(max_commitments)
+            .map(|p| p.commitment.thread_id)
+            .collect();
+        let commitments: Vec<CommitmentUpdate> = selected
+            .into_iter()
+            .filter_map(|k| self.commitments.remove(&k))
+            .map(|p| p.commitment)
+            .collect();
 
         let registrations = std::mem::take(&mut self.registrations);
         let anchors = std::mem::take(&mut self.anchors);
@@ -326,12 +562,26 @@ impl Mempool {
         let name_transfers = std::mem::take(&mut self.name_transfers);
         let name_record_updates = std::mem::take(&mut self.name_record_updates);
         let fraud_proofs = std::mem::take(&mut self.fraud_proofs);
-        let transfers = std::mem::take(&mut self.transfers);
+        let mut transfers = std::mem::take(&mut self.transfers);
+        transfers.sort_by_key(|t| t.timestamp);
         let token_definitions = std::mem::take(&mut self.token_definitions);
         let token_mints = std::mem::take(&mut self.token_mints);
         let token_burns = std::mem::take(&mut self.token_burns);
+        // Blocks must apply each token's updates in timestamp order.
+        let mut token_metadata_updates = std::mem::take(&mut self.token_metadata_updates);
+        token_metadata_updates.sort_by_key(|u| u.timestamp);
+        let mut updated_at: HashMap<TokenId, u64> = HashMap::new();
+        token_metadata_updates.retain(|u| {
+            updated_at
+                .insert(u.token_id, u.timestamp)
+                .is_none_or(|prev| u.timestamp > prev)
+        });
         let loom_deploys = std::mem::take(&mut self.loom_deploys);
+        let operator_handovers = std::mem::take(&mut self.operator_handovers);
+        let key_updates = std::mem::take(&mut self.key_updates);
         let stake_operations = std::mem::take(&mut self.stake_operations);
+        let slashing_evidence = std::mem::take(&mut self.slashing_evidence);
+        let spindle_operations = std::mem::take(&mut self.spindle_operations);
 
         BlockContents {
             commitments,
@@ -345,8 +595,13 @@ impl Mempool {
             token_definitions,
             token_mints,
             token_burns,
+            token_metadata_updates,
             loom_deploys,
+            operator_handovers,
+            key_updates,
             stake_operations,
+            slashing_evidence,
+            spindle_operations,
         }
     }
 
@@ -355,12 +610,44 @@ impl Mempool {
         self.commitments.len()
     }
 
+    /// Pending commitment updates, highest priority first.
+    pub fn pending_commitments(&self) -> Vec<&PendingCommitment> {
+        let mut pending: Vec<_> = self.commitments.values().collect();
+        pending.sort_by_key(|p| p.priority());
+        pending
+    }
+
+    /// Item counts, for inspection.
+    pub fn stats(&self) -> MempoolStats {
+        let commitments = self.commitments.len();
+        let transfers = self.transfers.len();
+        let registrations = self.registrations.len();
+        let evidence = self.fraud_proofs.len() + self.slashing_evidence.len();
+        let total = self.total_size();
+        MempoolStats {
+            total,
+            capacity: self.max_size,
+            commitments,
+            transfers,
+            registrations,
+            evidence,
+            other: total - commitments - transfers - registrations - evidence,
+            min_commitment_fee: self.commitments.values().map(|p| p.fee).min(),
+        }
+    }
+
     /// Whether the mempool has no pending items.
     pub fn is_empty(&self) -> bool {
         self.total_size() == 0
     }
 }
 
+/// Minimum fee for a same-version replacement of a commitment paying `fee`.
+fn replacement_fee(fee: Amount) -> Amount {
+    let bump = fee.saturating_mul(MEMPOOL_REPLACEMENT_FEE_BUMP_PERCENT) / 100;
+    fee.saturating_add(bump.max(1))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -375,6 +662,7 @@ mod tests {
             prev_commitment_hash: [0u8; 32],
             knot_count: 1,
             timestamp: 1000,
+            priority_fee: 0,
             signature: [0u8; 64],
         }
     }
@@ -393,7 +681,7 @@ mod tests {
     fn test_add_and_drain() {
         let mut pool = Mempool::new(100);
         let c = make_commitment([1u8; 20], 1);
-        pool.add_commitment(c).unwrap();
+        pool.add_commitment(c, 100).unwrap();
 
         let r = make_registration([2u8; 20]);
         pool.add_registration(r).unwrap();
@@ -411,8 +699,8 @@ mod tests {
     fn test_dedup_by_thread_id() {
         let mut pool = Mempool::new(100);
         let tid = [1u8; 20];
-        pool.add_commitment(make_commitment(tid, 1)).unwrap();
-        pool.add_commitment(make_commitment(tid, 2)).unwrap();
+        pool.add_commitment(make_commitment(tid, 1), 100).unwrap();
+        pool.add_commitment(make_commitment(tid, 2), 100).unwrap();
 
         assert_eq!(pool.commitment_count(), 1);
 
@@ -424,7 +712,8 @@ mod tests {
     #[test]
     fn test_capacity_limits() {
         let mut pool = Mempool::new(2);
-        pool.add_commitment(make_commitment([1u8; 20], 1)).unwrap();
+        pool.add_commitment(make_commitment([1u8; 20], 1), 100)
+            .unwrap();
         pool.add_registration(make_registration([2u8; 20])).unwrap();
 
         // Pool is full (2 items).
@@ -435,11 +724,13 @@ mod tests {
     #[test]
     fn test_dedup_does_not_count_as_new() {
         let mut pool = Mempool::new(2);
-        pool.add_commitment(make_commitment([1u8; 20], 1)).unwrap();
+        pool.add_commitment(make_commitment([1u8; 20], 1), 100)
+            .unwrap();
         pool.add_registration(make_registration([2u8; 20])).unwrap();
 
         // Replacing an existing commitment should work even at capacity.
-        pool.add_commitment(make_commitment([1u8; 20], 2)).unwrap();
+        pool.add_commitment(make_commitment([1u8; 20], 2), 100)
+            .unwrap();
         assert_eq!(pool.commitment_count(), 1);
     }
 
@@ -449,7 +740,7 @@ mod tests {
         for i in 0..10u8 {
             let mut tid = [0u8; 20];
             tid[0] = i;
-            pool.add_commitment(make_commitment(tid, 1)).unwrap();
+            pool.add_commitment(make_commitment(tid, 1), 100).unwrap();
         }
 
         let contents = pool.drain_for_block(3);
@@ -471,7 +762,7 @@ mod tests {
         for i in 0..10u8 {
             let mut tid = [0u8; 20];
             tid[0] = i;
-            pool.add_commitment(make_commitment(tid, 1)).unwrap();
+            pool.add_commitment(make_commitment(tid, 1), 100).unwrap();
         }
         assert_eq!(pool.commitment_count(), 10);
 
@@ -481,6 +772,105 @@ mod tests {
         assert_eq!(pool.commitment_count(), 7);
     }
 
+    #[test]
+    fn test_drain_orders_by_fee() {
+        let mut pool = Mempool::new(100);
+        pool.add_commitment(make_commitment([1u8; 20], 1), 100)
+            .unwrap();
+        pool.add_commitment(make_commitment([2u8; 20], 1), 300)
+            .unwrap();
+        pool.add_commitment(make_commitment([3u8; 20], 1), 100)
+            .unwrap();
+        pool.add_commitment(make_commitment([4u8; 20], 1), 200)
+            .unwrap();
+
+        // Highest fee first, then earliest arrival.
+        let contents = pool.drain_for_block(3);
+        let order: Vec<u8> = contents
+            .commitments
+            .iter()
+            .map(|c| c.thread_id[0])
+            .collect();
+        assert_eq!(order, vec![2, 4, 1]);
+        assert_eq!(
+            pool.pending_commitments()[0].commitment.thread_id,
+            [3u8; 20]
+        );
+    }
+
+    #[test]
+    fn test_replace_by_fee() {
+        let mut pool = Mempool::new(100);
+        let tid = [1u8; 20];
+        pool.add_commitment(make_commitment(tid, 3), 100).unwrap();
+
+        // Same version, different commitment: needs a 10% fee bump.
+        let mut rival = make_commitment(tid, 3);
+        rival.signature = [1u8; 64];
+        assert!(matches!(
+            pool.add_commitment(rival.clone(), 105),
+            Err(WeaveError::ReplacementUnderpriced { min_fee: 110, .. })
+        ));
+        pool.add_commitment(rival, 110).unwrap();
+        assert_eq!(pool.pending_commitments()[0].fee, 110);
+
+        // Older versions never replace; newer ones always do.
+        assert!(pool.add_commitment(make_commitment(tid, 2), 1_000).is_err());
+        pool.add_commitment(make_commitment(tid, 4), 50).unwrap();
+        let pending = pool.pending_commitments();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].commitment.version, 4);
+    }
+
+    #[test]
+    fn test_eviction_when_full() {
+        let mut pool = Mempool::new(2);
+        pool.add_commitment(make_commitment([1u8; 20], 1), 100)
+            .unwrap();
+        pool.add_commitment(make_commitment([2u8; 20], 1), 200)
+            .unwrap();
+
+        // Not outbidding the cheapest pending commitment.
+        assert!(matches!(
+            pool.add_commitment(make_commitment([3u8; 20], 1), 100),
+            Err(WeaveError::MempoolFull)
+        ));
+        // Outbidding it evicts it.
+        pool.add_commitment(make_commitment([3u8; 20], 1), 150)
+            .unwrap();
+        assert_eq!(pool.stats().min_commitment_fee, Some(150));
+        assert!(pool
+            .pending_commitments()
+            .iter()
+            .all(|p| p.commitment.thread_id != [1u8; 20]));
+
+        // Fraud proofs are never crowded out by commitments.
+        pool.add_fraud_proof(FraudProofSubmission {
+            proof: norn_types::fraud::FraudProof::StaleCommit {
+                thread_id: [9u8; 20],
+                commitment: Box::new(make_commitment([9u8; 20], 1)),
+                checkpoint: None,
+                missing_knots: vec![],
+            },
+            submitter: [0u8; 32],
+            timestamp: 1000,
+            signature: [0u8; 64],
+        })
+        .unwrap();
+        let stats = pool.stats();
+        assert_eq!((stats.commitments, stats.evidence), (1, 1));
+    }
+
+    #[test]
+    fn test_evict_expired() {
+        let mut pool = Mempool::new(100);
+        pool.add_commitment(make_commitment([1u8; 20], 1), 100)
+            .unwrap();
+        assert_eq!(pool.evict_expired(1000 + MAX_COMMITMENT_AGE), 0);
+        assert_eq!(pool.evict_expired(1001 + MAX_COMMITMENT_AGE), 1);
+        assert!(pool.is_empty());
+    }
+
     #[test]
     fn test_name_registration_dedup() {
         let mut pool = Mempool::new(100);
@@ -0,0 +1,209 @@
+use norn_crypto::keys::verify;
+use norn_types::consensus::{vote_signing_data, Vote};
+use norn_types::primitives::PublicKey;
+use norn_types::slashing::SlashingEvidence;
+use norn_types::weave::BlockHeader;
+
+use crate::error::WeaveError;
+use crate::staking::StakingState;
+
+/// Check that slashing evidence proves its offender signed two conflicting
+/// messages. Returns the offender's public key.
+pub fn verify_slashing_evidence(evidence: &SlashingEvidence) -> Result<PublicKey, WeaveError> {
+    match evidence {
+        SlashingEvidence::DoubleVote { vote_a, vote_b } => {
+            if vote_a.voter != vote_b.voter {
+                return Err(invalid("votes are from different validators"));
+            }
+            if vote_a.view != vote_b.view {
+                return Err(invalid("votes are for different views"));
+            }
+            if vote_a.block_hash == vote_b.block_hash {
+                return Err(invalid("votes are for the same block"));
+            }
+            verify_vote(vote_a)?;
+            verify_vote(vote_b)?;
+            Ok(vote_a.voter)
+        }
+        SlashingEvidence::DoubleSign {
+            validator,
+            header_a,
+            header_b,
+        } => {
+            if header_a.height != header_b.height {
+                return Err(invalid("headers are at different heights"));
+            }
+            if header_a.hash == header_b.hash {
+                return Err(invalid("headers are for the same block"));
+            }
+            verify_header_signature(header_a, validator)?;
+            verify_header_signature(header_b, validator)?;
+            Ok(*validator)
+        }
+    }
+}
+
+/// Validate slashing evidence for inclusion in a block: it must be genuine
+/// and accuse a staked validator that has not already been slashed for
+/// double-signing.
+pub fn validate_slashing_evidence(
+    evidence: &SlashingEvidence,
+    staking: &StakingState,
+) -> Result<PublicKey, WeaveError> {
+    let offender = verify_slashing_evidence(evidence)?;
+    if staking.validator_stake(&offender).is_none() {
+        return Err(invalid("offender is not a validator"));
+    }
+    if staking.is_tombstoned(&offender) {
+        return Err(invalid("offender was already slashed for double-signing"));
+    }
+    Ok(offender)
+}
+
+fn verify_vote(vote: &Vote) -> Result<(), WeaveError> {
+    let sig_data = vote_signing_data(vote.phase, vote.view, &vote.block_hash);
+    verify(&sig_data, &vote.signature, &vote.voter).map_err(|_| invalid("invalid vote signature"))
+}
+
+fn verify_header_signature(header: &BlockHeader, validator: &PublicKey) -> Result<(), WeaveError> {
+    if header.compute_hash() != header.hash {
+        return Err(invalid("header hash does not match its contents"));
+    }
+    if header.proposer != *validator {
+        return Err(invalid("header is not signed by the validator"));
+    }
+    verify(&header.hash, &header.proposer_signature, validator)
+        .map_err(|_| invalid("invalid header signature"))
+}
+
+fn invalid(reason: &str) -> WeaveError {
+    WeaveError::InvalidSlashingEvidence {
+        reason: reason.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::build_block;
+    use crate::mempool::BlockContents;
+    use norn_crypto::keys::Keypair;
+    use norn_types::consensus::ConsensusPhase;
+    use norn_types::primitives::Hash;
+
+    fn make_vote(kp: &Keypair, view: u64, block_hash: Hash) -> Box<Vote> {
+        Box::new(Vote {
+            view,
+            phase: ConsensusPhase::Prepare,
+            block_hash,
+            voter: kp.public_key(),
+            signature: kp.sign(&vote_signing_data(
+                ConsensusPhase::Prepare,
+                view,
+                &block_hash,
+            )),
+            bls_signature: [0u8; 96],
+        })
+    }
+
+    fn make_header(kp: &Keypair, timestamp: u64) -> Box<BlockHeader> {
+        let block = build_block(
+            [0u8; 32],
+            4,
+            BlockContents::default(),
+            kp,
+            timestamp,
+            [0u8; 32],
+            [0u8; 32],
+            100,
+        );
+        Box::new(block.header())
+    }
+
+    #[test]
+    fn test_verify_double_vote() {
+        let kp = Keypair::generate();
+        let evidence = SlashingEvidence::DoubleVote {
+            vote_a: make_vote(&kp, 3, [1u8; 32]),
+            vote_b: make_vote(&kp, 3, [2u8; 32]),
+        };
+        assert_eq!(
+            verify_slashing_evidence(&evidence).unwrap(),
+            kp.public_key()
+        );
+
+        let same_block = SlashingEvidence::DoubleVote {
+            vote_a: make_vote(&kp, 3, [1u8; 32]),
+            vote_b: make_vote(&kp, 3, [1u8; 32]),
+        };
+        assert!(verify_slashing_evidence(&same_block).is_err());
+
+        let other_view = SlashingEvidence::DoubleVote {
+            vote_a: make_vote(&kp, 3, [1u8; 32]),
+            vote_b: make_vote(&kp, 4, [2u8; 32]),
+        };
+        assert!(verify_slashing_evidence(&other_view).is_err());
+
+        let mut forged = make_vote(&kp, 3, [2u8; 32]);
+        forged.signature = [0u8; 64];
+        let forged = SlashingEvidence::DoubleVote {
+            vote_a: make_vote(&kp, 3, [1u8; 32]),
+            vote_b: forged,
+        };
+        assert!(verify_slashing_evidence(&forged).is_err());
+    }
+
+    #[test]
+    fn test_verify_double_sign() {
+        let kp = Keypair::generate();
+        let evidence = SlashingEvidence::DoubleSign {
+            validator: kp.public_key(),
+            header_a: make_header(&kp, 1000),
+            header_b: make_header(&kp, 1001),
+        };
+        assert_eq!(
+            verify_slashing_evidence(&evidence).unwrap(),
+            kp.public_key()
+        );
+
+        // Signed by someone else.
+        let other = Keypair::generate();
+        let wrong_signer = SlashingEvidence::DoubleSign {
+            validator: kp.public_key(),
+            header_a: make_header(&kp, 1000),
+            header_b: make_header(&other, 1001),
+        };
+        assert!(verify_slashing_evidence(&wrong_signer).is_err());
+
+        // A header whose contents no longer match its hash.
+        let mut tampered = make_header(&kp, 1001);
+        tampered.timestamp = 2000;
+        let tampered = SlashingEvidence::DoubleSign {
+            validator: kp.public_key(),
+            header_a: make_header(&kp, 1000),
+            header_b: tampered,
+        };
+        assert!(verify_slashing_evidence(&tampered).is_err());
+    }
+
+    #[test]
+    fn test_validate_rejects_unknown_and_tombstoned() {
+        let kp = Keypair::generate();
+        let evidence = SlashingEvidence::DoubleVote {
+            vote_a: make_vote(&kp, 3, [1u8; 32]),
+            vote_b: make_vote(&kp, 3, [2u8; 32]),
+        };
+
+        let mut staking = StakingState::new(100, 10).with_epoch_length(10);
+        assert!(validate_slashing_evidence(&evidence, &staking).is_err());
+
+        staking
+            .stake(kp.public_key(), [1u8; 48], [1u8; 20], 1_000)
+            .unwrap();
+        staking.activate_pending_stakes(0);
+        assert!(validate_slashing_evidence(&evidence, &staking).is_ok());
+
+        staking.apply_evidence(&evidence, 5).unwrap();
+        assert!(validate_slashing_evidence(&evidence, &staking).is_err());
+    }
+}
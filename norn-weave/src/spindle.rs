@@ -0,0 +1,153 @@
+use norn_crypto::hash::blake3_hash;
+use norn_crypto::keys::verify;
+use norn_types::constants::MAX_SPINDLE_SUBSCRIPTION_PERIODS;
+use norn_types::spindle::SpindleOperation;
+
+use crate::error::WeaveError;
+
+/// Compute the signing data for a spindle market operation.
+pub fn spindle_operation_signing_data(op: &SpindleOperation) -> Vec<u8> {
+    let mut data = Vec::new();
+    match op {
+        SpindleOperation::Bond {
+            spindle,
+            amount,
+            fee_per_period,
+            timestamp,
+            ..
+        } => {
+            data.extend_from_slice(spindle);
+            data.extend_from_slice(&amount.to_le_bytes());
+            data.extend_from_slice(&fee_per_period.to_le_bytes());
+            data.extend_from_slice(&timestamp.to_le_bytes());
+            data.extend_from_slice(b"spindle_bond");
+        }
+        SpindleOperation::Unbond {
+            spindle,
+            amount,
+            timestamp,
+            ..
+        } => {
+            data.extend_from_slice(spindle);
+            data.extend_from_slice(&amount.to_le_bytes());
+            data.extend_from_slice(&timestamp.to_le_bytes());
+            data.extend_from_slice(b"spindle_unbond");
+        }
+        SpindleOperation::Subscribe {
+            subscriber,
+            spindle,
+            periods,
+            max_fee_per_period,
+            timestamp,
+            ..
+        } => {
+            data.extend_from_slice(subscriber);
+            data.extend_from_slice(spindle);
+            data.extend_from_slice(&periods.to_le_bytes());
+            data.extend_from_slice(&max_fee_per_period.to_le_bytes());
+            data.extend_from_slice(&timestamp.to_le_bytes());
+            data.extend_from_slice(b"spindle_subscribe");
+        }
+        SpindleOperation::Unsubscribe {
+            subscriber,
+            spindle,
+            timestamp,
+            ..
+        } => {
+            data.extend_from_slice(subscriber);
+            data.extend_from_slice(spindle);
+            data.extend_from_slice(&timestamp.to_le_bytes());
+            data.extend_from_slice(b"spindle_unsubscribe");
+        }
+    }
+    blake3_hash(&data).to_vec()
+}
+
+/// Validate a spindle market operation's signature and parameters.
+///
+/// Whether the operation can be applied (bond size, balances, existing
+/// subscriptions) depends on node state and is checked when the block is
+/// applied.
+pub fn validate_spindle_operation(op: &SpindleOperation) -> Result<(), WeaveError> {
+    let sig_data = spindle_operation_signing_data(op);
+    verify(&sig_data, op.signature(), op.signer()).map_err(|_| invalid("invalid signature"))?;
+    match op {
+        SpindleOperation::Bond {
+            amount,
+            fee_per_period,
+            ..
+        } => {
+            if *amount == 0 && *fee_per_period == 0 {
+                return Err(invalid("bond must add stake or set a fee"));
+            }
+        }
+        SpindleOperation::Unbond { amount, .. } => {
+            if *amount == 0 {
+                return Err(invalid("unbond amount must be positive"));
+            }
+        }
+        SpindleOperation::Subscribe {
+            subscriber,
+            spindle,
+            periods,
+            ..
+        } => {
+            if subscriber == spindle {
+                return Err(invalid("cannot subscribe to yourself"));
+            }
+            if *periods == 0 || *periods > MAX_SPINDLE_SUBSCRIPTION_PERIODS {
+                return Err(invalid(&format!(
+                    "periods must be between 1 and {}",
+                    MAX_SPINDLE_SUBSCRIPTION_PERIODS
+                )));
+            }
+        }
+        SpindleOperation::Unsubscribe { .. } => {}
+    }
+    Ok(())
+}
+
+fn invalid(reason: &str) -> WeaveError {
+    WeaveError::InvalidSpindleOperation {
+        reason: reason.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use norn_crypto::keys::Keypair;
+
+    fn subscribe(kp: &Keypair, spindle: &Keypair, periods: u64) -> SpindleOperation {
+        let mut op = SpindleOperation::Subscribe {
+            subscriber: kp.public_key(),
+            spindle: spindle.public_key(),
+            periods,
+            max_fee_per_period: 10,
+            timestamp: 1000,
+            signature: [0u8; 64],
+        };
+        let signature = kp.sign(&spindle_operation_signing_data(&op));
+        if let SpindleOperation::Subscribe { signature: s, .. } = &mut op {
+            *s = signature;
+        }
+        op
+    }
+
+    #[test]
+    fn test_validate_subscribe() {
+        let kp = Keypair::generate();
+        let spindle = Keypair::generate();
+        assert!(validate_spindle_operation(&subscribe(&kp, &spindle, 30)).is_ok());
+        assert!(validate_spindle_operation(&subscribe(&kp, &spindle, 0)).is_err());
+        assert!(validate_spindle_operation(&subscribe(&kp, &kp, 30)).is_err());
+
+        // Signed by the spindle rather than the subscriber.
+        let mut forged = subscribe(&kp, &spindle, 30);
+        let forged_sig = spindle.sign(&spindle_operation_signing_data(&forged));
+        if let SpindleOperation::Subscribe { signature, .. } = &mut forged {
+            *signature = forged_sig;
+        }
+        assert!(validate_spindle_operation(&forged).is_err());
+    }
+}
@@ -1,8 +1,15 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
+use norn_crypto::address::pubkey_to_address;
+use norn_crypto::bls::verify_possession;
 use norn_crypto::hash::blake3_hash;
 use norn_crypto::keys::verify;
+use norn_types::constants::{
+    BLOCKS_PER_EPOCH, DEFAULT_COMMISSION_BPS, DOUBLE_SIGN_SLASH_BPS, DOWNTIME_JAIL_BLOCKS,
+    DOWNTIME_SLASH_BPS, DOWNTIME_WINDOW_ROUNDS, MAX_COMMISSION_BPS, MAX_VALIDATORS,
+};
 use norn_types::primitives::*;
+use norn_types::slashing::{SlashReason, SlashRecord, SlashingEvidence};
 use norn_types::weave::{StakeOperation, Validator, ValidatorSet};
 
 use crate::error::WeaveError;
@@ -11,17 +18,74 @@ use crate::error::WeaveError;
 #[derive(Debug, Clone)]
 struct ValidatorStake {
     pubkey: PublicKey,
+    bls_pubkey: BlsPublicKey,
     address: Address,
     stake: Amount,
+    pending_stake: Amount,                  // bonded at the next epoch boundary
     pending_unstake: Option<(Amount, u64)>, // (amount, effective_height)
+    commission_bps: u16,
+    pending_commission: Option<u16>, // applied at the next epoch boundary
+    jailed_until: Option<u64>,       // u64::MAX once tombstoned
+    last_active: u64,                // last proposal, or when it joined the active set
+}
+
+/// A delegator's stake with one validator.
+#[derive(Debug, Clone, Default)]
+struct Delegation {
+    amount: Amount,
+    pending: Amount,                           // bonded at the next epoch boundary
+    pending_undelegate: Option<(Amount, u64)>, // (amount, effective_height)
+}
+
+/// A snapshot of one delegation, as reported by [`StakingState::delegations_of`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DelegationInfo {
+    /// Validator the stake is delegated to.
+    pub validator: PublicKey,
+    /// Delegator public key.
+    pub delegator: PublicKey,
+    /// Bonded amount.
+    pub amount: Amount,
+    /// Amount bonded at the next epoch boundary.
+    pub pending: Amount,
+    /// Amount waiting to be released after the bonding period.
+    pub pending_undelegate: Amount,
 }
 
 /// Staking state tracking validator stakes, bonding periods, and slashing.
+///
+/// Stake and unstake operations take effect at epoch boundaries, where the
+/// active validator set is recomputed: the `max_validators` largest stakes at
+/// or above the minimum. Between boundaries the set only shrinks, when a
+/// validator is slashed below the minimum.
+///
+/// Token holders can delegate to validators. Delegations bond and unbond on
+/// the same schedule as self-stake and count towards the validator's weight
+/// in the active set, while eligibility only depends on self-stake. Epoch
+/// rewards are shared between a validator and its delegators in proportion
+/// to their bonded stake, after the validator's commission.
+///
+/// Validators are slashed for double-signing, which burns part of their
+/// stake and delegations and removes them for good, and for downtime, which
+/// burns a smaller share and jails them until they unjail. A jailed
+/// validator leaves the active set at once and is skipped at later epoch
+/// boundaries.
 #[derive(Debug, Clone)]
 pub struct StakingState {
     validators: BTreeMap<PublicKey, ValidatorStake>,
+    /// Delegations keyed by (validator, delegator).
+    delegations: BTreeMap<(PublicKey, PublicKey), Delegation>,
     bonding_period: u64,
     min_stake: Amount,
+    epoch_length: u64,
+    max_validators: usize,
+    /// The active validator set of the current epoch.
+    active: ValidatorSet,
+    /// Validators slashed for double-signing, which can never stake again.
+    tombstoned: BTreeSet<PublicKey>,
+    slash_history: Vec<SlashRecord>,
+    downtime_window_rounds: u64,
+    downtime_jail_blocks: u64,
 }
 
 impl StakingState {
@@ -29,15 +93,45 @@ impl StakingState {
     pub fn new(min_stake: Amount, bonding_period: u64) -> Self {
         Self {
             validators: BTreeMap::new(),
+            delegations: BTreeMap::new(),
             bonding_period,
             min_stake,
+            epoch_length: BLOCKS_PER_EPOCH,
+            max_validators: MAX_VALIDATORS,
+            active: ValidatorSet::new(0),
+            tombstoned: BTreeSet::new(),
+            slash_history: Vec::new(),
+            downtime_window_rounds: DOWNTIME_WINDOW_ROUNDS,
+            downtime_jail_blocks: DOWNTIME_JAIL_BLOCKS,
         }
     }
 
-    /// Stake tokens to become or increase stake as a validator.
+    /// Set the number of blocks per epoch (at least one).
+    pub fn with_epoch_length(mut self, epoch_length: u64) -> Self {
+        self.epoch_length = epoch_length.max(1);
+        self
+    }
+
+    /// Set the maximum size of the active validator set (at least one).
+    pub fn with_max_validators(mut self, max_validators: usize) -> Self {
+        self.max_validators = max_validators.max(1);
+        self
+    }
+
+    /// Set how many full leader rotations a validator may miss before it is
+    /// jailed for downtime, and how many blocks it then stays jailed.
+    pub fn with_downtime(mut self, window_rounds: u64, jail_blocks: u64) -> Self {
+        self.downtime_window_rounds = window_rounds.max(1);
+        self.downtime_jail_blocks = jail_blocks;
+        self
+    }
+
+    /// Stake tokens to become or increase stake as a validator. The stake is
+    /// bonded at the next epoch boundary.
     pub fn stake(
         &mut self,
         pubkey: PublicKey,
+        bls_pubkey: BlsPublicKey,
         address: Address,
         amount: Amount,
     ) -> Result<(), WeaveError> {
@@ -46,35 +140,151 @@ impl StakingState {
                 reason: "stake amount must be positive".to_string(),
             });
         }
+        if self.tombstoned.contains(&pubkey) {
+            return Err(WeaveError::StakingError {
+                reason: "validator was slashed for double-signing".to_string(),
+            });
+        }
+
+        let (stake, pending) = self
+            .validators
+            .get(&pubkey)
+            .map(|v| (v.stake, v.pending_stake))
+            .unwrap_or((0, 0));
+        let total = stake.saturating_add(pending).saturating_add(amount);
+        if total < self.min_stake {
+            return Err(WeaveError::StakingError {
+                reason: format!("total stake {} below minimum {}", total, self.min_stake),
+            });
+        }
+
+        if self
+            .validators
+            .get(&pubkey)
+            .is_some_and(|v| v.bls_pubkey != bls_pubkey)
+        {
+            return Err(WeaveError::StakingError {
+                reason: "BLS public key differs from the registered key".to_string(),
+            });
+        }
 
         let entry = self.validators.entry(pubkey).or_insert(ValidatorStake {
             pubkey,
+            bls_pubkey,
             address,
             stake: 0,
+            pending_stake: 0,
             pending_unstake: None,
+            commission_bps: DEFAULT_COMMISSION_BPS,
+            pending_commission: None,
+            jailed_until: None,
+            last_active: 0,
         });
+        entry.pending_stake = entry.pending_stake.saturating_add(amount);
+
+        Ok(())
+    }
+
+    /// Delegate tokens to a validator. The delegation is bonded at the next
+    /// epoch boundary.
+    pub fn delegate(
+        &mut self,
+        delegator: PublicKey,
+        validator: PublicKey,
+        amount: Amount,
+    ) -> Result<(), WeaveError> {
+        if amount == 0 {
+            return Err(WeaveError::StakingError {
+                reason: "delegation amount must be positive".to_string(),
+            });
+        }
+        if !self.validators.contains_key(&validator) {
+            return Err(WeaveError::StakingError {
+                reason: "validator not found".to_string(),
+            });
+        }
+        if self.tombstoned.contains(&validator) {
+            return Err(WeaveError::StakingError {
+                reason: "validator was slashed for double-signing".to_string(),
+            });
+        }
+
+        let entry = self.delegations.entry((validator, delegator)).or_default();
+        entry.pending = entry.pending.saturating_add(amount);
+        Ok(())
+    }
 
-        entry.stake = entry.stake.saturating_add(amount);
+    /// Request to withdraw bonded delegated tokens. Like unstaking, they stay
+    /// bonded for the bonding period and are released at the first epoch
+    /// boundary after it.
+    pub fn undelegate(
+        &mut self,
+        delegator: &PublicKey,
+        validator: &PublicKey,
+        amount: Amount,
+        current_height: u64,
+    ) -> Result<(), WeaveError> {
+        let effective_height = self.next_epoch_boundary(current_height + self.bonding_period);
+        let entry = self
+            .delegations
+            .get_mut(&(*validator, *delegator))
+            .ok_or_else(|| WeaveError::StakingError {
+                reason: "delegation not found".to_string(),
+            })?;
 
-        if entry.stake < self.min_stake {
+        let pending = entry
+            .pending_undelegate
+            .as_ref()
+            .map(|(a, _)| *a)
+            .unwrap_or(0);
+        let available = entry.amount.saturating_sub(pending);
+        if amount == 0 || amount > available {
             return Err(WeaveError::StakingError {
                 reason: format!(
-                    "total stake {} below minimum {}",
-                    entry.stake, self.min_stake
+                    "invalid undelegate amount {}: available {} (delegated {} - pending {})",
+                    amount, available, entry.amount, pending
                 ),
             });
         }
 
+        entry.pending_undelegate = Some((pending.saturating_add(amount), effective_height));
+        Ok(())
+    }
+
+    /// Set a validator's commission. The new rate applies from the next epoch
+    /// boundary, so delegators are never charged a rate they did not see.
+    pub fn set_commission(
+        &mut self,
+        pubkey: &PublicKey,
+        commission_bps: u16,
+    ) -> Result<(), WeaveError> {
+        if commission_bps > MAX_COMMISSION_BPS {
+            return Err(WeaveError::StakingError {
+                reason: format!(
+                    "commission {} bps exceeds maximum {}",
+                    commission_bps, MAX_COMMISSION_BPS
+                ),
+            });
+        }
+        let entry = self
+            .validators
+            .get_mut(pubkey)
+            .ok_or_else(|| WeaveError::StakingError {
+                reason: "validator not found".to_string(),
+            })?;
+        entry.pending_commission = Some(commission_bps);
         Ok(())
     }
 
-    /// Request to unstake tokens (subject to bonding period).
+    /// Request to unstake bonded tokens. They stay bonded for the bonding
+    /// period and are released at the first epoch boundary after it.
     pub fn unstake(
         &mut self,
         pubkey: &PublicKey,
         amount: Amount,
         current_height: u64,
     ) -> Result<(), WeaveError> {
+        let effective_height = self.next_epoch_boundary(current_height + self.bonding_period);
         let entry = self
             .validators
             .get_mut(pubkey)
@@ -93,7 +303,6 @@ impl StakingState {
             });
         }
 
-        let effective_height = current_height + self.bonding_period;
         // Accumulate pending unstakes.
         match &entry.pending_unstake {
             Some((existing_amount, _)) => {
@@ -108,7 +317,8 @@ impl StakingState {
         Ok(())
     }
 
-    /// Slash a validator's stake.
+    /// Slash a validator's stake. A validator slashed below the minimum
+    /// leaves the active set immediately.
     pub fn slash(&mut self, pubkey: &PublicKey, slash_amount: Amount) -> Result<(), WeaveError> {
         let entry = self
             .validators
@@ -129,78 +339,412 @@ impl StakingState {
             }
         }
 
+        let stake = entry.stake;
+        let weight = stake.saturating_add(self.delegated_stake(pubkey));
+        if let Some(v) = self
+            .active
+            .validators
+            .iter_mut()
+            .find(|v| v.pubkey == *pubkey)
+        {
+            v.stake = weight;
+        }
+        if stake < self.min_stake {
+            self.active.validators.retain(|v| v.pubkey != *pubkey);
+        }
+        self.active.total_stake = self.active.validators.iter().map(|v| v.stake).sum();
+
         Ok(())
     }
 
-    /// Process pending unstakes at the given block height.
-    /// Returns the public keys of validators that were removed (stake dropped to zero or below min).
+    /// Slash the validator accused by `evidence` at `height`: burn
+    /// [`DOUBLE_SIGN_SLASH_BPS`] of its stake and of its delegations, and jail
+    /// it for good. The evidence itself is checked by
+    /// [`crate::slashing::validate_slashing_evidence`].
+    pub fn apply_evidence(
+        &mut self,
+        evidence: &SlashingEvidence,
+        height: u64,
+    ) -> Result<SlashRecord, WeaveError> {
+        let offender = *evidence.offender();
+        if self.tombstoned.contains(&offender) {
+            return Err(WeaveError::StakingError {
+                reason: "validator was already slashed for double-signing".to_string(),
+            });
+        }
+        if !self.validators.contains_key(&offender) {
+            return Err(WeaveError::StakingError {
+                reason: "validator not found".to_string(),
+            });
+        }
+
+        let amount = self.burn_stake(&offender, DOUBLE_SIGN_SLASH_BPS);
+        self.tombstoned.insert(offender);
+        self.jail(&offender, u64::MAX);
+        Ok(self.record_slash(offender, SlashReason::DoubleSign, amount, height, None))
+    }
+
+    /// Release a validator jailed for downtime once its jail period is over.
+    /// It rejoins the active set at the next epoch boundary.
+    pub fn unjail(&mut self, pubkey: &PublicKey, current_height: u64) -> Result<(), WeaveError> {
+        if self.tombstoned.contains(pubkey) {
+            return Err(WeaveError::StakingError {
+                reason: "validator was slashed for double-signing".to_string(),
+            });
+        }
+        let entry = self
+            .validators
+            .get_mut(pubkey)
+            .ok_or_else(|| WeaveError::StakingError {
+                reason: "validator not found".to_string(),
+            })?;
+        match entry.jailed_until {
+            None => Err(WeaveError::StakingError {
+                reason: "validator is not jailed".to_string(),
+            }),
+            Some(until) if current_height < until => Err(WeaveError::StakingError {
+                reason: format!("validator is jailed until height {}", until),
+            }),
+            Some(_) => {
+                entry.jailed_until = None;
+                Ok(())
+            }
+        }
+    }
+
+    /// Burn `bps` basis points of a validator's bonded stake and of every
+    /// delegation to it. Returns the total burned.
+    fn burn_stake(&mut self, pubkey: &PublicKey, bps: u16) -> Amount {
+        let mut burned: Amount = 0;
+        if let Some(entry) = self.validators.get_mut(pubkey) {
+            let cut = entry.stake.saturating_mul(bps as Amount) / 10_000;
+            entry.stake -= cut;
+            entry.pending_unstake = entry
+                .pending_unstake
+                .map(|(amount, height)| (amount.min(entry.stake), height))
+                .filter(|(amount, _)| *amount > 0);
+            burned = burned.saturating_add(cut);
+        }
+        for (_, d) in self
+            .delegations
+            .range_mut((*pubkey, [0u8; 32])..=(*pubkey, [0xffu8; 32]))
+        {
+            let cut = d.amount.saturating_mul(bps as Amount) / 10_000;
+            d.amount -= cut;
+            d.pending_undelegate = d
+                .pending_undelegate
+                .map(|(amount, height)| (amount.min(d.amount), height))
+                .filter(|(amount, _)| *amount > 0);
+            burned = burned.saturating_add(cut);
+        }
+        burned
+    }
+
+    /// Jail a validator until `until` and drop it from the active set.
+    fn jail(&mut self, pubkey: &PublicKey, until: u64) {
+        if let Some(entry) = self.validators.get_mut(pubkey) {
+            entry.jailed_until = Some(until);
+        }
+        self.active.validators.retain(|v| v.pubkey != *pubkey);
+        self.active.total_stake = self.active.validators.iter().map(|v| v.stake).sum();
+    }
+
+    fn record_slash(
+        &mut self,
+        validator: PublicKey,
+        reason: SlashReason,
+        amount: Amount,
+        height: u64,
+        jailed_until: Option<u64>,
+    ) -> SlashRecord {
+        let record = SlashRecord {
+            validator,
+            reason,
+            amount,
+            height,
+            jailed_until,
+        };
+        self.slash_history.push(record.clone());
+        record
+    }
+
+    /// Record that `proposer` produced the block at `height`, then jail every
+    /// active validator that has not proposed for `downtime_window_rounds`
+    /// full leader rotations, burning [`DOWNTIME_SLASH_BPS`] of its stake.
+    fn check_downtime(&mut self, proposer: &PublicKey, height: u64) -> Vec<SlashRecord> {
+        if !self.active.contains(proposer) {
+            return Vec::new();
+        }
+        if let Some(entry) = self.validators.get_mut(proposer) {
+            entry.last_active = height;
+        }
+
+        let window = self
+            .downtime_window_rounds
+            .saturating_mul(self.active.len() as u64);
+        let offline: Vec<PublicKey> = self
+            .active
+            .validators
+            .iter()
+            .filter_map(|v| self.validators.get(&v.pubkey))
+            .filter(|v| height.saturating_sub(v.last_active) > window)
+            .map(|v| v.pubkey)
+            .collect();
+
+        offline
+            .into_iter()
+            .map(|pubkey| {
+                let amount = self.burn_stake(&pubkey, DOWNTIME_SLASH_BPS);
+                let until = height.saturating_add(self.downtime_jail_blocks);
+                self.jail(&pubkey, until);
+                self.record_slash(pubkey, SlashReason::Downtime, amount, height, Some(until))
+            })
+            .collect()
+    }
+
+    /// Apply the block at `height` proposed by `proposer`: its stake
+    /// operations, then its slashing evidence, then the downtime check, then
+    /// the epoch boundary if `height` is one. Returns the validators removed
+    /// from the staking state.
+    pub fn apply_block(
+        &mut self,
+        ops: &[StakeOperation],
+        evidence: &[SlashingEvidence],
+        proposer: &PublicKey,
+        height: u64,
+    ) -> Vec<PublicKey> {
+        for op in ops {
+            match op {
+                StakeOperation::Stake {
+                    pubkey,
+                    bls_pubkey,
+                    amount,
+                    ..
+                } => {
+                    let addr = pubkey_to_address(pubkey);
+                    if let Err(e) = self.stake(*pubkey, *bls_pubkey, addr, *amount) {
+                        tracing::debug!("stake operation failed: {}", e);
+                    }
+                }
+                StakeOperation::Unstake { pubkey, amount, .. } => {
+                    if let Err(e) = self.unstake(pubkey, *amount, height) {
+                        tracing::debug!("unstake operation failed: {}", e);
+                    }
+                }
+                StakeOperation::Delegate {
+                    delegator,
+                    validator,
+                    amount,
+                    ..
+                } => {
+                    if let Err(e) = self.delegate(*delegator, *validator, *amount) {
+                        tracing::debug!("delegate operation failed: {}", e);
+                    }
+                }
+                StakeOperation::Undelegate {
+                    delegator,
+                    validator,
+                    amount,
+                    ..
+                } => {
+                    if let Err(e) = self.undelegate(delegator, validator, *amount, height) {
+                        tracing::debug!("undelegate operation failed: {}", e);
+                    }
+                }
+                StakeOperation::SetCommission {
+                    pubkey,
+                    commission_bps,
+                    ..
+                } => {
+                    if let Err(e) = self.set_commission(pubkey, *commission_bps) {
+                        tracing::debug!("set commission operation failed: {}", e);
+                    }
+                }
+                StakeOperation::Unjail { pubkey, .. } => {
+                    if let Err(e) = self.unjail(pubkey, height) {
+                        tracing::debug!("unjail operation failed: {}", e);
+                    }
+                }
+            }
+        }
+        for e in evidence {
+            match self.apply_evidence(e, height) {
+                Ok(record) => tracing::warn!(
+                    validator = hex::encode(record.validator),
+                    amount = record.amount,
+                    "validator slashed for double-signing"
+                ),
+                Err(err) => tracing::debug!("slashing evidence not applied: {}", err),
+            }
+        }
+        for record in self.check_downtime(proposer, height) {
+            tracing::warn!(
+                validator = hex::encode(record.validator),
+                amount = record.amount,
+                jailed_until = record.jailed_until,
+                "validator jailed for downtime"
+            );
+        }
+        self.process_epoch(height)
+    }
+
+    /// Process the epoch boundary at `height`, if it is one: bond pending
+    /// stakes and delegations, release matured unstakes and undelegations,
+    /// apply commission changes, drop validators below the minimum, and
+    /// recompute the active set for the new epoch. Delegations to a dropped
+    /// validator stay in place but carry no weight until it rejoins.
+    /// Returns the public keys of validators that were removed.
     pub fn process_epoch(&mut self, current_height: u64) -> Vec<PublicKey> {
-        let mut removed = Vec::new();
+        if !self.is_epoch_boundary(current_height) {
+            return Vec::new();
+        }
 
-        for (_, entry) in self.validators.iter_mut() {
+        for entry in self.validators.values_mut() {
+            entry.stake = entry.stake.saturating_add(entry.pending_stake);
+            entry.pending_stake = 0;
             if let Some((amount, effective_height)) = entry.pending_unstake {
                 if current_height >= effective_height {
                     entry.stake = entry.stake.saturating_sub(amount);
                     entry.pending_unstake = None;
                 }
             }
+            if let Some(commission) = entry.pending_commission.take() {
+                entry.commission_bps = commission;
+            }
         }
-
-        // Remove validators whose stake dropped below the minimum.
-        let keys_to_check: Vec<PublicKey> = self.validators.keys().copied().collect();
-        for key in keys_to_check {
-            if let Some(entry) = self.validators.get(&key) {
-                if entry.stake < self.min_stake {
-                    removed.push(key);
+        for d in self.delegations.values_mut() {
+            d.amount = d.amount.saturating_add(d.pending);
+            d.pending = 0;
+            if let Some((amount, effective_height)) = d.pending_undelegate {
+                if current_height >= effective_height {
+                    d.amount = d.amount.saturating_sub(amount);
+                    d.pending_undelegate = None;
                 }
             }
         }
+        self.delegations
+            .retain(|_, d| d.amount > 0 || d.pending > 0 || d.pending_undelegate.is_some());
 
+        // Remove validators whose stake dropped below the minimum.
+        let removed: Vec<PublicKey> = self
+            .validators
+            .values()
+            .filter(|v| v.stake < self.min_stake)
+            .map(|v| v.pubkey)
+            .collect();
         for key in &removed {
             self.validators.remove(key);
         }
 
+        self.rotate(current_height);
         removed
     }
 
-    /// Get the current active validator set, sorted by stake descending.
-    pub fn active_validators(&self) -> ValidatorSet {
+    /// Bond all pending stakes now and recompute the active set for the epoch
+    /// containing `height`. Used when seeding validators from genesis.
+    pub fn activate_pending_stakes(&mut self, height: u64) {
+        for entry in self.validators.values_mut() {
+            entry.stake = entry.stake.saturating_add(entry.pending_stake);
+            entry.pending_stake = 0;
+        }
+        for d in self.delegations.values_mut() {
+            d.amount = d.amount.saturating_add(d.pending);
+            d.pending = 0;
+        }
+        self.rotate(height);
+    }
+
+    /// Install the validator set for the epoch containing `height`. Newly
+    /// active validators count as active from `height` for the downtime check.
+    fn rotate(&mut self, height: u64) {
+        let next = self.select_validators(height / self.epoch_length);
+        for v in &next.validators {
+            if self.active.contains(&v.pubkey) {
+                continue;
+            }
+            if let Some(entry) = self.validators.get_mut(&v.pubkey) {
+                entry.last_active = height;
+            }
+        }
+        self.active = next;
+    }
+
+    /// Rank validators by stake including delegations (descending, ties by
+    /// public key) and take the largest `max_validators` whose self-stake is
+    /// at or above the minimum, skipping jailed validators.
+    fn select_validators(&self, epoch: u64) -> ValidatorSet {
         let mut validators: Vec<Validator> = self
             .validators
             .values()
-            .filter(|v| v.stake >= self.min_stake)
+            .filter(|v| v.stake >= self.min_stake && v.jailed_until.is_none())
             .map(|v| Validator {
                 pubkey: v.pubkey,
+                bls_pubkey: v.bls_pubkey,
                 address: v.address,
-                stake: v.stake,
+                stake: v.stake.saturating_add(self.delegated_stake(&v.pubkey)),
                 active: true,
             })
             .collect();
 
-        validators.sort_by(|a, b| b.stake.cmp(&a.stake));
+        validators.sort_by(|a, b| b.stake.cmp(&a.stake).then(a.pubkey.cmp(&b.pubkey)));
+        validators.truncate(self.max_validators);
 
         let total_stake: Amount = validators.iter().map(|v| v.stake).sum();
 
         ValidatorSet {
             validators,
             total_stake,
-            epoch: 0,
+            epoch,
         }
     }
 
-    /// Check if a public key is an active validator.
+    /// Get the active validator set of the current epoch, sorted by stake descending.
+    pub fn active_validators(&self) -> ValidatorSet {
+        self.active.clone()
+    }
+
+    /// Every staker with bonded stake, sorted by stake including delegations
+    /// descending, marked active if in the current epoch's set.
+    pub fn candidates(&self) -> Vec<Validator> {
+        let mut validators: Vec<Validator> = self
+            .validators
+            .values()
+            .filter(|v| v.stake > 0)
+            .map(|v| Validator {
+                pubkey: v.pubkey,
+                bls_pubkey: v.bls_pubkey,
+                address: v.address,
+                stake: v.stake.saturating_add(self.delegated_stake(&v.pubkey)),
+                active: self.active.contains(&v.pubkey),
+            })
+            .collect();
+        validators.sort_by(|a, b| b.stake.cmp(&a.stake).then(a.pubkey.cmp(&b.pubkey)));
+        validators
+    }
+
+    /// Check if a public key is in the current epoch's active set.
     pub fn is_validator(&self, pubkey: &PublicKey) -> bool {
-        self.validators
-            .get(pubkey)
-            .map(|v| v.stake >= self.min_stake)
-            .unwrap_or(false)
+        self.active.contains(pubkey)
     }
 
-    /// Get the stake for a validator.
+    /// Get the bonded stake for a validator.
     pub fn validator_stake(&self, pubkey: &PublicKey) -> Option<Amount> {
         self.validators.get(pubkey).map(|v| v.stake)
     }
 
+    /// Get a validator's registered BLS public key.
+    pub fn validator_bls_pubkey(&self, pubkey: &PublicKey) -> Option<BlsPublicKey> {
+        self.validators.get(pubkey).map(|v| v.bls_pubkey)
+    }
+
+    /// Get the stake waiting to be bonded at the next epoch boundary (0 if none).
+    pub fn validator_pending_stake(&self, pubkey: &PublicKey) -> Amount {
+        self.validators
+            .get(pubkey)
+            .map(|v| v.pending_stake)
+            .unwrap_or(0)
+    }
+
     /// Get the pending unstake amount for a validator (0 if none).
     pub fn validator_pending_unstake(&self, pubkey: &PublicKey) -> Amount {
         self.validators
@@ -209,6 +753,131 @@ impl StakingState {
             .unwrap_or(0)
     }
 
+    /// Whether a validator is jailed.
+    pub fn is_jailed(&self, pubkey: &PublicKey) -> bool {
+        self.jailed_until(pubkey).is_some()
+    }
+
+    /// Get the height until which a validator is jailed (`u64::MAX` if it
+    /// was slashed for double-signing), or `None` if it is not jailed.
+    pub fn jailed_until(&self, pubkey: &PublicKey) -> Option<u64> {
+        self.validators.get(pubkey).and_then(|v| v.jailed_until)
+    }
+
+    /// Whether a validator was slashed for double-signing.
+    pub fn is_tombstoned(&self, pubkey: &PublicKey) -> bool {
+        self.tombstoned.contains(pubkey)
+    }
+
+    /// Get the number of jailed validators.
+    pub fn jailed_count(&self) -> usize {
+        self.validators
+            .values()
+            .filter(|v| v.jailed_until.is_some())
+            .count()
+    }
+
+    /// Get every slashing applied so far, oldest first.
+    pub fn slash_history(&self) -> &[SlashRecord] {
+        &self.slash_history
+    }
+
+    /// Get the bonded stake delegated to a validator.
+    pub fn delegated_stake(&self, validator: &PublicKey) -> Amount {
+        self.delegations_to(validator)
+            .map(|(_, d)| d.amount)
+            .fold(0, Amount::saturating_add)
+    }
+
+    /// Get a validator's commission in basis points.
+    pub fn validator_commission(&self, pubkey: &PublicKey) -> Option<u16> {
+        self.validators.get(pubkey).map(|v| v.commission_bps)
+    }
+
+    /// Get the number of delegators of a validator.
+    pub fn delegator_count(&self, validator: &PublicKey) -> usize {
+        self.delegations_to(validator).count()
+    }
+
+    /// Get the delegation from `delegator` to `validator`, if any.
+    pub fn delegation(
+        &self,
+        validator: &PublicKey,
+        delegator: &PublicKey,
+    ) -> Option<DelegationInfo> {
+        self.delegations
+            .get(&(*validator, *delegator))
+            .map(|d| delegation_info(validator, delegator, d))
+    }
+
+    /// Get every delegation made by `delegator`.
+    pub fn delegations_of(&self, delegator: &PublicKey) -> Vec<DelegationInfo> {
+        self.delegations
+            .iter()
+            .filter(|((_, d), _)| d == delegator)
+            .map(|((v, d), delegation)| delegation_info(v, d, delegation))
+            .collect()
+    }
+
+    fn delegations_to<'a>(
+        &'a self,
+        validator: &PublicKey,
+    ) -> impl Iterator<Item = (&'a PublicKey, &'a Delegation)> + 'a {
+        self.delegations
+            .range((*validator, [0u8; 32])..=(*validator, [0xffu8; 32]))
+            .map(|((_, delegator), d)| (delegator, d))
+    }
+
+    /// Split `total_rewards` over the current epoch's active set in
+    /// proportion to stake including delegations. Each validator keeps its
+    /// commission on its delegators' share; the rest of its reward is shared
+    /// between it and its delegators by bonded stake. Rounding dust stays with
+    /// the validator.
+    pub fn distribute_rewards(&self, total_rewards: Amount) -> Vec<(Address, Amount)> {
+        let mut rewards: Vec<(Address, Amount)> = Vec::new();
+        let mut credit = |address: Address, amount: Amount| {
+            if amount == 0 {
+                return;
+            }
+            match rewards.iter_mut().find(|(a, _)| *a == address) {
+                Some(entry) => entry.1 = entry.1.saturating_add(amount),
+                None => rewards.push((address, amount)),
+            }
+        };
+
+        for (address, share) in
+            crate::fees::compute_reward_distribution(&self.active, total_rewards)
+        {
+            let Some(v) = self
+                .active
+                .validators
+                .iter()
+                .find(|v| v.address == address)
+                .and_then(|v| self.validators.get(&v.pubkey))
+            else {
+                credit(address, share);
+                continue;
+            };
+
+            let delegated = self.delegated_stake(&v.pubkey);
+            let weight = v.stake.saturating_add(delegated);
+            let mut paid: Amount = 0;
+            if delegated > 0 && weight > 0 {
+                let delegators_share = share.saturating_mul(delegated) / weight;
+                let commission =
+                    delegators_share.saturating_mul(v.commission_bps as Amount) / 10_000;
+                let net = delegators_share - commission;
+                for (delegator, d) in self.delegations_to(&v.pubkey) {
+                    let reward = net.saturating_mul(d.amount) / delegated;
+                    credit(pubkey_to_address(delegator), reward);
+                    paid = paid.saturating_add(reward);
+                }
+            }
+            credit(v.address, share.saturating_sub(paid));
+        }
+        rewards
+    }
+
     /// Get the minimum stake requirement.
     pub fn min_stake(&self) -> Amount {
         self.min_stake
@@ -219,12 +888,55 @@ impl StakingState {
         self.bonding_period
     }
 
-    /// Get the total staked amount across all validators.
+    /// Get the number of blocks per epoch.
+    pub fn epoch_length(&self) -> u64 {
+        self.epoch_length
+    }
+
+    /// Get the maximum size of the active validator set.
+    pub fn max_validators(&self) -> usize {
+        self.max_validators
+    }
+
+    /// Get the current epoch number.
+    pub fn epoch(&self) -> u64 {
+        self.active.epoch
+    }
+
+    /// Whether `height` is an epoch boundary (height 0 is not).
+    pub fn is_epoch_boundary(&self, height: u64) -> bool {
+        height > 0 && height.is_multiple_of(self.epoch_length)
+    }
+
+    /// The first epoch boundary at or after `height`.
+    pub fn next_epoch_boundary(&self, height: u64) -> u64 {
+        height.div_ceil(self.epoch_length).max(1) * self.epoch_length
+    }
+
+    /// Get the total bonded amount across all validators.
     pub fn total_staked(&self) -> Amount {
         self.validators.values().map(|v| v.stake).sum()
     }
 }
 
+fn delegation_info(
+    validator: &PublicKey,
+    delegator: &PublicKey,
+    delegation: &Delegation,
+) -> DelegationInfo {
+    DelegationInfo {
+        validator: *validator,
+        delegator: *delegator,
+        amount: delegation.amount,
+        pending: delegation.pending,
+        pending_undelegate: delegation
+            .pending_undelegate
+            .as_ref()
+            .map(|(a, _)| *a)
+            .unwrap_or(0),
+    }
+}
+
 /// Compute the signing data for a stake operation.
 /// The wallet signs this data to authorize the stake/unstake.
 pub fn stake_operation_signing_data(op: &StakeOperation) -> Vec<u8> {
@@ -232,11 +944,13 @@ pub fn stake_operation_signing_data(op: &StakeOperation) -> Vec<u8> {
     match op {
         StakeOperation::Stake {
             pubkey,
+            bls_pubkey,
             amount,
             timestamp,
             ..
         } => {
             data.extend_from_slice(pubkey);
+            data.extend_from_slice(bls_pubkey);
             data.extend_from_slice(&amount.to_le_bytes());
             data.extend_from_slice(&timestamp.to_le_bytes());
             data.extend_from_slice(b"stake");
@@ -252,6 +966,50 @@ pub fn stake_operation_signing_data(op: &StakeOperation) -> Vec<u8> {
             data.extend_from_slice(&timestamp.to_le_bytes());
             data.extend_from_slice(b"unstake");
         }
+        StakeOperation::Delegate {
+            delegator,
+            validator,
+            amount,
+            timestamp,
+            ..
+        } => {
+            data.extend_from_slice(delegator);
+            data.extend_from_slice(validator);
+            data.extend_from_slice(&amount.to_le_bytes());
+            data.extend_from_slice(&timestamp.to_le_bytes());
+            data.extend_from_slice(b"delegate");
+        }
+        StakeOperation::Undelegate {
+            delegator,
+            validator,
+            amount,
+            timestamp,
+            ..
+        } => {
+            data.extend_from_slice(delegator);
+            data.extend_from_slice(validator);
+            data.extend_from_slice(&amount.to_le_bytes());
+            data.extend_from_slice(&timestamp.to_le_bytes());
+            data.extend_from_slice(b"undelegate");
+        }
+        StakeOperation::SetCommission {
+            pubkey,
+            commission_bps,
+            timestamp,
+            ..
+        } => {
+            data.extend_from_slice(pubkey);
+            data.extend_from_slice(&commission_bps.to_le_bytes());
+            data.extend_from_slice(&timestamp.to_le_bytes());
+            data.extend_from_slice(b"set_commission");
+        }
+        StakeOperation::Unjail {
+            pubkey, timestamp, ..
+        } => {
+            data.extend_from_slice(pubkey);
+            data.extend_from_slice(&timestamp.to_le_bytes());
+            data.extend_from_slice(b"unjail");
+        }
     }
     blake3_hash(&data).to_vec()
 }
@@ -265,6 +1023,8 @@ pub fn validate_stake_operation(
     match op {
         StakeOperation::Stake {
             pubkey,
+            bls_pubkey,
+            bls_pop,
             amount,
             signature,
             ..
@@ -272,6 +1032,17 @@ pub fn validate_stake_operation(
             verify(&sig_data, signature, pubkey).map_err(|_| WeaveError::StakingError {
                 reason: "invalid stake signature".to_string(),
             })?;
+            verify_possession(bls_pubkey, bls_pop).map_err(|_| WeaveError::StakingError {
+                reason: "invalid BLS proof of possession".to_string(),
+            })?;
+            if staking
+                .validator_bls_pubkey(pubkey)
+                .is_some_and(|registered| registered != *bls_pubkey)
+            {
+                return Err(WeaveError::StakingError {
+                    reason: "BLS public key differs from the registered key".to_string(),
+                });
+            }
             if *amount == 0 {
                 return Err(WeaveError::StakingError {
                     reason: "stake amount must be positive".to_string(),
@@ -321,6 +1092,103 @@ pub fn validate_stake_operation(
             }
             Ok(())
         }
+        StakeOperation::Delegate {
+            delegator,
+            validator,
+            amount,
+            signature,
+            ..
+        } => {
+            verify(&sig_data, signature, delegator).map_err(|_| WeaveError::StakingError {
+                reason: "invalid delegate signature".to_string(),
+            })?;
+            if *amount == 0 {
+                return Err(WeaveError::StakingError {
+                    reason: "delegation amount must be positive".to_string(),
+                });
+            }
+            if staking.validator_stake(validator).is_none() {
+                return Err(WeaveError::StakingError {
+                    reason: "validator not found".to_string(),
+                });
+            }
+            Ok(())
+        }
+        StakeOperation::Undelegate {
+            delegator,
+            validator,
+            amount,
+            signature,
+            ..
+        } => {
+            verify(&sig_data, signature, delegator).map_err(|_| WeaveError::StakingError {
+                reason: "invalid undelegate signature".to_string(),
+            })?;
+            if *amount == 0 {
+                return Err(WeaveError::StakingError {
+                    reason: "undelegate amount must be positive".to_string(),
+                });
+            }
+            let delegation = staking.delegation(validator, delegator).ok_or_else(|| {
+                WeaveError::StakingError {
+                    reason: "delegation not found".to_string(),
+                }
+            })?;
+            let available = delegation
+                .amount
+                .saturating_sub(delegation.pending_undelegate);
+            if *amount > available {
+                return Err(WeaveError::StakingError {
+                    reason: format!(
+                        "undelegate amount {} exceeds available {} (delegated {} - pending {})",
+                        amount, available, delegation.amount, delegation.pending_undelegate
+                    ),
+                });
+            }
+            Ok(())
+        }
+        StakeOperation::SetCommission {
+            pubkey,
+            commission_bps,
+            signature,
+            ..
+        } => {
+            verify(&sig_data, signature, pubkey).map_err(|_| WeaveError::StakingError {
+                reason: "invalid set commission signature".to_string(),
+            })?;
+            if *commission_bps > MAX_COMMISSION_BPS {
+                return Err(WeaveError::StakingError {
+                    reason: format!(
+                        "commission {} bps exceeds maximum {}",
+                        commission_bps, MAX_COMMISSION_BPS
+                    ),
+                });
+            }
+            if staking.validator_stake(pubkey).is_none() {
+                return Err(WeaveError::StakingError {
+                    reason: "validator not found".to_string(),
+                });
+            }
+            Ok(())
+        }
+        StakeOperation::Unjail {
+            pubkey, signature, ..
+        } => {
+            verify(&sig_data, signature, pubkey).map_err(|_| WeaveError::StakingError {
+                reason: "invalid unjail signature".to_string(),
+            })?;
+            if staking.is_tombstoned(pubkey) {
+                return Err(WeaveError::StakingError {
+                    reason: "validator was slashed for double-signing".to_string(),
+                });
+            }
+            if !staking.is_jailed(pubkey) {
+                return Err(WeaveError::StakingError {
+                    reason: "validator is not jailed".to_string(),
+                });
+            }
+            Ok(())
+        }
     }
 }
 
@@ -332,71 +1200,110 @@ mod tests {
         [byte; 32]
     }
 
+    fn make_bls_pubkey(byte: u8) -> BlsPublicKey {
+        [byte; 48]
+    }
+
     fn make_address(byte: u8) -> Address {
         [byte; 20]
     }
 
+    /// Staking with 10-block epochs, a minimum stake of 100 and a 10-block
+    /// bonding period.
+    fn make_staking() -> StakingState {
+        StakingState::new(100, 10).with_epoch_length(10)
+    }
+
+    /// Stake `amount` for validator `byte` and bond it at the next boundary.
+    fn bond(staking: &mut StakingState, byte: u8, amount: Amount, height: u64) {
+        staking
+            .stake(
+                make_pubkey(byte),
+                make_bls_pubkey(byte),
+                make_address(byte),
+                amount,
+            )
+            .unwrap();
+        let boundary = staking.next_epoch_boundary(height);
+        staking.process_epoch(boundary);
+    }
+
     #[test]
     fn test_stake_and_active() {
-        let mut staking = StakingState::new(100, 10);
+        let mut staking = make_staking();
         let pk = make_pubkey(1);
-        staking.stake(pk, make_address(1), 500).unwrap();
+        staking
+            .stake(pk, make_bls_pubkey(1), make_address(1), 500)
+            .unwrap();
+
+        // Pending until the epoch boundary.
+        assert!(!staking.is_validator(&pk));
+        assert_eq!(staking.validator_pending_stake(&pk), 500);
+        assert!(staking.process_epoch(5).is_empty());
+        assert!(!staking.is_validator(&pk));
 
+        staking.process_epoch(10);
         assert!(staking.is_validator(&pk));
         assert_eq!(staking.validator_stake(&pk), Some(500));
+        assert_eq!(staking.validator_pending_stake(&pk), 0);
+        assert_eq!(staking.epoch(), 1);
 
         let vs = staking.active_validators();
         assert_eq!(vs.len(), 1);
         assert_eq!(vs.validators[0].stake, 500);
+        assert_eq!(vs.epoch, 1);
     }
 
     #[test]
     fn test_stake_below_minimum() {
-        let mut staking = StakingState::new(100, 10);
+        let mut staking = make_staking();
         let pk = make_pubkey(1);
-        let result = staking.stake(pk, make_address(1), 50);
+        let result = staking.stake(pk, make_bls_pubkey(1), make_address(1), 50);
         assert!(result.is_err());
+        assert_eq!(staking.validator_stake(&pk), None);
     }
 
     #[test]
     fn test_stake_zero_amount() {
-        let mut staking = StakingState::new(100, 10);
+        let mut staking = make_staking();
         let pk = make_pubkey(1);
-        let result = staking.stake(pk, make_address(1), 0);
+        let result = staking.stake(pk, make_bls_pubkey(1), make_address(1), 0);
         assert!(result.is_err());
     }
 
     #[test]
     fn test_unstake() {
-        let mut staking = StakingState::new(100, 10);
+        let mut staking = make_staking();
         let pk = make_pubkey(1);
-        staking.stake(pk, make_address(1), 500).unwrap();
+        bond(&mut staking, 1, 500, 0);
 
-        staking.unstake(&pk, 200, 100).unwrap();
+        // Bonding period ends at 25, released at the boundary at 30.
+        staking.unstake(&pk, 200, 15).unwrap();
 
-        // Validator is still active until bonding period ends.
+        // Validator is still active until then.
         assert!(staking.is_validator(&pk));
         assert_eq!(staking.validator_stake(&pk), Some(500));
 
-        // Process epoch before bonding period ends.
-        let removed = staking.process_epoch(105);
+        let removed = staking.process_epoch(20);
         assert!(removed.is_empty());
         assert_eq!(staking.validator_stake(&pk), Some(500));
+        staking.process_epoch(25);
+        assert_eq!(staking.validator_stake(&pk), Some(500));
 
-        // Process epoch after bonding period.
-        let removed = staking.process_epoch(110);
+        let removed = staking.process_epoch(30);
         assert!(removed.is_empty());
         assert_eq!(staking.validator_stake(&pk), Some(300));
+        assert_eq!(staking.active_validators().validators[0].stake, 300);
     }
 
     #[test]
     fn test_unstake_full_removal() {
-        let mut staking = StakingState::new(100, 10);
+        let mut staking = make_staking();
         let pk = make_pubkey(1);
-        staking.stake(pk, make_address(1), 500).unwrap();
-        staking.unstake(&pk, 500, 100).unwrap();
+        bond(&mut staking, 1, 500, 0);
+        staking.unstake(&pk, 500, 10).unwrap();
 
-        let removed = staking.process_epoch(110);
+        let removed = staking.process_epoch(20);
         assert_eq!(removed.len(), 1);
         assert_eq!(removed[0], pk);
         assert!(!staking.is_validator(&pk));
@@ -404,19 +1311,20 @@ mod tests {
 
     #[test]
     fn test_slash() {
-        let mut staking = StakingState::new(100, 10);
+        let mut staking = make_staking();
         let pk = make_pubkey(1);
-        staking.stake(pk, make_address(1), 500).unwrap();
+        bond(&mut staking, 1, 500, 0);
         staking.slash(&pk, 200).unwrap();
 
         assert_eq!(staking.validator_stake(&pk), Some(300));
+        assert_eq!(staking.active_validators().total_stake, 300);
     }
 
     #[test]
     fn test_slash_below_minimum() {
-        let mut staking = StakingState::new(100, 10);
+        let mut staking = make_staking();
         let pk = make_pubkey(1);
-        staking.stake(pk, make_address(1), 150).unwrap();
+        bond(&mut staking, 1, 150, 0);
         staking.slash(&pk, 100).unwrap();
 
         // Stake is 50, below minimum 100, so not an active validator.
@@ -427,10 +1335,17 @@ mod tests {
 
     #[test]
     fn test_active_validators_sorted_by_stake() {
-        let mut staking = StakingState::new(100, 10);
-        staking.stake(make_pubkey(1), make_address(1), 300).unwrap();
-        staking.stake(make_pubkey(2), make_address(2), 500).unwrap();
-        staking.stake(make_pubkey(3), make_address(3), 100).unwrap();
+        let mut staking = make_staking();
+        staking
+            .stake(make_pubkey(1), make_bls_pubkey(1), make_address(1), 300)
+            .unwrap();
+        staking
+            .stake(make_pubkey(2), make_bls_pubkey(2), make_address(2), 500)
+            .unwrap();
+        staking
+            .stake(make_pubkey(3), make_bls_pubkey(3), make_address(3), 100)
+            .unwrap();
+        staking.process_epoch(10);
 
         let vs = staking.active_validators();
         assert_eq!(vs.len(), 3);
@@ -439,27 +1354,313 @@ mod tests {
         assert_eq!(vs.validators[2].stake, 100);
     }
 
+    #[test]
+    fn test_active_set_capped_by_max_validators() {
+        let mut staking = make_staking().with_max_validators(2);
+        staking
+            .stake(make_pubkey(1), make_bls_pubkey(1), make_address(1), 300)
+            .unwrap();
+        staking
+            .stake(make_pubkey(2), make_bls_pubkey(2), make_address(2), 500)
+            .unwrap();
+        staking
+            .stake(make_pubkey(3), make_bls_pubkey(3), make_address(3), 300)
+            .unwrap();
+        staking.process_epoch(10);
+
+        // Ties are broken by public key.
+        let vs = staking.active_validators();
+        assert_eq!(vs.len(), 2);
+        assert_eq!(vs.validators[0].pubkey, make_pubkey(2));
+        assert_eq!(vs.validators[1].pubkey, make_pubkey(1));
+        assert!(!staking.is_validator(&make_pubkey(3)));
+        assert_eq!(staking.candidates().len(), 3);
+        assert!(!staking.candidates()[2].active);
+
+        // Outstaking a member takes its seat at the next boundary.
+        staking
+            .stake(make_pubkey(3), make_bls_pubkey(3), make_address(3), 300)
+            .unwrap();
+        assert!(!staking.is_validator(&make_pubkey(3)));
+        staking.process_epoch(20);
+        assert!(staking.is_validator(&make_pubkey(3)));
+        assert!(!staking.is_validator(&make_pubkey(1)));
+        assert_eq!(staking.epoch(), 2);
+    }
+
+    #[test]
+    fn test_apply_block_waits_for_epoch_boundary() {
+        let mut staking = make_staking();
+        bond(&mut staking, 1, 500, 0);
+        let before = staking.active_validators();
+
+        let op = StakeOperation::Stake {
+            pubkey: make_pubkey(2),
+            bls_pubkey: make_bls_pubkey(2),
+            bls_pop: [0u8; 96],
+            amount: 400,
+            timestamp: 0,
+            signature: [0u8; 64],
+        };
+        staking.apply_block(std::slice::from_ref(&op), &[], &make_pubkey(1), 11);
+        assert_eq!(staking.active_validators(), before);
+        assert_eq!(staking.active_validators().hash(), before.hash());
+
+        staking.apply_block(&[], &[], &make_pubkey(1), 20);
+        let after = staking.active_validators();
+        assert_eq!(after.len(), 2);
+        assert_ne!(after.hash(), before.hash());
+    }
+
+    #[test]
+    fn test_activate_pending_stakes() {
+        let mut staking = make_staking();
+        staking
+            .stake(make_pubkey(1), make_bls_pubkey(1), make_address(1), 500)
+            .unwrap();
+        staking.activate_pending_stakes(25);
+        assert!(staking.is_validator(&make_pubkey(1)));
+        assert_eq!(staking.epoch(), 2);
+    }
+
+    #[test]
+    fn test_delegation_bonds_at_epoch_boundary() {
+        let mut staking = make_staking();
+        let validator = make_pubkey(1);
+        let delegator = make_pubkey(9);
+        bond(&mut staking, 1, 500, 0);
+
+        // Delegating to an unknown validator fails.
+        assert!(staking.delegate(delegator, make_pubkey(2), 100).is_err());
+        assert!(staking.delegate(delegator, validator, 0).is_err());
+
+        staking.delegate(delegator, validator, 300).unwrap();
+        assert_eq!(staking.delegated_stake(&validator), 0);
+        assert_eq!(
+            staking.delegation(&validator, &delegator).unwrap().pending,
+            300
+        );
+
+        staking.process_epoch(20);
+        assert_eq!(staking.delegated_stake(&validator), 300);
+        assert_eq!(staking.delegator_count(&validator), 1);
+        assert_eq!(staking.validator_stake(&validator), Some(500));
+        assert_eq!(staking.active_validators().validators[0].stake, 800);
+        assert_eq!(staking.delegations_of(&delegator).len(), 1);
+    }
+
+    #[test]
+    fn test_delegations_count_towards_selection() {
+        let mut staking = make_staking().with_max_validators(1);
+        bond(&mut staking, 1, 500, 0);
+        staking
+            .stake(make_pubkey(2), make_bls_pubkey(2), make_address(2), 200)
+            .unwrap();
+        staking
+            .delegate(make_pubkey(9), make_pubkey(2), 400)
+            .unwrap();
+        staking.process_epoch(20);
+
+        assert!(staking.is_validator(&make_pubkey(2)));
+        assert!(!staking.is_validator(&make_pubkey(1)));
+
+        // Delegations do not make a validator eligible on their own.
+        staking.slash(&make_pubkey(2), 150).unwrap();
+        assert!(!staking.is_validator(&make_pubkey(2)));
+        staking.process_epoch(30);
+        assert!(staking.is_validator(&make_pubkey(1)));
+    }
+
+    #[test]
+    fn test_undelegate_after_bonding_period() {
+        let mut staking = make_staking();
+        let validator = make_pubkey(1);
+        let delegator = make_pubkey(9);
+        bond(&mut staking, 1, 500, 0);
+        staking.delegate(delegator, validator, 300).unwrap();
+        staking.process_epoch(20);
+
+        assert!(staking.undelegate(&delegator, &validator, 400, 21).is_err());
+        assert!(staking
+            .undelegate(&make_pubkey(8), &validator, 100, 21)
+            .is_err());
+
+        // Bonding period ends at 31, released at the boundary at 40.
+        staking.undelegate(&delegator, &validator, 300, 21).unwrap();
+        staking.process_epoch(30);
+        assert_eq!(staking.delegated_stake(&validator), 300);
+        staking.process_epoch(40);
+        assert_eq!(staking.delegated_stake(&validator), 0);
+        assert!(staking.delegation(&validator, &delegator).is_none());
+        assert_eq!(staking.active_validators().validators[0].stake, 500);
+    }
+
+    #[test]
+    fn test_commission_applies_at_epoch_boundary() {
+        let mut staking = make_staking();
+        let pk = make_pubkey(1);
+        bond(&mut staking, 1, 500, 0);
+        assert_eq!(
+            staking.validator_commission(&pk),
+            Some(DEFAULT_COMMISSION_BPS)
+        );
+
+        assert!(staking.set_commission(&pk, MAX_COMMISSION_BPS + 1).is_err());
+        assert!(staking.set_commission(&make_pubkey(2), 100).is_err());
+        staking.set_commission(&pk, 2_500).unwrap();
+        assert_eq!(
+            staking.validator_commission(&pk),
+            Some(DEFAULT_COMMISSION_BPS)
+        );
+        staking.process_epoch(20);
+        assert_eq!(staking.validator_commission(&pk), Some(2_500));
+    }
+
+    #[test]
+    fn test_distribute_rewards_with_commission() {
+        let mut staking = make_staking();
+        let validator = make_pubkey(1);
+        bond(&mut staking, 1, 500, 0);
+        staking.set_commission(&validator, 2_000).unwrap();
+        staking.delegate(make_pubkey(8), validator, 300).unwrap();
+        staking.delegate(make_pubkey(9), validator, 200).unwrap();
+        staking.process_epoch(20);
+
+        // Delegators hold half the stake: 500 of 1000. Commission takes 20%
+        // of that, leaving 400 split 240/160.
+        let rewards = staking.distribute_rewards(1_000);
+        let reward_of = |pk: &PublicKey| {
+            rewards
+                .iter()
+                .find(|(a, _)| *a == pubkey_to_address(pk))
+                .map(|(_, r)| *r)
+        };
+        assert_eq!(reward_of(&make_pubkey(8)), Some(240));
+        assert_eq!(reward_of(&make_pubkey(9)), Some(160));
+        assert_eq!(
+            rewards
+                .iter()
+                .find(|(a, _)| *a == make_address(1))
+                .unwrap()
+                .1,
+            600
+        );
+        assert_eq!(rewards.iter().map(|(_, r)| r).sum::<Amount>(), 1_000);
+    }
+
+    #[test]
+    fn test_double_sign_slashes_and_tombstones() {
+        let mut staking = make_staking();
+        bond(&mut staking, 1, 1_000, 0);
+        bond(&mut staking, 2, 1_000, 0);
+        staking
+            .delegate(make_pubkey(9), make_pubkey(1), 400)
+            .unwrap();
+        staking.process_epoch(20);
+
+        let vote = |block_hash: Hash| norn_types::consensus::Vote {
+            view: 1,
+            phase: norn_types::consensus::ConsensusPhase::Prepare,
+            block_hash,
+            voter: make_pubkey(1),
+            signature: [0u8; 64],
+            bls_signature: [0u8; 96],
+        };
+        let evidence = SlashingEvidence::DoubleVote {
+            vote_a: Box::new(vote([1u8; 32])),
+            vote_b: Box::new(vote([2u8; 32])),
+        };
+        staking.apply_block(&[], std::slice::from_ref(&evidence), &make_pubkey(2), 25);
+
+        // 5% of both the validator's stake and its delegations is burned.
+        assert_eq!(staking.validator_stake(&make_pubkey(1)), Some(950));
+        assert_eq!(
+            staking
+                .delegation(&make_pubkey(1), &make_pubkey(9))
+                .unwrap()
+                .amount,
+            380
+        );
+        assert_eq!(staking.slash_history().len(), 1);
+        assert_eq!(staking.slash_history()[0].amount, 70);
+        assert_eq!(staking.slash_history()[0].reason, SlashReason::DoubleSign);
+        assert!(!staking.is_validator(&make_pubkey(1)));
+        assert!(staking.is_tombstoned(&make_pubkey(1)));
+
+        // It never rejoins, and cannot be slashed twice for the same offence.
+        staking.apply_block(&[], &[], &make_pubkey(2), 30);
+        assert!(!staking.is_validator(&make_pubkey(1)));
+        assert!(staking.unjail(&make_pubkey(1), 30).is_err());
+        assert!(staking
+            .stake(make_pubkey(1), make_bls_pubkey(1), make_address(1), 500)
+            .is_err());
+        assert!(staking.apply_evidence(&evidence, 31).is_err());
+    }
+
+    #[test]
+    fn test_downtime_jails_until_unjailed() {
+        let mut staking = make_staking().with_downtime(2, 50);
+        bond(&mut staking, 1, 1_000, 0);
+        bond(&mut staking, 2, 1_000, 0);
+
+        // Two validators, so validator 1 may miss 4 blocks.
+        for height in 11..=14 {
+            staking.apply_block(&[], &[], &make_pubkey(2), height);
+        }
+        assert!(staking.is_validator(&make_pubkey(1)));
+        staking.apply_block(&[], &[], &make_pubkey(2), 15);
+        assert!(!staking.is_validator(&make_pubkey(1)));
+        assert_eq!(staking.jailed_until(&make_pubkey(1)), Some(65));
+        assert_eq!(staking.validator_stake(&make_pubkey(1)), Some(999));
+        assert_eq!(staking.jailed_count(), 1);
+        assert_eq!(staking.slash_history()[0].reason, SlashReason::Downtime);
+
+        // Jailed validators are skipped at epoch boundaries until they unjail.
+        staking.apply_block(&[], &[], &make_pubkey(2), 20);
+        assert!(!staking.is_validator(&make_pubkey(1)));
+        assert!(staking.unjail(&make_pubkey(1), 40).is_err());
+
+        let op = StakeOperation::Unjail {
+            pubkey: make_pubkey(1),
+            timestamp: 0,
+            signature: [0u8; 64],
+        };
+        staking.apply_block(std::slice::from_ref(&op), &[], &make_pubkey(2), 65);
+        assert!(!staking.is_jailed(&make_pubkey(1)));
+        staking.apply_block(&[], &[], &make_pubkey(2), 70);
+        assert!(staking.is_validator(&make_pubkey(1)));
+    }
+
     #[test]
     fn test_unstake_nonexistent_validator() {
-        let mut staking = StakingState::new(100, 10);
+        let mut staking = make_staking();
         let result = staking.unstake(&make_pubkey(99), 100, 100);
         assert!(result.is_err());
     }
 
     #[test]
     fn test_unstake_more_than_staked() {
-        let mut staking = StakingState::new(100, 10);
+        let mut staking = make_staking();
         let pk = make_pubkey(1);
-        staking.stake(pk, make_address(1), 500).unwrap();
+        bond(&mut staking, 1, 500, 0);
         let result = staking.unstake(&pk, 600, 100);
         assert!(result.is_err());
+
+        // Stake that is not bonded yet cannot be unstaked.
+        staking
+            .stake(pk, make_bls_pubkey(1), make_address(1), 200)
+            .unwrap();
+        assert!(staking.unstake(&pk, 600, 100).is_err());
     }
 
     #[test]
     fn test_bonding_period() {
-        let mut staking = StakingState::new(100, 20);
+        let mut staking = StakingState::new(100, 20).with_epoch_length(10);
         let pk = make_pubkey(1);
-        staking.stake(pk, make_address(1), 500).unwrap();
+        staking
+            .stake(pk, make_bls_pubkey(1), make_address(1), 500)
+            .unwrap();
+        staking.process_epoch(50);
         staking.unstake(&pk, 500, 50).unwrap();
 
         // At height 60, still within bonding period (effective at 70).
@@ -471,4 +1672,50 @@ mod tests {
         let removed = staking.process_epoch(70);
         assert_eq!(removed.len(), 1);
     }
+
+    #[test]
+    fn test_validate_stake_requires_bls_possession() {
+        use norn_crypto::bls::BlsKeypair;
+        use norn_crypto::keys::Keypair;
+
+        let staking = make_staking();
+        let kp = Keypair::generate();
+        let bls = BlsKeypair::from_keypair(&kp);
+        let signed = |bls_pubkey: BlsPublicKey, bls_pop: BlsSignature| {
+            let mut op = StakeOperation::Stake {
+                pubkey: kp.public_key(),
+                bls_pubkey,
+                bls_pop,
+                amount: 500,
+                timestamp: 1,
+                signature: [0u8; 64],
+            };
+            let sig = kp.sign(&stake_operation_signing_data(&op));
+            if let StakeOperation::Stake { signature, .. } = &mut op {
+                *signature = sig;
+            }
+            op
+        };
+
+        let op = signed(bls.public_key(), bls.prove_possession());
+        assert!(validate_stake_operation(&op, &staking).is_ok());
+
+        // A proof for someone else's key does not cover this one.
+        let other = BlsKeypair::from_keypair(&Keypair::generate());
+        let op = signed(other.public_key(), bls.prove_possession());
+        assert!(validate_stake_operation(&op, &staking).is_err());
+    }
+
+    #[test]
+    fn test_stake_keeps_registered_bls_key() {
+        let mut staking = make_staking();
+        bond(&mut staking, 1, 500, 0);
+        assert!(staking
+            .stake(make_pubkey(1), make_bls_pubkey(2), make_address(1), 100)
+            .is_err());
+        assert_eq!(
+            staking.validator_bls_pubkey(&make_pubkey(1)),
+            Some(make_bls_pubkey(1))
+        );
+    }
 }
@@ -4,9 +4,10 @@ use norn_crypto::address::pubkey_to_address;
 use norn_crypto::keys::verify;
 use norn_types::primitives::{Amount, TokenId};
 use norn_types::token::{
-    compute_token_id, validate_token_name, validate_token_symbol, MAX_TOKEN_DECIMALS,
+    compute_token_id, validate_token_metadata, validate_token_name, validate_token_symbol,
+    MAX_TOKEN_DECIMALS,
 };
-use norn_types::weave::{TokenBurn, TokenDefinition, TokenMint};
+use norn_types::weave::{TokenBurn, TokenDefinition, TokenMetadataUpdate, TokenMint};
 
 use crate::error::WeaveError;
 
@@ -20,6 +21,8 @@ pub struct TokenMeta {
     pub current_supply: Amount,
     pub creator: [u8; 20],
     pub created_at: u64,
+    /// Timestamp of the last applied metadata update (0 if none).
+    pub metadata_updated_at: u64,
 }
 
 /// Compute the data that should be signed for a token definition.
@@ -56,6 +59,18 @@ pub fn token_burn_signing_data(burn: &TokenBurn) -> Vec<u8> {
     data
 }
 
+/// Compute the data that should be signed for a token metadata update.
+///
+/// The metadata is borsh-encoded, which length-prefixes every field.
+pub fn token_metadata_update_signing_data(update: &TokenMetadataUpdate) -> Vec<u8> {
+    let mut data = Vec::new();
+    data.extend_from_slice(&update.token_id);
+    data.extend_from_slice(&borsh::to_vec(&update.metadata).unwrap_or_default());
+    data.extend_from_slice(&update.authority);
+    data.extend_from_slice(&update.timestamp.to_le_bytes());
+    data
+}
+
 /// Validate a token definition.
 ///
 /// Returns the computed token ID on success.
@@ -249,6 +264,61 @@ pub fn validate_token_burn(
     Ok(())
 }
 
+/// Validate a token metadata update.
+pub fn validate_token_metadata_update(
+    update: &TokenMetadataUpdate,
+    known_tokens: &HashMap<TokenId, TokenMeta>,
+) -> Result<(), WeaveError> {
+    // 1. Token exists.
+    let meta = known_tokens.get(&update.token_id).ok_or_else(|| {
+        WeaveError::InvalidTokenMetadataUpdate {
+            reason: format!("token not found: {}", hex::encode(update.token_id)),
+        }
+    })?;
+
+    // 2. Authority == token creator.
+    if update.authority != meta.creator {
+        return Err(WeaveError::InvalidTokenMetadataUpdate {
+            reason: "not token authority".to_string(),
+        });
+    }
+
+    // 3. Pubkey matches authority.
+    let expected_address = pubkey_to_address(&update.authority_pubkey);
+    if update.authority != expected_address {
+        return Err(WeaveError::InvalidTokenMetadataUpdate {
+            reason: "authority address does not match authority_pubkey".to_string(),
+        });
+    }
+
+    // 4. Verify signature.
+    let sig_data = token_metadata_update_signing_data(update);
+    verify(&sig_data, &update.signature, &update.authority_pubkey).map_err(|_| {
+        WeaveError::InvalidTokenMetadataUpdate {
+            reason: "invalid signature".to_string(),
+        }
+    })?;
+
+    // 5. Newer than the last applied update (rejects replays).
+    if update.timestamp <= meta.metadata_updated_at {
+        return Err(WeaveError::InvalidTokenMetadataUpdate {
+            reason: format!(
+                "timestamp {} is not after the last update at {}",
+                update.timestamp, meta.metadata_updated_at
+            ),
+        });
+    }
+
+    // 6. Metadata within limits.
+    validate_token_metadata(&update.metadata).map_err(|e| {
+        WeaveError::InvalidTokenMetadataUpdate {
+            reason: e.to_string(),
+        }
+    })?;
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -285,6 +355,7 @@ mod tests {
             current_supply: 1_000,
             creator,
             created_at: 1000,
+            metadata_updated_at: 0,
         };
         (token_id, meta)
     }
@@ -510,4 +581,85 @@ mod tests {
             Err(WeaveError::InvalidTokenBurn { .. })
         ));
     }
+
+    fn make_signed_metadata_update(
+        kp: &Keypair,
+        token_id: TokenId,
+        timestamp: u64,
+    ) -> TokenMetadataUpdate {
+        let mut update = TokenMetadataUpdate {
+            token_id,
+            metadata: norn_types::weave::TokenMetadata {
+                description: Some("A test token.".to_string()),
+                icon_uri: Some("https://example.com/tst.png".to_string()),
+                project_url: None,
+                tags: vec!["test".to_string()],
+            },
+            authority: pubkey_to_address(&kp.public_key()),
+            authority_pubkey: kp.public_key(),
+            timestamp,
+            signature: [0u8; 64],
+        };
+        let sig_data = token_metadata_update_signing_data(&update);
+        update.signature = kp.sign(&sig_data);
+        update
+    }
+
+    #[test]
+    fn test_valid_token_metadata_update() {
+        let kp = Keypair::generate();
+        let (token_id, meta) = make_token_meta(&kp, "TST");
+        let update = make_signed_metadata_update(&kp, token_id, 2000);
+
+        let mut known_tokens = HashMap::new();
+        known_tokens.insert(token_id, meta);
+        assert!(validate_token_metadata_update(&update, &known_tokens).is_ok());
+    }
+
+    #[test]
+    fn test_metadata_update_not_authority_rejected() {
+        let creator_kp = Keypair::generate();
+        let other_kp = Keypair::generate();
+        let (token_id, meta) = make_token_meta(&creator_kp, "TST");
+        let update = make_signed_metadata_update(&other_kp, token_id, 2000);
+
+        let mut known_tokens = HashMap::new();
+        known_tokens.insert(token_id, meta);
+        assert!(matches!(
+            validate_token_metadata_update(&update, &known_tokens),
+            Err(WeaveError::InvalidTokenMetadataUpdate { .. })
+        ));
+    }
+
+    #[test]
+    fn test_metadata_update_replay_rejected() {
+        let kp = Keypair::generate();
+        let (token_id, mut meta) = make_token_meta(&kp, "TST");
+        meta.metadata_updated_at = 2000;
+        let update = make_signed_metadata_update(&kp, token_id, 2000);
+
+        let mut known_tokens = HashMap::new();
+        known_tokens.insert(token_id, meta);
+        assert!(matches!(
+            validate_token_metadata_update(&update, &known_tokens),
+            Err(WeaveError::InvalidTokenMetadataUpdate { .. })
+        ));
+    }
+
+    #[test]
+    fn test_metadata_update_invalid_metadata_rejected() {
+        let kp = Keypair::generate();
+        let (token_id, meta) = make_token_meta(&kp, "TST");
+        let mut update = make_signed_metadata_update(&kp, token_id, 2000);
+        update.metadata.tags = vec!["Not A Tag".to_string()];
+        let sig_data = token_metadata_update_signing_data(&update);
+        update.signature = kp.sign(&sig_data);
+
+        let mut known_tokens = HashMap::new();
+        known_tokens.insert(token_id, meta);
+        assert!(matches!(
+            validate_token_metadata_update(&update, &known_tokens),
+            Err(WeaveError::InvalidTokenMetadataUpdate { .. })
+        ));
+    }
 }
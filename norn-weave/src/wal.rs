@@ -0,0 +1,79 @@
+use std::sync::Arc;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use norn_storage::error::StorageError;
+use norn_storage::traits::KvStore;
+use norn_types::consensus::QuorumCertificate;
+use norn_types::primitives::Hash;
+
+const CONSENSUS_WAL_KEY: &[u8] = b"consensus:wal";
+
+/// Consensus state a validator must not lose across a restart.
+#[derive(Debug, Clone, Default, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct ConsensusRecord {
+    /// The view the validator was in.
+    pub view: u64,
+    /// The latest view the validator voted in, and the block it voted for.
+    pub last_vote: Option<(u64, Hash)>,
+    /// Highest prepare QC seen.
+    pub prepare_qc: Option<QuorumCertificate>,
+    /// QC the validator is locked on.
+    pub locked_qc: Option<QuorumCertificate>,
+}
+
+/// Write-ahead log for the HotStuff engine. The engine writes its record
+/// before any vote leaves it, so a validator that crashes and restarts
+/// resumes from what it last sent rather than from view 0.
+pub struct ConsensusWal {
+    store: Arc<dyn KvStore>,
+}
+
+impl ConsensusWal {
+    /// Create a WAL backed by the given store.
+    pub fn new(store: Arc<dyn KvStore>) -> Self {
+        Self { store }
+    }
+
+    /// Load the last record written, if any.
+    pub fn load(&self) -> Result<Option<ConsensusRecord>, StorageError> {
+        match self.store.get(CONSENSUS_WAL_KEY)? {
+            Some(bytes) => ConsensusRecord::try_from_slice(&bytes)
+                .map(Some)
+                .map_err(|e| StorageError::DeserializationError {
+                    reason: e.to_string(),
+                }),
+            None => Ok(None),
+        }
+    }
+
+    /// Durably replace the record.
+    pub fn save(&self, record: &ConsensusRecord) -> Result<(), StorageError> {
+        let value = borsh::to_vec(record).map_err(|e| StorageError::SerializationError {
+            reason: e.to_string(),
+        })?;
+        self.store.put(CONSENSUS_WAL_KEY, &value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use norn_storage::memory::MemoryStore;
+
+    #[test]
+    fn test_wal_roundtrip() {
+        let store: Arc<dyn KvStore> = Arc::new(MemoryStore::new());
+        let wal = ConsensusWal::new(store.clone());
+        assert_eq!(wal.load().unwrap(), None);
+
+        let record = ConsensusRecord {
+            view: 7,
+            last_vote: Some((7, [3u8; 32])),
+            prepare_qc: None,
+            locked_qc: None,
+        };
+        wal.save(&record).unwrap();
+        assert_eq!(ConsensusWal::new(store).load().unwrap(), Some(record));
+    }
+}
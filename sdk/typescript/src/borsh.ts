@@ -9,6 +9,8 @@
  * - Option<T>: u8 (0=None, 1=Some) + T if Some
  */
 
+import type { TokenMetadata } from "./types.js";
+
 /** Writer that accumulates bytes for borsh serialization. */
 export class BorshWriter {
   private buffer: number[] = [];
@@ -17,6 +19,11 @@ export class BorshWriter {
     this.buffer.push(v & 0xff);
   }
 
+  writeU16(v: number): void {
+    this.buffer.push(v & 0xff);
+    this.buffer.push((v >> 8) & 0xff);
+  }
+
   writeU32(v: number): void {
     this.buffer.push(v & 0xff);
     this.buffer.push((v >> 8) & 0xff);
@@ -218,6 +225,42 @@ export function tokenMintSigningData(params: {
   return w.toBytes();
 }
 
+/**
+ * Borsh encoding of TokenMetadata:
+ *   description: Option<String>, icon_uri: Option<String>,
+ *   project_url: Option<String>, tags: Vec<String>
+ */
+export function writeTokenMetadata(w: BorshWriter, metadata: TokenMetadata): void {
+  for (const field of [metadata.description, metadata.iconUri, metadata.projectUrl]) {
+    if (field === undefined) {
+      w.writeU8(0);
+    } else {
+      w.writeU8(1);
+      w.writeString(field);
+    }
+  }
+  const tags = metadata.tags ?? [];
+  w.writeU32(tags.length);
+  for (const tag of tags) {
+    w.writeString(tag);
+  }
+}
+
+/** Signing data for a token metadata update. */
+export function tokenMetadataUpdateSigningData(params: {
+  tokenId: Uint8Array;
+  metadata: TokenMetadata;
+  authority: Uint8Array;
+  timestamp: bigint;
+}): Uint8Array {
+  const w = new BorshWriter();
+  w.writeFixedBytes(params.tokenId); // 32 bytes
+  writeTokenMetadata(w, params.metadata);
+  w.writeFixedBytes(params.authority); // 20 bytes
+  w.writeU64(params.timestamp);
+  return w.toBytes();
+}
+
 /** Signing data for a token burn. */
 export function tokenBurnSigningData(params: {
   tokenId: Uint8Array;
@@ -6,9 +6,12 @@ import {
   tokenDefinitionSigningData,
   tokenMintSigningData,
   tokenBurnSigningData,
+  tokenMetadataUpdateSigningData,
+  writeTokenMetadata,
   loomDeploySigningData,
 } from "./borsh.js";
 import { blake3Hash, toHex, fromHex } from "./crypto.js";
+import type { TokenMetadata } from "./types.js";
 import type { Wallet } from "./wallet.js";
 
 /** NORN has 12 decimal places. */
@@ -68,6 +71,11 @@ function now(): bigint {
  * Returns hex-encoded borsh bytes of a full Knot struct ready to submit via
  * `submitKnot`. The Rust handler expects: id, knot_type, timestamp, expiry,
  * before_states, after_states, payload (KnotPayload::Transfer), signatures.
+ *
+ * `beforeState.version` should be the thread's `nonce` (see `getThread`);
+ * nodes reject a non-zero version below it as replayed or out of order.
+ * Without `beforeState` the knot is unsequenced and protected only by its
+ * one-hour expiry window and knot ID deduplication.
  */
 export function buildTransfer(
   wallet: Wallet,
@@ -387,6 +395,46 @@ export function buildTokenBurn(
   return toHex(w.toBytes());
 }
 
+/**
+ * Build and sign a token metadata update (creator only).
+ *
+ * The metadata replaces the token's current metadata as a whole. Returns
+ * hex-encoded borsh bytes ready to submit via `updateTokenMetadata`.
+ *
+ * Borsh layout matches Rust TokenMetadataUpdate struct:
+ *   token_id: [u8;32], metadata: TokenMetadata, authority: [u8;20],
+ *   authority_pubkey: [u8;32], timestamp: u64, signature: [u8;64]
+ */
+export function buildTokenMetadataUpdate(
+  wallet: Wallet,
+  params: {
+    tokenId: string;
+    metadata: TokenMetadata;
+  },
+): string {
+  const tokenId = fromHex(params.tokenId);
+  const authority = wallet.address;
+  const timestamp = now();
+
+  const sigData = tokenMetadataUpdateSigningData({
+    tokenId,
+    metadata: params.metadata,
+    authority,
+    timestamp,
+  });
+  const signature = wallet.sign(sigData);
+
+  const w = new BorshWriter();
+  w.writeFixedBytes(tokenId); // 32 bytes
+  writeTokenMetadata(w, params.metadata);
+  w.writeFixedBytes(authority); // 20 bytes
+  w.writeFixedBytes(wallet.publicKey); // 32 bytes
+  w.writeU64(timestamp);
+  w.writeFixedBytes(signature); // 64 bytes
+
+  return toHex(w.toBytes());
+}
+
 /** Loom deploy fee: 50 NORN. */
 const LOOM_DEPLOY_FEE = BigInt(50) * BigInt(10) ** BigInt(NORN_DECIMALS);
 
@@ -397,7 +445,8 @@ const LOOM_DEPLOY_FEE = BigInt(50) * BigInt(10) ** BigInt(NORN_DECIMALS);
  *
  * Borsh layout matches Rust LoomRegistration struct:
  *   config: LoomConfig { loom_id: [u8;32], name: String, max_participants: u64,
- *     min_participants: u64, accepted_tokens: Vec<[u8;32]>, config_data: Vec<u8> }
+ *     min_participants: u64, accepted_tokens: Vec<[u8;32]>, config_data: Vec<u8>,
+ *     co_operators: Vec<[u8;32]>, operator_threshold: u16 }
  *   operator: [u8;32], timestamp: u64, signature: [u8;64]
  */
 export function buildLoomRegistration(
@@ -430,6 +479,9 @@ export function buildLoomRegistration(
   w.writeFixedBytes(new Uint8Array(32)); // NATIVE_TOKEN_ID (32 zero bytes)
   // config_data: Vec<u8> — empty
   w.writeU32(0);
+  // co_operators: Vec<[u8;32]> — empty (single-operator loom)
+  w.writeU32(0);
+  w.writeU16(1); // operator_threshold: u16
 
   // LoomRegistration fields:
   w.writeFixedBytes(wallet.publicKey); // operator: [u8;32]
@@ -1,6 +1,8 @@
 import type {
   BlockInfo,
   BlockTransactionsInfo,
+  BlockHeaderInfo,
+  TransferProofInfo,
   WeaveStateInfo,
   ThreadInfo,
   ThreadStateInfo,
@@ -14,17 +16,31 @@ import type {
   TokenInfo,
   LoomInfo,
   ExecutionResult,
+  EventFilter,
+  IndexedEventInfo,
   QueryResult,
   SubmitResult,
+  KnotProposalInfo,
+  UploadBytecodeResult,
   StakingInfo,
+  DelegationInfo,
+  SlashRecordInfo,
+  SpindleInfo,
+  SpindleSubscriptionInfo,
+  SpindleCoverageInfo,
+  MempoolInfo,
+  EstimatedFeeInfo,
+  FeeHistoryInfo,
   StateProofInfo,
+  StateMultiProofInfo,
   NodeInfo,
+  PeerInfo,
   AddressHex,
   HashHex,
   ChatEvent,
   ChatHistoryFilter,
 } from "./types.js";
-import { verifyBalanceProof } from "./merkle.js";
+import { verifyBalanceMultiProof, verifyBalanceProof } from "./merkle.js";
 
 /** Options for creating a NornClient. */
 export interface NornClientOptions {
@@ -119,6 +135,15 @@ export class NornClient {
     return this.call("norn_getBalance", [address, tokenId]);
   }
 
+  /** Get the balance of an address for a token as of a past block height. */
+  async getBalanceAt(
+    address: AddressHex,
+    tokenId: HashHex,
+    height: number,
+  ): Promise<string> {
+    return this.call("norn_getBalanceAt", [address, tokenId, height]);
+  }
+
   /** Get block by height. */
   async getBlock(height: number): Promise<BlockInfo> {
     return this.call("norn_getBlock", [height]);
@@ -131,6 +156,19 @@ export class NornClient {
     return this.call("norn_getBlockTransactions", [height]);
   }
 
+  /** Get up to `count` consecutive block headers from a height, for light clients. */
+  async getBlockHeaders(
+    fromHeight: number,
+    count: number,
+  ): Promise<BlockHeaderInfo[]> {
+    return this.call("norn_getBlockHeaders", [fromHeight, count]);
+  }
+
+  /** Get an SPV proof that a transfer is included in the weave. */
+  async getTransferProof(knotId: string): Promise<TransferProofInfo | null> {
+    return this.call("norn_getTransferProof", [knotId]);
+  }
+
   /** Get the latest block. */
   async getLatestBlock(): Promise<BlockInfo> {
     return this.call("norn_getLatestBlock");
@@ -151,6 +189,14 @@ export class NornClient {
     return this.call("norn_getThreadState", [threadId]);
   }
 
+  /** Get a thread's state with balances as of a past block height. */
+  async getThreadStateAt(
+    threadId: HashHex,
+    height: number,
+  ): Promise<ThreadStateInfo | null> {
+    return this.call("norn_getThreadStateAt", [threadId, height]);
+  }
+
   /** Health check. */
   async health(): Promise<HealthInfo> {
     return this.call("norn_health");
@@ -166,6 +212,24 @@ export class NornClient {
     return this.call("norn_getFeeEstimate");
   }
 
+  /** Estimate the commitment fee that is enough with `confidence` percent certainty. */
+  async estimateFee(confidence?: number): Promise<EstimatedFeeInfo> {
+    return this.call(
+      "norn_estimateFee",
+      confidence !== undefined ? [confidence] : [],
+    );
+  }
+
+  /** Get the fees charged in the last `nBlocks` blocks. */
+  async getFeeHistory(nBlocks: number): Promise<FeeHistoryInfo> {
+    return this.call("norn_getFeeHistory", [nBlocks]);
+  }
+
+  /** Inspect the mempool, listing up to `limit` pending commitments. */
+  async getMempool(limit?: number): Promise<MempoolInfo> {
+    return this.call("norn_getMempool", limit !== undefined ? [limit] : []);
+  }
+
   /** Get a commitment proof for a thread. */
   async getCommitmentProof(threadId: HashHex): Promise<CommitmentProofInfo> {
     return this.call("norn_getCommitmentProof", [threadId]);
@@ -205,6 +269,11 @@ export class NornClient {
     return this.call("norn_getMetrics");
   }
 
+  /** Get connected peers with their reputation scores, lowest first. */
+  async getPeers(): Promise<PeerInfo[]> {
+    return this.call("norn_getPeers");
+  }
+
   /** Get node info. */
   async getNodeInfo(): Promise<NodeInfo> {
     return this.call("norn_getNodeInfo");
@@ -240,11 +309,64 @@ export class NornClient {
     return this.call("norn_queryLoom", [loomId, inputHex]);
   }
 
+  /** Query a loom contract (read-only) as of a past block height. */
+  async queryLoomAt(
+    loomId: HashHex,
+    inputHex: string,
+    height: number,
+  ): Promise<QueryResult> {
+    return this.call("norn_queryLoomAt", [loomId, inputHex, height]);
+  }
+
+  /** Query indexed loom events in emission order. */
+  async getEvents(
+    filter: EventFilter = {},
+    limit = 50,
+    offset = 0,
+  ): Promise<IndexedEventInfo[]> {
+    return this.call("norn_getEvents", [filter, limit, offset]);
+  }
+
   /** Get staking information. */
   async getStakingInfo(pubkeyHex?: string): Promise<StakingInfo> {
     return this.call("norn_getStakingInfo", [pubkeyHex ?? null]);
   }
 
+  /** Get the delegations made by a delegator public key. */
+  async getDelegations(delegatorHex: string): Promise<DelegationInfo[]> {
+    return this.call("norn_getDelegations", [delegatorHex]);
+  }
+
+  /** Get the slashings applied so far, optionally for one validator. */
+  async getSlashingHistory(pubkeyHex?: string): Promise<SlashRecordInfo[]> {
+    return this.call("norn_getSlashingHistory", [pubkeyHex ?? null]);
+  }
+
+  /** List bonded spindles, cheapest fee first. */
+  async getSpindles(): Promise<SpindleInfo[]> {
+    return this.call("norn_getSpindles");
+  }
+
+  /** Get the spindles paid to watch a thread. */
+  async getSpindleSubscriptions(
+    address: AddressHex,
+  ): Promise<SpindleSubscriptionInfo[]> {
+    return this.call("norn_getSpindleSubscriptions", [address]);
+  }
+
+  /**
+   * Get the spindles watching a thread, and whether at least `redundancy`
+   * of them do (the node's default when omitted).
+   */
+  async getSpindleCoverage(
+    address: AddressHex,
+    redundancy?: number,
+  ): Promise<SpindleCoverageInfo> {
+    const params: unknown[] = [address];
+    if (redundancy !== undefined) params.push(redundancy);
+    return this.call("norn_getSpindleCoverage", params);
+  }
+
   /** Get the current state root. */
   async getStateRoot(): Promise<{ state_root: HashHex }> {
     return this.call("norn_getStateRoot");
@@ -260,6 +382,19 @@ export class NornClient {
     return this.call("norn_getStateProof", params);
   }
 
+  /**
+   * Get one compact proof for several balances. Pass one token ID to use it
+   * for every address, or one per address. Defaults to native NORN.
+   */
+  async getStateProofs(
+    addresses: AddressHex[],
+    tokenIds?: HashHex[],
+  ): Promise<StateMultiProofInfo> {
+    const params: unknown[] = [addresses];
+    if (tokenIds) params.push(tokenIds);
+    return this.call("norn_getStateProof", params);
+  }
+
   /** Get recent transfers. */
   async getRecentTransfers(
     limit = 20,
@@ -282,6 +417,19 @@ export class NornClient {
     return this.call("norn_submitKnot", [knotHex]);
   }
 
+  /**
+   * Propose or sign a multi-party knot. Signatures merge with the copy the
+   * node holds; once every participant has signed, the knot is submitted.
+   */
+  async proposeKnot(knotHex: string): Promise<KnotProposalInfo> {
+    return this.call("norn_proposeKnot", [knotHex]);
+  }
+
+  /** Get a multi-party knot still waiting for signatures. */
+  async getKnotProposal(knotId: HashHex): Promise<KnotProposalInfo | null> {
+    return this.call("norn_getKnotProposal", [knotId]);
+  }
+
   /** Register a name. */
   async registerName(
     name: string,
@@ -327,6 +475,11 @@ export class NornClient {
     return this.call("norn_mintToken", [mintHex]);
   }
 
+  /** Update a token's metadata (see `buildTokenMetadataUpdate`). */
+  async updateTokenMetadata(updateHex: string): Promise<SubmitResult> {
+    return this.call("norn_updateTokenMetadata", [updateHex]);
+  }
+
   /** Burn tokens. */
   async burnToken(burnHex: string): Promise<SubmitResult> {
     return this.call("norn_burnToken", [burnHex]);
@@ -344,7 +497,7 @@ export class NornClient {
     initMsgHex: string | null,
     operatorSignatureHex: string,
     operatorPubkeyHex: string,
-  ): Promise<SubmitResult> {
+  ): Promise<UploadBytecodeResult> {
     return this.call("norn_uploadLoomBytecode", [
       loomId,
       bytecodeHex,
@@ -421,6 +574,31 @@ export class NornClient {
     return this.call("norn_unstake", [unstakeHex]);
   }
 
+  /** Delegate NORN to a validator. */
+  async delegate(delegateHex: string): Promise<SubmitResult> {
+    return this.call("norn_delegate", [delegateHex]);
+  }
+
+  /** Withdraw delegated NORN. */
+  async undelegate(undelegateHex: string): Promise<SubmitResult> {
+    return this.call("norn_undelegate", [undelegateHex]);
+  }
+
+  /** Release a validator from jail once its jail period is over. */
+  async unjail(unjailHex: string): Promise<SubmitResult> {
+    return this.call("norn_unjail", [unjailHex]);
+  }
+
+  /** Submit evidence that a validator double-signed. */
+  async submitSlashingEvidence(evidenceHex: string): Promise<SubmitResult> {
+    return this.call("norn_submitSlashingEvidence", [evidenceHex]);
+  }
+
+  /** Submit a spindle bond, unbond, subscribe, or unsubscribe operation. */
+  async submitSpindleOperation(operationHex: string): Promise<SubmitResult> {
+    return this.call("norn_submitSpindleOperation", [operationHex]);
+  }
+
   /** Request testnet faucet tokens. */
   async faucet(address: AddressHex): Promise<SubmitResult> {
     return this.call("norn_faucet", [address]);
@@ -465,4 +643,19 @@ export class NornClient {
       proof.proof,
     );
   }
+
+  /**
+   * Fetch and verify several balances with one multiproof from the node.
+   *
+   * @returns The verified balances in the order requested, or `null` if the
+   *   proof does not verify.
+   */
+  async verifyBalances(
+    addresses: AddressHex[],
+    tokenIds?: HashHex[],
+  ): Promise<bigint[] | null> {
+    const proof = await this.getStateProofs(addresses, tokenIds);
+    if (!verifyBalanceMultiProof(proof)) return null;
+    return proof.balances.map((b) => BigInt(b.balance));
+  }
 }
@@ -23,6 +23,8 @@ export {
   tokenDefinitionSigningData,
   tokenMintSigningData,
   tokenBurnSigningData,
+  tokenMetadataUpdateSigningData,
+  writeTokenMetadata,
   loomDeploySigningData,
 } from "./borsh.js";
 
@@ -32,12 +34,15 @@ export type { NornClientOptions } from "./client.js";
 export {
   verifyStateProof,
   verifyBalanceProof,
+  verifyStateMultiProof,
+  verifyBalanceMultiProof,
   getBit,
   hashLeaf,
   hashInternal,
   smtKey,
   encodeU128LE,
 } from "./merkle.js";
+export type { MultiProofLeaf } from "./merkle.js";
 
 export {
   buildTransfer,
@@ -47,6 +52,7 @@ export {
   buildTokenDefinition,
   buildTokenMint,
   buildTokenBurn,
+  buildTokenMetadataUpdate,
   buildLoomRegistration,
   parseAmount,
   formatAmount,
@@ -58,6 +64,7 @@ export {
   subscribeTransfers,
   subscribeTokenEvents,
   subscribeLoomEvents,
+  subscribeEvents,
   subscribePendingTransactions,
   subscribeChatEvents,
 } from "./subscriptions.js";
@@ -88,6 +95,8 @@ export type {
   Amount,
   BlockInfo,
   BlockTransactionsInfo,
+  BlockHeaderInfo,
+  TransferProofInfo,
   BlockTransferInfo,
   BlockTokenDefinitionInfo,
   BlockTokenMintInfo,
@@ -109,16 +118,35 @@ export type {
   NameResolution,
   NameInfo,
   TokenInfo,
+  TokenMetadata,
   LoomInfo,
   AttributeInfo,
   EventInfo,
+  EventFilter,
+  IndexedEventInfo,
   ExecutionResult,
   QueryResult,
   SubmitResult,
+  KnotProposalInfo,
+  ModuleDiagnostic,
+  UploadBytecodeResult,
   StakingInfo,
   ValidatorStakeInfo,
+  DelegationInfo,
+  SlashRecordInfo,
+  SpindleInfo,
+  SpindleSubscriptionInfo,
+  SpindleCoverageInfo,
+  MempoolInfo,
+  MempoolCommitmentInfo,
+  EstimatedFeeInfo,
+  FeeHistoryInfo,
+  BlockFeeInfo,
   StateProofInfo,
+  StateProofBalance,
+  StateMultiProofInfo,
   NodeInfo,
+  PeerInfo,
   TransferEvent,
   TokenEvent,
   LoomExecutionEvent,
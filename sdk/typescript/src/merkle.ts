@@ -1,4 +1,5 @@
 import { blake3Hash, fromHex } from "./crypto.js";
+import type { StateMultiProofInfo } from "./types.js";
 
 /** Tree depth: 256 bits = 32-byte key space. */
 const TREE_DEPTH = 256;
@@ -131,3 +132,157 @@ export function verifyBalanceProof(
   const siblings = proof.map((h) => fromHex(h));
   return verifyStateProof(root, key, value, siblings);
 }
+
+/** Order two byte arrays lexicographically. */
+function compareBytes(a: Uint8Array, b: Uint8Array): number {
+  const len = Math.min(a.length, b.length);
+  for (let i = 0; i < len; i++) {
+    if (a[i] !== b[i]) return a[i] - b[i];
+  }
+  return a.length - b.length;
+}
+
+/** Number of leading bits two distinct keys share. */
+function commonPrefixLen(a: Uint8Array, b: Uint8Array): number {
+  for (let i = 0; i < 32; i++) {
+    const diff = a[i] ^ b[i];
+    if (diff !== 0) return i * 8 + Math.clz32(diff) - 24;
+  }
+  return TREE_DEPTH;
+}
+
+/** Hash two children into their parent, keeping empty subtrees empty. */
+function hashChildren(left: Uint8Array, right: Uint8Array): Uint8Array {
+  if (bytesEqual(left, EMPTY_HASH) && bytesEqual(right, EMPTY_HASH)) {
+    return EMPTY_HASH;
+  }
+  return hashInternal(left, right);
+}
+
+/** A key and its value in a multiproof (empty value for non-inclusion). */
+export interface MultiProofLeaf {
+  key: Uint8Array;
+  value: Uint8Array;
+}
+
+/**
+ * Verify a sparse Merkle multiproof covering several keys at once.
+ *
+ * Leaves are hashed up their paths in ascending key order. A leaf's subtree
+ * is held back where it meets the next key's path and absorbed when that
+ * key's path reaches it; every other sibling is read from the proof.
+ *
+ * @param root - The expected state root (32 bytes).
+ * @param leaves - The proved keys and values, in any order.
+ * @param siblingBitmap - One bit per sibling consumed (MSB-first): set if the
+ *   sibling is the next entry of `siblings`, clear if it is empty.
+ * @param siblings - The non-empty sibling hashes, in the order consumed.
+ * @returns `true` if the proof is valid, `false` otherwise.
+ */
+export function verifyStateMultiProof(
+  root: Uint8Array,
+  leaves: MultiProofLeaf[],
+  siblingBitmap: Uint8Array,
+  siblings: Uint8Array[],
+): boolean {
+  const sorted = [...leaves].sort((a, b) => compareBytes(a.key, b.key));
+  const unique: MultiProofLeaf[] = [];
+  for (const leaf of sorted) {
+    const prev = unique[unique.length - 1];
+    if (prev && compareBytes(prev.key, leaf.key) === 0) {
+      if (!bytesEqual(prev.value, leaf.value)) return false;
+      continue;
+    }
+    unique.push(leaf);
+  }
+  if (unique.length === 0) return false;
+
+  let bitsRead = 0;
+  let nextIndex = 0;
+  const nextSibling = (): Uint8Array | null => {
+    const byteIndex = Math.floor(bitsRead / 8);
+    if (byteIndex >= siblingBitmap.length) return null;
+    const present = (siblingBitmap[byteIndex] >> (7 - (bitsRead % 8))) & 1;
+    bitsRead++;
+    if (!present) return EMPTY_HASH;
+    return nextIndex < siblings.length ? siblings[nextIndex++] : null;
+  };
+
+  // Subtrees waiting for their right-hand neighbour, deepest join on top.
+  const deferred: { depth: number; hash: Uint8Array }[] = [];
+  let computed: Uint8Array | null = null;
+
+  for (let i = 0; i < unique.length; i++) {
+    const { key, value } = unique[i];
+    const join =
+      i + 1 < unique.length ? commonPrefixLen(key, unique[i + 1].key) : null;
+    let hash =
+      value.length === 0 ? EMPTY_HASH : hashLeaf(key, blake3Hash(value));
+    let depth = TREE_DEPTH;
+
+    for (;;) {
+      const left =
+        deferred.length > 0 ? deferred[deferred.length - 1].depth : null;
+      const joinsRight = join !== null && (left === null || join > left);
+      const target = joinsRight ? join : left;
+      const stop = target === null ? 0 : target + 1;
+      while (depth > stop) {
+        depth--;
+        const sibling = nextSibling();
+        if (sibling === null) return false;
+        hash =
+          getBit(key, depth) === 0
+            ? hashChildren(hash, sibling)
+            : hashChildren(sibling, hash);
+      }
+      if (joinsRight) {
+        deferred.push({ depth: depth - 1, hash });
+        break;
+      }
+      const pending = deferred.pop();
+      if (!pending) {
+        computed = hash;
+        break;
+      }
+      hash = hashChildren(pending.hash, hash);
+      depth = pending.depth;
+    }
+  }
+
+  return (
+    computed !== null &&
+    bytesEqual(computed, root) &&
+    Math.ceil(bitsRead / 8) === siblingBitmap.length &&
+    nextIndex === siblings.length
+  );
+}
+
+/**
+ * Verify a multi-balance proof from the RPC `norn_getStateProof` response.
+ *
+ * Checks that each reported balance matches its proved value, then verifies
+ * the multiproof against the state root.
+ *
+ * @param proof - The multiproof response, as returned by the RPC.
+ * @returns `true` if every balance verifies correctly.
+ */
+export function verifyBalanceMultiProof(proof: StateMultiProofInfo): boolean {
+  const leaves: MultiProofLeaf[] = [];
+  for (const entry of proof.balances) {
+    const value = fromHex(entry.value);
+    const balance = BigInt(entry.balance);
+    const expected =
+      value.length === 0 && balance === 0n ? value : encodeU128LE(balance);
+    if (!bytesEqual(value, expected)) return false;
+    leaves.push({
+      key: smtKey(fromHex(entry.address), fromHex(entry.token_id)),
+      value,
+    });
+  }
+  return verifyStateMultiProof(
+    fromHex(proof.state_root),
+    leaves,
+    fromHex(proof.sibling_bitmap),
+    proof.siblings.map((h) => fromHex(h)),
+  );
+}
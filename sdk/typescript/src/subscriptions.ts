@@ -3,6 +3,8 @@ import type {
   TransferEvent,
   TokenEvent,
   LoomExecutionEvent,
+  EventFilter,
+  IndexedEventInfo,
   PendingTransactionEvent,
   ChatEvent,
 } from "./types.js";
@@ -129,6 +131,18 @@ export function subscribeLoomEvents(
   return subscribe(options, "norn_subscribeLoomEvents", params, onEvent);
 }
 
+/**
+ * Subscribe to indexed loom events matching a filter on loom ID, event type,
+ * and attribute value.
+ */
+export function subscribeEvents(
+  options: SubscribeOptions,
+  onEvent: (event: IndexedEventInfo) => void,
+  filter: EventFilter = {},
+): Subscription {
+  return subscribe(options, "norn_subscribeEvents", [filter], onEvent);
+}
+
 /** Subscribe to pending transaction events. */
 export function subscribePendingTransactions(
   options: SubscribeOptions,
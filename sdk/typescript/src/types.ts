@@ -50,6 +50,8 @@ export interface ThreadInfo {
   thread_id: HashHex;
   owner: PubKeyHex;
   version: number;
+  /** Next knot sequence number: the lowest `beforeState.version` the thread's next knot may use. */
+  nonce: number;
   state_hash: HashHex;
 }
 
@@ -58,6 +60,7 @@ export interface ThreadStateInfo {
   thread_id: HashHex;
   owner: PubKeyHex;
   version: number;
+  nonce: number;
   state_hash: HashHex;
   balances: BalanceEntry[];
 }
@@ -65,6 +68,8 @@ export interface ThreadStateInfo {
 /** A balance entry for a token. */
 export interface BalanceEntry {
   token_id: HashHex;
+  /** Token symbol; absent for unknown tokens. */
+  symbol?: string;
   amount: string;
   human_readable: string;
 }
@@ -106,6 +111,61 @@ export interface FeeEstimateInfo {
   transfer_fee: string;
 }
 
+/** A fee estimate at a given confidence. */
+export interface EstimatedFeeInfo {
+  /** Estimated fee per commitment in nits. */
+  fee: string;
+  /** Confidence (percent) the fee is enough. */
+  confidence: number;
+  /** Current fee per commitment in nits. */
+  current_fee: string;
+  blocks_sampled: number;
+}
+
+/** Fees charged in recent blocks. */
+export interface FeeHistoryInfo {
+  /** Per-block fees, oldest first. */
+  blocks: BlockFeeInfo[];
+  p10: string | null;
+  p50: string | null;
+  p90: string | null;
+}
+
+/** The fee charged in one block. */
+export interface BlockFeeInfo {
+  height: number;
+  fee_per_commitment: string;
+  fee_multiplier: number;
+  /** Total priority fees paid to the proposer in nits. */
+  priority_fees: string;
+  commitment_count: number;
+}
+
+/** Summary of a node's mempool. */
+export interface MempoolInfo {
+  total: number;
+  capacity: number;
+  commitment_count: number;
+  transfer_count: number;
+  registration_count: number;
+  /** Pending fraud proofs and slashing evidence. */
+  evidence_count: number;
+  other_count: number;
+  /** Lowest priority fee among pending commitments in nits, if any. */
+  min_commitment_fee: string | null;
+  /** Pending commitments, highest priority first. */
+  commitments: MempoolCommitmentInfo[];
+}
+
+/** A pending commitment in the mempool. */
+export interface MempoolCommitmentInfo {
+  thread_id: AddressHex;
+  version: number;
+  /** Priority fee (tip) offered in nits. */
+  fee: string;
+  timestamp: number;
+}
+
 /** Commitment proof. */
 export interface CommitmentProofInfo {
   thread_id: HashHex;
@@ -155,6 +215,22 @@ export interface TokenInfo {
   current_supply: string;
   creator: AddressHex;
   created_at: number;
+  /** Short description, set by the creator. */
+  description?: string;
+  /** Icon URI (https, ipfs or ar), set by the creator. */
+  icon_uri?: string;
+  /** Project URL, set by the creator. */
+  project_url?: string;
+  /** Tags, set by the creator. Omitted when empty. */
+  tags?: string[];
+}
+
+/** Token metadata, as set by `buildTokenMetadataUpdate`. */
+export interface TokenMetadata {
+  description?: string;
+  iconUri?: string;
+  projectUrl?: string;
+  tags?: string[];
 }
 
 /** Loom (smart contract) information. */
@@ -181,6 +257,32 @@ export interface EventInfo {
   attributes: AttributeInfo[];
 }
 
+/** Filter for indexed loom events. Unset fields match all events. */
+export interface EventFilter {
+  loom_id?: HashHex;
+  /** Event type (e.g. "Transfer"). */
+  type?: string;
+  /** Attribute key the event must carry. */
+  attribute_key?: string;
+  /** Required value for `attribute_key`. */
+  attribute_value?: string;
+  /** Inclusive lower bound on block height. */
+  from_height?: number;
+  /** Inclusive upper bound on block height. */
+  to_height?: number;
+}
+
+/** Loom event from the node's event index. */
+export interface IndexedEventInfo {
+  seq: number;
+  loom_id: HashHex;
+  sender: AddressHex;
+  block_height: number;
+  timestamp: number;
+  type: string;
+  attributes: AttributeInfo[];
+}
+
 /** Loom execution result. */
 export interface ExecutionResult {
   success: boolean;
@@ -207,20 +309,120 @@ export interface SubmitResult {
   reason?: string;
 }
 
+/** A multi-party knot collecting its participants' signatures. */
+export interface KnotProposalInfo {
+  knot_id: HashHex;
+  /** Hex-encoded borsh knot; unsigned slots are all zeros. */
+  knot: string;
+  participants: AddressHex[];
+  missing: AddressHex[];
+  deadline: number;
+  submitted: boolean;
+  reason?: string;
+}
+
+/** A problem found while validating uploaded loom bytecode. */
+export interface ModuleDiagnostic {
+  kind:
+    | "malformed"
+    | "non_deterministic"
+    | "disallowed_import"
+    | "excessive_memory"
+    | "missing_export";
+  message: string;
+}
+
+/** Result of uploading loom bytecode. */
+export interface UploadBytecodeResult extends SubmitResult {
+  diagnostics?: ModuleDiagnostic[];
+}
+
 /** Staking information. */
 export interface StakingInfo {
   validators: ValidatorStakeInfo[];
   total_staked: string;
   min_stake: string;
   bonding_period: number;
+  epoch: number;
+  max_validators: number;
 }
 
 /** Per-validator staking details. */
 export interface ValidatorStakeInfo {
   pubkey: PubKeyHex;
   address: AddressHex;
+  /** Self-stake. */
   stake: string;
+  /** Stake delegated to the validator. */
+  delegated: string;
+  delegator_count: number;
+  /** Commission on delegators' rewards, in basis points. */
+  commission_bps: number;
+  /** Stake bonded at the next epoch boundary. */
+  pending_stake: string;
+  /** Stake waiting to be released after the bonding period. */
+  pending_unstake: string;
   active: boolean;
+  jailed: boolean;
+  /** Height from which a validator jailed for downtime may unjail. */
+  jailed_until: number | null;
+}
+
+/** A slashing applied to a validator. */
+export interface SlashRecordInfo {
+  validator: PubKeyHex;
+  reason: "double_sign" | "downtime";
+  /** Stake burned, including delegations. */
+  amount: string;
+  height: number;
+  /** Height from which the validator may unjail; null if it never can. */
+  jailed_until: number | null;
+}
+
+/** A delegation from a delegator to a validator. */
+export interface DelegationInfo {
+  validator: PubKeyHex;
+  delegator: PubKeyHex;
+  amount: string;
+  /** Amount bonded at the next epoch boundary. */
+  pending: string;
+  /** Amount waiting to be released after the bonding period. */
+  pending_undelegate: string;
+  commission_bps: number;
+}
+
+/** A bonded spindle on the subscription market. */
+export interface SpindleInfo {
+  pubkey: PubKeyHex;
+  address: AddressHex;
+  bond: string;
+  /** Fee charged per fee period. */
+  fee_per_period: string;
+  subscriber_count: number;
+  /** Total bond slashed for missed fraud. */
+  slashed: string;
+}
+
+/** A thread's paid subscription to a spindle. */
+export interface SpindleSubscriptionInfo {
+  thread_id: AddressHex;
+  spindle: PubKeyHex;
+  fee_per_period: string;
+  start_height: number;
+  /** Height at which the last prepaid period ends. */
+  end_height: number;
+  periods: number;
+  periods_paid: number;
+}
+
+/** Spindles advertising coverage of a thread. */
+export interface SpindleCoverageInfo {
+  thread_id: AddressHex;
+  /** Public keys of the spindles watching the thread. */
+  watchers: PubKeyHex[];
+  redundancy: number;
+  /** Whether at least `redundancy` spindles watch the thread. */
+  covered: boolean;
 }
 
 /** State proof for a balance. */
@@ -232,6 +434,25 @@ export interface StateProofInfo {
   proof: HashHex[];
 }
 
+/** A balance covered by a state multiproof. */
+export interface StateProofBalance {
+  address: AddressHex;
+  token_id: HashHex;
+  balance: string;
+  /** Proved leaf value: the balance as u128 LE, or empty if never set. */
+  value: string;
+}
+
+/** One compact state proof covering several balances. */
+export interface StateMultiProofInfo {
+  state_root: HashHex;
+  /** Proved balances, in the order requested. */
+  balances: StateProofBalance[];
+  /** One bit per sibling consumed (MSB-first): set if listed in `siblings`. */
+  sibling_bitmap: string;
+  siblings: HashHex[];
+}
+
 /** Node info response. */
 export interface NodeInfo {
   version: string;
@@ -242,6 +463,43 @@ export interface NodeInfo {
   peer_count: number;
 }
 
+/** A connected peer, its reputation and its traffic. */
+export interface PeerInfo {
+  peer_id: string;
+  /** 0 is neutral; the peer is banned at -100. */
+  score: number;
+  protocol_version: number | null;
+  connected_secs: number;
+  /** Whether the peer is greylisted for repeated bans. */
+  greylisted: boolean;
+  bytes_in: number;
+  /** Bytes of direct messages sent to the peer. */
+  bytes_out: number;
+  bytes_in_per_sec: number;
+  bytes_out_per_sec: number;
+  /** Messages from the peer dropped for exceeding its rate limits. */
+  throttled: number;
+}
+
+/** A block header returned by norn_getBlockHeaders. */
+export interface BlockHeaderInfo {
+  height: number;
+  hash: HashHex;
+  /** Hex-encoded borsh `BlockHeader`. */
+  header_hex: string;
+}
+
+/** An SPV transfer proof returned by norn_getTransferProof. */
+export interface TransferProofInfo {
+  knot_id: HashHex;
+  block_height: number;
+  block_hash: HashHex;
+  /** Headers in the proof, from the including block towards the tip. */
+  confirmations: number;
+  /** Hex-encoded borsh `TransferProof`. */
+  proof_hex: string;
+}
+
 /** Detailed block transactions returned by norn_getBlockTransactions. */
 export interface BlockTransactionsInfo {
   height: number;
@@ -8,6 +8,7 @@ import {
   buildTokenDefinition,
   buildTokenMint,
   buildTokenBurn,
+  buildTokenMetadataUpdate,
   parseAmount,
   formatAmount,
 } from "../src/builders.js";
@@ -285,6 +286,36 @@ describe("buildTokenDefinition", () => {
   });
 });
 
+describe("buildTokenMetadataUpdate", () => {
+  it("contains correct borsh structure", () => {
+    const wallet = Wallet.fromPrivateKey(new Uint8Array(32).fill(1));
+    const hex = buildTokenMetadataUpdate(wallet, {
+      tokenId: "ab".repeat(32),
+      metadata: {
+        description: "A test token.",
+        projectUrl: "https://example.com",
+        tags: ["test", "defi"],
+      },
+    });
+    const r = new BorshReader(fromHex(hex));
+
+    expect(toHex(r.readFixedBytes(32))).toBe("ab".repeat(32)); // token_id
+    expect(r.readU8()).toBe(1); // description: Some
+    expect(r.readString()).toBe("A test token.");
+    expect(r.readU8()).toBe(0); // icon_uri: None
+    expect(r.readU8()).toBe(1); // project_url: Some
+    expect(r.readString()).toBe("https://example.com");
+    expect(r.readU32()).toBe(2); // tags
+    expect(r.readString()).toBe("test");
+    expect(r.readString()).toBe("defi");
+    expect(toHex(r.readFixedBytes(20))).toBe(toHex(wallet.address)); // authority
+    expect(toHex(r.readFixedBytes(32))).toBe(toHex(wallet.publicKey)); // authority_pubkey
+    expect(r.readU64()).toBeGreaterThan(0n); // timestamp
+    expect(r.readFixedBytes(64).length).toBe(64); // signature
+    expect(r.remaining()).toBe(0);
+  });
+});
+
 describe("buildTokenMint", () => {
   it("produces valid hex", () => {
     const wallet = Wallet.fromPrivateKey(new Uint8Array(32).fill(1));
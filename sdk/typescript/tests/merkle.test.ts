@@ -6,6 +6,7 @@ import {
   smtKey,
   encodeU128LE,
   verifyStateProof,
+  verifyStateMultiProof,
 } from "../src/merkle.js";
 import { blake3Hash, fromHex, toHex } from "../src/crypto.js";
 
@@ -227,3 +228,52 @@ describe("verifyStateProof", () => {
     expect(verifyStateProof(root, key, value, siblings)).toBe(true);
   });
 });
+
+describe("verifyStateMultiProof", () => {
+  // Generated by the Rust SparseMerkleTree: keys BLAKE3([i]) -> [i; 4] for
+  // i in 0..6, proving keys 1 and 4 and the missing key 9.
+  const root = fromHex(
+    "dee1e470f9d876719a2b7ffcf6dca9ed6c7a4b1ecae57919b2c74a1cdda8e397",
+  );
+  const bitmap = fromHex(
+    "00".repeat(31) + "04" + "00".repeat(63) + "80",
+  );
+  const siblings = [
+    fromHex("975a51b5e4591ec3a976d4b41450c8914784f703d4da5edbe48538a48dbeb77f"),
+    fromHex("aab093988a4d102b23eb5fba9ba151f28e22f7f1a673f760f684d15ed658d32e"),
+  ];
+  const leaf = (i: number, value: number[]) => ({
+    key: blake3Hash(new Uint8Array([i])),
+    value: new Uint8Array(value),
+  });
+  const leaves = [leaf(9, []), leaf(1, [1, 1, 1, 1]), leaf(4, [4, 4, 4, 4])];
+
+  it("verifies leaves in any order", () => {
+    expect(verifyStateMultiProof(root, leaves, bitmap, siblings)).toBe(true);
+    expect(
+      verifyStateMultiProof(root, [...leaves, leaves[0]], bitmap, siblings),
+    ).toBe(true);
+  });
+
+  it("rejects a wrong value or root", () => {
+    const tampered = [leaf(9, []), leaf(1, [1, 1, 1, 2]), leaf(4, [4, 4, 4, 4])];
+    expect(verifyStateMultiProof(root, tampered, bitmap, siblings)).toBe(false);
+    expect(
+      verifyStateMultiProof(new Uint8Array(32), leaves, bitmap, siblings),
+    ).toBe(false);
+  });
+
+  it("rejects missing or extra proof data", () => {
+    expect(
+      verifyStateMultiProof(root, leaves.slice(1), bitmap, siblings),
+    ).toBe(false);
+    expect(
+      verifyStateMultiProof(root, leaves, bitmap, siblings.slice(1)),
+    ).toBe(false);
+    expect(
+      verifyStateMultiProof(root, leaves, bitmap, [...siblings, root]),
+    ).toBe(false);
+    expect(verifyStateMultiProof(root, [], bitmap, siblings)).toBe(false);
+  });
+});
+
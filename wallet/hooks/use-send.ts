@@ -24,13 +24,14 @@ export function useSend() {
         let beforeState: { version: bigint; stateHash: string } | undefined;
         if (activeAddress) {
           try {
-            const ts = await rpcCall<{ version: number; state_hash: string }>(
+            const ts = await rpcCall<{ nonce?: number; state_hash: string }>(
               "norn_getThreadState",
               [activeAddress],
             );
             if (ts?.state_hash && ts.state_hash !== "0".repeat(64)) {
+              // The version continues the thread's knot sequence.
               beforeState = {
-                version: BigInt(ts.version),
+                version: BigInt(ts.nonce ?? 0),
                 stateHash: ts.state_hash,
               };
             }